@@ -26,9 +26,11 @@ use databend_query::api::HttpService;
 use databend_query::api::RpcService;
 use databend_query::clusters::ClusterDiscovery;
 use databend_query::metrics::MetricService;
+use databend_query::servers::ClickHouseHandler;
 use databend_query::servers::HttpHandler;
 use databend_query::servers::HttpHandlerKind;
 use databend_query::servers::MySQLHandler;
+use databend_query::servers::PostgresHandler;
 use databend_query::servers::Server;
 use databend_query::servers::ShutdownHandle;
 use databend_query::Config;
@@ -82,7 +84,7 @@ async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<
     {
         let hostname = conf.query.mysql_handler_host.clone();
         let listening = format!("{}:{}", hostname, conf.query.mysql_handler_port);
-        let mut handler = MySQLHandler::create()?;
+        let mut handler = MySQLHandler::create(conf.clone())?;
         let listening = handler.start(listening.parse()?).await?;
         shutdown_handle.add_service(handler);
 
@@ -94,6 +96,40 @@ async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<
         );
     }
 
+    // ClickHouse handler.
+    {
+        let hostname = conf.query.clickhouse_handler_host.clone();
+        let listening = format!("{}:{}", hostname, conf.query.clickhouse_handler_port);
+
+        let mut srv = ClickHouseHandler::create(conf.clone())?;
+        let listening = srv.start(listening.parse()?).await?;
+        shutdown_handle.add_service(srv);
+
+        info!(
+            "Listening for ClickHouse compatibility native protocol: {}, Usage: clickhouse client --host {} --port {}",
+            listening,
+            listening.ip(),
+            listening.port(),
+        );
+    }
+
+    // PostgreSQL handler.
+    {
+        let hostname = conf.query.postgres_handler_host.clone();
+        let listening = format!("{}:{}", hostname, conf.query.postgres_handler_port);
+
+        let mut srv = PostgresHandler::create(conf.clone())?;
+        let listening = srv.start(listening.parse()?).await?;
+        shutdown_handle.add_service(srv);
+
+        info!(
+            "Listening for PostgreSQL compatibility protocol: {}, Usage: psql -h {} -p {} -U root -d default",
+            listening,
+            listening.ip(),
+            listening.port(),
+        );
+    }
+
     // ClickHouse HTTP handler.
     {
         let hostname = conf.query.clickhouse_http_handler_host.clone();
@@ -194,6 +230,24 @@ async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<
         "    connect via: mysql -uroot -h{} -P{}",
         conf.query.mysql_handler_host, conf.query.mysql_handler_port
     );
+    println!("Clickhouse(native)");
+    println!(
+        "    listened at {}:{}",
+        conf.query.clickhouse_handler_host, conf.query.clickhouse_handler_port
+    );
+    println!(
+        "    connect via: clickhouse client --host {} --port {}",
+        conf.query.clickhouse_handler_host, conf.query.clickhouse_handler_port
+    );
+    println!("PostgreSQL");
+    println!(
+        "    listened at {}:{}",
+        conf.query.postgres_handler_host, conf.query.postgres_handler_port
+    );
+    println!(
+        "    connect via: psql -h {} -p {} -U root -d default",
+        conf.query.postgres_handler_host, conf.query.postgres_handler_port
+    );
     println!("Clickhouse(http)");
     println!(
         "    listened at {}:{}",
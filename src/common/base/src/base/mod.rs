@@ -40,6 +40,7 @@ pub use runtime::Runtime;
 pub use runtime::TrySpawn;
 pub use runtime_tracker::RuntimeTracker;
 pub use runtime_tracker::ThreadTracker;
+pub use runtime_tracker::ThreadTrackerGuard;
 pub use select::select3;
 pub use select::Select3Output;
 pub use shutdown_signal::signal_stream;
@@ -153,9 +153,17 @@ impl Runtime {
         Self::create(None, tracker, &mut runtime_builder)
     }
 
+    pub fn with_worker_threads(workers: usize, thread_name: Option<String>) -> Result<Self> {
+        Self::with_tracker_and_worker_threads(RuntimeTracker::create(), workers, thread_name)
+    }
+
+    /// Spawns a new tokio runtime whose threads are tracked by the tracker.
     #[allow(unused_mut)]
-    pub fn with_worker_threads(workers: usize, mut thread_name: Option<String>) -> Result<Self> {
-        let tracker = RuntimeTracker::create();
+    pub fn with_tracker_and_worker_threads(
+        tracker: Arc<RuntimeTracker>,
+        workers: usize,
+        mut thread_name: Option<String>,
+    ) -> Result<Self> {
         let mut runtime_builder = Self::tracker_builder(tracker.clone());
 
         #[cfg(debug_assertions)]
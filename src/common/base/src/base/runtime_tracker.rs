@@ -42,6 +42,22 @@ impl ThreadTracker {
         }
     }
 
+    /// Track the memory of the current thread by the runtime tracker until the guard is dropped,
+    /// the previous tracker of the thread is restored then.
+    pub fn attach(rt_tracker: Arc<RuntimeTracker>) -> ThreadTrackerGuard {
+        unsafe {
+            let previous = TRACKER;
+            TRACKER = std::ptr::null_mut();
+            let tracker = Box::into_raw(Box::new(ThreadTracker {
+                rt_tracker,
+                untracked_memory: 0,
+            }));
+            TRACKER = tracker;
+
+            ThreadTrackerGuard { previous, tracker }
+        }
+    }
+
     #[inline]
     pub fn current() -> *mut ThreadTracker {
         unsafe { TRACKER }
@@ -101,22 +117,66 @@ impl ThreadTracker {
     }
 }
 
+pub struct ThreadTrackerGuard {
+    previous: *mut ThreadTracker,
+    tracker: *mut ThreadTracker,
+}
+
+impl Drop for ThreadTrackerGuard {
+    fn drop(&mut self) {
+        unsafe {
+            // Flush the untracked memory, the allocations of the thread are not tracked anymore.
+            TRACKER = std::ptr::null_mut();
+            let tracker = Box::from_raw(self.tracker);
+            let untracked_memory = tracker.untracked_memory;
+            if untracked_memory > 0 {
+                tracker
+                    .rt_tracker
+                    .memory_tracker
+                    .alloc_memory(untracked_memory);
+            } else if untracked_memory < 0 {
+                tracker
+                    .rt_tracker
+                    .memory_tracker
+                    .dealloc_memory(-untracked_memory);
+            }
+            drop(tracker);
+
+            TRACKER = self.previous;
+        }
+    }
+}
+
 pub struct MemoryTracker {
     memory_usage: AtomicI64,
+    peak_memory_usage: AtomicI64,
+    /// The maximum memory usage in bytes, 0 means unlimited.
+    limit: i64,
     parent_memory_tracker: Option<Arc<MemoryTracker>>,
 }
 
 impl MemoryTracker {
     pub fn create(parent_memory_tracker: Option<Arc<MemoryTracker>>) -> Arc<MemoryTracker> {
+        Self::create_with_limit(parent_memory_tracker, 0)
+    }
+
+    pub fn create_with_limit(
+        parent_memory_tracker: Option<Arc<MemoryTracker>>,
+        limit: i64,
+    ) -> Arc<MemoryTracker> {
         Arc::new(MemoryTracker {
             parent_memory_tracker,
+            limit,
             memory_usage: AtomicI64::new(0),
+            peak_memory_usage: AtomicI64::new(0),
         })
     }
 
     #[inline]
     pub fn alloc_memory(&self, size: i64) {
-        self.memory_usage.fetch_add(size, Ordering::Relaxed);
+        let memory_usage = self.memory_usage.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak_memory_usage
+            .fetch_max(memory_usage, Ordering::Relaxed);
 
         if let Some(parent_memory_tracker) = &self.parent_memory_tracker {
             parent_memory_tracker.alloc_memory(size);
@@ -147,6 +207,22 @@ impl MemoryTracker {
     pub fn get_memory_usage(&self) -> i64 {
         self.memory_usage.load(Ordering::Relaxed)
     }
+
+    #[inline]
+    pub fn get_peak_memory_usage(&self) -> i64 {
+        self.peak_memory_usage.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub fn get_limit(&self) -> i64 {
+        self.limit
+    }
+
+    /// Whether the memory usage exceeds the limit.
+    #[inline]
+    pub fn is_exceeded(&self) -> bool {
+        self.limit != 0 && self.get_memory_usage() > self.limit
+    }
 }
 
 pub struct RuntimeTracker {
@@ -155,9 +231,14 @@ pub struct RuntimeTracker {
 
 impl RuntimeTracker {
     pub fn create() -> Arc<RuntimeTracker> {
+        Self::create_with_limit(0)
+    }
+
+    /// Create a tracker whose memory usage is limited to `limit` bytes, 0 means unlimited.
+    pub fn create_with_limit(limit: i64) -> Arc<RuntimeTracker> {
         let parent_memory_tracker = MemoryTracker::current();
         Arc::new(RuntimeTracker {
-            memory_tracker: MemoryTracker::create(parent_memory_tracker),
+            memory_tracker: MemoryTracker::create_with_limit(parent_memory_tracker, limit),
         })
     }
 
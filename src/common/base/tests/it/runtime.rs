@@ -77,3 +77,23 @@ async fn test_shutdown_long_run_runtime() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_runtime_tracker_memory_limit() {
+    let tracker = RuntimeTracker::create_with_limit(100);
+    let memory_tracker = tracker.get_memory_tracker();
+
+    memory_tracker.alloc_memory(200);
+    assert!(memory_tracker.is_exceeded());
+    assert_eq!(memory_tracker.get_memory_usage(), 200);
+
+    memory_tracker.dealloc_memory(150);
+    assert!(!memory_tracker.is_exceeded());
+    assert_eq!(memory_tracker.get_memory_usage(), 50);
+    assert_eq!(memory_tracker.get_peak_memory_usage(), 200);
+
+    // Unlimited.
+    let tracker = RuntimeTracker::create();
+    tracker.get_memory_tracker().alloc_memory(i64::MAX / 2);
+    assert!(!tracker.get_memory_tracker().is_exceeded());
+}
@@ -145,6 +145,8 @@ build_exceptions! {
     InvalidTimestamp(1080),
     InvalidClusterKeys(1081),
     UnknownFragmentExchange(1082),
+    ClusterNodeLost(1083),
+    NodeDraining(1084),
 
     // Tenant error codes.
     TenantIsEmpty(1101),
@@ -160,6 +162,16 @@ build_exceptions! {
 
     TableInfoError(1106),
     ReadTableDataError(1107),
+
+    // Query queue error codes
+    QueryQueueFull(1108),
+    QueryQueueTimeout(1109),
+
+    // Query memory error codes
+    QueryMemoryLimitExceeded(1110),
+
+    // Recursive CTE error codes
+    RecursionDepthExceeded(1111),
 }
 
 // Metasvr errors [2001, 3000].
@@ -217,6 +229,9 @@ build_exceptions! {
     IllegalUDFFormat(2601),
     UnknownUDF(2602),
     UdfAlreadyExists(2603),
+    UDFServerConnectError(2604),
+    UDFSchemaMismatch(2605),
+    UDFDataError(2606),
 
     // Database error codes.
     UnknownDatabaseEngine(2701),
@@ -244,12 +259,56 @@ build_exceptions! {
     TenantQuotaUnknown(2902),
     TenantQuotaExceeded(2903),
 
+    // Row access policy error codes.
+    IllegalRowAccessPolicyFormat(2951),
+    UnknownRowAccessPolicy(2952),
+    RowAccessPolicyAlreadyExists(2953),
+
+    // Masking policy error codes.
+    IllegalMaskingPolicyFormat(2961),
+    UnknownMaskingPolicy(2962),
+    MaskingPolicyAlreadyExists(2963),
+    MaskingPolicyInUse(2964),
+
+    // Network policy error codes.
+    IllegalNetworkPolicyFormat(2971),
+    UnknownNetworkPolicy(2972),
+    NetworkPolicyAlreadyExists(2973),
+    NetworkPolicyInUse(2974),
+    NetworkPolicyViolation(2975),
+
+    // Workload group error codes.
+    IllegalWorkloadGroupFormat(2981),
+    UnknownWorkloadGroup(2982),
+    WorkloadGroupAlreadyExists(2983),
+    WorkloadGroupInUse(2984),
+    WorkloadGroupLimitExceeded(2985),
+
+    // Aggregating index error codes.
+    IllegalAggregatingIndexFormat(2991),
+    UnknownAggregatingIndex(2992),
+    AggregatingIndexAlreadyExists(2993),
+
+    // Lock error codes.
+    LockLost(2995),
+
+    // Task error codes.
+    IllegalTaskFormat(2996),
+    UnknownTask(2997),
+    TaskAlreadyExists(2998),
+
 }
 
 // Storage errors [3001, 4000].
 build_exceptions! {
     StorageNotFound(3001),
     StoragePermissionDenied(3002),
+
+    // Index error codes.
+    IllegalIndexFormat(3011),
+    UnknownIndex(3012),
+    IndexAlreadyExists(3013),
+
     StorageUnavailable(3901),
     StorageUnsupported(3902),
     StorageInsecure(3903),
@@ -155,3 +155,51 @@ pub fn mask_string(s: &str, unmask_len: usize) -> String {
         ret
     }
 }
+
+/// Convert a glob of paths to an anchored regex.
+///
+/// `*` and `?` match any characters and one character except `/`, `**` matches any characters
+/// including `/`, and `[...]` (or `[!...]` for negation) matches a character of the class.
+pub fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::with_capacity(glob.len() * 2 + 2);
+    regex.push('^');
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                if chars.peek() == Some(&'*') {
+                    chars.next();
+                    regex.push_str(".*");
+                } else {
+                    regex.push_str("[^/]*");
+                }
+            }
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                regex.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    regex.push('^');
+                }
+                for c in chars.by_ref() {
+                    if c == '\\' {
+                        regex.push_str("\\\\");
+                        continue;
+                    }
+                    regex.push(c);
+                    if c == ']' {
+                        break;
+                    }
+                }
+            }
+            c => {
+                if "\\.+()|{}^$".contains(c) {
+                    regex.push('\\');
+                }
+                regex.push(c);
+            }
+        }
+    }
+    regex.push('$');
+    regex
+}
@@ -45,3 +45,17 @@ fn parse_escape() {
         assert_eq!(parse_escape_bytes(c[0].as_bytes()), c[1].as_bytes());
     }
 }
+
+#[test]
+fn glob_to_regex_test() {
+    let cases = vec![
+        vec!["data/*.csv", "^data/[^/]*\\.csv$"],
+        vec!["data/**/part-?.csv.gz", "^data/.*/part-[^/]\\.csv\\.gz$"],
+        vec!["data/[!a-c]*.csv", "^data/[^a-c][^/]*\\.csv$"],
+        vec!["data/(1)+{2}", "^data/\\(1\\)\\+\\{2\\}$"],
+    ];
+
+    for c in cases {
+        assert_eq!(glob_to_regex(c[0]), c[1]);
+    }
+}
@@ -113,6 +113,10 @@ impl StorageParams {
     }
 }
 
+/// The blob service of an azure storage account `<account>` is served at
+/// `https://<account>.blob.core.windows.net`.
+pub static STORAGE_AZBLOB_DEFAULT_ENDPOINT_SUFFIX: &str = "blob.core.windows.net";
+
 /// Config for storage backend azblob.
 #[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StorageAzblobConfig {
@@ -27,6 +27,7 @@ pub use config::StorageIpfsConfig;
 pub use config::StorageObsConfig;
 pub use config::StorageParams;
 pub use config::StorageS3Config;
+pub use config::STORAGE_AZBLOB_DEFAULT_ENDPOINT_SUFFIX;
 pub use config::STORAGE_GCS_DEFAULT_ENDPOINT;
 pub use config::STORAGE_IPFS_DEFAULT_ENDPOINT;
 pub use config::STORAGE_S3_DEFAULT_ENDPOINT;
@@ -23,6 +23,7 @@ use percent_encoding::percent_decode_str;
 
 use crate::config::StorageHttpConfig;
 use crate::config::StorageIpfsConfig;
+use crate::config::STORAGE_AZBLOB_DEFAULT_ENDPOINT_SUFFIX;
 use crate::config::STORAGE_IPFS_DEFAULT_ENDPOINT;
 use crate::config::STORAGE_S3_DEFAULT_ENDPOINT;
 use crate::StorageAzblobConfig;
@@ -50,29 +51,43 @@ pub fn parse_uri_location(l: &UriLocation) -> Result<(StorageParams, String)> {
         ("/", l.path.as_str())
     };
 
+    // OSS and COS are accessed by their S3 compatible API.
+    if l.protocol == "oss" || l.protocol == "cos" {
+        let cfg = parse_s3_compatible_location(l, root)?;
+        return Ok((StorageParams::S3(cfg), path.to_string()));
+    }
+
     let protocol = l.protocol.parse::<Scheme>()?;
 
     let sp = match protocol {
-        Scheme::Azblob => StorageParams::Azblob(StorageAzblobConfig {
-            endpoint_url: l
-                .connection
-                .get("endpoint_url")
-                .ok_or_else(|| {
-                    Error::new(
-                        ErrorKind::InvalidInput,
-                        anyhow!("endpoint_url is required for storage azblob"),
-                    )
-                })?
-                .to_string(),
-            container: l.name.to_string(),
-            account_name: l
+        Scheme::Azblob => {
+            let account_name = l
                 .connection
                 .get("account_name")
                 .cloned()
-                .unwrap_or_default(),
-            account_key: l.connection.get("account_key").cloned().unwrap_or_default(),
-            root: root.to_string(),
-        }),
+                .unwrap_or_default();
+            // The endpoint of an account could be inferred from its name.
+            let endpoint_url = match l.connection.get("endpoint_url") {
+                Some(v) => v.to_string(),
+                None if !account_name.is_empty() => {
+                    format!("https://{account_name}.{STORAGE_AZBLOB_DEFAULT_ENDPOINT_SUFFIX}")
+                }
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        anyhow!("endpoint_url or account_name is required for storage azblob"),
+                    ));
+                }
+            };
+
+            StorageParams::Azblob(StorageAzblobConfig {
+                endpoint_url,
+                container: l.name.to_string(),
+                account_name,
+                account_key: l.connection.get("account_key").cloned().unwrap_or_default(),
+                root: root.to_string(),
+            })
+        }
         Scheme::Gcs => StorageParams::Gcs(crate::StorageGcsConfig {
             endpoint_url: l
                 .connection
@@ -80,7 +95,7 @@ pub fn parse_uri_location(l: &UriLocation) -> Result<(StorageParams, String)> {
                 .cloned()
                 .unwrap_or_else(|| STORAGE_GCS_DEFAULT_ENDPOINT.to_string()),
             bucket: l.name.clone(),
-            root: l.path.clone(),
+            root: root.to_string(),
             credential: l.connection.get("credential").cloned().unwrap_or_default(),
         }),
         #[cfg(feature = "storage-hdfs")]
@@ -176,3 +191,64 @@ pub fn parse_uri_location(l: &UriLocation) -> Result<(StorageParams, String)> {
 
     Ok((sp, path.to_string()))
 }
+
+/// Parse the location of Aliyun OSS or Tencent COS into the config of their S3 compatible API.
+///
+/// The endpoint is inferred from `region` if not given, `internal` switches OSS to its endpoint
+/// of the internal network. Both services only support the virtual hosted style.
+fn parse_s3_compatible_location(l: &UriLocation, root: &str) -> Result<StorageS3Config> {
+    let region = l.connection.get("region").cloned().unwrap_or_default();
+    let internal: bool = l
+        .connection
+        .get("internal")
+        .cloned()
+        .unwrap_or_else(|| "false".to_string())
+        .parse()
+        .map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                anyhow!("value for internal is invalid: {err:?}"),
+            )
+        })?;
+
+    let endpoint_url = match l.connection.get("endpoint_url") {
+        Some(v) => v.to_string(),
+        None if region.is_empty() => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                anyhow!(
+                    "endpoint_url or region is required for storage {}",
+                    l.protocol
+                ),
+            ));
+        }
+        None if l.protocol == "cos" => format!("https://cos.{region}.myqcloud.com"),
+        None if internal => format!("https://oss-{region}-internal.aliyuncs.com"),
+        None => format!("https://oss-{region}.aliyuncs.com"),
+    };
+
+    Ok(StorageS3Config {
+        endpoint_url,
+        region,
+        bucket: l.name.to_string(),
+        access_key_id: l
+            .connection
+            .get("access_key_id")
+            .cloned()
+            .unwrap_or_default(),
+        secret_access_key: l
+            .connection
+            .get("secret_access_key")
+            .cloned()
+            .unwrap_or_default(),
+        security_token: l
+            .connection
+            .get("security_token")
+            .cloned()
+            .unwrap_or_default(),
+        master_key: "".to_string(),
+        root: root.to_string(),
+        disable_credential_loader: true,
+        enable_virtual_host_style: true,
+    })
+}
@@ -45,8 +45,10 @@ use crate::config::StorageObsConfig;
 use crate::StorageConfig;
 
 /// init_operator will init an opendal operator based on storage config.
+///
+/// The operations are measured and traced per backend, the spans attach to the current trace.
 pub fn init_operator(cfg: &StorageParams) -> Result<Operator> {
-    Ok(match &cfg {
+    let op = match &cfg {
         StorageParams::Azblob(cfg) => init_azblob_operator(cfg)?,
         StorageParams::Fs(cfg) => init_fs_operator(cfg)?,
         StorageParams::Gcs(cfg) => init_gcs_operator(cfg)?,
@@ -57,7 +59,9 @@ pub fn init_operator(cfg: &StorageParams) -> Result<Operator> {
         StorageParams::Memory => init_memory_operator()?,
         StorageParams::Obs(cfg) => init_obs_operator(cfg)?,
         StorageParams::S3(cfg) => init_s3_operator(cfg)?,
-    })
+    };
+
+    Ok(op.layer(MetricsLayer).layer(TracingLayer))
 }
 
 /// init_azblob_operator will init an opendal azblob operator.
@@ -65,7 +69,13 @@ pub fn init_azblob_operator(cfg: &StorageAzblobConfig) -> Result<Operator> {
     let mut builder = azblob::Builder::default();
 
     // Endpoint
-    builder.endpoint(&cfg.endpoint_url);
+    //
+    // ADLS Gen2 accounts serve the blob API too, so the dfs endpoint is
+    // replaced by the blob one of the same account.
+    builder.endpoint(
+        &cfg.endpoint_url
+            .replace(".dfs.core.windows.net", ".blob.core.windows.net"),
+    );
 
     // Container
     builder.container(&cfg.container);
@@ -231,10 +241,9 @@ impl StorageOperator {
 
     pub async fn try_create(conf: &StorageConfig) -> common_exception::Result<Operator> {
         let io_runtime = GlobalIORuntime::instance();
-        let operator = init_operator(&conf.params)?
-            .layer(RetryLayer::new(ExponentialBackoff::default()))
-            .layer(MetricsLayer)
-            .layer(TracingLayer);
+        // Retries are applied on top of the metrics, so that every attempt is measured.
+        let operator =
+            init_operator(&conf.params)?.layer(RetryLayer::new(ExponentialBackoff::default()));
 
         // OpenDAL will send a real request to underlying storage to check whether it works or not.
         // If this check failed, it's highly possible that the users have configured it wrongly.
@@ -16,6 +16,7 @@ use std::collections::BTreeMap;
 use std::io::Result;
 
 use common_storage::parse_uri_location;
+use common_storage::StorageAzblobConfig;
 use common_storage::StorageGcsConfig;
 use common_storage::StorageHttpConfig;
 use common_storage::StorageIpfsConfig;
@@ -177,6 +178,88 @@ fn test_parse_uri_location() -> Result<()> {
                 "/".to_string(),
             ),
         ),
+        (
+            "azblob_with_account_name",
+            UriLocation {
+                protocol: "azblob".to_string(),
+                name: "example".to_string(),
+                path: "/tmp/".to_string(),
+                connection: vec![("account_name", "account"), ("account_key", "key")]
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            },
+            (
+                StorageParams::Azblob(StorageAzblobConfig {
+                    endpoint_url: "https://account.blob.core.windows.net".to_string(),
+                    container: "example".to_string(),
+                    account_name: "account".to_string(),
+                    account_key: "key".to_string(),
+                    root: "/tmp/".to_string(),
+                }),
+                "/".to_string(),
+            ),
+        ),
+        (
+            "oss_with_internal_endpoint",
+            UriLocation {
+                protocol: "oss".to_string(),
+                name: "example".to_string(),
+                path: "/tmp/".to_string(),
+                connection: vec![
+                    ("region", "cn-hangzhou"),
+                    ("internal", "true"),
+                    ("access_key_id", "access_key_id"),
+                    ("secret_access_key", "secret_access_key"),
+                    ("security_token", "security_token"),
+                ]
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            },
+            (
+                StorageParams::S3(StorageS3Config {
+                    endpoint_url: "https://oss-cn-hangzhou-internal.aliyuncs.com".to_string(),
+                    region: "cn-hangzhou".to_string(),
+                    bucket: "example".to_string(),
+                    access_key_id: "access_key_id".to_string(),
+                    secret_access_key: "secret_access_key".to_string(),
+                    security_token: "security_token".to_string(),
+                    master_key: "".to_string(),
+                    root: "/tmp/".to_string(),
+                    disable_credential_loader: true,
+                    enable_virtual_host_style: true,
+                }),
+                "/".to_string(),
+            ),
+        ),
+        (
+            "cos_with_region",
+            UriLocation {
+                protocol: "cos".to_string(),
+                name: "example-1250000000".to_string(),
+                path: "/tmp/".to_string(),
+                connection: vec![("region", "ap-guangzhou")]
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            },
+            (
+                StorageParams::S3(StorageS3Config {
+                    endpoint_url: "https://cos.ap-guangzhou.myqcloud.com".to_string(),
+                    region: "ap-guangzhou".to_string(),
+                    bucket: "example-1250000000".to_string(),
+                    access_key_id: "".to_string(),
+                    secret_access_key: "".to_string(),
+                    security_token: "".to_string(),
+                    master_key: "".to_string(),
+                    root: "/tmp/".to_string(),
+                    disable_credential_loader: true,
+                    enable_virtual_host_style: true,
+                }),
+                "/".to_string(),
+            ),
+        ),
         (
             "http_without_glob",
             UriLocation {
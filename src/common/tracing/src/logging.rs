@@ -175,6 +175,10 @@ pub fn init_query_logger(
 
 pub struct QueryLogger {
     subscriber: Option<Arc<dyn Subscriber + Send + Sync>>,
+    // writes the slow queries to `{dir}/slow-query`
+    slow_query_subscriber: Option<Arc<dyn Subscriber + Send + Sync>>,
+    // writes the audit events to `{dir}/audit`
+    audit_subscriber: Option<Arc<dyn Subscriber + Send + Sync>>,
 
     /// log_guard preserve the nonblocking logger's guards so that our logger
     /// can flushes spans/events on a drop
@@ -194,19 +198,30 @@ impl QueryLogger {
         let app_name = format!("databend-query-{}", app_name_shuffle);
         let mut _log_guards = init_logging(app_name.as_str(), config);
         let query_detail_dir = format!("{}/query-detail", config.file.dir);
+        let slow_query_dir = format!("{}/slow-query", config.file.dir);
+        let audit_dir = format!("{}/audit", config.file.dir);
 
         v.init(match config.file.on {
             true => {
                 let (_guards, subscriber) = init_query_logger(&app_name_shuffle, &query_detail_dir);
                 _log_guards.extend(_guards);
+                let (_guards, slow_query_subscriber) =
+                    init_query_logger(&app_name_shuffle, &slow_query_dir);
+                _log_guards.extend(_guards);
+                let (_guards, audit_subscriber) = init_query_logger(&app_name_shuffle, &audit_dir);
+                _log_guards.extend(_guards);
 
                 Arc::new(QueryLogger {
                     _log_guards,
                     subscriber: Some(subscriber),
+                    slow_query_subscriber: Some(slow_query_subscriber),
+                    audit_subscriber: Some(audit_subscriber),
                 })
             }
             false => Arc::new(QueryLogger {
                 subscriber: None,
+                slow_query_subscriber: None,
+                audit_subscriber: None,
                 _log_guards: vec![],
             }),
         })?;
@@ -225,4 +240,12 @@ impl QueryLogger {
     pub fn get_subscriber(&self) -> Option<Arc<dyn Subscriber + Send + Sync>> {
         self.subscriber.clone()
     }
+
+    pub fn get_slow_query_subscriber(&self) -> Option<Arc<dyn Subscriber + Send + Sync>> {
+        self.slow_query_subscriber.clone()
+    }
+
+    pub fn get_audit_subscriber(&self) -> Option<Arc<dyn Subscriber + Send + Sync>> {
+        self.audit_subscriber.clone()
+    }
 }
@@ -44,6 +44,8 @@ use common_meta_app::schema::UndropDatabaseReply;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReply;
 use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateMultiTableMetaReply;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReply;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_app::schema::UpsertTableCopiedFileReply;
@@ -134,6 +136,13 @@ pub trait SchemaApi: Send + Sync {
         req: UpdateTableMetaReq,
     ) -> Result<UpdateTableMetaReply, KVAppError>;
 
+    /// Update the meta of several tables in one transaction, it fails if any of the tables is
+    /// changed after the version in the request.
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply, KVAppError>;
+
     // gc dropped {table|db} which out of retention time.
     async fn gc_dropped_data(
         &self,
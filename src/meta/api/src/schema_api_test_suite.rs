@@ -55,6 +55,7 @@ use common_meta_app::schema::TableStatistics;
 use common_meta_app::schema::TruncateTableReq;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_app::schema::UpsertTableCopiedFileReq;
 use common_meta_app::schema::UpsertTableOptionReq;
@@ -236,6 +237,7 @@ impl SchemaApiTestSuite {
         suite.table_create_get_drop(&b.build().await).await?;
         suite.table_rename(&b.build().await).await?;
         suite.table_update_meta(&b.build().await).await?;
+        suite.table_update_multi_meta(&b.build().await).await?;
         suite.table_upsert_option(&b.build().await).await?;
         suite.table_list(&b.build().await).await?;
         suite
@@ -2069,6 +2071,103 @@ impl SchemaApiTestSuite {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn table_update_multi_meta<MT: SchemaApi>(&self, mt: &MT) -> anyhow::Result<()> {
+        let tenant = "tenant1";
+        let db_name = "db1";
+        let tbl_names = ["tb1", "tb2"];
+
+        let table_meta = || TableMeta {
+            schema: Arc::new(DataSchema::new(vec![DataField::new(
+                "number",
+                u64::to_data_type(),
+            )])),
+            engine: "JSON".to_string(),
+            ..TableMeta::default()
+        };
+
+        info!("--- prepare db and tables");
+        {
+            let plan = CreateDatabaseReq {
+                if_not_exists: false,
+                name_ident: DatabaseNameIdent {
+                    tenant: tenant.to_string(),
+                    db_name: db_name.to_string(),
+                },
+                meta: DatabaseMeta {
+                    engine: "".to_string(),
+                    ..DatabaseMeta::default()
+                },
+            };
+            mt.create_database(plan).await?;
+
+            for tbl_name in tbl_names {
+                let req = CreateTableReq {
+                    if_not_exists: false,
+                    name_ident: TableNameIdent {
+                        tenant: tenant.to_string(),
+                        db_name: db_name.to_string(),
+                        table_name: tbl_name.to_string(),
+                    },
+                    table_meta: table_meta(),
+                };
+                mt.create_table(req).await?;
+            }
+        }
+
+        let update_req = |table: &TableInfo, seq: u64, data_bytes: u64| {
+            let mut new_table_meta = table.meta.clone();
+            new_table_meta.statistics = TableStatistics {
+                data_bytes,
+                ..Default::default()
+            };
+            UpdateTableMetaReq {
+                table_id: table.ident.table_id,
+                seq: MatchSeq::Exact(seq),
+                new_table_meta,
+            }
+        };
+
+        info!("--- update multi table meta, normal case");
+        {
+            let mut update_table_metas = vec![];
+            for tbl_name in tbl_names {
+                let table = mt.get_table((tenant, db_name, tbl_name).into()).await?;
+                update_table_metas.push(update_req(&table, table.ident.seq, 1));
+            }
+            mt.update_multi_table_meta(UpdateMultiTableMetaReq { update_table_metas })
+                .await?;
+
+            for tbl_name in tbl_names {
+                let table = mt.get_table((tenant, db_name, tbl_name).into()).await?;
+                assert_eq!(1, table.meta.statistics.data_bytes);
+            }
+        }
+
+        info!("--- update multi table meta: version mismatch, no table is updated");
+        {
+            let tb1 = mt.get_table((tenant, db_name, "tb1").into()).await?;
+            let tb2 = mt.get_table((tenant, db_name, "tb2").into()).await?;
+            let res = mt
+                .update_multi_table_meta(UpdateMultiTableMetaReq {
+                    update_table_metas: vec![
+                        update_req(&tb1, tb1.ident.seq, 2),
+                        update_req(&tb2, tb2.ident.seq + 1, 2),
+                    ],
+                })
+                .await;
+
+            let err = ErrorCode::from(res.unwrap_err());
+            assert_eq!(ErrorCode::table_version_mismatched_code(), err.code());
+
+            for tbl_name in tbl_names {
+                let table = mt.get_table((tenant, db_name, tbl_name).into()).await?;
+                assert_eq!(1, table.meta.statistics.data_bytes);
+            }
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     async fn table_upsert_option<MT: SchemaApi>(&self, mt: &MT) -> anyhow::Result<()> {
         let tenant = "tenant1";
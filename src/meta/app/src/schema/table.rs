@@ -540,6 +540,15 @@ pub struct UpsertTableOptionReply {}
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct UpdateTableMetaReply {}
 
+/// Update the meta of several tables atomically, either all of them are updated or none.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UpdateMultiTableMetaReq {
+    pub update_table_metas: Vec<UpdateTableMetaReq>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UpdateMultiTableMetaReply {}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct GetTableReq {
     pub inner: TableNameIdent,
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::fmt::Debug;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
@@ -56,6 +58,7 @@ use common_meta_types::MetaClientError;
 use common_meta_types::MetaError;
 use common_meta_types::MetaHandshakeError;
 use common_meta_types::MetaNetworkError;
+use common_meta_types::RaftTxId;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
 use common_metrics::label_counter_with_val_and_labels;
@@ -237,6 +240,13 @@ pub struct MetaGrpcClient {
     unhealthy_endpoints: Mutex<TtlHashMap<String, ()>>,
     auto_sync_interval: Option<Duration>,
 
+    /// Identifies this client in the txid of a write request.
+    ///
+    /// Together with `next_serial` it lets metasrv detect a write request retried after a timeout,
+    /// and not apply it twice.
+    client_id: String,
+    next_serial: AtomicU64,
+
     /// Dedicated runtime to support meta client background tasks.
     ///
     /// In order not to let a blocking operation(such as calling the new PipelinePullingExecutor) in a tokio runtime block meta-client background tasks.
@@ -307,6 +317,12 @@ impl MetaGrpcClient {
             current_endpoint: Arc::new(Mutex::new(None)),
             unhealthy_endpoints: Mutex::new(TtlHashMap::new(Duration::from_secs(120))),
             auto_sync_interval,
+            client_id: format!(
+                "meta-client-{}-{:016x}",
+                std::process::id(),
+                rand::random::<u64>()
+            ),
+            next_serial: AtomicU64::new(1),
             username: username.to_string(),
             password: password.to_string(),
             rt: rt.clone(),
@@ -786,12 +802,18 @@ impl MetaGrpcClient {
         R: DeserializeOwned,
     {
         let act: MetaGrpcWriteReq = v.into();
+        let txid = self.next_txid();
 
-        debug!(req = debug(&act), "MetaGrpcClient::do_write request");
+        debug!(
+            req = debug(&act),
+            txid = display(&txid),
+            "MetaGrpcClient::do_write request"
+        );
 
-        let req: Request<RaftRequest> = act.clone().try_into().map_err(|e| {
+        let mut req: Request<RaftRequest> = act.clone().try_into().map_err(|e| {
             MetaNetworkError::InvalidArgument(InvalidArgument::new(e, "fail to encode request"))
         })?;
+        txid.inject_to(req.metadata_mut());
 
         debug!(
             req = debug(&req),
@@ -808,12 +830,13 @@ impl MetaGrpcClient {
                 if status_is_retryable(&s) {
                     self.mark_as_unhealthy().await;
                     let mut client = self.make_client().await?;
-                    let req: Request<RaftRequest> = act.try_into().map_err(|e| {
+                    let mut req: Request<RaftRequest> = act.try_into().map_err(|e| {
                         MetaNetworkError::InvalidArgument(InvalidArgument::new(
                             e,
                             "fail to encode request",
                         ))
                     })?;
+                    txid.inject_to(req.metadata_mut());
                     let req = common_tracing::inject_span_to_tonic_request(req);
                     Ok(client.write_msg(req).await?.into_inner())
                 } else {
@@ -884,10 +907,16 @@ impl MetaGrpcClient {
     #[tracing::instrument(level = "debug", skip(self, req))]
     pub(crate) async fn transaction(&self, req: TxnRequest) -> Result<TxnReply, KVAppError> {
         let txn: TxnRequest = req;
+        let txid = self.next_txid();
 
-        debug!(req = display(&txn), "MetaGrpcClient::transaction request");
+        debug!(
+            req = display(&txn),
+            txid = display(&txid),
+            "MetaGrpcClient::transaction request"
+        );
 
-        let req: Request<TxnRequest> = Request::new(txn.clone());
+        let mut req: Request<TxnRequest> = Request::new(txn.clone());
+        txid.inject_to(req.metadata_mut());
         let req = common_tracing::inject_span_to_tonic_request(req);
 
         let mut client = self.make_client().await?;
@@ -899,7 +928,8 @@ impl MetaGrpcClient {
                 if status_is_retryable(&s) {
                     self.mark_as_unhealthy().await;
                     let mut client = self.make_client().await?;
-                    let req: Request<TxnRequest> = Request::new(txn);
+                    let mut req: Request<TxnRequest> = Request::new(txn);
+                    txid.inject_to(req.metadata_mut());
                     let req = common_tracing::inject_span_to_tonic_request(req);
                     let ret = client.transaction(req).await?.into_inner();
                     return Ok(ret);
@@ -915,6 +945,15 @@ impl MetaGrpcClient {
 
         Ok(reply)
     }
+
+    /// Generate a txid for a write request.
+    ///
+    /// The same txid has to be used when the request is retried.
+    fn next_txid(&self) -> RaftTxId {
+        let serial = self.next_serial.fetch_add(1, Ordering::Relaxed);
+        RaftTxId::new(&self.client_id, serial)
+    }
+
     async fn mark_as_unhealthy(&self) {
         let ca = self.current_endpoint.lock();
         let mut ue = self.unhealthy_endpoints.lock();
@@ -13,8 +13,10 @@
 // limitations under the License.
 
 use common_protos::pb;
+use common_storage::StorageAzblobConfig;
 use common_storage::StorageFsConfig;
 use common_storage::StorageGcsConfig;
+use common_storage::StorageHdfsConfig;
 use common_storage::StorageS3Config;
 
 use crate::check_ver;
@@ -89,6 +91,58 @@ impl FromToProto for StorageGcsConfig {
     }
 }
 
+impl FromToProto for StorageAzblobConfig {
+    type PB = pb::AzblobStorageConfig;
+
+    fn from_pb(p: Self::PB) -> Result<Self, Incompatible>
+    where Self: Sized {
+        check_ver(p.version, p.min_compatible)?;
+
+        Ok(StorageAzblobConfig {
+            endpoint_url: p.endpoint_url,
+            container: p.container,
+            root: p.root,
+            account_name: p.account_name,
+            account_key: p.account_key,
+        })
+    }
+
+    fn to_pb(&self) -> Result<Self::PB, Incompatible> {
+        Ok(pb::AzblobStorageConfig {
+            version: VER,
+            min_compatible: MIN_COMPATIBLE_VER,
+            endpoint_url: self.endpoint_url.clone(),
+            container: self.container.clone(),
+            root: self.root.clone(),
+            account_name: self.account_name.clone(),
+            account_key: self.account_key.clone(),
+        })
+    }
+}
+
+impl FromToProto for StorageHdfsConfig {
+    type PB = pb::HdfsStorageConfig;
+
+    fn from_pb(p: Self::PB) -> Result<Self, Incompatible>
+    where Self: Sized {
+        check_ver(p.version, p.min_compatible)?;
+
+        Ok(StorageHdfsConfig {
+            name_node: p.name_node,
+            root: p.root,
+        })
+    }
+
+    fn to_pb(&self) -> Result<Self::PB, Incompatible> {
+        Ok(pb::HdfsStorageConfig {
+            version: VER,
+            min_compatible: MIN_COMPATIBLE_VER,
+            name_node: self.name_node.clone(),
+            root: self.root.clone(),
+        })
+    }
+}
+
 impl FromToProto for StorageFsConfig {
     type PB = pb::FsStorageConfig;
 
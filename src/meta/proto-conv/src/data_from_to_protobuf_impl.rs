@@ -129,6 +129,8 @@ impl FromToProto for dv::DataTypeImpl {
                 Ok(dv::DataTypeImpl::VariantObject(dv::VariantObjectType {}))
             }
             Dt::IntervalType(x) => Ok(dv::DataTypeImpl::Interval(dv::IntervalType::from_pb(x)?)),
+            Dt::GeometryType(_) => Ok(dv::DataTypeImpl::Geometry(dv::GeometryType {})),
+            Dt::GeographyType(_) => Ok(dv::DataTypeImpl::Geography(dv::GeographyType {})),
         }
     }
 
@@ -321,6 +323,22 @@ impl FromToProto for dv::DataTypeImpl {
                 };
                 Ok(p)
             }
+            dv::DataTypeImpl::Geometry(_) => {
+                let p = pb::DataType {
+                    ver: VER,
+                    min_compatible: MIN_COMPATIBLE_VER,
+                    dt: Some(Dt::GeometryType(pb::Empty {})),
+                };
+                Ok(p)
+            }
+            dv::DataTypeImpl::Geography(_) => {
+                let p = pb::DataType {
+                    ver: VER,
+                    min_compatible: MIN_COMPATIBLE_VER,
+                    dt: Some(Dt::GeographyType(pb::Empty {})),
+                };
+                Ok(p)
+            }
         }
     }
 }
@@ -23,6 +23,7 @@ use common_datavalues::chrono::DateTime;
 use common_datavalues::chrono::Utc;
 use common_meta_types as mt;
 use common_protos::pb;
+use common_storage::StorageAzblobConfig;
 use common_storage::StorageFsConfig;
 use common_storage::StorageGcsConfig;
 use common_storage::StorageParams;
@@ -45,6 +46,7 @@ impl FromToProto for mt::AuthInfo {
         match p.info {
             Some(pb::auth_info::Info::None(pb::auth_info::None {})) => Ok(mt::AuthInfo::None),
             Some(pb::auth_info::Info::Jwt(pb::auth_info::Jwt {})) => Ok(mt::AuthInfo::JWT),
+            Some(pb::auth_info::Info::Ldap(pb::auth_info::Ldap {})) => Ok(mt::AuthInfo::LDAP),
             Some(pb::auth_info::Info::Password(pb::auth_info::Password {
                 hash_value,
                 hash_method,
@@ -64,6 +66,7 @@ impl FromToProto for mt::AuthInfo {
         let info = match self {
             mt::AuthInfo::None => Some(pb::auth_info::Info::None(pb::auth_info::None {})),
             mt::AuthInfo::JWT => Some(pb::auth_info::Info::Jwt(pb::auth_info::Jwt {})),
+            mt::AuthInfo::LDAP => Some(pb::auth_info::Info::Ldap(pb::auth_info::Ldap {})),
             mt::AuthInfo::Password {
                 hash_value,
                 hash_method,
@@ -91,7 +94,10 @@ impl FromToProto for mt::UserOption {
 
         Ok(mt::UserOption::default()
             .with_flags(flags)
-            .with_default_role(p.default_role))
+            .with_default_role(p.default_role)
+            .with_network_policy(p.network_policy)
+            .with_workload_group(p.workload_group)
+            .with_default_settings(p.default_settings))
     }
 
     fn to_pb(&self) -> Result<pb::UserOption, Incompatible> {
@@ -100,6 +106,9 @@ impl FromToProto for mt::UserOption {
             min_compatible: MIN_COMPATIBLE_VER,
             flags: self.flags().bits(),
             default_role: self.default_role().cloned(),
+            network_policy: self.network_policy().cloned(),
+            workload_group: self.workload_group().cloned(),
+            default_settings: self.default_settings().clone(),
         })
     }
 }
@@ -147,6 +156,12 @@ impl FromToProto for mt::GrantObject {
                 db,
                 table,
             })) => Ok(mt::GrantObject::Table(catalog, db, table)),
+            Some(pb::grant_object::Object::Stage(pb::grant_object::GrantStageObject { stage })) => {
+                Ok(mt::GrantObject::Stage(stage))
+            }
+            Some(pb::grant_object::Object::Udf(pb::grant_object::GrantUdfObject { udf })) => {
+                Ok(mt::GrantObject::UDF(udf))
+            }
             _ => Err(Incompatible {
                 reason: "GrantObject cannot be None".to_string(),
             }),
@@ -171,6 +186,14 @@ impl FromToProto for mt::GrantObject {
                     table: table.clone(),
                 },
             )),
+            mt::GrantObject::Stage(stage) => Some(pb::grant_object::Object::Stage(
+                pb::grant_object::GrantStageObject {
+                    stage: stage.clone(),
+                },
+            )),
+            mt::GrantObject::UDF(udf) => Some(pb::grant_object::Object::Udf(
+                pb::grant_object::GrantUdfObject { udf: udf.clone() },
+            )),
         };
         Ok(pb::GrantObject {
             ver: VER,
@@ -425,6 +448,17 @@ impl FromToProto for StorageParams {
             Some(pb::user_stage_info::stage_storage::Storage::Gcs(s)) => {
                 Ok(StorageParams::Gcs(StorageGcsConfig::from_pb(s)?))
             }
+            Some(pb::user_stage_info::stage_storage::Storage::Azblob(s)) => {
+                Ok(StorageParams::Azblob(StorageAzblobConfig::from_pb(s)?))
+            }
+            #[cfg(feature = "storage-hdfs")]
+            Some(pb::user_stage_info::stage_storage::Storage::Hdfs(s)) => Ok(StorageParams::Hdfs(
+                common_storage::StorageHdfsConfig::from_pb(s)?,
+            )),
+            #[cfg(not(feature = "storage-hdfs"))]
+            Some(pb::user_stage_info::stage_storage::Storage::Hdfs(_)) => Err(Incompatible {
+                reason: "StageStorage.storage hdfs is not enabled in this build".to_string(),
+            }),
             None => Err(Incompatible {
                 reason: "StageStorage.storage cannot be None".to_string(),
             }),
@@ -442,6 +476,17 @@ impl FromToProto for StorageParams {
             StorageParams::Gcs(v) => Ok(pb::user_stage_info::StageStorage {
                 storage: Some(pb::user_stage_info::stage_storage::Storage::Gcs(v.to_pb()?)),
             }),
+            StorageParams::Azblob(v) => Ok(pb::user_stage_info::StageStorage {
+                storage: Some(pb::user_stage_info::stage_storage::Storage::Azblob(
+                    v.to_pb()?,
+                )),
+            }),
+            #[cfg(feature = "storage-hdfs")]
+            StorageParams::Hdfs(v) => Ok(pb::user_stage_info::StageStorage {
+                storage: Some(pb::user_stage_info::stage_storage::Storage::Hdfs(
+                    v.to_pb()?,
+                )),
+            }),
             _ => todo!("other stage storage are not supported"),
         }
     }
@@ -489,6 +534,10 @@ impl FromToProto for mt::FileFormatOptions {
             field_delimiter: p.field_delimiter.clone(),
             record_delimiter: p.record_delimiter,
             compression,
+            quote: p.quote,
+            escape: p.escape,
+            null_display: p.null_display,
+            allow_column_count_mismatch: p.allow_column_count_mismatch,
         })
     }
 
@@ -503,6 +552,10 @@ impl FromToProto for mt::FileFormatOptions {
             field_delimiter: self.field_delimiter.clone(),
             record_delimiter: self.record_delimiter.clone(),
             compression,
+            quote: self.quote.clone(),
+            escape: self.escape.clone(),
+            null_display: self.null_display.clone(),
+            allow_column_count_mismatch: self.allow_column_count_mismatch,
         })
     }
 }
@@ -572,6 +625,9 @@ impl FromToProto for mt::CopyOptions {
             on_error,
             size_limit,
             purge: p.purge,
+            max_errors: p.max_errors,
+            max_file_size: p.max_file_size,
+            single: p.single,
         })
     }
 
@@ -584,6 +640,9 @@ impl FromToProto for mt::CopyOptions {
             on_error: Some(on_error),
             size_limit,
             purge: self.purge,
+            max_errors: self.max_errors,
+            max_file_size: self.max_file_size,
+            single: self.single,
         })
     }
 }
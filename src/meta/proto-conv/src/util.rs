@@ -48,6 +48,31 @@ const META_CHANGE_LOG: &[(u64, &str)] = &[
         "2022-09-20: Add: config.proto/S3StorageConfig::security_token",
     ),
     (10, "2022-09-23: Add: table.proto/TableMeta::catalog"),
+    (
+        11,
+        "2022-09-26: Add: users.proto/FileFormatOptions::{quote, escape, null_display, allow_column_count_mismatch}, CopyOptions::max_errors",
+    ),
+    (12, "2022-09-28: Add: config.proto/AzblobStorageConfig"),
+    (13, "2022-09-29: Add: config.proto/HdfsStorageConfig"),
+    (
+        14,
+        "2022-10-16: Add: user.proto/GrantObject::{GrantStageObject, GrantUdfObject}",
+    ),
+    (15, "2022-10-17: Add: user.proto/UserOption::network_policy"),
+    (16, "2022-10-18: Add: user.proto/UserOption::workload_group"),
+    (
+        17,
+        "2022-10-19: Add: datatype.proto/DataType::{geometry_type, geography_type}",
+    ),
+    (
+        18,
+        "2022-10-20: Add: user.proto/UserOption::default_settings",
+    ),
+    (19, "2022-10-21: Add: user.proto/AuthInfo::ldap"),
+    (
+        20,
+        "2022-10-22: Add: user.proto/CopyOptions::{max_file_size, single}",
+    ),
 ];
 
 pub const VER: u64 = META_CHANGE_LOG.last().unwrap().0;
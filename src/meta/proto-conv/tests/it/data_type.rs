@@ -0,0 +1,42 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test DataTypeImpl
+
+use common_datavalues as dv;
+
+use crate::common;
+
+#[test]
+fn test_data_type_geometry_latest() -> anyhow::Result<()> {
+    common::test_pb_from_to("data_type_geometry", dv::GeometryType::new_impl())?;
+    common::test_pb_from_to("data_type_geography", dv::GeographyType::new_impl())?;
+    Ok(())
+}
+
+#[test]
+fn test_data_type_geometry_v17() -> anyhow::Result<()> {
+    // Encoded data of version 17 of data_type_geometry:
+    // It is generated with common::test_pb_from_to.
+    let data_type_geometry_v17 = vec![160, 6, 17, 168, 6, 1, 178, 1, 0];
+    let want = dv::GeometryType::new_impl();
+    common::test_load_old(func_name!(), data_type_geometry_v17.as_slice(), want)?;
+
+    // Encoded data of version 17 of data_type_geography:
+    // It is generated with common::test_pb_from_to.
+    let data_type_geography_v17 = vec![160, 6, 17, 168, 6, 1, 186, 1, 0];
+    let want = dv::GeographyType::new_impl();
+    common::test_load_old(func_name!(), data_type_geography_v17.as_slice(), want)?;
+    Ok(())
+}
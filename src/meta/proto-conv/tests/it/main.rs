@@ -14,6 +14,8 @@
 
 #[macro_use]
 pub(crate) mod common;
+mod data_type;
 mod proto_conv;
+mod user_grant;
 mod user_proto_conv;
 mod user_stage;
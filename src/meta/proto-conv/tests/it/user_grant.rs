@@ -0,0 +1,80 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test GrantEntry
+
+use common_meta_types as mt;
+use common_meta_types::UserPrivilegeType;
+use enumflags2::make_bitflags;
+
+use crate::common;
+
+#[test]
+fn test_grant_entry_stage_latest() -> anyhow::Result<()> {
+    common::test_pb_from_to(
+        "grant_entry_stage",
+        mt::GrantEntry::new(
+            mt::GrantObject::Stage("s1".to_string()),
+            make_bitflags!(UserPrivilegeType::{Read | Write}),
+        ),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_grant_entry_udf_latest() -> anyhow::Result<()> {
+    common::test_pb_from_to(
+        "grant_entry_udf",
+        mt::GrantEntry::new(
+            mt::GrantObject::UDF("f1".to_string()),
+            make_bitflags!(UserPrivilegeType::{Drop}),
+        ),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_grant_entry_stage_v14() -> anyhow::Result<()> {
+    // Encoded data of version 14 of grant_entry_stage:
+    // It is generated with common::test_pb_from_to.
+    let grant_entry_stage_v14 = vec![
+        10, 12, 34, 4, 10, 2, 115, 49, 160, 6, 14, 168, 6, 1, 16, 128, 128, 3, 160, 6, 14, 168, 6,
+        1,
+    ];
+
+    let want = mt::GrantEntry::new(
+        mt::GrantObject::Stage("s1".to_string()),
+        make_bitflags!(UserPrivilegeType::{Read | Write}),
+    );
+
+    common::test_load_old(func_name!(), grant_entry_stage_v14.as_slice(), want)?;
+    Ok(())
+}
+
+#[test]
+fn test_grant_entry_udf_v14() -> anyhow::Result<()> {
+    // Encoded data of version 14 of grant_entry_udf:
+    // It is generated with common::test_pb_from_to.
+    let grant_entry_udf_v14 = vec![
+        10, 12, 42, 4, 10, 2, 102, 49, 160, 6, 14, 168, 6, 1, 16, 128, 1, 160, 6, 14, 168, 6, 1,
+    ];
+
+    let want = mt::GrantEntry::new(
+        mt::GrantObject::UDF("f1".to_string()),
+        make_bitflags!(UserPrivilegeType::{Drop}),
+    );
+
+    common::test_load_old(func_name!(), grant_entry_udf_v14.as_slice(), want)?;
+    Ok(())
+}
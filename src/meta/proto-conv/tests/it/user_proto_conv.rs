@@ -26,6 +26,7 @@ use common_proto_conv::FromToProto;
 use common_proto_conv::Incompatible;
 use common_proto_conv::VER;
 use common_protos::pb;
+use common_storage::StorageAzblobConfig;
 use common_storage::StorageFsConfig;
 use common_storage::StorageGcsConfig;
 use common_storage::StorageParams;
@@ -81,11 +82,18 @@ pub(crate) fn test_fs_stage_info() -> mt::UserStageInfo {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            quote: "'".to_string(),
+            escape: "\\".to_string(),
+            null_display: "NULL".to_string(),
+            allow_column_count_mismatch: true,
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
             size_limit: 1038,
             purge: true,
+            max_errors: 10,
+            max_file_size: 1048576,
+            single: true,
         },
         comment: "test".to_string(),
         ..Default::default()
@@ -113,11 +121,18 @@ pub(crate) fn test_s3_stage_info() -> mt::UserStageInfo {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            quote: "'".to_string(),
+            escape: "\\".to_string(),
+            null_display: "NULL".to_string(),
+            allow_column_count_mismatch: true,
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
             size_limit: 1038,
             purge: true,
+            max_errors: 10,
+            max_file_size: 1048576,
+            single: true,
         },
         comment: "test".to_string(),
         ..Default::default()
@@ -143,11 +158,56 @@ pub(crate) fn test_gcs_stage_info() -> mt::UserStageInfo {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            quote: "'".to_string(),
+            escape: "\\".to_string(),
+            null_display: "NULL".to_string(),
+            allow_column_count_mismatch: true,
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
             size_limit: 1038,
             purge: true,
+            max_errors: 10,
+            max_file_size: 1048576,
+            single: true,
+        },
+        comment: "test".to_string(),
+        ..Default::default()
+    }
+}
+
+// Version 12 added Azure Blob Storage as a stage backend, should be tested
+pub(crate) fn test_azblob_stage_info() -> mt::UserStageInfo {
+    mt::UserStageInfo {
+        stage_name: "azblob://my_container/data/files".to_string(),
+        stage_type: mt::StageType::External,
+        stage_params: mt::StageParams {
+            storage: StorageParams::Azblob(StorageAzblobConfig {
+                endpoint_url: "https://my_account.blob.core.windows.net".to_string(),
+                container: "my_container".to_string(),
+                root: "/data/files".to_string(),
+                account_name: "my_account".to_string(),
+                account_key: "my_account_key".to_string(),
+            }),
+        },
+        file_format_options: mt::FileFormatOptions {
+            format: mt::StageFileFormatType::Json,
+            skip_header: 1024,
+            field_delimiter: "|".to_string(),
+            record_delimiter: "//".to_string(),
+            compression: mt::StageFileCompression::Bz2,
+            quote: "'".to_string(),
+            escape: "\\".to_string(),
+            null_display: "NULL".to_string(),
+            allow_column_count_mismatch: true,
+        },
+        copy_options: mt::CopyOptions {
+            on_error: mt::OnErrorMode::SkipFileNum(666),
+            size_limit: 1038,
+            purge: true,
+            max_errors: 10,
+            max_file_size: 1048576,
+            single: true,
         },
         comment: "test".to_string(),
         ..Default::default()
@@ -344,6 +404,15 @@ fn test_build_user_pb_buf() -> anyhow::Result<()> {
         println!("gcs_stage_info: {:?}", buf);
     }
 
+    // Stage on Azure Blob Storage, supported in version >=12.
+    {
+        let azblob_stage_info = test_azblob_stage_info();
+        let p = azblob_stage_info.to_pb()?;
+        let mut buf = vec![];
+        common_protos::prost::Message::encode(&p, &mut buf)?;
+        println!("azblob_stage_info: {:?}", buf);
+    }
+
     Ok(())
 }
 
@@ -369,6 +438,89 @@ fn test_load_old_user() -> anyhow::Result<()> {
         assert_eq!(want, got);
     }
 
+    {
+        // UserOption::network_policy is added in version 15.
+        let user_info_v15: Vec<u8> = vec![
+            10, 9, 116, 101, 115, 116, 95, 117, 115, 101, 114, 18, 9, 108, 111, 99, 97, 108, 104,
+            111, 115, 116, 26, 25, 18, 17, 10, 13, 116, 101, 115, 116, 95, 112, 97, 115, 115, 119,
+            111, 114, 100, 16, 1, 160, 6, 15, 168, 6, 1, 34, 26, 10, 18, 10, 8, 10, 0, 160, 6, 15,
+            168, 6, 1, 16, 2, 160, 6, 15, 168, 6, 1, 160, 6, 15, 168, 6, 1, 42, 15, 8, 10, 16, 128,
+            80, 24, 128, 160, 1, 160, 6, 15, 168, 6, 1, 50, 20, 8, 1, 18, 5, 114, 111, 108, 101,
+            49, 26, 3, 110, 112, 49, 160, 6, 15, 168, 6, 1, 160, 6, 15, 168, 6, 1,
+        ];
+        let p: pb::UserInfo =
+            common_protos::prost::Message::decode(user_info_v15.as_slice()).map_err(print_err)?;
+        let got = mt::UserInfo::from_pb(p).map_err(print_err)?;
+        let mut want = test_user_info();
+        want.option.set_network_policy(Some("np1".to_string()));
+        assert_eq!(want, got);
+    }
+
+    {
+        // UserOption::workload_group is added in version 16.
+        let user_info_v16: Vec<u8> = vec![
+            10, 9, 116, 101, 115, 116, 95, 117, 115, 101, 114, 18, 9, 108, 111, 99, 97, 108, 104,
+            111, 115, 116, 26, 25, 18, 17, 10, 13, 116, 101, 115, 116, 95, 112, 97, 115, 115, 119,
+            111, 114, 100, 16, 1, 160, 6, 16, 168, 6, 1, 34, 26, 10, 18, 10, 8, 10, 0, 160, 6, 16,
+            168, 6, 1, 16, 2, 160, 6, 16, 168, 6, 1, 160, 6, 16, 168, 6, 1, 42, 15, 8, 10, 16, 128,
+            80, 24, 128, 160, 1, 160, 6, 16, 168, 6, 1, 50, 25, 8, 1, 18, 5, 114, 111, 108, 101,
+            49, 26, 3, 110, 112, 49, 34, 3, 101, 116, 108, 160, 6, 16, 168, 6, 1, 160, 6, 16, 168,
+            6, 1,
+        ];
+        let p: pb::UserInfo =
+            common_protos::prost::Message::decode(user_info_v16.as_slice()).map_err(print_err)?;
+        let got = mt::UserInfo::from_pb(p).map_err(print_err)?;
+        let mut want = test_user_info();
+        want.option.set_network_policy(Some("np1".to_string()));
+        want.option.set_workload_group(Some("etl".to_string()));
+        assert_eq!(want, got);
+    }
+
+    {
+        // UserOption::default_settings is added in version 18.
+        let user_info_v18: Vec<u8> = vec![
+            10, 9, 116, 101, 115, 116, 95, 117, 115, 101, 114, 18, 9, 108, 111, 99, 97, 108, 104,
+            111, 115, 116, 26, 25, 18, 17, 10, 13, 116, 101, 115, 116, 95, 112, 97, 115, 115, 119,
+            111, 114, 100, 16, 1, 160, 6, 18, 168, 6, 1, 34, 26, 10, 18, 10, 8, 10, 0, 160, 6, 18,
+            168, 6, 1, 16, 2, 160, 6, 18, 168, 6, 1, 160, 6, 18, 168, 6, 1, 42, 15, 8, 10, 16, 128,
+            80, 24, 128, 160, 1, 160, 6, 18, 168, 6, 1, 50, 43, 8, 1, 18, 5, 114, 111, 108, 101,
+            49, 26, 3, 110, 112, 49, 34, 3, 101, 116, 108, 42, 16, 10, 11, 109, 97, 120, 95, 116,
+            104, 114, 101, 97, 100, 115, 18, 1, 52, 160, 6, 18, 168, 6, 1, 160, 6, 18, 168, 6, 1,
+        ];
+        let p: pb::UserInfo =
+            common_protos::prost::Message::decode(user_info_v18.as_slice()).map_err(print_err)?;
+        let got = mt::UserInfo::from_pb(p).map_err(print_err)?;
+        let mut want = test_user_info();
+        want.option.set_network_policy(Some("np1".to_string()));
+        want.option.set_workload_group(Some("etl".to_string()));
+        want.option
+            .set_default_setting("max_threads".to_string(), Some("4".to_string()));
+        assert_eq!(want, got);
+    }
+
+    {
+        // AuthInfo::ldap is added in version 19.
+        let user_info_v19: Vec<u8> = vec![
+            10, 9, 116, 101, 115, 116, 95, 117, 115, 101, 114, 18, 9, 108, 111, 99, 97, 108, 104,
+            111, 115, 116, 26, 8, 34, 0, 160, 6, 19, 168, 6, 1, 34, 26, 10, 18, 10, 8, 10, 0, 160,
+            6, 19, 168, 6, 1, 16, 2, 160, 6, 19, 168, 6, 1, 160, 6, 19, 168, 6, 1, 42, 15, 8, 10,
+            16, 128, 80, 24, 128, 160, 1, 160, 6, 19, 168, 6, 1, 50, 43, 8, 1, 18, 5, 114, 111,
+            108, 101, 49, 26, 3, 110, 112, 49, 34, 3, 101, 116, 108, 42, 16, 10, 11, 109, 97, 120,
+            95, 116, 104, 114, 101, 97, 100, 115, 18, 1, 52, 160, 6, 19, 168, 6, 1, 160, 6, 19,
+            168, 6, 1,
+        ];
+        let p: pb::UserInfo =
+            common_protos::prost::Message::decode(user_info_v19.as_slice()).map_err(print_err)?;
+        let got = mt::UserInfo::from_pb(p).map_err(print_err)?;
+        let mut want = test_user_info();
+        want.auth_info = mt::AuthInfo::LDAP;
+        want.option.set_network_policy(Some("np1".to_string()));
+        want.option.set_workload_group(Some("etl".to_string()));
+        want.option
+            .set_default_setting("max_threads".to_string(), Some("4".to_string()));
+        assert_eq!(want, got);
+    }
+
     // UserInfo is loadable
     {
         let user_info_v1: Vec<u8> = vec![
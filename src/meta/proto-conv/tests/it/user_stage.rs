@@ -15,12 +15,15 @@
 //! Test UserStageInfo
 
 use common_meta_types as mt;
+use common_storage::StorageAzblobConfig;
 use common_storage::StorageFsConfig;
 use common_storage::StorageGcsConfig;
+use common_storage::StorageHdfsConfig;
 use common_storage::StorageParams;
 use common_storage::StorageS3Config;
 
 use crate::common;
+use crate::user_proto_conv::test_azblob_stage_info;
 use crate::user_proto_conv::test_fs_stage_info;
 use crate::user_proto_conv::test_gcs_stage_info;
 use crate::user_proto_conv::test_s3_stage_info;
@@ -43,6 +46,207 @@ fn test_user_stage_gcs_latest() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_user_stage_azblob_latest() -> anyhow::Result<()> {
+    common::test_pb_from_to("user_stage_azblob", test_azblob_stage_info())?;
+    Ok(())
+}
+
+#[test]
+fn test_hdfs_storage_config_latest() -> anyhow::Result<()> {
+    common::test_pb_from_to("hdfs_storage_config", StorageHdfsConfig {
+        name_node: "hdfs://127.0.0.1:9000".to_string(),
+        root: "/data/files".to_string(),
+    })?;
+    Ok(())
+}
+
+#[test]
+fn test_hdfs_storage_config_v13() -> anyhow::Result<()> {
+    // Encoded data of version 13 of hdfs_storage_config:
+    // It is generated with common::test_pb_from_to.
+    let hdfs_storage_config_v13 = vec![
+        10, 21, 104, 100, 102, 115, 58, 47, 47, 49, 50, 55, 46, 48, 46, 48, 46, 49, 58, 57, 48, 48,
+        48, 18, 11, 47, 100, 97, 116, 97, 47, 102, 105, 108, 101, 115, 160, 6, 13, 168, 6, 1,
+    ];
+
+    let want = StorageHdfsConfig {
+        name_node: "hdfs://127.0.0.1:9000".to_string(),
+        root: "/data/files".to_string(),
+    };
+
+    common::test_load_old(func_name!(), hdfs_storage_config_v13.as_slice(), want)?;
+    Ok(())
+}
+
+#[test]
+fn test_user_stage_s3_v20() -> anyhow::Result<()> {
+    // Encoded data of version 20 of user_stage_s3:
+    // It is generated with common::test_pb_from_to.
+    let user_stage_s3_v20 = vec![
+        10, 24, 115, 51, 58, 47, 47, 109, 121, 98, 117, 99, 107, 101, 116, 47, 100, 97, 116, 97,
+        47, 102, 105, 108, 101, 115, 16, 1, 26, 100, 10, 98, 10, 96, 18, 24, 104, 116, 116, 112,
+        115, 58, 47, 47, 115, 51, 46, 97, 109, 97, 122, 111, 110, 97, 119, 115, 46, 99, 111, 109,
+        26, 9, 109, 121, 95, 107, 101, 121, 95, 105, 100, 34, 13, 109, 121, 95, 115, 101, 99, 114,
+        101, 116, 95, 107, 101, 121, 42, 8, 109, 121, 98, 117, 99, 107, 101, 116, 50, 11, 47, 100,
+        97, 116, 97, 47, 102, 105, 108, 101, 115, 58, 13, 109, 121, 95, 109, 97, 115, 116, 101,
+        114, 95, 107, 101, 121, 160, 6, 20, 168, 6, 1, 34, 34, 8, 1, 16, 128, 8, 26, 1, 124, 34, 2,
+        47, 47, 40, 2, 50, 1, 39, 58, 1, 92, 66, 4, 78, 85, 76, 76, 72, 1, 160, 6, 20, 168, 6, 1,
+        42, 18, 10, 3, 32, 154, 5, 16, 142, 8, 24, 1, 32, 10, 40, 128, 128, 64, 48, 1, 50, 4, 116,
+        101, 115, 116, 160, 6, 20, 168, 6, 1,
+    ];
+
+    let want = mt::UserStageInfo {
+        stage_name: "s3://mybucket/data/files".to_string(),
+        stage_type: mt::StageType::External,
+        stage_params: mt::StageParams {
+            storage: StorageParams::S3(StorageS3Config {
+                bucket: "mybucket".to_string(),
+                root: "/data/files".to_string(),
+                access_key_id: "my_key_id".to_string(),
+                secret_access_key: "my_secret_key".to_string(),
+                master_key: "my_master_key".to_string(),
+                ..Default::default()
+            }),
+        },
+        file_format_options: mt::FileFormatOptions {
+            format: mt::StageFileFormatType::Json,
+            skip_header: 1024,
+            field_delimiter: "|".to_string(),
+            record_delimiter: "//".to_string(),
+            compression: mt::StageFileCompression::Bz2,
+            quote: "'".to_string(),
+            escape: "\\".to_string(),
+            null_display: "NULL".to_string(),
+            allow_column_count_mismatch: true,
+        },
+        copy_options: mt::CopyOptions {
+            on_error: mt::OnErrorMode::SkipFileNum(666),
+            size_limit: 1038,
+            purge: true,
+            max_errors: 10,
+            max_file_size: 1048576,
+            single: true,
+        },
+        comment: "test".to_string(),
+        ..Default::default()
+    };
+
+    common::test_load_old(func_name!(), user_stage_s3_v20.as_slice(), want)?;
+    Ok(())
+}
+
+#[test]
+fn test_user_stage_azblob_v12() -> anyhow::Result<()> {
+    // Encoded data of version 12 of user_stage_azblob:
+    // It is generated with common::test_pb_from_to.
+    let user_stage_azblob_v12 = vec![
+        10, 32, 97, 122, 98, 108, 111, 98, 58, 47, 47, 109, 121, 95, 99, 111, 110, 116, 97, 105,
+        110, 101, 114, 47, 100, 97, 116, 97, 47, 102, 105, 108, 101, 115, 16, 1, 26, 107, 10, 105,
+        34, 103, 10, 40, 104, 116, 116, 112, 115, 58, 47, 47, 109, 121, 95, 97, 99, 99, 111, 117,
+        110, 116, 46, 98, 108, 111, 98, 46, 99, 111, 114, 101, 46, 119, 105, 110, 100, 111, 119,
+        115, 46, 110, 101, 116, 18, 12, 109, 121, 95, 99, 111, 110, 116, 97, 105, 110, 101, 114,
+        26, 11, 47, 100, 97, 116, 97, 47, 102, 105, 108, 101, 115, 34, 10, 109, 121, 95, 97, 99,
+        99, 111, 117, 110, 116, 42, 14, 109, 121, 95, 97, 99, 99, 111, 117, 110, 116, 95, 107, 101,
+        121, 160, 6, 12, 168, 6, 1, 34, 34, 8, 1, 16, 128, 8, 26, 1, 124, 34, 2, 47, 47, 40, 2, 50,
+        1, 39, 58, 1, 92, 66, 4, 78, 85, 76, 76, 72, 1, 160, 6, 12, 168, 6, 1, 42, 12, 10, 3, 32,
+        154, 5, 16, 142, 8, 24, 1, 32, 10, 50, 4, 116, 101, 115, 116, 160, 6, 12, 168, 6, 1,
+    ];
+
+    let want = mt::UserStageInfo {
+        stage_name: "azblob://my_container/data/files".to_string(),
+        stage_type: mt::StageType::External,
+        stage_params: mt::StageParams {
+            storage: StorageParams::Azblob(StorageAzblobConfig {
+                endpoint_url: "https://my_account.blob.core.windows.net".to_string(),
+                container: "my_container".to_string(),
+                root: "/data/files".to_string(),
+                account_name: "my_account".to_string(),
+                account_key: "my_account_key".to_string(),
+            }),
+        },
+        file_format_options: mt::FileFormatOptions {
+            format: mt::StageFileFormatType::Json,
+            skip_header: 1024,
+            field_delimiter: "|".to_string(),
+            record_delimiter: "//".to_string(),
+            compression: mt::StageFileCompression::Bz2,
+            quote: "'".to_string(),
+            escape: "\\".to_string(),
+            null_display: "NULL".to_string(),
+            allow_column_count_mismatch: true,
+        },
+        copy_options: mt::CopyOptions {
+            on_error: mt::OnErrorMode::SkipFileNum(666),
+            size_limit: 1038,
+            purge: true,
+            max_errors: 10,
+            ..Default::default()
+        },
+        comment: "test".to_string(),
+        ..Default::default()
+    };
+
+    common::test_load_old(func_name!(), user_stage_azblob_v12.as_slice(), want)?;
+    Ok(())
+}
+
+#[test]
+fn test_user_stage_s3_v11() -> anyhow::Result<()> {
+    // Encoded data of version 11 of user_stage_s3:
+    // It is generated with common::test_pb_from_to.
+    let user_stage_s3_v11 = vec![
+        10, 24, 115, 51, 58, 47, 47, 109, 121, 98, 117, 99, 107, 101, 116, 47, 100, 97, 116, 97,
+        47, 102, 105, 108, 101, 115, 16, 1, 26, 100, 10, 98, 10, 96, 18, 24, 104, 116, 116, 112,
+        115, 58, 47, 47, 115, 51, 46, 97, 109, 97, 122, 111, 110, 97, 119, 115, 46, 99, 111, 109,
+        26, 9, 109, 121, 95, 107, 101, 121, 95, 105, 100, 34, 13, 109, 121, 95, 115, 101, 99, 114,
+        101, 116, 95, 107, 101, 121, 42, 8, 109, 121, 98, 117, 99, 107, 101, 116, 50, 11, 47, 100,
+        97, 116, 97, 47, 102, 105, 108, 101, 115, 58, 13, 109, 121, 95, 109, 97, 115, 116, 101,
+        114, 95, 107, 101, 121, 160, 6, 11, 168, 6, 1, 34, 34, 8, 1, 16, 128, 8, 26, 1, 124, 34, 2,
+        47, 47, 40, 2, 50, 1, 39, 58, 1, 92, 66, 4, 78, 85, 76, 76, 72, 1, 160, 6, 11, 168, 6, 1,
+        42, 12, 10, 3, 32, 154, 5, 16, 142, 8, 24, 1, 32, 10, 50, 4, 116, 101, 115, 116, 160, 6,
+        11, 168, 6, 1,
+    ];
+
+    let want = mt::UserStageInfo {
+        stage_name: "s3://mybucket/data/files".to_string(),
+        stage_type: mt::StageType::External,
+        stage_params: mt::StageParams {
+            storage: StorageParams::S3(StorageS3Config {
+                bucket: "mybucket".to_string(),
+                root: "/data/files".to_string(),
+                access_key_id: "my_key_id".to_string(),
+                secret_access_key: "my_secret_key".to_string(),
+                master_key: "my_master_key".to_string(),
+                ..Default::default()
+            }),
+        },
+        file_format_options: mt::FileFormatOptions {
+            format: mt::StageFileFormatType::Json,
+            skip_header: 1024,
+            field_delimiter: "|".to_string(),
+            record_delimiter: "//".to_string(),
+            compression: mt::StageFileCompression::Bz2,
+            quote: "'".to_string(),
+            escape: "\\".to_string(),
+            null_display: "NULL".to_string(),
+            allow_column_count_mismatch: true,
+        },
+        copy_options: mt::CopyOptions {
+            on_error: mt::OnErrorMode::SkipFileNum(666),
+            size_limit: 1038,
+            purge: true,
+            max_errors: 10,
+            ..Default::default()
+        },
+        comment: "test".to_string(),
+        ..Default::default()
+    };
+
+    common::test_load_old(func_name!(), user_stage_s3_v11.as_slice(), want)?;
+    Ok(())
+}
+
 #[test]
 fn test_user_stage_s3_v9() -> anyhow::Result<()> {
     // Encoded data of version 9 of user_stage_s3:
@@ -78,11 +282,13 @@ fn test_user_stage_s3_v9() -> anyhow::Result<()> {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            ..Default::default()
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
             size_limit: 1038,
             purge: true,
+            ..Default::default()
         },
         comment: "test".to_string(),
         ..Default::default()
@@ -118,11 +324,13 @@ fn test_user_stage_fs_v6() -> anyhow::Result<()> {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            ..Default::default()
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
             size_limit: 1038,
             purge: true,
+            ..Default::default()
         },
         comment: "test".to_string(),
         ..Default::default()
@@ -168,11 +376,13 @@ fn test_user_stage_s3_v6() -> anyhow::Result<()> {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            ..Default::default()
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
             size_limit: 1038,
             purge: true,
+            ..Default::default()
         },
         comment: "test".to_string(),
         ..Default::default()
@@ -214,11 +424,13 @@ fn test_user_stage_gcs_v6() -> anyhow::Result<()> {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            ..Default::default()
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
             size_limit: 1038,
             purge: true,
+            ..Default::default()
         },
         comment: "test".to_string(),
         ..Default::default()
@@ -253,6 +465,7 @@ fn test_user_stage_fs_v4() -> anyhow::Result<()> {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            ..Default::default()
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
@@ -303,6 +516,7 @@ fn test_user_stage_s3_v4() -> anyhow::Result<()> {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            ..Default::default()
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
@@ -348,6 +562,7 @@ fn test_user_stage_gcs_v4() -> anyhow::Result<()> {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            ..Default::default()
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
@@ -395,6 +610,7 @@ fn test_user_stage_s3_v1() -> anyhow::Result<()> {
             field_delimiter: "|".to_string(),
             record_delimiter: "//".to_string(),
             compression: mt::StageFileCompression::Bz2,
+            ..Default::default()
         },
         copy_options: mt::CopyOptions {
             on_error: mt::OnErrorMode::SkipFileNum(666),
@@ -16,6 +16,21 @@ use common_meta_types::AppliedState;
 use serde::Deserialize;
 use serde::Serialize;
 
+/// The max number of recently applied requests remembered for every client,
+/// including the last one.
+///
+/// A client may have several requests in flight and retry any of them,
+/// e.g., after a leader change. A retried request whose serial number is still in the window
+/// is answered with the stored response instead of being applied again.
+/// A request that falls out of the window is applied again if retried.
+pub const CLIENT_RECENT_RESP_WINDOW: usize = 64;
+
+/// The time in milliseconds after which the responses of a client that sends no request are removed.
+///
+/// Every client instance has its own id, the responses of the ones that are gone
+/// are removed by the expired cleaner of the leader.
+pub const CLIENT_LAST_RESP_TTL_MS: u64 = 3600 * 1000;
+
 /// Client last response that is stored in SledTree
 /// raft state: A mapping of client serial IDs to their state info:
 /// (serial, RaftResponse)
@@ -24,4 +39,46 @@ use serde::Serialize;
 pub struct ClientLastRespValue {
     pub req_serial_num: u64,
     pub res: AppliedState,
+
+    /// Responses of the recently applied requests, in apply order, excluding the last one.
+    ///
+    /// Older data does not have this field, in which case only the last response is remembered.
+    #[serde(default)]
+    pub recent: Vec<(u64, AppliedState)>,
+
+    /// The time in milliseconds of the log of the last request.
+    ///
+    /// Older data does not have this field, in which case the client is treated as not seen for long.
+    #[serde(default)]
+    pub last_seen_ms: u64,
+}
+
+impl ClientLastRespValue {
+    /// Find the response of a previously applied request by its serial number.
+    pub fn get(&self, serial: u64) -> Option<&AppliedState> {
+        if self.req_serial_num == serial {
+            return Some(&self.res);
+        }
+
+        self.recent
+            .iter()
+            .rev()
+            .find(|(s, _)| *s == serial)
+            .map(|(_, res)| res)
+    }
+
+    /// Record the response of a newly applied request.
+    ///
+    /// The previous last response is moved into the window and the oldest ones are evicted.
+    pub fn push(&mut self, serial: u64, res: AppliedState) {
+        let prev_serial = std::mem::replace(&mut self.req_serial_num, serial);
+        let prev_res = std::mem::replace(&mut self.res, res);
+
+        self.recent.push((prev_serial, prev_res));
+
+        if self.recent.len() >= CLIENT_RECENT_RESP_WINDOW {
+            let n = self.recent.len() + 1 - CLIENT_RECENT_RESP_WINDOW;
+            self.recent.drain(..n);
+        }
+    }
 }
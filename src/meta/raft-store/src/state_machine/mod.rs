@@ -16,6 +16,7 @@ pub use client_last_resp::ClientLastRespValue;
 pub use log_meta::LogMetaKey;
 pub use log_meta::LogMetaValue;
 pub use sm::SerializableSnapshot;
+pub use sm::SnapshotBuilder;
 pub use sm::SnapshotKeyValue;
 pub use sm::StateMachine;
 pub use sm::StateMachineSubscriber;
@@ -15,6 +15,7 @@
 use std::collections::BTreeMap;
 use std::convert::TryInto;
 use std::fmt::Debug;
+use std::ops::Bound;
 use std::time::Duration;
 use std::time::Instant;
 use std::time::SystemTime;
@@ -30,6 +31,7 @@ use common_meta_sled_store::SledKeySpace;
 use common_meta_sled_store::SledTree;
 use common_meta_sled_store::Store;
 use common_meta_sled_store::TransactionSledTree;
+use common_meta_types::anyerror::AnyError;
 use common_meta_types::error_context::WithContext;
 use common_meta_types::txn_condition;
 use common_meta_types::txn_op;
@@ -80,6 +82,7 @@ use crate::sled_key_spaces::GenericKV;
 use crate::sled_key_spaces::Nodes;
 use crate::sled_key_spaces::Sequences;
 use crate::sled_key_spaces::StateMachineMeta;
+use crate::state_machine::client_last_resp::CLIENT_LAST_RESP_TTL_MS;
 use crate::state_machine::ClientLastRespValue;
 use crate::state_machine::StateMachineMetaKey;
 use crate::state_machine::StateMachineMetaKey::Initialized;
@@ -117,6 +120,83 @@ pub struct StateMachine {
 pub type SnapshotKeyValue = Vec<Vec<u8>>;
 type DeleteByPrefixKeyMap = BTreeMap<TxnDeleteByPrefixRequest, Vec<(String, SeqV)>>;
 
+/// Builds a point-in-time snapshot of a state machine without blocking writes to it.
+///
+/// Key values are copied in batches from the live sled tree.
+/// Keys written after the snapshot started are restored to their previous values with the pre-images
+/// recorded by the sled tree, thus the result is consistent with `last_applied`.
+#[derive(Debug)]
+pub struct SnapshotBuilder {
+    sm_tree: SledTree,
+    last_applied: Option<LogId>,
+    snapshot_id: String,
+
+    /// The key to start the next batch from. `None` before the first batch.
+    next_key: Option<Bound<Vec<u8>>>,
+
+    kvs: BTreeMap<Vec<u8>, Vec<u8>>,
+
+    /// Whether the pre-image capture started by this builder has not yet been ended.
+    capturing: bool,
+}
+
+impl SnapshotBuilder {
+    /// Copy at most `n` key values from the state machine.
+    ///
+    /// Returns the number of key values copied. 0 means all key values have been copied.
+    pub fn copy_batch(&mut self, n: usize) -> Result<usize, MetaStorageError> {
+        let start = self.next_key.clone().unwrap_or(Bound::Unbounded);
+        let view = self.sm_tree.tree.range((start, Bound::Unbounded));
+
+        let mut copied = 0;
+        for rkv in view.take(n) {
+            let (k, v) = rkv.context(|| "taking snapshot")?;
+            self.next_key = Some(Bound::Excluded(k.to_vec()));
+            self.kvs.insert(k.to_vec(), v.to_vec());
+            copied += 1;
+        }
+
+        Ok(copied)
+    }
+
+    /// Number of key values copied so far.
+    pub fn copied(&self) -> usize {
+        self.kvs.len()
+    }
+
+    /// Stop copying and return the snapshot, the last applied log id and the snapshot id.
+    pub fn finish(mut self) -> (SerializableSnapshot, Option<LogId>, String) {
+        let pre_images = self.sm_tree.end_capture_pre_images();
+        self.capturing = false;
+
+        let mut kvs = std::mem::take(&mut self.kvs);
+        for (k, prev) in pre_images {
+            match prev {
+                Some(v) => kvs.insert(k.to_vec(), v.to_vec()),
+                None => kvs.remove(k.as_ref()),
+            };
+        }
+
+        let snap = SerializableSnapshot {
+            kvs: kvs.into_iter().map(|(k, v)| vec![k, v]).collect(),
+        };
+
+        (
+            snap,
+            self.last_applied,
+            std::mem::take(&mut self.snapshot_id),
+        )
+    }
+}
+
+impl Drop for SnapshotBuilder {
+    fn drop(&mut self) {
+        if self.capturing {
+            self.sm_tree.end_capture_pre_images();
+        }
+    }
+}
+
 /// Snapshot data for serialization and for transport.
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct SerializableSnapshot {
@@ -196,6 +276,17 @@ impl StateMachine {
     pub fn build_snapshot(
         &self,
     ) -> Result<(SerializableSnapshot, Option<LogId>, String), MetaStorageError> {
+        let mut builder = self.begin_snapshot()?;
+        while builder.copy_batch(usize::MAX)? > 0 {}
+        Ok(builder.finish())
+    }
+
+    /// Start building a snapshot of the current state.
+    ///
+    /// The returned builder copies key values in batches, while the state machine keeps applying logs.
+    /// The caller must ensure no log is being applied when calling this method,
+    /// so that the snapshot reflects exactly the state at the returned `last_applied`.
+    pub fn begin_snapshot(&self) -> Result<SnapshotBuilder, MetaStorageError> {
         let last_applied = self.get_last_applied()?;
 
         let snapshot_idx = SystemTime::now()
@@ -209,16 +300,20 @@ impl StateMachine {
             format!("--{}", snapshot_idx)
         };
 
-        let view = self.sm_tree.tree.iter();
-
-        let mut kvs = Vec::new();
-        for rkv in view {
-            let (k, v) = rkv.context(|| "taking snapshot")?;
-            kvs.push(vec![k.to_vec(), v.to_vec()]);
+        if !self.sm_tree.begin_capture_pre_images() {
+            return Err(MetaStorageError::SnapshotError(AnyError::error(
+                "another snapshot is being built",
+            )));
         }
-        let snap = SerializableSnapshot { kvs };
 
-        Ok((snap, last_applied, snapshot_id))
+        Ok(SnapshotBuilder {
+            sm_tree: self.sm_tree.clone(),
+            last_applied,
+            snapshot_id,
+            next_key: None,
+            kvs: BTreeMap::new(),
+            capturing: true,
+        })
     }
 
     fn scan_prefix_if_needed(
@@ -283,10 +378,10 @@ impl StateMachine {
                 EntryPayload::Normal(ref data) => {
                     info!("apply: {}", data);
                     if let Some(ref txid) = data.txid {
-                        let (serial, resp) =
-                            self.txn_get_client_last_resp(&txid.client, &txn_tree)?;
-                        if serial == txid.serial {
-                            return Ok(Some(resp));
+                        let last_resp = self.txn_get_client_last_resp(&txid.client, &txn_tree)?;
+                        if let Some(resp) = last_resp.get(txid.serial) {
+                            info!("apply: duplicated request: {}, return the previous resp", txid);
+                            return Ok(Some(resp.clone()));
                         }
                     }
 
@@ -316,6 +411,7 @@ impl StateMachine {
                         self.txn_client_last_resp_update(
                             &txid.client,
                             (txid.serial, applied_state.clone()),
+                            log_time_ms,
                             &txn_tree,
                         )?;
                     }
@@ -397,6 +493,30 @@ impl StateMachine {
         Ok((prev, None).into())
     }
 
+    fn apply_remove_client_last_resps_cmd(
+        &self,
+        clients: &[(String, u64)],
+        txn_tree: &TransactionSledTree,
+    ) -> MetaStorageResult<AppliedState> {
+        let txn_ks = txn_tree.key_space::<ClientLastResps>();
+
+        let mut removed = 0;
+        for (client, serial) in clients {
+            if let Some(prev) = txn_ks.get(client)? {
+                if prev.req_serial_num == *serial {
+                    txn_ks.remove(client)?;
+                    removed += 1;
+                }
+            }
+        }
+        info!(
+            "applied RemoveClientLastResps: {} of {} clients removed",
+            removed,
+            clients.len()
+        );
+        Ok(AppliedState::None)
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     fn apply_update_kv_cmd(
         &self,
@@ -785,6 +905,10 @@ impl StateMachine {
             }
 
             Cmd::Transaction(txn) => self.apply_txn_cmd(txn, txn_tree, kv_pairs, log_time_ms),
+
+            Cmd::RemoveClientLastResps { ref clients } => {
+                self.apply_remove_client_last_resps_cmd(clients, txn_tree)
+            }
         };
 
         let elapsed = now.elapsed().as_micros();
@@ -849,13 +973,24 @@ impl StateMachine {
         &self,
         key: &str,
         value: (u64, AppliedState),
+        log_time_ms: u64,
         txn_tree: &TransactionSledTree,
     ) -> MetaStorageResult<AppliedState> {
-        let v = ClientLastRespValue {
-            req_serial_num: value.0,
-            res: value.1.clone(),
-        };
         let txn_ks = txn_tree.key_space::<ClientLastResps>();
+
+        let mut v = match txn_ks.get(&key.to_string())? {
+            None => ClientLastRespValue {
+                req_serial_num: value.0,
+                res: value.1.clone(),
+                recent: vec![],
+                last_seen_ms: 0,
+            },
+            Some(mut prev) => {
+                prev.push(value.0, value.1.clone());
+                prev
+            }
+        };
+        v.last_seen_ms = log_time_ms;
         txn_ks.insert(&key.to_string(), &v)?;
 
         Ok(value.1)
@@ -896,18 +1031,26 @@ impl StateMachine {
         Ok(Some((0, AppliedState::None)))
     }
 
+    /// Get the recently applied responses of a client.
+    ///
+    /// An empty value is returned if the client has not yet sent any request with a txid.
     pub fn txn_get_client_last_resp(
         &self,
         key: &str,
         txn_tree: &TransactionSledTree,
-    ) -> MetaStorageResult<(u64, AppliedState)> {
+    ) -> MetaStorageResult<ClientLastRespValue> {
         let client_last_resps = txn_tree.key_space::<ClientLastResps>();
         let v = client_last_resps.get(&key.to_string())?;
 
         if let Some(resp) = v {
-            return Ok((resp.req_serial_num, resp.res));
+            return Ok(resp);
         }
-        Ok((0, AppliedState::None))
+        Ok(ClientLastRespValue {
+            req_serial_num: 0,
+            res: AppliedState::None,
+            recent: vec![],
+            last_seen_ms: 0,
+        })
     }
 
     #[allow(dead_code)]
@@ -932,6 +1075,110 @@ impl StateMachine {
         }
     }
 
+    /// Scan at most `limit` keys in GenericKV after `start_after`,
+    /// and return the keys with their seq that are expired at `now_ms`.
+    ///
+    /// It also returns the last scanned key to resume the scan from,
+    /// or `None` if the scan reaches the end of GenericKV.
+    #[allow(clippy::type_complexity)]
+    pub fn list_expired_keys(
+        &self,
+        now_ms: u64,
+        start_after: Option<String>,
+        limit: usize,
+    ) -> MetaStorageResult<(Vec<(String, u64)>, Option<String>)> {
+        let range = match start_after {
+            Some(key) => (Bound::Excluded(key), Bound::Unbounded),
+            None => (Bound::Unbounded, Bound::Unbounded),
+        };
+
+        let mut expired = vec![];
+        let mut scanned = 0;
+        let mut last_key = None;
+
+        for item in self.kvs().range(range)?.take(limit) {
+            let (key, seq_v) = item?;
+
+            if seq_v.get_expire_at() < now_ms {
+                expired.push((key.clone(), seq_v.seq));
+            }
+            scanned += 1;
+            last_key = Some(key);
+        }
+
+        let next = if scanned < limit { None } else { last_key };
+        Ok((expired, next))
+    }
+
+    /// Scan at most `limit` clients after `start_after`,
+    /// and return the clients with the serial number of their last request that are not seen
+    /// for `CLIENT_LAST_RESP_TTL_MS` at `now_ms`.
+    ///
+    /// It also returns the last scanned client to resume the scan from,
+    /// or `None` if the scan reaches the end of the clients.
+    #[allow(clippy::type_complexity)]
+    pub fn list_expired_clients(
+        &self,
+        now_ms: u64,
+        start_after: Option<String>,
+        limit: usize,
+    ) -> MetaStorageResult<(Vec<(String, u64)>, Option<String>)> {
+        let range = match start_after {
+            Some(client) => (Bound::Excluded(client), Bound::Unbounded),
+            None => (Bound::Unbounded, Bound::Unbounded),
+        };
+
+        let mut expired = vec![];
+        let mut scanned = 0;
+        let mut last_client = None;
+
+        for item in self.client_last_resps().range(range)?.take(limit) {
+            let (client, resp) = item?;
+
+            if resp.last_seen_ms.saturating_add(CLIENT_LAST_RESP_TTL_MS) < now_ms {
+                expired.push((client.clone(), resp.req_serial_num));
+            }
+            scanned += 1;
+            last_client = Some(client);
+        }
+
+        let next = if scanned < limit { None } else { last_client };
+        Ok((expired, next))
+    }
+
+    /// Build a transaction that deletes the expired keys listed by `list_expired_keys()`.
+    ///
+    /// Every key is deleted only if its seq is unchanged,
+    /// thus a key refreshed after it is listed fails the transaction and is kept.
+    /// Such a transaction is applied by any version of the state machine,
+    /// unlike a new `Cmd` that is unknown to the nodes not upgraded yet.
+    pub fn delete_expired_txn(expired: &[(String, u64)]) -> TxnRequest {
+        let condition = expired
+            .iter()
+            .map(|(key, seq)| TxnCondition {
+                key: key.clone(),
+                expected: ConditionResult::Eq as i32,
+                target: Some(txn_condition::Target::Seq(*seq)),
+            })
+            .collect();
+
+        let if_then = expired
+            .iter()
+            .map(|(key, _)| TxnOp {
+                request: Some(txn_op::Request::Delete(TxnDeleteRequest {
+                    key: key.clone(),
+                    prev_value: false,
+                })),
+            })
+            .collect();
+
+        TxnRequest {
+            condition,
+            if_then,
+            else_then: vec![],
+        }
+    }
+
     pub fn unexpired_opt<V: Debug>(
         seq_value: Option<SeqV<V>>,
         log_time_ms: u64,
@@ -116,6 +116,18 @@ pub fn cases_incr_seq() -> Vec<(&'static str, Option<RaftTxId>, &'static str, u6
             1,
         ),
         ("no txid, no de-dup", None, "k2", 2),
+        (
+            "dup: an earlier serial is still remembered, got its result",
+            Some(RaftTxId::new("foo", 1)),
+            "k1",
+            1,
+        ),
+        (
+            "new serial after dup",
+            Some(RaftTxId::new("foo", 3)),
+            "k1",
+            3,
+        ),
     ]
 }
 
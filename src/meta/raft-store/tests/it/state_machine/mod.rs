@@ -17,6 +17,8 @@ use std::time::UNIX_EPOCH;
 
 use common_base::base::tokio;
 use common_meta_api::KVApi;
+use common_meta_raft_store::state_machine::client_last_resp::CLIENT_LAST_RESP_TTL_MS;
+use common_meta_raft_store::state_machine::client_last_resp::CLIENT_RECENT_RESP_WINDOW;
 use common_meta_raft_store::state_machine::testing::pretty_snapshot;
 use common_meta_raft_store::state_machine::testing::snapshot_logs;
 use common_meta_raft_store::state_machine::StateMachine;
@@ -28,6 +30,7 @@ use common_meta_types::KVMeta;
 use common_meta_types::LogEntry;
 use common_meta_types::MatchSeq;
 use common_meta_types::Operation;
+use common_meta_types::RaftTxId;
 use common_meta_types::SeqV;
 use common_meta_types::UpsertKV;
 use common_meta_types::With;
@@ -115,6 +118,110 @@ async fn test_state_machine_apply_incr_seq() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_dup_window() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    let incr = |serial: u64| Entry {
+        log_id: LogId { term: 0, index: 5 },
+        payload: EntryPayload::Normal(LogEntry {
+            txid: Some(RaftTxId::new("foo", serial)),
+            time_ms: None,
+            cmd: Cmd::IncrSeq {
+                key: "k1".to_string(),
+            },
+        }),
+    };
+
+    let n = CLIENT_RECENT_RESP_WINDOW as u64 + 1;
+    for serial in 1..=n {
+        let resp = sm.apply(&incr(serial)).await?;
+        assert_eq!(AppliedState::Seq { seq: serial }, resp);
+    }
+
+    info!("--- a serial in the window is a dup");
+    {
+        let resp = sm.apply(&incr(2)).await?;
+        assert_eq!(AppliedState::Seq { seq: 2 }, resp);
+    }
+
+    info!("--- a serial out of the window is forgotten");
+    {
+        let resp = sm.apply(&incr(1)).await?;
+        assert_eq!(AppliedState::Seq { seq: n + 1 }, resp);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_remove_expired_clients() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    let now_ms = 1_000_000_000_000;
+
+    let incr = |client: &str, serial: u64, time_ms: u64| Entry {
+        log_id: LogId { term: 0, index: 5 },
+        payload: EntryPayload::Normal(LogEntry {
+            txid: Some(RaftTxId::new(client, serial)),
+            time_ms: Some(time_ms),
+            cmd: Cmd::IncrSeq {
+                key: "k1".to_string(),
+            },
+        }),
+    };
+
+    let remove = |clients: &[(String, u64)]| {
+        let cmd = Cmd::RemoveClientLastResps {
+            clients: clients.to_vec(),
+        };
+        sm.sm_tree
+            .txn(true, |t| Ok(sm.apply_cmd(&cmd, &t, None, now_ms).unwrap()))
+    };
+
+    sm.apply(&incr("gone", 1, now_ms - CLIENT_LAST_RESP_TTL_MS - 1))
+        .await?;
+    sm.apply(&incr("alive", 1, now_ms - CLIENT_LAST_RESP_TTL_MS))
+        .await?;
+
+    info!("--- list expired clients");
+    let expired = {
+        let (expired, next) = sm.list_expired_clients(now_ms, None, 10)?;
+        assert_eq!(vec![("gone".to_string(), 1)], expired);
+        assert_eq!(None, next);
+        expired
+    };
+
+    info!("--- a client that sent another request is not removed");
+    {
+        remove(&[("gone".to_string(), 0)])?;
+        assert!(sm.client_last_resps().get(&"gone".to_string())?.is_some());
+    }
+
+    info!("--- expired clients are removed");
+    {
+        remove(&expired)?;
+        assert!(sm.client_last_resps().get(&"gone".to_string())?.is_none());
+        assert!(sm.client_last_resps().get(&"alive".to_string())?.is_some());
+    }
+
+    info!("--- a request of a removed client is applied again");
+    {
+        let resp = sm.apply(&incr("gone", 1, now_ms)).await?;
+        assert_eq!(AppliedState::Seq { seq: 3 }, resp);
+    }
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_state_machine_apply_non_dup_generic_kv_upsert_get() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_raft_store_ut!();
@@ -359,6 +466,96 @@ async fn test_state_machine_apply_non_dup_generic_kv_value_meta() -> anyhow::Res
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_apply_delete_expired() -> anyhow::Result<()> {
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 1).await?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let upsert = |key: &str, expire_at: Option<u64>| {
+        Cmd::UpsertKV(UpsertKV {
+            key: key.to_string(),
+            seq: MatchSeq::Any,
+            value: Operation::Update(b"v".to_vec()),
+            value_meta: expire_at.map(|x| KVMeta { expire_at: Some(x) }),
+        })
+    };
+
+    sm.sm_tree.txn(true, |t| {
+        sm.apply_cmd(&upsert("expired", Some(now - 10)), &t, None, 0)
+            .unwrap();
+        sm.apply_cmd(&upsert("alive", Some(now + 1000)), &t, None, 0)
+            .unwrap();
+        Ok(sm.apply_cmd(&upsert("forever", None), &t, None, 0).unwrap())
+    })?;
+
+    info!("--- list expired keys");
+    let expired = {
+        let (expired, next) = sm.list_expired_keys(now * 1000, None, 10)?;
+        assert_eq!(vec!["expired".to_string()], keys_of(&expired));
+        assert_eq!(None, next);
+        expired
+    };
+
+    info!("--- list expired keys in bounded scans");
+    {
+        let (expired, next) = sm.list_expired_keys(now * 1000, None, 2)?;
+        assert_eq!(vec!["expired".to_string()], keys_of(&expired));
+        assert_eq!(Some("expired".to_string()), next);
+
+        let (expired, next) = sm.list_expired_keys(now * 1000, next, 2)?;
+        assert!(expired.is_empty());
+        assert_eq!(None, next);
+
+        let (expired, next) = sm.list_expired_keys(now * 1000 + 2_000_000, None, 2)?;
+        assert_eq!(
+            vec!["alive".to_string(), "expired".to_string()],
+            keys_of(&expired)
+        );
+        assert_eq!(Some("expired".to_string()), next);
+    }
+
+    info!("--- a refreshed key is not removed");
+    {
+        let refreshed = vec![("expired".to_string(), expired[0].1 + 100)];
+        let txn = StateMachine::delete_expired_txn(&refreshed);
+        sm.sm_tree.txn(true, |t| {
+            Ok(sm
+                .apply_cmd(&Cmd::Transaction(txn.clone()), &t, None, now * 1000)
+                .unwrap())
+        })?;
+
+        assert!(sm.kvs().get(&"expired".to_string())?.is_some());
+    }
+
+    info!("--- expired keys are removed");
+    {
+        let txn = StateMachine::delete_expired_txn(&expired);
+        sm.sm_tree.txn(true, |t| {
+            Ok(sm
+                .apply_cmd(&Cmd::Transaction(txn.clone()), &t, None, now * 1000)
+                .unwrap())
+        })?;
+
+        assert!(sm.kvs().get(&"expired".to_string())?.is_none());
+        assert!(sm.kvs().get(&"alive".to_string())?.is_some());
+        assert!(sm.kvs().get(&"forever".to_string())?.is_some());
+    }
+
+    Ok(())
+}
+
+fn keys_of(expired: &[(String, u64)]) -> Vec<String> {
+    expired.iter().map(|(key, _)| key.clone()).collect()
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_state_machine_apply_non_dup_generic_kv_delete() -> anyhow::Result<()> {
     let (_log_guards, ut_span) = init_raft_store_ut!();
@@ -468,3 +665,74 @@ async fn test_state_machine_snapshot() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_state_machine_snapshot_builder_with_concurrent_apply() -> anyhow::Result<()> {
+    // - Feed logs into state machine.
+    // - Start a snapshot, copy part of it, then apply more logs.
+    // - The snapshot should not include the changes made after it started.
+
+    let (_log_guards, ut_span) = init_raft_store_ut!();
+    let _ent = ut_span.enter();
+
+    let tc = new_raft_test_context();
+    let sm = StateMachine::open(&tc.raft_config, 0).await?;
+
+    let (logs, want) = snapshot_logs();
+
+    for l in logs.iter() {
+        sm.apply(l).await?;
+    }
+
+    let mut builder = sm.begin_snapshot()?;
+    assert!(sm.begin_snapshot().is_err(), "only one snapshot at a time");
+
+    assert_eq!(2, builder.copy_batch(2)?);
+
+    let more_logs = vec![
+        Entry {
+            log_id: LogId { term: 1, index: 10 },
+            payload: EntryPayload::Normal(LogEntry {
+                txid: None,
+                time_ms: None,
+                cmd: Cmd::UpsertKV(UpsertKV::update("a", b"A2")),
+            }),
+        },
+        Entry {
+            log_id: LogId { term: 1, index: 11 },
+            payload: EntryPayload::Normal(LogEntry {
+                txid: None,
+                time_ms: None,
+                cmd: Cmd::UpsertKV(UpsertKV::update("b", b"B")),
+            }),
+        },
+        Entry {
+            log_id: LogId { term: 1, index: 12 },
+            payload: EntryPayload::Normal(LogEntry {
+                txid: None,
+                time_ms: None,
+                cmd: Cmd::IncrSeq {
+                    key: "c".to_string(),
+                },
+            }),
+        },
+    ];
+
+    for l in more_logs.iter() {
+        sm.apply(l).await?;
+    }
+
+    while builder.copy_batch(2)? > 0 {}
+
+    let (snap, last_applied, id) = builder.finish();
+
+    assert_eq!(Some(LogId { term: 1, index: 9 }), last_applied);
+    assert!(id.starts_with(&format!("{}-{}-", 1, 9)));
+    assert_eq!(want, pretty_snapshot(&snap.kvs));
+
+    // The capture is released, a new snapshot sees the latest state.
+    let (_snap, last_applied, _id) = sm.build_snapshot()?;
+    assert_eq!(Some(LogId { term: 1, index: 12 }), last_applied);
+
+    Ok(())
+}
@@ -35,6 +35,7 @@ use common_meta_types::protobuf::RaftReply;
 use common_meta_types::protobuf::RaftRequest;
 use common_meta_types::protobuf::WatchRequest;
 use common_meta_types::protobuf::WatchResponse;
+use common_meta_types::RaftTxId;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
 use common_metrics::counter::WithCount;
@@ -60,6 +61,9 @@ use crate::version::to_digit_ver;
 use crate::version::METASRV_SEMVER;
 use crate::version::MIN_METACLI_SEMVER;
 
+/// The user expected by the gRPC handshake and the JSON gateway of the kv APIs.
+pub const META_SERVICE_USER: &str = "root";
+
 pub struct MetaServiceImpl {
     token: GrpcToken,
     pub(crate) meta_node: Arc<MetaNode>,
@@ -86,8 +90,8 @@ impl MetaServiceImpl {
         Ok(claim)
     }
 
-    async fn execute_txn(&self, req: TxnRequest) -> TxnReply {
-        let ret = self.meta_node.transaction(req).await;
+    async fn execute_txn(&self, req: TxnRequest, txid: Option<RaftTxId>) -> TxnReply {
+        let ret = self.meta_node.transaction_with_txid(req, txid).await;
         incr_meta_metrics_meta_request_result(ret.is_ok());
 
         match ret {
@@ -136,7 +140,7 @@ impl MetaService for MetaServiceImpl {
 
         let auth = BasicAuth::decode(&*payload).map_err(|e| Status::internal(e.to_string()))?;
 
-        let user = "root";
+        let user = META_SERVICE_USER;
         if auth.username == user {
             let claim = GrpcClaim {
                 username: user.to_string(),
@@ -175,13 +179,14 @@ impl MetaService for MetaServiceImpl {
         common_tracing::extract_remote_span_as_parent(&r);
         incr_meta_metrics_meta_recv_bytes(r.get_ref().encoded_len() as u64);
 
+        let txid = RaftTxId::extract_from(r.metadata());
         let req: MetaGrpcReq = r.try_into()?;
-        info!("Received MetaGrpcReq: {:?}", req);
+        info!("Received MetaGrpcReq: {:?}, txid: {:?}", req, txid);
 
         let m = &self.meta_node;
         let reply = match req {
             MetaGrpcReq::UpsertKV(a) => {
-                let res = m.upsert_kv(a).await;
+                let res = m.upsert_kv_with_txid(a, txid).await;
                 RaftReply::from(res)
             }
             MetaGrpcReq::GetKV(a) => {
@@ -253,11 +258,12 @@ impl MetaService for MetaServiceImpl {
 
         common_tracing::extract_remote_span_as_parent(&request);
 
+        let txid = RaftTxId::extract_from(request.metadata());
         let request = request.into_inner();
 
-        info!("Receive txn_request: {}", request);
+        info!("Receive txn_request: {}, txid: {:?}", request, txid);
 
-        let body = self.execute_txn(request).await;
+        let body = self.execute_txn(request, txid).await;
         incr_meta_metrics_meta_sent_bytes(body.encoded_len() as u64);
 
         Ok(Response::new(body))
@@ -18,5 +18,9 @@ use crate::configs::Config;
 
 #[poem::handler]
 pub async fn config_handler(cfg: Data<&Config>) -> String {
-    format!("{:?}", cfg.0)
+    let mut cfg = cfg.0.clone();
+    if !cfg.admin_api_kv_gateway_password.is_empty() {
+        cfg.admin_api_kv_gateway_password = "******".to_string();
+    }
+    format!("{:?}", cfg)
 }
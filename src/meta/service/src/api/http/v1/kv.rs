@@ -0,0 +1,111 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A JSON gateway of the read-only kv APIs.
+//!
+//! The requests and replies are the same types used by the gRPC kv API, in their JSON form.
+//! Requests must carry, in HTTP basic auth, the user of the gRPC handshake and the password
+//! `admin_api_kv_gateway_password`. The password is sent in plain text,
+//! thus the admin API should be served with TLS or on a local address.
+
+use std::sync::Arc;
+
+use common_meta_api::KVApi;
+use common_meta_types::GetKVReq;
+use common_meta_types::KVAppError;
+use common_meta_types::ListKVReq;
+use common_meta_types::MGetKVReq;
+use poem::http::StatusCode;
+use poem::web::headers::authorization::Basic;
+use poem::web::headers::Authorization;
+use poem::web::headers::HeaderMapExt;
+use poem::web::Data;
+use poem::web::IntoResponse;
+use poem::web::Json;
+use poem::web::Query;
+use poem::Request;
+
+use crate::api::grpc::grpc_service::META_SERVICE_USER;
+use crate::configs::Config;
+use crate::meta_service::MetaNode;
+
+/// Get a value by key: `GET /v1/kv/get?key=<key>`
+#[poem::handler]
+pub async fn get_handler(
+    request: &Request,
+    meta_node: Data<&Arc<MetaNode>>,
+    cfg: Data<&Config>,
+    Query(req): Query<GetKVReq>,
+) -> poem::Result<impl IntoResponse> {
+    check_auth(request, &cfg)?;
+    let res = meta_node.get_kv(&req.key).await.map_err(to_poem_error)?;
+    Ok(Json(res))
+}
+
+/// Get values by a list of keys: `POST /v1/kv/mget` with body `{"keys": [...]}`
+#[poem::handler]
+pub async fn mget_handler(
+    request: &Request,
+    meta_node: Data<&Arc<MetaNode>>,
+    cfg: Data<&Config>,
+    Json(req): Json<MGetKVReq>,
+) -> poem::Result<impl IntoResponse> {
+    check_auth(request, &cfg)?;
+    let res = meta_node.mget_kv(&req.keys).await.map_err(to_poem_error)?;
+    Ok(Json(res))
+}
+
+/// List key-values by prefix: `GET /v1/kv/list?prefix=<prefix>`
+#[poem::handler]
+pub async fn list_handler(
+    request: &Request,
+    meta_node: Data<&Arc<MetaNode>>,
+    cfg: Data<&Config>,
+    Query(req): Query<ListKVReq>,
+) -> poem::Result<impl IntoResponse> {
+    check_auth(request, &cfg)?;
+    let res = meta_node
+        .prefix_list_kv(&req.prefix)
+        .await
+        .map_err(to_poem_error)?;
+    Ok(Json(res))
+}
+
+fn check_auth(request: &Request, cfg: &Config) -> poem::Result<()> {
+    match request.headers().typed_get::<Authorization<Basic>>() {
+        // an empty password is never accepted, in case the gateway is served without one
+        Some(auth)
+            if auth.username() == META_SERVICE_USER
+                && !cfg.admin_api_kv_gateway_password.is_empty()
+                && auth.password() == cfg.admin_api_kv_gateway_password =>
+        {
+            Ok(())
+        }
+        Some(_) => Err(poem::Error::from_string(
+            "invalid user or password",
+            StatusCode::UNAUTHORIZED,
+        )),
+        None => Err(poem::Error::from_string(
+            "basic auth is required",
+            StatusCode::UNAUTHORIZED,
+        )),
+    }
+}
+
+fn to_poem_error(e: KVAppError) -> poem::Error {
+    poem::Error::from_string(
+        format!("failed to read kv: {}", e),
+        StatusCode::INTERNAL_SERVER_ERROR,
+    )
+}
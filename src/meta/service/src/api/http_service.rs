@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use common_base::base::tokio::sync::broadcast;
 use common_base::base::Stoppable;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_http::health_handler;
 use common_http::home::debug_home_handler;
@@ -27,6 +28,7 @@ use common_http::HttpShutdownHandler;
 use poem::get;
 use poem::listener::RustlsCertificate;
 use poem::listener::RustlsConfig;
+use poem::post;
 use poem::Endpoint;
 use poem::EndpointExt;
 use poem::Route;
@@ -52,7 +54,6 @@ impl HttpService {
     }
 
     fn build_router(&self) -> impl Endpoint {
-        #[cfg_attr(not(feature = "memory-profiling"), allow(unused_mut))]
         let mut route = Route::new()
             .at("/v1/health", get(health_handler))
             .at("/v1/config", get(super::http::v1::config::config_handler))
@@ -75,6 +76,13 @@ impl HttpService {
             .at("/debug/home", get(debug_home_handler))
             .at("/debug/pprof/profile", get(debug_pprof_handler));
 
+        if self.cfg.admin_api_kv_gateway {
+            route = route
+                .at("/v1/kv/get", get(super::http::v1::kv::get_handler))
+                .at("/v1/kv/mget", post(super::http::v1::kv::mget_handler))
+                .at("/v1/kv/list", get(super::http::v1::kv::list_handler));
+        }
+
         #[cfg(feature = "memory-profiling")]
         {
             route = route.at(
@@ -123,6 +131,12 @@ impl HttpService {
 impl Stoppable for HttpService {
     async fn start(&mut self) -> Result<()> {
         let conf = self.cfg.clone();
+        if conf.admin_api_kv_gateway && conf.admin_api_kv_gateway_password.is_empty() {
+            return Err(ErrorCode::InvalidConfig(
+                "admin_api_kv_gateway requires admin_api_kv_gateway_password",
+            ));
+        }
+
         let listening = conf.admin_api_address.parse::<SocketAddr>()?;
         match conf.admin_tls_server_key.is_empty() || conf.admin_tls_server_cert.is_empty() {
             true => self.start_without_tls(listening).await,
@@ -32,6 +32,11 @@ pub struct Config {
     pub admin_api_address: String,
     pub admin_tls_server_cert: String,
     pub admin_tls_server_key: String,
+    /// Serve the read-only kv APIs(get, mget and list) in JSON on the admin HTTP address,
+    /// to the requests with the user of the gRPC API and `admin_api_kv_gateway_password` in basic auth.
+    pub admin_api_kv_gateway: bool,
+    /// The password required by the kv gateway, which is refused to start without it.
+    pub admin_api_kv_gateway_password: String,
     pub grpc_api_address: String,
     /// Certificate for server to identify itself
     pub grpc_tls_server_cert: String,
@@ -53,6 +58,8 @@ impl Default for Config {
             admin_api_address: "127.0.0.1:28002".to_string(),
             admin_tls_server_cert: "".to_string(),
             admin_tls_server_key: "".to_string(),
+            admin_api_kv_gateway: false,
+            admin_api_kv_gateway_password: "".to_string(),
             grpc_api_address: "127.0.0.1:9191".to_string(),
             grpc_tls_server_cert: "".to_string(),
             grpc_tls_server_key: "".to_string(),
@@ -83,6 +83,15 @@ pub struct Config {
     #[clap(long, default_value = "")]
     pub admin_tls_server_key: String,
 
+    /// Serve the read-only kv APIs(get, mget and list) in JSON on the admin HTTP address,
+    /// to the requests with the user of the gRPC API and `admin_api_kv_gateway_password` in basic auth.
+    #[clap(long)]
+    pub admin_api_kv_gateway: bool,
+
+    /// The password required by the kv gateway, which is refused to start without it.
+    #[clap(long, default_value = "")]
+    pub admin_api_kv_gateway_password: String,
+
     #[clap(long, default_value = "127.0.0.1:9191")]
     pub grpc_api_address: String,
 
@@ -125,6 +134,8 @@ impl From<Config> for InnerConfig {
             admin_api_address: x.admin_api_address,
             admin_tls_server_cert: x.admin_tls_server_cert,
             admin_tls_server_key: x.admin_tls_server_key,
+            admin_api_kv_gateway: x.admin_api_kv_gateway,
+            admin_api_kv_gateway_password: x.admin_api_kv_gateway_password,
             grpc_api_address: x.grpc_api_address,
             grpc_tls_server_cert: x.grpc_tls_server_cert,
             grpc_tls_server_key: x.grpc_tls_server_key,
@@ -149,6 +160,8 @@ impl From<InnerConfig> for Config {
             admin_api_address: inner.admin_api_address,
             admin_tls_server_cert: inner.admin_tls_server_cert,
             admin_tls_server_key: inner.admin_tls_server_key,
+            admin_api_kv_gateway: inner.admin_api_kv_gateway,
+            admin_api_kv_gateway_password: inner.admin_api_kv_gateway_password,
             grpc_api_address: inner.grpc_api_address,
             grpc_tls_server_cert: inner.grpc_tls_server_cert,
             grpc_tls_server_key: inner.grpc_tls_server_key,
@@ -214,6 +227,8 @@ pub struct ConfigViaEnv {
     pub admin_api_address: String,
     pub admin_tls_server_cert: String,
     pub admin_tls_server_key: String,
+    pub admin_api_kv_gateway: bool,
+    pub admin_api_kv_gateway_password: String,
     pub metasrv_grpc_api_address: String,
     pub grpc_tls_server_cert: String,
     pub grpc_tls_server_key: String,
@@ -255,6 +270,8 @@ impl From<Config> for ConfigViaEnv {
             admin_api_address: cfg.admin_api_address,
             admin_tls_server_cert: cfg.admin_tls_server_cert,
             admin_tls_server_key: cfg.admin_tls_server_key,
+            admin_api_kv_gateway: cfg.admin_api_kv_gateway,
+            admin_api_kv_gateway_password: cfg.admin_api_kv_gateway_password,
             metasrv_grpc_api_address: cfg.grpc_api_address,
             grpc_tls_server_cert: cfg.grpc_tls_server_cert,
             grpc_tls_server_key: cfg.grpc_tls_server_key,
@@ -329,6 +346,8 @@ impl Into<Config> for ConfigViaEnv {
             admin_api_address: self.admin_api_address,
             admin_tls_server_cert: self.admin_tls_server_cert,
             admin_tls_server_key: self.admin_tls_server_key,
+            admin_api_kv_gateway: self.admin_api_kv_gateway,
+            admin_api_kv_gateway_password: self.admin_api_kv_gateway_password,
             grpc_api_address: self.metasrv_grpc_api_address,
             grpc_tls_server_cert: self.grpc_tls_server_cert,
             grpc_tls_server_key: self.grpc_tls_server_key,
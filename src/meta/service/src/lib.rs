@@ -18,6 +18,7 @@ pub mod export;
 pub mod meta_service;
 pub mod metrics;
 pub mod network;
+pub mod peer_quarantine;
 pub mod raft_client;
 pub mod store;
 pub mod version;
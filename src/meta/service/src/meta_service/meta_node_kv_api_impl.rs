@@ -24,6 +24,7 @@ use common_meta_types::ListKVReq;
 use common_meta_types::LogEntry;
 use common_meta_types::MGetKVReply;
 use common_meta_types::MGetKVReq;
+use common_meta_types::RaftTxId;
 use common_meta_types::TxnReply;
 use common_meta_types::TxnRequest;
 use common_meta_types::UpsertKV;
@@ -33,16 +34,18 @@ use tracing::info;
 
 use crate::meta_service::MetaNode;
 
-/// Impl KVApi for MetaNode.
-///
-/// Write through raft-log.
-/// Read through local state machine, which may not be consistent.
-/// E.g. Read is not guaranteed to see a write.
-#[async_trait]
-impl KVApi for MetaNode {
-    async fn upsert_kv(&self, act: UpsertKVReq) -> Result<UpsertKVReply, KVAppError> {
+impl MetaNode {
+    /// Upsert a kv with an optional client generated txid.
+    ///
+    /// A request with the same txid that has already been applied is not applied again,
+    /// the previous result is returned instead.
+    pub async fn upsert_kv_with_txid(
+        &self,
+        act: UpsertKVReq,
+        txid: Option<RaftTxId>,
+    ) -> Result<UpsertKVReply, KVAppError> {
         let ent = LogEntry {
-            txid: None,
+            txid,
             time_ms: None,
             cmd: Cmd::UpsertKV(UpsertKV {
                 key: act.key,
@@ -61,6 +64,41 @@ impl KVApi for MetaNode {
         }
     }
 
+    /// Execute a transaction with an optional client generated txid.
+    #[tracing::instrument(level = "debug", skip(self, txn))]
+    pub async fn transaction_with_txid(
+        &self,
+        txn: TxnRequest,
+        txid: Option<RaftTxId>,
+    ) -> Result<TxnReply, KVAppError> {
+        info!("MetaNode::transaction(): {}, txid: {:?}", txn, txid);
+        let ent = LogEntry {
+            txid,
+            time_ms: None,
+            cmd: Cmd::Transaction(txn),
+        };
+        let rst = self.write(ent).await?;
+
+        match rst {
+            AppliedState::TxnReply(x) => Ok(x),
+            _ => {
+                unreachable!("expect type {}", "AppliedState::transaction",)
+            }
+        }
+    }
+}
+
+/// Impl KVApi for MetaNode.
+///
+/// Write through raft-log.
+/// Read through local state machine, which may not be consistent.
+/// E.g. Read is not guaranteed to see a write.
+#[async_trait]
+impl KVApi for MetaNode {
+    async fn upsert_kv(&self, act: UpsertKVReq) -> Result<UpsertKVReply, KVAppError> {
+        self.upsert_kv_with_txid(act, None).await
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn get_kv(&self, key: &str) -> Result<GetKVReply, KVAppError> {
         let res = self
@@ -96,19 +134,6 @@ impl KVApi for MetaNode {
 
     #[tracing::instrument(level = "debug", skip(self, txn))]
     async fn transaction(&self, txn: TxnRequest) -> Result<TxnReply, KVAppError> {
-        info!("MetaNode::transaction(): {}", txn);
-        let ent = LogEntry {
-            txid: None,
-            time_ms: None,
-            cmd: Cmd::Transaction(txn),
-        };
-        let rst = self.write(ent).await?;
-
-        match rst {
-            AppliedState::TxnReply(x) => Ok(x),
-            _ => {
-                unreachable!("expect type {}", "AppliedState::transaction",)
-            }
-        }
+        self.transaction_with_txid(txn, None).await
     }
 }
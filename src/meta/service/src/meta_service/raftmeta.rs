@@ -21,6 +21,7 @@ use std::time::Duration;
 
 use anyerror::AnyError;
 use common_base::base::tokio;
+use common_base::base::tokio::select;
 use common_base::base::tokio::sync::watch;
 use common_base::base::tokio::sync::Mutex;
 use common_base::base::tokio::sync::RwLockReadGuard;
@@ -58,6 +59,7 @@ use common_meta_types::MetaStartupError;
 use common_meta_types::MetaStorageError;
 use common_meta_types::Node;
 use common_meta_types::NodeId;
+use common_meta_types::SeqV;
 use openraft::Config;
 use openraft::LogId;
 use openraft::Raft;
@@ -119,6 +121,9 @@ pub struct MetaNodeStatus {
     ///
     /// `seq` is a monotonically incremental integer for every value that is inserted or updated.
     pub last_seq: u64,
+
+    /// Ids of the peers that are quarantined because of repeated RPC failures.
+    pub quarantined_peers: Vec<NodeId>,
 }
 
 // MetaRaft is a impl of the generic Raft handling meta data R/W.
@@ -128,6 +133,8 @@ pub type MetaRaft = Raft<LogEntry, AppliedState, Network, RaftStore>;
 pub struct MetaNode {
     pub sto: Arc<RaftStore>,
     pub watcher: WatcherManager,
+    /// The network shared with `raft`, for building the channels to peers.
+    pub network: Arc<Network>,
     pub raft: MetaRaft,
     pub running_tx: watch::Sender<()>,
     pub running_rx: watch::Receiver<()>,
@@ -165,9 +172,9 @@ impl MetaNodeBuilder {
             .take()
             .ok_or_else(|| MetaStartupError::InvalidConfig(String::from("sto is not set")))?;
 
-        let net = Network::new(sto.clone());
+        let net = Arc::new(Network::new(sto.clone()));
 
-        let raft = MetaRaft::new(node_id, Arc::new(config), Arc::new(net), sto.clone());
+        let raft = MetaRaft::new(node_id, Arc::new(config), net.clone(), sto.clone());
         let metrics_rx = raft.metrics();
 
         let (tx, rx) = watch::channel::<()>(());
@@ -181,6 +188,7 @@ impl MetaNodeBuilder {
         let mn = Arc::new(MetaNode {
             sto: sto.clone(),
             watcher,
+            network: net,
             raft,
             running_tx: tx,
             running_rx: rx,
@@ -193,6 +201,8 @@ impl MetaNodeBuilder {
             MetaNode::subscribe_metrics(mn.clone(), metrics_rx).await;
         }
 
+        MetaNode::start_expired_cleaner(mn.clone()).await;
+
         let endpoint = if let Some(a) = self.endpoint.take() {
             a
         } else {
@@ -208,6 +218,8 @@ impl MetaNodeBuilder {
 
         MetaNode::start_grpc(mn.clone(), &endpoint.addr, endpoint.port).await?;
 
+        MetaNode::warm_up_network(mn.clone());
+
         Ok(mn)
     }
 
@@ -237,6 +249,14 @@ impl MetaNodeBuilder {
 }
 
 impl MetaNode {
+    /// The interval to check for expired keys to remove.
+    const EXPIRED_CLEAN_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// The max number of keys to scan for expired ones in one round.
+    ///
+    /// The scan holds the state machine lock, it resumes from where the last round stopped.
+    const EXPIRED_CLEAN_SCAN_BATCH: usize = 1024;
+
     pub fn builder(config: &RaftConfig) -> MetaNodeBuilder {
         let raft_config = MetaNode::new_raft_config(config);
 
@@ -440,6 +460,7 @@ impl MetaNode {
 
         let fut = async move {
             let mut last_leader: Option<u64> = None;
+            let mut last_membership = None;
 
             loop {
                 let changed = metrics_rx.changed().await;
@@ -490,6 +511,14 @@ impl MetaNode {
                 );
 
                 last_leader = mm.current_leader;
+
+                // Connect to the newly added peers in advance.
+                if last_membership.as_ref() != Some(&mm.membership_config.membership) {
+                    if last_membership.is_some() {
+                        MetaNode::warm_up_network(meta_node.clone());
+                    }
+                    last_membership = Some(mm.membership_config.membership.clone());
+                }
             }
 
             Ok::<(), AnyError>(())
@@ -504,6 +533,144 @@ impl MetaNode {
         }
     }
 
+    /// Spawn a task to establish the channels to all known peers.
+    pub fn warm_up_network(mn: Arc<Self>) {
+        let fut = async move { mn.network.warm_up().await };
+
+        let span = tracing::span!(tracing::Level::INFO, "warm-up-network");
+        tokio::task::spawn(fut.instrument(span));
+    }
+
+    /// Spawn a task that periodically removes expired keys from the state machine.
+    ///
+    /// Keys with an `expire_at`, such as the entries a query node registers and keeps alive with
+    /// heartbeats, are invisible to readers once expired, but still occupy the state machine
+    /// and no deletion event is sent to watchers.
+    /// Only the leader proposes the deletion, so that it is applied with a consistent time on every node.
+    /// The deletion is a transaction of conditional deletes, which every version of meta-service applies.
+    ///
+    /// The recent responses of the clients not seen for `CLIENT_LAST_RESP_TTL_MS` are removed too.
+    pub async fn start_expired_cleaner(mn: Arc<Self>) {
+        let meta_node = mn.clone();
+        let mut running_rx = mn.running_rx.clone();
+
+        let fut = async move {
+            // The key to resume the scan after, `None` to start from the beginning.
+            let mut cursor: Option<String> = None;
+            // The client to resume the scan of client responses after.
+            let mut client_cursor: Option<String> = None;
+
+            loop {
+                select! {
+                    _ = running_rx.changed() => {
+                        info!("shutting down expired cleaner: id={}", meta_node.sto.id);
+                        break;
+                    }
+                    _ = tokio::time::sleep(Self::EXPIRED_CLEAN_INTERVAL) => {}
+                }
+
+                let is_leader =
+                    meta_node.raft.metrics().borrow().current_leader == Some(meta_node.sto.id);
+                if !is_leader {
+                    cursor = None;
+                    client_cursor = None;
+                    continue;
+                }
+
+                meta_node.remove_expired_clients(&mut client_cursor).await;
+
+                let listed = {
+                    let sm = meta_node.get_state_machine().await;
+                    sm.list_expired_keys(
+                        SeqV::<()>::now_ms(),
+                        cursor.take(),
+                        Self::EXPIRED_CLEAN_SCAN_BATCH,
+                    )
+                };
+
+                let expired = match listed {
+                    Ok((expired, next)) => {
+                        cursor = next;
+                        expired
+                    }
+                    Err(e) => {
+                        warn!("fail to list expired keys: {}", e);
+                        continue;
+                    }
+                };
+
+                if expired.is_empty() {
+                    continue;
+                }
+
+                info!("about to remove {} expired keys", expired.len());
+
+                let res = meta_node
+                    .write(LogEntry {
+                        txid: None,
+                        time_ms: None,
+                        cmd: Cmd::Transaction(StateMachine::delete_expired_txn(&expired)),
+                    })
+                    .await;
+
+                if let Err(e) = res {
+                    warn!("fail to remove expired keys: {}", e);
+                }
+            }
+
+            Ok::<(), AnyError>(())
+        };
+
+        let span = tracing::span!(tracing::Level::INFO, "expired-cleaner");
+        let h = tokio::task::spawn(fut.instrument(span));
+
+        {
+            let mut jh = mn.join_handles.lock().await;
+            jh.push(h);
+        }
+    }
+
+    /// Scan a batch of clients after `cursor` and remove the ones not seen for long.
+    async fn remove_expired_clients(&self, cursor: &mut Option<String>) {
+        let listed = {
+            let sm = self.get_state_machine().await;
+            sm.list_expired_clients(
+                SeqV::<()>::now_ms(),
+                cursor.take(),
+                Self::EXPIRED_CLEAN_SCAN_BATCH,
+            )
+        };
+
+        let expired = match listed {
+            Ok((expired, next)) => {
+                *cursor = next;
+                expired
+            }
+            Err(e) => {
+                warn!("fail to list expired clients: {}", e);
+                return;
+            }
+        };
+
+        if expired.is_empty() {
+            return;
+        }
+
+        info!("about to remove {} expired clients", expired.len());
+
+        let res = self
+            .write(LogEntry {
+                txid: None,
+                time_ms: None,
+                cmd: Cmd::RemoveClientLastResps { clients: expired },
+            })
+            .await;
+
+        if let Err(e) = res {
+            warn!("fail to remove expired clients: {}", e);
+        }
+    }
+
     /// Start MetaNode in either `boot`, `single`, `join` or `open` mode,
     /// according to config.
     #[tracing::instrument(level = "debug", skip(config))]
@@ -800,6 +967,7 @@ impl MetaNode {
             voters,
             non_voters,
             last_seq,
+            quarantined_peers: self.network.quarantine.quarantined_peers(),
         })
     }
 
@@ -91,6 +91,30 @@ pub static APPLYING_SNAPSHOT: Lazy<IntGauge> = Lazy::new(|| {
     .expect("meta metric cannot be created")
 });
 
+pub static BUILDING_SNAPSHOT: Lazy<IntGauge> = Lazy::new(|| {
+    IntGauge::with_opts(
+        Opts::new(
+            "building_snapshot",
+            "Whether or not statemachine is building snapshot.",
+        )
+        .namespace(META_NAMESPACE)
+        .subsystem(SERVER_SUBSYSTEM),
+    )
+    .expect("meta metric cannot be created")
+});
+
+pub static SNAPSHOT_BUILT_KEYS: Lazy<IntGauge> = Lazy::new(|| {
+    IntGauge::with_opts(
+        Opts::new(
+            "snapshot_built_keys",
+            "Number of keys copied into the snapshot being built.",
+        )
+        .namespace(META_NAMESPACE)
+        .subsystem(SERVER_SUBSYSTEM),
+    )
+    .expect("meta metric cannot be created")
+});
+
 pub static PROPOSALS_APPLIED: Lazy<Gauge> = Lazy::new(|| {
     Gauge::with_opts(
         Opts::new(
@@ -180,6 +204,19 @@ pub static ACTIVE_PEERS: Lazy<GaugeVec> = Lazy::new(|| {
     .expect("meta metric cannot be created")
 });
 
+pub static QUARANTINED_PEERS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    IntGaugeVec::new(
+        Opts::new(
+            "quarantined_peers",
+            "Whether or not a peer is quarantined because of repeated failures.",
+        )
+        .namespace(META_NAMESPACE)
+        .subsystem(RAFT_NETWORK_SUBSYSTEM),
+        &["id"],
+    )
+    .expect("meta metric cannot be created")
+});
+
 pub static CONNECT_TO_PEER_FAIL: Lazy<CounterVec> = Lazy::new(|| {
     CounterVec::new(
         Opts::new(
@@ -446,6 +483,14 @@ fn init_meta_recorder() {
         .register(Box::new(APPLYING_SNAPSHOT.clone()))
         .expect("collector can be registered");
 
+    REGISTRY
+        .register(Box::new(BUILDING_SNAPSHOT.clone()))
+        .expect("collector can be registered");
+
+    REGISTRY
+        .register(Box::new(SNAPSHOT_BUILT_KEYS.clone()))
+        .expect("collector can be registered");
+
     REGISTRY
         .register(Box::new(PROPOSALS_APPLIED.clone()))
         .expect("collector can be registered");
@@ -482,6 +527,10 @@ fn init_meta_recorder() {
         .register(Box::new(ACTIVE_PEERS.clone()))
         .expect("collector can be registered");
 
+    REGISTRY
+        .register(Box::new(QUARANTINED_PEERS.clone()))
+        .expect("collector can be registered");
+
     REGISTRY
         .register(Box::new(CONNECT_TO_PEER_FAIL.clone()))
         .expect("collector can be registered");
@@ -579,6 +628,14 @@ pub fn incr_meta_metrics_applying_snapshot(cnt: i64) {
     APPLYING_SNAPSHOT.add(cnt);
 }
 
+pub fn set_meta_metrics_building_snapshot(building: bool) {
+    BUILDING_SNAPSHOT.set(i64::from(building));
+}
+
+pub fn set_meta_metrics_snapshot_built_keys(keys: usize) {
+    SNAPSHOT_BUILT_KEYS.set(keys as i64);
+}
+
 pub fn set_meta_metrics_proposals_applied(proposals_applied: u64) {
     PROPOSALS_APPLIED.set(proposals_applied as f64);
 }
@@ -617,6 +674,12 @@ pub fn incr_meta_metrics_active_peers(id: &NodeId, addr: &String, cnt: i64) {
         .add(cnt as f64);
 }
 
+pub fn set_meta_metrics_peer_quarantined(id: &NodeId, quarantined: bool) {
+    QUARANTINED_PEERS
+        .with_label_values(&[&id.to_string()])
+        .set(if quarantined { 1 } else { 0 });
+}
+
 pub fn incr_meta_metrics_fail_connections_to_peer(id: &NodeId, addr: &String) {
     CONNECT_TO_PEER_FAIL
         .with_label_values(&[&id.to_string(), addr])
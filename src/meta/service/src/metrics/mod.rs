@@ -40,12 +40,15 @@ pub use meta_metrics::init_meta_metrics_recorder;
 pub use meta_metrics::meta_metrics_to_prometheus_string;
 pub use meta_metrics::sample_meta_metrics_snapshot_recv;
 pub use meta_metrics::sample_meta_metrics_snapshot_sent;
+pub use meta_metrics::set_meta_metrics_building_snapshot;
 pub use meta_metrics::set_meta_metrics_current_leader;
 pub use meta_metrics::set_meta_metrics_current_term;
 pub use meta_metrics::set_meta_metrics_is_leader;
 pub use meta_metrics::set_meta_metrics_last_log_index;
 pub use meta_metrics::set_meta_metrics_last_seq;
 pub use meta_metrics::set_meta_metrics_node_is_health;
+pub use meta_metrics::set_meta_metrics_peer_quarantined;
 pub use meta_metrics::set_meta_metrics_proposals_applied;
+pub use meta_metrics::set_meta_metrics_snapshot_built_keys;
 pub(crate) use meta_metrics::ProposalPending;
 pub(crate) use meta_metrics::RequestInFlight;
@@ -16,11 +16,15 @@ use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
+use common_base::base::tokio::time::sleep;
+use common_base::base::tokio::time::timeout;
 use common_base::containers::ItemManager;
 use common_base::containers::Pool;
 use common_meta_sled_store::openraft;
 use common_meta_sled_store::openraft::MessageSummary;
 use common_meta_types::protobuf::RaftRequest;
+use common_meta_types::ForwardRequest;
+use common_meta_types::ForwardRequestBody;
 use common_meta_types::LogEntry;
 use common_meta_types::NodeId;
 use openraft::async_trait::async_trait;
@@ -35,6 +39,7 @@ use tonic::client::GrpcService;
 use tonic::transport::channel::Channel;
 use tracing::debug;
 use tracing::info;
+use tracing::warn;
 
 use crate::metrics::incr_meta_metrics_fail_connections_to_peer;
 use crate::metrics::incr_meta_metrics_sent_bytes_to_peer;
@@ -43,6 +48,8 @@ use crate::metrics::incr_meta_metrics_snapshot_send_failures_to_peer;
 use crate::metrics::incr_meta_metrics_snapshot_send_inflights_to_peer;
 use crate::metrics::incr_meta_metrics_snapshot_send_success_to_peer;
 use crate::metrics::sample_meta_metrics_snapshot_sent;
+use crate::peer_quarantine::Admission;
+use crate::peer_quarantine::PeerQuarantine;
 use crate::raft_client::RaftClient;
 use crate::raft_client::RaftClientApi;
 use crate::store::RaftStore;
@@ -71,14 +78,35 @@ pub struct Network {
     sto: Arc<RaftStore>,
 
     conn_pool: Pool<ChannelManager>,
+
+    pub quarantine: PeerQuarantine,
 }
 
 impl Network {
+    /// The max number of attempts to connect to a peer when warming up.
+    const WARM_UP_RETRIES: u32 = 3;
+
+    /// The base interval to wait before retrying to connect to a peer.
+    const WARM_UP_BACKOFF: Duration = Duration::from_millis(500);
+
+    /// The number of consecutive failures to quarantine a peer.
+    const QUARANTINE_THRESHOLD: u64 = 5;
+
+    /// The interval to probe a quarantined peer.
+    const QUARANTINE_PROBE_INTERVAL: Duration = Duration::from_secs(3);
+
+    /// The timeout of a probe to a quarantined peer.
+    const QUARANTINE_PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
     pub fn new(sto: Arc<RaftStore>) -> Network {
         let mgr = ChannelManager {};
         Network {
             sto,
             conn_pool: Pool::new(mgr, Duration::from_millis(50)),
+            quarantine: PeerQuarantine::new(
+                Self::QUARANTINE_THRESHOLD,
+                Self::QUARANTINE_PROBE_INTERVAL,
+            ),
         }
     }
 
@@ -103,6 +131,105 @@ impl Network {
         }
     }
 
+    /// Establish channels to all known peers in advance.
+    ///
+    /// Otherwise a channel is built lazily when the first RPC is sent to a peer,
+    /// and the first election or heartbeat after a restart pays the connecting latency,
+    /// which may cause a spurious timeout.
+    /// A peer that can not be connected is retried at most `WARM_UP_RETRIES` times.
+    #[tracing::instrument(level = "debug", skip(self), fields(id=self.sto.id))]
+    pub async fn warm_up(&self) {
+        let node_ids = {
+            let sm = self.sto.state_machine.read().await;
+            sm.nodes().range_keys(..)
+        };
+
+        let node_ids = match node_ids {
+            Ok(x) => x,
+            Err(e) => {
+                warn!("fail to list nodes to warm up: {}", e);
+                return;
+            }
+        };
+
+        let peers = node_ids.into_iter().filter(|id| *id != self.sto.id);
+        let futs = peers.map(|target| self.warm_up_peer(target));
+
+        futures::future::join_all(futs).await;
+    }
+
+    async fn warm_up_peer(&self, target: NodeId) {
+        for attempt in 1..=Self::WARM_UP_RETRIES {
+            match self.make_client(&target).await {
+                Ok(_) => {
+                    info!("warmed up connection to target={}", target);
+                    return;
+                }
+                Err(e) => {
+                    warn!(
+                        "fail to warm up connection to target={}, attempt: {}, error: {}",
+                        target, attempt, e
+                    );
+                    sleep(Self::WARM_UP_BACKOFF * attempt).await;
+                }
+            }
+        }
+    }
+
+    /// Check if an RPC can be sent to `target`.
+    ///
+    /// If `target` is quarantined, RPCs are rejected until it is time to probe it.
+    /// The client of a successful probe is returned, to send the RPC with.
+    async fn check_quarantine(&self, target: &NodeId) -> anyhow::Result<Option<RaftClient>> {
+        match self.quarantine.admit(target) {
+            Admission::Allow => Ok(None),
+            Admission::Probe => {
+                debug!("probe quarantined target={}", target);
+                let res = self.probe(target).await;
+                self.record_result(target, &res);
+                res.map(Some)
+            }
+            Admission::Reject => Err(anyhow::anyhow!("target={} is quarantined", target)),
+        }
+    }
+
+    /// Send a `Ping` to `target` that is answered without touching its raft state.
+    ///
+    /// A channel can be built to a peer that is too busy or stuck to serve any RPC,
+    /// thus only a reply within `QUARANTINE_PROBE_TIMEOUT` proves the peer is back.
+    async fn probe(&self, target: &NodeId) -> anyhow::Result<RaftClient> {
+        let mut client = self.make_client(target).await?;
+
+        let req = ForwardRequest {
+            forward_to_leader: 0,
+            body: ForwardRequestBody::Ping,
+        };
+        timeout(Self::QUARANTINE_PROBE_TIMEOUT, client.forward(req)).await??;
+
+        Ok(client)
+    }
+
+    fn record_result<T, E>(&self, target: &NodeId, res: &Result<T, E>) {
+        if res.is_ok() {
+            self.quarantine.record_success(target);
+        } else {
+            self.quarantine.record_failure(target);
+        }
+    }
+
+    /// Build a client to `target`, counting a failure against it in case of an error.
+    async fn connect(&self, target: &NodeId) -> anyhow::Result<RaftClient> {
+        if let Some(client) = self.check_quarantine(target).await? {
+            return Ok(client);
+        }
+
+        let res = self.make_client(target).await;
+        if res.is_err() {
+            self.quarantine.record_failure(target);
+        }
+        res
+    }
+
     fn incr_meta_metrics_sent_bytes_to_peer(&self, target: &NodeId, message: &RaftRequest) {
         let bytes = message.data.len() as u64;
         incr_meta_metrics_sent_bytes_to_peer(target, bytes);
@@ -123,7 +250,7 @@ impl RaftNetwork<LogEntry> for Network {
             rpc.summary()
         );
 
-        let mut client = self.make_client(&target).await?;
+        let mut client = self.connect(&target).await?;
 
         let req = common_tracing::inject_span_to_tonic_request(rpc);
 
@@ -131,6 +258,7 @@ impl RaftNetwork<LogEntry> for Network {
 
         let resp = client.append_entries(req).await;
         debug!("append_entries resp from: id={}: {:?}", target, resp);
+        self.record_result(&target, &resp);
 
         if resp.is_err() {
             incr_meta_metrics_sent_failure_to_peer(&target);
@@ -155,7 +283,7 @@ impl RaftNetwork<LogEntry> for Network {
         );
 
         let start = Instant::now();
-        let mut client = self.make_client(&target).await?;
+        let mut client = self.connect(&target).await?;
         let req = common_tracing::inject_span_to_tonic_request(rpc);
 
         self.incr_meta_metrics_sent_bytes_to_peer(&target, req.get_ref());
@@ -163,6 +291,7 @@ impl RaftNetwork<LogEntry> for Network {
 
         let resp = client.install_snapshot(req).await;
         info!("install_snapshot resp from: id={}: {:?}", target, resp);
+        self.record_result(&target, &resp);
 
         if resp.is_err() {
             incr_meta_metrics_sent_failure_to_peer(&target);
@@ -185,13 +314,14 @@ impl RaftNetwork<LogEntry> for Network {
     async fn send_vote(&self, target: NodeId, rpc: VoteRequest) -> anyhow::Result<VoteResponse> {
         info!("send_vote: target: {} rpc: {}", target, rpc.summary());
 
-        let mut client = self.make_client(&target).await?;
+        let mut client = self.connect(&target).await?;
         let req = common_tracing::inject_span_to_tonic_request(rpc);
 
         self.incr_meta_metrics_sent_bytes_to_peer(&target, req.get_ref());
 
         let resp = client.vote(req).await;
         info!("vote: resp from target={} {:?}", target, resp);
+        self.record_result(&target, &resp);
 
         if resp.is_err() {
             incr_meta_metrics_sent_failure_to_peer(&target);
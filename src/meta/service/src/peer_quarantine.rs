@@ -0,0 +1,132 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_meta_types::NodeId;
+use tracing::info;
+use tracing::warn;
+
+use crate::metrics::set_meta_metrics_peer_quarantined;
+
+/// What to do with an RPC to a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// The peer is healthy, send the RPC.
+    Allow,
+    /// The peer is quarantined and it is time to probe it.
+    Probe,
+    /// The peer is quarantined, do not send any RPC.
+    Reject,
+}
+
+#[derive(Debug, Default)]
+struct PeerState {
+    /// The number of consecutive failures.
+    failures: u64,
+
+    /// When the last probe is sent, if the peer is quarantined.
+    quarantined: Option<Instant>,
+}
+
+/// Keeps track of peers that fail repeatedly.
+///
+/// After `threshold` consecutive failures a peer is quarantined:
+/// no full RPC is sent to it and only a lightweight probe is allowed every `probe_interval`.
+/// The peer is restored once a probe succeeds.
+pub struct PeerQuarantine {
+    threshold: u64,
+    probe_interval: Duration,
+    peers: Mutex<BTreeMap<NodeId, PeerState>>,
+}
+
+impl PeerQuarantine {
+    pub fn new(threshold: u64, probe_interval: Duration) -> Self {
+        Self {
+            threshold,
+            probe_interval,
+            peers: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Decide whether an RPC can be sent to `target`.
+    pub fn admit(&self, target: &NodeId) -> Admission {
+        let mut peers = self.peers.lock().unwrap();
+
+        let state = match peers.get_mut(target) {
+            None => return Admission::Allow,
+            Some(x) => x,
+        };
+
+        match state.quarantined {
+            None => Admission::Allow,
+            Some(last_probe) => {
+                if last_probe.elapsed() >= self.probe_interval {
+                    state.quarantined = Some(Instant::now());
+                    Admission::Probe
+                } else {
+                    Admission::Reject
+                }
+            }
+        }
+    }
+
+    pub fn record_success(&self, target: &NodeId) {
+        let mut peers = self.peers.lock().unwrap();
+
+        if let Some(state) = peers.remove(target) {
+            if state.quarantined.is_some() {
+                info!("peer {} is restored from quarantine", target);
+                set_meta_metrics_peer_quarantined(target, false);
+            }
+        }
+    }
+
+    pub fn record_failure(&self, target: &NodeId) {
+        let mut peers = self.peers.lock().unwrap();
+
+        let state = peers.entry(*target).or_default();
+        state.failures += 1;
+
+        if state.quarantined.is_none() && state.failures >= self.threshold {
+            warn!(
+                "peer {} is quarantined after {} consecutive failures",
+                target, state.failures
+            );
+            state.quarantined = Some(Instant::now());
+            set_meta_metrics_peer_quarantined(target, true);
+        }
+    }
+
+    pub fn is_quarantined(&self, target: &NodeId) -> bool {
+        let peers = self.peers.lock().unwrap();
+        peers
+            .get(target)
+            .map(|s| s.quarantined.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Returns ids of all quarantined peers.
+    pub fn quarantined_peers(&self) -> Vec<NodeId> {
+        let peers = self.peers.lock().unwrap();
+        peers
+            .iter()
+            .filter(|(_, s)| s.quarantined.is_some())
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
@@ -17,10 +17,12 @@ use std::fmt::Debug;
 use std::io::Cursor;
 use std::io::ErrorKind;
 use std::ops::RangeBounds;
+use std::time::Duration;
 
 use anyerror::AnyError;
 use common_base::base::tokio::sync::RwLock;
 use common_base::base::tokio::sync::RwLockWriteGuard;
+use common_base::base::tokio::time::sleep;
 use common_meta_raft_store::config::RaftConfig;
 use common_meta_raft_store::log::RaftLog;
 use common_meta_raft_store::state::RaftState;
@@ -59,6 +61,8 @@ use tracing::info;
 use crate::export::vec_kv_to_json;
 use crate::metrics::incr_meta_metrics_applying_snapshot;
 use crate::metrics::incr_raft_storage_fail;
+use crate::metrics::set_meta_metrics_building_snapshot;
+use crate::metrics::set_meta_metrics_snapshot_built_keys;
 use crate::store::ToStorageError;
 use crate::Opened;
 
@@ -122,6 +126,12 @@ impl Opened for RaftStoreBare {
 }
 
 impl RaftStoreBare {
+    /// Number of key values to copy from the state machine before pausing, when building a snapshot.
+    const SNAPSHOT_BUILD_BATCH: usize = 1024;
+
+    /// The pause between two batches when building a snapshot.
+    const SNAPSHOT_BUILD_PAUSE: Duration = Duration::from_millis(1);
+
     /// Open an existent `metasrv` instance or create an new one:
     /// 1. If `open` is `Some`, try to open an existent one.
     /// 2. If `create` is `Some`, try to create one.
@@ -166,6 +176,39 @@ impl RaftStoreBare {
         })
     }
 
+    /// Copy the state machine into a snapshot in batches.
+    ///
+    /// The state machine lock is held only to start the snapshot, thus applying logs is not blocked
+    /// while key values are being copied.
+    /// It pauses between batches to reduce the IO pressure on the state machine.
+    async fn do_build_snapshot(
+        &self,
+    ) -> Result<(SerializableSnapshot, Option<LogId>, String), StorageError> {
+        // Applying a log holds the write lock thus it is not being applied when the snapshot starts.
+        let mut builder = self
+            .state_machine
+            .read()
+            .await
+            .begin_snapshot()
+            .map_to_sto_err(ErrorSubject::StateMachine, ErrorVerb::Read)?;
+
+        loop {
+            let n = builder
+                .copy_batch(Self::SNAPSHOT_BUILD_BATCH)
+                .map_to_sto_err(ErrorSubject::StateMachine, ErrorVerb::Read)?;
+
+            set_meta_metrics_snapshot_built_keys(builder.copied());
+
+            if n == 0 {
+                break;
+            }
+
+            sleep(Self::SNAPSHOT_BUILD_PAUSE).await;
+        }
+
+        Ok(builder.finish())
+    }
+
     /// Get a handle to the state machine for testing purposes.
     pub async fn get_state_machine(&self) -> RwLockWriteGuard<'_, StateMachine> {
         self.state_machine.write().await
@@ -433,13 +476,11 @@ impl RaftStorage<LogEntry, AppliedState> for RaftStoreBare {
 
         // 1. Take a serialized snapshot
 
-        let (snap, last_applied_log, snapshot_id) = match self
-            .state_machine
-            .write()
-            .await
-            .build_snapshot()
-            .map_to_sto_err(ErrorSubject::StateMachine, ErrorVerb::Read)
-        {
+        set_meta_metrics_building_snapshot(true);
+        let res = self.do_build_snapshot().await;
+        set_meta_metrics_building_snapshot(false);
+
+        let (snap, last_applied_log, snapshot_id) = match res {
             Err(err) => {
                 incr_raft_storage_fail("build_snapshot", false);
                 return Err(err);
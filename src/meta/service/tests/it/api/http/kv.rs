@@ -0,0 +1,143 @@
+// Copyright 2021 Datafuse Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+use common_base::base::tokio;
+use common_meta_api::KVApi;
+use common_meta_types::SeqV;
+use common_meta_types::UpsertKVReq;
+use databend_meta::api::http::v1::kv::get_handler;
+use databend_meta::api::http::v1::kv::list_handler;
+use databend_meta::api::http::v1::kv::mget_handler;
+use databend_meta::meta_service::MetaNode;
+use poem::get;
+use poem::http::Method;
+use poem::http::StatusCode;
+use poem::http::Uri;
+use poem::post;
+use poem::web::headers::Authorization;
+use poem::Endpoint;
+use poem::EndpointExt;
+use poem::Request;
+use poem::Route;
+use pretty_assertions::assert_eq;
+
+use crate::init_meta_ut;
+use crate::tests::service::MetaSrvTestContext;
+
+#[async_entry::test(worker_threads = 3, init = "init_meta_ut!()", tracing_span = "debug")]
+async fn test_kv_gateway() -> common_exception::Result<()> {
+    let mut tc0 = MetaSrvTestContext::new(0);
+    tc0.config.admin_api_kv_gateway = true;
+    tc0.config.admin_api_kv_gateway_password = "pass".to_string();
+
+    let meta_node = MetaNode::start(&tc0.config).await?;
+    meta_node
+        .join_cluster(&tc0.config.raft_config, tc0.config.grpc_api_address.clone())
+        .await?;
+
+    meta_node
+        .upsert_kv(UpsertKVReq::update("a/1", b"x"))
+        .await?;
+    meta_node
+        .upsert_kv(UpsertKVReq::update("a/2", b"y"))
+        .await?;
+
+    let router = Route::new()
+        .at("/v1/kv/get", get(get_handler))
+        .at("/v1/kv/mget", post(mget_handler))
+        .at("/v1/kv/list", get(list_handler))
+        .data(meta_node.clone())
+        .data(tc0.config.clone());
+
+    // without auth, not as the user of gRPC API, or with a wrong password
+    for auth in [
+        None,
+        Some(Authorization::basic("foo", "pass")),
+        Some(Authorization::basic("root", "")),
+        Some(Authorization::basic("root", "wrong")),
+    ] {
+        let mut builder = Request::builder()
+            .uri(Uri::from_static("/v1/kv/get?key=a/1"))
+            .method(Method::GET);
+        if let Some(auth) = auth {
+            builder = builder.typed_header(auth);
+        }
+        let response = router.call(builder.finish()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // get
+    {
+        let response = router
+            .call(
+                Request::builder()
+                    .uri(Uri::from_static("/v1/kv/get?key=a/1"))
+                    .method(Method::GET)
+                    .typed_header(Authorization::basic("root", "pass"))
+                    .finish(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().into_vec().await.unwrap();
+        let got: Option<SeqV<Vec<u8>>> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(b"x".to_vec(), got.unwrap().data);
+    }
+
+    // mget
+    {
+        let response = router
+            .call(
+                Request::builder()
+                    .uri(Uri::from_static("/v1/kv/mget"))
+                    .method(Method::POST)
+                    .content_type("application/json")
+                    .typed_header(Authorization::basic("root", "pass"))
+                    .body(r#"{"keys": ["a/2", "b"]}"#),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().into_vec().await.unwrap();
+        let got: Vec<Option<SeqV<Vec<u8>>>> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(2, got.len());
+        assert_eq!(b"y".to_vec(), got[0].clone().unwrap().data);
+        assert!(got[1].is_none());
+    }
+
+    // list
+    {
+        let response = router
+            .call(
+                Request::builder()
+                    .uri(Uri::from_static("/v1/kv/list?prefix=a/"))
+                    .method(Method::GET)
+                    .typed_header(Authorization::basic("root", "pass"))
+                    .finish(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().into_vec().await.unwrap();
+        let got: Vec<(String, SeqV<Vec<u8>>)> = serde_json::from_slice(&body).unwrap();
+        let keys = got.into_iter().map(|(k, _)| k).collect::<Vec<_>>();
+        assert_eq!(vec!["a/1".to_string(), "a/2".to_string()], keys);
+    }
+
+    meta_node.stop().await?;
+    Ok(())
+}
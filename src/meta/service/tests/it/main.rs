@@ -21,5 +21,6 @@ mod api;
 mod configs;
 mod grpc;
 mod meta_node;
+mod peer_quarantine;
 mod store;
 mod tests;
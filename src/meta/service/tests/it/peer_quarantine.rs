@@ -0,0 +1,57 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use databend_meta::peer_quarantine::Admission;
+use databend_meta::peer_quarantine::PeerQuarantine;
+
+#[test]
+fn test_peer_quarantine() -> anyhow::Result<()> {
+    let q = PeerQuarantine::new(2, Duration::from_millis(100));
+
+    assert_eq!(Admission::Allow, q.admit(&1));
+
+    // A single failure does not quarantine a peer.
+    q.record_failure(&1);
+    assert_eq!(Admission::Allow, q.admit(&1));
+    assert!(!q.is_quarantined(&1));
+
+    q.record_failure(&1);
+    assert!(q.is_quarantined(&1));
+    assert_eq!(vec![1], q.quarantined_peers());
+    assert_eq!(Admission::Reject, q.admit(&1));
+
+    // Other peers are not affected.
+    assert_eq!(Admission::Allow, q.admit(&2));
+
+    // A probe is allowed once every probe interval.
+    std::thread::sleep(Duration::from_millis(150));
+    assert_eq!(Admission::Probe, q.admit(&1));
+    assert_eq!(Admission::Reject, q.admit(&1));
+
+    // A failed probe keeps it quarantined.
+    q.record_failure(&1);
+    assert!(q.is_quarantined(&1));
+
+    // A successful probe restores it.
+    std::thread::sleep(Duration::from_millis(150));
+    assert_eq!(Admission::Probe, q.admit(&1));
+    q.record_success(&1);
+    assert!(!q.is_quarantined(&1));
+    assert!(q.quarantined_peers().is_empty());
+    assert_eq!(Admission::Allow, q.admit(&1));
+
+    Ok(())
+}
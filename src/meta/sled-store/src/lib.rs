@@ -26,6 +26,7 @@ pub use sled_serde::SledRangeSerde;
 pub use sled_serde::SledSerde;
 pub use sled_tree::AsKeySpace;
 pub use sled_tree::AsTxnKeySpace;
+pub use sled_tree::PreImages;
 pub use sled_tree::SledTree;
 pub use sled_tree::SledValueToKey;
 pub use sled_tree::TransactionSledTree;
@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::marker::PhantomData;
 use std::ops::Bound;
 use std::ops::Deref;
 use std::ops::RangeBounds;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 use common_meta_types::anyerror::AnyError;
 use common_meta_types::error_context::WithContext;
@@ -25,6 +28,7 @@ use common_meta_types::MetaStorageResult;
 use sled::transaction::ConflictableTransactionError;
 use sled::transaction::TransactionResult;
 use sled::transaction::TransactionalTree;
+use sled::IVec;
 use tracing::debug;
 use tracing::warn;
 
@@ -37,6 +41,11 @@ pub trait SledValueToKey<K> {
     fn to_key(&self) -> K;
 }
 
+/// The values of keys before they were first overwritten since a capture started.
+///
+/// `None` means the key did not exist when the capture started.
+pub type PreImages = BTreeMap<IVec, Option<IVec>>;
+
 /// SledTree is a wrapper of sled::Tree that provides access of more than one key-value
 /// types.
 /// A `SledKVType` defines a key-value type to be stored.
@@ -54,6 +63,11 @@ pub struct SledTree {
     sync: bool,
 
     pub tree: sled::Tree,
+
+    /// When it is `Some`, every transactional write records the previous value of the key
+    /// it touches for the first time.
+    /// It is used to restore a point-in-time view while iterating the tree concurrently with writes.
+    pre_images: Arc<Mutex<Option<PreImages>>>,
 }
 
 impl SledTree {
@@ -79,10 +93,29 @@ impl SledTree {
             name: tree_name.to_string(),
             sync,
             tree: t,
+            pre_images: Arc::new(Mutex::new(None)),
         };
         Ok(rl)
     }
 
+    /// Start recording the pre-images of keys written by transactions.
+    ///
+    /// Returns false if a capture is already in progress.
+    pub fn begin_capture_pre_images(&self) -> bool {
+        let mut pre_images = self.pre_images.lock().unwrap();
+        if pre_images.is_some() {
+            return false;
+        }
+        *pre_images = Some(BTreeMap::new());
+        true
+    }
+
+    /// Stop recording and return the pre-images recorded since `begin_capture_pre_images()`.
+    pub fn end_capture_pre_images(&self) -> PreImages {
+        let mut pre_images = self.pre_images.lock().unwrap();
+        pre_images.take().unwrap_or_default()
+    }
+
     /// Borrows the SledTree and creates a wrapper with access limited to a specified key space `KV`.
     pub fn key_space<KV: SledKeySpace>(&self) -> AsKeySpace<KV> {
         AsKeySpace::<KV> {
@@ -114,7 +147,10 @@ impl SledTree {
         let sync = sync && self.sync;
 
         let result: TransactionResult<T, MetaStorageError> = self.tree.transaction(move |tree| {
-            let txn_sled_tree = TransactionSledTree { txn_tree: tree };
+            let txn_sled_tree = TransactionSledTree {
+                txn_tree: tree,
+                pre_images: &self.pre_images,
+            };
             let r = f(txn_sled_tree.clone());
             match r {
                 Ok(r) => {
@@ -511,6 +547,7 @@ impl SledTree {
 #[derive(Clone)]
 pub struct TransactionSledTree<'a> {
     pub txn_tree: &'a TransactionalTree,
+    pre_images: &'a Mutex<Option<PreImages>>,
 }
 
 impl TransactionSledTree<'_> {
@@ -520,6 +557,29 @@ impl TransactionSledTree<'_> {
             phantom: PhantomData,
         }
     }
+
+    /// Record the value of `key` before this write, if a capture is in progress and it is the first write to `key`.
+    ///
+    /// If a transaction is retried or aborted, the recorded value is still the committed one,
+    /// because only the first write of a key is recorded.
+    fn record_pre_image(&self, key: &IVec, prev: &Option<IVec>) {
+        let mut pre_images = self.pre_images.lock().unwrap();
+        if let Some(m) = pre_images.as_mut() {
+            m.entry(key.clone()).or_insert_with(|| prev.clone());
+        }
+    }
+
+    fn insert_ivec(&self, key: IVec, value: IVec) -> Result<Option<IVec>, MetaStorageError> {
+        let prev = self.txn_tree.insert(key.clone(), value)?;
+        self.record_pre_image(&key, &prev);
+        Ok(prev)
+    }
+
+    fn remove_ivec(&self, key: IVec) -> Result<Option<IVec>, MetaStorageError> {
+        let prev = self.txn_tree.remove(key.clone())?;
+        self.record_pre_image(&key, &prev);
+        Ok(prev)
+    }
 }
 
 /// It borrows the internal SledTree with access limited to a specified namespace `KV`.
@@ -540,7 +600,7 @@ impl<'a, KV: SledKeySpace> Store<KV> for AsTxnKeySpace<'a, KV> {
         let k = KV::serialize_key(key)?;
         let v = KV::serialize_value(value)?;
 
-        let prev = self.txn_tree.insert(k, v)?;
+        let prev = self.insert_ivec(k, v)?;
         match prev {
             Some(v) => Ok(Some(KV::deserialize_value(v)?)),
             None => Ok(None),
@@ -559,7 +619,7 @@ impl<'a, KV: SledKeySpace> Store<KV> for AsTxnKeySpace<'a, KV> {
 
     fn remove(&self, key: &KV::K) -> Result<Option<KV::V>, Self::Error> {
         let k = KV::serialize_key(key)?;
-        let removed = self.txn_tree.remove(k)?;
+        let removed = self.remove_ivec(k)?;
 
         match removed {
             Some(v) => Ok(Some(KV::deserialize_value(v)?)),
@@ -581,8 +641,8 @@ impl<'a, KV: SledKeySpace> Store<KV> for AsTxnKeySpace<'a, KV> {
 
         let new_val = f(old_val);
         let _ = match new_val {
-            Some(ref v) => self.txn_tree.insert(key_ivec, KV::serialize_value(v)?)?,
-            None => self.txn_tree.remove(key_ivec)?,
+            Some(ref v) => self.insert_ivec(key_ivec, KV::serialize_value(v)?)?,
+            None => self.remove_ivec(key_ivec)?,
         };
 
         Ok(new_val)
@@ -61,6 +61,8 @@ pub struct NodeInfo {
     pub cpu_nums: u64,
     pub version: u32,
     pub flight_address: String,
+    /// The load of the node reported by its heartbeats, the queries running on it.
+    pub running_queries_count: u64,
 }
 
 impl NodeInfo {
@@ -70,6 +72,7 @@ impl NodeInfo {
             cpu_nums,
             version: 0,
             flight_address,
+            running_queries_count: 0,
         }
     }
 
@@ -43,6 +43,12 @@ pub enum Cmd {
 
     /// Update one or more kv with a transaction.
     Transaction(TxnRequest),
+
+    /// Remove the recent responses of the clients not seen for a long time.
+    ///
+    /// Every client is given with the serial number of its last request,
+    /// it is removed only if the serial number is unchanged.
+    RemoveClientLastResps { clients: Vec<(String, u64)> },
 }
 
 /// Update or insert a general purpose kv store
@@ -81,6 +87,9 @@ impl fmt::Display for Cmd {
             Cmd::Transaction(txn) => {
                 write!(f, "txn:{}", txn)
             }
+            Cmd::RemoveClientLastResps { clients } => {
+                write!(f, "remove_client_last_resps:{} clients", clients.len())
+            }
         }
     }
 }
@@ -25,15 +25,19 @@ mod endpoint;
 pub mod errors;
 mod kv_message;
 mod log_entry;
+mod masking_policy;
 mod match_seq;
 mod message;
+mod network_policy;
 mod operation;
 mod raft_txid;
 mod raft_types;
 mod role_info;
+mod row_access_policy;
 mod seq_errors;
 mod seq_num;
 mod seq_value;
+mod task;
 mod tenant_quota;
 mod user_auth;
 mod user_defined_function;
@@ -45,6 +49,7 @@ mod user_quota;
 mod user_setting;
 mod user_stage;
 mod with;
+mod workload_group;
 
 pub mod error_context;
 mod principal_identity;
@@ -125,6 +130,8 @@ pub use kv_message::MGetKVReq;
 pub use kv_message::UpsertKVReply;
 pub use kv_message::UpsertKVReq;
 pub use log_entry::LogEntry;
+pub use masking_policy::MaskedColumn;
+pub use masking_policy::MaskingPolicy;
 pub use match_seq::MatchSeq;
 pub use match_seq::MatchSeqExt;
 pub use message::ForwardRequest;
@@ -132,6 +139,7 @@ pub use message::ForwardRequestBody;
 pub use message::ForwardResponse;
 pub use message::JoinRequest;
 pub use message::LeaveRequest;
+pub use network_policy::NetworkPolicy;
 pub use operation::GCDroppedDataReply;
 pub use operation::GCDroppedDataReq;
 pub use operation::MetaId;
@@ -155,23 +163,32 @@ pub use protobuf::TxnPutResponse;
 pub use protobuf::TxnReply;
 pub use protobuf::TxnRequest;
 pub use raft_txid::RaftTxId;
+pub use raft_txid::TXID_CLIENT_KEY;
+pub use raft_txid::TXID_SERIAL_KEY;
 pub use raft_types::LogId;
 pub use raft_types::LogIndex;
 pub use raft_types::NodeId;
 pub use raft_types::Term;
 pub use role_info::RoleInfo;
 pub use role_info::RoleInfoSerdeError;
+pub use row_access_policy::RowAccessPolicy;
 pub use seq_errors::ConflictSeq;
 pub use seq_num::SeqNum;
 pub use seq_value::IntoSeqV;
 pub use seq_value::KVMeta;
 pub use seq_value::PbSeqV;
 pub use seq_value::SeqV;
+pub use task::CronSchedule;
+pub use task::Task;
+pub use task::TaskRun;
+pub use task::TaskRunState;
 pub use tenant_quota::TenantQuota;
 pub use user_auth::AuthInfo;
 pub use user_auth::AuthType;
 pub use user_auth::PasswordHashMethod;
+pub use user_defined_function::UDFServer;
 pub use user_defined_function::UserDefinedFunction;
+pub use user_defined_function::UserDefinedFunctionOverload;
 pub use user_grant::GrantEntry;
 pub use user_grant::GrantObject;
 pub use user_grant::UserGrantSet;
@@ -186,3 +203,4 @@ pub use user_setting::UserSetting;
 pub use user_setting::UserSettingValue;
 pub use user_stage::*;
 pub use with::With;
+pub use workload_group::WorkloadGroup;
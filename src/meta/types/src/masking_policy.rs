@@ -0,0 +1,64 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A column a masking policy is attached to.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct MaskedColumn {
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    pub column: String,
+}
+
+/// A masking policy replaces the values of the columns it is attached to by the masking
+/// expression of the parameter, unless the session has one of the exempt roles.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct MaskingPolicy {
+    pub name: String,
+    pub parameter: String,
+    pub body: String,
+    pub exempt_roles: Vec<String>,
+    pub columns: Vec<MaskedColumn>,
+}
+
+impl MaskingPolicy {
+    pub fn is_attached_to(&self, catalog: &str, database: &str, table: &str) -> bool {
+        self.columns
+            .iter()
+            .any(|c| c.catalog == catalog && c.database == database && c.table == table)
+    }
+}
+
+impl TryFrom<Vec<u8>> for MaskingPolicy {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(policy) => Ok(policy),
+            Err(serialize_error) => Err(ErrorCode::IllegalMaskingPolicyFormat(format!(
+                "Cannot deserialize masking policy from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
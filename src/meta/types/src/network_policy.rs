@@ -0,0 +1,108 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::net::IpAddr;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A network policy restricts the client addresses the users can connect from, an entry of the
+/// lists is an IP address or a CIDR block, like `192.168.1.0/24`.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct NetworkPolicy {
+    pub name: String,
+    pub allowed_ip_list: Vec<String>,
+    pub blocked_ip_list: Vec<String>,
+    pub comment: String,
+}
+
+impl NetworkPolicy {
+    pub fn validate(&self) -> Result<()> {
+        for entry in self.allowed_ip_list.iter().chain(&self.blocked_ip_list) {
+            parse_ip_block(entry)?;
+        }
+        Ok(())
+    }
+
+    /// The blocked list wins over the allowed list, an empty allowed list allows all the
+    /// addresses not blocked.
+    pub fn is_allowed(&self, ip: &IpAddr) -> bool {
+        let matches = |list: &[String]| {
+            list.iter().any(|entry| match parse_ip_block(entry) {
+                Ok((block, prefix_len)) => ip_block_contains(&block, prefix_len, ip),
+                Err(_) => false,
+            })
+        };
+        if matches(&self.blocked_ip_list) {
+            return false;
+        }
+        self.allowed_ip_list.is_empty() || matches(&self.allowed_ip_list)
+    }
+}
+
+fn parse_ip_block(entry: &str) -> Result<(IpAddr, u32)> {
+    let illegal = || ErrorCode::IllegalNetworkPolicyFormat(format!("Illegal IP block: {}", entry));
+
+    let (addr, prefix_len) = match entry.trim().split_once('/') {
+        Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+        None => (entry.trim(), None),
+    };
+    let addr = addr.parse::<IpAddr>().map_err(|_| illegal())?;
+    let max_len = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix_len = match prefix_len {
+        None => max_len,
+        Some(prefix_len) => prefix_len.parse::<u32>().map_err(|_| illegal())?,
+    };
+    if prefix_len > max_len {
+        return Err(illegal());
+    }
+    Ok((addr, prefix_len))
+}
+
+fn ip_block_contains(block: &IpAddr, prefix_len: u32, ip: &IpAddr) -> bool {
+    // The IPv4 clients of a dual stack listener have mapped IPv6 addresses.
+    let ip = match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(*ip),
+        _ => *ip,
+    };
+    match (block, ip) {
+        (IpAddr::V4(block), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0);
+            u32::from(*block) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(block), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix_len).unwrap_or(0);
+            u128::from(*block) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
+impl TryFrom<Vec<u8>> for NetworkPolicy {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(policy) => Ok(policy),
+            Err(serialize_error) => Err(ErrorCode::IllegalNetworkPolicyFormat(format!(
+                "Cannot deserialize network policy from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
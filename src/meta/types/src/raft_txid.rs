@@ -17,6 +17,14 @@ use std::fmt::Formatter;
 
 use serde::Deserialize;
 use serde::Serialize;
+use tonic::metadata::MetadataMap;
+use tonic::metadata::MetadataValue;
+
+/// gRPC metadata key of the client id of a [`RaftTxId`].
+pub const TXID_CLIENT_KEY: &str = "txid-client";
+
+/// gRPC metadata key of the serial number of a [`RaftTxId`].
+pub const TXID_SERIAL_KEY: &str = "txid-serial";
 
 /// RaftTxId is the essential info to identify an write operation to raft.
 /// Logs with the same RaftTxId are considered the same and only the first of them will be applied.
@@ -25,8 +33,11 @@ pub struct RaftTxId {
     /// The ID of the client which has sent the request.
     pub client: String,
     /// The serial number of this request.
-    /// TODO(xp): a client must generate consistent `client` and globally unique serial.
-    /// TODO(xp): in this impl the state machine records only one serial, which implies serial must be monotonic incremental for every client.
+    ///
+    /// A client must generate a consistent `client` and a unique serial for every request.
+    /// The state machine remembers a bounded window of recently applied serials for every client,
+    /// thus a retried request is applied at most once, as long as it is retried before
+    /// the window moves past it.
     pub serial: u64,
 }
 
@@ -37,6 +48,23 @@ impl RaftTxId {
             serial,
         }
     }
+
+    /// Attach this txid to the metadata of a gRPC request.
+    pub fn inject_to(&self, metadata: &mut MetadataMap) {
+        if let Ok(client) = MetadataValue::try_from(self.client.as_str()) {
+            metadata.insert(TXID_CLIENT_KEY, client);
+            metadata.insert(TXID_SERIAL_KEY, MetadataValue::from(self.serial));
+        }
+    }
+
+    /// Extract a txid from the metadata of a gRPC request, if there is one.
+    pub fn extract_from(metadata: &MetadataMap) -> Option<Self> {
+        let client = metadata.get(TXID_CLIENT_KEY)?.to_str().ok()?;
+        let serial = metadata.get(TXID_SERIAL_KEY)?.to_str().ok()?;
+        let serial = serial.parse::<u64>().ok()?;
+
+        Some(Self::new(client, serial))
+    }
 }
 
 impl Display for RaftTxId {
@@ -0,0 +1,47 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A row access policy restricts the rows of a table that the sessions of a role can read, the
+/// predicate is added to every scan of the table as a filter.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct RowAccessPolicy {
+    pub name: String,
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    pub role: String,
+    pub predicate: String,
+}
+
+impl TryFrom<Vec<u8>> for RowAccessPolicy {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(policy) => Ok(policy),
+            Err(serialize_error) => Err(ErrorCode::IllegalRowAccessPolicyFormat(format!(
+                "Cannot deserialize row access policy from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use chrono::Datelike;
+use chrono::NaiveDateTime;
+use chrono::Timelike;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::UserIdentity;
+
+/// A statement run by the query nodes on a schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct Task {
+    pub name: String,
+    /// The cron expression of the schedule, in UTC.
+    pub schedule: String,
+    /// The statement run by the task.
+    pub definition: String,
+    /// The task runs as the user who created it.
+    pub owner: UserIdentity,
+    /// The URL the failed runs are posted to, the failures are only logged if it is empty.
+    pub error_notification: String,
+    pub comment: String,
+    /// Seconds since the epoch.
+    pub created_on: u64,
+}
+
+impl Task {
+    pub fn validate(&self) -> Result<()> {
+        CronSchedule::parse(&self.schedule)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<Vec<u8>> for Task {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(task) => Ok(task),
+            Err(serialize_error) => Err(ErrorCode::IllegalTaskFormat(format!(
+                "Cannot deserialize task from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Default)]
+pub enum TaskRunState {
+    #[default]
+    Executing,
+    Succeeded,
+    Failed,
+    /// The previous run of the task is still executing at the fire time.
+    Skipped,
+}
+
+impl fmt::Display for TaskRunState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskRunState::Executing => write!(f, "EXECUTING"),
+            TaskRunState::Succeeded => write!(f, "SUCCEEDED"),
+            TaskRunState::Failed => write!(f, "FAILED"),
+            TaskRunState::Skipped => write!(f, "SKIPPED"),
+        }
+    }
+}
+
+/// A run of a task, the times are seconds since the epoch.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct TaskRun {
+    pub task: String,
+    pub definition: String,
+    /// The fire time of the schedule the run is for.
+    pub scheduled_time: u64,
+    pub start_time: u64,
+    /// 0 if the run is executing.
+    pub end_time: u64,
+    pub state: TaskRunState,
+    pub error: String,
+    /// The query node running the task.
+    pub node: String,
+}
+
+impl TryFrom<Vec<u8>> for TaskRun {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(run) => Ok(run),
+            Err(serialize_error) => Err(ErrorCode::IllegalTaskFormat(format!(
+                "Cannot deserialize task run from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
+
+/// A schedule of five cron fields, `<minute> <hour> <day of month> <month> <day of week>`.
+///
+/// A field is `*` or a comma separated list of values and ranges like `1-5`, each can have a
+/// step like `*/15`. The day of week is in `0-7`, both 0 and 7 are Sunday. If both the day of
+/// month and the day of week are restricted, a day matching either of them matches.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expr: &str) -> Result<CronSchedule> {
+        let fields = expr.split_whitespace().collect::<Vec<_>>();
+        if fields.len() != 5 {
+            return Err(ErrorCode::IllegalTaskFormat(format!(
+                "Illegal schedule '{}', expect `<minute> <hour> <day> <month> <weekday>`",
+                expr
+            )));
+        }
+
+        let mut days_of_week = Self::parse_field(expr, fields[4], 0, 7)?;
+        // Sunday is both 0 and 7.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+        Ok(CronSchedule {
+            minutes: Self::parse_field(expr, fields[0], 0, 59)?,
+            hours: Self::parse_field(expr, fields[1], 0, 23)?,
+            days_of_month: Self::parse_field(expr, fields[2], 1, 31)?,
+            months: Self::parse_field(expr, fields[3], 1, 12)?,
+            days_of_week,
+            any_day_of_month: fields[2].starts_with('*'),
+            any_day_of_week: fields[4].starts_with('*'),
+        })
+    }
+
+    /// Parse a field to the bit set of the values it matches.
+    fn parse_field(expr: &str, field: &str, min: u64, max: u64) -> Result<u64> {
+        let illegal = || {
+            ErrorCode::IllegalTaskFormat(format!(
+                "Illegal field '{}' of schedule '{}', expect values in {}-{}",
+                field, expr, min, max
+            ))
+        };
+        let parse_value = |value: &str| match value.parse::<u64>() {
+            Ok(value) if (min..=max).contains(&value) => Ok(value),
+            _ => Err(illegal()),
+        };
+
+        let mut bits = 0;
+        for item in field.split(',') {
+            let (range, step) = match item.split_once('/') {
+                None => (item, 1),
+                Some((range, step)) => match step.parse::<u64>() {
+                    Ok(step) if step > 0 => (range, step),
+                    _ => return Err(illegal()),
+                },
+            };
+            let (start, end) = match range.split_once('-') {
+                _ if range == "*" => (min, max),
+                Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+                // `<value>/<step>` runs from the value to the maximum
+                None if item.contains('/') => (parse_value(range)?, max),
+                None => (parse_value(range)?, parse_value(range)?),
+            };
+            if start > end {
+                return Err(illegal());
+            }
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+        Ok(bits)
+    }
+
+    /// Whether the minute of the time, in seconds since the epoch, matches the schedule.
+    pub fn matches(&self, time: u64) -> bool {
+        let time = NaiveDateTime::from_timestamp(time as i64, 0);
+        let matches = |bits: u64, value: u32| bits & (1 << value) != 0;
+
+        let day_of_month = matches(self.days_of_month, time.day());
+        let day_of_week = matches(self.days_of_week, time.weekday().num_days_from_sunday());
+        let day = match (self.any_day_of_month, self.any_day_of_week) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        };
+        day && matches(self.minutes, time.minute())
+            && matches(self.hours, time.hour())
+            && matches(self.months, time.month())
+    }
+
+    /// The fire times of the schedule in `(after, until]`, in seconds since the epoch.
+    pub fn fire_times(&self, after: u64, until: u64) -> Vec<u64> {
+        let first_minute = after / 60 * 60 + 60;
+        (first_minute..=until)
+            .step_by(60)
+            .filter(|time| self.matches(*time))
+            .collect()
+    }
+}
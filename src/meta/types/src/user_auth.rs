@@ -22,6 +22,7 @@ const NO_PASSWORD_STR: &str = "no_password";
 const SHA256_PASSWORD_STR: &str = "sha256_password";
 const DOUBLE_SHA1_PASSWORD_STR: &str = "double_sha1_password";
 const JWT_AUTH_STR: &str = "jwt";
+const LDAP_AUTH_STR: &str = "ldap";
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
 pub enum AuthType {
@@ -29,6 +30,7 @@ pub enum AuthType {
     Sha256Password,
     DoubleSha1Password,
     JWT,
+    LDAP,
 }
 
 impl std::str::FromStr for AuthType {
@@ -39,6 +41,7 @@ impl std::str::FromStr for AuthType {
             DOUBLE_SHA1_PASSWORD_STR => Ok(AuthType::DoubleSha1Password),
             NO_PASSWORD_STR => Ok(AuthType::NoPassword),
             JWT_AUTH_STR => Ok(AuthType::JWT),
+            LDAP_AUTH_STR => Ok(AuthType::LDAP),
             _ => Err(ErrorCode::InvalidAuthInfo(AuthType::bad_auth_types(s))),
         }
     }
@@ -51,6 +54,7 @@ impl AuthType {
             AuthType::Sha256Password => SHA256_PASSWORD_STR,
             AuthType::DoubleSha1Password => DOUBLE_SHA1_PASSWORD_STR,
             AuthType::JWT => JWT_AUTH_STR,
+            AuthType::LDAP => LDAP_AUTH_STR,
         }
     }
 
@@ -60,6 +64,7 @@ impl AuthType {
             SHA256_PASSWORD_STR,
             DOUBLE_SHA1_PASSWORD_STR,
             JWT_AUTH_STR,
+            LDAP_AUTH_STR,
         ];
         let all = all
             .iter()
@@ -86,6 +91,8 @@ pub enum AuthInfo {
         hash_method: PasswordHashMethod,
     },
     JWT,
+    /// Authenticated by binding to the LDAP server with the password.
+    LDAP,
 }
 
 fn calc_sha1(v: &[u8]) -> [u8; 20] {
@@ -103,6 +110,7 @@ impl AuthInfo {
         match auth_type {
             AuthType::NoPassword => Ok(AuthInfo::None),
             AuthType::JWT => Ok(AuthInfo::JWT),
+            AuthType::LDAP => Ok(AuthInfo::LDAP),
             AuthType::Sha256Password | AuthType::DoubleSha1Password => match auth_string {
                 Some(p) => {
                     let method = auth_type.get_password_type().unwrap();
@@ -161,6 +169,7 @@ impl AuthInfo {
         match self {
             AuthInfo::None => AuthType::NoPassword,
             AuthInfo::JWT => AuthType::JWT,
+            AuthInfo::LDAP => AuthType::LDAP,
             AuthInfo::Password {
                 hash_value: _,
                 hash_method: t,
@@ -177,7 +186,7 @@ impl AuthInfo {
                 hash_value: p,
                 hash_method: t,
             } => t.to_string(p),
-            AuthInfo::None | AuthInfo::JWT => "".to_string(),
+            AuthInfo::None | AuthInfo::JWT | AuthInfo::LDAP => "".to_string(),
         }
     }
 
@@ -13,6 +13,9 @@
 // limitations under the License.
 
 use std::convert::TryFrom;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::iter::once;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -27,6 +30,47 @@ pub struct UserDefinedFunction {
 
     pub description: String,
     pub definition: String,
+
+    /// Other definitions of the function, each one takes a different number of parameters.
+    pub overloads: Vec<UserDefinedFunctionOverload>,
+
+    /// The function is evaluated by an external UDF server if it is set, instead of being
+    /// expanded to the `definition`.
+    pub server: Option<UDFServer>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct UserDefinedFunctionOverload {
+    pub parameters: Vec<String>,
+    pub definition: String,
+}
+
+/// The external server evaluating a UDF, the batches of arguments are sent to the server by
+/// the Arrow Flight protocol.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct UDFServer {
+    pub address: String,
+    pub handler: String,
+    pub language: String,
+    /// The names of the argument types, e.g. `Int64`, `Nullable(String)`.
+    pub arg_types: Vec<String>,
+    pub return_type: String,
+}
+
+impl Display for UDFServer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({}) RETURNS {} LANGUAGE {} HANDLER = '{}' ADDRESS = '{}'",
+            self.arg_types.join(", "),
+            self.return_type,
+            self.language,
+            self.handler,
+            self.address
+        )
+    }
 }
 
 impl UserDefinedFunction {
@@ -36,7 +80,89 @@ impl UserDefinedFunction {
             parameters,
             definition: definition.to_string(),
             description: description.to_string(),
+            overloads: vec![],
+            server: None,
+        }
+    }
+
+    pub fn new_udf_server(name: &str, server: UDFServer, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters: vec![],
+            definition: server.to_string(),
+            description: description.to_string(),
+            overloads: vec![],
+            server: Some(server),
+        }
+    }
+
+    /// Find the definition which takes `num_args` parameters, returns the parameters and the
+    /// definition expression.
+    pub fn get_overload(&self, num_args: usize) -> Option<(&[String], &str)> {
+        if self.parameters.len() == num_args {
+            return Some((&self.parameters, &self.definition));
+        }
+        self.overloads
+            .iter()
+            .find(|overload| overload.parameters.len() == num_args)
+            .map(|overload| (overload.parameters.as_slice(), overload.definition.as_str()))
+    }
+
+    /// The number of parameters of all the definitions, in ascending order.
+    pub fn arities(&self) -> Vec<usize> {
+        let mut arities = once(self.parameters.len())
+            .chain(self.overloads.iter().map(|v| v.parameters.len()))
+            .collect::<Vec<_>>();
+        arities.sort_unstable();
+        arities
+    }
+
+    /// Add the definition of `other` as an overload, `other` must take a number of parameters
+    /// which is not defined yet.
+    pub fn add_overload(&mut self, other: UserDefinedFunction) -> Result<()> {
+        if self.server.is_some() || other.server.is_some() {
+            return Err(ErrorCode::UdfAlreadyExists(format!(
+                "UDF {} already exists, the functions on UDF servers can not be overloaded",
+                self.name
+            )));
+        }
+        if self.get_overload(other.parameters.len()).is_some() {
+            return Err(ErrorCode::UdfAlreadyExists(format!(
+                "UDF {} with {} parameters already exists",
+                self.name,
+                other.parameters.len()
+            )));
+        }
+        self.overloads.push(UserDefinedFunctionOverload {
+            parameters: other.parameters,
+            definition: other.definition,
+        });
+        Ok(())
+    }
+
+    /// Replace the definition taking the same number of parameters as `other`. A function
+    /// without overloads, including the functions on UDF servers, is replaced entirely.
+    pub fn replace_overload(&mut self, other: UserDefinedFunction) -> Result<()> {
+        let num_args = other.parameters.len();
+        if self.overloads.is_empty() || self.parameters.len() == num_args {
+            self.parameters = other.parameters;
+            self.definition = other.definition;
+            self.server = other.server;
+        } else if let Some(overload) = self
+            .overloads
+            .iter_mut()
+            .find(|overload| overload.parameters.len() == num_args)
+        {
+            overload.parameters = other.parameters;
+            overload.definition = other.definition;
+        } else {
+            return Err(ErrorCode::UnknownUDF(format!(
+                "Unknown Function {} with {} parameters",
+                self.name, num_args
+            )));
         }
+        self.description = other.description;
+        Ok(())
     }
 }
 
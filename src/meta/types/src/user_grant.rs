@@ -26,11 +26,13 @@ pub enum GrantObject {
     Global,
     Database(String, String),
     Table(String, String, String),
+    Stage(String),
+    UDF(String),
 }
 
 impl GrantObject {
     /// Comparing the grant objects, the Database object contains all the Table objects inside it.
-    /// Global object contains all the Database objects.
+    /// Global object contains all the Database, Stage and UDF objects.
     pub fn contains(&self, object: &GrantObject) -> bool {
         match (self, object) {
             (GrantObject::Global, _) => true,
@@ -46,15 +48,20 @@ impl GrantObject {
                 GrantObject::Table(rcat, rhs_db, rhs_table),
             ) => lcat == rcat && (lhs_db == rhs_db) && (lhs_table == rhs_table),
             (GrantObject::Table(_, _, _), _) => false,
+            (GrantObject::Stage(lstage), GrantObject::Stage(rstage)) => lstage == rstage,
+            (GrantObject::UDF(ludf), GrantObject::UDF(rudf)) => ludf == rudf,
+            _ => false,
         }
     }
 
-    /// Global, database and table has different available privileges
+    /// Global, database, table, stage and UDF has different available privileges
     pub fn available_privileges(&self) -> UserPrivilegeSet {
         match self {
             GrantObject::Global => UserPrivilegeSet::available_privileges_on_global(),
             GrantObject::Database(_, _) => UserPrivilegeSet::available_privileges_on_database(),
             GrantObject::Table(_, _, _) => UserPrivilegeSet::available_privileges_on_table(),
+            GrantObject::Stage(_) => UserPrivilegeSet::available_privileges_on_stage(),
+            GrantObject::UDF(_) => UserPrivilegeSet::available_privileges_on_udf(),
         }
     }
 }
@@ -67,6 +74,8 @@ impl fmt::Display for GrantObject {
             GrantObject::Table(ref cat, ref db, ref table) => {
                 write!(f, "'{}'.'{}'.'{}'", cat, db, table)
             }
+            GrantObject::Stage(ref stage) => write!(f, "STAGE '{}'", stage),
+            GrantObject::UDF(ref udf) => write!(f, "UDF '{}'", udf),
         }
     }
 }
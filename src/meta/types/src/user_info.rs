@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use core::fmt;
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 
 use common_exception::ErrorCode;
@@ -96,6 +97,13 @@ pub struct UserOption {
     flags: BitFlags<UserOptionFlag>,
 
     default_role: Option<String>,
+
+    network_policy: Option<String>,
+
+    workload_group: Option<String>,
+
+    /// The settings applied to the sessions of the user, unless set in the session.
+    default_settings: BTreeMap<String, String>,
 }
 
 impl UserOption {
@@ -103,6 +111,9 @@ impl UserOption {
         Self {
             flags,
             default_role: None,
+            network_policy: None,
+            workload_group: None,
+            default_settings: BTreeMap::new(),
         }
     }
 
@@ -120,6 +131,21 @@ impl UserOption {
         self
     }
 
+    pub fn with_network_policy(mut self, network_policy: Option<String>) -> Self {
+        self.network_policy = network_policy;
+        self
+    }
+
+    pub fn with_workload_group(mut self, workload_group: Option<String>) -> Self {
+        self.workload_group = workload_group;
+        self
+    }
+
+    pub fn with_default_settings(mut self, default_settings: BTreeMap<String, String>) -> Self {
+        self.default_settings = default_settings;
+        self
+    }
+
     pub fn with_set_flag(mut self, flag: UserOptionFlag) -> Self {
         self.flags.insert(flag);
         self
@@ -137,6 +163,34 @@ impl UserOption {
         self.default_role = default_role;
     }
 
+    pub fn network_policy(&self) -> Option<&String> {
+        self.network_policy.as_ref()
+    }
+
+    pub fn set_network_policy(&mut self, network_policy: Option<String>) {
+        self.network_policy = network_policy;
+    }
+
+    pub fn workload_group(&self) -> Option<&String> {
+        self.workload_group.as_ref()
+    }
+
+    pub fn set_workload_group(&mut self, workload_group: Option<String>) {
+        self.workload_group = workload_group;
+    }
+
+    pub fn default_settings(&self) -> &BTreeMap<String, String> {
+        &self.default_settings
+    }
+
+    /// Sets the default value of the setting `name` of the user, or removes it if `None`.
+    pub fn set_default_setting(&mut self, name: String, value: Option<String>) {
+        match value {
+            Some(value) => self.default_settings.insert(name, value),
+            None => self.default_settings.remove(&name),
+        };
+    }
+
     pub fn set_all_flag(&mut self) {
         self.flags = BitFlags::all();
     }
@@ -58,6 +58,10 @@ pub enum UserPrivilegeType {
     Grant = 1 << 12,
     // Privilege to Create Stage.
     CreateStage = 1 << 13,
+    // Privilege to read the files of a stage, by COPY INTO <table> FROM or LIST.
+    Read = 1 << 14,
+    // Privilege to write the files of a stage, by COPY INTO <location>, PUT or REMOVE.
+    Write = 1 << 15,
     // TODO: remove this later
     Set = 1 << 4,
 }
@@ -76,6 +80,8 @@ const ALL_PRIVILEGES: BitFlags<UserPrivilegeType> = make_bitflags!(
         | CreateRole
         | Grant
         | CreateStage
+        | Read
+        | Write
         | Set
     }
 );
@@ -95,6 +101,8 @@ impl std::fmt::Display for UserPrivilegeType {
             UserPrivilegeType::CreateUser => "CREATE USER",
             UserPrivilegeType::CreateRole => "CREATE ROLE",
             UserPrivilegeType::CreateStage => "CREATE STAGE",
+            UserPrivilegeType::Read => "READ",
+            UserPrivilegeType::Write => "WRITE",
             UserPrivilegeType::Grant => "GRANT",
             UserPrivilegeType::Set => "SET",
         })
@@ -121,9 +129,9 @@ impl UserPrivilegeSet {
     /// on databases and tables, and has some Global only privileges.
     pub fn available_privileges_on_global() -> Self {
         let database_privs = Self::available_privileges_on_database();
-        let privs =
-            make_bitflags!(UserPrivilegeType::{ Usage | Super | CreateUser | CreateRole | Grant });
-        (database_privs.privileges | privs).into()
+        let stage_privs = Self::available_privileges_on_stage();
+        let privs = make_bitflags!(UserPrivilegeType::{ Usage | Super | CreateUser | CreateRole | Grant | CreateStage });
+        (database_privs.privileges | stage_privs.privileges | privs).into()
     }
 
     /// The availabe privileges on database object contains ALL the available privileges to a table.
@@ -138,6 +146,16 @@ impl UserPrivilegeSet {
         make_bitflags!(UserPrivilegeType::{ Create | Update | Select | Insert | Delete | Drop | Alter | Grant }).into()
     }
 
+    /// The privileges available to a stage object, to read or write the files of it.
+    pub fn available_privileges_on_stage() -> Self {
+        make_bitflags!(UserPrivilegeType::{ Read | Write }).into()
+    }
+
+    /// The privileges available to a UDF object, to call, replace or drop it.
+    pub fn available_privileges_on_udf() -> Self {
+        make_bitflags!(UserPrivilegeType::{ Usage | Alter | Drop }).into()
+    }
+
     // TODO: remove this, as ALL has different meanings on different objects
     pub fn all_privileges() -> Self {
         ALL_PRIVILEGES.into()
@@ -158,6 +158,14 @@ pub struct FileFormatOptions {
     pub field_delimiter: String,
     pub record_delimiter: String,
     pub compression: StageFileCompression,
+    // Character to quote the fields of CSV, `"` if empty.
+    pub quote: String,
+    // Character to escape the quote character in quoted fields of CSV, by doubling it if empty.
+    pub escape: String,
+    // Representation of NULL in CSV and TSV, `NULL` if empty.
+    pub null_display: String,
+    // Pad missing fields with defaults and ignore extra fields, rather than failing the row.
+    pub allow_column_count_mismatch: bool,
 }
 
 impl Default for FileFormatOptions {
@@ -168,6 +176,10 @@ impl Default for FileFormatOptions {
             field_delimiter: ",".to_string(),
             skip_header: 0,
             compression: StageFileCompression::default(),
+            quote: "".to_string(),
+            escape: "".to_string(),
+            null_display: "".to_string(),
+            allow_column_count_mismatch: false,
         }
     }
 }
@@ -221,6 +233,12 @@ pub struct CopyOptions {
     pub on_error: OnErrorMode,
     pub size_limit: usize,
     pub purge: bool,
+    // With `ON_ERROR = CONTINUE`, fail the copy once more rows than this are rejected, 0 for no limit.
+    pub max_errors: u64,
+    // Target size in bytes of each file unloaded by `COPY INTO <location>`, 0 for the default.
+    pub max_file_size: u64,
+    // Unload into a single file regardless of `max_file_size`.
+    pub single: bool,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug, Eq, PartialEq)]
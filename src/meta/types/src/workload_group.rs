@@ -0,0 +1,70 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A workload group limits the resources of the queries of the users assigned to it, 0 means
+/// unlimited for all the limits.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(default)]
+pub struct WorkloadGroup {
+    pub name: String,
+    /// The percentage of the `max_threads` a query runs with, in [1, 100].
+    pub cpu_share: u64,
+    /// The maximum memory usage in bytes of a query.
+    pub memory_limit: u64,
+    /// The maximum number of the running queries of the group.
+    pub max_concurrency: u64,
+    /// The maximum bytes a query scans.
+    pub max_scan_bytes: u64,
+    pub comment: String,
+}
+
+impl WorkloadGroup {
+    pub fn validate(&self) -> Result<()> {
+        if self.cpu_share > 100 {
+            return Err(ErrorCode::IllegalWorkloadGroupFormat(format!(
+                "CPU_SHARE of the workload group {} must be in [1, 100], but got {}",
+                self.name, self.cpu_share
+            )));
+        }
+        Ok(())
+    }
+
+    /// The threads a query of the group runs with, at least one thread.
+    pub fn max_threads(&self, max_threads: u64) -> u64 {
+        match self.cpu_share {
+            0 => max_threads,
+            cpu_share => std::cmp::max(1, max_threads * cpu_share / 100),
+        }
+    }
+}
+
+impl TryFrom<Vec<u8>> for WorkloadGroup {
+    type Error = ErrorCode;
+
+    fn try_from(value: Vec<u8>) -> Result<Self> {
+        match serde_json::from_slice(&value) {
+            Ok(group) => Ok(group),
+            Err(serialize_error) => Err(ErrorCode::IllegalWorkloadGroupFormat(format!(
+                "Cannot deserialize workload group from bytes. cause {}",
+                serialize_error
+            ))),
+        }
+    }
+}
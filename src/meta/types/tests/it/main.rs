@@ -14,12 +14,15 @@
 
 mod cluster;
 mod match_seq;
+mod network_policy;
+mod task;
 mod user_defined_function;
 mod user_grant;
 mod user_info;
 mod user_privilege;
 mod user_quota;
 mod user_stage;
+mod workload_group;
 
 #[test]
 fn test_bin_commit_version() -> anyhow::Result<()> {
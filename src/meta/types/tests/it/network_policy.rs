@@ -0,0 +1,67 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::exception::Result;
+use common_meta_types::NetworkPolicy;
+
+#[test]
+fn test_network_policy() -> Result<()> {
+    let policy = NetworkPolicy {
+        name: "np1".to_string(),
+        allowed_ip_list: vec!["192.168.1.0/24".to_string(), "10.0.0.1".to_string()],
+        blocked_ip_list: vec!["192.168.1.99".to_string()],
+        comment: "".to_string(),
+    };
+    policy.validate()?;
+
+    assert!(policy.is_allowed(&"192.168.1.10".parse().unwrap()));
+    assert!(policy.is_allowed(&"10.0.0.1".parse().unwrap()));
+    assert!(policy.is_allowed(&"::ffff:192.168.1.10".parse().unwrap()));
+    assert!(!policy.is_allowed(&"192.168.1.99".parse().unwrap()));
+    assert!(!policy.is_allowed(&"192.168.2.10".parse().unwrap()));
+    assert!(!policy.is_allowed(&"10.0.0.2".parse().unwrap()));
+    assert!(!policy.is_allowed(&"::1".parse().unwrap()));
+
+    // An empty allowed list allows all the addresses not blocked.
+    let policy = NetworkPolicy {
+        name: "np2".to_string(),
+        allowed_ip_list: vec![],
+        blocked_ip_list: vec!["0.0.0.0/0".to_string()],
+        comment: "".to_string(),
+    };
+    assert!(!policy.is_allowed(&"127.0.0.1".parse().unwrap()));
+    assert!(policy.is_allowed(&"::1".parse().unwrap()));
+
+    Ok(())
+}
+
+#[test]
+fn test_network_policy_validate() -> Result<()> {
+    for entry in [
+        "192.168.1.0/33",
+        "192.168.1",
+        "::1/129",
+        "localhost",
+        "10.0.0.0/a",
+    ] {
+        let policy = NetworkPolicy {
+            name: "np1".to_string(),
+            allowed_ip_list: vec![entry.to_string()],
+            ..Default::default()
+        };
+        assert!(policy.validate().is_err(), "{}", entry);
+    }
+
+    Ok(())
+}
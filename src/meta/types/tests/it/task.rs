@@ -0,0 +1,63 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_exception::exception::Result;
+use common_meta_types::CronSchedule;
+
+// 2022-10-17 09:30:00 UTC, a Monday.
+const MONDAY_0930: u64 = 1665999000;
+
+#[test]
+fn test_cron_schedule() -> Result<()> {
+    let every_minute = CronSchedule::parse("* * * * *")?;
+    assert!(every_minute.matches(MONDAY_0930));
+    assert_eq!(
+        every_minute.fire_times(MONDAY_0930, MONDAY_0930 + 180),
+        vec![MONDAY_0930 + 60, MONDAY_0930 + 120, MONDAY_0930 + 180,]
+    );
+    // the fire time is at the start of the minute
+    assert_eq!(
+        every_minute.fire_times(MONDAY_0930 + 30, MONDAY_0930 + 90),
+        vec![MONDAY_0930 + 60]
+    );
+
+    let quarter_hours = CronSchedule::parse("*/15 9-17 * * 1-5")?;
+    assert!(quarter_hours.matches(MONDAY_0930));
+    assert!(!quarter_hours.matches(MONDAY_0930 + 60));
+    assert!(!quarter_hours.matches(MONDAY_0930 + 9 * 3600));
+    // Sunday
+    assert!(!quarter_hours.matches(MONDAY_0930 - 86400));
+
+    let sundays = CronSchedule::parse("30 9 * * 7")?;
+    assert!(sundays.matches(MONDAY_0930 - 86400));
+    assert!(!sundays.matches(MONDAY_0930));
+
+    // either the day of month or the day of week matches
+    let first_or_monday = CronSchedule::parse("30 9 1 * 1")?;
+    assert!(first_or_monday.matches(MONDAY_0930));
+    assert!(!first_or_monday.matches(MONDAY_0930 + 86400));
+
+    let illegals = [
+        "* * * *",
+        "60 * * * *",
+        "* * 0 * *",
+        "*/0 * * * *",
+        "5-1 * * * *",
+        "a * * * *",
+    ];
+    for illegal in illegals {
+        assert!(CronSchedule::parse(illegal).is_err(), "{}", illegal);
+    }
+
+    Ok(())
+}
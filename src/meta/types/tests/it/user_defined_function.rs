@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_exception::exception::Result;
+use common_meta_types::UDFServer;
 use common_meta_types::UserDefinedFunction;
 
 #[test]
@@ -30,3 +31,80 @@ fn test_udf() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_udf_overloads() -> Result<()> {
+    // The UDFs stored before overloads are supported.
+    let old = r#"{"name":"add1","parameters":["p"],"description":"","definition":"p + 1"}"#;
+    let mut udf = UserDefinedFunction::try_from(old.as_bytes().to_vec())?;
+    assert!(udf.overloads.is_empty());
+    assert_eq!(vec![1], udf.arities());
+
+    udf.add_overload(UserDefinedFunction::new(
+        "add1",
+        vec!["a".to_string(), "b".to_string()],
+        "a + b + 1",
+        "",
+    ))?;
+    assert_eq!(vec![1, 2], udf.arities());
+    assert_eq!(
+        Some((["p".to_string()].as_slice(), "p + 1")),
+        udf.get_overload(1)
+    );
+    assert_eq!(
+        Some((["a".to_string(), "b".to_string()].as_slice(), "a + b + 1")),
+        udf.get_overload(2)
+    );
+    assert!(udf.get_overload(0).is_none());
+
+    let dup = UserDefinedFunction::new("add1", vec!["q".to_string()], "q", "");
+    assert!(udf.add_overload(dup).is_err());
+
+    udf.replace_overload(UserDefinedFunction::new(
+        "add1",
+        vec!["x".to_string(), "y".to_string()],
+        "x * y + 1",
+        "",
+    ))?;
+    assert_eq!(
+        Some((["x".to_string(), "y".to_string()].as_slice(), "x * y + 1")),
+        udf.get_overload(2)
+    );
+    let unknown = UserDefinedFunction::new("add1", vec![], "1", "");
+    assert!(udf.replace_overload(unknown).is_err());
+
+    let ser = serde_json::to_string(&udf)?;
+    let de = UserDefinedFunction::try_from(ser.into_bytes())?;
+    assert_eq!(udf, de);
+
+    Ok(())
+}
+
+#[test]
+fn test_udf_server() -> Result<()> {
+    let server = UDFServer {
+        address: "http://127.0.0.1:8815".to_string(),
+        handler: "gcd".to_string(),
+        language: "python".to_string(),
+        arg_types: vec!["Int32".to_string(), "Int32".to_string()],
+        return_type: "Int32".to_string(),
+    };
+    assert_eq!(
+        "(Int32, Int32) RETURNS Int32 LANGUAGE python HANDLER = 'gcd' ADDRESS = 'http://127.0.0.1:8815'",
+        server.to_string()
+    );
+
+    let mut udf = UserDefinedFunction::new_udf_server("gcd", server, "");
+    let ser = serde_json::to_string(&udf)?;
+    let de = UserDefinedFunction::try_from(ser.into_bytes())?;
+    assert_eq!(udf, de);
+
+    let lambda = UserDefinedFunction::new("gcd", vec!["a".to_string()], "a", "");
+    assert!(udf.add_overload(lambda.clone()).is_err());
+
+    // Replaced by a lambda UDF.
+    udf.replace_overload(lambda.clone())?;
+    assert_eq!(lambda, udf);
+
+    Ok(())
+}
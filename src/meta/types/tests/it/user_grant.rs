@@ -77,6 +77,41 @@ fn test_grant_object_contains() -> Result<()> {
             rhs: GrantObject::Database("default".into(), "db1".into()),
             expect: false,
         },
+        Test {
+            lhs: GrantObject::Global,
+            rhs: GrantObject::Stage("s1".into()),
+            expect: true,
+        },
+        Test {
+            lhs: GrantObject::Stage("s1".into()),
+            rhs: GrantObject::Stage("s1".into()),
+            expect: true,
+        },
+        Test {
+            lhs: GrantObject::Stage("s1".into()),
+            rhs: GrantObject::Stage("s2".into()),
+            expect: false,
+        },
+        Test {
+            lhs: GrantObject::Database("default".into(), "s1".into()),
+            rhs: GrantObject::Stage("s1".into()),
+            expect: false,
+        },
+        Test {
+            lhs: GrantObject::Global,
+            rhs: GrantObject::UDF("f1".into()),
+            expect: true,
+        },
+        Test {
+            lhs: GrantObject::UDF("f1".into()),
+            rhs: GrantObject::UDF("f1".into()),
+            expect: true,
+        },
+        Test {
+            lhs: GrantObject::UDF("f1".into()),
+            rhs: GrantObject::Stage("f1".into()),
+            expect: false,
+        },
     ];
     for t in tests {
         assert_eq!(
@@ -0,0 +1,43 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_exception::exception::Result;
+use common_meta_types::WorkloadGroup;
+
+#[test]
+fn test_workload_group() -> Result<()> {
+    let group = WorkloadGroup {
+        name: "etl".to_string(),
+        cpu_share: 25,
+        ..Default::default()
+    };
+    group.validate()?;
+    assert_eq!(group.max_threads(16), 4);
+    assert_eq!(group.max_threads(2), 1);
+
+    let group = WorkloadGroup {
+        name: "dashboard".to_string(),
+        ..Default::default()
+    };
+    group.validate()?;
+    assert_eq!(group.max_threads(16), 16);
+
+    let group = WorkloadGroup {
+        name: "illegal".to_string(),
+        cpu_share: 101,
+        ..Default::default()
+    };
+    assert!(group.validate().is_err());
+
+    Ok(())
+}
@@ -22,6 +22,7 @@ use common_exception::Result;
 use crate::ast::write_comma_separated_list;
 use crate::ast::write_period_separated_list;
 use crate::ast::Identifier;
+use crate::ast::OrderByExpr;
 use crate::ast::Query;
 use crate::parser::token::Token;
 
@@ -140,6 +141,8 @@ pub enum Expr<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        /// Set if the function is called as a window function, like `SUM(a) OVER (...)`
+        window: Option<WindowSpec<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -255,6 +258,8 @@ pub enum TypeName {
     },
     Object,
     Variant,
+    Geometry,
+    Geography,
     Nullable(Box<TypeName>),
 }
 
@@ -275,6 +280,37 @@ pub enum TrimWhere {
     Trailing,
 }
 
+/// `OVER ([PARTITION BY <expr>, ...] [ORDER BY <expr>, ...] [<frame>])` of a window function
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSpec<'a> {
+    pub partition_by: Vec<Expr<'a>>,
+    pub order_by: Vec<OrderByExpr<'a>>,
+    pub window_frame: Option<WindowFrame>,
+}
+
+/// `{ROWS | RANGE} BETWEEN <bound> AND <bound>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound,
+    pub end_bound: WindowFrameBound,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowFrameBound {
+    CurrentRow,
+    /// `<n> PRECEDING`, or `UNBOUNDED PRECEDING` if `None`
+    Preceding(Option<u64>),
+    /// `<n> FOLLOWING`, or `UNBOUNDED FOLLOWING` if `None`
+    Following(Option<u64>),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BinaryOperator {
     Plus,
@@ -306,6 +342,10 @@ pub enum BinaryOperator {
     BitwiseOr,
     BitwiseAnd,
     BitwiseXor,
+    // `->` operator, gets a field or an element of a JSON value
+    JsonGet,
+    // `->>` operator, gets a field or an element of a JSON value as text
+    JsonGetText,
 }
 
 impl BinaryOperator {
@@ -469,6 +509,12 @@ impl Display for BinaryOperator {
             BinaryOperator::BitwiseXor => {
                 write!(f, "^")
             }
+            BinaryOperator::JsonGet => {
+                write!(f, "->")
+            }
+            BinaryOperator::JsonGetText => {
+                write!(f, "->>")
+            }
         }
     }
 }
@@ -561,6 +607,12 @@ impl Display for TypeName {
             TypeName::Variant => {
                 write!(f, "VARIANT")?;
             }
+            TypeName::Geometry => {
+                write!(f, "GEOMETRY")?;
+            }
+            TypeName::Geography => {
+                write!(f, "GEOGRAPHY")?;
+            }
             TypeName::Nullable(ty) => {
                 write!(f, "{} NULL", ty)?;
             }
@@ -579,6 +631,63 @@ impl Display for TrimWhere {
     }
 }
 
+impl<'a> Display for WindowSpec<'a> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let mut first = true;
+        if !self.partition_by.is_empty() {
+            first = false;
+            write!(f, "PARTITION BY ")?;
+            write_comma_separated_list(f, &self.partition_by)?;
+        }
+        if !self.order_by.is_empty() {
+            if !first {
+                write!(f, " ")?;
+            }
+            first = false;
+            write!(f, "ORDER BY ")?;
+            write_comma_separated_list(f, &self.order_by)?;
+        }
+        if let Some(frame) = &self.window_frame {
+            if !first {
+                write!(f, " ")?;
+            }
+            write!(f, "{frame}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for WindowFrame {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} BETWEEN {} AND {}",
+            self.units, self.start_bound, self.end_bound
+        )
+    }
+}
+
+impl Display for WindowFrameUnits {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            WindowFrameUnits::Rows => "ROWS",
+            WindowFrameUnits::Range => "RANGE",
+        })
+    }
+}
+
+impl Display for WindowFrameBound {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            WindowFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFrameBound::Preceding(Some(n)) => write!(f, "{n} PRECEDING"),
+            WindowFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFrameBound::Following(Some(n)) => write!(f, "{n} FOLLOWING"),
+        }
+    }
+}
+
 impl Display for Literal {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -762,6 +871,7 @@ impl<'a> Display for Expr<'a> {
                 name,
                 args,
                 params,
+                window,
                 ..
             } => {
                 write!(f, "{name}")?;
@@ -776,6 +886,9 @@ impl<'a> Display for Expr<'a> {
                 }
                 write_comma_separated_list(f, args)?;
                 write!(f, ")")?;
+                if let Some(window) = window {
+                    write!(f, " OVER ({window})")?;
+                }
             }
             Expr::Case {
                 operand,
@@ -457,12 +457,35 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<WindowSpec<'ast>>,
     ) {
-        let mut children = Vec::with_capacity(args.len());
+        let mut children = Vec::with_capacity(args.len() + 1);
         for arg in args.iter() {
             self.visit_expr(arg);
             children.push(self.children.pop().unwrap());
         }
+        if let Some(window) = window {
+            let mut window_children =
+                Vec::with_capacity(window.partition_by.len() + window.order_by.len());
+            for expr in window.partition_by.iter() {
+                self.visit_expr(expr);
+                window_children.push(self.children.pop().unwrap());
+            }
+            for order_by in window.order_by.iter() {
+                self.visit_order_by(order_by);
+                window_children.push(self.children.pop().unwrap());
+            }
+            let window_name = match &window.window_frame {
+                Some(frame) => format!("Window {frame}"),
+                None => "Window".to_string(),
+            };
+            let window_format_ctx =
+                AstFormatContext::with_children(window_name, window_children.len());
+            children.push(FormatTreeNode::with_children(
+                window_format_ctx,
+                window_children,
+            ));
+        }
         let node_name = if distinct {
             format!("Function {name}Distinct")
         } else {
@@ -722,6 +745,8 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             ExplainKind::Fragments => "Fragments",
             ExplainKind::Raw => "Raw",
             ExplainKind::Plan => "Plan",
+            ExplainKind::Analyze => "Analyze",
+            ExplainKind::Estimate => "Estimate",
         });
         let format_ctx = AstFormatContext::with_children(name, 1);
         let node = FormatTreeNode::with_children(format_ctx, vec![child]);
@@ -734,6 +759,26 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         children.push(self.children.pop().unwrap());
         self.visit_copy_unit(&copy.dst);
         children.push(self.children.pop().unwrap());
+        if !copy.partition_by.is_empty() {
+            let mut partition_by_children = Vec::with_capacity(copy.partition_by.len());
+            for key in copy.partition_by.iter() {
+                self.visit_expr(&key.expr);
+                let expr_node = self.children.pop().unwrap();
+                let key_name = match &key.alias {
+                    Some(alias) => format!("PartitionKey {}", alias),
+                    None => "PartitionKey".to_string(),
+                };
+                let key_format_ctx = AstFormatContext::with_children(key_name, 1);
+                let key_node = FormatTreeNode::with_children(key_format_ctx, vec![expr_node]);
+                partition_by_children.push(key_node);
+            }
+            let partition_by_name = "PartitionBy".to_string();
+            let partition_by_format_ctx =
+                AstFormatContext::with_children(partition_by_name, partition_by_children.len());
+            let partition_by_node =
+                FormatTreeNode::with_children(partition_by_format_ctx, partition_by_children);
+            children.push(partition_by_node);
+        }
         if !copy.files.is_empty() {
             let mut files_children = Vec::with_capacity(copy.files.len());
             for file in copy.files.iter() {
@@ -781,6 +826,30 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         let size_limit_format_ctx = AstFormatContext::new(size_limit_name);
         let size_limit_node = FormatTreeNode::new(size_limit_format_ctx);
         children.push(size_limit_node);
+        if !copy.on_error.is_empty() {
+            let on_error_name = format!("OnError {}", copy.on_error);
+            let on_error_format_ctx = AstFormatContext::new(on_error_name);
+            let on_error_node = FormatTreeNode::new(on_error_format_ctx);
+            children.push(on_error_node);
+        }
+        if copy.max_errors != 0 {
+            let max_errors_name = format!("MaxErrors {}", copy.max_errors);
+            let max_errors_format_ctx = AstFormatContext::new(max_errors_name);
+            let max_errors_node = FormatTreeNode::new(max_errors_format_ctx);
+            children.push(max_errors_node);
+        }
+        if copy.max_file_size != 0 {
+            let max_file_size_name = format!("MaxFileSize {}", copy.max_file_size);
+            let max_file_size_format_ctx = AstFormatContext::new(max_file_size_name);
+            let max_file_size_node = FormatTreeNode::new(max_file_size_format_ctx);
+            children.push(max_file_size_node);
+        }
+        if copy.single {
+            let single_name = format!("Single {}", copy.single);
+            let single_format_ctx = AstFormatContext::new(single_name);
+            let single_node = FormatTreeNode::new(single_format_ctx);
+            children.push(single_node);
+        }
 
         let purge_name = format!("Purge {}", copy.purge);
         let purge_name_ctx = AstFormatContext::new(purge_name);
@@ -1158,6 +1227,28 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             let engine_node = FormatTreeNode::new(engine_format_ctx);
             children.push(engine_node);
         }
+        if let Some(external) = &stmt.external {
+            let location_name = format!("Location @{}{}", external.stage, external.path);
+            let location_format_ctx = AstFormatContext::new(location_name);
+            children.push(FormatTreeNode::new(location_format_ctx));
+            if !external.partition_by.is_empty() {
+                let partition_by_children = external
+                    .partition_by
+                    .iter()
+                    .map(|column| {
+                        FormatTreeNode::new(AstFormatContext::new(format!("Column {}", column)))
+                    })
+                    .collect::<Vec<_>>();
+                let partition_by_format_ctx = AstFormatContext::with_children(
+                    "PartitionBy".to_string(),
+                    partition_by_children.len(),
+                );
+                children.push(FormatTreeNode::with_children(
+                    partition_by_format_ctx,
+                    partition_by_children,
+                ));
+            }
+        }
         if !stmt.cluster_by.is_empty() {
             let mut cluster_by_children = Vec::with_capacity(stmt.cluster_by.len());
             for cluster_by in stmt.cluster_by.iter() {
@@ -1237,6 +1328,18 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_attach_table(&mut self, stmt: &'ast AttachTableStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+        let prefix_format_ctx = AstFormatContext::new(format!("From '{}'", stmt.storage_prefix));
+        let prefix_child = FormatTreeNode::new(prefix_format_ctx);
+
+        let name = "AttachTable".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![table_child, prefix_child]);
+        self.children.push(node);
+    }
+
     fn visit_drop_table(&mut self, stmt: &'ast DropTableStmt<'ast>) {
         self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
         let child = self.children.pop().unwrap();
@@ -1294,6 +1397,41 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                     AstFormatContext::with_children(action_name, children.len());
                 FormatTreeNode::with_children(action_format_ctx, children)
             }
+            AlterTableAction::SetColumnMaskingPolicy { column, policy } => {
+                let action_name = format!("Action SetColumnMaskingPolicy {} {}", column, policy);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
+            AlterTableAction::UnsetColumnMaskingPolicy { column } => {
+                let action_name = format!("Action UnsetColumnMaskingPolicy {}", column);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
+            AlterTableAction::AddColumn { column } => {
+                self.visit_column_definition(column);
+                let column_child = self.children.pop().unwrap();
+                let action_name = "Action AddColumn".to_string();
+                let action_format_ctx = AstFormatContext::with_children(action_name, 1);
+                FormatTreeNode::with_children(action_format_ctx, vec![column_child])
+            }
+            AlterTableAction::DropColumn { column } => {
+                let action_name = format!("Action DropColumn {}", column);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                let action_name = format!("Action RenameColumn {} {}", old_column, new_column);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
+            AlterTableAction::ModifyColumn { column, data_type } => {
+                let action_name = format!("Action ModifyColumn {} {}", column, data_type);
+                let action_format_ctx = AstFormatContext::new(action_name);
+                FormatTreeNode::new(action_format_ctx)
+            }
         };
 
         let name = "AlterTable".to_string();
@@ -1324,6 +1462,18 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_flashback_table(&mut self, stmt: &'ast FlashbackTableStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+        self.visit_time_travel_point(&stmt.point);
+        let point_child = self.children.pop().unwrap();
+
+        let name = "FlashbackTable".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![table_child, point_child]);
+        self.children.push(node);
+    }
+
     fn visit_optimize_table(&mut self, stmt: &'ast OptimizeTableStmt<'ast>) {
         let mut children = Vec::new();
         self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
@@ -1340,6 +1490,16 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_analyze_table(&mut self, stmt: &'ast AnalyzeTableStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let child = self.children.pop().unwrap();
+
+        let name = "AnalyzeTable".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_exists_table(&mut self, stmt: &'ast ExistsTableStmt<'ast>) {
         self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
         let child = self.children.pop().unwrap();
@@ -1384,6 +1544,126 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_create_stream(&mut self, stmt: &'ast CreateStreamStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.stream);
+        let stream_child = self.children.pop().unwrap();
+        self.visit_table_ref(&None, &stmt.table_database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+
+        let name = "CreateStream".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![stream_child, table_child]);
+        self.children.push(node);
+    }
+
+    fn visit_drop_stream(&mut self, stmt: &'ast DropStreamStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.stream);
+        let child = self.children.pop().unwrap();
+
+        let name = "DropStream".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_create_aggregating_index(&mut self, stmt: &'ast CreateAggregatingIndexStmt<'ast>) {
+        let index_format_ctx = AstFormatContext::new(format!("Index {}", stmt.index_name));
+        let index_child = FormatTreeNode::new(index_format_ctx);
+        self.visit_query(&stmt.query);
+        let query_child = self.children.pop().unwrap();
+
+        let name = "CreateAggregatingIndex".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![index_child, query_child]);
+        self.children.push(node);
+    }
+
+    fn visit_drop_aggregating_index(&mut self, stmt: &'ast DropAggregatingIndexStmt<'ast>) {
+        let index_format_ctx = AstFormatContext::new(format!("Index {}", stmt.index_name));
+        let index_child = FormatTreeNode::new(index_format_ctx);
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+
+        let name = "DropAggregatingIndex".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![index_child, table_child]);
+        self.children.push(node);
+    }
+
+    fn visit_create_index(&mut self, stmt: &'ast CreateIndexStmt<'ast>) {
+        let index_format_ctx = AstFormatContext::new(format!(
+            "Index {} TYPE {}",
+            stmt.index_name, stmt.index_type
+        ));
+        let index_child = FormatTreeNode::new(index_format_ctx);
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+        let mut children = vec![index_child, table_child];
+        for column in stmt.columns.iter() {
+            self.visit_identifier(column);
+            children.push(self.children.pop().unwrap());
+        }
+        if let Some(tokenizer) = &stmt.tokenizer {
+            let tokenizer_format_ctx = AstFormatContext::new(format!("Tokenizer {}", tokenizer));
+            children.push(FormatTreeNode::new(tokenizer_format_ctx));
+        }
+
+        let name = "CreateIndex".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_index(&mut self, stmt: &'ast DropIndexStmt<'ast>) {
+        let index_format_ctx = AstFormatContext::new(format!("Index {}", stmt.index_name));
+        let index_child = FormatTreeNode::new(index_format_ctx);
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let table_child = self.children.pop().unwrap();
+
+        let name = "DropIndex".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 2);
+        let node = FormatTreeNode::with_children(format_ctx, vec![index_child, table_child]);
+        self.children.push(node);
+    }
+
+    fn visit_create_virtual_columns(&mut self, stmt: &'ast CreateVirtualColumnsStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let mut children = vec![self.children.pop().unwrap()];
+        for virtual_column in stmt.virtual_columns.iter() {
+            self.visit_expr(virtual_column);
+            children.push(self.children.pop().unwrap());
+        }
+
+        let name = "CreateVirtualColumns".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_alter_virtual_columns(&mut self, stmt: &'ast AlterVirtualColumnsStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let mut children = vec![self.children.pop().unwrap()];
+        for virtual_column in stmt.virtual_columns.iter() {
+            self.visit_expr(virtual_column);
+            children.push(self.children.pop().unwrap());
+        }
+
+        let name = "AlterVirtualColumns".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_virtual_columns(&mut self, stmt: &'ast DropVirtualColumnsStmt<'ast>) {
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        let child = self.children.pop().unwrap();
+
+        let name = "DropVirtualColumns".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_show_users(&mut self) {
         let name = "ShowUsers".to_string();
         let format_ctx = AstFormatContext::new(name);
@@ -1511,6 +1791,20 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_set_role(&mut self, role_name: &'ast Option<String>) {
+        let role_name = match role_name {
+            Some(role_name) => format!("Role {}", role_name),
+            None => "Role ALL".to_string(),
+        };
+        let role_format_ctx = AstFormatContext::new(role_name);
+        let child = FormatTreeNode::new(role_format_ctx);
+
+        let name = "SetRole".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_grant(&mut self, grant: &'ast GrantStmt) {
         let source_child = match &grant.source {
             AccountMgrSource::Role { role } => {
@@ -1647,6 +1941,47 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_create_udf_server(&mut self, stmt: &'ast CreateUDFServerStmt<'ast>) {
+        let mut children = Vec::new();
+        let udf_name_format_ctx =
+            AstFormatContext::new(format!("UdfNameIdentifier {}", stmt.udf_name));
+        children.push(FormatTreeNode::new(udf_name_format_ctx));
+        if !stmt.arg_types.is_empty() {
+            let arg_types_children = stmt
+                .arg_types
+                .iter()
+                .map(|arg_type| FormatTreeNode::new(AstFormatContext::new(arg_type.to_string())))
+                .collect::<Vec<_>>();
+            let arg_types_name = "UdfArgTypes".to_string();
+            let arg_types_format_ctx =
+                AstFormatContext::with_children(arg_types_name, arg_types_children.len());
+            children.push(FormatTreeNode::with_children(
+                arg_types_format_ctx,
+                arg_types_children,
+            ));
+        }
+        let properties = [
+            ("UdfReturnType", stmt.return_type.to_string()),
+            ("UdfLanguage", stmt.language.to_string()),
+            ("UdfHandler", stmt.handler.clone()),
+            ("UdfAddress", stmt.address.clone()),
+        ];
+        for (property_name, property) in properties {
+            let property_format_ctx = AstFormatContext::new(format!("{property_name} {property}"));
+            children.push(FormatTreeNode::new(property_format_ctx));
+        }
+        if let Some(description) = &stmt.description {
+            let description_name = format!("UdfDescription {}", description);
+            let description_format_ctx = AstFormatContext::new(description_name);
+            children.push(FormatTreeNode::new(description_format_ctx));
+        }
+
+        let name = "CreateUdfServer".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
     fn visit_drop_udf(&mut self, _if_exists: bool, udf_name: &'ast Identifier<'ast>) {
         let udf_name_format_ctx = AstFormatContext::new(format!("UdfIdentifier {}", udf_name));
         let child = FormatTreeNode::new(udf_name_format_ctx);
@@ -1817,6 +2152,210 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_create_row_access_policy(&mut self, stmt: &'ast CreateRowAccessPolicyStmt<'ast>) {
+        let mut children = Vec::with_capacity(4);
+        let policy_format_ctx = AstFormatContext::new(format!("Policy {}", stmt.name));
+        children.push(FormatTreeNode::new(policy_format_ctx));
+        self.visit_table_ref(&stmt.catalog, &stmt.database, &stmt.table);
+        children.push(self.children.pop().unwrap());
+        let role_format_ctx = AstFormatContext::new(format!("Role {}", stmt.role_name));
+        children.push(FormatTreeNode::new(role_format_ctx));
+        self.visit_expr(&stmt.predicate);
+        let predicate_child = self.children.pop().unwrap();
+        let predicate_format_ctx = AstFormatContext::with_children("Predicate".to_string(), 1);
+        children.push(FormatTreeNode::with_children(predicate_format_ctx, vec![
+            predicate_child,
+        ]));
+
+        let name = "CreateRowAccessPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_row_access_policy(&mut self, stmt: &'ast DropRowAccessPolicyStmt<'ast>) {
+        let policy_format_ctx = AstFormatContext::new(format!("Policy {}", stmt.name));
+        let child = FormatTreeNode::new(policy_format_ctx);
+
+        let name = "DropRowAccessPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_show_row_access_policies(&mut self) {
+        let name = "ShowRowAccessPolicies".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
+    fn visit_create_masking_policy(&mut self, stmt: &'ast CreateMaskingPolicyStmt<'ast>) {
+        let mut children = Vec::with_capacity(4);
+        let policy_format_ctx = AstFormatContext::new(format!("Policy {}", stmt.name));
+        children.push(FormatTreeNode::new(policy_format_ctx));
+        let parameter_format_ctx = AstFormatContext::new(format!("Parameter {}", stmt.parameter));
+        children.push(FormatTreeNode::new(parameter_format_ctx));
+        self.visit_expr(&stmt.body);
+        let body_child = self.children.pop().unwrap();
+        let body_format_ctx = AstFormatContext::with_children("Body".to_string(), 1);
+        children.push(FormatTreeNode::with_children(body_format_ctx, vec![
+            body_child,
+        ]));
+        if !stmt.exempt_roles.is_empty() {
+            let roles_format_ctx =
+                AstFormatContext::new(format!("ExemptRoles {}", stmt.exempt_roles.join(", ")));
+            children.push(FormatTreeNode::new(roles_format_ctx));
+        }
+
+        let name = "CreateMaskingPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_masking_policy(&mut self, stmt: &'ast DropMaskingPolicyStmt<'ast>) {
+        let policy_format_ctx = AstFormatContext::new(format!("Policy {}", stmt.name));
+        let child = FormatTreeNode::new(policy_format_ctx);
+
+        let name = "DropMaskingPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_show_masking_policies(&mut self) {
+        let name = "ShowMaskingPolicies".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
+    fn visit_create_network_policy(&mut self, stmt: &'ast CreateNetworkPolicyStmt<'ast>) {
+        let mut children = Vec::with_capacity(4);
+        let policy_format_ctx = AstFormatContext::new(format!("Policy {}", stmt.name));
+        children.push(FormatTreeNode::new(policy_format_ctx));
+        let allowed_format_ctx =
+            AstFormatContext::new(format!("AllowedIpList {}", stmt.allowed_ip_list.join(", ")));
+        children.push(FormatTreeNode::new(allowed_format_ctx));
+        if !stmt.blocked_ip_list.is_empty() {
+            let blocked_format_ctx =
+                AstFormatContext::new(format!("BlockedIpList {}", stmt.blocked_ip_list.join(", ")));
+            children.push(FormatTreeNode::new(blocked_format_ctx));
+        }
+        if let Some(comment) = &stmt.comment {
+            let comment_format_ctx = AstFormatContext::new(format!("Comment {}", comment));
+            children.push(FormatTreeNode::new(comment_format_ctx));
+        }
+
+        let name = "CreateNetworkPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_network_policy(&mut self, stmt: &'ast DropNetworkPolicyStmt<'ast>) {
+        let policy_format_ctx = AstFormatContext::new(format!("Policy {}", stmt.name));
+        let child = FormatTreeNode::new(policy_format_ctx);
+
+        let name = "DropNetworkPolicy".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_show_network_policies(&mut self) {
+        let name = "ShowNetworkPolicies".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
+    fn visit_create_workload_group(&mut self, stmt: &'ast CreateWorkloadGroupStmt<'ast>) {
+        let mut children = Vec::with_capacity(6);
+        let group_format_ctx = AstFormatContext::new(format!("Group {}", stmt.name));
+        children.push(FormatTreeNode::new(group_format_ctx));
+        let limits = [
+            ("CpuShare", stmt.cpu_share),
+            ("MemoryLimit", stmt.memory_limit),
+            ("MaxConcurrency", stmt.max_concurrency),
+            ("MaxScanBytes", stmt.max_scan_bytes),
+        ];
+        for (limit_name, limit) in limits {
+            if let Some(limit) = limit {
+                let limit_format_ctx = AstFormatContext::new(format!("{} {}", limit_name, limit));
+                children.push(FormatTreeNode::new(limit_format_ctx));
+            }
+        }
+        if let Some(comment) = &stmt.comment {
+            let comment_format_ctx = AstFormatContext::new(format!("Comment {}", comment));
+            children.push(FormatTreeNode::new(comment_format_ctx));
+        }
+
+        let name = "CreateWorkloadGroup".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_workload_group(&mut self, stmt: &'ast DropWorkloadGroupStmt<'ast>) {
+        let group_format_ctx = AstFormatContext::new(format!("Group {}", stmt.name));
+        let child = FormatTreeNode::new(group_format_ctx);
+
+        let name = "DropWorkloadGroup".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_show_workload_groups(&mut self) {
+        let name = "ShowWorkloadGroups".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
+    fn visit_create_task(&mut self, stmt: &'ast CreateTaskStmt<'ast>) {
+        let mut children = Vec::with_capacity(5);
+        let task_format_ctx = AstFormatContext::new(format!("Task {}", stmt.name));
+        children.push(FormatTreeNode::new(task_format_ctx));
+        let schedule_format_ctx = AstFormatContext::new(format!("Schedule {}", stmt.schedule));
+        children.push(FormatTreeNode::new(schedule_format_ctx));
+        if let Some(error_notification) = &stmt.error_notification {
+            let notification_format_ctx =
+                AstFormatContext::new(format!("ErrorNotification {}", error_notification));
+            children.push(FormatTreeNode::new(notification_format_ctx));
+        }
+        if let Some(comment) = &stmt.comment {
+            let comment_format_ctx = AstFormatContext::new(format!("Comment {}", comment));
+            children.push(FormatTreeNode::new(comment_format_ctx));
+        }
+        self.visit_statement(&stmt.statement);
+        children.push(self.children.pop().unwrap());
+
+        let name = "CreateTask".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
+    fn visit_drop_task(&mut self, stmt: &'ast DropTaskStmt<'ast>) {
+        let task_format_ctx = AstFormatContext::new(format!("Task {}", stmt.name));
+        let child = FormatTreeNode::new(task_format_ctx);
+
+        let name = "DropTask".to_string();
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
+    fn visit_show_tasks(&mut self) {
+        let name = "ShowTasks".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
     fn visit_create_share(&mut self, stmt: &'ast CreateShareStmt<'ast>) {
         let mut children = Vec::new();
         let share_format_ctx = AstFormatContext::new(format!("ShareIdentifier {}", stmt.share));
@@ -1932,6 +2471,27 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_begin(&mut self) {
+        let name = "Begin".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
+    fn visit_commit(&mut self) {
+        let name = "Commit".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
+    fn visit_rollback(&mut self) {
+        let name = "Rollback".to_string();
+        let format_ctx = AstFormatContext::new(name);
+        let node = FormatTreeNode::new(format_ctx);
+        self.children.push(node);
+    }
+
     fn visit_with(&mut self, with: &'ast With<'ast>) {
         let mut children = Vec::with_capacity(with.ctes.len());
         for cte in with.ctes.iter() {
@@ -2087,6 +2647,7 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                 table,
                 alias,
                 travel_point,
+                sample,
             } => {
                 let mut name = String::new();
                 name.push_str("TableIdentifier ");
@@ -2105,6 +2666,10 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                     self.visit_time_travel_point(travel_point);
                     children.push(self.children.pop().unwrap());
                 }
+                if let Some(sample) = sample {
+                    let sample_format_ctx = AstFormatContext::new(sample.to_string());
+                    children.push(FormatTreeNode::new(sample_format_ctx));
+                }
                 let format_ctx = if let Some(alias) = alias {
                     AstFormatContext::with_children_alias(
                         name,
@@ -2119,12 +2684,17 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             }
             TableReference::Subquery {
                 span: _,
+                lateral,
                 subquery,
                 alias,
             } => {
                 self.visit_query(subquery);
                 let child = self.children.pop().unwrap();
-                let name = "Subquery".to_string();
+                let name = if *lateral {
+                    "LateralSubquery".to_string()
+                } else {
+                    "Subquery".to_string()
+                };
                 let format_ctx = if let Some(alias) = alias {
                     AstFormatContext::with_children_alias(name, 1, Some(format!("{}", alias)))
                 } else {
@@ -2135,6 +2705,7 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             }
             TableReference::TableFunction {
                 span: _,
+                lateral,
                 name,
                 params,
                 alias,
@@ -2144,7 +2715,11 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
                     self.visit_expr(param);
                     children.push(self.children.pop().unwrap());
                 }
-                let func_name = format!("TableFunction {}", name);
+                let func_name = if *lateral {
+                    format!("LateralTableFunction {}", name)
+                } else {
+                    format!("TableFunction {}", name)
+                };
                 let format_ctx = if let Some(alias) = alias {
                     AstFormatContext::with_children_alias(
                         func_name,
@@ -180,6 +180,30 @@ pub(crate) fn pretty_alter_table_action(action: AlterTableAction) -> RcDoc {
             } else {
                 RcDoc::nil()
             }),
+        AlterTableAction::SetColumnMaskingPolicy { column, policy } => RcDoc::line()
+            .append(RcDoc::text(format!("MODIFY COLUMN {column}")))
+            .append(RcDoc::line().nest(NEST_FACTOR))
+            .append(RcDoc::text(format!("SET MASKING POLICY {policy}"))),
+        AlterTableAction::UnsetColumnMaskingPolicy { column } => RcDoc::line()
+            .append(RcDoc::text(format!("MODIFY COLUMN {column}")))
+            .append(RcDoc::line().nest(NEST_FACTOR))
+            .append(RcDoc::text("UNSET MASKING POLICY")),
+        AlterTableAction::AddColumn { column } => RcDoc::line()
+            .append(RcDoc::text("ADD COLUMN "))
+            .append(RcDoc::text(column.to_string())),
+        AlterTableAction::DropColumn { column } => RcDoc::line()
+            .append(RcDoc::text("DROP COLUMN "))
+            .append(RcDoc::text(column.to_string())),
+        AlterTableAction::RenameColumn {
+            old_column,
+            new_column,
+        } => RcDoc::line()
+            .append(RcDoc::text(format!("RENAME COLUMN {old_column}")))
+            .append(RcDoc::line().nest(NEST_FACTOR))
+            .append(RcDoc::text(format!("TO {new_column}"))),
+        action @ AlterTableAction::ModifyColumn { .. } => {
+            RcDoc::line().append(RcDoc::text(action.to_string()))
+        }
     }
 }
 
@@ -220,6 +220,7 @@ pub(crate) fn pretty_expr(expr: Expr) -> RcDoc {
             name,
             args,
             params,
+            window,
             ..
         } => RcDoc::text(name.to_string())
             .append(if !params.is_empty() {
@@ -240,7 +241,12 @@ pub(crate) fn pretty_expr(expr: Expr) -> RcDoc {
                 RcDoc::nil()
             })
             .append(inline_comma(args.into_iter().map(pretty_expr)))
-            .append(RcDoc::text(")")),
+            .append(RcDoc::text(")"))
+            .append(if let Some(window) = window {
+                RcDoc::text(format!(" OVER ({window})"))
+            } else {
+                RcDoc::nil()
+            }),
         Expr::Case {
             operand,
             conditions,
@@ -202,6 +202,7 @@ pub(crate) fn pretty_table(table: TableReference) -> RcDoc {
             table,
             alias,
             travel_point,
+            sample,
         } => if let Some(catalog) = catalog {
             RcDoc::text(catalog.to_string()).append(RcDoc::text("."))
         } else {
@@ -224,30 +225,48 @@ pub(crate) fn pretty_table(table: TableReference) -> RcDoc {
             RcDoc::text(format!(" AS {alias}"))
         } else {
             RcDoc::nil()
+        })
+        .append(if let Some(sample) = sample {
+            RcDoc::text(format!(" {sample}"))
+        } else {
+            RcDoc::nil()
         }),
         TableReference::Subquery {
             span: _,
+            lateral,
             subquery,
             alias,
-        } => parenthenized(pretty_query(*subquery)).append(if let Some(alias) = alias {
+        } => if lateral {
+            RcDoc::text("LATERAL ")
+        } else {
+            RcDoc::nil()
+        }
+        .append(parenthenized(pretty_query(*subquery)))
+        .append(if let Some(alias) = alias {
             RcDoc::text(format!(" AS {alias}"))
         } else {
             RcDoc::nil()
         }),
         TableReference::TableFunction {
             span: _,
+            lateral,
             name,
             params,
             alias,
-        } => RcDoc::text(name.to_string())
-            .append(RcDoc::text("("))
-            .append(inline_comma(params.into_iter().map(pretty_expr)))
-            .append(RcDoc::text(")"))
-            .append(if let Some(alias) = alias {
-                RcDoc::text(format!(" AS {alias}"))
-            } else {
-                RcDoc::nil()
-            }),
+        } => if lateral {
+            RcDoc::text("LATERAL ")
+        } else {
+            RcDoc::nil()
+        }
+        .append(RcDoc::text(name.to_string()))
+        .append(RcDoc::text("("))
+        .append(inline_comma(params.into_iter().map(pretty_expr)))
+        .append(RcDoc::text(")"))
+        .append(if let Some(alias) = alias {
+            RcDoc::text(format!(" AS {alias}"))
+        } else {
+            RcDoc::nil()
+        }),
         TableReference::Join { span: _, join } => pretty_table(*join.left)
             .append(RcDoc::line())
             .append(if join.condition == JoinCondition::Natural {
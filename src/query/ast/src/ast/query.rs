@@ -142,6 +142,31 @@ pub enum TimeTravelPoint<'a> {
     Timestamp(Box<Expr<'a>>),
 }
 
+/// `TABLESAMPLE <method> (<percent>)`, reads a fraction of a table
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableSample {
+    pub method: SampleMethod,
+    // The percentage of the blocks or rows to read, in [0, 100]
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleMethod {
+    // Read each block with the probability
+    System,
+    // Read each row with the probability
+    Bernoulli,
+}
+
+impl Display for TableSample {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.method {
+            SampleMethod::System => write!(f, "TABLESAMPLE SYSTEM ({})", self.percent),
+            SampleMethod::Bernoulli => write!(f, "TABLESAMPLE BERNOULLI ({})", self.percent),
+        }
+    }
+}
+
 /// A table name or a parenthesized subquery with an optional alias
 #[derive(Debug, Clone, PartialEq)]
 pub enum TableReference<'a> {
@@ -153,17 +178,21 @@ pub enum TableReference<'a> {
         table: Identifier<'a>,
         alias: Option<TableAlias<'a>>,
         travel_point: Option<TimeTravelPoint<'a>>,
+        sample: Option<TableSample>,
     },
-    // `TABLE(expr)[ AS alias ]`
+    // `[LATERAL] TABLE(expr)[ AS alias ]`
     TableFunction {
         span: &'a [Token<'a>],
+        lateral: bool,
         name: Identifier<'a>,
         params: Vec<Expr<'a>>,
         alias: Option<TableAlias<'a>>,
     },
-    // Derived table, which can be a subquery or joined tables or combination of them
+    // Derived table, which can be a subquery or joined tables or combination of them.
+    // A `LATERAL` one can reference the columns of the preceding tables in the FROM clause.
     Subquery {
         span: &'a [Token<'a>],
+        lateral: bool,
         subquery: Box<Query<'a>>,
         alias: Option<TableAlias<'a>>,
     },
@@ -206,6 +235,17 @@ pub enum JoinCondition<'a> {
     None,
 }
 
+impl<'a> TableReference<'a> {
+    /// Whether the table reference can see the columns of the preceding tables.
+    pub fn is_lateral(&self) -> bool {
+        matches!(
+            self,
+            TableReference::Subquery { lateral: true, .. }
+                | TableReference::TableFunction { lateral: true, .. }
+        )
+    }
+}
+
 impl<'a> Display for OrderByExpr<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.expr)?;
@@ -249,6 +289,7 @@ impl<'a> Display for TableReference<'a> {
                 table,
                 alias,
                 travel_point,
+                sample,
             } => {
                 write_period_separated_list(
                     f,
@@ -266,13 +307,21 @@ impl<'a> Display for TableReference<'a> {
                 if let Some(alias) = alias {
                     write!(f, " AS {alias}")?;
                 }
+
+                if let Some(sample) = sample {
+                    write!(f, " {sample}")?;
+                }
             }
             TableReference::TableFunction {
                 span: _,
+                lateral,
                 name,
                 params,
                 alias,
             } => {
+                if *lateral {
+                    write!(f, "LATERAL ")?;
+                }
                 write!(f, "{name}(")?;
                 write_comma_separated_list(f, params)?;
                 write!(f, ")")?;
@@ -282,9 +331,13 @@ impl<'a> Display for TableReference<'a> {
             }
             TableReference::Subquery {
                 span: _,
+                lateral,
                 subquery,
                 alias,
             } => {
+                if *lateral {
+                    write!(f, "LATERAL ")?;
+                }
                 write!(f, "({subquery})")?;
                 if let Some(alias) = alias {
                     write!(f, " AS {alias}")?;
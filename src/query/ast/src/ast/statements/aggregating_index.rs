@@ -0,0 +1,62 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::write_period_separated_list;
+use crate::ast::Identifier;
+use crate::ast::Query;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateAggregatingIndexStmt<'a> {
+    pub if_not_exists: bool,
+    pub index_name: Identifier<'a>,
+    pub query: Box<Query<'a>>,
+}
+
+impl Display for CreateAggregatingIndexStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE AGGREGATING INDEX ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{} AS {}", self.index_name, self.query)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropAggregatingIndexStmt<'a> {
+    pub if_exists: bool,
+    pub index_name: Identifier<'a>,
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+}
+
+impl Display for DropAggregatingIndexStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP AGGREGATING INDEX ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        write!(f, "{} ON ", self.index_name)?;
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )
+    }
+}
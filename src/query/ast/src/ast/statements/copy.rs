@@ -16,8 +16,10 @@ use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use crate::ast::write_comma_separated_list;
 use crate::ast::write_quoted_comma_separated_list;
 use crate::ast::write_space_seperated_map;
+use crate::ast::Expr;
 use crate::ast::Identifier;
 use crate::ast::Query;
 
@@ -32,12 +34,18 @@ use crate::ast::Query;
 pub struct CopyStmt<'a> {
     pub src: CopyUnit<'a>,
     pub dst: CopyUnit<'a>,
+    /// `PARTITION BY (...)`, only for `COPY INTO <location>`.
+    pub partition_by: Vec<CopyPartitionKey<'a>>,
     pub files: Vec<String>,
     pub pattern: String,
     pub file_format: BTreeMap<String, String>,
     /// TODO(xuanwo): parse into validation_mode directly.
     pub validation_mode: String,
     pub size_limit: usize,
+    pub on_error: String,
+    pub max_errors: u64,
+    pub max_file_size: u64,
+    pub single: bool,
     pub purge: bool,
     pub force: bool,
 }
@@ -48,6 +56,12 @@ impl Display for CopyStmt<'_> {
         write!(f, " INTO {}", self.dst)?;
         write!(f, " FROM {}", self.src)?;
 
+        if !self.partition_by.is_empty() {
+            write!(f, " PARTITION BY (")?;
+            write_comma_separated_list(f, &self.partition_by)?;
+            write!(f, ")")?;
+        }
+
         if !self.files.is_empty() {
             write!(f, " FILES = (")?;
             write_quoted_comma_separated_list(f, &self.files)?;
@@ -74,12 +88,46 @@ impl Display for CopyStmt<'_> {
             write!(f, " SIZE_LIMIT = {}", self.size_limit)?;
         }
 
+        if !self.on_error.is_empty() {
+            write!(f, " ON_ERROR = {}", self.on_error)?;
+        }
+
+        if self.max_errors != 0 {
+            write!(f, " MAX_ERRORS = {}", self.max_errors)?;
+        }
+
+        if self.max_file_size != 0 {
+            write!(f, " MAX_FILE_SIZE = {}", self.max_file_size)?;
+        }
+
+        if self.single {
+            write!(f, " SINGLE = {}", self.single)?;
+        }
+
         write!(f, " PURGE = {}", self.purge)?;
         write!(f, " FORCE = {}", self.force)?;
         Ok(())
     }
 }
 
+/// A key to partition the files unloaded by, like `dt` or `to_yyyymm(ts) AS month`, the files
+/// are written into the Hive style directories like `dt=2022-10-01/`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyPartitionKey<'a> {
+    pub expr: Expr<'a>,
+    pub alias: Option<Identifier<'a>>,
+}
+
+impl Display for CopyPartitionKey<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expr)?;
+        if let Some(alias) = &self.alias {
+            write!(f, " AS {}", alias)?;
+        }
+        Ok(())
+    }
+}
+
 /// CopyUnit is the unit that can be used in `COPY`.
 #[derive(Debug, Clone, PartialEq)]
 pub enum CopyUnit<'a> {
@@ -21,4 +21,6 @@ pub enum ExplainKind {
     Fragments,
     Raw,
     Plan,
+    Analyze,
+    Estimate,
 }
@@ -0,0 +1,97 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::write_comma_separated_list;
+use crate::ast::write_period_separated_list;
+use crate::ast::Identifier;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexType {
+    Bloom,
+    Inverted,
+}
+
+impl Display for IndexType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndexType::Bloom => write!(f, "BLOOM"),
+            IndexType::Inverted => write!(f, "INVERTED"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateIndexStmt<'a> {
+    pub if_not_exists: bool,
+    pub index_name: Identifier<'a>,
+    pub index_type: IndexType,
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+    pub columns: Vec<Identifier<'a>>,
+    /// The tokenizer of an inverted index
+    pub tokenizer: Option<String>,
+}
+
+impl Display for CreateIndexStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE INDEX ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{} TYPE {} ON ", self.index_name, self.index_type)?;
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )?;
+        write!(f, "(")?;
+        write_comma_separated_list(f, &self.columns)?;
+        write!(f, ")")?;
+        if let Some(tokenizer) = &self.tokenizer {
+            write!(f, " TOKENIZER = '{}'", tokenizer)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropIndexStmt<'a> {
+    pub if_exists: bool,
+    pub index_name: Identifier<'a>,
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+}
+
+impl Display for DropIndexStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP INDEX ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        write!(f, "{} ON ", self.index_name)?;
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )
+    }
+}
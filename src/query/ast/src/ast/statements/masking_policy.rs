@@ -0,0 +1,61 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::write_quoted_comma_separated_list;
+use crate::ast::Expr;
+use crate::ast::Identifier;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateMaskingPolicyStmt<'a> {
+    pub if_not_exists: bool,
+    pub name: Identifier<'a>,
+    pub parameter: Identifier<'a>,
+    pub body: Expr<'a>,
+    pub exempt_roles: Vec<String>,
+}
+
+impl Display for CreateMaskingPolicyStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE MASKING POLICY ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{} AS ({}) -> {}", self.name, self.parameter, self.body)?;
+        if !self.exempt_roles.is_empty() {
+            write!(f, " EXEMPT ROLES (")?;
+            write_quoted_comma_separated_list(f, &self.exempt_roles)?;
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropMaskingPolicyStmt<'a> {
+    pub if_exists: bool,
+    pub name: Identifier<'a>,
+}
+
+impl Display for DropMaskingPolicyStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP MASKING POLICY ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        write!(f, "{}", self.name)
+    }
+}
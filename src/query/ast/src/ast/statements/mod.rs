@@ -12,32 +12,52 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod aggregating_index;
 mod call;
 mod copy;
 mod database;
 mod explain;
+mod index;
 mod insert;
 mod kill;
+mod masking_policy;
+mod network_policy;
 mod presign;
+mod row_access_policy;
 mod share;
 mod show;
 mod stage;
 mod statement;
+mod stream;
 mod table;
+mod task;
+mod udf;
 mod user;
 mod view;
+mod virtual_column;
+mod workload_group;
 
+pub use aggregating_index::*;
 pub use call::*;
 pub use copy::*;
 pub use database::*;
 pub use explain::*;
+pub use index::*;
 pub use insert::*;
 pub use kill::*;
+pub use masking_policy::*;
+pub use network_policy::*;
 pub use presign::*;
+pub use row_access_policy::*;
 pub use share::*;
 pub use show::*;
 pub use stage::*;
 pub use statement::*;
+pub use stream::*;
 pub use table::*;
+pub use task::*;
+pub use udf::*;
 pub use user::*;
 pub use view::*;
+pub use virtual_column::*;
+pub use workload_group::*;
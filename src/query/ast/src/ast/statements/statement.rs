@@ -83,12 +83,15 @@ pub enum Statement<'a> {
     DescribeTable(DescribeTableStmt<'a>),
     ShowTablesStatus(ShowTablesStatusStmt<'a>),
     CreateTable(CreateTableStmt<'a>),
+    AttachTable(AttachTableStmt<'a>),
     DropTable(DropTableStmt<'a>),
     UndropTable(UndropTableStmt<'a>),
     AlterTable(AlterTableStmt<'a>),
     RenameTable(RenameTableStmt<'a>),
     TruncateTable(TruncateTableStmt<'a>),
+    FlashbackTable(FlashbackTableStmt<'a>),
     OptimizeTable(OptimizeTableStmt<'a>),
+    AnalyzeTable(AnalyzeTableStmt<'a>),
     ExistsTable(ExistsTableStmt<'a>),
 
     // Views
@@ -96,6 +99,23 @@ pub enum Statement<'a> {
     AlterView(AlterViewStmt<'a>),
     DropView(DropViewStmt<'a>),
 
+    // Streams
+    CreateStream(CreateStreamStmt<'a>),
+    DropStream(DropStreamStmt<'a>),
+
+    // Aggregating indexes
+    CreateAggregatingIndex(CreateAggregatingIndexStmt<'a>),
+    DropAggregatingIndex(DropAggregatingIndexStmt<'a>),
+
+    // Indexes
+    CreateIndex(CreateIndexStmt<'a>),
+    DropIndex(DropIndexStmt<'a>),
+
+    // Virtual columns
+    CreateVirtualColumns(CreateVirtualColumnsStmt<'a>),
+    AlterVirtualColumns(AlterVirtualColumnsStmt<'a>),
+    DropVirtualColumns(DropVirtualColumnsStmt<'a>),
+
     // User
     ShowUsers,
     CreateUser(CreateUserStmt),
@@ -113,6 +133,10 @@ pub enum Statement<'a> {
         if_exists: bool,
         role_name: String,
     },
+    SetRole {
+        // None to activate all the roles granted
+        role_name: Option<String>,
+    },
     Grant(GrantStmt),
     ShowGrants {
         principal: Option<PrincipalIdentity>,
@@ -127,6 +151,7 @@ pub enum Statement<'a> {
         definition: Box<Expr<'a>>,
         description: Option<String>,
     },
+    CreateUDFServer(CreateUDFServerStmt<'a>),
     DropUDF {
         if_exists: bool,
         udf_name: Identifier<'a>,
@@ -159,6 +184,31 @@ pub enum Statement<'a> {
 
     Presign(PresignStmt),
 
+    // Row access policies
+    CreateRowAccessPolicy(CreateRowAccessPolicyStmt<'a>),
+    DropRowAccessPolicy(DropRowAccessPolicyStmt<'a>),
+    ShowRowAccessPolicies,
+
+    // Masking policies
+    CreateMaskingPolicy(CreateMaskingPolicyStmt<'a>),
+    DropMaskingPolicy(DropMaskingPolicyStmt<'a>),
+    ShowMaskingPolicies,
+
+    // Network policies
+    CreateNetworkPolicy(CreateNetworkPolicyStmt<'a>),
+    DropNetworkPolicy(DropNetworkPolicyStmt<'a>),
+    ShowNetworkPolicies,
+
+    // Workload groups
+    CreateWorkloadGroup(CreateWorkloadGroupStmt<'a>),
+    DropWorkloadGroup(DropWorkloadGroupStmt<'a>),
+    ShowWorkloadGroups,
+
+    // Tasks
+    CreateTask(CreateTaskStmt<'a>),
+    DropTask(DropTaskStmt<'a>),
+    ShowTasks,
+
     // share
     CreateShare(CreateShareStmt<'a>),
     DropShare(DropShareStmt<'a>),
@@ -169,6 +219,11 @@ pub enum Statement<'a> {
     ShowShares(ShowSharesStmt),
     ShowObjectGrantPrivileges(ShowObjectGrantPrivilegesStmt),
     ShowGrantsOfShare(ShowGrantsOfShareStmt),
+
+    // Transactions
+    Begin,
+    Commit,
+    Rollback,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -190,6 +245,8 @@ impl<'a> Display for Statement<'a> {
                     ExplainKind::Fragments => write!(f, " FRAGMENTS")?,
                     ExplainKind::Raw => write!(f, " RAW")?,
                     ExplainKind::Plan => (),
+                    ExplainKind::Analyze => write!(f, " ANALYZE")?,
+                    ExplainKind::Estimate => write!(f, " ESTIMATE")?,
                 }
                 write!(f, " {query}")?;
             }
@@ -255,16 +312,28 @@ impl<'a> Display for Statement<'a> {
             Statement::DescribeTable(stmt) => write!(f, "{stmt}")?,
             Statement::ShowTablesStatus(stmt) => write!(f, "{stmt}")?,
             Statement::CreateTable(stmt) => write!(f, "{stmt}")?,
+            Statement::AttachTable(stmt) => write!(f, "{stmt}")?,
             Statement::DropTable(stmt) => write!(f, "{stmt}")?,
             Statement::UndropTable(stmt) => write!(f, "{stmt}")?,
             Statement::AlterTable(stmt) => write!(f, "{stmt}")?,
             Statement::RenameTable(stmt) => write!(f, "{stmt}")?,
             Statement::TruncateTable(stmt) => write!(f, "{stmt}")?,
+            Statement::FlashbackTable(stmt) => write!(f, "{stmt}")?,
             Statement::OptimizeTable(stmt) => write!(f, "{stmt}")?,
+            Statement::AnalyzeTable(stmt) => write!(f, "{stmt}")?,
             Statement::ExistsTable(stmt) => write!(f, "{stmt}")?,
             Statement::CreateView(stmt) => write!(f, "{stmt}")?,
             Statement::AlterView(stmt) => write!(f, "{stmt}")?,
             Statement::DropView(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateStream(stmt) => write!(f, "{stmt}")?,
+            Statement::DropStream(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateAggregatingIndex(stmt) => write!(f, "{stmt}")?,
+            Statement::DropAggregatingIndex(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateIndex(stmt) => write!(f, "{stmt}")?,
+            Statement::DropIndex(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateVirtualColumns(stmt) => write!(f, "{stmt}")?,
+            Statement::AlterVirtualColumns(stmt) => write!(f, "{stmt}")?,
+            Statement::DropVirtualColumns(stmt) => write!(f, "{stmt}")?,
             Statement::ShowUsers => write!(f, "SHOW USERS")?,
             Statement::ShowRoles => write!(f, "SHOW ROLES")?,
             Statement::CreateUser(stmt) => write!(f, "{stmt}")?,
@@ -296,6 +365,10 @@ impl<'a> Display for Statement<'a> {
                 }
                 write!(f, " '{role}'")?;
             }
+            Statement::SetRole { role_name } => match role_name {
+                Some(role) => write!(f, "SET ROLE '{role}'")?,
+                None => write!(f, "SET ROLE ALL")?,
+            },
             Statement::Grant(stmt) => write!(f, "{stmt}")?,
             Statement::ShowGrants { principal } => {
                 write!(f, "SHOW GRANTS")?;
@@ -323,6 +396,7 @@ impl<'a> Display for Statement<'a> {
                     write!(f, " DESC = '{description}'")?;
                 }
             }
+            Statement::CreateUDFServer(stmt) => write!(f, "{stmt}")?,
             Statement::DropUDF {
                 if_exists,
                 udf_name,
@@ -373,6 +447,21 @@ impl<'a> Display for Statement<'a> {
             Statement::DescribeStage { stage_name } => write!(f, "DESC STAGE {stage_name}")?,
             Statement::Call(stmt) => write!(f, "{stmt}")?,
             Statement::Presign(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateRowAccessPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::DropRowAccessPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::ShowRowAccessPolicies => write!(f, "SHOW ROW ACCESS POLICIES")?,
+            Statement::CreateMaskingPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::DropMaskingPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::ShowMaskingPolicies => write!(f, "SHOW MASKING POLICIES")?,
+            Statement::CreateNetworkPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::DropNetworkPolicy(stmt) => write!(f, "{stmt}")?,
+            Statement::ShowNetworkPolicies => write!(f, "SHOW NETWORK POLICIES")?,
+            Statement::CreateWorkloadGroup(stmt) => write!(f, "{stmt}")?,
+            Statement::DropWorkloadGroup(stmt) => write!(f, "{stmt}")?,
+            Statement::ShowWorkloadGroups => write!(f, "SHOW WORKLOAD GROUPS")?,
+            Statement::CreateTask(stmt) => write!(f, "{stmt}")?,
+            Statement::DropTask(stmt) => write!(f, "{stmt}")?,
+            Statement::ShowTasks => write!(f, "SHOW TASKS")?,
             Statement::CreateShare(stmt) => write!(f, "{stmt}")?,
             Statement::DropShare(stmt) => write!(f, "{stmt}")?,
             Statement::GrantShareObject(stmt) => write!(f, "{stmt}")?,
@@ -382,6 +471,9 @@ impl<'a> Display for Statement<'a> {
             Statement::ShowShares(stmt) => write!(f, "{stmt}")?,
             Statement::ShowObjectGrantPrivileges(stmt) => write!(f, "{stmt}")?,
             Statement::ShowGrantsOfShare(stmt) => write!(f, "{stmt}")?,
+            Statement::Begin => write!(f, "BEGIN")?,
+            Statement::Commit => write!(f, "COMMIT")?,
+            Statement::Rollback => write!(f, "ROLLBACK")?,
         }
         Ok(())
     }
@@ -24,6 +24,7 @@ use crate::ast::Expr;
 use crate::ast::Identifier;
 use crate::ast::Query;
 use crate::ast::TableReference;
+use crate::ast::TimeTravelPoint;
 use crate::ast::TypeName;
 
 #[derive(Debug, Clone, PartialEq)] // Tables
@@ -107,6 +108,7 @@ pub struct CreateTableStmt<'a> {
     pub table_options: BTreeMap<String, String>,
     pub as_query: Option<Box<Query<'a>>>,
     pub transient: bool,
+    pub external: Option<ExternalTableOptions<'a>>,
 }
 
 impl Display for CreateTableStmt<'_> {
@@ -115,6 +117,9 @@ impl Display for CreateTableStmt<'_> {
         if self.transient {
             write!(f, "TRANSIENT ")?;
         }
+        if self.external.is_some() {
+            write!(f, "EXTERNAL ")?;
+        }
         write!(f, "TABLE ")?;
         if self.if_not_exists {
             write!(f, "IF NOT EXISTS ")?;
@@ -135,6 +140,10 @@ impl Display for CreateTableStmt<'_> {
             write!(f, " ENGINE = {engine}")?;
         }
 
+        if let Some(external) = &self.external {
+            write!(f, " {external}")?;
+        }
+
         if !self.cluster_by.is_empty() {
             write!(f, " CLUSTER BY (")?;
             write_comma_separated_list(f, &self.cluster_by)?;
@@ -151,6 +160,37 @@ impl Display for CreateTableStmt<'_> {
     }
 }
 
+/// The files of `CREATE EXTERNAL TABLE`, in a stage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalTableOptions<'a> {
+    pub stage: String,
+    /// Path in the stage, starts with `/`.
+    pub path: String,
+    pub file_format: BTreeMap<String, String>,
+    /// Columns of the values in the paths like `dt=2022-10-01/`, inferred from the paths if
+    /// empty.
+    pub partition_by: Vec<Identifier<'a>>,
+}
+
+impl Display for ExternalTableOptions<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LOCATION = @{}{}", self.stage, self.path)?;
+        if !self.file_format.is_empty() {
+            write!(f, " FILE_FORMAT = (")?;
+            for (k, v) in self.file_format.iter() {
+                write!(f, " {} = '{}'", k, v)?;
+            }
+            write!(f, " )")?;
+        }
+        if !self.partition_by.is_empty() {
+            write!(f, " PARTITION BY (")?;
+            write_comma_separated_list(f, &self.partition_by)?;
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum CreateTableSource<'a> {
@@ -182,6 +222,40 @@ impl Display for CreateTableSource<'_> {
     }
 }
 
+/// `ATTACH TABLE`, creates a table over the data of an existing fuse table in the storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachTableStmt<'a> {
+    pub if_not_exists: bool,
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+    /// The storage prefix of the data of the table, like `1/2/`.
+    pub storage_prefix: String,
+    pub read_only: bool,
+}
+
+impl Display for AttachTableStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ATTACH TABLE ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )?;
+        write!(f, " FROM '{}'", self.storage_prefix)?;
+        if self.read_only {
+            write!(f, " READ_ONLY")?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DescribeTableStmt<'a> {
     pub catalog: Option<Identifier<'a>>,
@@ -282,6 +356,27 @@ pub enum AlterTableAction<'a> {
         is_final: bool,
         selection: Option<Expr<'a>>,
     },
+    SetColumnMaskingPolicy {
+        column: Identifier<'a>,
+        policy: Identifier<'a>,
+    },
+    UnsetColumnMaskingPolicy {
+        column: Identifier<'a>,
+    },
+    AddColumn {
+        column: ColumnDefinition<'a>,
+    },
+    DropColumn {
+        column: Identifier<'a>,
+    },
+    RenameColumn {
+        old_column: Identifier<'a>,
+        new_column: Identifier<'a>,
+    },
+    ModifyColumn {
+        column: Identifier<'a>,
+        data_type: TypeName,
+    },
 }
 
 impl Display for AlterTableAction<'_> {
@@ -310,6 +405,31 @@ impl Display for AlterTableAction<'_> {
                 }
                 Ok(())
             }
+            AlterTableAction::SetColumnMaskingPolicy { column, policy } => {
+                write!(f, "MODIFY COLUMN {column} SET MASKING POLICY {policy}")
+            }
+            AlterTableAction::UnsetColumnMaskingPolicy { column } => {
+                write!(f, "MODIFY COLUMN {column} UNSET MASKING POLICY")
+            }
+            AlterTableAction::AddColumn { column } => {
+                write!(f, "ADD COLUMN {column}")
+            }
+            AlterTableAction::DropColumn { column } => {
+                write!(f, "DROP COLUMN {column}")
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                write!(f, "RENAME COLUMN {old_column} TO {new_column}")
+            }
+            AlterTableAction::ModifyColumn { column, data_type } => {
+                write!(f, "MODIFY COLUMN {column} {data_type}")?;
+                if !matches!(data_type, TypeName::Nullable(_)) {
+                    write!(f, " NOT NULL")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -375,6 +495,31 @@ impl Display for TruncateTableStmt<'_> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlashbackTableStmt<'a> {
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+    pub point: TimeTravelPoint<'a>,
+}
+
+impl Display for FlashbackTableStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FLASHBACK TABLE ")?;
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )?;
+        match &self.point {
+            TimeTravelPoint::Snapshot(sid) => write!(f, " TO (SNAPSHOT => '{sid}')"),
+            TimeTravelPoint::Timestamp(ts) => write!(f, " TO (TIMESTAMP => {ts})"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OptimizeTableStmt<'a> {
     pub catalog: Option<Identifier<'a>>,
@@ -401,6 +546,26 @@ impl Display for OptimizeTableStmt<'_> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyzeTableStmt<'a> {
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+}
+
+impl Display for AnalyzeTableStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ANALYZE TABLE ")?;
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExistsTableStmt<'a> {
     pub catalog: Option<Identifier<'a>>,
@@ -428,6 +593,8 @@ pub enum Engine {
     Fuse,
     View,
     Random,
+    Delta,
+    External,
 }
 
 impl Display for Engine {
@@ -438,6 +605,8 @@ impl Display for Engine {
             Engine::Fuse => write!(f, "FUSE"),
             Engine::View => write!(f, "VIEW"),
             Engine::Random => write!(f, "RANDOM"),
+            Engine::Delta => write!(f, "DELTA"),
+            Engine::External => write!(f, "EXTERNAL"),
         }
     }
 }
@@ -446,7 +615,7 @@ impl Display for Engine {
 pub enum OptimizeTableAction {
     All,
     Purge,
-    Compact,
+    Compact { target: CompactTarget },
 }
 
 impl Display for OptimizeTableAction {
@@ -454,7 +623,22 @@ impl Display for OptimizeTableAction {
         match self {
             OptimizeTableAction::All => write!(f, "ALL"),
             OptimizeTableAction::Purge => write!(f, "PURGE"),
-            OptimizeTableAction::Compact => write!(f, "COMPACT"),
+            OptimizeTableAction::Compact { target } => write!(f, "COMPACT {target}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactTarget {
+    Block,
+    Segment,
+}
+
+impl Display for CompactTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompactTarget::Block => write!(f, "BLOCK"),
+            CompactTarget::Segment => write!(f, "SEGMENT"),
         }
     }
 }
@@ -0,0 +1,61 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::Identifier;
+use crate::ast::Statement;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateTaskStmt<'a> {
+    pub if_not_exists: bool,
+    pub name: Identifier<'a>,
+    pub schedule: String,
+    pub error_notification: Option<String>,
+    pub comment: Option<String>,
+    pub statement: Box<Statement<'a>>,
+}
+
+impl Display for CreateTaskStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE TASK ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{} SCHEDULE = '{}'", self.name, self.schedule)?;
+        if let Some(error_notification) = &self.error_notification {
+            write!(f, " ERROR_NOTIFICATION = '{error_notification}'")?;
+        }
+        if let Some(comment) = &self.comment {
+            write!(f, " COMMENT = '{comment}'")?;
+        }
+        write!(f, " AS {}", self.statement)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropTaskStmt<'a> {
+    pub if_exists: bool,
+    pub name: Identifier<'a>,
+}
+
+impl Display for DropTaskStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP TASK ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        write!(f, "{}", self.name)
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::write_comma_separated_list;
+use crate::ast::Identifier;
+use crate::ast::TypeName;
+
+/// `CREATE FUNCTION` of a function evaluated by an external UDF server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateUDFServerStmt<'a> {
+    pub if_not_exists: bool,
+    pub udf_name: Identifier<'a>,
+    pub arg_types: Vec<TypeName>,
+    pub return_type: TypeName,
+    pub language: Identifier<'a>,
+    pub handler: String,
+    pub address: String,
+    pub description: Option<String>,
+}
+
+impl Display for CreateUDFServerStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE FUNCTION")?;
+        if self.if_not_exists {
+            write!(f, " IF NOT EXISTS")?;
+        }
+        write!(f, " {} (", self.udf_name)?;
+        write_comma_separated_list(f, &self.arg_types)?;
+        write!(
+            f,
+            ") RETURNS {} LANGUAGE {} HANDLER = '{}' ADDRESS = '{}'",
+            self.return_type, self.language, self.handler, self.address
+        )?;
+        if let Some(description) = &self.description {
+            write!(f, " DESC = '{description}'")?;
+        }
+        Ok(())
+    }
+}
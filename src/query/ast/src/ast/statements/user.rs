@@ -152,12 +152,19 @@ pub enum AccountMgrLevel {
     Global,
     Database(Option<String>),
     Table(Option<String>, String),
+    Stage(String),
+    UDF(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UserOptionItem {
     TenantSetting(bool),
     DefaultRole(String),
+    NetworkPolicy(String),
+    WorkloadGroup(String),
+    /// `SET <setting> = <value>`, a default setting of the user.
+    SetSetting(String, String),
+    UnsetSetting(String),
 }
 
 impl UserOptionItem {
@@ -167,6 +174,16 @@ impl UserOptionItem {
                 option.switch_option_flag(UserOptionFlag::TenantSetting, *enabled);
             }
             Self::DefaultRole(v) => option.set_default_role(Some(v.clone())),
+            Self::NetworkPolicy(v) => {
+                option.set_network_policy(Some(v.clone()).filter(|v| !v.is_empty()))
+            }
+            Self::WorkloadGroup(v) => {
+                option.set_workload_group(Some(v.clone()).filter(|v| !v.is_empty()))
+            }
+            Self::SetSetting(name, value) => {
+                option.set_default_setting(name.to_lowercase(), Some(value.clone()))
+            }
+            Self::UnsetSetting(name) => option.set_default_setting(name.to_lowercase(), None),
         }
     }
 }
@@ -178,57 +195,51 @@ impl Display for AccountMgrSource {
             AccountMgrSource::Privs { privileges, level } => {
                 write!(f, " ")?;
                 write_comma_separated_list(f, privileges.iter().map(|p| p.to_string()))?;
-                write!(f, " ON")?;
-                match level {
-                    AccountMgrLevel::Global => write!(f, " *.*")?,
-                    AccountMgrLevel::Database(database_name) => {
-                        if let Some(database_name) = database_name {
-                            write!(f, " {database_name}.*")?;
-                        } else {
-                            write!(f, " *")?;
-                        }
-                    }
-                    AccountMgrLevel::Table(database_name, table_name) => {
-                        if let Some(database_name) = database_name {
-                            write!(f, " {database_name}.{table_name}")?;
-                        } else {
-                            write!(f, " {table_name}")?;
-                        }
-                    }
-                }
+                write!(f, " ON {level}")?;
             }
             AccountMgrSource::ALL { level, .. } => {
                 write!(f, " ALL PRIVILEGES")?;
-                write!(f, " ON")?;
-                match level {
-                    AccountMgrLevel::Global => write!(f, " *.*")?,
-                    AccountMgrLevel::Database(database_name) => {
-                        if let Some(database_name) = database_name {
-                            write!(f, " {database_name}.*")?;
-                        } else {
-                            write!(f, " *")?;
-                        }
-                    }
-                    AccountMgrLevel::Table(database_name, table_name) => {
-                        if let Some(database_name) = database_name {
-                            write!(f, " {database_name}.{table_name}")?;
-                        } else {
-                            write!(f, " {table_name}")?;
-                        }
-                    }
-                }
+                write!(f, " ON {level}")?;
             }
         }
         Ok(())
     }
 }
 
+impl Display for AccountMgrLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountMgrLevel::Global => write!(f, "*.*"),
+            AccountMgrLevel::Database(database_name) => {
+                if let Some(database_name) = database_name {
+                    write!(f, "{database_name}.*")
+                } else {
+                    write!(f, "*")
+                }
+            }
+            AccountMgrLevel::Table(database_name, table_name) => {
+                if let Some(database_name) = database_name {
+                    write!(f, "{database_name}.{table_name}")
+                } else {
+                    write!(f, "{table_name}")
+                }
+            }
+            AccountMgrLevel::Stage(stage_name) => write!(f, "STAGE {stage_name}"),
+            AccountMgrLevel::UDF(udf_name) => write!(f, "UDF {udf_name}"),
+        }
+    }
+}
+
 impl Display for UserOptionItem {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
             UserOptionItem::TenantSetting(true) => write!(f, "TENANTSETTING"),
             UserOptionItem::TenantSetting(false) => write!(f, "NOTENANTSETTING"),
             UserOptionItem::DefaultRole(v) => write!(f, "DEFAULT_ROLE = '{}'", v),
+            UserOptionItem::NetworkPolicy(v) => write!(f, "NETWORK_POLICY = '{}'", v),
+            UserOptionItem::WorkloadGroup(v) => write!(f, "WORKLOAD_GROUP = '{}'", v),
+            UserOptionItem::SetSetting(name, value) => write!(f, "SET {} = '{}'", name, value),
+            UserOptionItem::UnsetSetting(name) => write!(f, "UNSET {}", name),
         }
     }
 }
@@ -0,0 +1,70 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::Identifier;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateWorkloadGroupStmt<'a> {
+    pub if_not_exists: bool,
+    pub name: Identifier<'a>,
+    pub cpu_share: Option<u64>,
+    pub memory_limit: Option<u64>,
+    pub max_concurrency: Option<u64>,
+    pub max_scan_bytes: Option<u64>,
+    pub comment: Option<String>,
+}
+
+impl Display for CreateWorkloadGroupStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE WORKLOAD GROUP ")?;
+        if self.if_not_exists {
+            write!(f, "IF NOT EXISTS ")?;
+        }
+        write!(f, "{}", self.name)?;
+        if let Some(cpu_share) = self.cpu_share {
+            write!(f, " CPU_SHARE = {cpu_share}")?;
+        }
+        if let Some(memory_limit) = self.memory_limit {
+            write!(f, " MEMORY_LIMIT = {memory_limit}")?;
+        }
+        if let Some(max_concurrency) = self.max_concurrency {
+            write!(f, " MAX_CONCURRENCY = {max_concurrency}")?;
+        }
+        if let Some(max_scan_bytes) = self.max_scan_bytes {
+            write!(f, " MAX_SCAN_BYTES = {max_scan_bytes}")?;
+        }
+        if let Some(comment) = &self.comment {
+            write!(f, " COMMENT = '{comment}'")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropWorkloadGroupStmt<'a> {
+    pub if_exists: bool,
+    pub name: Identifier<'a>,
+}
+
+impl Display for DropWorkloadGroupStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DROP WORKLOAD GROUP ")?;
+        if self.if_exists {
+            write!(f, "IF EXISTS ")?;
+        }
+        write!(f, "{}", self.name)
+    }
+}
@@ -234,6 +234,7 @@ pub enum ExprElement<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        window: Option<WindowSpec<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -337,6 +338,9 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 BinaryOperator::Divide => Affix::Infix(Precedence(40), Associativity::Left),
                 BinaryOperator::Modulo => Affix::Infix(Precedence(40), Associativity::Left),
                 BinaryOperator::StringConcat => Affix::Infix(Precedence(40), Associativity::Left),
+
+                BinaryOperator::JsonGet => Affix::Infix(Precedence(45), Associativity::Left),
+                BinaryOperator::JsonGetText => Affix::Infix(Precedence(45), Associativity::Left),
             },
             ExprElement::PgCast { .. } => Affix::Postfix(Precedence(50)),
             _ => Affix::Nilfix,
@@ -409,12 +413,14 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 name,
                 args,
                 params,
+                window,
             } => Expr::FunctionCall {
                 span: elem.span.0,
                 distinct,
                 name,
                 args,
                 params,
+                window,
             },
             ExprElement::Case {
                 operand,
@@ -732,12 +738,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             ~ DISTINCT?
             ~ #comma_separated_list0(subexpr(0))?
             ~ ")"
+            ~ ( OVER ~ ^"(" ~ #window_spec ~ ^")" )?
         },
-        |(name, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, _, opt_distinct, opt_args, _, opt_window)| ExprElement::FunctionCall {
             distinct: opt_distinct.is_some(),
             name,
             args: opt_args.unwrap_or_default(),
             params: vec![],
+            window: opt_window.map(|(_, _, window, _)| window),
         },
     );
     let function_call_with_param = map(
@@ -751,6 +759,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             name,
             args: opt_args.unwrap_or_default(),
             params,
+            window: None,
         },
     );
     let case = map(
@@ -894,6 +903,86 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     Ok((rest, WithSpan { span, elem }))
 }
 
+pub fn window_spec(i: Input) -> IResult<WindowSpec> {
+    map(
+        rule! {
+            ( PARTITION ~ ^BY ~ ^#comma_separated_list1(subexpr(0)) )?
+            ~ ( ORDER ~ ^BY ~ ^#comma_separated_list1(order_by_expr) )?
+            ~ #window_frame?
+        },
+        |(opt_partition_by, opt_order_by, window_frame)| WindowSpec {
+            partition_by: opt_partition_by
+                .map(|(_, _, exprs)| exprs)
+                .unwrap_or_default(),
+            order_by: opt_order_by.map(|(_, _, exprs)| exprs).unwrap_or_default(),
+            window_frame,
+        },
+    )(i)
+}
+
+pub fn window_frame(i: Input) -> IResult<WindowFrame> {
+    fn units(i: Input) -> IResult<WindowFrameUnits> {
+        alt((
+            value(WindowFrameUnits::Rows, rule! { ROWS }),
+            value(WindowFrameUnits::Range, rule! { RANGE }),
+        ))(i)
+    }
+
+    let between = map(
+        rule! {
+            #units ~ BETWEEN ~ ^#window_frame_bound ~ ^AND ~ ^#window_frame_bound
+        },
+        |(units, _, start_bound, _, end_bound)| WindowFrame {
+            units,
+            start_bound,
+            end_bound,
+        },
+    );
+    // `ROWS <bound>` is short for `ROWS BETWEEN <bound> AND CURRENT ROW`
+    let start_only = map(
+        rule! {
+            #units ~ ^#window_frame_bound
+        },
+        |(units, start_bound)| WindowFrame {
+            units,
+            start_bound,
+            end_bound: WindowFrameBound::CurrentRow,
+        },
+    );
+
+    rule!(
+        #between
+        | #start_only
+    )(i)
+}
+
+pub fn window_frame_bound(i: Input) -> IResult<WindowFrameBound> {
+    // `None` if the offset is `UNBOUNDED`
+    fn offset(i: Input) -> IResult<Option<u64>> {
+        alt((value(None, rule! { UNBOUNDED }), map(literal_u64, Some)))(i)
+    }
+
+    let current_row = value(WindowFrameBound::CurrentRow, rule! { CURRENT ~ ROW });
+    let preceding = map(
+        rule! {
+            #offset ~ PRECEDING
+        },
+        |(offset, _)| WindowFrameBound::Preceding(offset),
+    );
+    let following = map(
+        rule! {
+            #offset ~ FOLLOWING
+        },
+        |(offset, _)| WindowFrameBound::Following(offset),
+    );
+
+    rule!(
+        #current_row
+        | #preceding
+        | #following
+    )(i)
+}
+
 pub fn unary_op(i: Input) -> IResult<UnaryOperator> {
     // Plus and Minus are parsed as binary op at first.
     value(UnaryOperator::Not, rule! { NOT })(i)
@@ -929,6 +1018,8 @@ pub fn binary_op(i: Input) -> IResult<BinaryOperator> {
             value(BinaryOperator::BitwiseOr, rule! { "|" }),
             value(BinaryOperator::BitwiseAnd, rule! { "&" }),
             value(BinaryOperator::BitwiseXor, rule! { "^" }),
+            value(BinaryOperator::JsonGet, rule! { "->" }),
+            value(BinaryOperator::JsonGetText, rule! { "->>" }),
         )),
     ))(i)
 }
@@ -1136,6 +1227,8 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
     );
     let ty_object = value(TypeName::Object, rule! { OBJECT | MAP });
     let ty_variant = value(TypeName::Variant, rule! { VARIANT | JSON });
+    let ty_geometry = value(TypeName::Geometry, rule! { GEOMETRY });
+    let ty_geography = value(TypeName::Geography, rule! { GEOGRAPHY });
     map(
         rule! {
             ( #ty_boolean
@@ -1156,6 +1249,8 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
             | #ty_string
             | #ty_object
             | #ty_variant
+            | #ty_geometry
+            | #ty_geography
             ) ~ NULL? : "type name"
         },
         |(ty, null_opt)| {
@@ -129,6 +129,33 @@ pub fn travel_point(i: Input) -> IResult<TimeTravelPoint> {
     )(i)
 }
 
+pub fn table_sample(i: Input) -> IResult<TableSample> {
+    let method = alt((
+        value(SampleMethod::System, rule! { SYSTEM }),
+        value(SampleMethod::Bernoulli, rule! { BERNOULLI }),
+    ));
+    let percent = alt((literal_f64, map(literal_u64, |v| v as f64)));
+    map(
+        rule! { TABLESAMPLE ~ ^#method ~ ^"(" ~ ^#percent ~ ^")" },
+        |(_, method, _, percent, _)| TableSample { method, percent },
+    )(i)
+}
+
+pub fn flashback_point(i: Input) -> IResult<TimeTravelPoint> {
+    let to_snapshot = map(
+        rule! { "(" ~ SNAPSHOT ~ "=>" ~ #literal_string ~ ")" },
+        |(_, _, _, s, _)| TimeTravelPoint::Snapshot(s),
+    );
+    let to_timestamp = map(
+        rule! { "(" ~ TIMESTAMP ~ "=>" ~ #expr ~ ")" },
+        |(_, _, _, e, _)| TimeTravelPoint::Timestamp(Box::new(e)),
+    );
+
+    rule!(
+        #to_snapshot | #to_timestamp
+    )(i)
+}
+
 pub fn alias_name(i: Input) -> IResult<Identifier> {
     let as_alias = map(rule! { AS ~ #ident_after_as }, |(_, name)| name);
 
@@ -201,15 +228,18 @@ pub enum TableReferenceElement<'a> {
         table: Identifier<'a>,
         alias: Option<TableAlias<'a>>,
         travel_point: Option<TimeTravelPoint<'a>>,
+        sample: Option<TableSample>,
     },
-    // `TABLE(expr)[ AS alias ]`
+    // `[LATERAL] TABLE(expr)[ AS alias ]`
     TableFunction {
+        lateral: bool,
         name: Identifier<'a>,
         params: Vec<Expr<'a>>,
         alias: Option<TableAlias<'a>>,
     },
     // Derived table, which can be a subquery or joined tables or combination of them
     Subquery {
+        lateral: bool,
         subquery: Box<Query<'a>>,
         alias: Option<TableAlias<'a>>,
     },
@@ -226,21 +256,23 @@ pub enum TableReferenceElement<'a> {
 pub fn table_reference_element(i: Input) -> IResult<WithSpan<TableReferenceElement>> {
     let aliased_table = map(
         rule! {
-            #peroid_separated_idents_1_to_3 ~ #travel_point? ~ #table_alias?
+            #peroid_separated_idents_1_to_3 ~ #travel_point? ~ #table_alias? ~ #table_sample?
         },
-        |((catalog, database, table), travel_point, alias)| TableReferenceElement::Table {
+        |((catalog, database, table), travel_point, alias, sample)| TableReferenceElement::Table {
             catalog,
             database,
             table,
             alias,
             travel_point,
+            sample,
         },
     );
     let table_function = map(
         rule! {
-            #ident ~ "(" ~ #comma_separated_list0(expr) ~ ")" ~ #table_alias?
+            LATERAL? ~ #ident ~ "(" ~ #comma_separated_list0(expr) ~ ")" ~ #table_alias?
         },
-        |(name, _, params, _, alias)| TableReferenceElement::TableFunction {
+        |(lateral, name, _, params, _, alias)| TableReferenceElement::TableFunction {
+            lateral: lateral.is_some(),
             name,
             params,
             alias,
@@ -248,9 +280,10 @@ pub fn table_reference_element(i: Input) -> IResult<WithSpan<TableReferenceEleme
     );
     let subquery = map(
         rule! {
-            ( #parenthesized_query | #query ) ~ #table_alias?
+            LATERAL? ~ ( #parenthesized_query | #query ) ~ #table_alias?
         },
-        |(subquery, alias)| TableReferenceElement::Subquery {
+        |(lateral, subquery, alias)| TableReferenceElement::Subquery {
+            lateral: lateral.is_some(),
             subquery: Box::new(subquery),
             alias,
         },
@@ -322,6 +355,7 @@ impl<'a, I: Iterator<Item = WithSpan<'a, TableReferenceElement<'a>>>> PrattParse
                 table,
                 alias,
                 travel_point,
+                sample,
             } => TableReference::Table {
                 span: input.span.0,
                 catalog,
@@ -329,19 +363,27 @@ impl<'a, I: Iterator<Item = WithSpan<'a, TableReferenceElement<'a>>>> PrattParse
                 table,
                 alias,
                 travel_point,
+                sample,
             },
             TableReferenceElement::TableFunction {
+                lateral,
                 name,
                 params,
                 alias,
             } => TableReference::TableFunction {
                 span: input.span.0,
+                lateral,
                 name,
                 params,
                 alias,
             },
-            TableReferenceElement::Subquery { subquery, alias } => TableReference::Subquery {
+            TableReferenceElement::Subquery {
+                lateral,
+                subquery,
+                alias,
+            } => TableReference::Subquery {
                 span: input.span.0,
+                lateral,
                 subquery,
                 alias,
             },
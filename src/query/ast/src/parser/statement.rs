@@ -53,7 +53,8 @@ pub enum CreateDatabaseOption {
 pub fn statement(i: Input) -> IResult<StatementMsg> {
     let explain = map_res(
         rule! {
-            EXPLAIN ~ ( AST | SYNTAX | PIPELINE | GRAPH | FRAGMENTS | RAW )? ~ #statement
+            EXPLAIN ~ ( AST | SYNTAX | PIPELINE | GRAPH | FRAGMENTS | RAW | ANALYZE | ESTIMATE )?
+            ~ #statement
         },
         |(_, opt_kind, statement)| {
             Ok(Statement::Explain {
@@ -72,6 +73,8 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
                     Some(TokenKind::GRAPH) => ExplainKind::Graph,
                     Some(TokenKind::FRAGMENTS) => ExplainKind::Fragments,
                     Some(TokenKind::RAW) => ExplainKind::Raw,
+                    Some(TokenKind::ANALYZE) => ExplainKind::Analyze,
+                    Some(TokenKind::ESTIMATE) => ExplainKind::Estimate,
                     None => ExplainKind::Plan,
                     _ => unreachable!(),
                 },
@@ -151,6 +154,12 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             value,
         },
     );
+    let set_role = map(
+        rule! {
+            SET ~ ROLE ~ ( #map(rule! { ALL }, |_| None) | #map(literal_string, Some) )
+        },
+        |(_, _, role_name)| Statement::SetRole { role_name },
+    );
     let show_databases = map(
         rule! {
             SHOW ~ ( DATABASES | SCHEMAS ) ~ #show_limit?
@@ -341,6 +350,79 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
                 table_options: opt_table_options.unwrap_or_default(),
                 as_query: opt_as_query.map(|(_, query)| Box::new(query)),
                 transient: opt_transient.is_some(),
+                external: None,
+            })
+        },
+    );
+    let create_external_table = map(
+        rule! {
+            CREATE ~ EXTERNAL ~ TABLE ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #peroid_separated_idents_1_to_3
+            ~ #create_table_source
+            ~ LOCATION ~ "="? ~ #stage_location
+            ~ ( FILE_FORMAT ~ "=" ~ #options )?
+            ~ ( PARTITION ~ ^BY ~ ^"(" ~ ^#comma_separated_list1(ident) ~ ^")" )?
+            ~ ( #table_option )?
+        },
+        |(
+            _,
+            _,
+            _,
+            opt_if_not_exists,
+            (catalog, database, table),
+            source,
+            _,
+            _,
+            (stage, path),
+            opt_file_format,
+            opt_partition_by,
+            opt_table_options,
+        )| {
+            Statement::CreateTable(CreateTableStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                catalog,
+                database,
+                table,
+                source: Some(source),
+                engine: None,
+                cluster_by: vec![],
+                table_options: opt_table_options.unwrap_or_default(),
+                as_query: None,
+                transient: false,
+                external: Some(ExternalTableOptions {
+                    stage,
+                    path,
+                    file_format: opt_file_format
+                        .map(|(_, _, options)| options)
+                        .unwrap_or_default(),
+                    partition_by: opt_partition_by
+                        .map(|(_, _, _, columns, _)| columns)
+                        .unwrap_or_default(),
+                }),
+            })
+        },
+    );
+    let attach_table = map(
+        rule! {
+            ATTACH ~ TABLE ~ ( IF ~ NOT ~ EXISTS )? ~ #peroid_separated_idents_1_to_3
+            ~ FROM ~ #literal_string ~ ( READ_ONLY )?
+        },
+        |(
+            _,
+            _,
+            opt_if_not_exists,
+            (catalog, database, table),
+            _,
+            storage_prefix,
+            opt_read_only,
+        )| {
+            Statement::AttachTable(AttachTableStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                catalog,
+                database,
+                table,
+                storage_prefix,
+                read_only: opt_read_only.is_some(),
             })
         },
     );
@@ -418,6 +500,19 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let flashback_table = map(
+        rule! {
+            FLASHBACK ~ TABLE ~ #peroid_separated_idents_1_to_3 ~ TO ~ #flashback_point
+        },
+        |(_, _, (catalog, database, table), _, point)| {
+            Statement::FlashbackTable(FlashbackTableStmt {
+                catalog,
+                database,
+                table,
+                point,
+            })
+        },
+    );
     let optimize_table = map(
         rule! {
             OPTIMIZE ~ TABLE ~ #peroid_separated_idents_1_to_3 ~ #optimize_table_action?
@@ -431,6 +526,18 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let analyze_table = map(
+        rule! {
+            ANALYZE ~ TABLE ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, (catalog, database, table))| {
+            Statement::AnalyzeTable(AnalyzeTableStmt {
+                catalog,
+                database,
+                table,
+            })
+        },
+    );
     let exists_table = map(
         rule! {
             EXISTS ~ TABLE ~ #peroid_separated_idents_1_to_3
@@ -472,6 +579,164 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let create_stream = map(
+        rule! {
+            CREATE ~ STREAM ~ ( IF ~ NOT ~ EXISTS )? ~ #peroid_separated_idents_1_to_3
+            ~ ON ~ TABLE ~ #peroid_separated_idents_1_to_2
+            ~ ( COMMENT ~ "=" ~ #literal_string )?
+        },
+        |(
+            _,
+            _,
+            opt_if_not_exists,
+            (catalog, database, stream),
+            _,
+            _,
+            (table_database, table),
+            opt_comment,
+        )| {
+            Statement::CreateStream(CreateStreamStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                catalog,
+                database,
+                stream,
+                table_database,
+                table,
+                comment: opt_comment.map(|(_, _, comment)| comment),
+            })
+        },
+    );
+    let drop_stream = map(
+        rule! {
+            DROP ~ STREAM ~ ( IF ~ EXISTS )? ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, opt_if_exists, (catalog, database, stream))| {
+            Statement::DropStream(DropStreamStmt {
+                if_exists: opt_if_exists.is_some(),
+                catalog,
+                database,
+                stream,
+            })
+        },
+    );
+    let create_aggregating_index = map(
+        rule! {
+            CREATE ~ AGGREGATING ~ INDEX ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #ident ~ AS ~ #query
+        },
+        |(_, _, _, opt_if_not_exists, index_name, _, query)| {
+            Statement::CreateAggregatingIndex(CreateAggregatingIndexStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                index_name,
+                query: Box::new(query),
+            })
+        },
+    );
+    let drop_aggregating_index = map(
+        rule! {
+            DROP ~ AGGREGATING ~ INDEX ~ ( IF ~ EXISTS )? ~ #ident
+            ~ ON ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, _, opt_if_exists, index_name, _, (catalog, database, table))| {
+            Statement::DropAggregatingIndex(DropAggregatingIndexStmt {
+                if_exists: opt_if_exists.is_some(),
+                index_name,
+                catalog,
+                database,
+                table,
+            })
+        },
+    );
+    let create_index = map(
+        rule! {
+            CREATE ~ INDEX ~ ( IF ~ NOT ~ EXISTS )? ~ #ident ~ TYPE ~ #index_type
+            ~ ON ~ #peroid_separated_idents_1_to_3 ~ "(" ~ #comma_separated_list1(ident) ~ ")"
+            ~ ( TOKENIZER ~ "=" ~ #literal_string )?
+        },
+        |(
+            _,
+            _,
+            opt_if_not_exists,
+            index_name,
+            _,
+            index_type,
+            _,
+            (catalog, database, table),
+            _,
+            columns,
+            _,
+            opt_tokenizer,
+        )| {
+            Statement::CreateIndex(CreateIndexStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                index_name,
+                index_type,
+                catalog,
+                database,
+                table,
+                columns,
+                tokenizer: opt_tokenizer.map(|(_, _, tokenizer)| tokenizer),
+            })
+        },
+    );
+    let drop_index = map(
+        rule! {
+            DROP ~ INDEX ~ ( IF ~ EXISTS )? ~ #ident ~ ON ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, opt_if_exists, index_name, _, (catalog, database, table))| {
+            Statement::DropIndex(DropIndexStmt {
+                if_exists: opt_if_exists.is_some(),
+                index_name,
+                catalog,
+                database,
+                table,
+            })
+        },
+    );
+    let create_virtual_columns = map(
+        rule! {
+            CREATE ~ VIRTUAL ~ COLUMNS ~ ( IF ~ NOT ~ EXISTS )?
+            ~ "(" ~ #comma_separated_list1(expr) ~ ")"
+            ~ FOR ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, _, opt_if_not_exists, _, virtual_columns, _, _, (catalog, database, table))| {
+            Statement::CreateVirtualColumns(CreateVirtualColumnsStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                catalog,
+                database,
+                table,
+                virtual_columns,
+            })
+        },
+    );
+    let alter_virtual_columns = map(
+        rule! {
+            ALTER ~ VIRTUAL ~ COLUMNS
+            ~ "(" ~ #comma_separated_list1(expr) ~ ")"
+            ~ FOR ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, _, _, virtual_columns, _, _, (catalog, database, table))| {
+            Statement::AlterVirtualColumns(AlterVirtualColumnsStmt {
+                catalog,
+                database,
+                table,
+                virtual_columns,
+            })
+        },
+    );
+    let drop_virtual_columns = map(
+        rule! {
+            DROP ~ VIRTUAL ~ COLUMNS ~ ( IF ~ EXISTS )? ~ FOR ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, _, opt_if_exists, _, (catalog, database, table))| {
+            Statement::DropVirtualColumns(DropVirtualColumnsStmt {
+                if_exists: opt_if_exists.is_some(),
+                catalog,
+                database,
+                table,
+            })
+        },
+    );
     let alter_view = map(
         rule! {
             ALTER ~ VIEW
@@ -625,6 +890,49 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             }
         },
     );
+    let create_udf_server = map(
+        rule! {
+            CREATE ~ FUNCTION ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #ident
+            ~ "(" ~ #comma_separated_list0(type_name) ~ ")"
+            ~ RETURNS ~ #type_name
+            ~ LANGUAGE ~ #ident
+            ~ HANDLER ~ ^"=" ~ ^#literal_string
+            ~ ADDRESS ~ ^"=" ~ ^#literal_string
+            ~ ( DESC ~ ^"=" ~ ^#literal_string )?
+        },
+        |(
+            _,
+            _,
+            opt_if_not_exists,
+            udf_name,
+            _,
+            arg_types,
+            _,
+            _,
+            return_type,
+            _,
+            language,
+            _,
+            _,
+            handler,
+            _,
+            _,
+            address,
+            opt_description,
+        )| {
+            Statement::CreateUDFServer(CreateUDFServerStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                udf_name,
+                arg_types,
+                return_type,
+                language,
+                handler,
+                address,
+                description: opt_description.map(|(_, _, description)| description),
+            })
+        },
+    );
     let drop_udf = map(
         rule! {
             DROP ~ FUNCTION ~ ( IF ~ EXISTS )? ~ #ident
@@ -736,11 +1044,16 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             COPY
             ~ INTO ~ #copy_unit
             ~ FROM ~ #copy_unit
+            ~ ( PARTITION ~ BY ~ "(" ~ #comma_separated_list1(copy_partition_key) ~ ")" )?
             ~ ( FILES ~ "=" ~ "(" ~ #comma_separated_list0(literal_string) ~ ")")?
             ~ ( PATTERN ~ "=" ~ #literal_string)?
             ~ ( FILE_FORMAT ~ "=" ~ #options)?
             ~ ( VALIDATION_MODE ~ "=" ~ #literal_string)?
             ~ ( SIZE_LIMIT ~ "=" ~ #literal_u64)?
+            ~ ( ON_ERROR ~ "=" ~ #ident)?
+            ~ ( MAX_ERRORS ~ "=" ~ #literal_u64)?
+            ~ ( MAX_FILE_SIZE ~ "=" ~ #literal_u64)?
+            ~ ( SINGLE ~ "=" ~ #literal_bool)?
             ~ ( PURGE ~ "=" ~ #literal_bool)?
             ~ ( FORCE ~ "=" ~ #literal_bool)?
         },
@@ -750,22 +1063,32 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             dst,
             _,
             src,
+            partition_by,
             files,
             pattern,
             file_format,
             validation_mode,
             size_limit,
+            on_error,
+            max_errors,
+            max_file_size,
+            single,
             purge,
             force,
         )| {
             Statement::Copy(CopyStmt {
                 src,
                 dst,
+                partition_by: partition_by.map(|v| v.3).unwrap_or_default(),
                 files: files.map(|v| v.3).unwrap_or_default(),
                 pattern: pattern.map(|v| v.2).unwrap_or_default(),
                 file_format: file_format.map(|v| v.2).unwrap_or_default(),
                 validation_mode: validation_mode.map(|v| v.2).unwrap_or_default(),
                 size_limit: size_limit.map(|v| v.2).unwrap_or_default() as usize,
+                on_error: on_error.map(|v| v.2.to_string()).unwrap_or_default(),
+                max_errors: max_errors.map(|v| v.2).unwrap_or_default(),
+                max_file_size: max_file_size.map(|v| v.2).unwrap_or_default(),
+                single: single.map(|v| v.2).unwrap_or_default(),
                 purge: purge.map(|v| v.2).unwrap_or_default(),
                 force: force.map(|v| v.2).unwrap_or_default(),
             })
@@ -878,10 +1201,241 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         |(_, _)| Statement::ShowShares(ShowSharesStmt {}),
     );
 
+    let create_row_access_policy = map(
+        rule! {
+            CREATE ~ ROW ~ ACCESS ~ POLICY ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #ident ~ ON ~ #peroid_separated_idents_1_to_3
+            ~ TO ~ ROLE ~ #literal_string
+            ~ USING ~ "(" ~ #expr ~ ")"
+        },
+        |(
+            _,
+            _,
+            _,
+            _,
+            opt_if_not_exists,
+            name,
+            _,
+            (catalog, database, table),
+            _,
+            _,
+            role_name,
+            _,
+            _,
+            predicate,
+            _,
+        )| {
+            Statement::CreateRowAccessPolicy(CreateRowAccessPolicyStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                name,
+                catalog,
+                database,
+                table,
+                role_name,
+                predicate,
+            })
+        },
+    );
+    let drop_row_access_policy = map(
+        rule! {
+            DROP ~ ROW ~ ACCESS ~ POLICY ~ ( IF ~ EXISTS )? ~ #ident
+        },
+        |(_, _, _, _, opt_if_exists, name)| {
+            Statement::DropRowAccessPolicy(DropRowAccessPolicyStmt {
+                if_exists: opt_if_exists.is_some(),
+                name,
+            })
+        },
+    );
+    let show_row_access_policies = value(
+        Statement::ShowRowAccessPolicies,
+        rule! { SHOW ~ ROW ~ ACCESS ~ POLICIES },
+    );
+
+    let create_masking_policy = map(
+        rule! {
+            CREATE ~ MASKING ~ POLICY ~ ( IF ~ NOT ~ EXISTS )?
+            ~ #ident
+            ~ AS ~ "(" ~ #ident ~ ")"
+            ~ "->" ~ #expr
+            ~ ( EXEMPT ~ ^ROLES ~ ^"(" ~ ^#comma_separated_list1(literal_string) ~ ^")" )?
+        },
+        |(_, _, _, opt_if_not_exists, name, _, _, parameter, _, _, body, opt_exempt_roles)| {
+            Statement::CreateMaskingPolicy(CreateMaskingPolicyStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                name,
+                parameter,
+                body,
+                exempt_roles: opt_exempt_roles
+                    .map(|(_, _, _, roles, _)| roles)
+                    .unwrap_or_default(),
+            })
+        },
+    );
+    let drop_masking_policy = map(
+        rule! {
+            DROP ~ MASKING ~ POLICY ~ ( IF ~ EXISTS )? ~ #ident
+        },
+        |(_, _, _, opt_if_exists, name)| {
+            Statement::DropMaskingPolicy(DropMaskingPolicyStmt {
+                if_exists: opt_if_exists.is_some(),
+                name,
+            })
+        },
+    );
+    let show_masking_policies = value(
+        Statement::ShowMaskingPolicies,
+        rule! { SHOW ~ MASKING ~ POLICIES },
+    );
+
+    let create_network_policy = map(
+        rule! {
+            CREATE ~ NETWORK ~ POLICY ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ "ALLOWED_IP_LIST" ~ ^"=" ~ ^"(" ~ ^#comma_separated_list0(literal_string) ~ ^")"
+            ~ ( "BLOCKED_IP_LIST" ~ ^"=" ~ ^"(" ~ ^#comma_separated_list0(literal_string) ~ ^")" )?
+            ~ ( COMMENT ~ ^"=" ~ ^#literal_string )?
+        },
+        |(
+            _,
+            _,
+            _,
+            opt_if_not_exists,
+            name,
+            _,
+            _,
+            _,
+            allowed_ip_list,
+            _,
+            opt_blocked_ip_list,
+            opt_comment,
+        )| {
+            Statement::CreateNetworkPolicy(CreateNetworkPolicyStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                name,
+                allowed_ip_list,
+                blocked_ip_list: opt_blocked_ip_list
+                    .map(|(_, _, _, ips, _)| ips)
+                    .unwrap_or_default(),
+                comment: opt_comment.map(|(_, _, comment)| comment),
+            })
+        },
+    );
+    let drop_network_policy = map(
+        rule! {
+            DROP ~ NETWORK ~ POLICY ~ ( IF ~ EXISTS )? ~ #ident
+        },
+        |(_, _, _, opt_if_exists, name)| {
+            Statement::DropNetworkPolicy(DropNetworkPolicyStmt {
+                if_exists: opt_if_exists.is_some(),
+                name,
+            })
+        },
+    );
+    let show_network_policies = value(
+        Statement::ShowNetworkPolicies,
+        rule! { SHOW ~ NETWORK ~ POLICIES },
+    );
+
+    let create_workload_group = map(
+        rule! {
+            CREATE ~ WORKLOAD ~ GROUP ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ ( "CPU_SHARE" ~ ^"=" ~ ^#literal_u64 )?
+            ~ ( "MEMORY_LIMIT" ~ ^"=" ~ ^#literal_u64 )?
+            ~ ( "MAX_CONCURRENCY" ~ ^"=" ~ ^#literal_u64 )?
+            ~ ( "MAX_SCAN_BYTES" ~ ^"=" ~ ^#literal_u64 )?
+            ~ ( COMMENT ~ ^"=" ~ ^#literal_string )?
+        },
+        |(
+            _,
+            _,
+            _,
+            opt_if_not_exists,
+            name,
+            opt_cpu_share,
+            opt_memory_limit,
+            opt_max_concurrency,
+            opt_max_scan_bytes,
+            opt_comment,
+        )| {
+            Statement::CreateWorkloadGroup(CreateWorkloadGroupStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                name,
+                cpu_share: opt_cpu_share.map(|(_, _, v)| v),
+                memory_limit: opt_memory_limit.map(|(_, _, v)| v),
+                max_concurrency: opt_max_concurrency.map(|(_, _, v)| v),
+                max_scan_bytes: opt_max_scan_bytes.map(|(_, _, v)| v),
+                comment: opt_comment.map(|(_, _, comment)| comment),
+            })
+        },
+    );
+    let drop_workload_group = map(
+        rule! {
+            DROP ~ WORKLOAD ~ GROUP ~ ( IF ~ EXISTS )? ~ #ident
+        },
+        |(_, _, _, opt_if_exists, name)| {
+            Statement::DropWorkloadGroup(DropWorkloadGroupStmt {
+                if_exists: opt_if_exists.is_some(),
+                name,
+            })
+        },
+    );
+    let show_workload_groups = value(
+        Statement::ShowWorkloadGroups,
+        rule! { SHOW ~ WORKLOAD ~ GROUPS },
+    );
+
+    let create_task = map(
+        rule! {
+            CREATE ~ TASK ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ "SCHEDULE" ~ ^"=" ~ ^#literal_string
+            ~ ( "ERROR_NOTIFICATION" ~ ^"=" ~ ^#literal_string )?
+            ~ ( COMMENT ~ ^"=" ~ ^#literal_string )?
+            ~ AS ~ ^#statement
+        },
+        |(
+            _,
+            _,
+            opt_if_not_exists,
+            name,
+            _,
+            _,
+            schedule,
+            opt_error_notification,
+            opt_comment,
+            _,
+            statement,
+        )| {
+            Statement::CreateTask(CreateTaskStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                name,
+                schedule,
+                error_notification: opt_error_notification.map(|(_, _, url)| url),
+                comment: opt_comment.map(|(_, _, comment)| comment),
+                statement: Box::new(statement.stmt),
+            })
+        },
+    );
+    let drop_task = map(
+        rule! {
+            DROP ~ TASK ~ ( IF ~ EXISTS )? ~ #ident
+        },
+        |(_, _, opt_if_exists, name)| {
+            Statement::DropTask(DropTaskStmt {
+                if_exists: opt_if_exists.is_some(),
+                name,
+            })
+        },
+    );
+    let show_tasks = value(Statement::ShowTasks, rule! { SHOW ~ TASKS });
+
+    let begin = value(Statement::Begin, rule! { BEGIN ~ TRANSACTION? });
+    let commit = value(Statement::Commit, rule! { COMMIT ~ TRANSACTION? });
+    let rollback = value(Statement::Rollback, rule! { ROLLBACK ~ TRANSACTION? });
+
     let statement_body = alt((
         rule!(
             #map(query, |query| Statement::Query(Box::new(query)))
-            | #explain : "`EXPLAIN [PIPELINE | GRAPH] <statement>`"
+            | #explain : "`EXPLAIN [PIPELINE | GRAPH | ANALYZE | ESTIMATE] <statement>`"
             | #insert : "`INSERT INTO [TABLE] <table> [(<column>, ...)] (FORMAT <format> | VALUES <values> | <query>)`"
             | #delete : "`DELETE FROM <table> [WHERE ...]`"
             | #show_settings : "`SHOW SETTINGS [<show_limit>]`"
@@ -891,6 +1445,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #show_metrics : "`SHOW METRICS`"
             | #show_functions : "`SHOW FUNCTIONS [<show_limit>]`"
             | #kill_stmt : "`KILL (QUERY | CONNECTION) <object_id>`"
+            | #set_role : "`SET ROLE { ALL | '<role_name>' }`"
             | #set_variable : "`SET <variable> = <value>`"
             | #show_databases : "`SHOW DATABASES [<show_limit>]`"
             | #undrop_database : "`UNDROP DATABASE <database>`"
@@ -907,18 +1462,31 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #show_fields : "`SHOW FIELDS FROM [<database>.]<table>`"
             | #show_tables_status : "`SHOW TABLES STATUS [FROM <database>] [<show_limit>]`"
             | #create_table : "`CREATE TABLE [IF NOT EXISTS] [<database>.]<table> [<source>] [<table_options>]`"
+            | #create_external_table : "`CREATE EXTERNAL TABLE [IF NOT EXISTS] [<database>.]<table> (<column_definition>, ...) LOCATION = @<stage>/<path> [FILE_FORMAT = (...)] [PARTITION BY (<column>, ...)]`"
+            | #attach_table : "`ATTACH TABLE [IF NOT EXISTS] [<database>.]<table> FROM '<storage_prefix>' [READ_ONLY]`"
             | #drop_table : "`DROP TABLE [IF EXISTS] [<database>.]<table>`"
             | #undrop_table : "`UNDROP TABLE [<database>.]<table>`"
             | #alter_table : "`ALTER TABLE [<database>.]<table> <action>`"
             | #rename_table : "`RENAME TABLE [<database>.]<table> TO <new_table>`"
             | #truncate_table : "`TRUNCATE TABLE [<database>.]<table> [PURGE]`"
-            | #optimize_table : "`OPTIMIZE TABLE [<database>.]<table> (ALL | PURGE | COMPACT)`"
+            | #flashback_table : "`FLASHBACK TABLE [<database>.]<table> TO (SNAPSHOT => '<snapshot_id>' | TIMESTAMP => <expr>)`"
+            | #optimize_table : "`OPTIMIZE TABLE [<database>.]<table> (ALL | PURGE | COMPACT [SEGMENT | BLOCK])`"
+            | #analyze_table : "`ANALYZE TABLE [<database>.]<table>`"
             | #exists_table : "`EXISTS TABLE [<database>.]<table>`"
         ),
         rule!(
             #create_view : "`CREATE VIEW [IF NOT EXISTS] [<database>.]<view> AS SELECT ...`"
             | #drop_view : "`DROP VIEW [IF EXISTS] [<database>.]<view>`"
             | #alter_view : "`ALTER VIEW [<database>.]<view> AS SELECT ...`"
+            | #create_stream : "`CREATE STREAM [IF NOT EXISTS] [<database>.]<stream> ON TABLE [<database>.]<table> [COMMENT = '<comment>']`"
+            | #drop_stream : "`DROP STREAM [IF EXISTS] [<database>.]<stream>`"
+            | #create_aggregating_index : "`CREATE AGGREGATING INDEX [IF NOT EXISTS] <index> AS SELECT ...`"
+            | #drop_aggregating_index : "`DROP AGGREGATING INDEX [IF EXISTS] <index> ON [<database>.]<table>`"
+            | #create_index : "`CREATE INDEX [IF NOT EXISTS] <index> TYPE {BLOOM | INVERTED} ON [<database>.]<table> (<column>, ...) [TOKENIZER = '<tokenizer>']`"
+            | #drop_index : "`DROP INDEX [IF EXISTS] <index> ON [<database>.]<table>`"
+            | #create_virtual_columns : "`CREATE VIRTUAL COLUMNS [IF NOT EXISTS] (<path>, ...) FOR [<database>.]<table>`"
+            | #alter_virtual_columns : "`ALTER VIRTUAL COLUMNS (<path>, ...) FOR [<database>.]<table>`"
+            | #drop_virtual_columns : "`DROP VIRTUAL COLUMNS [IF EXISTS] FOR [<database>.]<table>`"
         ),
         rule!(
             #show_users : "`SHOW USERS`"
@@ -929,6 +1497,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #create_role : "`CREATE ROLE [IF NOT EXISTS] '<role_name>']`"
             | #drop_role : "`DROP ROLE [IF EXISTS] '<role_name>'`"
             | #create_udf : "`CREATE FUNCTION [IF NOT EXISTS] <udf_name> (<parameter>, ...) -> <definition expr> [DESC = <description>]`"
+            | #create_udf_server : "`CREATE FUNCTION [IF NOT EXISTS] <udf_name> (<arg_type>, ...) RETURNS <return_type> LANGUAGE <language> HANDLER = <handler> ADDRESS = <address> [DESC = <description>]`"
             | #drop_udf : "`DROP FUNCTION [IF EXISTS] <udf_name>`"
             | #alter_udf : "`ALTER FUNCTION <udf_name> (<parameter>, ...) -> <definition_expr> [DESC = <description>]`"
         ),
@@ -973,6 +1542,36 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #desc_share: "`{DESC | DESCRIBE} SHARE <share_name>`"
             | #show_shares: "`SHOW SHARES`"
         ),
+        rule!(
+            #create_row_access_policy: "`CREATE ROW ACCESS POLICY [IF NOT EXISTS] <policy_name> ON [<database>.]<table> TO ROLE '<role_name>' USING (<predicate>)`"
+            | #drop_row_access_policy: "`DROP ROW ACCESS POLICY [IF EXISTS] <policy_name>`"
+            | #show_row_access_policies: "`SHOW ROW ACCESS POLICIES`"
+        ),
+        rule!(
+            #create_masking_policy: "`CREATE MASKING POLICY [IF NOT EXISTS] <policy_name> AS (<parameter>) -> <expr> [EXEMPT ROLES ('<role_name>', ...)]`"
+            | #drop_masking_policy: "`DROP MASKING POLICY [IF EXISTS] <policy_name>`"
+            | #show_masking_policies: "`SHOW MASKING POLICIES`"
+        ),
+        rule!(
+            #create_network_policy: "`CREATE NETWORK POLICY [IF NOT EXISTS] <policy_name> ALLOWED_IP_LIST = ('<ip>', ...) [BLOCKED_IP_LIST = ('<ip>', ...)] [COMMENT = '<string_literal>']`"
+            | #drop_network_policy: "`DROP NETWORK POLICY [IF EXISTS] <policy_name>`"
+            | #show_network_policies: "`SHOW NETWORK POLICIES`"
+        ),
+        rule!(
+            #create_workload_group: "`CREATE WORKLOAD GROUP [IF NOT EXISTS] <group_name> [CPU_SHARE = <u64>] [MEMORY_LIMIT = <u64>] [MAX_CONCURRENCY = <u64>] [MAX_SCAN_BYTES = <u64>] [COMMENT = '<string_literal>']`"
+            | #drop_workload_group: "`DROP WORKLOAD GROUP [IF EXISTS] <group_name>`"
+            | #show_workload_groups: "`SHOW WORKLOAD GROUPS`"
+        ),
+        rule!(
+            #create_task: "`CREATE TASK [IF NOT EXISTS] <task_name> SCHEDULE = '<cron>' [ERROR_NOTIFICATION = '<url>'] [COMMENT = '<string_literal>'] AS <statement>`"
+            | #drop_task: "`DROP TASK [IF EXISTS] <task_name>`"
+            | #show_tasks: "`SHOW TASKS`"
+        ),
+        rule!(
+            #begin: "`BEGIN [TRANSACTION]`"
+            | #commit: "`COMMIT [TRANSACTION]`"
+            | #rollback: "`ROLLBACK [TRANSACTION]`"
+        ),
     ));
 
     map(
@@ -1132,6 +1731,8 @@ pub fn priv_type(i: Input) -> IResult<UserPrivilegeType> {
         value(UserPrivilegeType::CreateRole, rule! { CREATE ~ ROLE }),
         value(UserPrivilegeType::Grant, rule! { GRANT }),
         value(UserPrivilegeType::CreateStage, rule! { CREATE ~ STAGE }),
+        value(UserPrivilegeType::Read, rule! { READ }),
+        value(UserPrivilegeType::Write, rule! { WRITE }),
         value(UserPrivilegeType::Set, rule! { SET }),
     ))(i)
 }
@@ -1197,8 +1798,20 @@ pub fn grant_level(i: Input) -> IResult<AccountMgrLevel> {
         },
     );
 
+    // STAGE s1
+    let stage = map(rule! { STAGE ~ #ident }, |(_, stage)| {
+        AccountMgrLevel::Stage(stage.name)
+    });
+
+    // UDF f1
+    let udf = map(rule! { UDF ~ #ident }, |(_, udf)| {
+        AccountMgrLevel::UDF(udf.name)
+    });
+
     rule!(
-        #global : "*.*"
+        #stage : "STAGE <stage>"
+        | #udf : "UDF <udf>"
+        | #global : "*.*"
         | #db : "<database>.*"
         | #table : "<database>.<table>"
     )(i)
@@ -1323,11 +1936,75 @@ pub fn alter_table_action(i: Input) -> IResult<AlterTableAction> {
         },
     );
 
+    let set_column_masking_policy = map(
+        rule! {
+            MODIFY ~ COLUMN ~ #ident ~ SET ~ MASKING ~ ^POLICY ~ ^#ident
+        },
+        |(_, _, column, _, _, _, policy)| AlterTableAction::SetColumnMaskingPolicy {
+            column,
+            policy,
+        },
+    );
+
+    let unset_column_masking_policy = map(
+        rule! {
+            MODIFY ~ COLUMN ~ #ident ~ UNSET ~ MASKING ~ ^POLICY
+        },
+        |(_, _, column, _, _, _)| AlterTableAction::UnsetColumnMaskingPolicy { column },
+    );
+
+    let add_column = map(
+        rule! {
+            ADD ~ COLUMN ~ ^#column_def
+        },
+        |(_, _, column)| AlterTableAction::AddColumn { column },
+    );
+
+    let drop_column = map(
+        rule! {
+            DROP ~ COLUMN ~ ^#ident
+        },
+        |(_, _, column)| AlterTableAction::DropColumn { column },
+    );
+
+    let rename_column = map(
+        rule! {
+            RENAME ~ COLUMN ~ ^#ident ~ ^TO ~ ^#ident
+        },
+        |(_, _, old_column, _, new_column)| AlterTableAction::RenameColumn {
+            old_column,
+            new_column,
+        },
+    );
+
+    let nullable = alt((
+        value(true, rule! { NULL }),
+        value(false, rule! { NOT ~ ^NULL }),
+    ));
+    let modify_column = map(
+        rule! {
+            MODIFY ~ COLUMN ~ #ident ~ #type_name ~ #nullable?
+        },
+        |(_, _, column, data_type, nullable)| {
+            let data_type = match nullable {
+                Some(true) => data_type.wrap_nullable(),
+                _ => data_type,
+            };
+            AlterTableAction::ModifyColumn { column, data_type }
+        },
+    );
+
     rule!(
         #rename_table
         | #alter_table_cluster_key
         | #drop_table_cluster_key
         | #recluster_table
+        | #set_column_masking_policy
+        | #unset_column_masking_policy
+        | #add_column
+        | #drop_column
+        | #rename_column
+        | #modify_column
     )(i)
 }
 
@@ -1335,7 +2012,18 @@ pub fn optimize_table_action(i: Input) -> IResult<OptimizeTableAction> {
     alt((
         value(OptimizeTableAction::All, rule! { ALL }),
         value(OptimizeTableAction::Purge, rule! { PURGE }),
-        value(OptimizeTableAction::Compact, rule! { COMPACT }),
+        map(rule! { COMPACT ~ #compact_target? }, |(_, target)| {
+            OptimizeTableAction::Compact {
+                target: target.unwrap_or(CompactTarget::Block),
+            }
+        }),
+    ))(i)
+}
+
+pub fn compact_target(i: Input) -> IResult<CompactTarget> {
+    alt((
+        value(CompactTarget::Block, rule! { BLOCK }),
+        value(CompactTarget::Segment, rule! { SEGMENT }),
     ))(i)
 }
 
@@ -1346,6 +2034,18 @@ pub fn kill_target(i: Input) -> IResult<KillTarget> {
     ))(i)
 }
 
+pub fn copy_partition_key(i: Input) -> IResult<CopyPartitionKey> {
+    map(
+        rule! {
+            #expr ~ ( AS ~ #ident )?
+        },
+        |(expr, alias)| CopyPartitionKey {
+            expr,
+            alias: alias.map(|(_, alias)| alias),
+        },
+    )(i)
+}
+
 /// Parse input into `CopyUnit`
 ///
 /// # Notes
@@ -1486,6 +2186,7 @@ pub fn engine(i: Input) -> IResult<Engine> {
         value(Engine::Fuse, rule! { FUSE }),
         value(Engine::View, rule! { VIEW }),
         value(Engine::Random, rule! { RANDOM }),
+        value(Engine::Delta, rule! { DELTA }),
     ));
 
     map(
@@ -1543,6 +2244,30 @@ pub fn user_option(i: Input) -> IResult<UserOptionItem> {
         },
         |(_, _, role)| UserOptionItem::DefaultRole(role),
     );
+    let network_policy_option = map(
+        rule! {
+            "NETWORK_POLICY" ~ "=" ~ #literal_string
+        },
+        |(_, _, policy)| UserOptionItem::NetworkPolicy(policy),
+    );
+    let workload_group_option = map(
+        rule! {
+            "WORKLOAD_GROUP" ~ "=" ~ #literal_string
+        },
+        |(_, _, group)| UserOptionItem::WorkloadGroup(group),
+    );
+    let set_setting_option = map(
+        rule! {
+            SET ~ #ident ~ "=" ~ ( #literal_string | #map(literal_u64, |v| v.to_string()) )
+        },
+        |(_, name, _, value)| UserOptionItem::SetSetting(name.name, value),
+    );
+    let unset_setting_option = map(
+        rule! {
+            UNSET ~ #ident
+        },
+        |(_, name)| UserOptionItem::UnsetSetting(name.name),
+    );
     alt((
         value(UserOptionItem::TenantSetting(true), rule! { TENANTSETTING }),
         value(
@@ -1550,6 +2275,10 @@ pub fn user_option(i: Input) -> IResult<UserOptionItem> {
             rule! { NOTENANTSETTING },
         ),
         default_role_option,
+        network_policy_option,
+        workload_group_option,
+        set_setting_option,
+        unset_setting_option,
     ))(i)
 }
 
@@ -1567,12 +2296,20 @@ pub fn user_identity(i: Input) -> IResult<UserIdentity> {
     )(i)
 }
 
+pub fn index_type(i: Input) -> IResult<IndexType> {
+    alt((
+        value(IndexType::Bloom, rule! { BLOOM }),
+        value(IndexType::Inverted, rule! { INVERTED }),
+    ))(i)
+}
+
 pub fn auth_type(i: Input) -> IResult<AuthType> {
     alt((
         value(AuthType::NoPassword, rule! { NO_PASSWORD }),
         value(AuthType::Sha256Password, rule! { SHA256_PASSWORD }),
         value(AuthType::DoubleSha1Password, rule! { DOUBLE_SHA1_PASSWORD }),
         value(AuthType::JWT, rule! { JWT }),
+        value(AuthType::LDAP, rule! { LDAP }),
     ))(i)
 }
 
@@ -1615,6 +2352,19 @@ pub fn presign_action(i: Input) -> IResult<PresignAction> {
     ))(i)
 }
 
+/// Parses a location in a stage like `@my_stage/path/to/dir`, into the name of the stage and
+/// the path which starts with `/`.
+pub fn stage_location(i: Input) -> IResult<(String, String)> {
+    map(at_string, |location| {
+        let parsed = location.splitn(2, '/').collect::<Vec<_>>();
+        if parsed.len() == 1 {
+            (parsed[0].to_string(), "/".to_string())
+        } else {
+            (parsed[0].to_string(), format!("/{}", parsed[1]))
+        }
+    })(i)
+}
+
 pub fn presign_location(i: Input) -> IResult<PresignLocation> {
     map(at_string, |location| {
         let parsed = location.splitn(2, '/').collect::<Vec<_>>();
@@ -1644,6 +2394,7 @@ pub fn table_reference_only(i: Input) -> IResult<TableReference> {
             table,
             alias: None,
             travel_point: None,
+            sample: None,
         },
     )(i)
 }
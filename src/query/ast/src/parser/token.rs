@@ -195,6 +195,8 @@ pub enum TokenKind {
     RBrace,
     #[token("->")]
     RArrow,
+    #[token("->>")]
+    LongRArrow,
     #[token("=>")]
     FatRArrow,
     #[token("#")]
@@ -239,10 +241,16 @@ pub enum TokenKind {
     // 2. Search in this file to see if the new keyword is a commented
     //    out reserverd keyword. If so, uncomment the keyword in the
     //    reserved list.
+    #[token("ACCESS", ignore(ascii_case))]
+    ACCESS,
     #[token("ALL", ignore(ascii_case))]
     ALL,
     #[token("ADD", ignore(ascii_case))]
     ADD,
+    #[token("ADDRESS", ignore(ascii_case))]
+    ADDRESS,
+    #[token("AGGREGATING", ignore(ascii_case))]
+    AGGREGATING,
     #[token("ANY", ignore(ascii_case))]
     ANY,
     #[token("SOME", ignore(ascii_case))]
@@ -261,16 +269,26 @@ pub enum TokenKind {
     AST,
     #[token("AT", ignore(ascii_case))]
     AT,
+    #[token("ATTACH", ignore(ascii_case))]
+    ATTACH,
     #[token("ASC", ignore(ascii_case))]
     ASC,
     #[token("AWS_KEY_ID", ignore(ascii_case))]
     AWS_KEY_ID,
     #[token("AWS_SECRET_KEY", ignore(ascii_case))]
     AWS_SECRET_KEY,
+    #[token("BEGIN", ignore(ascii_case))]
+    BEGIN,
+    #[token("BERNOULLI", ignore(ascii_case))]
+    BERNOULLI,
     #[token("BETWEEN", ignore(ascii_case))]
     BETWEEN,
     #[token("BIGINT", ignore(ascii_case))]
     BIGINT,
+    #[token("BLOCK", ignore(ascii_case))]
+    BLOCK,
+    #[token("BLOOM", ignore(ascii_case))]
+    BLOOM,
     #[token("BOOL", ignore(ascii_case))]
     BOOL,
     #[token("BOOLEAN", ignore(ascii_case))]
@@ -289,10 +307,16 @@ pub enum TokenKind {
     CENTURY,
     #[token("CLUSTER", ignore(ascii_case))]
     CLUSTER,
+    #[token("COLUMN", ignore(ascii_case))]
+    COLUMN,
+    #[token("COLUMNS", ignore(ascii_case))]
+    COLUMNS,
     #[token("COMMENT", ignore(ascii_case))]
     COMMENT,
     #[token("COMMENTS", ignore(ascii_case))]
     COMMENTS,
+    #[token("COMMIT", ignore(ascii_case))]
+    COMMIT,
     #[token("COMPACT", ignore(ascii_case))]
     COMPACT,
     #[token("CONNECTION", ignore(ascii_case))]
@@ -315,6 +339,8 @@ pub enum TokenKind {
     CROSS,
     #[token("CSV", ignore(ascii_case))]
     CSV,
+    #[token("CURRENT", ignore(ascii_case))]
+    CURRENT,
     #[token("CURRENT_TIMESTAMP", ignore(ascii_case))]
     CURRENT_TIMESTAMP,
     #[token("DATABASE", ignore(ascii_case))]
@@ -339,6 +365,8 @@ pub enum TokenKind {
     DEFAULT,
     #[token("DELETE", ignore(ascii_case))]
     DELETE,
+    #[token("DELTA", ignore(ascii_case))]
+    DELTA,
     #[token("DESC", ignore(ascii_case))]
     DESC,
     #[token("DESCRIBE", ignore(ascii_case))]
@@ -375,12 +403,18 @@ pub enum TokenKind {
     ENGINES,
     #[token("EPOCH", ignore(ascii_case))]
     EPOCH,
+    #[token("EXEMPT", ignore(ascii_case))]
+    EXEMPT,
     #[token("EXISTS", ignore(ascii_case))]
     EXISTS,
     #[token("EXPLAIN", ignore(ascii_case))]
     EXPLAIN,
+    #[token("ESTIMATE", ignore(ascii_case))]
+    ESTIMATE,
     #[token("EXPIRE", ignore(ascii_case))]
     EXPIRE,
+    #[token("EXTERNAL", ignore(ascii_case))]
+    EXTERNAL,
     #[token("EXTRACT", ignore(ascii_case))]
     EXTRACT,
     #[token("FALSE", ignore(ascii_case))]
@@ -395,12 +429,16 @@ pub enum TokenKind {
     FILES,
     #[token("FINAL", ignore(ascii_case))]
     FINAL,
+    #[token("FLASHBACK", ignore(ascii_case))]
+    FLASHBACK,
     #[token("FLOAT", ignore(ascii_case))]
     FLOAT,
     #[token("FLOAT32", ignore(ascii_case))]
     FLOAT32,
     #[token("FLOAT64", ignore(ascii_case))]
     FLOAT64,
+    #[token("FOLLOWING", ignore(ascii_case))]
+    FOLLOWING,
     #[token("FOR", ignore(ascii_case))]
     FOR,
     #[token("FORCE", ignore(ascii_case))]
@@ -419,14 +457,22 @@ pub enum TokenKind {
     FUNCTIONS,
     #[token("FUSE", ignore(ascii_case))]
     FUSE,
+    #[token("GEOGRAPHY", ignore(ascii_case))]
+    GEOGRAPHY,
+    #[token("GEOMETRY", ignore(ascii_case))]
+    GEOMETRY,
     #[token("GLOBAL", ignore(ascii_case))]
     GLOBAL,
     #[token("GRAPH", ignore(ascii_case))]
     GRAPH,
     #[token("GROUP", ignore(ascii_case))]
     GROUP,
+    #[token("GROUPS", ignore(ascii_case))]
+    GROUPS,
     #[token("HAVING", ignore(ascii_case))]
     HAVING,
+    #[token("HANDLER", ignore(ascii_case))]
+    HANDLER,
     #[token("HISTORY", ignore(ascii_case))]
     HISTORY,
     #[token("HOUR", ignore(ascii_case))]
@@ -439,6 +485,8 @@ pub enum TokenKind {
     IF,
     #[token("IN", ignore(ascii_case))]
     IN,
+    #[token("INDEX", ignore(ascii_case))]
+    INDEX,
     #[token("INNER", ignore(ascii_case))]
     INNER,
     #[token("INSERT", ignore(ascii_case))]
@@ -459,6 +507,8 @@ pub enum TokenKind {
     INTERVAL,
     #[token("INTO", ignore(ascii_case))]
     INTO,
+    #[token("INVERTED", ignore(ascii_case))]
+    INVERTED,
     #[token("IS", ignore(ascii_case))]
     IS,
     #[token("ISODOW", ignore(ascii_case))]
@@ -479,6 +529,12 @@ pub enum TokenKind {
     KILL,
     #[token("ROLES", ignore(ascii_case))]
     ROLES,
+    #[token("LANGUAGE", ignore(ascii_case))]
+    LANGUAGE,
+    #[token("LATERAL", ignore(ascii_case))]
+    LATERAL,
+    #[token("LDAP", ignore(ascii_case))]
+    LDAP,
     #[token("LEADING", ignore(ascii_case))]
     LEADING,
     #[token("LEFT", ignore(ascii_case))]
@@ -489,10 +545,18 @@ pub enum TokenKind {
     LIMIT,
     #[token("LIST", ignore(ascii_case))]
     LIST,
+    #[token("LOCATION", ignore(ascii_case))]
+    LOCATION,
     #[token("MAP", ignore(ascii_case))]
     MAP,
+    #[token("MASKING", ignore(ascii_case))]
+    MASKING,
     #[token("MASTER_KEY", ignore(ascii_case))]
     MASTER_KEY,
+    #[token("MAX_ERRORS", ignore(ascii_case))]
+    MAX_ERRORS,
+    #[token("MAX_FILE_SIZE", ignore(ascii_case))]
+    MAX_FILE_SIZE,
     #[token("MEMORY", ignore(ascii_case))]
     MEMORY,
     #[token("METRICS", ignore(ascii_case))]
@@ -505,10 +569,14 @@ pub enum TokenKind {
     MILLISECONDS,
     #[token("MINUTE", ignore(ascii_case))]
     MINUTE,
+    #[token("MODIFY", ignore(ascii_case))]
+    MODIFY,
     #[token("MONTH", ignore(ascii_case))]
     MONTH,
     #[token("NATURAL", ignore(ascii_case))]
     NATURAL,
+    #[token("NETWORK", ignore(ascii_case))]
+    NETWORK,
     #[token("NO_PASSWORD", ignore(ascii_case))]
     NO_PASSWORD,
     #[token("NOT", ignore(ascii_case))]
@@ -533,20 +601,30 @@ pub enum TokenKind {
     ORDER,
     #[token("OUTER", ignore(ascii_case))]
     OUTER,
+    #[token("OVER", ignore(ascii_case))]
+    OVER,
     #[token("ON_ERROR", ignore(ascii_case))]
     ON_ERROR,
     #[token("OVERWRITE", ignore(ascii_case))]
     OVERWRITE,
     #[token("PARQUET", ignore(ascii_case))]
     PARQUET,
+    #[token("PARTITION", ignore(ascii_case))]
+    PARTITION,
     #[token("PATTERN", ignore(ascii_case))]
     PATTERN,
     #[token("PIPELINE", ignore(ascii_case))]
     PIPELINE,
     #[token("PLAINTEXT_PASSWORD", ignore(ascii_case))]
     PLAINTEXT_PASSWORD,
+    #[token("POLICIES", ignore(ascii_case))]
+    POLICIES,
+    #[token("POLICY", ignore(ascii_case))]
+    POLICY,
     #[token("POSITION", ignore(ascii_case))]
     POSITION,
+    #[token("PRECEDING", ignore(ascii_case))]
+    PRECEDING,
     #[token("PROCESSLIST", ignore(ascii_case))]
     PROCESSLIST,
     #[token("PURGE", ignore(ascii_case))]
@@ -555,6 +633,12 @@ pub enum TokenKind {
     QUARTER,
     #[token("QUERY", ignore(ascii_case))]
     QUERY,
+    #[token("RANGE", ignore(ascii_case))]
+    RANGE,
+    #[token("READ", ignore(ascii_case))]
+    READ,
+    #[token("READ_ONLY", ignore(ascii_case))]
+    READ_ONLY,
     #[token("RECLUSTER", ignore(ascii_case))]
     RECLUSTER,
     #[token("RECORD_DELIMITER", ignore(ascii_case))]
@@ -569,12 +653,16 @@ pub enum TokenKind {
     GRANT,
     #[token("ROLE", ignore(ascii_case))]
     ROLE,
+    #[token("ROLLBACK", ignore(ascii_case))]
+    ROLLBACK,
     #[token("PRESIGN", ignore(ascii_case))]
     PRESIGN,
     #[token("PRIVILEGES", ignore(ascii_case))]
     PRIVILEGES,
     #[token("REMOVE", ignore(ascii_case))]
     REMOVE,
+    #[token("RETURNS", ignore(ascii_case))]
+    RETURNS,
     #[token("REVOKE", ignore(ascii_case))]
     REVOKE,
     #[token("RECURSIVE", ignore(ascii_case))]
@@ -585,6 +673,10 @@ pub enum TokenKind {
     RIGHT,
     #[token("RLIKE", ignore(ascii_case))]
     RLIKE,
+    #[token("ROW", ignore(ascii_case))]
+    ROW,
+    #[token("ROWS", ignore(ascii_case))]
+    ROWS,
     #[token("RAW", ignore(ascii_case))]
     RAW,
     #[token("SCHEMA", ignore(ascii_case))]
@@ -593,6 +685,8 @@ pub enum TokenKind {
     SCHEMAS,
     #[token("SECOND", ignore(ascii_case))]
     SECOND,
+    #[token("SEGMENT", ignore(ascii_case))]
+    SEGMENT,
     #[token("SELECT", ignore(ascii_case))]
     SELECT,
     #[token("SET", ignore(ascii_case))]
@@ -607,6 +701,8 @@ pub enum TokenKind {
     SHOW,
     #[token("SIGNED", ignore(ascii_case))]
     SIGNED,
+    #[token("SINGLE", ignore(ascii_case))]
+    SINGLE,
     #[token("SIZE_LIMIT", ignore(ascii_case))]
     SIZE_LIMIT,
     #[token("SKIP_HEADER", ignore(ascii_case))]
@@ -619,6 +715,8 @@ pub enum TokenKind {
     STAGE,
     #[token("SYNTAX", ignore(ascii_case))]
     SYNTAX,
+    #[token("SYSTEM", ignore(ascii_case))]
+    SYSTEM,
     #[token("USAGE", ignore(ascii_case))]
     USAGE,
     #[token("UPDATE", ignore(ascii_case))]
@@ -633,6 +731,8 @@ pub enum TokenKind {
     SUPER,
     #[token("STATUS", ignore(ascii_case))]
     STATUS,
+    #[token("STREAM", ignore(ascii_case))]
+    STREAM,
     #[token("STRING", ignore(ascii_case))]
     STRING,
     #[token("SUBSTRING", ignore(ascii_case))]
@@ -641,6 +741,12 @@ pub enum TokenKind {
     TABLE,
     #[token("TABLES", ignore(ascii_case))]
     TABLES,
+    #[token("TABLESAMPLE", ignore(ascii_case))]
+    TABLESAMPLE,
+    #[token("TASK", ignore(ascii_case))]
+    TASK,
+    #[token("TASKS", ignore(ascii_case))]
+    TASKS,
     #[token("TEXT", ignore(ascii_case))]
     TEXT,
     #[token("TENANTSETTING", ignore(ascii_case))]
@@ -663,8 +769,12 @@ pub enum TokenKind {
     TO,
     #[token("TOKEN", ignore(ascii_case))]
     TOKEN,
+    #[token("TOKENIZER", ignore(ascii_case))]
+    TOKENIZER,
     #[token("TRAILING", ignore(ascii_case))]
     TRAILING,
+    #[token("TRANSACTION", ignore(ascii_case))]
+    TRANSACTION,
     #[token("TRANSIENT", ignore(ascii_case))]
     TRANSIENT,
     #[token("TRIM", ignore(ascii_case))]
@@ -679,8 +789,12 @@ pub enum TokenKind {
     TUPLE,
     #[token("TYPE", ignore(ascii_case))]
     TYPE,
+    #[token("UDF", ignore(ascii_case))]
+    UDF,
     #[token("UNION", ignore(ascii_case))]
     UNION,
+    #[token("UNBOUNDED", ignore(ascii_case))]
+    UNBOUNDED,
     #[token("UINT16", ignore(ascii_case))]
     UINT16,
     #[token("UINT32", ignore(ascii_case))]
@@ -691,6 +805,8 @@ pub enum TokenKind {
     UINT8,
     #[token("UNDROP", ignore(ascii_case))]
     UNDROP,
+    #[token("UNSET", ignore(ascii_case))]
+    UNSET,
     #[token("UNSIGNED", ignore(ascii_case))]
     UNSIGNED,
     #[token("URL", ignore(ascii_case))]
@@ -713,6 +829,8 @@ pub enum TokenKind {
     VARIANT,
     #[token("VIEW", ignore(ascii_case))]
     VIEW,
+    #[token("VIRTUAL", ignore(ascii_case))]
+    VIRTUAL,
     #[token("WEEK", ignore(ascii_case))]
     WEEK,
     #[token("WHEN", ignore(ascii_case))]
@@ -721,6 +839,10 @@ pub enum TokenKind {
     WHERE,
     #[token("WITH", ignore(ascii_case))]
     WITH,
+    #[token("WORKLOAD", ignore(ascii_case))]
+    WORKLOAD,
+    #[token("WRITE", ignore(ascii_case))]
+    WRITE,
     #[token("XOR", ignore(ascii_case))]
     XOR,
     #[token("YEAR", ignore(ascii_case))]
@@ -782,6 +904,7 @@ impl TokenKind {
                 | LBrace
                 | RBrace
                 | RArrow
+                | LongRArrow
                 | FatRArrow
                 | Sharp
                 | Tilde
@@ -871,7 +994,7 @@ impl TokenKind {
             // | TokenKind::PRIMARY
             // | TokenKind::REAL
             // | TokenKind::REFERENCES
-            // | TokenKind::ROW
+            | TokenKind::ROW
             | TokenKind::SELECT
             // | TokenKind::SESSION_USER
             // | TokenKind::SETOF
@@ -986,7 +1109,7 @@ impl TokenKind {
             | TokenKind::INNER
             | TokenKind::IS
             | TokenKind::JOIN
-            // | TokenKind::LATERAL
+            | TokenKind::LATERAL
             | TokenKind::LEADING
             | TokenKind::LEFT
             | TokenKind::LIKE
@@ -1008,7 +1131,7 @@ impl TokenKind {
             | TokenKind::SOME
             // | TokenKind::SYMMETRIC
             // | TokenKind::TABLE
-            // | TokenKind::TABLESAMPLE
+            | TokenKind::TABLESAMPLE
             | TokenKind::THEN
             | TokenKind::TRAILING
             | TokenKind::TRUE
@@ -21,6 +21,7 @@ use common_functions::is_builtin_function;
 use crate::ast::Expr;
 use crate::ast::Identifier;
 use crate::ast::Literal;
+use crate::ast::WindowSpec;
 use crate::parser::token::Token;
 use crate::walk_expr;
 use crate::Visitor;
@@ -87,6 +88,7 @@ impl<'ast> Visitor<'ast> for UDFValidator {
         name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<WindowSpec<'ast>>,
     ) {
         let name = name.to_string();
         if !is_builtin_function(&name) && self.name.eq_ignore_ascii_case(&name) {
@@ -97,5 +99,13 @@ impl<'ast> Visitor<'ast> for UDFValidator {
         for arg in args {
             walk_expr(self, arg);
         }
+        if let Some(window) = window {
+            for expr in &window.partition_by {
+                walk_expr(self, expr);
+            }
+            for order_by in &window.order_by {
+                walk_expr(self, &order_by.expr);
+            }
+        }
     }
 }
@@ -233,10 +233,19 @@ pub trait Visitor<'ast>: Sized {
         _name: &'ast Identifier<'ast>,
         args: &'ast [Expr<'ast>],
         _params: &'ast [Literal],
+        window: &'ast Option<WindowSpec<'ast>>,
     ) {
         for arg in args {
             walk_expr(self, arg);
         }
+        if let Some(window) = window {
+            for expr in &window.partition_by {
+                walk_expr(self, expr);
+            }
+            for order_by in &window.order_by {
+                self.visit_order_by(order_by);
+            }
+        }
     }
 
     fn visit_case_when(
@@ -411,6 +420,8 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_column_definition(&mut self, _column_definition: &'ast ColumnDefinition<'ast>) {}
 
+    fn visit_attach_table(&mut self, _stmt: &'ast AttachTableStmt<'ast>) {}
+
     fn visit_drop_table(&mut self, _stmt: &'ast DropTableStmt<'ast>) {}
 
     fn visit_undrop_table(&mut self, _stmt: &'ast UndropTableStmt<'ast>) {}
@@ -421,8 +432,12 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_truncate_table(&mut self, _stmt: &'ast TruncateTableStmt<'ast>) {}
 
+    fn visit_flashback_table(&mut self, _stmt: &'ast FlashbackTableStmt<'ast>) {}
+
     fn visit_optimize_table(&mut self, _stmt: &'ast OptimizeTableStmt<'ast>) {}
 
+    fn visit_analyze_table(&mut self, _stmt: &'ast AnalyzeTableStmt<'ast>) {}
+
     fn visit_exists_table(&mut self, _stmt: &'ast ExistsTableStmt<'ast>) {}
 
     fn visit_create_view(&mut self, _stmt: &'ast CreateViewStmt<'ast>) {}
@@ -431,6 +446,24 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_drop_view(&mut self, _stmt: &'ast DropViewStmt<'ast>) {}
 
+    fn visit_create_stream(&mut self, _stmt: &'ast CreateStreamStmt<'ast>) {}
+
+    fn visit_drop_stream(&mut self, _stmt: &'ast DropStreamStmt<'ast>) {}
+
+    fn visit_create_aggregating_index(&mut self, _stmt: &'ast CreateAggregatingIndexStmt<'ast>) {}
+
+    fn visit_drop_aggregating_index(&mut self, _stmt: &'ast DropAggregatingIndexStmt<'ast>) {}
+
+    fn visit_create_index(&mut self, _stmt: &'ast CreateIndexStmt<'ast>) {}
+
+    fn visit_drop_index(&mut self, _stmt: &'ast DropIndexStmt<'ast>) {}
+
+    fn visit_create_virtual_columns(&mut self, _stmt: &'ast CreateVirtualColumnsStmt<'ast>) {}
+
+    fn visit_alter_virtual_columns(&mut self, _stmt: &'ast AlterVirtualColumnsStmt<'ast>) {}
+
+    fn visit_drop_virtual_columns(&mut self, _stmt: &'ast DropVirtualColumnsStmt<'ast>) {}
+
     fn visit_show_users(&mut self) {}
 
     fn visit_create_user(&mut self, _stmt: &'ast CreateUserStmt) {}
@@ -445,6 +478,8 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_drop_role(&mut self, _if_exists: bool, _role_name: &'ast str) {}
 
+    fn visit_set_role(&mut self, _role_name: &'ast Option<String>) {}
+
     fn visit_grant(&mut self, _grant: &'ast GrantStmt) {}
 
     fn visit_show_grant(&mut self, _principal: &'ast Option<PrincipalIdentity>) {}
@@ -461,6 +496,8 @@ pub trait Visitor<'ast>: Sized {
     ) {
     }
 
+    fn visit_create_udf_server(&mut self, _stmt: &'ast CreateUDFServerStmt<'ast>) {}
+
     fn visit_drop_udf(&mut self, _if_exists: bool, _udf_name: &'ast Identifier<'ast>) {}
 
     fn visit_alter_udf(
@@ -486,6 +523,36 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_presign(&mut self, _presign: &'ast PresignStmt) {}
 
+    fn visit_create_row_access_policy(&mut self, _stmt: &'ast CreateRowAccessPolicyStmt<'ast>) {}
+
+    fn visit_drop_row_access_policy(&mut self, _stmt: &'ast DropRowAccessPolicyStmt<'ast>) {}
+
+    fn visit_show_row_access_policies(&mut self) {}
+
+    fn visit_create_masking_policy(&mut self, _stmt: &'ast CreateMaskingPolicyStmt<'ast>) {}
+
+    fn visit_drop_masking_policy(&mut self, _stmt: &'ast DropMaskingPolicyStmt<'ast>) {}
+
+    fn visit_show_masking_policies(&mut self) {}
+
+    fn visit_create_network_policy(&mut self, _stmt: &'ast CreateNetworkPolicyStmt<'ast>) {}
+
+    fn visit_drop_network_policy(&mut self, _stmt: &'ast DropNetworkPolicyStmt<'ast>) {}
+
+    fn visit_show_network_policies(&mut self) {}
+
+    fn visit_create_workload_group(&mut self, _stmt: &'ast CreateWorkloadGroupStmt<'ast>) {}
+
+    fn visit_drop_workload_group(&mut self, _stmt: &'ast DropWorkloadGroupStmt<'ast>) {}
+
+    fn visit_show_workload_groups(&mut self) {}
+
+    fn visit_create_task(&mut self, _stmt: &'ast CreateTaskStmt<'ast>) {}
+
+    fn visit_drop_task(&mut self, _stmt: &'ast DropTaskStmt<'ast>) {}
+
+    fn visit_show_tasks(&mut self) {}
+
     fn visit_create_share(&mut self, _stmt: &'ast CreateShareStmt<'ast>) {}
 
     fn visit_drop_share(&mut self, _stmt: &'ast DropShareStmt<'ast>) {}
@@ -504,6 +571,12 @@ pub trait Visitor<'ast>: Sized {
 
     fn visit_show_grants_of_share(&mut self, _stmt: &'ast ShowGrantsOfShareStmt) {}
 
+    fn visit_begin(&mut self) {}
+
+    fn visit_commit(&mut self) {}
+
+    fn visit_rollback(&mut self) {}
+
     fn visit_with(&mut self, with: &'ast With<'ast>) {
         let With { ctes, .. } = with;
         for cte in ctes.iter() {
@@ -238,10 +238,19 @@ pub trait VisitorMut: Sized {
         _name: &mut Identifier<'_>,
         args: &mut [Expr<'_>],
         _params: &mut [Literal],
+        window: &mut Option<WindowSpec<'_>>,
     ) {
         for arg in args.iter_mut() {
             walk_expr_mut(self, arg);
         }
+        if let Some(window) = window {
+            for expr in window.partition_by.iter_mut() {
+                walk_expr_mut(self, expr);
+            }
+            for order_by in window.order_by.iter_mut() {
+                self.visit_order_by(order_by);
+            }
+        }
     }
 
     fn visit_case_when(
@@ -414,6 +423,8 @@ pub trait VisitorMut: Sized {
 
     fn visit_column_definition(&mut self, _column_definition: &mut ColumnDefinition<'_>) {}
 
+    fn visit_attach_table(&mut self, _stmt: &mut AttachTableStmt<'_>) {}
+
     fn visit_drop_table(&mut self, _stmt: &mut DropTableStmt<'_>) {}
 
     fn visit_undrop_table(&mut self, _stmt: &mut UndropTableStmt<'_>) {}
@@ -424,8 +435,12 @@ pub trait VisitorMut: Sized {
 
     fn visit_truncate_table(&mut self, _stmt: &mut TruncateTableStmt<'_>) {}
 
+    fn visit_flashback_table(&mut self, _stmt: &mut FlashbackTableStmt<'_>) {}
+
     fn visit_optimize_table(&mut self, _stmt: &mut OptimizeTableStmt<'_>) {}
 
+    fn visit_analyze_table(&mut self, _stmt: &mut AnalyzeTableStmt<'_>) {}
+
     fn visit_exists_table(&mut self, _stmt: &mut ExistsTableStmt<'_>) {}
 
     fn visit_create_view(&mut self, _stmt: &mut CreateViewStmt<'_>) {}
@@ -434,6 +449,24 @@ pub trait VisitorMut: Sized {
 
     fn visit_drop_view(&mut self, _stmt: &mut DropViewStmt<'_>) {}
 
+    fn visit_create_stream(&mut self, _stmt: &mut CreateStreamStmt<'_>) {}
+
+    fn visit_drop_stream(&mut self, _stmt: &mut DropStreamStmt<'_>) {}
+
+    fn visit_create_aggregating_index(&mut self, _stmt: &mut CreateAggregatingIndexStmt<'_>) {}
+
+    fn visit_drop_aggregating_index(&mut self, _stmt: &mut DropAggregatingIndexStmt<'_>) {}
+
+    fn visit_create_index(&mut self, _stmt: &mut CreateIndexStmt<'_>) {}
+
+    fn visit_drop_index(&mut self, _stmt: &mut DropIndexStmt<'_>) {}
+
+    fn visit_create_virtual_columns(&mut self, _stmt: &mut CreateVirtualColumnsStmt<'_>) {}
+
+    fn visit_alter_virtual_columns(&mut self, _stmt: &mut AlterVirtualColumnsStmt<'_>) {}
+
+    fn visit_drop_virtual_columns(&mut self, _stmt: &mut DropVirtualColumnsStmt<'_>) {}
+
     fn visit_show_users(&mut self) {}
 
     fn visit_create_user(&mut self, _stmt: &mut CreateUserStmt) {}
@@ -448,6 +481,8 @@ pub trait VisitorMut: Sized {
 
     fn visit_drop_role(&mut self, _if_exists: bool, _role_name: &mut String) {}
 
+    fn visit_set_role(&mut self, _role_name: &mut Option<String>) {}
+
     fn visit_grant(&mut self, _grant: &mut GrantStmt) {}
 
     fn visit_show_grant(&mut self, _principal: &mut Option<PrincipalIdentity>) {}
@@ -464,6 +499,8 @@ pub trait VisitorMut: Sized {
     ) {
     }
 
+    fn visit_create_udf_server(&mut self, _stmt: &mut CreateUDFServerStmt<'_>) {}
+
     fn visit_drop_udf(&mut self, _if_exists: bool, _udf_name: &mut Identifier<'_>) {}
 
     fn visit_alter_udf(
@@ -489,6 +526,36 @@ pub trait VisitorMut: Sized {
 
     fn visit_presign(&mut self, _presign: &mut PresignStmt) {}
 
+    fn visit_create_row_access_policy(&mut self, _stmt: &mut CreateRowAccessPolicyStmt<'_>) {}
+
+    fn visit_drop_row_access_policy(&mut self, _stmt: &mut DropRowAccessPolicyStmt<'_>) {}
+
+    fn visit_show_row_access_policies(&mut self) {}
+
+    fn visit_create_masking_policy(&mut self, _stmt: &mut CreateMaskingPolicyStmt<'_>) {}
+
+    fn visit_drop_masking_policy(&mut self, _stmt: &mut DropMaskingPolicyStmt<'_>) {}
+
+    fn visit_show_masking_policies(&mut self) {}
+
+    fn visit_create_network_policy(&mut self, _stmt: &mut CreateNetworkPolicyStmt<'_>) {}
+
+    fn visit_drop_network_policy(&mut self, _stmt: &mut DropNetworkPolicyStmt<'_>) {}
+
+    fn visit_show_network_policies(&mut self) {}
+
+    fn visit_create_workload_group(&mut self, _stmt: &mut CreateWorkloadGroupStmt<'_>) {}
+
+    fn visit_drop_workload_group(&mut self, _stmt: &mut DropWorkloadGroupStmt<'_>) {}
+
+    fn visit_show_workload_groups(&mut self) {}
+
+    fn visit_create_task(&mut self, _stmt: &mut CreateTaskStmt<'_>) {}
+
+    fn visit_drop_task(&mut self, _stmt: &mut DropTaskStmt<'_>) {}
+
+    fn visit_show_tasks(&mut self) {}
+
     fn visit_create_share(&mut self, _stmt: &mut CreateShareStmt<'_>) {}
 
     fn visit_drop_share(&mut self, _stmt: &mut DropShareStmt<'_>) {}
@@ -507,6 +574,12 @@ pub trait VisitorMut: Sized {
 
     fn visit_show_grants_of_share(&mut self, _stmt: &mut ShowGrantsOfShareStmt) {}
 
+    fn visit_begin(&mut self) {}
+
+    fn visit_commit(&mut self) {}
+
+    fn visit_rollback(&mut self) {}
+
     fn visit_with(&mut self, with: &mut With<'_>) {
         let With { ctes, .. } = with;
         for cte in ctes.iter_mut() {
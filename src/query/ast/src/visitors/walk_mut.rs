@@ -93,7 +93,8 @@ pub fn walk_expr_mut<'a, V: VisitorMut>(visitor: &mut V, expr: &mut Expr<'a>) {
             name,
             args,
             params,
-        } => visitor.visit_function_call(span, *distinct, name, args, params),
+            window,
+        } => visitor.visit_function_call(span, *distinct, name, args, params, window),
         Expr::Case {
             span,
             operand,
@@ -327,16 +328,28 @@ pub fn walk_statement_mut<'a, V: VisitorMut>(visitor: &mut V, statement: &mut St
         Statement::DescribeTable(stmt) => visitor.visit_describe_table(stmt),
         Statement::ShowTablesStatus(stmt) => visitor.visit_show_tables_status(stmt),
         Statement::CreateTable(stmt) => visitor.visit_create_table(stmt),
+        Statement::AttachTable(stmt) => visitor.visit_attach_table(stmt),
         Statement::DropTable(stmt) => visitor.visit_drop_table(stmt),
         Statement::UndropTable(stmt) => visitor.visit_undrop_table(stmt),
         Statement::AlterTable(stmt) => visitor.visit_alter_table(stmt),
         Statement::RenameTable(stmt) => visitor.visit_rename_table(stmt),
         Statement::TruncateTable(stmt) => visitor.visit_truncate_table(stmt),
+        Statement::FlashbackTable(stmt) => visitor.visit_flashback_table(stmt),
         Statement::OptimizeTable(stmt) => visitor.visit_optimize_table(stmt),
+        Statement::AnalyzeTable(stmt) => visitor.visit_analyze_table(stmt),
         Statement::ExistsTable(stmt) => visitor.visit_exists_table(stmt),
         Statement::CreateView(stmt) => visitor.visit_create_view(stmt),
         Statement::AlterView(stmt) => visitor.visit_alter_view(stmt),
         Statement::DropView(stmt) => visitor.visit_drop_view(stmt),
+        Statement::CreateStream(stmt) => visitor.visit_create_stream(stmt),
+        Statement::DropStream(stmt) => visitor.visit_drop_stream(stmt),
+        Statement::CreateAggregatingIndex(stmt) => visitor.visit_create_aggregating_index(stmt),
+        Statement::DropAggregatingIndex(stmt) => visitor.visit_drop_aggregating_index(stmt),
+        Statement::CreateIndex(stmt) => visitor.visit_create_index(stmt),
+        Statement::DropIndex(stmt) => visitor.visit_drop_index(stmt),
+        Statement::CreateVirtualColumns(stmt) => visitor.visit_create_virtual_columns(stmt),
+        Statement::AlterVirtualColumns(stmt) => visitor.visit_alter_virtual_columns(stmt),
+        Statement::DropVirtualColumns(stmt) => visitor.visit_drop_virtual_columns(stmt),
         Statement::ShowUsers => visitor.visit_show_users(),
         Statement::ShowRoles => visitor.visit_show_roles(),
         Statement::CreateUser(stmt) => visitor.visit_create_user(stmt),
@@ -350,6 +363,7 @@ pub fn walk_statement_mut<'a, V: VisitorMut>(visitor: &mut V, statement: &mut St
             if_exists,
             role_name,
         } => visitor.visit_drop_role(*if_exists, role_name),
+        Statement::SetRole { role_name } => visitor.visit_set_role(role_name),
         Statement::Grant(stmt) => visitor.visit_grant(stmt),
         Statement::ShowGrants { principal } => visitor.visit_show_grant(principal),
         Statement::Revoke(stmt) => visitor.visit_revoke(stmt),
@@ -366,6 +380,7 @@ pub fn walk_statement_mut<'a, V: VisitorMut>(visitor: &mut V, statement: &mut St
             definition,
             description,
         ),
+        Statement::CreateUDFServer(stmt) => visitor.visit_create_udf_server(stmt),
         Statement::DropUDF {
             if_exists,
             udf_name,
@@ -389,6 +404,21 @@ pub fn walk_statement_mut<'a, V: VisitorMut>(visitor: &mut V, statement: &mut St
         Statement::DescribeStage { stage_name } => visitor.visit_describe_stage(stage_name),
         Statement::Call(stmt) => visitor.visit_call(stmt),
         Statement::Presign(stmt) => visitor.visit_presign(stmt),
+        Statement::CreateRowAccessPolicy(stmt) => visitor.visit_create_row_access_policy(stmt),
+        Statement::DropRowAccessPolicy(stmt) => visitor.visit_drop_row_access_policy(stmt),
+        Statement::ShowRowAccessPolicies => visitor.visit_show_row_access_policies(),
+        Statement::CreateMaskingPolicy(stmt) => visitor.visit_create_masking_policy(stmt),
+        Statement::DropMaskingPolicy(stmt) => visitor.visit_drop_masking_policy(stmt),
+        Statement::ShowMaskingPolicies => visitor.visit_show_masking_policies(),
+        Statement::CreateNetworkPolicy(stmt) => visitor.visit_create_network_policy(stmt),
+        Statement::DropNetworkPolicy(stmt) => visitor.visit_drop_network_policy(stmt),
+        Statement::ShowNetworkPolicies => visitor.visit_show_network_policies(),
+        Statement::CreateWorkloadGroup(stmt) => visitor.visit_create_workload_group(stmt),
+        Statement::DropWorkloadGroup(stmt) => visitor.visit_drop_workload_group(stmt),
+        Statement::ShowWorkloadGroups => visitor.visit_show_workload_groups(),
+        Statement::CreateTask(stmt) => visitor.visit_create_task(stmt),
+        Statement::DropTask(stmt) => visitor.visit_drop_task(stmt),
+        Statement::ShowTasks => visitor.visit_show_tasks(),
         Statement::CreateShare(stmt) => visitor.visit_create_share(stmt),
         Statement::DropShare(stmt) => visitor.visit_drop_share(stmt),
         Statement::GrantShareObject(stmt) => visitor.visit_grant_share_object(stmt),
@@ -400,5 +430,8 @@ pub fn walk_statement_mut<'a, V: VisitorMut>(visitor: &mut V, statement: &mut St
             visitor.visit_show_object_grant_privileges(stmt)
         }
         Statement::ShowGrantsOfShare(stmt) => visitor.visit_show_grants_of_share(stmt),
+        Statement::Begin => visitor.visit_begin(),
+        Statement::Commit => visitor.visit_commit(),
+        Statement::Rollback => visitor.visit_rollback(),
     }
 }
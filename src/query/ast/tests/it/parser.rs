@@ -71,6 +71,8 @@ fn test_statement() {
         r#"show create table a.b;"#,
         r#"show create table a.b format TabSeparatedWithNamesAndTypes;"#,
         r#"explain pipeline select a from b;"#,
+        r#"explain analyze select a from b;"#,
+        r#"explain estimate select a from b;"#,
         r#"describe a;"#,
         r#"describe a format TabSeparatedWithNamesAndTypes;"#,
         r#"create table if not exists a.b (c integer not null default 1, b varchar);"#,
@@ -78,8 +80,11 @@ fn test_statement() {
         r#"create table if not exists a.b (c tuple(m integer, n string), d tuple(integer, string));"#,
         r#"create table a.b like c.d;"#,
         r#"create table t like t2 engine = memory;"#,
+        r#"create external table t (a int, b varchar, dt date) location = @s1/data/ file_format = (type = 'CSV' skip_header = 1) partition by (dt);"#,
+        r#"attach table db1.t from '1/2/' read_only;"#,
         r#"truncate table a;"#,
         r#"truncate table "a".b;"#,
+        r#"flashback table t to (snapshot => 'abc');"#,
         r#"drop table a;"#,
         r#"drop table if exists a."b";"#,
         r#"use "a";"#,
@@ -93,9 +98,21 @@ fn test_statement() {
         r#"create view v as select number % 3 as a from numbers(1000);"#,
         r#"alter view v as select number % 3 as a from numbers(1000);"#,
         r#"drop view v;"#,
+        r#"CREATE STREAM IF NOT EXISTS db1.s1 ON TABLE t1 COMMENT = 'changes of t1';"#,
+        r#"DROP STREAM IF EXISTS s1;"#,
+        r#"CREATE AGGREGATING INDEX idx1 AS SELECT a, COUNT(*) FROM t GROUP BY a;"#,
+        r#"DROP AGGREGATING INDEX IF EXISTS idx1 ON db1.t;"#,
+        r#"CREATE INDEX IF NOT EXISTS idx2 TYPE BLOOM ON t(a, b);"#,
+        r#"CREATE INDEX idx3 TYPE INVERTED ON t(msg) TOKENIZER = 'whitespace';"#,
+        r#"DROP INDEX idx2 ON db1.t;"#,
+        r#"CREATE VIRTUAL COLUMNS (v['a'][0], v:b) FOR db1.t;"#,
+        r#"DROP VIRTUAL COLUMNS IF EXISTS FOR t;"#,
         r#"rename table d.t to e.s;"#,
         r#"truncate table test;"#,
         r#"truncate table test_db.test;"#,
+        r#"analyze table test_db.test;"#,
+        r#"optimize table t compact;"#,
+        r#"optimize table t compact segment;"#,
         r#"DROP table table1;"#,
         r#"DROP table IF EXISTS table1;"#,
         r#"CREATE TABLE t(c1 int null, c2 bigint null, c3 varchar null);"#,
@@ -104,7 +121,11 @@ fn test_statement() {
         r#"ALTER USER u1 IDENTIFIED BY '123456';"#,
         r#"ALTER USER u1 WITH DEFAULT_ROLE = 'role1';"#,
         r#"ALTER USER u1 WITH DEFAULT_ROLE = 'role1', TENANTSETTING;"#,
+        r#"ALTER USER u1 WITH NETWORK_POLICY = 'np1';"#,
+        r#"ALTER USER u1 WITH WORKLOAD_GROUP = 'etl';"#,
+        r#"ALTER USER u1 WITH SET max_threads = 4, UNSET max_block_size;"#,
         r#"CREATE USER u1 IDENTIFIED BY '123456' WITH DEFAULT_ROLE='role123', TENANTSETTING"#,
+        r#"CREATE USER u1 IDENTIFIED WITH ldap WITH DEFAULT_ROLE='role123';"#,
         r#"DROP database if exists db1;"#,
         r#"select distinct a, count(*) from t where a = 1 and b - 1 < a group by a having a = 1;"#,
         r#"select * from t4;"#,
@@ -139,6 +160,12 @@ fn test_statement() {
         r#"ALTER TABLE t CLUSTER BY(c1);"#,
         r#"ALTER TABLE t DROP CLUSTER KEY;"#,
         r#"ALTER TABLE t RECLUSTER FINAL WHERE c1 > 0;"#,
+        r#"ALTER TABLE t MODIFY COLUMN c1 SET MASKING POLICY p1;"#,
+        r#"ALTER TABLE t MODIFY COLUMN c1 UNSET MASKING POLICY;"#,
+        r#"ALTER TABLE t ADD COLUMN c2 INT DEFAULT 1;"#,
+        r#"ALTER TABLE t DROP COLUMN c2;"#,
+        r#"ALTER TABLE t RENAME COLUMN c1 TO c3;"#,
+        r#"ALTER TABLE t MODIFY COLUMN c1 BIGINT NULL;"#,
         r#"ALTER DATABASE IF EXISTS catalog.c RENAME TO a;"#,
         r#"ALTER DATABASE c RENAME TO a;"#,
         r#"ALTER DATABASE catalog.c RENAME TO a;"#,
@@ -169,6 +196,29 @@ fn test_statement() {
         r#"REVOKE SELECT, CREATE ON * FROM 'test-grant'@'localhost';"#,
         r#"REVOKE SELECT ON tb1 FROM ROLE 'role1';"#,
         r#"REVOKE ALL ON tb1 FROM 'u1';"#,
+        r#"GRANT READ, WRITE ON STAGE s1 TO ROLE 'role1';"#,
+        r#"GRANT USAGE ON UDF f1 TO 'u1';"#,
+        r#"REVOKE WRITE ON STAGE s1 FROM ROLE 'role1';"#,
+        r#"SET ROLE 'role1';"#,
+        r#"SET ROLE ALL;"#,
+        r#"CREATE ROW ACCESS POLICY p1 ON db1.t1 TO ROLE 'role1' USING (region = 'EU');"#,
+        r#"DROP ROW ACCESS POLICY IF EXISTS p1;"#,
+        r#"SHOW ROW ACCESS POLICIES;"#,
+        r#"CREATE MASKING POLICY email_mask AS (val) -> '*****' EXEMPT ROLES ('analyst');"#,
+        r#"DROP MASKING POLICY IF EXISTS email_mask;"#,
+        r#"SHOW MASKING POLICIES;"#,
+        r#"CREATE NETWORK POLICY np1 ALLOWED_IP_LIST = ('192.168.1.0/24') BLOCKED_IP_LIST = ('192.168.1.99') COMMENT = 'office';"#,
+        r#"DROP NETWORK POLICY IF EXISTS np1;"#,
+        r#"SHOW NETWORK POLICIES;"#,
+        r#"CREATE WORKLOAD GROUP etl CPU_SHARE = 50 MAX_CONCURRENCY = 4 COMMENT = 'batch';"#,
+        r#"DROP WORKLOAD GROUP IF EXISTS etl;"#,
+        r#"SHOW WORKLOAD GROUPS;"#,
+        r#"CREATE TASK IF NOT EXISTS compact_t SCHEDULE = '0 * * * *' ERROR_NOTIFICATION = 'http://alert/hook' COMMENT = 'hourly' AS OPTIMIZE TABLE t COMPACT;"#,
+        r#"DROP TASK IF EXISTS compact_t;"#,
+        r#"SHOW TASKS;"#,
+        r#"BEGIN;"#,
+        r#"COMMIT TRANSACTION;"#,
+        r#"ROLLBACK;"#,
         r#"COPY INTO mytable
                 FROM 's3://mybucket/data.csv'
                 FILE_FORMAT = (
@@ -255,6 +305,29 @@ fn test_statement() {
                     skip_header = 1
                 )
                 force=true;"#,
+        r#"COPY INTO mytable
+                FROM @external_stage/path/to/file.csv
+                FILE_FORMAT = (
+                    type = 'CSV'
+                    quote = '"'
+                    null_display = 'NULL'
+                    allow_column_count_mismatch = 'true'
+                )
+                on_error = continue
+                max_errors = 10;"#,
+        r#"COPY INTO @my_stage/unload/
+                FROM mytable
+                FILE_FORMAT = (
+                    type = 'CSV'
+                    compression = 'gzip'
+                )
+                max_file_size = 1048576
+                single = true;"#,
+        r#"COPY INTO @my_stage/unload/
+                FROM mytable
+                PARTITION BY (dt, to_yyyymm(ts) AS ym)
+                FILE_FORMAT = (type = 'PARQUET')
+                max_file_size = 1048576;"#,
         // We used to support COPY FROM a quoted at string
         // r#"COPY INTO mytable
         //         FROM '@external_stage/path/to/file.csv'
@@ -288,6 +361,7 @@ fn test_statement() {
         r#"SHOW GRANTS ON TABLE db1.tb1;"#,
         r#"SHOW GRANTS ON DATABASE db;"#,
         r#"SHOW GRANTS OF SHARE t;"#,
+        r#"CREATE FUNCTION IF NOT EXISTS gcd (INT, INT) RETURNS BIGINT LANGUAGE python HANDLER = 'gcd' ADDRESS = 'http://0.0.0.0:8815';"#,
     ];
 
     for case in cases {
@@ -396,6 +470,7 @@ fn test_query() {
         r#"select * from t1 union select * from t2 intersect select * from t3"#,
         r#"(select * from t1 union select * from t2) union select * from t3"#,
         r#"select * from t1 union (select * from t2 union select * from t3)"#,
+        r#"select * from t1 as t tablesample bernoulli (0.5)"#,
     ];
 
     for case in cases {
@@ -488,6 +563,9 @@ fn test_expr() {
         r#"1 is distinct from 2"#,
         r#"a is distinct from b"#,
         r#"1 is not distinct from null"#,
+        r#"sum(a) OVER (PARTITION BY b ORDER BY c ROWS 1 PRECEDING)"#,
+        r#"a->'b'->>0"#,
+        r#"CAST(col1 AS GEOMETRY)"#,
     ];
 
     for case in cases {
@@ -16,6 +16,7 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_config::CatalogConfig;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::CountTablesReply;
@@ -41,6 +42,8 @@ use common_meta_app::schema::UndropDatabaseReply;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReply;
 use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateMultiTableMetaReply;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReply;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_app::schema::UpsertTableCopiedFileReply;
@@ -57,8 +60,15 @@ use crate::table_function::TableFunction;
 
 pub const CATALOG_DEFAULT: &str = "default";
 
+/// Creates the catalogs of one type from their configs.
+pub trait CatalogCreator: Send + Sync {
+    fn try_create(&self, name: &str, config: &CatalogConfig) -> Result<Arc<dyn Catalog>>;
+}
+
 pub struct CatalogManager {
     pub catalogs: HashMap<String, Arc<dyn Catalog>>,
+    /// The creators of the external catalogs, keyed by the catalog type, like `hive`.
+    pub catalog_creators: HashMap<String, Arc<dyn CatalogCreator>>,
 }
 impl CatalogManager {
     pub fn get_catalog(&self, catalog_name: &str) -> Result<Arc<dyn Catalog>> {
@@ -67,6 +77,47 @@ impl CatalogManager {
             .cloned()
             .ok_or_else(|| ErrorCode::BadArguments(format!("not such catalog {}", catalog_name)))
     }
+
+    /// The names of all the catalogs, sorted.
+    pub fn list_catalogs(&self) -> Vec<String> {
+        let mut names = self.catalogs.keys().cloned().collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    pub fn register_catalog_creator(
+        &mut self,
+        catalog_type: &str,
+        creator: Arc<dyn CatalogCreator>,
+    ) {
+        self.catalog_creators
+            .insert(catalog_type.to_string(), creator);
+    }
+
+    /// Create the catalog `name` by the creator of its type, and register it.
+    pub fn create_catalog(&mut self, name: &str, config: &CatalogConfig) -> Result<()> {
+        if self.catalogs.contains_key(name) {
+            return Err(ErrorCode::InvalidConfig(format!(
+                "catalog {} already exists",
+                name
+            )));
+        }
+
+        let creator = self
+            .catalog_creators
+            .get(config.catalog_type())
+            .ok_or_else(|| {
+                ErrorCode::InvalidConfig(format!(
+                    "catalog type {} of catalog {} is not supported, \
+                    the query node may be built without the feature of it",
+                    config.catalog_type(),
+                    name
+                ))
+            })?;
+        let catalog = creator.try_create(name, config)?;
+        self.catalogs.insert(name.to_string(), catalog);
+        Ok(())
+    }
 }
 
 #[derive(Default, Clone)]
@@ -156,6 +207,16 @@ pub trait Catalog: DynClone + Send + Sync {
 
     async fn update_table_meta(&self, req: UpdateTableMetaReq) -> Result<UpdateTableMetaReply>;
 
+    /// Update the meta of several tables atomically, used to commit a transaction.
+    async fn update_multi_table_meta(
+        &self,
+        _req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply> {
+        Err(ErrorCode::UnImplement(
+            "'update_multi_table_meta' not implemented",
+        ))
+    }
+
     async fn count_tables(&self, req: CountTablesReq) -> Result<CountTablesReply>;
 
     async fn get_table_copied_file_info(
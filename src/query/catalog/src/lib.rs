@@ -17,8 +17,10 @@
 pub mod catalog;
 pub mod cluster_info;
 pub mod database;
+pub mod runtime_filter;
 pub mod table;
 pub mod table_args;
 pub mod table_context;
 pub mod table_function;
 pub mod table_mutator;
+pub mod txn;
@@ -0,0 +1,176 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use common_datavalues::ColumnRef;
+use common_datavalues::ColumnWithField;
+use common_datavalues::DataField;
+use common_datavalues::DataValue;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
+use common_exception::Result;
+use common_functions::aggregates::eval_aggr;
+
+/// Bits of the bloom filter for each key of the build side, about 2% false positives.
+const BLOOM_BITS_PER_KEY: usize = 10;
+const BLOOM_HASHES: u64 = 3;
+
+/// Rows a filter checks before deciding whether it's worth checking the others.
+const ADAPTIVE_CHECK_ROWS: u64 = 65536;
+/// A filter stops checking the rows if it filters out less than this ratio of them.
+const ADAPTIVE_MIN_FILTERED_RATIO: f64 = 0.1;
+
+/// A filter on a column of the table scanned by the probe side of a hash join, built from the
+/// keys of the build side once its hash table is built.
+///
+/// The scan skips the blocks whose range of the column doesn't overlap the range of the keys,
+/// and drops the rows whose values are not in the bloom filter of the keys. Both may keep the
+/// rows matching nothing, which the join drops anyway.
+pub struct RuntimeFilter {
+    column_name: String,
+    /// Range of the non-null keys, `Null` if there is none
+    min: DataValue,
+    max: DataValue,
+    bloom: Vec<u64>,
+
+    checked_rows: AtomicU64,
+    filtered_rows: AtomicU64,
+    /// Set if the bloom filter drops too few rows to pay off
+    disabled: AtomicBool,
+}
+
+impl RuntimeFilter {
+    /// Build the filter of the column `column_name` from the chunks of a key of the build side.
+    pub fn try_create(column_name: String, keys: &[&ColumnRef]) -> Result<RuntimeFilter> {
+        let num_keys: usize = keys.iter().map(|key| key.len()).sum();
+        let num_bits = (num_keys * BLOOM_BITS_PER_KEY).next_power_of_two().max(64);
+        let mut bloom = vec![0u64; num_bits / 64];
+
+        let mut min = DataValue::Null;
+        let mut max = DataValue::Null;
+        for key in keys {
+            for hash in Self::hash_column(key).into_iter().flatten() {
+                for bit in Self::bloom_bits(hash, num_bits) {
+                    bloom[bit / 64] |= 1 << (bit % 64);
+                }
+            }
+
+            // later, during the evaluation of aggregations, name of field does not matter
+            let field = ColumnWithField::new((*key).clone(), DataField::new("", key.data_type()));
+            let mins = eval_aggr("min", vec![], &[field.clone()], key.len())?;
+            let maxs = eval_aggr("max", vec![], &[field], key.len())?;
+            if mins.len() > 0 {
+                min = match (min, mins.get(0)) {
+                    (DataValue::Null, v) | (v, DataValue::Null) => v,
+                    (l, r) => l.min(r),
+                };
+            }
+            if maxs.len() > 0 {
+                max = match (max, maxs.get(0)) {
+                    (DataValue::Null, v) | (v, DataValue::Null) => v,
+                    (l, r) => l.max(r),
+                };
+            }
+        }
+
+        Ok(RuntimeFilter {
+            column_name,
+            min,
+            max,
+            bloom,
+            checked_rows: AtomicU64::new(0),
+            filtered_rows: AtomicU64::new(0),
+            disabled: AtomicBool::new(false),
+        })
+    }
+
+    pub fn column_name(&self) -> &str {
+        &self.column_name
+    }
+
+    /// Whether any value between `min` and `max` may match the keys, `Null` bounds are unknown.
+    pub fn may_match_range(&self, min: &DataValue, max: &DataValue) -> bool {
+        if self.min.is_null() {
+            // no key matches anything
+            return false;
+        }
+
+        let below = !max.is_null() && *max < self.min;
+        let above = !min.is_null() && *min > self.max;
+        !below && !above
+    }
+
+    /// Filter the values of the column by the bloom filter, `None` if it's disabled.
+    ///
+    /// It's disabled once it has checked enough rows and dropped too few of them.
+    pub fn filter(&self, column: &ColumnRef) -> Option<ColumnRef> {
+        if self.disabled.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let num_bits = self.bloom.len() * 64;
+        let predicate: Vec<bool> = Self::hash_column(column)
+            .into_iter()
+            .map(|hash| match hash {
+                None => false,
+                Some(hash) => Self::bloom_bits(hash, num_bits)
+                    .all(|bit| self.bloom[bit / 64] & (1 << (bit % 64)) != 0),
+            })
+            .collect();
+
+        let num_rows = column.len() as u64;
+        let filtered = predicate.iter().filter(|keep| !**keep).count() as u64;
+        let checked = self.checked_rows.fetch_add(num_rows, Ordering::Relaxed) + num_rows;
+        let filtered = self.filtered_rows.fetch_add(filtered, Ordering::Relaxed) + filtered;
+        if checked >= ADAPTIVE_CHECK_ROWS
+            && (filtered as f64) < (checked as f64) * ADAPTIVE_MIN_FILTERED_RATIO
+        {
+            self.disabled.store(true, Ordering::Relaxed);
+        }
+
+        Some(Series::from_data(predicate))
+    }
+
+    /// Hashes of the values of the column, `None` for the nulls which match nothing.
+    fn hash_column(column: &ColumnRef) -> Vec<Option<u64>> {
+        let (is_all_null, validity) = column.validity();
+        let values = Series::remove_nullable(column);
+        let mut buffer = Vec::new();
+        (0..column.len())
+            .map(|row| {
+                if is_all_null || validity.map_or(false, |validity| !validity.get_bit(row)) {
+                    return None;
+                }
+
+                buffer.clear();
+                values.serialize(&mut buffer, row);
+                let mut hasher = DefaultHasher::new();
+                hasher.write(&buffer);
+                Some(hasher.finish())
+            })
+            .collect()
+    }
+
+    /// Positions of the bits of a hash, by double hashing. `num_bits` is a power of two.
+    fn bloom_bits(hash: u64, num_bits: usize) -> impl Iterator<Item = usize> {
+        let (h1, h2) = (hash & 0xffffffff, hash >> 32);
+        (0..BLOOM_HASHES)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) as usize) & (num_bits - 1))
+    }
+}
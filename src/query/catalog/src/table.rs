@@ -14,13 +14,16 @@
 
 use std::any::Any;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use chrono::DateTime;
 use chrono::Utc;
 use common_datablocks::DataBlock;
 use common_datavalues::chrono;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_legacy_expression::LegacyExpression;
@@ -112,6 +115,20 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    async fn alter_table_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        action: AlterColumnAction,
+    ) -> Result<()> {
+        let (_, _) = (ctx, action);
+
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support altering columns",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
     // defaults to generate one single part and empty statistics
     async fn read_partitions(
         &self,
@@ -183,12 +200,30 @@ pub trait Table: Sync + Send {
         Ok(())
     }
 
+    /// Collect the statistics of the columns for the optimizer.
+    async fn analyze(&self, ctx: Arc<dyn TableContext>) -> Result<()> {
+        let _ = ctx;
+
+        Ok(())
+    }
+
     async fn statistics(&self, ctx: Arc<dyn TableContext>) -> Result<Option<TableStatistics>> {
         let _ = ctx;
 
         Ok(None)
     }
 
+    /// The statistics of the columns, keyed by the index of the column in the schema. The columns
+    /// without statistics are absent.
+    async fn column_statistics(
+        &self,
+        ctx: Arc<dyn TableContext>,
+    ) -> Result<HashMap<usize, ColumnStatistics>> {
+        let _ = ctx;
+
+        Ok(HashMap::new())
+    }
+
     async fn navigate_to(
         &self,
         ctx: Arc<dyn TableContext>,
@@ -203,6 +238,17 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    /// Restore the table to the historical data at `point`.
+    async fn flashback(&self, ctx: Arc<dyn TableContext>, point: &NavigationPoint) -> Result<()> {
+        let (_, _) = (ctx, point);
+
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support flashback",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
     async fn delete(&self, ctx: Arc<dyn TableContext>, delete_plan: DeletePlan) -> Result<()> {
         let (_, _) = (ctx, delete_plan);
 
@@ -216,9 +262,10 @@ pub trait Table: Sync + Send {
     async fn compact(
         &self,
         ctx: Arc<dyn TableContext>,
+        target: CompactTarget,
         pipeline: &mut Pipeline,
     ) -> Result<Option<Arc<dyn TableMutator>>> {
-        let (_, _) = (ctx, pipeline);
+        let (_, _, _) = (ctx, target, pipeline);
 
         Err(ErrorCode::UnImplement(format!(
             "table {},  of engine type {}, does not support compact",
@@ -243,7 +290,37 @@ pub trait Table: Sync + Send {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CompactTarget {
+    /// Merge the small blocks into larger ones, rewriting the data.
+    Blocks,
+    /// Merge the small segments into larger ones, the blocks are left untouched.
+    Segments,
+}
+
+/// A change of the columns of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlterColumnAction {
+    /// Append a column, `default` is its value in the rows already in the table.
+    Add {
+        field: DataField,
+        default: DataValue,
+        comment: String,
+    },
+    Drop {
+        column: String,
+    },
+    Rename {
+        column: String,
+        new_column: String,
+    },
+    /// Replace the column of the same name by `field`, whose type must be wider.
+    Modify {
+        field: DataField,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NavigationPoint {
     SnapshotID(String),
     TimePoint(DateTime<Utc>),
@@ -256,3 +333,31 @@ pub struct TableStatistics {
     pub data_size_compressed: Option<u64>,
     pub index_size: Option<u64>,
 }
+
+#[derive(Debug, Clone)]
+pub struct ColumnStatistics {
+    pub min: DataValue,
+    pub max: DataValue,
+    pub null_count: u64,
+    /// An estimation of the number of distinct non-null values.
+    pub number_of_distinct_values: u64,
+    /// The histogram of the values, if the table has been analyzed and the column is numeric.
+    pub histogram: Option<Histogram>,
+    /// Whether the min, the max and the null count are exact, rather than bounds or estimations,
+    /// so that the aggregates of the column can be answered with them.
+    pub is_exact: bool,
+}
+
+/// An equi-height histogram, each bucket holds about the same number of values, which are
+/// assumed to be spread uniformly between the bounds of the bucket.
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    pub buckets: Vec<HistogramBucket>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HistogramBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: f64,
+}
@@ -26,13 +26,17 @@ use common_functions::scalars::FunctionContext;
 use common_io::prelude::FormatSettings;
 use common_legacy_planners::Partitions;
 use common_legacy_planners::ReadDataSourcePlan;
+use common_meta_app::schema::TableInfo;
+use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
 use opendal::Operator;
 
 use crate::catalog::Catalog;
 use crate::cluster_info::Cluster;
+use crate::runtime_filter::RuntimeFilter;
 use crate::table::Table;
+use crate::txn::TxnManagerRef;
 
 pub struct ProcessInfo {
     pub id: String,
@@ -46,7 +50,21 @@ pub struct ProcessInfo {
     pub memory_usage: i64,
     pub dal_metrics: Option<DalMetrics>,
     pub scan_progress_value: Option<ProgressValues>,
+    /// The rows and bytes estimated to scan of the running query.
+    pub total_scan_progress_value: Option<ProgressValues>,
     pub mysql_connection_id: Option<u32>,
+    pub query_id: Option<String>,
+    pub query_duration_ms: Option<u64>,
+    /// The stage the running query is in, like `Executing`.
+    pub query_stage: Option<String>,
+}
+
+pub struct QueryQueueInfo {
+    pub query_id: String,
+    pub user: String,
+    pub state: String,
+    pub query: String,
+    pub wait_time_ms: u64,
 }
 
 #[async_trait::async_trait]
@@ -67,10 +85,16 @@ pub trait TableContext: Send + Sync {
     fn try_get_partitions(&self, num: u64) -> Result<Partitions>;
     // Update the context partition pool from the pipeline builder.
     fn try_set_partitions(&self, partitions: Partitions) -> Result<()>;
+    /// Attach a runtime filter of a hash join to the scan reading the partitions.
+    fn set_runtime_filter(&self, filter: Arc<RuntimeFilter>);
+    fn get_runtime_filters(&self) -> Vec<Arc<RuntimeFilter>>;
     fn attach_query_str(&self, kind: String, query: &str);
     fn get_fragment_id(&self) -> usize;
     fn get_catalog(&self, catalog_name: &str) -> Result<Arc<dyn Catalog>>;
     fn get_id(&self) -> String;
+    /// Returns the cause if the query is killed. It's checked by the long running operations
+    /// outside the pipeline executor, e.g. the pruning of the partitions.
+    fn check_aborting(&self) -> Result<()>;
     fn get_current_catalog(&self) -> String;
     fn get_current_database(&self) -> String;
     fn get_config(&self) -> Config;
@@ -98,5 +122,15 @@ pub trait TableContext: Send + Sync {
     fn get_cluster(&self) -> Arc<Cluster>;
     async fn get_table(&self, catalog: &str, database: &str, table: &str)
     -> Result<Arc<dyn Table>>;
+    /// Get the explicit transaction of the session.
+    fn get_txn_manager(&self) -> TxnManagerRef;
+    /// Record a stream read by the query, with the offset it is advanced to if the query
+    /// commits its changes.
+    fn add_consumed_stream(&self, stream: TableInfo);
+    fn get_consumed_streams(&self) -> Vec<TableInfo>;
+    /// Get the roles in effect of the session, with the roles they inherit.
+    async fn get_related_roles(&self) -> Result<Vec<RoleInfo>>;
     fn get_processes_info(&self) -> Vec<ProcessInfo>;
+    /// Get the running and the queued queries of the query queue.
+    fn get_query_queue_info(&self) -> Vec<QueryQueueInfo>;
 }
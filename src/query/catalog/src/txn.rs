@@ -0,0 +1,84 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_meta_types::MetaId;
+use parking_lot::Mutex;
+
+pub type TxnManagerRef = Arc<Mutex<TxnManager>>;
+
+/// The explicit transaction of a session, started by `BEGIN` and ended by `COMMIT` or
+/// `ROLLBACK`.
+///
+/// Inside a transaction, the new table metas made by the statements are staged instead of
+/// being committed to the meta server. The staged tables are read by the following statements
+/// of the transaction, and are committed together in one meta transaction by `COMMIT`.
+#[derive(Default)]
+pub struct TxnManager {
+    active: bool,
+    /// The staged tables by id, `ident.seq` of a staged table is the version of the table meta
+    /// the changes are made on, the commit fails if the table is changed after it.
+    staged: BTreeMap<MetaId, TableInfo>,
+}
+
+impl TxnManager {
+    pub fn init() -> TxnManagerRef {
+        Arc::new(Mutex::new(TxnManager::default()))
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Start a transaction, it is a no-op inside a transaction.
+    pub fn begin(&mut self) {
+        self.active = true;
+    }
+
+    /// End the transaction, and return the staged tables to be committed.
+    pub fn end(&mut self) -> Vec<TableInfo> {
+        self.active = false;
+        std::mem::take(&mut self.staged).into_values().collect()
+    }
+
+    /// Stage the new meta of the table, `table_info` is the table the changes are made on.
+    pub fn stage_table_meta(&mut self, table_info: &TableInfo, new_table_meta: TableMeta) {
+        let staged = TableInfo {
+            meta: new_table_meta,
+            ..table_info.clone()
+        };
+        self.staged.insert(table_info.ident.table_id, staged);
+    }
+
+    pub fn get_staged_table(&self, table_id: MetaId) -> Option<TableInfo> {
+        self.staged.get(&table_id).cloned()
+    }
+
+    /// Check that the operation, which takes effect immediately, is not run in a transaction.
+    pub fn check_autocommit(&self, operation: &str) -> Result<()> {
+        match self.active {
+            true => Err(ErrorCode::UnImplement(format!(
+                "{} is not supported in a transaction",
+                operation
+            ))),
+            false => Ok(()),
+        }
+    }
+}
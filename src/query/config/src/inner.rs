@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
@@ -51,8 +52,14 @@ pub struct Config {
 
     // external catalog config.
     // - Later, catalog information SHOULD be kept in KV Service
-    // - currently only supports HIVE (via hive meta store)
+    // - currently only supports HIVE (via hive meta store) and ICEBERG (via warehouse directory)
+    // - deprecated by `catalogs`, still registers the catalogs `hive` and `iceberg` if they are
+    //   not in `catalogs`
     pub catalog: HiveCatalogConfig,
+
+    /// The external catalogs, keyed by the name they are addressed by in SQL, like
+    /// `<catalog>.<database>.<table>`.
+    pub catalogs: HashMap<String, CatalogConfig>,
 }
 
 impl Config {
@@ -103,8 +110,12 @@ pub struct QueryConfig {
     pub mysql_handler_host: String,
     pub mysql_handler_port: u16,
     pub max_active_sessions: u64,
+    pub clickhouse_handler_host: String,
+    pub clickhouse_handler_port: u16,
     pub clickhouse_http_handler_host: String,
     pub clickhouse_http_handler_port: u16,
+    pub postgres_handler_host: String,
+    pub postgres_handler_port: u16,
     pub http_handler_host: String,
     pub http_handler_port: u16,
     pub http_handler_result_timeout_millis: u64,
@@ -114,6 +125,14 @@ pub struct QueryConfig {
     pub http_handler_tls_server_cert: String,
     pub http_handler_tls_server_key: String,
     pub http_handler_tls_server_root_ca_cert: String,
+    pub postgres_handler_tls_server_cert: String,
+    pub postgres_handler_tls_server_key: String,
+    pub mysql_handler_tls_server_cert: String,
+    pub mysql_handler_tls_server_key: String,
+    pub mysql_handler_tls_server_root_ca_cert: String,
+    pub clickhouse_handler_tls_server_cert: String,
+    pub clickhouse_handler_tls_server_key: String,
+    pub clickhouse_handler_tls_server_root_ca_cert: String,
     pub api_tls_server_cert: String,
     pub api_tls_server_key: String,
     pub api_tls_server_root_ca_cert: String,
@@ -142,12 +161,101 @@ pub struct QueryConfig {
     pub table_disk_cache_root: String,
     /// Table disk cache size (mb)
     pub table_disk_cache_mb_size: u64,
+    /// Size of the in-memory cache of decoded columns of data blocks (mb)
+    pub table_data_block_memory_cache_mb_size: u64,
+    /// Also cache decoded columns of data blocks in the table disk cache
+    pub table_data_block_disk_cache_enabled: bool,
+    /// Size of the disk cache of query results (mb)
+    pub table_query_result_cache_mb_size: u64,
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     pub management_mode: bool,
     pub jwt_key_file: String,
+    /// The additional JWKS endpoints
+    pub jwt_key_files: Vec<String>,
+    /// The claim of the JWT taken as the user name
+    pub jwt_user_claim: String,
     pub async_insert_max_data_size: u64,
     pub async_insert_busy_timeout: u64,
     pub async_insert_stale_timeout: u64,
+    /// The maximum number of the running queries, 0 means unlimited
+    pub max_running_queries: u64,
+    /// The maximum number of the running queries of a user, 0 means unlimited
+    pub max_running_queries_per_user: u64,
+    /// The maximum number of the queries waiting for running, 0 means unlimited
+    pub max_queued_queries: u64,
+    /// The timeout in milliseconds of the queries waiting for running, 0 means no timeout
+    pub query_queue_timeout: u64,
+    /// Whether the functions on external UDF servers are enabled
+    pub enable_udf_server: bool,
+    /// The addresses of the UDF servers allowed, all the addresses are allowed if it is empty
+    pub udf_server_allow_list: Vec<String>,
+    /// Whether the tables are compacted in background after inserts
+    pub enable_auto_compaction: bool,
+    /// The interval in seconds of checking the inserted tables for compaction
+    pub auto_compaction_interval: u64,
+    /// The number of the blocks or segments exceeding the ideal layout to trigger a compaction
+    pub auto_compaction_threshold: u64,
+    /// The UTC hours `<start>-<end>` the compactions are scheduled in, any time if it is empty
+    pub auto_compaction_off_peak_hours: String,
+    /// The interval in seconds of checking the schedules of the tasks, 0 disables the tasks
+    pub task_scheduler_interval: u64,
+    /// Whether the query logs are persisted into `system_history.query_log`
+    pub enable_persistent_query_log: bool,
+    /// The interval in seconds of appending the query logs to the persistent table
+    pub query_log_flush_interval: u64,
+    /// The days the persisted query logs are kept, 0 keeps them forever
+    pub query_log_retention_days: u64,
+    /// The queries slower than the milliseconds are logged as slow queries, 0 disables it
+    pub slow_query_threshold_ms: u64,
+    /// The classes of the audit events logged, separated by commas: `login`, `ddl`, `dml` and
+    /// `denied`, empty disables the audit log
+    pub audit_log_events: String,
+    /// Where the audit events are written, separated by commas: `file`, `webhook` and `table`
+    pub audit_log_sinks: String,
+    /// The URL the audit events are posted to, by the `webhook` sink
+    pub audit_log_webhook_url: String,
+    /// The URL of the LDAP server, like `ldap://host:389` or `ldaps://host:636`, empty disables
+    /// the LDAP authentication
+    pub ldap_url: String,
+    /// Whether the connections to the LDAP server are upgraded to TLS by StartTLS
+    pub ldap_starttls: bool,
+    /// The DN template the users bind as, `{user}` is replaced by the user name, like
+    /// `uid={user},ou=people,dc=example,dc=com`. The DN is searched for if it is empty
+    pub ldap_bind_dn: String,
+    /// The base DN of searching for the DN of the users
+    pub ldap_search_base_dn: String,
+    /// The filter of searching for the DN of the users, `{user}` is replaced by the user name
+    pub ldap_search_filter: String,
+    /// The DN bound as to search for the users, the search is anonymous if it is empty
+    pub ldap_search_bind_dn: String,
+    /// The password of `ldap_search_bind_dn`
+    pub ldap_search_bind_password: String,
+    /// The attribute of the users listing the DNs of their groups
+    pub ldap_group_attribute: String,
+    /// The roles granted to the members of the groups, like `<group_dn>:<role>;...`
+    pub ldap_role_mapping: String,
+    /// The issuer URL of the OpenID Connect provider, like `https://example.okta.com`, empty
+    /// disables the OIDC login of the HTTP API
+    pub oidc_issuer: String,
+    /// The client ID registered on the OIDC provider
+    pub oidc_client_id: String,
+    /// The client secret registered on the OIDC provider
+    pub oidc_client_secret: String,
+    /// The URL of `/v1/oauth/callback` the provider redirects the users back to
+    pub oidc_redirect_url: String,
+    /// The scopes requested by the authorization code flow, separated by spaces
+    pub oidc_scopes: String,
+    /// The audience of the access tokens accepted besides the ID tokens of the client
+    pub oidc_audience: String,
+    /// The claim of the OIDC tokens taken as the user name
+    pub oidc_user_claim: String,
+    /// The claim of the OIDC tokens listing the groups or the roles of the user
+    pub oidc_role_claim: String,
+    /// The roles granted for the values of the role claim, like `<claim_value>:<role>;...`
+    pub oidc_role_mapping: String,
+    /// The seconds the node is kept in the cluster without heartbeats, its heartbeats are sent
+    /// at a third to two thirds of it
+    pub cluster_heartbeat_ttl: u64,
     pub idm: IDMConfig,
 }
 
@@ -160,8 +268,12 @@ impl Default for QueryConfig {
             mysql_handler_host: "127.0.0.1".to_string(),
             mysql_handler_port: 3307,
             max_active_sessions: 256,
+            clickhouse_handler_host: "127.0.0.1".to_string(),
+            clickhouse_handler_port: 9000,
             clickhouse_http_handler_host: "127.0.0.1".to_string(),
             clickhouse_http_handler_port: 8124,
+            postgres_handler_host: "127.0.0.1".to_string(),
+            postgres_handler_port: 5433,
             http_handler_host: "127.0.0.1".to_string(),
             http_handler_port: 8000,
             http_handler_result_timeout_millis: 10000,
@@ -174,6 +286,14 @@ impl Default for QueryConfig {
             http_handler_tls_server_cert: "".to_string(),
             http_handler_tls_server_key: "".to_string(),
             http_handler_tls_server_root_ca_cert: "".to_string(),
+            postgres_handler_tls_server_cert: "".to_string(),
+            postgres_handler_tls_server_key: "".to_string(),
+            mysql_handler_tls_server_cert: "".to_string(),
+            mysql_handler_tls_server_key: "".to_string(),
+            mysql_handler_tls_server_root_ca_cert: "".to_string(),
+            clickhouse_handler_tls_server_cert: "".to_string(),
+            clickhouse_handler_tls_server_key: "".to_string(),
+            clickhouse_handler_tls_server_root_ca_cert: "".to_string(),
             rpc_tls_server_cert: "".to_string(),
             rpc_tls_server_key: "".to_string(),
             rpc_tls_query_server_root_ca_cert: "".to_string(),
@@ -188,11 +308,53 @@ impl Default for QueryConfig {
             table_memory_cache_mb_size: 256,
             table_disk_cache_root: "_cache".to_string(),
             table_disk_cache_mb_size: 1024,
+            table_data_block_memory_cache_mb_size: 256,
+            table_data_block_disk_cache_enabled: false,
+            table_query_result_cache_mb_size: 256,
             management_mode: false,
             jwt_key_file: "".to_string(),
+            jwt_key_files: Vec::new(),
+            jwt_user_claim: "sub".to_string(),
             async_insert_max_data_size: 10000,
             async_insert_busy_timeout: 200,
             async_insert_stale_timeout: 0,
+            max_running_queries: 0,
+            max_running_queries_per_user: 0,
+            max_queued_queries: 0,
+            query_queue_timeout: 0,
+            enable_udf_server: false,
+            udf_server_allow_list: Vec::new(),
+            enable_auto_compaction: false,
+            auto_compaction_interval: 60,
+            auto_compaction_threshold: 100,
+            auto_compaction_off_peak_hours: "".to_string(),
+            task_scheduler_interval: 10,
+            enable_persistent_query_log: false,
+            query_log_flush_interval: 10,
+            query_log_retention_days: 30,
+            slow_query_threshold_ms: 0,
+            audit_log_events: "login".to_string(),
+            audit_log_sinks: "file".to_string(),
+            audit_log_webhook_url: "".to_string(),
+            ldap_url: "".to_string(),
+            ldap_starttls: false,
+            ldap_bind_dn: "".to_string(),
+            ldap_search_base_dn: "".to_string(),
+            ldap_search_filter: "(uid={user})".to_string(),
+            ldap_search_bind_dn: "".to_string(),
+            ldap_search_bind_password: "".to_string(),
+            ldap_group_attribute: "memberOf".to_string(),
+            ldap_role_mapping: "".to_string(),
+            oidc_issuer: "".to_string(),
+            oidc_client_id: "".to_string(),
+            oidc_client_secret: "".to_string(),
+            oidc_redirect_url: "".to_string(),
+            oidc_scopes: "openid email profile".to_string(),
+            oidc_audience: "".to_string(),
+            oidc_user_claim: "email".to_string(),
+            oidc_role_claim: "groups".to_string(),
+            oidc_role_mapping: "".to_string(),
+            cluster_heartbeat_ttl: 60,
             idm: IDMConfig::default(),
         }
     }
@@ -237,6 +399,8 @@ impl Display for ThriftProtocol {
 pub struct HiveCatalogConfig {
     pub meta_store_address: String,
     pub protocol: ThriftProtocol,
+    /// Directory of the iceberg warehouse in the storage, iceberg catalog is disabled if empty
+    pub iceberg_warehouse: String,
 }
 
 impl Default for HiveCatalogConfig {
@@ -244,10 +408,41 @@ impl Default for HiveCatalogConfig {
         Self {
             meta_store_address: "127.0.0.1:9083".to_string(),
             protocol: ThriftProtocol::Binary,
+            iceberg_warehouse: "".to_string(),
+        }
+    }
+}
+
+/// Config of an external catalog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CatalogConfig {
+    Hive(CatalogHiveConfig),
+    Iceberg(CatalogIcebergConfig),
+}
+
+impl CatalogConfig {
+    /// Name of the type of the catalog, the catalogs are created by the creator registered for it.
+    pub fn catalog_type(&self) -> &'static str {
+        match self {
+            CatalogConfig::Hive(_) => "hive",
+            CatalogConfig::Iceberg(_) => "iceberg",
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CatalogHiveConfig {
+    /// Address of the hive meta store service
+    pub address: String,
+    pub protocol: ThriftProtocol,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CatalogIcebergConfig {
+    /// Directory of the iceberg warehouse in the storage
+    pub warehouse: String,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct MetaConfig {
     /// The dir to store persisted meta state for a embedded meta store
@@ -27,7 +27,11 @@ mod inner;
 mod outer_v0;
 mod version;
 
+pub use inner::CatalogConfig;
+pub use inner::CatalogHiveConfig;
+pub use inner::CatalogIcebergConfig;
 pub use inner::Config;
 pub use inner::QueryConfig;
+pub use inner::ThriftProtocol;
 pub use version::DATABEND_COMMIT_VERSION;
 pub use version::QUERY_SEMVER;
@@ -45,6 +45,9 @@ use serfig::collectors::from_file;
 use serfig::collectors::from_self;
 use serfig::parsers::Toml;
 
+use super::inner::CatalogConfig as InnerCatalogConfig;
+use super::inner::CatalogHiveConfig as InnerCatalogHiveConfig;
+use super::inner::CatalogIcebergConfig as InnerCatalogIcebergConfig;
 use super::inner::Config as InnerConfig;
 use super::inner::HiveCatalogConfig as InnerHiveCatalogConfig;
 use super::inner::MetaConfig as InnerMetaConfig;
@@ -90,9 +93,19 @@ pub struct Config {
 
     // external catalog config.
     // - Later, catalog information SHOULD be kept in KV Service
-    // - currently only supports HIVE (via hive meta store)
+    // - currently only supports HIVE (via hive meta store) and ICEBERG (via warehouse directory)
     #[clap(flatten)]
     pub catalog: HiveCatalogConfig,
+
+    /// The external catalogs, keyed by their names, like:
+    ///
+    /// ```toml
+    /// [catalogs.hive_prod]
+    /// type = "hive"
+    /// address = "127.0.0.1:9083"
+    /// ```
+    #[clap(skip)]
+    pub catalogs: HashMap<String, CatalogConfig>,
 }
 
 impl Default for Config {
@@ -145,6 +158,11 @@ impl From<InnerConfig> for Config {
             meta: inner.meta.into(),
             storage: inner.storage.into(),
             catalog: inner.catalog.into(),
+            catalogs: inner
+                .catalogs
+                .into_iter()
+                .map(|(name, catalog)| (name, catalog.into()))
+                .collect(),
         }
     }
 }
@@ -161,6 +179,7 @@ impl TryInto<InnerConfig> for Config {
             meta: self.meta.try_into()?,
             storage: self.storage.try_into()?,
             catalog: self.catalog.try_into()?,
+            catalogs: catalogs_to_inner(self.catalogs)?,
         })
     }
 }
@@ -371,11 +390,25 @@ impl TryInto<InnerStorageGcsConfig> for GcsStorageConfig {
     type Error = ErrorCode;
 
     fn try_into(self) -> Result<InnerStorageGcsConfig, Self::Error> {
+        // Fallback to the service account key file of the application default credentials.
+        let credential = match env::var("GOOGLE_APPLICATION_CREDENTIALS") {
+            Ok(path) if self.credential.is_empty() => {
+                let content = std::fs::read(&path).map_err(|e| {
+                    ErrorCode::InvalidConfig(format!(
+                        "read gcs credential file {} failed: {}",
+                        path, e
+                    ))
+                })?;
+                base64::encode(content)
+            }
+            _ => self.credential,
+        };
+
         Ok(InnerStorageGcsConfig {
             endpoint_url: self.gcs_endpoint_url,
             bucket: self.gcs_bucket,
             root: self.gcs_root,
-            credential: self.credential,
+            credential,
         })
     }
 }
@@ -704,11 +737,9 @@ pub struct QueryConfig {
     #[clap(long, default_value = "256")]
     pub max_active_sessions: u64,
 
-    #[deprecated(note = "clickhouse tcp support is deprecated")]
     #[clap(long, default_value = "127.0.0.1")]
     pub clickhouse_handler_host: String,
 
-    #[deprecated(note = "clickhouse tcp support is deprecated")]
     #[clap(long, default_value = "9000")]
     pub clickhouse_handler_port: u16,
 
@@ -718,6 +749,12 @@ pub struct QueryConfig {
     #[clap(long, default_value = "8124")]
     pub clickhouse_http_handler_port: u16,
 
+    #[clap(long, default_value = "127.0.0.1")]
+    pub postgres_handler_host: String,
+
+    #[clap(long, default_value = "5433")]
+    pub postgres_handler_port: u16,
+
     #[clap(long, default_value = "127.0.0.1")]
     pub http_handler_host: String,
 
@@ -745,6 +782,30 @@ pub struct QueryConfig {
     #[clap(long, default_value_t)]
     pub http_handler_tls_server_root_ca_cert: String,
 
+    #[clap(long, default_value_t)]
+    pub postgres_handler_tls_server_cert: String,
+
+    #[clap(long, default_value_t)]
+    pub postgres_handler_tls_server_key: String,
+
+    #[clap(long, default_value_t)]
+    pub mysql_handler_tls_server_cert: String,
+
+    #[clap(long, default_value_t)]
+    pub mysql_handler_tls_server_key: String,
+
+    #[clap(long, default_value_t)]
+    pub mysql_handler_tls_server_root_ca_cert: String,
+
+    #[clap(long, default_value_t)]
+    pub clickhouse_handler_tls_server_cert: String,
+
+    #[clap(long, default_value_t)]
+    pub clickhouse_handler_tls_server_key: String,
+
+    #[clap(long, default_value_t)]
+    pub clickhouse_handler_tls_server_root_ca_cert: String,
+
     #[clap(long, default_value_t)]
     pub api_tls_server_cert: String,
 
@@ -811,6 +872,18 @@ pub struct QueryConfig {
     #[clap(long, default_value = "1024")]
     pub table_disk_cache_mb_size: u64,
 
+    /// Size of the in-memory cache of decoded columns of data blocks (mb)
+    #[clap(long, default_value = "256")]
+    pub table_data_block_memory_cache_mb_size: u64,
+
+    /// Also cache decoded columns of data blocks in the table disk cache
+    #[clap(long)]
+    pub table_data_block_disk_cache_enabled: bool,
+
+    /// Size of the disk cache of query results (mb)
+    #[clap(long, default_value = "256")]
+    pub table_query_result_cache_mb_size: u64,
+
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     #[clap(long)]
     pub management_mode: bool,
@@ -818,6 +891,14 @@ pub struct QueryConfig {
     #[clap(long, default_value_t)]
     pub jwt_key_file: String,
 
+    /// The additional JWKS endpoints, a token is verified by `jwt_key_file` and these in order.
+    #[clap(long)]
+    pub jwt_key_files: Vec<String>,
+
+    /// The claim of the JWT taken as the user name.
+    #[clap(long, default_value = "sub")]
+    pub jwt_user_claim: String,
+
     /// The maximum memory size of the buffered data collected per insert before being inserted.
     #[clap(long, default_value = "10000")]
     pub async_insert_max_data_size: u64,
@@ -830,6 +911,161 @@ pub struct QueryConfig {
     #[clap(long, default_value = "0")]
     pub async_insert_stale_timeout: u64,
 
+    /// The maximum number of the running queries, the others wait in the queue. 0 means unlimited.
+    #[clap(long, default_value = "0")]
+    pub max_running_queries: u64,
+
+    /// The maximum number of the running queries of a user. 0 means unlimited.
+    #[clap(long, default_value = "0")]
+    pub max_running_queries_per_user: u64,
+
+    /// The maximum number of the queries waiting in the queue, the others are rejected.
+    /// 0 means unlimited.
+    #[clap(long, default_value = "0")]
+    pub max_queued_queries: u64,
+
+    /// The timeout in milliseconds of the queries waiting in the queue. 0 means no timeout.
+    #[clap(long, default_value = "0")]
+    pub query_queue_timeout: u64,
+
+    /// Whether the functions on external UDF servers can be created and called.
+    #[clap(long)]
+    pub enable_udf_server: bool,
+
+    /// The addresses of the UDF servers allowed to connect to, all the addresses are allowed
+    /// if it is empty.
+    #[clap(long)]
+    pub udf_server_allow_list: Vec<String>,
+
+    /// Whether the tables are compacted in background after inserts.
+    #[clap(long)]
+    pub enable_auto_compaction: bool,
+
+    /// The interval in seconds of checking the inserted tables for compaction.
+    #[clap(long, default_value = "60")]
+    pub auto_compaction_interval: u64,
+
+    /// The number of the blocks or segments exceeding the ideal layout to trigger a compaction.
+    #[clap(long, default_value = "100")]
+    pub auto_compaction_threshold: u64,
+
+    /// The UTC hours `<start>-<end>` the compactions are scheduled in, any time if it is empty.
+    #[clap(long, default_value_t)]
+    pub auto_compaction_off_peak_hours: String,
+
+    /// The interval in seconds of checking the schedules of the tasks, 0 disables the tasks.
+    #[clap(long, default_value = "10")]
+    pub task_scheduler_interval: u64,
+
+    /// Whether the query logs are persisted into `system_history.query_log`.
+    #[clap(long)]
+    pub enable_persistent_query_log: bool,
+
+    /// The interval in seconds of appending the query logs to the persistent table.
+    #[clap(long, default_value = "10")]
+    pub query_log_flush_interval: u64,
+
+    /// The days the persisted query logs are kept, 0 keeps them forever.
+    #[clap(long, default_value = "30")]
+    pub query_log_retention_days: u64,
+
+    /// The queries slower than the milliseconds are logged as slow queries, 0 disables it.
+    #[clap(long, default_value = "0")]
+    pub slow_query_threshold_ms: u64,
+
+    /// The classes of the audit events logged, separated by commas: `login`, `ddl`, `dml` and
+    /// `denied`, empty disables the audit log.
+    #[clap(long, default_value = "login")]
+    pub audit_log_events: String,
+
+    /// Where the audit events are written, separated by commas: `file`, `webhook` and `table`.
+    #[clap(long, default_value = "file")]
+    pub audit_log_sinks: String,
+
+    /// The URL the audit events are posted to, by the `webhook` sink.
+    #[clap(long, default_value = "")]
+    pub audit_log_webhook_url: String,
+
+    /// The URL of the LDAP server, like `ldap://host:389` or `ldaps://host:636`, empty disables
+    /// the LDAP authentication.
+    #[clap(long, default_value = "")]
+    pub ldap_url: String,
+
+    /// Whether the connections to the LDAP server are upgraded to TLS by StartTLS.
+    #[clap(long)]
+    pub ldap_starttls: bool,
+
+    /// The DN template the users bind as, `{user}` is replaced by the user name, like
+    /// `uid={user},ou=people,dc=example,dc=com`. The DN is searched for if it is empty.
+    #[clap(long, default_value = "")]
+    pub ldap_bind_dn: String,
+
+    /// The base DN of searching for the DN of the users.
+    #[clap(long, default_value = "")]
+    pub ldap_search_base_dn: String,
+
+    /// The filter of searching for the DN of the users, `{user}` is replaced by the user name.
+    #[clap(long, default_value = "(uid={user})")]
+    pub ldap_search_filter: String,
+
+    /// The DN bound as to search for the users, the search is anonymous if it is empty.
+    #[clap(long, default_value = "")]
+    pub ldap_search_bind_dn: String,
+
+    /// The password of `ldap_search_bind_dn`.
+    #[clap(long, default_value = "")]
+    pub ldap_search_bind_password: String,
+
+    /// The attribute of the users listing the DNs of their groups.
+    #[clap(long, default_value = "memberOf")]
+    pub ldap_group_attribute: String,
+
+    /// The roles granted to the members of the groups, like `<group_dn>:<role>;...`.
+    #[clap(long, default_value = "")]
+    pub ldap_role_mapping: String,
+
+    /// The issuer URL of the OpenID Connect provider, like `https://example.okta.com`, empty
+    /// disables the OIDC login of the HTTP API.
+    #[clap(long, default_value = "")]
+    pub oidc_issuer: String,
+
+    /// The client ID registered on the OIDC provider.
+    #[clap(long, default_value = "")]
+    pub oidc_client_id: String,
+
+    /// The client secret registered on the OIDC provider.
+    #[clap(long, default_value = "")]
+    pub oidc_client_secret: String,
+
+    /// The URL of `/v1/oauth/callback` the provider redirects the users back to.
+    #[clap(long, default_value = "")]
+    pub oidc_redirect_url: String,
+
+    /// The scopes requested by the authorization code flow, separated by spaces.
+    #[clap(long, default_value = "openid email profile")]
+    pub oidc_scopes: String,
+
+    /// The audience of the access tokens accepted besides the ID tokens of the client.
+    #[clap(long, default_value = "")]
+    pub oidc_audience: String,
+
+    /// The claim of the OIDC tokens taken as the user name.
+    #[clap(long, default_value = "email")]
+    pub oidc_user_claim: String,
+
+    /// The claim of the OIDC tokens listing the groups or the roles of the user.
+    #[clap(long, default_value = "groups")]
+    pub oidc_role_claim: String,
+
+    /// The roles granted for the values of the role claim, like `<claim_value>:<role>;...`.
+    #[clap(long, default_value = "")]
+    pub oidc_role_mapping: String,
+
+    /// The seconds the node is kept in the cluster without heartbeats, the nodes down are left
+    /// out of the cluster after it.
+    #[clap(long, default_value = "60")]
+    pub cluster_heartbeat_ttl: u64,
+
     #[clap(skip)]
     users: Vec<UserConfig>,
 }
@@ -851,8 +1087,12 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             mysql_handler_host: self.mysql_handler_host,
             mysql_handler_port: self.mysql_handler_port,
             max_active_sessions: self.max_active_sessions,
+            clickhouse_handler_host: self.clickhouse_handler_host,
+            clickhouse_handler_port: self.clickhouse_handler_port,
             clickhouse_http_handler_host: self.clickhouse_http_handler_host,
             clickhouse_http_handler_port: self.clickhouse_http_handler_port,
+            postgres_handler_host: self.postgres_handler_host,
+            postgres_handler_port: self.postgres_handler_port,
             http_handler_host: self.http_handler_host,
             http_handler_port: self.http_handler_port,
             http_handler_result_timeout_millis: self.http_handler_result_timeout_millis,
@@ -862,6 +1102,15 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             http_handler_tls_server_cert: self.http_handler_tls_server_cert,
             http_handler_tls_server_key: self.http_handler_tls_server_key,
             http_handler_tls_server_root_ca_cert: self.http_handler_tls_server_root_ca_cert,
+            postgres_handler_tls_server_cert: self.postgres_handler_tls_server_cert,
+            postgres_handler_tls_server_key: self.postgres_handler_tls_server_key,
+            mysql_handler_tls_server_cert: self.mysql_handler_tls_server_cert,
+            mysql_handler_tls_server_key: self.mysql_handler_tls_server_key,
+            mysql_handler_tls_server_root_ca_cert: self.mysql_handler_tls_server_root_ca_cert,
+            clickhouse_handler_tls_server_cert: self.clickhouse_handler_tls_server_cert,
+            clickhouse_handler_tls_server_key: self.clickhouse_handler_tls_server_key,
+            clickhouse_handler_tls_server_root_ca_cert: self
+                .clickhouse_handler_tls_server_root_ca_cert,
             api_tls_server_cert: self.api_tls_server_cert,
             api_tls_server_key: self.api_tls_server_key,
             api_tls_server_root_ca_cert: self.api_tls_server_root_ca_cert,
@@ -879,11 +1128,53 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             table_memory_cache_mb_size: self.table_memory_cache_mb_size,
             table_disk_cache_root: self.table_disk_cache_root,
             table_disk_cache_mb_size: self.table_disk_cache_mb_size,
+            table_data_block_memory_cache_mb_size: self.table_data_block_memory_cache_mb_size,
+            table_data_block_disk_cache_enabled: self.table_data_block_disk_cache_enabled,
+            table_query_result_cache_mb_size: self.table_query_result_cache_mb_size,
             management_mode: self.management_mode,
             jwt_key_file: self.jwt_key_file,
+            jwt_key_files: self.jwt_key_files,
+            jwt_user_claim: self.jwt_user_claim,
             async_insert_max_data_size: self.async_insert_max_data_size,
             async_insert_busy_timeout: self.async_insert_busy_timeout,
             async_insert_stale_timeout: self.async_insert_stale_timeout,
+            max_running_queries: self.max_running_queries,
+            max_running_queries_per_user: self.max_running_queries_per_user,
+            max_queued_queries: self.max_queued_queries,
+            query_queue_timeout: self.query_queue_timeout,
+            enable_udf_server: self.enable_udf_server,
+            udf_server_allow_list: self.udf_server_allow_list,
+            enable_auto_compaction: self.enable_auto_compaction,
+            auto_compaction_interval: self.auto_compaction_interval,
+            auto_compaction_threshold: self.auto_compaction_threshold,
+            auto_compaction_off_peak_hours: self.auto_compaction_off_peak_hours,
+            task_scheduler_interval: self.task_scheduler_interval,
+            enable_persistent_query_log: self.enable_persistent_query_log,
+            query_log_flush_interval: self.query_log_flush_interval,
+            query_log_retention_days: self.query_log_retention_days,
+            slow_query_threshold_ms: self.slow_query_threshold_ms,
+            audit_log_events: self.audit_log_events,
+            audit_log_sinks: self.audit_log_sinks,
+            audit_log_webhook_url: self.audit_log_webhook_url,
+            ldap_url: self.ldap_url,
+            ldap_starttls: self.ldap_starttls,
+            ldap_bind_dn: self.ldap_bind_dn,
+            ldap_search_base_dn: self.ldap_search_base_dn,
+            ldap_search_filter: self.ldap_search_filter,
+            ldap_search_bind_dn: self.ldap_search_bind_dn,
+            ldap_search_bind_password: self.ldap_search_bind_password,
+            ldap_group_attribute: self.ldap_group_attribute,
+            ldap_role_mapping: self.ldap_role_mapping,
+            oidc_issuer: self.oidc_issuer,
+            oidc_client_id: self.oidc_client_id,
+            oidc_client_secret: self.oidc_client_secret,
+            oidc_redirect_url: self.oidc_redirect_url,
+            oidc_scopes: self.oidc_scopes,
+            oidc_audience: self.oidc_audience,
+            oidc_user_claim: self.oidc_user_claim,
+            oidc_role_claim: self.oidc_role_claim,
+            oidc_role_mapping: self.oidc_role_mapping,
+            cluster_heartbeat_ttl: self.cluster_heartbeat_ttl,
             idm: InnerIDMConfig {
                 users: users_to_inner(self.users)?,
             },
@@ -891,7 +1182,6 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
     }
 }
 
-#[allow(deprecated)]
 impl From<InnerQueryConfig> for QueryConfig {
     fn from(inner: InnerQueryConfig) -> Self {
         Self {
@@ -901,13 +1191,12 @@ impl From<InnerQueryConfig> for QueryConfig {
             mysql_handler_host: inner.mysql_handler_host,
             mysql_handler_port: inner.mysql_handler_port,
             max_active_sessions: inner.max_active_sessions,
-
-            // clickhouse tcp is deprecated
-            clickhouse_handler_host: "127.0.0.1".to_string(),
-            clickhouse_handler_port: 9000,
-
+            clickhouse_handler_host: inner.clickhouse_handler_host,
+            clickhouse_handler_port: inner.clickhouse_handler_port,
             clickhouse_http_handler_host: inner.clickhouse_http_handler_host,
             clickhouse_http_handler_port: inner.clickhouse_http_handler_port,
+            postgres_handler_host: inner.postgres_handler_host,
+            postgres_handler_port: inner.postgres_handler_port,
             http_handler_host: inner.http_handler_host,
             http_handler_port: inner.http_handler_port,
             http_handler_result_timeout_millis: inner.http_handler_result_timeout_millis,
@@ -917,6 +1206,15 @@ impl From<InnerQueryConfig> for QueryConfig {
             http_handler_tls_server_cert: inner.http_handler_tls_server_cert,
             http_handler_tls_server_key: inner.http_handler_tls_server_key,
             http_handler_tls_server_root_ca_cert: inner.http_handler_tls_server_root_ca_cert,
+            postgres_handler_tls_server_cert: inner.postgres_handler_tls_server_cert,
+            postgres_handler_tls_server_key: inner.postgres_handler_tls_server_key,
+            mysql_handler_tls_server_cert: inner.mysql_handler_tls_server_cert,
+            mysql_handler_tls_server_key: inner.mysql_handler_tls_server_key,
+            mysql_handler_tls_server_root_ca_cert: inner.mysql_handler_tls_server_root_ca_cert,
+            clickhouse_handler_tls_server_cert: inner.clickhouse_handler_tls_server_cert,
+            clickhouse_handler_tls_server_key: inner.clickhouse_handler_tls_server_key,
+            clickhouse_handler_tls_server_root_ca_cert: inner
+                .clickhouse_handler_tls_server_root_ca_cert,
             api_tls_server_cert: inner.api_tls_server_cert,
             api_tls_server_key: inner.api_tls_server_key,
             api_tls_server_root_ca_cert: inner.api_tls_server_root_ca_cert,
@@ -935,11 +1233,53 @@ impl From<InnerQueryConfig> for QueryConfig {
             table_memory_cache_mb_size: inner.table_memory_cache_mb_size,
             table_disk_cache_root: inner.table_disk_cache_root,
             table_disk_cache_mb_size: inner.table_disk_cache_mb_size,
+            table_data_block_memory_cache_mb_size: inner.table_data_block_memory_cache_mb_size,
+            table_data_block_disk_cache_enabled: inner.table_data_block_disk_cache_enabled,
+            table_query_result_cache_mb_size: inner.table_query_result_cache_mb_size,
             management_mode: inner.management_mode,
             jwt_key_file: inner.jwt_key_file,
+            jwt_key_files: inner.jwt_key_files,
+            jwt_user_claim: inner.jwt_user_claim,
             async_insert_max_data_size: inner.async_insert_max_data_size,
             async_insert_busy_timeout: inner.async_insert_busy_timeout,
             async_insert_stale_timeout: inner.async_insert_stale_timeout,
+            max_running_queries: inner.max_running_queries,
+            max_running_queries_per_user: inner.max_running_queries_per_user,
+            max_queued_queries: inner.max_queued_queries,
+            query_queue_timeout: inner.query_queue_timeout,
+            enable_udf_server: inner.enable_udf_server,
+            udf_server_allow_list: inner.udf_server_allow_list,
+            enable_auto_compaction: inner.enable_auto_compaction,
+            auto_compaction_interval: inner.auto_compaction_interval,
+            auto_compaction_threshold: inner.auto_compaction_threshold,
+            auto_compaction_off_peak_hours: inner.auto_compaction_off_peak_hours,
+            task_scheduler_interval: inner.task_scheduler_interval,
+            enable_persistent_query_log: inner.enable_persistent_query_log,
+            query_log_flush_interval: inner.query_log_flush_interval,
+            query_log_retention_days: inner.query_log_retention_days,
+            slow_query_threshold_ms: inner.slow_query_threshold_ms,
+            audit_log_events: inner.audit_log_events,
+            audit_log_sinks: inner.audit_log_sinks,
+            audit_log_webhook_url: inner.audit_log_webhook_url,
+            ldap_url: inner.ldap_url,
+            ldap_starttls: inner.ldap_starttls,
+            ldap_bind_dn: inner.ldap_bind_dn,
+            ldap_search_base_dn: inner.ldap_search_base_dn,
+            ldap_search_filter: inner.ldap_search_filter,
+            ldap_search_bind_dn: inner.ldap_search_bind_dn,
+            ldap_search_bind_password: inner.ldap_search_bind_password,
+            ldap_group_attribute: inner.ldap_group_attribute,
+            ldap_role_mapping: inner.ldap_role_mapping,
+            oidc_issuer: inner.oidc_issuer,
+            oidc_client_id: inner.oidc_client_id,
+            oidc_client_secret: inner.oidc_client_secret,
+            oidc_redirect_url: inner.oidc_redirect_url,
+            oidc_scopes: inner.oidc_scopes,
+            oidc_audience: inner.oidc_audience,
+            oidc_user_claim: inner.oidc_user_claim,
+            oidc_role_claim: inner.oidc_role_claim,
+            oidc_role_mapping: inner.oidc_role_mapping,
+            cluster_heartbeat_ttl: inner.cluster_heartbeat_ttl,
             users: users_from_inner(inner.idm.users),
         }
     }
@@ -1099,6 +1439,8 @@ pub struct HiveCatalogConfig {
     pub meta_store_address: String,
     #[clap(long = "hive-thrift-protocol", default_value = "binary")]
     pub protocol: String,
+    #[clap(long = "iceberg-warehouse", default_value = "")]
+    pub iceberg_warehouse: String,
 }
 
 impl Default for HiveCatalogConfig {
@@ -1114,6 +1456,7 @@ impl TryInto<InnerHiveCatalogConfig> for HiveCatalogConfig {
         Ok(InnerHiveCatalogConfig {
             meta_store_address: self.meta_store_address,
             protocol: self.protocol.parse()?,
+            iceberg_warehouse: self.iceberg_warehouse,
         })
     }
 }
@@ -1123,10 +1466,112 @@ impl From<InnerHiveCatalogConfig> for HiveCatalogConfig {
         Self {
             meta_store_address: inner.meta_store_address,
             protocol: inner.protocol.to_string(),
+            iceberg_warehouse: inner.iceberg_warehouse,
+        }
+    }
+}
+
+/// Config of an external catalog, the type of the catalog is given by the `type` key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CatalogConfig {
+    Hive(CatalogHiveConfig),
+    Iceberg(CatalogIcebergConfig),
+}
+
+impl TryInto<InnerCatalogConfig> for CatalogConfig {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<InnerCatalogConfig> {
+        match self {
+            CatalogConfig::Hive(v) => Ok(InnerCatalogConfig::Hive(v.try_into()?)),
+            CatalogConfig::Iceberg(v) => Ok(InnerCatalogConfig::Iceberg(v.into())),
         }
     }
 }
 
+impl From<InnerCatalogConfig> for CatalogConfig {
+    fn from(inner: InnerCatalogConfig) -> Self {
+        match inner {
+            InnerCatalogConfig::Hive(v) => CatalogConfig::Hive(v.into()),
+            InnerCatalogConfig::Iceberg(v) => CatalogConfig::Iceberg(v.into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatalogHiveConfig {
+    pub address: String,
+    pub protocol: String,
+}
+
+impl Default for CatalogHiveConfig {
+    fn default() -> Self {
+        Self {
+            address: "".to_string(),
+            protocol: "binary".to_string(),
+        }
+    }
+}
+
+impl TryInto<InnerCatalogHiveConfig> for CatalogHiveConfig {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<InnerCatalogHiveConfig> {
+        if self.address.is_empty() {
+            return Err(ErrorCode::InvalidConfig(
+                "address of the hive meta store is required by hive catalogs",
+            ));
+        }
+        Ok(InnerCatalogHiveConfig {
+            address: self.address,
+            protocol: self.protocol.parse()?,
+        })
+    }
+}
+
+impl From<InnerCatalogHiveConfig> for CatalogHiveConfig {
+    fn from(inner: InnerCatalogHiveConfig) -> Self {
+        Self {
+            address: inner.address,
+            protocol: inner.protocol.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatalogIcebergConfig {
+    pub warehouse: String,
+}
+
+impl From<CatalogIcebergConfig> for InnerCatalogIcebergConfig {
+    fn from(outer: CatalogIcebergConfig) -> Self {
+        Self {
+            warehouse: outer.warehouse,
+        }
+    }
+}
+
+impl From<InnerCatalogIcebergConfig> for CatalogIcebergConfig {
+    fn from(inner: InnerCatalogIcebergConfig) -> Self {
+        Self {
+            warehouse: inner.warehouse,
+        }
+    }
+}
+
+fn catalogs_to_inner(
+    outer: HashMap<String, CatalogConfig>,
+) -> Result<HashMap<String, InnerCatalogConfig>> {
+    let mut inner = HashMap::new();
+    for (name, catalog) in outer.into_iter() {
+        inner.insert(name, catalog.try_into()?);
+    }
+    Ok(inner)
+}
+
 /// Meta config group.
 /// TODO(xuanwo): All meta_xxx should be rename to xxx.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Args)]
@@ -1287,6 +1732,7 @@ impl TryInto<AuthInfo> for UserAuthConfig {
         match auth_type {
             AuthType::NoPassword => check_no_auth_string(self.auth_string, AuthInfo::None),
             AuthType::JWT => check_no_auth_string(self.auth_string, AuthInfo::JWT),
+            AuthType::LDAP => check_no_auth_string(self.auth_string, AuthInfo::LDAP),
             AuthType::Sha256Password | AuthType::DoubleSha1Password => {
                 let password_type = auth_type.get_password_type().expect("must success");
                 match self.auth_string {
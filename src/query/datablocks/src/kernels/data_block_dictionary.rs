@@ -0,0 +1,55 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::DataBlock;
+
+impl DataBlock {
+    /// Encode the low-cardinality string columns as dictionaries. A column is encoded if its
+    /// distinct values are no more than `max_ratio` of its rows.
+    pub fn encode_dictionaries(block: DataBlock, max_ratio: f64) -> Result<DataBlock> {
+        let max_values = (block.num_rows() as f64 * max_ratio) as usize;
+        let columns = block
+            .columns()
+            .iter()
+            .map(|column| Self::encode_dictionary(column, max_values))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(DataBlock::create(block.schema().clone(), columns))
+    }
+
+    fn encode_dictionary(column: &ColumnRef, max_values: usize) -> Result<ColumnRef> {
+        if column.is_const() || column.is_dictionary() {
+            return Ok(column.clone());
+        }
+
+        if column.is_nullable() {
+            let column: &NullableColumn = Series::check_get(column)?;
+            let inner = Self::encode_dictionary(column.inner(), max_values)?;
+            let validity = column.ensure_validity().clone();
+            return Ok(NullableColumn::wrap_inner(inner, Some(validity)));
+        }
+
+        if column.data_type_id() != TypeID::String {
+            return Ok(column.clone());
+        }
+
+        let strings: &StringColumn = Series::check_get(column)?;
+        Ok(match DictionaryColumn::try_encode(strings, max_values) {
+            Some(dictionary) => dictionary.arc(),
+            None => column.clone(),
+        })
+    }
+}
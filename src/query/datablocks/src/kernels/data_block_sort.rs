@@ -182,6 +182,15 @@ fn compare_variant(left: &dyn Array, right: &dyn Array) -> ArrowResult<DynCompar
     }))
 }
 
+fn compare_geometry(left: &dyn Array, right: &dyn Array) -> ArrowResult<DynComparator> {
+    let left = StringColumn::from_arrow_array(left);
+    let right = StringColumn::from_arrow_array(right);
+
+    Ok(Box::new(move |i, j| {
+        left.get_data(i).cmp(right.get_data(j))
+    }))
+}
+
 fn compare_array(left: &dyn Array, right: &dyn Array) -> ArrowResult<DynComparator> {
     let left = ArrayColumn::from_arrow_array(left);
     let right = ArrayColumn::from_arrow_array(right);
@@ -197,6 +206,8 @@ fn build_compare(left: &dyn Array, right: &dyn Array) -> ArrowResult<DynComparat
         ArrowType::Extension(name, _, _) => {
             if name == "Variant" || name == "VariantArray" || name == "VariantObject" {
                 compare_variant(left, right)
+            } else if name == "Geometry" || name == "Geography" {
+                compare_geometry(left, right)
             } else {
                 Err(ArrowError::NotYetImplemented(format!(
                     "Sort not supported for data type {:?}",
@@ -22,6 +22,7 @@ use common_arrow::parquet::encoding::Encoding;
 use common_arrow::parquet::metadata::ThriftFileMetaData;
 use common_arrow::parquet::write::Version;
 use common_arrow::write_parquet_file;
+use common_arrow::ArrayRef;
 use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -33,6 +34,20 @@ pub fn serialize_data_blocks_with_compression(
     schema: impl AsRef<DataSchema>,
     buf: &mut Vec<u8>,
     compression: CompressionOptions,
+) -> Result<(u64, ThriftFileMetaData)> {
+    let batches = blocks
+        .into_iter()
+        .map(Chunk::try_from)
+        .collect::<Result<Vec<_>>>()?;
+    serialize_chunks_with_compression(batches, schema, buf, compression)
+}
+
+/// Serialize the arrow chunks of the blocks, for the callers converting the blocks by themselves.
+pub fn serialize_chunks_with_compression(
+    batches: Vec<Chunk<ArrayRef>>,
+    schema: impl AsRef<DataSchema>,
+    buf: &mut Vec<u8>,
+    compression: CompressionOptions,
 ) -> Result<(u64, ThriftFileMetaData)> {
     let arrow_schema = schema.as_ref().to_arrow();
 
@@ -41,10 +56,6 @@ pub fn serialize_data_blocks_with_compression(
         compression,
         version: Version::V2,
     };
-    let batches = blocks
-        .into_iter()
-        .map(Chunk::try_from)
-        .collect::<Result<Vec<_>>>()?;
 
     let encoding_map = |data_type: &ArrowDataType| match data_type {
         ArrowDataType::Dictionary(..) => Encoding::RleDictionary,
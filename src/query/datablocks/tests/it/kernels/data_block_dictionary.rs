@@ -0,0 +1,63 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::*;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+#[test]
+fn test_data_block_encode_dictionaries() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i64::to_data_type()),
+        DataField::new("b", Vu8::to_data_type()),
+        DataField::new("c", Vu8::to_data_type()),
+        DataField::new("d", NullableType::new_impl(Vu8::to_data_type())),
+    ]);
+
+    let raw = DataBlock::create(schema, vec![
+        Series::from_data(vec![1i64, 2, 3, 4]),
+        Series::from_data(vec!["x", "y", "x", "x"]),
+        Series::from_data(vec!["p", "q", "r", "s"]),
+        Series::from_data(vec![Some("m"), None, Some("m"), Some("m")]),
+    ]);
+
+    let encoded = DataBlock::encode_dictionaries(raw, 0.5)?;
+    assert!(!encoded.column(0).is_dictionary());
+    assert!(encoded.column(1).is_dictionary());
+    // four distinct values in four rows
+    assert!(!encoded.column(2).is_dictionary());
+    let d: &NullableColumn = Series::check_get(encoded.column(3))?;
+    assert!(d.inner().is_dictionary());
+
+    let filter = BooleanColumn::from_slice(&[true, false, true, true]);
+    let filtered = DataBlock::filter_block_with_bool_column(encoded, &filter)?;
+    assert!(filtered.column(1).is_dictionary());
+
+    let expected = vec![
+        "+---+---+---+---+",
+        "| a | b | c | d |",
+        "+---+---+---+---+",
+        "| 1 | x | p | m |",
+        "| 3 | x | r | m |",
+        "| 4 | x | s | m |",
+        "+---+---+---+---+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, &[filtered.clone()]);
+
+    let taken = DataBlock::block_take_by_indices(&filtered, &[2, 0])?;
+    assert!(taken.column(1).is_dictionary());
+    let strings: &StringColumn = Series::check_get(taken.column(1))?;
+    assert_eq!(strings.get_data(0), b"x");
+    Ok(())
+}
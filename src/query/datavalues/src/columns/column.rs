@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use common_arrow::arrow::array::Array;
 use common_arrow::arrow::bitmap::Bitmap;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
 use common_arrow::ArrayRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -57,6 +58,10 @@ pub trait Column: Send + Sync {
         false
     }
 
+    fn is_dictionary(&self) -> bool {
+        false
+    }
+
     fn len(&self) -> usize;
     /// whether the array is empty
     fn is_empty(&self) -> bool {
@@ -181,6 +186,9 @@ where A: AsRef<dyn Array>
 {
     fn into_column(self) -> ColumnRef {
         use TypeID::*;
+        if let ArrowDataType::Dictionary(..) = self.as_ref().data_type() {
+            return Arc::new(DictionaryColumn::from_arrow_array(self.as_ref()));
+        }
         let data_type: DataTypeImpl = from_arrow_type(self.as_ref().data_type());
         match data_type.data_type_id() {
             // arrow type has no nullable type
@@ -199,7 +207,9 @@ where A: AsRef<dyn Array>
             Float64 => Arc::new(Float64Column::from_arrow_array(self.as_ref())),
             Array => Arc::new(ArrayColumn::from_arrow_array(self.as_ref())),
             Struct => Arc::new(StructColumn::from_arrow_array(self.as_ref())),
-            String => Arc::new(StringColumn::from_arrow_array(self.as_ref())),
+            String | Geometry | Geography => {
+                Arc::new(StringColumn::from_arrow_array(self.as_ref()))
+            }
             Variant => Arc::new(VariantColumn::from_arrow_array(self.as_ref())),
             VariantArray => Arc::new(VariantColumn::from_arrow_array(self.as_ref())),
             VariantObject => Arc::new(VariantColumn::from_arrow_array(self.as_ref())),
@@ -0,0 +1,219 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::DictionaryArray;
+use common_arrow::arrow::array::PrimitiveArray;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::IntegerType;
+use common_arrow::arrow::types::Index;
+use common_arrow::ArrayRef;
+use common_exception::Result;
+use once_cell::sync::OnceCell;
+
+use crate::prelude::*;
+
+/// A column stored as the distinct values and, for each row, the index of its value.
+///
+/// The rows are moved around by their keys only, e.g. by filter, take and scatter, the values
+/// are shared. The column is materialized, once, when it is downcast to the column of its data
+/// type, so it can be passed to any code expecting a plain column.
+///
+/// The values are neither nullable nor const, nullable(dictionary) is used for nullable rows.
+#[derive(Clone)]
+pub struct DictionaryColumn {
+    keys: UInt32Column,
+    values: ColumnRef,
+    full: OnceCell<ColumnRef>,
+}
+
+impl DictionaryColumn {
+    pub fn new(keys: UInt32Column, values: ColumnRef) -> Self {
+        debug_assert!(!values.is_nullable() && !values.is_const() && !values.is_dictionary());
+        Self {
+            keys,
+            values,
+            full: OnceCell::new(),
+        }
+    }
+
+    /// Encode the string column, if it has no more than `max_values` distinct values.
+    pub fn try_encode(column: &StringColumn, max_values: usize) -> Option<Self> {
+        let mut positions: HashMap<&[u8], u32> = HashMap::new();
+        let mut values: Vec<&[u8]> = Vec::new();
+        let mut keys = Vec::with_capacity(column.len());
+
+        for value in column.scalar_iter() {
+            let key = match positions.get(value) {
+                Some(key) => *key,
+                None => {
+                    if values.len() >= max_values {
+                        return None;
+                    }
+                    let key = values.len() as u32;
+                    positions.insert(value, key);
+                    values.push(value);
+                    key
+                }
+            };
+            keys.push(key);
+        }
+
+        Some(Self::new(
+            UInt32Column::new_from_vec(keys),
+            StringColumn::from_slice(&values).arc(),
+        ))
+    }
+
+    pub fn from_arrow_array(array: &dyn Array) -> Self {
+        let array = array
+            .as_any()
+            .downcast_ref::<DictionaryArray<u32>>()
+            .expect("dictionary cast should be ok");
+        let keys = UInt32Column::new(array.keys().clone());
+        let values = array.values().as_ref().into_column();
+        Self::new(keys, values)
+    }
+
+    /// The arrow dictionary array of the column, unlike `as_arrow_array` which materializes it.
+    pub fn as_arrow_dictionary_array(&self, logical_type: DataTypeImpl) -> Result<ArrayRef> {
+        let values = self.values.as_arrow_array(logical_type);
+        let keys = self.keys.as_arrow_array(u32::to_data_type());
+        let keys: &PrimitiveArray<u32> = keys
+            .as_any()
+            .downcast_ref()
+            .expect("primitive cast should be ok");
+        let data_type = ArrowDataType::Dictionary(
+            IntegerType::UInt32,
+            Box::new(values.data_type().clone()),
+            false,
+        );
+        let array = DictionaryArray::<u32>::try_new(data_type, keys.clone(), values)?;
+        Ok(Box::new(array))
+    }
+
+    pub fn keys(&self) -> &UInt32Column {
+        &self.keys
+    }
+
+    pub fn values(&self) -> &ColumnRef {
+        &self.values
+    }
+
+    /// The column of the values of the rows, materialized on the first call.
+    pub fn full_column(&self) -> &ColumnRef {
+        self.full.get_or_init(|| {
+            Series::take(&self.values, self.keys.values()).expect("take should be ok")
+        })
+    }
+
+    /// Replace the values by the `values` of the same length, e.g. the result of a function
+    /// evaluated on the values. The result is kept encoded if it is a plain string column.
+    pub fn map_values(&self, values: ColumnRef) -> Result<ColumnRef> {
+        debug_assert_eq!(values.len(), self.values.len());
+        if values.data_type_id() == TypeID::String
+            && !values.is_nullable()
+            && !values.is_const()
+            && !values.is_dictionary()
+        {
+            return Ok(Self::new(self.keys.clone(), values).arc());
+        }
+        Series::take(&values, self.keys.values())
+    }
+
+    /// Take the rows at `indices`, keeping the column encoded.
+    pub fn take<I: Index>(&self, indices: &[I]) -> Result<ColumnRef> {
+        let keys = Series::take(&self.keys.arc(), indices)?;
+        Ok(self.with_keys(keys))
+    }
+
+    fn with_keys(&self, keys: ColumnRef) -> ColumnRef {
+        let keys: &UInt32Column = Series::check_get(&keys).expect("keys cast should be ok");
+        Self::new(keys.clone(), self.values.clone()).arc()
+    }
+
+    #[inline]
+    fn key(&self, row: usize) -> usize {
+        self.keys.values()[row] as usize
+    }
+}
+
+impl Column for DictionaryColumn {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn data_type(&self) -> DataTypeImpl {
+        self.values.data_type()
+    }
+
+    fn column_type_name(&self) -> String {
+        format!("Dictionary({})", self.values.column_type_name())
+    }
+
+    fn is_dictionary(&self) -> bool {
+        true
+    }
+
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn memory_size(&self) -> usize {
+        self.keys.memory_size() + self.values.memory_size()
+    }
+
+    fn as_arrow_array(&self, logical_type: DataTypeImpl) -> ArrayRef {
+        self.full_column().as_arrow_array(logical_type)
+    }
+
+    fn arc(&self) -> ColumnRef {
+        Arc::new(self.clone())
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> ColumnRef {
+        self.with_keys(self.keys.slice(offset, length))
+    }
+
+    fn filter(&self, filter: &BooleanColumn) -> ColumnRef {
+        self.with_keys(self.keys.filter(filter))
+    }
+
+    fn scatter(&self, indices: &[usize], scattered_size: usize) -> Vec<ColumnRef> {
+        self.keys
+            .scatter(indices, scattered_size)
+            .into_iter()
+            .map(|keys| self.with_keys(keys))
+            .collect()
+    }
+
+    fn replicate(&self, offsets: &[usize]) -> ColumnRef {
+        self.with_keys(self.keys.replicate(offsets))
+    }
+
+    fn convert_full_column(&self) -> ColumnRef {
+        self.full_column().clone()
+    }
+
+    fn get(&self, index: usize) -> DataValue {
+        self.values.get(self.key(index))
+    }
+
+    fn serialize(&self, vec: &mut Vec<u8>, row: usize) {
+        self.values.serialize(vec, self.key(row));
+    }
+}
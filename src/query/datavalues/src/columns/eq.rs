@@ -46,7 +46,7 @@ pub fn equal(lhs: &dyn Column, rhs: &dyn Column) -> bool {
         return false;
     }
 
-    if lhs.is_const() || rhs.is_const() {
+    if lhs.is_const() || rhs.is_const() || lhs.is_dictionary() || rhs.is_dictionary() {
         return equal(
             lhs.convert_full_column().as_ref(),
             rhs.convert_full_column().as_ref(),
@@ -20,6 +20,7 @@ mod boolean;
 mod column;
 mod column_with_field;
 mod const_;
+mod dictionary;
 mod eq;
 #[allow(clippy::ptr_arg)]
 mod group_hash;
@@ -39,6 +40,7 @@ pub use builder::*;
 pub use column::*;
 pub use column_with_field::*;
 pub use const_::*;
+pub use dictionary::*;
 pub use group_hash::GroupHash;
 pub use mutable::*;
 pub use null::*;
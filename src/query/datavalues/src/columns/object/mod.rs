@@ -55,8 +55,7 @@ impl<T: ObjectType> ObjectColumn<T> {
             }
             let off = offsets[i] as usize;
             let len = (offsets[i + 1] - offsets[i]) as usize;
-            let val = std::str::from_utf8(&array_values[off..off + len]).unwrap();
-            match T::from_str(val) {
+            match T::decode(&array_values[off..off + len]) {
                 Ok(v) => values.push(v),
                 Err(_) => values.push(T::default()),
             }
@@ -86,6 +85,26 @@ impl<T: ObjectType> ObjectColumn<T> {
         self.values.as_slice()
     }
 
+    /// Converts to an arrow array of the binary encoding of the values rather than their text,
+    /// which is how the values are stored.
+    pub fn as_binary_arrow_array(&self, logical_type: DataTypeImpl) -> common_arrow::ArrayRef {
+        let mut offsets: Vec<i64> = Vec::with_capacity(self.values.len() + 1);
+        let mut values: Vec<u8> = Vec::with_capacity(self.values.len());
+
+        offsets.push(0);
+        for val in &self.values {
+            val.encode_binary(&mut values);
+            offsets.push(values.len() as i64);
+        }
+
+        Box::new(LargeBinaryArray::from_data(
+            logical_type.arrow_type(),
+            Buffer::from(offsets),
+            Buffer::from(values),
+            None,
+        ))
+    }
+
     /// Create a new DataArray by taking ownership of the Vec. This operation is zero copy.
     pub fn new_from_vec(values: Vec<T>) -> Self {
         Self { values }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::any::TypeId;
 use std::sync::Arc;
 
 use common_arrow::arrow::bitmap::MutableBitmap;
@@ -37,13 +38,24 @@ impl Series {
     /// # Safety
     /// Assumes that the `column` is T.
     pub unsafe fn static_cast<T: Any>(column: &ColumnRef) -> &T {
-        let object = column.as_ref();
+        let object = Self::downcast_target::<T>(column).as_ref();
         debug_assert!(object.as_any().is::<T>());
         &*(object as *const dyn Column as *const T)
     }
 
+    /// The column to downcast to `T`. A dictionary-encoded column is materialized, unless the
+    /// dictionary itself is asked for.
+    #[inline]
+    fn downcast_target<T: Any>(column: &ColumnRef) -> &ColumnRef {
+        if column.is_dictionary() && TypeId::of::<T>() != TypeId::of::<DictionaryColumn>() {
+            let dictionary: &DictionaryColumn = column.as_any().downcast_ref().unwrap();
+            return dictionary.full_column();
+        }
+        column
+    }
+
     pub fn check_get_scalar<T: Scalar>(column: &ColumnRef) -> Result<&<T as Scalar>::ColumnType> {
-        let arr = column
+        let arr = Self::downcast_target::<<T as Scalar>::ColumnType>(column)
             .as_any()
             .downcast_ref::<<T as Scalar>::ColumnType>()
             .ok_or_else(|| {
@@ -57,7 +69,8 @@ impl Series {
     }
 
     pub fn check_get<T: 'static + Column>(column: &ColumnRef) -> Result<&T> {
-        let arr = column.as_any().downcast_ref::<T>().ok_or_else(|| {
+        let target = Self::downcast_target::<T>(column);
+        let arr = target.as_any().downcast_ref::<T>().ok_or_else(|| {
             ErrorCode::UnknownColumn(format!(
                 "downcast column error, column type: {:?}, expected column: {:?}",
                 column.column_type_name(),
@@ -84,7 +97,7 @@ impl Series {
     pub fn check_get_scalar_column<T: Scalar>(
         column: &ColumnRef,
     ) -> Result<&<T as Scalar>::ColumnType> {
-        let arr = column
+        let arr = Self::downcast_target::<<T as Scalar>::ColumnType>(column)
             .as_any()
             .downcast_ref::<<T as Scalar>::ColumnType>()
             .ok_or_else(|| {
@@ -28,6 +28,9 @@ impl Series {
     pub fn take<I: Index>(column: &ColumnRef, indices: &[I]) -> Result<ColumnRef> {
         if column.is_const() || column.is_null() {
             Ok(column.slice(0, indices.len()))
+        } else if column.is_dictionary() {
+            let dictionary: &DictionaryColumn = Series::check_get(column)?;
+            dictionary.take(indices)
         } else if column.is_nullable() {
             let nullable_c: &NullableColumn = unsafe { Series::static_cast(column) };
             let inner_result = Self::take(nullable_c.inner(), indices)?;
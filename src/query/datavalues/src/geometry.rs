@@ -0,0 +1,780 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoding of the GEOMETRY and GEOGRAPHY values.
+//!
+//! The values are stored as 2D WKB (well-known binary) in little endian. They can be parsed from
+//! WKT (well-known text), WKB, hex-encoded WKB or GeoJSON, and are displayed as WKT. The SRID of
+//! EWKT and EWKB is accepted but not kept.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use geo_types::Coordinate;
+use geo_types::Geometry;
+use geo_types::GeometryCollection;
+use geo_types::LineString;
+use geo_types::MultiLineString;
+use geo_types::MultiPoint;
+use geo_types::MultiPolygon;
+use geo_types::Point;
+use geo_types::Polygon;
+use serde_json::json;
+use serde_json::Value as JsonValue;
+
+const WKB_POINT: u32 = 1;
+const WKB_LINE_STRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTI_POINT: u32 = 4;
+const WKB_MULTI_LINE_STRING: u32 = 5;
+const WKB_MULTI_POLYGON: u32 = 6;
+const WKB_GEOMETRY_COLLECTION: u32 = 7;
+
+// The flags of the geometry type in EWKB.
+const EWKB_Z_FLAG: u32 = 0x80000000;
+const EWKB_M_FLAG: u32 = 0x40000000;
+const EWKB_SRID_FLAG: u32 = 0x20000000;
+
+/// Parse a geometry from WKT, WKB, hex-encoded WKB or GeoJSON, the format is detected from the
+/// first byte.
+pub fn parse_geometry(buf: &[u8]) -> Result<Geometry<f64>> {
+    // WKB starts with the byte order, which is never the first byte of a text.
+    if matches!(buf.first(), Some(0) | Some(1)) {
+        return geometry_from_wkb(buf);
+    }
+
+    let text = std::str::from_utf8(buf)
+        .map_err(|_| ErrorCode::BadBytes("Invalid geometry, not a valid UTF-8 text"))?
+        .trim();
+    if text.starts_with('{') {
+        let value: JsonValue = serde_json::from_str(text)
+            .map_err(|e| ErrorCode::BadBytes(format!("Invalid GeoJSON: {}", e)))?;
+        geometry_from_geojson(&value)
+    } else if !text.is_empty() && text.bytes().all(|c| c.is_ascii_hexdigit()) {
+        geometry_from_wkb(&decode_hex(text)?)
+    } else {
+        geometry_from_wkt(text)
+    }
+}
+
+/// Parse a geometry like `parse_geometry`, and encode it to the WKB stored in the GEOMETRY or
+/// GEOGRAPHY columns. The coordinates of a geography are checked.
+pub fn parse_geometry_to_wkb(buf: &[u8], geography: bool) -> Result<Vec<u8>> {
+    let geometry = parse_geometry(buf)?;
+    if geography {
+        check_geography(&geometry)?;
+    }
+    let mut wkb = Vec::with_capacity(buf.len());
+    geometry_to_wkb(&geometry, &mut wkb);
+    Ok(wkb)
+}
+
+/// Check that the coordinates of a geography are valid longitudes and latitudes in degrees.
+pub fn check_geography(geometry: &Geometry<f64>) -> Result<()> {
+    let mut result = Ok(());
+    for_each_coordinate(geometry, &mut |c| {
+        if result.is_ok() && !((-180.0..=180.0).contains(&c.x) && (-90.0..=90.0).contains(&c.y)) {
+            result = Err(ErrorCode::BadArguments(format!(
+                "Invalid geography, the coordinate ({} {}) is not a valid longitude and latitude",
+                c.x, c.y
+            )));
+        }
+    });
+    result
+}
+
+/// Call `f` on all the coordinates of a geometry.
+pub fn for_each_coordinate(geometry: &Geometry<f64>, f: &mut impl FnMut(Coordinate<f64>)) {
+    match geometry {
+        Geometry::Point(p) => f(p.0),
+        Geometry::Line(l) => {
+            f(l.start);
+            f(l.end);
+        }
+        Geometry::LineString(l) => l.0.iter().for_each(|c| f(*c)),
+        Geometry::Polygon(p) => for_each_polygon_coordinate(p, f),
+        Geometry::MultiPoint(mp) => mp.0.iter().for_each(|p| f(p.0)),
+        Geometry::MultiLineString(ml) => ml.0.iter().flat_map(|l| l.0.iter()).for_each(|c| f(*c)),
+        Geometry::MultiPolygon(mp) => mp.0.iter().for_each(|p| for_each_polygon_coordinate(p, f)),
+        Geometry::GeometryCollection(gc) => gc.0.iter().for_each(|g| for_each_coordinate(g, f)),
+        Geometry::Rect(r) => for_each_polygon_coordinate(&r.to_polygon(), f),
+        Geometry::Triangle(t) => for_each_polygon_coordinate(&t.to_polygon(), f),
+    }
+}
+
+fn for_each_polygon_coordinate(polygon: &Polygon<f64>, f: &mut impl FnMut(Coordinate<f64>)) {
+    polygon.exterior().0.iter().for_each(|c| f(*c));
+    for interior in polygon.interiors() {
+        interior.0.iter().for_each(|c| f(*c));
+    }
+}
+
+fn decode_hex(text: &str) -> Result<Vec<u8>> {
+    if text.len() % 2 != 0 {
+        return Err(ErrorCode::BadBytes("Invalid hex-encoded WKB, odd length"));
+    }
+    let bytes = text.as_bytes();
+    let digit = |c: u8| match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        _ => c - b'A' + 10,
+    };
+    Ok(bytes
+        .chunks(2)
+        .map(|pair| digit(pair[0]) << 4 | digit(pair[1]))
+        .collect())
+}
+
+/// Encode a geometry to WKB in little endian.
+pub fn geometry_to_wkb(geometry: &Geometry<f64>, buf: &mut Vec<u8>) {
+    match geometry {
+        Geometry::Point(p) => {
+            write_wkb_header(buf, WKB_POINT);
+            write_wkb_coordinate(buf, p.0);
+        }
+        Geometry::Line(l) => write_wkb_line_string(buf, &LineString(vec![l.start, l.end])),
+        Geometry::LineString(l) => write_wkb_line_string(buf, l),
+        Geometry::Polygon(p) => write_wkb_polygon(buf, p),
+        Geometry::MultiPoint(mp) => {
+            write_wkb_header(buf, WKB_MULTI_POINT);
+            buf.extend_from_slice(&(mp.0.len() as u32).to_le_bytes());
+            for p in mp.0.iter() {
+                write_wkb_header(buf, WKB_POINT);
+                write_wkb_coordinate(buf, p.0);
+            }
+        }
+        Geometry::MultiLineString(ml) => {
+            write_wkb_header(buf, WKB_MULTI_LINE_STRING);
+            buf.extend_from_slice(&(ml.0.len() as u32).to_le_bytes());
+            for l in ml.0.iter() {
+                write_wkb_line_string(buf, l);
+            }
+        }
+        Geometry::MultiPolygon(mp) => {
+            write_wkb_header(buf, WKB_MULTI_POLYGON);
+            buf.extend_from_slice(&(mp.0.len() as u32).to_le_bytes());
+            for p in mp.0.iter() {
+                write_wkb_polygon(buf, p);
+            }
+        }
+        Geometry::GeometryCollection(gc) => {
+            write_wkb_header(buf, WKB_GEOMETRY_COLLECTION);
+            buf.extend_from_slice(&(gc.0.len() as u32).to_le_bytes());
+            for g in gc.0.iter() {
+                geometry_to_wkb(g, buf);
+            }
+        }
+        Geometry::Rect(r) => write_wkb_polygon(buf, &r.to_polygon()),
+        Geometry::Triangle(t) => write_wkb_polygon(buf, &t.to_polygon()),
+    }
+}
+
+fn write_wkb_header(buf: &mut Vec<u8>, geometry_type: u32) {
+    buf.push(1);
+    buf.extend_from_slice(&geometry_type.to_le_bytes());
+}
+
+fn write_wkb_coordinate(buf: &mut Vec<u8>, c: Coordinate<f64>) {
+    buf.extend_from_slice(&c.x.to_le_bytes());
+    buf.extend_from_slice(&c.y.to_le_bytes());
+}
+
+fn write_wkb_ring(buf: &mut Vec<u8>, ring: &LineString<f64>) {
+    buf.extend_from_slice(&(ring.0.len() as u32).to_le_bytes());
+    for c in ring.0.iter() {
+        write_wkb_coordinate(buf, *c);
+    }
+}
+
+fn write_wkb_line_string(buf: &mut Vec<u8>, line_string: &LineString<f64>) {
+    write_wkb_header(buf, WKB_LINE_STRING);
+    write_wkb_ring(buf, line_string);
+}
+
+fn write_wkb_polygon(buf: &mut Vec<u8>, polygon: &Polygon<f64>) {
+    write_wkb_header(buf, WKB_POLYGON);
+    if polygon.exterior().0.is_empty() {
+        buf.extend_from_slice(&0u32.to_le_bytes());
+        return;
+    }
+    buf.extend_from_slice(&(polygon.interiors().len() as u32 + 1).to_le_bytes());
+    write_wkb_ring(buf, polygon.exterior());
+    for interior in polygon.interiors() {
+        write_wkb_ring(buf, interior);
+    }
+}
+
+/// Decode a geometry from WKB or EWKB in either byte order.
+pub fn geometry_from_wkb(buf: &[u8]) -> Result<Geometry<f64>> {
+    let mut reader = WkbReader {
+        buf,
+        pos: 0,
+        little_endian: true,
+    };
+    let geometry = reader.read_geometry()?;
+    if reader.pos != buf.len() {
+        return Err(ErrorCode::BadBytes("Invalid WKB, trailing bytes"));
+    }
+    Ok(geometry)
+}
+
+struct WkbReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    little_endian: bool,
+}
+
+impl<'a> WkbReader<'a> {
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        if self.pos + N > self.buf.len() {
+            return Err(ErrorCode::BadBytes("Invalid WKB, unexpected end"));
+        }
+        let bytes = self.buf[self.pos..self.pos + N].try_into().unwrap();
+        self.pos += N;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes::<4>()?;
+        Ok(match self.little_endian {
+            true => u32::from_le_bytes(bytes),
+            false => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn read_f64(&mut self) -> Result<f64> {
+        let bytes = self.read_bytes::<8>()?;
+        Ok(match self.little_endian {
+            true => f64::from_le_bytes(bytes),
+            false => f64::from_be_bytes(bytes),
+        })
+    }
+
+    fn read_coordinate(&mut self) -> Result<Coordinate<f64>> {
+        let x = self.read_f64()?;
+        let y = self.read_f64()?;
+        Ok(Coordinate { x, y })
+    }
+
+    fn read_ring(&mut self) -> Result<LineString<f64>> {
+        let n = self.read_u32()? as usize;
+        // Every coordinate takes 16 bytes, which bounds the allocation by the input.
+        let mut coordinates = Vec::with_capacity(n.min(self.buf.len() / 16));
+        for _ in 0..n {
+            coordinates.push(self.read_coordinate()?);
+        }
+        Ok(LineString(coordinates))
+    }
+
+    // Read the header of a geometry, and return its type.
+    fn read_header(&mut self) -> Result<u32> {
+        self.little_endian = match self.read_bytes::<1>()?[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(ErrorCode::BadBytes("Invalid WKB, unknown byte order")),
+        };
+        let geometry_type = self.read_u32()?;
+        // The Z or M coordinates, which are flags in EWKB, or the thousands in ISO WKB.
+        if geometry_type & (EWKB_Z_FLAG | EWKB_M_FLAG) != 0
+            || geometry_type & !EWKB_SRID_FLAG >= 1000
+        {
+            return Err(ErrorCode::BadBytes(
+                "Invalid WKB, only 2D geometries are supported",
+            ));
+        }
+        if geometry_type & EWKB_SRID_FLAG != 0 {
+            self.read_u32()?;
+        }
+        Ok(geometry_type & !EWKB_SRID_FLAG)
+    }
+
+    fn read_polygon_body(&mut self) -> Result<Polygon<f64>> {
+        let n = self.read_u32()? as usize;
+        if n == 0 {
+            return Ok(Polygon::new(LineString(vec![]), vec![]));
+        }
+        let exterior = self.read_ring()?;
+        let mut interiors = Vec::with_capacity(self.read_capacity(n - 1));
+        for _ in 1..n {
+            interiors.push(self.read_ring()?);
+        }
+        Ok(Polygon::new(exterior, interiors))
+    }
+
+    fn read_typed<T>(&mut self, expected: u32, read: impl Fn(&mut Self) -> Result<T>) -> Result<T> {
+        let geometry_type = self.read_header()?;
+        if geometry_type != expected {
+            return Err(ErrorCode::BadBytes(format!(
+                "Invalid WKB, unexpected geometry type {} in a multi geometry",
+                geometry_type
+            )));
+        }
+        read(self)
+    }
+
+    fn read_geometry(&mut self) -> Result<Geometry<f64>> {
+        let geometry_type = self.read_header()?;
+        let geometry = match geometry_type {
+            WKB_POINT => {
+                let c = self.read_coordinate()?;
+                if c.x.is_nan() && c.y.is_nan() {
+                    return Err(ErrorCode::BadBytes("Empty points are not supported"));
+                }
+                Geometry::Point(Point(c))
+            }
+            WKB_LINE_STRING => Geometry::LineString(self.read_ring()?),
+            WKB_POLYGON => Geometry::Polygon(self.read_polygon_body()?),
+            WKB_MULTI_POINT => {
+                let n = self.read_u32()? as usize;
+                let mut points = Vec::with_capacity(self.read_capacity(n));
+                for _ in 0..n {
+                    points.push(Point(self.read_typed(WKB_POINT, Self::read_coordinate)?));
+                }
+                Geometry::MultiPoint(MultiPoint(points))
+            }
+            WKB_MULTI_LINE_STRING => {
+                let n = self.read_u32()? as usize;
+                let mut line_strings = Vec::with_capacity(self.read_capacity(n));
+                for _ in 0..n {
+                    line_strings.push(self.read_typed(WKB_LINE_STRING, Self::read_ring)?);
+                }
+                Geometry::MultiLineString(MultiLineString(line_strings))
+            }
+            WKB_MULTI_POLYGON => {
+                let n = self.read_u32()? as usize;
+                let mut polygons = Vec::with_capacity(self.read_capacity(n));
+                for _ in 0..n {
+                    polygons.push(self.read_typed(WKB_POLYGON, Self::read_polygon_body)?);
+                }
+                Geometry::MultiPolygon(MultiPolygon(polygons))
+            }
+            WKB_GEOMETRY_COLLECTION => {
+                let n = self.read_u32()? as usize;
+                let mut geometries = Vec::with_capacity(self.read_capacity(n));
+                for _ in 0..n {
+                    geometries.push(self.read_geometry()?);
+                }
+                Geometry::GeometryCollection(GeometryCollection(geometries))
+            }
+            _ => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Invalid WKB, unknown geometry type {}",
+                    geometry_type
+                )));
+            }
+        };
+        Ok(geometry)
+    }
+
+    // The capacity to reserve for `n` geometries, which is bounded by the remaining bytes, as
+    // every geometry takes at least 5 bytes.
+    fn read_capacity(&self, n: usize) -> usize {
+        n.min((self.buf.len() - self.pos) / 5)
+    }
+}
+
+/// Format a geometry as WKT, like `POINT(1 2)` or `LINESTRING(0 0,1 1)`.
+pub fn geometry_to_wkt(geometry: &Geometry<f64>) -> String {
+    let mut buf = String::new();
+    write_wkt(geometry, &mut buf);
+    buf
+}
+
+fn write_wkt(geometry: &Geometry<f64>, buf: &mut String) {
+    match geometry {
+        Geometry::Point(p) => {
+            buf.push_str("POINT(");
+            write_wkt_coordinate(buf, p.0);
+            buf.push(')');
+        }
+        Geometry::Line(l) => {
+            write_wkt(&Geometry::LineString(LineString(vec![l.start, l.end])), buf)
+        }
+        Geometry::LineString(l) => {
+            buf.push_str("LINESTRING");
+            write_wkt_ring(buf, l);
+        }
+        Geometry::Polygon(p) => {
+            buf.push_str("POLYGON");
+            write_wkt_polygon(buf, p);
+        }
+        Geometry::MultiPoint(mp) => {
+            buf.push_str("MULTIPOINT");
+            write_wkt_list(buf, &mp.0, |buf, p| {
+                buf.push('(');
+                write_wkt_coordinate(buf, p.0);
+                buf.push(')');
+            });
+        }
+        Geometry::MultiLineString(ml) => {
+            buf.push_str("MULTILINESTRING");
+            write_wkt_list(buf, &ml.0, write_wkt_ring);
+        }
+        Geometry::MultiPolygon(mp) => {
+            buf.push_str("MULTIPOLYGON");
+            write_wkt_list(buf, &mp.0, write_wkt_polygon);
+        }
+        Geometry::GeometryCollection(gc) => {
+            buf.push_str("GEOMETRYCOLLECTION");
+            write_wkt_list(buf, &gc.0, |buf, g| write_wkt(g, buf));
+        }
+        Geometry::Rect(r) => write_wkt(&Geometry::Polygon(r.to_polygon()), buf),
+        Geometry::Triangle(t) => write_wkt(&Geometry::Polygon(t.to_polygon()), buf),
+    }
+}
+
+fn write_wkt_coordinate(buf: &mut String, c: Coordinate<f64>) {
+    buf.push_str(&format!("{} {}", c.x, c.y));
+}
+
+fn write_wkt_list<T>(buf: &mut String, items: &[T], write: impl Fn(&mut String, &T)) {
+    if items.is_empty() {
+        buf.push_str(" EMPTY");
+        return;
+    }
+    buf.push('(');
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            buf.push(',');
+        }
+        write(buf, item);
+    }
+    buf.push(')');
+}
+
+fn write_wkt_ring(buf: &mut String, ring: &LineString<f64>) {
+    write_wkt_list(buf, &ring.0, |buf, c| write_wkt_coordinate(buf, *c));
+}
+
+fn write_wkt_polygon(buf: &mut String, polygon: &Polygon<f64>) {
+    if polygon.exterior().0.is_empty() {
+        buf.push_str(" EMPTY");
+        return;
+    }
+    buf.push('(');
+    write_wkt_ring(buf, polygon.exterior());
+    for interior in polygon.interiors() {
+        buf.push(',');
+        write_wkt_ring(buf, interior);
+    }
+    buf.push(')');
+}
+
+/// Parse a geometry from WKT or EWKT.
+pub fn geometry_from_wkt(text: &str) -> Result<Geometry<f64>> {
+    let mut text = text.trim();
+    if text.len() >= 5 && text[..5].eq_ignore_ascii_case("SRID=") {
+        text = match text.find(';') {
+            Some(pos) => &text[pos + 1..],
+            None => return Err(ErrorCode::BadBytes("Invalid EWKT, missing ';' after SRID")),
+        };
+    }
+
+    let mut parser = WktParser { text, pos: 0 };
+    let geometry = parser.parse_geometry()?;
+    parser.skip_whitespace();
+    if parser.pos != text.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+    Ok(geometry)
+}
+
+struct WktParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> WktParser<'a> {
+    fn error(&self, message: &str) -> ErrorCode {
+        ErrorCode::BadBytes(format!(
+            "Invalid WKT '{}', {} at position {}",
+            self.text, message, self.pos
+        ))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn consume(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, c: u8) -> Result<()> {
+        match self.consume(c) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected '{}'", c as char))),
+        }
+    }
+
+    fn read_word(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = &self.text[self.pos..];
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn read_number(&mut self) -> Result<f64> {
+        self.skip_whitespace();
+        let rest = &self.text[self.pos..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E')))
+            .unwrap_or(rest.len());
+        let number = rest[..len]
+            .parse::<f64>()
+            .map_err(|_| self.error("expected a number"))?;
+        self.pos += len;
+        Ok(number)
+    }
+
+    // `EMPTY` or `(` before the body of a geometry, returns whether the geometry is empty.
+    fn parse_empty(&mut self) -> Result<bool> {
+        if self.consume(b'(') {
+            return Ok(false);
+        }
+        let start = self.pos;
+        if self.read_word().eq_ignore_ascii_case("EMPTY") {
+            return Ok(true);
+        }
+        self.pos = start;
+        Err(self.error("expected '(' or EMPTY"))
+    }
+
+    fn parse_list<T>(&mut self, parse: impl Fn(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        if self.parse_empty()? {
+            return Ok(vec![]);
+        }
+        let mut items = vec![parse(self)?];
+        while self.consume(b',') {
+            items.push(parse(self)?);
+        }
+        self.expect(b')')?;
+        Ok(items)
+    }
+
+    fn parse_coordinate(&mut self) -> Result<Coordinate<f64>> {
+        let x = self.read_number()?;
+        let y = self.read_number()?;
+        if matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == b'-' || c == b'+') {
+            return Err(self.error("only 2D coordinates are supported"));
+        }
+        Ok(Coordinate { x, y })
+    }
+
+    fn parse_ring(&mut self) -> Result<LineString<f64>> {
+        Ok(LineString(self.parse_list(Self::parse_coordinate)?))
+    }
+
+    fn parse_polygon(&mut self) -> Result<Polygon<f64>> {
+        let mut rings = self.parse_list(Self::parse_ring)?;
+        if rings.is_empty() {
+            return Ok(Polygon::new(LineString(vec![]), vec![]));
+        }
+        let exterior = rings.remove(0);
+        Ok(Polygon::new(exterior, rings))
+    }
+
+    // A point of a multipoint, which is either `(x y)` or `x y`.
+    fn parse_multi_point_item(&mut self) -> Result<Point<f64>> {
+        if self.consume(b'(') {
+            let c = self.parse_coordinate()?;
+            self.expect(b')')?;
+            return Ok(Point(c));
+        }
+        Ok(Point(self.parse_coordinate()?))
+    }
+
+    fn parse_geometry(&mut self) -> Result<Geometry<f64>> {
+        let tag = self.read_word().to_ascii_uppercase();
+        let geometry = match tag.as_str() {
+            "POINT" => {
+                if self.parse_empty()? {
+                    return Err(self.error("empty points are not supported"));
+                }
+                let c = self.parse_coordinate()?;
+                self.expect(b')')?;
+                Geometry::Point(Point(c))
+            }
+            "LINESTRING" => Geometry::LineString(self.parse_ring()?),
+            "POLYGON" => Geometry::Polygon(self.parse_polygon()?),
+            "MULTIPOINT" => {
+                Geometry::MultiPoint(MultiPoint(self.parse_list(Self::parse_multi_point_item)?))
+            }
+            "MULTILINESTRING" => {
+                Geometry::MultiLineString(MultiLineString(self.parse_list(Self::parse_ring)?))
+            }
+            "MULTIPOLYGON" => {
+                Geometry::MultiPolygon(MultiPolygon(self.parse_list(Self::parse_polygon)?))
+            }
+            "GEOMETRYCOLLECTION" => Geometry::GeometryCollection(GeometryCollection(
+                self.parse_list(Self::parse_geometry)?,
+            )),
+            "" => return Err(self.error("expected a geometry type")),
+            _ => return Err(self.error(&format!("unknown geometry type {}", tag))),
+        };
+        Ok(geometry)
+    }
+}
+
+/// Format a geometry as a GeoJSON geometry object.
+pub fn geometry_to_geojson(geometry: &Geometry<f64>) -> JsonValue {
+    let coordinate = |c: &Coordinate<f64>| json!([c.x, c.y]);
+    let ring = |l: &LineString<f64>| JsonValue::Array(l.0.iter().map(coordinate).collect());
+    let polygon = |p: &Polygon<f64>| {
+        let mut rings = vec![];
+        if !p.exterior().0.is_empty() {
+            rings.push(ring(p.exterior()));
+            rings.extend(p.interiors().iter().map(ring));
+        }
+        JsonValue::Array(rings)
+    };
+
+    match geometry {
+        Geometry::Point(p) => json!({"type": "Point", "coordinates": coordinate(&p.0)}),
+        Geometry::Line(l) => {
+            geometry_to_geojson(&Geometry::LineString(LineString(vec![l.start, l.end])))
+        }
+        Geometry::LineString(l) => json!({"type": "LineString", "coordinates": ring(l)}),
+        Geometry::Polygon(p) => json!({"type": "Polygon", "coordinates": polygon(p)}),
+        Geometry::MultiPoint(mp) => {
+            let points: Vec<_> = mp.0.iter().map(|p| coordinate(&p.0)).collect();
+            json!({"type": "MultiPoint", "coordinates": points})
+        }
+        Geometry::MultiLineString(ml) => {
+            let line_strings: Vec<_> = ml.0.iter().map(ring).collect();
+            json!({"type": "MultiLineString", "coordinates": line_strings})
+        }
+        Geometry::MultiPolygon(mp) => {
+            let polygons: Vec<_> = mp.0.iter().map(polygon).collect();
+            json!({"type": "MultiPolygon", "coordinates": polygons})
+        }
+        Geometry::GeometryCollection(gc) => {
+            let geometries: Vec<_> = gc.0.iter().map(geometry_to_geojson).collect();
+            json!({"type": "GeometryCollection", "geometries": geometries})
+        }
+        Geometry::Rect(r) => geometry_to_geojson(&Geometry::Polygon(r.to_polygon())),
+        Geometry::Triangle(t) => geometry_to_geojson(&Geometry::Polygon(t.to_polygon())),
+    }
+}
+
+/// Parse a geometry from a GeoJSON geometry object, or the geometry of a GeoJSON feature.
+pub fn geometry_from_geojson(value: &JsonValue) -> Result<Geometry<f64>> {
+    let geometry_type = value
+        .get("type")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| invalid_geojson("missing type"))?;
+    if geometry_type == "Feature" {
+        let geometry = value
+            .get("geometry")
+            .ok_or_else(|| invalid_geojson("missing geometry of the feature"))?;
+        return geometry_from_geojson(geometry);
+    }
+    if geometry_type == "GeometryCollection" {
+        let geometries = value
+            .get("geometries")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| invalid_geojson("missing geometries"))?;
+        let geometries = geometries
+            .iter()
+            .map(geometry_from_geojson)
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(Geometry::GeometryCollection(GeometryCollection(geometries)));
+    }
+
+    let coordinates = value
+        .get("coordinates")
+        .ok_or_else(|| invalid_geojson("missing coordinates"))?;
+    let geometry = match geometry_type {
+        "Point" => Geometry::Point(Point(geojson_coordinate(coordinates)?)),
+        "LineString" => Geometry::LineString(geojson_ring(coordinates)?),
+        "Polygon" => Geometry::Polygon(geojson_polygon(coordinates)?),
+        "MultiPoint" => {
+            let points = geojson_array(coordinates)?
+                .iter()
+                .map(|v| Ok(Point(geojson_coordinate(v)?)))
+                .collect::<Result<Vec<_>>>()?;
+            Geometry::MultiPoint(MultiPoint(points))
+        }
+        "MultiLineString" => {
+            let line_strings = geojson_array(coordinates)?
+                .iter()
+                .map(geojson_ring)
+                .collect::<Result<Vec<_>>>()?;
+            Geometry::MultiLineString(MultiLineString(line_strings))
+        }
+        "MultiPolygon" => {
+            let polygons = geojson_array(coordinates)?
+                .iter()
+                .map(geojson_polygon)
+                .collect::<Result<Vec<_>>>()?;
+            Geometry::MultiPolygon(MultiPolygon(polygons))
+        }
+        _ => {
+            return Err(invalid_geojson(&format!("unknown type {}", geometry_type)));
+        }
+    };
+    Ok(geometry)
+}
+
+fn invalid_geojson(message: &str) -> ErrorCode {
+    ErrorCode::BadBytes(format!("Invalid GeoJSON, {}", message))
+}
+
+fn geojson_array(value: &JsonValue) -> Result<&Vec<JsonValue>> {
+    value
+        .as_array()
+        .ok_or_else(|| invalid_geojson("coordinates must be arrays"))
+}
+
+fn geojson_coordinate(value: &JsonValue) -> Result<Coordinate<f64>> {
+    match geojson_array(value)?.as_slice() {
+        [x, y] => match (x.as_f64(), y.as_f64()) {
+            (Some(x), Some(y)) => Ok(Coordinate { x, y }),
+            _ => Err(invalid_geojson("a position must be numbers")),
+        },
+        _ => Err(invalid_geojson("only 2D positions are supported")),
+    }
+}
+
+fn geojson_ring(value: &JsonValue) -> Result<LineString<f64>> {
+    let coordinates = geojson_array(value)?
+        .iter()
+        .map(geojson_coordinate)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(LineString(coordinates))
+}
+
+fn geojson_polygon(value: &JsonValue) -> Result<Polygon<f64>> {
+    let mut rings = geojson_array(value)?
+        .iter()
+        .map(geojson_ring)
+        .collect::<Result<Vec<_>>>()?;
+    if rings.is_empty() {
+        return Ok(Polygon::new(LineString(vec![]), vec![]));
+    }
+    let exterior = rings.remove(0);
+    Ok(Polygon::new(exterior, rings))
+}
@@ -0,0 +1,230 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Binary encoding of VARIANT values, which is used by the fuse engine to store them.
+//!
+//! An encoded value starts with the `JSONB_HEADER` byte, followed by the value:
+//!
+//! ```text
+//! value  := tag payload
+//! null   := 0x00
+//! false  := 0x01
+//! true   := 0x02
+//! int    := 0x03 i64
+//! uint   := 0x04 u64
+//! float  := 0x05 f64
+//! string := 0x06 len:u32 bytes
+//! array  := 0x07 n:u32 end_offsets:[u32; n] values
+//! object := 0x08 n:u32 key_end_offsets:[u32; n] value_end_offsets:[u32; n] keys values
+//! ```
+//!
+//! All the numbers are little endian. The end offsets are relative to the start of the keys or
+//! the values, so an element can be located without decoding the ones before it. The keys of an
+//! object keep their order, so the values read back are displayed the same as they are written.
+//!
+//! The header is never the first byte of a UTF-8 text, so the values stored as JSON text by the
+//! former versions can still be read.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Map;
+use serde_json::Number;
+use serde_json::Value;
+
+/// The first byte of an encoded value, a UTF-8 continuation byte with the format version.
+pub const JSONB_HEADER: u8 = 0x80 | 1;
+
+const TAG_NULL: u8 = 0x00;
+const TAG_FALSE: u8 = 0x01;
+const TAG_TRUE: u8 = 0x02;
+const TAG_INT: u8 = 0x03;
+const TAG_UINT: u8 = 0x04;
+const TAG_FLOAT: u8 = 0x05;
+const TAG_STRING: u8 = 0x06;
+const TAG_ARRAY: u8 = 0x07;
+const TAG_OBJECT: u8 = 0x08;
+
+/// Returns whether the bytes are an encoded value rather than a JSON text.
+pub fn is_jsonb(buf: &[u8]) -> bool {
+    buf.first() == Some(&JSONB_HEADER)
+}
+
+pub fn encode_jsonb(value: &Value, buf: &mut Vec<u8>) {
+    buf.push(JSONB_HEADER);
+    encode_value(value, buf);
+}
+
+pub fn decode_jsonb(buf: &[u8]) -> Result<Value> {
+    if !is_jsonb(buf) {
+        return Err(ErrorCode::BadBytes("Invalid JSONB value, unknown header"));
+    }
+    let mut reader = Reader { buf, pos: 1 };
+    let value = reader.read_value()?;
+    if reader.pos != buf.len() {
+        return Err(ErrorCode::BadBytes("Invalid JSONB value, trailing bytes"));
+    }
+    Ok(value)
+}
+
+fn encode_value(value: &Value, buf: &mut Vec<u8>) {
+    match value {
+        Value::Null => buf.push(TAG_NULL),
+        Value::Bool(false) => buf.push(TAG_FALSE),
+        Value::Bool(true) => buf.push(TAG_TRUE),
+        Value::Number(n) => {
+            if let Some(v) = n.as_i64() {
+                buf.push(TAG_INT);
+                buf.extend_from_slice(&v.to_le_bytes());
+            } else if let Some(v) = n.as_u64() {
+                buf.push(TAG_UINT);
+                buf.extend_from_slice(&v.to_le_bytes());
+            } else {
+                buf.push(TAG_FLOAT);
+                buf.extend_from_slice(&n.as_f64().unwrap_or_default().to_le_bytes());
+            }
+        }
+        Value::String(v) => {
+            buf.push(TAG_STRING);
+            buf.extend_from_slice(&(v.len() as u32).to_le_bytes());
+            buf.extend_from_slice(v.as_bytes());
+        }
+        Value::Array(values) => {
+            buf.push(TAG_ARRAY);
+            buf.extend_from_slice(&(values.len() as u32).to_le_bytes());
+            encode_elements(values.iter(), buf);
+        }
+        Value::Object(object) => {
+            buf.push(TAG_OBJECT);
+            buf.extend_from_slice(&(object.len() as u32).to_le_bytes());
+            let mut end = 0u32;
+            for key in object.keys() {
+                end += key.len() as u32;
+                buf.extend_from_slice(&end.to_le_bytes());
+            }
+            let value_offsets = buf.len();
+            buf.resize(value_offsets + 4 * object.len(), 0);
+            for key in object.keys() {
+                buf.extend_from_slice(key.as_bytes());
+            }
+            write_elements(object.values(), value_offsets, buf);
+        }
+    }
+}
+
+// Encode the elements after their end offsets.
+fn encode_elements<'a>(values: impl ExactSizeIterator<Item = &'a Value>, buf: &mut Vec<u8>) {
+    let offsets = buf.len();
+    buf.resize(offsets + 4 * values.len(), 0);
+    write_elements(values, offsets, buf);
+}
+
+// Encode the elements, and fill their end offsets reserved at `offsets`.
+fn write_elements<'a>(values: impl Iterator<Item = &'a Value>, offsets: usize, buf: &mut Vec<u8>) {
+    let start = buf.len();
+    for (i, value) in values.enumerate() {
+        encode_value(value, buf);
+        let end = (buf.len() - start) as u32;
+        buf[offsets + 4 * i..offsets + 4 * (i + 1)].copy_from_slice(&end.to_le_bytes());
+    }
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.pos + len > self.buf.len() {
+            return Err(ErrorCode::BadBytes("Invalid JSONB value, unexpected end"));
+        }
+        let bytes = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        let bytes = self.read_bytes(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_string(&mut self, len: usize) -> Result<String> {
+        let bytes = self.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| ErrorCode::BadBytes("Invalid JSONB value, invalid UTF-8 string"))
+    }
+
+    fn read_value(&mut self) -> Result<Value> {
+        let tag = self.read_bytes(1)?[0];
+        match tag {
+            TAG_NULL => Ok(Value::Null),
+            TAG_FALSE => Ok(Value::Bool(false)),
+            TAG_TRUE => Ok(Value::Bool(true)),
+            TAG_INT => Ok(Value::from(self.read_u64()? as i64)),
+            TAG_UINT => Ok(Value::from(self.read_u64()?)),
+            TAG_FLOAT => {
+                let v = f64::from_bits(self.read_u64()?);
+                Number::from_f64(v)
+                    .map(Value::Number)
+                    .ok_or_else(|| ErrorCode::BadBytes("Invalid JSONB value, invalid float"))
+            }
+            TAG_STRING => {
+                let len = self.read_u32()? as usize;
+                Ok(Value::String(self.read_string(len)?))
+            }
+            TAG_ARRAY => {
+                let n = self.read_u32()? as usize;
+                // The elements are self-delimited, the end offsets are only for random access.
+                self.read_bytes(4 * n)?;
+                let mut values = Vec::with_capacity(n);
+                for _ in 0..n {
+                    values.push(self.read_value()?);
+                }
+                Ok(Value::Array(values))
+            }
+            TAG_OBJECT => {
+                let n = self.read_u32()? as usize;
+                let mut key_lens = Vec::with_capacity(n);
+                let mut start = 0;
+                for _ in 0..n {
+                    let end = self.read_u32()? as usize;
+                    if end < start {
+                        return Err(ErrorCode::BadBytes("Invalid JSONB value, invalid offsets"));
+                    }
+                    key_lens.push(end - start);
+                    start = end;
+                }
+                self.read_bytes(4 * n)?;
+                let mut keys = Vec::with_capacity(n);
+                for len in key_lens {
+                    keys.push(self.read_string(len)?);
+                }
+                let mut object = Map::new();
+                for key in keys {
+                    object.insert(key, self.read_value()?);
+                }
+                Ok(Value::Object(object))
+            }
+            _ => Err(ErrorCode::BadBytes(format!(
+                "Invalid JSONB value, unknown tag {}",
+                tag
+            ))),
+        }
+    }
+}
@@ -29,6 +29,8 @@ mod data_group_value;
 mod data_schema;
 mod data_value;
 mod data_value_operator;
+mod geometry;
+mod jsonb;
 mod scalars;
 mod struct_value;
 mod types;
@@ -42,6 +44,8 @@ pub use data_field::*;
 pub use data_schema::*;
 pub use data_value::*;
 pub use data_value_operator::*;
+pub use geometry::*;
+pub use jsonb::*;
 pub use prelude::*;
 pub use scalars::*;
 pub use struct_value::*;
@@ -75,6 +75,8 @@ pub enum DataTypeImpl {
     VariantArray(VariantArrayType),
     VariantObject(VariantObjectType),
     Interval(IntervalType),
+    Geometry(GeometryType),
+    Geography(GeographyType),
 }
 
 #[enum_dispatch]
@@ -205,6 +207,8 @@ pub fn from_arrow_type(dt: &ArrowType) -> DataTypeImpl {
 
         ArrowType::Date32 | ArrowType::Date64 => DataTypeImpl::Date(DateType::default()),
 
+        ArrowType::Dictionary(_, values, _) => from_arrow_type(values),
+
         ArrowType::Struct(fields) => {
             let names = fields.iter().map(|f| f.name.clone()).collect();
             let types = fields.iter().map(from_arrow_field).collect();
@@ -215,6 +219,8 @@ pub fn from_arrow_type(dt: &ArrowType) -> DataTypeImpl {
             "Variant" => DataTypeImpl::Variant(VariantType::default()),
             "VariantArray" => DataTypeImpl::VariantArray(VariantArrayType::default()),
             "VariantObject" => DataTypeImpl::VariantObject(VariantObjectType::default()),
+            "Geometry" => DataTypeImpl::Geometry(GeometryType::default()),
+            "Geography" => DataTypeImpl::Geography(GeographyType::default()),
             _ => unimplemented!("data_type: {:?}", dt),
         },
 
@@ -244,6 +250,8 @@ pub fn from_arrow_field(f: &ArrowField) -> DataTypeImpl {
             "Variant" => return VariantType::new_impl(),
             "VariantArray" => return VariantArrayType::new_impl(),
             "VariantObject" => return VariantObjectType::new_impl(),
+            "Geometry" => return GeometryType::new_impl(),
+            "Geography" => return GeographyType::new_impl(),
             "Tuple" => {
                 let dt = f.data_type();
                 match dt {
@@ -311,7 +319,9 @@ macro_rules! for_all_data_type_impl_enum {
             { Variant },
             { VariantArray },
             { VariantObject },
-            { Interval }
+            { Interval },
+            { Geometry },
+            { Geography }
         }
     };
 }
@@ -383,6 +393,8 @@ impl Display for DataTypeImpl {
             DataTypeImpl::Variant(_) => write!(f, "variant"),
             DataTypeImpl::VariantArray(_) => write!(f, "variant_array"),
             DataTypeImpl::VariantObject(_) => write!(f, "variant_object"),
+            DataTypeImpl::Geometry(_) => write!(f, "geometry"),
+            DataTypeImpl::Geography(_) => write!(f, "geography"),
             DataTypeImpl::Interval(_) => write!(f, "interval"),
         }
     }
@@ -0,0 +1,160 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use crate::check_geography;
+use crate::geometry_from_geojson;
+use crate::geometry_to_wkb;
+use crate::parse_geometry_to_wkb;
+use crate::prelude::*;
+
+/// Deserializer of the GEOMETRY and GEOGRAPHY values, the texts are parsed as WKT, hex-encoded
+/// WKB or GeoJSON, and stored as WKB.
+pub struct GeometryDeserializer {
+    pub buffer: Vec<u8>,
+    pub builder: MutableStringColumn,
+    pub geography: bool,
+}
+
+impl GeometryDeserializer {
+    pub fn with_capacity(capacity: usize, geography: bool) -> Self {
+        Self {
+            buffer: Vec::new(),
+            builder: MutableStringColumn::with_capacity(capacity),
+            geography,
+        }
+    }
+
+    fn append_text(&mut self, text: &[u8]) -> Result<()> {
+        let wkb = parse_geometry_to_wkb(text, self.geography)?;
+        self.builder.append_value(wkb);
+        Ok(())
+    }
+}
+
+impl TypeDeserializer for GeometryDeserializer {
+    #[allow(clippy::uninit_vec)]
+    fn de_binary(&mut self, reader: &mut &[u8], _format: &FormatSettings) -> Result<()> {
+        let offset: u64 = reader.read_uvarint()?;
+
+        self.buffer.clear();
+        self.buffer.reserve(offset as usize);
+        unsafe {
+            self.buffer.set_len(offset as usize);
+        }
+
+        reader.read_exact(&mut self.buffer)?;
+        self.builder.append_value(&self.buffer);
+        Ok(())
+    }
+
+    fn de_default(&mut self, _format: &FormatSettings) {
+        self.builder.append_value(EMPTY_GEOMETRY_WKB);
+    }
+
+    fn de_fixed_binary_batch(
+        &mut self,
+        reader: &[u8],
+        step: usize,
+        rows: usize,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        for row in 0..rows {
+            let reader = &reader[step * row..];
+            self.builder.append_value(reader);
+        }
+        Ok(())
+    }
+
+    fn de_json(&mut self, value: &serde_json::Value, _format: &FormatSettings) -> Result<()> {
+        match value {
+            serde_json::Value::String(s) => self.append_text(s.as_bytes()),
+            serde_json::Value::Object(_) => {
+                let geometry = geometry_from_geojson(value)?;
+                if self.geography {
+                    check_geography(&geometry)?;
+                }
+                let mut wkb = Vec::new();
+                geometry_to_wkb(&geometry, &mut wkb);
+                self.builder.append_value(wkb);
+                Ok(())
+            }
+            _ => Err(ErrorCode::BadBytes(
+                "Incorrect json value, must be string or GeoJSON object",
+            )),
+        }
+    }
+
+    fn de_whole_text(&mut self, reader: &[u8], _format: &FormatSettings) -> Result<()> {
+        self.append_text(reader)
+    }
+
+    fn de_text<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.clear();
+        reader.read_escaped_string_text(&mut buffer)?;
+        let result = self.append_text(&buffer);
+        self.buffer = buffer;
+        result
+    }
+
+    fn de_text_quoted<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.clear();
+        reader.read_quoted_text(&mut buffer, b'\'')?;
+        let result = self.append_text(&buffer);
+        self.buffer = buffer;
+        result
+    }
+
+    fn de_text_csv<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        settings: &FormatSettings,
+    ) -> Result<()> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.clear();
+        reader.read_csv_string(&mut buffer, settings)?;
+        let result = self.append_text(&buffer);
+        self.buffer = buffer;
+        result
+    }
+
+    fn append_data_value(&mut self, value: DataValue, _format: &FormatSettings) -> Result<()> {
+        // The values of the literals are texts, which are parsed like the text formats.
+        let text = value.as_string()?;
+        self.append_text(&text)
+    }
+
+    fn pop_data_value(&mut self) -> Result<DataValue> {
+        self.builder.pop_data_value()
+    }
+
+    fn finish_to_column(&mut self) -> ColumnRef {
+        self.builder.to_column()
+    }
+}
@@ -22,6 +22,7 @@ use crate::prelude::*;
 mod array;
 mod boolean;
 mod date;
+mod geometry;
 mod null;
 mod nullable;
 mod number;
@@ -33,6 +34,7 @@ mod variant;
 pub use array::*;
 pub use boolean::*;
 pub use date::*;
+pub use geometry::*;
 pub use null::*;
 pub use nullable::*;
 pub use number::*;
@@ -124,4 +126,5 @@ pub enum TypeDeserializerImpl {
     String(StringDeserializer),
     Struct(StructDeserializer),
     Variant(VariantDeserializer),
+    Geometry(GeometryDeserializer),
 }
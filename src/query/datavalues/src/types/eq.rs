@@ -49,9 +49,8 @@ pub fn equal(lhs: &DataTypeImpl, rhs: &DataTypeImpl) -> bool {
     use crate::prelude::TypeID::*;
     match lhs.data_type_id() {
         Boolean | UInt8 | UInt16 | UInt32 | UInt64 | Int8 | Int16 | Int32 | Int64 | Float32
-        | Float64 | String | Date | Interval | Null | Variant | VariantArray | VariantObject => {
-            true
-        }
+        | Float64 | String | Date | Interval | Null | Variant | VariantArray | VariantObject
+        | Geometry | Geography => true,
 
         Timestamp => {
             let lhs: TimestampType = lhs.to_owned().try_into().unwrap();
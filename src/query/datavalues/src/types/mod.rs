@@ -18,6 +18,8 @@ pub mod type_array;
 pub mod type_boolean;
 pub mod type_coercion;
 pub mod type_date;
+pub mod type_geography;
+pub mod type_geometry;
 pub mod type_interval;
 pub mod type_null;
 pub mod type_nullable;
@@ -49,6 +51,8 @@ pub use type_array::*;
 pub use type_boolean::*;
 pub use type_date::*;
 pub use type_factory::*;
+pub use type_geography::*;
+pub use type_geometry::*;
 pub use type_id::*;
 pub use type_interval::*;
 pub use type_null::*;
@@ -0,0 +1,111 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+use serde_json::Value;
+
+use crate::geometry_from_wkb;
+use crate::geometry_to_geojson;
+use crate::geometry_to_wkt;
+use crate::prelude::*;
+use crate::types::serializations::helper::escape::write_escaped_string;
+
+/// Serializer of the GEOMETRY and GEOGRAPHY values, which are written as WKT, or GeoJSON in the
+/// JSON objects.
+#[derive(Clone)]
+pub struct GeometrySerializer<'a> {
+    pub(crate) column: &'a StringColumn,
+}
+
+impl<'a> GeometrySerializer<'a> {
+    pub fn try_create(col: &'a ColumnRef) -> Result<Self> {
+        let column: &StringColumn = Series::check_get(col)?;
+        Ok(Self { column })
+    }
+
+    fn to_wkt(&self, row_index: usize) -> Result<String> {
+        let wkb = unsafe { self.column.value_unchecked(row_index) };
+        Ok(geometry_to_wkt(&geometry_from_wkb(wkb)?))
+    }
+}
+
+impl<'a> TypeSerializer<'a> for GeometrySerializer<'a> {
+    fn need_quote(&self) -> bool {
+        true
+    }
+
+    fn write_field(&self, row_index: usize, buf: &mut Vec<u8>, _format: &FormatSettings) {
+        // The values are always valid WKB, which are checked when they are written.
+        if let Ok(wkt) = self.to_wkt(row_index) {
+            buf.extend_from_slice(wkt.as_bytes());
+        }
+    }
+
+    fn write_field_escaped(
+        &self,
+        row_index: usize,
+        buf: &mut Vec<u8>,
+        _format: &FormatSettings,
+        quote: u8,
+    ) {
+        if let Ok(wkt) = self.to_wkt(row_index) {
+            write_escaped_string(wkt.as_bytes(), buf, quote);
+        }
+    }
+
+    fn serialize_field(&self, row_index: usize, _format: &FormatSettings) -> Result<String> {
+        self.to_wkt(row_index)
+    }
+
+    fn serialize_json_values(&self, _format: &FormatSettings) -> Result<Vec<Value>> {
+        (0..self.column.len())
+            .map(|row| Ok(Value::String(self.to_wkt(row)?)))
+            .collect()
+    }
+
+    fn serialize_json_object(
+        &self,
+        valids: Option<&Bitmap>,
+        _format: &FormatSettings,
+    ) -> Result<Vec<Value>> {
+        let mut result: Vec<Value> = Vec::with_capacity(self.column.len());
+        for (i, v) in self.column.iter().enumerate() {
+            if let Some(valids) = valids {
+                if !valids.get_bit(i) {
+                    result.push(Value::Null);
+                    continue;
+                }
+            }
+            result.push(geometry_to_geojson(&geometry_from_wkb(v)?));
+        }
+        Ok(result)
+    }
+
+    fn serialize_json_object_suppress_error(
+        &self,
+        _format: &FormatSettings,
+    ) -> Result<Vec<Option<Value>>> {
+        Ok(self
+            .column
+            .iter()
+            .map(|v| {
+                geometry_from_wkb(v)
+                    .ok()
+                    .map(|geometry| geometry_to_geojson(&geometry))
+            })
+            .collect())
+    }
+}
@@ -16,6 +16,7 @@ mod array;
 mod boolean;
 mod const_;
 mod date;
+mod geometry;
 pub mod helper;
 mod null;
 mod nullable;
@@ -35,6 +36,7 @@ use common_io::prelude::FormatSettings;
 pub use const_::ConstSerializer;
 pub use date::DateSerializer;
 use enum_dispatch::enum_dispatch;
+pub use geometry::GeometrySerializer;
 pub use helper::escape::write_escaped_string;
 pub use helper::json::write_json_string;
 pub use null::NullSerializer;
@@ -140,4 +142,5 @@ pub enum TypeSerializerImpl<'a> {
     Array(ArraySerializer<'a>),
     Struct(StructSerializer<'a>),
     Variant(VariantSerializer<'a>),
+    Geometry(GeometrySerializer<'a>),
 }
@@ -52,6 +52,8 @@ static TYPE_FACTORY: Lazy<Arc<TypeFactory>> = Lazy::new(|| {
     type_factory.register(VariantType::new_impl());
     type_factory.register(VariantArrayType::new_impl());
     type_factory.register(VariantObjectType::new_impl());
+    type_factory.register(GeometryType::new_impl());
+    type_factory.register(GeographyType::new_impl());
 
     // Timestamp is a special case
     {
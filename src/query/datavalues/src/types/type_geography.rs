@@ -0,0 +1,95 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_exception::Result;
+
+use super::data_type::DataType;
+use super::data_type::ARROW_EXTENSION_NAME;
+use super::type_geometry::EMPTY_GEOMETRY_WKB;
+use super::type_id::TypeID;
+use crate::prelude::*;
+use crate::serializations::GeometrySerializer;
+use crate::serializations::TypeSerializerImpl;
+
+#[derive(Default, Clone, Hash, serde::Deserialize, serde::Serialize)]
+pub struct GeographyType {}
+
+impl GeographyType {
+    pub fn new_impl() -> DataTypeImpl {
+        DataTypeImpl::Geography(Self {})
+    }
+}
+
+impl DataType for GeographyType {
+    fn data_type_id(&self) -> TypeID {
+        TypeID::Geography
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> String {
+        "Geography".to_string()
+    }
+
+    fn default_value(&self) -> DataValue {
+        DataValue::String(EMPTY_GEOMETRY_WKB.to_vec())
+    }
+
+    fn create_constant_column(&self, data: &DataValue, size: usize) -> Result<ColumnRef> {
+        // The values are stored as WKB in the string columns.
+        StringType::new_impl().create_constant_column(data, size)
+    }
+
+    fn create_column(&self, data: &[DataValue]) -> Result<ColumnRef> {
+        StringType::new_impl().create_column(data)
+    }
+
+    fn arrow_type(&self) -> ArrowType {
+        ArrowType::Extension(
+            "Geography".to_owned(),
+            Box::new(ArrowType::LargeBinary),
+            None,
+        )
+    }
+
+    fn custom_arrow_meta(&self) -> Option<BTreeMap<String, String>> {
+        let mut mp = BTreeMap::new();
+        mp.insert(ARROW_EXTENSION_NAME.to_string(), "Geography".to_string());
+        Some(mp)
+    }
+
+    fn create_serializer_inner<'a>(&self, col: &'a ColumnRef) -> Result<TypeSerializerImpl<'a>> {
+        Ok(GeometrySerializer::try_create(col)?.into())
+    }
+
+    fn create_deserializer(&self, capacity: usize) -> TypeDeserializerImpl {
+        GeometryDeserializer::with_capacity(capacity, true).into()
+    }
+
+    fn create_mutable(&self, capacity: usize) -> Box<dyn MutableColumn> {
+        Box::new(MutableStringColumn::with_capacity(capacity))
+    }
+}
+
+impl std::fmt::Debug for GeographyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -56,6 +56,14 @@ pub enum TypeID {
     Variant,
     VariantArray,
     VariantObject,
+
+    /// Geometry is a spatial object in the planar coordinates, such as a point, a line string or
+    /// a polygon, it's physical type is String, which stores the WKB (well-known binary).
+    Geometry,
+
+    /// Geography is a spatial object on the earth, whose coordinates are longitudes and
+    /// latitudes in degrees (WGS 84), it's physical type is String, which stores the WKB.
+    Geography,
 }
 
 impl TypeID {
@@ -162,7 +170,14 @@ impl TypeID {
 
     #[inline]
     pub fn is_quoted(&self) -> bool {
-        matches!(self, TypeID::String | TypeID::Date | TypeID::Timestamp)
+        matches!(
+            self,
+            TypeID::String
+                | TypeID::Date
+                | TypeID::Timestamp
+                | TypeID::Geometry
+                | TypeID::Geography
+        )
     }
 
     #[inline]
@@ -183,6 +198,11 @@ impl TypeID {
         matches!(self, TypeID::Variant | TypeID::VariantObject)
     }
 
+    #[inline]
+    pub fn is_geometry_or_geography(&self) -> bool {
+        matches!(self, TypeID::Geometry | TypeID::Geography)
+    }
+
     #[inline]
     pub fn numeric_byte_size(&self) -> Result<usize> {
         match self {
@@ -216,7 +236,7 @@ impl TypeID {
             Float32 => PhysicalTypeID::Float32,
             Float64 => PhysicalTypeID::Float64,
 
-            String => PhysicalTypeID::String,
+            String | Geometry | Geography => PhysicalTypeID::String,
             Array => PhysicalTypeID::Array,
             Struct => PhysicalTypeID::Struct,
             Variant | VariantArray | VariantObject => PhysicalTypeID::Variant,
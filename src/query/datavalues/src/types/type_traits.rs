@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::str::FromStr;
+
 use common_arrow::arrow::compute::arithmetics::basic::NativeArithmetics;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -21,6 +23,9 @@ use primitive_types::U512;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use crate::decode_jsonb;
+use crate::encode_jsonb;
+use crate::is_jsonb;
 use crate::DFTryFrom;
 use crate::DataTypeImpl;
 use crate::DataValue;
@@ -139,6 +144,13 @@ pub trait ObjectType:
     fn column_name() -> &'static str;
 
     fn memory_size(&self) -> usize;
+
+    /// Decodes a value from the bytes of an arrow array, which are either the text of the value
+    /// or its binary encoding.
+    fn decode(bytes: &[u8]) -> Result<Self>;
+
+    /// Appends the binary encoding of the value, which is how the value is stored.
+    fn encode_binary(&self, buf: &mut Vec<u8>);
 }
 
 impl ObjectType for VariantValue {
@@ -153,6 +165,19 @@ impl ObjectType for VariantValue {
     fn memory_size(&self) -> usize {
         self.calculate_memory_size()
     }
+
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        if is_jsonb(bytes) {
+            return Ok(VariantValue::from(decode_jsonb(bytes)?));
+        }
+        let text = std::str::from_utf8(bytes)
+            .map_err(|_| ErrorCode::BadBytes("Invalid VARIANT value, invalid UTF-8 text"))?;
+        VariantValue::from_str(text)
+    }
+
+    fn encode_binary(&self, buf: &mut Vec<u8>) {
+        encode_jsonb(self.as_ref(), buf)
+    }
 }
 
 pub trait LargePrimitive: Default + Sized + 'static {
@@ -27,7 +27,7 @@ use itertools::EitherOrBoth::Right;
 use itertools::Itertools;
 use serde_json::Value;
 
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 pub struct VariantValue(pub Value);
 
 impl From<Value> for VariantValue {
@@ -250,12 +250,54 @@ impl PartialOrd for VariantValue {
     }
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
+// The equality follows the ordering, so numbers are equal if they have the same value,
+// such as `1` and `1.0`, no matter how they are represented.
+impl PartialEq for VariantValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for VariantValue {}
+
 impl Hash for VariantValue {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let v = self.as_ref().to_string();
-        let u = v.as_bytes();
-        Hash::hash(&u, state);
+        hash_value(self.as_ref(), state)
+    }
+}
+
+// Hash the value consistently with the equality, numbers are compared as f64 if either of
+// them is a float, so all of them are hashed as f64.
+fn hash_value<H: std::hash::Hasher>(value: &Value, state: &mut H) {
+    match value {
+        Value::Null => 0u8.hash(state),
+        Value::Bool(v) => (1u8, v).hash(state),
+        Value::Number(v) => {
+            let n = if v.is_u64() {
+                v.as_u64().unwrap() as f64
+            } else if v.is_i64() {
+                v.as_i64().unwrap() as f64
+            } else {
+                v.as_f64().unwrap()
+            };
+            // -0.0 equals to 0.0
+            let n = if n == 0.0 { 0.0 } else { n };
+            (2u8, n.to_bits()).hash(state)
+        }
+        Value::String(v) => (3u8, v).hash(state),
+        Value::Array(values) => {
+            (4u8, values.len()).hash(state);
+            for v in values {
+                hash_value(v, state);
+            }
+        }
+        Value::Object(object) => {
+            (5u8, object.len()).hash(state);
+            for (k, v) in object {
+                k.hash(state);
+                hash_value(v, state);
+            }
+        }
     }
 }
 
@@ -0,0 +1,126 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::geometry_from_geojson;
+use common_datavalues::geometry_from_wkb;
+use common_datavalues::geometry_from_wkt;
+use common_datavalues::geometry_to_geojson;
+use common_datavalues::geometry_to_wkb;
+use common_datavalues::geometry_to_wkt;
+use common_datavalues::parse_geometry;
+use common_datavalues::parse_geometry_to_wkb;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+use serde_json::json;
+
+#[test]
+fn test_geometry_wkt_wkb() -> Result<()> {
+    let wkts = vec![
+        "POINT(1 2)",
+        "POINT(-1.5 100000)",
+        "LINESTRING(0 0,1 1,2 0)",
+        "POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,2 1,2 2,1 1))",
+        "MULTIPOINT((0 0),(1 1))",
+        "MULTILINESTRING((0 0,1 1),(2 2,3 3))",
+        "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((2 2,3 2,3 3,2 2)))",
+        "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(0 0,1 1))",
+        "LINESTRING EMPTY",
+        "GEOMETRYCOLLECTION EMPTY",
+    ];
+
+    for wkt in wkts {
+        let geometry = geometry_from_wkt(wkt)?;
+        assert_eq!(geometry_to_wkt(&geometry), wkt);
+
+        let mut wkb = Vec::new();
+        geometry_to_wkb(&geometry, &mut wkb);
+        assert_eq!(geometry_from_wkb(&wkb)?, geometry, "wkt: {}", wkt);
+        assert_eq!(parse_geometry(&wkb)?, geometry, "wkt: {}", wkt);
+
+        let geojson = geometry_to_geojson(&geometry);
+        assert_eq!(geometry_from_geojson(&geojson)?, geometry, "wkt: {}", wkt);
+    }
+
+    assert!(geometry_from_wkt("POINT EMPTY").is_err());
+    assert!(geometry_from_wkt("POINT(1)").is_err());
+    assert!(geometry_from_wkt("POINT(1 2) x").is_err());
+    assert!(geometry_from_wkt("CIRCLE(1 2)").is_err());
+    assert!(geometry_from_wkb(&[1, 1, 0, 0, 0]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_geometry_parse() -> Result<()> {
+    let point = geometry_from_wkt("POINT(1 2)")?;
+    let texts = vec![
+        " point ( 1 2 ) ",
+        "SRID=4326;POINT(1 2)",
+        "0101000000000000000000F03F0000000000000040",
+        // big endian WKB
+        "00000000013FF00000000000004000000000000000",
+        r#"{"type":"Point","coordinates":[1,2]}"#,
+        r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[1,2]},"properties":{}}"#,
+    ];
+    for text in texts {
+        assert_eq!(parse_geometry(text.as_bytes())?, point, "text: {}", text);
+    }
+
+    assert_eq!(
+        geometry_to_geojson(&geometry_from_wkt("LINESTRING(0 0,1 1)")?),
+        json!({"type":"LineString","coordinates":[[0.0,0.0],[1.0,1.0]]})
+    );
+    Ok(())
+}
+
+#[test]
+fn test_geography_check() -> Result<()> {
+    assert!(parse_geometry_to_wkb(b"POINT(-180 90)", true).is_ok());
+    assert!(parse_geometry_to_wkb(b"POINT(181 0)", true).is_err());
+    assert!(parse_geometry_to_wkb(b"LINESTRING(0 0,0 91)", true).is_err());
+    assert!(parse_geometry_to_wkb(b"LINESTRING(0 0,0 91)", false).is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_geometry_serde() -> Result<()> {
+    let format = FormatSettings::default();
+    let data_type = GeographyType::new_impl();
+    let mut deserializer = data_type.create_deserializer(3);
+    deserializer.de_whole_text(b"POINT(1 2)", &format)?;
+    deserializer.de_json(&json!({"type":"Point","coordinates":[3,4]}), &format)?;
+    deserializer.de_default(&format);
+    assert!(
+        deserializer
+            .de_whole_text(b"POINT(200 0)", &format)
+            .is_err()
+    );
+    let column = deserializer.finish_to_column();
+
+    let serializer = data_type.create_serializer(&column)?;
+    let values = (0..column.len())
+        .map(|row| serializer.serialize_field(row, &format))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(values, vec![
+        "POINT(1 2)",
+        "POINT(3 4)",
+        "GEOMETRYCOLLECTION EMPTY"
+    ]);
+    assert_eq!(serializer.serialize_json_object(None, &format)?, vec![
+        json!({"type":"Point","coordinates":[1.0,2.0]}),
+        json!({"type":"Point","coordinates":[3.0,4.0]}),
+        json!({"type":"GeometryCollection","geometries":[]}),
+    ]);
+    Ok(())
+}
@@ -13,5 +13,6 @@
 // limitations under the License.
 
 mod columns;
+mod geometry;
 mod types;
 mod variant_value;
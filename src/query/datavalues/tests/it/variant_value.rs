@@ -13,6 +13,9 @@
 // limitations under the License.
 
 use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 
 use common_datavalues::prelude::*;
 use common_exception::Result;
@@ -277,3 +280,70 @@ fn test_variant_calculate_memory_size() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_variant_value_eq_and_hash() -> Result<()> {
+    fn hash(value: &VariantValue) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let equals = vec![
+        (json!(1i64), json!(1.0f64)),
+        (json!(-3i64), json!(-3.0f64)),
+        (json!(0.0f64), json!(-0.0f64)),
+        (json!([1i64, {"a": 2u64}]), json!([1.0f64, {"a": 2.0f64}])),
+    ];
+    for (v1, v2) in equals {
+        let (v1, v2) = (VariantValue::from(v1), VariantValue::from(v2));
+        assert_eq!(v1, v2);
+        assert_eq!(hash(&v1), hash(&v2), "value: {:?}", v1);
+    }
+
+    assert_ne!(
+        VariantValue::from(json!(1i64)),
+        VariantValue::from(json!("1"))
+    );
+    assert_ne!(
+        VariantValue::from(json!(1i64)),
+        VariantValue::from(json!(1.5f64))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_variant_value_jsonb() -> Result<()> {
+    let values = vec![
+        JsonValue::Null,
+        json!(true),
+        json!(false),
+        json!(-100000i64),
+        json!(u64::MAX),
+        json!(12.34e10f64),
+        json!(""),
+        json!("unicode 字符串"),
+        json!([]),
+        json!([1i64, "2", [3.5f64], null]),
+        json!({}),
+        json!({"b":["an","array"],"a":"some string","c":{"an":"object","nested":{"x":1}}}),
+    ];
+
+    for value in values {
+        let mut buf = Vec::new();
+        encode_jsonb(&value, &mut buf);
+        assert!(is_jsonb(&buf));
+        assert_eq!(decode_jsonb(&buf)?, value);
+
+        // both the binary encoding and the text can be decoded
+        let expect = VariantValue::from(value.clone());
+        assert_eq!(VariantValue::decode(&buf)?, expect);
+        assert_eq!(VariantValue::decode(value.to_string().as_bytes())?, expect);
+    }
+
+    assert!(decode_jsonb(&[JSONB_HEADER]).is_err());
+    assert!(decode_jsonb(&[JSONB_HEADER, 0x06, 10, 0, 0, 0, b'a']).is_err());
+    assert!(decode_jsonb(&[JSONB_HEADER, 0x00, 0x00]).is_err());
+    assert!(decode_jsonb(b"null").is_err());
+    Ok(())
+}
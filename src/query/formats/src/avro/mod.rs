@@ -0,0 +1,27 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reader of avro object container files.
+//!
+//! Only the decoding with the schema written in the file is supported, with the null, deflate and
+//! snappy codecs. See https://avro.apache.org/docs/1.11.1/specification/
+
+mod reader;
+mod schema;
+mod value;
+
+pub use reader::Reader;
+pub use schema::RecordField;
+pub use schema::Schema;
+pub use value::Value;
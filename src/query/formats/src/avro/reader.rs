@@ -0,0 +1,350 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use flate2::read::DeflateDecoder;
+
+use crate::avro::Schema;
+use crate::avro::Value;
+
+const MAGIC: &[u8] = b"Obj\x01";
+const SYNC_MARKER_SIZE: usize = 16;
+
+/// Iterator over the values of an avro object container file, decoded with the schema in its
+/// header.
+///
+/// Iteration stops after the first error.
+pub struct Reader<'a> {
+    /// Blocks not read yet
+    data: &'a [u8],
+    schema: Schema,
+    codec: Codec,
+    sync_marker: &'a [u8],
+    /// Decompressed data of the current block
+    block: Vec<u8>,
+    block_pos: usize,
+    /// Number of values of the current block not decoded yet
+    block_remaining: u64,
+    failed: bool,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Result<Reader<'a>> {
+        let mut decoder = Decoder::new(data);
+        if decoder.read_fixed(MAGIC.len()).ok() != Some(MAGIC) {
+            return Err(invalid_data("not an avro object container file"));
+        }
+
+        let mut metadata = HashMap::new();
+        decoder.read_blocks(|decoder| {
+            let key = decoder.read_string()?;
+            let value = decoder.read_bytes()?;
+            metadata.insert(key, value);
+            Ok(())
+        })?;
+        let schema = match metadata.get("avro.schema") {
+            Some(schema) => Schema::parse(schema)?,
+            None => return Err(invalid_data("missing avro.schema in the header")),
+        };
+        let codec = match metadata.get("avro.codec") {
+            Some(codec) => Codec::from_name(codec)?,
+            None => Codec::Null,
+        };
+        let sync_marker = decoder.read_fixed(SYNC_MARKER_SIZE)?;
+
+        Ok(Reader {
+            data: &data[decoder.pos..],
+            schema,
+            codec,
+            sync_marker,
+            block: vec![],
+            block_pos: 0,
+            block_remaining: 0,
+            failed: false,
+        })
+    }
+
+    pub fn writer_schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn read_block(&mut self) -> Result<()> {
+        let data = self.data;
+        let mut decoder = Decoder::new(data);
+        let count = decoder.read_len()?;
+        let size = decoder.read_len()?;
+        let block = decoder.read_fixed(size)?;
+        if decoder.read_fixed(SYNC_MARKER_SIZE)? != self.sync_marker {
+            return Err(invalid_data("sync marker of block mismatch"));
+        }
+
+        self.block = self.codec.decompress(block)?;
+        if count > self.block.len() {
+            return Err(invalid_data(format!(
+                "block of {} values is too large",
+                count
+            )));
+        }
+        self.block_pos = 0;
+        self.block_remaining = count as u64;
+        self.data = &data[decoder.pos..];
+        Ok(())
+    }
+
+    fn try_next(&mut self) -> Result<Option<Value>> {
+        while self.block_remaining == 0 {
+            if self.data.is_empty() {
+                return Ok(None);
+            }
+            self.read_block()?;
+        }
+
+        let mut decoder = Decoder::new(&self.block[self.block_pos..]);
+        let value = decoder.read_value(&self.schema)?;
+        self.block_pos += decoder.pos;
+        self.block_remaining -= 1;
+        Ok(Some(value))
+    }
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        match self.try_next() {
+            Ok(value) => value.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+enum Codec {
+    Null,
+    Deflate,
+    /// Snappy compressed data, followed by the big-endian CRC32 of the decompressed data
+    Snappy,
+}
+
+impl Codec {
+    fn from_name(name: &[u8]) -> Result<Codec> {
+        match name {
+            b"null" => Ok(Codec::Null),
+            b"deflate" => Ok(Codec::Deflate),
+            b"snappy" => Ok(Codec::Snappy),
+            _ => Err(invalid_data(format!(
+                "unsupported codec {}",
+                String::from_utf8_lossy(name)
+            ))),
+        }
+    }
+
+    fn decompress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Codec::Null => Ok(data.to_vec()),
+            Codec::Deflate => {
+                let mut decompressed = vec![];
+                DeflateDecoder::new(data)
+                    .read_to_end(&mut decompressed)
+                    .map_err(|e| invalid_data(format!("fail to inflate block, {}", e)))?;
+                Ok(decompressed)
+            }
+            Codec::Snappy => {
+                if data.len() < 4 {
+                    return Err(invalid_data("snappy block is too short"));
+                }
+                let (data, checksum) = data.split_at(data.len() - 4);
+                let decompressed = snap::raw::Decoder::new()
+                    .decompress_vec(data)
+                    .map_err(|e| invalid_data(format!("fail to decompress block, {}", e)))?;
+                let checksum =
+                    u32::from_be_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]);
+                if crc32fast::hash(&decompressed) != checksum {
+                    return Err(invalid_data("checksum of snappy block mismatch"));
+                }
+                Ok(decompressed)
+            }
+        }
+    }
+}
+
+/// Decodes the binary encoding of values, failing instead of reading past the end of the data.
+struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(data: &'a [u8]) -> Decoder<'a> {
+        Decoder { data, pos: 0 }
+    }
+
+    fn read_fixed(&mut self, size: usize) -> Result<&'a [u8]> {
+        if size > self.data.len() - self.pos {
+            return Err(invalid_data("unexpected end of data"));
+        }
+        let bytes = &self.data[self.pos..self.pos + size];
+        self.pos += size;
+        Ok(bytes)
+    }
+
+    /// Zig-zag encoded variable-length long.
+    fn read_long(&mut self) -> Result<i64> {
+        let mut v = 0u64;
+        for i in 0..10 {
+            let byte = self.read_fixed(1)?[0];
+            v |= ((byte & 0x7f) as u64) << (i * 7);
+            if byte & 0x80 == 0 {
+                return Ok((v >> 1) as i64 ^ -((v & 1) as i64));
+            }
+        }
+        Err(invalid_data("variable-length long is too long"))
+    }
+
+    fn read_int(&mut self) -> Result<i32> {
+        let v = self.read_long()?;
+        i32::try_from(v).map_err(|_| invalid_data(format!("int {} is out of range", v)))
+    }
+
+    fn read_len(&mut self) -> Result<usize> {
+        let v = self.read_long()?;
+        usize::try_from(v).map_err(|_| invalid_data(format!("invalid length {}", v)))
+    }
+
+    fn read_bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.read_len()?;
+        Ok(self.read_fixed(len)?.to_vec())
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        String::from_utf8(self.read_bytes()?).map_err(|_| invalid_data("invalid utf-8 string"))
+    }
+
+    /// Arrays and maps are encoded as blocks of items, ended by an empty block.
+    fn read_blocks(&mut self, mut read_item: impl FnMut(&mut Self) -> Result<()>) -> Result<()> {
+        loop {
+            let count = self.read_long()?;
+            if count == 0 {
+                return Ok(());
+            }
+            if count < 0 {
+                // the size in bytes of the block follows a negative count
+                self.read_len()?;
+            }
+            // every item takes at least one byte, unless it is as useless as a null
+            if count.unsigned_abs() > (self.data.len() - self.pos) as u64 {
+                return Err(invalid_data(format!(
+                    "block of {} items is too large",
+                    count
+                )));
+            }
+            for _ in 0..count.unsigned_abs() {
+                read_item(self)?;
+            }
+        }
+    }
+
+    fn read_value(&mut self, schema: &Schema) -> Result<Value> {
+        let value = match schema {
+            Schema::Null => Value::Null,
+            Schema::Boolean => match self.read_fixed(1)?[0] {
+                0 => Value::Boolean(false),
+                1 => Value::Boolean(true),
+                v => return Err(invalid_data(format!("invalid boolean {}", v))),
+            },
+            Schema::Int => Value::Int(self.read_int()?),
+            Schema::Long => Value::Long(self.read_long()?),
+            Schema::Float => {
+                let bytes = self.read_fixed(4)?;
+                Value::Float(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            }
+            Schema::Double => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(self.read_fixed(8)?);
+                Value::Double(f64::from_le_bytes(bytes))
+            }
+            Schema::Bytes => Value::Bytes(self.read_bytes()?),
+            Schema::String => Value::String(self.read_string()?),
+            Schema::Array(item_schema) => {
+                let mut items = vec![];
+                self.read_blocks(|decoder| {
+                    items.push(decoder.read_value(item_schema)?);
+                    Ok(())
+                })?;
+                Value::Array(items)
+            }
+            Schema::Map(value_schema) => {
+                let mut entries = HashMap::new();
+                self.read_blocks(|decoder| {
+                    let key = decoder.read_string()?;
+                    entries.insert(key, decoder.read_value(value_schema)?);
+                    Ok(())
+                })?;
+                Value::Map(entries)
+            }
+            Schema::Union(variants) => {
+                let index = self.read_long()?;
+                let variant = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| variants.get(index))
+                    .ok_or_else(|| invalid_data(format!("invalid index {} of union", index)))?;
+                Value::Union(index as u32, Box::new(self.read_value(variant)?))
+            }
+            Schema::Record { fields, .. } => Value::Record(
+                fields
+                    .iter()
+                    .map(|field| Ok((field.name.clone(), self.read_value(&field.schema)?)))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            Schema::Enum { name, symbols } => {
+                let index = self.read_int()?;
+                match usize::try_from(index).ok().and_then(|i| symbols.get(i)) {
+                    Some(symbol) => Value::Enum(index as u32, symbol.clone()),
+                    None => {
+                        return Err(invalid_data(format!(
+                            "invalid index {} of enum {}",
+                            index, name
+                        )));
+                    }
+                }
+            }
+            Schema::Fixed { size, .. } => Value::Fixed(*size, self.read_fixed(*size)?.to_vec()),
+            Schema::Decimal { inner, .. } => match self.read_value(inner)? {
+                Value::Bytes(v) | Value::Fixed(_, v) => Value::Decimal(v),
+                v => return Err(invalid_data(format!("invalid decimal {:?}", v))),
+            },
+            Schema::Uuid => Value::Uuid(self.read_string()?),
+            Schema::Date => Value::Date(self.read_int()?),
+            Schema::TimeMillis => Value::TimeMillis(self.read_int()?),
+            Schema::TimeMicros => Value::TimeMicros(self.read_long()?),
+            Schema::TimestampMillis => Value::TimestampMillis(self.read_long()?),
+            Schema::TimestampMicros => Value::TimestampMicros(self.read_long()?),
+        };
+        Ok(value)
+    }
+}
+
+fn invalid_data(message: impl std::fmt::Display) -> ErrorCode {
+    ErrorCode::BadBytes(format!("invalid avro data: {}", message))
+}
@@ -0,0 +1,266 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Map;
+use serde_json::Value as JsonValue;
+
+/// Schema of avro values, with the references to named types resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Schema {
+    Null,
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+    Array(Box<Schema>),
+    Map(Box<Schema>),
+    Union(Vec<Schema>),
+    Record {
+        name: String,
+        fields: Vec<RecordField>,
+    },
+    Enum {
+        name: String,
+        symbols: Vec<String>,
+    },
+    Fixed {
+        name: String,
+        size: usize,
+    },
+    /// Decimal stored in bytes or fixed
+    Decimal {
+        precision: usize,
+        scale: usize,
+        inner: Box<Schema>,
+    },
+    Uuid,
+    Date,
+    TimeMillis,
+    TimeMicros,
+    TimestampMillis,
+    TimestampMicros,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordField {
+    pub name: String,
+    pub schema: Schema,
+}
+
+impl Schema {
+    pub fn parse(json: &[u8]) -> Result<Schema> {
+        let json: JsonValue = serde_json::from_slice(json)
+            .map_err(|e| invalid_schema(format!("invalid json, {}", e)))?;
+        SchemaParser::default().parse(&json, None)
+    }
+}
+
+/// Parses schemas, registering the named types to resolve the later references to them.
+#[derive(Default)]
+struct SchemaParser {
+    named: HashMap<String, Schema>,
+}
+
+impl SchemaParser {
+    fn parse(&mut self, json: &JsonValue, namespace: Option<&str>) -> Result<Schema> {
+        match json {
+            JsonValue::String(name) => self.parse_name(name, namespace),
+            JsonValue::Array(variants) => {
+                let variants = variants
+                    .iter()
+                    .map(|variant| self.parse(variant, namespace))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Schema::Union(variants))
+            }
+            JsonValue::Object(object) => self.parse_object(object, namespace),
+            _ => Err(invalid_schema(format!("unexpected {}", json))),
+        }
+    }
+
+    fn parse_name(&self, name: &str, namespace: Option<&str>) -> Result<Schema> {
+        let schema = match name {
+            "null" => Schema::Null,
+            "boolean" => Schema::Boolean,
+            "int" => Schema::Int,
+            "long" => Schema::Long,
+            "float" => Schema::Float,
+            "double" => Schema::Double,
+            "bytes" => Schema::Bytes,
+            "string" => Schema::String,
+            _ => {
+                // types are registered once their definition is complete, so recursive types are
+                // reported as unknown
+                let (full_name, _) = full_name(name, namespace);
+                match self.named.get(&full_name).or_else(|| self.named.get(name)) {
+                    Some(schema) => schema.clone(),
+                    None => return Err(invalid_schema(format!("unknown type {}", name))),
+                }
+            }
+        };
+        Ok(schema)
+    }
+
+    fn parse_object(
+        &mut self,
+        object: &Map<String, JsonValue>,
+        namespace: Option<&str>,
+    ) -> Result<Schema> {
+        let type_name = match object.get("type") {
+            Some(JsonValue::String(type_name)) => type_name.as_str(),
+            Some(json) => return self.parse(json, namespace),
+            None => return Err(invalid_schema("missing type")),
+        };
+
+        let schema = match type_name {
+            "record" | "error" => {
+                let (name, namespace) = self.parse_type_name(object, namespace)?;
+                let fields = match object.get("fields") {
+                    Some(JsonValue::Array(fields)) => fields
+                        .iter()
+                        .map(|field| self.parse_field(field, namespace.as_deref()))
+                        .collect::<Result<Vec<_>>>()?,
+                    _ => return Err(invalid_schema(format!("missing fields of {}", name))),
+                };
+                self.register(Schema::Record { name, fields })
+            }
+            "enum" => {
+                let (name, _) = self.parse_type_name(object, namespace)?;
+                let symbols = match object.get("symbols") {
+                    Some(JsonValue::Array(symbols)) => symbols
+                        .iter()
+                        .map(|symbol| match symbol {
+                            JsonValue::String(symbol) => Ok(symbol.clone()),
+                            _ => Err(invalid_schema(format!("invalid symbol of {}", name))),
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                    _ => return Err(invalid_schema(format!("missing symbols of {}", name))),
+                };
+                self.register(Schema::Enum { name, symbols })
+            }
+            "fixed" => {
+                let (name, _) = self.parse_type_name(object, namespace)?;
+                let size = match object.get("size").and_then(JsonValue::as_u64) {
+                    Some(size) => size as usize,
+                    None => return Err(invalid_schema(format!("missing size of {}", name))),
+                };
+                self.register(Schema::Fixed { name, size })
+            }
+            "array" => match object.get("items") {
+                Some(items) => Schema::Array(Box::new(self.parse(items, namespace)?)),
+                None => return Err(invalid_schema("missing items of array")),
+            },
+            "map" => match object.get("values") {
+                Some(values) => Schema::Map(Box::new(self.parse(values, namespace)?)),
+                None => return Err(invalid_schema("missing values of map")),
+            },
+            name => self.parse_name(name, namespace)?,
+        };
+        Ok(logical_type(object, schema))
+    }
+
+    fn parse_field(&mut self, json: &JsonValue, namespace: Option<&str>) -> Result<RecordField> {
+        let name = match json.get("name") {
+            Some(JsonValue::String(name)) => name.clone(),
+            _ => return Err(invalid_schema("missing name of field")),
+        };
+        let schema = match json.get("type") {
+            Some(json) => self.parse(json, namespace)?,
+            None => return Err(invalid_schema(format!("missing type of field {}", name))),
+        };
+        Ok(RecordField { name, schema })
+    }
+
+    /// Full name of a named type, and the namespace of the types defined inside it.
+    fn parse_type_name(
+        &self,
+        object: &Map<String, JsonValue>,
+        namespace: Option<&str>,
+    ) -> Result<(String, Option<String>)> {
+        let name = match object.get("name") {
+            Some(JsonValue::String(name)) => name,
+            _ => return Err(invalid_schema("missing name of named type")),
+        };
+        let namespace = match object.get("namespace") {
+            Some(JsonValue::String(namespace)) => Some(namespace.as_str()),
+            _ => namespace,
+        };
+        Ok(full_name(name, namespace))
+    }
+
+    fn register(&mut self, schema: Schema) -> Schema {
+        if let Schema::Record { name, .. }
+        | Schema::Enum { name, .. }
+        | Schema::Fixed { name, .. } = &schema
+        {
+            self.named.insert(name.clone(), schema.clone());
+        }
+        schema
+    }
+}
+
+fn full_name(name: &str, namespace: Option<&str>) -> (String, Option<String>) {
+    match name.rsplit_once('.') {
+        Some((namespace, _)) => (name.to_string(), Some(namespace.to_string())),
+        None => match namespace.filter(|namespace| !namespace.is_empty()) {
+            Some(namespace) => (
+                format!("{}.{}", namespace, name),
+                Some(namespace.to_string()),
+            ),
+            None => (name.to_string(), None),
+        },
+    }
+}
+
+/// Logical types with an invalid underlying type or attributes are ignored, as the spec requires.
+fn logical_type(object: &Map<String, JsonValue>, schema: Schema) -> Schema {
+    let logical_type = match object.get("logicalType") {
+        Some(JsonValue::String(logical_type)) => logical_type.as_str(),
+        _ => return schema,
+    };
+
+    match (logical_type, schema) {
+        ("decimal", schema @ (Schema::Bytes | Schema::Fixed { .. })) => {
+            let precision = object.get("precision").and_then(JsonValue::as_u64);
+            let scale = object.get("scale").map_or(Some(0), JsonValue::as_u64);
+            match (precision, scale) {
+                (Some(precision), Some(scale)) if precision > 0 && scale <= precision => {
+                    Schema::Decimal {
+                        precision: precision as usize,
+                        scale: scale as usize,
+                        inner: Box::new(schema),
+                    }
+                }
+                _ => schema,
+            }
+        }
+        ("uuid", Schema::String) => Schema::Uuid,
+        ("date", Schema::Int) => Schema::Date,
+        ("time-millis", Schema::Int) => Schema::TimeMillis,
+        ("time-micros", Schema::Long) => Schema::TimeMicros,
+        ("timestamp-millis", Schema::Long) => Schema::TimestampMillis,
+        ("timestamp-micros", Schema::Long) => Schema::TimestampMicros,
+        (_, schema) => schema,
+    }
+}
+
+fn invalid_schema(message: impl std::fmt::Display) -> ErrorCode {
+    ErrorCode::BadBytes(format!("invalid avro schema: {}", message))
+}
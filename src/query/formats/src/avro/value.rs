@@ -0,0 +1,90 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use serde_json::Value as JsonValue;
+
+/// Value decoded from an avro file, logical types included.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Boolean(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<Value>),
+    Map(HashMap<String, Value>),
+    /// Index of the variant in the union, and the value
+    Union(u32, Box<Value>),
+    /// Names and values of the fields, in the order of the schema
+    Record(Vec<(String, Value)>),
+    /// Index of the symbol, and the symbol
+    Enum(u32, String),
+    /// Size of the fixed, and the bytes
+    Fixed(usize, Vec<u8>),
+    /// Unscaled value of the decimal, in big-endian two's-complement
+    Decimal(Vec<u8>),
+    Uuid(String),
+    /// Days since the unix epoch
+    Date(i32),
+    TimeMillis(i32),
+    TimeMicros(i64),
+    TimestampMillis(i64),
+    TimestampMicros(i64),
+}
+
+/// Bytes are converted to arrays of numbers, and non-finite floats to nulls.
+impl From<Value> for JsonValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => JsonValue::Null,
+            Value::Boolean(v) => JsonValue::Bool(v),
+            Value::Int(v) | Value::Date(v) | Value::TimeMillis(v) => JsonValue::from(v),
+            Value::Long(v)
+            | Value::TimeMicros(v)
+            | Value::TimestampMillis(v)
+            | Value::TimestampMicros(v) => JsonValue::from(v),
+            Value::Float(v) => float_to_json(v as f64),
+            Value::Double(v) => float_to_json(v),
+            Value::Bytes(v) | Value::Fixed(_, v) | Value::Decimal(v) => {
+                JsonValue::Array(v.into_iter().map(JsonValue::from).collect())
+            }
+            Value::String(v) | Value::Enum(_, v) | Value::Uuid(v) => JsonValue::String(v),
+            Value::Array(items) => {
+                JsonValue::Array(items.into_iter().map(JsonValue::from).collect())
+            }
+            Value::Map(entries) => JsonValue::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, JsonValue::from(value)))
+                    .collect(),
+            ),
+            Value::Record(fields) => JsonValue::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, JsonValue::from(value)))
+                    .collect(),
+            ),
+            Value::Union(_, value) => JsonValue::from(*value),
+        }
+    }
+}
+
+fn float_to_json(v: f64) -> JsonValue {
+    serde_json::Number::from_f64(v).map_or(JsonValue::Null, JsonValue::Number)
+}
@@ -12,9 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod avro;
 mod format_diagnostic;
 mod format_factory;
 pub mod output_format;
+mod output_format_arrow;
 pub mod output_format_csv;
 mod output_format_json_each_row;
 mod output_format_parquet;
@@ -22,6 +22,7 @@ use common_exception::Result;
 use common_io::prelude::FormatSettings;
 use strum_macros::EnumIter;
 
+use crate::output_format_arrow::ArrowStreamOutputFormat;
 use crate::output_format_csv::CSVOutputFormat;
 use crate::output_format_csv::CSVWithNamesAndTypesOutputFormat;
 use crate::output_format_csv::CSVWithNamesOutputFormat;
@@ -76,6 +77,7 @@ pub enum OutputFormatType {
     TSVWithNames,
     TSVWithNamesAndTypes,
     Parquet,
+    ArrowStream,
     JsonEachRow,
     JsonStringsEachRow,
     JsonCompactEachRow,
@@ -140,6 +142,7 @@ impl OutputFormatType {
                 "text/csv; charset=UTF-8; header=present"
             }
             OutputFormatType::Parquet => "application/octet-stream",
+            OutputFormatType::ArrowStream => "application/vnd.apache.arrow.stream",
             OutputFormatType::JsonEachRow
             | OutputFormatType::JsonStringsEachRow
             | OutputFormatType::JsonCompactEachRow
@@ -180,6 +183,9 @@ impl OutputFormatType {
             OutputFormatType::Parquet => {
                 Box::new(ParquetOutputFormat::create(schema, format_setting))
             }
+            OutputFormatType::ArrowStream => {
+                Box::new(ArrowStreamOutputFormat::create(schema, format_setting))
+            }
             OutputFormatType::JsonEachRow => {
                 Box::new(JsonEachRowOutputFormat::create(schema, format_setting))
             }
@@ -0,0 +1,68 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow::io::ipc::write::default_ipc_fields;
+use common_arrow::arrow::io::ipc::write::StreamWriter;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_arrow::arrow::io::ipc::IpcField;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+
+use crate::output_format::OutputFormat;
+
+/// The Arrow IPC streaming format: the schema, a record batch for each block, and the end of
+/// the stream.
+pub struct ArrowStreamOutputFormat {
+    schema: ArrowSchema,
+    ipc_fields: Vec<IpcField>,
+}
+
+impl ArrowStreamOutputFormat {
+    pub fn create(schema: DataSchemaRef, _format_setting: FormatSettings) -> Self {
+        let schema = schema.to_arrow();
+        let ipc_fields = default_ipc_fields(&schema.fields);
+        Self { schema, ipc_fields }
+    }
+
+    // Each message is written by a writer of its own, the messages share no state since the
+    // columns are never dictionary encoded.
+    fn writer(buf: &mut Vec<u8>) -> StreamWriter<&mut Vec<u8>> {
+        StreamWriter::new(buf, WriteOptions { compression: None })
+    }
+}
+
+impl OutputFormat for ArrowStreamOutputFormat {
+    fn serialize_prefix(&self) -> Result<Vec<u8>> {
+        let mut buf = vec![];
+        Self::writer(&mut buf).start(&self.schema, Some(self.ipc_fields.clone()))?;
+        Ok(buf)
+    }
+
+    fn serialize_block(&mut self, block: &DataBlock) -> Result<Vec<u8>> {
+        let chunk = Chunk::try_from(block.clone())?;
+        let mut buf = vec![];
+        Self::writer(&mut buf).write(&chunk, Some(&self.ipc_fields))?;
+        Ok(buf)
+    }
+
+    fn finalize(&mut self) -> Result<Vec<u8>> {
+        let mut buf = vec![];
+        Self::writer(&mut buf).finish()?;
+        Ok(buf)
+    }
+}
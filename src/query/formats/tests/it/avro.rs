@@ -0,0 +1,207 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+
+use common_exception::Result;
+use common_formats::avro::Reader;
+use common_formats::avro::Schema;
+use common_formats::avro::Value;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+const SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "item",
+    "namespace": "test",
+    "fields": [
+        {"name": "id", "type": "long"},
+        {"name": "name", "type": ["null", "string"]},
+        {"name": "tags", "type": {"type": "array", "items": "string"}},
+        {"name": "price", "type": {"type": "bytes", "logicalType": "decimal", "precision": 9, "scale": 2}},
+        {"name": "kind", "type": {"type": "enum", "name": "kind", "symbols": ["a", "b"]}},
+        {"name": "previous_kind", "type": "kind"}
+    ]
+}"#;
+
+const SYNC_MARKER: [u8; 16] = [7; 16];
+
+fn write_long(buf: &mut Vec<u8>, v: i64) {
+    let mut v = ((v << 1) ^ (v >> 63)) as u64;
+    while v > 0x7f {
+        buf.push((v & 0x7f) as u8 | 0x80);
+        v >>= 7;
+    }
+    buf.push(v as u8);
+}
+
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_long(buf, bytes.len() as i64);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_container(codec: &str, count: i64, block: &[u8]) -> Vec<u8> {
+    let mut buf = b"Obj\x01".to_vec();
+    write_long(&mut buf, 2);
+    write_bytes(&mut buf, b"avro.schema");
+    write_bytes(&mut buf, SCHEMA.as_bytes());
+    write_bytes(&mut buf, b"avro.codec");
+    write_bytes(&mut buf, codec.as_bytes());
+    write_long(&mut buf, 0);
+    buf.extend_from_slice(&SYNC_MARKER);
+
+    write_long(&mut buf, count);
+    write_bytes(&mut buf, block);
+    buf.extend_from_slice(&SYNC_MARKER);
+    buf
+}
+
+/// Two records, the second of which has a null name and no tags.
+fn records() -> Vec<u8> {
+    let mut buf = vec![];
+    write_long(&mut buf, 1);
+    write_long(&mut buf, 1);
+    write_bytes(&mut buf, b"x");
+    write_long(&mut buf, 2);
+    write_bytes(&mut buf, b"t1");
+    write_bytes(&mut buf, b"t2");
+    write_long(&mut buf, 0);
+    write_bytes(&mut buf, &[0x04, 0xd2]);
+    write_long(&mut buf, 1);
+    write_long(&mut buf, 0);
+
+    write_long(&mut buf, -2);
+    write_long(&mut buf, 0);
+    write_long(&mut buf, 0);
+    write_bytes(&mut buf, &[0xff]);
+    write_long(&mut buf, 0);
+    write_long(&mut buf, 1);
+    buf
+}
+
+fn expected_records() -> Vec<Value> {
+    vec![
+        Value::Record(vec![
+            ("id".to_string(), Value::Long(1)),
+            (
+                "name".to_string(),
+                Value::Union(1, Box::new(Value::String("x".to_string()))),
+            ),
+            (
+                "tags".to_string(),
+                Value::Array(vec![
+                    Value::String("t1".to_string()),
+                    Value::String("t2".to_string()),
+                ]),
+            ),
+            ("price".to_string(), Value::Decimal(vec![0x04, 0xd2])),
+            ("kind".to_string(), Value::Enum(1, "b".to_string())),
+            ("previous_kind".to_string(), Value::Enum(0, "a".to_string())),
+        ]),
+        Value::Record(vec![
+            ("id".to_string(), Value::Long(-2)),
+            ("name".to_string(), Value::Union(0, Box::new(Value::Null))),
+            ("tags".to_string(), Value::Array(vec![])),
+            ("price".to_string(), Value::Decimal(vec![0xff])),
+            ("kind".to_string(), Value::Enum(0, "a".to_string())),
+            ("previous_kind".to_string(), Value::Enum(1, "b".to_string())),
+        ]),
+    ]
+}
+
+fn read_all(data: &[u8]) -> Result<Vec<Value>> {
+    Reader::new(data)?.collect()
+}
+
+#[test]
+fn test_avro_reader() -> Result<()> {
+    let data = write_container("null", 2, &records());
+    let reader = Reader::new(&data)?;
+    match reader.writer_schema() {
+        Schema::Record { name, fields } => {
+            assert_eq!(name, "test.item");
+            assert_eq!(fields.len(), 6);
+            assert_eq!(fields[3].schema, Schema::Decimal {
+                precision: 9,
+                scale: 2,
+                inner: Box::new(Schema::Bytes),
+            });
+            assert_eq!(fields[4].schema, fields[5].schema);
+        }
+        schema => panic!("unexpected schema {:?}", schema),
+    }
+    assert_eq!(reader.collect::<Result<Vec<_>>>()?, expected_records());
+    Ok(())
+}
+
+#[test]
+fn test_avro_reader_deflate() -> Result<()> {
+    let mut encoder = DeflateEncoder::new(vec![], Compression::default());
+    encoder.write_all(&records())?;
+    let data = write_container("deflate", 2, &encoder.finish()?);
+    assert_eq!(read_all(&data)?, expected_records());
+    Ok(())
+}
+
+#[test]
+fn test_avro_reader_snappy() -> Result<()> {
+    let records = records();
+    let mut block = snap::raw::Encoder::new()
+        .compress_vec(&records)
+        .map_err(common_exception::ErrorCode::from_std_error)?;
+    block.extend_from_slice(&crc32fast::hash(&records).to_be_bytes());
+    let data = write_container("snappy", 2, &block);
+    assert_eq!(read_all(&data)?, expected_records());
+
+    // corrupted checksum
+    let last = block.len() - 1;
+    block[last] ^= 1;
+    let data = write_container("snappy", 2, &block);
+    assert!(read_all(&data).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_avro_reader_invalid_data() -> Result<()> {
+    // truncated files are refused, without panicking
+    let data = write_container("null", 2, &records());
+    for len in 0..data.len() {
+        if let Ok(values) = read_all(&data[..len]) {
+            assert!(values.is_empty());
+        }
+    }
+
+    // more records than the block holds
+    let data = write_container("null", 3, &records());
+    assert!(read_all(&data).is_err());
+
+    // block claiming more items than its data
+    let mut block = vec![];
+    write_long(&mut block, 1);
+    write_long(&mut block, 0);
+    write_long(&mut block, i64::MAX);
+    let data = write_container("null", 1, &block);
+    assert!(read_all(&data).is_err());
+
+    // sync marker mismatch
+    let mut data = write_container("null", 2, &records());
+    let last = data.len() - 1;
+    data[last] ^= 1;
+    assert!(read_all(&data).is_err());
+
+    // unsupported codec
+    let data = write_container("zstandard", 2, &records());
+    assert!(Reader::new(&data).is_err());
+    Ok(())
+}
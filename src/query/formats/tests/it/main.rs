@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod avro;
 mod format_factory;
+mod output_format_arrow;
 mod output_format_json_each_row;
 mod output_format_tcsv;
 mod output_format_utils;
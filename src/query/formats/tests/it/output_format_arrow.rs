@@ -0,0 +1,59 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::ipc::read::read_stream_metadata;
+use common_arrow::arrow::io::ipc::read::StreamReader;
+use common_arrow::arrow::io::ipc::read::StreamState;
+use common_exception::Result;
+use common_formats::output_format::OutputFormatType;
+use common_io::prelude::FormatSettings;
+use pretty_assertions::assert_eq;
+
+use crate::output_format_utils::get_simple_block;
+
+#[test]
+fn test_arrow_stream() -> Result<()> {
+    for is_nullable in [false, true] {
+        let block = get_simple_block(is_nullable)?;
+        let schema = block.schema().clone();
+        let fmt = OutputFormatType::ArrowStream;
+        let mut formatter = fmt.create_format(schema.clone(), FormatSettings::default());
+        let mut buffer = formatter.serialize_prefix()?;
+        buffer.extend(formatter.serialize_block(&block)?);
+        buffer.extend(formatter.serialize_block(&block)?);
+        buffer.extend(formatter.finalize()?);
+
+        let mut cursor = Cursor::new(buffer);
+        let metadata = read_stream_metadata(&mut cursor)?;
+        assert_eq!(metadata.schema, schema.to_arrow());
+
+        let expect = Chunk::try_from(block)?;
+        let mut num_chunks = 0;
+        for state in StreamReader::new(cursor, metadata, None) {
+            match state? {
+                StreamState::Some(chunk) => {
+                    assert_eq!(chunk.arrays(), expect.arrays());
+                    num_chunks += 1;
+                }
+                StreamState::Waiting => unreachable!(),
+            }
+        }
+        assert_eq!(num_chunks, 2);
+    }
+
+    Ok(())
+}
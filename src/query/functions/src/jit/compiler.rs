@@ -0,0 +1,565 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::mem::ManuallyDrop;
+use std::sync::Arc;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_datavalues::type_coercion::compare_coercion;
+use common_datavalues::with_match_primitive_type_id;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use cranelift_codegen::ir::condcodes::FloatCC;
+use cranelift_codegen::ir::condcodes::IntCC;
+use cranelift_codegen::ir::types;
+use cranelift_codegen::ir::AbiParam;
+use cranelift_codegen::ir::InstBuilder;
+use cranelift_codegen::ir::MemFlags;
+use cranelift_codegen::ir::Type;
+use cranelift_codegen::ir::Value;
+use cranelift_frontend::FunctionBuilder;
+use cranelift_frontend::FunctionBuilderContext;
+use cranelift_jit::JITBuilder;
+use cranelift_jit::JITModule;
+use cranelift_module::default_libcall_names;
+use cranelift_module::Linkage;
+use cranelift_module::Module;
+
+use super::JitExpr;
+use super::JitOperator;
+
+/// `fn(inputs, num_rows, output)`, `inputs` are the pointers to the values of the input columns.
+type CompiledFn = unsafe extern "C" fn(*const *const u8, i64, *mut u8);
+
+pub struct JitCompiler;
+
+impl JitCompiler {
+    /// Compile the expression into a function evaluating it for all the rows of its inputs.
+    ///
+    /// Returns an `UnImplement` error if some node of the expression can't be compiled.
+    pub fn compile(expr: &JitExpr) -> Result<CompiledExpr> {
+        let mut inputs = vec![];
+        collect_inputs(expr, &mut inputs)?;
+        let inputs = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, data_type)| {
+                data_type.ok_or_else(|| {
+                    ErrorCode::LogicalError(format!(
+                        "Input {} of the JIT expression is unused",
+                        index
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let return_type = expr.data_type().clone();
+        storage_type(&return_type)?;
+
+        let builder = JITBuilder::new(default_libcall_names()).map_err(jit_error)?;
+        let mut module = JITModule::new(builder);
+        let pointer_type = module.target_config().pointer_type();
+
+        let mut ctx = module.make_context();
+        let signature = &mut ctx.func.signature;
+        signature.params.push(AbiParam::new(pointer_type));
+        signature.params.push(AbiParam::new(types::I64));
+        signature.params.push(AbiParam::new(pointer_type));
+
+        let mut builder_ctx = FunctionBuilderContext::new();
+        let mut builder = FunctionBuilder::new(&mut ctx.func, &mut builder_ctx);
+        let entry = builder.create_block();
+        let header = builder.create_block();
+        let body = builder.create_block();
+        let exit = builder.create_block();
+
+        // entry: load the pointers of the inputs
+        builder.append_block_params_for_function_params(entry);
+        builder.switch_to_block(entry);
+        let params = builder.block_params(entry).to_vec();
+        let (inputs_ptr, num_rows, output_ptr) = (params[0], params[1], params[2]);
+        let input_ptrs = (0..inputs.len())
+            .map(|index| {
+                let offset = (index * pointer_type.bytes() as usize) as i32;
+                builder
+                    .ins()
+                    .load(pointer_type, MemFlags::trusted(), inputs_ptr, offset)
+            })
+            .collect::<Vec<_>>();
+        let zero = builder.ins().iconst(types::I64, 0);
+        builder.append_block_param(header, types::I64);
+        builder.ins().jump(header, &[zero]);
+
+        // header: loop while row < num_rows
+        builder.switch_to_block(header);
+        let row = builder.block_params(header)[0];
+        let in_range = builder.ins().icmp(IntCC::SignedLessThan, row, num_rows);
+        builder.ins().brz(in_range, exit, &[]);
+        builder.ins().jump(body, &[]);
+
+        // body: evaluate the expression for the row, and store the result
+        builder.switch_to_block(body);
+        let mut codegen = Codegen {
+            builder: &mut builder,
+            pointer_type,
+            input_ptrs,
+            row,
+        };
+        let value = codegen.gen(expr)?;
+        codegen.store(value, &return_type, output_ptr)?;
+        let next = builder.ins().iadd_imm(row, 1);
+        builder.ins().jump(header, &[next]);
+
+        builder.switch_to_block(exit);
+        builder.ins().return_(&[]);
+        builder.seal_all_blocks();
+        builder.finalize();
+
+        let id = module
+            .declare_function("eval", Linkage::Export, &ctx.func.signature)
+            .map_err(jit_error)?;
+        module.define_function(id, &mut ctx).map_err(jit_error)?;
+        module.clear_context(&mut ctx);
+        module.finalize_definitions();
+
+        let code = module.get_finalized_function(id);
+        // SAFETY: the function is generated with the signature of `CompiledFn`.
+        let func = unsafe { std::mem::transmute::<*const u8, CompiledFn>(code) };
+        Ok(CompiledExpr {
+            module: ManuallyDrop::new(module),
+            func,
+            inputs,
+            return_type,
+        })
+    }
+}
+
+/// The native code of a [`JitExpr`], it's freed when dropped.
+pub struct CompiledExpr {
+    module: ManuallyDrop<JITModule>,
+    func: CompiledFn,
+    inputs: Vec<DataTypeImpl>,
+    return_type: DataTypeImpl,
+}
+
+// SAFETY: the module is not changed after the function is finalized, the function only reads
+// its inputs and writes its output.
+unsafe impl Send for CompiledExpr {}
+unsafe impl Sync for CompiledExpr {}
+
+impl CompiledExpr {
+    pub fn inputs(&self) -> &[DataTypeImpl] {
+        &self.inputs
+    }
+
+    pub fn return_type(&self) -> &DataTypeImpl {
+        &self.return_type
+    }
+
+    /// Evaluate the expression for the columns of the inputs.
+    ///
+    /// Returns `None` if some column isn't a full column of its type, e.g. a constant column,
+    /// which is left to the interpreted functions.
+    pub fn eval(&self, columns: &[ColumnRef], num_rows: usize) -> Result<Option<ColumnRef>> {
+        if columns.len() != self.inputs.len() {
+            return Err(ErrorCode::LogicalError(format!(
+                "JIT expression expects {} inputs, but got {}",
+                self.inputs.len(),
+                columns.len()
+            )));
+        }
+
+        let mut input_ptrs = Vec::with_capacity(columns.len());
+        for (column, data_type) in columns.iter().zip(self.inputs.iter()) {
+            let ptr = with_match_primitive_type_id!(data_type.data_type_id(), |$T| {
+                column
+                    .as_any()
+                    .downcast_ref::<PrimitiveColumn<$T>>()
+                    .filter(|column| column.len() == num_rows)
+                    .map(|column| column.values().as_ptr() as *const u8)
+            }, {
+                None
+            });
+            match ptr {
+                Some(ptr) => input_ptrs.push(ptr),
+                None => return Ok(None),
+            }
+        }
+
+        let column = with_match_primitive_type_id!(self.return_type.data_type_id(), |$T| {
+            let mut values = Vec::<$T>::with_capacity(num_rows);
+            // SAFETY: the function writes a value of the return type for each of the `num_rows` rows.
+            unsafe {
+                (self.func)(input_ptrs.as_ptr(), num_rows as i64, values.as_mut_ptr() as *mut u8);
+                values.set_len(num_rows);
+            }
+            Arc::new(PrimitiveColumn::<$T>::new_from_vec(values)) as ColumnRef
+        }, {
+            // booleans are written as bytes of 0 or 1
+            let mut values = Vec::<u8>::with_capacity(num_rows);
+            unsafe {
+                (self.func)(input_ptrs.as_ptr(), num_rows as i64, values.as_mut_ptr());
+                values.set_len(num_rows);
+            }
+            let bitmap: Bitmap = values.iter().map(|v| *v != 0).collect();
+            Arc::new(BooleanColumn::from_arrow_data(bitmap)) as ColumnRef
+        });
+        Ok(Some(column))
+    }
+}
+
+impl Drop for CompiledExpr {
+    fn drop(&mut self) {
+        // SAFETY: the function is not called any more, and the module is not used after this.
+        unsafe {
+            let module = ManuallyDrop::take(&mut self.module);
+            module.free_memory();
+        }
+    }
+}
+
+struct Codegen<'a, 'b> {
+    builder: &'a mut FunctionBuilder<'b>,
+    pointer_type: Type,
+    input_ptrs: Vec<Value>,
+    row: Value,
+}
+
+impl<'a, 'b> Codegen<'a, 'b> {
+    /// Integers are evaluated as `I64`, extended by their signedness, booleans as `B1`.
+    fn gen(&mut self, expr: &JitExpr) -> Result<Value> {
+        match expr {
+            JitExpr::Input { index, data_type } => {
+                let storage = storage_type(data_type)?;
+                let offset = self
+                    .builder
+                    .ins()
+                    .imul_imm(self.row, storage.bytes() as i64);
+                let offset = self.cast_pointer(offset);
+                let address = self.builder.ins().iadd(self.input_ptrs[*index], offset);
+                let value = self
+                    .builder
+                    .ins()
+                    .load(storage, MemFlags::trusted(), address, 0);
+                Ok(self.extend(value, data_type))
+            }
+            JitExpr::Constant { value, data_type } => {
+                let type_id = data_type.data_type_id();
+                match value {
+                    DataValue::Boolean(v) if type_id == TypeID::Boolean => {
+                        Ok(self.builder.ins().bconst(types::B1, *v))
+                    }
+                    DataValue::Int64(v) if type_id.is_integer() => {
+                        Ok(self.builder.ins().iconst(types::I64, *v))
+                    }
+                    DataValue::UInt64(v) if type_id.is_integer() => {
+                        Ok(self.builder.ins().iconst(types::I64, *v as i64))
+                    }
+                    DataValue::Float64(v) if type_id == TypeID::Float64 => {
+                        Ok(self.builder.ins().f64const(*v))
+                    }
+                    DataValue::Float64(v) if type_id == TypeID::Float32 => {
+                        Ok(self.builder.ins().f32const(*v as f32))
+                    }
+                    _ => Err(unsupported(format!(
+                        "constant {:?} of {:?}",
+                        value, type_id
+                    ))),
+                }
+            }
+            JitExpr::Function {
+                op,
+                args,
+                return_type,
+            } => {
+                if args.len() != op.num_args() {
+                    return Err(ErrorCode::LogicalError(format!(
+                        "JIT operator {:?} expects {} arguments, but got {}",
+                        op,
+                        op.num_args(),
+                        args.len()
+                    )));
+                }
+                storage_type(return_type)?;
+                match op {
+                    JitOperator::Plus
+                    | JitOperator::Minus
+                    | JitOperator::Multiply
+                    | JitOperator::Divide => self.gen_arithmetic(*op, args, return_type),
+                    JitOperator::Eq
+                    | JitOperator::NotEq
+                    | JitOperator::Lt
+                    | JitOperator::LtEq
+                    | JitOperator::Gt
+                    | JitOperator::GtEq => self.gen_comparison(*op, args, return_type),
+                    JitOperator::And | JitOperator::Or | JitOperator::Not => {
+                        self.gen_logic(*op, args, return_type)
+                    }
+                }
+            }
+        }
+    }
+
+    /// The arguments are cast into the return type first, same as the arithmetic functions.
+    fn gen_arithmetic(
+        &mut self,
+        op: JitOperator,
+        args: &[JitExpr],
+        return_type: &DataTypeImpl,
+    ) -> Result<Value> {
+        let type_id = return_type.data_type_id();
+        if !type_id.is_numeric() || (op == JitOperator::Divide && !type_id.is_floating()) {
+            return Err(unsupported(format!("{:?} returning {:?}", op, type_id)));
+        }
+
+        let lhs = self.gen(&args[0])?;
+        let lhs = self.cast(lhs, args[0].data_type(), return_type)?;
+        let rhs = self.gen(&args[1])?;
+        let rhs = self.cast(rhs, args[1].data_type(), return_type)?;
+        let ins = self.builder.ins();
+        if type_id.is_floating() {
+            return Ok(match op {
+                JitOperator::Plus => ins.fadd(lhs, rhs),
+                JitOperator::Minus => ins.fsub(lhs, rhs),
+                JitOperator::Multiply => ins.fmul(lhs, rhs),
+                _ => ins.fdiv(lhs, rhs),
+            });
+        }
+
+        let value = match op {
+            JitOperator::Plus => ins.iadd(lhs, rhs),
+            JitOperator::Minus => ins.isub(lhs, rhs),
+            _ => ins.imul(lhs, rhs),
+        };
+        // wrap around in the width of the return type
+        self.cast(value, return_type, return_type)
+    }
+
+    /// The arguments are cast into their least super type first, same as the comparison functions.
+    fn gen_comparison(
+        &mut self,
+        op: JitOperator,
+        args: &[JitExpr],
+        return_type: &DataTypeImpl,
+    ) -> Result<Value> {
+        if return_type.data_type_id() != TypeID::Boolean {
+            return Err(unsupported(format!("{:?} returning {:?}", op, return_type)));
+        }
+        let (lhs_type, rhs_type) = (args[0].data_type(), args[1].data_type());
+        let super_type = compare_coercion(lhs_type, rhs_type)?;
+        let super_type_id = super_type.data_type_id();
+        if super_type_id.is_integer()
+            && !(fits(lhs_type, &super_type) && fits(rhs_type, &super_type))
+        {
+            return Err(unsupported(format!(
+                "{:?} of {:?} and {:?}",
+                op, lhs_type, rhs_type
+            )));
+        }
+
+        let lhs = self.gen(&args[0])?;
+        let lhs = self.cast(lhs, lhs_type, &super_type)?;
+        let rhs = self.gen(&args[1])?;
+        let rhs = self.cast(rhs, rhs_type, &super_type)?;
+        let ins = self.builder.ins();
+        if super_type_id.is_floating() {
+            let cc = match op {
+                JitOperator::Eq => FloatCC::Equal,
+                JitOperator::NotEq => FloatCC::NotEqual,
+                JitOperator::Lt => FloatCC::LessThan,
+                JitOperator::LtEq => FloatCC::LessThanOrEqual,
+                JitOperator::Gt => FloatCC::GreaterThan,
+                _ => FloatCC::GreaterThanOrEqual,
+            };
+            return Ok(ins.fcmp(cc, lhs, rhs));
+        }
+
+        let signed = super_type_id.is_signed_integer();
+        let cc = match (op, signed) {
+            (JitOperator::Eq, _) => IntCC::Equal,
+            (JitOperator::NotEq, _) => IntCC::NotEqual,
+            (JitOperator::Lt, true) => IntCC::SignedLessThan,
+            (JitOperator::Lt, false) => IntCC::UnsignedLessThan,
+            (JitOperator::LtEq, true) => IntCC::SignedLessThanOrEqual,
+            (JitOperator::LtEq, false) => IntCC::UnsignedLessThanOrEqual,
+            (JitOperator::Gt, true) => IntCC::SignedGreaterThan,
+            (JitOperator::Gt, false) => IntCC::UnsignedGreaterThan,
+            (_, true) => IntCC::SignedGreaterThanOrEqual,
+            (_, false) => IntCC::UnsignedGreaterThanOrEqual,
+        };
+        Ok(ins.icmp(cc, lhs, rhs))
+    }
+
+    fn gen_logic(
+        &mut self,
+        op: JitOperator,
+        args: &[JitExpr],
+        return_type: &DataTypeImpl,
+    ) -> Result<Value> {
+        let all_boolean = args
+            .iter()
+            .all(|arg| arg.data_type().data_type_id() == TypeID::Boolean);
+        if !all_boolean || return_type.data_type_id() != TypeID::Boolean {
+            return Err(unsupported(format!("{:?} returning {:?}", op, return_type)));
+        }
+
+        let lhs = self.gen(&args[0])?;
+        if op == JitOperator::Not {
+            return Ok(self.builder.ins().bnot(lhs));
+        }
+        let rhs = self.gen(&args[1])?;
+        Ok(match op {
+            JitOperator::And => self.builder.ins().band(lhs, rhs),
+            _ => self.builder.ins().bor(lhs, rhs),
+        })
+    }
+
+    /// Cast the value like `as`.
+    fn cast(&mut self, value: Value, from: &DataTypeImpl, to: &DataTypeImpl) -> Result<Value> {
+        let (from_id, to_id) = (from.data_type_id(), to.data_type_id());
+        let ins = self.builder.ins();
+        match (from_id.is_integer(), to_id.is_integer()) {
+            (true, true) => {
+                let storage = storage_type(to)?;
+                if storage == types::I64 {
+                    return Ok(value);
+                }
+                let value = ins.ireduce(storage, value);
+                Ok(self.extend(value, to))
+            }
+            (true, false) if to_id.is_floating() => {
+                let float = storage_type(to)?;
+                Ok(match from_id.is_signed_integer() {
+                    true => ins.fcvt_from_sint(float, value),
+                    false => ins.fcvt_from_uint(float, value),
+                })
+            }
+            (false, false) if from_id.is_floating() && to_id.is_floating() => {
+                Ok(match (from_id, to_id) {
+                    (TypeID::Float32, TypeID::Float64) => ins.fpromote(types::F64, value),
+                    (TypeID::Float64, TypeID::Float32) => ins.fdemote(types::F32, value),
+                    _ => value,
+                })
+            }
+            _ if from_id == to_id => Ok(value),
+            _ => Err(unsupported(format!("cast {:?} to {:?}", from_id, to_id))),
+        }
+    }
+
+    /// Extend an integer loaded in its storage type into `I64`.
+    fn extend(&mut self, value: Value, data_type: &DataTypeImpl) -> Value {
+        let type_id = data_type.data_type_id();
+        if !type_id.is_integer() || matches!(type_id, TypeID::Int64 | TypeID::UInt64) {
+            return value;
+        }
+        match type_id.is_signed_integer() {
+            true => self.builder.ins().sextend(types::I64, value),
+            false => self.builder.ins().uextend(types::I64, value),
+        }
+    }
+
+    fn store(&mut self, value: Value, data_type: &DataTypeImpl, output_ptr: Value) -> Result<()> {
+        let storage = storage_type(data_type)?;
+        let value = match data_type.data_type_id() {
+            TypeID::Boolean => self.builder.ins().bint(types::I8, value),
+            type_id if type_id.is_integer() && storage != types::I64 => {
+                self.builder.ins().ireduce(storage, value)
+            }
+            _ => value,
+        };
+        let offset = self
+            .builder
+            .ins()
+            .imul_imm(self.row, storage.bytes() as i64);
+        let offset = self.cast_pointer(offset);
+        let address = self.builder.ins().iadd(output_ptr, offset);
+        self.builder
+            .ins()
+            .store(MemFlags::trusted(), value, address, 0);
+        Ok(())
+    }
+
+    fn cast_pointer(&mut self, offset: Value) -> Value {
+        match self.pointer_type == types::I64 {
+            true => offset,
+            false => self.builder.ins().ireduce(self.pointer_type, offset),
+        }
+    }
+}
+
+/// The type of the values in the columns.
+fn storage_type(data_type: &DataTypeImpl) -> Result<Type> {
+    match data_type.data_type_id() {
+        TypeID::Boolean | TypeID::Int8 | TypeID::UInt8 => Ok(types::I8),
+        TypeID::Int16 | TypeID::UInt16 => Ok(types::I16),
+        TypeID::Int32 | TypeID::UInt32 => Ok(types::I32),
+        TypeID::Int64 | TypeID::UInt64 => Ok(types::I64),
+        TypeID::Float32 => Ok(types::F32),
+        TypeID::Float64 => Ok(types::F64),
+        type_id => Err(unsupported(format!("type {:?}", type_id))),
+    }
+}
+
+/// Whether all the values of the integer type can be represented by the super type.
+fn fits(data_type: &DataTypeImpl, super_type: &DataTypeImpl) -> bool {
+    let (type_id, super_type_id) = (data_type.data_type_id(), super_type.data_type_id());
+    match (
+        type_id.numeric_byte_size(),
+        super_type_id.numeric_byte_size(),
+    ) {
+        (Ok(size), Ok(super_size)) => {
+            match (
+                type_id.is_signed_integer(),
+                super_type_id.is_signed_integer(),
+            ) {
+                (false, true) => size < super_size,
+                (true, false) => false,
+                _ => size <= super_size,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn collect_inputs(expr: &JitExpr, inputs: &mut Vec<Option<DataTypeImpl>>) -> Result<()> {
+    match expr {
+        JitExpr::Input { index, data_type } => {
+            if inputs.len() <= *index {
+                inputs.resize(*index + 1, None);
+            }
+            match &inputs[*index] {
+                Some(input_type) if input_type != data_type => {
+                    return Err(ErrorCode::LogicalError(format!(
+                        "Input {} of the JIT expression is of both {:?} and {:?}",
+                        index, input_type, data_type
+                    )));
+                }
+                _ => inputs[*index] = Some(data_type.clone()),
+            }
+            Ok(())
+        }
+        JitExpr::Constant { .. } => Ok(()),
+        JitExpr::Function { args, .. } => {
+            args.iter().try_for_each(|arg| collect_inputs(arg, inputs))
+        }
+    }
+}
+
+fn unsupported(what: String) -> ErrorCode {
+    ErrorCode::UnImplement(format!("JIT doesn't support {}", what))
+}
+
+fn jit_error(e: impl std::fmt::Display) -> ErrorCode {
+    ErrorCode::LogicalError(format!("JIT error: {}", e))
+}
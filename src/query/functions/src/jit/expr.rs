@@ -0,0 +1,90 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+
+/// A scalar expression which can be compiled by [`JitCompiler`](super::JitCompiler).
+#[derive(Clone, Debug)]
+pub enum JitExpr {
+    /// The column of the input at `index`.
+    Input {
+        index: usize,
+        data_type: DataTypeImpl,
+    },
+    Constant {
+        value: DataValue,
+        data_type: DataTypeImpl,
+    },
+    Function {
+        op: JitOperator,
+        args: Vec<JitExpr>,
+        return_type: DataTypeImpl,
+    },
+}
+
+impl JitExpr {
+    pub fn data_type(&self) -> &DataTypeImpl {
+        match self {
+            JitExpr::Input { data_type, .. } => data_type,
+            JitExpr::Constant { data_type, .. } => data_type,
+            JitExpr::Function { return_type, .. } => return_type,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JitOperator {
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    And,
+    Or,
+    Not,
+}
+
+impl JitOperator {
+    /// Find the operator of the function registered with `name` in the `FunctionFactory`.
+    pub fn from_name(name: &str) -> Option<JitOperator> {
+        match name.to_lowercase().as_str() {
+            "+" | "plus" => Some(JitOperator::Plus),
+            "-" | "minus" => Some(JitOperator::Minus),
+            "*" | "multiply" => Some(JitOperator::Multiply),
+            "/" | "divide" => Some(JitOperator::Divide),
+            "=" => Some(JitOperator::Eq),
+            "!=" | "<>" => Some(JitOperator::NotEq),
+            "<" => Some(JitOperator::Lt),
+            "<=" => Some(JitOperator::LtEq),
+            ">" => Some(JitOperator::Gt),
+            ">=" => Some(JitOperator::GtEq),
+            "and" | "and_filters" => Some(JitOperator::And),
+            "or" => Some(JitOperator::Or),
+            "not" => Some(JitOperator::Not),
+            _ => None,
+        }
+    }
+
+    pub fn num_args(&self) -> usize {
+        match self {
+            JitOperator::Not => 1,
+            _ => 2,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compiles the scalar expressions over non-nullable numeric and boolean columns
+//! into native code with cranelift, a whole column at a time in a single loop.
+//!
+//! The expressions which can't be compiled are left to the interpreted functions.
+
+mod compiler;
+mod expr;
+
+pub use compiler::CompiledExpr;
+pub use compiler::JitCompiler;
+pub use expr::JitExpr;
+pub use expr::JitOperator;
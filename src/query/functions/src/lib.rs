@@ -17,6 +17,8 @@
 #![feature(portable_simd)]
 
 pub mod aggregates;
+#[cfg(feature = "jit")]
+pub mod jit;
 pub mod rdoc;
 pub mod scalars;
 pub mod window;
@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 
 use common_datavalues::prelude::*;
+use memchr::memmem;
 use regex::bytes::Regex as BytesRegex;
 
 use super::comparison::StringSearchCreator;
@@ -73,6 +74,13 @@ impl StringSearchImpl for StringSearchLike {
                 let ends_with = &rhs[1..];
                 BooleanColumn::from_iterator(lhs.scalar_iter().map(|x| op(x.ends_with(ends_with))))
             }
+            PatternType::SurroundByPercent => {
+                // fast path, can use the vectorized substring search
+                let finder = memmem::Finder::new(&rhs[1..rhs.len() - 1]);
+                BooleanColumn::from_iterator(
+                    lhs.scalar_iter().map(|x| op(finder.find(x).is_some())),
+                )
+            }
             PatternType::PatternStr => {
                 let pattern = simdutf8::basic::from_utf8(rhs)
                     .expect("Unable to convert the LIKE pattern to string: {}");
@@ -100,6 +108,8 @@ pub enum PatternType {
     StartOfPercent,
     // e.g. 'Arro%'
     EndOfPercent,
+    // e.g. '%rro%'
+    SurroundByPercent,
 }
 
 /// Check the like pattern type.
@@ -136,7 +146,10 @@ pub fn check_pattern_type(pattern: &[u8], is_pruning: bool) -> PatternType {
         match pattern[index] {
             b'_' => return PatternType::PatternStr,
             b'%' => {
-                if index == len - 1 && !start_percent {
+                if index == len - 1 {
+                    if start_percent {
+                        return PatternType::SurroundByPercent;
+                    }
                     return PatternType::EndOfPercent;
                 }
                 return PatternType::PatternStr;
@@ -0,0 +1,73 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::check_geography;
+use common_datavalues::geometry_from_wkb;
+use common_datavalues::geometry_to_geojson;
+use common_datavalues::geometry_to_wkt;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+pub fn cast_from_geometry(
+    column: &ColumnRef,
+    from_type: &DataTypeImpl,
+    data_type: &DataTypeImpl,
+) -> Result<(ColumnRef, Option<Bitmap>)> {
+    let column = Series::remove_nullable(column);
+    let wkb_column: &StringColumn = Series::check_get(&column)?;
+    let size = wkb_column.len();
+
+    match data_type.data_type_id() {
+        // geometries are formatted as WKT
+        TypeID::String => {
+            let mut builder = ColumnBuilder::<Vu8>::with_capacity(size);
+            for wkb in wkb_column.iter() {
+                let geometry = geometry_from_wkb(wkb)?;
+                builder.append(geometry_to_wkt(&geometry).as_bytes());
+            }
+            Ok((builder.build(size), None))
+        }
+        TypeID::Geometry => Ok((column.clone(), None)),
+        // the coordinates of geometries must be valid longitudes and latitudes
+        TypeID::Geography => {
+            let mut builder = NullableColumnBuilder::<Vu8>::with_capacity(size);
+            for wkb in wkb_column.iter() {
+                let geometry = geometry_from_wkb(wkb)?;
+                builder.append(wkb, check_geography(&geometry).is_ok());
+            }
+            let column = builder.build(size);
+            let nullable_column: &NullableColumn = Series::check_get(&column)?;
+            Ok((
+                nullable_column.inner().clone(),
+                Some(nullable_column.ensure_validity().clone()),
+            ))
+        }
+        // geometries are converted to GeoJSON
+        TypeID::Variant => {
+            let mut builder = ColumnBuilder::<VariantValue>::with_capacity(size);
+            for wkb in wkb_column.iter() {
+                let geometry = geometry_from_wkb(wkb)?;
+                builder.append(&VariantValue::from(geometry_to_geojson(&geometry)));
+            }
+            Ok((builder.build(size), None))
+        }
+        _ => Err(ErrorCode::BadDataValueType(format!(
+            "Cast error happens in casting from {} to {}",
+            from_type.name(),
+            data_type.name()
+        ))),
+    }
+}
@@ -18,6 +18,7 @@ use common_arrow::arrow::temporal_conversions::EPOCH_DAYS_FROM_CE;
 use common_datavalues::chrono::DateTime;
 use common_datavalues::chrono::Datelike;
 use common_datavalues::chrono::NaiveDate;
+use common_datavalues::parse_geometry_to_wkb;
 use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_io::prelude::BufferReadDateTimeExt;
@@ -87,6 +88,18 @@ pub fn cast_from_string(
             let column = builder.build(size);
             return_res(column)
         }
+        // strings are parsed as WKT, hex-encoded WKB or GeoJSON
+        id @ (TypeID::Geometry | TypeID::Geography) => {
+            let mut builder = NullableColumnBuilder::<Vu8>::with_capacity(size);
+            for v in str_column.iter() {
+                match parse_geometry_to_wkb(v, id == TypeID::Geography) {
+                    Ok(wkb) => builder.append(wkb.as_slice(), true),
+                    Err(_) => builder.append_null(),
+                }
+            }
+            let column = builder.build(size);
+            return_res(column)
+        }
         TypeID::Interval => todo!(),
         _ => arrow_cast_compute(column, from_type, data_type, cast_options, func_ctx),
     }
@@ -26,6 +26,7 @@ use common_exception::Result;
 use common_io::prelude::FormatSettings;
 
 use super::cast_from_datetimes::cast_from_date;
+use super::cast_from_geometry::cast_from_geometry;
 use super::cast_from_string::cast_from_string;
 use super::cast_from_variant::cast_from_variant;
 use crate::scalars::expressions::cast_from_datetimes::cast_from_timestamp;
@@ -100,8 +101,12 @@ pub fn cast_with_type(
     cast_options: &CastOptions,
     func_ctx: &FunctionContext,
 ) -> Result<ColumnRef> {
-    // they are pyhsically the same type
-    if &column.data_type() == target_type {
+    // they are pyhsically the same type, except the geometries which are stored as strings
+    if &column.data_type() == target_type
+        && !remove_nullable(from_type)
+            .data_type_id()
+            .is_geometry_or_geography()
+    {
         return Ok(column.clone());
     }
 
@@ -167,6 +172,9 @@ pub fn cast_with_type(
         TypeID::Variant | TypeID::VariantArray | TypeID::VariantObject => {
             cast_from_variant(column, &nonull_data_type, func_ctx)
         }
+        TypeID::Geometry | TypeID::Geography => {
+            cast_from_geometry(column, &nonull_from_type, &nonull_data_type)
+        }
         _ => arrow_cast_compute(
             column,
             &nonull_from_type,
@@ -231,8 +239,10 @@ pub fn cast_to_variant(
     column: &ColumnRef,
     from_type: &DataTypeImpl,
     data_type: &DataTypeImpl,
-    _func_ctx: &FunctionContext,
+    func_ctx: &FunctionContext,
 ) -> Result<(ColumnRef, Option<Bitmap>)> {
+    let (_, valids) = column.validity();
+    let valids = valids.cloned();
     let column = Series::remove_nullable(column);
     let size = column.len();
 
@@ -261,6 +271,28 @@ pub fn cast_to_variant(
         }
         return Ok((builder.build(size), None));
     }
+    // strings are parsed as JSON text, the same as `parse_json`
+    if from_type.data_type_id() == TypeID::String {
+        let serializer = from_type.create_serializer(&column)?;
+        let format = FormatSettings::default();
+        for v in serializer.serialize_json_object(valids.as_ref(), &format)? {
+            builder.append(&VariantValue::from(v));
+        }
+        return Ok((builder.build(size), None));
+    }
+    // dates and timestamps are kept as JSON strings of their text
+    if matches!(from_type.data_type_id(), TypeID::Date | TypeID::Timestamp) {
+        let serializer = from_type.create_serializer(&column)?;
+        let format = FormatSettings {
+            timezone: func_ctx.tz,
+            ..Default::default()
+        };
+        for row in 0..size {
+            let v = serializer.serialize_field(row, &format)?;
+            builder.append(&VariantValue::from(serde_json::Value::String(v)));
+        }
+        return Ok((builder.build(size), None));
+    }
     // other data types can't automatically casted to variant
     Err(ErrorCode::BadDataValueType(format!(
         "Expression type does not match column data type, expecting VARIANT but got {}",
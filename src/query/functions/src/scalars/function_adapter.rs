@@ -28,6 +28,7 @@ use common_datavalues::ConstColumn;
 use common_datavalues::DataField;
 use common_datavalues::DataType;
 use common_datavalues::DataTypeImpl;
+use common_datavalues::DictionaryColumn;
 use common_datavalues::NullColumn;
 use common_datavalues::NullType;
 use common_datavalues::NullableColumn;
@@ -44,6 +45,8 @@ use crate::scalars::FunctionContext;
 pub struct FunctionAdapter {
     inner: Option<Box<dyn Function>>,
     has_nullable: bool,
+    // whether the result of a row depends on the values of the row only
+    is_deterministic: bool,
 }
 
 impl FunctionAdapter {
@@ -51,6 +54,7 @@ impl FunctionAdapter {
         Box::new(Self {
             inner: Some(inner),
             has_nullable,
+            is_deterministic: false,
         })
     }
 
@@ -65,6 +69,7 @@ impl FunctionAdapter {
                 return Ok(Box::new(Self {
                     inner: None,
                     has_nullable: false,
+                    is_deterministic: false,
                 }));
             }
 
@@ -76,7 +81,24 @@ impl FunctionAdapter {
             ((desc.function_creator)(name, args)?, false)
         };
 
-        Ok(Self::create(inner, has_nullable))
+        Ok(Box::new(Self {
+            inner: Some(inner),
+            has_nullable,
+            is_deterministic: desc.features.is_deterministic,
+        }))
+    }
+
+    /// The dictionary-encoded column, if it is the only argument which is not constant.
+    fn single_dictionary(columns: &ColumnsWithField) -> Option<&DictionaryColumn> {
+        let mut dictionary = None;
+        for column in columns.iter().map(|v| v.column()) {
+            if column.is_dictionary() && dictionary.is_none() {
+                dictionary = Some(Series::check_get::<DictionaryColumn>(column).ok()?);
+            } else if !column.is_const() {
+                return None;
+            }
+        }
+        dictionary
     }
 }
 
@@ -139,6 +161,28 @@ impl Function for FunctionAdapter {
             return Ok(col);
         }
 
+        // evaluate once for each distinct value of the dictionary-encoded column
+        if self.is_deterministic && self.passthrough_constant() {
+            if let Some(dictionary) = Self::single_dictionary(columns) {
+                let values = dictionary.values();
+                let columns = columns
+                    .iter()
+                    .map(|v| {
+                        let c = v.column();
+                        let c = if c.is_dictionary() {
+                            values.clone()
+                        } else {
+                            c.slice(0, values.len())
+                        };
+                        ColumnWithField::new(c, v.field().clone())
+                    })
+                    .collect::<Vec<_>>();
+
+                let col = self.eval(func_ctx, &columns, values.len())?;
+                return dictionary.map_values(col);
+            }
+        }
+
         // nullable
         if self.has_nullable && columns.iter().any(|v| v.data_type().is_nullable()) {
             let mut validity: Option<Bitmap> = None;
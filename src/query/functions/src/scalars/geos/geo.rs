@@ -13,6 +13,21 @@
 // limitations under the License.
 
 use crate::scalars::geos::GeoToH3Function;
+use crate::scalars::geos::H3GetResolutionFunction;
+use crate::scalars::geos::H3IsValidFunction;
+use crate::scalars::geos::H3ToGeoBoundaryFunction;
+use crate::scalars::geos::H3ToGeoFunction;
+use crate::scalars::geos::StAsGeoJsonFunction;
+use crate::scalars::geos::StAsTextFunction;
+use crate::scalars::geos::StAsWkbFunction;
+use crate::scalars::geos::StContainsFunction;
+use crate::scalars::geos::StDistanceFunction;
+use crate::scalars::geos::StGeogFromFunction;
+use crate::scalars::geos::StGeomFromFunction;
+use crate::scalars::geos::StIntersectsFunction;
+use crate::scalars::geos::StMakePointFunction;
+use crate::scalars::geos::StXFunction;
+use crate::scalars::geos::StYFunction;
 use crate::FunctionFactory;
 
 #[derive(Clone)]
@@ -21,5 +36,35 @@ pub struct GeoFunction;
 impl GeoFunction {
     pub fn register(factory: &mut FunctionFactory) {
         factory.register("geo_to_h3", GeoToH3Function::desc());
+        factory.register("h3_to_geo", H3ToGeoFunction::desc());
+        factory.register("h3_to_geo_boundary", H3ToGeoBoundaryFunction::desc());
+        factory.register("h3_get_resolution", H3GetResolutionFunction::desc());
+        factory.register("h3_is_valid", H3IsValidFunction::desc());
+
+        // constructors
+        factory.register("st_geomfromtext", StGeomFromFunction::desc());
+        factory.register("st_geometryfromtext", StGeomFromFunction::desc());
+        factory.register("st_geomfromwkb", StGeomFromFunction::desc());
+        factory.register("st_geomfromgeojson", StGeomFromFunction::desc());
+        factory.register("to_geometry", StGeomFromFunction::desc());
+        factory.register("st_geogfromtext", StGeogFromFunction::desc());
+        factory.register("st_geographyfromtext", StGeogFromFunction::desc());
+        factory.register("to_geography", StGeogFromFunction::desc());
+        factory.register("st_makepoint", StMakePointFunction::desc());
+        factory.register("st_point", StMakePointFunction::desc());
+
+        // outputs
+        factory.register("st_astext", StAsTextFunction::desc());
+        factory.register("st_aswkt", StAsTextFunction::desc());
+        factory.register("st_aswkb", StAsWkbFunction::desc());
+        factory.register("st_asbinary", StAsWkbFunction::desc());
+        factory.register("st_asgeojson", StAsGeoJsonFunction::desc());
+        factory.register("st_x", StXFunction::desc());
+        factory.register("st_y", StYFunction::desc());
+
+        // relationships
+        factory.register("st_distance", StDistanceFunction::desc());
+        factory.register("st_contains", StContainsFunction::desc());
+        factory.register("st_intersects", StIntersectsFunction::desc());
     }
 }
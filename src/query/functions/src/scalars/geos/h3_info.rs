@@ -0,0 +1,111 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use h3ron::H3Cell;
+use h3ron::Index;
+
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+pub type H3IsValidFunction = H3InfoFunctionImpl<false>;
+
+pub type H3GetResolutionFunction = H3InfoFunctionImpl<true>;
+
+/// `H3_IS_VALID(h3)` returns whether a number is a valid [H3](https://eng.uber.com/h3/) cell
+/// index, and `H3_GET_RESOLUTION(h3)` returns the resolution of a cell.
+#[derive(Clone)]
+pub struct H3InfoFunctionImpl<const RESOLUTION: bool> {
+    display_name: String,
+}
+
+impl<const RESOLUTION: bool> H3InfoFunctionImpl<RESOLUTION> {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        if !args[0].data_type_id().is_unsigned_integer() {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Invalid type {} of argument {} for function '{}'. Must be UInt64",
+                args[0].data_type_id(),
+                1,
+                display_name,
+            )));
+        }
+
+        Ok(Box::new(H3InfoFunctionImpl::<RESOLUTION> {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(1))
+    }
+}
+
+impl<const RESOLUTION: bool> Function for H3InfoFunctionImpl<RESOLUTION> {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        if RESOLUTION {
+            UInt8Type::new_impl()
+        } else {
+            BooleanType::new_impl()
+        }
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let cells = (0..input_rows)
+            .map(|row| Ok(H3Cell::new(columns[0].column().get_u64(row)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        if !RESOLUTION {
+            let mut builder = ColumnBuilder::<bool>::with_capacity(input_rows);
+            cells
+                .iter()
+                .for_each(|cell| builder.append(cell.is_valid()));
+            return Ok(builder.build(input_rows));
+        }
+
+        let mut builder = ColumnBuilder::<u8>::with_capacity(input_rows);
+        for cell in cells {
+            if !cell.is_valid() {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Invalid H3 index {} for function '{}'",
+                    cell.h3index(),
+                    self.display_name.to_uppercase()
+                )));
+            }
+            builder.append(cell.resolution());
+        }
+        Ok(builder.build(input_rows))
+    }
+}
+
+impl<const RESOLUTION: bool> fmt::Display for H3InfoFunctionImpl<RESOLUTION> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
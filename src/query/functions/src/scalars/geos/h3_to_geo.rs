@@ -0,0 +1,118 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::geometry_to_wkb;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use geo_types::Geometry;
+use geo_types::Point;
+use h3ron::H3Cell;
+use h3ron::Index;
+use h3ron::ToCoordinate;
+use h3ron::ToPolygon;
+
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+pub type H3ToGeoFunction = H3ToGeoFunctionImpl<false>;
+
+pub type H3ToGeoBoundaryFunction = H3ToGeoFunctionImpl<true>;
+
+/// `H3_TO_GEO(h3)` returns the center of an [H3](https://eng.uber.com/h3/) cell as a POINT
+/// GEOGRAPHY, and `H3_TO_GEO_BOUNDARY(h3)` returns the boundary of the cell as a POLYGON.
+#[derive(Clone)]
+pub struct H3ToGeoFunctionImpl<const BOUNDARY: bool> {
+    display_name: String,
+}
+
+impl<const BOUNDARY: bool> H3ToGeoFunctionImpl<BOUNDARY> {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        if !args[0].data_type_id().is_unsigned_integer() {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Invalid type {} of argument {} for function '{}'. Must be UInt64",
+                args[0].data_type_id(),
+                1,
+                display_name,
+            )));
+        }
+
+        Ok(Box::new(H3ToGeoFunctionImpl::<BOUNDARY> {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(1))
+    }
+}
+
+impl<const BOUNDARY: bool> Function for H3ToGeoFunctionImpl<BOUNDARY> {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        GeographyType::new_impl()
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let mut builder = ColumnBuilder::<Vu8>::with_capacity(input_rows);
+        let mut wkb = Vec::new();
+        for row in 0..input_rows {
+            let h3 = columns[0].column().get_u64(row)?;
+            let cell = H3Cell::new(h3);
+            if !cell.is_valid() {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Invalid H3 index {} for function '{}'",
+                    h3,
+                    self.display_name.to_uppercase()
+                )));
+            }
+
+            // h3ron returns the coordinates as `Coordinate{x: lon, y: lat}`.
+            let geometry = if BOUNDARY {
+                let polygon = cell
+                    .to_polygon()
+                    .map_err(|e| ErrorCode::BadArguments(e.to_string()))?;
+                Geometry::Polygon(polygon)
+            } else {
+                let center = cell
+                    .to_coordinate()
+                    .map_err(|e| ErrorCode::BadArguments(e.to_string()))?;
+                Geometry::Point(Point(center))
+            };
+            wkb.clear();
+            geometry_to_wkb(&geometry, &mut wkb);
+            builder.append(wkb.as_slice());
+        }
+        Ok(builder.build(input_rows))
+    }
+}
+
+impl<const BOUNDARY: bool> fmt::Display for H3ToGeoFunctionImpl<BOUNDARY> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
@@ -14,6 +14,36 @@
 
 mod geo;
 mod geo_to_h3;
+mod h3_info;
+mod h3_to_geo;
+mod spatial;
+mod st_as;
+mod st_coordinate;
+mod st_distance;
+mod st_geom_from;
+mod st_make_point;
+mod st_predicate;
 
 pub use geo::GeoFunction;
 pub use geo_to_h3::GeoToH3Function;
+pub use h3_info::H3GetResolutionFunction;
+pub use h3_info::H3InfoFunctionImpl;
+pub use h3_info::H3IsValidFunction;
+pub use h3_to_geo::H3ToGeoBoundaryFunction;
+pub use h3_to_geo::H3ToGeoFunction;
+pub use h3_to_geo::H3ToGeoFunctionImpl;
+pub use st_as::StAsFunctionImpl;
+pub use st_as::StAsGeoJsonFunction;
+pub use st_as::StAsTextFunction;
+pub use st_as::StAsWkbFunction;
+pub use st_coordinate::StCoordinateFunctionImpl;
+pub use st_coordinate::StXFunction;
+pub use st_coordinate::StYFunction;
+pub use st_distance::StDistanceFunction;
+pub use st_geom_from::StGeogFromFunction;
+pub use st_geom_from::StGeomFromFunction;
+pub use st_geom_from::StGeomFromFunctionImpl;
+pub use st_make_point::StMakePointFunction;
+pub use st_predicate::StContainsFunction;
+pub use st_predicate::StIntersectsFunction;
+pub use st_predicate::StPredicateFunctionImpl;
@@ -0,0 +1,320 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Planar algorithms of the spatial functions, and the helpers to read their arguments.
+//!
+//! A geometry is decomposed into its points, segments and polygons. A point is treated as a
+//! segment whose ends are the same, so most of the predicates only need to handle segments.
+
+use common_datavalues::geometry_from_wkb;
+use common_datavalues::parse_geometry;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use geo_types::Coordinate;
+use geo_types::Geometry;
+use geo_types::LineString;
+use geo_types::Polygon;
+
+/// The mean radius of the earth in meters, which is used to compute the distance of geographies.
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Whether the values of the type can be used as geometries, the strings are parsed as WKT,
+/// hex-encoded WKB or GeoJSON.
+pub fn is_geometry_type(data_type: &DataTypeImpl) -> bool {
+    let data_type_id = data_type.data_type_id();
+    data_type_id.is_geometry_or_geography() || data_type_id.is_string()
+}
+
+/// Check the arguments of a function of two geometries, and returns whether they are
+/// geographies. A GEOMETRY can't be mixed with a GEOGRAPHY, while the strings are parsed as the
+/// type of the other argument.
+pub fn check_binary_geometry_args(display_name: &str, args: &[&DataTypeImpl]) -> Result<bool> {
+    let (lhs, rhs) = (args[0].data_type_id(), args[1].data_type_id());
+    let mixed = matches!(
+        (lhs, rhs),
+        (TypeID::Geometry, TypeID::Geography) | (TypeID::Geography, TypeID::Geometry)
+    );
+    if !is_geometry_type(args[0]) || !is_geometry_type(args[1]) || mixed {
+        return Err(ErrorCode::IllegalDataType(format!(
+            "Invalid argument types for function '{}': ({:?}, {:?})",
+            display_name.to_uppercase(),
+            lhs,
+            rhs
+        )));
+    }
+    Ok(lhs == TypeID::Geography || rhs == TypeID::Geography)
+}
+
+/// The geometries of a GEOMETRY, GEOGRAPHY or string column.
+pub fn geometry_values(column: &ColumnWithField) -> Result<Vec<Geometry<f64>>> {
+    let full_column = column.column().convert_full_column();
+    let string_column: &StringColumn = Series::check_get(&full_column)?;
+    if column.data_type().data_type_id().is_string() {
+        string_column.iter().map(parse_geometry).collect()
+    } else {
+        string_column.iter().map(geometry_from_wkb).collect()
+    }
+}
+
+/// The components of a geometry, which are used by the planar algorithms.
+#[derive(Default)]
+pub struct Components {
+    pub segments: Vec<(Coordinate<f64>, Coordinate<f64>)>,
+    pub polygons: Vec<Polygon<f64>>,
+}
+
+impl Components {
+    pub fn new(geometry: &Geometry<f64>) -> Self {
+        let mut components = Components::default();
+        components.add(geometry);
+        components
+    }
+
+    fn add(&mut self, geometry: &Geometry<f64>) {
+        match geometry {
+            Geometry::Point(p) => self.segments.push((p.0, p.0)),
+            Geometry::MultiPoint(mp) => mp.0.iter().for_each(|p| self.segments.push((p.0, p.0))),
+            Geometry::Line(l) => self.segments.push((l.start, l.end)),
+            Geometry::LineString(l) => self.add_line_string(l),
+            Geometry::MultiLineString(ml) => ml.0.iter().for_each(|l| self.add_line_string(l)),
+            Geometry::Polygon(p) => self.add_polygon(p.clone()),
+            Geometry::MultiPolygon(mp) => mp.0.iter().for_each(|p| self.add_polygon(p.clone())),
+            Geometry::Rect(r) => self.add_polygon(r.to_polygon()),
+            Geometry::Triangle(t) => self.add_polygon(t.to_polygon()),
+            Geometry::GeometryCollection(gc) => gc.0.iter().for_each(|g| self.add(g)),
+        }
+    }
+
+    fn add_line_string(&mut self, line_string: &LineString<f64>) {
+        match line_string.0.as_slice() {
+            [] => {}
+            [c] => self.segments.push((*c, *c)),
+            coords => coords
+                .windows(2)
+                .for_each(|w| self.segments.push((w[0], w[1]))),
+        }
+    }
+
+    // The boundaries of polygons are added as segments.
+    fn add_polygon(&mut self, polygon: Polygon<f64>) {
+        self.add_line_string(polygon.exterior());
+        polygon
+            .interiors()
+            .iter()
+            .for_each(|ring| self.add_line_string(ring));
+        self.polygons.push(polygon);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Whether a point is on the segments or in the polygons.
+    fn covers_point(&self, p: Coordinate<f64>) -> bool {
+        self.segments.iter().any(|(a, b)| on_segment(p, *a, *b))
+            || self.polygons.iter().any(|polygon| in_polygon(p, polygon))
+    }
+
+    /// Whether a point is strictly in the interior of the polygons.
+    fn interior_covers_point(&self, p: Coordinate<f64>) -> bool {
+        !self.segments.iter().any(|(a, b)| on_segment(p, *a, *b))
+            && self.polygons.iter().any(|polygon| in_polygon(p, polygon))
+    }
+}
+
+/// Whether the geometries have any point in common.
+pub fn intersects(a: &Components, b: &Components) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    let segments_intersect = a.segments.iter().any(|(p1, p2)| {
+        b.segments
+            .iter()
+            .any(|(q1, q2)| segments_intersect(*p1, *p2, *q1, *q2))
+    });
+    // Without crossing the boundaries, a component intersects the polygons of the other geometry
+    // only if it's inside them, so checking one of its points is enough.
+    segments_intersect
+        || a.segments
+            .iter()
+            .any(|(p, _)| b.polygons.iter().any(|polygon| in_polygon(*p, polygon)))
+        || b.segments
+            .iter()
+            .any(|(q, _)| a.polygons.iter().any(|polygon| in_polygon(*q, polygon)))
+}
+
+/// Whether no point of `b` lies in the exterior of `a`, and at least one point of `b` lies in the
+/// interior of `a`. The segments of `b` are checked at their ends and middle points, and must
+/// not properly cross the boundaries of `a`.
+pub fn contains(a: &Components, b: &Components) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    let mut has_interior_point = a.polygons.is_empty();
+    for (p1, p2) in &b.segments {
+        let middle = Coordinate {
+            x: (p1.x + p2.x) / 2.0,
+            y: (p1.y + p2.y) / 2.0,
+        };
+        for p in [*p1, *p2, middle] {
+            if !a.covers_point(p) {
+                return false;
+            }
+            has_interior_point = has_interior_point || a.interior_covers_point(p);
+        }
+        let crosses_boundary = !a.polygons.is_empty()
+            && a.segments
+                .iter()
+                .any(|(q1, q2)| segments_cross(*p1, *p2, *q1, *q2));
+        if crosses_boundary {
+            return false;
+        }
+    }
+    has_interior_point
+}
+
+/// The minimum planar distance between the geometries.
+pub fn distance(a: &Components, b: &Components) -> Option<f64> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    if intersects(a, b) {
+        return Some(0.0);
+    }
+    let mut min = f64::INFINITY;
+    for (p1, p2) in &a.segments {
+        for (q1, q2) in &b.segments {
+            min = min
+                .min(point_segment_distance(*p1, *q1, *q2))
+                .min(point_segment_distance(*p2, *q1, *q2))
+                .min(point_segment_distance(*q1, *p1, *p2))
+                .min(point_segment_distance(*q2, *p1, *p2));
+        }
+    }
+    Some(min)
+}
+
+/// The great-circle distance in meters between the points of longitudes and latitudes.
+pub fn haversine_distance(a: Coordinate<f64>, b: Coordinate<f64>) -> f64 {
+    let (lat1, lat2) = (a.y.to_radians(), b.y.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (b.x - a.x).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * h.sqrt().min(1.0).asin()
+}
+
+/// The coordinate of a point, other geometries are rejected.
+pub fn point_coordinate(geometry: &Geometry<f64>, func_name: &str) -> Result<Coordinate<f64>> {
+    match geometry {
+        Geometry::Point(p) => Ok(p.0),
+        _ => Err(ErrorCode::BadArguments(format!(
+            "Function '{}' only supports points",
+            func_name.to_uppercase()
+        ))),
+    }
+}
+
+// The cross product of `b - a` and `c - a`, whose sign is the orientation of `c` to `a -> b`.
+fn cross(a: Coordinate<f64>, b: Coordinate<f64>, c: Coordinate<f64>) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn on_segment(p: Coordinate<f64>, a: Coordinate<f64>, b: Coordinate<f64>) -> bool {
+    cross(a, b, p) == 0.0
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+fn segments_intersect(
+    p1: Coordinate<f64>,
+    p2: Coordinate<f64>,
+    q1: Coordinate<f64>,
+    q2: Coordinate<f64>,
+) -> bool {
+    let d1 = cross(q1, q2, p1);
+    let d2 = cross(q1, q2, p2);
+    let d3 = cross(p1, p2, q1);
+    let d4 = cross(p1, p2, q2);
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+    on_segment(p1, q1, q2)
+        || on_segment(p2, q1, q2)
+        || on_segment(q1, p1, p2)
+        || on_segment(q2, p1, p2)
+}
+
+// Whether the segments cross at a single point which is in the middle of both of them.
+fn segments_cross(
+    p1: Coordinate<f64>,
+    p2: Coordinate<f64>,
+    q1: Coordinate<f64>,
+    q2: Coordinate<f64>,
+) -> bool {
+    let d1 = cross(q1, q2, p1);
+    let d2 = cross(q1, q2, p2);
+    let d3 = cross(p1, p2, q1);
+    let d4 = cross(p1, p2, q2);
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+// Whether the point is in the polygon or on its boundaries.
+fn in_polygon(p: Coordinate<f64>, polygon: &Polygon<f64>) -> bool {
+    if !in_ring(p, polygon.exterior()) {
+        return false;
+    }
+    polygon
+        .interiors()
+        .iter()
+        .all(|ring| !in_ring(p, ring) || on_ring(p, ring))
+}
+
+fn on_ring(p: Coordinate<f64>, ring: &LineString<f64>) -> bool {
+    ring.0.windows(2).any(|w| on_segment(p, w[0], w[1]))
+}
+
+// The ray casting algorithm, the points on the boundaries are in the ring.
+fn in_ring(p: Coordinate<f64>, ring: &LineString<f64>) -> bool {
+    if on_ring(p, ring) {
+        return true;
+    }
+    let mut inside = false;
+    for w in ring.0.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        if (a.y > p.y) != (b.y > p.y) {
+            let x = a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if p.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn point_segment_distance(p: Coordinate<f64>, a: Coordinate<f64>, b: Coordinate<f64>) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len2 = dx * dx + dy * dy;
+    let t = if len2 == 0.0 {
+        0.0
+    } else {
+        (((p.x - a.x) * dx + (p.y - a.y) * dy) / len2).clamp(0.0, 1.0)
+    };
+    let (x, y) = (a.x + t * dx, a.y + t * dy);
+    ((p.x - x).powi(2) + (p.y - y).powi(2)).sqrt()
+}
@@ -0,0 +1,115 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::geometry_to_geojson;
+use common_datavalues::geometry_to_wkb;
+use common_datavalues::geometry_to_wkt;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::geos::spatial::geometry_values;
+use crate::scalars::geos::spatial::is_geometry_type;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+const FORMAT_WKT: u8 = 0;
+const FORMAT_WKB: u8 = 1;
+const FORMAT_GEOJSON: u8 = 2;
+
+pub type StAsTextFunction = StAsFunctionImpl<FORMAT_WKT>;
+
+pub type StAsWkbFunction = StAsFunctionImpl<FORMAT_WKB>;
+
+pub type StAsGeoJsonFunction = StAsFunctionImpl<FORMAT_GEOJSON>;
+
+/// `ST_ASTEXT(geometry)` formats a geometry as WKT, `ST_ASWKB(geometry)` as WKB, and
+/// `ST_ASGEOJSON(geometry)` as a GeoJSON VARIANT.
+#[derive(Clone)]
+pub struct StAsFunctionImpl<const FORMAT: u8> {
+    display_name: String,
+}
+
+impl<const FORMAT: u8> StAsFunctionImpl<FORMAT> {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        if !is_geometry_type(args[0]) {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Invalid argument types for function '{}': ({:?})",
+                display_name.to_uppercase(),
+                args[0].data_type_id()
+            )));
+        }
+
+        Ok(Box::new(StAsFunctionImpl::<FORMAT> {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(1))
+    }
+}
+
+impl<const FORMAT: u8> Function for StAsFunctionImpl<FORMAT> {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        match FORMAT {
+            FORMAT_GEOJSON => VariantType::new_impl(),
+            _ => StringType::new_impl(),
+        }
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let geometries = geometry_values(&columns[0])?;
+        if FORMAT == FORMAT_GEOJSON {
+            let mut builder = ColumnBuilder::<VariantValue>::with_capacity(input_rows);
+            for geometry in geometries {
+                builder.append(&VariantValue::from(geometry_to_geojson(&geometry)));
+            }
+            return Ok(builder.build(input_rows));
+        }
+
+        let mut builder = ColumnBuilder::<Vu8>::with_capacity(input_rows);
+        let mut wkb = Vec::new();
+        for geometry in geometries {
+            if FORMAT == FORMAT_WKT {
+                builder.append(geometry_to_wkt(&geometry).as_bytes());
+            } else {
+                wkb.clear();
+                geometry_to_wkb(&geometry, &mut wkb);
+                builder.append(wkb.as_slice());
+            }
+        }
+        Ok(builder.build(input_rows))
+    }
+}
+
+impl<const FORMAT: u8> fmt::Display for StAsFunctionImpl<FORMAT> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::geos::spatial::geometry_values;
+use crate::scalars::geos::spatial::is_geometry_type;
+use crate::scalars::geos::spatial::point_coordinate;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+pub type StXFunction = StCoordinateFunctionImpl<false>;
+
+pub type StYFunction = StCoordinateFunctionImpl<true>;
+
+/// `ST_X(point)` and `ST_Y(point)` return the coordinates of a POINT.
+#[derive(Clone)]
+pub struct StCoordinateFunctionImpl<const Y: bool> {
+    display_name: String,
+}
+
+impl<const Y: bool> StCoordinateFunctionImpl<Y> {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        if !is_geometry_type(args[0]) {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Invalid argument types for function '{}': ({:?})",
+                display_name.to_uppercase(),
+                args[0].data_type_id()
+            )));
+        }
+
+        Ok(Box::new(StCoordinateFunctionImpl::<Y> {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(1))
+    }
+}
+
+impl<const Y: bool> Function for StCoordinateFunctionImpl<Y> {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        Float64Type::new_impl()
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let mut builder = ColumnBuilder::<f64>::with_capacity(input_rows);
+        for geometry in geometry_values(&columns[0])? {
+            let c = point_coordinate(&geometry, &self.display_name)?;
+            builder.append(if Y { c.y } else { c.x });
+        }
+        Ok(builder.build(input_rows))
+    }
+}
+
+impl<const Y: bool> fmt::Display for StCoordinateFunctionImpl<Y> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::geos::spatial::check_binary_geometry_args;
+use crate::scalars::geos::spatial::distance;
+use crate::scalars::geos::spatial::geometry_values;
+use crate::scalars::geos::spatial::haversine_distance;
+use crate::scalars::geos::spatial::point_coordinate;
+use crate::scalars::geos::spatial::Components;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// `ST_DISTANCE(a, b)` returns the minimum planar distance between two GEOMETRY values, or the
+/// great-circle distance in meters between two GEOGRAPHY points. The distance to an empty
+/// geometry is NULL.
+#[derive(Clone)]
+pub struct StDistanceFunction {
+    display_name: String,
+    geography: bool,
+}
+
+impl StDistanceFunction {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        let geography = check_binary_geometry_args(display_name, args)?;
+
+        Ok(Box::new(StDistanceFunction {
+            display_name: display_name.to_string(),
+            geography,
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(2))
+    }
+}
+
+impl Function for StDistanceFunction {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        NullableType::new_impl(Float64Type::new_impl())
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let lhs = geometry_values(&columns[0])?;
+        let rhs = geometry_values(&columns[1])?;
+
+        let mut builder = NullableColumnBuilder::<f64>::with_capacity(input_rows);
+        for (a, b) in lhs.iter().zip(rhs.iter()) {
+            if self.geography {
+                let a = point_coordinate(a, &self.display_name)?;
+                let b = point_coordinate(b, &self.display_name)?;
+                builder.append(haversine_distance(a, b), true);
+                continue;
+            }
+            match distance(&Components::new(a), &Components::new(b)) {
+                Some(d) => builder.append(d, true),
+                None => builder.append_null(),
+            }
+        }
+        Ok(builder.build(input_rows))
+    }
+}
+
+impl fmt::Display for StDistanceFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
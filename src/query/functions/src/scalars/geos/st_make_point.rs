@@ -0,0 +1,89 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::geometry_to_wkb;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use geo_types::Geometry;
+use geo_types::Point;
+
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// `ST_MAKEPOINT(x, y)` makes a POINT GEOMETRY of the coordinates.
+#[derive(Clone)]
+pub struct StMakePointFunction {
+    display_name: String,
+}
+
+impl StMakePointFunction {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        if !args[0].data_type_id().is_numeric() || !args[1].data_type_id().is_numeric() {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Invalid argument types for function '{}': ({:?}, {:?})",
+                display_name.to_uppercase(),
+                args[0].data_type_id(),
+                args[1].data_type_id()
+            )));
+        }
+
+        Ok(Box::new(StMakePointFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(2))
+    }
+}
+
+impl Function for StMakePointFunction {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        GeometryType::new_impl()
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let mut builder = ColumnBuilder::<Vu8>::with_capacity(input_rows);
+        let mut wkb = Vec::new();
+        for row in 0..input_rows {
+            let x = columns[0].column().get_f64(row)?;
+            let y = columns[1].column().get_f64(row)?;
+            wkb.clear();
+            geometry_to_wkb(&Geometry::Point(Point::new(x, y)), &mut wkb);
+            builder.append(wkb.as_slice());
+        }
+        Ok(builder.build(input_rows))
+    }
+}
+
+impl fmt::Display for StMakePointFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
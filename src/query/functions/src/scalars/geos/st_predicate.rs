@@ -0,0 +1,92 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::geos::spatial::check_binary_geometry_args;
+use crate::scalars::geos::spatial::contains;
+use crate::scalars::geos::spatial::geometry_values;
+use crate::scalars::geos::spatial::intersects;
+use crate::scalars::geos::spatial::Components;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+pub type StContainsFunction = StPredicateFunctionImpl<true>;
+
+pub type StIntersectsFunction = StPredicateFunctionImpl<false>;
+
+/// `ST_CONTAINS(a, b)` returns whether no point of `b` lies in the exterior of `a` and some
+/// point of `b` lies in the interior of `a`, and `ST_INTERSECTS(a, b)` returns whether the
+/// geometries have any point in common. The coordinates of geographies are treated as planar.
+#[derive(Clone)]
+pub struct StPredicateFunctionImpl<const CONTAINS: bool> {
+    display_name: String,
+}
+
+impl<const CONTAINS: bool> StPredicateFunctionImpl<CONTAINS> {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        check_binary_geometry_args(display_name, args)?;
+
+        Ok(Box::new(StPredicateFunctionImpl::<CONTAINS> {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(2))
+    }
+}
+
+impl<const CONTAINS: bool> Function for StPredicateFunctionImpl<CONTAINS> {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        BooleanType::new_impl()
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let lhs = geometry_values(&columns[0])?;
+        let rhs = geometry_values(&columns[1])?;
+
+        let mut builder = ColumnBuilder::<bool>::with_capacity(input_rows);
+        for (a, b) in lhs.iter().zip(rhs.iter()) {
+            let (a, b) = (Components::new(a), Components::new(b));
+            if CONTAINS {
+                builder.append(contains(&a, &b));
+            } else {
+                builder.append(intersects(&a, &b));
+            }
+        }
+        Ok(builder.build(input_rows))
+    }
+}
+
+impl<const CONTAINS: bool> fmt::Display for StPredicateFunctionImpl<CONTAINS> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
@@ -23,6 +23,8 @@ use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::Parser;
 use sqlparser::tokenizer::Tokenizer;
 
+use crate::scalars::semi_structureds::json_extract::is_json_type;
+use crate::scalars::semi_structureds::json_extract::json_values;
 use crate::scalars::Function;
 use crate::scalars::FunctionContext;
 use crate::scalars::FunctionDescription;
@@ -38,7 +40,7 @@ impl GetPathFunction {
         let data_type = args[0];
         let path_type = args[1];
 
-        if !data_type.data_type_id().is_variant() || !path_type.data_type_id().is_string() {
+        if !is_json_type(data_type) || !path_type.data_type_id().is_string() {
             return Err(ErrorCode::IllegalDataType(format!(
                 "Invalid argument types for function '{}': ({:?}, {:?})",
                 display_name.to_uppercase(),
@@ -74,43 +76,23 @@ impl Function for GetPathFunction {
         input_rows: usize,
     ) -> Result<ColumnRef> {
         let path_keys = parse_path_keys(columns[1].column())?;
+        let values = json_values(&columns[0])?;
 
-        let variant_column: &VariantColumn = if columns[0].column().is_const() {
-            let const_column: &ConstColumn = Series::check_get(columns[0].column())?;
-            Series::check_get(const_column.inner())?
-        } else {
-            Series::check_get(columns[0].column())?
-        };
         let mut builder = NullableColumnBuilder::<VariantValue>::with_capacity(input_rows);
-        if columns[0].column().is_const() {
-            let value = variant_column.get_data(0);
-            for path_key in path_keys.iter() {
-                match extract_value_by_path(value.as_ref(), path_key) {
-                    Some(child_value) => {
-                        builder.append(&VariantValue::from(child_value), true);
-                    }
-                    None => builder.append_null(),
-                }
-            }
-        } else if columns[1].column().is_const() {
-            let path_key = path_keys.get(0).unwrap();
-            for value in variant_column.scalar_iter() {
-                match extract_value_by_path(value.as_ref(), path_key) {
-                    Some(child_value) => {
-                        builder.append(&VariantValue::from(child_value), true);
-                    }
-                    None => builder.append_null(),
-                }
-            }
-        } else {
-            for (i, value) in variant_column.scalar_iter().enumerate() {
-                let path_key = path_keys.get(i).unwrap();
-                match extract_value_by_path(value.as_ref(), path_key) {
-                    Some(child_value) => {
-                        builder.append(&VariantValue::from(child_value), true);
-                    }
-                    None => builder.append_null(),
+        for (i, value) in values.iter().enumerate() {
+            let path_key = if columns[1].column().is_const() {
+                &path_keys[0]
+            } else {
+                &path_keys[i]
+            };
+            match value
+                .as_ref()
+                .and_then(|v| extract_value_by_path(v, path_key))
+            {
+                Some(child_value) => {
+                    builder.append(&VariantValue::from(child_value), true);
                 }
+                None => builder.append_null(),
             }
         }
 
@@ -183,7 +165,7 @@ pub fn parse_path_keys(column: &ColumnRef) -> Result<Vec<Vec<DataValue>>> {
 
 pub fn extract_value_by_path<'a>(
     value: &'a JsonValue,
-    path_key: &'a Vec<DataValue>,
+    path_key: &[DataValue],
 ) -> Option<&'a JsonValue> {
     if path_key.is_empty() {
         return None;
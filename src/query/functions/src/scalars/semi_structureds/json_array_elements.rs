@@ -0,0 +1,106 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Value as JsonValue;
+
+use crate::scalars::semi_structureds::json_extract::is_json_type;
+use crate::scalars::semi_structureds::json_extract::json_values;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// `JSON_ARRAY_ELEMENTS(json)` expands a JSON array to an ARRAY of its VARIANT elements.
+#[derive(Clone)]
+pub struct JsonArrayElementsFunction {
+    display_name: String,
+}
+
+impl JsonArrayElementsFunction {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        if !is_json_type(args[0]) {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Invalid argument types for function '{}': ({:?})",
+                display_name.to_uppercase(),
+                args[0].data_type_id()
+            )));
+        }
+
+        Ok(Box::new(JsonArrayElementsFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(1))
+    }
+}
+
+impl Function for JsonArrayElementsFunction {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        ArrayType::new_impl(VariantType::new_impl())
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let values = json_values(&columns[0])?;
+
+        let mut elements = vec![];
+        let mut offsets: Vec<i64> = Vec::with_capacity(input_rows + 1);
+        offsets.push(0);
+        for value in values.into_iter() {
+            match value {
+                Some(JsonValue::Array(array)) => {
+                    elements.extend(array.into_iter().map(VariantValue::from));
+                }
+                // The NULL values and the invalid JSON texts have no elements.
+                None | Some(JsonValue::Null) => {}
+                Some(_) => {
+                    return Err(ErrorCode::BadDataValueType(format!(
+                        "Cannot extract elements from a non-array value in function '{}'",
+                        self.display_name.to_uppercase()
+                    )));
+                }
+            }
+            offsets.push(elements.len() as i64);
+        }
+
+        Ok(ArrayColumn::from_data(
+            ArrayType::new_impl(VariantType::new_impl()),
+            offsets.into(),
+            VariantColumn::new_from_vec(elements).arc(),
+        )
+        .arc())
+    }
+}
+
+impl fmt::Display for JsonArrayElementsFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
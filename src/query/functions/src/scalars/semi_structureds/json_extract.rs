@@ -0,0 +1,188 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Value as JsonValue;
+
+use crate::scalars::semi_structureds::get_path::extract_value_by_path;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// `JSON_EXTRACT(json, path)` extracts the value at a JSON path like `$.a.b[0]` from a VARIANT
+/// or a JSON string.
+#[derive(Clone)]
+pub struct JsonExtractFunction {
+    display_name: String,
+}
+
+impl JsonExtractFunction {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        let data_type = args[0];
+        let path_type = args[1];
+
+        if !is_json_type(data_type) || !path_type.data_type_id().is_string() {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Invalid argument types for function '{}': ({:?}, {:?})",
+                display_name.to_uppercase(),
+                data_type.data_type_id(),
+                path_type.data_type_id()
+            )));
+        }
+
+        Ok(Box::new(JsonExtractFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(2))
+    }
+}
+
+impl Function for JsonExtractFunction {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        NullableType::new_impl(VariantType::new_impl())
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let values = json_values(&columns[0])?;
+        let path_column = columns[1].column().convert_full_column();
+        let path_column: &StringColumn = Series::check_get(&path_column)?;
+
+        // The paths are usually constant, parse each of them only once.
+        let mut last_path: Option<(&[u8], Vec<DataValue>)> = None;
+        let mut builder = NullableColumnBuilder::<VariantValue>::with_capacity(input_rows);
+        for (value, path) in values.iter().zip(path_column.iter()) {
+            if !matches!(&last_path, Some((p, _)) if *p == path) {
+                last_path = Some((path, parse_json_path(path)?));
+            }
+            let path_key = &last_path.as_ref().unwrap().1;
+
+            let child_value = match value {
+                Some(value) if path_key.is_empty() => Some(value),
+                Some(value) => extract_value_by_path(value, path_key),
+                None => None,
+            };
+            match child_value {
+                Some(child_value) => builder.append(&VariantValue::from(child_value), true),
+                None => builder.append_null(),
+            }
+        }
+        Ok(builder.build(input_rows))
+    }
+}
+
+impl fmt::Display for JsonExtractFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
+
+/// Whether the values of the type can be used as JSON, which are VARIANT values or JSON strings.
+pub fn is_json_type(data_type: &DataTypeImpl) -> bool {
+    let data_type_id = data_type.data_type_id();
+    data_type_id.is_variant() || data_type_id.is_string()
+}
+
+/// The JSON values of a VARIANT or a string column, the strings are parsed as JSON text, and
+/// the invalid ones are `None`.
+pub fn json_values(column: &ColumnWithField) -> Result<Vec<Option<JsonValue>>> {
+    let full_column = column.column().convert_full_column();
+    if column.data_type().data_type_id().is_variant() {
+        let variant_column: &VariantColumn = Series::check_get(&full_column)?;
+        return Ok(variant_column
+            .iter()
+            .map(|v| Some(v.as_ref().clone()))
+            .collect());
+    }
+
+    let string_column: &StringColumn = Series::check_get(&full_column)?;
+    Ok(string_column
+        .iter()
+        .map(|v| serde_json::from_slice(v).ok())
+        .collect())
+}
+
+/// Parse a JSON path like `$.a.b[0]`, `$["a"][0]` or `a.b[0]` into the keys of the path, which
+/// are field names or array indexes. The path `$` is the whole value, whose keys are empty.
+pub fn parse_json_path(path: &[u8]) -> Result<Vec<DataValue>> {
+    let invalid_path = || {
+        ErrorCode::SyntaxException(format!(
+            "Invalid JSON path: '{}'",
+            String::from_utf8_lossy(path)
+        ))
+    };
+
+    let path = std::str::from_utf8(path)
+        .map_err(|_| invalid_path())?
+        .trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let bytes = path.as_bytes();
+
+    let mut keys = vec![];
+    let mut pos = 0;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'[' => {
+                let end = path[pos..].find(']').ok_or_else(invalid_path)? + pos;
+                let key = path[pos + 1..end].trim();
+                let quoted = key.len() >= 2
+                    && ((key.starts_with('"') && key.ends_with('"'))
+                        || (key.starts_with('\'') && key.ends_with('\'')));
+                if quoted {
+                    let name = &key[1..key.len() - 1];
+                    keys.push(DataValue::String(name.as_bytes().to_vec()));
+                } else {
+                    let index = key.parse::<u64>().map_err(|_| invalid_path())?;
+                    keys.push(DataValue::UInt64(index));
+                }
+                pos = end + 1;
+            }
+            c => {
+                // A field name, which follows a `.` unless it's the first key of the path.
+                let start = match c {
+                    b'.' => pos + 1,
+                    _ if pos == 0 => pos,
+                    _ => return Err(invalid_path()),
+                };
+                let end = path[start..]
+                    .find(|c| c == '.' || c == '[')
+                    .map_or(path.len(), |end| end + start);
+                let name = path[start..end].trim();
+                if name.is_empty() {
+                    return Err(invalid_path());
+                }
+                keys.push(DataValue::String(name.as_bytes().to_vec()));
+                pos = end;
+            }
+        }
+    }
+    Ok(keys)
+}
@@ -0,0 +1,131 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde_json::Value as JsonValue;
+
+use crate::scalars::semi_structureds::get_path::extract_value_by_path;
+use crate::scalars::semi_structureds::json_extract::is_json_type;
+use crate::scalars::semi_structureds::json_extract::json_values;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// The `->` operator, which gets the field of an object or the element of an array as VARIANT.
+pub type JsonGetFunction = JsonGetFunctionImpl<false>;
+
+/// The `->>` operator, which gets the field of an object or the element of an array as text.
+pub type JsonGetTextFunction = JsonGetFunctionImpl<true>;
+
+#[derive(Clone)]
+pub struct JsonGetFunctionImpl<const TEXT: bool> {
+    display_name: String,
+}
+
+impl<const TEXT: bool> JsonGetFunctionImpl<TEXT> {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        let data_type = args[0];
+        let key_type = args[1];
+
+        if !is_json_type(data_type)
+            || (!key_type.data_type_id().is_string() && !key_type.data_type_id().is_integer())
+        {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Invalid argument types for function '{}': ({:?}, {:?})",
+                display_name.to_uppercase(),
+                data_type.data_type_id(),
+                key_type.data_type_id()
+            )));
+        }
+
+        Ok(Box::new(JsonGetFunctionImpl::<TEXT> {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(2))
+    }
+}
+
+impl<const TEXT: bool> Function for JsonGetFunctionImpl<TEXT> {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        if TEXT {
+            NullableType::new_impl(StringType::new_impl())
+        } else {
+            NullableType::new_impl(VariantType::new_impl())
+        }
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let values = json_values(&columns[0])?;
+        let key_column = columns[1].column();
+
+        let mut child_values = Vec::with_capacity(input_rows);
+        for (i, value) in values.iter().enumerate() {
+            let key = match key_column.get(i) {
+                DataValue::Int64(index) if index >= 0 => DataValue::UInt64(index as u64),
+                key @ (DataValue::UInt64(_) | DataValue::String(_)) => key,
+                // A negative index is never in the array.
+                _ => DataValue::Null,
+            };
+            let child_value = value
+                .as_ref()
+                .and_then(|v| extract_value_by_path(v, &[key]));
+            child_values.push(child_value);
+        }
+
+        if TEXT {
+            let mut builder = NullableColumnBuilder::<Vu8>::with_capacity(input_rows);
+            for child_value in child_values {
+                match child_value {
+                    None | Some(JsonValue::Null) => builder.append_null(),
+                    Some(JsonValue::String(v)) => builder.append(v.as_bytes(), true),
+                    Some(v) => builder.append(v.to_string().as_bytes(), true),
+                }
+            }
+            Ok(builder.build(input_rows))
+        } else {
+            let mut builder = NullableColumnBuilder::<VariantValue>::with_capacity(input_rows);
+            for child_value in child_values {
+                match child_value {
+                    Some(v) => builder.append(&VariantValue::from(v), true),
+                    None => builder.append_null(),
+                }
+            }
+            Ok(builder.build(input_rows))
+        }
+    }
+}
+
+impl<const TEXT: bool> fmt::Display for JsonGetFunctionImpl<TEXT> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+use serde_json::Value as JsonValue;
+
+use crate::scalars::semi_structureds::json_extract::is_json_type;
+use crate::scalars::semi_structureds::json_extract::json_values;
+use crate::scalars::semi_structureds::json_extract::parse_json_path;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// `JSON_SET(json, path, value)` replaces the value at a JSON path, or inserts it if the last key
+/// of the path is a missing field of an object or an index past the end of an array.
+#[derive(Clone)]
+pub struct JsonSetFunction {
+    display_name: String,
+}
+
+impl JsonSetFunction {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        let data_type = args[0];
+        let path_type = args[1];
+
+        if !is_json_type(data_type) || !path_type.data_type_id().is_string() {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Invalid argument types for function '{}': ({:?}, {:?}, {:?})",
+                display_name.to_uppercase(),
+                data_type.data_type_id(),
+                path_type.data_type_id(),
+                args[2].data_type_id()
+            )));
+        }
+
+        Ok(Box::new(JsonSetFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(3))
+    }
+}
+
+impl Function for JsonSetFunction {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        NullableType::new_impl(VariantType::new_impl())
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let values = json_values(&columns[0])?;
+        let path_column = columns[1].column().convert_full_column();
+        let path_column: &StringColumn = Series::check_get(&path_column)?;
+        let new_values = to_json_values(&columns[2])?;
+
+        let mut last_path: Option<(&[u8], Vec<DataValue>)> = None;
+        let mut builder = NullableColumnBuilder::<VariantValue>::with_capacity(input_rows);
+        let iter = values.into_iter().zip(path_column.iter()).zip(new_values);
+        for ((value, path), new_value) in iter {
+            if !matches!(&last_path, Some((p, _)) if *p == path) {
+                last_path = Some((path, parse_json_path(path)?));
+            }
+            let path_key = &last_path.as_ref().unwrap().1;
+
+            match value {
+                Some(mut value) => {
+                    set_value_by_path(&mut value, path_key, new_value);
+                    builder.append(&VariantValue::from(value), true);
+                }
+                None => builder.append_null(),
+            }
+        }
+        Ok(builder.build(input_rows))
+    }
+}
+
+impl fmt::Display for JsonSetFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name.to_uppercase())
+    }
+}
+
+// The strings are set as JSON strings rather than parsed, use PARSE_JSON to set a JSON text.
+fn to_json_values(column: &ColumnWithField) -> Result<Vec<JsonValue>> {
+    let data_type = column.data_type();
+    if data_type.data_type_id().is_string() {
+        let string_column = column.column().convert_full_column();
+        let string_column: &StringColumn = Series::check_get(&string_column)?;
+        return Ok(string_column
+            .iter()
+            .map(|v| JsonValue::String(String::from_utf8_lossy(v).into_owned()))
+            .collect());
+    }
+
+    let serializer = data_type.create_serializer(column.column())?;
+    let format = FormatSettings::default();
+    serializer.serialize_json_object(None, &format)
+}
+
+fn set_value_by_path(value: &mut JsonValue, path_key: &[DataValue], new_value: JsonValue) {
+    let (last_key, parent_key) = match path_key.split_last() {
+        Some(keys) => keys,
+        None => {
+            *value = new_value;
+            return;
+        }
+    };
+
+    // Only the last key of the path can be inserted, the path is left unchanged if any of its
+    // parents is missing.
+    let mut parent = value;
+    for key in parent_key {
+        let child = match key {
+            DataValue::UInt64(index) => parent.get_mut(*index as usize),
+            DataValue::String(name) => match std::str::from_utf8(name) {
+                Ok(name) => parent.get_mut(name),
+                Err(_) => None,
+            },
+            _ => None,
+        };
+        match child {
+            Some(child) => parent = child,
+            None => return,
+        }
+    }
+
+    match (parent, last_key) {
+        (JsonValue::Object(object), DataValue::String(name)) => {
+            object.insert(String::from_utf8_lossy(name).into_owned(), new_value);
+        }
+        (JsonValue::Array(array), DataValue::UInt64(index)) => {
+            match array.get_mut(*index as usize) {
+                Some(element) => *element = new_value,
+                None => array.push(new_value),
+            }
+        }
+        _ => {}
+    }
+}
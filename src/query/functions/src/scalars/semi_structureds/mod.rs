@@ -18,7 +18,11 @@ mod as_type;
 mod check_json;
 mod get;
 mod get_path;
+mod json_array_elements;
+mod json_extract;
 mod json_extract_path_text;
+mod json_get;
+mod json_set;
 mod length;
 mod object_keys;
 mod parse_json;
@@ -31,7 +35,12 @@ pub use check_json::CheckJsonFunction;
 pub use get::GetFunction;
 pub use get::GetIgnoreCaseFunction;
 pub use get_path::GetPathFunction;
+pub use json_array_elements::JsonArrayElementsFunction;
+pub use json_extract::JsonExtractFunction;
 pub use json_extract_path_text::JsonExtractPathTextFunction;
+pub use json_get::JsonGetFunction;
+pub use json_get::JsonGetTextFunction;
+pub use json_set::JsonSetFunction;
 pub use length::VariantArrayLengthFunction;
 pub use object_keys::ObjectKeysFunction;
 pub use parse_json::ParseJsonFunction;
@@ -64,20 +64,26 @@ impl Function for ObjectKeysFunction {
         columns: &ColumnsWithField,
         input_rows: usize,
     ) -> Result<ColumnRef> {
-        let object_column: &VariantColumn = Series::check_get(columns[0].column())?;
+        let object_column = columns[0].column().convert_full_column();
+        let object_column: &VariantColumn = Series::check_get(&object_column)?;
         let mut data_column = MutableStringColumn::default();
-        let mut offsets: Vec<i64> = vec![0];
+        let mut offsets: Vec<i64> = Vec::with_capacity(input_rows + 1);
+        offsets.push(0);
         for i in 0..input_rows {
-            let object = match object_column.get_data(i).as_object() {
-                Some(x) => x,
+            let value = object_column.get_data(i);
+            match value.as_object() {
+                Some(object) => {
+                    for key in object.keys() {
+                        data_column.append_value(key);
+                    }
+                }
+                // The NULL values have no keys.
+                None if value.is_null() => {}
                 None => {
                     return Err(ErrorCode::BadDataValueType("Variant is not Object Type"));
                 }
-            };
-            for key in object.keys() {
-                data_column.append_value(key);
             }
-            offsets.push(object.keys().len() as i64);
+            offsets.push(data_column.len() as i64);
         }
 
         Ok(ArrayColumn::from_data(
@@ -18,7 +18,12 @@ use super::as_type::as_function_creator;
 use super::get::GetFunction;
 use super::get::GetIgnoreCaseFunction;
 use super::get_path::GetPathFunction;
+use super::json_array_elements::JsonArrayElementsFunction;
+use super::json_extract::JsonExtractFunction;
 use super::json_extract_path_text::JsonExtractPathTextFunction;
+use super::json_get::JsonGetFunction;
+use super::json_get::JsonGetTextFunction;
+use super::json_set::JsonSetFunction;
 use super::parse_json::ParseJsonFunction;
 use super::parse_json::TryParseJsonFunction;
 use super::ObjectKeysFunction;
@@ -45,6 +50,11 @@ impl SemiStructuredFunction {
             "json_extract_path_text",
             JsonExtractPathTextFunction::desc(),
         );
+        factory.register("json_extract", JsonExtractFunction::desc());
+        factory.register("json_array_elements", JsonArrayElementsFunction::desc());
+        factory.register("json_set", JsonSetFunction::desc());
+        factory.register("->", JsonGetFunction::desc());
+        factory.register("->>", JsonGetTextFunction::desc());
         factory.register("object_keys", ObjectKeysFunction::desc())
     }
 }
@@ -0,0 +1,351 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::assert_string;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// Splits a text into the terms indexed by the inverted indexes and searched by the full-text
+/// functions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tokenizer {
+    /// Splits on the characters other than letters and digits, the terms are lowercased.
+    #[default]
+    Standard,
+    /// Splits on the whitespaces, the terms are case sensitive.
+    Whitespace,
+}
+
+impl Tokenizer {
+    pub fn from_name(name: &str) -> Result<Tokenizer> {
+        match name.to_lowercase().as_str() {
+            "standard" => Ok(Tokenizer::Standard),
+            "whitespace" => Ok(Tokenizer::Whitespace),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Unknown tokenizer '{}', expect 'standard' or 'whitespace'",
+                name
+            ))),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tokenizer::Standard => "standard",
+            Tokenizer::Whitespace => "whitespace",
+        }
+    }
+
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        match self {
+            Tokenizer::Standard => text
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|term| !term.is_empty())
+                .map(|term| term.to_lowercase())
+                .collect(),
+            Tokenizer::Whitespace => text.split_whitespace().map(|t| t.to_string()).collect(),
+        }
+    }
+}
+
+impl fmt::Display for Tokenizer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A full-text query of terms combined with `AND`, `OR` and parentheses, the terms next to each
+/// other without an operator are combined with `AND`, e.g. `error (timeout OR refused)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextQuery {
+    Term(String),
+    And(Vec<TextQuery>),
+    Or(Vec<TextQuery>),
+}
+
+impl TextQuery {
+    /// The query matching the texts containing all the terms of `text`.
+    pub fn all_terms(text: &str, tokenizer: Tokenizer) -> TextQuery {
+        let mut terms = tokenizer
+            .tokenize(text)
+            .into_iter()
+            .map(TextQuery::Term)
+            .collect::<Vec<_>>();
+        match terms.len() {
+            1 => terms.remove(0),
+            _ => TextQuery::And(terms),
+        }
+    }
+
+    pub fn parse(query: &str, tokenizer: Tokenizer) -> Result<TextQuery> {
+        // the parentheses are words even if they are not separated by whitespaces
+        let mut words = vec![];
+        for word in query.split_whitespace() {
+            let mut start = 0;
+            for (i, c) in word.char_indices() {
+                if c == '(' || c == ')' {
+                    if start < i {
+                        words.push(&word[start..i]);
+                    }
+                    words.push(&word[i..i + 1]);
+                    start = i + 1;
+                }
+            }
+            if start < word.len() {
+                words.push(&word[start..]);
+            }
+        }
+
+        let mut parser = QueryParser {
+            query,
+            words,
+            pos: 0,
+            tokenizer,
+        };
+        let text_query = parser.parse_or()?;
+        match parser.next() {
+            None => Ok(text_query),
+            Some(word) => Err(parser.error(&format!("unexpected '{}'", word))),
+        }
+    }
+
+    /// Whether a text matches the query, `contains` tells whether the text has a term.
+    pub fn eval<F: Fn(&str) -> bool>(&self, contains: &F) -> bool {
+        match self {
+            TextQuery::Term(term) => contains(term),
+            TextQuery::And(queries) => queries.iter().all(|query| query.eval(contains)),
+            TextQuery::Or(queries) => queries.iter().any(|query| query.eval(contains)),
+        }
+    }
+}
+
+struct QueryParser<'a> {
+    query: &'a str,
+    words: Vec<&'a str>,
+    pos: usize,
+    tokenizer: Tokenizer,
+}
+
+impl<'a> QueryParser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.words.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let word = self.peek();
+        self.pos += 1;
+        word
+    }
+
+    fn error(&self, message: &str) -> ErrorCode {
+        ErrorCode::BadArguments(format!(
+            "Invalid full-text query '{}': {}",
+            self.query, message
+        ))
+    }
+
+    fn parse_or(&mut self) -> Result<TextQuery> {
+        let mut operands = vec![self.parse_and()?];
+        while self.peek() == Some("OR") {
+            self.pos += 1;
+            operands.push(self.parse_and()?);
+        }
+        match operands.len() {
+            1 => Ok(operands.remove(0)),
+            _ => Ok(TextQuery::Or(operands)),
+        }
+    }
+
+    fn parse_and(&mut self) -> Result<TextQuery> {
+        let mut operands = vec![self.parse_primary()?];
+        loop {
+            match self.peek() {
+                None | Some("OR") | Some(")") => break,
+                Some("AND") => {
+                    self.pos += 1;
+                    operands.push(self.parse_primary()?);
+                }
+                Some(_) => operands.push(self.parse_primary()?),
+            }
+        }
+        match operands.len() {
+            1 => Ok(operands.remove(0)),
+            _ => Ok(TextQuery::And(operands)),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<TextQuery> {
+        match self.next() {
+            Some("(") => {
+                let text_query = self.parse_or()?;
+                match self.next() {
+                    Some(")") => Ok(text_query),
+                    _ => Err(self.error("missing ')'")),
+                }
+            }
+            None => Err(self.error("expect a term")),
+            Some(word @ ("AND" | "OR" | ")")) => {
+                Err(self.error(&format!("expect a term, but got '{}'", word)))
+            }
+            Some(word) => Ok(TextQuery::all_terms(word, self.tokenizer)),
+        }
+    }
+}
+
+/// The texts of the rows of a string or VARIANT column searched by the full-text functions, the
+/// VARIANT values are searched as their JSON text. The nulls are `None`.
+pub fn column_texts(column: &ColumnRef) -> Result<Vec<Option<String>>> {
+    let column = column.convert_full_column();
+    let inner = Series::remove_nullable(&column);
+
+    let texts: Vec<String> = if inner.data_type().data_type_id().is_variant() {
+        let variant_column: &VariantColumn = Series::check_get(&inner)?;
+        variant_column.iter().map(|v| v.to_string()).collect()
+    } else {
+        let string_column = Vu8::try_create_viewer(&inner)?;
+        string_column
+            .iter()
+            .map(|v| String::from_utf8_lossy(v).into_owned())
+            .collect()
+    };
+
+    Ok(texts
+        .into_iter()
+        .enumerate()
+        .map(|(row, text)| match column.null_at(row) {
+            true => None,
+            false => Some(text),
+        })
+        .collect())
+}
+
+/// `MATCH(<column>, <text>[, <tokenizer>])`, whether the text of the column contains all the terms
+/// of the text.
+pub type MatchFunction = FullTextFunctionImpl<false>;
+
+/// `QUERY(<column>, <query>[, <tokenizer>])`, whether the text of the column matches the
+/// full-text query.
+pub type QueryFunction = FullTextFunctionImpl<true>;
+
+#[derive(Clone)]
+pub struct FullTextFunctionImpl<const QUERY: bool> {
+    display_name: String,
+}
+
+impl<const QUERY: bool> FullTextFunctionImpl<QUERY> {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        let data_type_id = args[0].data_type_id();
+        if !data_type_id.is_string() && !data_type_id.is_variant() {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Expected a string or variant type, but got {}",
+                args[0].name()
+            )));
+        }
+        for arg in &args[1..] {
+            assert_string(*arg)?;
+        }
+
+        Ok(Box::new(FullTextFunctionImpl::<QUERY> {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create)).features(
+            FunctionFeatures::default()
+                .deterministic()
+                .variadic_arguments(2, 3),
+        )
+    }
+
+    fn tokenizer(name: Option<Vec<u8>>) -> Result<Tokenizer> {
+        match name {
+            Some(name) => Tokenizer::from_name(&String::from_utf8_lossy(&name)),
+            None => Ok(Tokenizer::default()),
+        }
+    }
+
+    fn text_query(query: &[u8], tokenizer: Tokenizer) -> Result<TextQuery> {
+        let query = String::from_utf8_lossy(query);
+        match QUERY {
+            true => TextQuery::parse(&query, tokenizer),
+            false => Ok(TextQuery::all_terms(&query, tokenizer)),
+        }
+    }
+
+    fn matches(text_query: &TextQuery, tokenizer: Tokenizer, text: &Option<String>) -> bool {
+        match text {
+            Some(text) => {
+                let terms = tokenizer.tokenize(text).into_iter().collect::<HashSet<_>>();
+                text_query.eval(&|term| terms.contains(term))
+            }
+            None => false,
+        }
+    }
+}
+
+impl<const QUERY: bool> Function for FullTextFunctionImpl<QUERY> {
+    fn name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        BooleanType::new_impl()
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let texts = column_texts(columns[0].column())?;
+        let query_column = columns[1].column();
+        let tokenizer_column = columns.get(2).map(|c| c.column());
+
+        let mut builder: ColumnBuilder<bool> = ColumnBuilder::with_capacity(input_rows);
+        if query_column.is_const() && tokenizer_column.map_or(true, |c| c.is_const()) {
+            let tokenizer_name = tokenizer_column.map(|c| c.get_string(0)).transpose()?;
+            let tokenizer = Self::tokenizer(tokenizer_name)?;
+            let text_query = Self::text_query(&query_column.get_string(0)?, tokenizer)?;
+            for text in &texts {
+                builder.append(Self::matches(&text_query, tokenizer, text));
+            }
+        } else {
+            for (row, text) in texts.iter().enumerate() {
+                let tokenizer_name = tokenizer_column.map(|c| c.get_string(row)).transpose()?;
+                let tokenizer = Self::tokenizer(tokenizer_name)?;
+                let text_query = Self::text_query(&query_column.get_string(row)?, tokenizer)?;
+                builder.append(Self::matches(&text_query, tokenizer, text));
+            }
+        }
+
+        Ok(Arc::new(builder.build_column()))
+    }
+}
+
+impl<const QUERY: bool> fmt::Display for FullTextFunctionImpl<QUERY> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
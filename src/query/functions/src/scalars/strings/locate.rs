@@ -16,6 +16,7 @@ use std::fmt;
 
 use common_datavalues::prelude::*;
 use common_exception::Result;
+use memchr::memmem;
 
 use crate::scalars::assert_numeric;
 use crate::scalars::assert_string;
@@ -117,10 +118,7 @@ fn find_at(str: &[u8], substr: &[u8], pos: u64) -> u64 {
     }
     let p = pos - 1;
     if p + substr.len() <= str.len() {
-        str[p..]
-            .windows(substr.len())
-            .position(|w| w == substr)
-            .map_or(0, |i| i + 1 + p) as u64
+        memmem::find(&str[p..], substr).map_or(0, |i| i + 1 + p) as u64
     } else {
         0_u64
     }
@@ -16,6 +16,7 @@ use bstr::ByteSlice;
 use bytes::BufMut;
 use common_exception::Result;
 
+use super::simd::ascii_to_lower;
 use super::string2string::String2StringFunction;
 use super::string2string::StringOperator;
 
@@ -25,6 +26,11 @@ pub struct Lower;
 impl StringOperator for Lower {
     #[inline]
     fn try_apply<'a>(&'a mut self, s: &'a [u8], mut buffer: &mut [u8]) -> Result<usize> {
+        if s.is_ascii() {
+            ascii_to_lower(s, buffer);
+            return Ok(s.len());
+        }
+
         for (start, end, ch) in s.char_indices() {
             if ch == '\u{FFFD}' {
                 // If char is not valid, just copy it.
@@ -25,6 +25,7 @@ mod export_set;
 mod field;
 mod find_in_set;
 mod format;
+mod full_text;
 mod hex;
 mod insert;
 mod leftright;
@@ -44,6 +45,7 @@ mod regexp_substr;
 mod repeat;
 mod replace;
 mod reverse;
+mod simd;
 mod soundex;
 mod space;
 mod strcmp;
@@ -70,6 +72,12 @@ pub use export_set::ExportSetFunction;
 pub use field::FieldFunction;
 pub use find_in_set::FindInSetFunction;
 pub use format::FormatFunction;
+pub use full_text::column_texts;
+pub use full_text::FullTextFunctionImpl;
+pub use full_text::MatchFunction;
+pub use full_text::QueryFunction;
+pub use full_text::TextQuery;
+pub use full_text::Tokenizer;
 pub use insert::InsertFunction;
 pub use leftright::LeftFunction;
 pub use leftright::RightFunction;
@@ -0,0 +1,199 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Vectorized kernels of the hot string functions.
+//!
+//! On x86_64 the kernels are dispatched at runtime to AVX2 when the CPU supports it, and to SSE2
+//! otherwise. The bytes left over by the vectorized loop, and all the bytes on the other
+//! architectures, go through the scalar code.
+
+/// Copy `src` to `dst` with the ASCII letters converted to lowercase, the other bytes are copied
+/// untouched.
+#[inline]
+pub fn ascii_to_lower(src: &[u8], dst: &mut [u8]) {
+    convert_case::<false>(src, dst)
+}
+
+/// Copy `src` to `dst` with the ASCII letters converted to uppercase, the other bytes are copied
+/// untouched.
+#[inline]
+pub fn ascii_to_upper(src: &[u8], dst: &mut [u8]) {
+    convert_case::<true>(src, dst)
+}
+
+/// The index of the first byte which is neither a space nor a tab.
+#[inline]
+pub fn first_non_blank(s: &[u8]) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    let (start, found) = x86::first_non_blank(s);
+    #[cfg(not(target_arch = "x86_64"))]
+    let (start, found) = (0, None);
+
+    found.or_else(|| {
+        s[start..]
+            .iter()
+            .position(|c| !is_blank(*c))
+            .map(|i| start + i)
+    })
+}
+
+/// The index of the last byte which is neither a space nor a tab.
+#[inline]
+pub fn last_non_blank(s: &[u8]) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    let (end, found) = x86::last_non_blank(s);
+    #[cfg(not(target_arch = "x86_64"))]
+    let (end, found) = (s.len(), None);
+
+    found.or_else(|| s[..end].iter().rposition(|c| !is_blank(*c)))
+}
+
+#[inline(always)]
+fn is_blank(c: u8) -> bool {
+    c == b' ' || c == b'\t'
+}
+
+#[inline]
+fn convert_case<const UPPER: bool>(src: &[u8], dst: &mut [u8]) {
+    let dst = &mut dst[..src.len()];
+
+    #[cfg(target_arch = "x86_64")]
+    let done = x86::convert_case::<UPPER>(src, dst);
+    #[cfg(not(target_arch = "x86_64"))]
+    let done = 0;
+
+    for (d, s) in dst[done..].iter_mut().zip(&src[done..]) {
+        *d = if UPPER {
+            s.to_ascii_uppercase()
+        } else {
+            s.to_ascii_lowercase()
+        };
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    const BLANKS_MASK_16: i32 = 0xFFFF;
+
+    /// Convert the case of the leading whole chunks of `src`, return the number of bytes done.
+    ///
+    /// A letter differs from its other case by the 0x20 bit only, so the bit is flipped for the
+    /// bytes in the range of the letters to convert. The bytes not in ASCII are negative as i8,
+    /// they are never in the range and are left untouched.
+    #[inline]
+    pub fn convert_case<const UPPER: bool>(src: &[u8], dst: &mut [u8]) -> usize {
+        debug_assert_eq!(src.len(), dst.len());
+        let (first, last) = if UPPER { (b'a', b'z') } else { (b'A', b'Z') };
+
+        // SAFETY: AVX2 is checked at runtime, SSE2 is always available on x86_64.
+        unsafe {
+            if is_x86_feature_detected!("avx2") {
+                convert_case_avx2(src, dst, first, last)
+            } else {
+                convert_case_sse2(src, dst, first, last)
+            }
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn convert_case_avx2(src: &[u8], dst: &mut [u8], first: u8, last: u8) -> usize {
+        let lower_bound = _mm256_set1_epi8(first as i8 - 1);
+        let upper_bound = _mm256_set1_epi8(last as i8 + 1);
+        let flip = _mm256_set1_epi8(0x20);
+
+        let mut index = 0;
+        while index + 32 <= src.len() {
+            let bytes = _mm256_loadu_si256(src.as_ptr().add(index) as *const __m256i);
+            let in_range = _mm256_and_si256(
+                _mm256_cmpgt_epi8(bytes, lower_bound),
+                _mm256_cmpgt_epi8(upper_bound, bytes),
+            );
+            let converted = _mm256_xor_si256(bytes, _mm256_and_si256(in_range, flip));
+            _mm256_storeu_si256(dst.as_mut_ptr().add(index) as *mut __m256i, converted);
+            index += 32;
+        }
+        index
+    }
+
+    unsafe fn convert_case_sse2(src: &[u8], dst: &mut [u8], first: u8, last: u8) -> usize {
+        let lower_bound = _mm_set1_epi8(first as i8 - 1);
+        let upper_bound = _mm_set1_epi8(last as i8 + 1);
+        let flip = _mm_set1_epi8(0x20);
+
+        let mut index = 0;
+        while index + 16 <= src.len() {
+            let bytes = _mm_loadu_si128(src.as_ptr().add(index) as *const __m128i);
+            let in_range = _mm_and_si128(
+                _mm_cmpgt_epi8(bytes, lower_bound),
+                _mm_cmplt_epi8(bytes, upper_bound),
+            );
+            let converted = _mm_xor_si128(bytes, _mm_and_si128(in_range, flip));
+            _mm_storeu_si128(dst.as_mut_ptr().add(index) as *mut __m128i, converted);
+            index += 16;
+        }
+        index
+    }
+
+    /// Scan the leading whole chunks of `s` for the first non blank byte. Return the index where
+    /// the scan stopped, and the index of the byte if it is found.
+    #[inline]
+    pub fn first_non_blank(s: &[u8]) -> (usize, Option<usize>) {
+        let mut index = 0;
+        // SAFETY: SSE2 is always available on x86_64, the loads are in the bounds of `s`.
+        unsafe {
+            while index + 16 <= s.len() {
+                let mask = blanks_mask(s.as_ptr().add(index));
+                if mask != BLANKS_MASK_16 {
+                    let offset = (!mask & BLANKS_MASK_16).trailing_zeros() as usize;
+                    return (index, Some(index + offset));
+                }
+                index += 16;
+            }
+        }
+        (index, None)
+    }
+
+    /// Scan the trailing whole chunks of `s` for the last non blank byte. Return the index where
+    /// the scan stopped, and the index of the byte if it is found.
+    #[inline]
+    pub fn last_non_blank(s: &[u8]) -> (usize, Option<usize>) {
+        let mut end = s.len();
+        // SAFETY: SSE2 is always available on x86_64, the loads are in the bounds of `s`.
+        unsafe {
+            while end >= 16 {
+                let start = end - 16;
+                let mask = blanks_mask(s.as_ptr().add(start));
+                if mask != BLANKS_MASK_16 {
+                    let offset = 31 - (!mask & BLANKS_MASK_16).leading_zeros() as usize;
+                    return (end, Some(start + offset));
+                }
+                end = start;
+            }
+        }
+        (end, None)
+    }
+
+    /// One bit per byte of the 16 bytes at `ptr`, set if the byte is a space or a tab.
+    #[inline(always)]
+    unsafe fn blanks_mask(ptr: *const u8) -> i32 {
+        let bytes = _mm_loadu_si128(ptr as *const __m128i);
+        let blanks = _mm_or_si128(
+            _mm_cmpeq_epi8(bytes, _mm_set1_epi8(b' ' as i8)),
+            _mm_cmpeq_epi8(bytes, _mm_set1_epi8(b'\t' as i8)),
+        );
+        _mm_movemask_epi8(blanks)
+    }
+}
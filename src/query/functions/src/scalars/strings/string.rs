@@ -35,10 +35,12 @@ use crate::scalars::LeftFunction;
 use crate::scalars::LeftPadFunction;
 use crate::scalars::LocateFunction;
 use crate::scalars::LowerFunction;
+use crate::scalars::MatchFunction;
 use crate::scalars::OctFunction;
 use crate::scalars::OctetLengthFunction;
 use crate::scalars::OrdFunction;
 use crate::scalars::PositionFunction;
+use crate::scalars::QueryFunction;
 use crate::scalars::QuoteFunction;
 use crate::scalars::RTrimFunction;
 use crate::scalars::RegexpInStrFunction;
@@ -120,5 +122,7 @@ impl StringFunction {
         factory.register("locate", LocateFunction::desc());
         factory.register("position", PositionFunction::desc());
         factory.register("instr", InstrFunction::desc());
+        factory.register("match", MatchFunction::desc());
+        factory.register("query", QueryFunction::desc());
     }
 }
@@ -14,6 +14,8 @@
 
 use common_exception::Result;
 
+use super::simd::first_non_blank;
+use super::simd::last_non_blank;
 use super::string2string::String2StringFunction;
 use super::string2string::StringOperator;
 
@@ -23,15 +25,14 @@ pub struct LTrim;
 impl StringOperator for LTrim {
     #[inline]
     fn try_apply<'a>(&'a mut self, s: &'a [u8], buffer: &mut [u8]) -> Result<usize> {
-        for (idx, ch) in s.iter().enumerate() {
-            if *ch != b' ' && *ch != b'\t' {
-                let len = s.len() - idx;
-                let buffer = &mut buffer[0..s.len() - idx];
-                buffer.copy_from_slice(&s[idx..]);
-                return Ok(len);
+        match first_non_blank(s) {
+            Some(start) => {
+                let len = s.len() - start;
+                buffer[0..len].copy_from_slice(&s[start..]);
+                Ok(len)
             }
+            None => Ok(0),
         }
-        Ok(0)
     }
 }
 
@@ -40,15 +41,14 @@ pub struct RTrim;
 
 impl StringOperator for RTrim {
     fn try_apply<'a>(&'a mut self, s: &'a [u8], buffer: &mut [u8]) -> Result<usize> {
-        for (idx, ch) in s.iter().rev().enumerate() {
-            if *ch != b' ' && *ch != b'\t' {
-                let len = s.len() - idx;
-                let buffer = &mut buffer[0..len];
-                buffer.copy_from_slice(&s[..s.len() - idx]);
-                return Ok(len);
+        match last_non_blank(s) {
+            Some(end) => {
+                let len = end + 1;
+                buffer[0..len].copy_from_slice(&s[..len]);
+                Ok(len)
             }
+            None => Ok(0),
         }
-        Ok(0)
     }
 }
 
@@ -57,16 +57,15 @@ pub struct Trim;
 
 impl StringOperator for Trim {
     fn try_apply<'a>(&'a mut self, s: &'a [u8], buffer: &mut [u8]) -> Result<usize> {
-        let start_index = s.iter().position(|ch| *ch != b' ' && *ch != b'\t');
-        let end_index = s.iter().rev().position(|ch| *ch != b' ' && *ch != b'\t');
-        match (start_index, end_index) {
-            (Some(start_index), Some(end_index)) => {
-                let len = s.len() - end_index - start_index;
-                let buffer = &mut buffer[0..len];
-                buffer.copy_from_slice(&s[start_index..s.len() - end_index]);
+        match first_non_blank(s) {
+            Some(start) => {
+                // There is a non blank byte, so the last one exists too.
+                let end = last_non_blank(s).unwrap_or(start);
+                let len = end + 1 - start;
+                buffer[0..len].copy_from_slice(&s[start..=end]);
                 Ok(len)
             }
-            (_, _) => Ok(0),
+            None => Ok(0),
         }
     }
 }
@@ -0,0 +1,137 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::jit::JitCompiler;
+use common_functions::jit::JitExpr;
+use common_functions::jit::JitOperator;
+use common_functions::scalars::FunctionContext;
+use common_functions::scalars::FunctionFactory;
+use pretty_assertions::assert_eq;
+
+fn input(index: usize, column: &ColumnRef) -> JitExpr {
+    JitExpr::Input {
+        index,
+        data_type: column.data_type(),
+    }
+}
+
+fn function(name: &str, args: Vec<JitExpr>) -> Result<JitExpr> {
+    let types: Vec<&DataTypeImpl> = args.iter().map(|arg| arg.data_type()).collect();
+    let return_type = FunctionFactory::instance().get(name, &types)?.return_type();
+    Ok(JitExpr::Function {
+        op: JitOperator::from_name(name).unwrap(),
+        args,
+        return_type,
+    })
+}
+
+// the compiled function must give the same result as the interpreted one
+fn test_binary(name: &str, lhs: ColumnRef, rhs: ColumnRef) -> Result<()> {
+    let num_rows = lhs.len();
+    let types = vec![lhs.data_type(), rhs.data_type()];
+    let func = FunctionFactory::instance().get(name, &types.iter().collect::<Vec<_>>())?;
+    let arguments = vec![
+        ColumnWithField::new(lhs.clone(), DataField::new("a", lhs.data_type())),
+        ColumnWithField::new(rhs.clone(), DataField::new("b", rhs.data_type())),
+    ];
+    let expect = func.eval(FunctionContext::default(), &arguments, num_rows)?;
+
+    let expr = function(name, vec![input(0, &lhs), input(1, &rhs)])?;
+    let compiled = JitCompiler::compile(&expr)?;
+    let actual = compiled.eval(&[lhs, rhs], num_rows)?.unwrap();
+    assert_eq!(expect.convert_full_column(), actual, "{}", name);
+    Ok(())
+}
+
+#[test]
+fn test_jit_arithmetic() -> Result<()> {
+    let int8s = Series::from_data(vec![i8::MIN, -1, 0, 1, i8::MAX]);
+    let uint8s = Series::from_data(vec![1u8, 0, 2, 200, u8::MAX]);
+    let int64s = Series::from_data(vec![i64::MIN, -3, 0, 7, i64::MAX]);
+    let other_int64s = Series::from_data(vec![i64::MIN, 2, 5, -7, i64::MAX]);
+    let uint64s = Series::from_data(vec![0u64, 3, 9, u64::MAX - 1, u64::MAX]);
+    let float32s = Series::from_data(vec![-1.5f32, 0.0, 0.25, 3.0, f32::MAX]);
+    let float64s = Series::from_data(vec![-2.5f64, 2.0, 1.0, 0.5, 1e300]);
+
+    // without NaN, which isn't equal to itself
+    for name in ["plus", "minus", "multiply", "divide"] {
+        test_binary(name, int8s.clone(), uint8s.clone())?;
+        test_binary(name, int64s.clone(), other_int64s.clone())?;
+        test_binary(name, uint64s.clone(), uint8s.clone())?;
+        test_binary(name, int8s.clone(), float32s.clone())?;
+        test_binary(name, float32s.clone(), float64s.clone())?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_jit_comparison() -> Result<()> {
+    let int16s = Series::from_data(vec![i16::MIN, -1, 0, 1, i16::MAX]);
+    let uint32s = Series::from_data(vec![0u32, 1, 2, 200, u32::MAX]);
+    let int32s = Series::from_data(vec![-1i32, 1, 0, 200, i32::MAX]);
+    let float64s = Series::from_data(vec![-2.5f64, 1.0, 0.0, f64::NAN, 1e300]);
+
+    for name in ["=", "<>", "<", "<=", ">", ">="] {
+        test_binary(name, int16s.clone(), uint32s.clone())?;
+        test_binary(name, int32s.clone(), int16s.clone())?;
+        test_binary(name, uint32s.clone(), uint32s.clone())?;
+        test_binary(name, int32s.clone(), float64s.clone())?;
+        test_binary(name, float64s.clone(), float64s.clone())?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_jit_predicate() -> Result<()> {
+    // (a + 1 > b) and not (b = 2.0)
+    let a = Series::from_data(vec![1i32, 2, 3, 4]);
+    let b = Series::from_data(vec![1.0f64, 2.0, 5.0, 0.5]);
+    let one = JitExpr::Constant {
+        value: DataValue::UInt64(1),
+        data_type: u8::to_data_type(),
+    };
+    let two = JitExpr::Constant {
+        value: DataValue::Float64(2.0),
+        data_type: f64::to_data_type(),
+    };
+    let plus = function("plus", vec![input(0, &a), one])?;
+    let gt = function(">", vec![plus, input(1, &b)])?;
+    let eq = function("=", vec![input(1, &b), two])?;
+    let not = function("not", vec![eq])?;
+    let expr = function("and", vec![gt, not])?;
+
+    let compiled = JitCompiler::compile(&expr)?;
+    let actual = compiled.eval(&[a, b], 4)?.unwrap();
+    assert_eq!(Series::from_data(vec![true, false, false, true]), actual);
+    Ok(())
+}
+
+#[test]
+fn test_jit_fallback() -> Result<()> {
+    let a = Series::from_data(vec![1i32, 2, 3]);
+    let expr = function("plus", vec![input(0, &a), input(0, &a)])?;
+    let compiled = JitCompiler::compile(&expr)?;
+
+    // constant columns are left to the interpreted functions
+    let constant = ConstColumn::new(Series::from_data(vec![1i32]), 3).arc();
+    assert!(compiled.eval(&[constant], 3)?.is_none());
+
+    // so are the expressions which can't be compiled
+    let strings = Series::from_data(vec!["a", "b", "c"]);
+    let expr = function("=", vec![input(0, &strings), input(0, &strings)])?;
+    assert!(JitCompiler::compile(&expr).is_err());
+    Ok(())
+}
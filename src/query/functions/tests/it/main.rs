@@ -13,5 +13,7 @@
 // limitations under the License.
 
 mod aggregates;
+#[cfg(feature = "jit")]
+mod jit;
 mod rdoc;
 mod scalars;
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_datavalues::prelude::*;
 use common_exception::Result;
 use serde_json::json;
@@ -315,6 +317,15 @@ fn test_like_comparison_function() -> Result<()> {
             expect: Series::from_data(vec![true, true, true, false]),
             error: "",
         },
+        ScalarFunctionTest {
+            name: "like-contains-passed",
+            columns: vec![
+                Series::from_data(vec!["abc", "bcd", "cde", "the pattern is at the end: xbx"]),
+                Arc::new(ConstColumn::new(Series::from_data(vec!["%b%"]), 4)),
+            ],
+            expect: Series::from_data(vec![true, true, false, true]),
+            error: "",
+        },
     ];
 
     test_scalar_functions("like", &tests)
@@ -346,6 +357,15 @@ fn test_not_like_comparison_function() -> Result<()> {
             expect: Series::from_data(vec![false, false, false, true]),
             error: "",
         },
+        ScalarFunctionTest {
+            name: "not-like-contains-passed",
+            columns: vec![
+                Series::from_data(vec!["abc", "bcd", "cde", "the pattern is at the end: xbx"]),
+                Arc::new(ConstColumn::new(Series::from_data(vec!["%b%"]), 4)),
+            ],
+            expect: Series::from_data(vec![false, false, true, false]),
+            error: "",
+        },
     ];
 
     test_scalar_functions("not like", &tests)
@@ -0,0 +1,51 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::scalar_function_test::test_scalar_functions;
+use crate::scalars::scalar_function_test::ScalarFunctionTest;
+
+#[test]
+fn test_h3_get_resolution_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "h3_get_resolution",
+            columns: vec![Series::from_data(vec![644325524701193974_u64])],
+            expect: Series::from_data(vec![15_u8]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "h3_get_resolution_invalid",
+            columns: vec![Series::from_data(vec![0_u64])],
+            expect: Series::from_data(vec![0_u8]),
+            error: "Invalid H3 index 0 for function 'H3_GET_RESOLUTION'",
+        },
+    ];
+
+    test_scalar_functions("h3_get_resolution", &tests)
+}
+
+#[test]
+fn test_h3_is_valid_function() -> Result<()> {
+    let tests = vec![ScalarFunctionTest {
+        name: "h3_is_valid",
+        columns: vec![Series::from_data(vec![644325524701193974_u64, 0])],
+        expect: Series::from_data(vec![true, false]),
+        error: "",
+    }];
+
+    test_scalar_functions("h3_is_valid", &tests)
+}
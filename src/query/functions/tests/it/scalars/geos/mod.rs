@@ -13,3 +13,5 @@
 // limitations under the License.
 
 mod geo_to_h3;
+mod h3;
+mod st;
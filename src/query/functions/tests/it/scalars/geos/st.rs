@@ -0,0 +1,174 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::scalar_function_test::test_scalar_functions;
+use crate::scalars::scalar_function_test::ScalarFunctionTest;
+
+#[test]
+fn test_st_astext_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "st_astext_wkt",
+            columns: vec![Series::from_data(vec![
+                "POINT(1 2)",
+                "SRID=4326;polygon((0 0, 1 0, 1 1, 0 0))",
+                "MULTIPOINT((0 0),(1.5 -2))",
+                "GEOMETRYCOLLECTION EMPTY",
+            ])],
+            expect: Series::from_data(vec![
+                "POINT(1 2)",
+                "POLYGON((0 0,1 0,1 1,0 0))",
+                "MULTIPOINT((0 0),(1.5 -2))",
+                "GEOMETRYCOLLECTION EMPTY",
+            ]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "st_astext_geojson",
+            columns: vec![Series::from_data(vec![
+                r#"{"type":"LineString","coordinates":[[0,0],[1,1]]}"#,
+            ])],
+            expect: Series::from_data(vec!["LINESTRING(0 0,1 1)"]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "st_astext_hex_wkb",
+            columns: vec![Series::from_data(vec![
+                "0101000000000000000000F03F0000000000000040",
+            ])],
+            expect: Series::from_data(vec!["POINT(1 2)"]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "st_astext_error_type",
+            columns: vec![Series::from_data(vec![1_i32])],
+            expect: Series::from_data(vec![""]),
+            error: "Invalid argument types for function 'ST_ASTEXT': (Int32)",
+        },
+    ];
+
+    test_scalar_functions("st_astext", &tests)
+}
+
+#[test]
+fn test_st_x_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "st_x_point",
+            columns: vec![Series::from_data(vec!["POINT(1.5 2)", "POINT(-3 4)"])],
+            expect: Series::from_data(vec![1.5_f64, -3.0]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "st_x_line_string",
+            columns: vec![Series::from_data(vec!["LINESTRING(0 0,1 1)"])],
+            expect: Series::from_data(vec![0_f64]),
+            error: "Function 'ST_X' only supports points",
+        },
+    ];
+
+    test_scalar_functions("st_x", &tests)
+}
+
+#[test]
+fn test_st_distance_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "st_distance_geometry",
+            columns: vec![
+                Series::from_data(vec![
+                    "POINT(0 0)",
+                    "POINT(0 0)",
+                    "POINT(1 1)",
+                    "LINESTRING(0 0,1 1)",
+                    "POINT(0 0)",
+                ]),
+                Series::from_data(vec![
+                    "POINT(3 4)",
+                    "LINESTRING(1 -1,1 1)",
+                    "POLYGON((0 0,4 0,4 4,0 4,0 0))",
+                    "LINESTRING(0 1,1 0)",
+                    "GEOMETRYCOLLECTION EMPTY",
+                ]),
+            ],
+            expect: Series::from_data(vec![Some(5.0_f64), Some(1.0), Some(0.0), Some(0.0), None]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "st_distance_error_type",
+            columns: vec![
+                Series::from_data(vec![1_i32]),
+                Series::from_data(vec!["POINT(0 0)"]),
+            ],
+            expect: Series::from_data(vec![None::<f64>]),
+            error: "Invalid argument types for function 'ST_DISTANCE': (Int32, String)",
+        },
+    ];
+
+    test_scalar_functions("st_distance", &tests)
+}
+
+#[test]
+fn test_st_contains_function() -> Result<()> {
+    let square = "POLYGON((0 0,4 0,4 4,0 4,0 0))";
+    let with_hole = "POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,3 1,3 3,1 3,1 1))";
+    let tests = vec![ScalarFunctionTest {
+        name: "st_contains_polygon",
+        columns: vec![
+            Series::from_data(vec![square, square, square, square, with_hole, square]),
+            Series::from_data(vec![
+                "POINT(1 1)",
+                "POINT(5 5)",
+                "POINT(0 2)",
+                "LINESTRING(1 1,3 3)",
+                "POINT(2 2)",
+                "LINESTRING(1 1,5 5)",
+            ]),
+        ],
+        expect: Series::from_data(vec![true, false, false, true, false, false]),
+        error: "",
+    }];
+
+    test_scalar_functions("st_contains", &tests)
+}
+
+#[test]
+fn test_st_intersects_function() -> Result<()> {
+    let tests = vec![ScalarFunctionTest {
+        name: "st_intersects_geometry",
+        columns: vec![
+            Series::from_data(vec![
+                "LINESTRING(0 0,2 2)",
+                "LINESTRING(0 0,1 1)",
+                "POLYGON((0 0,4 0,4 4,0 4,0 0))",
+                "POINT(1 1)",
+                "POINT(1 1)",
+            ]),
+            Series::from_data(vec![
+                "LINESTRING(0 2,2 0)",
+                "LINESTRING(2 2,3 3)",
+                "POINT(1 1)",
+                "POINT(1 1)",
+                "GEOMETRYCOLLECTION EMPTY",
+            ]),
+        ],
+        expect: Series::from_data(vec![true, false, true, true, false]),
+        error: "",
+    }];
+
+    test_scalar_functions("st_intersects", &tests)
+}
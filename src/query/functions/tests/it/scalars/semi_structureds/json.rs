@@ -0,0 +1,152 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use serde_json::json;
+
+use crate::scalars::scalar_function_test::test_scalar_functions;
+use crate::scalars::scalar_function_test::ScalarFunctionTest;
+
+#[test]
+fn test_json_extract_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "json_extract_variant",
+            columns: vec![
+                Series::from_data(vec![
+                    VariantValue::from(json!({"a":[[1_i32],[2_i32]],"o":{"p":{"q":"r"}}})),
+                    VariantValue::from(json!({"a":[[1_i32],[2_i32]],"o":{"p":{"q":"r"}}})),
+                    VariantValue::from(json!({"a":[[1_i32],[2_i32]],"o":{"p":{"q":"r"}}})),
+                    VariantValue::from(json!({"a":[[1_i32],[2_i32]],"o":{"p":{"q":"r"}}})),
+                    VariantValue::from(json!({"a":[[1_i32],[2_i32]],"o":{"p":{"q":"r"}}})),
+                ]),
+                Series::from_data(vec!["$.a[1][0]", "$.a.b", "$.o['p'].q", "o.p", "$"]),
+            ],
+            expect: Series::from_data(vec![
+                Some(VariantValue::from(json!(2_i32))),
+                None,
+                Some(VariantValue::from(json!("r"))),
+                Some(VariantValue::from(json!({"q":"r"}))),
+                Some(VariantValue::from(
+                    json!({"a":[[1_i32],[2_i32]],"o":{"p":{"q":"r"}}}),
+                )),
+            ]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "json_extract_string",
+            columns: vec![
+                Series::from_data(vec![r#"{"a":{"b":1}}"#, r#"[1,{"c":2}]"#, "invalid"]),
+                Series::from_data(vec!["$.a.b", r#"$[1]["c"]"#, "$.a"]),
+            ],
+            expect: Series::from_data(vec![
+                Some(VariantValue::from(json!(1_i32))),
+                Some(VariantValue::from(json!(2_i32))),
+                None,
+            ]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "json_extract_invalid_path",
+            columns: vec![
+                Series::from_data(vec![r#"{"a":1}"#]),
+                Series::from_data(vec!["$.a[b]"]),
+            ],
+            expect: Series::from_data(vec![None::<&str>]),
+            error: "Invalid JSON path: '$.a[b]'",
+        },
+        ScalarFunctionTest {
+            name: "json_extract_error_type",
+            columns: vec![
+                Series::from_data(vec![1_i32, 2]),
+                Series::from_data(vec!["$.a", "$.b"]),
+            ],
+            expect: Series::from_data(vec![None::<&str>, None::<&str>]),
+            error: "Invalid argument types for function 'JSON_EXTRACT': (Int32, String)",
+        },
+    ];
+
+    test_scalar_functions("json_extract", &tests)
+}
+
+#[test]
+fn test_json_get_function() -> Result<()> {
+    let tests = vec![ScalarFunctionTest {
+        name: "json_get",
+        columns: vec![
+            Series::from_data(vec![
+                VariantValue::from(json!({"a":{"b":"c"}})),
+                VariantValue::from(json!([1_i32, 2_i32])),
+                VariantValue::from(json!([1_i32, 2_i32])),
+            ]),
+            Series::from_data(vec![0_i64, 1, -1]),
+        ],
+        expect: Series::from_data(vec![None, Some(VariantValue::from(json!(2_i32))), None]),
+        error: "",
+    }];
+    test_scalar_functions("->", &tests)?;
+
+    let tests = vec![ScalarFunctionTest {
+        name: "json_get_text",
+        columns: vec![
+            Series::from_data(vec![r#"{"a":"b"}"#, r#"{"a":{"b":1}}"#, r#"{"a":null}"#]),
+            Series::from_data(vec!["a", "a", "a"]),
+        ],
+        expect: Series::from_data(vec![Some("b"), Some(r#"{"b":1}"#), None]),
+        error: "",
+    }];
+    test_scalar_functions("->>", &tests)
+}
+
+#[test]
+fn test_json_set_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "json_set_variant",
+            columns: vec![
+                Series::from_data(vec![
+                    VariantValue::from(json!({"a":1_i32})),
+                    VariantValue::from(json!({"a":[1_i32]})),
+                    VariantValue::from(json!({"a":[1_i32]})),
+                    VariantValue::from(json!({"a":1_i32})),
+                ]),
+                Series::from_data(vec!["$.a", "$.a[0]", "$.a[3]", "$.b.c"]),
+                Series::from_data(vec![2_i32, 2, 2, 2]),
+            ],
+            expect: Series::from_data(vec![
+                Some(VariantValue::from(json!({"a":2_i32}))),
+                Some(VariantValue::from(json!({"a":[2_i32]}))),
+                Some(VariantValue::from(json!({"a":[1_i32, 2_i32]}))),
+                Some(VariantValue::from(json!({"a":1_i32}))),
+            ]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "json_set_string",
+            columns: vec![
+                Series::from_data(vec![r#"{"a":1}"#, "invalid"]),
+                Series::from_data(vec!["$.b", "$.b"]),
+                Series::from_data(vec!["x", "y"]),
+            ],
+            expect: Series::from_data(vec![
+                Some(VariantValue::from(json!({"a":1_i32,"b":"x"}))),
+                None,
+            ]),
+            error: "",
+        },
+    ];
+
+    test_scalar_functions("json_set", &tests)
+}
@@ -14,4 +14,5 @@
 
 mod check_json;
 mod get;
+mod json;
 mod parse_json;
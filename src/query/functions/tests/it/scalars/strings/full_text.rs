@@ -0,0 +1,144 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::scalars::TextQuery;
+use common_functions::scalars::Tokenizer;
+
+use crate::scalars::scalar_function_test::test_scalar_functions;
+use crate::scalars::scalar_function_test::ScalarFunctionTest;
+
+#[test]
+fn test_match_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "match-passed",
+            columns: vec![
+                Series::from_data(vec!["Connection timeout to db", "timeout", "DB error", ""]),
+                Series::from_data(vec!["db timeout", "db timeout", "error-db", "x"]),
+            ],
+            expect: Series::from_data(vec![true, false, true, false]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "match-whitespace-tokenizer-passed",
+            columns: vec![
+                Series::from_data(vec!["Error: Timeout", "error: timeout"]),
+                Series::from_data(vec!["Timeout", "Timeout"]),
+                Series::from_data(vec!["whitespace", "whitespace"]),
+            ],
+            expect: Series::from_data(vec![true, false]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "match-nullable-passed",
+            columns: vec![
+                Series::from_data(vec![Some("error"), None]),
+                Series::from_data(vec![Some("error"), Some("error")]),
+            ],
+            expect: Series::from_data(vec![Some(true), None]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "match-type-error",
+            columns: vec![
+                Series::from_data(vec![1, 2]),
+                Series::from_data(vec!["1", "2"]),
+            ],
+            expect: Series::from_data(Vec::<bool>::new()),
+            error: "Expected a string or variant type, but got Int32",
+        },
+        ScalarFunctionTest {
+            name: "match-tokenizer-error",
+            columns: vec![
+                Series::from_data(vec!["error"]),
+                Series::from_data(vec!["error"]),
+                Series::from_data(vec!["ngram"]),
+            ],
+            expect: Series::from_data(Vec::<bool>::new()),
+            error: "Unknown tokenizer 'ngram', expect 'standard' or 'whitespace'",
+        },
+    ];
+
+    test_scalar_functions("match", &tests)
+}
+
+#[test]
+fn test_query_function() -> Result<()> {
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "query-passed",
+            columns: vec![
+                Series::from_data(vec![
+                    "error timeout",
+                    "error refused",
+                    "warn timeout",
+                    "error",
+                ]),
+                Series::from_data(vec![
+                    "error AND (timeout OR refused)",
+                    "error (timeout OR refused)",
+                    "error OR warn",
+                    "(timeout OR refused) error",
+                ]),
+            ],
+            expect: Series::from_data(vec![true, true, true, false]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "query-syntax-error",
+            columns: vec![
+                Series::from_data(vec!["error"]),
+                Series::from_data(vec!["error AND"]),
+            ],
+            expect: Series::from_data(Vec::<bool>::new()),
+            error: "Invalid full-text query 'error AND': expect a term",
+        },
+        ScalarFunctionTest {
+            name: "query-parentheses-error",
+            columns: vec![
+                Series::from_data(vec!["error"]),
+                Series::from_data(vec!["(error OR warn"]),
+            ],
+            expect: Series::from_data(Vec::<bool>::new()),
+            error: "Invalid full-text query '(error OR warn': missing ')'",
+        },
+    ];
+
+    test_scalar_functions("query", &tests)
+}
+
+#[test]
+fn test_text_query_parse() -> Result<()> {
+    let term = |t: &str| TextQuery::Term(t.to_string());
+
+    assert_eq!(
+        TextQuery::parse("Error (Timeout OR conn-refused)", Tokenizer::Standard)?,
+        TextQuery::And(vec![
+            term("error"),
+            TextQuery::Or(vec![
+                term("timeout"),
+                TextQuery::And(vec![term("conn"), term("refused")])
+            ]),
+        ])
+    );
+    assert_eq!(
+        TextQuery::parse("a OR b c", Tokenizer::Whitespace)?,
+        TextQuery::Or(vec![term("a"), TextQuery::And(vec![term("b"), term("c")])])
+    );
+    assert!(TextQuery::parse("a)", Tokenizer::Standard).is_err());
+
+    Ok(())
+}
@@ -33,6 +33,16 @@ fn test_lower_function() -> Result<()> {
             expect: Series::from_data(vec!["dobrý den"]),
             error: "",
         },
+        ScalarFunctionTest {
+            name: "lower-long-ascii-passed",
+            columns: vec![Series::from_data(vec![
+                "The Quick Brown Fox Jumps Over The Lazy Dog @[`{ 0123456789",
+            ])],
+            expect: Series::from_data(vec![
+                "the quick brown fox jumps over the lazy dog @[`{ 0123456789",
+            ]),
+            error: "",
+        },
         ScalarFunctionTest {
             name: "lcase-utf8-passed",
             columns: vec![Series::from_data(vec!["Dobrý den"])],
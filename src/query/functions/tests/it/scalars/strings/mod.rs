@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod full_text;
 // mod locate;
 mod locate;
 mod lower;
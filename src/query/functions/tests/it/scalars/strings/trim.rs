@@ -20,24 +20,44 @@ use crate::scalars::scalar_function_test::ScalarFunctionTest;
 
 #[test]
 fn test_ltrim_function() -> Result<()> {
-    let tests = vec![ScalarFunctionTest {
-        name: "ltrim-abc-passed",
-        columns: vec![Series::from_data(vec!["  abc"])],
-        expect: Series::from_data(vec!["abc"]),
-        error: "",
-    }];
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "ltrim-abc-passed",
+            columns: vec![Series::from_data(vec!["  abc"])],
+            expect: Series::from_data(vec!["abc"]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "ltrim-long-blanks-passed",
+            columns: vec![Series::from_data(vec![
+                " \t \t \t \t \t \t \t \t \t \t \t \tabc d  ",
+            ])],
+            expect: Series::from_data(vec!["abc d  "]),
+            error: "",
+        },
+    ];
 
     test_scalar_functions("ltrim", &tests)
 }
 
 #[test]
 fn test_rtrim_function() -> Result<()> {
-    let tests = vec![ScalarFunctionTest {
-        name: "rtrim-abc-passed",
-        columns: vec![Series::from_data(vec!["abc  "])],
-        expect: Series::from_data(vec!["abc"]),
-        error: "",
-    }];
+    let tests = vec![
+        ScalarFunctionTest {
+            name: "rtrim-abc-passed",
+            columns: vec![Series::from_data(vec!["abc  "])],
+            expect: Series::from_data(vec!["abc"]),
+            error: "",
+        },
+        ScalarFunctionTest {
+            name: "rtrim-long-blanks-passed",
+            columns: vec![Series::from_data(vec![
+                "  a bc \t \t \t \t \t \t \t \t \t \t \t \t",
+            ])],
+            expect: Series::from_data(vec!["  a bc"]),
+            error: "",
+        },
+    ];
 
     test_scalar_functions("rtrim", &tests)
 }
@@ -57,6 +77,15 @@ fn test_trim_function() -> Result<()> {
             expect: Series::from_data(vec![""]),
             error: "",
         },
+        ScalarFunctionTest {
+            name: "trim-long-blanks-passed",
+            columns: vec![Series::from_data(vec![
+                " \t \t \t \t \t \t \t \t \t \t \t \tabc \t \t \t \t \t \t \t \t \t \t \t \t",
+                " \t \t \t \t \t \t \t \t \t \t \t \t",
+            ])],
+            expect: Series::from_data(vec!["abc", ""]),
+            error: "",
+        },
     ];
 
     test_scalar_functions("trim", &tests)
@@ -33,6 +33,16 @@ fn test_upper_function() -> Result<()> {
             expect: Series::from_data(vec!["DOBRÝ DEN"]),
             error: "",
         },
+        ScalarFunctionTest {
+            name: "upper-long-ascii-passed",
+            columns: vec![Series::from_data(vec![
+                "The Quick Brown Fox Jumps Over The Lazy Dog @[`{ 0123456789",
+            ])],
+            expect: Series::from_data(vec![
+                "THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG @[`{ 0123456789",
+            ]),
+            error: "",
+        },
         ScalarFunctionTest {
             name: "ucase-utf8-passed",
             columns: vec![Series::from_data(vec!["Dobrý den"])],
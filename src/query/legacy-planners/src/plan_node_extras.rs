@@ -77,6 +77,18 @@ pub struct PrewhereInfo {
     pub filter: LegacyExpression,
 }
 
+/// The sampling of a scan, by `TABLESAMPLE`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ScanSample {
+    /// Read each block with the probability in [0, 1]
+    Block(f64),
+    /// Read each row with the probability in [0, 1]
+    Row(f64),
+}
+
+// The probabilities are never NaN.
+impl Eq for ScanSample {}
+
 /// Extras is a wrapper for push down items.
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Default)]
 pub struct Extras {
@@ -92,6 +104,10 @@ pub struct Extras {
     pub limit: Option<usize>,
     /// Optional order_by expression plan
     pub order_by: Vec<LegacyExpression>,
+    /// Optional sampling of the blocks or rows
+    pub sample: Option<ScanSample>,
+    /// Names of the virtual columns to read, appended to the projected columns
+    pub virtual_columns: Vec<String>,
 }
 
 impl Extras {
@@ -102,6 +118,8 @@ impl Extras {
             prewhere: None,
             limit: None,
             order_by: vec![],
+            sample: None,
+            virtual_columns: vec![],
         }
     }
 }
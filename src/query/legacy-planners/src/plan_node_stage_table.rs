@@ -24,6 +24,8 @@ pub struct StageTableInfo {
     pub stage_info: UserStageInfo,
     pub path: String,
     pub files: Vec<String>,
+    // Names of the keys to partition the unloaded files by, the values are the last columns.
+    pub partition_by: Vec<String>,
 }
 
 impl StageTableInfo {
@@ -19,8 +19,7 @@ use pretty_assertions::assert_eq;
 #[test]
 fn test_plan_extras() -> Result<()> {
     let extras = Extras::default();
-    let expect =
-        "Extras { projection: None, filters: [], prewhere: None, limit: None, order_by: [] }";
+    let expect = "Extras { projection: None, filters: [], prewhere: None, limit: None, order_by: [], sample: None, virtual_columns: [] }";
     let actual = format!("{:?}", extras);
     assert_eq!(expect, actual);
     Ok(())
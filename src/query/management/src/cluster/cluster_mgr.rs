@@ -144,9 +144,11 @@ impl ClusterApi for ClusterMgr {
             Some(exact) => MatchSeq::Exact(exact),
         };
 
-        let upsert_meta =
-            self.metastore
-                .upsert_kv(UpsertKVReq::new(&node_key, seq, Operation::AsIs, meta));
+        // The node info is written again, it carries the load of the node.
+        let value = Operation::Update(serde_json::to_vec(node)?);
+        let upsert_meta = self
+            .metastore
+            .upsert_kv(UpsertKVReq::new(&node_key, seq, value, meta));
 
         match upsert_meta.await? {
             UpsertKVReply {
@@ -13,27 +13,45 @@
 // limitations under the License.
 
 mod cluster;
+mod lock;
+mod masking_policy;
+mod network_policy;
 mod quota;
 mod role;
+mod row_access_policy;
 mod serde;
 mod setting;
 mod stage;
+mod task;
 mod udf;
 mod user;
+mod workload_group;
 
 pub use cluster::ClusterApi;
 pub use cluster::ClusterMgr;
+pub use lock::LockApi;
+pub use lock::LockMgr;
+pub use masking_policy::MaskingPolicyApi;
+pub use masking_policy::MaskingPolicyMgr;
+pub use network_policy::NetworkPolicyApi;
+pub use network_policy::NetworkPolicyMgr;
 pub use quota::QuotaApi;
 pub use quota::QuotaMgr;
 pub use role::RoleApi;
 pub use role::RoleMgr;
+pub use row_access_policy::RowAccessPolicyApi;
+pub use row_access_policy::RowAccessPolicyMgr;
 pub use serde::deserialize_struct;
 pub use serde::serialize_struct;
 pub use setting::SettingApi;
 pub use setting::SettingMgr;
 pub use stage::StageApi;
 pub use stage::StageMgr;
+pub use task::TaskApi;
+pub use task::TaskMgr;
 pub use udf::UdfApi;
 pub use udf::UdfMgr;
 pub use user::UserApi;
 pub use user::UserMgr;
+pub use workload_group::WorkloadGroupApi;
+pub use workload_group::WorkloadGroupMgr;
@@ -0,0 +1,29 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+/// Locks shared by the query nodes of a tenant, each lock is leased for a while and released
+/// automatically if its holder is gone.
+#[async_trait::async_trait]
+pub trait LockApi: Sync + Send {
+    // Acquire the lock for the holder, returns the seq of the lock, or None if it is held.
+    async fn try_lock(&self, name: &str, holder: &str) -> Result<Option<u64>>;
+
+    // Extend the lease of the lock acquired with the seq, returns the new seq.
+    async fn renew(&self, name: &str, seq: u64) -> Result<u64>;
+
+    // Release the lock acquired with the seq, does nothing if it has expired.
+    async fn unlock(&self, name: &str, seq: u64) -> Result<()>;
+}
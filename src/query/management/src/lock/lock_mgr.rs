@@ -0,0 +1,130 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Add;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use common_base::base::escape_for_key;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_store::MetaStore;
+use common_meta_types::KVMeta;
+use common_meta_types::MatchSeq;
+use common_meta_types::Operation;
+use common_meta_types::SeqV;
+use common_meta_types::UpsertKVReply;
+use common_meta_types::UpsertKVReq;
+
+use crate::lock::LockApi;
+
+static LOCK_API_KEY_PREFIX: &str = "__fd_locks";
+
+pub struct LockMgr {
+    metastore: MetaStore,
+    lease: Duration,
+    lock_prefix: String,
+}
+
+impl LockMgr {
+    pub fn create(metastore: MetaStore, tenant: &str, lease: Duration) -> Result<Self> {
+        if tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while lock mgr create)",
+            ));
+        }
+
+        Ok(LockMgr {
+            metastore,
+            lease,
+            lock_prefix: format!("{}/{}", LOCK_API_KEY_PREFIX, escape_for_key(tenant)?),
+        })
+    }
+
+    fn lock_key(&self, name: &str) -> Result<String> {
+        Ok(format!("{}/{}", self.lock_prefix, escape_for_key(name)?))
+    }
+
+    fn new_lease(&self) -> KVMeta {
+        let now = std::time::SystemTime::now();
+        let expire_at = now
+            .add(self.lease)
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards");
+
+        KVMeta {
+            expire_at: Some(expire_at.as_secs()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl LockApi for LockMgr {
+    async fn try_lock(&self, name: &str, holder: &str) -> Result<Option<u64>> {
+        // Only when there is no record, i.e. seq=0, an expired lock has no record.
+        let key = self.lock_key(name)?;
+        let value = Operation::Update(holder.as_bytes().to_vec());
+        let upsert_lock = self.metastore.upsert_kv(UpsertKVReq::new(
+            &key,
+            MatchSeq::Exact(0),
+            value,
+            Some(self.new_lease()),
+        ));
+
+        match upsert_lock.await? {
+            UpsertKVReply {
+                prev: None,
+                result: Some(SeqV { seq: s, .. }),
+                ..
+            } => Ok(Some(s)),
+            UpsertKVReply { .. } => Ok(None),
+        }
+    }
+
+    async fn renew(&self, name: &str, seq: u64) -> Result<u64> {
+        let key = self.lock_key(name)?;
+        let upsert_lock = self.metastore.upsert_kv(UpsertKVReq::new(
+            &key,
+            MatchSeq::Exact(seq),
+            Operation::AsIs,
+            Some(self.new_lease()),
+        ));
+
+        match upsert_lock.await? {
+            UpsertKVReply {
+                prev: Some(_),
+                result: Some(SeqV { seq: s, .. }),
+                ..
+            } => Ok(s),
+            UpsertKVReply { .. } => Err(ErrorCode::LockLost(format!(
+                "lock {} is expired or held by others",
+                name
+            ))),
+        }
+    }
+
+    async fn unlock(&self, name: &str, seq: u64) -> Result<()> {
+        let key = self.lock_key(name)?;
+        let upsert_lock = self.metastore.upsert_kv(UpsertKVReq::new(
+            &key,
+            MatchSeq::Exact(seq),
+            Operation::Delete,
+            None,
+        ));
+
+        upsert_lock.await?;
+        Ok(())
+    }
+}
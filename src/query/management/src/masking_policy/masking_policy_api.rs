@@ -0,0 +1,35 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_meta_types::MaskingPolicy;
+use common_meta_types::SeqV;
+
+#[async_trait::async_trait]
+pub trait MaskingPolicyApi: Sync + Send {
+    // Add a masking policy to /tenant/policy-name.
+    async fn add_policy(&self, policy: MaskingPolicy) -> Result<u64>;
+
+    // Update a masking policy, the columns it is attached to are changed by the update.
+    async fn update_policy(&self, policy: MaskingPolicy, seq: Option<u64>) -> Result<u64>;
+
+    // Get a masking policy by name.
+    async fn get_policy(&self, name: &str, seq: Option<u64>) -> Result<SeqV<MaskingPolicy>>;
+
+    // Get all the masking policies for a tenant.
+    async fn get_policies(&self) -> Result<Vec<MaskingPolicy>>;
+
+    // Drop the tenant's masking policy by name.
+    async fn drop_policy(&self, name: &str, seq: Option<u64>) -> Result<()>;
+}
@@ -0,0 +1,32 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_meta_types::NetworkPolicy;
+use common_meta_types::SeqV;
+
+#[async_trait::async_trait]
+pub trait NetworkPolicyApi: Sync + Send {
+    // Add a network policy to /tenant/policy-name.
+    async fn add_policy(&self, policy: NetworkPolicy) -> Result<u64>;
+
+    // Get a network policy by name.
+    async fn get_policy(&self, name: &str, seq: Option<u64>) -> Result<SeqV<NetworkPolicy>>;
+
+    // Get all the network policies for a tenant.
+    async fn get_policies(&self) -> Result<Vec<NetworkPolicy>>;
+
+    // Drop the tenant's network policy by name.
+    async fn drop_policy(&self, name: &str, seq: Option<u64>) -> Result<()>;
+}
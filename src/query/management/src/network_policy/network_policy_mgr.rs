@@ -0,0 +1,118 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::escape_for_key;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::IntoSeqV;
+use common_meta_types::MatchSeq;
+use common_meta_types::MatchSeqExt;
+use common_meta_types::NetworkPolicy;
+use common_meta_types::Operation;
+use common_meta_types::SeqV;
+use common_meta_types::UpsertKVReq;
+
+use crate::network_policy::NetworkPolicyApi;
+
+static NETWORK_POLICY_API_KEY_PREFIX: &str = "__fd_network_policies";
+
+pub struct NetworkPolicyMgr {
+    kv_api: Arc<dyn KVApi>,
+    policy_prefix: String,
+}
+
+impl NetworkPolicyMgr {
+    pub fn create(kv_api: Arc<dyn KVApi>, tenant: &str) -> Result<Self> {
+        if tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while network policy mgr create)",
+            ));
+        }
+
+        Ok(NetworkPolicyMgr {
+            kv_api,
+            policy_prefix: format!(
+                "{}/{}",
+                NETWORK_POLICY_API_KEY_PREFIX,
+                escape_for_key(tenant)?
+            ),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl NetworkPolicyApi for NetworkPolicyMgr {
+    async fn add_policy(&self, policy: NetworkPolicy) -> Result<u64> {
+        let seq = MatchSeq::Exact(0);
+        let val = Operation::Update(serde_json::to_vec(&policy)?);
+        let key = format!("{}/{}", self.policy_prefix, escape_for_key(&policy.name)?);
+        let upsert_info = self
+            .kv_api
+            .upsert_kv(UpsertKVReq::new(&key, seq, val, None));
+
+        let res = upsert_info.await?.added_or_else(|v| {
+            ErrorCode::NetworkPolicyAlreadyExists(format!(
+                "Network policy already exists, seq [{}]",
+                v.seq
+            ))
+        })?;
+
+        Ok(res.seq)
+    }
+
+    async fn get_policy(&self, name: &str, seq: Option<u64>) -> Result<SeqV<NetworkPolicy>> {
+        let key = format!("{}/{}", self.policy_prefix, escape_for_key(name)?);
+        let res = self.kv_api.get_kv(&key).await?;
+        let seq_value = res.ok_or_else(|| {
+            ErrorCode::UnknownNetworkPolicy(format!("Unknown network policy {}", name))
+        })?;
+
+        match MatchSeq::from(seq).match_seq(&seq_value) {
+            Ok(_) => Ok(seq_value.into_seqv()?),
+            Err(_) => Err(ErrorCode::UnknownNetworkPolicy(format!(
+                "Unknown network policy {}",
+                name
+            ))),
+        }
+    }
+
+    async fn get_policies(&self) -> Result<Vec<NetworkPolicy>> {
+        let values = self.kv_api.prefix_list_kv(&self.policy_prefix).await?;
+
+        let mut policies = Vec::with_capacity(values.len());
+        for (_, value) in values {
+            policies.push(NetworkPolicy::try_from(value.data)?);
+        }
+        Ok(policies)
+    }
+
+    async fn drop_policy(&self, name: &str, seq: Option<u64>) -> Result<()> {
+        let key = format!("{}/{}", self.policy_prefix, escape_for_key(name)?);
+        let res = self
+            .kv_api
+            .upsert_kv(UpsertKVReq::new(&key, seq.into(), Operation::Delete, None))
+            .await?;
+        if res.prev.is_some() && res.result.is_none() {
+            Ok(())
+        } else {
+            Err(ErrorCode::UnknownNetworkPolicy(format!(
+                "Unknown network policy {}",
+                name
+            )))
+        }
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_meta_types::SeqV;
+use common_meta_types::Task;
+use common_meta_types::TaskRun;
+
+#[async_trait::async_trait]
+pub trait TaskApi: Sync + Send {
+    // Add a task to /tenant/task-name.
+    async fn add_task(&self, task: Task) -> Result<u64>;
+
+    // Get a task by name.
+    async fn get_task(&self, name: &str, seq: Option<u64>) -> Result<SeqV<Task>>;
+
+    // Get all the tasks for a tenant.
+    async fn get_tasks(&self) -> Result<Vec<Task>>;
+
+    // Drop the tenant's task by name.
+    async fn drop_task(&self, name: &str, seq: Option<u64>) -> Result<()>;
+
+    // Add the run of a task for its fire time, returns the seq of the run, or None if the run
+    // is already added, by this node or by others.
+    async fn add_run(&self, run: TaskRun) -> Result<Option<u64>>;
+
+    // Update the run added with the seq, returns the new seq.
+    async fn update_run(&self, run: TaskRun, seq: u64) -> Result<u64>;
+
+    // Get the runs of all the tasks, the runs expire after the retention.
+    async fn get_runs(&self) -> Result<Vec<TaskRun>>;
+}
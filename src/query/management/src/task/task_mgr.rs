@@ -0,0 +1,183 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::escape_for_key;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::IntoSeqV;
+use common_meta_types::KVMeta;
+use common_meta_types::MatchSeq;
+use common_meta_types::MatchSeqExt;
+use common_meta_types::Operation;
+use common_meta_types::SeqV;
+use common_meta_types::Task;
+use common_meta_types::TaskRun;
+use common_meta_types::UpsertKVReply;
+use common_meta_types::UpsertKVReq;
+
+use crate::task::TaskApi;
+
+static TASK_API_KEY_PREFIX: &str = "__fd_tasks";
+static TASK_RUN_API_KEY_PREFIX: &str = "__fd_task_runs";
+
+/// The runs of the tasks are kept for 7 days.
+const TASK_RUN_RETENTION_SECS: u64 = 7 * 24 * 3600;
+
+pub struct TaskMgr {
+    kv_api: Arc<dyn KVApi>,
+    task_prefix: String,
+    run_prefix: String,
+}
+
+impl TaskMgr {
+    pub fn create(kv_api: Arc<dyn KVApi>, tenant: &str) -> Result<Self> {
+        if tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while task mgr create)",
+            ));
+        }
+
+        let tenant = escape_for_key(tenant)?;
+        Ok(TaskMgr {
+            kv_api,
+            task_prefix: format!("{}/{}", TASK_API_KEY_PREFIX, tenant),
+            run_prefix: format!("{}/{}", TASK_RUN_API_KEY_PREFIX, tenant),
+        })
+    }
+
+    fn run_key(&self, run: &TaskRun) -> Result<String> {
+        // the fire times are padded, so that the runs of a task are listed in order
+        Ok(format!(
+            "{}/{}/{:020}",
+            self.run_prefix,
+            escape_for_key(&run.task)?,
+            run.scheduled_time
+        ))
+    }
+
+    fn run_meta(run: &TaskRun) -> KVMeta {
+        KVMeta {
+            expire_at: Some(run.scheduled_time + TASK_RUN_RETENTION_SECS),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskApi for TaskMgr {
+    async fn add_task(&self, task: Task) -> Result<u64> {
+        let seq = MatchSeq::Exact(0);
+        let val = Operation::Update(serde_json::to_vec(&task)?);
+        let key = format!("{}/{}", self.task_prefix, escape_for_key(&task.name)?);
+        let upsert_info = self
+            .kv_api
+            .upsert_kv(UpsertKVReq::new(&key, seq, val, None));
+
+        let res = upsert_info.await?.added_or_else(|v| {
+            ErrorCode::TaskAlreadyExists(format!("Task already exists, seq [{}]", v.seq))
+        })?;
+
+        Ok(res.seq)
+    }
+
+    async fn get_task(&self, name: &str, seq: Option<u64>) -> Result<SeqV<Task>> {
+        let key = format!("{}/{}", self.task_prefix, escape_for_key(name)?);
+        let res = self.kv_api.get_kv(&key).await?;
+        let seq_value =
+            res.ok_or_else(|| ErrorCode::UnknownTask(format!("Unknown task {}", name)))?;
+
+        match MatchSeq::from(seq).match_seq(&seq_value) {
+            Ok(_) => Ok(seq_value.into_seqv()?),
+            Err(_) => Err(ErrorCode::UnknownTask(format!("Unknown task {}", name))),
+        }
+    }
+
+    async fn get_tasks(&self) -> Result<Vec<Task>> {
+        let values = self.kv_api.prefix_list_kv(&self.task_prefix).await?;
+
+        let mut tasks = Vec::with_capacity(values.len());
+        for (_, value) in values {
+            tasks.push(Task::try_from(value.data)?);
+        }
+        Ok(tasks)
+    }
+
+    async fn drop_task(&self, name: &str, seq: Option<u64>) -> Result<()> {
+        let key = format!("{}/{}", self.task_prefix, escape_for_key(name)?);
+        let res = self
+            .kv_api
+            .upsert_kv(UpsertKVReq::new(&key, seq.into(), Operation::Delete, None))
+            .await?;
+        if res.prev.is_some() && res.result.is_none() {
+            Ok(())
+        } else {
+            Err(ErrorCode::UnknownTask(format!("Unknown task {}", name)))
+        }
+    }
+
+    async fn add_run(&self, run: TaskRun) -> Result<Option<u64>> {
+        let key = self.run_key(&run)?;
+        let val = Operation::Update(serde_json::to_vec(&run)?);
+        let upsert_run = self.kv_api.upsert_kv(UpsertKVReq::new(
+            &key,
+            MatchSeq::Exact(0),
+            val,
+            Some(Self::run_meta(&run)),
+        ));
+
+        match upsert_run.await? {
+            UpsertKVReply {
+                prev: None,
+                result: Some(SeqV { seq: s, .. }),
+                ..
+            } => Ok(Some(s)),
+            UpsertKVReply { .. } => Ok(None),
+        }
+    }
+
+    async fn update_run(&self, run: TaskRun, seq: u64) -> Result<u64> {
+        let key = self.run_key(&run)?;
+        let val = Operation::Update(serde_json::to_vec(&run)?);
+        let upsert_run = self.kv_api.upsert_kv(UpsertKVReq::new(
+            &key,
+            MatchSeq::Exact(seq),
+            val,
+            Some(Self::run_meta(&run)),
+        ));
+
+        match upsert_run.await? {
+            UpsertKVReply {
+                prev: Some(_),
+                result: Some(SeqV { seq: s, .. }),
+                ..
+            } => Ok(s),
+            UpsertKVReply { .. } => Err(ErrorCode::UnknownTask(format!(
+                "The run of task {} at {} is expired or changed by others",
+                run.task, run.scheduled_time
+            ))),
+        }
+    }
+
+    async fn get_runs(&self) -> Result<Vec<TaskRun>> {
+        let values = self.kv_api.prefix_list_kv(&self.run_prefix).await?;
+
+        let mut runs = Vec::with_capacity(values.len());
+        for (_, value) in values {
+            runs.push(TaskRun::try_from(value.data)?);
+        }
+        Ok(runs)
+    }
+}
@@ -138,6 +138,21 @@ async fn test_successfully_heartbeat_node() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_heartbeat_node_reports_load() -> Result<()> {
+    let (_, cluster_api) = new_cluster_api().await?;
+
+    let mut node_info = create_test_node_info();
+    cluster_api.add_node(node_info.clone()).await?;
+
+    node_info.running_queries_count = 3;
+    cluster_api.heartbeat(&node_info, None).await?;
+
+    let nodes = cluster_api.get_nodes().await?;
+    assert_eq!(nodes, vec![node_info]);
+    Ok(())
+}
+
 fn current_seconds_time() -> u64 {
     let now = std::time::SystemTime::now();
     now.duration_since(UNIX_EPOCH)
@@ -151,6 +166,7 @@ fn create_test_node_info() -> NodeInfo {
         cpu_nums: 0,
         version: 0,
         flight_address: String::from("ip:port"),
+        running_queries_count: 0,
     }
 }
 
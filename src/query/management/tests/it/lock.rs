@@ -0,0 +1,71 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_exception::Result;
+use common_management::*;
+use common_meta_api::KVApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_store::MetaStore;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_lock_and_unlock() -> Result<()> {
+    let (kv_api, lock_api) = new_lock_api().await?;
+
+    let seq = lock_api.try_lock("compaction", "node_1").await?;
+    assert!(seq.is_some());
+
+    let value = kv_api.get_kv("__fd_locks/test%2dtenant/compaction").await?;
+    assert_eq!(value.unwrap().data, b"node_1".to_vec());
+
+    // the lock is held by node_1
+    assert_eq!(lock_api.try_lock("compaction", "node_2").await?, None);
+    // other locks are not affected
+    assert!(lock_api.try_lock("other", "node_2").await?.is_some());
+
+    lock_api.unlock("compaction", seq.unwrap()).await?;
+    assert!(lock_api.try_lock("compaction", "node_2").await?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_renew_lock() -> Result<()> {
+    let (_, lock_api) = new_lock_api().await?;
+
+    let seq = lock_api.try_lock("compaction", "node_1").await?.unwrap();
+    let new_seq = lock_api.renew("compaction", seq).await?;
+    assert!(new_seq > seq);
+
+    // the seq is outdated
+    match lock_api.renew("compaction", seq).await {
+        Ok(_) => panic!("Renew with an outdated seq must return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2995),
+    }
+
+    // unlocking with an outdated seq keeps the lock
+    lock_api.unlock("compaction", seq).await?;
+    assert_eq!(lock_api.try_lock("compaction", "node_2").await?, None);
+
+    Ok(())
+}
+
+async fn new_lock_api() -> Result<(MetaStore, LockMgr)> {
+    let test_api = MetaStore::L(Arc::new(MetaEmbedded::new_temp().await?));
+    let lock_manager = LockMgr::create(test_api.clone(), "test-tenant", Duration::from_secs(60))?;
+    Ok((test_api, lock_manager))
+}
@@ -13,7 +13,13 @@
 // limitations under the License.
 
 mod cluster;
+mod lock;
+mod masking_policy;
+mod network_policy;
+mod row_access_policy;
 mod setting;
 mod stage;
+mod task;
 mod udf;
 mod user;
+mod workload_group;
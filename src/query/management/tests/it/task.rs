@@ -0,0 +1,127 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_exception::Result;
+use common_management::*;
+use common_meta_api::KVApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_types::SeqV;
+use common_meta_types::Task;
+use common_meta_types::TaskRun;
+use common_meta_types::TaskRunState;
+use common_meta_types::UserIdentity;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_task() -> Result<()> {
+    let (kv_api, task_api) = new_task_api().await?;
+
+    let task = create_test_task();
+    task_api.add_task(task.clone()).await?;
+    let value = kv_api.get_kv("__fd_tasks/admin/refresh").await?;
+
+    match value {
+        Some(SeqV {
+            seq: 1,
+            meta: _,
+            data: value,
+        }) => {
+            assert_eq!(value, serde_json::to_vec(&task)?);
+        }
+        catch => panic!("GetKVActionReply{:?}", catch),
+    }
+
+    match task_api.add_task(task).await {
+        Ok(_) => panic!("Already exists add task must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2998),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_successfully_drop_task() -> Result<()> {
+    let (_, task_api) = new_task_api().await?;
+
+    let task = create_test_task();
+    task_api.add_task(task.clone()).await?;
+
+    let tasks = task_api.get_tasks().await?;
+    assert_eq!(tasks, vec![task.clone()]);
+
+    task_api.drop_task(&task.name, None).await?;
+
+    let tasks = task_api.get_tasks().await?;
+    assert_eq!(tasks, vec![]);
+
+    match task_api.drop_task(&task.name, None).await {
+        Ok(_) => panic!("Unknown task drop must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2997),
+    }
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_task_runs() -> Result<()> {
+    let (_, task_api) = new_task_api().await?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut run = TaskRun {
+        task: "refresh".to_string(),
+        definition: "OPTIMIZE TABLE t COMPACT".to_string(),
+        scheduled_time: now / 60 * 60,
+        start_time: now,
+        node: "node1".to_string(),
+        ..Default::default()
+    };
+
+    // the run of a fire time is added once
+    let seq = task_api.add_run(run.clone()).await?.unwrap();
+    assert_eq!(task_api.add_run(run.clone()).await?, None);
+
+    run.end_time = now + 1;
+    run.state = TaskRunState::Succeeded;
+    let new_seq = task_api.update_run(run.clone(), seq).await?;
+    assert!(task_api.update_run(run.clone(), seq).await.is_err());
+
+    let runs = task_api.get_runs().await?;
+    assert_eq!(runs, vec![run.clone()]);
+
+    run.state = TaskRunState::Failed;
+    task_api.update_run(run, new_seq).await?;
+    assert_eq!(task_api.get_runs().await?[0].state, TaskRunState::Failed);
+    Ok(())
+}
+
+fn create_test_task() -> Task {
+    Task {
+        name: "refresh".to_string(),
+        schedule: "0 * * * *".to_string(),
+        definition: "OPTIMIZE TABLE t COMPACT".to_string(),
+        owner: UserIdentity::new("admin", "%"),
+        error_notification: "".to_string(),
+        comment: "compact t hourly".to_string(),
+        created_on: 1665999000,
+    }
+}
+
+async fn new_task_api() -> Result<(Arc<MetaEmbedded>, TaskMgr)> {
+    let test_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let mgr = TaskMgr::create(test_api.clone(), "admin")?;
+    Ok((test_api, mgr))
+}
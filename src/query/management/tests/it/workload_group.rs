@@ -0,0 +1,88 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_exception::Result;
+use common_management::*;
+use common_meta_api::KVApi;
+use common_meta_embedded::MetaEmbedded;
+use common_meta_types::SeqV;
+use common_meta_types::WorkloadGroup;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_add_workload_group() -> Result<()> {
+    let (kv_api, group_api) = new_workload_group_api().await?;
+
+    let group = create_test_group();
+    group_api.add_group(group.clone()).await?;
+    let value = kv_api.get_kv("__fd_workload_groups/admin/etl").await?;
+
+    match value {
+        Some(SeqV {
+            seq: 1,
+            meta: _,
+            data: value,
+        }) => {
+            assert_eq!(value, serde_json::to_vec(&group)?);
+        }
+        catch => panic!("GetKVActionReply{:?}", catch),
+    }
+
+    match group_api.add_group(group).await {
+        Ok(_) => panic!("Already exists add workload group must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2983),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_successfully_drop_workload_group() -> Result<()> {
+    let (_, group_api) = new_workload_group_api().await?;
+
+    let group = create_test_group();
+    group_api.add_group(group.clone()).await?;
+
+    let groups = group_api.get_groups().await?;
+    assert_eq!(groups, vec![group.clone()]);
+
+    group_api.drop_group(&group.name, None).await?;
+
+    let groups = group_api.get_groups().await?;
+    assert_eq!(groups, vec![]);
+
+    match group_api.drop_group(&group.name, None).await {
+        Ok(_) => panic!("Unknown workload group drop must be return Err."),
+        Err(cause) => assert_eq!(cause.code(), 2982),
+    }
+    Ok(())
+}
+
+fn create_test_group() -> WorkloadGroup {
+    WorkloadGroup {
+        name: "etl".to_string(),
+        cpu_share: 50,
+        memory_limit: 4 * 1024 * 1024 * 1024,
+        max_concurrency: 2,
+        max_scan_bytes: 0,
+        comment: "etl jobs".to_string(),
+    }
+}
+
+async fn new_workload_group_api() -> Result<(Arc<MetaEmbedded>, WorkloadGroupMgr)> {
+    let test_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let mgr = WorkloadGroupMgr::create(test_api.clone(), "admin")?;
+    Ok((test_api, mgr))
+}
@@ -17,6 +17,7 @@ pub mod processors;
 pub mod pipe;
 pub mod pipeline;
 pub mod pipeline_display;
+pub mod pipeline_profile;
 pub mod unsafe_cell_wrap;
 
 pub use pipe::Pipe;
@@ -24,3 +25,4 @@ pub use pipe::SinkPipeBuilder;
 pub use pipe::SourcePipeBuilder;
 pub use pipe::TransformPipeBuilder;
 pub use pipeline::Pipeline;
+pub use pipeline_profile::PipelineProfile;
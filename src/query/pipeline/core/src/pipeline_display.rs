@@ -28,21 +28,10 @@ struct PipelineIndentDisplayWrapper<'a> {
     pipeline: &'a Pipeline,
 }
 
-impl<'a> PipelineIndentDisplayWrapper<'a> {
-    fn pipe_name(pipe: &Pipe) -> &'static str {
-        unsafe {
-            match pipe {
-                Pipe::SimplePipe { processors, .. } => processors[0].name(),
-                Pipe::ResizePipe { processor, .. } => processor.name(),
-            }
-        }
-    }
-}
-
 impl<'a> Display for PipelineIndentDisplayWrapper<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let pipes = &self.pipeline.pipes;
-        for (index, pipe) in pipes.iter().rev().enumerate() {
+        for (index, position) in (0..pipes.len()).rev().enumerate() {
             if index > 0 {
                 writeln!(f)?;
             }
@@ -51,45 +40,73 @@ impl<'a> Display for PipelineIndentDisplayWrapper<'a> {
                 write!(f, "  ")?;
             }
 
-            match pipe {
-                Pipe::SimplePipe { processors, .. } => {
-                    write!(
-                        f,
-                        "{} × {} {}",
-                        Self::pipe_name(pipe),
-                        processors.len(),
-                        if processors.len() == 1 {
-                            "processor"
-                        } else {
-                            "processors"
-                        },
-                    )?;
-                }
-                Pipe::ResizePipe {
-                    inputs_port,
-                    outputs_port,
-                    ..
-                } => {
-                    let prev_name = Self::pipe_name(&pipes[pipes.len() - index - 2]);
-                    let post_name = Self::pipe_name(&pipes[pipes.len() - index]);
+            write!(f, "{}", PipeDisplay::create(pipes, position))?;
+        }
+
+        Ok(())
+    }
+}
 
-                    write!(
-                        f,
-                        "Merge ({} × {} {}) to ({} × {})",
-                        prev_name,
-                        inputs_port.len(),
-                        if inputs_port.len() == 1 {
-                            "processor"
-                        } else {
-                            "processors"
-                        },
-                        post_name,
-                        outputs_port.len(),
-                    )?;
-                }
+/// The display of the pipe at `position` of the pipes.
+pub(crate) struct PipeDisplay<'a> {
+    pipes: &'a [Pipe],
+    position: usize,
+}
+
+impl<'a> PipeDisplay<'a> {
+    pub fn create(pipes: &'a [Pipe], position: usize) -> PipeDisplay<'a> {
+        PipeDisplay { pipes, position }
+    }
+
+    fn pipe_name(pipe: &Pipe) -> &'static str {
+        unsafe {
+            match pipe {
+                Pipe::SimplePipe { processors, .. } => processors[0].name(),
+                Pipe::ResizePipe { processor, .. } => processor.name(),
             }
         }
+    }
+}
 
-        Ok(())
+impl<'a> Display for PipeDisplay<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let pipe = &self.pipes[self.position];
+        match pipe {
+            Pipe::SimplePipe { processors, .. } => {
+                write!(
+                    f,
+                    "{} × {} {}",
+                    Self::pipe_name(pipe),
+                    processors.len(),
+                    if processors.len() == 1 {
+                        "processor"
+                    } else {
+                        "processors"
+                    },
+                )
+            }
+            Pipe::ResizePipe {
+                inputs_port,
+                outputs_port,
+                ..
+            } => {
+                let prev_name = Self::pipe_name(&self.pipes[self.position - 1]);
+                let post_name = Self::pipe_name(&self.pipes[self.position + 1]);
+
+                write!(
+                    f,
+                    "Merge ({} × {} {}) to ({} × {})",
+                    prev_name,
+                    inputs_port.len(),
+                    if inputs_port.len() == 1 {
+                        "processor"
+                    } else {
+                        "processors"
+                    },
+                    post_name,
+                    outputs_port.len(),
+                )
+            }
+        }
     }
 }
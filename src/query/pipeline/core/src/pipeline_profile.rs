@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use crate::pipeline_display::PipeDisplay;
+use crate::processors::ProcessorProfile;
+use crate::Pipe;
+use crate::Pipeline;
+
+/// The runtime statistics of every pipe of a pipeline, they are still available after the
+/// pipeline is consumed by the executor.
+pub struct PipelineProfile {
+    pipes: Vec<(String, Arc<ProcessorProfile>)>,
+}
+
+impl Pipeline {
+    /// Collect the runtime statistics of the processors while the pipeline is executed, the
+    /// statistics of the processors in the same pipe are summed up.
+    pub fn enable_profiling(&self) -> PipelineProfile {
+        let pipes = self.pipes.iter().enumerate().map(|(position, pipe)| {
+            let profile = ProcessorProfile::create();
+            unsafe {
+                match pipe {
+                    Pipe::SimplePipe {
+                        processors,
+                        outputs_port,
+                        ..
+                    } => {
+                        processors
+                            .iter()
+                            .for_each(|processor| processor.set_profile(profile.clone()));
+                        outputs_port
+                            .iter()
+                            .for_each(|port| port.set_profile(profile.clone()));
+                    }
+                    Pipe::ResizePipe {
+                        processor,
+                        outputs_port,
+                        ..
+                    } => {
+                        processor.set_profile(profile.clone());
+                        outputs_port
+                            .iter()
+                            .for_each(|port| port.set_profile(profile.clone()));
+                    }
+                }
+            }
+
+            (
+                PipeDisplay::create(&self.pipes, position).to_string(),
+                profile,
+            )
+        });
+
+        PipelineProfile {
+            pipes: pipes.collect(),
+        }
+    }
+}
+
+impl PipelineProfile {
+    pub fn display_indent(&self) -> impl Display + '_ {
+        PipelineProfileIndentDisplayWrapper { profile: self }
+    }
+}
+
+struct PipelineProfileIndentDisplayWrapper<'a> {
+    profile: &'a PipelineProfile,
+}
+
+impl<'a> Display for PipelineProfileIndentDisplayWrapper<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, (pipe, profile)) in self.profile.pipes.iter().rev().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            for _ in 0..index {
+                write!(f, "  ")?;
+            }
+
+            write!(f, "{} [{}]", pipe, profile)?;
+        }
+
+        Ok(())
+    }
+}
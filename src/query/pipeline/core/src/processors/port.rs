@@ -20,6 +20,7 @@ use common_datablocks::DataBlock;
 use common_exception::Result;
 use common_io::prelude::FileSplit;
 
+use crate::processors::ProcessorProfile;
 use crate::processors::UpdateTrigger;
 use crate::unsafe_cell_wrap::UnSafeCellWrap;
 
@@ -219,6 +220,7 @@ impl InputPort {
 pub struct OutputPort {
     shared: UnSafeCellWrap<Arc<SharedStatus>>,
     update_trigger: UnSafeCellWrap<*mut UpdateTrigger>,
+    profile: UnSafeCellWrap<Option<Arc<ProcessorProfile>>>,
 }
 
 impl OutputPort {
@@ -226,6 +228,7 @@ impl OutputPort {
         Arc::new(OutputPort {
             shared: UnSafeCellWrap::create(SharedStatus::create()),
             update_trigger: UnSafeCellWrap::create(std::ptr::null_mut()),
+            profile: UnSafeCellWrap::create(None),
         })
     }
 
@@ -234,6 +237,10 @@ impl OutputPort {
         unsafe {
             UpdateTrigger::update_output(&self.update_trigger);
 
+            if let (Some(profile), Ok(block)) = (&*self.profile, &data) {
+                profile.add_output(block);
+            }
+
             let data = Box::into_raw(Box::new(SharedData::Data(data)));
             self.shared.swap(data, HAS_DATA, HAS_DATA);
         }
@@ -284,6 +291,13 @@ impl OutputPort {
     pub unsafe fn set_trigger(&self, update_trigger: *mut UpdateTrigger) {
         self.update_trigger.set_value(update_trigger)
     }
+
+    /// # Safety
+    ///
+    /// Method is thread unsafe and require thread safe call
+    pub unsafe fn set_profile(&self, profile: Arc<ProcessorProfile>) {
+        self.profile.set_value(Some(profile))
+    }
 }
 
 /// Connect input and output ports.
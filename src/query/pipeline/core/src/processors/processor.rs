@@ -15,6 +15,7 @@
 use std::any::Any;
 use std::cell::UnsafeCell;
 use std::sync::Arc;
+use std::time::Instant;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -23,6 +24,8 @@ use futures::FutureExt;
 use petgraph::graph::node_index;
 use petgraph::prelude::NodeIndex;
 
+use crate::processors::ProcessorProfile;
+
 pub enum Event {
     NeedData,
     NeedConsume,
@@ -56,6 +59,7 @@ pub trait Processor: Send {
 pub struct ProcessorPtr {
     id: Arc<UnsafeCell<NodeIndex>>,
     inner: Arc<UnsafeCell<Box<dyn Processor>>>,
+    profile: Arc<UnsafeCell<Option<Arc<ProcessorProfile>>>>,
 }
 
 unsafe impl Send for ProcessorPtr {}
@@ -67,6 +71,7 @@ impl ProcessorPtr {
         ProcessorPtr {
             id: Arc::new(UnsafeCell::new(node_index(0))),
             inner: Arc::new(UnsafeCell::new(inner)),
+            profile: Arc::new(UnsafeCell::new(None)),
         }
     }
 
@@ -85,6 +90,11 @@ impl ProcessorPtr {
         *self.id.get() = id;
     }
 
+    /// # Safety
+    pub unsafe fn set_profile(&self, profile: Arc<ProcessorProfile>) {
+        *self.profile.get() = Some(profile);
+    }
+
     /// # Safety
     pub unsafe fn name(&self) -> &'static str {
         (*self.inner.get()).name()
@@ -97,11 +107,26 @@ impl ProcessorPtr {
 
     /// # Safety
     pub unsafe fn process(&self) -> Result<()> {
-        (*self.inner.get()).process()
+        match &*self.profile.get() {
+            None => (*self.inner.get()).process(),
+            Some(profile) => profile.measure(|| (*self.inner.get()).process()),
+        }
     }
 
     /// # Safety
     pub unsafe fn async_process(&self) -> BoxFuture<'static, Result<()>> {
-        (*self.inner.get()).async_process().boxed()
+        let future = (*self.inner.get()).async_process();
+
+        match (*self.profile.get()).clone() {
+            None => future.boxed(),
+            // The async work may be moved between threads, only its wall time is recorded.
+            Some(profile) => async move {
+                let instant = Instant::now();
+                let res = future.await;
+                profile.add_wall_time(instant.elapsed());
+                res
+            }
+            .boxed(),
+        }
     }
 }
@@ -0,0 +1,159 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_datablocks::DataBlock;
+use common_io::prelude::convert_byte_size;
+
+thread_local! {
+    static CURRENT_PROFILE: RefCell<Option<Arc<ProcessorProfile>>> = RefCell::new(None);
+}
+
+/// The runtime statistics of processors, collected while the pipeline is executed.
+///
+/// The times are summed up over all the processors sharing the profile, so they may be longer
+/// than the elapsed time of the query if the processors run in parallel.
+#[derive(Default)]
+pub struct ProcessorProfile {
+    wall_time_ns: AtomicU64,
+    cpu_time_ns: AtomicU64,
+    output_rows: AtomicU64,
+    output_bytes: AtomicU64,
+    spilled_bytes: AtomicU64,
+    spilled_files: AtomicU64,
+}
+
+impl ProcessorProfile {
+    pub fn create() -> Arc<ProcessorProfile> {
+        Arc::new(ProcessorProfile::default())
+    }
+
+    /// Run the synchronous work of a processor, the spilled data during it is recorded too.
+    pub fn measure<T>(self: &Arc<Self>, f: impl FnOnce() -> T) -> T {
+        let previous = CURRENT_PROFILE.with(|current| current.replace(Some(self.clone())));
+        let instant = Instant::now();
+        let cpu_time = thread_cpu_time();
+
+        let res = f();
+
+        self.add_cpu_time(thread_cpu_time().saturating_sub(cpu_time));
+        self.add_wall_time(instant.elapsed());
+        CURRENT_PROFILE.with(|current| current.replace(previous));
+        res
+    }
+
+    pub fn add_wall_time(&self, elapsed: Duration) {
+        self.wall_time_ns
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn add_cpu_time(&self, elapsed: Duration) {
+        self.cpu_time_ns
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn add_output(&self, block: &DataBlock) {
+        self.output_rows
+            .fetch_add(block.num_rows() as u64, Ordering::Relaxed);
+        self.output_bytes
+            .fetch_add(block.memory_size() as u64, Ordering::Relaxed);
+    }
+
+    pub fn add_spill(&self, bytes: usize) {
+        self.spilled_bytes
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+        self.spilled_files.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a file of `bytes` spilled by the processor running in the current thread, if it's
+    /// profiled.
+    pub fn record_spill(bytes: usize) {
+        CURRENT_PROFILE.with(|current| {
+            if let Some(profile) = current.borrow().as_ref() {
+                profile.add_spill(bytes);
+            }
+        })
+    }
+
+    pub fn get_wall_time(&self) -> Duration {
+        Duration::from_nanos(self.wall_time_ns.load(Ordering::Relaxed))
+    }
+
+    pub fn get_cpu_time(&self) -> Duration {
+        Duration::from_nanos(self.cpu_time_ns.load(Ordering::Relaxed))
+    }
+
+    pub fn get_output_rows(&self) -> u64 {
+        self.output_rows.load(Ordering::Relaxed)
+    }
+
+    pub fn get_output_bytes(&self) -> u64 {
+        self.output_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn get_spilled_bytes(&self) -> u64 {
+        self.spilled_bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn get_spilled_files(&self) -> u64 {
+        self.spilled_files.load(Ordering::Relaxed)
+    }
+}
+
+impl Display for ProcessorProfile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rows: {}, bytes: {}, wall time: {:.2?}, cpu time: {:.2?}",
+            self.get_output_rows(),
+            convert_byte_size(self.get_output_bytes() as f64),
+            self.get_wall_time(),
+            self.get_cpu_time(),
+        )?;
+
+        if self.get_spilled_files() != 0 {
+            write!(
+                f,
+                ", spilled: {} in {} files",
+                convert_byte_size(self.get_spilled_bytes() as f64),
+                self.get_spilled_files(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The CPU time consumed by the current thread.
+fn thread_cpu_time() -> Duration {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // It never fails with a valid clock id and pointer, and the time stays zero if it does.
+    unsafe {
+        libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time);
+    }
+
+    Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+}
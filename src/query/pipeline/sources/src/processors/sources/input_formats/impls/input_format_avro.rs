@@ -0,0 +1,497 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_datavalues::TypeDeserializer;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_formats::avro::Reader;
+use common_formats::avro::RecordField;
+use common_formats::avro::Schema;
+use common_formats::avro::Value;
+use common_io::prelude::FormatSettings;
+use common_pipeline_core::Pipeline;
+use common_settings::Settings;
+use opendal::Object;
+
+use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
+use crate::processors::sources::input_formats::input_context::InputContext;
+use crate::processors::sources::input_formats::input_format::FileInfo;
+use crate::processors::sources::input_formats::input_format::InputData;
+use crate::processors::sources::input_formats::input_format::SplitInfo;
+use crate::processors::sources::input_formats::input_format_text::get_time_zone;
+use crate::processors::sources::input_formats::input_pipeline::AligningStateTrait;
+use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::RowBatchTrait;
+use crate::processors::sources::input_formats::InputFormat;
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// Avro object container files, the records of which are loaded as rows.
+///
+/// Columns are matched with the fields of the records by name, the ones missing from the file are
+/// filled with nulls. Values are converted to the types of their columns, logical types included.
+pub struct InputFormatAvro;
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatAvro {
+    fn get_format_settings(&self, settings: &Arc<Settings>) -> Result<FormatSettings> {
+        let timezone = get_time_zone(settings)?;
+        Ok(FormatSettings {
+            timezone,
+            ..Default::default()
+        })
+    }
+
+    fn default_record_delimiter(&self) -> RecordDelimiter {
+        RecordDelimiter::Crlf
+    }
+
+    fn default_field_delimiter(&self) -> u8 {
+        b'_'
+    }
+
+    async fn read_file_meta(
+        &self,
+        _obj: &Object,
+        _size: usize,
+    ) -> Result<Option<Arc<dyn InputData>>> {
+        Ok(None)
+    }
+
+    async fn read_split_meta(
+        &self,
+        _obj: &Object,
+        _split_info: &SplitInfo,
+    ) -> Result<Option<Box<dyn InputData>>> {
+        Ok(None)
+    }
+
+    fn split_files(&self, file_infos: Vec<FileInfo>, _split_size: usize) -> Vec<SplitInfo> {
+        file_infos
+            .into_iter()
+            .map(SplitInfo::from_file_info)
+            .collect()
+    }
+
+    fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        AvroFormatPipe::execute_copy_with_aligner(ctx, pipeline)
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        AvroFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+pub struct AvroFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for AvroFormatPipe {
+    type ReadBatch = Vec<u8>;
+    type RowBatch = RecordBatch;
+    type AligningState = AligningState;
+    type BlockBuilder = AvroBlockBuilder;
+}
+
+/// Records decoded from a container file, to be converted into a block.
+pub struct RecordBatch {
+    path: String,
+    /// Index in the file of the first record
+    start_row: usize,
+    /// Fields of the records, as written to the file
+    fields: Arc<Vec<RecordField>>,
+    /// Index of the field of each column, none if the column is missing from the file
+    field_indexes: Arc<Vec<Option<usize>>>,
+    records: Vec<Value>,
+}
+
+impl RowBatchTrait for RecordBatch {
+    fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Debug for RecordBatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordBatch")
+            .field("path", &self.path)
+            .field("start_row", &self.start_row)
+            .field("records", &self.records.len())
+            .finish()
+    }
+}
+
+pub struct AvroBlockBuilder {
+    ctx: Arc<InputContext>,
+}
+
+impl BlockBuilderTrait for AvroBlockBuilder {
+    type Pipe = AvroFormatPipe;
+
+    fn create(ctx: Arc<InputContext>) -> Self {
+        AvroBlockBuilder { ctx }
+    }
+
+    fn deserialize(&mut self, batch: Option<RecordBatch>) -> Result<Vec<DataBlock>> {
+        let batch = match batch {
+            Some(batch) => batch,
+            None => return Ok(vec![]),
+        };
+
+        let schema = &self.ctx.schema;
+        let format_settings = &self.ctx.format_settings;
+        let mut deserializers = schema.create_deserializers(batch.records.len());
+        for (row, record) in batch.records.into_iter().enumerate() {
+            let mut values = match record {
+                Value::Record(values) => values,
+                value => {
+                    return Err(ErrorCode::BadBytes(format!(
+                        "fail to parse AVRO: record expected, got {:?}, path={}, row={}",
+                        value,
+                        batch.path,
+                        batch.start_row + row
+                    )));
+                }
+            };
+
+            for ((field, deserializer), index) in schema
+                .fields()
+                .iter()
+                .zip(deserializers.iter_mut())
+                .zip(batch.field_indexes.iter())
+            {
+                let index = match index {
+                    Some(index) => *index,
+                    None => {
+                        deserializer.de_default(format_settings);
+                        continue;
+                    }
+                };
+
+                let value = std::mem::replace(&mut values[index].1, Value::Null);
+                let writer_schema = &batch.fields[index].schema;
+                to_data_value(value, writer_schema, field.data_type())
+                    .and_then(|value| deserializer.append_data_value(value, format_settings))
+                    .map_err(|e| {
+                        ErrorCode::BadBytes(format!(
+                            "fail to parse AVRO: {}, path={}, row={}, column={}",
+                            e,
+                            batch.path,
+                            batch.start_row + row,
+                            field.name()
+                        ))
+                    })?;
+            }
+        }
+
+        let columns = deserializers
+            .iter_mut()
+            .map(|deserializer| deserializer.finish_to_column())
+            .collect();
+        Ok(vec![DataBlock::create(schema.clone(), columns)])
+    }
+}
+
+pub struct AligningState {
+    ctx: Arc<InputContext>,
+    split_info: SplitInfo,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl AligningStateTrait for AligningState {
+    type Pipe = AvroFormatPipe;
+
+    fn try_create(ctx: &Arc<InputContext>, split_info: &SplitInfo) -> Result<Self> {
+        Ok(AligningState {
+            ctx: ctx.clone(),
+            split_info: split_info.clone(),
+            buffers: vec![],
+        })
+    }
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<RecordBatch>> {
+        if let Some(buffer) = read_batch {
+            self.buffers.push(buffer);
+            return Ok(vec![]);
+        }
+
+        // blocks of a container file can't be decoded without the schema in its header
+        let file_in_memory = self.buffers.concat();
+        let path = self.split_info.file_info.path.clone();
+        let reader = Reader::new(file_in_memory.as_slice())
+            .map_err(|e| ErrorCode::BadBytes(format!("fail to read AVRO file {}: {}", path, e)))?;
+        let fields = match reader.writer_schema() {
+            Schema::Record { fields, .. } => Arc::new(fields.clone()),
+            schema => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "schema of AVRO file {} should be a record, got {:?}",
+                    path, schema
+                )));
+            }
+        };
+        let field_indexes = Arc::new(resolve_fields(&fields, &self.ctx.schema)?);
+
+        let rows_per_block = std::cmp::max(self.ctx.rows_per_block, 1);
+        let mut batches = vec![];
+        let mut records = Vec::with_capacity(rows_per_block);
+        let mut start_row = 0;
+        for record in reader {
+            let record = record.map_err(|e| {
+                ErrorCode::BadBytes(format!("fail to read AVRO file {}: {}", path, e))
+            })?;
+            records.push(record);
+
+            if records.len() == rows_per_block {
+                batches.push(RecordBatch {
+                    path: path.clone(),
+                    start_row,
+                    fields: fields.clone(),
+                    field_indexes: field_indexes.clone(),
+                    records: std::mem::replace(&mut records, Vec::with_capacity(rows_per_block)),
+                });
+                start_row += rows_per_block;
+            }
+        }
+        if !records.is_empty() {
+            batches.push(RecordBatch {
+                path: path.clone(),
+                start_row,
+                fields,
+                field_indexes,
+                records,
+            });
+        }
+
+        tracing::info!(
+            "align AVRO file {} of {} bytes to {} batches",
+            path,
+            file_in_memory.len(),
+            batches.len()
+        );
+        Ok(batches)
+    }
+}
+
+/// Resolve the columns of the table with the fields written to the file, by name.
+///
+/// Columns missing from the file are filled with nulls, so they must be nullable.
+fn resolve_fields(fields: &[RecordField], schema: &DataSchemaRef) -> Result<Vec<Option<usize>>> {
+    schema
+        .fields()
+        .iter()
+        .map(|column| {
+            let index = fields
+                .iter()
+                .position(|f| f.name == *column.name())
+                .or_else(|| {
+                    fields
+                        .iter()
+                        .position(|f| f.name.eq_ignore_ascii_case(column.name()))
+                });
+            match index {
+                Some(index) => Ok(Some(index)),
+                None if column.is_nullable() => Ok(None),
+                None => Err(ErrorCode::BadBytes(format!(
+                    "schema field size mismatch, expected to find column: {}",
+                    column.name()
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// Convert a value decoded with the schema `schema` into a value of the type `data_type`.
+fn to_data_value(value: Value, schema: &Schema, data_type: &DataTypeImpl) -> Result<DataValue> {
+    let data_type = remove_nullable(data_type);
+    if let Value::Union(index, value) = value {
+        let schema = match schema {
+            Schema::Union(variants) => variants.get(index as usize).unwrap_or(schema),
+            _ => schema,
+        };
+        return to_data_value(*value, schema, &data_type);
+    }
+
+    if matches!(
+        data_type,
+        DataTypeImpl::Variant(_) | DataTypeImpl::VariantArray(_) | DataTypeImpl::VariantObject(_)
+    ) && !matches!(value, Value::Null)
+    {
+        let json = serde_json::Value::from(value);
+        return Ok(DataValue::Variant(json.into()));
+    }
+
+    let value = match value {
+        Value::Null => DataValue::Null,
+        Value::Boolean(v) => DataValue::Boolean(v),
+        Value::Int(v) => DataValue::Int64(v as i64),
+        Value::Long(v) => DataValue::Int64(v),
+        Value::Float(v) => DataValue::Float64(v as f64),
+        Value::Double(v) => DataValue::Float64(v),
+        Value::Bytes(v) | Value::Fixed(_, v) => DataValue::String(v),
+        Value::String(v) | Value::Enum(_, v) | Value::Uuid(v) => DataValue::String(v.into_bytes()),
+        Value::Date(days) => match data_type {
+            DataTypeImpl::Timestamp(_) => DataValue::Int64(days as i64 * MICROS_PER_DAY),
+            _ => DataValue::Int64(days as i64),
+        },
+        // time of day, in microseconds
+        Value::TimeMillis(v) => DataValue::Int64(v as i64 * 1000),
+        Value::TimeMicros(v) => DataValue::Int64(v),
+        Value::TimestampMillis(v) => timestamp_to_data_value(v * 1000, &data_type),
+        Value::TimestampMicros(v) => timestamp_to_data_value(v, &data_type),
+        Value::Decimal(bytes) => {
+            let scale = match schema {
+                Schema::Decimal { scale, .. } => *scale,
+                _ => 0,
+            };
+            decimal_to_data_value(&bytes, scale, &data_type)?
+        }
+        Value::Array(items) => {
+            let (item_schema, item_type) = match (schema, &data_type) {
+                (Schema::Array(item_schema), DataTypeImpl::Array(array)) => {
+                    (item_schema.as_ref(), array.inner_type())
+                }
+                _ => {
+                    return Err(ErrorCode::BadBytes(format!(
+                        "array can't be converted to {:?}",
+                        data_type
+                    )));
+                }
+            };
+            let items = items
+                .into_iter()
+                .map(|item| to_data_value(item, item_schema, item_type))
+                .collect::<Result<Vec<_>>>()?;
+            DataValue::Array(items)
+        }
+        Value::Record(values) => {
+            let (fields, struct_type) = match (schema, &data_type) {
+                (Schema::Record { fields, .. }, DataTypeImpl::Struct(struct_type)) => {
+                    (fields, struct_type)
+                }
+                _ => {
+                    return Err(ErrorCode::BadBytes(format!(
+                        "record can't be converted to {:?}",
+                        data_type
+                    )));
+                }
+            };
+            record_to_data_value(values, fields, struct_type.names(), struct_type.types())?
+        }
+        value => {
+            return Err(ErrorCode::BadBytes(format!(
+                "{:?} can't be converted to {:?}",
+                value, data_type
+            )));
+        }
+    };
+    Ok(value)
+}
+
+fn timestamp_to_data_value(micros: i64, data_type: &DataTypeImpl) -> DataValue {
+    match data_type {
+        DataTypeImpl::Date(_) => DataValue::Int64(micros.div_euclid(MICROS_PER_DAY)),
+        _ => DataValue::Int64(micros),
+    }
+}
+
+/// Convert the unscaled value of a decimal, in big-endian two's-complement.
+fn decimal_to_data_value(
+    bytes: &[u8],
+    scale: usize,
+    data_type: &DataTypeImpl,
+) -> Result<DataValue> {
+    if bytes.len() > 16 {
+        return Err(ErrorCode::BadBytes(format!(
+            "decimal of {} bytes is too large",
+            bytes.len()
+        )));
+    }
+
+    let sign = match bytes.first() {
+        Some(b) if b & 0x80 != 0 => -1,
+        _ => 0,
+    };
+    let unscaled = bytes
+        .iter()
+        .fold(sign as i128, |v, b| (v << 8) | *b as i128);
+
+    match data_type {
+        DataTypeImpl::Float32(_) | DataTypeImpl::Float64(_) => Ok(DataValue::Float64(
+            unscaled as f64 / 10f64.powi(scale as i32),
+        )),
+        DataTypeImpl::String(_) => Ok(DataValue::String(
+            format_decimal(unscaled, scale).into_bytes(),
+        )),
+        _ if scale == 0 => match i64::try_from(unscaled) {
+            Ok(v) => Ok(DataValue::Int64(v)),
+            Err(_) => Err(ErrorCode::BadBytes(format!(
+                "decimal {} is out of range",
+                unscaled
+            ))),
+        },
+        _ => Err(ErrorCode::BadBytes(format!(
+            "decimal with scale {} can't be converted to {:?}",
+            scale, data_type
+        ))),
+    }
+}
+
+fn format_decimal(unscaled: i128, scale: usize) -> String {
+    let sign = if unscaled < 0 { "-" } else { "" };
+    let digits = unscaled.unsigned_abs().to_string();
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+fn record_to_data_value(
+    values: Vec<(String, Value)>,
+    fields: &[RecordField],
+    names: &Option<Vec<String>>,
+    types: &[DataTypeImpl],
+) -> Result<DataValue> {
+    let mut values = values.into_iter().map(Some).collect::<Vec<_>>();
+    let mut struct_values = Vec::with_capacity(types.len());
+    for (i, data_type) in types.iter().enumerate() {
+        // fields of unnamed tuples are matched by position
+        let index = match names {
+            Some(names) => fields
+                .iter()
+                .position(|f| f.name.eq_ignore_ascii_case(&names[i])),
+            None => (i < fields.len()).then_some(i),
+        };
+        let value = match index {
+            Some(index) => match values[index].take() {
+                Some((_, value)) => to_data_value(value, &fields[index].schema, data_type)?,
+                None => DataValue::Null,
+            },
+            None => DataValue::Null,
+        };
+        struct_values.push(value);
+    }
+    Ok(DataValue::Struct(struct_values))
+}
@@ -28,6 +28,7 @@ use csv_core::ReadRecordResult;
 use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
 use crate::processors::sources::input_formats::impls::input_format_tsv::format_column_error;
 use crate::processors::sources::input_formats::input_format_text::get_time_zone;
+use crate::processors::sources::input_formats::input_format_text::rollback_row;
 use crate::processors::sources::input_formats::input_format_text::AligningState;
 use crate::processors::sources::input_formats::input_format_text::BlockBuilder;
 use crate::processors::sources::input_formats::input_format_text::InputFormatTextBase;
@@ -46,21 +47,23 @@ impl InputFormatCSV {
         row_index: usize,
     ) -> Result<()> {
         let mut field_start = 0;
-        for (c, deserializer) in deserializers.iter_mut().enumerate() {
+        for c in 0..deserializers.len() {
             let field_end = field_ends[c];
             let col_data = &buf[field_start..field_end];
             let mut reader = NestedCheckpointReader::new(col_data);
             reader.ignore_white_spaces().expect("must success");
             if reader.eof().expect("must success") {
-                deserializer.de_default(format_settings);
+                deserializers[c].de_default(format_settings);
             } else {
                 // todo(youngsofun): do not need escape, already done in csv-core
-                if let Err(e) = deserializer.de_text(&mut reader, format_settings) {
+                if let Err(e) = deserializers[c].de_text(&mut reader, format_settings) {
+                    rollback_row(&mut deserializers[..c])?;
                     let err_msg = format_column_error(c, col_data, &e.message());
                     return Err(csv_error(&err_msg, path, row_index));
                 };
                 reader.ignore_white_spaces().expect("must success");
                 if reader.must_eof().is_err() {
+                    rollback_row(&mut deserializers[..=c])?;
                     let err_msg = format_column_error(c, col_data, "bad field end");
                     return Err(csv_error(&err_msg, path, row_index));
                 }
@@ -69,6 +72,20 @@ impl InputFormatCSV {
         }
         Ok(())
     }
+
+    // the fields of a row joined by the delimiter, to show in the rejected rows
+    fn row_data(buf: &[u8], field_ends: &[usize], delimiter: u8) -> String {
+        let mut data = vec![];
+        let mut field_start = 0;
+        for (i, field_end) in field_ends.iter().enumerate() {
+            if i > 0 {
+                data.push(delimiter);
+            }
+            data.extend_from_slice(&buf[field_start..*field_end]);
+            field_start = *field_end;
+        }
+        String::from_utf8_lossy(&data).to_string()
+    }
 }
 
 impl InputFormatTextBase for InputFormatCSV {
@@ -100,21 +117,31 @@ impl InputFormatTextBase for InputFormatCSV {
     }
 
     fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<()> {
+        let ctx = &builder.ctx;
         let columns = &mut builder.mutable_columns;
         let n_column = columns.len();
         let mut start = 0usize;
         let start_row = batch.start_row.expect("must success");
         let mut field_end_idx = 0;
+        let mut row_errors = batch.row_errors.iter().peekable();
         for (i, end) in batch.row_ends.iter().enumerate() {
             let buf = &batch.data[start..*end];
-            Self::read_row(
-                buf,
-                columns,
-                &batch.field_ends[field_end_idx..field_end_idx + n_column],
-                &builder.ctx.format_settings,
-                &batch.path,
-                start_row + i,
-            )?;
+            let field_ends = &batch.field_ends[field_end_idx..field_end_idx + n_column];
+            let result = match row_errors.next_if(|(row, _)| *row == i) {
+                Some((_, msg)) => Err(csv_error(msg, &batch.path, start_row + i)),
+                None => Self::read_row(
+                    buf,
+                    columns,
+                    field_ends,
+                    &ctx.format_settings,
+                    &batch.path,
+                    start_row + i,
+                ),
+            };
+            if let Err(e) = result {
+                let data = Self::row_data(buf, field_ends, ctx.field_delimiter);
+                ctx.reject_row(&batch.path, start_row + i, data, e)?;
+            }
             start = *end;
             field_end_idx += n_column;
         }
@@ -165,26 +192,8 @@ impl InputFormatTextBase for InputFormatCSV {
                     ));
                 }
                 ReadRecordResult::Record => {
-                    if endlen < num_fields {
-                        return Err(csv_error(
-                            &format!("expect {} fields, only found {} ", num_fields, n_end),
-                            &state.path,
-                            state.rows,
-                        ));
-                    } else if endlen > num_fields + 1 {
-                        return Err(csv_error(
-                            &format!("too many fields, expect {}, got {}", num_fields, n_end),
-                            &state.path,
-                            state.rows,
-                        ));
-                    } else if endlen == num_fields + 1
-                        && field_ends[num_fields] != field_ends[num_fields - 1]
-                    {
-                        return Err(csv_error(
-                            "CSV allow ending with ',', but should not have data after it",
-                            &state.path,
-                            state.rows,
-                        ));
+                    if let Some(msg) = field_count_error(field_ends, endlen, num_fields) {
+                        return Err(csv_error(&msg, &state.path, state.rows));
                     }
 
                     state.rows_to_skip -= 1;
@@ -214,6 +223,7 @@ impl InputFormatTextBase for InputFormatCSV {
             batch_id: state.batch_id,
             offset: 0,
             start_row: Some(state.rows),
+            row_errors: vec![],
         };
 
         while !buf.is_empty() {
@@ -245,26 +255,23 @@ impl InputFormatTextBase for InputFormatCSV {
                     ));
                 }
                 ReadRecordResult::Record => {
-                    if endlen < num_fields {
-                        return Err(csv_error(
-                            &format!("expect {} fields, only found {} ", num_fields, n_end),
-                            &state.path,
-                            start_row + row_batch.row_ends.len(),
-                        ));
-                    } else if endlen > num_fields + 1 {
-                        return Err(csv_error(
-                            &format!("too many fields, expect {}, got {}", num_fields, n_end),
-                            &state.path,
-                            start_row + row_batch.row_ends.len(),
-                        ));
-                    } else if endlen == num_fields + 1
-                        && field_ends[num_fields] != field_ends[num_fields - 1]
-                    {
-                        return Err(csv_error(
-                            "CSV allow ending with ',', but should not have data after it",
-                            &state.path,
-                            start_row + row_batch.row_ends.len(),
-                        ));
+                    if let Some(msg) = field_count_error(field_ends, endlen, num_fields) {
+                        let row = row_batch.row_ends.len();
+                        if state.ctx.allow_column_count_mismatch {
+                            // missing fields are empty, which are deserialized as the defaults
+                            let last_end = if endlen == 0 {
+                                0
+                            } else {
+                                field_ends[endlen - 1]
+                            };
+                            for field_end in field_ends.iter_mut().take(num_fields).skip(endlen) {
+                                *field_end = last_end;
+                            }
+                        } else if state.ctx.skip_bad_rows() {
+                            row_batch.row_errors.push((row, msg));
+                        } else {
+                            return Err(csv_error(&msg, &state.path, start_row + row));
+                        }
                     }
                     row_batch
                         .field_ends
@@ -333,6 +340,7 @@ impl CsvReaderState {
         let reader = csv_core::ReaderBuilder::new()
             .delimiter(ctx.field_delimiter)
             .quote(ctx.format_settings.quote_char)
+            .escape(ctx.escape)
             .terminator(match ctx.record_delimiter {
                 RecordDelimiter::Crlf => csv_core::Terminator::CRLF,
                 RecordDelimiter::Any(v) => csv_core::Terminator::Any(v),
@@ -347,6 +355,25 @@ impl CsvReaderState {
     }
 }
 
+// check the number of fields of a record, which may end with an extra delimiter
+fn field_count_error(field_ends: &[usize], endlen: usize, num_fields: usize) -> Option<String> {
+    if endlen < num_fields {
+        Some(format!(
+            "expect {} fields, only found {}",
+            num_fields, endlen
+        ))
+    } else if endlen > num_fields + 1 {
+        Some(format!(
+            "too many fields, expect {}, got {}",
+            num_fields, endlen
+        ))
+    } else if endlen == num_fields + 1 && field_ends[num_fields] != field_ends[num_fields - 1] {
+        Some("CSV allow ending with ',', but should not have data after it".to_string())
+    } else {
+        None
+    }
+}
+
 fn csv_error(msg: &str, path: &str, row: usize) -> ErrorCode {
     let row = row + 1;
     let msg = format!("fail to parse CSV {}:{} {} ", path, row, msg);
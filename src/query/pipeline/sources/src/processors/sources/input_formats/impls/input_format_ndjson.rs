@@ -26,6 +26,7 @@ use common_meta_types::StageFileFormatType;
 use common_settings::Settings;
 
 use crate::processors::sources::input_formats::input_format_text::get_time_zone;
+use crate::processors::sources::input_formats::input_format_text::rollback_row;
 use crate::processors::sources::input_formats::input_format_text::AligningState;
 use crate::processors::sources::input_formats::input_format_text::BlockBuilder;
 use crate::processors::sources::input_formats::input_format_text::InputFormatTextBase;
@@ -39,6 +40,7 @@ impl InputFormatNDJson {
         deserializers: &mut [TypeDeserializerImpl],
         format_settings: &FormatSettings,
         schema: &DataSchemaRef,
+        unknown_fields_column: Option<usize>,
     ) -> Result<()> {
         let mut json: serde_json::Value = serde_json::from_reader(buf)?;
         // if it's not case_sensitive, we convert to lowercase
@@ -48,26 +50,68 @@ impl InputFormatNDJson {
                 json = serde_json::Value::Object(y);
             }
         }
+        let names = schema
+            .fields()
+            .iter()
+            .map(|f| {
+                if format_settings.ident_case_sensitive {
+                    f.name().to_owned()
+                } else {
+                    f.name().to_lowercase()
+                }
+            })
+            .collect::<Vec<_>>();
 
-        for (f, deser) in schema.fields().iter().zip(deserializers.iter_mut()) {
-            let value = if format_settings.ident_case_sensitive {
-                &json[f.name().to_owned()]
+        for (c, f) in schema.fields().iter().enumerate() {
+            let unknown_fields;
+            let value = if unknown_fields_column == Some(c) {
+                unknown_fields = Self::unknown_fields(&json, &names, c);
+                &unknown_fields
             } else {
-                &json[f.name().to_lowercase()]
+                &json[&names[c]]
             };
 
-            deser.de_json(value, format_settings).map_err(|e| {
+            if let Err(e) = deserializers[c].de_json(value, format_settings) {
+                rollback_row(&mut deserializers[..c])?;
                 let value_str = format!("{:?}", value);
-                ErrorCode::BadBytes(format!(
+                return Err(ErrorCode::BadBytes(format!(
                     "{}. column={} value={}",
                     e,
                     f.name(),
                     maybe_truncated(&value_str, 1024),
-                ))
-            })?;
+                )));
+            }
         }
         Ok(())
     }
+
+    // the fields of a record matching no other column, null if none
+    fn unknown_fields(
+        json: &serde_json::Value,
+        names: &[String],
+        column: usize,
+    ) -> serde_json::Value {
+        match json {
+            serde_json::Value::Object(obj) => {
+                let fields = obj
+                    .iter()
+                    .filter(|(k, _)| {
+                        !names
+                            .iter()
+                            .enumerate()
+                            .any(|(c, name)| c != column && name == *k)
+                    })
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect::<serde_json::Map<_, _>>();
+                if fields.is_empty() {
+                    serde_json::Value::Null
+                } else {
+                    serde_json::Value::Object(fields)
+                }
+            }
+            _ => serde_json::Value::Null,
+        }
+    }
 }
 
 impl InputFormatTextBase for InputFormatNDJson {
@@ -89,6 +133,7 @@ impl InputFormatTextBase for InputFormatNDJson {
     }
 
     fn deserialize(builder: &mut BlockBuilder<Self>, batch: RowBatch) -> Result<()> {
+        let ctx = &builder.ctx;
         let columns = &mut builder.mutable_columns;
         let mut start = 0usize;
         let start_row = batch.start_row;
@@ -99,8 +144,9 @@ impl InputFormatTextBase for InputFormatNDJson {
                 if let Err(e) = Self::read_row(
                     buf,
                     columns,
-                    &builder.ctx.format_settings,
-                    &builder.ctx.schema,
+                    &ctx.format_settings,
+                    &ctx.schema,
+                    ctx.unknown_fields_column,
                 ) {
                     let row_info = if let Some(r) = start_row {
                         format!("row={},", r + i)
@@ -114,7 +160,9 @@ impl InputFormatTextBase for InputFormatNDJson {
                         batch.offset + start,
                         row_info,
                     );
-                    return Err(ErrorCode::BadBytes(msg));
+                    let row = start_row.map(|n| n + i).unwrap_or_default();
+                    let data = String::from_utf8_lossy(buf).to_string();
+                    ctx.reject_row(&batch.path, row, data, ErrorCode::BadBytes(msg))?;
                 }
             }
             start = *end;
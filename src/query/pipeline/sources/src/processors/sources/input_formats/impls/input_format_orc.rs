@@ -0,0 +1,453 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io::Cursor;
+use std::io::Read;
+use std::sync::Arc;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::io::orc::format::proto;
+use common_arrow::arrow::io::orc::format::read::decompress::Decompressor;
+use common_arrow::arrow::io::orc::format::read::read_metadata;
+use common_arrow::arrow::io::orc::format::read::read_stripe_column;
+use common_arrow::arrow::io::orc::format::read::read_stripe_footer;
+use common_arrow::arrow::io::orc::format::read::Column;
+use common_arrow::arrow::io::orc::format::read::FileMetadata;
+use common_arrow::arrow::io::orc::read;
+use common_datablocks::DataBlock;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::ColumnStatistics;
+use common_io::prelude::FormatSettings;
+use common_pipeline_core::Pipeline;
+use common_settings::Settings;
+use opendal::Object;
+use prost::Message;
+
+use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
+use crate::processors::sources::input_formats::input_context::InputContext;
+use crate::processors::sources::input_formats::input_format::FileInfo;
+use crate::processors::sources::input_formats::input_format::InputData;
+use crate::processors::sources::input_formats::input_format::SplitInfo;
+use crate::processors::sources::input_formats::input_pipeline::AligningStateTrait;
+use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::RowBatchTrait;
+use crate::processors::sources::input_formats::InputFormat;
+
+/// ORC files, each stripe of which is loaded as a block.
+///
+/// Only the columns of the table are read from the stripes, and stripes are skipped if their
+/// statistics can't satisfy the filters of the query, see `InputContext::range_filter`.
+pub struct InputFormatOrc;
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatOrc {
+    fn get_format_settings(&self, _settings: &Arc<Settings>) -> Result<FormatSettings> {
+        Ok(FormatSettings::default())
+    }
+
+    fn default_record_delimiter(&self) -> RecordDelimiter {
+        RecordDelimiter::Crlf
+    }
+
+    fn default_field_delimiter(&self) -> u8 {
+        b'_'
+    }
+
+    async fn read_file_meta(
+        &self,
+        _obj: &Object,
+        _size: usize,
+    ) -> Result<Option<Arc<dyn InputData>>> {
+        Ok(None)
+    }
+
+    async fn read_split_meta(
+        &self,
+        _obj: &Object,
+        _split_info: &SplitInfo,
+    ) -> Result<Option<Box<dyn InputData>>> {
+        Ok(None)
+    }
+
+    fn split_files(&self, file_infos: Vec<FileInfo>, _split_size: usize) -> Vec<SplitInfo> {
+        file_infos
+            .into_iter()
+            .map(SplitInfo::from_file_info)
+            .collect()
+    }
+
+    fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        OrcFormatPipe::execute_copy_with_aligner(ctx, pipeline)
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        OrcFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+pub struct OrcFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for OrcFormatPipe {
+    type ReadBatch = Vec<u8>;
+    type RowBatch = StripeInMemory;
+    type AligningState = AligningState;
+    type BlockBuilder = OrcBlockBuilder;
+}
+
+/// The columns of a stripe read from the file, in the order of the columns of the table.
+pub struct StripeInMemory {
+    path: String,
+    stripe: usize,
+    fields: Arc<Vec<Field>>,
+    columns: Vec<Column>,
+}
+
+impl RowBatchTrait for StripeInMemory {
+    fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Debug for StripeInMemory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StripeInMemory")
+            .field("path", &self.path)
+            .field("stripe", &self.stripe)
+            .finish()
+    }
+}
+
+pub struct OrcBlockBuilder {
+    ctx: Arc<InputContext>,
+}
+
+impl BlockBuilderTrait for OrcBlockBuilder {
+    type Pipe = OrcFormatPipe;
+
+    fn create(ctx: Arc<InputContext>) -> Self {
+        OrcBlockBuilder { ctx }
+    }
+
+    fn deserialize(&mut self, batch: Option<StripeInMemory>) -> Result<Vec<DataBlock>> {
+        let stripe = match batch {
+            Some(stripe) => stripe,
+            None => return Ok(vec![]),
+        };
+
+        let mut arrays = Vec::with_capacity(stripe.columns.len());
+        for (field, column) in stripe.fields.iter().zip(stripe.columns.iter()) {
+            let array = read::deserialize(field.data_type.clone(), column).map_err(|e| {
+                ErrorCode::BadBytes(format!(
+                    "fail to read ORC file {}: {}, stripe={}, column={}",
+                    stripe.path, e, stripe.stripe, field.name
+                ))
+            })?;
+            arrays.push(array);
+        }
+        let chunk = Chunk::try_new(arrays)?;
+        Ok(vec![DataBlock::from_chunk(&self.ctx.schema, &chunk)?])
+    }
+}
+
+pub struct AligningState {
+    ctx: Arc<InputContext>,
+    split_info: SplitInfo,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl AligningStateTrait for AligningState {
+    type Pipe = OrcFormatPipe;
+
+    fn try_create(ctx: &Arc<InputContext>, split_info: &SplitInfo) -> Result<Self> {
+        Ok(AligningState {
+            ctx: ctx.clone(),
+            split_info: split_info.clone(),
+            buffers: vec![],
+        })
+    }
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<StripeInMemory>> {
+        if let Some(buffer) = read_batch {
+            self.buffers.push(buffer);
+            return Ok(vec![]);
+        }
+
+        // the footer is at the end of the file, stripes can't be located before reading it
+        let file_in_memory = self.buffers.concat();
+        let size = file_in_memory.len();
+        let path = self.split_info.file_info.path.clone();
+
+        let mut cursor = Cursor::new(file_in_memory);
+        let metadata = read_metadata(&mut cursor).map_err(|e| orc_error(&path, e))?;
+        let (column_ids, fields) = get_columns(&metadata, &self.ctx.schema)?;
+        let fields = Arc::new(fields);
+        let stripe_statistics = match &self.ctx.range_filter {
+            None => vec![],
+            Some(_) => read_stripe_statistics(cursor.get_ref(), &metadata).unwrap_or_else(|e| {
+                tracing::debug!("fail to read stripe statistics of ORC file {}: {}", path, e);
+                vec![]
+            }),
+        };
+
+        let mut stripes = vec![];
+        let mut scratch = vec![];
+        for (index, info) in metadata.footer.stripes.iter().enumerate() {
+            if let Some(statistics) = stripe_statistics.get(index) {
+                if !self.should_keep(statistics, &column_ids, info.number_of_rows()) {
+                    continue;
+                }
+            }
+
+            let footer = read_stripe_footer(&mut cursor, &metadata, index, &mut scratch)
+                .map_err(|e| orc_error(&path, e))?;
+            let mut columns = Vec::with_capacity(column_ids.len());
+            for column_id in column_ids.iter() {
+                let column = read_stripe_column(
+                    &mut cursor,
+                    &metadata,
+                    index,
+                    footer.clone(),
+                    *column_id,
+                    vec![],
+                )
+                .map_err(|e| orc_error(&path, e))?;
+                columns.push(column);
+            }
+            stripes.push(StripeInMemory {
+                path: path.clone(),
+                stripe: index,
+                fields: fields.clone(),
+                columns,
+            });
+        }
+
+        tracing::info!(
+            "align ORC file {} of {} bytes to {} of {} stripes",
+            path,
+            size,
+            stripes.len(),
+            metadata.footer.stripes.len()
+        );
+        Ok(stripes)
+    }
+}
+
+impl AligningState {
+    fn should_keep(
+        &self,
+        statistics: &proto::StripeStatistics,
+        column_ids: &[u32],
+        num_rows: u64,
+    ) -> bool {
+        let range_filter = match &self.ctx.range_filter {
+            None => return true,
+            Some(range_filter) => range_filter,
+        };
+
+        let mut columns = vec![];
+        for (index, (field, column_id)) in self
+            .ctx
+            .schema
+            .fields()
+            .iter()
+            .zip(column_ids.iter())
+            .enumerate()
+        {
+            let column_stats = statistics
+                .col_stats
+                .get(*column_id as usize)
+                .and_then(|s| to_column_statistics(s, field.data_type(), num_rows));
+            if let Some(column_stats) = column_stats {
+                columns.push((index, column_stats));
+            }
+        }
+
+        let path = &self.split_info.file_info.path;
+        let stats = self.ctx.statistics_of_columns(path, columns, num_rows);
+        match range_filter.eval(&stats, num_rows) {
+            Ok(keep) => keep,
+            Err(e) => {
+                // columns without statistics can't be pruned
+                tracing::debug!("failed to prune ORC stripes, keep them. {}", e);
+                true
+            }
+        }
+    }
+}
+
+fn orc_error(path: &str, e: impl Display) -> ErrorCode {
+    ErrorCode::BadBytes(format!("fail to read ORC file {}: {}", path, e))
+}
+
+/// Find the ORC column of each column of the table, by name.
+///
+/// Returns the ids of the columns in the type tree of the file, and their arrow fields.
+fn get_columns(metadata: &FileMetadata, schema: &DataSchemaRef) -> Result<(Vec<u32>, Vec<Field>)> {
+    let infer_schema = read::infer_schema(&metadata.footer)?;
+    let root = metadata
+        .footer
+        .types
+        .first()
+        .ok_or_else(|| ErrorCode::BadBytes("ORC file without columns"))?;
+
+    let mut column_ids = Vec::with_capacity(schema.num_fields());
+    let mut read_fields = Vec::with_capacity(schema.num_fields());
+    for f in schema.fields().iter() {
+        let position = root
+            .field_names
+            .iter()
+            .position(|name| name == f.name())
+            .or_else(|| {
+                root.field_names
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(f.name()))
+            });
+        let (column_id, field) = match position {
+            Some(position) => (root.subtypes[position], &infer_schema.fields[position]),
+            None => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "schema field size mismatch, expected to find column: {}",
+                    f.name()
+                )));
+            }
+        };
+
+        let data_type = DataField::from(field).data_type().clone();
+        if remove_nullable(&data_type) != remove_nullable(f.data_type()) {
+            return Err(ErrorCode::BadBytes(format!(
+                "ORC schema mismatch, column {} is of type {:?} in the file, {:?} expected",
+                f.name(),
+                data_type,
+                f.data_type()
+            )));
+        }
+        column_ids.push(column_id);
+        read_fields.push(field.clone());
+    }
+    Ok((column_ids, read_fields))
+}
+
+/// Read the statistics of the stripes, in the metadata section before the footer.
+fn read_stripe_statistics(
+    file: &[u8],
+    metadata: &FileMetadata,
+) -> Result<Vec<proto::StripeStatistics>> {
+    let postscript = &metadata.postscript;
+    let postscript_len = *file.last().unwrap_or(&0) as u64;
+    let tail_len = 1 + postscript_len + postscript.footer_length() + postscript.metadata_length();
+    if tail_len > file.len() as u64 {
+        return Err(ErrorCode::BadBytes("metadata out of the range of the file"));
+    }
+
+    let start = file.len() - tail_len as usize;
+    let end = start + postscript.metadata_length() as usize;
+    let mut decompressor =
+        Decompressor::new(file[start..end].to_vec(), postscript.compression(), vec![]);
+    let mut buffer = vec![];
+    decompressor.read_to_end(&mut buffer)?;
+    let metadata = proto::Metadata::decode(buffer.as_slice())
+        .map_err(|e| ErrorCode::BadBytes(e.to_string()))?;
+    Ok(metadata.stripe_stats)
+}
+
+/// Convert the statistics of a column in a stripe, into the ones the range filter evaluates.
+///
+/// Returns none if the statistics can't be used for pruning.
+fn to_column_statistics(
+    stats: &proto::ColumnStatistics,
+    data_type: &DataTypeImpl,
+    num_rows: u64,
+) -> Option<ColumnStatistics> {
+    let non_null_count = stats.number_of_values?;
+    let null_count = num_rows.checked_sub(non_null_count)?;
+    if non_null_count == 0 {
+        return Some(ColumnStatistics {
+            min: DataValue::Null,
+            max: DataValue::Null,
+            null_count,
+            in_memory_size: 0,
+        });
+    }
+
+    let (min, max) = match remove_nullable(data_type) {
+        DataTypeImpl::Int8(_)
+        | DataTypeImpl::Int16(_)
+        | DataTypeImpl::Int32(_)
+        | DataTypeImpl::Int64(_) => {
+            let stats = stats.int_statistics.as_ref()?;
+            (
+                DataValue::Int64(stats.minimum?),
+                DataValue::Int64(stats.maximum?),
+            )
+        }
+        DataTypeImpl::UInt8(_)
+        | DataTypeImpl::UInt16(_)
+        | DataTypeImpl::UInt32(_)
+        | DataTypeImpl::UInt64(_) => {
+            let stats = stats.int_statistics.as_ref()?;
+            (
+                DataValue::UInt64(u64::try_from(stats.minimum?).ok()?),
+                DataValue::UInt64(u64::try_from(stats.maximum?).ok()?),
+            )
+        }
+        DataTypeImpl::Float32(_) | DataTypeImpl::Float64(_) => {
+            let stats = stats.double_statistics.as_ref()?;
+            (
+                DataValue::Float64(stats.minimum?),
+                DataValue::Float64(stats.maximum?),
+            )
+        }
+        DataTypeImpl::Date(_) => {
+            let stats = stats.date_statistics.as_ref()?;
+            (
+                DataValue::Int64(stats.minimum? as i64),
+                DataValue::Int64(stats.maximum? as i64),
+            )
+        }
+        DataTypeImpl::Timestamp(_) => {
+            // in milliseconds, the nanoseconds are truncated
+            let stats = stats.timestamp_statistics.as_ref()?;
+            let min = stats.minimum_utc?.checked_mul(1000)?;
+            let max = stats.maximum_utc?.checked_mul(1000)?.checked_add(999)?;
+            (DataValue::Int64(min), DataValue::Int64(max))
+        }
+        DataTypeImpl::String(_) => {
+            let stats = stats.string_statistics.as_ref()?;
+            (
+                DataValue::String(stats.minimum.clone()?.into_bytes()),
+                DataValue::String(stats.maximum.clone()?.into_bytes()),
+            )
+        }
+        _ => return None,
+    };
+
+    Some(ColumnStatistics {
+        min,
+        max,
+        null_count,
+        in_memory_size: 0,
+    })
+}
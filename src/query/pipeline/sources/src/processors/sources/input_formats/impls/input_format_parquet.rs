@@ -23,21 +23,42 @@ use std::sync::Arc;
 
 use common_arrow::arrow::array::Array;
 use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
 use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::datatypes::TimeUnit;
 use common_arrow::arrow::io::parquet::read;
 use common_arrow::arrow::io::parquet::read::read_columns;
 use common_arrow::arrow::io::parquet::read::to_deserializer;
 use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
+use common_arrow::parquet::indexes::compute_rows;
+use common_arrow::parquet::indexes::select_pages;
+use common_arrow::parquet::indexes::BooleanIndex;
+use common_arrow::parquet::indexes::ByteIndex;
+use common_arrow::parquet::indexes::FilteredPage;
+use common_arrow::parquet::indexes::Index;
+use common_arrow::parquet::indexes::Interval;
+use common_arrow::parquet::indexes::NativeIndex;
 use common_arrow::parquet::metadata::ColumnChunkMetaData;
 use common_arrow::parquet::metadata::FileMetaData;
 use common_arrow::parquet::metadata::RowGroupMetaData;
+use common_arrow::parquet::read::read_columns_indexes;
 use common_arrow::parquet::read::read_metadata;
+use common_arrow::parquet::read::read_pages_locations;
+use common_arrow::parquet::statistics::BinaryStatistics;
+use common_arrow::parquet::statistics::BooleanStatistics;
+use common_arrow::parquet::statistics::PrimitiveStatistics;
+use common_arrow::parquet::statistics::Statistics;
+use common_cache::Cache;
 use common_datablocks::DataBlock;
 use common_datavalues::remove_nullable;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_fuse_meta::caches::CacheManager;
+use common_fuse_meta::meta::ColumnStatistics;
 use common_io::prelude::FormatSettings;
 use common_pipeline_core::Pipeline;
 use common_settings::Settings;
@@ -46,14 +67,21 @@ use similar_asserts::traits::MakeDiff;
 
 use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
 use crate::processors::sources::input_formats::input_context::InputContext;
+use crate::processors::sources::input_formats::input_context::InputPlan;
 use crate::processors::sources::input_formats::input_format::FileInfo;
 use crate::processors::sources::input_formats::input_format::InputData;
 use crate::processors::sources::input_formats::input_format::SplitInfo;
 use crate::processors::sources::input_formats::input_pipeline::AligningStateTrait;
 use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
 use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::RowBatchTrait;
 use crate::processors::sources::input_formats::InputFormat;
 
+/// Parquet files, each row group of which is loaded as a block.
+///
+/// Only the columns read by the query are decoded, see `InputContext::projection`. Row groups are
+/// skipped if their statistics can't satisfy the filters of the query, and so are the pages of
+/// the row groups if the file has page indexes, see `InputContext::range_filter`.
 pub struct InputFormatParquet;
 
 #[async_trait::async_trait]
@@ -116,17 +144,31 @@ impl InputFormatPipe for ParquetFormatPipe {
 }
 
 pub struct RowGroupInMemory {
+    pub path: String,
     pub meta: RowGroupMetaData,
+    // the rows to read, fewer than the rows of the row group if pages are skipped
+    pub num_rows: usize,
+    // the pages to read of each column chunk, all of them if none
+    pub pages: Option<Vec<Vec<FilteredPage>>>,
     pub fields: Arc<Vec<Field>>,
     pub field_meta_indexes: Vec<Vec<usize>>,
     pub field_arrays: Vec<Vec<Vec<u8>>>,
 }
 
+impl RowBatchTrait for RowGroupInMemory {
+    fn path(&self) -> &str {
+        &self.path
+    }
+}
+
 impl RowGroupInMemory {
     fn read<R: Read + Seek>(
+        path: String,
         reader: &mut R,
         meta: RowGroupMetaData,
         fields: Arc<Vec<Field>>,
+        pages: Option<Vec<Vec<FilteredPage>>>,
+        num_rows: usize,
     ) -> Result<Self> {
         let field_names = fields.iter().map(|x| x.name.as_str()).collect::<Vec<_>>();
         let field_meta_indexes = split_column_metas_by_field(meta.columns(), &field_names);
@@ -137,7 +179,10 @@ impl RowGroupInMemory {
             filed_arrays.push(data)
         }
         Ok(Self {
+            path,
             meta,
+            num_rows,
+            pages,
             field_meta_indexes,
             field_arrays: filed_arrays,
             fields,
@@ -152,16 +197,22 @@ impl RowGroupInMemory {
                 .iter()
                 .map(|c| &self.meta.columns()[*c]);
             let meta_data = meta_iters.zip(datas.into_iter()).collect::<Vec<_>>();
+            let pages = self.pages.as_ref().map(|pages| {
+                self.field_meta_indexes[f]
+                    .iter()
+                    .map(|c| pages[*c].clone())
+                    .collect()
+            });
             let array_iters = to_deserializer(
                 meta_data,
                 self.fields[f].clone(),
-                self.meta.num_rows() as usize,
-                None,
+                self.num_rows,
                 None,
+                pages,
             )?;
             column_chunks.push(array_iters);
         }
-        match RowGroupDeserializer::new(column_chunks, self.meta.num_rows(), None).next() {
+        match RowGroupDeserializer::new(column_chunks, self.num_rows, None).next() {
             None => Err(ErrorCode::ParquetError("fail to get a chunk")),
             Some(Ok(chunk)) => Ok(chunk),
             Some(Err(e)) => Err(ErrorCode::ParquetError(e.to_string())),
@@ -202,7 +253,14 @@ impl BlockBuilderTrait for ParquetBlockBuilder {
     fn deserialize(&mut self, mut batch: Option<RowGroupInMemory>) -> Result<Vec<DataBlock>> {
         if let Some(rg) = batch.as_mut() {
             let chunk = rg.get_arrow_chunk()?;
-            let block = DataBlock::from_chunk(&self.ctx.schema, &chunk)?;
+            let block = match &self.ctx.projection {
+                None => DataBlock::from_chunk(&self.ctx.schema, &chunk)?,
+                Some(projection) => {
+                    let schema = Arc::new(self.ctx.schema.project(projection));
+                    let block = DataBlock::from_chunk(&schema, &chunk)?;
+                    fill_unread_columns(&self.ctx.schema, projection, block)?
+                }
+            };
             Ok(vec![block])
         } else {
             Ok(vec![])
@@ -242,29 +300,201 @@ impl AligningStateTrait for AligningState {
                 size,
             );
             let mut cursor = Cursor::new(file_in_memory);
-            let file_meta =
-                read_metadata(&mut cursor).map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
-            let read_fields = Arc::new(get_fields(&file_meta, &self.ctx.schema)?);
+            let file_meta = self.read_file_meta(&mut cursor)?;
+            let fields = get_fields(&file_meta, &self.ctx.schema)?;
+            let columns = match &self.ctx.projection {
+                Some(projection) => projection.clone(),
+                None => (0..fields.len()).collect(),
+            };
+            let read_fields = Arc::new(
+                columns
+                    .iter()
+                    .map(|i| fields[*i].clone())
+                    .collect::<Vec<_>>(),
+            );
+            let field_names = read_fields
+                .iter()
+                .map(|x| x.name.as_str())
+                .collect::<Vec<_>>();
 
             let mut row_batches = Vec::with_capacity(file_meta.row_groups.len());
-            for row_group in file_meta.row_groups.into_iter() {
+            for row_group in file_meta.row_groups.iter() {
+                let mut num_rows = row_group.num_rows();
+                let mut pages = None;
+                if self.ctx.range_filter.is_some() {
+                    let leaves = split_column_metas_by_field(row_group.columns(), &field_names);
+                    let stats = row_group_statistics(row_group, &columns, &read_fields, &leaves);
+                    if !self.should_keep(stats.clone(), num_rows as u64) {
+                        continue;
+                    }
+                    if let Some((selected, selected_rows)) = self.select_pages(
+                        &mut cursor,
+                        row_group,
+                        &columns,
+                        &read_fields,
+                        &leaves,
+                        &stats,
+                    ) {
+                        if selected_rows == 0 {
+                            continue;
+                        }
+                        pages = Some(selected);
+                        num_rows = selected_rows;
+                    }
+                }
+
                 row_batches.push(RowGroupInMemory::read(
+                    self.split_info.file_info.path.clone(),
                     &mut cursor,
-                    row_group,
+                    row_group.clone(),
                     read_fields.clone(),
+                    pages,
+                    num_rows,
                 )?)
             }
             tracing::info!(
-                "align parquet file {} of {} bytes to {} row groups",
+                "align parquet file {} of {} bytes to {} of {} row groups",
                 self.split_info.file_info.path,
                 size,
-                row_batches.len()
+                row_batches.len(),
+                file_meta.row_groups.len()
             );
             Ok(row_batches)
         }
     }
 }
 
+impl AligningState {
+    /// Footers of staged files are cached, keyed by the stage, path and size of the file.
+    ///
+    /// The cache is skipped if it is busy, `align` is not async and should not wait for the lock.
+    fn read_file_meta<R: Read + Seek>(&self, reader: &mut R) -> Result<Arc<FileMetaData>> {
+        let cache = CacheManager::instance().get_file_meta_data_cache();
+        let key = match &self.ctx.plan {
+            InputPlan::CopyInto(plan) => Some(format!(
+                "{}/{}-{}",
+                plan.stage_info.stage_name,
+                self.split_info.file_info.path,
+                self.split_info.file_info.size
+            )),
+            InputPlan::StreamingLoad(_) => None,
+        };
+
+        if let (Some(cache), Some(key)) = (&cache, &key) {
+            if let Ok(mut cache) = cache.try_write() {
+                if let Some(file_meta) = cache.get(key) {
+                    return Ok(file_meta.clone());
+                }
+            }
+        }
+
+        let file_meta =
+            Arc::new(read_metadata(reader).map_err(|e| ErrorCode::ParquetError(e.to_string()))?);
+        if let (Some(cache), Some(key)) = (&cache, key) {
+            if let Ok(mut cache) = cache.try_write() {
+                cache.put(key, file_meta.clone());
+            }
+        }
+        Ok(file_meta)
+    }
+
+    fn should_keep(&self, columns: Vec<(usize, ColumnStatistics)>, num_rows: u64) -> bool {
+        let range_filter = match &self.ctx.range_filter {
+            None => return true,
+            Some(range_filter) => range_filter,
+        };
+
+        let path = &self.split_info.file_info.path;
+        let stats = self.ctx.statistics_of_columns(path, columns, num_rows);
+        match range_filter.eval(&stats, num_rows) {
+            Ok(keep) => keep,
+            Err(e) => {
+                // columns without statistics can't be pruned
+                tracing::debug!("failed to prune parquet row groups, keep them. {}", e);
+                true
+            }
+        }
+    }
+
+    /// Select the rows of the row group by the page indexes, the rows of the pages whose
+    /// statistics can't satisfy the filters are skipped.
+    ///
+    /// A page is checked with the statistics of the row group for the other columns, the rows
+    /// selected are the ones kept by all the columns. Returns the pages to read of each column
+    /// chunk and the number of rows of them, or none if the file has no page indexes or no page
+    /// is skipped.
+    fn select_pages<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        row_group: &RowGroupMetaData,
+        columns: &[usize],
+        fields: &[Field],
+        leaves: &[Vec<usize>],
+        row_group_stats: &[(usize, ColumnStatistics)],
+    ) -> Option<(Vec<Vec<FilteredPage>>, usize)> {
+        let chunks = row_group.columns();
+        let num_rows = row_group.num_rows();
+        let indexes = read_columns_indexes(reader, chunks).ok()?;
+        let locations = read_pages_locations(reader, chunks).ok()?;
+        if indexes.len() != chunks.len()
+            || locations.len() != chunks.len()
+            || locations.iter().any(|pages| pages.is_empty())
+        {
+            return None;
+        }
+
+        let mut rows = vec![Interval {
+            start: 0,
+            length: num_rows,
+        }];
+        let mut skipped = false;
+        for ((index, field), leaves) in columns.iter().zip(fields).zip(leaves) {
+            let leaf = match leaves.as_slice() {
+                [leaf] => *leaf,
+                _ => continue,
+            };
+            let pages = &locations[leaf];
+            let mut selected = Vec::with_capacity(pages.len());
+            for (page, location) in pages.iter().enumerate() {
+                let end = pages
+                    .get(page + 1)
+                    .map(|next| next.first_row_index as usize)
+                    .unwrap_or(num_rows);
+                let page_rows = end.saturating_sub(location.first_row_index as usize) as u64;
+                let mut stats = row_group_stats.to_vec();
+                if let Some(page_stats) =
+                    to_page_statistics(indexes[leaf].as_ref(), page, field, page_rows)
+                {
+                    stats.retain(|(i, _)| i != index);
+                    stats.push((*index, page_stats));
+                }
+                selected.push(self.should_keep(stats, page_rows));
+            }
+            if selected.iter().all(|keep| *keep) {
+                continue;
+            }
+
+            let intervals = compute_rows(&selected, pages, num_rows).ok()?;
+            rows = intersect_intervals(&rows, &intervals);
+            skipped = true;
+        }
+        if !skipped {
+            return None;
+        }
+
+        let selected_rows = rows.iter().map(|interval| interval.length).sum::<usize>();
+        if selected_rows == 0 {
+            return Some((vec![], 0));
+        }
+        let pages = locations
+            .iter()
+            .map(|pages| select_pages(&rows, pages, num_rows))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .ok()?;
+        Some((pages, selected_rows))
+    }
+}
+
 fn get_fields(file_meta: &FileMetaData, schema: &DataSchemaRef) -> Result<Vec<Field>> {
     let infer_schema = read::infer_schema(file_meta)?;
     let mut read_fields = Vec::with_capacity(schema.num_fields());
@@ -313,3 +543,235 @@ pub fn split_column_metas_by_field(
     });
     r
 }
+
+/// Fill the columns of `schema` not in the projection with their default values, the block
+/// holds the columns in the projection.
+fn fill_unread_columns(
+    schema: &DataSchemaRef,
+    projection: &[usize],
+    block: DataBlock,
+) -> Result<DataBlock> {
+    let num_rows = block.num_rows();
+    let mut columns = Vec::with_capacity(schema.num_fields());
+    for (index, field) in schema.fields().iter().enumerate() {
+        let column = match projection.iter().position(|i| *i == index) {
+            Some(position) => block.column(position).clone(),
+            None => {
+                let data_type = field.data_type();
+                data_type.create_constant_column(&data_type.default_value(), num_rows)?
+            }
+        };
+        columns.push(column);
+    }
+    Ok(DataBlock::create(schema.clone(), columns))
+}
+
+/// The statistics of the columns of the schema in the row group, the nested columns of more
+/// than one column chunk are not pruned.
+fn row_group_statistics(
+    row_group: &RowGroupMetaData,
+    columns: &[usize],
+    fields: &[Field],
+    leaves: &[Vec<usize>],
+) -> Vec<(usize, ColumnStatistics)> {
+    let num_rows = row_group.num_rows() as u64;
+    let mut stats = vec![];
+    for ((index, field), leaves) in columns.iter().zip(fields).zip(leaves) {
+        let column_stats = match leaves.as_slice() {
+            [leaf] => match row_group.columns()[*leaf].statistics() {
+                Some(Ok(s)) => to_column_statistics(s.as_ref(), field, num_rows),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(column_stats) = column_stats {
+            stats.push((*index, column_stats));
+        }
+    }
+    stats
+}
+
+/// Convert the statistics of a column chunk, into the ones the range filter evaluates.
+///
+/// Returns none if the statistics can't be used for pruning.
+fn to_column_statistics(
+    stats: &dyn Statistics,
+    field: &Field,
+    num_rows: u64,
+) -> Option<ColumnStatistics> {
+    let stats = stats.as_any();
+    if let Some(s) = stats.downcast_ref::<PrimitiveStatistics<i32>>() {
+        to_statistics(s.min_value, s.max_value, s.null_count, field, num_rows)
+    } else if let Some(s) = stats.downcast_ref::<PrimitiveStatistics<i64>>() {
+        to_statistics(s.min_value, s.max_value, s.null_count, field, num_rows)
+    } else if let Some(s) = stats.downcast_ref::<PrimitiveStatistics<f32>>() {
+        to_statistics(s.min_value, s.max_value, s.null_count, field, num_rows)
+    } else if let Some(s) = stats.downcast_ref::<PrimitiveStatistics<f64>>() {
+        to_statistics(s.min_value, s.max_value, s.null_count, field, num_rows)
+    } else if let Some(s) = stats.downcast_ref::<BinaryStatistics>() {
+        let (min, max) = (s.min_value.clone(), s.max_value.clone());
+        to_statistics(min, max, s.null_count, field, num_rows)
+    } else if let Some(s) = stats.downcast_ref::<BooleanStatistics>() {
+        to_statistics(s.min_value, s.max_value, s.null_count, field, num_rows)
+    } else {
+        None
+    }
+}
+
+/// Convert the statistics of a page in the column index, into the ones the range filter
+/// evaluates.
+///
+/// Returns none if the statistics can't be used for pruning.
+fn to_page_statistics(
+    index: &dyn Index,
+    page: usize,
+    field: &Field,
+    num_rows: u64,
+) -> Option<ColumnStatistics> {
+    let index = index.as_any();
+    if let Some(index) = index.downcast_ref::<NativeIndex<i32>>() {
+        let p = index.indexes.get(page)?;
+        to_statistics(p.min, p.max, p.null_count, field, num_rows)
+    } else if let Some(index) = index.downcast_ref::<NativeIndex<i64>>() {
+        let p = index.indexes.get(page)?;
+        to_statistics(p.min, p.max, p.null_count, field, num_rows)
+    } else if let Some(index) = index.downcast_ref::<NativeIndex<f32>>() {
+        let p = index.indexes.get(page)?;
+        to_statistics(p.min, p.max, p.null_count, field, num_rows)
+    } else if let Some(index) = index.downcast_ref::<NativeIndex<f64>>() {
+        let p = index.indexes.get(page)?;
+        to_statistics(p.min, p.max, p.null_count, field, num_rows)
+    } else if let Some(index) = index.downcast_ref::<ByteIndex>() {
+        let p = index.indexes.get(page)?;
+        to_statistics(p.min.clone(), p.max.clone(), p.null_count, field, num_rows)
+    } else if let Some(index) = index.downcast_ref::<BooleanIndex>() {
+        let p = index.indexes.get(page)?;
+        to_statistics(p.min, p.max, p.null_count, field, num_rows)
+    } else {
+        None
+    }
+}
+
+fn to_statistics<T: StatisticsValue>(
+    min: Option<T>,
+    max: Option<T>,
+    null_count: Option<i64>,
+    field: &Field,
+    num_rows: u64,
+) -> Option<ColumnStatistics> {
+    let null_count = u64::try_from(null_count?).ok()?;
+    if null_count > num_rows {
+        return None;
+    }
+    if null_count == num_rows {
+        return Some(ColumnStatistics {
+            min: DataValue::Null,
+            max: DataValue::Null,
+            null_count,
+            in_memory_size: 0,
+        });
+    }
+
+    Some(ColumnStatistics {
+        min: min?.to_data_value(field)?,
+        max: max?.to_data_value(field)?,
+        null_count,
+        in_memory_size: 0,
+    })
+}
+
+/// A value in the parquet statistics, as the value of the column read from it.
+trait StatisticsValue {
+    fn to_data_value(self, field: &Field) -> Option<DataValue>;
+}
+
+impl StatisticsValue for i32 {
+    fn to_data_value(self, field: &Field) -> Option<DataValue> {
+        match field.data_type().to_logical_type() {
+            ArrowDataType::Int8
+            | ArrowDataType::Int16
+            | ArrowDataType::Int32
+            | ArrowDataType::Date32 => Some(DataValue::Int64(self as i64)),
+            // stored as signed of the same width, but ordered as unsigned
+            ArrowDataType::UInt8 | ArrowDataType::UInt16 | ArrowDataType::UInt32 => {
+                Some(DataValue::UInt64(self as u32 as u64))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl StatisticsValue for i64 {
+    fn to_data_value(self, field: &Field) -> Option<DataValue> {
+        match field.data_type().to_logical_type() {
+            ArrowDataType::Int64 | ArrowDataType::Timestamp(TimeUnit::Microsecond, _) => {
+                Some(DataValue::Int64(self))
+            }
+            ArrowDataType::UInt64 => Some(DataValue::UInt64(self as u64)),
+            _ => None,
+        }
+    }
+}
+
+impl StatisticsValue for f32 {
+    fn to_data_value(self, field: &Field) -> Option<DataValue> {
+        match field.data_type().to_logical_type() {
+            ArrowDataType::Float32 => Some(DataValue::Float64(self as f64)),
+            _ => None,
+        }
+    }
+}
+
+impl StatisticsValue for f64 {
+    fn to_data_value(self, field: &Field) -> Option<DataValue> {
+        match field.data_type().to_logical_type() {
+            ArrowDataType::Float64 => Some(DataValue::Float64(self)),
+            _ => None,
+        }
+    }
+}
+
+impl StatisticsValue for Vec<u8> {
+    fn to_data_value(self, field: &Field) -> Option<DataValue> {
+        match field.data_type().to_logical_type() {
+            ArrowDataType::Utf8
+            | ArrowDataType::LargeUtf8
+            | ArrowDataType::Binary
+            | ArrowDataType::LargeBinary => Some(DataValue::String(self)),
+            _ => None,
+        }
+    }
+}
+
+impl StatisticsValue for bool {
+    fn to_data_value(self, field: &Field) -> Option<DataValue> {
+        match field.data_type().to_logical_type() {
+            ArrowDataType::Boolean => Some(DataValue::Boolean(self)),
+            _ => None,
+        }
+    }
+}
+
+/// The rows in both of the sorted and disjoint intervals.
+fn intersect_intervals(a: &[Interval], b: &[Interval]) -> Vec<Interval> {
+    let mut intervals = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let a_end = a[i].start + a[i].length;
+        let b_end = b[j].start + b[j].length;
+        let start = a[i].start.max(b[j].start);
+        let end = a_end.min(b_end);
+        if start < end {
+            intervals.push(Interval {
+                start,
+                length: end - start,
+            });
+        }
+        if a_end < b_end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    intervals
+}
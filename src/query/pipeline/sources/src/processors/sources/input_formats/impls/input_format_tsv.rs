@@ -25,6 +25,7 @@ use common_meta_types::StageFileFormatType;
 use common_settings::Settings;
 
 use crate::processors::sources::input_formats::input_format_text::get_time_zone;
+use crate::processors::sources::input_formats::input_format_text::rollback_row;
 use crate::processors::sources::input_formats::input_format_text::AligningState;
 use crate::processors::sources::input_formats::input_format_text::BlockBuilder;
 use crate::processors::sources::input_formats::input_format_text::InputFormatTextBase;
@@ -33,10 +34,12 @@ use crate::processors::sources::input_formats::input_format_text::RowBatch;
 pub struct InputFormatTSV {}
 
 impl InputFormatTSV {
+    #[allow(clippy::too_many_arguments)]
     fn read_row(
         buf: &[u8],
         deserializers: &mut Vec<common_datavalues::TypeDeserializerImpl>,
         format_settings: &FormatSettings,
+        allow_column_count_mismatch: bool,
         path: &str,
         batch_id: usize,
         offset: usize,
@@ -50,6 +53,12 @@ impl InputFormatTSV {
         let buf_len = buf.len();
         while pos <= buf_len {
             if pos == buf_len || buf[pos] == b'\t' {
+                if column_index == num_columns {
+                    if !allow_column_count_mismatch {
+                        err_msg = Some("too many columns".to_string());
+                    }
+                    break;
+                }
                 let col_data = &buf[field_start..pos];
                 if col_data.is_empty() {
                     deserializers[column_index].de_default(format_settings);
@@ -66,27 +75,30 @@ impl InputFormatTSV {
                     if reader.must_eof().is_err() {
                         err_msg =
                             Some(format_column_error(column_index, col_data, "bad field end"));
+                        column_index += 1;
                         break;
                     }
                 }
                 column_index += 1;
                 field_start = pos + 1;
-                if column_index > num_columns {
-                    err_msg = Some("too many columns".to_string());
-                    break;
-                }
             }
             pos += 1;
         }
         if err_msg.is_none() && column_index < num_columns {
-            // todo(youngsofun): allow it optionally (set default)
-            err_msg = Some(format!(
-                "need {} columns, find {} only",
-                num_columns, column_index
-            ));
+            if allow_column_count_mismatch {
+                for deserializer in deserializers[column_index..].iter_mut() {
+                    deserializer.de_default(format_settings);
+                }
+            } else {
+                err_msg = Some(format!(
+                    "need {} columns, find {} only",
+                    num_columns, column_index
+                ));
+            }
         }
 
         if let Some(m) = err_msg {
+            rollback_row(&mut deserializers[..column_index])?;
             let row_info = if let Some(r) = row_index {
                 format!("at row {},", r)
             } else {
@@ -137,20 +149,26 @@ impl InputFormatTextBase for InputFormatTSV {
             batch.start_row,
             batch.offset
         );
+        let ctx = &builder.ctx;
         let columns = &mut builder.mutable_columns;
         let mut start = 0usize;
         let start_row = batch.start_row;
         for (i, end) in batch.row_ends.iter().enumerate() {
             let buf = &batch.data[start..*end]; // include \n
-            Self::read_row(
+            if let Err(e) = Self::read_row(
                 buf,
                 columns,
-                &builder.ctx.format_settings,
+                &ctx.format_settings,
+                ctx.allow_column_count_mismatch,
                 &batch.path,
                 batch.batch_id,
                 batch.offset + start,
                 start_row.map(|n| n + i),
-            )?;
+            ) {
+                let row = start_row.map(|n| n + i).unwrap_or_default();
+                let data = String::from_utf8_lossy(buf).trim_end().to_string();
+                ctx.reject_row(&batch.path, row, data, e)?;
+            }
             start = *end;
         }
         Ok(())
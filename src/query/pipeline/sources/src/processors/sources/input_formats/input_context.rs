@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::mem;
@@ -21,20 +22,32 @@ use std::sync::Mutex;
 
 use common_base::base::tokio::sync::mpsc::Receiver;
 use common_base::base::Progress;
+use common_datablocks::DataBlock;
+use common_datavalues::remove_nullable;
+use common_datavalues::Column;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_fuse_meta::meta::ColumnStatistics;
+use common_fuse_meta::meta::StatisticsOfColumns;
 use common_io::prelude::FormatSettings;
+use common_meta_types::OnErrorMode;
 use common_meta_types::StageFileCompression;
 use common_meta_types::StageFileFormatType;
 use common_meta_types::UserStageInfo;
 use common_settings::Settings;
+use common_storages_index::RangeFilter;
 use opendal::io_util::CompressAlgorithm;
 use opendal::Operator;
 
 use crate::processors::sources::input_formats::delimiter::RecordDelimiter;
+use crate::processors::sources::input_formats::impls::input_format_avro::InputFormatAvro;
 use crate::processors::sources::input_formats::impls::input_format_csv::InputFormatCSV;
 use crate::processors::sources::input_formats::impls::input_format_ndjson::InputFormatNDJson;
+use crate::processors::sources::input_formats::impls::input_format_orc::InputFormatOrc;
 use crate::processors::sources::input_formats::impls::input_format_parquet::InputFormatParquet;
 use crate::processors::sources::input_formats::impls::input_format_tsv::InputFormatTSV;
 use crate::processors::sources::input_formats::input_format::FileInfo;
@@ -68,8 +81,10 @@ pub struct CopyIntoPlan {
 
 #[derive(Debug)]
 pub struct StreamPlan {
+    pub format: StageFileFormatType,
     pub is_multi_part: bool,
     pub compression: StageFileCompression,
+    pub skip_bad_rows: bool,
 }
 
 pub enum InputSource {
@@ -114,6 +129,10 @@ pub struct InputContext {
     pub rows_to_skip: usize,
     pub field_delimiter: u8,
     pub record_delimiter: RecordDelimiter,
+    pub escape: Option<u8>,
+    pub allow_column_count_mismatch: bool,
+    // the VARIANT column to hold the fields of NDJSON records matching no column
+    pub unknown_fields_column: Option<usize>,
 
     // runtime config
     pub settings: Arc<Settings>,
@@ -122,9 +141,73 @@ pub struct InputContext {
     pub read_batch_size: usize,
     pub rows_per_block: usize,
 
+    // filters of the query, to skip the parts of the files by their statistics
+    pub range_filter: Option<RangeFilter>,
+    // columns of `schema` read by the query, the formats storing the columns apart may skip
+    // reading the others, and fill them with the default values
+    pub projection: Option<Vec<usize>>,
+
+    // rows failed to parse, which are skipped with `ON_ERROR = CONTINUE`
+    pub rejected_rows: Mutex<Vec<RejectedRow>>,
+
+    // columns of the values in the paths of the files, of the external tables
+    pub partition_columns: Option<PartitionColumns>,
+
     pub scan_progress: Arc<Progress>,
 }
 
+/// Columns of the values in the paths of the files, like `dt=2022-10-01/`, which are not
+/// stored in the files. The values of the file are appended to each block read from it.
+pub struct PartitionColumns {
+    /// Schema of the blocks with the columns appended, `InputContext::schema` is the schema of
+    /// the columns in the files.
+    pub schema: DataSchemaRef,
+    pub names: Vec<String>,
+    /// Values of the columns, keyed by the path of the file.
+    pub values: HashMap<String, Vec<DataValue>>,
+}
+
+impl PartitionColumns {
+    pub fn append(&self, path: &str, block: DataBlock) -> Result<DataBlock> {
+        let values = self.values.get(path).ok_or_else(|| {
+            ErrorCode::LogicalError(format!("no partition values of file {}", path))
+        })?;
+
+        let num_rows = block.num_rows();
+        let mut columns = Vec::with_capacity(self.schema.num_fields());
+        for field in self.schema.fields() {
+            let column = match self.names.iter().position(|name| name == field.name()) {
+                Some(index) => field
+                    .data_type()
+                    .create_constant_column(&values[index], num_rows)?
+                    .convert_full_column(),
+                None => block.try_column_by_name(field.name())?.clone(),
+            };
+            columns.push(column);
+        }
+        Ok(DataBlock::create(self.schema.clone(), columns))
+    }
+}
+
+#[derive(Debug)]
+pub struct RejectedRow {
+    pub path: String,
+    pub row: usize,
+    pub error: String,
+    pub data: String,
+}
+
+impl RejectedRow {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "file": self.path,
+            "row": self.row,
+            "error": self.error,
+            "data": self.data,
+        })
+    }
+}
+
 impl Debug for InputContext {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("InputContext")
@@ -132,10 +215,22 @@ impl Debug for InputContext {
             .field("rows_to_skip", &self.rows_to_skip)
             .field("field_delimiter", &self.field_delimiter)
             .field("record_delimiter", &self.record_delimiter)
+            .field("escape", &self.escape)
+            .field(
+                "allow_column_count_mismatch",
+                &self.allow_column_count_mismatch,
+            )
+            .field("unknown_fields_column", &self.unknown_fields_column)
             .field("format_settings", &self.format_settings)
             .field("rows_per_block", &self.rows_per_block)
             .field("read_batch_size", &self.read_batch_size)
             .field("num_splits", &self.splits.len())
+            .field("has_range_filter", &self.range_filter.is_some())
+            .field("projection", &self.projection)
+            .field(
+                "partition_columns",
+                &self.partition_columns.as_ref().map(|p| &p.names),
+            )
             .finish()
     }
 }
@@ -149,6 +244,8 @@ impl InputContext {
                 Ok(Arc::new(InputFormatText::<InputFormatNDJson>::create()))
             }
             StageFileFormatType::Parquet => Ok(Arc::new(InputFormatParquet {})),
+            StageFileFormatType::Avro => Ok(Arc::new(InputFormatAvro {})),
+            StageFileFormatType::Orc => Ok(Arc::new(InputFormatOrc {})),
             format => Err(ErrorCode::LogicalError(format!(
                 "Unsupported file format: {:?}",
                 format
@@ -159,7 +256,7 @@ impl InputContext {
     pub async fn try_create_from_copy(
         operator: Operator,
         settings: Arc<Settings>,
-        format_settings: FormatSettings,
+        mut format_settings: FormatSettings,
         schema: DataSchemaRef,
         stage_info: UserStageInfo,
         files: Vec<String>,
@@ -192,6 +289,15 @@ impl InputContext {
                 file_format_options.field_delimiter.as_bytes()[0]
             }
         };
+        if !file_format_options.quote.is_empty() {
+            format_settings.quote_char = file_format_options.quote.as_bytes()[0];
+        }
+        if !file_format_options.null_display.is_empty() {
+            format_settings.null_bytes = file_format_options.null_display.as_bytes().to_vec();
+        }
+        let escape = file_format_options.escape.as_bytes().first().cloned();
+        let allow_column_count_mismatch = file_format_options.allow_column_count_mismatch;
+        let unknown_fields_column = Self::get_unknown_fields_column(&settings, &schema)?;
         Ok(InputContext {
             format,
             schema,
@@ -203,7 +309,14 @@ impl InputContext {
             read_batch_size,
             rows_to_skip,
             field_delimiter,
+            escape,
+            allow_column_count_mismatch,
+            unknown_fields_column,
             scan_progress,
+            range_filter: None,
+            projection: None,
+            rejected_rows: Mutex::new(vec![]),
+            partition_columns: None,
             source: InputSource::Operator(operator),
             plan: InputPlan::CopyInto(plan),
         })
@@ -242,9 +355,12 @@ impl InputContext {
             StageFileCompression::Auto
         };
         let plan = StreamPlan {
+            format: format_type,
             is_multi_part,
             compression,
+            skip_bad_rows: settings.get_skip_bad_rows()? > 0,
         };
+        let unknown_fields_column = Self::get_unknown_fields_column(&settings, &schema)?;
 
         Ok(InputContext {
             format,
@@ -256,13 +372,39 @@ impl InputContext {
             read_batch_size,
             field_delimiter,
             rows_to_skip,
+            escape: None,
+            allow_column_count_mismatch: false,
+            unknown_fields_column,
             scan_progress,
+            range_filter: None,
+            projection: None,
+            rejected_rows: Mutex::new(vec![]),
+            partition_columns: None,
             source: InputSource::Stream(Mutex::new(Some(stream_receiver))),
             plan: InputPlan::StreamingLoad(plan),
             splits: vec![],
         })
     }
 
+    fn get_unknown_fields_column(
+        settings: &Arc<Settings>,
+        schema: &DataSchemaRef,
+    ) -> Result<Option<usize>> {
+        let name = settings.get_ndjson_unknown_fields_column()?;
+        if name.is_empty() {
+            return Ok(None);
+        }
+        let index = schema.index_of(&name)?;
+        match remove_nullable(schema.field(index).data_type()) {
+            DataTypeImpl::Variant(_) | DataTypeImpl::VariantObject(_) => Ok(Some(index)),
+            data_type => Err(ErrorCode::BadArguments(format!(
+                "ndjson_unknown_fields_column {} must be VARIANT, but got {}",
+                name,
+                data_type.name()
+            ))),
+        }
+    }
+
     async fn get_file_infos(
         format: &Arc<dyn InputFormat>,
         op: &Operator,
@@ -273,10 +415,13 @@ impl InputContext {
             let obj = op.object(p);
             let size = obj.metadata().await?.content_length() as usize;
             let file_meta = format.read_file_meta(&obj, size).await?;
-            let compress_alg = InputContext::get_compression_alg_copy(
-                plan.stage_info.file_format_options.compression,
-                p,
-            )?;
+            let compression = plan.stage_info.file_format_options.compression;
+            let mut compress_alg = InputContext::get_compression_alg_copy(compression, p)?;
+            if compression == StageFileCompression::Auto && compress_alg.is_none() && size > 0 {
+                // no known suffix, detect by the magic bytes
+                let header = obj.range_read(0..size.min(6) as u64).await?;
+                compress_alg = detect_compression_alg(&header);
+            }
             let info = FileInfo {
                 path: p.clone(),
                 size,
@@ -304,6 +449,114 @@ impl InputContext {
         Self::get_compression_alg_copy(opt, path)
     }
 
+    /// Whether the rows failed to parse are skipped rather than failing the load.
+    pub fn skip_bad_rows(&self) -> bool {
+        match &self.plan {
+            InputPlan::CopyInto(p) => p.stage_info.copy_options.on_error == OnErrorMode::Continue,
+            InputPlan::StreamingLoad(p) => p.skip_bad_rows,
+        }
+    }
+
+    /// Skip a row failed to parse if allowed by `ON_ERROR`, otherwise return the error.
+    pub fn reject_row(&self, path: &str, row: usize, data: String, error: ErrorCode) -> Result<()> {
+        if !self.skip_bad_rows() {
+            return Err(error);
+        }
+        let max_errors = match &self.plan {
+            InputPlan::CopyInto(p) => p.stage_info.copy_options.max_errors as usize,
+            InputPlan::StreamingLoad(_) => 0,
+        };
+        let mut rejected_rows = self.rejected_rows.lock().expect("must success");
+        if max_errors > 0 && rejected_rows.len() >= max_errors {
+            return Err(ErrorCode::BadBytes(format!(
+                "more than {} rows failed to load (MAX_ERRORS), the last one: {}",
+                max_errors,
+                error.message()
+            )));
+        }
+        rejected_rows.push(RejectedRow {
+            path: path.to_string(),
+            row: row + 1,
+            error: error.message(),
+            data,
+        });
+        Ok(())
+    }
+
+    pub fn take_rejected_rows(&self) -> Vec<RejectedRow> {
+        mem::take(&mut *self.rejected_rows.lock().expect("must success"))
+    }
+
+    /// Write the rejected rows as NDJSON to `_errors/<query_id>.ndjson` of the stage.
+    ///
+    /// Returns the path of the file, or `None` if no row is rejected.
+    pub async fn write_rejected_rows(&self, query_id: &str) -> Result<Option<String>> {
+        let rows = self.take_rejected_rows();
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let plan = match &self.plan {
+            InputPlan::CopyInto(p) => p,
+            InputPlan::StreamingLoad(_) => return Ok(None),
+        };
+        let path = format!(
+            "{}_errors/{}.ndjson",
+            plan.stage_info.get_prefix(),
+            query_id
+        );
+        let mut data = vec![];
+        for row in rows {
+            serde_json::to_writer(&mut data, &row.to_json())?;
+            data.push(b'\n');
+        }
+        let operator = self.source.get_operator()?;
+        operator.object(&path).write(data).await?;
+        Ok(Some(path))
+    }
+
+    /// Key the statistics of the columns of `schema` in a part of the file at `path`, by the ids
+    /// of the columns in `range_filter`.
+    ///
+    /// The range filter of an external table is built on `PartitionColumns::schema`, the values
+    /// of the partition columns of the file are added as their statistics.
+    pub fn statistics_of_columns(
+        &self,
+        path: &str,
+        columns: Vec<(usize, ColumnStatistics)>,
+        num_rows: u64,
+    ) -> StatisticsOfColumns {
+        let mut stats = StatisticsOfColumns::new();
+        let partition_columns = match &self.partition_columns {
+            None => {
+                for (index, column_stats) in columns {
+                    stats.insert(index as u32, column_stats);
+                }
+                return stats;
+            }
+            Some(partition_columns) => partition_columns,
+        };
+
+        for (index, column_stats) in columns {
+            let name = self.schema.field(index).name();
+            if let Ok(id) = partition_columns.schema.index_of(name) {
+                stats.insert(id as u32, column_stats);
+            }
+        }
+        if let Some(values) = partition_columns.values.get(path) {
+            for (name, value) in partition_columns.names.iter().zip(values) {
+                if let Ok(id) = partition_columns.schema.index_of(name) {
+                    stats.insert(id as u32, ColumnStatistics {
+                        min: value.clone(),
+                        max: value.clone(),
+                        null_count: if value.is_null() { num_rows } else { 0 },
+                        in_memory_size: 0,
+                    });
+                }
+            }
+        }
+        stats
+    }
+
     pub fn get_compression_alg_copy(
         compress_option: StageFileCompression,
         path: &str,
@@ -331,6 +584,21 @@ impl InputContext {
     }
 }
 
+/// Detect the compression algorithm of a file by its leading magic bytes.
+fn detect_compression_alg(header: &[u8]) -> Option<CompressAlgorithm> {
+    if header.starts_with(&[0x1f, 0x8b]) {
+        Some(CompressAlgorithm::Gzip)
+    } else if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        Some(CompressAlgorithm::Zstd)
+    } else if header.starts_with(b"BZh") {
+        Some(CompressAlgorithm::Bz2)
+    } else if header.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+        Some(CompressAlgorithm::Xz)
+    } else {
+        None
+    }
+}
+
 const WITH_NAMES_AND_TYPES: &str = "withnamesandtypes";
 const WITH_NAMES: &str = "withnames";
 
@@ -40,6 +40,7 @@ use crate::processors::sources::input_formats::input_format::SplitInfo;
 use crate::processors::sources::input_formats::input_pipeline::AligningStateTrait;
 use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
 use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::RowBatchTrait;
 
 pub trait InputFormatTextBase: Sized + Send + Sync + 'static {
     fn format_type() -> StageFileFormatType;
@@ -148,9 +149,19 @@ pub struct RowBatch {
     pub batch_id: usize,
     pub offset: usize,
     pub start_row: Option<usize>,
+
+    // rows found bad when aligning, by their index in the batch
+    pub row_errors: Vec<(usize, String)>,
+}
+
+impl RowBatchTrait for RowBatch {
+    fn path(&self) -> &str {
+        &self.path
+    }
 }
 
 pub struct AligningState<T> {
+    pub ctx: Arc<InputContext>,
     pub path: String,
     pub record_delimiter_end: u8,
     pub field_delimiter: u8,
@@ -242,6 +253,7 @@ impl<T: InputFormatTextBase> AligningState<T> {
                 batch_id: self.batch_id,
                 offset: self.offset,
                 start_row: Some(self.rows),
+                row_errors: vec![],
             };
             tracing::debug!(
                 "align flush batch {}, bytes = {}, start_row = {}",
@@ -265,7 +277,10 @@ impl<T: InputFormatTextBase> AligningStateTrait for AligningState<T> {
         };
         let path = split_info.file_info.path.clone();
 
-        let decoder = ctx.get_compression_alg(&path)?.map(DecompressDecoder::new);
+        let decoder = split_info
+            .file_info
+            .compress_alg
+            .map(DecompressDecoder::new);
         let csv_reader = if T::format_type() == StageFileFormatType::Csv {
             Some(CsvReaderState::create(ctx))
         } else {
@@ -273,6 +288,7 @@ impl<T: InputFormatTextBase> AligningStateTrait for AligningState<T> {
         };
 
         Ok(AligningState::<T> {
+            ctx: ctx.clone(),
             path,
             decoder,
             rows_to_skip,
@@ -391,6 +407,14 @@ fn decompress(decoder: &mut DecompressDecoder, compressed: &[u8]) -> Result<Vec<
     Ok(decompress_bufs.concat())
 }
 
+/// Remove the values of a row which failed to parse from the columns already deserialized.
+pub fn rollback_row(deserializers: &mut [TypeDeserializerImpl]) -> Result<()> {
+    for deserializer in deserializers {
+        deserializer.pop_data_value()?;
+    }
+    Ok(())
+}
+
 pub fn get_time_zone(settings: &Settings) -> Result<Tz> {
     let tz = settings.get_timezone()?;
     tz.parse::<Tz>()
@@ -62,6 +62,11 @@ pub trait AligningStateTrait: Sized {
     ) -> Result<Vec<<Self::Pipe as InputFormatPipe>::RowBatch>>;
 }
 
+pub trait RowBatchTrait: Send {
+    /// Path of the file the rows are read from.
+    fn path(&self) -> &str;
+}
+
 pub trait BlockBuilderTrait {
     type Pipe: InputFormatPipe<BlockBuilder = Self>;
     fn create(ctx: Arc<InputContext>) -> Self;
@@ -72,10 +77,55 @@ pub trait BlockBuilderTrait {
     ) -> Result<Vec<DataBlock>>;
 }
 
+/// Wraps the block builder to append the values of the partition columns of the files, if any
+/// in the `InputContext`. The rows buffered are flushed when the file changes, so that each block
+/// is read from a single file.
+pub struct PartitionedBlockBuilder<I: InputFormatPipe> {
+    ctx: Arc<InputContext>,
+    block_builder: I::BlockBuilder,
+    // the file of the rows buffered
+    path: Option<String>,
+}
+
+impl<I: InputFormatPipe> PartitionedBlockBuilder<I> {
+    pub fn create(ctx: Arc<InputContext>) -> Self {
+        PartitionedBlockBuilder {
+            block_builder: I::BlockBuilder::create(ctx.clone()),
+            ctx,
+            path: None,
+        }
+    }
+
+    pub fn deserialize(&mut self, batch: Option<I::RowBatch>) -> Result<Vec<DataBlock>> {
+        let partition_columns = match &self.ctx.partition_columns {
+            Some(partition_columns) => partition_columns,
+            None => return self.block_builder.deserialize(batch),
+        };
+
+        let mut blocks = vec![];
+        if let Some(batch) = &batch {
+            if self.path.as_deref() != Some(batch.path()) {
+                if let Some(path) = self.path.replace(batch.path().to_string()) {
+                    for block in self.block_builder.deserialize(None)? {
+                        blocks.push(partition_columns.append(&path, block)?);
+                    }
+                }
+            }
+        }
+        let new_blocks = self.block_builder.deserialize(batch)?;
+        if let Some(path) = &self.path {
+            for block in new_blocks {
+                blocks.push(partition_columns.append(path, block)?);
+            }
+        }
+        Ok(blocks)
+    }
+}
+
 #[async_trait::async_trait]
 pub trait InputFormatPipe: Sized + Send + 'static {
     type ReadBatch: From<Vec<u8>> + Send + Debug;
-    type RowBatch: Send;
+    type RowBatch: RowBatchTrait;
     type AligningState: AligningStateTrait<Pipe = Self> + Send;
     type BlockBuilder: BlockBuilderTrait<Pipe = Self> + Send;
 
@@ -23,5 +23,6 @@ mod source_deserializer;
 mod transform_deserializer;
 
 pub use input_context::InputContext;
+pub use input_context::PartitionColumns;
 pub use input_format::InputFormat;
 pub use input_pipeline::StreamingReadBatch;
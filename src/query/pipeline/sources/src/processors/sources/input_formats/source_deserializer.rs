@@ -24,14 +24,14 @@ use common_pipeline_core::processors::processor::ProcessorPtr;
 use common_pipeline_core::processors::Processor;
 
 use crate::processors::sources::input_formats::input_context::InputContext;
-use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
 use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::PartitionedBlockBuilder;
 
 pub struct DeserializeSource<I: InputFormatPipe> {
     #[allow(unused)]
     output: Arc<OutputPort>,
 
-    block_builder: I::BlockBuilder,
+    block_builder: PartitionedBlockBuilder<I>,
     input_rx: async_channel::Receiver<I::RowBatch>,
     input_buffer: Option<I::RowBatch>,
     input_finished: bool,
@@ -46,7 +46,7 @@ impl<I: InputFormatPipe> DeserializeSource<I> {
         rx: async_channel::Receiver<I::RowBatch>,
     ) -> Result<ProcessorPtr> {
         Ok(ProcessorPtr::create(Box::new(Self {
-            block_builder: I::BlockBuilder::create(ctx),
+            block_builder: PartitionedBlockBuilder::create(ctx),
             output,
             input_rx: rx,
             input_buffer: Default::default(),
@@ -26,11 +26,11 @@ use common_pipeline_core::processors::Processor;
 use crossbeam_channel::TryRecvError;
 
 use crate::processors::sources::input_formats::input_context::InputContext;
-use crate::processors::sources::input_formats::input_pipeline::BlockBuilderTrait;
 use crate::processors::sources::input_formats::input_pipeline::InputFormatPipe;
+use crate::processors::sources::input_formats::input_pipeline::PartitionedBlockBuilder;
 
 struct DeserializeProcessor<I: InputFormatPipe> {
-    pub block_builder: I::BlockBuilder,
+    pub block_builder: PartitionedBlockBuilder<I>,
     pub input_buffer: Option<I::RowBatch>,
     pub output_buffer: VecDeque<DataBlock>,
 }
@@ -38,7 +38,7 @@ struct DeserializeProcessor<I: InputFormatPipe> {
 impl<I: InputFormatPipe> DeserializeProcessor<I> {
     pub(crate) fn create(ctx: Arc<InputContext>) -> Result<Self> {
         Ok(Self {
-            block_builder: I::BlockBuilder::create(ctx),
+            block_builder: PartitionedBlockBuilder::create(ctx),
             input_buffer: Default::default(),
             output_buffer: Default::default(),
         })
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::fmt::Debug;
@@ -49,6 +50,8 @@ pub type MetadataRef = Arc<RwLock<Metadata>>;
 pub struct Metadata {
     tables: Vec<TableEntry>,
     columns: Vec<ColumnEntry>,
+    /// Names of the user defined functions expanded in the query.
+    udfs: BTreeSet<String>,
 }
 
 impl Metadata {
@@ -77,6 +80,14 @@ impl Metadata {
         self.columns.as_slice()
     }
 
+    pub fn udfs(&self) -> &BTreeSet<String> {
+        &self.udfs
+    }
+
+    pub fn add_udf(&mut self, name: String) {
+        self.udfs.insert(name);
+    }
+
     pub fn columns_by_table_index(&self, index: IndexType) -> Vec<ColumnEntry> {
         self.columns
             .iter()
@@ -99,6 +110,19 @@ impl Metadata {
         column_index
     }
 
+    /// Adds a virtual column of the table, which is a materialized path of a VARIANT column
+    /// instead of a column of the schema of the table.
+    pub fn add_virtual_column(
+        &mut self,
+        name: String,
+        data_type: DataTypeImpl,
+        table_index: IndexType,
+    ) -> IndexType {
+        let column_index = self.add_column(name, data_type, Some(table_index), None);
+        self.columns[column_index].virtual_column = true;
+        column_index
+    }
+
     pub fn add_table(
         &mut self,
         catalog: String,
@@ -266,6 +290,8 @@ pub struct ColumnEntry {
     table_index: Option<IndexType>,
     /// Path indices for inner column of struct data type.
     path_indices: Option<Vec<IndexType>>,
+    /// Whether the column is a virtual column of the table.
+    virtual_column: bool,
 }
 
 impl ColumnEntry {
@@ -282,6 +308,7 @@ impl ColumnEntry {
             data_type,
             table_index,
             path_indices,
+            virtual_column: false,
         }
     }
 
@@ -314,4 +341,9 @@ impl ColumnEntry {
     pub fn has_path_indices(&self) -> bool {
         self.path_indices.is_some()
     }
+
+    /// Check if this column entry is a virtual column
+    pub fn is_virtual_column(&self) -> bool {
+        self.virtual_column
+    }
 }
@@ -13,26 +13,50 @@
 // limitations under the License.
 
 mod alter_table_cluster_key;
+mod alter_table_column;
 mod alter_udf;
 mod alter_user;
 mod alter_view;
+mod alter_virtual_columns;
+mod analyze_table;
+mod attach_table;
 mod call;
+mod create_aggregating_index;
+mod create_bloom_index;
 mod create_database;
+mod create_inverted_index;
+mod create_masking_policy;
+mod create_network_policy;
 mod create_role;
+mod create_row_access_policy;
 mod create_stage;
+mod create_stream;
+mod create_task;
 mod create_udf;
 mod create_user;
 mod create_view;
+mod create_virtual_columns;
+mod create_workload_group;
 mod describe_table;
+mod drop_aggregating_index;
 mod drop_database;
+mod drop_index;
+mod drop_masking_policy;
+mod drop_network_policy;
 mod drop_role;
+mod drop_row_access_policy;
 mod drop_stage;
+mod drop_stream;
 mod drop_table;
 mod drop_table_cluster_key;
+mod drop_task;
 mod drop_udf;
 mod drop_user;
 mod drop_view;
+mod drop_virtual_columns;
+mod drop_workload_group;
 mod exists_table;
+mod flashback_table;
 mod grant_privilege;
 mod grant_role;
 mod kill;
@@ -43,35 +67,62 @@ mod rename_database;
 mod rename_table;
 mod revoke_privilege;
 mod revoke_role;
+mod set_column_masking_policy;
+mod set_role;
 mod show_create_database;
 mod show_create_table;
 mod show_grants;
 mod truncate_table;
 mod undrop_database;
 mod undrop_table;
+mod unset_column_masking_policy;
 mod use_database;
 
 pub use alter_table_cluster_key::AlterTableClusterKeyPlan;
+pub use alter_table_column::AlterTableColumnPlan;
 pub use alter_udf::AlterUDFPlan;
 pub use alter_user::AlterUserPlan;
 pub use alter_view::AlterViewPlan;
+pub use alter_virtual_columns::AlterVirtualColumnsPlan;
+pub use analyze_table::AnalyzeTablePlan;
+pub use attach_table::AttachTablePlan;
 pub use call::CallPlan;
+pub use create_aggregating_index::CreateAggregatingIndexPlan;
+pub use create_bloom_index::CreateBloomIndexPlan;
 pub use create_database::CreateDatabasePlan;
+pub use create_inverted_index::CreateInvertedIndexPlan;
+pub use create_masking_policy::CreateMaskingPolicyPlan;
+pub use create_network_policy::CreateNetworkPolicyPlan;
 pub use create_role::CreateRolePlan;
+pub use create_row_access_policy::CreateRowAccessPolicyPlan;
 pub use create_stage::CreateStagePlan;
+pub use create_stream::CreateStreamPlan;
+pub use create_task::CreateTaskPlan;
 pub use create_udf::CreateUDFPlan;
 pub use create_user::CreateUserPlan;
 pub use create_view::CreateViewPlan;
+pub use create_virtual_columns::CreateVirtualColumnsPlan;
+pub use create_workload_group::CreateWorkloadGroupPlan;
 pub use describe_table::DescribeTablePlan;
+pub use drop_aggregating_index::DropAggregatingIndexPlan;
 pub use drop_database::DropDatabasePlan;
+pub use drop_index::DropIndexPlan;
+pub use drop_masking_policy::DropMaskingPolicyPlan;
+pub use drop_network_policy::DropNetworkPolicyPlan;
 pub use drop_role::DropRolePlan;
+pub use drop_row_access_policy::DropRowAccessPolicyPlan;
 pub use drop_stage::DropStagePlan;
+pub use drop_stream::DropStreamPlan;
 pub use drop_table::DropTablePlan;
 pub use drop_table_cluster_key::DropTableClusterKeyPlan;
+pub use drop_task::DropTaskPlan;
 pub use drop_udf::DropUDFPlan;
 pub use drop_user::DropUserPlan;
 pub use drop_view::DropViewPlan;
+pub use drop_virtual_columns::DropVirtualColumnsPlan;
+pub use drop_workload_group::DropWorkloadGroupPlan;
 pub use exists_table::ExistsTablePlan;
+pub use flashback_table::FlashbackTablePlan;
 pub use grant_privilege::GrantPrivilegePlan;
 pub use grant_role::GrantRolePlan;
 pub use kill::KillPlan;
@@ -85,10 +136,13 @@ pub use rename_table::RenameTableEntity;
 pub use rename_table::RenameTablePlan;
 pub use revoke_privilege::RevokePrivilegePlan;
 pub use revoke_role::RevokeRolePlan;
+pub use set_column_masking_policy::SetColumnMaskingPolicyPlan;
+pub use set_role::SetRolePlan;
 pub use show_create_database::ShowCreateDatabasePlan;
 pub use show_create_table::ShowCreateTablePlan;
 pub use show_grants::ShowGrantsPlan;
 pub use truncate_table::TruncateTablePlan;
 pub use undrop_database::UndropDatabasePlan;
 pub use undrop_table::UndropTablePlan;
+pub use unset_column_masking_policy::UnsetColumnMaskingPolicyPlan;
 pub use use_database::UseDatabasePlan;
@@ -13,6 +13,7 @@
 // limitations under the License.
 use std::sync::Arc;
 
+use common_catalog::table::CompactTarget;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 
@@ -34,5 +35,5 @@ impl OptimizeTablePlan {
 pub enum OptimizeTableAction {
     All,
     Purge,
-    Compact,
+    Compact(CompactTarget),
 }
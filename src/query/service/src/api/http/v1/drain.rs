@@ -0,0 +1,102 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+use std::time::SystemTime;
+
+use poem::web::Json;
+use poem::web::Query;
+use poem::IntoResponse;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::api::DataExchangeManager;
+use crate::clusters::ClusterDiscovery;
+use crate::sessions::SessionManager;
+
+const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 600;
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+pub enum DrainState {
+    Running,
+    Draining,
+    Drained,
+}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+pub struct DrainStatus {
+    pub state: DrainState,
+    pub running_queries_count: u64,
+    // queries of other coordinators with fragments on this node
+    pub running_fragments_count: u64,
+    // secs since epoch
+    pub drain_started_at: Option<u64>,
+    // secs since epoch
+    pub drain_deadline: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct DrainParams {
+    pub timeout_secs: Option<u64>,
+}
+
+fn secs_since_epoch(t: SystemTime) -> u64 {
+    t.duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs()
+}
+
+fn drain_status() -> DrainStatus {
+    let status = {
+        let status = SessionManager::instance().status.read();
+        status.clone()
+    };
+    let running_fragments_count = DataExchangeManager::instance().running_queries_count() as u64;
+    let state = match status.drain_started_at {
+        None => DrainState::Running,
+        Some(_) if status.running_queries_count == 0 && running_fragments_count == 0 => {
+            DrainState::Drained
+        }
+        Some(_) => DrainState::Draining,
+    };
+
+    DrainStatus {
+        state,
+        running_queries_count: status.running_queries_count,
+        running_fragments_count,
+        drain_started_at: status.drain_started_at.map(secs_since_epoch),
+        drain_deadline: status.drain_deadline.map(secs_since_epoch),
+    }
+}
+
+// Start draining the node: the new queries and fragments are rejected, the node leaves the
+// cluster and the queries still running after timeout_secs are killed.
+// The node can be stopped once the state is Drained.
+#[poem::handler]
+pub async fn drain_handler(Query(params): Query<DrainParams>) -> poem::Result<impl IntoResponse> {
+    let timeout_secs = params.timeout_secs.unwrap_or(DEFAULT_DRAIN_TIMEOUT_SECS);
+    if SessionManager::instance().drain(Duration::from_secs(timeout_secs)) {
+        if let Err(cause) = ClusterDiscovery::instance().leave_cluster().await {
+            warn!("Cannot leave the cluster while draining, cause {:?}", cause);
+        }
+    }
+
+    Ok(Json(drain_status()))
+}
+
+#[poem::handler]
+pub async fn drain_status_handler() -> poem::Result<impl IntoResponse> {
+    Ok(Json(drain_status()))
+}
@@ -14,6 +14,7 @@
 
 pub mod cluster;
 pub mod config;
+pub mod drain;
 pub mod instance_status;
 pub mod logs;
 pub mod tenant_tables;
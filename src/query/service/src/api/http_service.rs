@@ -56,6 +56,11 @@ impl HttpService {
                 "/v1/status",
                 get(super::http::v1::instance_status::instance_status_handler),
             )
+            .at(
+                "/v1/drain",
+                get(super::http::v1::drain::drain_status_handler)
+                    .post(super::http::v1::drain::drain_handler),
+            )
             .at(
                 "/v1/tables",
                 get(super::http::v1::tenant_tables::list_tables_handler),
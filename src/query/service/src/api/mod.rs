@@ -35,6 +35,7 @@ pub use rpc::QueryFragmentsPlanPacket;
 pub use rpc::ServerFlightExchange;
 pub use rpc::ShuffleDataExchange;
 pub use rpc::ShuffleDataExchangeV2;
+pub use rpc::UDFFlightClient;
 pub use rpc_service::RpcService;
 
 pub mod http;
@@ -28,6 +28,7 @@ use common_grpc::ConnectionFactory;
 use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
 use parking_lot::ReentrantMutex;
+use tracing::warn;
 
 use crate::api::rpc::exchange::exchange_params::ExchangeParams;
 use crate::api::rpc::exchange::exchange_params::MergeExchangeParams;
@@ -36,17 +37,20 @@ use crate::api::rpc::exchange::exchange_sink::ExchangeSink;
 use crate::api::rpc::exchange::exchange_transform::ExchangeTransform;
 use crate::api::rpc::exchange::statistics_receiver::StatisticsReceiver;
 use crate::api::rpc::exchange::statistics_sender::StatisticsSender;
+use crate::api::rpc::flight_actions::CancelAction;
 use crate::api::rpc::flight_client::FlightExchange;
 use crate::api::rpc::flight_scatter_broadcast::BroadcastFlightScatter;
 use crate::api::rpc::flight_scatter_hash::HashFlightScatter;
 use crate::api::rpc::flight_scatter_hash_v2::HashFlightScatterV2;
 use crate::api::rpc::Packet;
 use crate::api::DataExchange;
+use crate::api::FlightAction;
 use crate::api::FlightClient;
 use crate::api::FragmentPayload;
 use crate::api::FragmentPlanPacket;
 use crate::api::InitNodesChannelPacket;
 use crate::api::QueryFragmentsPlanPacket;
+use crate::clusters::ClusterHelper;
 use crate::interpreters::QueryFragmentActions;
 use crate::interpreters::QueryFragmentsActions;
 use crate::pipelines::executor::ExecutorSettings;
@@ -82,6 +86,13 @@ impl DataExchangeManager {
         }
     }
 
+    // The number of the queries with fragments on this node.
+    pub fn running_queries_count(&self) -> usize {
+        let queries_coordinator_guard = self.queries_coordinator.lock();
+        let queries_coordinator = unsafe { &*queries_coordinator_guard.deref().get() };
+        queries_coordinator.len()
+    }
+
     // Create connections for cluster all nodes. We will push data through this connection.
     pub async fn init_nodes_channel(&self, packet: &InitNodesChannelPacket) -> Result<()> {
         let mut request_exchanges = vec![];
@@ -228,6 +239,12 @@ impl DataExchangeManager {
         }
     }
 
+    // Cancel the query on this node, it is removed whether it is executing or not.
+    pub fn cancel_query(&self, query_id: &str) {
+        self.shutdown_query(query_id);
+        self.on_finished_query(query_id);
+    }
+
     pub async fn commit_actions(
         &self,
         ctx: Arc<QueryContext>,
@@ -235,36 +252,111 @@ impl DataExchangeManager {
     ) -> Result<PipelineBuildResult> {
         let settings = ctx.get_settings();
         let timeout = settings.get_flight_client_timeout()?;
+
+        match self.try_commit_actions(&ctx, &actions, timeout).await {
+            Ok(build_res) => Ok(build_res),
+            Err(cause) => {
+                // Clean up the parts of the query committed to the nodes, so that it can be
+                // scheduled again.
+                self.cancel_distributed_query(&ctx, timeout).await;
+                Err(cause)
+            }
+        }
+    }
+
+    async fn try_commit_actions(
+        &self,
+        ctx: &Arc<QueryContext>,
+        actions: &QueryFragmentsActions,
+        timeout: u64,
+    ) -> Result<PipelineBuildResult> {
         let root_actions = actions.get_root_actions()?;
 
         // Initialize channels between cluster nodes
-        actions
-            .get_init_nodes_channel_packets()?
-            .commit(&self.config, timeout)
-            .await?;
+        for packet in actions.get_init_nodes_channel_packets()? {
+            self.commit_packet(ctx, &packet.executor.id, &packet, timeout)
+                .await?;
+        }
 
         // Submit distributed tasks to all nodes.
         let (local_query_fragments_plan_packet, query_fragments_plan_packets) =
             actions.get_query_fragments_plan_packets()?;
 
         // Submit tasks to other nodes
-        query_fragments_plan_packets
-            .commit(&self.config, timeout)
-            .await?;
+        for packet in query_fragments_plan_packets {
+            self.commit_packet(ctx, &packet.executor, &packet, timeout)
+                .await?;
+        }
 
         // Submit tasks to localhost
-        self.init_query_fragments_plan(&ctx, &local_query_fragments_plan_packet)?;
+        self.init_query_fragments_plan(ctx, &local_query_fragments_plan_packet)?;
 
         // Get local pipeline of local task
-        let build_res = self.get_root_pipeline(ctx, root_actions)?;
+        let build_res = self.get_root_pipeline(ctx.clone(), root_actions)?;
+
+        for packet in actions.get_execute_partial_query_packets()? {
+            self.commit_packet(ctx, &packet.executor, &packet, timeout)
+                .await?;
+        }
 
-        actions
-            .get_execute_partial_query_packets()?
-            .commit(&self.config, timeout)
-            .await?;
         Ok(build_res)
     }
 
+    // Commit the packet to the executor, which is left out of the cluster of the query if it is lost.
+    async fn commit_packet(
+        &self,
+        ctx: &Arc<QueryContext>,
+        executor: &str,
+        packet: &impl Packet,
+        timeout: u64,
+    ) -> Result<()> {
+        match packet.commit(&self.config, timeout).await {
+            Ok(_) => Ok(()),
+            Err(cause)
+                if executor != ctx.get_cluster().local_id
+                    && (cause.code() == ErrorCode::ClusterNodeLostCode()
+                        || cause.code() == ErrorCode::CannotConnectNodeCode()) =>
+            {
+                ctx.exclude_cluster_node(executor);
+                Err(ErrorCode::ClusterNodeLost(format!(
+                    "Lost node {} of the cluster, cause: {}",
+                    executor,
+                    cause.message()
+                )))
+            }
+            Err(cause) => Err(cause),
+        }
+    }
+
+    async fn cancel_distributed_query(&self, ctx: &Arc<QueryContext>, timeout: u64) {
+        let query_id = ctx.get_id();
+        let cluster = ctx.get_cluster();
+
+        for node in cluster.get_nodes() {
+            if cluster.is_local(&node) {
+                continue;
+            }
+
+            let action = FlightAction::CancelAction(CancelAction {
+                query_id: query_id.clone(),
+            });
+
+            let res = match Self::create_client(&self.config, &node.flight_address).await {
+                Ok(mut flight_client) => flight_client.execute_action(action, timeout).await,
+                Err(cause) => Err(cause),
+            };
+
+            if let Err(cause) = res {
+                warn!(
+                    "Cannot cancel query {} on node {}, cause: {:?}",
+                    query_id, node.id, cause
+                );
+            }
+        }
+
+        self.cancel_query(&query_id);
+    }
+
     fn get_root_pipeline(
         &self,
         ctx: Arc<QueryContext>,
@@ -550,7 +642,7 @@ impl QueryCoordinator {
             }
         }
 
-        let executor_settings = ExecutorSettings::try_create(&info.query_ctx.get_settings())?;
+        let executor_settings = ExecutorSettings::try_create(&info.query_ctx)?;
 
         let executor = PipelineCompleteExecutor::from_pipelines(pipelines, executor_settings)?;
 
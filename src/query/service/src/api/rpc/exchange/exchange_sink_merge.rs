@@ -15,7 +15,6 @@
 use std::any::Any;
 use std::sync::Arc;
 
-use common_arrow::arrow::io::flight::serialize_batch;
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -100,19 +99,9 @@ impl Processor for ExchangeMergeSink {
                 return Ok(());
             }
 
-            let chunks = data_block.try_into()?;
             let options = &self.serialize_params.options;
             let ipc_fields = &self.serialize_params.ipc_fields;
-            let (dicts, values) = serialize_batch(&chunks, ipc_fields, options)?;
-
-            if !dicts.is_empty() {
-                return Err(ErrorCode::UnImplement(
-                    "DatabendQuery does not implement dicts.",
-                ));
-            }
-
-            // FlightData
-            let data = FragmentData::create(values);
+            let data = FragmentData::serialize_block(data_block, ipc_fields, options)?;
             self.output_data = Some(DataPacket::FragmentData(data));
         }
 
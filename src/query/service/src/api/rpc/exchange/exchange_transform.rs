@@ -15,10 +15,6 @@
 use std::any::Any;
 use std::sync::Arc;
 
-use common_arrow::arrow::io::flight::default_ipc_fields;
-use common_arrow::arrow::io::flight::deserialize_batch;
-use common_arrow::arrow::io::flight::serialize_batch;
-use common_arrow::arrow::io::ipc::IpcSchema;
 use common_catalog::table_context::TableContext;
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
@@ -228,19 +224,11 @@ impl Processor for ExchangeTransform {
                     output_data.data_block = Some(data_block);
                     output_data.serialized_blocks.push(None);
                 } else {
-                    let chunks = data_block.try_into()?;
                     let options = &self.serialize_params.options;
                     let ipc_fields = &self.serialize_params.ipc_fields;
-                    let (dicts, values) = serialize_batch(&chunks, ipc_fields, options)?;
-
-                    if !dicts.is_empty() {
-                        return Err(ErrorCode::UnImplement(
-                            "DatabendQuery does not implement dicts.",
-                        ));
-                    }
+                    let data = FragmentData::serialize_block(data_block, ipc_fields, options)?;
 
                     output_data.has_serialized_blocks = true;
-                    let data = FragmentData::create(values);
                     output_data
                         .serialized_blocks
                         .push(Some(DataPacket::FragmentData(data)));
@@ -306,25 +294,12 @@ impl ExchangeTransform {
         }
 
         let schema = &self.shuffle_exchange_params.schema;
-
-        let arrow_schema = Arc::new(schema.to_arrow());
-        let ipc_fields = default_ipc_fields(&arrow_schema.fields);
-        let ipc_schema = IpcSchema {
-            fields: ipc_fields,
-            is_little_endian: true,
-        };
-
-        let batch = deserialize_batch(
-            &fragment_data.data,
-            &arrow_schema.fields,
-            &ipc_schema,
-            &Default::default(),
-        )?;
+        let data_block = fragment_data.deserialize_block(schema)?;
 
         self.output_data = Some(OutputData {
             serialized_blocks: vec![],
             has_serialized_blocks: false,
-            data_block: Some(DataBlock::from_chunk(schema, &batch)?),
+            data_block: Some(data_block),
         });
 
         Ok(())
@@ -69,12 +69,17 @@ impl StatisticsSender {
 
             while !shutdown_flag.load(Ordering::Relaxed) {
                 match futures::future::select(recv, notified).await {
-                    Either::Right((Ok(None), _))
-                    | Either::Right((Err(_), _))
-                    | Either::Left((Ok(None), _))
-                    | Either::Left((Err(_), _)) => {
+                    Either::Right((Ok(None), _)) | Either::Right((Err(_), _)) => {
                         break;
                     }
+                    Either::Left((Ok(None), _)) | Either::Left((Err(_), _)) => {
+                        // The request server is gone, e.g. the query is killed or failed there,
+                        // so the fragments of the node are cancelled instead of running for nothing.
+                        if !shutdown_flag.load(Ordering::Relaxed) {
+                            ctx.get_exchange_manager().shutdown_query(&query_id);
+                        }
+                        return;
+                    }
                     Either::Right((Ok(Some(error_code)), _recv)) => {
                         let data = DataPacket::ErrorCode(error_code);
                         if let Err(error_code) = flight_exchange.send(data).await {
@@ -27,6 +27,31 @@ pub struct CancelAction {
     pub query_id: String,
 }
 
+impl TryInto<CancelAction> for Vec<u8> {
+    type Error = Status;
+
+    fn try_into(self) -> Result<CancelAction, Self::Error> {
+        match std::str::from_utf8(&self) {
+            Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+            Ok(utf8_body) => match serde_json::from_str::<CancelAction>(utf8_body) {
+                Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+                Ok(action) => Ok(action),
+            },
+        }
+    }
+}
+
+impl TryInto<Vec<u8>> for CancelAction {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(&self).map_err_to_code(
+            ErrorCode::LogicalError,
+            || "Logical error: cannot serialize CancelAction.",
+        )
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct InitQueryFragmentsPlan {
     pub executor_packet: QueryFragmentsPlanPacket,
@@ -92,6 +117,7 @@ pub enum FlightAction {
     InitQueryFragmentsPlan(InitQueryFragmentsPlan),
     InitNodesChannel(InitNodesChannel),
     ExecutePartialQuery(String),
+    CancelAction(CancelAction),
 }
 
 impl TryInto<FlightAction> for Action {
@@ -107,6 +133,7 @@ impl TryInto<FlightAction> for Action {
                 Ok(query_id) => Ok(FlightAction::ExecutePartialQuery(query_id)),
                 Err(cause) => Err(Status::invalid_argument(cause.to_string())),
             },
+            "CancelAction" => Ok(FlightAction::CancelAction(self.body.try_into()?)),
             un_implemented => Err(Status::unimplemented(format!(
                 "UnImplement action {}",
                 un_implemented
@@ -132,6 +159,10 @@ impl TryInto<Action> for FlightAction {
                 r#type: String::from("ExecutePartialQuery"),
                 body: query_id.into_bytes(),
             }),
+            FlightAction::CancelAction(cancel_action) => Ok(Action {
+                r#type: String::from("CancelAction"),
+                body: cancel_action.try_into()?,
+            }),
         }
     }
 }
@@ -30,6 +30,7 @@ use futures_util::StreamExt;
 use tonic::metadata::MetadataKey;
 use tonic::metadata::MetadataValue;
 use tonic::transport::channel::Channel;
+use tonic::Code;
 use tonic::Request;
 use tonic::Status;
 use tonic::Streaming;
@@ -79,7 +80,8 @@ impl FlightClient {
                         .with_metadata("x-query-id", query_id)?
                         .build(),
                 )
-                .await?
+                .await
+                .map_err(from_status)?
                 .into_inner(),
         ))
     }
@@ -102,7 +104,8 @@ impl FlightClient {
                         .with_metadata("x-fragment-id", &fragment_id.to_string())?
                         .build(),
                 )
-                .await?
+                .await
+                .map_err(from_status)?
                 .into_inner(),
         ))
     }
@@ -130,9 +133,9 @@ impl FlightClient {
         let mut request = common_tracing::inject_span_to_tonic_request(request);
         request.set_timeout(Duration::from_secs(timeout));
 
-        let response = self.inner.do_action(request).await?;
+        let response = self.inner.do_action(request).await.map_err(from_status)?;
 
-        match response.into_inner().message().await? {
+        match response.into_inner().message().await.map_err(from_status)? {
             Some(response) => Ok(response.body),
             None => Err(ErrorCode::EmptyDataFromServer(format!(
                 "Can not receive data from flight server, action: {:?}",
@@ -142,6 +145,23 @@ impl FlightClient {
     }
 }
 
+/// The failures of the transport, rather than the errors raised by the remote node, mean that the
+/// remote node is unreachable or the connection to it is broken.
+fn from_status(status: Status) -> ErrorCode {
+    match status.code() {
+        Code::Unavailable
+        | Code::Cancelled
+        | Code::Aborted
+        | Code::Internal
+        | Code::DeadlineExceeded => ErrorCode::ClusterNodeLost(status.to_string()),
+        // The errors raised by the remote node always carry the serialized error code.
+        Code::Unknown if status.details().is_empty() => {
+            ErrorCode::ClusterNodeLost(status.to_string())
+        }
+        _ => ErrorCode::from(status),
+    }
+}
+
 #[derive(Clone)]
 pub enum FlightExchange {
     // dummy if localhost
@@ -277,7 +297,7 @@ impl ClientFlightExchange {
             Err(_) => Ok(None),
             Ok(message) => match message {
                 Ok(data) => Ok(Some(DataPacket::try_from(data)?)),
-                Err(status) => Err(ErrorCode::from(status)),
+                Err(status) => Err(from_status(status)),
             },
         }
     }
@@ -287,7 +307,7 @@ impl ClientFlightExchange {
             Err(_) => Ok(None),
             Ok(message) => match message {
                 Ok(data) => Ok(Some(DataPacket::try_from(data)?)),
-                Err(status) => Err(ErrorCode::from(status)),
+                Err(status) => Err(from_status(status)),
             },
         }
     }
@@ -393,7 +413,7 @@ impl ServerFlightExchange {
             Err(_) => Ok(None),
             Ok(message) => match message {
                 Ok(data) => Ok(Some(DataPacket::try_from(data)?)),
-                Err(status) => Err(ErrorCode::from(status)),
+                Err(status) => Err(from_status(status)),
             },
         }
     }
@@ -403,7 +423,7 @@ impl ServerFlightExchange {
             Err(_) => Ok(None),
             Ok(message) => match message {
                 Ok(data) => Ok(Some(DataPacket::try_from(data)?)),
-                Err(status) => Err(ErrorCode::from(status)),
+                Err(status) => Err(from_status(status)),
             },
         }
     }
@@ -29,6 +29,7 @@ use common_arrow::arrow_format::flight::data::Result as FlightResult;
 use common_arrow::arrow_format::flight::data::SchemaResult;
 use common_arrow::arrow_format::flight::data::Ticket;
 use common_arrow::arrow_format::flight::service::flight_service_server::FlightService;
+use common_exception::ErrorCode;
 use tokio_stream::Stream;
 use tonic::Request;
 use tonic::Response as RawResponse;
@@ -141,6 +142,7 @@ impl FlightService for DatabendQueryFlightService {
 
         let action_result = match &flight_action {
             FlightAction::InitQueryFragmentsPlan(init_query_fragments_plan) => {
+                reject_if_draining()?;
                 let session = SessionManager::instance()
                     .create_session(SessionType::FlightRPC)
                     .await?;
@@ -151,6 +153,7 @@ impl FlightService for DatabendQueryFlightService {
                 FlightResult { body: vec![] }
             }
             FlightAction::InitNodesChannel(init_nodes_channel) => {
+                reject_if_draining()?;
                 let publisher_packet = &init_nodes_channel.init_nodes_channel_packet;
                 DataExchangeManager::instance()
                     .init_nodes_channel(publisher_packet)
@@ -161,6 +164,11 @@ impl FlightService for DatabendQueryFlightService {
             FlightAction::ExecutePartialQuery(query_id) => {
                 DataExchangeManager::instance().execute_partial_query(query_id)?;
 
+                FlightResult { body: vec![] }
+            }
+            FlightAction::CancelAction(cancel_action) => {
+                DataExchangeManager::instance().cancel_query(&cancel_action.query_id);
+
                 FlightResult { body: vec![] }
             }
         };
@@ -185,3 +193,14 @@ impl FlightService for DatabendQueryFlightService {
         ))
     }
 }
+
+// A draining node takes no new fragments, the coordinator sees it as lost and reschedules the
+// fragments on the rest of the cluster.
+fn reject_if_draining() -> common_exception::Result<()> {
+    match SessionManager::instance().is_draining() {
+        false => Ok(()),
+        true => Err(ErrorCode::ClusterNodeLost(
+            "The node is draining, no new fragment is accepted",
+        )),
+    }
+}
@@ -15,6 +15,7 @@
 pub use flight_actions::FlightAction;
 pub use flight_client::FlightClient;
 pub use flight_service::DatabendQueryFlightService;
+pub use udf_client::UDFFlightClient;
 
 mod exchange;
 mod flight_actions;
@@ -26,6 +27,7 @@ mod flight_scatter_hash_v2;
 mod flight_service;
 mod packets;
 mod request_builder;
+mod udf_client;
 
 pub use exchange::BroadcastExchange;
 pub use exchange::DataExchange;
@@ -24,15 +24,27 @@ use std::task::Poll;
 use byteorder::BigEndian;
 use byteorder::ReadBytesExt;
 use byteorder::WriteBytesExt;
+use common_arrow::arrow::bitmap::Bitmap;
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::IntegerType;
 use common_arrow::arrow::io::flight::deserialize_batch;
+use common_arrow::arrow::io::flight::deserialize_dictionary;
 use common_arrow::arrow::io::flight::serialize_batch;
+use common_arrow::arrow::io::ipc::read::Dictionaries;
 use common_arrow::arrow::io::ipc::write::default_ipc_fields;
 use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_arrow::arrow::io::ipc::IpcField;
 use common_arrow::arrow::io::ipc::IpcSchema;
 use common_arrow::arrow_format::flight::data::FlightData;
 use common_base::base::ProgressValues;
 use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
 use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DictionaryColumn;
+use common_datavalues::NullableColumn;
+use common_datavalues::Series;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use futures::Stream;
@@ -43,11 +55,117 @@ use crate::sessions::TableContext;
 
 pub struct FragmentData {
     pub data: FlightData,
+    // the dictionary batches of the dictionary-encoded columns
+    pub dictionaries: Vec<FlightData>,
+    // the indices of the dictionary-encoded columns
+    pub dictionary_columns: Vec<usize>,
 }
 
 impl FragmentData {
     pub fn create(data: FlightData) -> FragmentData {
-        FragmentData { data }
+        FragmentData {
+            data,
+            dictionaries: vec![],
+            dictionary_columns: vec![],
+        }
+    }
+
+    /// Serialize the block, the dictionary-encoded columns are sent as arrow dictionaries rather
+    /// than materialized.
+    pub fn serialize_block(
+        block: DataBlock,
+        ipc_fields: &[IpcField],
+        options: &WriteOptions,
+    ) -> Result<FragmentData> {
+        let dictionary_columns = block
+            .columns()
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| Self::dictionary_of(column).is_some())
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        if dictionary_columns.is_empty() {
+            let chunks = block.try_into()?;
+            let (dicts, values) = serialize_batch(&chunks, ipc_fields, options)?;
+
+            if !dicts.is_empty() {
+                return Err(ErrorCode::UnImplement(
+                    "DatabendQuery does not implement dicts.",
+                ));
+            }
+            return Ok(FragmentData::create(values));
+        }
+
+        let schema = block.schema();
+        let mut fields = schema.to_arrow().fields;
+        let mut arrays = Vec::with_capacity(block.num_columns());
+        for (index, (column, field)) in block.columns().iter().zip(schema.fields()).enumerate() {
+            let array = match Self::dictionary_of(column) {
+                Some((dictionary, validity)) => {
+                    let array = dictionary.as_arrow_dictionary_array(field.data_type().clone())?;
+                    let array = match validity {
+                        Some(validity) => array.with_validity(Some(validity)),
+                        None => array,
+                    };
+                    fields[index].data_type = array.data_type().clone();
+                    array
+                }
+                None => column.as_arrow_array(field.data_type().clone()),
+            };
+            arrays.push(array);
+        }
+
+        let ipc_fields = default_ipc_fields(&fields);
+        let chunk = Chunk::try_new(arrays)?;
+        let (dictionaries, data) = serialize_batch(&chunk, &ipc_fields, options)?;
+        Ok(FragmentData {
+            data,
+            dictionaries,
+            dictionary_columns,
+        })
+    }
+
+    /// Deserialize the block of the `schema` sent by `serialize_block`.
+    pub fn deserialize_block(&self, schema: &DataSchemaRef) -> Result<DataBlock> {
+        let mut fields = schema.to_arrow().fields;
+        for index in &self.dictionary_columns {
+            let field = fields.get_mut(*index).ok_or_else(|| {
+                ErrorCode::BadBytes(format!("Unknown dictionary-encoded column {}", index))
+            })?;
+            let values = Box::new(field.data_type.clone());
+            field.data_type = ArrowDataType::Dictionary(IntegerType::UInt32, values, false);
+        }
+
+        let ipc_schema = IpcSchema {
+            fields: default_ipc_fields(&fields),
+            is_little_endian: true,
+        };
+
+        let mut dictionaries = Dictionaries::default();
+        for dictionary in &self.dictionaries {
+            deserialize_dictionary(dictionary, &fields, &ipc_schema, &mut dictionaries)?;
+        }
+
+        let batch = deserialize_batch(&self.data, &fields, &ipc_schema, &dictionaries)?;
+        DataBlock::from_chunk(schema, &batch)
+    }
+
+    /// The dictionary of the column and the validity of its rows, if it is dictionary-encoded.
+    fn dictionary_of(column: &ColumnRef) -> Option<(&DictionaryColumn, Option<Bitmap>)> {
+        if column.is_dictionary() {
+            let dictionary = Series::check_get::<DictionaryColumn>(column).ok()?;
+            return Some((dictionary, None));
+        }
+
+        if column.is_nullable() && !column.is_const() {
+            let nullable: &NullableColumn = Series::check_get(column).ok()?;
+            if nullable.inner().is_dictionary() {
+                let dictionary = Series::check_get::<DictionaryColumn>(nullable.inner()).ok()?;
+                return Some((dictionary, Some(nullable.ensure_validity().clone())));
+            }
+        }
+        None
     }
 }
 
@@ -173,8 +291,33 @@ impl From<DataPacket> for FlightData {
 
 impl From<FragmentData> for FlightData {
     fn from(data: FragmentData) -> Self {
+        // the dictionaries, if any, follow the type of the packet in the app metadata
+        let mut app_metadata = vec![0x01];
+        if !data.dictionaries.is_empty() {
+            app_metadata
+                .write_u64::<BigEndian>(data.dictionary_columns.len() as u64)
+                .unwrap();
+            for index in &data.dictionary_columns {
+                app_metadata.write_u64::<BigEndian>(*index as u64).unwrap();
+            }
+
+            app_metadata
+                .write_u64::<BigEndian>(data.dictionaries.len() as u64)
+                .unwrap();
+            for dictionary in &data.dictionaries {
+                app_metadata
+                    .write_u64::<BigEndian>(dictionary.data_header.len() as u64)
+                    .unwrap();
+                app_metadata
+                    .write_u64::<BigEndian>(dictionary.data_body.len() as u64)
+                    .unwrap();
+                app_metadata.extend_from_slice(&dictionary.data_header);
+                app_metadata.extend_from_slice(&dictionary.data_body);
+            }
+        }
+
         FlightData {
-            app_metadata: vec![0x01],
+            app_metadata,
             data_body: data.data.data_body,
             data_header: data.data.data_header,
             flight_descriptor: None,
@@ -299,11 +442,43 @@ impl TryFrom<FlightData> for FragmentData {
     type Error = ErrorCode;
 
     fn try_from(flight_data: FlightData) -> Result<Self> {
-        Ok(FragmentData::create(FlightData {
-            app_metadata: vec![],
-            flight_descriptor: None,
-            data_body: flight_data.data_body,
-            data_header: flight_data.data_header,
-        }))
+        let mut dictionary_columns = vec![];
+        let mut dictionaries = vec![];
+
+        let mut bytes = &flight_data.app_metadata[1..];
+        if !bytes.is_empty() {
+            let columns = bytes.read_u64::<BigEndian>()?;
+            for _index in 0..columns {
+                dictionary_columns.push(bytes.read_u64::<BigEndian>()? as usize);
+            }
+
+            let dictionaries_size = bytes.read_u64::<BigEndian>()?;
+            for _index in 0..dictionaries_size {
+                let header_len = bytes.read_u64::<BigEndian>()? as usize;
+                let body_len = bytes.read_u64::<BigEndian>()? as usize;
+
+                let mut data_header = vec![0; header_len];
+                let mut data_body = vec![0; body_len];
+                bytes.read_exact(&mut data_header)?;
+                bytes.read_exact(&mut data_body)?;
+                dictionaries.push(FlightData {
+                    app_metadata: vec![],
+                    flight_descriptor: None,
+                    data_body,
+                    data_header,
+                });
+            }
+        }
+
+        Ok(FragmentData {
+            data: FlightData {
+                app_metadata: vec![],
+                flight_descriptor: None,
+                data_body: flight_data.data_body,
+                data_header: flight_data.data_header,
+            },
+            dictionaries,
+            dictionary_columns,
+        })
     }
 }
@@ -0,0 +1,184 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_arrow::arrow::io::flight::default_ipc_fields;
+use common_arrow::arrow::io::flight::deserialize_batch;
+use common_arrow::arrow::io::flight::deserialize_schemas;
+use common_arrow::arrow::io::flight::serialize_batch;
+use common_arrow::arrow::io::flight::serialize_schema;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_arrow::arrow_format::flight::data::flight_descriptor::DescriptorType;
+use common_arrow::arrow_format::flight::data::FlightData;
+use common_arrow::arrow_format::flight::data::FlightDescriptor;
+use common_arrow::arrow_format::flight::service::flight_service_client::FlightServiceClient;
+use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataTypeImpl;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_grpc::ConnectionFactory;
+use futures::stream;
+use futures::StreamExt;
+use tonic::transport::channel::Channel;
+use tonic::Request;
+
+/// Client of the external UDF servers.
+///
+/// A UDF server is an Arrow Flight server. The handler of a function is addressed by a
+/// flight descriptor whose path is the handler name:
+/// - `GetFlightInfo` returns the schema of the handler, the fields of the arguments
+///   followed by the field of the result.
+/// - `DoExchange` receives the arguments as record batches and streams back the results,
+///   one row per input row.
+pub struct UDFFlightClient {
+    inner: FlightServiceClient<Channel>,
+    timeout: Duration,
+}
+
+impl UDFFlightClient {
+    pub async fn connect(address: &str, timeout: u64) -> Result<UDFFlightClient> {
+        let timeout = Duration::from_secs(timeout);
+        let channel = ConnectionFactory::create_rpc_channel(address, Some(timeout), None)
+            .await
+            .map_err(|cause| {
+                ErrorCode::UDFServerConnectError(format!(
+                    "Cannot connect to UDF server {}, cause: {}",
+                    address, cause
+                ))
+            })?;
+
+        Ok(UDFFlightClient {
+            inner: FlightServiceClient::new(channel),
+            timeout,
+        })
+    }
+
+    fn descriptor(handler: &str) -> FlightDescriptor {
+        FlightDescriptor {
+            r#type: DescriptorType::Path as i32,
+            cmd: vec![],
+            path: vec![handler.to_string()],
+        }
+    }
+
+    /// Check that the handler exists on the server and accepts the declared types.
+    pub async fn check_schema(
+        &mut self,
+        handler: &str,
+        arg_types: &[DataTypeImpl],
+        return_type: &DataTypeImpl,
+    ) -> Result<()> {
+        let mut request = Request::new(Self::descriptor(handler));
+        request.set_timeout(self.timeout);
+        let flight_info = self.inner.get_flight_info(request).await?.into_inner();
+        let (schema, _) = deserialize_schemas(&flight_info.schema)?;
+
+        let remote_types = schema
+            .fields
+            .iter()
+            .map(|field| field.data_type().clone())
+            .collect::<Vec<_>>();
+        let expect_types = arg_types
+            .iter()
+            .chain(std::iter::once(return_type))
+            .map(|data_type| DataField::new("", data_type.clone()).to_arrow().data_type)
+            .collect::<Vec<_>>();
+
+        if remote_types != expect_types {
+            return Err(ErrorCode::UDFSchemaMismatch(format!(
+                "The schema of handler {} is {:?}, but the function declares {:?}",
+                handler, remote_types, expect_types
+            )));
+        }
+        Ok(())
+    }
+
+    /// Send the arguments to the handler and return the computed column.
+    pub async fn do_exchange(
+        &mut self,
+        handler: &str,
+        arguments: DataBlock,
+        return_type: &DataTypeImpl,
+    ) -> Result<ColumnRef> {
+        let num_rows = arguments.num_rows();
+        let arrow_schema = arguments.schema().to_arrow();
+        let ipc_fields = default_ipc_fields(&arrow_schema.fields);
+
+        let mut schema_flight = serialize_schema(&arrow_schema, Some(&ipc_fields));
+        schema_flight.flight_descriptor = Some(Self::descriptor(handler));
+        let chunk = arguments.try_into()?;
+        let (_dicts, data_flight) =
+            serialize_batch(&chunk, &ipc_fields, &WriteOptions { compression: None })?;
+
+        let mut request = Request::new(stream::iter(vec![schema_flight, data_flight]));
+        request.set_timeout(self.timeout);
+        let mut response = self.inner.do_exchange(request).await?.into_inner();
+
+        let result_schema = match response.next().await {
+            Some(flight_data) => deserialize_schemas(&flight_data?.data_header)?,
+            None => {
+                return Err(ErrorCode::UDFDataError(format!(
+                    "UDF server returned no schema for handler {}",
+                    handler
+                )));
+            }
+        };
+        let data_schema = Arc::new(DataSchema::new(vec![DataField::new(
+            "result",
+            return_type.clone(),
+        )]));
+
+        let mut blocks = vec![];
+        while let Some(flight_data) = response.next().await {
+            let flight_data: FlightData = flight_data?;
+            let chunk = deserialize_batch(
+                &flight_data,
+                &result_schema.0.fields,
+                &result_schema.1,
+                &Default::default(),
+            )?;
+            if chunk.columns().len() != 1 {
+                return Err(ErrorCode::UDFDataError(format!(
+                    "UDF server returned {} columns for handler {}, expect 1",
+                    chunk.columns().len(),
+                    handler
+                )));
+            }
+            blocks.push(DataBlock::from_chunk(&data_schema, &chunk)?);
+        }
+
+        if blocks.is_empty() {
+            return Err(ErrorCode::UDFDataError(format!(
+                "UDF server returned no data for handler {}",
+                handler
+            )));
+        }
+
+        let result = DataBlock::concat_blocks(&blocks)?;
+        if result.num_rows() != num_rows {
+            return Err(ErrorCode::UDFDataError(format!(
+                "UDF server returned {} rows for handler {}, expect {}",
+                result.num_rows(),
+                handler,
+                num_rows
+            )));
+        }
+        Ok(result.column(0).clone())
+    }
+}
@@ -0,0 +1,305 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use common_base::base::GlobalIORuntime;
+use common_base::base::TrySpawn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_storages_preludes::system::AuditLogElement;
+use common_storages_preludes::system::AuditLogQueue;
+use common_tracing::QueryLogger;
+use once_cell::sync::OnceCell;
+use tracing::error;
+use tracing::info;
+use tracing::subscriber;
+
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::Config;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The classes of the audit events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditEventClass {
+    /// The authentications of the users, succeeded or failed.
+    Login,
+    /// The statements changing the schemas, the users, the privileges and the policies.
+    Ddl,
+    /// The statements changing the data.
+    Dml,
+    /// The statements failed for lacking the privileges.
+    Denied,
+}
+
+impl AuditEventClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEventClass::Login => "login",
+            AuditEventClass::Ddl => "ddl",
+            AuditEventClass::Dml => "dml",
+            AuditEventClass::Denied => "denied",
+        }
+    }
+
+    /// The class of the statements by the kinds of the plans, `None` for the reads.
+    pub fn of_query_kind(kind: &str) -> Option<AuditEventClass> {
+        const DML_KINDS: &[&str] = &["Insert", "Delete", "Copy", "RemoveStage"];
+        const DDL_PREFIXES: &[&str] = &[
+            "Create",
+            "Drop",
+            "Undrop",
+            "Rename",
+            "Alter",
+            "Truncate",
+            "Flashback",
+            "Grant",
+            "Revoke",
+            "SetColumn",
+            "UnsetColumn",
+        ];
+
+        if DML_KINDS.contains(&kind) {
+            Some(AuditEventClass::Dml)
+        } else if DDL_PREFIXES.iter().any(|prefix| kind.starts_with(prefix)) {
+            Some(AuditEventClass::Ddl)
+        } else {
+            None
+        }
+    }
+}
+
+impl TryFrom<&str> for AuditEventClass {
+    type Error = ErrorCode;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "login" => Ok(AuditEventClass::Login),
+            "ddl" => Ok(AuditEventClass::Ddl),
+            "dml" => Ok(AuditEventClass::Dml),
+            "denied" => Ok(AuditEventClass::Denied),
+            _ => Err(ErrorCode::InvalidConfig(format!(
+                "Unknown audit event class {}, expect login, ddl, dml or denied",
+                value
+            ))),
+        }
+    }
+}
+
+/// Where the audit events are written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum AuditSink {
+    /// The rotating files under `{log.file.dir}/audit`.
+    File,
+    /// POST the events as JSON to the URL.
+    Webhook(String),
+    /// The in-memory table `system.audit_log`.
+    Table,
+}
+
+/// Writes the audit events of the classes configured by `audit_log_events` to the sinks
+/// configured by `audit_log_sinks`.
+///
+/// The events are written by the sessions, the webhook posts are sent in background, the
+/// failures of them are logged and never fail the sessions.
+pub struct AuditLogger {
+    classes: Vec<AuditEventClass>,
+    sinks: Vec<AuditSink>,
+}
+
+static AUDIT_LOGGER: OnceCell<Arc<AuditLogger>> = OnceCell::new();
+
+impl AuditLogger {
+    pub fn init(config: &Config) -> Result<()> {
+        let classes = split_list(&config.query.audit_log_events)
+            .map(AuditEventClass::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        let sinks = split_list(&config.query.audit_log_sinks)
+            .map(|sink| match sink.to_lowercase().as_str() {
+                "file" => Ok(AuditSink::File),
+                "table" => Ok(AuditSink::Table),
+                "webhook" if config.query.audit_log_webhook_url.is_empty() => {
+                    Err(ErrorCode::InvalidConfig(
+                        "audit_log_webhook_url is required by the webhook audit sink",
+                    ))
+                }
+                "webhook" => Ok(AuditSink::Webhook(
+                    config.query.audit_log_webhook_url.clone(),
+                )),
+                _ => Err(ErrorCode::InvalidConfig(format!(
+                    "Unknown audit sink {}, expect file, webhook or table",
+                    sink
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if classes.is_empty() || sinks.is_empty() {
+            return Ok(());
+        }
+        AUDIT_LOGGER
+            .set(Arc::new(AuditLogger { classes, sinks }))
+            .ok();
+        Ok(())
+    }
+
+    /// Returns `None` if the audit log is disabled.
+    pub fn instance() -> Option<Arc<AuditLogger>> {
+        AUDIT_LOGGER.get().cloned()
+    }
+
+    /// Logs an authentication of the user, `err` is the cause if it failed.
+    pub fn log_login(
+        tenant: &str,
+        user: &str,
+        client_address: Option<&str>,
+        handler_type: String,
+        err: Option<&ErrorCode>,
+    ) {
+        let logger = match Self::instance() {
+            Some(logger) if logger.is_enabled(AuditEventClass::Login) => logger,
+            _ => return,
+        };
+
+        let (exception_code, exception_text) = exception_fields(err);
+        logger.write(AuditLogElement {
+            event_time: Utc::now().timestamp_micros(),
+            event_class: AuditEventClass::Login.as_str().to_string(),
+            tenant_id: tenant.to_string(),
+            sql_user: user.to_string(),
+            client_address: client_address.unwrap_or_default().to_string(),
+            handler_type,
+            query_id: "".to_string(),
+            query_kind: "".to_string(),
+            query_text: "".to_string(),
+            exception_code,
+            exception_text,
+        });
+    }
+
+    /// Logs the statement of the query finished, `err` is the cause if it failed. The statements
+    /// failed for lacking the privileges are logged as `denied`, whatever the kinds.
+    pub fn log_query(ctx: &QueryContext, err: Option<&ErrorCode>) {
+        let logger = match Self::instance() {
+            Some(logger) => logger,
+            None => return,
+        };
+
+        let query_kind = ctx.get_query_kind();
+        let class = match err {
+            Some(e) if e.code() == ErrorCode::PermissionDenied("").code() => {
+                AuditEventClass::Denied
+            }
+            _ => match AuditEventClass::of_query_kind(&query_kind) {
+                Some(class) => class,
+                None => return,
+            },
+        };
+        if !logger.is_enabled(class) {
+            return;
+        }
+
+        let (exception_code, exception_text) = exception_fields(err);
+        logger.write(AuditLogElement {
+            event_time: Utc::now().timestamp_micros(),
+            event_class: class.as_str().to_string(),
+            tenant_id: ctx.get_tenant(),
+            sql_user: ctx
+                .get_current_user()
+                .map(|user| user.identity().to_string())
+                .unwrap_or_default(),
+            client_address: ctx
+                .get_client_address()
+                .map(|addr| addr.to_string())
+                .unwrap_or_default(),
+            handler_type: ctx.get_current_session().get_type().to_string(),
+            query_id: ctx.get_id(),
+            query_kind,
+            query_text: ctx.get_query_str(),
+            exception_code,
+            exception_text,
+        });
+    }
+
+    fn is_enabled(&self, class: AuditEventClass) -> bool {
+        self.classes.contains(&class)
+    }
+
+    fn write(&self, event: AuditLogElement) {
+        let event_str = match serde_json::to_string(&event) {
+            Ok(event_str) => event_str,
+            Err(cause) => {
+                error!("fail to serialize audit event {:?}", cause);
+                return;
+            }
+        };
+
+        for sink in &self.sinks {
+            match sink {
+                AuditSink::File => {
+                    if let Some(logger) = QueryLogger::instance().get_audit_subscriber() {
+                        subscriber::with_default(logger, || {
+                            info!("{}", event_str);
+                        });
+                    }
+                }
+                AuditSink::Webhook(url) => {
+                    let url = url.clone();
+                    let body = event_str.clone();
+                    GlobalIORuntime::instance().spawn(async move {
+                        if let Err(cause) = post_event(&url, body).await {
+                            error!("fail to post audit event to {}: {:?}", url, cause);
+                        }
+                    });
+                }
+                AuditSink::Table => match AuditLogQueue::instance() {
+                    Ok(queue) => {
+                        if let Err(cause) = queue.append_data(event.clone()) {
+                            error!("fail to append audit event {:?}", cause);
+                        }
+                    }
+                    Err(cause) => error!("fail to append audit event {:?}", cause),
+                },
+            }
+        }
+    }
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+fn exception_fields(err: Option<&ErrorCode>) -> (i32, String) {
+    match err {
+        None => (0, "".to_string()),
+        Some(e) => (e.code().into(), e.message()),
+    }
+}
+
+async fn post_event(url: &str, body: String) -> reqwest::Result<()> {
+    reqwest::Client::new()
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body)
+        .timeout(WEBHOOK_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
@@ -12,22 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_types::AuthInfo;
+use common_meta_types::UserIdentity;
 use common_meta_types::UserInfo;
 use common_users::CustomClaims;
 use common_users::JwtAuthenticator;
+use common_users::LdapAuthenticator;
+use common_users::LdapConfig;
+use common_users::OidcClient;
 use common_users::UserApiProvider;
 use jwtk::Claims;
 
+use crate::audit::AuditLogger;
 use crate::sessions::Session;
 pub use crate::Config;
 
 pub struct AuthMgr {
     jwt_auth: Option<JwtAuthenticator>,
+    ldap_auth: Option<LdapAuthenticator>,
 }
 
 pub enum Credential {
@@ -45,31 +52,65 @@ pub enum Credential {
 impl AuthMgr {
     pub async fn create(cfg: Config) -> Result<Arc<AuthMgr>> {
         Ok(Arc::new(AuthMgr {
-            jwt_auth: JwtAuthenticator::try_create(cfg.query.jwt_key_file).await?,
+            jwt_auth: JwtAuthenticator::try_create(
+                cfg.query.jwt_key_file,
+                cfg.query.jwt_key_files,
+                cfg.query.jwt_user_claim,
+            )
+            .await?,
+            ldap_auth: LdapAuthenticator::try_create(LdapConfig {
+                url: cfg.query.ldap_url,
+                starttls: cfg.query.ldap_starttls,
+                bind_dn: cfg.query.ldap_bind_dn,
+                search_base_dn: cfg.query.ldap_search_base_dn,
+                search_filter: cfg.query.ldap_search_filter,
+                search_bind_dn: cfg.query.ldap_search_bind_dn,
+                search_bind_password: cfg.query.ldap_search_bind_password,
+                group_attribute: cfg.query.ldap_group_attribute,
+                role_mapping: cfg.query.ldap_role_mapping,
+            })?,
         }))
     }
 
+    /// Authenticates the user of the session, the authentications are logged as the `login`
+    /// audit events.
     pub async fn auth(&self, session: Arc<Session>, credential: &Credential) -> Result<()> {
-        let user_info = match credential {
+        let res = self.do_auth(&session, credential).await;
+        let (user, hostname) = match credential {
+            Credential::Jwt { hostname, .. } => (
+                session
+                    .get_current_user()
+                    .map(|user| user.identity().to_string())
+                    .unwrap_or_default(),
+                hostname,
+            ),
+            Credential::Password { name, hostname, .. } => (name.clone(), hostname),
+        };
+        AuditLogger::log_login(
+            &session.get_current_tenant(),
+            &user,
+            hostname.as_deref(),
+            session.get_type().to_string(),
+            res.as_ref().err(),
+        );
+        res
+    }
+
+    async fn do_auth(&self, session: &Arc<Session>, credential: &Credential) -> Result<()> {
+        let (user_info, hostname) = match credential {
             Credential::Jwt {
                 token: t,
                 hostname: h,
             } => {
-                let jwt_auth = self
-                    .jwt_auth
-                    .as_ref()
-                    .ok_or_else(|| ErrorCode::AuthenticateFailure("jwt auth not configured."))?;
-                let parsed_jwt = jwt_auth.parse_jwt(t.as_str()).await?;
-                let (tenant, user_name) = self
-                    .process_jwt_claims(&session, parsed_jwt.claims())
-                    .await?;
-                UserApiProvider::instance()
+                let (tenant, user_name) = self.process_bearer_token(session, t).await?;
+                let user = UserApiProvider::instance()
                     .get_user_with_client_ip(
                         &tenant,
                         &user_name,
                         h.as_ref().unwrap_or(&"%".to_string()),
                     )
-                    .await?
+                    .await?;
+                (user, h)
             }
             Credential::Password {
                 name: n,
@@ -77,10 +118,21 @@ impl AuthMgr {
                 hostname: h,
             } => {
                 let tenant = session.get_current_tenant();
-                let user = UserApiProvider::instance()
+                let (user, exists) = match UserApiProvider::instance()
                     .get_user_with_client_ip(&tenant, n, h.as_ref().unwrap_or(&"%".to_string()))
-                    .await?;
-                match &user.auth_info {
+                    .await
+                {
+                    Ok(user) => (user, true),
+                    // the users of the LDAP server are created on their first login
+                    Err(e)
+                        if e.code() == ErrorCode::unknown_user_code()
+                            && self.ldap_auth.is_some() =>
+                    {
+                        (UserInfo::new(n, "%", AuthInfo::LDAP), false)
+                    }
+                    Err(e) => return Err(e),
+                };
+                let user = match &user.auth_info {
                     AuthInfo::None => Ok(user),
                     AuthInfo::Password {
                         hash_value: h,
@@ -95,16 +147,170 @@ impl AuthMgr {
                             }
                         }
                     },
-                    _ => Err(ErrorCode::AuthenticateFailure("wrong auth type")),
-                }?
+                    // The clients without bearer token support, like the MySQL clients with the
+                    // clear text password plugin, send the JWT as the password.
+                    AuthInfo::JWT => match p {
+                        None => Err(ErrorCode::AuthenticateFailure("jwt required")),
+                        Some(p) => {
+                            let jwt_auth = self.jwt_auth()?;
+                            let token = String::from_utf8_lossy(p);
+                            let parsed_jwt = jwt_auth.parse_jwt(token.trim()).await?;
+                            if jwt_auth.user_name(parsed_jwt.claims())? == *n {
+                                Ok(user)
+                            } else {
+                                Err(ErrorCode::AuthenticateFailure("jwt of another user"))
+                            }
+                        }
+                    },
+                    AuthInfo::LDAP => match p {
+                        None => Err(ErrorCode::AuthenticateFailure("password required")),
+                        Some(p) => self.process_ldap_login(&tenant, user, exists, p).await,
+                    },
+                }?;
+                (user, h)
             }
         };
+        self.check_network_policy(session, &user_info, hostname.as_deref())
+            .await?;
         session.set_current_user(user_info);
         Ok(())
     }
 
+    /// Reject the client addresses not allowed by the network policies of the tenant and the
+    /// user, the rejected attempts are logged as failed `login` audit events.
+    pub async fn check_network_policy(
+        &self,
+        session: &Arc<Session>,
+        user: &UserInfo,
+        client_ip: Option<&str>,
+    ) -> Result<()> {
+        let tenant = session.get_current_tenant();
+        UserApiProvider::instance()
+            .verify_client_ip(&tenant, user, client_ip.unwrap_or_default())
+            .await
+    }
+
+    fn jwt_auth(&self) -> Result<&JwtAuthenticator> {
+        self.jwt_auth
+            .as_ref()
+            .ok_or_else(|| ErrorCode::AuthenticateFailure("jwt auth not configured."))
+    }
+
+    fn ldap_auth(&self) -> Result<&LdapAuthenticator> {
+        self.ldap_auth
+            .as_ref()
+            .ok_or_else(|| ErrorCode::AuthenticateFailure("ldap auth not configured."))
+    }
+
+    /// Verifies the password by the LDAP server. The user is created unless it `exists`, and the
+    /// roles of the mapping are granted or revoked by the groups of the user.
+    async fn process_ldap_login(
+        &self,
+        tenant: &str,
+        mut user: UserInfo,
+        exists: bool,
+        password: &[u8],
+    ) -> Result<UserInfo> {
+        let ldap_auth = self.ldap_auth()?;
+        let roles = ldap_auth.authenticate(&user.name, password).await?;
+        self.sync_mapped_roles(tenant, user, exists, ldap_auth.mapped_roles(), roles)
+            .await
+    }
+
+    /// Verifies the bearer token by the JWKS endpoints of `jwt_key_file` and `jwt_key_files`,
+    /// or else by the OIDC provider.
+    async fn process_bearer_token(
+        &self,
+        session: &Arc<Session>,
+        token: &str,
+    ) -> Result<(String, String)> {
+        let oidc = OidcClient::instance();
+        let jwt_error = match &self.jwt_auth {
+            Some(jwt_auth) => match jwt_auth.parse_jwt(token).await {
+                Ok(parsed_jwt) => {
+                    return self
+                        .process_jwt_claims(jwt_auth, session, parsed_jwt.claims())
+                        .await;
+                }
+                Err(e) if oidc.is_none() => return Err(e),
+                Err(e) => Some(e),
+            },
+            None => None,
+        };
+        let oidc = match oidc {
+            Some(oidc) => oidc,
+            None => return Err(ErrorCode::AuthenticateFailure("jwt auth not configured.")),
+        };
+        let parsed = oidc.verify(token).await.map_err(|e| match jwt_error {
+            Some(jwt_error) => {
+                ErrorCode::AuthenticateFailure(format!("{}; {}", jwt_error.message(), e.message()))
+            }
+            None => e,
+        })?;
+
+        // the users of the OIDC provider are created on their first login
+        let tenant = session.get_current_tenant();
+        let user_name = oidc.user_name(parsed.claims())?;
+        let (user, exists) = match UserApiProvider::instance()
+            .get_user(&tenant, UserIdentity::new(&user_name, "%"))
+            .await
+        {
+            Ok(user) => (user, true),
+            Err(e) if e.code() == ErrorCode::unknown_user_code() => {
+                (UserInfo::new(&user_name, "%", AuthInfo::JWT), false)
+            }
+            Err(e) => return Err(e),
+        };
+        let roles = oidc.roles(parsed.claims());
+        self.sync_mapped_roles(&tenant, user, exists, oidc.mapped_roles(), roles)
+            .await?;
+        Ok((tenant, user_name))
+    }
+
+    /// Creates the user with the `roles` unless it `exists`, or grants the `roles` to it and
+    /// revokes the other roles of the mapping from it.
+    async fn sync_mapped_roles(
+        &self,
+        tenant: &str,
+        mut user: UserInfo,
+        exists: bool,
+        mapped_roles: BTreeSet<String>,
+        roles: Vec<String>,
+    ) -> Result<UserInfo> {
+        let user_api = UserApiProvider::instance();
+        if !exists {
+            for role in roles {
+                user.grants.grant_role(role);
+            }
+            user_api.ensure_builtin_roles(tenant).await?;
+            user_api.add_user(tenant, user.clone(), true).await?;
+            return Ok(user);
+        }
+
+        let granted = user.grants.roles();
+        for role in mapped_roles {
+            match (roles.contains(&role), granted.contains(&role)) {
+                (true, false) => {
+                    user_api
+                        .grant_role_to_user(tenant, user.identity(), role.clone())
+                        .await?;
+                    user.grants.grant_role(role);
+                }
+                (false, true) => {
+                    user_api
+                        .revoke_role_from_user(tenant, user.identity(), role.clone())
+                        .await?;
+                    user.grants.revoke_role(&role);
+                }
+                _ => {}
+            }
+        }
+        Ok(user)
+    }
+
     async fn process_jwt_claims(
         &self,
+        jwt_auth: &JwtAuthenticator,
         session: &Arc<Session>,
         claims: &Claims<CustomClaims>,
     ) -> Result<(String, String)> {
@@ -114,11 +320,8 @@ impl AuthMgr {
         }
         let tenant = session.get_current_tenant();
 
-        // take `sub` field in the claims as user name
-        let user_name = claims
-            .sub
-            .clone()
-            .ok_or_else(|| ErrorCode::AuthenticateFailure("sub not found in claims"))?;
+        // take the `jwt_user_claim` field in the claims as user name, `sub` by default
+        let user_name = jwt_auth.user_name(claims)?;
 
         // set user auth_role if claims contain extra.role
         if let Some(ref auth_role) = claims.extra.role {
@@ -17,12 +17,20 @@ use std::sync::Arc;
 
 use common_base::base::Singleton;
 use common_catalog::catalog::Catalog;
+#[cfg(any(feature = "hive", feature = "iceberg"))]
+use common_catalog::catalog::CatalogCreator;
 pub use common_catalog::catalog::CatalogManager;
 use common_catalog::catalog::CATALOG_DEFAULT;
+#[cfg(any(feature = "hive", feature = "iceberg"))]
+use common_config::CatalogConfig;
 use common_config::Config;
+#[cfg(any(feature = "hive", feature = "iceberg"))]
+use common_exception::ErrorCode;
 use common_exception::Result;
 #[cfg(feature = "hive")]
 use common_storages_hive::CATALOG_HIVE;
+#[cfg(feature = "iceberg")]
+use common_storages_iceberg::CATALOG_ICEBERG;
 use once_cell::sync::OnceCell;
 
 use crate::catalogs::DatabaseCatalog;
@@ -37,7 +45,6 @@ pub trait CatalogManagerHelper {
 
     async fn register_build_in_catalogs(&mut self, conf: &Config) -> Result<()>;
 
-    #[cfg(feature = "hive")]
     fn register_external_catalogs(&mut self, conf: &Config) -> Result<()>;
 }
 
@@ -62,14 +69,11 @@ impl CatalogManagerHelper for CatalogManager {
     async fn try_create(conf: &Config) -> Result<Arc<CatalogManager>> {
         let mut catalog_manager = CatalogManager {
             catalogs: HashMap::new(),
+            catalog_creators: HashMap::new(),
         };
 
         catalog_manager.register_build_in_catalogs(conf).await?;
-
-        #[cfg(feature = "hive")]
-        {
-            catalog_manager.register_external_catalogs(conf)?;
-        }
+        catalog_manager.register_external_catalogs(conf)?;
 
         Ok(Arc::new(catalog_manager))
     }
@@ -82,15 +86,95 @@ impl CatalogManagerHelper for CatalogManager {
         Ok(())
     }
 
-    #[cfg(feature = "hive")]
     fn register_external_catalogs(&mut self, conf: &Config) -> Result<()> {
-        use crate::catalogs::hive::HiveCatalog;
-        let hms_address = &conf.catalog.meta_store_address;
-        if !hms_address.is_empty() {
-            // register hive catalog
-            let hive_catalog: Arc<dyn Catalog> = Arc::new(HiveCatalog::try_create(hms_address)?);
-            self.catalogs.insert(CATALOG_HIVE.to_owned(), hive_catalog);
+        #[cfg(feature = "hive")]
+        self.register_catalog_creator(CATALOG_HIVE, Arc::new(HiveCatalogCreator));
+        #[cfg(feature = "iceberg")]
+        self.register_catalog_creator(CATALOG_ICEBERG, Arc::new(IcebergCatalogCreator));
+
+        #[allow(unused_mut)]
+        let mut catalogs = conf.catalogs.clone();
+
+        // the deprecated `catalog` config registers the catalogs named after their types
+        #[cfg(feature = "hive")]
+        {
+            use common_config::CatalogHiveConfig;
+            let hms_address = &conf.catalog.meta_store_address;
+            if !hms_address.is_empty() {
+                catalogs.entry(CATALOG_HIVE.to_owned()).or_insert_with(|| {
+                    CatalogConfig::Hive(CatalogHiveConfig {
+                        address: hms_address.clone(),
+                        protocol: conf.catalog.protocol.clone(),
+                    })
+                });
+            }
+        }
+
+        #[cfg(feature = "iceberg")]
+        {
+            use common_config::CatalogIcebergConfig;
+            let warehouse = &conf.catalog.iceberg_warehouse;
+            if !warehouse.is_empty() {
+                catalogs
+                    .entry(CATALOG_ICEBERG.to_owned())
+                    .or_insert_with(|| {
+                        CatalogConfig::Iceberg(CatalogIcebergConfig {
+                            warehouse: warehouse.clone(),
+                        })
+                    });
+            }
+        }
+
+        for (name, config) in catalogs.iter() {
+            self.create_catalog(name, config)?;
         }
         Ok(())
     }
 }
+
+#[cfg(feature = "hive")]
+struct HiveCatalogCreator;
+
+#[cfg(feature = "hive")]
+impl CatalogCreator for HiveCatalogCreator {
+    fn try_create(&self, name: &str, config: &CatalogConfig) -> Result<Arc<dyn Catalog>> {
+        use crate::catalogs::hive::HiveCatalog;
+        match config {
+            CatalogConfig::Hive(hive) => Ok(Arc::new(HiveCatalog::try_create(
+                name,
+                hive.address.as_str(),
+            )?)),
+            _ => Err(ErrorCode::InvalidConfig(format!(
+                "catalog {} is not a hive catalog",
+                name
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "iceberg")]
+struct IcebergCatalogCreator;
+
+#[cfg(feature = "iceberg")]
+impl CatalogCreator for IcebergCatalogCreator {
+    fn try_create(&self, name: &str, config: &CatalogConfig) -> Result<Arc<dyn Catalog>> {
+        use common_storage::StorageOperator;
+
+        use crate::catalogs::iceberg::IcebergCatalog;
+        match config {
+            // iceberg catalogs live in the warehouse directories of the storage
+            CatalogConfig::Iceberg(iceberg) => {
+                let operator = StorageOperator::instance();
+                Ok(Arc::new(IcebergCatalog::try_create(
+                    name,
+                    operator,
+                    &iceberg.warehouse,
+                )?))
+            }
+            _ => Err(ErrorCode::InvalidConfig(format!(
+                "catalog {} is not an iceberg catalog",
+                name
+            ))),
+        }
+    }
+}
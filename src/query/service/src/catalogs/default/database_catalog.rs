@@ -40,6 +40,8 @@ use common_meta_app::schema::UndropDatabaseReply;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReply;
 use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateMultiTableMetaReply;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReply;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_app::schema::UpsertTableCopiedFileReply;
@@ -474,6 +476,13 @@ impl Catalog for DatabaseCatalog {
         self.mutable_catalog.update_table_meta(req).await
     }
 
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply> {
+        self.mutable_catalog.update_multi_table_meta(req).await
+    }
+
     fn get_table_function(
         &self,
         func_name: &str,
@@ -48,6 +48,8 @@ use common_meta_app::schema::UndropDatabaseReply;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReply;
 use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateMultiTableMetaReply;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReply;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_app::schema::UpsertTableCopiedFileReply;
@@ -322,6 +324,14 @@ impl Catalog for MutableCatalog {
         Ok(res)
     }
 
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply> {
+        let res = self.ctx.meta.update_multi_table_meta(req).await?;
+        Ok(res)
+    }
+
     async fn get_table_copied_file_info(
         &self,
         req: GetTableCopiedFileReq,
@@ -21,6 +21,8 @@ pub use catalog_manager::CatalogManager;
 pub use catalog_manager::CatalogManagerHelper;
 #[cfg(feature = "hive")]
 pub use common_storages_hive as hive;
+#[cfg(feature = "iceberg")]
+pub use common_storages_iceberg as iceberg;
 pub use default::table_id_ranges::*;
 pub use default::table_memory_meta::InMemoryMetas;
 pub use default::DatabaseCatalog;
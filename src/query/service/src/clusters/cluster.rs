@@ -47,17 +47,24 @@ use futures::Future;
 use futures::StreamExt;
 use metrics::gauge;
 use once_cell::sync::OnceCell;
+use parking_lot::RwLock;
 use rand::thread_rng;
 use rand::Rng;
 use tracing::error;
 use tracing::warn;
 
+use crate::api::DataExchangeManager;
 use crate::api::FlightClient;
+use crate::sessions::SessionManager;
 use crate::Config;
 
+/// Gets the load of the local node, reported in the heartbeats.
+pub type LoadProbe = Arc<dyn Fn() -> u64 + Send + Sync>;
+
 pub struct ClusterDiscovery {
     local_id: String,
     heartbeat: Mutex<ClusterHeartbeat>,
+    load_probe: Arc<RwLock<Option<LoadProbe>>>,
     api_provider: Arc<dyn ClusterApi>,
     cluster_id: String,
     tenant_id: String,
@@ -140,6 +147,8 @@ static CLUSTER_DISCOVERY: OnceCell<Singleton<Arc<ClusterDiscovery>>> = OnceCell:
 
 impl ClusterDiscovery {
     const METRIC_LABEL_FUNCTION: &'static str = "function";
+    // The nodes not connected in time are taken as down, until their entries expire.
+    const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
 
     pub async fn create_meta_client(cfg: &Config) -> Result<MetaStore> {
         let meta_api_provider = MetaStoreProvider::new(cfg.meta.to_meta_grpc_client_conf());
@@ -161,6 +170,7 @@ impl ClusterDiscovery {
 
     pub async fn try_create(cfg: &Config, metastore: MetaStore) -> Result<Arc<ClusterDiscovery>> {
         let (lift_time, provider) = Self::create_provider(cfg, metastore)?;
+        let load_probe = Arc::new(RwLock::new(None));
 
         Ok(Arc::new(ClusterDiscovery {
             local_id: GlobalUniqName::unique(),
@@ -168,9 +178,11 @@ impl ClusterDiscovery {
             heartbeat: Mutex::new(ClusterHeartbeat::create(
                 lift_time,
                 provider,
+                load_probe.clone(),
                 cfg.query.cluster_id.clone(),
                 cfg.query.tenant_id.clone(),
             )),
+            load_probe,
             cluster_id: cfg.query.cluster_id.clone(),
             tenant_id: cfg.query.tenant_id.clone(),
             flight_address: cfg.query.flight_api_address.clone(),
@@ -184,6 +196,24 @@ impl ClusterDiscovery {
         }
     }
 
+    /// Report the load of the node in the heartbeats: the queries coordinated by the node plus the
+    /// queries with fragments on it. The sessions and the exchanges must be initialized.
+    pub fn init_load_probe(&self) {
+        let status = SessionManager::instance().status.clone();
+        let exchange_manager = DataExchangeManager::instance();
+        let probe: LoadProbe = Arc::new(move || {
+            status.read().running_queries_count + exchange_manager.running_queries_count() as u64
+        });
+        *self.load_probe.write() = Some(probe);
+    }
+
+    pub fn local_load(&self) -> u64 {
+        match self.load_probe.read().as_ref() {
+            None => 0,
+            Some(probe) => probe(),
+        }
+    }
+
     fn create_provider(
         cfg: &Config,
         metastore: MetaStore,
@@ -191,7 +221,13 @@ impl ClusterDiscovery {
         // TODO: generate if tenant or cluster id is empty
         let tenant_id = &cfg.query.tenant_id;
         let cluster_id = &cfg.query.cluster_id;
-        let lift_time = Duration::from_secs(60);
+        if cfg.query.cluster_heartbeat_ttl == 0 {
+            return Err(ErrorCode::InvalidConfig(
+                "cluster_heartbeat_ttl must be greater than 0",
+            ));
+        }
+
+        let lift_time = Duration::from_secs(cfg.query.cluster_heartbeat_ttl);
         let cluster_manager = ClusterMgr::create(metastore, tenant_id, cluster_id, lift_time)?;
 
         Ok((lift_time, Arc::new(cluster_manager)))
@@ -229,20 +265,39 @@ impl ClusterDiscovery {
                 Err(cause.add_message_back("(while cluster api get_nodes)."))
             }
             Ok(cluster_nodes) => {
-                let mut res = Vec::with_capacity(cluster_nodes.len());
-                for node in &cluster_nodes {
-                    if node.id != self.local_id {
-                        if let Err(cause) = create_client(config, &node.flight_address).await {
+                // Probe the other nodes concurrently, so that a node down costs one timeout.
+                let probes = cluster_nodes.iter().map(|node| async move {
+                    if node.id == self.local_id {
+                        return true;
+                    }
+
+                    let connect = create_client(config, &node.flight_address);
+                    match tokio::time::timeout(Self::PROBE_TIMEOUT, connect).await {
+                        Ok(Ok(_)) => true,
+                        Ok(Err(cause)) => {
                             warn!(
                                 "Cannot connect node [{:?}], remove it in query. cause: {:?}",
                                 node.flight_address, cause
                             );
-
-                            continue;
+                            false
+                        }
+                        Err(_) => {
+                            warn!(
+                                "Cannot connect node [{:?}] in {:?}, remove it in query.",
+                                node.flight_address,
+                                Self::PROBE_TIMEOUT
+                            );
+                            false
                         }
                     }
+                });
 
-                    res.push(Arc::new(node.clone()));
+                let connected = futures::future::join_all(probes).await;
+                let mut res = Vec::with_capacity(cluster_nodes.len());
+                for (node, connected) in cluster_nodes.iter().zip(connected) {
+                    if connected {
+                        res.push(Arc::new(node.clone()));
+                    }
                 }
 
                 gauge!(
@@ -352,6 +407,16 @@ impl ClusterDiscovery {
         };
     }
 
+    /// Leave the cluster while the node keeps running, the other nodes stop scheduling fragments
+    /// on it once their view of the cluster is refreshed.
+    pub async fn leave_cluster(self: &Arc<Self>) -> Result<()> {
+        let mut heartbeat = self.heartbeat.lock().await;
+        heartbeat.shutdown().await?;
+        self.api_provider
+            .drop_node(self.local_id.clone(), None)
+            .await
+    }
+
     pub async fn register_to_metastore(self: &Arc<Self>, cfg: &Config) -> Result<()> {
         let cpus = cfg.query.num_cpus;
         let mut address = cfg.query.flight_api_address.clone();
@@ -396,6 +461,7 @@ struct ClusterHeartbeat {
     shutdown: Arc<AtomicBool>,
     shutdown_notify: Arc<Notify>,
     cluster_api: Arc<dyn ClusterApi>,
+    load_probe: Arc<RwLock<Option<LoadProbe>>>,
     shutdown_handler: Option<JoinHandle<()>>,
     cluster_id: String,
     tenant_id: String,
@@ -407,12 +473,14 @@ impl ClusterHeartbeat {
     pub fn create(
         timeout: Duration,
         cluster_api: Arc<dyn ClusterApi>,
+        load_probe: Arc<RwLock<Option<LoadProbe>>>,
         cluster_id: String,
         tenant_id: String,
     ) -> ClusterHeartbeat {
         ClusterHeartbeat {
             timeout,
             cluster_api,
+            load_probe,
             shutdown: Arc::new(AtomicBool::new(false)),
             shutdown_notify: Arc::new(Notify::new()),
             shutdown_handler: None,
@@ -421,10 +489,11 @@ impl ClusterHeartbeat {
         }
     }
 
-    fn heartbeat_loop(&self, node: NodeInfo) -> impl Future<Output = ()> + 'static {
+    fn heartbeat_loop(&self, mut node: NodeInfo) -> impl Future<Output = ()> + 'static {
         let shutdown = self.shutdown.clone();
         let shutdown_notify = self.shutdown_notify.clone();
         let cluster_api = self.cluster_api.clone();
+        let load_probe = self.load_probe.clone();
         let sleep_range = self.heartbeat_interval(self.timeout);
         let cluster_id = self.cluster_id.clone();
         let tenant_id = self.tenant_id.clone();
@@ -446,6 +515,10 @@ impl ClusterHeartbeat {
                     }
                     Either::Right((_, new_shutdown_notified)) => {
                         shutdown_notified = new_shutdown_notified;
+                        if let Some(probe) = load_probe.read().as_ref() {
+                            node.running_queries_count = probe();
+                        }
+
                         let heartbeat = cluster_api.heartbeat(&node, None);
                         if let Err(failure) = heartbeat.await {
                             label_counter_with_val_and_labels(
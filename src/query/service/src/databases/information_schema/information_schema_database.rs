@@ -22,6 +22,7 @@ use common_meta_app::schema::DatabaseNameIdent;
 use crate::catalogs::InMemoryMetas;
 use crate::databases::Database;
 use crate::storages::information_schema::ColumnsTable;
+use crate::storages::information_schema::KeyColumnUsageTable;
 use crate::storages::information_schema::KeywordsTable;
 use crate::storages::information_schema::SchemataTable;
 use crate::storages::information_schema::TablesTable;
@@ -41,6 +42,7 @@ impl InformationSchemaDatabase {
             KeywordsTable::create(sys_db_meta.next_table_id()),
             ViewsTable::create(sys_db_meta.next_table_id()),
             SchemataTable::create(sys_db_meta.next_table_id()),
+            KeyColumnUsageTable::create(sys_db_meta.next_table_id()),
         ];
 
         let db = "INFORMATION_SCHEMA";
@@ -12,16 +12,24 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use common_meta_app::schema::DatabaseIdent;
 use common_meta_app::schema::DatabaseInfo;
 use common_meta_app::schema::DatabaseMeta;
 use common_meta_app::schema::DatabaseNameIdent;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
 
 use crate::catalogs::InMemoryMetas;
 use crate::databases::Database;
+use crate::interpreters::QUERY_LOG_DATABASE;
+use crate::interpreters::QUERY_LOG_TABLE;
 use crate::storages::system;
+use crate::storages::view::view_table::QUERY;
+use crate::storages::view::ViewTable;
 use crate::storages::Table;
 use crate::Config;
 
@@ -41,14 +49,21 @@ impl SystemDatabase {
             system::TablesTableWithoutHistory::create(sys_db_meta.next_table_id()),
             system::TablesTableWithHistory::create(sys_db_meta.next_table_id()),
             system::ClustersTable::create(sys_db_meta.next_table_id()),
+            system::CatalogsTable::create(sys_db_meta.next_table_id()),
             system::DatabasesTable::create(sys_db_meta.next_table_id()),
             Arc::new(system::TracingTable::create(sys_db_meta.next_table_id())),
             system::ProcessesTable::create(sys_db_meta.next_table_id()),
+            system::QueryQueueTable::create(sys_db_meta.next_table_id()),
             system::ConfigsTable::create(sys_db_meta.next_table_id()),
             system::MetricsTable::create(sys_db_meta.next_table_id()),
             system::ColumnsTable::create(sys_db_meta.next_table_id()),
             system::UsersTable::create(sys_db_meta.next_table_id()),
-            Arc::new(system::QueryLogTable::create(
+            Self::query_log_table(sys_db_meta.next_table_id(), config),
+            Arc::new(system::SlowQueryLogTable::create(
+                sys_db_meta.next_table_id(),
+                config.query.max_query_log_size,
+            )),
+            Arc::new(system::AuditLogTable::create(
                 sys_db_meta.next_table_id(),
                 config.query.max_query_log_size,
             )),
@@ -58,7 +73,16 @@ impl SystemDatabase {
             )),
             system::EnginesTable::create(sys_db_meta.next_table_id()),
             system::RolesTable::create(sys_db_meta.next_table_id()),
+            system::RowAccessPoliciesTable::create(sys_db_meta.next_table_id()),
+            system::MaskingPoliciesTable::create(sys_db_meta.next_table_id()),
+            system::NetworkPoliciesTable::create(sys_db_meta.next_table_id()),
+            system::WorkloadGroupsTable::create(sys_db_meta.next_table_id()),
+            system::TasksTable::create(sys_db_meta.next_table_id()),
+            system::TaskHistoryTable::create(sys_db_meta.next_table_id()),
             system::StagesTable::create(sys_db_meta.next_table_id()),
+            system::TableSnapshotsTable::create(sys_db_meta.next_table_id()),
+            system::IndexesTable::create(sys_db_meta.next_table_id()),
+            system::ViewsTable::create(sys_db_meta.next_table_id()),
         ];
 
         for tbl in table_list.into_iter() {
@@ -82,6 +106,37 @@ impl SystemDatabase {
 
         Self { db_info }
     }
+
+    /// `system.query_log` reads the persistent query logs of the tenant if they are enabled,
+    /// otherwise the query logs kept in the memory of the node.
+    fn query_log_table(table_id: u64, config: &Config) -> Arc<dyn Table> {
+        if !config.query.enable_persistent_query_log {
+            return Arc::new(system::QueryLogTable::create(
+                table_id,
+                config.query.max_query_log_size,
+            ));
+        }
+
+        // the query logs are still queued, for the interpreters appending them
+        system::QueryLogQueue::init(config.query.max_query_log_size);
+
+        let mut options = BTreeMap::new();
+        options.insert(
+            QUERY.to_string(),
+            format!("SELECT * FROM {}.{}", QUERY_LOG_DATABASE, QUERY_LOG_TABLE),
+        );
+        let table_info = TableInfo {
+            desc: "'system'.'query_log'".to_string(),
+            name: "query_log".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                options,
+                engine: "VIEW".to_string(),
+                ..Default::default()
+            },
+        };
+        ViewTable::create(table_info)
+    }
 }
 
 #[async_trait::async_trait]
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "jit")]
+use std::sync::Arc;
+
 use common_datablocks::DataBlock;
 use common_datavalues::ColumnWithField;
 use common_datavalues::DataField;
@@ -19,6 +22,8 @@ use common_datavalues::DataTypeImpl;
 use common_datavalues::DataValue;
 use common_datavalues::NullType;
 use common_exception::Result;
+#[cfg(feature = "jit")]
+use common_functions::jit::CompiledExpr;
 use common_functions::scalars::Function;
 use common_functions::scalars::FunctionContext;
 
@@ -44,6 +49,14 @@ pub enum EvalNode {
     IndexedVariable {
         index: usize,
     },
+    /// The expression compiled into native code, `fallback` evaluates the blocks it can't.
+    #[cfg(feature = "jit")]
+    Compiled {
+        expr: Arc<CompiledExpr>,
+        inputs: Vec<Self>,
+        return_type: DataTypeImpl,
+        fallback: Box<Self>,
+    },
 }
 
 impl EvalNode {
@@ -89,6 +102,22 @@ impl EvalNode {
                     logical_type: data_type,
                 })
             }
+            #[cfg(feature = "jit")]
+            EvalNode::Compiled {
+                expr,
+                inputs,
+                return_type,
+                fallback,
+            } => {
+                let columns = inputs
+                    .iter()
+                    .map(|input| Ok(input.eval(func_ctx, data_block)?.vector))
+                    .collect::<Result<Vec<_>>>()?;
+                match expr.eval(&columns, data_block.num_rows())? {
+                    Some(column) => Ok(TypedVector::new(column, return_type.clone())),
+                    None => fallback.eval(func_ctx, data_block),
+                }
+            }
         }
     }
 
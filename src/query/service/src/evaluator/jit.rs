@@ -0,0 +1,120 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataTypeImpl;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::jit::JitCompiler;
+use common_functions::jit::JitExpr;
+use common_functions::jit::JitOperator;
+use common_functions::scalars::FunctionFactory;
+
+use crate::evaluator::eval_node::EvalNode;
+use crate::sql::executor::PhysicalScalar;
+
+/// Compile the function of a filter or projection into native code, `fallback` is the interpreted
+/// `EvalNode` of the same scalar, used as it is if the scalar can't be compiled.
+pub fn try_compile(physical_scalar: &PhysicalScalar, fallback: EvalNode) -> EvalNode {
+    if !matches!(physical_scalar, PhysicalScalar::Function { .. }) {
+        return fallback;
+    }
+
+    let mut inputs = vec![];
+    let compiled = to_jit_expr(physical_scalar, &mut inputs)
+        .and_then(|expr| Ok((JitCompiler::compile(&expr)?, expr.data_type().clone())));
+    match compiled {
+        Ok((expr, return_type)) => EvalNode::Compiled {
+            expr: Arc::new(expr),
+            inputs: inputs.into_iter().map(|(input, _)| input).collect(),
+            return_type,
+            fallback: Box::new(fallback),
+        },
+        Err(e) => {
+            tracing::debug!("scalar is not compiled, evaluate it by interpreter: {}", e);
+            fallback
+        }
+    }
+}
+
+/// `inputs` are the variables of the scalar, each of them is an input of the `JitExpr`.
+fn to_jit_expr(
+    physical_scalar: &PhysicalScalar,
+    inputs: &mut Vec<(EvalNode, String)>,
+) -> Result<JitExpr> {
+    match physical_scalar {
+        PhysicalScalar::Variable {
+            column_id,
+            data_type,
+        } => {
+            let index = input_index(
+                inputs,
+                EvalNode::Variable {
+                    name: column_id.clone(),
+                },
+                column_id.clone(),
+            );
+            Ok(JitExpr::Input {
+                index,
+                data_type: data_type.clone(),
+            })
+        }
+        PhysicalScalar::IndexedVariable { index, data_type } => {
+            let index = input_index(
+                inputs,
+                EvalNode::IndexedVariable { index: *index },
+                format!("#{}", index),
+            );
+            Ok(JitExpr::Input {
+                index,
+                data_type: data_type.clone(),
+            })
+        }
+        PhysicalScalar::Constant { value, data_type } => Ok(JitExpr::Constant {
+            value: value.clone(),
+            data_type: data_type.clone(),
+        }),
+        PhysicalScalar::Function { name, args, .. } => {
+            let op = JitOperator::from_name(name).ok_or_else(|| {
+                ErrorCode::UnImplement(format!("JIT doesn't support function {}", name))
+            })?;
+            // the return type of the interpreted function, which the compiled one must produce
+            let data_types: Vec<&DataTypeImpl> = args.iter().map(|(_, v)| v).collect();
+            let return_type = FunctionFactory::instance()
+                .get(name, &data_types)?
+                .return_type();
+            let args = args
+                .iter()
+                .map(|(arg, _)| to_jit_expr(arg, inputs))
+                .collect::<Result<_>>()?;
+            Ok(JitExpr::Function {
+                op,
+                args,
+                return_type,
+            })
+        }
+        _ => Err(ErrorCode::UnImplement("JIT doesn't support casts and UDFs")),
+    }
+}
+
+fn input_index(inputs: &mut Vec<(EvalNode, String)>, input: EvalNode, key: String) -> usize {
+    match inputs.iter().position(|(_, k)| *k == key) {
+        Some(index) => index,
+        None => {
+            inputs.push((input, key));
+            inputs.len() - 1
+        }
+    }
+}
@@ -13,12 +13,16 @@
 // limitations under the License.
 
 mod eval_node;
+#[cfg(feature = "jit")]
+mod jit;
 mod physical_scalar;
 mod scalar;
+mod udf_server_function;
 
 use common_datavalues::ColumnRef;
 use common_datavalues::DataTypeImpl;
 pub use eval_node::EvalNode;
+pub use udf_server_function::UDFServerFunction;
 
 pub struct Evaluator;
 
@@ -20,6 +20,7 @@ use common_functions::scalars::FunctionFactory;
 
 use crate::evaluator::eval_node::EvalNode;
 use crate::evaluator::Evaluator;
+use crate::evaluator::UDFServerFunction;
 use crate::sql::executor::PhysicalScalar;
 
 impl Evaluator {
@@ -30,6 +31,15 @@ impl Evaluator {
             .collect::<Result<_>>()
     }
 
+    /// Same as `eval_physical_scalar`, but the scalar is compiled into native code if the `jit`
+    /// feature is enabled and the scalar is supported, for the filters and projections.
+    pub fn compile_physical_scalar(physical_scalar: &PhysicalScalar) -> Result<EvalNode> {
+        let eval_node = Self::eval_physical_scalar(physical_scalar)?;
+        #[cfg(feature = "jit")]
+        let eval_node = crate::evaluator::jit::try_compile(physical_scalar, eval_node);
+        Ok(eval_node)
+    }
+
     pub fn eval_physical_scalar(physical_scalar: &PhysicalScalar) -> Result<EvalNode> {
         match physical_scalar {
             PhysicalScalar::Variable { column_id, .. } => Ok(EvalNode::Variable {
@@ -63,6 +73,28 @@ impl Evaluator {
             PhysicalScalar::IndexedVariable { index, .. } => {
                 Ok(EvalNode::IndexedVariable { index: *index })
             }
+            PhysicalScalar::UDFServerCall {
+                func_name,
+                server_addr,
+                handler,
+                args,
+                return_type,
+                timeout,
+            } => {
+                let func = Box::new(UDFServerFunction {
+                    func_name: func_name.clone(),
+                    server_addr: server_addr.clone(),
+                    handler: handler.clone(),
+                    arg_types: args.iter().map(|(_, v)| v.clone()).collect(),
+                    return_type: return_type.clone(),
+                    timeout: *timeout,
+                });
+                let args = args
+                    .iter()
+                    .map(|(v, _)| Self::eval_physical_scalar(v))
+                    .collect::<Result<_>>()?;
+                Ok(EvalNode::Function { func, args })
+            }
         }
     }
 }
@@ -21,6 +21,7 @@ use common_functions::scalars::FunctionFactory;
 
 use crate::evaluator::eval_node::EvalNode;
 use crate::evaluator::Evaluator;
+use crate::evaluator::UDFServerFunction;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarExpr;
 
@@ -97,6 +98,25 @@ impl Evaluator {
             Scalar::AggregateFunction(_) => Err(ErrorCode::LogicalError(
                 "Cannot evaluate aggregate function",
             )),
+            Scalar::WindowFunction(_) => {
+                Err(ErrorCode::LogicalError("Cannot evaluate window function"))
+            }
+            Scalar::UDFServerCall(udf) => {
+                let args: Vec<EvalNode> = udf
+                    .arguments
+                    .iter()
+                    .map(Self::eval_scalar)
+                    .collect::<Result<_>>()?;
+                let func = Box::new(UDFServerFunction {
+                    func_name: udf.func_name.clone(),
+                    server_addr: udf.server_addr.clone(),
+                    handler: udf.handler.clone(),
+                    arg_types: udf.arg_types.clone(),
+                    return_type: *udf.return_type.clone(),
+                    timeout: udf.timeout,
+                });
+                Ok(EvalNode::Function { func, args })
+            }
         }
     }
 }
@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_base::base::GlobalIORuntime;
+use common_datablocks::DataBlock;
+use common_datavalues::Column;
+use common_datavalues::ColumnRef;
+use common_datavalues::ColumnsWithField;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::Function;
+use common_functions::scalars::FunctionContext;
+
+use crate::api::UDFFlightClient;
+
+/// Function which sends its arguments to an external UDF server and returns the results.
+#[derive(Clone)]
+pub struct UDFServerFunction {
+    pub func_name: String,
+    pub server_addr: String,
+    pub handler: String,
+    pub arg_types: Vec<DataTypeImpl>,
+    pub return_type: DataTypeImpl,
+    pub timeout: u64,
+}
+
+impl Function for UDFServerFunction {
+    fn name(&self) -> &str {
+        &self.func_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        self.return_type.clone()
+    }
+
+    fn eval(
+        &self,
+        _func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        if input_rows == 0 {
+            return self.return_type.create_column(&[]);
+        }
+
+        let fields = self
+            .arg_types
+            .iter()
+            .enumerate()
+            .map(|(i, data_type)| DataField::new(&format!("arg{}", i), data_type.clone()))
+            .collect::<Vec<_>>();
+        let arguments = columns
+            .iter()
+            .map(|column| column.column().convert_full_column())
+            .collect::<Vec<_>>();
+        let block = DataBlock::create(DataSchemaRefExt::create(fields), arguments);
+
+        let func = self.clone();
+        let task = GlobalIORuntime::instance().spawn(async move {
+            let mut client = UDFFlightClient::connect(&func.server_addr, func.timeout).await?;
+            client
+                .do_exchange(&func.handler, block, &func.return_type)
+                .await
+        });
+
+        match futures::executor::block_on(task) {
+            Ok(result) => result,
+            Err(cause) => Err(ErrorCode::PanicError(format!(
+                "Maybe panic while calling UDF server. {}",
+                cause
+            ))),
+        }
+    }
+
+    fn passthrough_constant(&self) -> bool {
+        false
+    }
+}
+
+impl fmt::Display for UDFServerFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.func_name)
+    }
+}
@@ -24,13 +24,20 @@ use common_exception::Result;
 use common_fuse_meta::caches::CacheManager;
 use common_storage::StorageOperator;
 use common_tracing::QueryLogger;
+use common_users::OidcClient;
+use common_users::OidcConfig;
 use common_users::RoleCacheManager;
 use common_users::UserApiProvider;
 use opendal::Operator;
 
 use crate::api::DataExchangeManager;
+use crate::audit::AuditLogger;
 use crate::catalogs::CatalogManagerHelper;
 use crate::clusters::ClusterDiscovery;
+use crate::interpreters::AsyncInsertManager;
+use crate::interpreters::AutoCompactionManager;
+use crate::interpreters::QueryLogPersister;
+use crate::interpreters::TaskScheduler;
 use crate::servers::http::v1::HttpQueryManager;
 use crate::sessions::SessionManager;
 
@@ -46,6 +53,9 @@ pub struct GlobalServices {
     session_manager: UnsafeCell<Option<Arc<SessionManager>>>,
     users_manager: UnsafeCell<Option<Arc<UserApiProvider>>>,
     users_role_manager: UnsafeCell<Option<Arc<RoleCacheManager>>>,
+    async_insert_manager: UnsafeCell<Option<Arc<AsyncInsertManager>>>,
+    auto_compaction_manager: UnsafeCell<Option<Arc<AutoCompactionManager>>>,
+    task_scheduler: UnsafeCell<Option<Arc<TaskScheduler>>>,
 }
 
 unsafe impl Send for GlobalServices {}
@@ -66,6 +76,9 @@ impl GlobalServices {
             users_manager: UnsafeCell::new(None),
             global_runtime: UnsafeCell::new(None),
             users_role_manager: UnsafeCell::new(None),
+            async_insert_manager: UnsafeCell::new(None),
+            auto_compaction_manager: UnsafeCell::new(None),
+            task_scheduler: UnsafeCell::new(None),
         });
 
         // The order of initialization is very important
@@ -83,13 +96,30 @@ impl GlobalServices {
         HttpQueryManager::init(&config, global_services.clone()).await?;
         DataExchangeManager::init(config.clone(), global_services.clone())?;
         SessionManager::init(config.clone(), global_services.clone())?;
+        ClusterDiscovery::instance().init_load_probe();
         UserApiProvider::init(
             config.meta.to_meta_grpc_client_conf(),
-            config.query.idm,
+            config.query.idm.clone(),
             global_services.clone(),
         )
         .await?;
-        RoleCacheManager::init(global_services.clone())
+        RoleCacheManager::init(global_services.clone())?;
+        AsyncInsertManager::init(&config, global_services.clone())?;
+        AutoCompactionManager::init(&config, global_services.clone())?;
+        TaskScheduler::init(&config, global_services.clone())?;
+        QueryLogPersister::init(&config)?;
+        OidcClient::init(OidcConfig {
+            issuer: config.query.oidc_issuer.clone(),
+            client_id: config.query.oidc_client_id.clone(),
+            client_secret: config.query.oidc_client_secret.clone(),
+            redirect_url: config.query.oidc_redirect_url.clone(),
+            scopes: config.query.oidc_scopes.clone(),
+            audience: config.query.oidc_audience.clone(),
+            user_claim: config.query.oidc_user_claim.clone(),
+            role_claim: config.query.oidc_role_claim.clone(),
+            role_mapping: config.query.oidc_role_mapping.clone(),
+        })?;
+        AuditLogger::init(&config)
     }
 }
 
@@ -291,3 +321,59 @@ impl SingletonImpl<Arc<RoleCacheManager>> for GlobalServices {
         }
     }
 }
+
+impl SingletonImpl<Arc<AsyncInsertManager>> for GlobalServices {
+    fn get(&self) -> Arc<AsyncInsertManager> {
+        unsafe {
+            match &*self.async_insert_manager.get() {
+                None => panic!("AsyncInsertManager is not init"),
+                Some(async_insert_manager) => async_insert_manager.clone(),
+            }
+        }
+    }
+
+    fn init(&self, value: Arc<AsyncInsertManager>) -> Result<()> {
+        unsafe {
+            *(self.async_insert_manager.get() as *mut Option<Arc<AsyncInsertManager>>) =
+                Some(value);
+            Ok(())
+        }
+    }
+}
+
+impl SingletonImpl<Arc<AutoCompactionManager>> for GlobalServices {
+    fn get(&self) -> Arc<AutoCompactionManager> {
+        unsafe {
+            match &*self.auto_compaction_manager.get() {
+                None => panic!("AutoCompactionManager is not init"),
+                Some(auto_compaction_manager) => auto_compaction_manager.clone(),
+            }
+        }
+    }
+
+    fn init(&self, value: Arc<AutoCompactionManager>) -> Result<()> {
+        unsafe {
+            *(self.auto_compaction_manager.get() as *mut Option<Arc<AutoCompactionManager>>) =
+                Some(value);
+            Ok(())
+        }
+    }
+}
+
+impl SingletonImpl<Arc<TaskScheduler>> for GlobalServices {
+    fn get(&self) -> Arc<TaskScheduler> {
+        unsafe {
+            match &*self.task_scheduler.get() {
+                None => panic!("TaskScheduler is not init"),
+                Some(task_scheduler) => task_scheduler.clone(),
+            }
+        }
+    }
+
+    fn init(&self, value: Arc<TaskScheduler>) -> Result<()> {
+        unsafe {
+            *(self.task_scheduler.get() as *mut Option<Arc<TaskScheduler>>) = Some(value);
+            Ok(())
+        }
+    }
+}
@@ -51,7 +51,12 @@ impl AccessChecker for ManagementModeAccess {
                             | RewriteKind::ShowUsers
                             | RewriteKind::ShowStages
                             | RewriteKind::DescribeStage
-                            | RewriteKind::ShowRoles),
+                            | RewriteKind::ShowRoles
+                            | RewriteKind::ShowRowAccessPolicies
+                            | RewriteKind::ShowMaskingPolicies
+                            | RewriteKind::ShowNetworkPolicies
+                            | RewriteKind::ShowWorkloadGroups
+                            | RewriteKind::ShowTasks),
                         _ => false
                     }
                 },
@@ -67,6 +72,7 @@ impl AccessChecker for ManagementModeAccess {
                 // Table.
                 | Plan::DescribeTable(_)
                 | Plan::CreateTable(_)
+                | Plan::AttachTable(_)
                 | Plan::DropTable(_)
 
                 // User.
@@ -78,6 +84,24 @@ impl AccessChecker for ManagementModeAccess {
                 | Plan::RevokePriv(_)
                 | Plan::GrantRole(_)
                 | Plan::RevokeRole(_)
+                | Plan::SetRole(_)
+                // Row access policy.
+                | Plan::CreateRowAccessPolicy(_)
+                | Plan::DropRowAccessPolicy(_)
+                // Masking policy.
+                | Plan::CreateMaskingPolicy(_)
+                | Plan::DropMaskingPolicy(_)
+                | Plan::SetColumnMaskingPolicy(_)
+                | Plan::UnsetColumnMaskingPolicy(_)
+                // Network policy.
+                | Plan::CreateNetworkPolicy(_)
+                | Plan::DropNetworkPolicy(_)
+                // Workload group.
+                | Plan::CreateWorkloadGroup(_)
+                | Plan::DropWorkloadGroup(_)
+                // Task.
+                | Plan::CreateTask(_)
+                | Plan::DropTask(_)
                 // Stage.
                 | Plan::CreateStage(_)
                 | Plan::DropStage(_)
@@ -14,13 +14,22 @@
 
 use std::sync::Arc;
 
+use common_ast::ast::ExplainKind;
 use common_exception::Result;
+use common_legacy_planners::SourceInfo;
 use common_meta_types::GrantObject;
 use common_meta_types::UserPrivilegeType;
+use common_meta_types::UserStageInfo;
+use common_planner::MetadataRef;
+use common_users::UserApiProvider;
 
 use crate::interpreters::access::AccessChecker;
 use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::plans::CopyPlanV2;
+use crate::sql::plans::InsertInputSource;
 use crate::sql::plans::Plan;
+use crate::sql::plans::PresignAction;
 
 pub struct PrivilegeAccess {
     ctx: Arc<QueryContext>,
@@ -30,6 +39,65 @@ impl PrivilegeAccess {
     pub fn create(ctx: Arc<QueryContext>) -> Box<dyn AccessChecker> {
         Box::new(PrivilegeAccess { ctx })
     }
+
+    // Every table read by the query needs the Select privilege, except the system tables.
+    async fn validate_query_privilege(&self, metadata: &MetadataRef) -> Result<()> {
+        let tables = metadata
+            .read()
+            .tables()
+            .iter()
+            .filter(|table| !matches!(table.database(), "system" | "information_schema"))
+            .map(|table| {
+                GrantObject::Table(
+                    table.catalog().to_string(),
+                    table.database().to_string(),
+                    table.name().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let udfs = metadata
+            .read()
+            .udfs()
+            .iter()
+            .map(|udf| GrantObject::UDF(udf.clone()))
+            .collect::<Vec<_>>();
+
+        let session = self.ctx.get_current_session();
+        for table in tables {
+            session
+                .validate_privilege(&table, UserPrivilegeType::Select)
+                .await?;
+        }
+        for udf in udfs {
+            session
+                .validate_privilege(&udf, UserPrivilegeType::Usage)
+                .await?;
+        }
+        Ok(())
+    }
+
+    // Only the named stages are granted, the external locations like `s3://bucket/path/` are
+    // accessed with the credentials given in the statement.
+    async fn validate_stage_privilege(
+        &self,
+        stage: &UserStageInfo,
+        privilege: UserPrivilegeType,
+    ) -> Result<()> {
+        let tenant = self.ctx.get_tenant();
+        let named_stage = UserApiProvider::instance()
+            .get_stage(&tenant, &stage.stage_name)
+            .await
+            .is_ok();
+        if !named_stage {
+            return Ok(());
+        }
+
+        self.ctx
+            .get_current_session()
+            .validate_privilege(&GrantObject::Stage(stage.stage_name.clone()), privilege)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -38,9 +106,49 @@ impl AccessChecker for PrivilegeAccess {
         let session = self.ctx.get_current_session();
 
         match plan {
-            Plan::Query { .. } => {}
+            Plan::Query { metadata, .. } => {
+                self.validate_query_privilege(metadata).await?;
+            }
+            // The query is executed by EXPLAIN ANALYZE.
+            Plan::Explain {
+                kind: ExplainKind::Analyze,
+                plan,
+            } => self.check(plan).await?,
             Plan::Explain { .. } => {}
-            Plan::Copy(_) => {}
+            Plan::Copy(plan) => match plan.as_ref() {
+                CopyPlanV2::IntoTable {
+                    catalog_name,
+                    database_name,
+                    table_name,
+                    from,
+                    ..
+                } => {
+                    session
+                        .validate_privilege(
+                            &GrantObject::Table(
+                                catalog_name.clone(),
+                                database_name.clone(),
+                                table_name.clone(),
+                            ),
+                            UserPrivilegeType::Insert,
+                        )
+                        .await?;
+                    if let SourceInfo::StageSource(table_info) = &from.source_info {
+                        self.validate_stage_privilege(
+                            &table_info.stage_info,
+                            UserPrivilegeType::Read,
+                        )
+                        .await?;
+                    }
+                }
+                CopyPlanV2::IntoStage { stage, from, .. } => {
+                    self.validate_stage_privilege(stage, UserPrivilegeType::Write)
+                        .await?;
+                    if let Plan::Query { metadata, .. } = from.as_ref() {
+                        self.validate_query_privilege(metadata).await?;
+                    }
+                }
+            },
             Plan::Call(_) => {}
 
             // Database.
@@ -78,6 +186,14 @@ impl AccessChecker for PrivilegeAccess {
                     )
                     .await?;
             }
+            Plan::AttachTable(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Database(plan.catalog.clone(), plan.database.clone()),
+                        UserPrivilegeType::Create,
+                    )
+                    .await?;
+            }
             Plan::DropTable(plan) => {
                 session
                     .validate_privilege(
@@ -107,6 +223,18 @@ impl AccessChecker for PrivilegeAccess {
                     )
                     .await?;
             }
+            Plan::AlterTableColumn(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
             Plan::DropTableClusterKey(plan) => {
                 session
                     .validate_privilege(
@@ -143,12 +271,52 @@ impl AccessChecker for PrivilegeAccess {
                     )
                     .await?;
             }
+            Plan::FlashbackTable(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
             Plan::OptimizeTable(_) => {}
+            Plan::AnalyzeTable(_) => {}
             Plan::ExistsTable(_) => {}
 
             // Others.
-            Plan::Insert(_) => {}
-            Plan::Delete(_) => {}
+            Plan::Insert(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Insert,
+                    )
+                    .await?;
+                if let InsertInputSource::SelectPlan(select_plan) = &plan.source {
+                    if let Plan::Query { metadata, .. } = select_plan.as_ref() {
+                        self.validate_query_privilege(metadata).await?;
+                    }
+                }
+            }
+            Plan::Delete(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog_name.clone(),
+                            plan.database_name.clone(),
+                            plan.table_name.clone(),
+                        ),
+                        UserPrivilegeType::Delete,
+                    )
+                    .await?;
+            }
             Plan::CreateView(plan) => {
                 session
                     .validate_privilege(
@@ -173,24 +341,245 @@ impl AccessChecker for PrivilegeAccess {
                     )
                     .await?;
             }
-            Plan::AlterUser(_) => {}
-            Plan::CreateUser(_) => {}
-            Plan::DropUser(_) => {}
-            Plan::CreateUDF(_) => {}
-            Plan::AlterUDF(_) => {}
-            Plan::DropUDF(_) => {}
-            Plan::CreateRole(_) => {}
-            Plan::DropRole(_) => {}
-            Plan::GrantRole(_) => {}
-            Plan::GrantPriv(_) => {}
+            Plan::CreateStream(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Database(plan.catalog.clone(), plan.database.clone()),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.table_database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Select,
+                    )
+                    .await?;
+            }
+            Plan::DropStream(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Database(plan.catalog.clone(), plan.database.clone()),
+                        UserPrivilegeType::Drop,
+                    )
+                    .await?;
+            }
+            Plan::CreateAggregatingIndex(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::DropAggregatingIndex(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::CreateBloomIndex(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::CreateInvertedIndex(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::DropIndex(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+
+            // Virtual columns.
+            Plan::CreateVirtualColumns(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::AlterVirtualColumns(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::DropVirtualColumns(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::Table(
+                            plan.catalog.clone(),
+                            plan.database.clone(),
+                            plan.table.clone(),
+                        ),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+
+            // User.
+            Plan::AlterUser(plan) => {
+                // The users can change the password of themselves.
+                let current_user = session.get_current_user()?;
+                if plan.user != current_user.identity() || plan.user_option.is_some() {
+                    session
+                        .validate_privilege(&GrantObject::Global, UserPrivilegeType::CreateUser)
+                        .await?;
+                }
+            }
+            Plan::CreateUser(_) | Plan::DropUser(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::CreateUser)
+                    .await?;
+            }
+
+            // UDF.
+            Plan::CreateUDF(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Create)
+                    .await?;
+            }
+            Plan::AlterUDF(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::UDF(plan.udf.name.clone()),
+                        UserPrivilegeType::Alter,
+                    )
+                    .await?;
+            }
+            Plan::DropUDF(plan) => {
+                session
+                    .validate_privilege(
+                        &GrantObject::UDF(plan.name.clone()),
+                        UserPrivilegeType::Drop,
+                    )
+                    .await?;
+            }
+
+            // Role.
+            Plan::CreateRole(_) | Plan::DropRole(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::CreateRole)
+                    .await?;
+            }
+            Plan::GrantRole(_) | Plan::GrantPriv(_) | Plan::RevokePriv(_) | Plan::RevokeRole(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Grant)
+                    .await?;
+            }
             Plan::ShowGrants(_) => {}
-            Plan::RevokePriv(_) => {}
-            Plan::RevokeRole(_) => {}
-            Plan::ListStage(_) => {}
-            Plan::CreateStage(_) => {}
-            Plan::DropStage(_) => {}
-            Plan::RemoveStage(_) => {}
-            Plan::Presign(_) => {}
+            Plan::SetRole(_) => {}
+
+            // Stage.
+            Plan::ListStage(plan) => {
+                self.validate_stage_privilege(&plan.stage, UserPrivilegeType::Read)
+                    .await?;
+            }
+            Plan::RemoveStage(plan) => {
+                self.validate_stage_privilege(&plan.stage, UserPrivilegeType::Write)
+                    .await?;
+            }
+            Plan::Presign(plan) => {
+                let privilege = match plan.action {
+                    PresignAction::Download => UserPrivilegeType::Read,
+                    PresignAction::Upload => UserPrivilegeType::Write,
+                };
+                self.validate_stage_privilege(&plan.stage, privilege)
+                    .await?;
+            }
+            Plan::CreateStage(_) | Plan::DropStage(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::CreateStage)
+                    .await?;
+            }
+
+            // Row access policy.
+            Plan::CreateRowAccessPolicy(_) | Plan::DropRowAccessPolicy(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+                    .await?;
+            }
+
+            // Masking policy.
+            Plan::CreateMaskingPolicy(_)
+            | Plan::DropMaskingPolicy(_)
+            | Plan::SetColumnMaskingPolicy(_)
+            | Plan::UnsetColumnMaskingPolicy(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+                    .await?;
+            }
+
+            // Network policy.
+            Plan::CreateNetworkPolicy(_) | Plan::DropNetworkPolicy(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+                    .await?;
+            }
+
+            // Workload group.
+            Plan::CreateWorkloadGroup(_) | Plan::DropWorkloadGroup(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+                    .await?;
+            }
+
+            // Task.
+            Plan::CreateTask(_) | Plan::DropTask(_) => {
+                session
+                    .validate_privilege(&GrantObject::Global, UserPrivilegeType::Super)
+                    .await?;
+            }
+
             Plan::SetVariable(_) => {}
             Plan::Kill(_) => {
                 session
@@ -208,6 +597,7 @@ impl AccessChecker for PrivilegeAccess {
             Plan::ShowGrantTenantsOfShare(_) => {}
             Plan::ExplainAst { .. } => {}
             Plan::ExplainSyntax { .. } => {}
+            Plan::Begin | Plan::Commit | Plan::Rollback => {}
         }
 
         Ok(())
@@ -0,0 +1,245 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::Timelike;
+use chrono::Utc;
+use common_base::base::tokio;
+use common_base::base::tokio::time::Duration;
+use common_base::base::GlobalIORuntime;
+use common_base::base::GlobalUniqName;
+use common_base::base::Singleton;
+use common_base::base::TrySpawn;
+use common_catalog::table::CompactTarget;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::LockApi;
+use common_management::LockMgr;
+use common_planner::plans::OptimizeTableAction;
+use common_planner::plans::OptimizeTablePlan;
+use common_users::UserApiProvider;
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+
+use super::Interpreter;
+use super::OptimizeTableInterpreter;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+use crate::Config;
+
+/// The lease of the compaction locks, renewed while the compaction is running.
+const LOCK_LEASE: Duration = Duration::from_secs(60);
+
+/// A table inserted into since the last check.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct TableKey {
+    tenant: String,
+    catalog: String,
+    database: String,
+    table: String,
+}
+
+/// Compacts the tables in background, so that the small blocks and segments piled up by the
+/// inserts are merged without scheduled `OPTIMIZE TABLE` jobs.
+///
+/// The tables inserted into are checked every `auto_compaction_interval` seconds within the
+/// `auto_compaction_off_peak_hours`, and compacted if they have `auto_compaction_threshold`
+/// more blocks or segments than needed. A table is compacted by one query node at a time, which
+/// holds the lock of the table in the meta service.
+pub struct AutoCompactionManager {
+    enabled: bool,
+    interval: Duration,
+    threshold: u64,
+    // the UTC hours [start, end) the compactions are scheduled in
+    off_peak_hours: Option<(u32, u32)>,
+    // identifies the holder of the locks
+    node_id: String,
+    pending: Mutex<HashSet<TableKey>>,
+}
+
+static AUTO_COMPACTION_MANAGER: OnceCell<Singleton<Arc<AutoCompactionManager>>> = OnceCell::new();
+
+impl AutoCompactionManager {
+    pub fn init(config: &Config, v: Singleton<Arc<AutoCompactionManager>>) -> Result<()> {
+        let manager = Arc::new(AutoCompactionManager {
+            enabled: config.query.enable_auto_compaction,
+            interval: Duration::from_secs(config.query.auto_compaction_interval.max(1)),
+            threshold: config.query.auto_compaction_threshold.max(1),
+            off_peak_hours: Self::parse_off_peak_hours(
+                &config.query.auto_compaction_off_peak_hours,
+            )?,
+            node_id: GlobalUniqName::unique(),
+            pending: Mutex::new(HashSet::new()),
+        });
+
+        if manager.enabled {
+            // The timer stops with the manager.
+            let weak_manager = Arc::downgrade(&manager);
+            let interval = manager.interval;
+            GlobalIORuntime::instance().spawn(async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+                    match weak_manager.upgrade() {
+                        None => break,
+                        Some(manager) => manager.check().await,
+                    }
+                }
+            });
+        }
+
+        v.init(manager)?;
+        AUTO_COMPACTION_MANAGER.set(v).ok();
+        Ok(())
+    }
+
+    pub fn instance() -> Arc<AutoCompactionManager> {
+        match AUTO_COMPACTION_MANAGER.get() {
+            None => panic!("AutoCompactionManager is not init"),
+            Some(auto_compaction_manager) => auto_compaction_manager.get(),
+        }
+    }
+
+    fn parse_off_peak_hours(hours: &str) -> Result<Option<(u32, u32)>> {
+        if hours.is_empty() {
+            return Ok(None);
+        }
+
+        let invalid = || {
+            ErrorCode::InvalidConfig(format!(
+                "Invalid auto_compaction_off_peak_hours {:?}, expect <start>-<end> in 0..=24",
+                hours
+            ))
+        };
+        let (start, end) = hours.split_once('-').ok_or_else(invalid)?;
+        let start = start.trim().parse::<u32>().map_err(|_| invalid())?;
+        let end = end.trim().parse::<u32>().map_err(|_| invalid())?;
+        if start > 24 || end > 24 {
+            return Err(invalid());
+        }
+        Ok(Some((start, end)))
+    }
+
+    fn is_off_peak(&self, hour: u32) -> bool {
+        match self.off_peak_hours {
+            None => true,
+            Some((start, end)) if start <= end => start <= hour && hour < end,
+            // the hours wrap around midnight, e.g. 22-4
+            Some((start, end)) => start <= hour || hour < end,
+        }
+    }
+
+    /// Records that the table is inserted into, it is checked for compaction later.
+    pub fn record_insert(&self, tenant: &str, catalog: &str, database: &str, table: &str) {
+        if !self.enabled {
+            return;
+        }
+
+        self.pending.lock().insert(TableKey {
+            tenant: tenant.to_string(),
+            catalog: catalog.to_string(),
+            database: database.to_string(),
+            table: table.to_string(),
+        });
+    }
+
+    async fn check(&self) {
+        // the inserted tables are kept until the off-peak hours
+        if !self.is_off_peak(Utc::now().hour()) {
+            return;
+        }
+
+        let tables = std::mem::take(&mut *self.pending.lock());
+        for key in tables {
+            if let Err(cause) = self.compact_table(&key).await {
+                tracing::error!(
+                    "auto compaction of {}.{} error: {:?}",
+                    key.database,
+                    key.table,
+                    cause
+                );
+            }
+        }
+    }
+
+    async fn compact_table(&self, key: &TableKey) -> Result<()> {
+        let session = SessionManager::instance()
+            .create_session(SessionType::AutoCompaction)
+            .await?;
+        session.set_current_tenant(key.tenant.clone());
+        let ctx = session.create_query_context().await?;
+
+        let table = ctx
+            .get_table(&key.catalog, &key.database, &key.table)
+            .await?;
+        let target = match FuseTable::try_from_table(table.as_ref()) {
+            // only the fuse tables are compacted
+            Err(_) => return Ok(()),
+            Ok(fuse_table) => {
+                fuse_table
+                    .compaction_target(ctx.clone(), self.threshold)
+                    .await?
+            }
+        };
+        let target = match target {
+            None => return Ok(()),
+            Some(target) => target,
+        };
+
+        let metastore = UserApiProvider::instance().get_meta_store_client();
+        let lock_api = LockMgr::create(metastore.as_ref().clone(), &key.tenant, LOCK_LEASE)?;
+        let lock_name = format!(
+            "auto_compaction/{}/{}/{}",
+            key.catalog, key.database, key.table
+        );
+        let mut seq = match lock_api.try_lock(&lock_name, &self.node_id).await? {
+            // another node is compacting the table
+            None => return Ok(()),
+            Some(seq) => seq,
+        };
+
+        tracing::info!(
+            "auto compaction of {}.{} starts, compact {:?}",
+            key.database,
+            key.table,
+            target
+        );
+        let plan = OptimizeTablePlan {
+            catalog: key.catalog.clone(),
+            database: key.database.clone(),
+            table: key.table.clone(),
+            action: OptimizeTableAction::Compact(target),
+        };
+        let interpreter = OptimizeTableInterpreter::try_create(ctx, plan)?;
+        let compaction = interpreter.execute2();
+        tokio::pin!(compaction);
+
+        let result = loop {
+            tokio::select! {
+                result = &mut compaction => break result,
+                _ = tokio::time::sleep(LOCK_LEASE / 3) => {
+                    seq = lock_api.renew(&lock_name, seq).await?;
+                }
+            }
+        };
+        lock_api.unlock(&lock_name, seq).await?;
+
+        result?;
+        tracing::info!("auto compaction of {}.{} finished", key.database, key.table);
+        Ok(())
+    }
+}
@@ -107,6 +107,28 @@ impl Fragmenter {
         Ok(root_fragment)
     }
 
+    /// Build the fragments to run a local plan on `executor`,
+    /// its output is merged back to the current node.
+    pub fn build_delegated_fragment(
+        self,
+        plan: &PhysicalPlan,
+        executor: String,
+    ) -> Result<PlanFragment> {
+        let plan = PhysicalPlan::Exchange(Exchange {
+            input: Box::new(plan.clone()),
+            kind: StageKind::Merge,
+            keys: vec![],
+            stat_info: None,
+        });
+
+        let mut root_fragment = self.build_fragment(&plan)?;
+        for fragment in root_fragment.source_fragments.iter_mut() {
+            fragment.fragment_type = FragmentType::Delegated(executor.clone());
+        }
+
+        Ok(root_fragment)
+    }
+
     fn resolve_fragment_connection(fragment: &mut PlanFragment) {
         for input in fragment.source_fragments.iter_mut() {
             if let PhysicalPlan::ExchangeSink(ExchangeSink {
@@ -148,6 +170,7 @@ impl PhysicalPlanReplacer for Fragmenter {
             join_type: plan.join_type.clone(),
             marker_index: plan.marker_index,
             from_correlated_subquery: plan.from_correlated_subquery,
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
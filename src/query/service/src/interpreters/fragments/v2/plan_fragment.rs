@@ -41,6 +41,10 @@ pub enum FragmentType {
     /// Leaf fragment of a query plan, which contains
     /// a `TableScan` operator.
     Source,
+
+    /// The whole query plan run on a single executor,
+    /// the root fragment only merges its output.
+    Delegated(String),
 }
 
 #[derive(Debug, Clone)]
@@ -104,6 +108,14 @@ impl PlanFragment {
                 }
                 actions.add_fragment_actions(fragment_actions)?;
             }
+            FragmentType::Delegated(executor) => {
+                let action = QueryFragmentAction::create_v2(executor.clone(), self.plan.clone());
+                fragment_actions.add_action(action);
+                if let Some(ref exchange) = self.exchange {
+                    fragment_actions.set_exchange(exchange.clone());
+                }
+                actions.add_fragment_actions(fragment_actions)?;
+            }
             FragmentType::Source => {
                 // Redistribute partitions
                 let mut fragment_actions = self.redistribute_source_fragment(ctx)?;
@@ -206,6 +218,7 @@ impl PhysicalPlanReplacer for ReplaceReadSource {
             source: Box::new(self.source.clone()),
             name_mapping: plan.name_mapping.clone(),
             table_index: plan.table_index,
+            stat_info: plan.stat_info.clone(),
         }))
     }
 }
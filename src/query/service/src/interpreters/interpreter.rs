@@ -13,17 +13,22 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 use std::time::SystemTime;
 
+use common_base::base::tokio;
+use common_base::base::GlobalIORuntime;
 use common_catalog::table_context::TableContext;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::WorkloadGroup;
 use common_streams::DataBlockStream;
 use common_streams::ProgressStream;
 use common_streams::SendableDataBlockStream;
+use common_users::UserApiProvider;
 
 use crate::interpreters::InterpreterQueryLog;
 use crate::interpreters::PullingExecutorStream;
@@ -33,7 +38,9 @@ use crate::pipelines::executor::PipelinePullingExecutor;
 use crate::pipelines::PipelineBuildResult;
 use crate::pipelines::SourcePipeBuilder;
 use crate::sessions::QueryContext;
+use crate::sessions::QueryStage;
 use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
 
 #[async_trait::async_trait]
 /// Interpreter is a trait for different PlanNode
@@ -51,6 +58,12 @@ pub trait Interpreter: Sync + Send {
     async fn execute(&self, ctx: Arc<QueryContext>) -> Result<SendableDataBlockStream> {
         log_query_start(&ctx);
 
+        if let Err(queue_error) = acquire_query_permit(&ctx).await {
+            log_query_finished(&ctx, Some(queue_error.clone()));
+            return Err(queue_error);
+        }
+
+        ctx.set_stage(QueryStage::BuildingPipeline);
         let mut build_res = match self.execute2().await {
             Ok(build_res) => build_res,
             Err(build_error) => {
@@ -80,8 +93,12 @@ pub trait Interpreter: Sync + Send {
         });
 
         let settings = ctx.get_settings();
-        build_res.set_max_threads(settings.get_max_threads()? as usize);
-        let settings = ExecutorSettings::try_create(&settings)?;
+        let mut max_threads = settings.get_max_threads()?;
+        if let Some(group) = ctx.get_workload_group() {
+            max_threads = group.max_threads(max_threads);
+        }
+        build_res.set_max_threads(max_threads as usize);
+        let settings = ExecutorSettings::try_create(&ctx)?;
 
         if build_res.main_pipeline.is_complete_pipeline()? {
             let mut pipelines = build_res.sources_pipelines;
@@ -138,9 +155,98 @@ fn log_query_start(ctx: &QueryContext) {
     }
 }
 
+/// Wait for the admission of the query queue, the permit is released when the query finished.
+/// No query is admitted once the node is draining.
+async fn acquire_query_permit(ctx: &Arc<QueryContext>) -> Result<()> {
+    // The flushes of the buffered async inserts are admitted by the inserts themselves.
+    let typ = ctx.get_current_session().get_type();
+    if !typ.is_user_session() || matches!(typ, SessionType::AsyncInsert) {
+        return Ok(());
+    }
+
+    if ctx.has_query_permit() {
+        return Ok(());
+    }
+
+    if SessionManager::instance().is_draining() {
+        return Err(ErrorCode::NodeDraining(
+            "The node is draining, no new query is accepted",
+        ));
+    }
+
+    ctx.set_stage(QueryStage::Queued);
+    let user = ctx.get_current_user()?;
+    let group = UserApiProvider::instance()
+        .get_user_workload_group(&ctx.get_tenant(), &user)
+        .await?;
+    let permit = SessionManager::instance()
+        .get_query_queue()
+        .acquire(
+            &ctx.get_id(),
+            &user.name,
+            group.as_ref(),
+            &ctx.get_query_str(),
+        )
+        .await?;
+    ctx.set_query_permit(permit);
+
+    if let Some(group) = group {
+        watch_workload_group_limits(ctx, &group);
+        ctx.set_workload_group(group);
+    }
+    Ok(())
+}
+
+/// Kill the query once it exceeds the memory or scan limits of its workload group, the watching
+/// stops when the query finished and released its permit.
+fn watch_workload_group_limits(ctx: &Arc<QueryContext>, group: &WorkloadGroup) {
+    if group.memory_limit == 0 && group.max_scan_bytes == 0 {
+        return;
+    }
+
+    let weak_ctx = Arc::downgrade(ctx);
+    let group = group.clone();
+    GlobalIORuntime::instance().spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let ctx = match weak_ctx.upgrade() {
+                Some(ctx) if ctx.has_query_permit() => ctx,
+                _ => return,
+            };
+
+            if let Some(cause) = check_workload_group_limits(&ctx, &group) {
+                ctx.get_current_session().force_kill_query(cause);
+                return;
+            }
+        }
+    });
+}
+
+fn check_workload_group_limits(ctx: &QueryContext, group: &WorkloadGroup) -> Option<ErrorCode> {
+    let memory_usage = ctx.get_memory_usage().max(0) as u64;
+    if group.memory_limit != 0 && memory_usage > group.memory_limit {
+        return Some(ErrorCode::WorkloadGroupLimitExceeded(format!(
+            "The query used {} bytes of memory, exceeds MEMORY_LIMIT {} of workload group {}",
+            memory_usage, group.memory_limit, group.name
+        )));
+    }
+
+    let scan_bytes = ctx.get_scan_progress_value().bytes as u64;
+    if group.max_scan_bytes != 0 && scan_bytes > group.max_scan_bytes {
+        return Some(ErrorCode::WorkloadGroupLimitExceeded(format!(
+            "The query scanned {} bytes, exceeds MAX_SCAN_BYTES {} of workload group {}",
+            scan_bytes, group.max_scan_bytes, group.name
+        )));
+    }
+
+    None
+}
+
 fn log_query_finished(ctx: &QueryContext, error: Option<ErrorCode>) {
     let now = SystemTime::now();
     let session = ctx.get_current_session();
+    ctx.take_query_permit();
 
     session.get_status().write().query_finish();
     if session.get_type().is_user_session() {
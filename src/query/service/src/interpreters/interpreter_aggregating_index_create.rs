@@ -0,0 +1,93 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_planner::plans::CreateAggregatingIndexPlan;
+use common_storages_index::AggregatingIndexItem;
+use common_storages_index::AggregatingIndexMeta;
+use common_storages_util::table_option_keys::OPT_KEY_AGGREGATING_INDEX_PREFIX;
+use uuid::Uuid;
+
+use super::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+
+pub struct CreateAggregatingIndexInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateAggregatingIndexPlan,
+}
+
+impl CreateAggregatingIndexInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateAggregatingIndexPlan) -> Result<Self> {
+        Ok(CreateAggregatingIndexInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateAggregatingIndexInterpreter {
+    fn name(&self) -> &str {
+        "CreateAggregatingIndexInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        // The indexes are maintained by the fuse engine only.
+        FuseTable::try_from_table(table.as_ref())?;
+
+        let key = format!("{}{}", OPT_KEY_AGGREGATING_INDEX_PREFIX, plan.index_name);
+        if table.options().contains_key(&key) {
+            return if plan.if_not_exists {
+                Ok(PipelineBuildResult::create())
+            } else {
+                Err(ErrorCode::AggregatingIndexAlreadyExists(format!(
+                    "Aggregating index {} already exists on table {}.{}",
+                    plan.index_name, plan.database, plan.table
+                )))
+            };
+        }
+
+        let index = AggregatingIndexMeta {
+            id: Uuid::new_v4().simple().to_string(),
+            name: plan.index_name.clone(),
+            query: plan.query.clone(),
+            group_by: plan.group_by.clone(),
+            aggregates: plan
+                .aggregates
+                .iter()
+                .map(|(func_name, arg)| AggregatingIndexItem {
+                    func_name: func_name.clone(),
+                    arg: arg.clone(),
+                })
+                .collect(),
+        };
+        // Make sure the index can be computed from the blocks of the table.
+        index.schema(&table.schema())?;
+
+        // The blocks written before are aggregated when the index is read.
+        let value = serde_json::to_string(&index)?;
+        let req = UpsertTableOptionReq::new(&table.get_table_info().ident, key, value);
+        catalog.upsert_table_option(req).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_planner::plans::DropAggregatingIndexPlan;
+use common_storages_util::table_option_keys::OPT_KEY_AGGREGATING_INDEX_PREFIX;
+
+use super::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct DropAggregatingIndexInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: DropAggregatingIndexPlan,
+}
+
+impl DropAggregatingIndexInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: DropAggregatingIndexPlan) -> Result<Self> {
+        Ok(DropAggregatingIndexInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropAggregatingIndexInterpreter {
+    fn name(&self) -> &str {
+        "DropAggregatingIndexInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+
+        let key = format!("{}{}", OPT_KEY_AGGREGATING_INDEX_PREFIX, plan.index_name);
+        if !table.options().contains_key(&key) {
+            return if plan.if_exists {
+                Ok(PipelineBuildResult::create())
+            } else {
+                Err(ErrorCode::UnknownAggregatingIndex(format!(
+                    "Unknown aggregating index {} on table {}.{}",
+                    plan.index_name, plan.database, plan.table
+                )))
+            };
+        }
+
+        // The index files are left to be purged with the table.
+        let ident = &table.get_table_info().ident;
+        let req = UpsertTableOptionReq {
+            table_id: ident.table_id,
+            seq: MatchSeq::Exact(ident.seq),
+            options: HashMap::from([(key, None)]),
+        };
+        catalog.upsert_table_option(req).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
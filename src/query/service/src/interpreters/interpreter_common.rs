@@ -26,6 +26,7 @@ use common_meta_types::GrantObject;
 use common_meta_types::StageFile;
 use common_meta_types::UserStageInfo;
 use common_pipeline_core::Pipeline;
+use common_users::UserApiProvider;
 use futures::TryStreamExt;
 use regex::Regex;
 use tracing::debug;
@@ -110,7 +111,7 @@ pub fn append2table(
 }
 
 pub fn execute_pipeline(ctx: Arc<QueryContext>, mut res: PipelineBuildResult) -> Result<()> {
-    let executor_settings = ExecutorSettings::try_create(&ctx.get_settings())?;
+    let executor_settings = ExecutorSettings::try_create(&ctx)?;
     res.set_max_threads(ctx.get_settings().get_max_threads()? as usize);
     let mut pipelines = res.sources_pipelines;
     pipelines.push(res.main_pipeline);
@@ -150,6 +151,16 @@ pub async fn validate_grant_object_exists(
                 )));
             }
         }
+        GrantObject::Stage(stage_name) => {
+            UserApiProvider::instance()
+                .get_stage(&tenant, stage_name)
+                .await?;
+        }
+        GrantObject::UDF(udf_name) => {
+            UserApiProvider::instance()
+                .get_udf(&tenant, udf_name)
+                .await?;
+        }
         GrantObject::Global => (),
     }
 
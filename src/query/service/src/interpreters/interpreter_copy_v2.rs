@@ -21,6 +21,7 @@ use common_base::base::TrySpawn;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_io::prelude::glob_to_regex;
 use common_legacy_planners::ReadDataSourcePlan;
 use common_legacy_planners::SourceInfo;
 use common_legacy_planners::StageTableInfo;
@@ -149,9 +150,10 @@ impl CopyInterpreterV2 {
     /// List the files.
     /// There are two cases here:
     /// 1. If the plan.files is not empty, we already set the files sets to the COPY command with: `files=(<file1>, <file2>)` syntax, only need to add the prefix to the file.
-    /// 2. If the plan.files is empty, there are also two case:
-    ///     2.1 If the path is a file like /path/to/path/file, S3File::list() will return the same file path.
-    ///     2.2 If the path is a folder, S3File::list() will return all the files in it.
+    /// 2. If the plan.files is empty, there are also three case:
+    ///     2.1 If the path has a glob like /path/to/*.csv, return the files matched by it.
+    ///     2.2 If the path is a file like /path/to/path/file, S3File::list() will return the same file path.
+    ///     2.3 If the path is a folder, S3File::list() will return all the files in it.
     ///
     /// TODO(xuanwo): Align with interpreters/interpreter_common.rs `list_files`
     async fn list_files(
@@ -170,6 +172,32 @@ impl CopyInterpreterV2 {
                         files_with_path.push(new_path.to_string_lossy().to_string());
                     }
                     files_with_path
+                } else if let Some(pos) = path.find(|c| matches!(c, '*' | '?' | '[')) {
+                    // A glob in the path like `@s1/data/*.csv`, list the files of the directory
+                    // before it and match them with the glob.
+                    let dir = match path[..pos].rfind('/') {
+                        Some(i) => &path[..=i],
+                        None => "/",
+                    };
+                    let glob = path.trim_start_matches('/');
+                    let regex = Regex::new(&glob_to_regex(glob)).map_err(|e| {
+                        ErrorCode::SyntaxException(format!(
+                            "Glob format invalid, got:{}, error:{:?}",
+                            glob, e
+                        ))
+                    })?;
+
+                    let rename_me: Arc<dyn TableContext> = self.ctx.clone();
+                    let op = StageTable::get_op(&rename_me, &table_info.stage_info).await?;
+                    let mut list = HashSet::new();
+                    let mut objects = op.batch().walk_top_down(dir)?;
+                    while let Some(de) = objects.try_next().await? {
+                        if !de.mode().is_dir() && regex.is_match(de.path()) {
+                            list.insert(de.path().to_string());
+                        }
+                    }
+
+                    list.into_iter().collect::<Vec<_>>()
                 } else if !path.ends_with('/') {
                     let rename_me: Arc<dyn TableContext> = self.ctx.clone();
                     let op = StageTable::get_op(&rename_me, &table_info.stage_info).await?;
@@ -289,6 +317,7 @@ impl CopyInterpreterV2 {
                 let ctx = ctx.clone();
                 let files = files.clone();
                 let from = from.clone();
+                let from_table = from_table.clone();
                 let to_table = to_table.clone();
 
                 let task = GlobalIORuntime::instance().spawn(async move {
@@ -298,6 +327,12 @@ impl CopyInterpreterV2 {
                         .commit_insertion(ctx.clone(), operations, false)
                         .await?;
 
+                    // Rows skipped by `ON_ERROR = CONTINUE`
+                    if let Some(stage_table) = from_table.as_any().downcast_ref::<StageTable>() {
+                        let table_ctx: Arc<dyn TableContext> = ctx.clone();
+                        stage_table.write_rejected_rows(&table_ctx).await?;
+                    }
+
                     // Purge
                     CopyInterpreterV2::purge_files(ctx, &from, &files).await
                 });
@@ -323,6 +358,7 @@ impl CopyInterpreterV2 {
         stage: &UserStageInfo,
         path: &str,
         query: &Plan,
+        partition_by: &[String],
     ) -> Result<PipelineBuildResult> {
         let (s_expr, metadata, bind_context) = match query {
             Plan::Query {
@@ -359,6 +395,7 @@ impl CopyInterpreterV2 {
             stage_info: stage.clone(),
             path: path.to_string(),
             files: vec![],
+            partition_by: partition_by.to_vec(),
         };
 
         let mut build_res = select_interpreter.execute2().await?;
@@ -472,8 +509,15 @@ impl Interpreter for CopyInterpreterV2 {
                 }
             }
             CopyPlanV2::IntoStage {
-                stage, from, path, ..
-            } => self.execute_copy_into_stage(stage, path, from).await,
+                stage,
+                from,
+                path,
+                partition_by,
+                ..
+            } => {
+                self.execute_copy_into_stage(stage, path, from, partition_by)
+                    .await
+            }
         }
     }
 }
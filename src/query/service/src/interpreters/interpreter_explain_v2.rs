@@ -22,8 +22,12 @@ use common_planner::MetadataRef;
 
 use super::fragments::Fragmenter;
 use super::QueryFragmentsActions;
+use crate::interpreters::execute_pipeline;
 use crate::interpreters::Interpreter;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::EmptySink;
 use crate::pipelines::PipelineBuildResult;
+use crate::pipelines::SinkPipeBuilder;
 use crate::sessions::QueryContext;
 use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::PhysicalPlanBuilder;
@@ -64,6 +68,18 @@ impl Interpreter for ExplainInterpreterV2 {
                 }
                 _ => self.explain_plan(&self.plan)?,
             },
+            ExplainKind::Estimate => match &self.plan {
+                Plan::Query {
+                    s_expr, metadata, ..
+                } => {
+                    let builder = PhysicalPlanBuilder::new(metadata.clone(), self.ctx.clone());
+                    let plan = builder.build(s_expr).await?;
+                    self.explain_estimate(&plan, metadata)?
+                }
+                _ => {
+                    return Err(ErrorCode::UnImplement("Unsupported EXPLAIN statement"));
+                }
+            },
             ExplainKind::Pipeline => match &self.plan {
                 Plan::Query {
                     s_expr, metadata, ..
@@ -86,6 +102,17 @@ impl Interpreter for ExplainInterpreterV2 {
                     return Err(ErrorCode::UnImplement("Unsupported EXPLAIN statement"));
                 }
             },
+            ExplainKind::Analyze => match &self.plan {
+                Plan::Query {
+                    s_expr, metadata, ..
+                } => {
+                    self.explain_analyze(*s_expr.clone(), metadata.clone())
+                        .await?
+                }
+                _ => {
+                    return Err(ErrorCode::UnImplement("Unsupported EXPLAIN statement"));
+                }
+            },
             ExplainKind::Graph => {
                 return Err(ErrorCode::UnImplement("ExplainKind graph is unimplemented"));
             }
@@ -137,6 +164,19 @@ impl ExplainInterpreterV2 {
         ])])
     }
 
+    pub fn explain_estimate(
+        &self,
+        plan: &PhysicalPlan,
+        metadata: &MetadataRef,
+    ) -> Result<Vec<DataBlock>> {
+        let result = plan.format_with_estimates(metadata.clone())?;
+        let line_splitted_result: Vec<&str> = result.lines().collect();
+        let formatted_plan = Series::from_data(line_splitted_result);
+        Ok(vec![DataBlock::create(self.schema.clone(), vec![
+            formatted_plan,
+        ])])
+    }
+
     pub async fn explain_pipeline(
         &self,
         s_expr: SExpr,
@@ -172,6 +212,61 @@ impl ExplainInterpreterV2 {
         Ok(blocks)
     }
 
+    /// Execute the query and format the pipelines with the runtime statistics of the processors.
+    async fn explain_analyze(
+        &self,
+        s_expr: SExpr,
+        metadata: MetadataRef,
+    ) -> Result<Vec<DataBlock>> {
+        let builder = PhysicalPlanBuilder::new(metadata, self.ctx.clone());
+        let plan = builder.build(&s_expr).await?;
+
+        let pipeline_builder = PipelineBuilder::create(self.ctx.clone());
+        let mut build_res = pipeline_builder.finalize(&plan)?;
+
+        let main_profile = build_res.main_pipeline.enable_profiling();
+        let sources_profiles = build_res
+            .sources_pipelines
+            .iter()
+            .map(|pipeline| pipeline.enable_profiling())
+            .collect::<Vec<_>>();
+
+        // The results of the query are discarded.
+        let mut sink_pipe_builder = SinkPipeBuilder::create();
+        for _ in 0..build_res.main_pipeline.output_len() {
+            let input_port = InputPort::create();
+            sink_pipe_builder.add_sink(input_port.clone(), EmptySink::create(input_port));
+        }
+        build_res
+            .main_pipeline
+            .add_pipe(sink_pipe_builder.finalize());
+
+        execute_pipeline(self.ctx.clone(), build_res)?;
+
+        let mut blocks = vec![];
+        // Format root pipeline
+        blocks.push(DataBlock::create(self.schema.clone(), vec![
+            Series::from_data(
+                format!("{}", main_profile.display_indent())
+                    .lines()
+                    .map(|s| s.as_bytes())
+                    .collect::<Vec<_>>(),
+            ),
+        ]));
+        // Format child pipelines
+        for profile in sources_profiles.iter() {
+            blocks.push(DataBlock::create(self.schema.clone(), vec![
+                Series::from_data(
+                    format!("\n{}", profile.display_indent())
+                        .lines()
+                        .map(|s| s.as_bytes())
+                        .collect::<Vec<_>>(),
+                ),
+            ]));
+        }
+        Ok(blocks)
+    }
+
     async fn explain_fragments(
         &self,
         s_expr: SExpr,
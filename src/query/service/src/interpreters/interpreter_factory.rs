@@ -21,6 +21,7 @@ use tracing::error;
 use super::interpreter_share_desc::DescShareInterpreter;
 use super::interpreter_user_stage_drop::DropUserStageInterpreter;
 use super::*;
+use crate::audit::AuditLogger;
 use crate::interpreters::access::Accessor;
 use crate::interpreters::interpreter_copy_v2::CopyInterpreterV2;
 use crate::interpreters::interpreter_presign::PresignInterpreter;
@@ -43,9 +44,17 @@ impl InterpreterFactory {
         let access_checker = Accessor::create(ctx.clone());
         access_checker.check(plan).await.map_err(|e| {
             error!("Access.denied(v2): {:?}", e);
+            AuditLogger::log_query(&ctx, Some(&e));
             e
         })?;
 
+        // the plans of the queries are summarized only if the slow queries are logged
+        if ctx.get_config().query.slow_query_threshold_ms > 0 {
+            if let Plan::Query { .. } = plan {
+                ctx.attach_plan_summary(plan.format_indent()?);
+            }
+        }
+
         match plan {
             Plan::Query {
                 s_expr,
@@ -116,6 +125,10 @@ impl InterpreterFactory {
                 ctx,
                 *create_table.clone(),
             )?)),
+            Plan::AttachTable(attach_table) => Ok(Arc::new(AttachTableInterpreter::try_create(
+                ctx,
+                *attach_table.clone(),
+            )?)),
             Plan::DropTable(drop_table) => Ok(Arc::new(DropTableInterpreter::try_create(
                 ctx,
                 *drop_table.clone(),
@@ -131,6 +144,9 @@ impl InterpreterFactory {
             Plan::AlterTableClusterKey(alter_table_cluster_key) => Ok(Arc::new(
                 AlterTableClusterKeyInterpreter::try_create(ctx, *alter_table_cluster_key.clone())?,
             )),
+            Plan::AlterTableColumn(alter_table_column) => Ok(Arc::new(
+                AlterTableColumnInterpreter::try_create(ctx, *alter_table_column.clone())?,
+            )),
             Plan::DropTableClusterKey(drop_table_cluster_key) => Ok(Arc::new(
                 DropTableClusterKeyInterpreter::try_create(ctx, *drop_table_cluster_key.clone())?,
             )),
@@ -140,9 +156,16 @@ impl InterpreterFactory {
             Plan::TruncateTable(truncate_table) => Ok(Arc::new(
                 TruncateTableInterpreter::try_create(ctx, *truncate_table.clone())?,
             )),
+            Plan::FlashbackTable(flashback_table) => Ok(Arc::new(
+                FlashbackTableInterpreter::try_create(ctx, *flashback_table.clone())?,
+            )),
             Plan::OptimizeTable(optimize_table) => Ok(Arc::new(
                 OptimizeTableInterpreter::try_create(ctx, *optimize_table.clone())?,
             )),
+            Plan::AnalyzeTable(analyze_table) => Ok(Arc::new(AnalyzeTableInterpreter::try_create(
+                ctx,
+                *analyze_table.clone(),
+            )?)),
             Plan::ExistsTable(exists_table) => Ok(Arc::new(ExistsTableInterpreter::try_create(
                 ctx,
                 *exists_table.clone(),
@@ -162,6 +185,46 @@ impl InterpreterFactory {
                 *drop_view.clone(),
             )?)),
 
+            // Streams
+            Plan::CreateStream(p) => Ok(Arc::new(CreateStreamInterpreter::try_create(
+                ctx,
+                *p.clone(),
+            )?)),
+            Plan::DropStream(p) => Ok(Arc::new(DropStreamInterpreter::try_create(
+                ctx,
+                *p.clone(),
+            )?)),
+
+            // Aggregating indexes
+            Plan::CreateAggregatingIndex(p) => Ok(Arc::new(
+                CreateAggregatingIndexInterpreter::try_create(ctx, *p.clone())?,
+            )),
+            Plan::DropAggregatingIndex(p) => Ok(Arc::new(
+                DropAggregatingIndexInterpreter::try_create(ctx, *p.clone())?,
+            )),
+
+            // Bloom and inverted indexes
+            Plan::CreateBloomIndex(p) => Ok(Arc::new(CreateBloomIndexInterpreter::try_create(
+                ctx,
+                *p.clone(),
+            )?)),
+            Plan::CreateInvertedIndex(p) => Ok(Arc::new(
+                CreateInvertedIndexInterpreter::try_create(ctx, *p.clone())?,
+            )),
+            Plan::DropIndex(p) => Ok(Arc::new(DropIndexInterpreter::try_create(ctx, *p.clone())?)),
+
+            // Virtual columns
+            Plan::CreateVirtualColumns(p) => Ok(Arc::new(
+                CreateVirtualColumnsInterpreter::try_create(ctx, *p.clone())?,
+            )),
+            Plan::AlterVirtualColumns(p) => Ok(Arc::new(
+                AlterVirtualColumnsInterpreter::try_create(ctx, *p.clone())?,
+            )),
+            Plan::DropVirtualColumns(p) => Ok(Arc::new(DropVirtualColumnsInterpreter::try_create(
+                ctx,
+                *p.clone(),
+            )?)),
+
             // Users
             Plan::CreateUser(create_user) => Ok(Arc::new(CreateUserInterpreter::try_create(
                 ctx,
@@ -192,6 +255,10 @@ impl InterpreterFactory {
                 ctx,
                 *drop_role.clone(),
             )?)),
+            Plan::SetRole(set_role) => Ok(Arc::new(SetRoleInterpreter::try_create(
+                ctx,
+                *set_role.clone(),
+            )?)),
 
             // Stages
             Plan::ListStage(s) => Ok(Arc::new(ListInterpreter::try_create(ctx, *s.clone())?)),
@@ -246,6 +313,50 @@ impl InterpreterFactory {
                 *presign.clone(),
             )?)),
 
+            Plan::CreateRowAccessPolicy(p) => Ok(Arc::new(
+                CreateRowAccessPolicyInterpreter::try_create(ctx, *p.clone())?,
+            )),
+            Plan::DropRowAccessPolicy(p) => Ok(Arc::new(
+                DropRowAccessPolicyInterpreter::try_create(ctx, *p.clone())?,
+            )),
+
+            Plan::CreateMaskingPolicy(p) => Ok(Arc::new(
+                CreateMaskingPolicyInterpreter::try_create(ctx, *p.clone())?,
+            )),
+            Plan::DropMaskingPolicy(p) => Ok(Arc::new(DropMaskingPolicyInterpreter::try_create(
+                ctx,
+                *p.clone(),
+            )?)),
+            Plan::SetColumnMaskingPolicy(p) => Ok(Arc::new(
+                SetColumnMaskingPolicyInterpreter::try_create(ctx, *p.clone())?,
+            )),
+            Plan::UnsetColumnMaskingPolicy(p) => Ok(Arc::new(
+                UnsetColumnMaskingPolicyInterpreter::try_create(ctx, *p.clone())?,
+            )),
+
+            Plan::CreateNetworkPolicy(p) => Ok(Arc::new(
+                CreateNetworkPolicyInterpreter::try_create(ctx, *p.clone())?,
+            )),
+            Plan::DropNetworkPolicy(p) => Ok(Arc::new(DropNetworkPolicyInterpreter::try_create(
+                ctx,
+                *p.clone(),
+            )?)),
+
+            Plan::CreateWorkloadGroup(p) => Ok(Arc::new(
+                CreateWorkloadGroupInterpreter::try_create(ctx, *p.clone())?,
+            )),
+            Plan::DropWorkloadGroup(p) => Ok(Arc::new(DropWorkloadGroupInterpreter::try_create(
+                ctx,
+                *p.clone(),
+            )?)),
+
+            // Tasks
+            Plan::CreateTask(p) => Ok(Arc::new(CreateTaskInterpreter::try_create(
+                ctx,
+                *p.clone(),
+            )?)),
+            Plan::DropTask(p) => Ok(Arc::new(DropTaskInterpreter::try_create(ctx, *p.clone())?)),
+
             Plan::SetVariable(set_variable) => Ok(Arc::new(SettingInterpreter::try_create(
                 ctx,
                 *set_variable.clone(),
@@ -285,6 +396,11 @@ impl InterpreterFactory {
             Plan::ShowGrantTenantsOfShare(p) => Ok(Arc::new(
                 ShowGrantTenantsOfShareInterpreter::try_create(ctx, *p.clone())?,
             )),
+
+            // transaction plans
+            Plan::Begin => Ok(Arc::new(BeginInterpreter::try_create(ctx)?)),
+            Plan::Commit => Ok(Arc::new(CommitInterpreter::try_create(ctx)?)),
+            Plan::Rollback => Ok(Arc::new(RollbackInterpreter::try_create(ctx)?)),
         }
     }
 }
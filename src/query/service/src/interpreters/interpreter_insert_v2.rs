@@ -12,15 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::VecDeque;
 use std::io::Cursor;
 use std::ops::Not;
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_ast::ast::Expr;
 use common_ast::parser::parse_comma_separated_exprs;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
+use common_base::base::tokio;
 use common_base::base::GlobalIORuntime;
+use common_base::base::ProgressValues;
 use common_base::base::TrySpawn;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
@@ -42,10 +46,14 @@ use super::interpreter_common::append2table;
 use super::plan_schedulers::build_schedule_pipeline;
 use crate::evaluator::EvalNode;
 use crate::evaluator::Evaluator;
+use crate::interpreters::AsyncInsertManager;
+use crate::interpreters::AutoCompactionManager;
+use crate::interpreters::InsertKey;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::transforms::ExpressionTransformV2;
+use crate::pipelines::processors::BlocksSource;
 use crate::pipelines::PipelineBuildResult;
 use crate::pipelines::SourcePipeBuilder;
 use crate::sessions::QueryContext;
@@ -100,6 +108,70 @@ impl InsertInterpreterV2 {
         let cast_needed = select_schema != *output_schema;
         Ok(cast_needed)
     }
+
+    /// Queue the data of INSERT VALUES or the blocks from the client, to be merged with the other
+    /// inserts into the table. Returns false if the insert is not supported in async mode.
+    ///
+    /// The statement returns once the data is queued, or committed with `wait_for_async_insert`.
+    async fn push_async_insert(&self) -> Result<bool> {
+        if self.plan.overwrite {
+            return Ok(false);
+        }
+        let block = match &self.plan.source {
+            InsertInputSource::Values(data) => {
+                let settings = self.ctx.get_settings();
+                let name_resolution_ctx = NameResolutionContext::try_from(settings.as_ref())?;
+                let source = ValueSource::new(
+                    data.to_string(),
+                    self.ctx.clone(),
+                    name_resolution_ctx,
+                    self.plan.schema(),
+                );
+                let cursor = Cursor::new(data.as_bytes());
+                let mut reader = NestedCheckpointReader::new(BufferReader::new(cursor));
+                source.read(&mut reader).await?
+            }
+            InsertInputSource::Blocks(blocks) if blocks.is_empty() => return Ok(true),
+            InsertInputSource::Blocks(blocks) => DataBlock::concat_blocks(blocks)?,
+            _ => return Ok(false),
+        };
+        if block.num_rows() == 0 {
+            return Ok(true);
+        }
+
+        let progress_values = ProgressValues {
+            rows: block.num_rows(),
+            bytes: block.memory_size(),
+        };
+        self.ctx.get_scan_progress().incr(&progress_values);
+
+        // the data is in the block, the source of the plan is not used any more
+        let mut plan = self.plan.clone();
+        plan.source = InsertInputSource::Blocks(vec![]);
+        let key = InsertKey::create(
+            Arc::new(plan),
+            self.ctx.get_tenant(),
+            self.ctx.get_current_user()?,
+            self.ctx.get_changed_settings(),
+        );
+        let rx = AsyncInsertManager::instance().push(key, block);
+
+        let settings = self.ctx.get_settings();
+        if settings.get_wait_for_async_insert()? == 0 {
+            return Ok(true);
+        }
+        let timeout = settings.get_wait_for_async_insert_timeout()?;
+        match tokio::time::timeout(Duration::from_secs(timeout), rx).await {
+            Ok(Ok(result)) => result.map(|_| true),
+            Ok(Err(_)) => Err(ErrorCode::LogicalError(
+                "Async insert is dropped before flushed",
+            )),
+            Err(_) => Err(ErrorCode::AsyncInsertTimeoutError(format!(
+                "Async insert is not flushed in {} seconds, the data will be inserted later",
+                timeout
+            ))),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -118,6 +190,19 @@ impl Interpreter for InsertInterpreterV2 {
         let mut build_res = PipelineBuildResult::create();
         let mut builder = SourcePipeBuilder::create();
 
+        let enable_async_insert = self.ctx.get_settings().get_enable_async_insert()? == 1;
+        if !self.async_insert && enable_async_insert && self.push_async_insert().await? {
+            return Ok(build_res);
+        }
+
+        // the table is checked for the small blocks after a while
+        AutoCompactionManager::instance().record_insert(
+            &self.ctx.get_tenant(),
+            &plan.catalog,
+            &plan.database,
+            &plan.table,
+        );
+
         if self.async_insert {
             build_res.main_pipeline.add_pipe(
                 ((*self.source_pipe_builder.lock()).clone())
@@ -142,6 +227,15 @@ impl Interpreter for InsertInterpreterV2 {
 
                     build_res.main_pipeline.add_pipe(builder.finalize());
                 }
+                InsertInputSource::Blocks(blocks) => {
+                    let output_port = OutputPort::create();
+                    let blocks = Arc::new(Mutex::new(VecDeque::from(blocks.clone())));
+                    let source =
+                        BlocksSource::create(self.ctx.clone(), output_port.clone(), blocks)?;
+                    builder.add_source(output_port, source);
+
+                    build_res.main_pipeline.add_pipe(builder.finalize());
+                }
                 InsertInputSource::StreamingWithFormat(_, _, input_context) => {
                     let input_context = input_context.as_ref().expect("must success").clone();
                     input_context
@@ -583,6 +677,7 @@ async fn exprs_to_datavalue<'a>(
     let one_row_block = DataBlock::create(dummy, vec![Series::from_data(vec![1u8])]);
     let func_ctx = ctx.try_get_function_context()?;
     let mut expression_transform = ExpressionTransformV2 {
+        common_expressions: vec![],
         expressions,
         func_ctx,
     };
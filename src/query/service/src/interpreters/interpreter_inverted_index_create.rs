@@ -0,0 +1,131 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::GlobalIORuntime;
+use common_base::base::TrySpawn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_planner::plans::CreateInvertedIndexPlan;
+use common_storages_index::check_inverted_index_column;
+use common_storages_index::InvertedIndexMeta;
+use common_storages_util::table_option_keys::OPT_KEY_BLOOM_INDEX_PREFIX;
+use common_storages_util::table_option_keys::OPT_KEY_INVERTED_INDEX_PREFIX;
+use uuid::Uuid;
+
+use super::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+
+pub struct CreateInvertedIndexInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateInvertedIndexPlan,
+}
+
+impl CreateInvertedIndexInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateInvertedIndexPlan) -> Result<Self> {
+        Ok(CreateInvertedIndexInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateInvertedIndexInterpreter {
+    fn name(&self) -> &str {
+        "CreateInvertedIndexInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        // The indexes are maintained by the fuse engine only.
+        FuseTable::try_from_table(table.as_ref())?;
+
+        // the names of the indexes of all the types are unique in a table
+        let exists = [OPT_KEY_BLOOM_INDEX_PREFIX, OPT_KEY_INVERTED_INDEX_PREFIX]
+            .iter()
+            .any(|prefix| {
+                let key = format!("{}{}", prefix, plan.index_name);
+                table.options().contains_key(&key)
+            });
+        if exists {
+            return if plan.if_not_exists {
+                Ok(PipelineBuildResult::create())
+            } else {
+                Err(ErrorCode::IndexAlreadyExists(format!(
+                    "Index {} already exists on table {}.{}",
+                    plan.index_name, plan.database, plan.table
+                )))
+            };
+        }
+
+        let schema = table.schema();
+        for column in &plan.columns {
+            check_inverted_index_column(schema.field_with_name(column)?)?;
+        }
+
+        let index = InvertedIndexMeta {
+            id: Uuid::new_v4().simple().to_string(),
+            name: plan.index_name.clone(),
+            columns: plan.columns.clone(),
+            tokenizer: plan.tokenizer.clone(),
+        };
+        let key = format!("{}{}", OPT_KEY_INVERTED_INDEX_PREFIX, plan.index_name);
+        let value = serde_json::to_string(&index)?;
+        let req = UpsertTableOptionReq::new(&table.get_table_info().ident, key, value);
+        catalog.upsert_table_option(req).await?;
+
+        // The postings of the blocks written before are built in background, the blocks are
+        // scanned in full until then.
+        let plan = plan.clone();
+        GlobalIORuntime::instance().spawn(async move {
+            if let Err(cause) = build_inverted_index(&plan, &index).await {
+                tracing::error!(
+                    "build inverted index {} of {}.{} error: {:?}",
+                    plan.index_name,
+                    plan.database,
+                    plan.table,
+                    cause
+                );
+            }
+        });
+
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+async fn build_inverted_index(
+    plan: &CreateInvertedIndexPlan,
+    index: &InvertedIndexMeta,
+) -> Result<()> {
+    let session = SessionManager::instance()
+        .create_session(SessionType::IndexBuilder)
+        .await?;
+    session.set_current_tenant(plan.tenant.clone());
+    let ctx = session.create_query_context().await?;
+
+    let table = ctx
+        .get_table(&plan.catalog, &plan.database, &plan.table)
+        .await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    fuse_table.do_build_inverted_index(ctx.clone(), index).await
+}
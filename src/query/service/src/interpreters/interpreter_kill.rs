@@ -32,11 +32,18 @@ impl KillInterpreter {
         Ok(KillInterpreter { ctx, plan })
     }
 
-    async fn execute_kill(&self, session_id: &String) -> Result<PipelineBuildResult> {
-        match self.ctx.get_session_by_id(session_id) {
+    async fn execute_kill(&self, id: &String) -> Result<PipelineBuildResult> {
+        let session = match self.ctx.get_session_by_id(id) {
+            Some(session) => Some(session),
+            // `KILL QUERY` also accepts the id of the query.
+            None if !self.plan.kill_connection => self.ctx.get_session_by_query_id(id),
+            None => None,
+        };
+
+        match session {
             None => Err(ErrorCode::UnknownSession(format!(
-                "Not found session id {}",
-                session_id
+                "Not found session or query id {}",
+                id
             ))),
             Some(kill_session) if self.plan.kill_connection => {
                 kill_session.force_kill_session();
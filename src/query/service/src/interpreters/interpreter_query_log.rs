@@ -22,12 +22,16 @@ use common_exception::Result;
 use common_storages_preludes::system::LogType;
 use common_storages_preludes::system::QueryLogElement;
 use common_storages_preludes::system::QueryLogQueue;
+use common_storages_preludes::system::SlowQueryLogElement;
+use common_storages_preludes::system::SlowQueryLogQueue;
 use common_tracing::QueryLogger;
 use serde_json;
 use tracing::error;
 use tracing::info;
 use tracing::subscriber;
 
+use crate::audit::AuditLogger;
+use crate::interpreters::QueryLogPersister;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
 
@@ -67,9 +71,23 @@ impl InterpreterQueryLog {
             });
         };
 
+        if let Some(persister) = QueryLogPersister::instance() {
+            persister.append(&event);
+        }
         QueryLogQueue::instance()?.append_data(event)
     }
 
+    fn write_slow_log(event: SlowQueryLogElement) -> Result<()> {
+        if let Some(logger) = QueryLogger::instance().get_slow_query_subscriber() {
+            let event_str = serde_json::to_string(&event)?;
+            subscriber::with_default(logger, || {
+                info!("{}", event_str);
+            });
+        };
+
+        SlowQueryLogQueue::instance()?.append_data(event)
+    }
+
     pub fn fail_to_start(ctx: Arc<QueryContext>, err: ErrorCode) {
         InterpreterQueryLog::log_start(&ctx, SystemTime::now(), Some(err))
             .unwrap_or_else(|e| error!("fail to write query_log {:?}", e));
@@ -112,7 +130,9 @@ impl InterpreterQueryLog {
         let result_rows = 0u64;
         let result_bytes = 0u64;
         let cpu_usage = ctx.get_settings().get_max_threads()? as u32;
-        let memory_usage = ctx.get_current_session().get_memory_usage() as u64;
+        let memory_usage = ctx.get_memory_usage().max(0) as u64;
+        let peak_memory_usage = ctx.get_peak_memory_usage().max(0) as u64;
+        let query_duration_ms = 0u64;
 
         // Client.
         let client_address = match ctx.get_client_address() {
@@ -168,6 +188,8 @@ impl InterpreterQueryLog {
             result_bytes,
             cpu_usage,
             memory_usage,
+            peak_memory_usage,
+            query_duration_ms,
             client_info: "".to_string(),
             client_address,
 
@@ -181,9 +203,11 @@ impl InterpreterQueryLog {
     }
 
     pub fn log_finish(ctx: &QueryContext, now: SystemTime, err: Option<ErrorCode>) -> Result<()> {
+        AuditLogger::log_query(ctx, err.as_ref());
+
         // User.
         let handler_type = ctx.get_current_session().get_type().to_string();
-        let tenant_id = ctx.get_config().query.tenant_id;
+        let tenant_id = ctx.get_tenant();
         let cluster_id = ctx.get_config().query.cluster_id;
         let user = ctx.get_current_user()?;
         let sql_user = user.name;
@@ -216,7 +240,12 @@ impl InterpreterQueryLog {
         let scan_partitions = dal_metrics.get_partitions_scanned();
         let total_partitions = dal_metrics.get_partitions_total();
         let cpu_usage = ctx.get_settings().get_max_threads()? as u32;
-        let memory_usage = ctx.get_current_session().get_memory_usage() as u64;
+        let memory_usage = ctx.get_memory_usage().max(0) as u64;
+        let peak_memory_usage = ctx.get_peak_memory_usage().max(0) as u64;
+        let query_duration_ms = now
+            .duration_since(ctx.get_created_time())
+            .unwrap_or_default()
+            .as_millis() as u64;
 
         // Result.
         let result_rows = ctx.get_result_progress_value().rows as u64;
@@ -247,7 +276,7 @@ impl InterpreterQueryLog {
         let (log_type, exception_code, exception_text, stack_trace) =
             error_fields(LogType::Finish, err);
 
-        Self::write_log(QueryLogElement {
+        let event = QueryLogElement {
             log_type,
             handler_type,
             tenant_id,
@@ -278,6 +307,8 @@ impl InterpreterQueryLog {
             result_bytes,
             cpu_usage,
             memory_usage,
+            peak_memory_usage,
+            query_duration_ms,
             client_info: "".to_string(),
             client_address,
             current_database,
@@ -288,6 +319,12 @@ impl InterpreterQueryLog {
             server_version: "".to_string(),
             session_settings,
             extra: "".to_string(),
-        })
+        };
+
+        let slow_query_threshold_ms = ctx.get_config().query.slow_query_threshold_ms;
+        if slow_query_threshold_ms > 0 && query_duration_ms >= slow_query_threshold_ms {
+            Self::write_slow_log(SlowQueryLogElement::create(&event, ctx.get_plan_summary()))?;
+        }
+        Self::write_log(event)
     }
 }
@@ -17,9 +17,13 @@ use std::sync::Arc;
 use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 use common_planner::MetadataRef;
+use tracing::warn;
 
 use super::plan_schedulers::schedule_query_v2;
+use crate::interpreters::query_result_cache::read_query_result;
+use crate::interpreters::query_result_cache::write_query_result_on_finished;
 use crate::interpreters::Interpreter;
+use crate::interpreters::QueryResultCacheKey;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
@@ -89,7 +93,23 @@ impl Interpreter for SelectInterpreterV2 {
     /// The QueryPipelineBuilder will use the optimized plan to generate a Pipeline
     #[tracing::instrument(level = "debug", name = "select_interpreter_v2_execute", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
-        let build_res = self.build_pipeline().await?;
+        let cache_key =
+            QueryResultCacheKey::try_create(&self.ctx, &self.metadata, &self.s_expr).await?;
+
+        if let Some(key) = &cache_key {
+            match read_query_result(key, &self.schema()).await {
+                Ok(Some(build_res)) => return Ok(build_res),
+                Ok(None) => {}
+                Err(cause) => warn!("failed to read cached query result: {:?}", cause),
+            }
+        }
+
+        let mut build_res = self.build_pipeline().await?;
+
+        if let Some(key) = cache_key {
+            write_query_result_on_finished(key, self.schema(), &mut build_res).await?;
+        }
+
         Ok(build_res)
     }
 }
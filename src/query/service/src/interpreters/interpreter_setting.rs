@@ -18,6 +18,7 @@ use chrono_tz::Tz;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_legacy_planners::SettingPlan;
+use common_users::UserApiProvider;
 
 use crate::interpreters::Interpreter;
 use crate::pipelines::PipelineBuildResult;
@@ -61,6 +62,21 @@ impl Interpreter for SettingInterpreter {
                     )?;
                     true
                 }
+                "network_policy" => {
+                    // check if the network policy exists
+                    let name = var.value.trim_matches(|c| c == '\'' || c == '\"');
+                    if !name.is_empty() {
+                        UserApiProvider::instance()
+                            .get_network_policy(&self.ctx.get_tenant(), name)
+                            .await?;
+                    }
+                    self.ctx.get_settings().set_settings(
+                        var.variable.clone(),
+                        name.to_string(),
+                        var.is_global,
+                    )?;
+                    true
+                }
                 _ => {
                     self.ctx.get_settings().set_settings(
                         var.variable.clone(),
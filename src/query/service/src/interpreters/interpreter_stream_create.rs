@@ -0,0 +1,96 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_meta_app::schema::CreateTableReq;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TableNameIdent;
+use common_planner::plans::CreateStreamPlan;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::StreamTable;
+use crate::storages::fuse::OPT_KEY_STREAM_OFFSET;
+use crate::storages::fuse::OPT_KEY_STREAM_SOURCE_TABLE_ID;
+use crate::storages::fuse::OPT_KEY_STREAM_SOURCE_TABLE_NAME;
+use crate::storages::fuse::STREAM_ENGINE;
+use crate::storages::Table;
+
+pub struct CreateStreamInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateStreamPlan,
+}
+
+impl CreateStreamInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateStreamPlan) -> Result<Self> {
+        Ok(CreateStreamInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateStreamInterpreter {
+    fn name(&self) -> &str {
+        "CreateStreamInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        let table = catalog
+            .get_table(&plan.tenant, &plan.table_database, &plan.table)
+            .await?;
+        // The changes are tracked by the snapshots of the fuse engine.
+        let source = FuseTable::try_from_table(table.as_ref())?;
+
+        // The stream starts from the current snapshot of the source table, the changes made
+        // before the stream is created are not tracked.
+        let mut options = BTreeMap::new();
+        options.insert(
+            OPT_KEY_STREAM_SOURCE_TABLE_ID.to_string(),
+            source.get_table_info().ident.table_id.to_string(),
+        );
+        options.insert(
+            OPT_KEY_STREAM_SOURCE_TABLE_NAME.to_string(),
+            format!("{}.{}", plan.table_database, plan.table),
+        );
+        if let Some(location) = source.snapshot_loc() {
+            options.insert(OPT_KEY_STREAM_OFFSET.to_string(), location);
+        }
+
+        let req = CreateTableReq {
+            if_not_exists: plan.if_not_exists,
+            name_ident: TableNameIdent {
+                tenant: plan.tenant.clone(),
+                db_name: plan.database.clone(),
+                table_name: plan.stream.clone(),
+            },
+            table_meta: TableMeta {
+                engine: STREAM_ENGINE.to_string(),
+                schema: Arc::new(StreamTable::schema_of_source(&source.schema())),
+                options,
+                comment: plan.comment.clone().unwrap_or_default(),
+                ..Default::default()
+            },
+        };
+        catalog.create_table(req).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
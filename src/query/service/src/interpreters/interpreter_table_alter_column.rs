@@ -0,0 +1,57 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planner::plans::AlterTableColumnPlan;
+
+use super::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct AlterTableColumnInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AlterTableColumnPlan,
+}
+
+impl AlterTableColumnInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: AlterTableColumnPlan) -> Result<Self> {
+        Ok(AlterTableColumnInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AlterTableColumnInterpreter {
+    fn name(&self) -> &str {
+        "AlterTableColumnInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let tenant = self.ctx.get_tenant();
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+
+        let table = catalog
+            .get_table(tenant.as_str(), &plan.database, &plan.table)
+            .await?;
+
+        table
+            .alter_table_column(self.ctx.clone(), plan.action.clone())
+            .await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::CreateTableReq;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TableNameIdent;
+use common_meta_app::schema::TableStatistics;
+use common_planner::plans::AttachTablePlan;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::sql::OPT_KEY_STORAGE_PREFIX;
+use crate::sql::OPT_KEY_TABLE_ATTACHED_READ_ONLY;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::FuseTable;
+
+pub struct AttachTableInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AttachTablePlan,
+}
+
+impl AttachTableInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: AttachTablePlan) -> Result<Self> {
+        Ok(AttachTableInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AttachTableInterpreter {
+    fn name(&self) -> &str {
+        "AttachTableInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        if plan.if_not_exists
+            && catalog
+                .exists_table(&plan.tenant, &plan.database, &plan.table)
+                .await?
+        {
+            return Ok(PipelineBuildResult::create());
+        }
+
+        // The schema and the statistics of the table are taken from the latest snapshot of the
+        // data, which is located by the hint file the fuse engine leaves on each commit.
+        let operator = self.ctx.get_storage_operator()?;
+        let location_generator =
+            TableMetaLocationGenerator::with_prefix(plan.storage_prefix.clone());
+        let snapshot_location = FuseTable::read_last_snapshot_hint(&operator, &location_generator)
+            .await?
+            .ok_or_else(|| {
+                ErrorCode::BadArguments(format!(
+                    "no fuse table is found under the storage prefix '{}'",
+                    plan.storage_prefix
+                ))
+            })?;
+        let reader = MetaReaders::table_snapshot_reader(self.ctx.clone());
+        let version = TableMetaLocationGenerator::snapshot_version(&snapshot_location);
+        let snapshot = reader
+            .read(snapshot_location.as_str(), None, version)
+            .await?;
+
+        let db = catalog.get_database(&plan.tenant, &plan.database).await?;
+        let mut options = BTreeMap::new();
+        options.insert(
+            OPT_KEY_DATABASE_ID.to_string(),
+            db.get_db_info().ident.db_id.to_string(),
+        );
+        options.insert(
+            OPT_KEY_STORAGE_PREFIX.to_string(),
+            plan.storage_prefix.clone(),
+        );
+        options.insert(OPT_KEY_SNAPSHOT_LOCATION.to_string(), snapshot_location);
+        if plan.read_only {
+            options.insert(
+                OPT_KEY_TABLE_ATTACHED_READ_ONLY.to_string(),
+                "T".to_string(),
+            );
+        }
+
+        let summary = &snapshot.summary;
+        let req = CreateTableReq {
+            if_not_exists: plan.if_not_exists,
+            name_ident: TableNameIdent {
+                tenant: plan.tenant.clone(),
+                db_name: plan.database.clone(),
+                table_name: plan.table.clone(),
+            },
+            table_meta: TableMeta {
+                engine: "FUSE".to_string(),
+                schema: Arc::new(snapshot.schema.clone()),
+                options,
+                statistics: TableStatistics {
+                    number_of_rows: summary.row_count,
+                    data_bytes: summary.uncompressed_byte_size,
+                    compressed_data_bytes: summary.compressed_byte_size,
+                    index_data_bytes: summary.index_size,
+                },
+                ..Default::default()
+            },
+        };
+        catalog.create_table(req).await?;
+
+        // Attached without `READ_ONLY`, the ownership of the data is transferred to the new
+        // table, the table wrote the data before becomes read-only.
+        if !plan.read_only {
+            let table = catalog
+                .get_table(&plan.tenant, &plan.database, &plan.table)
+                .await?;
+            let owner = FuseTable::data_owner_identity(&plan.tenant, table.get_table_info());
+            FuseTable::write_data_owner(&operator, &location_generator, owner).await?;
+        }
+
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_catalog::table::CompactTarget;
 use common_exception::Result;
 use common_planner::plans::OptimizeTableAction;
 use common_planner::plans::OptimizeTablePlan;
@@ -51,29 +52,29 @@ impl Interpreter for OptimizeTableInterpreter {
             .get_table(&plan.catalog, &plan.database, &plan.table)
             .await?;
 
-        let action = &plan.action;
-        let do_purge = matches!(
-            action,
-            OptimizeTableAction::Purge | OptimizeTableAction::All
-        );
-        let do_compact = matches!(
-            action,
-            OptimizeTableAction::Compact | OptimizeTableAction::All
-        );
+        let (compact_target, do_purge) = match plan.action {
+            OptimizeTableAction::All => (Some(CompactTarget::Blocks), true),
+            OptimizeTableAction::Purge => (None, true),
+            OptimizeTableAction::Compact(target) => (Some(target), false),
+        };
 
-        if do_compact {
+        if let Some(target) = compact_target {
             let mut pipeline = Pipeline::create();
-            let mutator = table.compact(ctx.clone(), &mut pipeline).await?;
+            let mutator = table.compact(ctx.clone(), target, &mut pipeline).await?;
 
             if let Some(mutator) = mutator {
-                let settings = ctx.get_settings();
-                pipeline.set_max_threads(settings.get_max_threads()? as usize);
-                let executor_settings = ExecutorSettings::try_create(&settings)?;
-                let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
+                // compacting segments does not rewrite any block, nothing to execute
+                if !pipeline.pipes.is_empty() {
+                    let settings = ctx.get_settings();
+                    pipeline.set_max_threads(settings.get_max_threads()? as usize);
+                    let executor_settings = ExecutorSettings::try_create(&ctx)?;
+                    let executor =
+                        PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
 
-                ctx.set_executor(Arc::downgrade(&executor.get_inner()));
-                executor.execute()?;
-                drop(executor);
+                    ctx.set_executor(Arc::downgrade(&executor.get_inner()));
+                    executor.execute()?;
+                    drop(executor);
+                }
 
                 mutator.try_commit(table.get_table_info()).await?;
             }
@@ -84,7 +84,7 @@ impl Interpreter for ReclusterTableInterpreter {
 
             pipeline.set_max_threads(settings.get_max_threads()? as usize);
 
-            let executor_settings = ExecutorSettings::try_create(&settings)?;
+            let executor_settings = ExecutorSettings::try_create(&ctx)?;
             let executor = PipelineCompleteExecutor::try_create(pipeline, executor_settings)?;
 
             ctx.set_executor(Arc::downgrade(&executor.get_inner()));
@@ -26,6 +26,10 @@ use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
 use crate::sql::executor::PhysicalScalar;
 use crate::sql::is_internal_opt_key;
+use crate::storages::stage::ExternalTable;
+use crate::storages::stage::OPT_KEY_FILE_FORMAT;
+use crate::storages::stage::OPT_KEY_LOCATION;
+use crate::storages::stage::OPT_KEY_PARTITION_COLUMNS;
 
 pub struct ShowCreateTableInterpreter {
     ctx: Arc<QueryContext>,
@@ -61,8 +65,12 @@ impl Interpreter for ShowCreateTableInterpreter {
         let schema = table.schema();
         let field_comments = table.field_comments();
         let n_fields = schema.fields().len();
+        let external_table = table.as_any().downcast_ref::<ExternalTable>();
 
-        let mut table_create_sql = format!("CREATE TABLE `{}` (\n", name);
+        let mut table_create_sql = match external_table {
+            Some(_) => format!("CREATE EXTERNAL TABLE `{}` (\n", name),
+            None => format!("CREATE TABLE `{}` (\n", name),
+        };
 
         // Append columns.
         {
@@ -105,7 +113,10 @@ impl Interpreter for ShowCreateTableInterpreter {
             table_create_sql.push_str(&columns_str);
         }
 
-        let table_engine = format!(") ENGINE={}", engine);
+        let table_engine = match external_table {
+            Some(external_table) => format!(") {}", external_table.external_options_sql()),
+            None => format!(") ENGINE={}", engine),
+        };
         table_create_sql.push_str(table_engine.as_str());
 
         let table_info = table.get_table_info();
@@ -118,6 +129,7 @@ impl Interpreter for ShowCreateTableInterpreter {
             opts.sort_by_key(|(k, _)| *k);
             opts.iter()
                 .filter(|(k, _)| !is_internal_opt_key(k))
+                .filter(|(k, _)| external_table.is_none() || !is_external_opt_key(k))
                 .map(|(k, v)| format!(" {}='{}'", k.to_uppercase(), v))
                 .collect::<Vec<_>>()
                 .join("")
@@ -133,3 +145,8 @@ impl Interpreter for ShowCreateTableInterpreter {
         PipelineBuildResult::from_blocks(vec![block])
     }
 }
+
+// The options of external tables, shown as the clauses of `CREATE EXTERNAL TABLE`.
+fn is_external_opt_key(key: &str) -> bool {
+    key == OPT_KEY_LOCATION || key == OPT_KEY_FILE_FORMAT || key == OPT_KEY_PARTITION_COLUMNS
+}
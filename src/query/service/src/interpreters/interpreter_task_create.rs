@@ -0,0 +1,60 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use common_exception::Result;
+use common_planner::plans::CreateTaskPlan;
+use common_users::UserApiProvider;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+#[derive(Debug)]
+pub struct CreateTaskInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateTaskPlan,
+}
+
+impl CreateTaskInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateTaskPlan) -> Result<Self> {
+        Ok(CreateTaskInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateTaskInterpreter {
+    fn name(&self) -> &str {
+        "CreateTaskInterpreter"
+    }
+
+    #[tracing::instrument(level = "debug", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = self.plan.clone();
+        let tenant = self.ctx.get_tenant();
+
+        // the task runs as its creator
+        let mut task = plan.task;
+        task.owner = self.ctx.get_current_user()?.identity();
+        task.created_on = Utc::now().timestamp() as u64;
+        UserApiProvider::instance()
+            .add_task(&tenant, task, plan.if_not_exists)
+            .await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
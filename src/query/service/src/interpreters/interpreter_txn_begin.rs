@@ -0,0 +1,44 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct BeginInterpreter {
+    ctx: Arc<QueryContext>,
+}
+
+impl BeginInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>) -> Result<Self> {
+        Ok(BeginInterpreter { ctx })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for BeginInterpreter {
+    fn name(&self) -> &str {
+        "BeginInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        self.ctx.get_txn_manager().lock().begin();
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
+use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_types::MatchSeq;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct CommitInterpreter {
+    ctx: Arc<QueryContext>,
+}
+
+impl CommitInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>) -> Result<Self> {
+        Ok(CommitInterpreter { ctx })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CommitInterpreter {
+    fn name(&self) -> &str {
+        "CommitInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        // the transaction is ended even if the commit fails, the changes are discarded then
+        let staged = self.ctx.get_txn_manager().lock().end();
+        if staged.is_empty() {
+            return Ok(PipelineBuildResult::create());
+        }
+
+        let catalog_name = staged[0].meta.catalog.clone();
+        if staged
+            .iter()
+            .any(|table| table.meta.catalog != catalog_name)
+        {
+            return Err(ErrorCode::UnImplement(
+                "A transaction changing the tables of several catalogs is not supported",
+            ));
+        }
+
+        // the commit fails if any of the tables is changed after the transaction reads it
+        let update_table_metas = staged
+            .into_iter()
+            .map(|table_info| UpdateTableMetaReq {
+                table_id: table_info.ident.table_id,
+                seq: MatchSeq::Exact(table_info.ident.seq),
+                new_table_meta: table_info.meta,
+            })
+            .collect();
+        let catalog = self.ctx.get_catalog(&catalog_name)?;
+        catalog
+            .update_multi_table_meta(UpdateMultiTableMetaReq { update_table_metas })
+            .await?;
+        Ok(PipelineBuildResult::create())
+    }
+}
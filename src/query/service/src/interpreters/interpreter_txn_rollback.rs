@@ -0,0 +1,45 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+
+use crate::interpreters::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct RollbackInterpreter {
+    ctx: Arc<QueryContext>,
+}
+
+impl RollbackInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>) -> Result<Self> {
+        Ok(RollbackInterpreter { ctx })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for RollbackInterpreter {
+    fn name(&self) -> &str {
+        "RollbackInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        // the snapshots written by the transaction are not referenced, they are purged by GC
+        let _ = self.ctx.get_txn_manager().lock().end();
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -45,6 +45,23 @@ impl Interpreter for AlterUserInterpreter {
     async fn execute2(&self) -> Result<PipelineBuildResult> {
         let plan = self.plan.clone();
         let tenant = self.ctx.get_tenant();
+        if let Some(name) = plan.user_option.as_ref().and_then(|o| o.network_policy()) {
+            UserApiProvider::instance()
+                .get_network_policy(&tenant, name)
+                .await?;
+        }
+        if let Some(name) = plan.user_option.as_ref().and_then(|o| o.workload_group()) {
+            UserApiProvider::instance()
+                .get_workload_group(&tenant, name)
+                .await?;
+        }
+        if let Some(option) = &plan.user_option {
+            for (name, value) in option.default_settings() {
+                self.ctx
+                    .get_settings()
+                    .check_setting_value(name, value.clone())?;
+            }
+        }
         if plan.auth_info.is_some() || plan.user_option.is_some() {
             UserApiProvider::instance()
                 .update_user(&tenant, plan.user, plan.auth_info, plan.user_option)
@@ -60,6 +60,17 @@ impl Interpreter for CreateUserInterpreter {
             quota: UserQuota::no_limit(),
             option: plan.user_option,
         };
+        if let Some(name) = user_info.option.network_policy() {
+            user_mgr.get_network_policy(&tenant, name).await?;
+        }
+        if let Some(name) = user_info.option.workload_group() {
+            user_mgr.get_workload_group(&tenant, name).await?;
+        }
+        for (name, value) in user_info.option.default_settings() {
+            self.ctx
+                .get_settings()
+                .check_setting_value(name, value.clone())?;
+        }
         user_mgr
             .add_user(&tenant, user_info, plan.if_not_exists)
             .await?;
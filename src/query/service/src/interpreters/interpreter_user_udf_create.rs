@@ -14,10 +14,12 @@
 
 use std::sync::Arc;
 
+use common_datavalues::TypeFactory;
 use common_exception::Result;
 use common_planner::plans::CreateUDFPlan;
 use common_users::UserApiProvider;
 
+use crate::api::UDFFlightClient;
 use crate::interpreters::Interpreter;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -46,6 +48,22 @@ impl Interpreter for CreateUserUDFInterpreter {
         let plan = self.plan.clone();
         let tenant = self.ctx.get_tenant();
         let udf = plan.udf;
+
+        // Make sure the handler is served with the declared types before saving the function.
+        if let Some(server) = &udf.server {
+            let arg_types = server
+                .arg_types
+                .iter()
+                .map(|arg_type| TypeFactory::instance().get(arg_type))
+                .collect::<Result<Vec<_>>>()?;
+            let return_type = TypeFactory::instance().get(&server.return_type)?;
+            let timeout = self.ctx.get_settings().get_udf_server_timeout()?;
+            let mut client = UDFFlightClient::connect(&server.address, timeout).await?;
+            client
+                .check_schema(&server.handler, &arg_types, &return_type)
+                .await?;
+        }
+
         let _ = UserApiProvider::instance()
             .add_udf(&tenant, udf, plan.if_not_exists)
             .await?;
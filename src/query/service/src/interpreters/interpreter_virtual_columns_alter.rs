@@ -0,0 +1,84 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_planner::plans::AlterVirtualColumnsPlan;
+use common_storages_util::table_option_keys::OPT_KEY_VIRTUAL_COLUMNS;
+
+use super::interpreter_virtual_columns_create::build_virtual_columns_meta;
+use super::interpreter_virtual_columns_create::spawn_build_virtual_columns;
+use super::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+
+pub struct AlterVirtualColumnsInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: AlterVirtualColumnsPlan,
+}
+
+impl AlterVirtualColumnsInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: AlterVirtualColumnsPlan) -> Result<Self> {
+        Ok(AlterVirtualColumnsInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for AlterVirtualColumnsInterpreter {
+    fn name(&self) -> &str {
+        "AlterVirtualColumnsInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        FuseTable::try_from_table(table.as_ref())?;
+
+        if !table.options().contains_key(OPT_KEY_VIRTUAL_COLUMNS) {
+            return Err(ErrorCode::BadArguments(format!(
+                "Virtual columns do not exist on table {}.{}",
+                plan.database, plan.table
+            )));
+        }
+
+        // The virtual columns are materialized again with a new id, the files of the replaced
+        // virtual columns are never read, and are left until the blocks are purged.
+        let virtual_columns = build_virtual_columns_meta(&table.schema(), &plan.virtual_columns)?;
+        let value = serde_json::to_string(&virtual_columns)?;
+        let req = UpsertTableOptionReq::new(
+            &table.get_table_info().ident,
+            OPT_KEY_VIRTUAL_COLUMNS,
+            value,
+        );
+        catalog.upsert_table_option(req).await?;
+
+        spawn_build_virtual_columns(
+            plan.tenant.clone(),
+            plan.catalog.clone(),
+            plan.database.clone(),
+            plan.table.clone(),
+            virtual_columns,
+        );
+
+        Ok(PipelineBuildResult::create())
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_base::base::GlobalIORuntime;
+use common_base::base::TrySpawn;
+use common_datavalues::DataSchema;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_planner::plans::CreateVirtualColumnsPlan;
+use common_storages_index::check_virtual_column_source;
+use common_storages_index::VirtualColumnMeta;
+use common_storages_index::VirtualColumnsMeta;
+use common_storages_index::VirtualPathElement;
+use common_storages_util::table_option_keys::OPT_KEY_VIRTUAL_COLUMNS;
+use uuid::Uuid;
+
+use super::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+
+pub struct CreateVirtualColumnsInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateVirtualColumnsPlan,
+}
+
+impl CreateVirtualColumnsInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateVirtualColumnsPlan) -> Result<Self> {
+        Ok(CreateVirtualColumnsInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateVirtualColumnsInterpreter {
+    fn name(&self) -> &str {
+        "CreateVirtualColumnsInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        // The virtual columns are maintained by the fuse engine only.
+        FuseTable::try_from_table(table.as_ref())?;
+
+        if table.options().contains_key(OPT_KEY_VIRTUAL_COLUMNS) {
+            return if plan.if_not_exists {
+                Ok(PipelineBuildResult::create())
+            } else {
+                Err(ErrorCode::BadArguments(format!(
+                    "Virtual columns already exist on table {}.{}",
+                    plan.database, plan.table
+                )))
+            };
+        }
+
+        let virtual_columns = build_virtual_columns_meta(&table.schema(), &plan.virtual_columns)?;
+        let value = serde_json::to_string(&virtual_columns)?;
+        let req = UpsertTableOptionReq::new(
+            &table.get_table_info().ident,
+            OPT_KEY_VIRTUAL_COLUMNS,
+            value,
+        );
+        catalog.upsert_table_option(req).await?;
+
+        spawn_build_virtual_columns(
+            plan.tenant.clone(),
+            plan.catalog.clone(),
+            plan.database.clone(),
+            plan.table.clone(),
+            virtual_columns,
+        );
+
+        Ok(PipelineBuildResult::create())
+    }
+}
+
+/// Checks the virtual columns of the plan, the files of them are identified by a new id.
+pub(crate) fn build_virtual_columns_meta(
+    schema: &DataSchema,
+    virtual_columns: &[(String, Vec<DataValue>)],
+) -> Result<VirtualColumnsMeta> {
+    let mut columns = Vec::with_capacity(virtual_columns.len());
+    let mut names = HashSet::with_capacity(virtual_columns.len());
+    for (source, path) in virtual_columns {
+        check_virtual_column_source(schema.field_with_name(source)?)?;
+        let path = path
+            .iter()
+            .map(|value| match value {
+                DataValue::String(key) => {
+                    Ok(VirtualPathElement::Key(String::from_utf8(key.clone())?))
+                }
+                DataValue::UInt64(index) => Ok(VirtualPathElement::Index(*index)),
+                other => Err(ErrorCode::LogicalError(format!(
+                    "Invalid path element {:?} of virtual column",
+                    other
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let column = VirtualColumnMeta {
+            source: source.clone(),
+            path,
+        };
+        if !names.insert(column.name()) {
+            return Err(ErrorCode::BadArguments(format!(
+                "Duplicate virtual column {}",
+                column.name()
+            )));
+        }
+        columns.push(column);
+    }
+
+    Ok(VirtualColumnsMeta {
+        id: Uuid::new_v4().simple().to_string(),
+        columns,
+    })
+}
+
+/// The virtual columns of the blocks written before are materialized in background, they are
+/// extracted from the source columns when read until then.
+pub(crate) fn spawn_build_virtual_columns(
+    tenant: String,
+    catalog: String,
+    database: String,
+    table: String,
+    virtual_columns: VirtualColumnsMeta,
+) {
+    GlobalIORuntime::instance().spawn(async move {
+        let result =
+            build_virtual_columns(&tenant, &catalog, &database, &table, &virtual_columns).await;
+        if let Err(cause) = result {
+            tracing::error!(
+                "build virtual columns {} of {}.{} error: {:?}",
+                virtual_columns.id,
+                database,
+                table,
+                cause
+            );
+        }
+    });
+}
+
+async fn build_virtual_columns(
+    tenant: &str,
+    catalog: &str,
+    database: &str,
+    table: &str,
+    virtual_columns: &VirtualColumnsMeta,
+) -> Result<()> {
+    let session = SessionManager::instance()
+        .create_session(SessionType::IndexBuilder)
+        .await?;
+    session.set_current_tenant(tenant.to_string());
+    let ctx = session.create_query_context().await?;
+
+    let table = ctx.get_table(catalog, database, table).await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    fuse_table
+        .do_build_virtual_columns(ctx.clone(), virtual_columns)
+        .await
+}
@@ -0,0 +1,77 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MatchSeq;
+use common_planner::plans::DropVirtualColumnsPlan;
+use common_storages_util::table_option_keys::OPT_KEY_VIRTUAL_COLUMNS;
+
+use super::Interpreter;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct DropVirtualColumnsInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: DropVirtualColumnsPlan,
+}
+
+impl DropVirtualColumnsInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: DropVirtualColumnsPlan) -> Result<Self> {
+        Ok(DropVirtualColumnsInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for DropVirtualColumnsInterpreter {
+    fn name(&self) -> &str {
+        "DropVirtualColumnsInterpreter"
+    }
+
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+
+        if !table.options().contains_key(OPT_KEY_VIRTUAL_COLUMNS) {
+            return if plan.if_exists {
+                Ok(PipelineBuildResult::create())
+            } else {
+                Err(ErrorCode::BadArguments(format!(
+                    "Virtual columns do not exist on table {}.{}",
+                    plan.database, plan.table
+                )))
+            };
+        }
+
+        // The files of the virtual columns are left until the blocks are purged, they are never
+        // read since re-created virtual columns have another id.
+        let ident = &table.get_table_info().ident;
+        let req = UpsertTableOptionReq {
+            table_id: ident.table_id,
+            seq: MatchSeq::Exact(ident.seq),
+            options: HashMap::from([(OPT_KEY_VIRTUAL_COLUMNS.to_string(), None)]),
+        };
+        catalog.upsert_table_option(req).await?;
+
+        Ok(PipelineBuildResult::create())
+    }
+}
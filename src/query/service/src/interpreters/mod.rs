@@ -13,9 +13,13 @@
 // limitations under the License.
 
 mod access;
-// mod async_insert_queue_v2;
+mod async_insert_queue_v2;
+mod auto_compaction;
 mod fragments;
 mod interpreter;
+mod interpreter_aggregating_index_create;
+mod interpreter_aggregating_index_drop;
+mod interpreter_bloom_index_create;
 mod interpreter_call;
 mod interpreter_cluster_key_alter;
 mod interpreter_cluster_key_drop;
@@ -30,9 +34,17 @@ mod interpreter_database_undrop;
 mod interpreter_delete;
 mod interpreter_explain_v2;
 mod interpreter_factory;
+mod interpreter_index_drop;
 mod interpreter_insert_v2;
+mod interpreter_inverted_index_create;
 mod interpreter_kill;
 mod interpreter_list;
+mod interpreter_masking_policy_create;
+mod interpreter_masking_policy_drop;
+mod interpreter_masking_policy_set_column;
+mod interpreter_masking_policy_unset_column;
+mod interpreter_network_policy_create;
+mod interpreter_network_policy_drop;
 mod interpreter_presign;
 mod interpreter_privilege_grant;
 mod interpreter_privilege_revoke;
@@ -41,6 +53,9 @@ mod interpreter_role_create;
 mod interpreter_role_drop;
 mod interpreter_role_grant;
 mod interpreter_role_revoke;
+mod interpreter_role_set;
+mod interpreter_row_access_policy_create;
+mod interpreter_row_access_policy_drop;
 mod interpreter_select_v2;
 mod interpreter_setting;
 mod interpreter_share_alter_tenants;
@@ -53,16 +68,27 @@ mod interpreter_share_show;
 mod interpreter_share_show_grant_tenants;
 mod interpreter_show_grants;
 mod interpreter_show_object_grant_privileges;
+mod interpreter_stream_create;
+mod interpreter_stream_drop;
+mod interpreter_table_alter_column;
+mod interpreter_table_analyze;
+mod interpreter_table_attach;
 mod interpreter_table_create_v2;
 mod interpreter_table_describe;
 mod interpreter_table_drop;
 mod interpreter_table_exists;
+mod interpreter_table_flashback;
 mod interpreter_table_optimize;
 mod interpreter_table_recluster;
 mod interpreter_table_rename;
 mod interpreter_table_show_create;
 mod interpreter_table_truncate;
 mod interpreter_table_undrop;
+mod interpreter_task_create;
+mod interpreter_task_drop;
+mod interpreter_txn_begin;
+mod interpreter_txn_commit;
+mod interpreter_txn_rollback;
 mod interpreter_use_database;
 mod interpreter_user_alter;
 mod interpreter_user_create;
@@ -76,15 +102,29 @@ mod interpreter_user_udf_drop;
 mod interpreter_view_alter;
 mod interpreter_view_create;
 mod interpreter_view_drop;
+mod interpreter_virtual_columns_alter;
+mod interpreter_virtual_columns_create;
+mod interpreter_virtual_columns_drop;
+mod interpreter_workload_group_create;
+mod interpreter_workload_group_drop;
 mod plan_schedulers;
+mod query_log_persister;
+mod query_result_cache;
 mod stream;
+mod task_scheduler;
 
 pub use access::ManagementModeAccess;
+pub use async_insert_queue_v2::AsyncInsertManager;
+pub use async_insert_queue_v2::InsertKey;
+pub use auto_compaction::AutoCompactionManager;
 pub use fragments::QueryFragmentAction;
 pub use fragments::QueryFragmentActions;
 pub use fragments::QueryFragmentsActions;
 pub use interpreter::Interpreter;
 pub use interpreter::InterpreterPtr;
+pub use interpreter_aggregating_index_create::CreateAggregatingIndexInterpreter;
+pub use interpreter_aggregating_index_drop::DropAggregatingIndexInterpreter;
+pub use interpreter_bloom_index_create::CreateBloomIndexInterpreter;
 pub use interpreter_call::CallInterpreter;
 pub use interpreter_cluster_key_alter::AlterTableClusterKeyInterpreter;
 pub use interpreter_cluster_key_drop::DropTableClusterKeyInterpreter;
@@ -101,9 +141,17 @@ pub use interpreter_database_undrop::UndropDatabaseInterpreter;
 pub use interpreter_delete::DeleteInterpreter;
 pub use interpreter_explain_v2::ExplainInterpreterV2;
 pub use interpreter_factory::InterpreterFactory;
+pub use interpreter_index_drop::DropIndexInterpreter;
 pub use interpreter_insert_v2::InsertInterpreterV2;
+pub use interpreter_inverted_index_create::CreateInvertedIndexInterpreter;
 pub use interpreter_kill::KillInterpreter;
 pub use interpreter_list::ListInterpreter;
+pub use interpreter_masking_policy_create::CreateMaskingPolicyInterpreter;
+pub use interpreter_masking_policy_drop::DropMaskingPolicyInterpreter;
+pub use interpreter_masking_policy_set_column::SetColumnMaskingPolicyInterpreter;
+pub use interpreter_masking_policy_unset_column::UnsetColumnMaskingPolicyInterpreter;
+pub use interpreter_network_policy_create::CreateNetworkPolicyInterpreter;
+pub use interpreter_network_policy_drop::DropNetworkPolicyInterpreter;
 pub use interpreter_privilege_grant::GrantPrivilegeInterpreter;
 pub use interpreter_privilege_revoke::RevokePrivilegeInterpreter;
 pub use interpreter_query_log::InterpreterQueryLog;
@@ -111,6 +159,9 @@ pub use interpreter_role_create::CreateRoleInterpreter;
 pub use interpreter_role_drop::DropRoleInterpreter;
 pub use interpreter_role_grant::GrantRoleInterpreter;
 pub use interpreter_role_revoke::RevokeRoleInterpreter;
+pub use interpreter_role_set::SetRoleInterpreter;
+pub use interpreter_row_access_policy_create::CreateRowAccessPolicyInterpreter;
+pub use interpreter_row_access_policy_drop::DropRowAccessPolicyInterpreter;
 pub use interpreter_select_v2::SelectInterpreterV2;
 pub use interpreter_setting::SettingInterpreter;
 pub use interpreter_share_alter_tenants::AlterShareTenantsInterpreter;
@@ -122,16 +173,27 @@ pub use interpreter_share_show::ShowSharesInterpreter;
 pub use interpreter_share_show_grant_tenants::ShowGrantTenantsOfShareInterpreter;
 pub use interpreter_show_grants::ShowGrantsInterpreter;
 pub use interpreter_show_object_grant_privileges::ShowObjectGrantPrivilegesInterpreter;
+pub use interpreter_stream_create::CreateStreamInterpreter;
+pub use interpreter_stream_drop::DropStreamInterpreter;
+pub use interpreter_table_alter_column::AlterTableColumnInterpreter;
+pub use interpreter_table_analyze::AnalyzeTableInterpreter;
+pub use interpreter_table_attach::AttachTableInterpreter;
 pub use interpreter_table_create_v2::CreateTableInterpreterV2;
 pub use interpreter_table_describe::DescribeTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
 pub use interpreter_table_exists::ExistsTableInterpreter;
+pub use interpreter_table_flashback::FlashbackTableInterpreter;
 pub use interpreter_table_optimize::OptimizeTableInterpreter;
 pub use interpreter_table_recluster::ReclusterTableInterpreter;
 pub use interpreter_table_rename::RenameTableInterpreter;
 pub use interpreter_table_show_create::ShowCreateTableInterpreter;
 pub use interpreter_table_truncate::TruncateTableInterpreter;
 pub use interpreter_table_undrop::UndropTableInterpreter;
+pub use interpreter_task_create::CreateTaskInterpreter;
+pub use interpreter_task_drop::DropTaskInterpreter;
+pub use interpreter_txn_begin::BeginInterpreter;
+pub use interpreter_txn_commit::CommitInterpreter;
+pub use interpreter_txn_rollback::RollbackInterpreter;
 pub use interpreter_use_database::UseDatabaseInterpreter;
 pub use interpreter_user_alter::AlterUserInterpreter;
 pub use interpreter_user_create::CreateUserInterpreter;
@@ -145,4 +207,16 @@ pub use interpreter_user_udf_drop::DropUserUDFInterpreter;
 pub use interpreter_view_alter::AlterViewInterpreter;
 pub use interpreter_view_create::CreateViewInterpreter;
 pub use interpreter_view_drop::DropViewInterpreter;
+pub use interpreter_virtual_columns_alter::AlterVirtualColumnsInterpreter;
+pub use interpreter_virtual_columns_create::CreateVirtualColumnsInterpreter;
+pub use interpreter_virtual_columns_drop::DropVirtualColumnsInterpreter;
+pub use interpreter_workload_group_create::CreateWorkloadGroupInterpreter;
+pub use interpreter_workload_group_drop::DropWorkloadGroupInterpreter;
+pub use query_log_persister::QueryLogPersister;
+pub use query_log_persister::QUERY_LOG_DATABASE;
+pub use query_log_persister::QUERY_LOG_TABLE;
+pub use query_result_cache::is_deterministic_plan;
+pub use query_result_cache::normalize_statement;
+pub use query_result_cache::QueryResultCacheKey;
 pub use stream::PullingExecutorStream;
+pub use task_scheduler::TaskScheduler;
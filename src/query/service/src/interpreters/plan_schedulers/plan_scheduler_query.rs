@@ -14,9 +14,14 @@
 
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
+use tracing::warn;
 
+use crate::clusters::ClusterDiscovery;
+use crate::clusters::ClusterHelper;
 use crate::interpreters::fragments::Fragmenter;
+use crate::interpreters::fragments::PlanFragment;
 use crate::interpreters::fragments::QueryFragmentsActions;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -31,6 +36,28 @@ pub async fn schedule_query_v2(
     plan: &PhysicalPlan,
 ) -> Result<PipelineBuildResult> {
     if !plan.is_distributed_plan() {
+        if let Some(executor) = choose_delegate_executor(&ctx, plan)? {
+            let fragmenter = Fragmenter::try_create(ctx.clone())?;
+            let root_fragment = fragmenter.build_delegated_fragment(plan, executor)?;
+            match commit_fragments(ctx.clone(), root_fragment).await {
+                Ok(mut build_res) => {
+                    PipelineBuilderV2::render_result_set(
+                        plan.output_schema()?,
+                        result_columns,
+                        &mut build_res.main_pipeline,
+                    )?;
+                    return Ok(build_res);
+                }
+                // The query is run locally if the node is lost in the meantime.
+                Err(cause) if cause.code() == ErrorCode::ClusterNodeLostCode() => warn!(
+                    "Run query {} locally, cause: {}",
+                    ctx.get_id(),
+                    cause.message()
+                ),
+                Err(cause) => return Err(cause),
+            }
+        }
+
         let pb = PipelineBuilderV2::create(ctx.clone());
         let mut build_res = pb.finalize(plan)?;
         PipelineBuilderV2::render_result_set(
@@ -57,10 +84,45 @@ pub async fn schedule_query_v2(
 pub async fn build_schedule_pipeline(
     ctx: Arc<QueryContext>,
     plan: &PhysicalPlan,
+) -> Result<PipelineBuildResult> {
+    // The nodes found lost while committing the fragments are left out of the cluster of the
+    // query, their fragments are scheduled again on the rest of the nodes.
+    let max_retries = match plan.is_side_effect_free() {
+        true => ctx.get_settings().get_max_node_failure_retries()?,
+        false => 0,
+    };
+
+    let mut retries = 0;
+    loop {
+        match try_build_schedule_pipeline(ctx.clone(), plan).await {
+            Err(cause)
+                if cause.code() == ErrorCode::ClusterNodeLostCode() && retries < max_retries =>
+            {
+                retries += 1;
+                warn!(
+                    "Reschedule query {} on the rest of the cluster, cause: {}",
+                    ctx.get_id(),
+                    cause.message()
+                );
+            }
+            res => return res,
+        }
+    }
+}
+
+async fn try_build_schedule_pipeline(
+    ctx: Arc<QueryContext>,
+    plan: &PhysicalPlan,
 ) -> Result<PipelineBuildResult> {
     let fragmenter = Fragmenter::try_create(ctx.clone())?;
     let root_fragment = fragmenter.build_fragment(plan)?;
+    commit_fragments(ctx, root_fragment).await
+}
 
+async fn commit_fragments(
+    ctx: Arc<QueryContext>,
+    root_fragment: PlanFragment,
+) -> Result<PipelineBuildResult> {
     let mut fragments_actions = QueryFragmentsActions::create(ctx.clone());
     root_fragment.get_actions(ctx.clone(), &mut fragments_actions)?;
 
@@ -74,3 +136,74 @@ pub async fn build_schedule_pipeline(
     build_res.set_max_threads(settings.get_max_threads()? as usize);
     Ok(build_res)
 }
+
+/// The node to run a local plan on, if routing is enabled and a remote node would be less loaded
+/// than the current one after taking the query. The load of a node is its running queries per CPU,
+/// as reported by its heartbeats.
+fn choose_delegate_executor(
+    ctx: &Arc<QueryContext>,
+    plan: &PhysicalPlan,
+) -> Result<Option<String>> {
+    if !ctx.get_settings().get_enable_query_routing()? {
+        return Ok(None);
+    }
+
+    let cluster = ctx.get_cluster();
+    if cluster.is_empty() || !is_delegable(ctx, plan)? {
+        return Ok(None);
+    }
+
+    let load_per_cpu =
+        |running_queries: u64, cpu_nums: u64| running_queries as f64 / cpu_nums.max(1) as f64;
+
+    let nodes = cluster.get_nodes();
+    // The running queries of the current node count the query itself.
+    let local_load = match nodes.iter().find(|node| cluster.is_local(node)) {
+        None => return Ok(None),
+        Some(node) => load_per_cpu(ClusterDiscovery::instance().local_load(), node.cpu_nums),
+    };
+
+    let least_loaded = nodes
+        .iter()
+        .filter(|node| !cluster.is_local(node))
+        .map(|node| {
+            let load = load_per_cpu(node.running_queries_count + 1, node.cpu_nums);
+            (load, node)
+        })
+        .min_by(|(left, _), (right, _)| left.total_cmp(right));
+
+    match least_loaded {
+        Some((load, node)) if load < local_load => Ok(Some(node.id.clone())),
+        _ => Ok(None),
+    }
+}
+
+// Only the plans reading the tables shared by the cluster can run on the other nodes.
+fn is_delegable(ctx: &Arc<QueryContext>, plan: &PhysicalPlan) -> Result<bool> {
+    let mut sources = vec![];
+    let mut evaluated_locally = false;
+    PhysicalPlan::traverse(
+        plan,
+        &mut |_| true,
+        &mut |plan| match plan {
+            PhysicalPlan::TableScan(scan) => sources.push(scan.source.clone()),
+            PhysicalPlan::RecursiveUnion(_) | PhysicalPlan::RecursiveCteScan(_) => {
+                evaluated_locally = true
+            }
+            _ => {}
+        },
+        &mut |_| {},
+    );
+
+    if sources.is_empty() || evaluated_locally {
+        return Ok(false);
+    }
+
+    for source in sources {
+        if ctx.build_table_from_source_plan(&source)?.is_local() {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,293 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::Duration as ChronoDuration;
+use chrono::Utc;
+use common_base::base::tokio;
+use common_base::base::tokio::time::Duration;
+use common_base::base::GlobalIORuntime;
+use common_base::base::TrySpawn;
+use common_catalog::catalog::CATALOG_DEFAULT;
+use common_datablocks::DataBlock;
+use common_datavalues::DataType;
+use common_datavalues::MutableColumn;
+use common_exception::Result;
+use common_meta_app::schema::CreateDatabaseReq;
+use common_meta_app::schema::CreateTableReq;
+use common_meta_app::schema::DatabaseMeta;
+use common_meta_app::schema::DatabaseNameIdent;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TableNameIdent;
+use common_storages_preludes::system::QueryLogElement;
+use common_storages_preludes::system::SystemLogElement;
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+
+use super::InsertInterpreterV2;
+use super::Interpreter;
+use super::InterpreterFactory;
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelineCompleteExecutor;
+use crate::sessions::QueryContext;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+use crate::sql::plans::insert::Insert;
+use crate::sql::plans::insert::InsertInputSource;
+use crate::sql::Planner;
+use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::Config;
+
+/// The database of the persistent query logs, created in every tenant.
+pub const QUERY_LOG_DATABASE: &str = "system_history";
+pub const QUERY_LOG_TABLE: &str = "query_log";
+
+/// The interval of deleting the query logs older than the retention.
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Persists the query logs into the FUSE table `system_history.query_log` of the tenants, which
+/// is read by `system.query_log`, so that the logs survive the restarts of the query nodes.
+///
+/// The logs are buffered in memory and appended to the table every `query_log_flush_interval`
+/// seconds. If the appends fail, at most `max_query_log_size` logs are kept, the oldest ones are
+/// dropped. The logs older than `query_log_retention_days` are deleted every hour.
+pub struct QueryLogPersister {
+    retention_days: u64,
+    max_pending: usize,
+    pending: Mutex<VecDeque<QueryLogElement>>,
+    // the tenants whose table of the query logs is created
+    created: Mutex<HashSet<String>>,
+    last_purge: Mutex<Instant>,
+}
+
+static QUERY_LOG_PERSISTER: OnceCell<Arc<QueryLogPersister>> = OnceCell::new();
+
+impl QueryLogPersister {
+    pub fn init(config: &Config) -> Result<()> {
+        if !config.query.enable_persistent_query_log {
+            return Ok(());
+        }
+
+        let persister = Arc::new(QueryLogPersister {
+            retention_days: config.query.query_log_retention_days,
+            max_pending: config.query.max_query_log_size.max(1),
+            pending: Mutex::new(VecDeque::new()),
+            created: Mutex::new(HashSet::new()),
+            last_purge: Mutex::new(Instant::now()),
+        });
+
+        let interval = Duration::from_secs(config.query.query_log_flush_interval.max(1));
+        let flusher = persister.clone();
+        GlobalIORuntime::instance().spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                flusher.flush().await;
+            }
+        });
+
+        QUERY_LOG_PERSISTER.set(persister).ok();
+        Ok(())
+    }
+
+    /// Returns `None` if the query logs are not persisted.
+    pub fn instance() -> Option<Arc<QueryLogPersister>> {
+        QUERY_LOG_PERSISTER.get().cloned()
+    }
+
+    pub fn append(&self, event: &QueryLogElement) {
+        let mut pending = self.pending.lock();
+        pending.push_back(event.clone());
+        while pending.len() > self.max_pending {
+            pending.pop_front();
+        }
+    }
+
+    /// Puts back the logs failed to append, before the logs appended since.
+    fn requeue(&self, events: Vec<QueryLogElement>) {
+        let mut pending = self.pending.lock();
+        for event in events.into_iter().rev() {
+            pending.push_front(event);
+        }
+        while pending.len() > self.max_pending {
+            pending.pop_front();
+        }
+    }
+
+    async fn flush(&self) {
+        let events = std::mem::take(&mut *self.pending.lock());
+        let mut tenant_events: BTreeMap<String, Vec<QueryLogElement>> = BTreeMap::new();
+        for event in events {
+            tenant_events
+                .entry(event.tenant_id.clone())
+                .or_default()
+                .push(event);
+        }
+
+        for (tenant, events) in tenant_events {
+            if let Err(cause) = self.persist(&tenant, &events).await {
+                tracing::error!(
+                    "persist {} query logs of tenant {} error: {:?}",
+                    events.len(),
+                    tenant,
+                    cause
+                );
+                self.requeue(events);
+            }
+        }
+
+        let purge = {
+            let mut last_purge = self.last_purge.lock();
+            let purge = self.retention_days > 0 && last_purge.elapsed() >= PURGE_INTERVAL;
+            if purge {
+                *last_purge = Instant::now();
+            }
+            purge
+        };
+        if purge {
+            let tenants = self.created.lock().clone();
+            for tenant in tenants {
+                if let Err(cause) = self.purge(&tenant).await {
+                    tracing::error!("purge query logs of tenant {} error: {:?}", tenant, cause);
+                }
+            }
+        }
+    }
+
+    async fn persist(&self, tenant: &str, events: &[QueryLogElement]) -> Result<()> {
+        let ctx = Self::create_context(tenant).await?;
+        self.create_table(&ctx, tenant).await?;
+
+        let schema = QueryLogElement::schema();
+        let mut columns: Vec<Box<dyn MutableColumn>> = schema
+            .fields()
+            .iter()
+            .map(|field| field.data_type().create_mutable(events.len()))
+            .collect();
+        for event in events {
+            event.fill_to_data_block(&mut columns)?;
+        }
+        let columns = columns
+            .iter_mut()
+            .map(|column| column.to_column())
+            .collect();
+        let block = DataBlock::create(schema.clone(), columns);
+
+        let table = ctx
+            .get_table(CATALOG_DEFAULT, QUERY_LOG_DATABASE, QUERY_LOG_TABLE)
+            .await?;
+        let plan = Insert {
+            catalog: CATALOG_DEFAULT.to_string(),
+            database: QUERY_LOG_DATABASE.to_string(),
+            table: QUERY_LOG_TABLE.to_string(),
+            table_id: table.get_id(),
+            schema,
+            overwrite: false,
+            source: InsertInputSource::Blocks(vec![block]),
+        };
+        let interpreter = InsertInterpreterV2::try_create(ctx.clone(), plan, false)?;
+        Self::execute(&ctx, interpreter.as_ref()).await
+    }
+
+    async fn create_table(&self, ctx: &QueryContext, tenant: &str) -> Result<()> {
+        if self.created.lock().contains(tenant) {
+            return Ok(());
+        }
+
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+        catalog
+            .create_database(CreateDatabaseReq {
+                if_not_exists: true,
+                name_ident: DatabaseNameIdent {
+                    tenant: tenant.to_string(),
+                    db_name: QUERY_LOG_DATABASE.to_string(),
+                },
+                meta: DatabaseMeta {
+                    engine: "".to_string(),
+                    ..Default::default()
+                },
+            })
+            .await?;
+
+        let database = catalog.get_database(tenant, QUERY_LOG_DATABASE).await?;
+        let mut table_meta = TableMeta {
+            schema: QueryLogElement::schema(),
+            engine: "FUSE".to_string(),
+            ..Default::default()
+        };
+        table_meta.options.insert(
+            OPT_KEY_DATABASE_ID.to_string(),
+            database.get_db_info().ident.db_id.to_string(),
+        );
+        catalog
+            .create_table(CreateTableReq {
+                if_not_exists: true,
+                name_ident: TableNameIdent {
+                    tenant: tenant.to_string(),
+                    db_name: QUERY_LOG_DATABASE.to_string(),
+                    table_name: QUERY_LOG_TABLE.to_string(),
+                },
+                table_meta,
+            })
+            .await?;
+
+        self.created.lock().insert(tenant.to_string());
+        Ok(())
+    }
+
+    /// Deletes the query logs older than the retention.
+    async fn purge(&self, tenant: &str) -> Result<()> {
+        let ctx = Self::create_context(tenant).await?;
+        let retention = ChronoDuration::days(self.retention_days as i64);
+        let expire_date = Utc::now().naive_utc().date() - retention;
+        let sql = format!(
+            "DELETE FROM {}.{} WHERE event_date < '{}'",
+            QUERY_LOG_DATABASE,
+            QUERY_LOG_TABLE,
+            expire_date.format("%Y-%m-%d")
+        );
+        let mut planner = Planner::new(ctx.clone());
+        let (plan, _, _) = planner.plan_sql(&sql).await?;
+        let interpreter = InterpreterFactory::get(ctx.clone(), &plan).await?;
+        Self::execute(&ctx, interpreter.as_ref()).await
+    }
+
+    async fn create_context(tenant: &str) -> Result<Arc<QueryContext>> {
+        let session = SessionManager::instance()
+            .create_session(SessionType::QueryLogPersister)
+            .await?;
+        session.set_current_tenant(tenant.to_string());
+        session.create_query_context().await
+    }
+
+    /// Executes the interpreter without `Interpreter::execute`, so that the appends and the
+    /// deletions of the query logs are not logged themselves.
+    async fn execute(ctx: &Arc<QueryContext>, interpreter: &dyn Interpreter) -> Result<()> {
+        let mut build_res = interpreter.execute2().await?;
+        if build_res.main_pipeline.pipes.is_empty() {
+            return Ok(());
+        }
+
+        build_res.set_max_threads(1);
+        let settings = ExecutorSettings::try_create(ctx)?;
+        let mut pipelines = build_res.sources_pipelines;
+        pipelines.push(build_res.main_pipeline);
+        PipelineCompleteExecutor::from_pipelines(pipelines, settings)?.execute()
+    }
+}
@@ -0,0 +1,411 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Cursor;
+use std::io::Read;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::ipc::read::read_file_metadata;
+use common_arrow::arrow::io::ipc::read::FileReader;
+use common_arrow::arrow::io::ipc::write::FileWriter;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_ast::parser::token::TokenKind;
+use common_ast::parser::tokenize_sql;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
+use common_fuse_meta::caches::CacheManager;
+use common_fuse_meta::caches::QueryResultCache;
+use common_planner::MetadataRef;
+use common_storages_util::table_option_keys::OPT_KEY_SNAPSHOT_LOCATION;
+use common_users::UserApiProvider;
+use sha2::Digest;
+use sha2::Sha256;
+use tracing::debug;
+use tracing::warn;
+
+use crate::pipelines::processors::CollectedBlocks;
+use crate::pipelines::processors::TransformCollect;
+use crate::pipelines::PipelineBuildResult;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+
+/// Length of the hex encoded digest of base tables, stored at the head of a cached result.
+const TABLES_DIGEST_LEN: usize = 64;
+
+/// Identifies a cached result set of a query.
+///
+/// `name` is derived from the normalized statement, the session settings, the current user, roles and database,
+/// and is the name of the cache file. The binder turns `current_user()` and `database()` into constants,
+/// so a result is only shared by the sessions that would bind the query in the same way.
+/// `tables` is derived from the id, version and snapshot of every base table, and is stored in the cache file.
+/// When a base table changes, `tables` no longer matches and the cached result is removed on the next lookup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryResultCacheKey {
+    pub name: String,
+    pub tables: String,
+}
+
+impl QueryResultCacheKey {
+    /// Build the cache key for a query, or returns `None` if the result of the query should not be cached.
+    ///
+    /// A query is cached only when:
+    /// - `enable_query_result_cache` is set;
+    /// - all expressions in the plan are deterministic;
+    /// - it reads at least one table and all tables are fuse tables;
+    /// - no table has a row access policy or a masking policy, the result depends on the roles;
+    /// - it does not call `connection_id()`, the result would only be valid for the connection.
+    pub async fn try_create(
+        ctx: &QueryContext,
+        metadata: &MetadataRef,
+        s_expr: &SExpr,
+    ) -> Result<Option<Self>> {
+        let settings = ctx.get_settings();
+        if !settings.get_enable_query_result_cache()? {
+            return Ok(None);
+        }
+
+        if !is_deterministic_plan(s_expr) {
+            return Ok(None);
+        }
+
+        let tenant = ctx.get_tenant();
+        let user_api = UserApiProvider::instance();
+        let row_access_policies = user_api.get_row_access_policies(&tenant).await?;
+        let masking_policies = user_api.get_masking_policies(&tenant).await?;
+
+        let mut tables = Sha256::new();
+        {
+            let metadata = metadata.read();
+            if metadata.tables().is_empty() {
+                return Ok(None);
+            }
+
+            for entry in metadata.tables() {
+                let (catalog, database, name) = (entry.catalog(), entry.database(), entry.name());
+                let has_row_access_policy = row_access_policies
+                    .iter()
+                    .any(|p| p.catalog == catalog && p.database == database && p.table == name);
+                let has_masking_policy = masking_policies
+                    .iter()
+                    .any(|p| p.is_attached_to(catalog, database, name));
+                if has_row_access_policy || has_masking_policy {
+                    return Ok(None);
+                }
+
+                let table = entry.table();
+                if table.engine() != "FUSE" {
+                    return Ok(None);
+                }
+
+                let info = table.get_table_info();
+                let snapshot = info.options().get(OPT_KEY_SNAPSHOT_LOCATION);
+                tables.update(format!(
+                    "{}.{}.{}:{}:{}:{:?};",
+                    entry.catalog(),
+                    entry.database(),
+                    entry.name(),
+                    info.ident.table_id,
+                    info.ident.seq,
+                    snapshot
+                ));
+            }
+        }
+
+        let sql = ctx.get_query_str();
+        if calls_connection_id(&sql) {
+            return Ok(None);
+        }
+
+        let statement = match normalize_statement(&sql) {
+            None => return Ok(None),
+            Some(s) => s,
+        };
+
+        let session = ctx.get_current_session();
+        let user = ctx.get_current_user()?;
+        let mut roles = match session.get_current_role() {
+            Some(role) => vec![role],
+            None => session.get_all_roles()?,
+        };
+        roles.sort();
+
+        let mut name = Sha256::new();
+        name.update(tenant);
+        name.update(";");
+        name.update(user.identity().to_string());
+        name.update(";");
+        name.update(roles.join(","));
+        name.update(";");
+        name.update(ctx.get_current_database());
+        name.update(";");
+        name.update(statement);
+        for (k, v) in settings.get_setting_values_short() {
+            name.update(format!(";{}={}", k, v));
+        }
+
+        Ok(Some(QueryResultCacheKey {
+            name: format!("{:x}", name.finalize()),
+            tables: format!("{:x}", tables.finalize()),
+        }))
+    }
+}
+
+/// Returns the cached result of a query if there is one and it is still valid.
+pub async fn read_query_result(
+    key: &QueryResultCacheKey,
+    schema: &DataSchemaRef,
+) -> Result<Option<PipelineBuildResult>> {
+    let cache = match CacheManager::instance().get_query_result_cache() {
+        None => return Ok(None),
+        Some(cache) => cache,
+    };
+
+    let mut cache = cache.write().await;
+    if !cache.contains_key(&key.name) {
+        return Ok(None);
+    }
+
+    let mut bytes = vec![];
+    cache.get(&key.name)?.read_to_end(&mut bytes)?;
+
+    if bytes.len() < TABLES_DIGEST_LEN || &bytes[..TABLES_DIGEST_LEN] != key.tables.as_bytes() {
+        debug!(
+            "base tables changed, remove cached query result: {}",
+            key.name
+        );
+        cache.remove(&key.name)?;
+        return Ok(None);
+    }
+
+    let blocks = deserialize_blocks(schema, &bytes[TABLES_DIGEST_LEN..])?;
+    let block = if blocks.is_empty() {
+        DataBlock::empty_with_schema(schema.clone())
+    } else {
+        DataBlock::concat_blocks(&blocks)?
+    };
+
+    debug!("hit cached query result: {}", key.name);
+    Ok(Some(PipelineBuildResult::from_blocks(vec![block])?))
+}
+
+/// Collects the output of a query and stores it in the cache when the query finishes successfully.
+///
+/// The output is collected up to the capacity of the cache, a larger result is not cached.
+pub async fn write_query_result_on_finished(
+    key: QueryResultCacheKey,
+    schema: DataSchemaRef,
+    build_res: &mut PipelineBuildResult,
+) -> Result<()> {
+    let cache = match CacheManager::instance().get_query_result_cache() {
+        None => return Ok(()),
+        Some(cache) => cache,
+    };
+
+    let capacity = cache.read().await.capacity();
+    let blocks = Arc::new(Mutex::new(CollectedBlocks::new(capacity as usize)));
+    {
+        let blocks = blocks.clone();
+        build_res
+            .main_pipeline
+            .add_transform(move |input, output| {
+                TransformCollect::try_create(input, output, blocks.clone())
+            })?;
+    }
+
+    build_res.main_pipeline.set_on_finished(move |may_error| {
+        if may_error.is_none() {
+            if let Some(blocks) = blocks.lock().unwrap().take() {
+                if let Err(cause) = write_query_result(&cache, &key, &schema, &blocks) {
+                    warn!("failed to cache query result: {}, {:?}", key.name, cause);
+                }
+            }
+        }
+        Ok(())
+    });
+
+    Ok(())
+}
+
+/// Store a result set. It is skipped if the cache is being accessed by another query or the result is too large.
+fn write_query_result(
+    cache: &QueryResultCache,
+    key: &QueryResultCacheKey,
+    schema: &DataSchemaRef,
+    blocks: &[DataBlock],
+) -> Result<()> {
+    let mut bytes = key.tables.as_bytes().to_vec();
+    serialize_blocks(schema, blocks, &mut bytes)?;
+
+    let mut cache = match cache.try_write() {
+        Err(_) => return Ok(()),
+        Ok(cache) => cache,
+    };
+
+    if !cache.can_store(bytes.len() as u64) {
+        return Ok(());
+    }
+
+    cache.remove(&key.name)?;
+    cache.insert_bytes(&key.name, &bytes)?;
+    Ok(())
+}
+
+fn serialize_blocks(schema: &DataSchemaRef, blocks: &[DataBlock], buf: &mut Vec<u8>) -> Result<()> {
+    let options = WriteOptions { compression: None };
+    let mut writer = FileWriter::new(buf, schema.to_arrow(), None, options);
+    writer.start()?;
+    for block in blocks {
+        let chunk = Chunk::try_from(block.clone())?;
+        writer.write(&chunk, None)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn deserialize_blocks(schema: &DataSchemaRef, bytes: &[u8]) -> Result<Vec<DataBlock>> {
+    let mut cursor = Cursor::new(bytes);
+    let metadata = read_file_metadata(&mut cursor)?;
+    let reader = FileReader::new(cursor, metadata, None, None);
+
+    let mut blocks = vec![];
+    for chunk in reader {
+        blocks.push(DataBlock::from_chunk(schema, &chunk?)?);
+    }
+    Ok(blocks)
+}
+
+/// Normalize a statement by joining its tokens with a single space,
+/// so that queries differ only in whitespace share the same cached result.
+pub fn normalize_statement(sql: &str) -> Option<String> {
+    let tokens = tokenize_sql(sql).ok()?;
+    let texts = tokens
+        .iter()
+        .filter(|t| t.kind != TokenKind::EOI && t.kind != TokenKind::SemiColon)
+        .map(|t| t.text())
+        .collect::<Vec<_>>();
+    Some(texts.join(" "))
+}
+
+/// Returns true if the statement calls `connection_id()`, which the binder turns into a constant.
+fn calls_connection_id(sql: &str) -> bool {
+    match tokenize_sql(sql) {
+        Ok(tokens) => tokens.windows(2).any(|w| {
+            w[0].kind == TokenKind::Ident
+                && w[0].text().eq_ignore_ascii_case("connection_id")
+                && w[1].kind == TokenKind::LParen
+        }),
+        Err(_) => true,
+    }
+}
+
+/// Returns true if evaluating the plan twice on the same data always produces the same result.
+///
+/// Unlike `ScalarExpr::is_deterministic()`, aggregate functions and subqueries are deterministic
+/// if their arguments are.
+pub fn is_deterministic_plan(s_expr: &SExpr) -> bool {
+    let deterministic = match s_expr.plan() {
+        RelOperator::LogicalGet(op) => {
+            op.push_down_predicates
+                .iter()
+                .flatten()
+                .all(is_deterministic_scalar)
+                && op
+                    .prewhere
+                    .iter()
+                    .flat_map(|p| p.predicates.iter())
+                    .all(is_deterministic_scalar)
+        }
+        RelOperator::PhysicalScan(op) => {
+            op.push_down_predicates
+                .iter()
+                .flatten()
+                .all(is_deterministic_scalar)
+                && op
+                    .prewhere
+                    .iter()
+                    .flat_map(|p| p.predicates.iter())
+                    .all(is_deterministic_scalar)
+        }
+        RelOperator::LogicalInnerJoin(op) => op
+            .left_conditions
+            .iter()
+            .chain(op.right_conditions.iter())
+            .chain(op.other_conditions.iter())
+            .all(is_deterministic_scalar),
+        RelOperator::PhysicalHashJoin(op) => op
+            .build_keys
+            .iter()
+            .chain(op.probe_keys.iter())
+            .chain(op.other_conditions.iter())
+            .all(is_deterministic_scalar),
+        RelOperator::EvalScalar(op) => op.items.iter().all(|i| is_deterministic_scalar(&i.scalar)),
+        RelOperator::Filter(op) => op.predicates.iter().all(is_deterministic_scalar),
+        RelOperator::Aggregate(op) => op
+            .group_items
+            .iter()
+            .chain(op.aggregate_functions.iter())
+            .all(|i| is_deterministic_scalar(&i.scalar)),
+        RelOperator::Window(op) => op.function.inner_scalars().all(is_deterministic_scalar),
+        RelOperator::Sort(_)
+        | RelOperator::Limit(_)
+        | RelOperator::Exchange(_)
+        | RelOperator::UnionAll(_)
+        | RelOperator::RecursiveUnion(_)
+        | RelOperator::RecursiveCteScan(_)
+        | RelOperator::DummyTableScan(_)
+        | RelOperator::Pattern(_) => true,
+    };
+
+    deterministic && s_expr.children().iter().all(is_deterministic_plan)
+}
+
+fn is_deterministic_scalar(scalar: &Scalar) -> bool {
+    let is_deterministic_func = |name: &str| {
+        FunctionFactory::instance()
+            .get_features(name)
+            .map_or(false, |features| features.is_deterministic)
+    };
+
+    match scalar {
+        Scalar::BoundColumnRef(_) | Scalar::ConstantExpr(_) => true,
+        Scalar::AndExpr(e) => is_deterministic_scalar(&e.left) && is_deterministic_scalar(&e.right),
+        Scalar::OrExpr(e) => is_deterministic_scalar(&e.left) && is_deterministic_scalar(&e.right),
+        Scalar::ComparisonExpr(e) => {
+            is_deterministic_func(&e.op.to_func_name())
+                && is_deterministic_scalar(&e.left)
+                && is_deterministic_scalar(&e.right)
+        }
+        Scalar::AggregateFunction(e) => e.args.iter().all(is_deterministic_scalar),
+        Scalar::WindowFunction(e) => e.inner_scalars().all(is_deterministic_scalar),
+        Scalar::FunctionCall(e) => {
+            is_deterministic_func(&e.func_name) && e.arguments.iter().all(is_deterministic_scalar)
+        }
+        Scalar::CastExpr(e) => is_deterministic_scalar(&e.argument),
+        Scalar::SubqueryExpr(e) => {
+            e.child_expr
+                .as_ref()
+                .map_or(true, |c| is_deterministic_scalar(c))
+                && is_deterministic_plan(&e.subquery)
+        }
+        // The results of a UDF server are not under our control.
+        Scalar::UDFServerCall(_) => false,
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::Utc;
+use common_base::base::tokio;
+use common_base::base::tokio::time::Duration;
+use common_base::base::GlobalIORuntime;
+use common_base::base::GlobalUniqName;
+use common_base::base::Singleton;
+use common_base::base::TrySpawn;
+use common_exception::Result;
+use common_management::LockApi;
+use common_management::LockMgr;
+use common_meta_types::CronSchedule;
+use common_meta_types::Task;
+use common_meta_types::TaskRun;
+use common_meta_types::TaskRunState;
+use common_users::UserApiProvider;
+use futures::TryStreamExt;
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+
+use super::InterpreterFactory;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sql::Planner;
+use crate::Config;
+
+/// The name of the lock held by the leader of the schedulers.
+const LEADER_LOCK: &str = "task_scheduler";
+
+/// The timeout of posting the failures to the error notification URLs.
+const NOTIFICATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs the tasks created by `CREATE TASK` on their schedules.
+///
+/// Every query node of the tenant runs a scheduler, one of them holds the leader lock in the
+/// meta service and checks the schedules every `task_scheduler_interval` seconds. A run is
+/// claimed in the meta service before it starts, so that a fire time is run once even if the
+/// leader changes. The fire times missed for less than the lease of the lock are run by the
+/// next leader.
+pub struct TaskScheduler {
+    tenant: String,
+    interval: Duration,
+    lease: Duration,
+    // identifies the holder of the leader lock
+    node_id: String,
+    // the seq of the leader lock if this node is the leader
+    leader_seq: Mutex<Option<u64>>,
+    // the tasks with a run executing on this node
+    running: Mutex<HashSet<String>>,
+}
+
+static TASK_SCHEDULER: OnceCell<Singleton<Arc<TaskScheduler>>> = OnceCell::new();
+
+impl TaskScheduler {
+    pub fn init(config: &Config, v: Singleton<Arc<TaskScheduler>>) -> Result<()> {
+        let interval = Duration::from_secs(config.query.task_scheduler_interval);
+        let scheduler = Arc::new(TaskScheduler {
+            tenant: config.query.tenant_id.clone(),
+            interval,
+            lease: (interval * 3).max(Duration::from_secs(30)),
+            node_id: GlobalUniqName::unique(),
+            leader_seq: Mutex::new(None),
+            running: Mutex::new(HashSet::new()),
+        });
+
+        if !interval.is_zero() {
+            // The timer stops with the scheduler.
+            let weak_scheduler = Arc::downgrade(&scheduler);
+            GlobalIORuntime::instance().spawn(async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+                    match weak_scheduler.upgrade() {
+                        None => break,
+                        Some(scheduler) => scheduler.check().await,
+                    }
+                }
+            });
+        }
+
+        v.init(scheduler)?;
+        TASK_SCHEDULER.set(v).ok();
+        Ok(())
+    }
+
+    pub fn instance() -> Arc<TaskScheduler> {
+        match TASK_SCHEDULER.get() {
+            None => panic!("TaskScheduler is not init"),
+            Some(task_scheduler) => task_scheduler.get(),
+        }
+    }
+
+    async fn check(self: &Arc<Self>) {
+        if let Err(cause) = self.schedule().await {
+            tracing::error!("task scheduler error: {:?}", cause);
+        }
+    }
+
+    /// Take or keep the leader lock, returns whether this node is the leader.
+    async fn elect(&self) -> Result<bool> {
+        let metastore = UserApiProvider::instance().get_meta_store_client();
+        let lock_api = LockMgr::create(metastore.as_ref().clone(), &self.tenant, self.lease)?;
+
+        let leader_seq = *self.leader_seq.lock();
+        let seq = match leader_seq {
+            None => lock_api.try_lock(LEADER_LOCK, &self.node_id).await?,
+            Some(seq) => match lock_api.renew(LEADER_LOCK, seq).await {
+                Ok(seq) => Some(seq),
+                Err(cause) => {
+                    tracing::warn!("task scheduler lost the leader lock: {:?}", cause);
+                    None
+                }
+            },
+        };
+        *self.leader_seq.lock() = seq;
+        Ok(seq.is_some())
+    }
+
+    async fn schedule(self: &Arc<Self>) -> Result<()> {
+        if !self.elect().await? {
+            return Ok(());
+        }
+
+        let now = Utc::now().timestamp() as u64;
+        // looks back over the lease, a new leader runs the fire times missed by the previous one
+        let after = now.saturating_sub((self.lease + self.interval).as_secs());
+        let tasks = UserApiProvider::instance().get_tasks(&self.tenant).await?;
+        for task in tasks {
+            let schedule = CronSchedule::parse(&task.schedule)?;
+            // only the latest fire time is run, the older ones missed are skipped
+            let scheduled_time = match schedule.fire_times(after.max(task.created_on), now).last() {
+                None => continue,
+                Some(scheduled_time) => *scheduled_time,
+            };
+
+            let scheduler = self.clone();
+            GlobalIORuntime::instance().spawn(async move {
+                if let Err(cause) = scheduler.run_task(&task, scheduled_time).await {
+                    tracing::error!("run of task {} error: {:?}", task.name, cause);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    async fn run_task(&self, task: &Task, scheduled_time: u64) -> Result<()> {
+        let task_api = UserApiProvider::instance().get_task_api_client(&self.tenant)?;
+        let mut run = TaskRun {
+            task: task.name.clone(),
+            definition: task.definition.clone(),
+            scheduled_time,
+            start_time: Utc::now().timestamp() as u64,
+            end_time: 0,
+            state: TaskRunState::Executing,
+            error: "".to_string(),
+            node: self.node_id.clone(),
+        };
+
+        // a run overlapping with the previous one is recorded as skipped
+        let executing = !self.running.lock().insert(task.name.clone());
+        if executing {
+            run.end_time = run.start_time;
+            run.state = TaskRunState::Skipped;
+            run.error = "The previous run of the task is still executing".to_string();
+            task_api.add_run(run).await?;
+            return Ok(());
+        }
+
+        let result = self.claim_and_execute(task, run).await;
+        self.running.lock().remove(&task.name);
+        result
+    }
+
+    async fn claim_and_execute(&self, task: &Task, mut run: TaskRun) -> Result<()> {
+        let task_api = UserApiProvider::instance().get_task_api_client(&self.tenant)?;
+        let seq = match task_api.add_run(run.clone()).await? {
+            // the fire time is run already
+            None => return Ok(()),
+            Some(seq) => seq,
+        };
+
+        tracing::info!("run of task {} at {} starts", task.name, run.scheduled_time);
+        let result = self.execute(task).await;
+        run.end_time = Utc::now().timestamp() as u64;
+        match result {
+            Ok(_) => run.state = TaskRunState::Succeeded,
+            Err(cause) => {
+                run.state = TaskRunState::Failed;
+                run.error = cause.message();
+            }
+        }
+        task_api.update_run(run.clone(), seq).await?;
+
+        if run.state == TaskRunState::Failed {
+            tracing::error!(
+                "run of task {} at {} failed: {}",
+                task.name,
+                run.scheduled_time,
+                run.error
+            );
+            if !task.error_notification.is_empty() {
+                if let Err(cause) = self.notify(task, &run).await {
+                    tracing::error!("notify the failure of task {} error: {}", task.name, cause);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the statement of the task as its owner.
+    async fn execute(&self, task: &Task) -> Result<()> {
+        let user = UserApiProvider::instance()
+            .get_user(&self.tenant, task.owner.clone())
+            .await?;
+        let session = SessionManager::instance()
+            .create_session(SessionType::Task)
+            .await?;
+        session.set_current_tenant(self.tenant.clone());
+        session.set_current_user(user);
+        let ctx = session.create_query_context().await?;
+
+        let mut planner = Planner::new(ctx.clone());
+        let (plan, _, _) = planner.plan_sql(&task.definition).await?;
+        let interpreter = InterpreterFactory::get(ctx.clone(), &plan).await?;
+        let mut stream = interpreter.execute(ctx).await?;
+        while stream.try_next().await?.is_some() {}
+        Ok(())
+    }
+
+    /// Post the failed run to the error notification URL of the task, as JSON.
+    async fn notify(&self, task: &Task, run: &TaskRun) -> reqwest::Result<()> {
+        let body = serde_json::json!({
+            "tenant": self.tenant,
+            "task": run.task,
+            "definition": run.definition,
+            "scheduled_time": run.scheduled_time,
+            "start_time": run.start_time,
+            "end_time": run.end_time,
+            "error": run.error,
+        });
+        reqwest::Client::new()
+            .post(&task.error_notification)
+            .header("Content-Type", "application/json")
+            .body(body.to_string())
+            .timeout(NOTIFICATION_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
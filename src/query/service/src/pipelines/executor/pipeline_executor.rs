@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::collections::VecDeque;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use common_base::base::catch_unwind;
@@ -22,6 +24,7 @@ use common_base::base::GlobalIORuntime;
 use common_base::base::Runtime;
 use common_base::base::Thread;
 use common_base::base::ThreadJoinHandle;
+use common_base::base::ThreadTracker;
 use common_base::base::TrySpawn;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -39,6 +42,29 @@ use crate::pipelines::pipeline::Pipeline;
 pub type FinishedCallback =
     Arc<Box<dyn Fn(&Option<ErrorCode>) -> Result<()> + Send + Sync + 'static>>;
 
+/// The executors running on the node and the threads of them.
+static RUNNING_EXECUTORS: AtomicUsize = AtomicUsize::new(0);
+static RUNNING_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+struct RunningGuard {
+    threads: usize,
+}
+
+impl RunningGuard {
+    fn create(threads: usize) -> RunningGuard {
+        RUNNING_EXECUTORS.fetch_add(1, Ordering::Relaxed);
+        RUNNING_THREADS.fetch_add(threads, Ordering::Relaxed);
+        RunningGuard { threads }
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING_EXECUTORS.fetch_sub(1, Ordering::Relaxed);
+        RUNNING_THREADS.fetch_sub(self.threads, Ordering::Relaxed);
+    }
+}
+
 pub struct PipelineExecutor {
     threads_num: usize,
     graph: RunningGraph,
@@ -60,6 +86,7 @@ impl PipelineExecutor {
         let on_finished_callback = pipeline.take_on_finished();
 
         assert_ne!(threads_num, 0, "Pipeline max threads cannot equals zero.");
+        let threads_num = Self::adaptive_threads_num(threads_num, &settings);
         Self::try_create(
             RunningGraph::create(pipeline)?,
             threads_num,
@@ -88,6 +115,7 @@ impl PipelineExecutor {
             .collect::<Vec<_>>();
 
         assert_ne!(threads_num, 0, "Pipeline max threads cannot equals zero.");
+        let threads_num = Self::adaptive_threads_num(threads_num, &settings);
         Self::try_create(
             RunningGraph::from_pipelines(pipelines)?,
             threads_num,
@@ -102,6 +130,22 @@ impl PipelineExecutor {
         )
     }
 
+    /// Limits the threads to the idle CPUs of the node if adaptive parallelism is enabled, but
+    /// never below a fair share of the CPUs among the running executors.
+    fn adaptive_threads_num(threads_num: usize, settings: &ExecutorSettings) -> usize {
+        if !settings.enable_adaptive_parallelism {
+            return threads_num;
+        }
+
+        let cpus = num_cpus::get();
+        let idle_cpus = cpus.saturating_sub(RUNNING_THREADS.load(Ordering::Relaxed));
+        let fair_share = cpus / (RUNNING_EXECUTORS.load(Ordering::Relaxed) + 1);
+        std::cmp::min(
+            threads_num,
+            std::cmp::max(1, std::cmp::max(idle_cpus, fair_share)),
+        )
+    }
+
     fn try_create(
         graph: RunningGraph,
         threads_num: usize,
@@ -147,6 +191,7 @@ impl PipelineExecutor {
     pub fn execute(self: &Arc<Self>) -> Result<()> {
         self.start_executor_daemon()?;
 
+        let _running_guard = RunningGuard::create(self.threads_num);
         let mut thread_join_handles = self.execute_threads(self.threads_num);
 
         while let Some(join_handle) = thread_join_handles.pop() {
@@ -208,6 +253,7 @@ impl PipelineExecutor {
             }
 
             thread_join_handles.push(Thread::named_spawn(name, move || unsafe {
+                let _tracker_guard = ThreadTracker::attach(this.settings.runtime_tracker.clone());
                 let this_clone = this.clone();
                 let try_result = catch_unwind(move || -> Result<()> {
                     match this_clone.execute_single_thread(thread_num) {
@@ -249,11 +295,25 @@ impl PipelineExecutor {
                     let schedule_queue = self.graph.schedule_queue(executed_pid)?;
                     schedule_queue.schedule(&self.global_tasks_queue, &mut context);
                 }
+
+                self.check_memory_limit()?;
             }
         }
 
         Ok(())
     }
+
+    fn check_memory_limit(&self) -> Result<()> {
+        let memory_tracker = self.settings.runtime_tracker.get_memory_tracker();
+        match memory_tracker.is_exceeded() {
+            false => Ok(()),
+            true => Err(ErrorCode::QueryMemoryLimitExceeded(format!(
+                "Memory limit exceeded, the query used {} bytes, the limit is {} bytes",
+                memory_tracker.get_memory_usage(),
+                memory_tracker.get_limit()
+            ))),
+        }
+    }
 }
 
 impl Drop for PipelineExecutor {
@@ -35,6 +35,7 @@ pub use sources::SyncSourcer;
 pub use transforms::AggregatorParams;
 pub use transforms::AggregatorTransformParams;
 pub use transforms::BlockCompactor;
+pub use transforms::CollectedBlocks;
 pub use transforms::ExpressionTransform;
 pub use transforms::HashJoinDesc;
 pub use transforms::HashJoinState;
@@ -49,6 +50,7 @@ pub use transforms::KeyU64HashTable;
 pub use transforms::KeyU8HashTable;
 pub use transforms::MarkJoinCompactor;
 pub use transforms::ProjectionTransform;
+pub use transforms::RecursiveCteSource;
 pub use transforms::RightJoinCompactor;
 pub use transforms::SerializerHashTable;
 pub use transforms::SinkBuildHashTable;
@@ -57,9 +59,11 @@ pub use transforms::TransformAddOn;
 pub use transforms::TransformAggregator;
 pub use transforms::TransformBlockCompact;
 pub use transforms::TransformCastSchema;
+pub use transforms::TransformCollect;
 pub use transforms::TransformCompact;
 pub use transforms::TransformCreateSets;
 pub use transforms::TransformDummy;
+pub use transforms::TransformExternalSort;
 pub use transforms::TransformFilter;
 pub use transforms::TransformHashJoinProbe;
 pub use transforms::TransformHaving;
@@ -67,3 +71,4 @@ pub use transforms::TransformLimit;
 pub use transforms::TransformLimitBy;
 pub use transforms::TransformSortMerge;
 pub use transforms::TransformSortPartial;
+pub use transforms::TransformWindow;
@@ -15,6 +15,7 @@
 use std::borrow::BorrowMut;
 use std::sync::Arc;
 
+use bytes::BytesMut;
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethod;
 use common_datablocks::HashMethodKeysU128;
@@ -25,9 +26,13 @@ use common_datablocks::HashMethodKeysU512;
 use common_datablocks::HashMethodKeysU64;
 use common_datablocks::HashMethodKeysU8;
 use common_datablocks::HashMethodSerializer;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
 use common_datavalues::DataType;
 use common_datavalues::MutableColumn;
+use common_datavalues::MutableStringColumn;
 use common_datavalues::ScalarColumn;
+use common_datavalues::ScalarColumnBuilder;
 use common_datavalues::Series;
 use common_datavalues::StringColumn;
 use common_exception::Result;
@@ -36,9 +41,12 @@ use common_functions::aggregates::StateAddrs;
 
 use crate::pipelines::processors::transforms::group_by::AggregatorState;
 use crate::pipelines::processors::transforms::group_by::GroupColumnsBuilder;
+use crate::pipelines::processors::transforms::group_by::KeysColumnBuilder;
 use crate::pipelines::processors::transforms::group_by::KeysColumnIter;
 use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
 use crate::pipelines::processors::transforms::group_by::StateEntity;
+use crate::pipelines::processors::transforms::spiller::hash_partition;
+use crate::pipelines::processors::transforms::spiller::Spiller;
 use crate::pipelines::processors::transforms::transform_aggregator::Aggregator;
 use crate::pipelines::processors::AggregatorParams;
 use crate::sessions::QueryContext;
@@ -58,6 +66,9 @@ pub type KeysU512FinalAggregator<const HAS_AGG: bool> =
 pub type SerializerFinalAggregator<const HAS_AGG: bool> =
     FinalAggregator<HAS_AGG, HashMethodSerializer>;
 
+/// Number of partitions the aggregate states are split into, once spilled.
+const SPILLED_PARTITIONS: usize = 16;
+
 pub struct FinalAggregator<
     const HAS_AGG: bool,
     Method: HashMethod + PolymorphicKeysHelper<Method> + Send,
@@ -71,6 +82,12 @@ pub struct FinalAggregator<
     // used for deserialization only, so we can reuse it during the loop
     temp_place: Option<StateAddr>,
     ctx: Arc<QueryContext>,
+
+    // Once the states exceed the threshold, they are serialized, split into partitions by the keys
+    // and spilled to disk. The partitions are merged one by one when generating.
+    spilling_threshold: usize,
+    spiller: Option<Spiller>,
+    next_spilled_partition: usize,
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
@@ -87,6 +104,7 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
         } else {
             state.alloc_layout2(&params)
         };
+        let spilling_threshold = ctx.get_settings().get_spilling_memory_threshold()? as usize;
 
         Ok(Self {
             is_generated: false,
@@ -96,8 +114,132 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
             params,
             temp_place,
             ctx,
+            spilling_threshold,
+            spiller: None,
+            next_spilled_partition: 0,
         })
     }
+
+    /// Drop all the states, and start over with an empty hash table.
+    fn reset_state(&mut self) {
+        self.drop_states();
+        self.state = self.method.aggregate_state();
+        self.temp_place = if self.params.aggregate_functions.is_empty() {
+            None
+        } else {
+            self.state.alloc_layout2(&self.params)
+        };
+        self.states_dropped = false;
+    }
+
+    /// Spill the states if they exceed the memory threshold.
+    fn try_spill(&mut self, schema: &DataSchemaRef) -> Result<()> {
+        if self.spilling_threshold > 0 && self.state.allocated_bytes() > self.spilling_threshold {
+            self.spill_states(schema)?;
+        }
+        Ok(())
+    }
+
+    /// Serialize the states in the same format as the input blocks, and spill them by the hash of the keys.
+    fn spill_states(&mut self, schema: &DataSchemaRef) -> Result<()> {
+        if self.state.len() == 0 {
+            return Ok(());
+        }
+
+        if self.spiller.is_none() {
+            self.spiller = Some(Spiller::create(
+                &self.ctx,
+                "aggregate",
+                schema.clone(),
+                SPILLED_PARTITIONS,
+            )?);
+        }
+
+        let block = self.serialize_states(schema)?;
+        self.reset_state();
+
+        let keys_column = block.column(self.params.aggregate_functions.len());
+        let partitions = hash_partition(&block, &[keys_column], SPILLED_PARTITIONS, 0)?;
+        let spiller = self.spiller.as_mut().unwrap();
+        for (partition, block) in partitions.into_iter().enumerate() {
+            spiller.spill(partition, block)?;
+        }
+        Ok(())
+    }
+
+    fn serialize_states(&self, schema: &DataSchemaRef) -> Result<DataBlock> {
+        let state_groups_len = self.state.len();
+        let aggregate_functions = &self.params.aggregate_functions;
+        let offsets_aggregate_states = &self.params.offsets_aggregate_states;
+
+        let mut state_builders: Vec<MutableStringColumn> = (0..aggregate_functions.len())
+            .map(|_| MutableStringColumn::with_capacity(state_groups_len * 4))
+            .collect();
+        let mut keys_column_builder = self.method.keys_column_builder(state_groups_len);
+
+        let mut bytes = BytesMut::new();
+        for group_entity in self.state.iter() {
+            if !aggregate_functions.is_empty() {
+                let place: StateAddr = (*group_entity.get_state_value()).into();
+                for (idx, aggregate_function) in aggregate_functions.iter().enumerate() {
+                    let arg_place = place.next(offsets_aggregate_states[idx]);
+                    aggregate_function.serialize(arg_place, &mut bytes)?;
+                    state_builders[idx].append_value(&bytes[..]);
+                    bytes.clear();
+                }
+            }
+
+            keys_column_builder.append_value(group_entity.get_state_key());
+        }
+
+        let mut columns: Vec<ColumnRef> = Vec::with_capacity(schema.fields().len());
+        for mut builder in state_builders {
+            columns.push(builder.to_column());
+        }
+
+        columns.push(keys_column_builder.finish());
+        Ok(DataBlock::create(schema.clone(), columns))
+    }
+
+    /// Blocks of the next non-empty spilled partition, spilling the states left in memory first.
+    fn next_spilled_partition(&mut self) -> Result<Option<Vec<DataBlock>>> {
+        if self.next_spilled_partition == 0 {
+            let schema = self.spiller.as_ref().unwrap().schema().clone();
+            self.spill_states(&schema)?;
+        }
+
+        let spiller = self.spiller.as_ref().unwrap();
+        while self.next_spilled_partition < spiller.num_partitions() {
+            let blocks = spiller.read_partition(self.next_spilled_partition)?;
+            self.next_spilled_partition += 1;
+            if !blocks.is_empty() {
+                return Ok(Some(blocks));
+            }
+        }
+
+        self.spiller = None;
+        self.reset_state();
+        self.is_generated = true;
+        Ok(None)
+    }
+
+    fn generate_spilled(
+        &mut self,
+        consume: fn(&mut Self, DataBlock) -> Result<()>,
+        generate: fn(&mut Self) -> Result<Option<DataBlock>>,
+    ) -> Result<Option<DataBlock>> {
+        match self.next_spilled_partition()? {
+            None => Ok(None),
+            Some(blocks) => {
+                self.reset_state();
+                for block in blocks {
+                    consume(self, block)?;
+                }
+                self.is_generated = false;
+                generate(self)
+            }
+        }
+    }
 }
 
 impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<true, Method> {
@@ -129,14 +271,8 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<
         }
         places
     }
-}
-
-impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
-    for FinalAggregator<true, Method>
-{
-    const NAME: &'static str = "GroupByFinalTransform";
 
-    fn consume(&mut self, block: DataBlock) -> Result<()> {
+    fn consume_block(&mut self, block: DataBlock) -> Result<()> {
         // 1.1 and 1.2.
         let aggregate_function_len = self.params.aggregate_functions.len();
         let keys_column = block.column(aggregate_function_len);
@@ -178,7 +314,7 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         Ok(())
     }
 
-    fn generate(&mut self) -> Result<Option<DataBlock>> {
+    fn generate_block(&mut self) -> Result<Option<DataBlock>> {
         match self.state.len() == 0 || self.is_generated {
             true => {
                 self.drop_states();
@@ -231,11 +367,26 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
 }
 
 impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
-    for FinalAggregator<false, Method>
+    for FinalAggregator<true, Method>
 {
     const NAME: &'static str = "GroupByFinalTransform";
 
     fn consume(&mut self, block: DataBlock) -> Result<()> {
+        let schema = block.schema().clone();
+        self.consume_block(block)?;
+        self.try_spill(&schema)
+    }
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        match self.spiller.is_some() {
+            true => self.generate_spilled(Self::consume_block, Self::generate_block),
+            false => self.generate_block(),
+        }
+    }
+}
+
+impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<false, Method> {
+    fn consume_block(&mut self, block: DataBlock) -> Result<()> {
         let key_array = block.column(0);
         let keys_iter = self.method.keys_iter_from_column(key_array)?;
 
@@ -253,7 +404,7 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         Ok(())
     }
 
-    fn generate(&mut self) -> Result<Option<DataBlock>> {
+    fn generate_block(&mut self) -> Result<Option<DataBlock>> {
         match self.state.len() == 0 || self.is_generated {
             true => Ok(None),
             false => {
@@ -272,6 +423,25 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
     }
 }
 
+impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
+    for FinalAggregator<false, Method>
+{
+    const NAME: &'static str = "GroupByFinalTransform";
+
+    fn consume(&mut self, block: DataBlock) -> Result<()> {
+        let schema = block.schema().clone();
+        self.consume_block(block)?;
+        self.try_spill(&schema)
+    }
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        match self.spiller.is_some() {
+            true => self.generate_spilled(Self::consume_block, Self::generate_block),
+            false => self.generate_block(),
+        }
+    }
+}
+
 impl<const FINAL: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
     FinalAggregator<FINAL, Method>
 {
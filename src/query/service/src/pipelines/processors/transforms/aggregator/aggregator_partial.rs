@@ -26,6 +26,7 @@ use common_datablocks::HashMethodKeysU64;
 use common_datablocks::HashMethodKeysU8;
 use common_datablocks::HashMethodSerializer;
 use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
 use common_datavalues::MutableColumn;
 use common_datavalues::MutableStringColumn;
 use common_datavalues::ScalarColumnBuilder;
@@ -37,6 +38,8 @@ use crate::pipelines::processors::transforms::group_by::AggregatorState;
 use crate::pipelines::processors::transforms::group_by::KeysColumnBuilder;
 use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
 use crate::pipelines::processors::transforms::group_by::StateEntity;
+use crate::pipelines::processors::transforms::spiller::hash_partition;
+use crate::pipelines::processors::transforms::spiller::Spiller;
 use crate::pipelines::processors::transforms::transform_aggregator::Aggregator;
 use crate::pipelines::processors::AggregatorParams;
 use crate::sessions::QueryContext;
@@ -62,6 +65,9 @@ pub type KeysU512PartialAggregator<const HAS_AGG: bool> =
 pub type SerializerPartialAggregator<const HAS_AGG: bool> =
     PartialAggregator<HAS_AGG, HashMethodSerializer>;
 
+/// Number of partitions the aggregate states are split into, once spilled.
+const SPILLED_PARTITIONS: usize = 16;
+
 pub struct PartialAggregator<
     const HAS_AGG: bool,
     Method: HashMethod + PolymorphicKeysHelper<Method>,
@@ -73,23 +79,130 @@ pub struct PartialAggregator<
     state: Method::State,
     params: Arc<AggregatorParams>,
     ctx: Arc<QueryContext>,
+
+    // Once the states exceed the threshold, they are serialized, split into partitions by the keys
+    // and spilled to disk. The partitions are sent one by one after the states left in memory.
+    spilling_threshold: usize,
+    spiller: Option<Spiller>,
+    next_spilled_partition: usize,
+    spilled_blocks: Vec<DataBlock>,
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
     PartialAggregator<HAS_AGG, Method>
 {
-    pub fn create(ctx: Arc<QueryContext>, method: Method, params: Arc<AggregatorParams>) -> Self {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        method: Method,
+        params: Arc<AggregatorParams>,
+    ) -> Result<Self> {
         let state = method.aggregate_state();
-        Self {
+        let spilling_threshold = ctx.get_settings().get_spilling_memory_threshold()? as usize;
+        Ok(Self {
             is_generated: false,
             states_dropped: false,
             state,
             method,
             params,
             ctx,
+            spilling_threshold,
+            spiller: None,
+            next_spilled_partition: 0,
+            spilled_blocks: vec![],
+        })
+    }
+
+    /// Spill the states if they exceed the memory threshold, and start over with empty states.
+    fn try_spill(&mut self) -> Result<()> {
+        if self.spilling_threshold == 0 || self.state.allocated_bytes() <= self.spilling_threshold {
+            return Ok(());
+        }
+
+        let schema = &self.params.schema;
+        if self.spiller.is_none() {
+            self.spiller = Some(Spiller::create(
+                &self.ctx,
+                "aggregate-partial",
+                schema.clone(),
+                SPILLED_PARTITIONS,
+            )?);
+        }
+
+        let block = self.serialize_states(schema)?;
+        self.drop_states();
+        self.state = self.method.aggregate_state();
+        self.states_dropped = false;
+
+        let keys_column = block.column(self.params.aggregate_functions.len());
+        let partitions = hash_partition(&block, &[keys_column], SPILLED_PARTITIONS, 0)?;
+        let spiller = self.spiller.as_mut().unwrap();
+        for (partition, block) in partitions.into_iter().enumerate() {
+            spiller.spill(partition, block)?;
+        }
+        Ok(())
+    }
+
+    /// The next block of the spilled partitions, which are read one by one.
+    fn next_spilled_block(&mut self) -> Result<Option<DataBlock>> {
+        loop {
+            if let Some(block) = self.spilled_blocks.pop() {
+                return Ok(Some(block));
+            }
+
+            let spiller = match self.spiller.as_ref() {
+                Some(spiller) => spiller,
+                None => return Ok(None),
+            };
+            if self.next_spilled_partition == spiller.num_partitions() {
+                self.spiller = None;
+                return Ok(None);
+            }
+            self.spilled_blocks = spiller.read_partition(self.next_spilled_partition)?;
+            self.next_spilled_partition += 1;
         }
     }
 
+    /// Serialize the states into a block of `schema`: the states of each aggregate function,
+    /// followed by the keys.
+    fn serialize_states(&self, schema: &DataSchemaRef) -> Result<DataBlock> {
+        let state_groups_len = self.state.len();
+        let aggregator_params = self.params.as_ref();
+        let funcs = &aggregator_params.aggregate_functions;
+        let aggr_len = funcs.len();
+        let offsets_aggregate_states = &aggregator_params.offsets_aggregate_states;
+
+        // Builders.
+        let mut state_builders: Vec<MutableStringColumn> = (0..aggr_len)
+            .map(|_| MutableStringColumn::with_capacity(state_groups_len * 4))
+            .collect();
+
+        let mut group_key_builder = self.method.keys_column_builder(state_groups_len);
+
+        let mut bytes = BytesMut::new();
+        for group_entity in self.state.iter() {
+            if !funcs.is_empty() {
+                let place: StateAddr = (*group_entity.get_state_value()).into();
+
+                for (idx, func) in funcs.iter().enumerate() {
+                    let arg_place = place.next(offsets_aggregate_states[idx]);
+                    func.serialize(arg_place, &mut bytes)?;
+                    state_builders[idx].append_value(&bytes[..]);
+                    bytes.clear();
+                }
+            }
+
+            group_key_builder.append_value(group_entity.get_state_key());
+        }
+
+        let mut columns: Vec<ColumnRef> = Vec::with_capacity(schema.fields().len());
+        for mut builder in state_builders {
+            columns.push(builder.to_column());
+        }
+
+        columns.push(group_key_builder.finish());
+        Ok(DataBlock::create(schema.clone(), columns))
+    }
+
     #[inline(always)]
     fn lookup_key(keys_iter: Method::HashKeyIter<'_>, state: &mut Method::State) {
         let mut inserted = true;
@@ -186,45 +299,12 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
     #[inline(always)]
     fn generate_data(&mut self) -> Result<Option<DataBlock>> {
         if self.state.len() == 0 || self.is_generated {
-            return Ok(None);
+            return self.next_spilled_block();
         }
 
         self.is_generated = true;
-        let state_groups_len = self.state.len();
-        let aggregator_params = self.params.as_ref();
-        let funcs = &aggregator_params.aggregate_functions;
-        let aggr_len = funcs.len();
-        let offsets_aggregate_states = &aggregator_params.offsets_aggregate_states;
-
-        // Builders.
-        let mut state_builders: Vec<MutableStringColumn> = (0..aggr_len)
-            .map(|_| MutableStringColumn::with_capacity(state_groups_len * 4))
-            .collect();
-
-        let mut group_key_builder = self.method.keys_column_builder(state_groups_len);
-
-        let mut bytes = BytesMut::new();
-        for group_entity in self.state.iter() {
-            let place: StateAddr = (*group_entity.get_state_value()).into();
-
-            for (idx, func) in funcs.iter().enumerate() {
-                let arg_place = place.next(offsets_aggregate_states[idx]);
-                func.serialize(arg_place, &mut bytes)?;
-                state_builders[idx].append_value(&bytes[..]);
-                bytes.clear();
-            }
-
-            group_key_builder.append_value(group_entity.get_state_key());
-        }
-
-        let schema = &self.params.schema;
-        let mut columns: Vec<ColumnRef> = Vec::with_capacity(schema.fields().len());
-        for mut builder in state_builders {
-            columns.push(builder.to_column());
-        }
-
-        columns.push(group_key_builder.finish());
-        Ok(Some(DataBlock::create(schema.clone(), columns)))
+        let block = self.serialize_states(&self.params.schema)?;
+        Ok(Some(block))
     }
 }
 
@@ -249,7 +329,8 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         }
 
         let places = Self::lookup_state(&self.params, group_keys_iter, &mut self.state);
-        Self::execute(&self.params, &block, &places)
+        Self::execute(&self.params, &block, &places)?;
+        self.try_spill()
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
@@ -278,14 +359,14 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         }
 
         Self::lookup_key(group_keys_iter, &mut self.state);
-        Ok(())
+        self.try_spill()
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
         match self.state.len() == 0 || self.is_generated {
             true => {
                 self.drop_states();
-                Ok(None)
+                self.next_spilled_block()
             }
             false => {
                 self.is_generated = true;
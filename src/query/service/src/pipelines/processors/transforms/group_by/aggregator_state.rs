@@ -51,6 +51,9 @@ pub trait AggregatorState<Method: HashMethod>: Sync + Send {
 
     fn len(&self) -> usize;
 
+    /// Estimated memory used by the keys and the aggregate function states, in bytes.
+    fn allocated_bytes(&self) -> usize;
+
     fn iter(&self) -> Self::Iterator;
 
     fn alloc_place(&self, layout: Layout) -> StateAddr;
@@ -155,6 +158,12 @@ where
         self.size
     }
 
+    #[inline(always)]
+    fn allocated_bytes(&self) -> usize {
+        self.area.allocated_bytes()
+            + self.max_size * std::mem::size_of::<ShortFixedKeysStateEntity<T>>()
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         Self::Iterator::create(self.data, self.max_size as isize)
@@ -242,6 +251,11 @@ where
         self.data.len()
     }
 
+    #[inline(always)]
+    fn allocated_bytes(&self) -> usize {
+        self.area.allocated_bytes() + self.data.len() * std::mem::size_of::<Self::Entity>()
+    }
+
     #[inline(always)]
     fn iter(&self) -> Self::Iterator {
         self.data.iter()
@@ -302,6 +316,11 @@ impl AggregatorState<HashMethodSerializer> for SerializedKeysAggregatorState {
     fn len(&self) -> usize {
         self.data_state_map.len()
     }
+    fn allocated_bytes(&self) -> usize {
+        self.keys_area.allocated_bytes()
+            + self.state_area.allocated_bytes()
+            + self.data_state_map.len() * std::mem::size_of::<Self::Entity>()
+    }
     fn iter(&self) -> Self::Iterator {
         self.data_state_map.iter()
     }
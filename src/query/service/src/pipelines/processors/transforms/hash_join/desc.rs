@@ -42,6 +42,15 @@ impl RightJoinDesc {
     }
 }
 
+/// A runtime filter built from a key of the build side, on a column scanned by the probe side.
+#[derive(Clone)]
+pub struct RuntimeFilterDesc {
+    /// Index of the key in the build keys
+    pub(crate) build_key: usize,
+    /// Name of the column in the schema of the scanned table
+    pub(crate) column_name: String,
+}
+
 pub struct HashJoinDesc {
     pub(crate) build_keys: Vec<EvalNode>,
     pub(crate) probe_keys: Vec<EvalNode>,
@@ -51,10 +60,14 @@ pub struct HashJoinDesc {
     /// Whether the Join are derived from correlated subquery.
     pub(crate) from_correlated_subquery: bool,
     pub(crate) right_join_desc: RightJoinDesc,
+    pub(crate) runtime_filters: Vec<RuntimeFilterDesc>,
 }
 
 impl HashJoinDesc {
-    pub fn create(join: &HashJoin) -> Result<HashJoinDesc> {
+    pub fn create(
+        join: &HashJoin,
+        runtime_filters: Vec<RuntimeFilterDesc>,
+    ) -> Result<HashJoinDesc> {
         let predicate = Self::join_predicate(&join.other_conditions)?;
 
         Ok(HashJoinDesc {
@@ -71,9 +84,28 @@ impl HashJoinDesc {
             },
             from_correlated_subquery: join.from_correlated_subquery,
             right_join_desc: RightJoinDesc::create(),
+            runtime_filters,
         })
     }
 
+    /// Create a desc of the same join with empty states, to join a spilled partition.
+    pub fn for_partition(&self) -> HashJoinDesc {
+        HashJoinDesc {
+            join_type: self.join_type.clone(),
+            build_keys: self.build_keys.clone(),
+            probe_keys: self.probe_keys.clone(),
+            other_predicate: self.other_predicate.clone(),
+            marker_join_desc: MarkJoinDesc {
+                has_null: RwLock::new(false),
+                marker_index: self.marker_join_desc.marker_index,
+            },
+            from_correlated_subquery: self.from_correlated_subquery,
+            right_join_desc: RightJoinDesc::create(),
+            // only the whole build side makes runtime filters
+            runtime_filters: vec![],
+        }
+    }
+
     fn join_predicate(other_conditions: &[PhysicalScalar]) -> Result<Option<PhysicalScalar>> {
         if other_conditions.is_empty() {
             return Ok(None);
@@ -12,16 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_datablocks::DataBlock;
 use common_exception::Result;
 
 use super::ProbeState;
+use crate::pipelines::processors::transforms::spiller::Spiller;
 
 #[async_trait::async_trait]
 /// Concurrent hash table for hash join.
 pub trait HashJoinState: Send + Sync {
-    /// Build hash table with input DataBlock
-    fn build(&self, input: DataBlock) -> Result<()>;
+    /// Build hash table with input DataBlock.
+    ///
+    /// Once the build side is spilled, the block is partitioned into `spiller` instead,
+    /// which is owned by the build processor, so that no lock is held while writing to disk.
+    fn build(&self, input: DataBlock, spiller: &mut Option<Spiller>) -> Result<()>;
+
+    /// Add the partitions spilled by a build processor, once it's finished.
+    fn add_build_spiller(&self, spiller: Spiller) -> Result<()>;
 
     /// Probe the hash table and retrieve matched rows as DataBlocks
     fn probe(&self, input: &DataBlock, probe_state: &mut ProbeState) -> Result<Vec<DataBlock>>;
@@ -42,6 +51,16 @@ pub trait HashJoinState: Send + Sync {
     /// Wait until the build phase is finished
     async fn wait_finish(&self) -> Result<()>;
 
+    /// Whether the build side is spilled to disk.
+    /// If so, the probe side is partitioned and spilled too, then partitions are joined one by one.
+    fn is_spilled(&self) -> bool;
+
+    /// Split a probe block into partitions, in the same way as the spilled build side
+    fn partition_probe_block(&self, input: &DataBlock) -> Result<Vec<DataBlock>>;
+
+    /// Get the state to join a spilled partition, whose hash table is built from the build blocks of the partition
+    fn spilled_partition_state(&self, partition: usize) -> Result<Arc<dyn HashJoinState>>;
+
     /// Get mark join results
     fn mark_join_blocks(&self) -> Result<Vec<DataBlock>>;
 
@@ -21,6 +21,7 @@ use std::sync::Mutex;
 use common_arrow::arrow::bitmap::Bitmap;
 use common_arrow::arrow::bitmap::MutableBitmap;
 use common_base::base::tokio::sync::Notify;
+use common_catalog::runtime_filter::RuntimeFilter;
 use common_datablocks::DataBlock;
 use common_datablocks::HashMethod;
 use common_datablocks::HashMethodFixedKeys;
@@ -52,10 +53,15 @@ use primitive_types::U512;
 use super::ProbeState;
 use crate::pipelines::processors::transforms::group_by::keys_ref::KeysRef;
 use crate::pipelines::processors::transforms::hash_join::desc::HashJoinDesc;
+use crate::pipelines::processors::transforms::hash_join::row::Chunk;
 use crate::pipelines::processors::transforms::hash_join::row::RowPtr;
 use crate::pipelines::processors::transforms::hash_join::row::RowSpace;
+use crate::pipelines::processors::transforms::hash_join::spill_state::HashJoinSpillState;
+use crate::pipelines::processors::transforms::hash_join::spill_state::SPILLED_PARTITIONS;
 use crate::pipelines::processors::transforms::hash_join::util::build_schema_wrap_nullable;
 use crate::pipelines::processors::transforms::hash_join::util::probe_schema_wrap_nullable;
+use crate::pipelines::processors::transforms::spiller::hash_partition;
+use crate::pipelines::processors::transforms::spiller::Spiller;
 use crate::pipelines::processors::HashJoinState;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
@@ -63,6 +69,10 @@ use crate::sql::executor::PhysicalScalar;
 use crate::sql::planner::plans::JoinType;
 use crate::sql::plans::JoinType::Mark;
 
+/// Runtime filters are not built from the build sides of more rows, which are too large to
+/// filter out much of the probe side.
+const RUNTIME_FILTER_MAX_BUILD_ROWS: usize = 1 << 22;
+
 pub struct SerializerHashTable {
     pub(crate) hash_table: HashMap<KeysRef, Vec<RowPtr>>,
     pub(crate) hash_method: HashMethodSerializer,
@@ -138,6 +148,8 @@ pub struct JoinHashTable {
     pub(crate) row_ptrs: RwLock<Vec<RowPtr>>,
     pub(crate) probe_schema: DataSchemaRef,
     finished_notify: Arc<Notify>,
+    /// Set if the build side can be spilled to disk
+    spill_state: Option<HashJoinSpillState>,
 }
 
 impl JoinHashTable {
@@ -147,92 +159,86 @@ impl JoinHashTable {
         build_schema: DataSchemaRef,
         probe_schema: DataSchemaRef,
         hash_join_desc: HashJoinDesc,
+    ) -> Result<Arc<JoinHashTable>> {
+        Self::create_join_state_at_level(
+            ctx,
+            build_keys,
+            build_schema,
+            probe_schema,
+            hash_join_desc,
+            0,
+        )
+    }
+
+    /// Create the state of a join, `level` is the level of recursive partitioning once spilled.
+    fn create_join_state_at_level(
+        ctx: Arc<QueryContext>,
+        build_keys: &[PhysicalScalar],
+        build_schema: DataSchemaRef,
+        probe_schema: DataSchemaRef,
+        hash_join_desc: HashJoinDesc,
+        level: usize,
     ) -> Result<Arc<JoinHashTable>> {
         let hash_key_types: Vec<DataTypeImpl> =
             build_keys.iter().map(|expr| expr.data_type()).collect();
         let method = DataBlock::choose_hash_method_with_types(&hash_key_types)?;
-        Ok(match method {
-            HashMethodKind::Serializer(_) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::SerializerHashTable(SerializerHashTable {
-                    hash_table: HashMap::<KeysRef, Vec<RowPtr>>::create(),
-                    hash_method: HashMethodSerializer::default(),
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU8(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU8HashTable(KeyU8HashTable {
-                    hash_table: HashMap::<u8, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU16(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU16HashTable(KeyU16HashTable {
-                    hash_table: HashMap::<u16, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU32(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU32HashTable(KeyU32HashTable {
-                    hash_table: HashMap::<u32, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU64(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU64HashTable(KeyU64HashTable {
-                    hash_table: HashMap::<u64, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU128(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
+        let hash_table = match method {
+            HashMethodKind::Serializer(_) => HashTable::SerializerHashTable(SerializerHashTable {
+                hash_table: HashMap::<KeysRef, Vec<RowPtr>>::create(),
+                hash_method: HashMethodSerializer::default(),
+            }),
+            HashMethodKind::KeysU8(hash_method) => HashTable::KeyU8HashTable(KeyU8HashTable {
+                hash_table: HashMap::<u8, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU16(hash_method) => HashTable::KeyU16HashTable(KeyU16HashTable {
+                hash_table: HashMap::<u16, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU32(hash_method) => HashTable::KeyU32HashTable(KeyU32HashTable {
+                hash_table: HashMap::<u32, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU64(hash_method) => HashTable::KeyU64HashTable(KeyU64HashTable {
+                hash_table: HashMap::<u64, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU128(hash_method) => {
                 HashTable::KeyU128HashTable(KeyU128HashTable {
                     hash_table: HashMap::<u128, Vec<RowPtr>>::create(),
                     hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU256(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
+                })
+            }
+            HashMethodKind::KeysU256(hash_method) => {
                 HashTable::KeyU256HashTable(KeyU256HashTable {
                     hash_table: HashMap::<U256, Vec<RowPtr>>::create(),
                     hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU512(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
+                })
+            }
+            HashMethodKind::KeysU512(hash_method) => {
                 HashTable::KeyU512HashTable(KeyU512HashTable {
                     hash_table: HashMap::<U512, Vec<RowPtr>>::create(),
                     hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-        })
+                })
+            }
+        };
+
+        let mut join_hash_table = JoinHashTable::try_create(
+            ctx.clone(),
+            hash_table,
+            build_schema.clone(),
+            probe_schema.clone(),
+            hash_join_desc,
+        )?;
+        join_hash_table.spill_state = HashJoinSpillState::try_create(
+            ctx.get_runtime_tracker(),
+            &join_hash_table.hash_join_desc.join_type,
+            level,
+            build_keys.to_vec(),
+            build_schema,
+            probe_schema,
+        );
+        Ok(Arc::new(join_hash_table))
     }
 
     pub fn try_create(
@@ -264,6 +270,7 @@ impl JoinHashTable {
             row_ptrs: RwLock::new(vec![]),
             probe_schema: probe_data_schema,
             finished_notify: Arc::new(Notify::new()),
+            spill_state: None,
         })
     }
 
@@ -426,6 +433,68 @@ impl JoinHashTable {
         }
     }
 
+    /// Split a build block into partitions, and append them to the spiller of the build processor.
+    fn spill_block(
+        &self,
+        spiller: &mut Option<Spiller>,
+        block: &DataBlock,
+        keys: &[ColumnRef],
+    ) -> Result<()> {
+        if spiller.is_none() {
+            *spiller = Some(Spiller::create(
+                &self.ctx,
+                "hash_join_build",
+                block.schema().clone(),
+                SPILLED_PARTITIONS,
+            )?);
+        }
+
+        if let Some(spiller) = spiller.as_mut() {
+            let keys = keys.iter().collect::<Vec<_>>();
+            let partitions =
+                hash_partition(block, &keys, SPILLED_PARTITIONS, self.spilled_level())?;
+            for (partition, block) in partitions.into_iter().enumerate() {
+                spiller.spill(partition, block)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Spill the blocks built in memory.
+    fn spill_chunks(&self, spiller: &mut Option<Spiller>) -> Result<()> {
+        let chunks = std::mem::take(&mut *self.row_space.chunks.write().unwrap());
+        for chunk in chunks {
+            self.spill_block(spiller, &chunk.data_block, &chunk.cols)?;
+        }
+        Ok(())
+    }
+
+    /// Level of recursive partitioning, the hash of partitions differs for each level.
+    fn spilled_level(&self) -> u64 {
+        self.spill_state
+            .as_ref()
+            .map_or(0, |spill_state| spill_state.level as u64)
+    }
+
+    /// Attach the runtime filters built from the keys of the build side to the context, whose
+    /// scan reads the probe side.
+    fn build_runtime_filters(&self, chunks: &[Chunk]) -> Result<()> {
+        let num_rows: usize = chunks.iter().map(|chunk| chunk.num_rows()).sum();
+        if num_rows > RUNTIME_FILTER_MAX_BUILD_ROWS {
+            return Ok(());
+        }
+
+        for desc in &self.hash_join_desc.runtime_filters {
+            let keys = chunks
+                .iter()
+                .map(|chunk| &chunk.cols[desc.build_key])
+                .collect::<Vec<_>>();
+            let filter = RuntimeFilter::try_create(desc.column_name.clone(), &keys)?;
+            self.ctx.set_runtime_filter(Arc::new(filter));
+        }
+        Ok(())
+    }
+
     fn find_unmatched_build_indexes(&self) -> Result<Vec<RowPtr>> {
         // For right/full join, build side will appear at least once in the joined table
         // Find the unmatched rows in build side
@@ -462,7 +531,7 @@ impl JoinHashTable {
 
 #[async_trait::async_trait]
 impl HashJoinState for JoinHashTable {
-    fn build(&self, input: DataBlock) -> Result<()> {
+    fn build(&self, input: DataBlock, spiller: &mut Option<Spiller>) -> Result<()> {
         let func_ctx = self.ctx.try_get_function_context()?;
         let build_cols = self
             .hash_join_desc
@@ -470,7 +539,34 @@ impl HashJoinState for JoinHashTable {
             .iter()
             .map(|expr| Ok(expr.eval(&func_ctx, &input)?.vector().clone()))
             .collect::<Result<Vec<ColumnRef>>>()?;
-        self.row_space.push_cols(input, build_cols)
+
+        let spill_state = match &self.spill_state {
+            None => return self.row_space.push_cols(input, build_cols),
+            Some(spill_state) => spill_state,
+        };
+
+        if !spill_state.is_spilled() {
+            if !spill_state.try_spill() {
+                return self.row_space.push_cols(input, build_cols);
+            }
+
+            // The query is close to its memory limit, spill all the blocks in memory
+            self.spill_chunks(spiller)?;
+        }
+
+        self.spill_block(spiller, &input, &build_cols)
+    }
+
+    fn add_build_spiller(&self, mut spiller: Spiller) -> Result<()> {
+        let spill_state = self
+            .spill_state
+            .as_ref()
+            .ok_or_else(|| ErrorCode::LogicalError("It's a bug. Hash join is not spilled"))?;
+
+        // Write to disk before taking the lock
+        spiller.flush()?;
+        spill_state.build_spillers.lock().push(spiller);
+        Ok(())
     }
 
     fn probe(&self, input: &DataBlock, probe_state: &mut ProbeState) -> Result<Vec<DataBlock>> {
@@ -512,6 +608,17 @@ impl HashJoinState for JoinHashTable {
     }
 
     fn finish(&self) -> Result<()> {
+        if self.is_spilled() {
+            // Blocks pushed to memory while another processor was spilling are spilled now,
+            // hash tables are built for each partition while probing
+            let mut spiller = None;
+            self.spill_chunks(&mut spiller)?;
+            if let Some(spiller) = spiller {
+                self.add_build_spiller(spiller)?;
+            }
+            return Ok(());
+        }
+
         macro_rules! insert_key {
             ($table: expr, $markers: expr, $method: expr, $chunk: expr, $columns: expr,  $chunk_index: expr, ) => {{
                 let keys_state = $method.build_keys_state(&$columns, $chunk.num_rows())?;
@@ -664,7 +771,7 @@ impl HashJoinState for JoinHashTable {
                 },
             }
         }
-        Ok(())
+        self.build_runtime_filters(&chunks)
     }
 
     async fn wait_finish(&self) -> Result<()> {
@@ -675,6 +782,62 @@ impl HashJoinState for JoinHashTable {
         Ok(())
     }
 
+    fn is_spilled(&self) -> bool {
+        self.spill_state
+            .as_ref()
+            .map_or(false, |spill_state| spill_state.is_spilled())
+    }
+
+    fn partition_probe_block(&self, input: &DataBlock) -> Result<Vec<DataBlock>> {
+        let func_ctx = self.ctx.try_get_function_context()?;
+        let probe_keys = self
+            .hash_join_desc
+            .probe_keys
+            .iter()
+            .map(|expr| Ok(expr.eval(&func_ctx, input)?.vector().clone()))
+            .collect::<Result<Vec<ColumnRef>>>()?;
+        let probe_keys = probe_keys.iter().collect::<Vec<&ColumnRef>>();
+        hash_partition(input, &probe_keys, SPILLED_PARTITIONS, self.spilled_level())
+    }
+
+    fn spilled_partition_state(&self, partition: usize) -> Result<Arc<dyn HashJoinState>> {
+        let spill_state = self
+            .spill_state
+            .as_ref()
+            .ok_or_else(|| ErrorCode::LogicalError("It's a bug. Hash join is not spilled"))?;
+
+        let mut partition_tables = spill_state.partition_tables.lock();
+        if let Some(table) = partition_tables[partition].upgrade() {
+            return Ok(table);
+        }
+
+        let table = Self::create_join_state_at_level(
+            self.ctx.clone(),
+            &spill_state.build_keys,
+            spill_state.build_schema.clone(),
+            spill_state.probe_schema.clone(),
+            self.hash_join_desc.for_partition(),
+            spill_state.level + 1,
+        )?;
+
+        // Build the partition file by file, it's split again if it doesn't fit in memory either
+        let mut spiller = None;
+        for build_spiller in spill_state.build_spillers.lock().iter() {
+            for file in build_spiller.files(partition) {
+                for block in build_spiller.read_file(file)? {
+                    table.build(block, &mut spiller)?;
+                }
+            }
+        }
+        if let Some(spiller) = spiller {
+            table.add_build_spiller(spiller)?;
+        }
+        table.finish()?;
+
+        partition_tables[partition] = Arc::downgrade(&table);
+        Ok(table)
+    }
+
     fn mark_join_blocks(&self) -> Result<Vec<DataBlock>> {
         let mut row_ptrs = self.row_ptrs.write();
         let has_null = self.hash_join_desc.marker_join_desc.has_null.read();
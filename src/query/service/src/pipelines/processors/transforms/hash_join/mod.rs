@@ -18,9 +18,11 @@ mod join_hash_table;
 mod probe_state;
 mod result_blocks;
 pub(crate) mod row;
+mod spill_state;
 mod util;
 
 pub use desc::HashJoinDesc;
+pub use desc::RuntimeFilterDesc;
 pub use hash_join_state::HashJoinState;
 pub use join_hash_table::HashTable;
 pub use join_hash_table::JoinHashTable;
@@ -35,3 +37,4 @@ pub use join_hash_table::MarkJoinDesc;
 pub use join_hash_table::SerializerHashTable;
 pub use probe_state::ProbeState;
 pub use result_blocks::*;
+pub use spill_state::SPILLED_PARTITIONS;
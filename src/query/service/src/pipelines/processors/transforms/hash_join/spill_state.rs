@@ -0,0 +1,116 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Weak;
+
+use common_base::base::RuntimeTracker;
+use common_datavalues::DataSchemaRef;
+use parking_lot::Mutex;
+
+use crate::pipelines::processors::transforms::hash_join::JoinHashTable;
+use crate::pipelines::processors::transforms::spiller::Spiller;
+use crate::sql::executor::PhysicalScalar;
+use crate::sql::planner::plans::JoinType;
+
+/// Number of partitions the build side and the probe side are split into, once spilled.
+pub const SPILLED_PARTITIONS: usize = 16;
+
+/// Max level of recursive partitioning.
+///
+/// A partition at this level is joined in memory even if it's too large, e.g. all its rows have the same key.
+pub const MAX_SPILLED_LEVEL: usize = 3;
+
+/// The build side spills once the memory usage of the query exceeds this percent of `max_query_memory_usage`,
+/// before the query is aborted for exceeding it.
+const SPILLING_MEMORY_PERCENT: i64 = 80;
+
+/// State of grace hash join.
+///
+/// Once the memory usage of the query is close to `max_query_memory_usage`, all the build blocks are split
+/// into partitions by the hash of the join keys, and spilled to disk. The probe side is split and spilled
+/// in the same way, then partitions are joined one by one, each with a hash table built from its build blocks.
+/// A partition that is still too large is split again with another hash, until `MAX_SPILLED_LEVEL`.
+///
+/// Right, full and mark joins track rows of the build side across all probe blocks, they never spill.
+pub struct HashJoinSpillState {
+    runtime_tracker: Arc<RuntimeTracker>,
+    memory_budget: i64,
+    /// Level of recursive partitioning, 0 for the join itself
+    pub(crate) level: usize,
+    /// Keys and schemas of the join, to create the hash tables of partitions
+    pub(crate) build_keys: Vec<PhysicalScalar>,
+    pub(crate) build_schema: DataSchemaRef,
+    pub(crate) probe_schema: DataSchemaRef,
+    spilled: AtomicBool,
+    /// Partitions of the build side, each build processor spills to its own spiller and adds it once finished
+    pub(crate) build_spillers: Mutex<Vec<Spiller>>,
+    /// Hash tables of partitions being joined, released once no probe processor holds them
+    pub(crate) partition_tables: Mutex<Vec<Weak<JoinHashTable>>>,
+}
+
+impl HashJoinSpillState {
+    /// Create the state if the join can spill, that is, the query has a memory limit and
+    /// the join doesn't track rows of the build side across probe blocks.
+    pub fn try_create(
+        runtime_tracker: Arc<RuntimeTracker>,
+        join_type: &JoinType,
+        level: usize,
+        build_keys: Vec<PhysicalScalar>,
+        build_schema: DataSchemaRef,
+        probe_schema: DataSchemaRef,
+    ) -> Option<Self> {
+        let limit = runtime_tracker.get_memory_tracker().get_limit();
+        if limit == 0 || level >= MAX_SPILLED_LEVEL || !Self::is_spillable(join_type) {
+            return None;
+        }
+
+        Some(HashJoinSpillState {
+            runtime_tracker,
+            memory_budget: limit / 100 * SPILLING_MEMORY_PERCENT,
+            level,
+            build_keys,
+            build_schema,
+            probe_schema,
+            spilled: AtomicBool::new(false),
+            build_spillers: Mutex::new(vec![]),
+            partition_tables: Mutex::new(vec![Weak::new(); SPILLED_PARTITIONS]),
+        })
+    }
+
+    fn is_spillable(join_type: &JoinType) -> bool {
+        matches!(
+            join_type,
+            JoinType::Inner | JoinType::Left | JoinType::Semi | JoinType::Anti | JoinType::Single
+        )
+    }
+
+    pub fn is_spilled(&self) -> bool {
+        self.spilled.load(Ordering::Acquire)
+    }
+
+    /// Mark the join as spilled if the memory usage of the query exceeds the budget.
+    ///
+    /// Returns true only for the caller that marks it, which spills the blocks already built in memory.
+    pub fn try_spill(&self) -> bool {
+        let memory_usage = self.runtime_tracker.get_memory_tracker().get_memory_usage();
+        memory_usage > self.memory_budget
+            && self
+                .spilled
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+    }
+}
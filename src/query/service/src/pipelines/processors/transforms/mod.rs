@@ -23,9 +23,11 @@ use common_pipeline_transforms::processors::transforms::transform_expression;
 use common_pipeline_transforms::processors::transforms::transform_sort_merge;
 use common_pipeline_transforms::processors::transforms::transform_sort_partial;
 mod transform_cast_schema;
+mod transform_collect;
 mod transform_create_sets;
 mod transform_dummy;
 mod transform_expression_v2;
+mod transform_external_sort;
 mod transform_filter;
 mod transform_filter_v2;
 mod transform_hash_join;
@@ -36,8 +38,11 @@ mod transform_project;
 mod transform_rename;
 
 pub mod group_by;
+pub(crate) mod spiller;
 mod transform_merge_block;
+mod transform_recursive_cte;
 mod transform_right_join;
+mod transform_window;
 
 pub use aggregator::AggregatorParams;
 pub use aggregator::AggregatorTransformParams;
@@ -53,12 +58,15 @@ pub use hash_join::KeyU32HashTable;
 pub use hash_join::KeyU512HashTable;
 pub use hash_join::KeyU64HashTable;
 pub use hash_join::KeyU8HashTable;
+pub use hash_join::RuntimeFilterDesc;
 pub use hash_join::SerializerHashTable;
 pub use transform_addon::TransformAddOn;
 pub use transform_aggregator::TransformAggregator;
 pub use transform_block_compact::BlockCompactor;
 pub use transform_block_compact::TransformBlockCompact;
 pub use transform_cast_schema::TransformCastSchema;
+pub use transform_collect::CollectedBlocks;
+pub use transform_collect::TransformCollect;
 pub use transform_compact::Compactor;
 pub use transform_compact::TransformCompact;
 pub use transform_create_sets::SubqueryReceiver;
@@ -67,6 +75,7 @@ pub use transform_dummy::TransformDummy;
 pub use transform_expression::ExpressionTransform;
 pub use transform_expression::ProjectionTransform;
 pub use transform_expression_v2::ExpressionTransformV2;
+pub use transform_external_sort::TransformExternalSort;
 pub use transform_filter::TransformFilter;
 pub use transform_filter::TransformHaving;
 pub use transform_filter_v2::TransformFilterV2;
@@ -78,6 +87,7 @@ pub use transform_mark_join::MarkJoinCompactor;
 pub use transform_mark_join::TransformMarkJoin;
 pub use transform_merge_block::TransformMergeBlock;
 pub use transform_project::TransformProject;
+pub use transform_recursive_cte::RecursiveCteSource;
 pub use transform_rename::TransformRename;
 pub use transform_right_join::RightJoinCompactor;
 pub use transform_right_join::TransformRightJoin;
@@ -85,3 +95,4 @@ pub use transform_sort_merge::SortMergeCompactor;
 pub use transform_sort_merge::TransformSortMerge;
 pub use transform_sort_partial::get_sort_descriptions;
 pub use transform_sort_partial::TransformSortPartial;
+pub use transform_window::TransformWindow;
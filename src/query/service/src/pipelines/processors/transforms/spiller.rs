@@ -0,0 +1,216 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::Path;
+use std::path::PathBuf;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::ipc::read::read_file_metadata;
+use common_arrow::arrow::io::ipc::read::FileReader;
+use common_arrow::arrow::io::ipc::write::FileWriter;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::pipelines::processors::ProcessorProfile;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+// sub directory of the table disk cache root, for spilled data of queries
+static SPILL_DIR: &str = "spill";
+
+// buffered blocks of a partition are written to a file once they exceed this size, 8M
+static SPILL_FILE_SIZE: usize = 8 * 1024 * 1024;
+
+/// Spills data blocks of an operator to local disk, and reads them back.
+///
+/// Blocks are grouped into partitions, buffered in memory and written as arrow IPC files.
+/// Files are in `<table_disk_cache_root>/spill/<query_id>/<name>-<uuid>`, and removed when the spiller is dropped.
+pub struct Spiller {
+    dir: PathBuf,
+    schema: DataSchemaRef,
+    partitions: Vec<SpilledPartition>,
+    next_file: usize,
+}
+
+#[derive(Default)]
+struct SpilledPartition {
+    buffered: Vec<DataBlock>,
+    buffered_bytes: usize,
+    files: Vec<PathBuf>,
+}
+
+impl Spiller {
+    pub fn create(
+        ctx: &QueryContext,
+        name: &str,
+        schema: DataSchemaRef,
+        num_partitions: usize,
+    ) -> Result<Spiller> {
+        let dir = Path::new(&ctx.get_config().query.table_disk_cache_root)
+            .join(SPILL_DIR)
+            .join(ctx.get_id())
+            .join(format!("{}-{}", name, Uuid::new_v4().simple()));
+        fs::create_dir_all(&dir)?;
+
+        Ok(Spiller {
+            dir,
+            schema,
+            partitions: (0..num_partitions)
+                .map(|_| SpilledPartition::default())
+                .collect(),
+            next_file: 0,
+        })
+    }
+
+    pub fn schema(&self) -> &DataSchemaRef {
+        &self.schema
+    }
+
+    pub fn num_partitions(&self) -> usize {
+        self.partitions.len()
+    }
+
+    /// Add a new empty partition, returns its index.
+    pub fn add_partition(&mut self) -> usize {
+        self.partitions.push(SpilledPartition::default());
+        self.partitions.len() - 1
+    }
+
+    /// Append a block to a partition.
+    pub fn spill(&mut self, partition: usize, block: DataBlock) -> Result<()> {
+        if block.is_empty() {
+            return Ok(());
+        }
+
+        let p = &mut self.partitions[partition];
+        p.buffered_bytes += block.memory_size();
+        p.buffered.push(block);
+        if p.buffered_bytes >= SPILL_FILE_SIZE {
+            self.flush_partition(partition)?;
+        }
+        Ok(())
+    }
+
+    /// Write all buffered blocks to disk.
+    pub fn flush(&mut self) -> Result<()> {
+        for partition in 0..self.partitions.len() {
+            self.flush_partition(partition)?;
+        }
+        Ok(())
+    }
+
+    /// Files of a partition, in the order they are written. Buffered blocks are not included.
+    pub fn files(&self, partition: usize) -> &[PathBuf] {
+        &self.partitions[partition].files
+    }
+
+    /// Read all blocks of a partition, including the buffered ones.
+    pub fn read_partition(&self, partition: usize) -> Result<Vec<DataBlock>> {
+        let p = &self.partitions[partition];
+        let mut blocks = vec![];
+        for file in &p.files {
+            blocks.extend(self.read_file(file)?);
+        }
+        blocks.extend(p.buffered.iter().cloned());
+        Ok(blocks)
+    }
+
+    /// Read blocks of a spilled file.
+    pub fn read_file(&self, file: &Path) -> Result<Vec<DataBlock>> {
+        let mut reader = BufReader::new(File::open(file)?);
+        let metadata = read_file_metadata(&mut reader)?;
+        let reader = FileReader::new(reader, metadata, None, None);
+
+        let mut blocks = vec![];
+        for chunk in reader {
+            blocks.push(DataBlock::from_chunk(&self.schema, &chunk?)?);
+        }
+        Ok(blocks)
+    }
+
+    fn flush_partition(&mut self, partition: usize) -> Result<()> {
+        let blocks = std::mem::take(&mut self.partitions[partition].buffered);
+        let bytes = std::mem::take(&mut self.partitions[partition].buffered_bytes);
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.dir.join(format!("{}-{}", partition, self.next_file));
+        self.next_file += 1;
+
+        let options = WriteOptions { compression: None };
+        let file = BufWriter::new(File::create(&path)?);
+        let mut writer = FileWriter::new(file, self.schema.to_arrow(), None, options);
+        writer.start()?;
+        for block in blocks {
+            let chunk = Chunk::try_from(block)?;
+            writer.write(&chunk, None)?;
+        }
+        writer.finish()?;
+
+        self.partitions[partition].files.push(path);
+        ProcessorProfile::record_spill(bytes);
+        Ok(())
+    }
+}
+
+impl Drop for Spiller {
+    fn drop(&mut self) {
+        if let Err(cause) = fs::remove_dir_all(&self.dir) {
+            warn!("failed to remove spill directory {:?}: {}", self.dir, cause);
+        }
+    }
+}
+
+/// Split a block into `num_partitions` blocks by the hash of `keys`, seeded with `seed`.
+///
+/// Rows with equal keys always go to the same partition.
+/// Rows of one partition are spread across all partitions with a different seed.
+pub fn hash_partition(
+    block: &DataBlock,
+    keys: &[&ColumnRef],
+    num_partitions: usize,
+    seed: u64,
+) -> Result<Vec<DataBlock>> {
+    let method = HashMethodSerializer::default();
+    let keys_state = method.build_keys_state(keys, block.num_rows())?;
+    let keys_iter = method.build_keys_iter(&keys_state)?;
+
+    let mut indices = vec![vec![]; num_partitions];
+    for (row, key) in keys_iter.enumerate().take(block.num_rows()) {
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(seed);
+        hasher.write(key);
+        let partition = (hasher.finish() % num_partitions as u64) as usize;
+        indices[partition].push(row as u32);
+    }
+
+    indices
+        .iter()
+        .map(|indices| DataBlock::block_take_by_indices(block, indices))
+        .collect()
+}
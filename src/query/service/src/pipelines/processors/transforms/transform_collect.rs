@@ -0,0 +1,93 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::transforms::transform::Transform;
+use crate::pipelines::processors::transforms::transform::Transformer;
+
+/// The blocks kept by `TransformCollect`, up to `max_bytes` in memory.
+///
+/// Once the limit is exceeded, the blocks kept are dropped and no more are collected.
+pub struct CollectedBlocks {
+    blocks: Vec<DataBlock>,
+    bytes: usize,
+    max_bytes: usize,
+    exceeded: bool,
+}
+
+impl CollectedBlocks {
+    pub fn new(max_bytes: usize) -> Self {
+        CollectedBlocks {
+            blocks: vec![],
+            bytes: 0,
+            max_bytes,
+            exceeded: false,
+        }
+    }
+
+    fn push(&mut self, block: &DataBlock) {
+        if self.exceeded {
+            return;
+        }
+        self.bytes += block.memory_size();
+        if self.bytes > self.max_bytes {
+            self.exceeded = true;
+            self.blocks = vec![];
+            return;
+        }
+        self.blocks.push(block.clone());
+    }
+
+    /// Take the blocks collected, or `None` if the limit was exceeded.
+    pub fn take(&mut self) -> Option<Vec<DataBlock>> {
+        match self.exceeded {
+            true => None,
+            false => Some(std::mem::take(&mut self.blocks)),
+        }
+    }
+}
+
+/// Passes data blocks through unchanged, and keeps a copy of every block in a shared buffer.
+pub struct TransformCollect {
+    blocks: Arc<Mutex<CollectedBlocks>>,
+}
+
+impl TransformCollect {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        blocks: Arc<Mutex<CollectedBlocks>>,
+    ) -> Result<ProcessorPtr> {
+        Ok(Transformer::create(input, output, TransformCollect {
+            blocks,
+        }))
+    }
+}
+
+impl Transform for TransformCollect {
+    const NAME: &'static str = "CollectTransform";
+
+    fn transform(&mut self, data: DataBlock) -> Result<DataBlock> {
+        self.blocks.lock().unwrap().push(&data);
+        Ok(data)
+    }
+}
@@ -27,6 +27,8 @@ use crate::pipelines::processors::transforms::transform::Transform;
 use crate::pipelines::processors::transforms::transform::Transformer;
 
 pub struct ExpressionTransformV2 {
+    // The subexpressions shared by the expressions, evaluated into temporary columns first.
+    pub(crate) common_expressions: Vec<(EvalNode, String)>,
     pub(crate) expressions: Vec<(EvalNode, String)>,
     pub(crate) func_ctx: FunctionContext,
 }
@@ -35,10 +37,12 @@ impl ExpressionTransformV2 {
     pub fn create(
         input: Arc<InputPort>,
         output: Arc<OutputPort>,
+        common_expressions: Vec<(EvalNode, String)>,
         expressions: Vec<(EvalNode, String)>,
         func_ctx: FunctionContext,
     ) -> ProcessorPtr {
         Transformer::create(input, output, Self {
+            common_expressions,
             expressions,
             func_ctx,
         })
@@ -49,13 +53,20 @@ impl Transform for ExpressionTransformV2 {
     const NAME: &'static str = "Expression";
 
     fn transform(&mut self, mut data: DataBlock) -> Result<DataBlock> {
-        for (eval, output_name) in self.expressions.iter() {
+        for (eval, output_name) in self
+            .common_expressions
+            .iter()
+            .chain(self.expressions.iter())
+        {
             let typed_vector = eval.eval(&self.func_ctx, &data)?;
             let data_field = DataField::new(output_name.as_str(), typed_vector.logical_type());
             let column = typed_vector.vector().clone();
             data = data.add_column(column, data_field)?;
         }
 
+        for (_, name) in self.common_expressions.iter() {
+            data = data.remove_column(name)?;
+        }
         Ok(data)
     }
 }
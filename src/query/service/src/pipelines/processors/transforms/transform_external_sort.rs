@@ -0,0 +1,334 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::DataValue;
+use common_exception::Result;
+
+use super::spiller::Spiller;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+enum SortStep {
+    Consume,
+    Merge,
+    Finished,
+}
+
+/// A sorted run spilled to disk, read back one file at a time.
+struct SortedRun {
+    files: VecDeque<PathBuf>,
+    blocks: VecDeque<DataBlock>,
+}
+
+/// Merge sorted blocks into one, spilling to disk if they don't fit in memory.
+///
+/// Input blocks are buffered until their size exceeds `spilling_memory_threshold`, then they are
+/// merged into a sorted run and written to local disk. Once the input is finished, the runs are
+/// k-way merged, holding at most one file of each run in memory.
+pub struct TransformExternalSort {
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    input_data: Option<DataBlock>,
+    output_data: Option<DataBlock>,
+    step: SortStep,
+
+    ctx: Arc<QueryContext>,
+    limit: Option<usize>,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    spilling_threshold: usize,
+    max_block_size: usize,
+
+    buffered: Vec<DataBlock>,
+    buffered_bytes: usize,
+    spiller: Option<Spiller>,
+    runs: Vec<SortedRun>,
+    output_rows: usize,
+}
+
+impl TransformExternalSort {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        ctx: Arc<QueryContext>,
+        limit: Option<usize>,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+    ) -> Result<ProcessorPtr> {
+        let settings = ctx.get_settings();
+        let spilling_threshold = settings.get_spilling_memory_threshold()? as usize;
+        let max_block_size = settings.get_max_block_size()? as usize;
+
+        Ok(ProcessorPtr::create(Box::new(TransformExternalSort {
+            input,
+            output,
+            input_data: None,
+            output_data: None,
+            step: SortStep::Consume,
+            ctx,
+            limit,
+            sort_columns_descriptions,
+            spilling_threshold,
+            max_block_size,
+            buffered: vec![],
+            buffered_bytes: 0,
+            spiller: None,
+            runs: vec![],
+            output_rows: 0,
+        })))
+    }
+
+    fn consume(&mut self, block: DataBlock) -> Result<()> {
+        self.buffered_bytes += block.memory_size();
+        self.buffered.push(block);
+
+        if self.spilling_threshold > 0 && self.buffered_bytes > self.spilling_threshold {
+            self.spill_run()?;
+        }
+        Ok(())
+    }
+
+    fn finish_consume(&mut self) -> Result<()> {
+        if self.spiller.is_none() {
+            if !self.buffered.is_empty() {
+                let blocks = std::mem::take(&mut self.buffered);
+                self.output_data = Some(DataBlock::merge_sort_blocks(
+                    &blocks,
+                    &self.sort_columns_descriptions,
+                    self.limit,
+                )?);
+            }
+            self.step = SortStep::Finished;
+            return Ok(());
+        }
+
+        self.spill_run()?;
+        let spiller = self.spiller.as_mut().unwrap();
+        spiller.flush()?;
+        self.runs = (0..spiller.num_partitions())
+            .map(|run| SortedRun {
+                files: spiller.files(run).iter().cloned().collect(),
+                blocks: VecDeque::new(),
+            })
+            .collect();
+        self.step = SortStep::Merge;
+        Ok(())
+    }
+
+    /// Merge the buffered blocks into a sorted run, and spill it as a new partition of the spiller.
+    fn spill_run(&mut self) -> Result<()> {
+        if self.buffered.is_empty() {
+            return Ok(());
+        }
+
+        let blocks = std::mem::take(&mut self.buffered);
+        self.buffered_bytes = 0;
+        let run =
+            DataBlock::merge_sort_blocks(&blocks, &self.sort_columns_descriptions, self.limit)?;
+
+        if self.spiller.is_none() {
+            self.spiller = Some(Spiller::create(&self.ctx, "sort", run.schema().clone(), 0)?);
+        }
+
+        let spiller = self.spiller.as_mut().unwrap();
+        let partition = spiller.add_partition();
+        for block in DataBlock::split_block_by_size(&run, self.max_block_size)? {
+            spiller.spill(partition, block)?;
+        }
+        Ok(())
+    }
+
+    /// Merge the heads of all the runs, up to the smallest last row among them.
+    ///
+    /// All rows not greater than that bound are already in memory, so they can be output in order.
+    fn merge_next(&mut self) -> Result<Option<DataBlock>> {
+        if let Some(limit) = self.limit {
+            if self.output_rows >= limit {
+                return Ok(None);
+            }
+        }
+
+        let spiller = self.spiller.as_ref().unwrap();
+        for run in self.runs.iter_mut() {
+            while run.blocks.is_empty() {
+                match run.files.pop_front() {
+                    None => break,
+                    Some(file) => run.blocks.extend(spiller.read_file(&file)?),
+                }
+            }
+        }
+        self.runs.retain(|run| !run.blocks.is_empty());
+
+        if self.runs.is_empty() {
+            return Ok(None);
+        }
+
+        let mut bound: Option<Vec<DataValue>> = None;
+        for run in &self.runs {
+            let head = run.blocks.front().unwrap();
+            let last = self.sort_key(head, head.num_rows() - 1)?;
+            if bound.is_none() || self.compare(&last, bound.as_ref().unwrap()) == Ordering::Less {
+                bound = Some(last);
+            }
+        }
+        let bound = bound.unwrap();
+
+        let mut merged = vec![];
+        for run_index in 0..self.runs.len() {
+            let head = self.runs[run_index].blocks.front().unwrap();
+            let rows = self.rows_not_greater(head, &bound)?;
+            if rows == 0 {
+                continue;
+            }
+
+            let run = &mut self.runs[run_index];
+            let head = run.blocks.pop_front().unwrap();
+            if rows < head.num_rows() {
+                run.blocks
+                    .push_front(head.slice(rows, head.num_rows() - rows));
+            }
+            merged.push(head.slice(0, rows));
+        }
+
+        let mut block =
+            DataBlock::merge_sort_blocks(&merged, &self.sort_columns_descriptions, None)?;
+        if let Some(limit) = self.limit {
+            block = block.slice(0, limit - self.output_rows);
+        }
+        self.output_rows += block.num_rows();
+        Ok(Some(block))
+    }
+
+    fn sort_key(&self, block: &DataBlock, row: usize) -> Result<Vec<DataValue>> {
+        self.sort_columns_descriptions
+            .iter()
+            .map(|desc| Ok(block.try_column_by_name(&desc.column_name)?.get(row)))
+            .collect()
+    }
+
+    /// Number of leading rows of a sorted block, which are not greater than `bound`.
+    fn rows_not_greater(&self, block: &DataBlock, bound: &[DataValue]) -> Result<usize> {
+        let (mut lo, mut hi) = (0, block.num_rows());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.compare(&self.sort_key(block, mid)?, bound) {
+                Ordering::Greater => hi = mid,
+                _ => lo = mid + 1,
+            }
+        }
+        Ok(lo)
+    }
+
+    fn compare(&self, lhs: &[DataValue], rhs: &[DataValue]) -> Ordering {
+        for ((l, r), desc) in lhs
+            .iter()
+            .zip(rhs.iter())
+            .zip(self.sort_columns_descriptions.iter())
+        {
+            let ordering = match (l.is_null(), r.is_null()) {
+                (true, true) => Ordering::Equal,
+                (true, false) if desc.nulls_first => Ordering::Less,
+                (true, false) => Ordering::Greater,
+                (false, true) if desc.nulls_first => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) if desc.asc => l.cmp(r),
+                (false, false) => r.cmp(l),
+            };
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl Processor for TransformExternalSort {
+    fn name(&self) -> &'static str {
+        "TransformExternalSort"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(output_data) = self.output_data.take() {
+            self.output.push_data(Ok(output_data));
+            return Ok(Event::NeedConsume);
+        }
+
+        match self.step {
+            SortStep::Consume => {
+                if self.input_data.is_some() || self.input.is_finished() {
+                    return Ok(Event::Sync);
+                }
+
+                if self.input.has_data() {
+                    self.input_data = Some(self.input.pull_data().unwrap()?);
+                    return Ok(Event::Sync);
+                }
+
+                self.input.set_need_data();
+                Ok(Event::NeedData)
+            }
+            SortStep::Merge => Ok(Event::Sync),
+            SortStep::Finished => {
+                self.output.finish();
+                Ok(Event::Finished)
+            }
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match self.step {
+            SortStep::Consume => match self.input_data.take() {
+                Some(block) => self.consume(block),
+                None => self.finish_consume(),
+            },
+            SortStep::Merge => {
+                match self.merge_next()? {
+                    Some(block) => self.output_data = Some(block),
+                    None => {
+                        self.runs.clear();
+                        self.spiller = None;
+                        self.step = SortStep::Finished;
+                    }
+                }
+                Ok(())
+            }
+            SortStep::Finished => Ok(()),
+        }
+    }
+}
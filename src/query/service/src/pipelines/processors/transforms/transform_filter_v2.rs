@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
+use common_datavalues::DataField;
 use common_exception::Result;
 use common_functions::scalars::FunctionContext;
 
@@ -26,6 +27,8 @@ use crate::pipelines::processors::transforms::transform::Transform;
 use crate::pipelines::processors::transforms::transform::Transformer;
 
 pub struct TransformFilterV2 {
+    // The subexpressions shared by the predicate, evaluated into temporary columns first.
+    common_expressions: Vec<(EvalNode, String)>,
     predicate: EvalNode,
     func_ctx: FunctionContext,
 }
@@ -34,10 +37,12 @@ impl TransformFilterV2 {
     pub fn try_create(
         input: Arc<InputPort>,
         output: Arc<OutputPort>,
+        common_expressions: Vec<(EvalNode, String)>,
         predicate: EvalNode,
         func_ctx: FunctionContext,
     ) -> Result<ProcessorPtr> {
         Ok(Transformer::create(input, output, Self {
+            common_expressions,
             predicate,
             func_ctx,
         }))
@@ -48,7 +53,14 @@ impl Transform for TransformFilterV2 {
     const NAME: &'static str = "Filter";
 
     fn transform(&mut self, data: DataBlock) -> Result<DataBlock> {
-        let typed_vector = self.predicate.eval(&self.func_ctx, &data)?;
+        let mut evaluated = data.clone();
+        for (eval, name) in self.common_expressions.iter() {
+            let typed_vector = eval.eval(&self.func_ctx, &evaluated)?;
+            let data_field = DataField::new(name.as_str(), typed_vector.logical_type());
+            evaluated = evaluated.add_column(typed_vector.vector().clone(), data_field)?;
+        }
+
+        let typed_vector = self.predicate.eval(&self.func_ctx, &evaluated)?;
         let column = typed_vector.vector();
         DataBlock::filter_block(data, column)
     }
@@ -21,11 +21,13 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 
 use super::hash_join::ProbeState;
+use super::spiller::Spiller;
 use crate::pipelines::processors::port::InputPort;
 use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::processor::Event;
 use crate::pipelines::processors::processor::ProcessorPtr;
 use crate::pipelines::processors::transforms::hash_join::HashJoinState;
+use crate::pipelines::processors::transforms::hash_join::SPILLED_PARTITIONS;
 use crate::pipelines::processors::Processor;
 use crate::pipelines::processors::Sink;
 use crate::sessions::QueryContext;
@@ -33,12 +35,17 @@ use crate::sessions::TableContext;
 
 pub struct SinkBuildHashTable {
     join_state: Arc<dyn HashJoinState>,
+    /// Partitions of the build input, if the build side is spilled
+    spiller: Option<Spiller>,
 }
 
 impl SinkBuildHashTable {
     pub fn try_create(join_state: Arc<dyn HashJoinState>) -> Result<Self> {
         join_state.attach()?;
-        Ok(Self { join_state })
+        Ok(Self {
+            join_state,
+            spiller: None,
+        })
     }
 }
 
@@ -46,20 +53,34 @@ impl Sink for SinkBuildHashTable {
     const NAME: &'static str = "BuildHashTable";
 
     fn on_finish(&mut self) -> Result<()> {
+        if let Some(spiller) = self.spiller.take() {
+            self.join_state.add_build_spiller(spiller)?;
+        }
         self.join_state.detach()
     }
 
     fn consume(&mut self, data_block: DataBlock) -> Result<()> {
-        self.join_state.build(data_block)
+        self.join_state.build(data_block, &mut self.spiller)
     }
 }
 
 enum HashJoinStep {
     Build,
     Probe,
+    /// Join the spilled partitions, after all the probe input is consumed
+    ProbeSpilled,
+}
+
+/// Spilled partitions of the probe side at one level of recursive partitioning.
+struct SpilledLevel {
+    /// The spilled state, whose partitions are joined one by one
+    join_state: Arc<dyn HashJoinState>,
+    spiller: Spiller,
+    next_partition: usize,
 }
 
 pub struct TransformHashJoinProbe {
+    ctx: Arc<QueryContext>,
     input_data: Option<DataBlock>,
     output_data_blocks: VecDeque<DataBlock>,
 
@@ -68,6 +89,13 @@ pub struct TransformHashJoinProbe {
     step: HashJoinStep,
     join_state: Arc<dyn HashJoinState>,
     probe_state: ProbeState,
+
+    /// Partitions of the probe input, if the build side is spilled
+    spiller: Option<Spiller>,
+    /// Levels of spilled partitions to join, a partition that is spilled again adds a level
+    spilled_levels: Vec<SpilledLevel>,
+    /// The spilled partition being joined, and its remaining probe blocks
+    spilled_partition: Option<(Arc<dyn HashJoinState>, VecDeque<DataBlock>)>,
 }
 
 impl TransformHashJoinProbe {
@@ -80,6 +108,7 @@ impl TransformHashJoinProbe {
     ) -> ProcessorPtr {
         let default_block_size = ctx.get_settings().get_max_block_size().unwrap_or(102400);
         ProcessorPtr::create(Box::new(TransformHashJoinProbe {
+            ctx,
             input_data: None,
             output_data_blocks: VecDeque::new(),
             input_port,
@@ -87,15 +116,132 @@ impl TransformHashJoinProbe {
             step: HashJoinStep::Build,
             join_state,
             probe_state: ProbeState::with_capacity(default_block_size as usize),
+            spiller: None,
+            spilled_levels: vec![],
+            spilled_partition: None,
         }))
     }
 
     fn probe(&mut self, block: &DataBlock) -> Result<()> {
+        if self.join_state.is_spilled() {
+            return self.spill(block);
+        }
+
         self.probe_state.clear();
         self.output_data_blocks
             .extend(self.join_state.probe(block, &mut self.probe_state)?);
         Ok(())
     }
+
+    fn spill(&mut self, block: &DataBlock) -> Result<()> {
+        if self.spiller.is_none() {
+            self.spiller = Some(Spiller::create(
+                &self.ctx,
+                "hash_join_probe",
+                block.schema().clone(),
+                SPILLED_PARTITIONS,
+            )?);
+        }
+
+        if let Some(spiller) = self.spiller.as_mut() {
+            Self::spill_partitions(spiller, self.join_state.as_ref(), block)?;
+        }
+        Ok(())
+    }
+
+    /// Split a probe block in the same way as the build side of `join_state`, and append them to the spiller.
+    fn spill_partitions(
+        spiller: &mut Spiller,
+        join_state: &dyn HashJoinState,
+        block: &DataBlock,
+    ) -> Result<()> {
+        let partitions = join_state.partition_probe_block(block)?;
+        for (partition, block) in partitions.into_iter().enumerate() {
+            spiller.spill(partition, block)?;
+        }
+        Ok(())
+    }
+
+    /// Start to join the spilled partitions, once all the probe input is spilled.
+    fn finish_spilling(&mut self) {
+        if let Some(spiller) = self.spiller.take() {
+            self.spilled_levels.push(SpilledLevel {
+                join_state: self.join_state.clone(),
+                spiller,
+                next_partition: 0,
+            });
+        }
+    }
+
+    fn has_spilled_data(&self) -> bool {
+        let remaining_blocks = self
+            .spilled_partition
+            .as_ref()
+            .map_or(false, |(_, blocks)| !blocks.is_empty());
+        let remaining_partitions = self
+            .spilled_levels
+            .iter()
+            .any(|level| level.next_partition < level.spiller.num_partitions());
+        remaining_blocks || remaining_partitions
+    }
+
+    /// Probe a spilled block with the hash table of its partition, or load the next partition.
+    fn probe_spilled(&mut self) -> Result<()> {
+        if let Some((state, blocks)) = self.spilled_partition.as_mut() {
+            if let Some(block) = blocks.pop_front() {
+                self.probe_state.clear();
+                self.output_data_blocks
+                    .extend(state.probe(&block, &mut self.probe_state)?);
+                return Ok(());
+            }
+        }
+
+        // Release the hash table of the finished partition before building the next one
+        self.spilled_partition = None;
+
+        let level = match self.spilled_levels.last_mut() {
+            None => return Ok(()),
+            Some(level) => level,
+        };
+        if level.next_partition >= level.spiller.num_partitions() {
+            self.spilled_levels.pop();
+            return Ok(());
+        }
+
+        let partition = level.next_partition;
+        level.next_partition += 1;
+
+        let blocks = level.spiller.read_partition(partition)?;
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let state = level.join_state.spilled_partition_state(partition)?;
+        if !state.is_spilled() {
+            self.spilled_partition = Some((state, blocks.into()));
+            return Ok(());
+        }
+
+        // The build blocks of the partition don't fit in memory and are split again,
+        // split its probe blocks in the same way
+        let mut spiller = Spiller::create(
+            &self.ctx,
+            "hash_join_probe",
+            blocks[0].schema().clone(),
+            SPILLED_PARTITIONS,
+        )?;
+        for block in blocks.iter() {
+            Self::spill_partitions(&mut spiller, state.as_ref(), block)?;
+        }
+        spiller.flush()?;
+
+        self.spilled_levels.push(SpilledLevel {
+            join_state: state,
+            spiller,
+            next_partition: 0,
+        });
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -139,6 +285,12 @@ impl Processor for TransformHashJoinProbe {
                 }
 
                 if self.input_port.is_finished() {
+                    self.finish_spilling();
+                    if self.has_spilled_data() {
+                        self.step = HashJoinStep::ProbeSpilled;
+                        return Ok(Event::Sync);
+                    }
+
                     self.output_port.finish();
                     return Ok(Event::Finished);
                 }
@@ -146,6 +298,28 @@ impl Processor for TransformHashJoinProbe {
                 self.input_port.set_need_data();
                 Ok(Event::NeedData)
             }
+            HashJoinStep::ProbeSpilled => {
+                if self.output_port.is_finished() {
+                    return Ok(Event::Finished);
+                }
+
+                if !self.output_port.can_push() {
+                    return Ok(Event::NeedConsume);
+                }
+
+                if !self.output_data_blocks.is_empty() {
+                    let data = self.output_data_blocks.pop_front().unwrap();
+                    self.output_port.push_data(Ok(data));
+                    return Ok(Event::NeedConsume);
+                }
+
+                if self.has_spilled_data() {
+                    return Ok(Event::Sync);
+                }
+
+                self.output_port.finish();
+                Ok(Event::Finished)
+            }
         }
     }
 
@@ -158,6 +332,7 @@ impl Processor for TransformHashJoinProbe {
                 }
                 Ok(())
             }
+            HashJoinStep::ProbeSpilled => self.probe_spilled(),
         }
     }
 
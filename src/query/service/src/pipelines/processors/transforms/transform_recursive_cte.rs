@@ -0,0 +1,161 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_pipeline_core::processors::port::OutputPort;
+use common_pipeline_core::processors::processor::ProcessorPtr;
+
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelinePullingExecutor;
+use crate::pipelines::processors::SyncSource;
+use crate::pipelines::processors::SyncSourcer;
+use crate::sessions::QueryContext;
+use crate::sql::executor::PhysicalPlan;
+use crate::sql::executor::PipelineBuilder;
+use crate::sql::executor::RecursiveUnion;
+
+/// Evaluates a recursive CTE iteratively, each call of `generate` outputs the rows of one
+/// iteration.
+///
+/// The anchor term is evaluated first, then the recursive term is evaluated with the rows of the
+/// last iteration as the working table, until an iteration produces no rows.
+pub struct RecursiveCteSource {
+    ctx: Arc<QueryContext>,
+    plan: RecursiveUnion,
+    max_depth: u64,
+
+    // The number of finished iterations, including the one of the anchor term
+    iterations: u64,
+    finished: bool,
+    // Rows already produced, only used by UNION
+    seen: HashSet<Vec<DataValue>>,
+}
+
+impl RecursiveCteSource {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        output: Arc<OutputPort>,
+        plan: RecursiveUnion,
+    ) -> Result<ProcessorPtr> {
+        let max_depth = ctx.get_settings().get_cte_max_recursion_depth()?;
+        let ctx = QueryContext::create_recursive_cte_scope(ctx);
+        SyncSourcer::create(ctx.clone(), output, RecursiveCteSource {
+            ctx,
+            plan,
+            max_depth,
+            iterations: 0,
+            finished: false,
+            seen: HashSet::new(),
+        })
+    }
+
+    fn execute(&self, plan: &PhysicalPlan) -> Result<Vec<DataBlock>> {
+        let ctx = QueryContext::create_from(self.ctx.clone());
+        let build_res = PipelineBuilder::create(ctx.clone()).finalize(plan)?;
+        let settings = ExecutorSettings::try_create(&ctx)?;
+        let mut executor = PipelinePullingExecutor::from_pipelines(build_res, settings)?;
+
+        executor.start();
+        let mut blocks = vec![];
+        while let Some(block) = executor.pull_data()? {
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    fn project_block(&self, block: &DataBlock, anchor: bool) -> Result<DataBlock> {
+        let columns = self
+            .plan
+            .pairs
+            .iter()
+            .map(|(left, right)| {
+                let name = if anchor { left } else { right };
+                Ok(block.try_column_by_name(name)?.clone())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(DataBlock::create(self.plan.schema.clone(), columns))
+    }
+
+    // Drop the rows produced before, which ends the cycles of the recursion.
+    fn distinct_block(&mut self, block: DataBlock) -> Result<DataBlock> {
+        let mut predicate = Vec::with_capacity(block.num_rows());
+        for row in 0..block.num_rows() {
+            let values = block
+                .columns()
+                .iter()
+                .map(|column| column.get(row))
+                .collect::<Vec<_>>();
+            predicate.push(self.seen.insert(values));
+        }
+
+        let predicate = BooleanColumn::from_slice(&predicate);
+        DataBlock::filter_block_with_bool_column(block, &predicate)
+    }
+}
+
+impl SyncSource for RecursiveCteSource {
+    const NAME: &'static str = "RecursiveCteSource";
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let anchor = self.iterations == 0;
+        let plan = match anchor {
+            true => &self.plan.anchor,
+            false => &self.plan.recursive,
+        };
+        let blocks = self
+            .execute(plan)?
+            .iter()
+            .map(|block| self.project_block(block, anchor))
+            .collect::<Result<Vec<_>>>()?;
+        self.iterations += 1;
+
+        let mut block = match blocks.is_empty() {
+            true => DataBlock::empty_with_schema(self.plan.schema.clone()),
+            false => DataBlock::concat_blocks(&blocks)?,
+        };
+        if self.plan.distinct {
+            block = self.distinct_block(block)?;
+        }
+
+        if block.num_rows() > 0 && self.iterations > self.max_depth + 1 {
+            return Err(ErrorCode::RecursionDepthExceeded(format!(
+                "Recursive CTE {} exceeds the maximum recursion depth {}, \
+                 try to increase the setting cte_max_recursion_depth",
+                self.plan.cte_name, self.max_depth
+            )));
+        }
+
+        if block.num_rows() == 0 {
+            self.finished = true;
+            self.ctx
+                .set_recursive_cte_table(&self.plan.cte_name, vec![]);
+            return Ok(None);
+        }
+
+        self.ctx
+            .set_recursive_cte_table(&self.plan.cte_name, vec![block.clone()]);
+        Ok(Some(block))
+    }
+}
@@ -0,0 +1,436 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::ops::Range;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_datavalues::MutableColumn;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::aggregates::StateAddr;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+use crate::sql::executor::ColumnID;
+use crate::sql::executor::WindowFunctionDesc;
+use crate::sql::plans::WindowFuncFrame;
+use crate::sql::plans::WindowFuncFrameBound;
+use crate::sql::plans::WindowFuncFrameUnits;
+use crate::sql::plans::WindowFuncType;
+
+/// Evaluate a window function over its input, which is sorted by the partition keys and then the
+/// order keys, in single thread.
+///
+/// Only the rows of the last partition are buffered, the partitions before it are complete and
+/// output with the result of the function appended as a new column.
+pub struct TransformWindow {
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    input_data: Option<DataBlock>,
+    output_data: Option<DataBlock>,
+
+    func: WindowFunctionDesc,
+    aggregate: Option<AggregateFunctionRef>,
+    partition_by: Vec<ColumnID>,
+    order_by: Vec<ColumnID>,
+    frame: WindowFuncFrame,
+
+    /// Rows of the last partition, which may continue in the next blocks
+    pending: Vec<DataBlock>,
+}
+
+impl TransformWindow {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        func: WindowFunctionDesc,
+        partition_by: Vec<ColumnID>,
+        order_by: Vec<ColumnID>,
+        frame: WindowFuncFrame,
+    ) -> Result<ProcessorPtr> {
+        let aggregate = match &func.func {
+            WindowFuncType::Aggregate { func_name, params } => {
+                let arguments = func
+                    .args
+                    .iter()
+                    .zip(func.arg_types.iter())
+                    .map(|(arg, data_type)| DataField::new(arg, data_type.clone()))
+                    .collect();
+                let factory = AggregateFunctionFactory::instance();
+                Some(factory.get(func_name, params.clone(), arguments)?)
+            }
+            _ => None,
+        };
+
+        Ok(ProcessorPtr::create(Box::new(TransformWindow {
+            input,
+            output,
+            input_data: None,
+            output_data: None,
+            func,
+            aggregate,
+            partition_by,
+            order_by,
+            frame,
+            pending: vec![],
+        })))
+    }
+
+    /// Buffer the block, and evaluate the partitions completed by it.
+    fn consume(&mut self, block: DataBlock) -> Result<()> {
+        let num_rows = block.num_rows();
+        if num_rows == 0 {
+            return Ok(());
+        }
+
+        // The rows of the last partition of the block are the suffix with its partition key.
+        let last_key = Self::key(&self.partition_by, &block, num_rows - 1)?;
+        let (mut lo, mut hi) = (0, num_rows - 1);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if Self::key(&self.partition_by, &block, mid)? == last_key {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+
+        let mut completed = std::mem::take(&mut self.pending);
+        if lo == 0 {
+            match completed.first() {
+                Some(first) if Self::key(&self.partition_by, first, 0)? == last_key => {
+                    completed.push(block);
+                    self.pending = completed;
+                    return Ok(());
+                }
+                _ => self.pending.push(block),
+            }
+        } else {
+            completed.push(block.slice(0, lo));
+            self.pending.push(block.slice(lo, num_rows - lo));
+        }
+
+        if !completed.is_empty() {
+            self.output_data = Some(self.evaluate(DataBlock::concat_blocks(&completed)?)?);
+        }
+        Ok(())
+    }
+
+    fn finish_consume(&mut self) -> Result<()> {
+        let completed = std::mem::take(&mut self.pending);
+        if !completed.is_empty() {
+            self.output_data = Some(self.evaluate(DataBlock::concat_blocks(&completed)?)?);
+        }
+        Ok(())
+    }
+
+    /// Evaluate the function over the block made of whole partitions.
+    fn evaluate(&self, block: DataBlock) -> Result<DataBlock> {
+        let num_rows = block.num_rows();
+        let mut partitions = vec![];
+        let mut start = 0;
+        for row in 1..num_rows {
+            if !self.same_keys(&self.partition_by, &block, row - 1, row)? {
+                partitions.push(start..row);
+                start = row;
+            }
+        }
+        partitions.push(start..num_rows);
+
+        let return_type = &self.func.return_type;
+        let column = match &self.aggregate {
+            Some(aggregate) => {
+                let mut builder = return_type.create_mutable(num_rows);
+                for partition in partitions {
+                    self.aggregate_partition(aggregate, &block, partition, builder.as_mut())?;
+                }
+                builder.to_column()
+            }
+            None => {
+                let mut values = Vec::with_capacity(num_rows);
+                for partition in partitions {
+                    self.evaluate_partition(&block, partition, &mut values)?;
+                }
+                return_type.create_column(&values)?
+            }
+        };
+
+        let field = DataField::new(&self.func.column_id, return_type.clone());
+        block.add_column(column, field)
+    }
+
+    /// Evaluate the ranking and offset functions over a partition.
+    fn evaluate_partition(
+        &self,
+        block: &DataBlock,
+        partition: Range<usize>,
+        values: &mut Vec<DataValue>,
+    ) -> Result<()> {
+        match &self.func.func {
+            WindowFuncType::RowNumber => {
+                values.extend((1..=partition.len() as u64).map(DataValue::UInt64));
+            }
+            WindowFuncType::Rank | WindowFuncType::DenseRank => {
+                let dense = matches!(self.func.func, WindowFuncType::DenseRank);
+                let mut rank = 0;
+                for row in partition.clone() {
+                    if row == partition.start
+                        || !self.same_keys(&self.order_by, block, row - 1, row)?
+                    {
+                        rank = if dense {
+                            rank + 1
+                        } else {
+                            (row - partition.start + 1) as u64
+                        };
+                    }
+                    values.push(DataValue::UInt64(rank));
+                }
+            }
+            WindowFuncType::Lag { offset } | WindowFuncType::Lead { offset } => {
+                let offset = match self.func.func {
+                    WindowFuncType::Lag { .. } => -(*offset as i128),
+                    _ => *offset as i128,
+                };
+                let column = block.try_column_by_name(&self.func.args[0])?;
+                let default = match self.func.args.get(1) {
+                    Some(arg) => Some(block.try_column_by_name(arg)?),
+                    None => None,
+                };
+
+                let rows = partition.start as i128..partition.end as i128;
+                for row in partition.clone() {
+                    let target = row as i128 + offset;
+                    let value = if rows.contains(&target) {
+                        column.get(target as usize)
+                    } else {
+                        default.map_or(DataValue::Null, |default| default.get(row))
+                    };
+                    values.push(value);
+                }
+            }
+            WindowFuncType::Aggregate { .. } => unreachable!(),
+        }
+        Ok(())
+    }
+
+    /// Evaluate the aggregate function over the frame of each row of a partition.
+    ///
+    /// Frames starting at the start of the partition only grow, so the rows are accumulated into
+    /// one state. The others are aggregated from scratch for each row.
+    fn aggregate_partition(
+        &self,
+        aggregate: &AggregateFunctionRef,
+        block: &DataBlock,
+        partition: Range<usize>,
+        builder: &mut dyn MutableColumn,
+    ) -> Result<()> {
+        let columns = self
+            .func
+            .args
+            .iter()
+            .map(|arg| Ok(block.try_column_by_name(arg)?.clone()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut peers = partition.start..partition.start;
+        let mut running: Option<(AggregateState, usize)> = None;
+        for row in partition.clone() {
+            if row >= peers.end {
+                let mut end = row + 1;
+                while end < partition.end && self.same_keys(&self.order_by, block, row, end)? {
+                    end += 1;
+                }
+                peers = row..end;
+            }
+
+            let start_bound = &self.frame.start_bound;
+            let start = self.frame_position(start_bound, row, &peers, &partition, false);
+            let end = self.frame_position(&self.frame.end_bound, row, &peers, &partition, true);
+
+            if matches!(
+                self.frame.start_bound,
+                WindowFuncFrameBound::Preceding(None)
+            ) {
+                let (state, accumulated) = running
+                    .get_or_insert_with(|| (AggregateState::create(aggregate), partition.start));
+                if end > *accumulated {
+                    state.accumulate(&columns, *accumulated..end)?;
+                    *accumulated = end;
+                }
+                aggregate.merge_result(state.addr, builder)?;
+            } else {
+                let state = AggregateState::create(aggregate);
+                if end > start {
+                    state.accumulate(&columns, start..end)?;
+                }
+                aggregate.merge_result(state.addr, builder)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Position of a bound of the frame of `row`, the frame is the rows from the position of its
+    /// start bound up to the position of its end bound, exclusively.
+    fn frame_position(
+        &self,
+        bound: &WindowFuncFrameBound,
+        row: usize,
+        peers: &Range<usize>,
+        partition: &Range<usize>,
+        is_end: bool,
+    ) -> usize {
+        let offset = match bound {
+            WindowFuncFrameBound::Preceding(None) => return partition.start,
+            WindowFuncFrameBound::Following(None) => return partition.end,
+            // the frame of RANGE units includes all the peers of the current row
+            WindowFuncFrameBound::CurrentRow
+                if matches!(self.frame.units, WindowFuncFrameUnits::Range) =>
+            {
+                return if is_end { peers.end } else { peers.start };
+            }
+            WindowFuncFrameBound::CurrentRow => 0,
+            WindowFuncFrameBound::Preceding(Some(n)) => -(*n as i128),
+            WindowFuncFrameBound::Following(Some(n)) => *n as i128,
+        };
+
+        let position = row as i128 + offset + is_end as i128;
+        position.clamp(partition.start as i128, partition.end as i128) as usize
+    }
+
+    fn key(columns: &[ColumnID], block: &DataBlock, row: usize) -> Result<Vec<DataValue>> {
+        columns
+            .iter()
+            .map(|column| Ok(block.try_column_by_name(column)?.get(row)))
+            .collect()
+    }
+
+    fn same_keys(
+        &self,
+        columns: &[ColumnID],
+        block: &DataBlock,
+        l: usize,
+        r: usize,
+    ) -> Result<bool> {
+        for column in columns {
+            let column = block.try_column_by_name(column)?;
+            if column.get(l) != column.get(r) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// State of an aggregate function, allocated in its own arena.
+struct AggregateState {
+    func: AggregateFunctionRef,
+    addr: StateAddr,
+    _arena: Bump,
+}
+
+impl AggregateState {
+    fn create(func: &AggregateFunctionRef) -> Self {
+        let _arena = Bump::new();
+        let addr = _arena.alloc_layout(func.state_layout()).into();
+        func.init_state(addr);
+
+        Self {
+            func: func.clone(),
+            addr,
+            _arena,
+        }
+    }
+
+    fn accumulate(&self, columns: &[ColumnRef], rows: Range<usize>) -> Result<()> {
+        let columns = columns
+            .iter()
+            .map(|column| column.slice(rows.start, rows.len()))
+            .collect::<Vec<_>>();
+        self.func.accumulate(self.addr, &columns, None, rows.len())
+    }
+}
+
+impl Drop for AggregateState {
+    fn drop(&mut self) {
+        if self.func.need_manual_drop_state() {
+            unsafe {
+                self.func.drop_state(self.addr);
+            }
+        }
+    }
+}
+
+impl Processor for TransformWindow {
+    fn name(&self) -> &'static str {
+        "TransformWindow"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(output_data) = self.output_data.take() {
+            self.output.push_data(Ok(output_data));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input.has_data() {
+            self.input_data = Some(self.input.pull_data().unwrap()?);
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            if !self.pending.is_empty() {
+                return Ok(Event::Sync);
+            }
+
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match self.input_data.take() {
+            Some(block) => self.consume(block),
+            None => self.finish_consume(),
+        }
+    }
+}
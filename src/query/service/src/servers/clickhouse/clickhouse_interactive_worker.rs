@@ -0,0 +1,328 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio::io::AsyncReadExt;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_base::base::ProgressValues;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures_util::FutureExt;
+use futures_util::StreamExt;
+use tracing::info;
+
+use crate::auth::Credential;
+use crate::interpreters::InterpreterFactory;
+use crate::interpreters::InterpreterQueryLog;
+use crate::servers::clickhouse::clickhouse_session::ClickHouseStream;
+use crate::servers::clickhouse::native_format::NativeBlock;
+use crate::servers::clickhouse::protocol::write_data;
+use crate::servers::clickhouse::protocol::write_end_of_stream;
+use crate::servers::clickhouse::protocol::write_exception;
+use crate::servers::clickhouse::protocol::write_hello;
+use crate::servers::clickhouse::protocol::write_pong;
+use crate::servers::clickhouse::protocol::write_progress;
+use crate::servers::clickhouse::protocol::ClientHello;
+use crate::servers::clickhouse::protocol::ClientPacket;
+use crate::servers::clickhouse::protocol::ClientQuery;
+use crate::servers::clickhouse::protocol::PacketDecoder;
+use crate::servers::clickhouse::protocol::Progress;
+use crate::servers::clickhouse::protocol::ServerHello;
+use crate::servers::clickhouse::CLICKHOUSE_REVISION;
+use crate::servers::clickhouse::CLICKHOUSE_SERVER_NAME;
+use crate::servers::clickhouse::CLICKHOUSE_VERSION_MAJOR;
+use crate::servers::clickhouse::CLICKHOUSE_VERSION_MINOR;
+use crate::servers::clickhouse::CLICKHOUSE_VERSION_PATCH;
+use crate::servers::http::CLickHouseFederated;
+use crate::sessions::QueryContext;
+use crate::sessions::Session;
+use crate::sessions::TableContext;
+use crate::sql::plans::InsertInputSource;
+use crate::sql::plans::Plan;
+use crate::sql::Planner;
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Serve the queries of a connection of the ClickHouse native protocol.
+///
+/// The packets are read one by one, a query is finished before the next packet is read, so the
+/// cancel packets only arrive after the query.
+pub struct InteractiveWorker {
+    session: Arc<Session>,
+    stream: Box<dyn ClickHouseStream>,
+    client_addr: String,
+    // The bytes received but not decoded yet.
+    read_buf: Vec<u8>,
+    decoder: PacketDecoder,
+    write_buf: Vec<u8>,
+    // The negotiated revision of the protocol.
+    revision: u64,
+    compression: bool,
+}
+
+impl InteractiveWorker {
+    pub fn create(
+        session: Arc<Session>,
+        stream: Box<dyn ClickHouseStream>,
+        client_addr: String,
+    ) -> Self {
+        InteractiveWorker {
+            session,
+            stream,
+            client_addr,
+            read_buf: Vec::with_capacity(READ_BUFFER_SIZE),
+            decoder: PacketDecoder::default(),
+            write_buf: vec![],
+            revision: 0,
+            compression: false,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<()> {
+        let hello = match self.read_packet().await? {
+            None => return Ok(()),
+            Some(ClientPacket::Hello(hello)) => hello,
+            Some(_) => return Err(ErrorCode::BadBytes("expect hello from clickhouse client")),
+        };
+        self.revision = hello.revision.min(CLICKHOUSE_REVISION);
+        if let Err(cause) = self.do_hello(&hello).await {
+            write_exception(&mut self.write_buf, &cause);
+            return self.flush().await;
+        }
+
+        while let Some(packet) = self.read_packet().await? {
+            match packet {
+                ClientPacket::Ping => write_pong(&mut self.write_buf),
+                ClientPacket::Query(query) => {
+                    if let Err(cause) = self.do_query(query).await {
+                        // the blocks written before the exception are discarded by the client
+                        write_exception(&mut self.write_buf, &cause);
+                    }
+                }
+                // the query has finished before the cancel packet is read, and the rest blocks
+                // of a failed insert are dropped.
+                ClientPacket::Cancel | ClientPacket::Data(_) => {}
+                ClientPacket::Hello(_) => {
+                    return Err(ErrorCode::BadBytes(
+                        "unexpected hello from clickhouse client",
+                    ));
+                }
+            }
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    async fn do_hello(&mut self, hello: &ClientHello) -> Result<()> {
+        info!(
+            "ClickHouse client {} {}.{} (revision {}) connected from {}",
+            hello.client_name,
+            hello.version_major,
+            hello.version_minor,
+            hello.revision,
+            self.client_addr
+        );
+
+        let ctx = self.session.create_query_context().await?;
+        let client_ip = self.client_addr.split(':').collect::<Vec<_>>()[0];
+        let credential = Credential::Password {
+            name: hello.user.clone(),
+            password: Some(hello.password.as_bytes().to_vec()),
+            hostname: Some(client_ip.to_string()),
+        };
+        ctx.get_auth_manager()
+            .auth(self.session.clone(), &credential)
+            .await?;
+        if !hello.database.is_empty() {
+            self.session.set_current_database(hello.database.clone());
+        }
+
+        let timezone = self.session.get_settings().get_timezone()?;
+        let server_hello = ServerHello {
+            name: CLICKHOUSE_SERVER_NAME,
+            version_major: CLICKHOUSE_VERSION_MAJOR,
+            version_minor: CLICKHOUSE_VERSION_MINOR,
+            version_patch: CLICKHOUSE_VERSION_PATCH,
+            revision: CLICKHOUSE_REVISION,
+            timezone: &timezone,
+            display_name: CLICKHOUSE_SERVER_NAME,
+        };
+        write_hello(&mut self.write_buf, &server_hello, self.revision);
+        self.flush().await
+    }
+
+    async fn do_query(&mut self, query: ClientQuery) -> Result<()> {
+        info!("ClickHouse query: {}", query.query);
+        self.compression = query.compression;
+
+        // the data of the external tables follow the query, ended by an empty block.
+        if !self.read_data_block().await?.is_empty() {
+            return Err(ErrorCode::UnImplement(
+                "external tables of clickhouse are not supported",
+            ));
+        }
+
+        self.session
+            .get_settings()
+            .set_batch_settings(&query.settings, false)?;
+        let context = self.session.create_query_context().await?;
+
+        if let Some(block) = CLickHouseFederated::check(&query.query) {
+            self.write_data(&NativeBlock::header(block.schema()))?;
+            self.write_data(&NativeBlock::from_data_block(&block))?;
+            write_end_of_stream(&mut self.write_buf);
+            return Ok(());
+        }
+
+        let mut planner = Planner::new(context.clone());
+        let (mut plan, _, _) = planner.plan_sql(&query.query).await?;
+        context.attach_query_str(plan.to_string(), &query.query);
+
+        if let Plan::Insert(insert) = &mut plan {
+            let from_client = match &insert.source {
+                InsertInputSource::Values(data) => data.is_empty(),
+                InsertInputSource::StreamingWithFormat(..) => true,
+                _ => false,
+            };
+            // The client strips the data out of the query, and sends it in blocks, which are
+            // converted by the columns in the header.
+            if from_client {
+                let schema = insert.schema();
+                self.write_data(&NativeBlock::header(&schema))?;
+                self.flush().await?;
+                insert.source = InsertInputSource::Blocks(self.read_insert_blocks(&schema).await?);
+            }
+        }
+
+        let interpreter = match InterpreterFactory::get(context.clone(), &plan).await {
+            Ok(interpreter) => interpreter,
+            Err(cause) => {
+                InterpreterQueryLog::fail_to_start(context, cause.clone());
+                return Err(cause);
+            }
+        };
+
+        let schema = interpreter.schema();
+        let mut data_stream = interpreter.execute(context.clone()).await?;
+        if !schema.fields().is_empty() {
+            self.write_data(&NativeBlock::header(&schema))?;
+        }
+
+        let mut progress = Default::default();
+        while let Some(block) = data_stream.next().await {
+            let block = block?;
+            if block.num_rows() == 0 {
+                continue;
+            }
+            self.write_progress(&context, &mut progress);
+            self.write_data(&NativeBlock::from_data_block(&block))?;
+            self.flush().await?;
+        }
+        self.write_progress(&context, &mut progress);
+        write_end_of_stream(&mut self.write_buf);
+        Ok(())
+    }
+
+    async fn read_insert_blocks(&mut self, schema: &DataSchemaRef) -> Result<Vec<DataBlock>> {
+        let mut blocks = vec![];
+        loop {
+            let block = self.read_data_block().await?;
+            if block.is_empty() {
+                return Ok(blocks);
+            }
+            if block.rows > 0 {
+                blocks.push(block.to_data_block(schema)?);
+            }
+        }
+    }
+
+    async fn read_data_block(&mut self) -> Result<NativeBlock> {
+        match self.read_packet().await? {
+            Some(ClientPacket::Data(block)) => Ok(block),
+            Some(_) => Err(ErrorCode::BadBytes("expect data from clickhouse client")),
+            None => Err(ErrorCode::AbortedSession(
+                "clickhouse client closed the connection",
+            )),
+        }
+    }
+
+    async fn read_packet(&mut self) -> Result<Option<ClientPacket>> {
+        loop {
+            if !self.read_buf.is_empty() {
+                let (packet, consumed) =
+                    self.decoder
+                        .decode(&self.read_buf, self.revision, self.compression)?;
+                self.read_buf.drain(..consumed);
+                if packet.is_some() {
+                    return Ok(packet);
+                }
+            }
+
+            self.read_buf.reserve(READ_BUFFER_SIZE);
+            if self.stream.read_buf(&mut self.read_buf).await? == 0 {
+                return match self.read_buf.is_empty() && !self.decoder.in_packet() {
+                    true => Ok(None),
+                    false => Err(ErrorCode::AbortedSession(
+                        "clickhouse client closed the connection in the middle of a packet",
+                    )),
+                };
+            }
+
+            // Take all the bytes already received, so a large packet is decoded fewer times.
+            // Reading is polled once, and gives up if it would wait for more bytes.
+            loop {
+                self.read_buf.reserve(READ_BUFFER_SIZE);
+                match self.stream.read_buf(&mut self.read_buf).now_or_never() {
+                    None | Some(Ok(0)) => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(error)) => return Err(error.into()),
+                }
+            }
+        }
+    }
+
+    fn write_data(&mut self, block: &NativeBlock) -> Result<()> {
+        write_data(&mut self.write_buf, block, self.compression)
+    }
+
+    // Progress packets carry the increments of the scan and write progress since the last one.
+    fn write_progress(
+        &mut self,
+        context: &Arc<QueryContext>,
+        last: &mut (ProgressValues, ProgressValues),
+    ) {
+        let scan = context.get_scan_progress_value();
+        let write = context.get_write_progress_value();
+        let progress = Progress {
+            read_rows: scan.rows.saturating_sub(last.0.rows) as u64,
+            read_bytes: scan.bytes.saturating_sub(last.0.bytes) as u64,
+            total_rows: 0,
+            written_rows: write.rows.saturating_sub(last.1.rows) as u64,
+            written_bytes: write.bytes.saturating_sub(last.1.bytes) as u64,
+        };
+        *last = (scan, write);
+        write_progress(&mut self.write_buf, &progress, self.revision);
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        if !self.write_buf.is_empty() {
+            self.stream.write_all(&self.write_buf).await?;
+            self.write_buf.clear();
+        }
+        Ok(())
+    }
+}
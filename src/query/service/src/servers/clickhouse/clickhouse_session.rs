@@ -0,0 +1,107 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::Shutdown;
+use std::sync::Arc;
+
+use common_base::base::tokio::io::AsyncRead;
+use common_base::base::tokio::io::AsyncWrite;
+use common_base::base::tokio::net::TcpStream;
+use common_base::base::Runtime;
+use common_base::base::Thread;
+use common_base::base::TrySpawn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use tokio_rustls::TlsAcceptor;
+use tracing::error;
+
+use crate::servers::clickhouse::clickhouse_interactive_worker::InteractiveWorker;
+use crate::sessions::Session;
+
+/// The stream of a connection, in plain text or in TLS.
+pub trait ClickHouseStream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> ClickHouseStream for T {}
+
+pub struct ClickHouseConnection;
+
+impl ClickHouseConnection {
+    pub fn run_on_stream(
+        session: Arc<Session>,
+        stream: TcpStream,
+        tls: Option<TlsAcceptor>,
+    ) -> Result<()> {
+        let blocking_stream = Self::convert_stream(stream)?;
+        ClickHouseConnection::attach_session(&session, &blocking_stream)?;
+
+        // the worker reads the bytes available without waiting, the stream must be non-blocking.
+        blocking_stream.set_nonblocking(true)?;
+        let non_blocking_stream = TcpStream::from_std(blocking_stream)?;
+        let query_executor =
+            Runtime::with_worker_threads(1, Some("clickhouse-query-executor".to_string()))?;
+        Thread::spawn(move || {
+            let join_handle = query_executor.spawn(async move {
+                let client_addr = non_blocking_stream.peer_addr().unwrap().to_string();
+                let stream: Box<dyn ClickHouseStream> = match tls {
+                    None => Box::new(non_blocking_stream),
+                    Some(tls) => match tls.accept(non_blocking_stream).await {
+                        Ok(stream) => Box::new(stream),
+                        Err(error) => {
+                            error!(
+                                "Unexpected error occurred during TLS handshake: {:?}",
+                                error
+                            );
+                            return;
+                        }
+                    },
+                };
+                let interactive_worker = InteractiveWorker::create(session, stream, client_addr);
+                if let Err(error) = interactive_worker.run().await {
+                    error!(
+                        "Unexpected error occurred during clickhouse session: {:?}",
+                        error
+                    );
+                }
+            });
+            let _ = futures::executor::block_on(join_handle);
+        });
+        Ok(())
+    }
+
+    fn attach_session(session: &Arc<Session>, blocking_stream: &std::net::TcpStream) -> Result<()> {
+        let host = blocking_stream.peer_addr().ok();
+        let blocking_stream_ref = blocking_stream.try_clone()?;
+        session.attach(host, move || {
+            if let Err(error) = blocking_stream_ref.shutdown(Shutdown::Both) {
+                error!("Cannot shutdown ClickHouse session io {}", error);
+            }
+        });
+
+        Ok(())
+    }
+
+    fn convert_stream(stream: TcpStream) -> Result<std::net::TcpStream> {
+        let stream = stream.into_std().map_err_to_code(
+            ErrorCode::TokioError,
+            || "Cannot to convert Tokio TcpStream to Std TcpStream",
+        )?;
+        stream.set_nonblocking(false).map_err_to_code(
+            ErrorCode::TokioError,
+            || "Cannot to convert Tokio TcpStream to Std TcpStream",
+        )?;
+
+        Ok(stream)
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod clickhouse_handler;
+mod clickhouse_interactive_worker;
+mod clickhouse_session;
+mod native_format;
+mod protocol;
+
+pub use self::clickhouse_handler::ClickHouseHandler;
+pub use self::clickhouse_session::ClickHouseConnection;
+pub(crate) use self::protocol::compress_block;
+
+const CLICKHOUSE_SERVER_NAME: &str = "Databend";
+// the same version as `SELECT version()` of the clickhouse http handler
+const CLICKHOUSE_VERSION_MAJOR: u64 = 8;
+const CLICKHOUSE_VERSION_MINOR: u64 = 12;
+const CLICKHOUSE_VERSION_PATCH: u64 = 14;
+// The newest revision of the native protocol supported, the features of the later revisions,
+// such as the interserver secret and OpenTelemetry, are left out.
+const CLICKHOUSE_REVISION: u64 = 54429;
@@ -0,0 +1,745 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use common_datablocks::DataBlock;
+use common_datavalues::geometry_from_wkb;
+use common_datavalues::geometry_to_wkt;
+use common_datavalues::parse_geometry_to_wkb;
+use common_datavalues::prelude::*;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::VariantValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::servers::clickhouse::protocol::write_binary;
+use crate::servers::clickhouse::protocol::write_varint;
+use crate::servers::clickhouse::protocol::ReadError;
+use crate::servers::clickhouse::protocol::ReadResult;
+use crate::servers::clickhouse::protocol::Reader;
+
+/// Type of a column in the ClickHouse Native format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NativeType {
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float32,
+    Float64,
+    Bool,
+    String,
+    FixedString(usize),
+    Date,
+    Date32,
+    DateTime,
+    DateTime64(u32),
+    Nullable(Box<NativeType>),
+    Array(Box<NativeType>),
+    Tuple(Vec<NativeType>),
+}
+
+impl NativeType {
+    pub fn from_data_type(data_type: &DataTypeImpl) -> NativeType {
+        match data_type {
+            // the values of Null are all NULL, any nullable type works.
+            DataTypeImpl::Null(_) => NativeType::Nullable(Box::new(NativeType::UInt8)),
+            DataTypeImpl::Nullable(v) => {
+                NativeType::Nullable(Box::new(Self::from_data_type(v.inner_type())))
+            }
+            DataTypeImpl::Boolean(_) => NativeType::Bool,
+            DataTypeImpl::Int8(_) => NativeType::Int8,
+            DataTypeImpl::Int16(_) => NativeType::Int16,
+            DataTypeImpl::Int32(_) => NativeType::Int32,
+            DataTypeImpl::Int64(_) => NativeType::Int64,
+            DataTypeImpl::UInt8(_) => NativeType::UInt8,
+            DataTypeImpl::UInt16(_) => NativeType::UInt16,
+            DataTypeImpl::UInt32(_) => NativeType::UInt32,
+            DataTypeImpl::UInt64(_) => NativeType::UInt64,
+            DataTypeImpl::Float32(_) => NativeType::Float32,
+            DataTypeImpl::Float64(_) => NativeType::Float64,
+            DataTypeImpl::Date(_) => NativeType::Date32,
+            DataTypeImpl::Timestamp(_) => NativeType::DateTime64(6),
+            DataTypeImpl::String(_) => NativeType::String,
+            DataTypeImpl::Struct(v) => {
+                NativeType::Tuple(v.types().iter().map(Self::from_data_type).collect())
+            }
+            DataTypeImpl::Array(v) => {
+                NativeType::Array(Box::new(Self::from_data_type(v.inner_type())))
+            }
+            // variants are exchanged as JSON strings
+            DataTypeImpl::Variant(_)
+            | DataTypeImpl::VariantArray(_)
+            | DataTypeImpl::VariantObject(_) => NativeType::String,
+            DataTypeImpl::Interval(_) => NativeType::Int64,
+            // geometries are exchanged as WKT strings
+            DataTypeImpl::Geometry(_) | DataTypeImpl::Geography(_) => NativeType::String,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            NativeType::Int8 => "Int8".to_string(),
+            NativeType::Int16 => "Int16".to_string(),
+            NativeType::Int32 => "Int32".to_string(),
+            NativeType::Int64 => "Int64".to_string(),
+            NativeType::UInt8 => "UInt8".to_string(),
+            NativeType::UInt16 => "UInt16".to_string(),
+            NativeType::UInt32 => "UInt32".to_string(),
+            NativeType::UInt64 => "UInt64".to_string(),
+            NativeType::Float32 => "Float32".to_string(),
+            NativeType::Float64 => "Float64".to_string(),
+            NativeType::Bool => "Bool".to_string(),
+            NativeType::String => "String".to_string(),
+            NativeType::FixedString(n) => format!("FixedString({})", n),
+            NativeType::Date => "Date".to_string(),
+            NativeType::Date32 => "Date32".to_string(),
+            NativeType::DateTime => "DateTime".to_string(),
+            NativeType::DateTime64(precision) => format!("DateTime64({})", precision),
+            NativeType::Nullable(inner) => format!("Nullable({})", inner.name()),
+            NativeType::Array(inner) => format!("Array({})", inner.name()),
+            NativeType::Tuple(inner) => {
+                let names = inner.iter().map(|v| v.name()).collect::<Vec<_>>();
+                format!("Tuple({})", names.join(", "))
+            }
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<NativeType> {
+        let name = name.trim();
+        let (base, args) = match name.find('(') {
+            Some(pos) if name.ends_with(')') => {
+                (&name[..pos], Some(&name[pos + 1..name.len() - 1]))
+            }
+            _ => (name, None),
+        };
+
+        let native_type = match (base, args) {
+            ("Int8", None) => NativeType::Int8,
+            ("Int16", None) => NativeType::Int16,
+            ("Int32", None) => NativeType::Int32,
+            ("Int64", None) => NativeType::Int64,
+            ("UInt8", None) => NativeType::UInt8,
+            ("UInt16", None) => NativeType::UInt16,
+            ("UInt32", None) => NativeType::UInt32,
+            ("UInt64", None) => NativeType::UInt64,
+            ("Float32", None) => NativeType::Float32,
+            ("Float64", None) => NativeType::Float64,
+            ("Bool", None) => NativeType::Bool,
+            ("String", None) => NativeType::String,
+            ("FixedString", Some(n)) => {
+                NativeType::FixedString(n.trim().parse().map_err(|_| {
+                    ErrorCode::BadDataValueType(format!("invalid clickhouse type: {}", name))
+                })?)
+            }
+            ("Date", None) => NativeType::Date,
+            ("Date32", None) => NativeType::Date32,
+            // the timezone only matters to display the values
+            ("DateTime", _) => NativeType::DateTime,
+            ("DateTime64", Some(args)) => {
+                let precision = split_arguments(args)[0];
+                NativeType::DateTime64(precision.trim().parse().map_err(|_| {
+                    ErrorCode::BadDataValueType(format!("invalid clickhouse type: {}", name))
+                })?)
+            }
+            ("Nullable", Some(inner)) => NativeType::Nullable(Box::new(Self::parse(inner)?)),
+            ("Array", Some(inner)) => NativeType::Array(Box::new(Self::parse(inner)?)),
+            ("Tuple", Some(args)) => NativeType::Tuple(
+                split_arguments(args)
+                    .into_iter()
+                    .map(|element| {
+                        // the elements of named tuples are `<name> <type>`
+                        let element = element.trim();
+                        match element.split_once(' ') {
+                            Some((name, element_type)) if !name.contains('(') => {
+                                Self::parse(element_type)
+                            }
+                            _ => Self::parse(element),
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            _ => {
+                return Err(ErrorCode::BadDataValueType(format!(
+                    "unsupported clickhouse type: {}",
+                    name
+                )));
+            }
+        };
+        Ok(native_type)
+    }
+
+    fn default_value(&self) -> DataValue {
+        match self {
+            NativeType::Int8
+            | NativeType::Int16
+            | NativeType::Int32
+            | NativeType::Int64
+            | NativeType::Date
+            | NativeType::Date32
+            | NativeType::DateTime
+            | NativeType::DateTime64(_) => DataValue::Int64(0),
+            NativeType::UInt8 | NativeType::UInt16 | NativeType::UInt32 | NativeType::UInt64 => {
+                DataValue::UInt64(0)
+            }
+            NativeType::Float32 | NativeType::Float64 => DataValue::Float64(0.0),
+            NativeType::Bool => DataValue::Boolean(false),
+            NativeType::String | NativeType::FixedString(_) => DataValue::String(vec![]),
+            NativeType::Nullable(_) => DataValue::Null,
+            NativeType::Array(_) => DataValue::Array(vec![]),
+            NativeType::Tuple(inner) => {
+                DataValue::Struct(inner.iter().map(|v| v.default_value()).collect())
+            }
+        }
+    }
+
+    fn write_values(&self, values: &[DataValue], buf: &mut Vec<u8>) -> Result<()> {
+        match self {
+            NativeType::Int8 => write_each(values, buf, |v| Ok((v.as_i64()? as i8).to_le_bytes())),
+            NativeType::Int16 => {
+                write_each(values, buf, |v| Ok((v.as_i64()? as i16).to_le_bytes()))
+            }
+            NativeType::Int32 => {
+                write_each(values, buf, |v| Ok((v.as_i64()? as i32).to_le_bytes()))
+            }
+            NativeType::Int64 => write_each(values, buf, |v| Ok(v.as_i64()?.to_le_bytes())),
+            NativeType::UInt8 => write_each(values, buf, |v| Ok((v.as_u64()? as u8).to_le_bytes())),
+            NativeType::UInt16 => {
+                write_each(values, buf, |v| Ok((v.as_u64()? as u16).to_le_bytes()))
+            }
+            NativeType::UInt32 => {
+                write_each(values, buf, |v| Ok((v.as_u64()? as u32).to_le_bytes()))
+            }
+            NativeType::UInt64 => write_each(values, buf, |v| Ok(v.as_u64()?.to_le_bytes())),
+            NativeType::Float32 => {
+                write_each(values, buf, |v| Ok((v.as_f64()? as f32).to_le_bytes()))
+            }
+            NativeType::Float64 => write_each(values, buf, |v| Ok(v.as_f64()?.to_le_bytes())),
+            NativeType::Bool => write_each(values, buf, |v| Ok([v.as_bool()? as u8])),
+            NativeType::String => {
+                for value in values {
+                    write_binary(buf, &value.as_string()?);
+                }
+                Ok(())
+            }
+            NativeType::FixedString(n) => {
+                for value in values {
+                    let mut value = value.as_string()?;
+                    value.resize(*n, 0);
+                    buf.extend_from_slice(&value);
+                }
+                Ok(())
+            }
+            NativeType::Date => write_each(values, buf, |v| Ok((v.as_i64()? as u16).to_le_bytes())),
+            NativeType::Date32 => {
+                write_each(values, buf, |v| Ok((v.as_i64()? as i32).to_le_bytes()))
+            }
+            NativeType::DateTime => write_each(values, buf, |v| {
+                Ok(((v.as_i64()? / MICROSECONDS) as u32).to_le_bytes())
+            }),
+            NativeType::DateTime64(precision) => write_each(values, buf, |v| {
+                Ok(from_micros(v.as_i64()?, *precision).to_le_bytes())
+            }),
+            NativeType::Nullable(inner) => {
+                let default_value = inner.default_value();
+                let mut inner_values = Vec::with_capacity(values.len());
+                for value in values {
+                    buf.push(value.is_null() as u8);
+                    match value.is_null() {
+                        true => inner_values.push(default_value.clone()),
+                        false => inner_values.push(value.clone()),
+                    }
+                }
+                inner.write_values(&inner_values, buf)
+            }
+            NativeType::Array(inner) => {
+                let mut offset = 0u64;
+                let mut inner_values = vec![];
+                for value in values {
+                    let array = value.as_array()?;
+                    offset += array.len() as u64;
+                    buf.extend_from_slice(&offset.to_le_bytes());
+                    inner_values.extend(array);
+                }
+                inner.write_values(&inner_values, buf)
+            }
+            NativeType::Tuple(inner) => {
+                let structs = values
+                    .iter()
+                    .map(|v| v.as_struct())
+                    .collect::<Result<Vec<_>>>()?;
+                for (i, element_type) in inner.iter().enumerate() {
+                    let element_values = structs.iter().map(|v| v[i].clone()).collect::<Vec<_>>();
+                    element_type.write_values(&element_values, buf)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // The size of each value, for the types whose values are all of the same size.
+    fn fixed_size(&self) -> Option<usize> {
+        match self {
+            NativeType::Int8 | NativeType::UInt8 | NativeType::Bool => Some(1),
+            NativeType::Int16 | NativeType::UInt16 | NativeType::Date => Some(2),
+            NativeType::Int32
+            | NativeType::UInt32
+            | NativeType::Float32
+            | NativeType::Date32
+            | NativeType::DateTime => Some(4),
+            NativeType::Int64
+            | NativeType::UInt64
+            | NativeType::Float64
+            | NativeType::DateTime64(_) => Some(8),
+            NativeType::FixedString(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn read_values(&self, reader: &mut Reader, rows: usize) -> ReadResult<Vec<DataValue>> {
+        let values = match self {
+            NativeType::Int8 => read_each(reader, rows, |v: [u8; 1]| {
+                DataValue::Int64(i8::from_le_bytes(v) as i64)
+            })?,
+            NativeType::Int16 => read_each(reader, rows, |v: [u8; 2]| {
+                DataValue::Int64(i16::from_le_bytes(v) as i64)
+            })?,
+            NativeType::Int32 | NativeType::Date32 => read_each(reader, rows, |v: [u8; 4]| {
+                DataValue::Int64(i32::from_le_bytes(v) as i64)
+            })?,
+            NativeType::Int64 => read_each(reader, rows, |v: [u8; 8]| {
+                DataValue::Int64(i64::from_le_bytes(v))
+            })?,
+            NativeType::UInt8 => {
+                read_each(reader, rows, |v: [u8; 1]| DataValue::UInt64(v[0] as u64))?
+            }
+            NativeType::UInt16 => read_each(reader, rows, |v: [u8; 2]| {
+                DataValue::UInt64(u16::from_le_bytes(v) as u64)
+            })?,
+            NativeType::UInt32 => read_each(reader, rows, |v: [u8; 4]| {
+                DataValue::UInt64(u32::from_le_bytes(v) as u64)
+            })?,
+            NativeType::UInt64 => read_each(reader, rows, |v: [u8; 8]| {
+                DataValue::UInt64(u64::from_le_bytes(v))
+            })?,
+            NativeType::Float32 => read_each(reader, rows, |v: [u8; 4]| {
+                DataValue::Float64(f32::from_le_bytes(v) as f64)
+            })?,
+            NativeType::Float64 => read_each(reader, rows, |v: [u8; 8]| {
+                DataValue::Float64(f64::from_le_bytes(v))
+            })?,
+            NativeType::Bool => {
+                read_each(reader, rows, |v: [u8; 1]| DataValue::Boolean(v[0] != 0))?
+            }
+            NativeType::String => (0..rows)
+                .map(|_| Ok(DataValue::String(reader.binary()?.to_vec())))
+                .collect::<ReadResult<Vec<_>>>()?,
+            NativeType::FixedString(n) => (0..rows)
+                .map(|_| Ok(DataValue::String(reader.bytes(*n)?.to_vec())))
+                .collect::<ReadResult<Vec<_>>>()?,
+            NativeType::Date => read_each(reader, rows, |v: [u8; 2]| {
+                DataValue::Int64(u16::from_le_bytes(v) as i64)
+            })?,
+            NativeType::DateTime => read_each(reader, rows, |v: [u8; 4]| {
+                DataValue::Int64(u32::from_le_bytes(v) as i64 * MICROSECONDS)
+            })?,
+            NativeType::DateTime64(precision) => read_each(reader, rows, |v: [u8; 8]| {
+                DataValue::Int64(to_micros(i64::from_le_bytes(v), *precision))
+            })?,
+            NativeType::Nullable(inner) => {
+                let nulls = reader.bytes(rows)?;
+                let values = inner.read_values(reader, rows)?;
+                nulls
+                    .iter()
+                    .zip(values.into_iter())
+                    .map(|(is_null, value)| match *is_null {
+                        0 => value,
+                        _ => DataValue::Null,
+                    })
+                    .collect()
+            }
+            NativeType::Array(inner) => {
+                let offsets = (0..rows)
+                    .map(|_| Ok(u64::from_le_bytes(reader.fixed::<8>()?) as usize))
+                    .collect::<ReadResult<Vec<_>>>()?;
+                let mut inner_values = inner
+                    .read_values(reader, offsets.last().cloned().unwrap_or(0))?
+                    .into_iter();
+                let mut start = 0;
+                let mut values = Vec::with_capacity(rows);
+                for offset in offsets {
+                    if offset < start {
+                        return Err(ReadError::Invalid(ErrorCode::BadBytes(
+                            "offsets of array are not increasing",
+                        )));
+                    }
+                    values.push(DataValue::Array(
+                        inner_values.by_ref().take(offset - start).collect(),
+                    ));
+                    start = offset;
+                }
+                values
+            }
+            NativeType::Tuple(inner) => {
+                let mut structs = vec![Vec::with_capacity(inner.len()); rows];
+                for element_type in inner {
+                    let element_values = element_type.read_values(reader, rows)?;
+                    for (fields, value) in structs.iter_mut().zip(element_values.into_iter()) {
+                        fields.push(value);
+                    }
+                }
+                structs.into_iter().map(DataValue::Struct).collect()
+            }
+        };
+        Ok(values)
+    }
+}
+
+fn write_each<const N: usize>(
+    values: &[DataValue],
+    buf: &mut Vec<u8>,
+    f: impl Fn(&DataValue) -> Result<[u8; N]>,
+) -> Result<()> {
+    for value in values {
+        buf.extend_from_slice(&f(value)?);
+    }
+    Ok(())
+}
+
+fn read_each<const N: usize>(
+    reader: &mut Reader,
+    rows: usize,
+    f: impl Fn([u8; N]) -> DataValue,
+) -> ReadResult<Vec<DataValue>> {
+    (0..rows).map(|_| Ok(f(reader.fixed::<N>()?))).collect()
+}
+
+// Databend timestamps are in microseconds.
+fn to_micros(value: i64, precision: u32) -> i64 {
+    match precision {
+        0..=6 => value * 10i64.pow(6 - precision),
+        _ => value / 10i64.pow(precision - 6),
+    }
+}
+
+fn from_micros(value: i64, precision: u32) -> i64 {
+    match precision {
+        0..=6 => value / 10i64.pow(6 - precision),
+        _ => value * 10i64.pow(precision - 6),
+    }
+}
+
+// Split the arguments of a type by the top level commas.
+fn split_arguments(args: &str) -> Vec<&str> {
+    let mut arguments = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    arguments.push(&args[start..]);
+    arguments
+}
+
+pub struct NativeColumn {
+    pub name: String,
+    pub data_type: NativeType,
+    pub values: Vec<DataValue>,
+}
+
+/// A block in the ClickHouse Native format.
+///
+/// See https://clickhouse.com/docs/en/interfaces/formats/#native
+pub struct NativeBlock {
+    pub rows: usize,
+    pub columns: Vec<NativeColumn>,
+}
+
+impl NativeBlock {
+    /// The block without columns, which marks the end of the data.
+    pub fn empty() -> NativeBlock {
+        NativeBlock {
+            rows: 0,
+            columns: vec![],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// The block without rows, which tells the names and types of the columns.
+    pub fn header(schema: &DataSchemaRef) -> NativeBlock {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| NativeColumn {
+                name: field.name().clone(),
+                data_type: NativeType::from_data_type(field.data_type()),
+                values: vec![],
+            })
+            .collect();
+        NativeBlock { rows: 0, columns }
+    }
+
+    pub fn from_data_block(block: &DataBlock) -> NativeBlock {
+        let columns = block
+            .schema()
+            .fields()
+            .iter()
+            .zip(block.columns())
+            .map(|(field, column)| {
+                let mut values = column.convert_full_column().to_values();
+                if remove_nullable(field.data_type())
+                    .data_type_id()
+                    .is_geometry_or_geography()
+                {
+                    values = values.iter().map(format_geometry).collect();
+                }
+                NativeColumn {
+                    name: field.name().clone(),
+                    data_type: NativeType::from_data_type(field.data_type()),
+                    values,
+                }
+            })
+            .collect();
+        NativeBlock {
+            rows: block.num_rows(),
+            columns,
+        }
+    }
+
+    /// Convert the columns to the fields of `schema` by their names.
+    pub fn to_data_block(&self, schema: &DataSchemaRef) -> Result<DataBlock> {
+        let mut columns = Vec::with_capacity(schema.num_fields());
+        for field in schema.fields() {
+            let column = self
+                .columns
+                .iter()
+                .find(|column| &column.name == field.name())
+                .ok_or_else(|| {
+                    ErrorCode::BadArguments(format!("column {} is missing", field.name()))
+                })?;
+
+            let data_type = field.data_type();
+            let values = match remove_nullable(data_type).data_type_id() {
+                TypeID::Variant | TypeID::VariantArray | TypeID::VariantObject => column
+                    .values
+                    .iter()
+                    .map(parse_variant)
+                    .collect::<Result<Vec<_>>>()?,
+                id @ (TypeID::Geometry | TypeID::Geography) => column
+                    .values
+                    .iter()
+                    .map(|v| parse_geometry(v, id == TypeID::Geography))
+                    .collect::<Result<Vec<_>>>()?,
+                _ => column.values.clone(),
+            };
+            columns.push(data_type.create_column(&values)?);
+        }
+        Ok(DataBlock::create(schema.clone(), columns))
+    }
+
+    // Read the block info, and the number of columns and rows.
+    fn read_head(reader: &mut Reader) -> ReadResult<(usize, usize)> {
+        read_block_info(reader)?;
+
+        let num_columns = reader.varint()? as usize;
+        let rows = reader.varint()? as usize;
+        Ok((num_columns, rows))
+    }
+
+    pub fn write(&self, buf: &mut Vec<u8>) -> Result<()> {
+        write_block_info(buf);
+
+        write_varint(buf, self.columns.len() as u64);
+        write_varint(buf, self.rows as u64);
+        for column in &self.columns {
+            write_binary(buf, column.name.as_bytes());
+            write_binary(buf, column.data_type.name().as_bytes());
+            if self.rows > 0 {
+                column.data_type.write_values(&column.values, buf)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Decode a block whose bytes are received by parts.
+///
+/// The parsed columns and values are kept, so the bytes are parsed once, except the values of a
+/// nested type, which are parsed again as a whole until they are received completely.
+#[derive(Default)]
+pub struct NativeBlockDecoder {
+    data: Vec<u8>,
+    // The position in `data` of the bytes not parsed yet.
+    pos: usize,
+    // The length `data` needs at least to continue, set when the bytes are incomplete.
+    needed: usize,
+    // The number of columns and rows, once they are parsed.
+    head: Option<(usize, usize)>,
+    columns: Vec<NativeColumn>,
+    // The column whose values are being parsed.
+    column: Option<NativeColumn>,
+}
+
+impl NativeBlockDecoder {
+    /// Take in the received bytes, returns the block once it is complete.
+    pub fn push(&mut self, bytes: &[u8]) -> Result<Option<NativeBlock>> {
+        self.data.extend_from_slice(bytes);
+        let data = std::mem::take(&mut self.data);
+        let res = self.parse(&data);
+        self.data = data;
+        res
+    }
+
+    /// The number of bytes taken in but not parsed, which follow the block once it is complete.
+    pub fn unparsed_len(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn parse(&mut self, data: &[u8]) -> Result<Option<NativeBlock>> {
+        while data.len() >= self.needed {
+            let mut reader = Reader::new(&data[self.pos..]);
+            match self.read_next(&mut reader) {
+                Ok(Some(block)) => return Ok(Some(block)),
+                Ok(None) => self.pos += reader.position(),
+                Err(ReadError::Incomplete) => self.needed = self.pos + reader.needed(),
+                Err(ReadError::Invalid(cause)) => return Err(cause),
+            }
+        }
+        Ok(None)
+    }
+
+    // Read the next part of the block: the head, the name and type of a column, or the values of
+    // the current column. Nothing is changed if the bytes of the part are incomplete.
+    fn read_next(&mut self, reader: &mut Reader) -> ReadResult<Option<NativeBlock>> {
+        let (num_columns, rows) = match self.head {
+            Some(head) => head,
+            None => {
+                self.head = Some(NativeBlock::read_head(reader)?);
+                return Ok(None);
+            }
+        };
+
+        let column = match self.column.as_mut() {
+            Some(column) => column,
+            None if self.columns.len() == num_columns => {
+                let columns = std::mem::take(&mut self.columns);
+                return Ok(Some(NativeBlock { rows, columns }));
+            }
+            None => {
+                let name = reader.string()?;
+                let data_type = NativeType::parse(&reader.string()?)?;
+                self.column = Some(NativeColumn {
+                    name,
+                    data_type,
+                    values: vec![],
+                });
+                return Ok(None);
+            }
+        };
+
+        // zero rows are represented as zero bytes
+        let left = rows - column.values.len();
+        if left > 0 {
+            let values = match (&column.data_type, column.data_type.fixed_size()) {
+                (NativeType::Nullable(_) | NativeType::Array(_) | NativeType::Tuple(_), _) => {
+                    column.data_type.read_values(reader, rows)?
+                }
+                // the values of the same size are read as many as received
+                (_, Some(size)) if size > 0 => {
+                    let received = (reader.remaining() / size).clamp(1, left);
+                    column.data_type.read_values(reader, received)?
+                }
+                _ => column.data_type.read_values(reader, 1)?,
+            };
+            column.values.extend(values);
+        }
+        if column.values.len() == rows {
+            self.columns.extend(self.column.take());
+        }
+        Ok(None)
+    }
+}
+
+fn parse_variant(value: &DataValue) -> Result<DataValue> {
+    match value {
+        DataValue::String(v) => {
+            let v = String::from_utf8_lossy(v);
+            Ok(DataValue::Variant(VariantValue::from_str(&v)?))
+        }
+        other => Ok(other.clone()),
+    }
+}
+
+fn parse_geometry(value: &DataValue, geography: bool) -> Result<DataValue> {
+    match value {
+        DataValue::String(v) => Ok(DataValue::String(parse_geometry_to_wkb(v, geography)?)),
+        other => Ok(other.clone()),
+    }
+}
+
+// The WKB of the geometries are formatted as WKT, the values which are not valid WKB are kept.
+fn format_geometry(value: &DataValue) -> DataValue {
+    match value {
+        DataValue::String(v) => match geometry_from_wkb(v) {
+            Ok(geometry) => DataValue::String(geometry_to_wkt(&geometry).into_bytes()),
+            Err(_) => value.clone(),
+        },
+        other => other.clone(),
+    }
+}
+
+// field 1 is `is_overflows`, field 2 is `bucket_num`, and field 0 ends the info.
+fn read_block_info(reader: &mut Reader) -> ReadResult<()> {
+    loop {
+        match reader.varint()? {
+            0 => return Ok(()),
+            1 => {
+                reader.u8()?;
+            }
+            2 => {
+                reader.fixed::<4>()?;
+            }
+            field => {
+                return Err(ReadError::Invalid(ErrorCode::BadBytes(format!(
+                    "unknown field of block info: {}",
+                    field
+                ))));
+            }
+        }
+    }
+}
+
+fn write_block_info(buf: &mut Vec<u8>) {
+    write_varint(buf, 1);
+    buf.push(0);
+    write_varint(buf, 2);
+    buf.extend_from_slice(&(-1i32).to_le_bytes());
+    write_varint(buf, 0);
+}
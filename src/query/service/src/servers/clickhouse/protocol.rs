@@ -0,0 +1,506 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use naive_cityhash::cityhash128;
+
+use crate::servers::clickhouse::native_format::NativeBlock;
+use crate::servers::clickhouse::native_format::NativeBlockDecoder;
+
+// https://github.com/ClickHouse/ClickHouse/blob/master/src/Core/Protocol.h
+const CLIENT_HELLO: u64 = 0;
+const CLIENT_QUERY: u64 = 1;
+const CLIENT_DATA: u64 = 2;
+const CLIENT_CANCEL: u64 = 3;
+const CLIENT_PING: u64 = 4;
+
+const SERVER_HELLO: u64 = 0;
+const SERVER_DATA: u64 = 1;
+const SERVER_EXCEPTION: u64 = 2;
+const SERVER_PROGRESS: u64 = 3;
+const SERVER_PONG: u64 = 4;
+const SERVER_END_OF_STREAM: u64 = 5;
+
+const DBMS_MIN_REVISION_WITH_CLIENT_INFO: u64 = 54032;
+const DBMS_MIN_REVISION_WITH_SERVER_TIMEZONE: u64 = 54058;
+const DBMS_MIN_REVISION_WITH_QUOTA_KEY_IN_CLIENT_INFO: u64 = 54060;
+const DBMS_MIN_REVISION_WITH_SERVER_DISPLAY_NAME: u64 = 54372;
+const DBMS_MIN_REVISION_WITH_VERSION_PATCH: u64 = 54401;
+const DBMS_MIN_REVISION_WITH_CLIENT_WRITE_INFO: u64 = 54420;
+const DBMS_MIN_REVISION_WITH_SETTINGS_SERIALIZED_AS_STRINGS: u64 = 54429;
+
+const CLIENT_INTERFACE_TCP: u8 = 1;
+const CLIENT_INTERFACE_HTTP: u8 = 2;
+
+const COMPRESSION_METHOD_NONE: u8 = 0x02;
+const COMPRESSION_METHOD_LZ4: u8 = 0x82;
+// 1 byte for method, 4 bytes for compressed size, 4 bytes for decompressed size
+const COMPRESSION_HEADER_SIZE: usize = 9;
+const CHECKSUM_SIZE: usize = 16;
+// The max size of a compressed frame, and of the data decompressed from it, as ClickHouse.
+const MAX_COMPRESSED_FRAME_SIZE: usize = 0x4000_0000;
+
+/// Error of decoding a packet from the received bytes.
+pub enum ReadError {
+    /// The packet is not received completely, more bytes need to be read from the socket.
+    Incomplete,
+    Invalid(ErrorCode),
+}
+
+impl From<ErrorCode> for ReadError {
+    fn from(error: ErrorCode) -> Self {
+        ReadError::Invalid(error)
+    }
+}
+
+pub type ReadResult<T> = std::result::Result<T, ReadError>;
+
+pub struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    // The length the buffer needs at least to continue, set by the last incomplete read.
+    needed: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(buf: &'a [u8]) -> Reader<'a> {
+        Reader {
+            buf,
+            pos: 0,
+            needed: 0,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    pub fn needed(&self) -> usize {
+        self.needed
+    }
+
+    pub fn bytes(&mut self, len: usize) -> ReadResult<&'a [u8]> {
+        if self.remaining() < len {
+            self.needed = self.pos.saturating_add(len);
+            return Err(ReadError::Incomplete);
+        }
+        let bytes = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    pub fn fixed<const N: usize>(&mut self) -> ReadResult<[u8; N]> {
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    pub fn u8(&mut self) -> ReadResult<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub fn varint(&mut self) -> ReadResult<u64> {
+        let mut value = 0u64;
+        for i in 0..10 {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7F) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ReadError::Invalid(ErrorCode::BadBytes(
+            "varint is too long",
+        )))
+    }
+
+    pub fn binary(&mut self) -> ReadResult<&'a [u8]> {
+        let len = self.varint()? as usize;
+        self.bytes(len)
+    }
+
+    pub fn string(&mut self) -> ReadResult<String> {
+        let bytes = self.binary()?;
+        let string = String::from_utf8(bytes.to_vec())
+            .map_err_to_code(ErrorCode::BadBytes, || "string is not valid utf8")?;
+        Ok(string)
+    }
+}
+
+pub fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+pub fn write_binary(buf: &mut Vec<u8>, value: &[u8]) {
+    write_varint(buf, value.len() as u64);
+    buf.extend_from_slice(value);
+}
+
+pub struct ClientHello {
+    pub client_name: String,
+    pub version_major: u64,
+    pub version_minor: u64,
+    pub revision: u64,
+    pub database: String,
+    pub user: String,
+    pub password: String,
+}
+
+pub struct ClientQuery {
+    pub query_id: String,
+    pub settings: HashMap<String, String>,
+    pub stage: u64,
+    pub compression: bool,
+    pub query: String,
+}
+
+pub enum ClientPacket {
+    Hello(ClientHello),
+    Query(ClientQuery),
+    Data(NativeBlock),
+    Cancel,
+    Ping,
+}
+
+impl ClientPacket {
+    // Decode a packet, `revision` is the negotiated protocol revision. The block of a data packet
+    // is not decoded, `None` is returned once the head of the data packet is read.
+    fn read(reader: &mut Reader, revision: u64) -> ReadResult<Option<ClientPacket>> {
+        let packet = match reader.varint()? {
+            CLIENT_HELLO => Ok(ClientPacket::Hello(ClientHello {
+                client_name: reader.string()?,
+                version_major: reader.varint()?,
+                version_minor: reader.varint()?,
+                revision: reader.varint()?,
+                database: reader.string()?,
+                user: reader.string()?,
+                password: reader.string()?,
+            })),
+            CLIENT_QUERY => Ok(ClientPacket::Query(read_query(reader, revision)?)),
+            CLIENT_DATA => {
+                // name of the external table, which is not supported.
+                reader.string()?;
+                return Ok(None);
+            }
+            CLIENT_CANCEL => Ok(ClientPacket::Cancel),
+            CLIENT_PING => Ok(ClientPacket::Ping),
+            packet => Err(ReadError::Invalid(ErrorCode::UnImplement(format!(
+                "unsupported packet from clickhouse client: {}",
+                packet
+            )))),
+        };
+        packet.map(Some)
+    }
+}
+
+/// Decode the packets from the received bytes.
+///
+/// The block of a data packet may be received in many reads, it is decoded by parts as the bytes
+/// arrive, and a compressed block is decompressed frame by frame, so the received bytes are
+/// decoded only once.
+#[derive(Default)]
+pub struct PacketDecoder {
+    // The block of the data packet being received.
+    block: Option<NativeBlockDecoder>,
+}
+
+impl PacketDecoder {
+    /// Whether a packet is received partly, and its bytes are taken.
+    pub fn in_packet(&self) -> bool {
+        self.block.is_some()
+    }
+
+    /// Decode a packet from `buf`, `revision` is the negotiated protocol revision, and
+    /// `compression` tells whether the blocks of the current query are compressed.
+    ///
+    /// Returns the packet if it is complete, and the number of bytes taken from `buf`, the bytes
+    /// received of an incomplete data packet are taken too.
+    pub fn decode(
+        &mut self,
+        buf: &[u8],
+        revision: u64,
+        compression: bool,
+    ) -> Result<(Option<ClientPacket>, usize)> {
+        let mut reader = Reader::new(buf);
+        let mut block = match self.block.take() {
+            Some(block) => block,
+            None => match ClientPacket::read(&mut reader, revision) {
+                Ok(Some(packet)) => return Ok((Some(packet), reader.position())),
+                Ok(None) => NativeBlockDecoder::default(),
+                Err(ReadError::Incomplete) => return Ok((None, 0)),
+                Err(ReadError::Invalid(cause)) => return Err(cause),
+            },
+        };
+
+        if !compression {
+            let data = &buf[reader.position()..];
+            return match block.push(data)? {
+                Some(block_read) => Ok((
+                    Some(ClientPacket::Data(block_read)),
+                    buf.len() - block.unparsed_len(),
+                )),
+                None => {
+                    self.block = Some(block);
+                    Ok((None, buf.len()))
+                }
+            };
+        }
+
+        // A compressed block may be split into several frames, which are taken once complete.
+        loop {
+            let taken = reader.position();
+            let frame = match read_compressed_frame(&mut reader) {
+                Ok(frame) => frame,
+                Err(ReadError::Incomplete) => {
+                    self.block = Some(block);
+                    return Ok((None, taken));
+                }
+                Err(ReadError::Invalid(cause)) => return Err(cause),
+            };
+            if let Some(block_read) = block.push(&frame)? {
+                return Ok((Some(ClientPacket::Data(block_read)), reader.position()));
+            }
+        }
+    }
+}
+
+fn read_query(reader: &mut Reader, revision: u64) -> ReadResult<ClientQuery> {
+    let query_id = reader.string()?;
+    if revision >= DBMS_MIN_REVISION_WITH_CLIENT_INFO {
+        skip_client_info(reader, revision)?;
+    }
+
+    let mut settings = HashMap::new();
+    loop {
+        let name = reader.string()?;
+        if name.is_empty() {
+            break;
+        }
+        if revision < DBMS_MIN_REVISION_WITH_SETTINGS_SERIALIZED_AS_STRINGS {
+            return Err(ReadError::Invalid(ErrorCode::UnImplement(format!(
+                "settings of clickhouse client with revision {} are not supported",
+                revision
+            ))));
+        }
+        // flags, whether the setting is important or custom
+        reader.varint()?;
+        settings.insert(name, reader.string()?);
+    }
+
+    Ok(ClientQuery {
+        query_id,
+        settings,
+        stage: reader.varint()?,
+        compression: reader.varint()? != 0,
+        query: reader.string()?,
+    })
+}
+
+fn skip_client_info(reader: &mut Reader, revision: u64) -> ReadResult<()> {
+    let query_kind = reader.u8()?;
+    if query_kind == 0 {
+        return Ok(());
+    }
+
+    // initial user, query id and address
+    for _ in 0..3 {
+        reader.binary()?;
+    }
+
+    let interface = reader.u8()?;
+    match interface {
+        CLIENT_INTERFACE_TCP => {
+            // os user, client hostname and client name
+            for _ in 0..3 {
+                reader.binary()?;
+            }
+            // client version major, minor and revision
+            for _ in 0..3 {
+                reader.varint()?;
+            }
+        }
+        CLIENT_INTERFACE_HTTP => {
+            // http method and user agent
+            reader.u8()?;
+            reader.binary()?;
+        }
+        _ => {}
+    }
+
+    if revision >= DBMS_MIN_REVISION_WITH_QUOTA_KEY_IN_CLIENT_INFO {
+        reader.binary()?;
+    }
+    if interface == CLIENT_INTERFACE_TCP && revision >= DBMS_MIN_REVISION_WITH_VERSION_PATCH {
+        reader.varint()?;
+    }
+    Ok(())
+}
+
+fn read_compressed_frame(reader: &mut Reader) -> ReadResult<Vec<u8>> {
+    let checksum = reader.fixed::<CHECKSUM_SIZE>()?;
+    let header = reader.fixed::<COMPRESSION_HEADER_SIZE>()?;
+    let compressed_size = u32::from_le_bytes(header[1..5].try_into().unwrap()) as usize;
+    let decompressed_size = u32::from_le_bytes(header[5..9].try_into().unwrap()) as usize;
+    if compressed_size < COMPRESSION_HEADER_SIZE {
+        return Err(ReadError::Invalid(ErrorCode::BadBytes(
+            "invalid size of compressed frame",
+        )));
+    }
+    // the sizes are from the client, check them before waiting for the data or allocating
+    if compressed_size > MAX_COMPRESSED_FRAME_SIZE || decompressed_size > MAX_COMPRESSED_FRAME_SIZE
+    {
+        return Err(ReadError::Invalid(ErrorCode::BadBytes(format!(
+            "compressed frame is too large: compressed size {}, decompressed size {}",
+            compressed_size, decompressed_size
+        ))));
+    }
+    let data = reader.bytes(compressed_size - COMPRESSION_HEADER_SIZE)?;
+
+    let mut frame = header.to_vec();
+    frame.extend_from_slice(data);
+    let hash = cityhash128(&frame);
+    if checksum[0..8] != hash.lo.to_le_bytes() || checksum[8..16] != hash.hi.to_le_bytes() {
+        return Err(ReadError::Invalid(ErrorCode::BadBytes(
+            "checksum mismatch of compressed frame",
+        )));
+    }
+
+    match header[0] {
+        COMPRESSION_METHOD_NONE => Ok(data.to_vec()),
+        COMPRESSION_METHOD_LZ4 => {
+            let decompressed = lz4::block::decompress(data, Some(decompressed_size as i32))
+                .map_err_to_code(ErrorCode::BadBytes, || "lz4 decompress error")?;
+            Ok(decompressed)
+        }
+        method => Err(ReadError::Invalid(ErrorCode::UnImplement(format!(
+            "unsupported compression method: {:#x}",
+            method
+        )))),
+    }
+}
+
+// default codec is always lz4
+pub fn compress_block(input: Vec<u8>) -> Result<Vec<u8>> {
+    if input.is_empty() {
+        Ok(vec![])
+    } else {
+        // TODO(youngsofun): optimize buffer usages
+        let uncompressed_size = input.len();
+        let compressed =
+            lz4::block::compress(&input, Some(lz4::block::CompressionMode::FAST(1)), false)
+                .map_err_to_code(ErrorCode::BadBytes, || "lz4 compress error")?;
+
+        let mut compressed_with_header =
+            Vec::with_capacity(compressed.len() + COMPRESSION_HEADER_SIZE);
+        compressed_with_header.push(COMPRESSION_METHOD_LZ4);
+        let compressed_size = (compressed.len() + COMPRESSION_HEADER_SIZE) as u32;
+        let uncompressed_size = uncompressed_size as u32;
+        compressed_with_header.extend_from_slice(&compressed_size.to_le_bytes());
+        compressed_with_header.extend_from_slice(&uncompressed_size.to_le_bytes());
+        compressed_with_header.extend_from_slice(&compressed);
+
+        let mut output = Vec::with_capacity(compressed_with_header.len() + CHECKSUM_SIZE);
+        let checksum = cityhash128(&compressed_with_header);
+        output.extend_from_slice(&checksum.lo.to_le_bytes());
+        output.extend_from_slice(&checksum.hi.to_le_bytes());
+        output.extend_from_slice(&compressed_with_header);
+        Ok(output)
+    }
+}
+
+pub struct ServerHello<'a> {
+    pub name: &'a str,
+    pub version_major: u64,
+    pub version_minor: u64,
+    pub version_patch: u64,
+    pub revision: u64,
+    pub timezone: &'a str,
+    pub display_name: &'a str,
+}
+
+pub fn write_hello(buf: &mut Vec<u8>, hello: &ServerHello, revision: u64) {
+    write_varint(buf, SERVER_HELLO);
+    write_binary(buf, hello.name.as_bytes());
+    write_varint(buf, hello.version_major);
+    write_varint(buf, hello.version_minor);
+    write_varint(buf, hello.revision);
+    if revision >= DBMS_MIN_REVISION_WITH_SERVER_TIMEZONE {
+        write_binary(buf, hello.timezone.as_bytes());
+    }
+    if revision >= DBMS_MIN_REVISION_WITH_SERVER_DISPLAY_NAME {
+        write_binary(buf, hello.display_name.as_bytes());
+    }
+    if revision >= DBMS_MIN_REVISION_WITH_VERSION_PATCH {
+        write_varint(buf, hello.version_patch);
+    }
+}
+
+pub fn write_data(buf: &mut Vec<u8>, block: &NativeBlock, compression: bool) -> Result<()> {
+    write_varint(buf, SERVER_DATA);
+    // name of the temporary table
+    write_binary(buf, b"");
+    let mut data = vec![];
+    block.write(&mut data)?;
+    match compression {
+        true => buf.extend(compress_block(data)?),
+        false => buf.extend(data),
+    }
+    Ok(())
+}
+
+pub fn write_exception(buf: &mut Vec<u8>, error: &ErrorCode) {
+    write_varint(buf, SERVER_EXCEPTION);
+    buf.extend_from_slice(&(error.code() as i32).to_le_bytes());
+    write_binary(buf, b"DB::Exception");
+    write_binary(buf, error.message().as_bytes());
+    write_binary(buf, error.backtrace_str().as_bytes());
+    // has nested exception
+    buf.push(0);
+}
+
+pub struct Progress {
+    pub read_rows: u64,
+    pub read_bytes: u64,
+    pub total_rows: u64,
+    pub written_rows: u64,
+    pub written_bytes: u64,
+}
+
+pub fn write_progress(buf: &mut Vec<u8>, progress: &Progress, revision: u64) {
+    write_varint(buf, SERVER_PROGRESS);
+    write_varint(buf, progress.read_rows);
+    write_varint(buf, progress.read_bytes);
+    write_varint(buf, progress.total_rows);
+    if revision >= DBMS_MIN_REVISION_WITH_CLIENT_WRITE_INFO {
+        write_varint(buf, progress.written_rows);
+        write_varint(buf, progress.written_bytes);
+    }
+}
+
+pub fn write_pong(buf: &mut Vec<u8>) {
+    write_varint(buf, SERVER_PONG);
+}
+
+pub fn write_end_of_stream(buf: &mut Vec<u8>) {
+    write_varint(buf, SERVER_END_OF_STREAM);
+}
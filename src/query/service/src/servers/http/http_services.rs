@@ -29,6 +29,7 @@ use poem::EndpointExt;
 use poem::Route;
 use tracing::info;
 
+use super::v1::oauth_route;
 use super::v1::upload_to_stage;
 use crate::auth::AuthMgr;
 use crate::servers::http::middleware::HTTPSessionMiddleware;
@@ -98,9 +99,16 @@ impl HttpHandler {
         };
 
         let auth_manager = AuthMgr::create(config).await?;
-        let session_middleware = HTTPSessionMiddleware::create(self.kind, auth_manager);
+        let session_middleware = HTTPSessionMiddleware::create(self.kind, auth_manager.clone());
+        let ep = match self.kind {
+            // the OAuth endpoints authenticate the users by the OIDC provider
+            HttpHandlerKind::Query => Route::new()
+                .nest("/v1/oauth", oauth_route(auth_manager))
+                .nest("/", ep.with(session_middleware))
+                .boxed(),
+            HttpHandlerKind::Clickhouse => ep.with(session_middleware).boxed(),
+        };
         Ok(ep
-            .with(session_middleware)
             .with(NormalizePath::new(TrailingSlash::Trim))
             .with(CatchPanic::new())
             .boxed())
@@ -48,17 +48,21 @@ impl HTTPSessionMiddleware {
     }
 }
 
+pub(crate) fn client_ip(req: &Request) -> Option<String> {
+    match req.remote_addr().0 {
+        Addr::SocketAddr(addr) => Some(addr.ip().to_string()),
+        Addr::Custom(..) => Some("127.0.0.1".to_string()),
+        _ => None,
+    }
+}
+
 fn get_credential(req: &Request, kind: HttpHandlerKind) -> Result<Credential> {
     let auth_headers: Vec<_> = req.headers().get_all(AUTHORIZATION).iter().collect();
     if auth_headers.len() > 1 {
         let msg = &format!("Multiple {} headers detected", AUTHORIZATION);
         return Err(ErrorCode::AuthenticateFailure(msg));
     }
-    let client_ip = match req.remote_addr().0 {
-        Addr::SocketAddr(addr) => Some(addr.ip().to_string()),
-        Addr::Custom(..) => Some("127.0.0.1".to_string()),
-        _ => None,
-    };
+    let client_ip = client_ip(req);
     if auth_headers.is_empty() {
         if let HttpHandlerKind::Clickhouse = kind {
             let (user, key) = (
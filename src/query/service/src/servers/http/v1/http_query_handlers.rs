@@ -14,9 +14,13 @@
 
 use std::str::FromStr;
 
+use async_stream::stream;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_formats::output_format::OutputFormatType;
+use futures::StreamExt;
+use http::header;
+use http::HeaderMap;
 use poem::error::BadRequest;
 use poem::error::Error as PoemError;
 use poem::error::InternalServerError;
@@ -30,6 +34,7 @@ use poem::web::Path;
 use poem::web::Query;
 use poem::Body;
 use poem::IntoResponse;
+use poem::Response;
 use poem::Route;
 use serde::Deserialize;
 use serde::Serialize;
@@ -40,13 +45,18 @@ use tracing::info;
 use super::query::ExecuteStateKind;
 use super::query::HttpQueryRequest;
 use super::query::HttpQueryResponseInternal;
+use crate::interpreters::InterpreterFactory;
+use crate::servers::http::v1::query::HttpQuery;
 use crate::servers::http::v1::query::Progresses;
+use crate::servers::http::v1::query::StageTiming;
 use crate::servers::http::v1::HttpQueryContext;
 use crate::servers::http::v1::HttpQueryManager;
 use crate::servers::http::v1::HttpSessionConf;
 use crate::servers::http::v1::JsonBlock;
 use crate::sessions::QueryAffect;
 use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+use crate::sql::Planner;
 use crate::storages::result::ResultTable;
 
 pub fn make_page_uri(query_id: &str, page_no: usize) -> String {
@@ -65,6 +75,10 @@ pub fn make_kill_uri(query_id: &str) -> String {
     format!("/v1/query/{}/kill", query_id)
 }
 
+pub fn make_progress_uri(query_id: &str) -> String {
+    format!("/v1/query/{}/progress", query_id)
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct QueryError {
     pub code: u16,
@@ -85,6 +99,20 @@ pub struct QueryStats {
     #[serde(flatten)]
     pub progresses: Progresses,
     pub running_time_ms: f64,
+    /// the time spent in each stage the query went through, in order
+    #[serde(default)]
+    pub stages: Vec<StageTiming>,
+}
+
+impl QueryStats {
+    /// Percentage of the rows estimated to scan that are scanned, `None` if nothing is estimated.
+    pub fn scan_percentage(&self) -> Option<f64> {
+        let scanned = self.progresses.scan_progress.rows;
+        match self.progresses.total_scan_progress.rows {
+            0 => None,
+            total => Some((scanned as f64 * 100.0 / total as f64).min(100.0)),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -100,6 +128,8 @@ pub struct QueryResponse {
     pub stats: QueryStats,
     pub affect: Option<QueryAffect>,
     pub stats_uri: Option<String>,
+    #[serde(default)]
+    pub progress_uri: Option<String>,
     // just call it after client not use it anymore, not care about the server-side behavior
     pub final_uri: Option<String>,
     pub next_uri: Option<String>,
@@ -120,6 +150,7 @@ impl QueryResponse {
         let stats = QueryStats {
             progresses: state.progresses.clone(),
             running_time_ms: state.running_time_ms,
+            stages: state.stages.clone(),
         };
         QueryResponse {
             data: data.into(),
@@ -132,6 +163,7 @@ impl QueryResponse {
             id: id.clone(),
             next_uri: next_url,
             stats_uri: Some(make_state_uri(&id)),
+            progress_uri: Some(make_progress_uri(&id)),
             final_uri: Some(make_final_uri(&id)),
             kill_uri: Some(make_kill_uri(&id)),
             error: r.state.error.as_ref().map(QueryError::from_error_code),
@@ -150,6 +182,7 @@ impl QueryResponse {
             session: None,
             next_uri: None,
             stats_uri: None,
+            progress_uri: None,
             final_uri: None,
             kill_uri: None,
             error: Some(QueryError::from_error_code(err)),
@@ -204,6 +237,42 @@ async fn query_state_handler(
     }
 }
 
+/// The progress of a query, polled by the clients to draw progress bars, lighter than the state
+/// of the query since no data is returned.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryProgress {
+    pub id: String,
+    pub state: ExecuteStateKind,
+    pub stats: QueryStats,
+    /// percentage of the rows estimated to scan that are scanned
+    pub scan_percentage: Option<f64>,
+}
+
+#[poem::handler]
+async fn query_progress_handler(
+    _ctx: &HttpQueryContext,
+    Path(query_id): Path<String>,
+) -> PoemResult<Json<QueryProgress>> {
+    let http_query_manager = HttpQueryManager::instance();
+    match http_query_manager.get_query(&query_id).await {
+        Some(query) => {
+            let state = query.get_response_state_only().await.state;
+            let stats = QueryStats {
+                progresses: state.progresses,
+                running_time_ms: state.running_time_ms,
+                stages: state.stages,
+            };
+            Ok(Json(QueryProgress {
+                id: query_id,
+                state: state.state,
+                scan_percentage: stats.scan_percentage(),
+                stats,
+            }))
+        }
+        None => Err(query_id_not_found(query_id)),
+    }
+}
+
 #[poem::handler]
 async fn query_page_handler(
     _ctx: &HttpQueryContext,
@@ -224,12 +293,23 @@ async fn query_page_handler(
     }
 }
 
+#[derive(Deserialize)]
+struct QueryHandlerParams {
+    pub format: Option<String>,
+}
+
 #[poem::handler]
 pub(crate) async fn query_handler(
     ctx: &HttpQueryContext,
+    Query(params): Query<QueryHandlerParams>,
+    headers: &HeaderMap,
     Json(req): Json<HttpQueryRequest>,
-) -> PoemResult<Json<QueryResponse>> {
+) -> PoemResult<Response> {
     info!("receive http query: {:?}", req);
+    if is_arrow_stream_requested(&params, headers)? {
+        return query_arrow_stream(ctx, req).await;
+    }
+
     let http_query_manager = HttpQueryManager::instance();
     let sql = req.sql.clone();
     let query = http_query_manager.try_create_query(ctx, req).await;
@@ -250,25 +330,114 @@ pub(crate) async fn query_handler(
                 &query.id, &resp.state, rows, next_page, sql
             );
             query.update_expire_time(false).await;
-            Ok(Json(QueryResponse::from_internal(
-                query.id.to_string(),
-                resp,
-            )))
+            Ok(Json(QueryResponse::from_internal(query.id.to_string(), resp)).into_response())
         }
         Err(e) => {
             error!("Fail to start sql, Error: {:?}", e);
-            Ok(Json(QueryResponse::fail_to_start_sql(&e)))
+            Ok(Json(QueryResponse::fail_to_start_sql(&e)).into_response())
         }
     }
 }
 
+// The results are in the pages of JSON by default, the clients ask for the Arrow IPC stream by
+// `format=arrow` or the `Accept` header.
+fn is_arrow_stream_requested(params: &QueryHandlerParams, headers: &HeaderMap) -> PoemResult<bool> {
+    let content_type = OutputFormatType::ArrowStream.get_content_type();
+    match params.format.as_deref().map(|format| format.to_lowercase()) {
+        Some(format) if format == "json" => Ok(false),
+        Some(format) if format == "arrow" => Ok(true),
+        Some(format) => Err(PoemError::from_string(
+            format!("unsupported format {}, expect json or arrow", format),
+            StatusCode::BAD_REQUEST,
+        )),
+        None => Ok(headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|media_type| media_type.trim().starts_with(&content_type))),
+    }
+}
+
+/// Streams all the results in the Arrow IPC streaming format, the query is not kept by the
+/// server, so there are no pages, stats or kill URIs.
+async fn query_arrow_stream(ctx: &HttpQueryContext, req: HttpQueryRequest) -> PoemResult<Response> {
+    let session = HttpQuery::get_session(ctx, &req)
+        .await
+        .map_err(BadRequest)?;
+    let context = session
+        .create_query_context()
+        .await
+        .map_err(InternalServerError)?;
+
+    let mut planner = Planner::new(context.clone());
+    let (plan, _, _) = planner.plan_sql(&req.sql).await.map_err(BadRequest)?;
+    context.attach_query_str(plan.to_string(), &req.sql);
+    let interpreter = InterpreterFactory::get(context.clone(), &plan)
+        .await
+        .map_err(BadRequest)?;
+    let mut data_stream = interpreter
+        .execute(context.clone())
+        .await
+        .map_err(InternalServerError)?;
+
+    let format = OutputFormatType::ArrowStream;
+    let format_settings = context.get_format_settings().map_err(InternalServerError)?;
+    let mut output_format = format.create_format(plan.schema(), format_settings);
+    let prefix = output_format
+        .serialize_prefix()
+        .map_err(InternalServerError)?;
+
+    // try to catch runtime error before http response, so the client gets http 500
+    let first_block = match data_stream.next().await {
+        Some(block) => Some(
+            block
+                .and_then(|block| output_format.serialize_block(&block))
+                .map_err(InternalServerError)?,
+        ),
+        None => None,
+    };
+
+    let stream = stream! {
+        yield Ok(prefix);
+        let mut ok = true;
+        if let Some(block) = first_block {
+            yield Ok(block);
+            while let Some(block) = data_stream.next().await {
+                match block.and_then(|block| output_format.serialize_block(&block)) {
+                    Ok(bytes) => yield Ok(bytes),
+                    // the stream is broken without the end, the client fails to read it
+                    Err(cause) => {
+                        error!("Fail to stream the results in arrow, Error: {:?}", cause);
+                        yield Err(cause);
+                        ok = false;
+                        break;
+                    }
+                }
+            }
+        }
+        if ok {
+            yield output_format.finalize();
+        }
+        // to hold session ref until stream is all consumed
+        let _ = session.get_id();
+    };
+    Ok(Body::from_bytes_stream::<_, _, ErrorCode>(stream)
+        .with_content_type(format.get_content_type())
+        .into_response())
+}
+
 pub fn query_route() -> Route {
     // Note: endpoints except /v1/query may change without notice, use uris in response instead
     Route::new()
         .at("/", post(query_handler))
-        .at("/:id", get(query_state_handler))
+        .at(
+            "/:id",
+            get(query_state_handler).delete(query_cancel_handler),
+        )
         .at("/:id/download", get(result_download_handler))
         .at("/:id/page/:page_no", get(query_page_handler))
+        .at("/:id/progress", get(query_progress_handler))
         .at(
             "/:id/kill",
             get(query_cancel_handler).post(query_cancel_handler),
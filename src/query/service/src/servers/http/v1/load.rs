@@ -18,21 +18,27 @@ use std::sync::Arc;
 use common_base::base::tokio;
 use common_base::base::tokio::io::AsyncRead;
 use common_base::base::tokio::io::AsyncReadExt;
+use common_base::base::tokio::task::JoinHandle;
 use common_base::base::ProgressValues;
 use common_base::base::TrySpawn;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::parse_escape_string;
+use common_meta_types::StageFileFormatType;
 use common_pipeline_sources::processors::sources::input_formats::InputContext;
 use common_pipeline_sources::processors::sources::input_formats::StreamingReadBatch;
 use futures::StreamExt;
+use opendal::io_util::CompressAlgorithm;
 use poem::error::BadRequest;
 use poem::error::InternalServerError;
 use poem::error::Result as PoemResult;
 use poem::http::StatusCode;
 use poem::web::Json;
 use poem::web::Multipart;
+use poem::Body;
+use poem::FromRequest;
 use poem::Request;
+use poem::RequestBody;
 use serde::Deserialize;
 use serde::Serialize;
 use tokio::sync::mpsc::Sender;
@@ -40,6 +46,7 @@ use tokio::sync::mpsc::Sender;
 use super::HttpQueryContext;
 use crate::interpreters::InterpreterFactory;
 use crate::sessions::QueryContext;
+use crate::sessions::Session;
 use crate::sessions::SessionType;
 use crate::sessions::TableContext;
 use crate::sql::plans::InsertInputSource;
@@ -53,6 +60,8 @@ pub struct LoadResponse {
     pub stats: ProgressValues,
     pub error: Option<String>,
     pub files: Vec<String>,
+    // rows skipped with the setting `skip_bad_rows`
+    pub rejected_rows: Vec<serde_json::Value>,
 }
 
 #[allow(clippy::manual_async_fn)]
@@ -81,25 +90,25 @@ fn remove_quote(s: &[u8]) -> &[u8] {
     r
 }
 
+/// Load the files of a multipart request, or the whole body as a single file.
+///
+/// With the setting `streaming_load_batch_size`, the input is committed by separate INSERTs of
+/// about the size each, the files in CSV/TSV/NDJSON are cut at record boundaries.
 #[poem::handler]
 pub async fn streaming_load(
     ctx: &HttpQueryContext,
     req: &Request,
-    mut multipart: Multipart,
+    body: Body,
 ) -> PoemResult<Json<LoadResponse>> {
     let session = ctx.get_session(SessionType::HTTPStreamingLoad);
-    let context = session
-        .create_query_context()
-        .await
-        .map_err(InternalServerError)?;
-
     let insert_sql = req
         .headers()
         .get("insert_sql")
         .and_then(|v| v.to_str().ok())
-        .unwrap_or("");
+        .unwrap_or("")
+        .to_string();
 
-    let settings = context.get_settings();
+    let settings = session.get_settings();
     for (key, value) in req.headers().iter() {
         if settings.has_setting(key.as_str()) {
             let value = value.to_str().map_err(InternalServerError)?;
@@ -109,137 +118,380 @@ pub async fn streaming_load(
                 .map_err(InternalServerError)?
         }
     }
-
-    let mut planner = Planner::new(context.clone());
-    let (mut plan, _, _) = planner
-        .plan_sql(insert_sql)
-        .await
+    let batch_size = settings
+        .get_streaming_load_batch_size()
         .map_err(InternalServerError)?;
-    context.attach_query_str(plan.to_string(), insert_sql);
-
-    let schema = plan.schema();
-    match &mut plan {
-        Plan::Insert(insert) => match &mut insert.source {
-            InsertInputSource::StreamingWithFormat(format, start, input_context_ref) => {
-                let sql_rest = &insert_sql[*start..].trim();
-                if !sql_rest.is_empty() {
+    let buffer_size = settings
+        .get_streaming_load_buffer_size()
+        .map_err(InternalServerError)?;
+
+    let is_multi_part = req
+        .content_type()
+        .map(|t| t.starts_with("multipart/form-data"))
+        .unwrap_or(false);
+    let mut loader = StreamingLoader {
+        session,
+        insert_sql,
+        is_multi_part,
+        batch_size: batch_size as usize,
+        buffer_size: buffer_size as usize,
+        batch: None,
+        files: vec![],
+        committed_batches: 0,
+        stats: ProgressValues::default(),
+        rejected_rows: vec![],
+    };
+    let mut body = RequestBody::new(body);
+    let multipart = if is_multi_part {
+        Some(Multipart::from_request(req, &mut body).await?)
+    } else {
+        None
+    };
+
+    // the INSERT is checked before reading any input
+    loader.start_batch(false).await?;
+    match multipart {
+        Some(multipart) => loader.load_multi_part(multipart).await?,
+        None => {
+            let reader = body.take()?.into_async_read();
+            loader
+                .load_file("file_with_no_name".to_string(), reader)
+                .await?
+        }
+    }
+    loader.finish_batch().await?;
+
+    Ok(Json(LoadResponse {
+        error: None,
+        state: "SUCCESS".to_string(),
+        id: uuid::Uuid::new_v4().to_string(),
+        stats: loader.stats,
+        files: loader.files,
+        rejected_rows: loader.rejected_rows,
+    }))
+}
+
+/// An INSERT fed with a part of the input.
+struct LoadBatch {
+    context: Arc<QueryContext>,
+    input_context: Arc<InputContext>,
+    // taken when the input is complete
+    tx: Option<Sender<Result<StreamingReadBatch>>>,
+    handler: JoinHandle<Result<()>>,
+    bytes: usize,
+    // the index of the last file sent to the batch
+    last_file: Option<usize>,
+    // started in the middle of a file, the batch holds the rest of the file only
+    is_partial: bool,
+}
+
+impl Drop for LoadBatch {
+    // the loading stopped with the input partly read, the INSERT must not commit
+    fn drop(&mut self) {
+        if let Some(tx) = self.tx.take() {
+            tokio::spawn(async move {
+                let cause = ErrorCode::BadBytes("streaming load stopped before the end of input");
+                if let Err(e) = tx.send(Err(cause)).await {
+                    tracing::warn!("streaming load channel disconnect. {}", e);
+                }
+            });
+        }
+    }
+}
+
+struct StreamingLoader {
+    session: Arc<Session>,
+    insert_sql: String,
+    is_multi_part: bool,
+    // 0 for all the input in one batch
+    batch_size: usize,
+    buffer_size: usize,
+    batch: Option<LoadBatch>,
+    files: Vec<String>,
+    committed_batches: usize,
+    stats: ProgressValues,
+    rejected_rows: Vec<serde_json::Value>,
+}
+
+impl StreamingLoader {
+    async fn start_batch(&mut self, is_partial: bool) -> PoemResult<()> {
+        let context = self
+            .session
+            .create_query_context()
+            .await
+            .map_err(InternalServerError)?;
+        let mut planner = Planner::new(context.clone());
+        let (mut plan, _, _) = planner
+            .plan_sql(&self.insert_sql)
+            .await
+            .map_err(InternalServerError)?;
+        context.attach_query_str(plan.to_string(), &self.insert_sql);
+
+        let schema = plan.schema();
+        let (format, input_context_ref) = match &mut plan {
+            Plan::Insert(insert) => match &mut insert.source {
+                InsertInputSource::StreamingWithFormat(format, start, input_context_ref) => {
+                    let sql_rest = &self.insert_sql[*start..].trim();
+                    if !sql_rest.is_empty() {
+                        return Err(poem::Error::from_string(
+                            "should NOT have data after `Format` in streaming load.",
+                            StatusCode::BAD_REQUEST,
+                        ));
+                    };
+                    (format.clone(), input_context_ref)
+                }
+                _non_supported_source => {
                     return Err(poem::Error::from_string(
-                        "should NOT have data after `Format` in streaming load.",
+                        "Only supports streaming upload. e.g. INSERT INTO $table FORMAT CSV, got insert ... select.",
                         StatusCode::BAD_REQUEST,
                     ));
-                };
-                let (tx, rx) = tokio::sync::mpsc::channel(2);
-                let input_context = Arc::new(
-                    InputContext::try_create_from_insert(
-                        format.as_str(),
-                        rx,
-                        context.get_settings(),
-                        schema,
-                        context.get_scan_progress(),
-                        true,
-                    )
-                    .await
-                    .map_err(InternalServerError)?,
-                );
-                *input_context_ref = Some(input_context.clone());
-                tracing::info!("streaming load {:?}", input_context);
-
-                let handler = context.spawn(execute_query(context.clone(), plan));
-                let files = read_multi_part(multipart, tx, &input_context).await?;
-
-                match handler.await {
-                    Ok(Ok(_)) => Ok(Json(LoadResponse {
-                        error: None,
-                        state: "SUCCESS".to_string(),
-                        id: uuid::Uuid::new_v4().to_string(),
-                        stats: context.get_scan_progress_value(),
-                        files,
-                    })),
-                    Ok(Err(cause)) => Err(poem::Error::from_string(
-                        format!("execute fail: {}", cause.message()),
-                        StatusCode::BAD_REQUEST,
-                    )),
-                    Err(_) => Err(poem::Error::from_string(
-                        "Maybe panic.",
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                    )),
                 }
+            },
+            non_insert_plan => {
+                return Err(poem::Error::from_string(
+                    format!(
+                        "Only supports INSERT statement in streaming load, but got {}",
+                        non_insert_plan
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        // the batches of the input buffered are bounded, the reading waits for the parsing
+        let settings = context.get_settings();
+        let read_batch_size = settings
+            .get_input_read_buffer_size()
+            .map_err(InternalServerError)? as usize;
+        let capacity = std::cmp::max(1, self.buffer_size / std::cmp::max(1, read_batch_size));
+        let (tx, rx) = tokio::sync::mpsc::channel(capacity);
+        let mut input_context = InputContext::try_create_from_insert(
+            format.as_str(),
+            rx,
+            settings,
+            schema,
+            context.get_scan_progress(),
+            self.is_multi_part,
+        )
+        .await
+        .map_err(InternalServerError)?;
+        if is_partial {
+            // the header was skipped by the previous batch
+            input_context.rows_to_skip = 0;
+        }
+        let input_context = Arc::new(input_context);
+        *input_context_ref = Some(input_context.clone());
+        tracing::info!("streaming load {:?}", input_context);
+
+        let handler = context.spawn(execute_query(context.clone(), plan));
+        self.batch = Some(LoadBatch {
+            context,
+            input_context,
+            tx: Some(tx),
+            handler,
+            bytes: 0,
+            last_file: None,
+            is_partial,
+        });
+        Ok(())
+    }
+
+    /// Close the input of the current batch and wait for its INSERT to commit.
+    async fn finish_batch(&mut self) -> PoemResult<()> {
+        let mut batch = match self.batch.take() {
+            None => return Ok(()),
+            Some(batch) => batch,
+        };
+        batch.tx.take();
+        match (&mut batch.handler).await {
+            Ok(Ok(_)) => {
+                let stats = batch.context.get_scan_progress_value();
+                self.stats.rows += stats.rows;
+                self.stats.bytes += stats.bytes;
+                self.rejected_rows.extend(
+                    batch
+                        .input_context
+                        .take_rejected_rows()
+                        .iter()
+                        .map(|row| row.to_json()),
+                );
+                self.committed_batches += 1;
+                tracing::info!(
+                    "streaming load committed batch {} of {} bytes",
+                    self.committed_batches,
+                    batch.bytes
+                );
+                Ok(())
             }
-            _non_supported_source => Err(poem::Error::from_string(
-                "Only supports streaming upload. e.g. INSERT INTO $table FORMAT CSV, got insert ... select.",
+            Ok(Err(cause)) => Err(poem::Error::from_string(
+                format!("execute fail: {}{}", cause.message(), self.committed()),
                 StatusCode::BAD_REQUEST,
             )),
-        },
-        non_insert_plan => Err(poem::Error::from_string(
-            format!(
-                "Only supports INSERT statement in streaming load, but got {}",
-                non_insert_plan
-            ),
-            StatusCode::BAD_REQUEST,
-        )),
+            Err(_) => Err(poem::Error::from_string(
+                format!("Maybe panic.{}", self.committed()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        }
+    }
+
+    // the batches committed are kept when a later one fails
+    fn committed(&self) -> String {
+        if self.committed_batches == 0 {
+            return "".to_string();
+        }
+        format!(
+            ", {} batches with {} rows were committed before",
+            self.committed_batches, self.stats.rows
+        )
     }
-}
 
-async fn read_multi_part(
-    mut multipart: Multipart,
-    tx: Sender<Result<StreamingReadBatch>>,
-    input_context: &Arc<InputContext>,
-) -> poem::Result<Vec<String>> {
-    let mut files = vec![];
-    loop {
-        match multipart.next_field().await {
-            Err(cause) => {
-                if let Err(cause) = tx
-                    .send(Err(ErrorCode::BadBytes(format!(
-                        "Parse multipart error, cause {:?}",
-                        cause
-                    ))))
-                    .await
-                {
-                    tracing::warn!("Multipart channel disconnect. {}", cause);
+    async fn send(
+        &mut self,
+        path: &str,
+        compression: Option<CompressAlgorithm>,
+        data: Vec<u8>,
+    ) -> PoemResult<()> {
+        let file = self.files.len() - 1;
+        let batch = self.batch.as_mut().expect("batch must be started");
+        let is_start = batch.last_file != Some(file);
+        batch.last_file = Some(file);
+        batch.bytes += data.len();
+        tracing::debug!("streaming load read {} bytes", data.len());
+        let read_batch = StreamingReadBatch {
+            data,
+            path: path.to_string(),
+            is_start,
+            compression,
+        };
+        let tx = batch.tx.as_ref().expect("batch must be open");
+        if tx.send(Ok(read_batch)).await.is_err() {
+            // the INSERT stopped early, its error is returned
+            self.finish_batch().await?;
+            return Err(poem::Error::from_string(
+                "streaming load stopped",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn load_multi_part(&mut self, mut multipart: Multipart) -> PoemResult<()> {
+        loop {
+            match multipart.next_field().await {
+                Err(cause) => {
+                    tracing::warn!("streaming load parse multipart error, cause {:?}", cause);
+                    return Err(cause.into());
                 }
-                return Err(cause.into());
+                Ok(None) => return Ok(()),
+                Ok(Some(field)) => {
+                    let filename = field.file_name().unwrap_or("file_with_no_name").to_string();
+                    self.load_file(filename, field.into_async_read()).await?;
+                }
+            }
+        }
+    }
+
+    async fn load_file<R: AsyncRead + Unpin>(
+        &mut self,
+        filename: String,
+        mut reader: R,
+    ) -> PoemResult<()> {
+        if self.batch.is_none() {
+            self.start_batch(false).await?;
+        }
+        let input_context = self.batch.as_ref().unwrap().input_context.clone();
+        let compression = input_context
+            .get_compression_alg(&filename)
+            .map_err(BadRequest)?;
+        let record_end = self.record_end(&input_context, &compression);
+        let quote = match input_context.plan.as_stream() {
+            Ok(plan) if plan.format == StageFileFormatType::Csv => {
+                Some(input_context.format_settings.quote_char)
             }
-            Ok(None) => {
+            _ => None,
+        };
+        let mut quoted = false;
+
+        tracing::debug!("streaming load start read {}", &filename);
+        self.files.push(filename.clone());
+        loop {
+            let mut data = vec![0u8; input_context.read_batch_size];
+            let n = read_full(&mut reader, &mut data[0..])
+                .await
+                .map_err(InternalServerError)?;
+            if n == 0 {
                 break;
             }
-            Ok(Some(field)) => {
-                let filename = field.file_name().unwrap_or("file_with_no_name").to_string();
-                let compression = input_context
-                    .get_compression_alg(&filename)
-                    .map_err(BadRequest)?;
-                tracing::debug!("Multipart start read {}", &filename);
-                files.push(filename.clone());
-                let mut async_reader = field.into_async_read();
-                let mut is_start = true;
-                loop {
-                    let mut batch = vec![0u8; input_context.read_batch_size];
-                    let n = read_full(&mut async_reader, &mut batch[0..])
-                        .await
-                        .map_err(InternalServerError)?;
-                    if n == 0 {
-                        break;
-                    } else {
-                        batch.truncate(n);
-                        tracing::debug!("Multipart read {} bytes", n);
-                        if let Err(e) = tx
-                            .send(Ok(StreamingReadBatch {
-                                data: batch,
-                                path: filename.clone(),
-                                is_start,
-                                compression,
-                            }))
-                            .await
-                        {
-                            tracing::warn!(" Multipart fail to send ReadBatch: {}", e);
-                        }
-                        is_start = false;
+            data.truncate(n);
+            if self.batch.is_none() {
+                self.start_batch(true).await?;
+            }
+
+            let cut = record_end.and_then(|end| last_record_end(&data, end, quote, &mut quoted));
+            let bytes = self.batch.as_ref().unwrap().bytes + data.len();
+            match cut {
+                Some(end) if bytes >= self.batch_size => {
+                    let rest = data.split_off(end + 1);
+                    self.send(&filename, compression, data).await?;
+                    self.finish_batch().await?;
+                    if !rest.is_empty() {
+                        self.start_batch(true).await?;
+                        self.send(&filename, compression, rest).await?;
                     }
                 }
+                _ => self.send(&filename, compression, data).await?,
             }
         }
+
+        // the batch is finished at the end of the file if it is large enough, so that the files
+        // can not be cut are committed one or several at a time
+        if let Some(batch) = self.batch.as_ref() {
+            if self.batch_size > 0 && (batch.is_partial || batch.bytes >= self.batch_size) {
+                self.finish_batch().await?;
+            }
+        }
+        Ok(())
+    }
+
+    // the files are cut only when they are uncompressed and no record contains a record
+    // delimiter out of quotes.
+    fn record_end(
+        &self,
+        input_context: &InputContext,
+        compression: &Option<CompressAlgorithm>,
+    ) -> Option<u8> {
+        if self.batch_size == 0 || compression.is_some() {
+            return None;
+        }
+        match input_context.plan.as_stream() {
+            Ok(plan) => match plan.format {
+                StageFileFormatType::Csv
+                | StageFileFormatType::Tsv
+                | StageFileFormatType::NdJson => Some(input_context.record_delimiter.end()),
+                _ => None,
+            },
+            Err(_) => None,
+        }
+    }
+}
+
+/// The offset of the last record delimiter in the chunk, the quote state is kept across the
+/// chunks of a file.
+fn last_record_end(
+    data: &[u8],
+    record_end: u8,
+    quote: Option<u8>,
+    quoted: &mut bool,
+) -> Option<usize> {
+    let mut end = None;
+    for (i, b) in data.iter().enumerate() {
+        if Some(*b) == quote {
+            *quoted = !*quoted;
+        } else if *b == record_end && !*quoted {
+            end = Some(i);
+        }
     }
-    Ok(files)
+    end
 }
 
 pub async fn read_full<R: AsyncRead + Unpin>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
@@ -15,18 +15,23 @@
 mod http_query_handlers;
 pub mod json_block;
 mod load;
+mod oauth;
 mod query;
 mod stage;
 
 pub use http_query_handlers::make_final_uri;
 pub use http_query_handlers::make_page_uri;
+pub use http_query_handlers::make_progress_uri;
 pub use http_query_handlers::make_state_uri;
 pub use http_query_handlers::query_route;
+pub use http_query_handlers::QueryProgress;
 pub use http_query_handlers::QueryResponse;
 pub use http_query_handlers::QueryStats;
 pub(crate) use json_block::JsonBlock;
 pub use load::streaming_load;
 pub use load::LoadResponse;
+pub use oauth::oauth_route;
+pub use oauth::OAuthLoginResponse;
 pub use query::ExecuteStateKind;
 pub use query::HttpQueryContext;
 pub use query::HttpQueryHandle;
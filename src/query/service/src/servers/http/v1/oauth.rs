@@ -0,0 +1,182 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_users::OAuthToken;
+use common_users::OidcClient;
+use poem::error::Error as PoemError;
+use poem::error::Result as PoemResult;
+use poem::get;
+use poem::http::StatusCode;
+use poem::post;
+use poem::web::Data;
+use poem::web::Form;
+use poem::web::Json;
+use poem::web::Query;
+use poem::web::Redirect;
+use poem::Endpoint;
+use poem::EndpointExt;
+use poem::Request;
+use poem::Route;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::auth::AuthMgr;
+use crate::auth::Credential;
+use crate::servers::http::middleware::client_ip;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+
+/// The token to access the HTTP API with, as the bearer token.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OAuthLoginResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: Option<u64>,
+    /// The user the token is authenticated as.
+    pub user: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OAuthCallbackParams {
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct OAuthTokenParams {
+    grant_type: String,
+    client_id: String,
+    client_secret: String,
+    scope: Option<String>,
+}
+
+/// The single sign-on by the OIDC provider, the endpoints are not authenticated by the session
+/// middleware.
+pub fn oauth_route(auth_manager: Arc<AuthMgr>) -> impl Endpoint {
+    Route::new()
+        .at("/login", get(oauth_login_handler))
+        .at("/callback", get(oauth_callback_handler))
+        .at("/token", post(oauth_token_handler))
+        .data(auth_manager)
+}
+
+/// Redirects the user to the provider, which redirects it back to `/v1/oauth/callback`.
+#[poem::handler]
+async fn oauth_login_handler() -> PoemResult<Redirect> {
+    let url = oidc_client()?
+        .authorize_url()
+        .await
+        .map_err(|e| unauthorized(e.message()))?;
+    Ok(Redirect::see_other(url))
+}
+
+/// Completes the authorization code flow.
+#[poem::handler]
+async fn oauth_callback_handler(
+    req: &Request,
+    auth_manager: Data<&Arc<AuthMgr>>,
+    Query(params): Query<OAuthCallbackParams>,
+) -> PoemResult<Json<OAuthLoginResponse>> {
+    if let Some(error) = params.error {
+        let description = params.error_description.unwrap_or_default();
+        return Err(unauthorized(format!("{}: {}", error, description)));
+    }
+    let (code, state) = match (params.code, params.state) {
+        (Some(code), Some(state)) => (code, state),
+        _ => {
+            return Err(PoemError::from_string(
+                "code and state are required",
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+    };
+    let token = oidc_client()?
+        .exchange_code(&code, &state)
+        .await
+        .map_err(|e| unauthorized(e.message()))?;
+    // the ID token is always a JWT, while the access token may be opaque
+    let bearer = match &token.id_token {
+        Some(id_token) => id_token.clone(),
+        None => token.access_token.clone(),
+    };
+    login(req, &auth_manager, bearer, &token).await
+}
+
+/// The client credentials flow, for the clients without users, like the notebooks and the jobs.
+#[poem::handler]
+async fn oauth_token_handler(
+    req: &Request,
+    auth_manager: Data<&Arc<AuthMgr>>,
+    Form(params): Form<OAuthTokenParams>,
+) -> PoemResult<Json<OAuthLoginResponse>> {
+    if params.grant_type != "client_credentials" {
+        return Err(PoemError::from_string(
+            format!("unsupported grant_type {}", params.grant_type),
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+    let token = oidc_client()?
+        .client_credentials(
+            &params.client_id,
+            &params.client_secret,
+            params.scope.as_deref(),
+        )
+        .await
+        .map_err(|e| unauthorized(e.message()))?;
+    login(req, &auth_manager, token.access_token.clone(), &token).await
+}
+
+// Authenticates by the token like the requests with it, which creates the user and grants the
+// mapped roles to it.
+async fn login(
+    req: &Request,
+    auth_manager: &AuthMgr,
+    bearer: String,
+    token: &OAuthToken,
+) -> PoemResult<Json<OAuthLoginResponse>> {
+    let session = SessionManager::instance()
+        .create_session(SessionType::Dummy)
+        .await
+        .map_err(|e| unauthorized(e.message()))?;
+    let credential = Credential::Jwt {
+        token: bearer.clone(),
+        hostname: client_ip(req),
+    };
+    auth_manager
+        .auth(session.clone(), &credential)
+        .await
+        .map_err(|e| unauthorized(e.message()))?;
+    let user = session
+        .get_current_user()
+        .map_err(|e| unauthorized(e.message()))?;
+    Ok(Json(OAuthLoginResponse {
+        access_token: bearer,
+        token_type: "Bearer".to_string(),
+        expires_in: token.expires_in,
+        user: user.identity().to_string(),
+    }))
+}
+
+fn oidc_client() -> PoemResult<Arc<OidcClient>> {
+    OidcClient::instance()
+        .ok_or_else(|| PoemError::from_string("oidc is not configured", StatusCode::NOT_FOUND))
+}
+
+fn unauthorized(message: impl Into<String>) -> PoemError {
+    PoemError::from_string(message, StatusCode::UNAUTHORIZED)
+}
@@ -66,6 +66,9 @@ pub enum ExecuteStateKind {
 #[derive(Clone, Serialize, Deserialize, Default, Debug)]
 pub struct Progresses {
     pub scan_progress: ProgressValues,
+    /// the rows and bytes estimated to scan, by the statistics of the partitions of the tables
+    #[serde(default)]
+    pub total_scan_progress: ProgressValues,
     pub write_progress: ProgressValues,
     pub result_progress: ProgressValues,
 }
@@ -74,12 +77,32 @@ impl Progresses {
     fn from_context(ctx: &Arc<QueryContext>) -> Self {
         Progresses {
             scan_progress: ctx.get_scan_progress_value(),
+            total_scan_progress: ctx.get_total_scan_progress().get_values(),
             write_progress: ctx.get_write_progress_value(),
             result_progress: ctx.get_result_progress_value(),
         }
     }
 }
 
+/// The time a query spent in one of its stages, like `Executing`.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct StageTiming {
+    pub stage: String,
+    pub elapsed_ms: f64,
+}
+
+impl StageTiming {
+    pub(crate) fn from_context(ctx: &Arc<QueryContext>) -> Vec<StageTiming> {
+        ctx.get_stage_durations()
+            .into_iter()
+            .map(|(stage, elapsed)| StageTiming {
+                stage: stage.to_string(),
+                elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+            })
+            .collect()
+    }
+}
+
 pub enum ExecuteState {
     Starting(ExecuteStarting),
     Running(ExecuteRunning),
@@ -111,6 +134,7 @@ pub struct ExecuteRunning {
 
 pub struct ExecuteStopped {
     pub stats: Progresses,
+    pub stages: Vec<StageTiming>,
     pub affect: Option<QueryAffect>,
     pub reason: Result<()>,
     pub stop_time: Instant,
@@ -131,6 +155,14 @@ impl Executor {
         }
     }
 
+    pub fn get_stages(&self) -> Vec<StageTiming> {
+        match &self.state {
+            Starting(s) => StageTiming::from_context(&s.ctx),
+            Running(r) => StageTiming::from_context(&r.ctx),
+            Stopped(f) => f.stages.clone(),
+        }
+    }
+
     pub fn get_affect(&self) -> Option<QueryAffect> {
         match &self.state {
             Starting(_) => None,
@@ -178,6 +210,7 @@ impl Executor {
                 }
                 guard.state = Stopped(ExecuteStopped {
                     stats: Default::default(),
+                    stages: StageTiming::from_context(&s.ctx),
                     reason,
                     stop_time: Instant::now(),
                     affect: Default::default(),
@@ -197,6 +230,7 @@ impl Executor {
 
                 guard.state = Stopped(ExecuteStopped {
                     stats: Progresses::from_context(&r.ctx),
+                    stages: StageTiming::from_context(&r.ctx),
                     reason,
                     stop_time: Instant::now(),
                     affect: r.ctx.get_affect(),
@@ -368,7 +402,7 @@ impl HttpQueryHandle {
         });
 
         let query_ctx = ctx.clone();
-        let executor_settings = ExecutorSettings::try_create(&ctx.get_settings())?;
+        let executor_settings = ExecutorSettings::try_create(&ctx)?;
 
         let run = move || -> Result<()> {
             let mut pipelines = build_res.sources_pipelines;
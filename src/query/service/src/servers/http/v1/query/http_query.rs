@@ -31,6 +31,7 @@ use crate::interpreters::InterpreterQueryLog;
 use crate::servers::http::v1::query::execute_state::ExecuteStarting;
 use crate::servers::http::v1::query::execute_state::ExecuteStopped;
 use crate::servers::http::v1::query::execute_state::Progresses;
+use crate::servers::http::v1::query::execute_state::StageTiming;
 use crate::servers::http::v1::query::expirable::Expirable;
 use crate::servers::http::v1::query::expirable::ExpiringState;
 use crate::servers::http::v1::query::http_query_manager::HttpQueryConfig;
@@ -42,6 +43,7 @@ use crate::servers::http::v1::query::ResponseData;
 use crate::servers::http::v1::query::Wait;
 use crate::servers::http::v1::HttpQueryManager;
 use crate::sessions::QueryAffect;
+use crate::sessions::Session;
 use crate::sessions::SessionType;
 use crate::sessions::TableContext;
 use crate::storages::result::block_buffer::BlockBuffer;
@@ -58,17 +60,27 @@ pub struct HttpQueryRequest {
 }
 
 const DEFAULT_MAX_ROWS_IN_BUFFER: usize = 5 * 1000 * 1000;
+const DEFAULT_MAX_BYTES_IN_BUFFER: usize = 100 * 1024 * 1024;
 const DEFAULT_MAX_ROWS_PER_PAGE: usize = 10000;
+const DEFAULT_MAX_BYTES_PER_PAGE: usize = 10 * 1024 * 1024;
 const DEFAULT_WAIT_TIME_SECS: u32 = 1;
 
 fn default_max_rows_in_buffer() -> usize {
     DEFAULT_MAX_ROWS_IN_BUFFER
 }
 
+fn default_max_bytes_in_buffer() -> usize {
+    DEFAULT_MAX_BYTES_IN_BUFFER
+}
+
 fn default_max_rows_per_page() -> usize {
     DEFAULT_MAX_ROWS_PER_PAGE
 }
 
+fn default_max_bytes_per_page() -> usize {
+    DEFAULT_MAX_BYTES_PER_PAGE
+}
+
 fn default_wait_time_secs() -> u32 {
     DEFAULT_WAIT_TIME_SECS
 }
@@ -79,8 +91,12 @@ pub struct PaginationConf {
     pub(crate) wait_time_secs: u32,
     #[serde(default = "default_max_rows_in_buffer")]
     pub(crate) max_rows_in_buffer: usize,
+    #[serde(default = "default_max_bytes_in_buffer")]
+    pub(crate) max_bytes_in_buffer: usize,
     #[serde(default = "default_max_rows_per_page")]
     pub(crate) max_rows_per_page: usize,
+    #[serde(default = "default_max_bytes_per_page")]
+    pub(crate) max_bytes_per_page: usize,
 }
 
 impl Default for PaginationConf {
@@ -88,7 +104,9 @@ impl Default for PaginationConf {
         PaginationConf {
             wait_time_secs: 1,
             max_rows_in_buffer: DEFAULT_MAX_ROWS_IN_BUFFER,
+            max_bytes_in_buffer: DEFAULT_MAX_BYTES_IN_BUFFER,
             max_rows_per_page: DEFAULT_MAX_ROWS_PER_PAGE,
+            max_bytes_per_page: DEFAULT_MAX_BYTES_PER_PAGE,
         }
     }
 }
@@ -139,6 +157,7 @@ impl HttpSessionConf {
 pub struct ResponseState {
     pub running_time_ms: f64,
     pub progresses: Progresses,
+    pub stages: Vec<StageTiming>,
     pub state: ExecuteStateKind,
     pub affect: Option<QueryAffect>,
     pub error: Option<ErrorCode>,
@@ -167,57 +186,7 @@ impl HttpQuery {
         request: HttpQueryRequest,
         config: HttpQueryConfig,
     ) -> Result<Arc<HttpQuery>> {
-        let http_query_manager = HttpQueryManager::instance();
-
-        let session = if let Some(id) = &request.session_id {
-            let session = http_query_manager.get_session(id).await.ok_or_else(|| {
-                ErrorCode::UnknownSession(format!("unknown session-id {}, maybe expired", id))
-            })?;
-            let mut n = 1;
-            while let ExpiringState::InUse(query_id) = session.expire_state() {
-                if let Some(last_query) = &http_query_manager.get_query(&query_id).await {
-                    if last_query.get_state().await.state == ExecuteStateKind::Running {
-                        return Err(ErrorCode::BadArguments(
-                            "last query on the session not finished",
-                        ));
-                    } else {
-                        http_query_manager.remove_query(&query_id).await;
-                    }
-                }
-                // wait for Arc<QueryContextShared> to drop and detach itself from session
-                // should not take too long
-                tokio::time::sleep(Duration::from_millis(1)).await;
-                n += 1;
-                if n > 10 {
-                    return Err(ErrorCode::UnexpectedError(
-                        "last query stop but not released",
-                    ));
-                }
-            }
-            session
-        } else {
-            ctx.get_session(SessionType::HTTPQuery)
-        };
-
-        if let Some(session_conf) = &request.session {
-            if let Some(db) = &session_conf.database {
-                session.set_current_database(db.clone());
-            }
-            if let Some(conf_settings) = &session_conf.settings {
-                let settings = session.get_settings();
-                for (k, v) in conf_settings {
-                    settings.set_settings(k.to_string(), v.to_string(), false)?;
-                }
-            }
-            if let Some(secs) = session_conf.keep_server_session_secs {
-                if secs > 0 && request.session_id.is_none() {
-                    http_query_manager
-                        .add_session(session.clone(), Duration::from_secs(secs))
-                        .await;
-                }
-            }
-        };
-
+        let session = Self::get_session(ctx, &request).await?;
         let session_id = session.get_id().clone();
 
         let ctx = session.create_query_context().await?;
@@ -225,7 +194,10 @@ impl HttpQuery {
         let sql = &request.sql;
         tracing::info!("run query_id={id} in session_id={session_id}, sql='{sql}'");
 
-        let block_buffer = BlockBuffer::new(request.pagination.max_rows_in_buffer);
+        let block_buffer = BlockBuffer::new(
+            request.pagination.max_rows_in_buffer,
+            request.pagination.max_bytes_in_buffer,
+        );
         let start_time = Instant::now();
         let state = Arc::new(RwLock::new(Executor {
             query_id: id.clone(),
@@ -256,6 +228,7 @@ impl HttpQuery {
                     InterpreterQueryLog::fail_to_start(ctx_clone.clone(), e.clone());
                     let state = ExecuteStopped {
                         stats: Progresses::default(),
+                        stages: StageTiming::from_context(&ctx_clone),
                         reason: Err(e.clone()),
                         stop_time: Instant::now(),
                         affect: ctx_clone.get_affect(),
@@ -274,6 +247,7 @@ impl HttpQuery {
         let format_settings = ctx.get_format_settings()?;
         let data = Arc::new(TokioMutex::new(PageManager::new(
             request.pagination.max_rows_per_page,
+            request.pagination.max_bytes_per_page,
             block_buffer,
             request.string_fields,
             format_settings,
@@ -290,11 +264,70 @@ impl HttpQuery {
         Ok(Arc::new(query))
     }
 
+    /// The session to run the query in, with the session configurations of the request applied.
+    pub(crate) async fn get_session(
+        ctx: &HttpQueryContext,
+        request: &HttpQueryRequest,
+    ) -> Result<Arc<Session>> {
+        let http_query_manager = HttpQueryManager::instance();
+
+        let session = if let Some(id) = &request.session_id {
+            let session = http_query_manager.get_session(id).await.ok_or_else(|| {
+                ErrorCode::UnknownSession(format!("unknown session-id {}, maybe expired", id))
+            })?;
+            let mut n = 1;
+            while let ExpiringState::InUse(query_id) = session.expire_state() {
+                if let Some(last_query) = &http_query_manager.get_query(&query_id).await {
+                    if last_query.get_state().await.state == ExecuteStateKind::Running {
+                        return Err(ErrorCode::BadArguments(
+                            "last query on the session not finished",
+                        ));
+                    } else {
+                        http_query_manager.remove_query(&query_id).await;
+                    }
+                }
+                // wait for Arc<QueryContextShared> to drop and detach itself from session
+                // should not take too long
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                n += 1;
+                if n > 10 {
+                    return Err(ErrorCode::UnexpectedError(
+                        "last query stop but not released",
+                    ));
+                }
+            }
+            session
+        } else {
+            ctx.get_session(SessionType::HTTPQuery)
+        };
+
+        if let Some(session_conf) = &request.session {
+            if let Some(db) = &session_conf.database {
+                session.set_current_database(db.clone());
+            }
+            if let Some(conf_settings) = &session_conf.settings {
+                let settings = session.get_settings();
+                for (k, v) in conf_settings {
+                    settings.set_settings(k.to_string(), v.to_string(), false)?;
+                }
+            }
+            if let Some(secs) = session_conf.keep_server_session_secs {
+                if secs > 0 && request.session_id.is_none() {
+                    http_query_manager
+                        .add_session(session.clone(), Duration::from_secs(secs))
+                        .await;
+                }
+            }
+        };
+        Ok(session)
+    }
+
     pub fn is_async(&self) -> bool {
         self.request.pagination.wait_time_secs == 0
     }
 
     pub async fn get_response_page(&self, page_no: usize) -> Result<HttpQueryResponseInternal> {
+        self.keep_session_alive().await;
         let data = Some(self.get_page(page_no).await?);
         let state = self.get_state().await;
         let session_conf = if let Some(conf) = &self.request.session {
@@ -329,6 +362,7 @@ impl HttpQuery {
         ResponseState {
             running_time_ms: state.elapsed().as_secs_f64() * 1000.0,
             progresses: state.get_progress(),
+            stages: state.get_stages(),
             state: exe_state,
             error: err,
             affect: state.get_affect(),
@@ -356,11 +390,28 @@ impl HttpQuery {
         .await;
     }
 
+    // the results are not needed anymore, stop the query if it is still running.
     pub async fn detach(&self) {
+        Executor::stop(
+            &self.state,
+            Err(ErrorCode::AbortedQuery(
+                "killed by http, the results are not needed anymore",
+            )),
+            true,
+        )
+        .await;
         let data = self.data.lock().await;
         data.detach().await
     }
 
+    // the server-side session does not expire while the results of its last query are fetched.
+    async fn keep_session_alive(&self) {
+        let http_query_manager = HttpQueryManager::instance();
+        if let Some(session) = http_query_manager.get_session(&self.session_id).await {
+            session.get_status().write().access();
+        }
+    }
+
     pub async fn update_expire_time(&self, before_wait: bool) {
         let duration = Duration::from_millis(self.config.result_timeout_millis)
             + if before_wait {
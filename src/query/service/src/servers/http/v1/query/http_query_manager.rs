@@ -88,34 +88,27 @@ impl HttpQueryManager {
         let mut queries = self.queries.write().await;
         queries.insert(query_id.to_string(), query.clone());
 
+        // the query is removed if the client neither fetches the next page nor calls the final
+        // uri before the result timeout.
         let self_clone = self.clone();
         let query_id_clone = query_id.to_string();
         let query_clone = query.clone();
-        if query.is_async() {
-            tokio::spawn(async move {
-                while let Some(t) = query_clone.check_expire().await {
-                    sleep(t).await;
-                }
-                if self_clone.remove_query(&query_id_clone).await.is_none() {
-                    warn!("http query {} timeout, but fail to remove", &query_id_clone);
-                } else {
-                    warn!("http query {} timeout", &query_id_clone);
-                    query.detach().await;
-                }
-            });
-        };
+        tokio::spawn(async move {
+            while let Some(t) = query_clone.check_expire().await {
+                sleep(t).await;
+            }
+            // already removed by the final or kill uri.
+            if self_clone.remove_query(&query_id_clone).await.is_some() {
+                warn!("http query {} timeout", &query_id_clone);
+                query.detach().await;
+            }
+        });
     }
 
     // not remove it until timeout or cancelled by user, even if query execution is aborted
     pub(crate) async fn remove_query(self: &Arc<Self>, query_id: &str) -> Option<Arc<HttpQuery>> {
         let mut queries = self.queries.write().await;
-        let q = queries.remove(query_id);
-        if let Some(q) = queries.remove(query_id) {
-            if q.is_async() {
-                q.update_expire_time(false).await;
-            }
-        }
-        q
+        queries.remove(query_id)
     }
 
     pub(crate) async fn get_session(self: &Arc<Self>, session_id: &str) -> Option<Arc<Session>> {
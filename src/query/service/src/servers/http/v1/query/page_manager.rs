@@ -47,6 +47,7 @@ pub struct ResponseData {
 
 pub struct PageManager {
     max_rows_per_page: usize,
+    max_bytes_per_page: usize,
     total_rows: usize,
     total_pages: usize,
     end: bool,
@@ -54,6 +55,8 @@ pub struct PageManager {
     schema: DataSchemaRef,
     last_page: Option<Page>,
     row_buffer: VecDeque<Vec<JsonValue>>,
+    // estimated by the memory size of the block which the rows in row_buffer come from.
+    row_buffer_row_bytes: usize,
     block_buffer: Arc<BlockBuffer>,
     string_fields: bool,
     format_settings: FormatSettings,
@@ -62,6 +65,7 @@ pub struct PageManager {
 impl PageManager {
     pub fn new(
         max_rows_per_page: usize,
+        max_bytes_per_page: usize,
         block_buffer: Arc<BlockBuffer>,
         string_fields: bool,
         format_settings: FormatSettings,
@@ -73,9 +77,11 @@ impl PageManager {
             end: false,
             block_end: false,
             row_buffer: Default::default(),
+            row_buffer_row_bytes: 0,
             schema: Arc::new(DataSchema::empty()),
             block_buffer,
             max_rows_per_page,
+            max_bytes_per_page,
             string_fields,
             format_settings,
         }
@@ -119,20 +125,28 @@ impl PageManager {
         }
     }
 
-    async fn collect_new_page(&mut self, tp: &Wait) -> Result<(JsonBlock, bool)> {
-        let format_settings = &self.format_settings;
-        let mut res: Vec<Vec<JsonValue>> = Vec::with_capacity(self.max_rows_per_page);
-        while res.len() < self.max_rows_per_page {
-            if let Some(row) = self.row_buffer.pop_front() {
-                res.push(row)
-            } else {
-                break;
+    // a page is full when reaching max_rows_per_page or max_bytes_per_page, with at least one row.
+    fn take_buffered_rows(&mut self, rows: &mut Vec<Vec<JsonValue>>, bytes: &mut usize) {
+        while rows.len() < self.max_rows_per_page
+            && (rows.is_empty() || *bytes + self.row_buffer_row_bytes <= self.max_bytes_per_page)
+        {
+            match self.row_buffer.pop_front() {
+                Some(row) => {
+                    rows.push(row);
+                    *bytes += self.row_buffer_row_bytes;
+                }
+                None => break,
             }
         }
+    }
+
+    async fn collect_new_page(&mut self, tp: &Wait) -> Result<(JsonBlock, bool)> {
+        let mut res: Vec<Vec<JsonValue>> = Vec::with_capacity(self.max_rows_per_page);
+        let mut bytes = 0;
         loop {
-            assert!(self.max_rows_per_page >= res.len());
-            let remain = self.max_rows_per_page - res.len();
-            if remain == 0 {
+            self.take_buffered_rows(&mut res, &mut bytes);
+            // the rows left in row_buffer do not fit in this page.
+            if !self.row_buffer.is_empty() || res.len() >= self.max_rows_per_page {
                 break;
             }
             let (block, done) = self.block_buffer.pop().await?;
@@ -141,14 +155,13 @@ impl PageManager {
                     if self.schema.fields().is_empty() {
                         self.schema = block.schema().clone();
                     }
-                    let mut iter =
-                        block_to_json_value(&block, format_settings, self.string_fields)?
+                    self.row_buffer =
+                        block_to_json_value(&block, &self.format_settings, self.string_fields)?
                             .into_iter()
-                            .peekable();
-                    let chunk: Vec<_> = iter.by_ref().take(remain).collect();
-                    res.extend(chunk);
-                    self.row_buffer = iter.by_ref().collect();
+                            .collect();
+                    self.row_buffer_row_bytes = block.memory_size() / block.num_rows().max(1);
                     if done {
+                        self.take_buffered_rows(&mut res, &mut bytes);
                         self.block_end = true;
                         break;
                     }
@@ -17,13 +17,20 @@
 pub use server::Server;
 pub use server::ShutdownHandle;
 
+pub use self::clickhouse::ClickHouseConnection;
+pub use self::clickhouse::ClickHouseHandler;
 pub use self::http::HttpHandler;
 pub use self::http::HttpHandlerKind;
 pub use self::mysql::MySQLConnection;
 pub use self::mysql::MySQLFederated;
 pub use self::mysql::MySQLHandler;
+pub use self::postgres::PostgresConnection;
+pub use self::postgres::PostgresHandler;
 
+mod clickhouse;
 pub(crate) mod federated_helper;
 pub mod http;
 mod mysql;
+mod postgres;
 pub(crate) mod server;
+pub(crate) mod tls;
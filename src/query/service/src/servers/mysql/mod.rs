@@ -16,6 +16,7 @@ mod mysql_federated;
 mod mysql_handler;
 mod mysql_interactive_worker;
 mod mysql_metrics;
+mod mysql_prepared_statement;
 mod mysql_session;
 #[allow(clippy::unused_io_amount)]
 mod reject_connection;
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::marker::PhantomData;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -23,6 +23,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
 use common_io::prelude::*;
+use common_meta_types::AuthInfo;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_users::CertifiedInfo;
@@ -30,6 +31,9 @@ use common_users::UserApiProvider;
 use futures_util::StreamExt;
 use metrics::histogram;
 use opensrv_mysql::AsyncMysqlShim;
+use opensrv_mysql::Column;
+use opensrv_mysql::ColumnFlags;
+use opensrv_mysql::ColumnType;
 use opensrv_mysql::ErrorKind;
 use opensrv_mysql::InitWriter;
 use opensrv_mysql::ParamParser;
@@ -40,9 +44,12 @@ use tracing::error;
 use tracing::info;
 use tracing::Instrument;
 
+use crate::audit::AuditLogger;
+use crate::auth::Credential;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterQueryLog;
+use crate::servers::mysql::mysql_prepared_statement::PreparedStatement;
 use crate::servers::mysql::writers::DFInitResultWriter;
 use crate::servers::mysql::writers::DFQueryResultWriter;
 use crate::servers::mysql::writers::ProgressReporter;
@@ -52,44 +59,25 @@ use crate::servers::mysql::MYSQL_VERSION;
 use crate::sessions::QueryContext;
 use crate::sessions::Session;
 use crate::sessions::TableContext;
-use crate::sql::plans::Plan;
 use crate::sql::Planner;
 
-fn has_result_set_by_plan(plan: &Plan) -> bool {
-    matches!(
-        plan,
-        Plan::Query { .. }
-            | Plan::Explain { .. }
-            | Plan::ExplainAst { .. }
-            | Plan::ExplainSyntax { .. }
-            | Plan::Call(_)
-            | Plan::ShowCreateDatabase(_)
-            | Plan::ShowCreateTable(_)
-            | Plan::DescShare(_)
-            | Plan::ShowShares(_)
-            | Plan::ShowObjectGrantPrivileges(_)
-            | Plan::ShowGrantTenantsOfShare(_)
-            | Plan::DescribeTable(_)
-            | Plan::ShowGrants(_)
-            | Plan::ListStage(_)
-            | Plan::Presign(_)
-    )
-}
-
-struct InteractiveWorkerBase<W: AsyncWrite + Send + Unpin> {
+struct InteractiveWorkerBase {
     session: Arc<Session>,
-    generic_hold: PhantomData<W>,
+    prepared_statements: HashMap<u32, PreparedStatement>,
+    next_statement_id: u32,
 }
 
-pub struct InteractiveWorker<W: AsyncWrite + Send + Unpin> {
-    base: InteractiveWorkerBase<W>,
+// The same worker serves the connection in plain text or upgraded to TLS, which differ in the
+// type of the writer.
+pub struct InteractiveWorker {
+    base: InteractiveWorkerBase,
     version: String,
     salt: [u8; 20],
     client_addr: String,
 }
 
 #[async_trait::async_trait]
-impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorker<W> {
+impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorker {
     type Error = ErrorCode;
 
     fn version(&self) -> &str {
@@ -110,8 +98,17 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorke
         "mysql_native_password"
     }
 
-    async fn auth_plugin_for_username(&self, _user: &[u8]) -> &str {
-        "mysql_native_password"
+    async fn auth_plugin_for_username(&self, user: &[u8]) -> &str {
+        // The users identified by JWT send the token as the clear text password, and the users
+        // identified by LDAP send the password to bind to the LDAP server with.
+        let user_name = String::from_utf8_lossy(user);
+        match self.base.get_auth_info(&user_name, &self.client_addr).await {
+            Ok(AuthInfo::JWT) | Ok(AuthInfo::LDAP) => "mysql_clear_password",
+            Err(e) if e.code() == ErrorCode::unknown_user_code() && self.base.ldap_enabled() => {
+                "mysql_clear_password"
+            }
+            _ => "mysql_native_password",
+        }
     }
 
     fn salt(&self) -> [u8; 20] {
@@ -120,7 +117,7 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorke
 
     async fn authenticate(
         &self,
-        _auth_plugin: &str,
+        auth_plugin: &str,
         username: &[u8],
         salt: &[u8],
         auth_data: &[u8],
@@ -129,8 +126,11 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorke
         let client_addr = self.client_addr.clone();
         let info = CertifiedInfo::create(&username, auth_data, &client_addr);
 
-        let authenticate = self.base.authenticate(salt, info);
-        match authenticate.await {
+        let authenticate = match auth_plugin {
+            "mysql_clear_password" => self.base.authenticate_clear_password(info).await,
+            _ => self.base.authenticate(salt, info).await,
+        };
+        match authenticate {
             Ok(res) => res,
             Err(failure) => {
                 error!(
@@ -257,8 +257,29 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for InteractiveWorke
     }
 }
 
-impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
+impl InteractiveWorkerBase {
+    /// The native authentications are logged as the `login` audit events here, the others are
+    /// logged by the `AuthMgr`.
     async fn authenticate(&self, salt: &[u8], info: CertifiedInfo) -> Result<bool> {
+        let user_name = info.user_name.clone();
+        let client_ip = info.user_client_address.split(':').collect::<Vec<_>>()[0].to_string();
+        let res = self.authenticate_native(salt, info).await;
+        let err = match &res {
+            Ok(true) => None,
+            Ok(false) => Some(ErrorCode::AuthenticateFailure("wrong password")),
+            Err(cause) => Some(cause.clone()),
+        };
+        AuditLogger::log_login(
+            &self.session.get_current_tenant(),
+            &user_name,
+            Some(&client_ip),
+            self.session.get_type().to_string(),
+            err.as_ref(),
+        );
+        res
+    }
+
+    async fn authenticate_native(&self, salt: &[u8], info: CertifiedInfo) -> Result<bool> {
         let user_name = &info.user_name;
         let client_ip = info.user_client_address.split(':').collect::<Vec<_>>()[0];
 
@@ -269,37 +290,105 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
 
         let authed = user_info.auth_info.auth_mysql(&info.user_password, salt)?;
         if authed {
+            ctx.get_auth_manager()
+                .check_network_policy(&self.session, &user_info, Some(client_ip))
+                .await?;
             self.session.set_current_user(user_info);
         }
         Ok(authed)
     }
 
-    async fn do_prepare(&mut self, _: &str, writer: StatementMetaWriter<'_, W>) -> Result<()> {
-        writer
-            .error(
-                ErrorKind::ER_UNKNOWN_ERROR,
-                "Prepare is not support in Databend.".as_bytes(),
-            )
+    /// The password is sent in clear text by the `mysql_clear_password` plugin, it is the JWT of
+    /// the users identified by JWT, or the LDAP password of the users identified by LDAP.
+    async fn authenticate_clear_password(&self, info: CertifiedInfo) -> Result<bool> {
+        let client_ip = info.user_client_address.split(':').collect::<Vec<_>>()[0];
+        // The password is terminated by NUL.
+        let password = match info.user_password.split_last() {
+            Some((0, password)) => password.to_vec(),
+            _ => info.user_password.clone(),
+        };
+        let credential = Credential::Password {
+            name: info.user_name.clone(),
+            password: Some(password),
+            hostname: Some(client_ip.to_string()),
+        };
+
+        let ctx = self.session.create_query_context().await?;
+        ctx.get_auth_manager()
+            .auth(self.session.clone(), &credential)
             .await?;
+        Ok(true)
+    }
+
+    // The users unknown may be the users of the LDAP server, created on their first login.
+    fn ldap_enabled(&self) -> bool {
+        !self.session.get_config().query.ldap_url.is_empty()
+    }
+
+    async fn get_auth_info(&self, user_name: &str, client_addr: &str) -> Result<AuthInfo> {
+        let client_ip = client_addr.split(':').collect::<Vec<_>>()[0];
+        let user_info = UserApiProvider::instance()
+            .get_user_with_client_ip(&self.session.get_current_tenant(), user_name, client_ip)
+            .await?;
+        Ok(user_info.auth_info)
+    }
+
+    /// https://dev.mysql.com/doc/internals/en/com-stmt-prepare.html
+    ///
+    /// The columns of the result are unknown before the execution, they are sent with the result.
+    async fn do_prepare<W: AsyncWrite + Send + Unpin>(
+        &mut self,
+        query: &str,
+        writer: StatementMetaWriter<'_, W>,
+    ) -> Result<()> {
+        let statement = PreparedStatement::create(query);
+        let params = (0..statement.num_params())
+            .map(|_| Column {
+                table: "".to_string(),
+                column: "?".to_string(),
+                coltype: ColumnType::MYSQL_TYPE_VAR_STRING,
+                colflags: ColumnFlags::empty(),
+            })
+            .collect::<Vec<_>>();
+
+        let id = self.next_statement_id;
+        self.next_statement_id = self.next_statement_id.wrapping_add(1);
+        self.prepared_statements.insert(id, statement);
+        writer.reply(id, &params, &[] as &[Column]).await?;
         Ok(())
     }
 
-    async fn do_execute(
+    /// https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
+    async fn do_execute<W: AsyncWrite + Send + Unpin>(
         &mut self,
-        _: u32,
-        _: ParamParser<'_>,
+        id: u32,
+        params: ParamParser<'_>,
         writer: QueryResultWriter<'_, W>,
     ) -> Result<()> {
-        writer
-            .error(
-                ErrorKind::ER_UNKNOWN_ERROR,
-                "Execute is not support in Databend.".as_bytes(),
-            )
-            .await?;
-        Ok(())
+        let query = match self.prepared_statements.get(&id) {
+            None => Err(ErrorCode::BadArguments(format!(
+                "unknown prepared statement: {}",
+                id
+            ))),
+            Some(statement) => statement.bind(params),
+        };
+
+        let mut writer = DFQueryResultWriter::create_binary(writer);
+        let format = self.session.get_format_settings()?;
+        let query = match query {
+            Ok(query) => query,
+            Err(cause) => return writer.write(Err(cause), &format).await,
+        };
+
+        let query_result = self.do_query(&query).await;
+        let write_result = writer.write(query_result, &format).await;
+        write_result.map_err(|cause| cause.add_message_back(format!("(while in query {})", query)))
     }
 
-    async fn do_close(&mut self, _: u32) {}
+    /// https://dev.mysql.com/doc/internals/en/com-stmt-close.html
+    async fn do_close(&mut self, id: u32) {
+        self.prepared_statements.remove(&id);
+    }
 
     // Check the query is a federated or driver setup command.
     // Here we fake some values for the command which Databend not supported.
@@ -334,7 +423,7 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
 
                 context.attach_query_str(plan.to_string(), query);
                 let interpreter = InterpreterFactory::get(context.clone(), &plan).await;
-                let has_result_set = has_result_set_by_plan(&plan);
+                let has_result_set = plan.has_result_set();
 
                 match interpreter {
                     Ok(interpreter) => {
@@ -412,8 +501,8 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorkerBase<W> {
     }
 }
 
-impl<W: AsyncWrite + Send + Unpin> InteractiveWorker<W> {
-    pub fn create(session: Arc<Session>, client_addr: String) -> InteractiveWorker<W> {
+impl InteractiveWorker {
+    pub fn create(session: Arc<Session>, client_addr: String) -> InteractiveWorker {
         let mut bs = vec![0u8; 20];
         let mut rng = rand::thread_rng();
         rng.fill_bytes(bs.as_mut());
@@ -426,10 +515,11 @@ impl<W: AsyncWrite + Send + Unpin> InteractiveWorker<W> {
             }
         }
 
-        InteractiveWorker::<W> {
-            base: InteractiveWorkerBase::<W> {
+        InteractiveWorker {
+            base: InteractiveWorkerBase {
                 session,
-                generic_hold: PhantomData::default(),
+                prepared_statements: HashMap::new(),
+                next_statement_id: 1,
             },
             salt: scramble,
             version: format!(
@@ -0,0 +1,161 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opensrv_mysql::ParamParser;
+use opensrv_mysql::ValueInner;
+
+/// A statement prepared by COM_STMT_PREPARE, whose parameters are the `?` placeholders.
+///
+/// The parameters bound by COM_STMT_EXECUTE are substituted into the query as literals, so the
+/// statement is planned as a normal query on each execution.
+pub struct PreparedStatement {
+    query: String,
+    // Byte offsets of the placeholders in the query.
+    placeholders: Vec<usize>,
+}
+
+impl PreparedStatement {
+    pub fn create(query: &str) -> PreparedStatement {
+        PreparedStatement {
+            query: query.to_string(),
+            placeholders: find_placeholders(query),
+        }
+    }
+
+    pub fn num_params(&self) -> usize {
+        self.placeholders.len()
+    }
+
+    pub fn bind(&self, params: ParamParser<'_>) -> Result<String> {
+        let literals = params
+            .into_iter()
+            .map(|param| param_to_literal(param.value.into_inner()))
+            .collect::<Result<Vec<_>>>()?;
+
+        if literals.len() != self.placeholders.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "prepared statement expects {} parameters, but {} are bound",
+                self.placeholders.len(),
+                literals.len()
+            )));
+        }
+
+        let mut query = String::with_capacity(self.query.len());
+        let mut pos = 0;
+        for (placeholder, literal) in self.placeholders.iter().zip(literals.iter()) {
+            query.push_str(&self.query[pos..*placeholder]);
+            query.push_str(literal);
+            pos = placeholder + 1;
+        }
+        query.push_str(&self.query[pos..]);
+        Ok(query)
+    }
+}
+
+/// Find the `?` out of the quoted strings, identifiers and comments.
+fn find_placeholders(query: &str) -> Vec<usize> {
+    let bytes = query.as_bytes();
+    let mut placeholders = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' && quote != b'`' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'?' => placeholders.push(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    placeholders
+}
+
+fn param_to_literal(value: ValueInner<'_>) -> Result<String> {
+    Ok(match value {
+        ValueInner::NULL => "NULL".to_string(),
+        ValueInner::Int(v) => v.to_string(),
+        ValueInner::UInt(v) => v.to_string(),
+        ValueInner::Double(v) => v.to_string(),
+        ValueInner::Bytes(v) => {
+            let v = String::from_utf8_lossy(v);
+            format!("'{}'", v.replace('\\', "\\\\").replace('\'', "\\'"))
+        }
+        ValueInner::Date(v) | ValueInner::Datetime(v) => format!("'{}'", decode_datetime(v)?),
+        ValueInner::Time(v) => format!("'{}'", decode_time(v)?),
+    })
+}
+
+// https://dev.mysql.com/doc/internals/en/binary-protocol-value.html
+fn decode_datetime(v: &[u8]) -> Result<String> {
+    match v.len() {
+        0 => Ok("0000-00-00 00:00:00".to_string()),
+        4 | 7 | 11 => {
+            let year = u16::from_le_bytes([v[0], v[1]]);
+            let mut datetime = format!("{:04}-{:02}-{:02}", year, v[2], v[3]);
+            if v.len() >= 7 {
+                datetime.push_str(&format!(" {:02}:{:02}:{:02}", v[4], v[5], v[6]));
+            }
+            if v.len() == 11 {
+                let micros = u32::from_le_bytes([v[7], v[8], v[9], v[10]]);
+                datetime.push_str(&format!(".{:06}", micros));
+            }
+            Ok(datetime)
+        }
+        len => Err(ErrorCode::BadBytes(format!(
+            "invalid length of binary datetime: {}",
+            len
+        ))),
+    }
+}
+
+fn decode_time(v: &[u8]) -> Result<String> {
+    match v.len() {
+        0 => Ok("00:00:00".to_string()),
+        8 | 12 => {
+            let sign = if v[0] == 1 { "-" } else { "" };
+            let days = u32::from_le_bytes([v[1], v[2], v[3], v[4]]);
+            let hours = days * 24 + v[5] as u32;
+            let mut time = format!("{}{:02}:{:02}:{:02}", sign, hours, v[6], v[7]);
+            if v.len() == 12 {
+                let micros = u32::from_le_bytes([v[8], v[9], v[10], v[11]]);
+                time.push_str(&format!(".{:06}", micros));
+            }
+            Ok(time)
+        }
+        len => Err(ErrorCode::BadBytes(format!(
+            "invalid length of binary time: {}",
+            len
+        ))),
+    }
+}
@@ -23,8 +23,11 @@ use common_base::base::TrySpawn;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::ToErrorCode;
+use opensrv_mysql::plain_run_with_options;
+use opensrv_mysql::secure_run_with_options;
 use opensrv_mysql::AsyncMysqlIntermediary;
 use opensrv_mysql::IntermediaryOptions;
+use tokio_rustls::rustls::ServerConfig;
 use tracing::error;
 
 use crate::servers::mysql::mysql_interactive_worker::InteractiveWorker;
@@ -36,7 +39,11 @@ const DEFAULT_RESULT_SET_WRITE_BUFFER_SIZE: usize = 100 * 1024;
 pub struct MySQLConnection;
 
 impl MySQLConnection {
-    pub fn run_on_stream(session: Arc<Session>, stream: TcpStream) -> Result<()> {
+    pub fn run_on_stream(
+        session: Arc<Session>,
+        stream: TcpStream,
+        tls: Option<Arc<ServerConfig>>,
+    ) -> Result<()> {
         let blocking_stream = Self::convert_stream(stream)?;
         MySQLConnection::attach_session(&session, &blocking_stream)?;
 
@@ -47,18 +54,45 @@ impl MySQLConnection {
             let join_handle = query_executor.spawn(async move {
                 let client_addr = non_blocking_stream.peer_addr().unwrap().to_string();
                 let interactive_worker = InteractiveWorker::create(session, client_addr);
-                let opts = IntermediaryOptions {
-                    process_use_statement_on_query: true,
-                };
-                let (r, w) = non_blocking_stream.into_split();
-                let w = BufWriter::with_capacity(DEFAULT_RESULT_SET_WRITE_BUFFER_SIZE, w);
-                AsyncMysqlIntermediary::run_with_options(interactive_worker, r, w, &opts).await
+                if let Err(error) =
+                    Self::run_with_tls(interactive_worker, non_blocking_stream, tls).await
+                {
+                    error!(
+                        "Unexpected error occurred during mysql session: {:?}",
+                        error
+                    );
+                }
             });
             let _ = futures::executor::block_on(join_handle);
         });
         Ok(())
     }
 
+    /// The handshake advertises `CLIENT_SSL` if TLS is enabled, the clients upgrade the connection
+    /// by the SSLRequest packet before the handshake response, or go on in plain text.
+    async fn run_with_tls(
+        mut interactive_worker: InteractiveWorker,
+        stream: TcpStream,
+        tls: Option<Arc<ServerConfig>>,
+    ) -> Result<()> {
+        let opts = IntermediaryOptions {
+            process_use_statement_on_query: true,
+            reject_connection_on_dbname_absence: false,
+        };
+        let (mut r, w) = stream.into_split();
+        let mut w = BufWriter::with_capacity(DEFAULT_RESULT_SET_WRITE_BUFFER_SIZE, w);
+        let (is_ssl, init_params) =
+            AsyncMysqlIntermediary::init_before_ssl(&mut interactive_worker, &mut r, &mut w, &tls)
+                .await?;
+
+        match tls {
+            Some(config) if is_ssl => {
+                secure_run_with_options(interactive_worker, w, opts, config, init_params).await
+            }
+            _ => plain_run_with_options(interactive_worker, w, opts, init_params).await,
+        }
+    }
+
     fn attach_session(session: &Arc<Session>, blocking_stream: &std::net::TcpStream) -> Result<()> {
         let host = blocking_stream.peer_addr().ok();
         let blocking_stream_ref = blocking_stream.try_clone()?;
@@ -65,11 +65,23 @@ impl QueryResult {
 
 pub struct DFQueryResultWriter<'a, W: AsyncWrite + Send + Unpin> {
     inner: Option<QueryResultWriter<'a, W>>,
+    // Whether the rows are encoded by the binary protocol, the result of prepared statements.
+    binary: bool,
 }
 
 impl<'a, W: AsyncWrite + Send + Unpin> DFQueryResultWriter<'a, W> {
     pub fn create(inner: QueryResultWriter<'a, W>) -> DFQueryResultWriter<'a, W> {
-        DFQueryResultWriter::<'a, W> { inner: Some(inner) }
+        DFQueryResultWriter::<'a, W> {
+            inner: Some(inner),
+            binary: false,
+        }
+    }
+
+    pub fn create_binary(inner: QueryResultWriter<'a, W>) -> DFQueryResultWriter<'a, W> {
+        DFQueryResultWriter::<'a, W> {
+            inner: Some(inner),
+            binary: true,
+        }
     }
 
     pub async fn write(
@@ -79,7 +91,7 @@ impl<'a, W: AsyncWrite + Send + Unpin> DFQueryResultWriter<'a, W> {
     ) -> Result<()> {
         if let Some(writer) = self.inner.take() {
             match query_result {
-                Ok(query_result) => Self::ok(query_result, writer, format).await?,
+                Ok(query_result) => Self::ok(query_result, writer, format, self.binary).await?,
                 Err(error) => Self::err(&error, writer).await?,
             }
         }
@@ -90,6 +102,7 @@ impl<'a, W: AsyncWrite + Send + Unpin> DFQueryResultWriter<'a, W> {
         mut query_result: QueryResult,
         dataset_writer: QueryResultWriter<'a, W>,
         format: &FormatSettings,
+        binary: bool,
     ) -> Result<()> {
         // XXX: num_columns == 0 may is error?
 
@@ -110,6 +123,25 @@ impl<'a, W: AsyncWrite + Send + Unpin> DFQueryResultWriter<'a, W> {
             return Ok(());
         }
 
+        // The binary protocol encodes the values by the column types, so the integers are declared
+        // as 8 bytes, and the values serialized as text are declared as strings.
+        fn convert_binary_field_type(field: &DataField) -> Result<ColumnType> {
+            match remove_nullable(field.data_type()).data_type_id() {
+                TypeID::Int8 | TypeID::Int16 | TypeID::Int32 | TypeID::Int64 => {
+                    Ok(ColumnType::MYSQL_TYPE_LONGLONG)
+                }
+                TypeID::UInt8 | TypeID::UInt16 | TypeID::UInt32 | TypeID::UInt64 => {
+                    Ok(ColumnType::MYSQL_TYPE_LONGLONG)
+                }
+                TypeID::Interval => Ok(ColumnType::MYSQL_TYPE_LONGLONG),
+                TypeID::Boolean => Ok(ColumnType::MYSQL_TYPE_TINY),
+                TypeID::Float32 | TypeID::Float64 | TypeID::Timestamp => {
+                    Ok(ColumnType::MYSQL_TYPE_VAR_STRING)
+                }
+                _ => convert_field_type(field),
+            }
+        }
+
         fn convert_field_type(field: &DataField) -> Result<ColumnType> {
             match remove_nullable(field.data_type()).data_type_id() {
                 TypeID::Int8 => Ok(ColumnType::MYSQL_TYPE_LONG),
@@ -133,6 +165,8 @@ impl<'a, W: AsyncWrite + Send + Unpin> DFQueryResultWriter<'a, W> {
                 TypeID::Variant => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 TypeID::VariantArray => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 TypeID::VariantObject => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+                TypeID::Geometry => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
+                TypeID::Geography => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 _ => Err(ErrorCode::UnImplement(format!(
                     "Unsupported column type:{:?}",
                     field.data_type()
@@ -140,8 +174,12 @@ impl<'a, W: AsyncWrite + Send + Unpin> DFQueryResultWriter<'a, W> {
             }
         }
 
-        fn make_column_from_field(field: &DataField) -> Result<Column> {
-            convert_field_type(field).map(|column_type| Column {
+        fn make_column_from_field(field: &DataField, binary: bool) -> Result<Column> {
+            let column_type = match binary {
+                true => convert_binary_field_type(field)?,
+                false => convert_field_type(field)?,
+            };
+            Ok(Column {
                 table: "".to_string(),
                 column: field.name().to_string(),
                 coltype: column_type,
@@ -149,12 +187,16 @@ impl<'a, W: AsyncWrite + Send + Unpin> DFQueryResultWriter<'a, W> {
             })
         }
 
-        fn convert_schema(schema: &DataSchemaRef) -> Result<Vec<Column>> {
-            schema.fields().iter().map(make_column_from_field).collect()
+        fn convert_schema(schema: &DataSchemaRef, binary: bool) -> Result<Vec<Column>> {
+            schema
+                .fields()
+                .iter()
+                .map(|field| make_column_from_field(field, binary))
+                .collect()
         }
 
         let tz = format.timezone;
-        match convert_schema(&query_result.schema) {
+        match convert_schema(&query_result.schema, binary) {
             Err(error) => Self::err(&error, dataset_writer).await,
             Ok(columns) => {
                 let mut row_writer = dataset_writer.start(&columns).await?;
@@ -202,6 +244,10 @@ impl<'a, W: AsyncWrite + Send + Unpin> DFQueryResultWriter<'a, W> {
                                         (TypeID::String, DataValue::String(v)) => {
                                             row_writer.write_col(v)?
                                         }
+                                        (TypeID::Geometry | TypeID::Geography, _) => row_writer
+                                            .write_col(
+                                                serializer.serialize_field(row_index, format)?,
+                                            )?,
                                         (TypeID::Array, DataValue::Array(_)) => row_writer
                                             .write_col(
                                                 serializer.serialize_field(row_index, format)?,
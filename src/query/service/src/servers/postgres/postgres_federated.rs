@@ -0,0 +1,84 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::DataSchemaRefExt;
+
+use crate::servers::federated_helper::FederatedHelper;
+use crate::servers::postgres::POSTGRES_VERSION;
+
+pub struct PostgresFederated {
+    postgres_version: String,
+    databend_version: String,
+}
+
+impl PostgresFederated {
+    pub fn create() -> Self {
+        PostgresFederated {
+            postgres_version: POSTGRES_VERSION.to_string(),
+            databend_version: crate::version::DATABEND_COMMIT_VERSION.to_string(),
+        }
+    }
+
+    // Build block for select function.
+    // Format:
+    // |function_name|
+    // |value|
+    fn select_function_block(name: &str, value: &str) -> Option<DataBlock> {
+        Some(DataBlock::create(
+            DataSchemaRefExt::create(vec![DataField::new(name, StringType::new_impl())]),
+            vec![Series::from_data(vec![value])],
+        ))
+    }
+
+    // Check the query is a federated or driver setup command.
+    // Here we fake some values for the command which Databend not supported.
+    pub fn check(&self, query: &str) -> Option<DataBlock> {
+        let rules: Vec<(&str, Option<DataBlock>)> = vec![
+            (
+                "(?i)^(SELECT VERSION\\(\\))",
+                Self::select_function_block(
+                    "version",
+                    format!(
+                        "PostgreSQL {} (Databend {})",
+                        self.postgres_version, self.databend_version
+                    )
+                    .as_str(),
+                ),
+            ),
+            (
+                "(?i)^(SHOW TRANSACTION ISOLATION LEVEL)",
+                Self::select_function_block("transaction_isolation", "read committed"),
+            ),
+            // Txn.
+            ("(?i)^(BEGIN(.*))", None),
+            ("(?i)^(START TRANSACTION(.*))", None),
+            ("(?i)^(COMMIT(.*))", None),
+            ("(?i)^(END(.*))", None),
+            ("(?i)^(ROLLBACK(.*))", None),
+            // Set, by psql, JDBC and the other drivers.
+            ("(?i)^(SET extra_float_digits(.*))", None),
+            ("(?i)^(SET application_name(.*))", None),
+            ("(?i)^(SET client_encoding(.*))", None),
+            ("(?i)^(SET DateStyle(.*))", None),
+            ("(?i)^(SET search_path(.*))", None),
+            ("(?i)^(SET statement_timeout(.*))", None),
+            ("(?i)^(SET SESSION CHARACTERISTICS(.*))", None),
+            ("(?i)^(DISCARD ALL)", None),
+        ];
+
+        FederatedHelper::block_match_rule(query, rules)
+    }
+}
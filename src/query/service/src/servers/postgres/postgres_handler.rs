@@ -0,0 +1,169 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_base::base::tokio::net::TcpStream;
+use common_base::base::tokio::task::JoinHandle;
+use common_base::base::Runtime;
+use common_base::base::TrySpawn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::future::AbortHandle;
+use futures::future::AbortRegistration;
+use futures::future::Abortable;
+use futures::StreamExt;
+use tokio_rustls::TlsAcceptor;
+use tokio_stream::wrappers::TcpListenerStream;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+use crate::servers::postgres::postgres_session::PostgresConnection;
+use crate::servers::postgres::protocol::write_error;
+use crate::servers::server::ListeningStream;
+use crate::servers::server::Server;
+use crate::servers::tls::build_tls_server_config;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::Config;
+
+pub struct PostgresHandler {
+    abort_handle: AbortHandle,
+    abort_registration: Option<AbortRegistration>,
+    join_handle: Option<JoinHandle<()>>,
+    tls: Option<TlsAcceptor>,
+}
+
+impl PostgresHandler {
+    pub fn create(config: Config) -> Result<Box<dyn Server>> {
+        let (abort_handle, registration) = AbortHandle::new_pair();
+        Ok(Box::new(PostgresHandler {
+            abort_handle,
+            abort_registration: Some(registration),
+            join_handle: None,
+            tls: Self::build_tls(&config)?,
+        }))
+    }
+
+    // The clients upgrade the connection by SSLRequest if TLS is enabled, or go on in plain text.
+    fn build_tls(config: &Config) -> Result<Option<TlsAcceptor>> {
+        let server_config = build_tls_server_config(
+            "postgres",
+            &config.query.postgres_handler_tls_server_cert,
+            &config.query.postgres_handler_tls_server_key,
+            "",
+        )?;
+        Ok(server_config.map(TlsAcceptor::from))
+    }
+
+    async fn listener_tcp(listening: SocketAddr) -> Result<(TcpListenerStream, SocketAddr)> {
+        let listener = tokio::net::TcpListener::bind(listening)
+            .await
+            .map_err(|e| {
+                ErrorCode::TokioError(format!("{{{}:{}}} {}", listening.ip(), listening.port(), e))
+            })?;
+        let listener_addr = listener.local_addr()?;
+        Ok((TcpListenerStream::new(listener), listener_addr))
+    }
+
+    fn listen_loop(&self, stream: ListeningStream, rt: Arc<Runtime>) -> impl Future<Output = ()> {
+        let tls = self.tls.clone();
+        stream.for_each(move |accept_socket| {
+            let executor = rt.clone();
+            let tls = tls.clone();
+            let sessions = SessionManager::instance();
+            async move {
+                match accept_socket {
+                    Err(error) => error!("Broken session connection: {}", error),
+                    Ok(socket) => PostgresHandler::accept_socket(sessions, executor, socket, tls),
+                };
+            }
+        })
+    }
+
+    fn accept_socket(
+        sessions: Arc<SessionManager>,
+        executor: Arc<Runtime>,
+        socket: TcpStream,
+        tls: Option<TlsAcceptor>,
+    ) {
+        executor.spawn(async move {
+            match sessions.create_session(SessionType::Postgres).await {
+                Err(error) => {
+                    warn!("create session failed, {:?}", error);
+                    Self::reject_session(socket, error).await
+                }
+                Ok(session) => {
+                    info!("Postgres connection coming: {:?}", socket.peer_addr());
+                    if let Err(error) = PostgresConnection::run_on_stream(session, socket, tls) {
+                        error!("Unexpected error occurred during query: {:?}", error);
+                    };
+                }
+            }
+        });
+    }
+
+    // The client reads an ErrorResponse in place of the response of its startup message.
+    async fn reject_session(mut stream: TcpStream, error: ErrorCode) {
+        let mut buf = vec![];
+        write_error(&mut buf, &error);
+        if let Err(error) = stream.write_all(&buf).await {
+            error!(
+                "Unexpected error occurred during reject connection: {:?}",
+                error
+            );
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Server for PostgresHandler {
+    async fn shutdown(&mut self, graceful: bool) {
+        if !graceful {
+            return;
+        }
+
+        self.abort_handle.abort();
+
+        if let Some(join_handle) = self.join_handle.take() {
+            if let Err(error) = join_handle.await {
+                error!(
+                    "Unexpected error during shutdown PostgresHandler. cause {}",
+                    error
+                );
+            }
+        }
+    }
+
+    async fn start(&mut self, listening: SocketAddr) -> Result<SocketAddr> {
+        match self.abort_registration.take() {
+            None => Err(ErrorCode::LogicalError("PostgresHandler already running.")),
+            Some(registration) => {
+                let rejected_rt = Arc::new(Runtime::with_worker_threads(
+                    1,
+                    Some("postgres-handler".to_string()),
+                )?);
+                let (stream, listener) = Self::listener_tcp(listening).await?;
+                let stream = Abortable::new(stream, registration);
+                self.join_handle = Some(tokio::spawn(self.listen_loop(stream, rejected_rt)));
+                Ok(listener)
+            }
+        }
+    }
+}
@@ -0,0 +1,601 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use futures_util::StreamExt;
+use rand::Rng;
+use tracing::info;
+
+use crate::auth::Credential;
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterFactory;
+use crate::interpreters::InterpreterQueryLog;
+use crate::servers::postgres::postgres_federated::PostgresFederated;
+use crate::servers::postgres::postgres_prepared_statement::format_of;
+use crate::servers::postgres::postgres_prepared_statement::PreparedStatement;
+use crate::servers::postgres::postgres_session::PostgresStream;
+use crate::servers::postgres::postgres_types::write_field;
+use crate::servers::postgres::postgres_types::PgType;
+use crate::servers::postgres::protocol::begin_message;
+use crate::servers::postgres::protocol::end_message;
+use crate::servers::postgres::protocol::read_message;
+use crate::servers::postgres::protocol::write_authentication;
+use crate::servers::postgres::protocol::write_backend_key_data;
+use crate::servers::postgres::protocol::write_bind_complete;
+use crate::servers::postgres::protocol::write_close_complete;
+use crate::servers::postgres::protocol::write_command_complete;
+use crate::servers::postgres::protocol::write_empty_query_response;
+use crate::servers::postgres::protocol::write_error;
+use crate::servers::postgres::protocol::write_no_data;
+use crate::servers::postgres::protocol::write_parameter_description;
+use crate::servers::postgres::protocol::write_parameter_status;
+use crate::servers::postgres::protocol::write_parse_complete;
+use crate::servers::postgres::protocol::write_portal_suspended;
+use crate::servers::postgres::protocol::write_ready_for_query;
+use crate::servers::postgres::protocol::write_row_description;
+use crate::servers::postgres::protocol::FieldDescription;
+use crate::servers::postgres::protocol::FrontendMessage;
+use crate::servers::postgres::protocol::Target;
+use crate::servers::postgres::protocol::AUTHENTICATION_CLEARTEXT_PASSWORD;
+use crate::servers::postgres::protocol::AUTHENTICATION_OK;
+use crate::servers::postgres::protocol::FORMAT_BINARY;
+use crate::servers::postgres::POSTGRES_VERSION;
+use crate::sessions::QueryContext;
+use crate::sessions::Session;
+use crate::sessions::TableContext;
+use crate::sql::plans::Plan;
+use crate::sql::Planner;
+
+// The rows are sent when the buffer exceeds the size, not held until the end of the result.
+const FLUSH_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Serve the queries of a connection of the PostgreSQL protocol, by the simple query protocol
+/// and the extended query protocol.
+pub struct InteractiveWorker {
+    session: Arc<Session>,
+    stream: Box<dyn PostgresStream>,
+    client_addr: String,
+    write_buf: Vec<u8>,
+    statements: HashMap<String, PreparedStatement>,
+    portals: HashMap<String, Portal>,
+    // After an error of the extended query protocol, the messages are discarded until Sync.
+    skip_until_sync: bool,
+}
+
+/// A statement bound with its parameters by Bind.
+struct Portal {
+    query: String,
+    result_formats: Vec<i16>,
+    // Planned by Describe, so Execute does not plan the query again.
+    plan: Option<(Arc<QueryContext>, Plan)>,
+    // The result of an Execute which reached its row limit, continued by the next Execute.
+    result: Option<QueryResult>,
+}
+
+struct QueryResult {
+    context: Option<Arc<QueryContext>>,
+    // The command of the tag in CommandComplete, such as `SELECT` or `CREATE TABLE`.
+    command: String,
+    has_result_set: bool,
+    schema: DataSchemaRef,
+    stream: SendableDataBlockStream,
+    // The block being sent and its next row.
+    block: Option<(DataBlock, usize)>,
+    // The rows sent by the last Execute, as the tag of a portal counts its last Execute.
+    rows: usize,
+}
+
+impl QueryResult {
+    fn command_tag(&self) -> String {
+        match self.command.as_str() {
+            "SELECT" => format!("SELECT {}", self.rows),
+            "INSERT" => {
+                let written = match &self.context {
+                    Some(context) => context.get_write_progress_value().rows,
+                    None => 0,
+                };
+                format!("INSERT 0 {}", written)
+            }
+            command => command.to_string(),
+        }
+    }
+}
+
+impl InteractiveWorker {
+    pub fn create(
+        session: Arc<Session>,
+        stream: Box<dyn PostgresStream>,
+        client_addr: String,
+    ) -> Self {
+        InteractiveWorker {
+            session,
+            stream,
+            client_addr,
+            write_buf: vec![],
+            statements: HashMap::new(),
+            portals: HashMap::new(),
+            skip_until_sync: false,
+        }
+    }
+
+    pub async fn run(mut self, params: HashMap<String, String>) -> Result<()> {
+        if let Err(cause) = self.do_startup(&params).await {
+            write_error(&mut self.write_buf, &cause);
+            return self.flush().await;
+        }
+
+        loop {
+            let message = match read_message(&mut self.stream).await {
+                Ok(Some(message)) => message,
+                Ok(None) => return Ok(()),
+                Err(cause) => {
+                    write_error(&mut self.write_buf, &cause);
+                    self.flush().await?;
+                    return Err(cause);
+                }
+            };
+            if self.skip_until_sync && !matches!(message, FrontendMessage::Sync) {
+                continue;
+            }
+
+            let result = match message {
+                FrontendMessage::Terminate => return Ok(()),
+                FrontendMessage::Query(query) => {
+                    if let Err(cause) = self.do_query(&query).await {
+                        write_error(&mut self.write_buf, &cause);
+                    }
+                    write_ready_for_query(&mut self.write_buf);
+                    self.flush().await?;
+                    continue;
+                }
+                FrontendMessage::Sync => {
+                    self.skip_until_sync = false;
+                    write_ready_for_query(&mut self.write_buf);
+                    self.flush().await?;
+                    continue;
+                }
+                FrontendMessage::Flush => {
+                    self.flush().await?;
+                    continue;
+                }
+                FrontendMessage::Parse {
+                    name,
+                    query,
+                    param_types,
+                } => self.do_parse(name, &query, &param_types),
+                FrontendMessage::Bind {
+                    portal,
+                    statement,
+                    param_formats,
+                    params,
+                    result_formats,
+                } => self.do_bind(portal, &statement, &param_formats, &params, result_formats),
+                FrontendMessage::Describe { target, name } => self.do_describe(target, &name).await,
+                FrontendMessage::Execute { portal, max_rows } => {
+                    self.do_execute(&portal, max_rows).await
+                }
+                FrontendMessage::Close { target, name } => self.do_close(target, &name),
+                FrontendMessage::Password(_) => Err(ErrorCode::BadBytes(
+                    "unexpected password message from postgres client",
+                )),
+            };
+            if let Err(cause) = result {
+                write_error(&mut self.write_buf, &cause);
+                self.skip_until_sync = true;
+            }
+        }
+    }
+
+    // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.6.7.3
+    async fn do_startup(&mut self, params: &HashMap<String, String>) -> Result<()> {
+        let user = match params.get("user") {
+            Some(user) if !user.is_empty() => user.clone(),
+            _ => {
+                return Err(ErrorCode::AuthenticateFailure(
+                    "postgres client sent no user",
+                ));
+            }
+        };
+
+        // The password is sent in clear text, which is protected by TLS if it is enabled.
+        write_authentication(&mut self.write_buf, AUTHENTICATION_CLEARTEXT_PASSWORD);
+        self.flush().await?;
+        let password = match read_message(&mut self.stream).await? {
+            Some(FrontendMessage::Password(password)) => password,
+            Some(_) => return Err(ErrorCode::BadBytes("expect password from postgres client")),
+            None => {
+                return Err(ErrorCode::AbortedSession(
+                    "postgres client closed the connection",
+                ));
+            }
+        };
+
+        info!(
+            "Postgres client {} connected from {}",
+            user, self.client_addr
+        );
+        let ctx = self.session.create_query_context().await?;
+        let client_ip = self.client_addr.split(':').collect::<Vec<_>>()[0];
+        let credential = Credential::Password {
+            name: user,
+            password: Some(password.into_bytes()),
+            hostname: Some(client_ip.to_string()),
+        };
+        ctx.get_auth_manager()
+            .auth(self.session.clone(), &credential)
+            .await?;
+        if let Some(database) = params.get("database").filter(|db| !db.is_empty()) {
+            ctx.set_current_database(database.clone()).await?;
+        }
+
+        write_authentication(&mut self.write_buf, AUTHENTICATION_OK);
+        let timezone = self.session.get_settings().get_timezone()?;
+        let application_name = params.get("application_name").cloned().unwrap_or_default();
+        let parameters = [
+            ("server_version", POSTGRES_VERSION),
+            ("server_encoding", "UTF8"),
+            ("client_encoding", "UTF8"),
+            ("DateStyle", "ISO, MDY"),
+            ("TimeZone", timezone.as_str()),
+            ("integer_datetimes", "on"),
+            // The backslashes in the string literals are escapes.
+            ("standard_conforming_strings", "off"),
+            ("is_superuser", "off"),
+            ("application_name", application_name.as_str()),
+        ];
+        for (name, value) in parameters {
+            write_parameter_status(&mut self.write_buf, name, value);
+        }
+        // The key of CancelRequest, which is not supported.
+        let mut rng = rand::thread_rng();
+        write_backend_key_data(&mut self.write_buf, rng.gen(), rng.gen());
+        write_ready_for_query(&mut self.write_buf);
+        self.flush().await
+    }
+
+    async fn do_query(&mut self, query: &str) -> Result<()> {
+        if is_empty_query(query) {
+            write_empty_query_response(&mut self.write_buf);
+            return Ok(());
+        }
+
+        let mut result = self.execute_query(query, None).await?;
+        if result.has_result_set {
+            let fields = field_descriptions(&result.schema, &[])?;
+            write_row_description(&mut self.write_buf, &fields);
+        }
+        self.write_rows(&mut result, &[], 0).await?;
+        write_command_complete(&mut self.write_buf, &result.command_tag());
+        Ok(())
+    }
+
+    fn do_parse(&mut self, name: String, query: &str, param_types: &[i32]) -> Result<()> {
+        let statement = PreparedStatement::create(query, param_types);
+        self.statements.insert(name, statement);
+        write_parse_complete(&mut self.write_buf);
+        Ok(())
+    }
+
+    fn do_bind(
+        &mut self,
+        portal: String,
+        statement: &str,
+        param_formats: &[i16],
+        params: &[Option<Vec<u8>>],
+        result_formats: Vec<i16>,
+    ) -> Result<()> {
+        let query = self.statement(statement)?.bind(params, param_formats)?;
+        self.portals.insert(portal, Portal {
+            query,
+            result_formats,
+            plan: None,
+            result: None,
+        });
+        write_bind_complete(&mut self.write_buf);
+        Ok(())
+    }
+
+    async fn do_describe(&mut self, target: Target, name: &str) -> Result<()> {
+        match target {
+            Target::Statement => {
+                let statement = self.statement(name)?;
+                let param_oids = statement.param_oids();
+                let query = statement.describe_query();
+                write_parameter_description(&mut self.write_buf, &param_oids);
+
+                let (schema, _) = self.describe_query(&query).await?;
+                self.write_description(schema, &[])
+            }
+            Target::Portal => {
+                let portal = self.portal(name)?;
+                let query = portal.query.clone();
+                let formats = portal.result_formats.clone();
+
+                let (schema, plan) = self.describe_query(&query).await?;
+                self.portal(name)?.plan = plan;
+                self.write_description(schema, &formats)
+            }
+        }
+    }
+
+    async fn do_execute(&mut self, name: &str, max_rows: i32) -> Result<()> {
+        let portal = self.portal(name)?;
+        let formats = portal.result_formats.clone();
+        let mut result = match portal.result.take() {
+            Some(result) => result,
+            None if is_empty_query(&portal.query) => {
+                write_empty_query_response(&mut self.write_buf);
+                return Ok(());
+            }
+            None => {
+                let query = portal.query.clone();
+                let plan = portal.plan.take();
+                self.execute_query(&query, plan).await?
+            }
+        };
+
+        // The rows of the rest are sent by the next Execute, after PortalSuspended.
+        match self
+            .write_rows(&mut result, &formats, max_rows.max(0) as usize)
+            .await?
+        {
+            true => write_command_complete(&mut self.write_buf, &result.command_tag()),
+            false => {
+                write_portal_suspended(&mut self.write_buf);
+                self.portal(name)?.result = Some(result);
+            }
+        }
+        Ok(())
+    }
+
+    fn do_close(&mut self, target: Target, name: &str) -> Result<()> {
+        match target {
+            Target::Statement => {
+                self.statements.remove(name);
+            }
+            Target::Portal => {
+                self.portals.remove(name);
+            }
+        }
+        write_close_complete(&mut self.write_buf);
+        Ok(())
+    }
+
+    fn statement(&self, name: &str) -> Result<&PreparedStatement> {
+        self.statements.get(name).ok_or_else(|| {
+            ErrorCode::BadArguments(format!("prepared statement \"{}\" does not exist", name))
+        })
+    }
+
+    fn portal(&mut self, name: &str) -> Result<&mut Portal> {
+        self.portals
+            .get_mut(name)
+            .ok_or_else(|| ErrorCode::BadArguments(format!("portal \"{}\" does not exist", name)))
+    }
+
+    async fn plan_query(&self, query: &str) -> Result<(Arc<QueryContext>, Plan)> {
+        let context = self.session.create_query_context().await?;
+        let mut planner = Planner::new(context.clone());
+        let (plan, _, _) = planner.plan_sql(query).await?;
+        context.attach_query_str(plan.to_string(), query);
+        Ok((context, plan))
+    }
+
+    // The schema of the rows, None if the query returns no rows, with the plan for the execution.
+    async fn describe_query(
+        &self,
+        query: &str,
+    ) -> Result<(Option<DataSchemaRef>, Option<(Arc<QueryContext>, Plan)>)> {
+        if is_empty_query(query) {
+            return Ok((None, None));
+        }
+        if let Some(block) = PostgresFederated::create().check(query.trim()) {
+            return Ok(((block.num_rows() > 0).then(|| block.schema().clone()), None));
+        }
+
+        let (context, plan) = self.plan_query(query).await?;
+        let schema = plan.has_result_set().then(|| plan.schema());
+        Ok((schema, Some((context, plan))))
+    }
+
+    async fn execute_query(
+        &self,
+        query: &str,
+        plan: Option<(Arc<QueryContext>, Plan)>,
+    ) -> Result<QueryResult> {
+        if self.session.is_aborting() {
+            return Err(ErrorCode::AbortedSession(
+                "Aborting this connection. because we are try aborting server.",
+            ));
+        }
+
+        if let Some(block) = PostgresFederated::create().check(query.trim()) {
+            info!("Federated query: {}", query);
+            let schema = block.schema().clone();
+            let has_result_set = block.num_rows() > 0;
+            return Ok(QueryResult {
+                context: None,
+                command: command_of(query, has_result_set),
+                has_result_set,
+                schema: schema.clone(),
+                stream: DataBlockStream::create(schema, None, vec![block]).boxed(),
+                block: None,
+                rows: 0,
+            });
+        }
+
+        info!("Postgres query: {}", query);
+        let (context, plan) = match plan {
+            Some(planned) => planned,
+            None => self.plan_query(query).await?,
+        };
+        let interpreter = match InterpreterFactory::get(context.clone(), &plan).await {
+            Ok(interpreter) => interpreter,
+            Err(cause) => {
+                InterpreterQueryLog::fail_to_start(context, cause.clone());
+                return Err(cause);
+            }
+        };
+
+        let stream = interpreter.execute(context.clone()).await?;
+        let has_result_set = plan.has_result_set();
+        Ok(QueryResult {
+            context: Some(context),
+            command: command_of(query, has_result_set),
+            has_result_set,
+            schema: interpreter.schema(),
+            stream,
+            block: None,
+            rows: 0,
+        })
+    }
+
+    fn write_description(&mut self, schema: Option<DataSchemaRef>, formats: &[i16]) -> Result<()> {
+        match schema {
+            None => write_no_data(&mut self.write_buf),
+            Some(schema) => {
+                let fields = field_descriptions(&schema, formats)?;
+                write_row_description(&mut self.write_buf, &fields);
+            }
+        }
+        Ok(())
+    }
+
+    /// Send the rows as DataRow, at most `max_rows` if it is not 0.
+    ///
+    /// Return whether the result is completed, which is pulled to the end even if it has no rows,
+    /// as the errors may occur in the stream.
+    async fn write_rows(
+        &mut self,
+        result: &mut QueryResult,
+        formats: &[i16],
+        max_rows: usize,
+    ) -> Result<bool> {
+        let format = self.session.get_format_settings()?;
+        let fields = result.schema.fields();
+        let types = fields
+            .iter()
+            .map(|field| PgType::from_data_type(field.data_type()))
+            .collect::<Vec<_>>();
+        let binary = (0..fields.len())
+            .map(|i| Ok(format_of(formats, i)? == FORMAT_BINARY))
+            .collect::<Result<Vec<_>>>()?;
+
+        result.rows = 0;
+        loop {
+            if max_rows > 0 && result.rows >= max_rows {
+                return Ok(false);
+            }
+            let (block, start) = match result.block.take() {
+                Some(current) => current,
+                None => match result.stream.next().await {
+                    None => return Ok(true),
+                    Some(block) => (block?, 0),
+                },
+            };
+            if !result.has_result_set {
+                continue;
+            }
+
+            let serializers = block.get_serializers()?;
+            let end = match max_rows {
+                0 => block.num_rows(),
+                max_rows => block.num_rows().min(start + max_rows - result.rows),
+            };
+            for row in start..end {
+                let message = begin_message(&mut self.write_buf, b'D');
+                self.write_buf
+                    .extend_from_slice(&(types.len() as i16).to_be_bytes());
+                for (i, column) in block.columns().iter().enumerate() {
+                    write_field(
+                        &mut self.write_buf,
+                        column,
+                        &serializers[i],
+                        types[i],
+                        row,
+                        binary[i],
+                        &format,
+                    )?;
+                }
+                end_message(&mut self.write_buf, message);
+                if self.write_buf.len() >= FLUSH_BUFFER_SIZE {
+                    self.flush().await?;
+                }
+            }
+
+            result.rows += end - start;
+            if end < block.num_rows() {
+                result.block = Some((block, end));
+            }
+        }
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        if !self.write_buf.is_empty() {
+            self.stream.write_all(&self.write_buf).await?;
+            self.stream.flush().await?;
+            self.write_buf.clear();
+        }
+        Ok(())
+    }
+}
+
+fn is_empty_query(query: &str) -> bool {
+    query.trim().trim_end_matches(';').trim().is_empty()
+}
+
+fn field_descriptions(schema: &DataSchemaRef, formats: &[i16]) -> Result<Vec<FieldDescription>> {
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            Ok(FieldDescription {
+                name: field.name().clone(),
+                data_type: PgType::from_data_type(field.data_type()),
+                format: format_of(formats, i)?,
+            })
+        })
+        .collect()
+}
+
+// The tag has the number of rows of SELECT and INSERT, and the leading keywords of the others.
+fn command_of(query: &str, has_result_set: bool) -> String {
+    if has_result_set {
+        return "SELECT".to_string();
+    }
+
+    let mut words = query
+        .split_whitespace()
+        .map(|word| word.trim_end_matches(';').to_uppercase());
+    match words.next() {
+        None => "".to_string(),
+        Some(first) if matches!(first.as_str(), "CREATE" | "DROP" | "ALTER" | "UNDROP") => {
+            match words.next() {
+                Some(second) => format!("{} {}", first, second),
+                None => first,
+            }
+        }
+        Some(first) => first,
+    }
+}
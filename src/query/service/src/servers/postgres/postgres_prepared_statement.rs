@@ -0,0 +1,184 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::servers::postgres::postgres_types::param_to_literal;
+use crate::servers::postgres::postgres_types::placeholder_literal;
+use crate::servers::postgres::postgres_types::PgType;
+use crate::servers::postgres::protocol::FORMAT_BINARY;
+use crate::servers::postgres::protocol::FORMAT_TEXT;
+
+/// A statement prepared by Parse, whose parameters are the `$n` placeholders.
+///
+/// The parameters bound by Bind are substituted into the query as literals, so the statement is
+/// planned as a normal query on each execution.
+pub struct PreparedStatement {
+    query: String,
+    placeholders: Vec<Placeholder>,
+    // The types of the parameters `$1` to `$n`, unspecified if the client does not declare them.
+    param_types: Vec<PgType>,
+}
+
+struct Placeholder {
+    start: usize,
+    end: usize,
+    // The index of the parameter, from 0.
+    index: usize,
+}
+
+impl PreparedStatement {
+    pub fn create(query: &str, declared_types: &[i32]) -> PreparedStatement {
+        let placeholders = find_placeholders(query);
+        let num_params = placeholders
+            .iter()
+            .map(|placeholder| placeholder.index + 1)
+            .max()
+            .unwrap_or(0)
+            .max(declared_types.len());
+        let param_types = (0..num_params)
+            .map(|i| match declared_types.get(i) {
+                Some(oid) => PgType::from_oid(*oid),
+                None => PgType::UNSPECIFIED,
+            })
+            .collect();
+
+        PreparedStatement {
+            query: query.to_string(),
+            placeholders,
+            param_types,
+        }
+    }
+
+    /// The types sent in ParameterDescription, the unspecified ones are bound as text.
+    pub fn param_oids(&self) -> Vec<i32> {
+        self.param_types
+            .iter()
+            .map(|data_type| match *data_type {
+                PgType::UNSPECIFIED => PgType::TEXT.oid,
+                data_type => data_type.oid,
+            })
+            .collect()
+    }
+
+    /// The query to plan the columns of the result before the parameters are bound.
+    pub fn describe_query(&self) -> String {
+        self.substitute(|index| placeholder_literal(self.param_types[index]).to_string())
+    }
+
+    pub fn bind(&self, params: &[Option<Vec<u8>>], formats: &[i16]) -> Result<String> {
+        // `$n` also refers to the columns of the staged files, which has no parameters bound.
+        if params.is_empty() {
+            return Ok(self.query.clone());
+        }
+
+        if params.len() != self.param_types.len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "prepared statement expects {} parameters, but {} are bound",
+                self.param_types.len(),
+                params.len()
+            )));
+        }
+
+        let literals = params
+            .iter()
+            .enumerate()
+            .map(|(i, param)| {
+                let binary = format_of(formats, i)? == FORMAT_BINARY;
+                param_to_literal(param.as_deref(), self.param_types[i], binary)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.substitute(|index| literals[index].clone()))
+    }
+
+    fn substitute(&self, literal: impl Fn(usize) -> String) -> String {
+        let mut query = String::with_capacity(self.query.len());
+        let mut pos = 0;
+        for placeholder in self.placeholders.iter() {
+            query.push_str(&self.query[pos..placeholder.start]);
+            query.push_str(&literal(placeholder.index));
+            pos = placeholder.end;
+        }
+        query.push_str(&self.query[pos..]);
+        query
+    }
+}
+
+/// The format of the i-th parameter or column: one format code applies to all of them, and no
+/// format code means text.
+pub fn format_of(formats: &[i16], i: usize) -> Result<i16> {
+    let format = match formats.len() {
+        0 => FORMAT_TEXT,
+        1 => formats[0],
+        _ => formats.get(i).copied().unwrap_or(FORMAT_TEXT),
+    };
+    match format {
+        FORMAT_TEXT | FORMAT_BINARY => Ok(format),
+        format => Err(ErrorCode::BadBytes(format!(
+            "invalid postgres format code {}",
+            format
+        ))),
+    }
+}
+
+/// Find the `$n` out of the quoted strings, identifiers and comments.
+fn find_placeholders(query: &str) -> Vec<Placeholder> {
+    let bytes = query.as_bytes();
+    let mut placeholders = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' && quote != b'`' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'$' => {
+                let digits = bytes[i + 1..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
+                let number = query[i + 1..i + 1 + digits].parse::<usize>().unwrap_or(0);
+                if number > 0 {
+                    placeholders.push(Placeholder {
+                        start: i,
+                        end: i + 1 + digits,
+                        index: number - 1,
+                    });
+                    i += digits;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    placeholders
+}
@@ -0,0 +1,140 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::net::Shutdown;
+use std::sync::Arc;
+
+use common_base::base::tokio::io::AsyncRead;
+use common_base::base::tokio::io::AsyncWrite;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_base::base::tokio::net::TcpStream;
+use common_base::base::Runtime;
+use common_base::base::Thread;
+use common_base::base::TrySpawn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_exception::ToErrorCode;
+use tokio_rustls::TlsAcceptor;
+use tracing::error;
+
+use crate::servers::postgres::postgres_interactive_worker::InteractiveWorker;
+use crate::servers::postgres::protocol::read_startup;
+use crate::servers::postgres::protocol::StartupMessage;
+use crate::sessions::Session;
+
+/// The stream of a connection, in plain text or upgraded to TLS.
+pub trait PostgresStream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> PostgresStream for T {}
+
+pub struct PostgresConnection;
+
+impl PostgresConnection {
+    pub fn run_on_stream(
+        session: Arc<Session>,
+        stream: TcpStream,
+        tls: Option<TlsAcceptor>,
+    ) -> Result<()> {
+        let blocking_stream = Self::convert_stream(stream)?;
+        PostgresConnection::attach_session(&session, &blocking_stream)?;
+
+        blocking_stream.set_nonblocking(true)?;
+        let non_blocking_stream = TcpStream::from_std(blocking_stream)?;
+        let query_executor =
+            Runtime::with_worker_threads(1, Some("postgres-query-executor".to_string()))?;
+        Thread::spawn(move || {
+            let join_handle = query_executor.spawn(async move {
+                let client_addr = non_blocking_stream.peer_addr().unwrap().to_string();
+                let (stream, params) = match Self::negotiate(non_blocking_stream, tls).await {
+                    Ok(Some(startup)) => startup,
+                    Ok(None) => return,
+                    Err(error) => {
+                        error!(
+                            "Unexpected error occurred during postgres startup: {:?}",
+                            error
+                        );
+                        return;
+                    }
+                };
+                let interactive_worker = InteractiveWorker::create(session, stream, client_addr);
+                if let Err(error) = interactive_worker.run(params).await {
+                    error!(
+                        "Unexpected error occurred during postgres session: {:?}",
+                        error
+                    );
+                }
+            });
+            let _ = futures::executor::block_on(join_handle);
+        });
+        Ok(())
+    }
+
+    /// Read the startup message, the connection is upgraded to TLS first if the client requests.
+    ///
+    /// None if the client closed the connection, or sent a CancelRequest, which is not supported.
+    async fn negotiate(
+        mut stream: TcpStream,
+        tls: Option<TlsAcceptor>,
+    ) -> Result<Option<(Box<dyn PostgresStream>, HashMap<String, String>)>> {
+        loop {
+            match read_startup(&mut stream).await? {
+                None | Some(StartupMessage::CancelRequest) => return Ok(None),
+                Some(StartupMessage::Startup(params)) => {
+                    return Ok(Some((Box::new(stream), params)));
+                }
+                Some(StartupMessage::SslRequest) if tls.is_some() => break,
+                // The client goes on in plain text, or closes the connection.
+                Some(StartupMessage::SslRequest) | Some(StartupMessage::GssEncRequest) => {
+                    stream.write_all(b"N").await?
+                }
+            }
+        }
+
+        stream.write_all(b"S").await?;
+        let mut stream = tls.unwrap().accept(stream).await?;
+        match read_startup(&mut stream).await? {
+            None => Ok(None),
+            Some(StartupMessage::Startup(params)) => Ok(Some((Box::new(stream), params))),
+            Some(_) => Err(ErrorCode::BadBytes(
+                "expect startup message from postgres client after TLS handshake",
+            )),
+        }
+    }
+
+    fn attach_session(session: &Arc<Session>, blocking_stream: &std::net::TcpStream) -> Result<()> {
+        let host = blocking_stream.peer_addr().ok();
+        let blocking_stream_ref = blocking_stream.try_clone()?;
+        session.attach(host, move || {
+            if let Err(error) = blocking_stream_ref.shutdown(Shutdown::Both) {
+                error!("Cannot shutdown Postgres session io {}", error);
+            }
+        });
+
+        Ok(())
+    }
+
+    fn convert_stream(stream: TcpStream) -> Result<std::net::TcpStream> {
+        let stream = stream.into_std().map_err_to_code(
+            ErrorCode::TokioError,
+            || "Cannot to convert Tokio TcpStream to Std TcpStream",
+        )?;
+        stream.set_nonblocking(false).map_err_to_code(
+            ErrorCode::TokioError,
+            || "Cannot to convert Tokio TcpStream to Std TcpStream",
+        )?;
+
+        Ok(stream)
+    }
+}
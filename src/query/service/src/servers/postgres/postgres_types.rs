@@ -0,0 +1,285 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::Duration;
+use chrono::NaiveDate;
+use common_datavalues::prelude::TypeID;
+use common_datavalues::remove_nullable;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DateConverter;
+use common_datavalues::TypeSerializer;
+use common_datavalues::TypeSerializerImpl;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+
+// The epoch of the binary date and timestamp is 2000-01-01.
+const POSTGRES_EPOCH_DAYS: i64 = 10957;
+
+/// The type of a column or a parameter, identified by the oid of `pg_type`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PgType {
+    pub oid: i32,
+    // The size of the binary value, -1 for the types of variable length.
+    pub size: i16,
+}
+
+impl PgType {
+    pub const UNSPECIFIED: PgType = PgType { oid: 0, size: -1 };
+    pub const BOOL: PgType = PgType { oid: 16, size: 1 };
+    pub const INT8: PgType = PgType { oid: 20, size: 8 };
+    pub const INT2: PgType = PgType { oid: 21, size: 2 };
+    pub const INT4: PgType = PgType { oid: 23, size: 4 };
+    pub const TEXT: PgType = PgType { oid: 25, size: -1 };
+    pub const JSON: PgType = PgType { oid: 114, size: -1 };
+    pub const FLOAT4: PgType = PgType { oid: 700, size: 4 };
+    pub const FLOAT8: PgType = PgType { oid: 701, size: 8 };
+    pub const VARCHAR: PgType = PgType {
+        oid: 1043,
+        size: -1,
+    };
+    pub const DATE: PgType = PgType { oid: 1082, size: 4 };
+    pub const TIMESTAMP: PgType = PgType { oid: 1114, size: 8 };
+    pub const NUMERIC: PgType = PgType {
+        oid: 1700,
+        size: -1,
+    };
+
+    /// The unsigned integers are widened, as postgres has only the signed ones, and the types
+    /// without an equivalent, such as arrays and tuples, are sent as text.
+    pub fn from_data_type(data_type: &DataTypeImpl) -> PgType {
+        match remove_nullable(data_type).data_type_id() {
+            TypeID::Boolean => PgType::BOOL,
+            TypeID::Int8 | TypeID::Int16 | TypeID::UInt8 => PgType::INT2,
+            TypeID::Int32 | TypeID::UInt16 => PgType::INT4,
+            TypeID::Int64 | TypeID::UInt32 | TypeID::Interval => PgType::INT8,
+            TypeID::UInt64 => PgType::NUMERIC,
+            TypeID::Float32 => PgType::FLOAT4,
+            TypeID::Float64 => PgType::FLOAT8,
+            TypeID::Date => PgType::DATE,
+            TypeID::Timestamp => PgType::TIMESTAMP,
+            TypeID::Variant | TypeID::VariantArray | TypeID::VariantObject => PgType::JSON,
+            _ => PgType::TEXT,
+        }
+    }
+
+    pub fn from_oid(oid: i32) -> PgType {
+        [
+            PgType::BOOL,
+            PgType::INT8,
+            PgType::INT2,
+            PgType::INT4,
+            PgType::TEXT,
+            PgType::JSON,
+            PgType::FLOAT4,
+            PgType::FLOAT8,
+            PgType::VARCHAR,
+            PgType::DATE,
+            PgType::TIMESTAMP,
+            PgType::NUMERIC,
+        ]
+        .into_iter()
+        .find(|data_type| data_type.oid == oid)
+        .unwrap_or(PgType::UNSPECIFIED)
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            *self,
+            PgType::INT2
+                | PgType::INT4
+                | PgType::INT8
+                | PgType::FLOAT4
+                | PgType::FLOAT8
+                | PgType::NUMERIC
+        )
+    }
+}
+
+/// Write a field of DataRow: the length of the value, followed by the value in the text or
+/// binary format.
+pub fn write_field(
+    buf: &mut Vec<u8>,
+    column: &ColumnRef,
+    serializer: &TypeSerializerImpl,
+    data_type: PgType,
+    row: usize,
+    binary: bool,
+    format: &FormatSettings,
+) -> Result<()> {
+    let value = column.get_checked(row)?;
+    if value.is_null() {
+        buf.extend_from_slice(&(-1i32).to_be_bytes());
+        return Ok(());
+    }
+
+    let start = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+    match (binary, data_type) {
+        (false, PgType::BOOL) => buf.push(if value.as_bool()? { b't' } else { b'f' }),
+        (true, PgType::BOOL) => buf.push(value.as_bool()? as u8),
+        (true, PgType::INT2) => buf.extend_from_slice(&(value.as_i64()? as i16).to_be_bytes()),
+        (true, PgType::INT4) => buf.extend_from_slice(&(value.as_i64()? as i32).to_be_bytes()),
+        (true, PgType::INT8) => buf.extend_from_slice(&value.as_i64()?.to_be_bytes()),
+        (true, PgType::FLOAT4) => buf.extend_from_slice(&(value.as_f64()? as f32).to_be_bytes()),
+        (true, PgType::FLOAT8) => buf.extend_from_slice(&value.as_f64()?.to_be_bytes()),
+        (true, PgType::NUMERIC) => write_binary_numeric(buf, value.as_u64()?),
+        (true, PgType::DATE) => {
+            let days = value.as_i64()? - POSTGRES_EPOCH_DAYS;
+            buf.extend_from_slice(&(days as i32).to_be_bytes());
+        }
+        (true, PgType::TIMESTAMP) => {
+            // The same wall clock as the text format, which is in the timezone of the session.
+            let datetime = value.as_i64()?.to_timestamp(&format.timezone).naive_local();
+            let epoch = NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0);
+            let micros = (datetime - epoch).num_microseconds().unwrap_or(i64::MAX);
+            buf.extend_from_slice(&micros.to_be_bytes());
+        }
+        // The binary format of text and json is the text itself.
+        _ => serializer.write_field(row, buf, format),
+    }
+    let len = (buf.len() - start - 4) as i32;
+    buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+    Ok(())
+}
+
+// The digits are in base 10000, from the most significant one, whose weight is the exponent.
+fn write_binary_numeric(buf: &mut Vec<u8>, mut value: u64) {
+    let mut digits = vec![];
+    while value > 0 {
+        digits.push((value % 10000) as i16);
+        value /= 10000;
+    }
+    let weight = digits.len() as i16 - 1;
+    // The trailing zero digits are implied by the weight.
+    let zeros = digits.iter().take_while(|digit| **digit == 0).count();
+    digits.drain(..zeros);
+    digits.reverse();
+
+    buf.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+    buf.extend_from_slice(&weight.max(0).to_be_bytes());
+    // The sign is positive and the scale is 0.
+    buf.extend_from_slice(&0i16.to_be_bytes());
+    buf.extend_from_slice(&0i16.to_be_bytes());
+    for digit in digits {
+        buf.extend_from_slice(&digit.to_be_bytes());
+    }
+}
+
+/// Convert a bound parameter to a SQL literal, by the type declared by the client.
+pub fn param_to_literal(value: Option<&[u8]>, data_type: PgType, binary: bool) -> Result<String> {
+    let value = match value {
+        None => return Ok("NULL".to_string()),
+        Some(value) => value,
+    };
+
+    if !binary {
+        let text = String::from_utf8_lossy(value);
+        return match data_type {
+            PgType::BOOL => match text.to_lowercase().as_str() {
+                "t" | "true" | "y" | "yes" | "on" | "1" => Ok("TRUE".to_string()),
+                "f" | "false" | "n" | "no" | "off" | "0" => Ok("FALSE".to_string()),
+                _ => Err(invalid_param(data_type, &text)),
+            },
+            // The number is validated, as it is not quoted in the query.
+            data_type if data_type.is_numeric() => match text.trim().parse::<f64>() {
+                Ok(number) if number.is_finite() => Ok(text.trim().to_string()),
+                _ => Err(invalid_param(data_type, &text)),
+            },
+            _ => Ok(quote_string(&text)),
+        };
+    }
+
+    let expect_size = |size: usize| match value.len() == size {
+        true => Ok(()),
+        false => Err(ErrorCode::BadBytes(format!(
+            "invalid length {} of binary parameter of type oid {}",
+            value.len(),
+            data_type.oid
+        ))),
+    };
+    match data_type {
+        PgType::BOOL => {
+            expect_size(1)?;
+            Ok(if value[0] != 0 { "TRUE" } else { "FALSE" }.to_string())
+        }
+        PgType::INT2 => {
+            expect_size(2)?;
+            Ok(i16::from_be_bytes([value[0], value[1]]).to_string())
+        }
+        PgType::INT4 => {
+            expect_size(4)?;
+            Ok(i32::from_be_bytes(value.try_into().unwrap()).to_string())
+        }
+        PgType::INT8 => {
+            expect_size(8)?;
+            Ok(i64::from_be_bytes(value.try_into().unwrap()).to_string())
+        }
+        PgType::FLOAT4 => {
+            expect_size(4)?;
+            Ok(f32::from_be_bytes(value.try_into().unwrap()).to_string())
+        }
+        PgType::FLOAT8 => {
+            expect_size(8)?;
+            Ok(f64::from_be_bytes(value.try_into().unwrap()).to_string())
+        }
+        PgType::DATE => {
+            expect_size(4)?;
+            let days = i32::from_be_bytes(value.try_into().unwrap());
+            let date = NaiveDate::from_ymd(2000, 1, 1)
+                .checked_add_signed(Duration::days(days as i64))
+                .ok_or_else(|| invalid_param(data_type, &days.to_string()))?;
+            Ok(format!("'{}'", date.format("%Y-%m-%d")))
+        }
+        PgType::TIMESTAMP => {
+            expect_size(8)?;
+            let micros = i64::from_be_bytes(value.try_into().unwrap());
+            let epoch = NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0);
+            let datetime = epoch
+                .checked_add_signed(Duration::microseconds(micros))
+                .ok_or_else(|| invalid_param(data_type, &micros.to_string()))?;
+            Ok(format!("'{}'", datetime.format("%Y-%m-%d %H:%M:%S%.6f")))
+        }
+        PgType::TEXT | PgType::VARCHAR | PgType::JSON | PgType::UNSPECIFIED => {
+            Ok(quote_string(&String::from_utf8_lossy(value)))
+        }
+        _ => Err(ErrorCode::UnImplement(format!(
+            "binary parameter of type oid {} is not supported",
+            data_type.oid
+        ))),
+    }
+}
+
+/// The literal of a parameter when the statement is described before the binding, which has the
+/// declared type, so the columns are typed as the ones of the execution.
+pub fn placeholder_literal(data_type: PgType) -> &'static str {
+    match data_type {
+        PgType::BOOL => "FALSE",
+        data_type if data_type.is_numeric() => "0",
+        _ => "NULL",
+    }
+}
+
+fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn invalid_param(data_type: PgType, value: &str) -> ErrorCode {
+    ErrorCode::BadArguments(format!(
+        "invalid parameter {:?} of type oid {}",
+        value, data_type.oid
+    ))
+}
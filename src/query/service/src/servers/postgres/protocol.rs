@@ -0,0 +1,400 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The messages of the PostgreSQL frontend/backend protocol 3.0.
+//!
+//! https://www.postgresql.org/docs/current/protocol-message-formats.html
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+
+use common_base::base::tokio::io::AsyncRead;
+use common_base::base::tokio::io::AsyncReadExt;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::servers::postgres::postgres_types::PgType;
+
+pub const PROTOCOL_VERSION: i32 = 196608;
+pub const CANCEL_REQUEST_CODE: i32 = 80877102;
+pub const SSL_REQUEST_CODE: i32 = 80877103;
+pub const GSSENC_REQUEST_CODE: i32 = 80877104;
+
+pub const AUTHENTICATION_OK: i32 = 0;
+pub const AUTHENTICATION_CLEARTEXT_PASSWORD: i32 = 3;
+
+pub const FORMAT_TEXT: i16 = 0;
+pub const FORMAT_BINARY: i16 = 1;
+
+// A corrupted length must not allocate the memory of the server.
+const MAX_MESSAGE_SIZE: usize = 1 << 30;
+
+/// The first message of a connection, which has no type byte.
+pub enum StartupMessage {
+    SslRequest,
+    GssEncRequest,
+    CancelRequest,
+    Startup(HashMap<String, String>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    Statement,
+    Portal,
+}
+
+pub enum FrontendMessage {
+    Password(String),
+    Query(String),
+    Parse {
+        name: String,
+        query: String,
+        param_types: Vec<i32>,
+    },
+    Bind {
+        portal: String,
+        statement: String,
+        param_formats: Vec<i16>,
+        params: Vec<Option<Vec<u8>>>,
+        result_formats: Vec<i16>,
+    },
+    Describe {
+        target: Target,
+        name: String,
+    },
+    Execute {
+        portal: String,
+        max_rows: i32,
+    },
+    Close {
+        target: Target,
+        name: String,
+    },
+    Sync,
+    Flush,
+    Terminate,
+}
+
+/// Read the startup message, None if the client closed the connection.
+pub async fn read_startup<R: AsyncRead + Unpin>(stream: &mut R) -> Result<Option<StartupMessage>> {
+    let len = match stream.read_i32().await {
+        Ok(len) => len,
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+    let body = read_body(stream, len).await?;
+    let mut reader = Reader::new(&body);
+    match reader.i32()? {
+        SSL_REQUEST_CODE => Ok(Some(StartupMessage::SslRequest)),
+        GSSENC_REQUEST_CODE => Ok(Some(StartupMessage::GssEncRequest)),
+        CANCEL_REQUEST_CODE => Ok(Some(StartupMessage::CancelRequest)),
+        PROTOCOL_VERSION => {
+            let mut params = HashMap::new();
+            loop {
+                let name = reader.cstr()?;
+                if name.is_empty() {
+                    break;
+                }
+                params.insert(name, reader.cstr()?);
+            }
+            Ok(Some(StartupMessage::Startup(params)))
+        }
+        version => Err(ErrorCode::UnImplement(format!(
+            "unsupported postgres protocol version {}.{}",
+            version >> 16,
+            version & 0xFFFF
+        ))),
+    }
+}
+
+/// Read a message after the startup, None if the client closed the connection.
+pub async fn read_message<R: AsyncRead + Unpin>(stream: &mut R) -> Result<Option<FrontendMessage>> {
+    let tag = match stream.read_u8().await {
+        Ok(tag) => tag,
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+    let len = stream.read_i32().await?;
+    let body = read_body(stream, len).await?;
+    let mut reader = Reader::new(&body);
+
+    let message = match tag {
+        b'p' => FrontendMessage::Password(reader.cstr()?),
+        b'Q' => FrontendMessage::Query(reader.cstr()?),
+        b'P' => FrontendMessage::Parse {
+            name: reader.cstr()?,
+            query: reader.cstr()?,
+            param_types: reader.list(|r| r.i32())?,
+        },
+        b'B' => FrontendMessage::Bind {
+            portal: reader.cstr()?,
+            statement: reader.cstr()?,
+            param_formats: reader.list(|r| r.i16())?,
+            params: reader.list(|r| match r.i32()? {
+                len if len < 0 => Ok(None),
+                len => Ok(Some(r.bytes(len as usize)?.to_vec())),
+            })?,
+            result_formats: reader.list(|r| r.i16())?,
+        },
+        b'D' => FrontendMessage::Describe {
+            target: reader.target()?,
+            name: reader.cstr()?,
+        },
+        b'E' => FrontendMessage::Execute {
+            portal: reader.cstr()?,
+            max_rows: reader.i32()?,
+        },
+        b'C' => FrontendMessage::Close {
+            target: reader.target()?,
+            name: reader.cstr()?,
+        },
+        b'S' => FrontendMessage::Sync,
+        b'H' => FrontendMessage::Flush,
+        b'X' => FrontendMessage::Terminate,
+        // CopyData, CopyDone, CopyFail and FunctionCall.
+        tag => {
+            return Err(ErrorCode::UnImplement(format!(
+                "unsupported postgres message '{}'",
+                tag as char
+            )));
+        }
+    };
+    Ok(Some(message))
+}
+
+async fn read_body<R: AsyncRead + Unpin>(stream: &mut R, len: i32) -> Result<Vec<u8>> {
+    // The length includes itself.
+    if len < 4 || len as usize - 4 > MAX_MESSAGE_SIZE {
+        return Err(ErrorCode::BadBytes(format!(
+            "invalid length of postgres message: {}",
+            len
+        )));
+    }
+    let mut body = vec![0; len as usize - 4];
+    stream.read_exact(&mut body).await?;
+    Ok(body)
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Reader { buf, pos: 0 }
+    }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.buf.len() - self.pos < n {
+            return Err(ErrorCode::BadBytes("postgres message is truncated"));
+        }
+        let bytes = &self.buf[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        let bytes = self.bytes(2)?;
+        Ok(i16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        let bytes = self.bytes(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn cstr(&mut self) -> Result<String> {
+        let rest = &self.buf[self.pos..];
+        let end = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| ErrorCode::BadBytes("postgres string is not terminated"))?;
+        self.pos += end + 1;
+        String::from_utf8(rest[..end].to_vec())
+            .map_err(|_| ErrorCode::BadBytes("postgres string is not valid utf8"))
+    }
+
+    fn target(&mut self) -> Result<Target> {
+        match self.bytes(1)?[0] {
+            b'S' => Ok(Target::Statement),
+            b'P' => Ok(Target::Portal),
+            target => Err(ErrorCode::BadBytes(format!(
+                "invalid postgres describe target '{}'",
+                target as char
+            ))),
+        }
+    }
+
+    // A list is prefixed by the number of its items in int16.
+    fn list<T>(&mut self, f: impl Fn(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let n = self.i16()?;
+        (0..n.max(0)).map(|_| f(self)).collect()
+    }
+}
+
+/// Start a backend message, the returned position is passed to `end_message` to fill the length.
+pub fn begin_message(buf: &mut Vec<u8>, tag: u8) -> usize {
+    buf.push(tag);
+    let start = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+    start
+}
+
+pub fn end_message(buf: &mut Vec<u8>, start: usize) {
+    let len = (buf.len() - start) as i32;
+    buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+}
+
+fn write_cstr(buf: &mut Vec<u8>, value: &str) {
+    buf.extend_from_slice(value.as_bytes());
+    buf.push(0);
+}
+
+fn write_empty_message(buf: &mut Vec<u8>, tag: u8) {
+    let start = begin_message(buf, tag);
+    end_message(buf, start);
+}
+
+pub fn write_authentication(buf: &mut Vec<u8>, code: i32) {
+    let start = begin_message(buf, b'R');
+    buf.extend_from_slice(&code.to_be_bytes());
+    end_message(buf, start);
+}
+
+pub fn write_parameter_status(buf: &mut Vec<u8>, name: &str, value: &str) {
+    let start = begin_message(buf, b'S');
+    write_cstr(buf, name);
+    write_cstr(buf, value);
+    end_message(buf, start);
+}
+
+pub fn write_backend_key_data(buf: &mut Vec<u8>, process_id: i32, secret_key: i32) {
+    let start = begin_message(buf, b'K');
+    buf.extend_from_slice(&process_id.to_be_bytes());
+    buf.extend_from_slice(&secret_key.to_be_bytes());
+    end_message(buf, start);
+}
+
+/// Databend has no transactions, the session is always idle between the queries.
+pub fn write_ready_for_query(buf: &mut Vec<u8>) {
+    let start = begin_message(buf, b'Z');
+    buf.push(b'I');
+    end_message(buf, start);
+}
+
+pub fn write_error(buf: &mut Vec<u8>, error: &ErrorCode) {
+    let start = begin_message(buf, b'E');
+    buf.push(b'S');
+    write_cstr(buf, "ERROR");
+    buf.push(b'V');
+    write_cstr(buf, "ERROR");
+    buf.push(b'C');
+    write_cstr(buf, sql_state(error.code()));
+    buf.push(b'M');
+    write_cstr(buf, &error.message());
+    buf.push(b'D');
+    write_cstr(buf, &format!("Databend error code: {}", error.code()));
+    buf.push(0);
+    end_message(buf, start);
+}
+
+// https://www.postgresql.org/docs/current/errcodes-appendix.html
+fn sql_state(code: u16) -> &'static str {
+    let states = [
+        (ErrorCode::syntax_exception_code(), "42601"),
+        (ErrorCode::semantic_error_code(), "42601"),
+        (ErrorCode::unknown_database_code(), "3D000"),
+        (ErrorCode::unknown_table_code(), "42P01"),
+        (ErrorCode::unknown_column_code(), "42703"),
+        (ErrorCode::unknown_function_code(), "42883"),
+        (ErrorCode::database_already_exists_code(), "42P04"),
+        (ErrorCode::table_already_exists_code(), "42P07"),
+        (ErrorCode::authenticate_failure_code(), "28P01"),
+        (ErrorCode::unknown_user_code(), "28000"),
+        (ErrorCode::permission_denied_code(), "42501"),
+        (ErrorCode::bad_arguments_code(), "22023"),
+        (ErrorCode::un_implement_code(), "0A000"),
+        (ErrorCode::too_many_user_connections_code(), "53300"),
+        (ErrorCode::aborted_query_code(), "57014"),
+        (ErrorCode::aborted_session_code(), "57P01"),
+    ];
+    states
+        .iter()
+        .find(|(state_code, _)| *state_code == code)
+        .map(|(_, state)| *state)
+        .unwrap_or("XX000")
+}
+
+pub struct FieldDescription {
+    pub name: String,
+    pub data_type: PgType,
+    pub format: i16,
+}
+
+pub fn write_row_description(buf: &mut Vec<u8>, fields: &[FieldDescription]) {
+    let start = begin_message(buf, b'T');
+    buf.extend_from_slice(&(fields.len() as i16).to_be_bytes());
+    for field in fields {
+        write_cstr(buf, &field.name);
+        // The oid of the table and the attribute number of the column.
+        buf.extend_from_slice(&0i32.to_be_bytes());
+        buf.extend_from_slice(&0i16.to_be_bytes());
+        buf.extend_from_slice(&field.data_type.oid.to_be_bytes());
+        buf.extend_from_slice(&field.data_type.size.to_be_bytes());
+        // The type modifier.
+        buf.extend_from_slice(&(-1i32).to_be_bytes());
+        buf.extend_from_slice(&field.format.to_be_bytes());
+    }
+    end_message(buf, start);
+}
+
+pub fn write_parameter_description(buf: &mut Vec<u8>, param_types: &[i32]) {
+    let start = begin_message(buf, b't');
+    buf.extend_from_slice(&(param_types.len() as i16).to_be_bytes());
+    for param_type in param_types {
+        buf.extend_from_slice(&param_type.to_be_bytes());
+    }
+    end_message(buf, start);
+}
+
+pub fn write_command_complete(buf: &mut Vec<u8>, tag: &str) {
+    let start = begin_message(buf, b'C');
+    write_cstr(buf, tag);
+    end_message(buf, start);
+}
+
+pub fn write_empty_query_response(buf: &mut Vec<u8>) {
+    write_empty_message(buf, b'I');
+}
+
+pub fn write_parse_complete(buf: &mut Vec<u8>) {
+    write_empty_message(buf, b'1');
+}
+
+pub fn write_bind_complete(buf: &mut Vec<u8>) {
+    write_empty_message(buf, b'2');
+}
+
+pub fn write_close_complete(buf: &mut Vec<u8>) {
+    write_empty_message(buf, b'3');
+}
+
+pub fn write_no_data(buf: &mut Vec<u8>) {
+    write_empty_message(buf, b'n');
+}
+
+pub fn write_portal_suspended(buf: &mut Vec<u8>) {
+    write_empty_message(buf, b's');
+}
@@ -0,0 +1,105 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use tokio_rustls::rustls::server::AllowAnyAuthenticatedClient;
+use tokio_rustls::rustls::Certificate;
+use tokio_rustls::rustls::PrivateKey;
+use tokio_rustls::rustls::RootCertStore;
+use tokio_rustls::rustls::ServerConfig;
+
+/// Build the TLS config of the handler of a wire protocol, None if the cert or the key is not
+/// configured, the connections are in plain text then.
+///
+/// The clients must present a certificate signed by the `root_ca_path` if it is configured.
+pub fn build_tls_server_config(
+    handler: &str,
+    cert_path: &str,
+    key_path: &str,
+    root_ca_path: &str,
+) -> Result<Option<Arc<ServerConfig>>> {
+    if cert_path.is_empty() || key_path.is_empty() {
+        return Ok(None);
+    }
+
+    let certs = load_certs(cert_path)?;
+    let key = load_private_key(key_path)?;
+    let builder = ServerConfig::builder().with_safe_defaults();
+    let builder = if root_ca_path.is_empty() {
+        builder.with_no_client_auth()
+    } else {
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs(root_ca_path)? {
+            roots.add(&cert).map_err(|cause| {
+                ErrorCode::TLSConfigurationFailure(format!(
+                    "Cannot load {}: {}",
+                    root_ca_path, cause
+                ))
+            })?;
+        }
+        builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots))
+    };
+
+    let server_config = builder.with_single_cert(certs, key).map_err(|cause| {
+        ErrorCode::TLSConfigurationFailure(format!(
+            "Cannot build TLS config of {} handler: {}",
+            handler, cause
+        ))
+    })?;
+    Ok(Some(Arc::new(server_config)))
+}
+
+fn tls_error(path: &str, cause: std::io::Error) -> ErrorCode {
+    ErrorCode::TLSConfigurationFailure(format!("Cannot load {}: {}", path, cause))
+}
+
+fn load_certs(path: &str) -> Result<Vec<Certificate>> {
+    let file = File::open(path).map_err(|e| tls_error(path, e))?;
+    let mut reader = BufReader::new(file);
+    let certs = rustls_pemfile::certs(&mut reader)
+        .map_err(|e| tls_error(path, e))?
+        .into_iter()
+        .map(Certificate)
+        .collect::<Vec<_>>();
+    match certs.is_empty() {
+        true => Err(ErrorCode::TLSConfigurationFailure(format!(
+            "No certificate found in {}",
+            path
+        ))),
+        false => Ok(certs),
+    }
+}
+
+fn load_private_key(path: &str) -> Result<PrivateKey> {
+    let file = File::open(path).map_err(|e| tls_error(path, e))?;
+    let mut reader = BufReader::new(file);
+    loop {
+        match rustls_pemfile::read_one(&mut reader).map_err(|e| tls_error(path, e))? {
+            Some(rustls_pemfile::Item::RSAKey(key))
+            | Some(rustls_pemfile::Item::PKCS8Key(key))
+            | Some(rustls_pemfile::Item::ECKey(key)) => return Ok(PrivateKey(key)),
+            Some(_) => continue,
+            None => {
+                return Err(ErrorCode::TLSConfigurationFailure(format!(
+                    "No private key found in {}",
+                    path
+                )));
+            }
+        }
+    }
+}
@@ -14,3 +14,9 @@
 
 pub static METRIC_SESSION_CONNECT_NUMBERS: &str = "session.connect_numbers";
 pub static METRIC_SESSION_CLOSE_NUMBERS: &str = "session.close_numbers";
+
+pub static METRIC_QUERY_QUEUE_RUNNING: &str = "query_queue.running";
+pub static METRIC_QUERY_QUEUE_QUEUED: &str = "query_queue.queued";
+pub static METRIC_QUERY_QUEUE_WAIT_MILLISECONDS: &str = "query_queue.wait_milliseconds";
+pub static METRIC_QUERY_QUEUE_FULL: &str = "query_queue.full_numbers";
+pub static METRIC_QUERY_QUEUE_TIMEOUT: &str = "query_queue.timeout_numbers";
@@ -16,6 +16,8 @@ mod metrics;
 mod query_affect;
 pub mod query_ctx;
 mod query_ctx_shared;
+mod query_queue;
+mod query_stage;
 mod session;
 mod session_ctx;
 mod session_info;
@@ -29,6 +31,10 @@ pub use common_catalog::table_context::TableContext;
 pub use query_affect::QueryAffect;
 pub use query_ctx::QueryContext;
 pub use query_ctx_shared::QueryContextShared;
+pub use query_queue::QueryPermit;
+pub use query_queue::QueryQueue;
+pub use query_queue::QueryQueueInfo;
+pub use query_stage::QueryStage;
 pub use session::Session;
 pub use session_ctx::SessionContext;
 pub use session_info::ProcessInfo;
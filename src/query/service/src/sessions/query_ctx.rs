@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::future::Future;
 use std::net::SocketAddr;
@@ -19,12 +20,17 @@ use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Weak;
+use std::time::Duration;
+use std::time::SystemTime;
 
 use chrono_tz::Tz;
 use common_base::base::tokio::task::JoinHandle;
 use common_base::base::Progress;
 use common_base::base::ProgressValues;
+use common_base::base::RuntimeTracker;
 use common_base::base::TrySpawn;
+use common_catalog::runtime_filter::RuntimeFilter;
+use common_catalog::txn::TxnManagerRef;
 use common_contexts::DalContext;
 use common_contexts::DalMetrics;
 use common_datablocks::DataBlock;
@@ -39,7 +45,9 @@ use common_legacy_planners::ReadDataSourcePlan;
 use common_legacy_planners::SourceInfo;
 use common_legacy_planners::StageTableInfo;
 use common_meta_app::schema::TableInfo;
+use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
+use common_meta_types::WorkloadGroup;
 use opendal::Operator;
 use parking_lot::RwLock;
 use tracing::debug;
@@ -53,6 +61,9 @@ use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::query_affect::QueryAffect;
 use crate::sessions::ProcessInfo;
 use crate::sessions::QueryContextShared;
+use crate::sessions::QueryPermit;
+use crate::sessions::QueryQueueInfo;
+use crate::sessions::QueryStage;
 use crate::sessions::Session;
 use crate::sessions::SessionManager;
 use crate::sessions::Settings;
@@ -65,25 +76,44 @@ use crate::Config;
 pub struct QueryContext {
     version: String,
     partition_queue: Arc<RwLock<VecDeque<PartInfoPtr>>>,
+    runtime_filters: Arc<RwLock<Vec<Arc<RuntimeFilter>>>>,
     shared: Arc<QueryContextShared>,
     precommit_blocks: Arc<RwLock<Vec<DataBlock>>>,
     fragment_id: Arc<AtomicUsize>,
+    // The rows produced by the last iteration of each recursive CTE, by the name of the CTE
+    recursive_cte_tables: Arc<RwLock<HashMap<String, Vec<DataBlock>>>>,
 }
 
 impl QueryContext {
     pub fn create_from(other: Arc<QueryContext>) -> Arc<QueryContext> {
-        QueryContext::create_from_shared(other.shared.clone())
+        QueryContext::create(other.shared.clone(), other.recursive_cte_tables.clone())
     }
 
     pub fn create_from_shared(shared: Arc<QueryContextShared>) -> Arc<QueryContext> {
+        QueryContext::create(shared, Arc::new(RwLock::new(HashMap::new())))
+    }
+
+    /// Create a context whose working tables of recursive CTEs are copied from `other`, so the
+    /// iterations of a recursive CTE don't interfere with the other evaluations of it.
+    pub fn create_recursive_cte_scope(other: Arc<QueryContext>) -> Arc<QueryContext> {
+        let tables = other.recursive_cte_tables.read().clone();
+        QueryContext::create(other.shared.clone(), Arc::new(RwLock::new(tables)))
+    }
+
+    fn create(
+        shared: Arc<QueryContextShared>,
+        recursive_cte_tables: Arc<RwLock<HashMap<String, Vec<DataBlock>>>>,
+    ) -> Arc<QueryContext> {
         debug!("Create QueryContext");
 
         Arc::new(QueryContext {
             partition_queue: Arc::new(RwLock::new(VecDeque::new())),
+            runtime_filters: Arc::new(RwLock::new(Vec::new())),
             version: format!("DatabendQuery {}", *crate::version::DATABEND_COMMIT_VERSION),
             shared,
             precommit_blocks: Arc::new(RwLock::new(Vec::new())),
             fragment_id: Arc::new(AtomicUsize::new(0)),
+            recursive_cte_tables,
         })
     }
 
@@ -161,6 +191,10 @@ impl QueryContext {
         SessionManager::instance().get_session_by_id(id)
     }
 
+    pub fn get_session_by_query_id(self: &Arc<Self>, query_id: &str) -> Option<Arc<Session>> {
+        SessionManager::instance().get_session_by_query_id(query_id)
+    }
+
     // Get session id by mysql connection id.
     pub fn get_id_by_mysql_conn_id(self: &Arc<Self>, conn_id: &Option<u32>) -> Option<String> {
         SessionManager::instance().get_id_by_mysql_conn_id(conn_id)
@@ -171,6 +205,26 @@ impl QueryContext {
         SessionManager::instance().processes_info()
     }
 
+    /// The rows and bytes estimated to scan, compared with the scan progress to tell the
+    /// progress of the query.
+    pub fn get_total_scan_progress(&self) -> Arc<Progress> {
+        self.shared.total_scan_progress.clone()
+    }
+
+    /// Attaches the summary of the plan executed, logged if the query is slow.
+    pub fn attach_plan_summary(&self, summary: String) {
+        self.shared.attach_plan_summary(summary);
+    }
+
+    pub fn get_plan_summary(&self) -> String {
+        self.shared.get_plan_summary()
+    }
+
+    /// The time the query is created, the durations of the queries are measured from it.
+    pub fn get_created_time(&self) -> SystemTime {
+        self.shared.created_time
+    }
+
     /// Get the client socket address.
     pub fn get_client_address(&self) -> Option<SocketAddr> {
         self.shared.session.session_ctx.get_client_host()
@@ -187,6 +241,61 @@ impl QueryContext {
     pub fn set_executor(&self, weak_ptr: Weak<PipelineExecutor>) {
         self.shared.set_executor(weak_ptr)
     }
+
+    pub fn set_stage(&self, stage: QueryStage) {
+        self.shared.set_stage(stage)
+    }
+
+    pub fn get_stage_durations(&self) -> Vec<(QueryStage, Duration)> {
+        self.shared.get_stage_durations()
+    }
+
+    /// Leave the node out of the cluster the rest of the query is scheduled on, e.g. it is lost.
+    pub fn exclude_cluster_node(&self, node_id: &str) {
+        self.shared.exclude_cluster_node(node_id)
+    }
+
+    pub fn has_query_permit(&self) -> bool {
+        self.shared.has_query_permit()
+    }
+
+    pub fn set_query_permit(&self, permit: QueryPermit) {
+        self.shared.set_query_permit(permit)
+    }
+
+    pub fn take_query_permit(&self) -> Option<QueryPermit> {
+        self.shared.take_query_permit()
+    }
+
+    pub fn get_workload_group(&self) -> Option<WorkloadGroup> {
+        self.shared.get_workload_group()
+    }
+
+    pub fn set_workload_group(&self, group: WorkloadGroup) {
+        self.shared.set_workload_group(group)
+    }
+
+    pub fn set_recursive_cte_table(&self, cte_name: &str, blocks: Vec<DataBlock>) {
+        let mut tables = self.recursive_cte_tables.write();
+        tables.insert(cte_name.to_string(), blocks);
+    }
+
+    pub fn get_recursive_cte_table(&self, cte_name: &str) -> Vec<DataBlock> {
+        let tables = self.recursive_cte_tables.read();
+        tables.get(cte_name).cloned().unwrap_or_default()
+    }
+
+    pub fn get_runtime_tracker(&self) -> Arc<RuntimeTracker> {
+        self.shared.get_runtime_tracker()
+    }
+
+    pub fn get_memory_usage(&self) -> i64 {
+        self.shared.get_memory_usage()
+    }
+
+    pub fn get_peak_memory_usage(&self) -> i64 {
+        self.shared.get_peak_memory_usage()
+    }
 }
 
 #[async_trait::async_trait]
@@ -247,6 +356,12 @@ impl TableContext for QueryContext {
         }
         Ok(())
     }
+    fn set_runtime_filter(&self, filter: Arc<RuntimeFilter>) {
+        self.runtime_filters.write().push(filter);
+    }
+    fn get_runtime_filters(&self) -> Vec<Arc<RuntimeFilter>> {
+        self.runtime_filters.read().clone()
+    }
     fn attach_query_str(&self, kind: String, query: &str) {
         self.shared.attach_query_str(kind, query);
     }
@@ -263,6 +378,9 @@ impl TableContext for QueryContext {
     fn get_id(&self) -> String {
         self.shared.init_query_id.as_ref().read().clone()
     }
+    fn check_aborting(&self) -> Result<()> {
+        self.shared.check_aborting()
+    }
     fn get_current_catalog(&self) -> String {
         self.shared.get_current_catalog()
     }
@@ -361,10 +479,30 @@ impl TableContext for QueryContext {
         self.shared.get_table(catalog, database, table).await
     }
 
+    fn get_txn_manager(&self) -> TxnManagerRef {
+        self.shared.session.get_txn_manager()
+    }
+
+    fn add_consumed_stream(&self, stream: TableInfo) {
+        self.shared.add_consumed_stream(stream)
+    }
+
+    fn get_consumed_streams(&self) -> Vec<TableInfo> {
+        self.shared.get_consumed_streams()
+    }
+
+    async fn get_related_roles(&self) -> Result<Vec<RoleInfo>> {
+        self.shared.get_related_roles().await
+    }
+
     // Get all the processes list info.
     fn get_processes_info(&self) -> Vec<ProcessInfo> {
         SessionManager::instance().processes_info()
     }
+
+    fn get_query_queue_info(&self) -> Vec<QueryQueueInfo> {
+        SessionManager::instance().get_query_queue().queue_info()
+    }
 }
 
 impl TrySpawn for QueryContext {
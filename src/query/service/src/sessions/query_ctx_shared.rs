@@ -13,16 +13,25 @@
 // limitations under the License.
 
 use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
 
 use common_base::base::Progress;
 use common_base::base::Runtime;
+use common_base::base::RuntimeTracker;
 use common_contexts::DalContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_app::schema::TableInfo;
+use common_meta_types::MetaId;
+use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
+use common_meta_types::WorkloadGroup;
 use common_storage::StorageOperator;
 use opendal::Operator;
 use parking_lot::Mutex;
@@ -36,6 +45,8 @@ use crate::clusters::Cluster;
 use crate::pipelines::executor::PipelineExecutor;
 use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::query_affect::QueryAffect;
+use crate::sessions::query_queue::QueryPermit;
+use crate::sessions::query_stage::QueryStage;
 use crate::sessions::Session;
 use crate::sessions::Settings;
 use crate::sql::SQLCommon;
@@ -57,6 +68,9 @@ pub struct QueryContextShared {
     pub(in crate::sessions) config: Config,
     /// scan_progress for scan metrics of datablocks (uncompressed)
     pub(in crate::sessions) scan_progress: Arc<Progress>,
+    /// total_scan_progress for the rows and bytes estimated to scan, by the statistics of the
+    /// partitions of the tables read
+    pub(in crate::sessions) total_scan_progress: Arc<Progress>,
     /// write_progress for write/commit metrics of datablocks (uncompressed)
     pub(in crate::sessions) write_progress: Arc<Progress>,
     /// result_progress for metrics of result datablocks (uncompressed)
@@ -64,10 +78,14 @@ pub struct QueryContextShared {
     pub(in crate::sessions) error: Arc<Mutex<Option<ErrorCode>>>,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
+    /// runtime_tracker for the memory of the query runtime and the pipeline executor threads
+    pub(in crate::sessions) runtime_tracker: Arc<RuntimeTracker>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
-    pub(in crate::sessions) cluster_cache: Arc<Cluster>,
+    /// The nodes the query is scheduled on, the lost ones are left out
+    pub(in crate::sessions) cluster_cache: Arc<RwLock<Arc<Cluster>>>,
     pub(in crate::sessions) running_query: Arc<RwLock<Option<String>>>,
     pub(in crate::sessions) running_query_kind: Arc<RwLock<Option<String>>>,
+    pub(in crate::sessions) running_plan_summary: Arc<RwLock<Option<String>>>,
     pub(in crate::sessions) http_query: Arc<RwLock<Option<HttpQueryHandle>>>,
     pub(in crate::sessions) tables_refs: Arc<Mutex<HashMap<DatabaseAndTable, Arc<dyn Table>>>>,
     pub(in crate::sessions) dal_ctx: Arc<DalContext>,
@@ -76,6 +94,13 @@ pub struct QueryContextShared {
     pub(in crate::sessions) catalog_manager: Arc<CatalogManager>,
     pub(in crate::sessions) storage_operator: Operator,
     pub(in crate::sessions) executor: Arc<RwLock<Weak<PipelineExecutor>>>,
+    /// The stages the query went through, with the instants they started at
+    pub(in crate::sessions) stages: Arc<RwLock<Vec<(QueryStage, Instant)>>>,
+    pub(in crate::sessions) query_permit: Arc<Mutex<Option<QueryPermit>>>,
+    pub(in crate::sessions) workload_group: Arc<RwLock<Option<WorkloadGroup>>>,
+    /// The streams read by the query, with the offsets they are advanced to, by table id
+    pub(in crate::sessions) consumed_streams: Arc<RwLock<BTreeMap<MetaId, TableInfo>>>,
+    pub(in crate::sessions) created_time: SystemTime,
 }
 
 impl QueryContextShared {
@@ -84,26 +109,35 @@ impl QueryContextShared {
         session: Arc<Session>,
         cluster_cache: Arc<Cluster>,
     ) -> Result<Arc<QueryContextShared>> {
+        let max_memory_usage = session.get_settings().get_max_query_memory_usage()?;
         Ok(Arc::new(QueryContextShared {
             session,
-            cluster_cache,
+            cluster_cache: Arc::new(RwLock::new(cluster_cache)),
             config: config.clone(),
             catalog_manager: CatalogManager::instance(),
             storage_operator: StorageOperator::instance(),
             init_query_id: Arc::new(RwLock::new(Uuid::new_v4().to_string())),
             scan_progress: Arc::new(Progress::create()),
+            total_scan_progress: Arc::new(Progress::create()),
             result_progress: Arc::new(Progress::create()),
             write_progress: Arc::new(Progress::create()),
             error: Arc::new(Mutex::new(None)),
             runtime: Arc::new(RwLock::new(None)),
+            runtime_tracker: RuntimeTracker::create_with_limit(max_memory_usage as i64),
             running_query: Arc::new(RwLock::new(None)),
             running_query_kind: Arc::new(RwLock::new(None)),
+            running_plan_summary: Arc::new(RwLock::new(None)),
             http_query: Arc::new(RwLock::new(None)),
             tables_refs: Arc::new(Mutex::new(HashMap::new())),
             dal_ctx: Arc::new(Default::default()),
             auth_manager: AuthMgr::create(config).await?,
             affect: Arc::new(Mutex::new(None)),
             executor: Arc::new(RwLock::new(Weak::new())),
+            stages: Arc::new(RwLock::new(vec![(QueryStage::Planning, Instant::now())])),
+            query_permit: Arc::new(Mutex::new(None)),
+            workload_group: Arc::new(RwLock::new(None)),
+            consumed_streams: Arc::new(RwLock::new(BTreeMap::new())),
+            created_time: SystemTime::now(),
         }))
     }
 
@@ -122,8 +156,30 @@ impl QueryContextShared {
         // TODO: Wait for the query to be processed (write out the last error)
     }
 
+    pub fn check_aborting(&self) -> Result<()> {
+        match self.error.lock().as_ref() {
+            Some(cause) => Err(cause.clone()),
+            None => Ok(()),
+        }
+    }
+
     pub fn get_cluster(&self) -> Arc<Cluster> {
-        self.cluster_cache.clone()
+        self.cluster_cache.read().clone()
+    }
+
+    pub fn exclude_cluster_node(&self, node_id: &str) {
+        let mut cluster = self.cluster_cache.write();
+        let nodes = cluster
+            .nodes
+            .iter()
+            .filter(|node| node.id != node_id)
+            .cloned()
+            .collect();
+
+        *cluster = Arc::new(Cluster {
+            local_id: cluster.local_id.clone(),
+            nodes,
+        });
     }
 
     pub fn get_current_catalog(&self) -> String {
@@ -146,6 +202,10 @@ impl QueryContextShared {
         self.session.set_current_user(user);
     }
 
+    pub async fn get_related_roles(&self) -> Result<Vec<RoleInfo>> {
+        self.session.get_related_roles().await
+    }
+
     pub fn set_current_tenant(&self, tenant: String) {
         self.session.set_current_tenant(tenant);
     }
@@ -202,6 +262,16 @@ impl QueryContextShared {
         let table_meta_key = (catalog.to_string(), database.to_string(), table.to_string());
         let catalog = self.catalog_manager.get_catalog(catalog)?;
         let cache_table = catalog.get_table(tenant.as_str(), database, table).await?;
+        // the tables changed by the transaction of the session are read with the changes
+        let staged = self
+            .session
+            .get_txn_manager()
+            .lock()
+            .get_staged_table(cache_table.get_id());
+        let cache_table = match staged {
+            Some(table_info) => catalog.get_table_by_info(&table_info)?,
+            None => cache_table,
+        };
 
         let mut tables_refs = self.tables_refs.lock();
 
@@ -219,7 +289,8 @@ impl QueryContextShared {
             Some(query_runtime) => Ok(query_runtime.clone()),
             None => {
                 // To avoid possible deadlock, we should keep at least two threads.
-                let runtime = Arc::new(Runtime::with_worker_threads(
+                let runtime = Arc::new(Runtime::with_tracker_and_worker_threads(
+                    self.runtime_tracker.clone(),
                     2,
                     Some("query-ctx".to_string()),
                 )?);
@@ -254,6 +325,16 @@ impl QueryContextShared {
         running_query.as_ref().unwrap_or(&"".to_string()).clone()
     }
 
+    pub fn attach_plan_summary(&self, summary: String) {
+        let mut running_plan_summary = self.running_plan_summary.write();
+        *running_plan_summary = Some(summary);
+    }
+
+    pub fn get_plan_summary(&self) -> String {
+        let running_plan_summary = self.running_plan_summary.read();
+        running_plan_summary.clone().unwrap_or_default()
+    }
+
     pub fn get_query_kind(&self) -> String {
         let running_query_kind = self.running_query_kind.read();
         running_query_kind
@@ -283,5 +364,79 @@ impl QueryContextShared {
     pub fn set_executor(&self, weak_ptr: Weak<PipelineExecutor>) {
         let mut executor = self.executor.write();
         *executor = weak_ptr;
+        self.set_stage(QueryStage::Executing);
+    }
+
+    pub fn get_stage(&self) -> QueryStage {
+        match self.stages.read().last() {
+            Some((stage, _)) => *stage,
+            None => QueryStage::Planning,
+        }
+    }
+
+    pub fn set_stage(&self, stage: QueryStage) {
+        let mut stages = self.stages.write();
+        if !matches!(stages.last(), Some((last, _)) if *last == stage) {
+            stages.push((stage, Instant::now()));
+        }
+    }
+
+    /// The time spent in each stage the query went through, the last one is still going on.
+    pub fn get_stage_durations(&self) -> Vec<(QueryStage, Duration)> {
+        let stages = self.stages.read();
+        let now = Instant::now();
+        stages
+            .iter()
+            .enumerate()
+            .map(|(i, (stage, start))| {
+                let end = stages.get(i + 1).map(|(_, end)| *end).unwrap_or(now);
+                (*stage, end - *start)
+            })
+            .collect()
+    }
+
+    pub fn has_query_permit(&self) -> bool {
+        self.query_permit.lock().is_some()
+    }
+
+    pub fn set_query_permit(&self, permit: QueryPermit) {
+        let mut query_permit = self.query_permit.lock();
+        *query_permit = Some(permit);
+    }
+
+    pub fn take_query_permit(&self) -> Option<QueryPermit> {
+        self.query_permit.lock().take()
+    }
+
+    pub fn get_workload_group(&self) -> Option<WorkloadGroup> {
+        self.workload_group.read().clone()
+    }
+
+    pub fn set_workload_group(&self, group: WorkloadGroup) {
+        let mut workload_group = self.workload_group.write();
+        *workload_group = Some(group);
+    }
+
+    pub fn add_consumed_stream(&self, stream: TableInfo) {
+        let mut consumed_streams = self.consumed_streams.write();
+        consumed_streams.insert(stream.ident.table_id, stream);
+    }
+
+    pub fn get_consumed_streams(&self) -> Vec<TableInfo> {
+        self.consumed_streams.read().values().cloned().collect()
+    }
+
+    pub fn get_runtime_tracker(&self) -> Arc<RuntimeTracker> {
+        self.runtime_tracker.clone()
+    }
+
+    pub fn get_memory_usage(&self) -> i64 {
+        self.runtime_tracker.get_memory_tracker().get_memory_usage()
+    }
+
+    pub fn get_peak_memory_usage(&self) -> i64 {
+        self.runtime_tracker
+            .get_memory_tracker()
+            .get_peak_memory_usage()
     }
 }
@@ -0,0 +1,373 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_base::base::tokio;
+use common_base::base::tokio::sync::Notify;
+pub use common_catalog::table_context::QueryQueueInfo;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::WorkloadGroup;
+use common_metrics::label_counter;
+use common_metrics::label_decrement_gauge_with_val_and_labels;
+use common_metrics::label_histogram_with_val;
+use common_metrics::label_increment_gauge_with_val_and_labels;
+use common_metrics::LABEL_KEY_CLUSTER;
+use common_metrics::LABEL_KEY_TENANT;
+use parking_lot::Mutex;
+
+use crate::sessions::metrics::METRIC_QUERY_QUEUE_FULL;
+use crate::sessions::metrics::METRIC_QUERY_QUEUE_QUEUED;
+use crate::sessions::metrics::METRIC_QUERY_QUEUE_RUNNING;
+use crate::sessions::metrics::METRIC_QUERY_QUEUE_TIMEOUT;
+use crate::sessions::metrics::METRIC_QUERY_QUEUE_WAIT_MILLISECONDS;
+use crate::Config;
+
+struct QueryEntry {
+    user: String,
+    /// The workload group of the query and its `max_concurrency`, if it's limited.
+    group: Option<(String, usize)>,
+    query: String,
+    queued_at: Instant,
+    wait_time: Duration,
+}
+
+#[derive(Default)]
+struct QueryQueueState {
+    running: HashMap<String, QueryEntry>,
+    running_per_user: HashMap<String, usize>,
+    running_per_group: HashMap<String, usize>,
+    queued: VecDeque<(String, QueryEntry)>,
+}
+
+/// The admission queue of the queries.
+///
+/// A query runs at once if the running queries are under `max_running_queries`, the running
+/// queries of its user are under `max_running_queries_per_user` and the running queries of its
+/// workload group are under the `max_concurrency` of the group, or waits in the queue in FIFO
+/// order. The queries over `max_queued_queries` are rejected, and the queries waiting longer than
+/// `query_queue_timeout` milliseconds are timed out, instead of slowing down all the others.
+pub struct QueryQueue {
+    max_running: usize,
+    max_running_per_user: usize,
+    max_queued: usize,
+    timeout: Option<Duration>,
+    tenant_id: String,
+    cluster_id: String,
+    state: Mutex<QueryQueueState>,
+    notify: Notify,
+}
+
+impl QueryQueue {
+    pub fn create(conf: &Config) -> Arc<QueryQueue> {
+        let timeout = match conf.query.query_queue_timeout {
+            0 => None,
+            millis => Some(Duration::from_millis(millis)),
+        };
+
+        Arc::new(QueryQueue {
+            max_running: conf.query.max_running_queries as usize,
+            max_running_per_user: conf.query.max_running_queries_per_user as usize,
+            max_queued: conf.query.max_queued_queries as usize,
+            timeout,
+            tenant_id: conf.query.tenant_id.clone(),
+            cluster_id: conf.query.cluster_id.clone(),
+            state: Mutex::new(QueryQueueState::default()),
+            notify: Notify::new(),
+        })
+    }
+
+    /// Wait until the query is admitted, the query runs until the permit is dropped.
+    pub async fn acquire(
+        self: &Arc<Self>,
+        query_id: &str,
+        user: &str,
+        group: Option<&WorkloadGroup>,
+        query: &str,
+    ) -> Result<QueryPermit> {
+        let group = group
+            .filter(|group| group.max_concurrency != 0)
+            .map(|group| (group.name.clone(), group.max_concurrency as usize));
+        let entry = QueryEntry {
+            user: user.to_string(),
+            group,
+            query: query.to_string(),
+            queued_at: Instant::now(),
+            wait_time: Duration::ZERO,
+        };
+
+        {
+            let mut state = self.state.lock();
+            if state.queued.is_empty() && self.can_run(&state, &entry, &Ahead::default()) {
+                self.start_running(&mut state, query_id.to_string(), entry);
+                return Ok(self.permit(query_id));
+            }
+
+            if self.max_queued != 0 && state.queued.len() >= self.max_queued {
+                label_counter(METRIC_QUERY_QUEUE_FULL, &self.tenant_id, &self.cluster_id);
+                return Err(ErrorCode::QueryQueueFull(format!(
+                    "The query queue is full, {} queries are waiting",
+                    state.queued.len()
+                )));
+            }
+
+            state.queued.push_back((query_id.to_string(), entry));
+            label_increment_gauge_with_val_and_labels(
+                METRIC_QUERY_QUEUE_QUEUED,
+                self.labels(),
+                1.0,
+            );
+        }
+
+        // Removes the query from the queue if the waiting is timed out or cancelled.
+        let _guard = QueuedGuard {
+            queue: self.clone(),
+            query_id: query_id.to_string(),
+        };
+
+        let wait = async {
+            loop {
+                let notified = self.notify.notified();
+                if self.try_admit(query_id) {
+                    return;
+                }
+                notified.await;
+            }
+        };
+
+        match self.timeout {
+            None => wait.await,
+            Some(timeout) => {
+                if tokio::time::timeout(timeout, wait).await.is_err() {
+                    label_counter(
+                        METRIC_QUERY_QUEUE_TIMEOUT,
+                        &self.tenant_id,
+                        &self.cluster_id,
+                    );
+                    return Err(ErrorCode::QueryQueueTimeout(format!(
+                        "The query {} waited in the query queue for more than {} milliseconds",
+                        query_id,
+                        timeout.as_millis()
+                    )));
+                }
+            }
+        }
+
+        Ok(self.permit(query_id))
+    }
+
+    pub fn queue_info(&self) -> Vec<QueryQueueInfo> {
+        let state = self.state.lock();
+        let running = state.running.iter().map(|(id, entry)| QueryQueueInfo {
+            query_id: id.clone(),
+            user: entry.user.clone(),
+            state: "Running".to_string(),
+            query: entry.query.clone(),
+            wait_time_ms: entry.wait_time.as_millis() as u64,
+        });
+        let queued = state.queued.iter().map(|(id, entry)| QueryQueueInfo {
+            query_id: id.clone(),
+            user: entry.user.clone(),
+            state: "Queued".to_string(),
+            query: entry.query.clone(),
+            wait_time_ms: entry.queued_at.elapsed().as_millis() as u64,
+        });
+        running.chain(queued).collect()
+    }
+
+    fn permit(self: &Arc<Self>, query_id: &str) -> QueryPermit {
+        QueryPermit {
+            queue: self.clone(),
+            query_id: query_id.to_string(),
+        }
+    }
+
+    fn labels(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (LABEL_KEY_TENANT, self.tenant_id.clone()),
+            (LABEL_KEY_CLUSTER, self.cluster_id.clone()),
+        ]
+    }
+
+    /// Whether the query could run, with the queries admitted ahead of it in this round.
+    fn can_run(&self, state: &QueryQueueState, entry: &QueryEntry, ahead: &Ahead) -> bool {
+        if self.max_running != 0 && state.running.len() + ahead.total >= self.max_running {
+            return false;
+        }
+
+        if self.max_running_per_user != 0 {
+            let user = entry.user.as_str();
+            let running = state.running_per_user.get(user).cloned().unwrap_or(0)
+                + ahead.per_user.get(user).cloned().unwrap_or(0);
+            if running >= self.max_running_per_user {
+                return false;
+            }
+        }
+
+        if let Some((group, max_concurrency)) = &entry.group {
+            let running = state.running_per_group.get(group).cloned().unwrap_or(0)
+                + ahead.per_group.get(group.as_str()).cloned().unwrap_or(0);
+            if running >= *max_concurrency {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Admit the query if it could run in the queue, the queries before it which could run are
+    /// also notified and admitted by themselves.
+    fn try_admit(&self, query_id: &str) -> bool {
+        let mut state = self.state.lock();
+        let admitted = self.admitted_position(&state, query_id);
+
+        match admitted.and_then(|index| state.queued.remove(index)) {
+            None => false,
+            Some((id, mut entry)) => {
+                label_decrement_gauge_with_val_and_labels(
+                    METRIC_QUERY_QUEUE_QUEUED,
+                    self.labels(),
+                    1.0,
+                );
+                entry.wait_time = entry.queued_at.elapsed();
+                self.start_running(&mut state, id, entry);
+                true
+            }
+        }
+    }
+
+    fn admitted_position(&self, state: &QueryQueueState, query_id: &str) -> Option<usize> {
+        let mut ahead = Ahead::default();
+
+        for (index, (id, entry)) in state.queued.iter().enumerate() {
+            if !self.can_run(state, entry, &ahead) {
+                continue;
+            }
+
+            if id == query_id {
+                return Some(index);
+            }
+
+            ahead.total += 1;
+            *ahead.per_user.entry(entry.user.as_str()).or_insert(0) += 1;
+            if let Some((group, _)) = &entry.group {
+                *ahead.per_group.entry(group.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        None
+    }
+
+    fn start_running(&self, state: &mut QueryQueueState, query_id: String, entry: QueryEntry) {
+        label_histogram_with_val(
+            METRIC_QUERY_QUEUE_WAIT_MILLISECONDS,
+            self.labels(),
+            entry.wait_time.as_millis() as f64,
+        );
+        label_increment_gauge_with_val_and_labels(METRIC_QUERY_QUEUE_RUNNING, self.labels(), 1.0);
+
+        *state
+            .running_per_user
+            .entry(entry.user.clone())
+            .or_insert(0) += 1;
+        if let Some((group, _)) = &entry.group {
+            *state.running_per_group.entry(group.clone()).or_insert(0) += 1;
+        }
+        state.running.insert(query_id, entry);
+    }
+
+    fn release(&self, query_id: &str) {
+        {
+            let mut state = self.state.lock();
+            if let Some(entry) = state.running.remove(query_id) {
+                label_decrement_gauge_with_val_and_labels(
+                    METRIC_QUERY_QUEUE_RUNNING,
+                    self.labels(),
+                    1.0,
+                );
+                decrease_running(&mut state.running_per_user, &entry.user);
+                if let Some((group, _)) = &entry.group {
+                    decrease_running(&mut state.running_per_group, group);
+                }
+            }
+        }
+
+        self.notify.notify_waiters();
+    }
+
+    fn dequeue(&self, query_id: &str) {
+        {
+            let mut state = self.state.lock();
+            match state.queued.iter().position(|(id, _)| id == query_id) {
+                None => return,
+                Some(index) => {
+                    state.queued.remove(index);
+                    label_decrement_gauge_with_val_and_labels(
+                        METRIC_QUERY_QUEUE_QUEUED,
+                        self.labels(),
+                        1.0,
+                    );
+                }
+            }
+        }
+
+        // The queries behind it may be able to run now.
+        self.notify.notify_waiters();
+    }
+}
+
+/// The queries admitted ahead of a queued query in a round of admission.
+#[derive(Default)]
+struct Ahead<'a> {
+    total: usize,
+    per_user: HashMap<&'a str, usize>,
+    per_group: HashMap<&'a str, usize>,
+}
+
+fn decrease_running(running: &mut HashMap<String, usize>, key: &str) {
+    if let Some(count) = running.get_mut(key) {
+        *count -= 1;
+        if *count == 0 {
+            running.remove(key);
+        }
+    }
+}
+
+/// The admission of a running query, the next queued query is admitted once it's dropped.
+pub struct QueryPermit {
+    queue: Arc<QueryQueue>,
+    query_id: String,
+}
+
+impl Drop for QueryPermit {
+    fn drop(&mut self) {
+        self.queue.release(&self.query_id);
+    }
+}
+
+struct QueuedGuard {
+    queue: Arc<QueryQueue>,
+    query_id: String,
+}
+
+impl Drop for QueuedGuard {
+    fn drop(&mut self) {
+        self.queue.dequeue(&self.query_id);
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+/// The stage a running query is in, shown by the processlist.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QueryStage {
+    /// Parsing, binding and optimizing the query.
+    Planning,
+    /// Waiting for the admission of the query queue.
+    Queued,
+    /// Building the pipeline of the query.
+    BuildingPipeline,
+    /// Running the pipeline, until the results are all consumed.
+    Executing,
+}
+
+impl Display for QueryStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryStage::Planning => write!(f, "Planning"),
+            QueryStage::Queued => write!(f, "Queued"),
+            QueryStage::BuildingPipeline => write!(f, "Building pipeline"),
+            QueryStage::Executing => write!(f, "Executing"),
+        }
+    }
+}
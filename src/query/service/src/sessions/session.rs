@@ -16,10 +16,12 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use chrono_tz::Tz;
+use common_catalog::txn::TxnManagerRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_io::prelude::FormatSettings;
 use common_meta_types::GrantObject;
+use common_meta_types::RoleInfo;
 use common_meta_types::UserInfo;
 use common_meta_types::UserPrivilegeType;
 use common_users::RoleCacheManager;
@@ -208,6 +210,15 @@ impl Session {
     }
 
     pub fn set_current_user(self: &Arc<Self>, user: UserInfo) {
+        // The settings set in the session take precedence over the default settings of the user.
+        let user_settings = user.option.default_settings();
+        if let Err(cause) = self.get_settings().apply_user_settings(user_settings) {
+            tracing::warn!(
+                "Cannot apply the settings of user {}, cause: {}",
+                user.name,
+                cause
+            );
+        }
         self.session_ctx.set_current_user(user);
     }
 
@@ -215,6 +226,14 @@ impl Session {
         self.session_ctx.set_auth_role(role)
     }
 
+    pub fn get_current_role(self: &Arc<Self>) -> Option<String> {
+        self.session_ctx.get_current_role()
+    }
+
+    pub fn set_current_role(self: &Arc<Self>, role: Option<String>) {
+        self.session_ctx.set_current_role(role)
+    }
+
     // returns all the roles the current session has, which includes the roles of
     // the current user and the roles granted on the authentication phase.
     pub fn get_all_roles(self: &Arc<Self>) -> Result<Vec<String>> {
@@ -227,6 +246,19 @@ impl Session {
         Ok(all_roles)
     }
 
+    // returns the roles in effect with the roles granted to them, the active roles are the current
+    // role if activated by SET ROLE, or all the roles the current session has.
+    pub async fn get_related_roles(self: &Arc<Self>) -> Result<Vec<RoleInfo>> {
+        let active_roles = match self.get_current_role() {
+            Some(current_role) => vec![current_role],
+            None => self.get_all_roles()?,
+        };
+        let tenant = self.get_current_tenant();
+        RoleCacheManager::instance()
+            .find_related_roles(&tenant, &active_roles)
+            .await
+    }
+
     pub async fn validate_privilege(
         self: &Arc<Self>,
         object: &GrantObject,
@@ -238,11 +270,8 @@ impl Session {
             return Ok(());
         }
 
-        // TODO: take current role instead of all roles
-        let all_roles = self.get_all_roles()?;
-        let tenant = self.get_current_tenant();
-        let role_verified = RoleCacheManager::instance()
-            .find_related_roles(&tenant, &all_roles)
+        let role_verified = self
+            .get_related_roles()
             .await?
             .iter()
             .any(|r| r.grants.verify_privilege(object, privilege));
@@ -262,6 +291,10 @@ impl Session {
         self.session_ctx.get_settings()
     }
 
+    pub fn get_txn_manager(self: &Arc<Self>) -> TxnManagerRef {
+        self.session_ctx.get_txn_manager()
+    }
+
     pub fn get_changed_settings(self: &Arc<Self>) -> Arc<Settings> {
         self.session_ctx.get_changed_settings()
     }
@@ -270,11 +303,6 @@ impl Session {
         self.session_ctx.apply_changed_settings(changed_settings)
     }
 
-    pub fn get_memory_usage(self: &Arc<Self>) -> usize {
-        // TODO(winter): use thread memory tracker
-        0
-    }
-
     pub fn get_config(&self) -> Config {
         SessionManager::instance().get_conf()
     }
@@ -18,6 +18,8 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Weak;
 
+use common_catalog::txn::TxnManager;
+use common_catalog::txn::TxnManagerRef;
 use common_exception::Result;
 use common_meta_types::UserInfo;
 use common_settings::Settings;
@@ -36,9 +38,11 @@ pub struct SessionContext {
     current_tenant: RwLock<String>,
     current_user: RwLock<Option<UserInfo>>,
     auth_role: RwLock<Option<String>>,
+    current_role: RwLock<Option<String>>,
     client_host: RwLock<Option<SocketAddr>>,
     io_shutdown_tx: RwLock<Option<Sender<Sender<()>>>>,
     query_context_shared: RwLock<Weak<QueryContextShared>>,
+    txn_manager: TxnManagerRef,
 }
 
 impl SessionContext {
@@ -49,12 +53,14 @@ impl SessionContext {
             abort: Default::default(),
             current_user: Default::default(),
             auth_role: Default::default(),
+            current_role: Default::default(),
             current_tenant: Default::default(),
             client_host: Default::default(),
             current_catalog: RwLock::new("default".to_string()),
             current_database: RwLock::new("default".to_string()),
             io_shutdown_tx: Default::default(),
             query_context_shared: Default::default(),
+            txn_manager: TxnManager::init(),
         }))
     }
 
@@ -80,6 +86,10 @@ impl SessionContext {
         self.settings.apply_changed_settings(changed_settings)
     }
 
+    pub fn get_txn_manager(&self) -> TxnManagerRef {
+        self.txn_manager.clone()
+    }
+
     // Get current catalog name.
     pub fn get_current_catalog(&self) -> String {
         let lock = self.current_catalog.read();
@@ -142,6 +152,18 @@ impl SessionContext {
         *lock = Some(role);
     }
 
+    // Get current role. Current role is the role activated by SET ROLE, None if all the roles
+    // granted are active.
+    pub fn get_current_role(&self) -> Option<String> {
+        let lock = self.current_role.read();
+        lock.clone()
+    }
+
+    pub fn set_current_role(&self, role: Option<String>) {
+        let mut lock = self.current_role.write();
+        *lock = role;
+    }
+
     pub fn get_client_host(&self) -> Option<SocketAddr> {
         let lock = self.client_host.read();
         *lock
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use common_base::base::ProgressValues;
 pub use common_catalog::table_context::ProcessInfo;
@@ -29,15 +30,10 @@ impl Session {
     }
 
     fn to_process_info(self: &Arc<Self>, status: &SessionContext) -> ProcessInfo {
-        let mut memory_usage = 0;
-
-        if let Some(shared) = status.get_query_context_shared() {
-            if let Ok(runtime) = shared.try_get_runtime() {
-                let runtime_tracker = runtime.get_tracker();
-                let runtime_memory_tracker = runtime_tracker.get_memory_tracker();
-                memory_usage = runtime_memory_tracker.get_memory_usage();
-            }
-        }
+        let memory_usage = match status.get_query_context_shared() {
+            None => 0,
+            Some(shared) => shared.get_memory_usage(),
+        };
 
         ProcessInfo {
             id: self.id.clone(),
@@ -51,7 +47,11 @@ impl Session {
             memory_usage,
             dal_metrics: Session::query_dal_metrics(status),
             scan_progress_value: Session::query_scan_progress_value(status),
+            total_scan_progress_value: Session::query_total_scan_progress_value(status),
             mysql_connection_id: self.mysql_connection_id,
+            query_id: Session::query_id(status),
+            query_duration_ms: Session::query_duration_ms(status),
+            query_stage: Session::query_stage(status),
         }
     }
 
@@ -96,4 +96,37 @@ impl Session {
             .as_ref()
             .map(|context_shared| context_shared.scan_progress.get_values())
     }
+
+    fn query_total_scan_progress_value(status: &SessionContext) -> Option<ProgressValues> {
+        status
+            .get_query_context_shared()
+            .as_ref()
+            .map(|context_shared| context_shared.total_scan_progress.get_values())
+    }
+
+    fn query_id(status: &SessionContext) -> Option<String> {
+        status
+            .get_query_context_shared()
+            .as_ref()
+            .map(|context_shared| context_shared.init_query_id.read().clone())
+    }
+
+    fn query_stage(status: &SessionContext) -> Option<String> {
+        status
+            .get_query_context_shared()
+            .as_ref()
+            .map(|context_shared| context_shared.get_stage().to_string())
+    }
+
+    fn query_duration_ms(status: &SessionContext) -> Option<u64> {
+        status
+            .get_query_context_shared()
+            .as_ref()
+            .map(|context_shared| {
+                SystemTime::now()
+                    .duration_since(context_shared.created_time)
+                    .unwrap_or_default()
+                    .as_millis() as u64
+            })
+    }
 }
@@ -20,6 +20,7 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Weak;
 use std::time::Duration;
+use std::time::SystemTime;
 
 use common_base::base::tokio;
 use common_base::base::SignalStream;
@@ -35,9 +36,11 @@ use once_cell::sync::OnceCell;
 use parking_lot::RwLock;
 use tracing::debug;
 use tracing::info;
+use tracing::warn;
 
 use crate::sessions::session::Session;
 use crate::sessions::ProcessInfo;
+use crate::sessions::QueryQueue;
 use crate::sessions::SessionContext;
 use crate::sessions::SessionManagerStatus;
 use crate::sessions::SessionType;
@@ -52,6 +55,7 @@ pub struct SessionManager {
     // When typ is MySQL, insert into this map, key is id, val is MySQL connection id.
     pub(crate) mysql_conn_map: Arc<RwLock<HashMap<Option<u32>, String>>>,
     pub(in crate::sessions) mysql_basic_conn_id: AtomicU32,
+    pub(in crate::sessions) query_queue: Arc<QueryQueue>,
 }
 
 static SESSION_MANAGER: OnceCell<Singleton<Arc<SessionManager>>> = OnceCell::new();
@@ -66,8 +70,10 @@ impl SessionManager {
 
     pub fn create(conf: Config) -> Arc<SessionManager> {
         let max_sessions = conf.query.max_active_sessions as usize;
+        let query_queue = QueryQueue::create(&conf);
         Arc::new(SessionManager {
             conf,
+            query_queue,
             max_sessions,
             mysql_basic_conn_id: AtomicU32::new(9_u32.to_le() as u32),
             status: Arc::new(RwLock::new(SessionManagerStatus::default())),
@@ -87,6 +93,10 @@ impl SessionManager {
         self.conf.clone()
     }
 
+    pub fn get_query_queue(&self) -> Arc<QueryQueue> {
+        self.query_queue.clone()
+    }
+
     pub async fn create_session(&self, typ: SessionType) -> Result<Arc<Session>> {
         // TODO: maybe deadlock
         let config = self.get_conf();
@@ -152,6 +162,15 @@ impl SessionManager {
         sessions.get(id).and_then(|weak_ptr| weak_ptr.upgrade())
     }
 
+    /// Get the session running the query `query_id`.
+    pub fn get_session_by_query_id(&self, query_id: &str) -> Option<Arc<Session>> {
+        let sessions = self.active_sessions.read();
+        sessions
+            .values()
+            .filter_map(|weak_ptr| weak_ptr.upgrade())
+            .find(|session| session.get_current_query_id().as_deref() == Some(query_id))
+    }
+
     pub fn get_id_by_mysql_conn_id(&self, mysql_conn_id: &Option<u32>) -> Option<String> {
         let sessions = self.mysql_conn_map.read();
         sessions.get(mysql_conn_id).cloned()
@@ -216,6 +235,50 @@ impl SessionManager {
         }
     }
 
+    /// Stop accepting new queries, the queries still running after `timeout` are killed. Returns
+    /// false if the node is already draining.
+    pub fn drain(&self, timeout: Duration) -> bool {
+        {
+            let mut status = self.status.write();
+            if status.drain_started_at.is_some() {
+                return false;
+            }
+
+            let now = SystemTime::now();
+            status.drain_started_at = Some(now);
+            status.drain_deadline = Some(now + timeout);
+        }
+
+        info!("Draining the node, deadline in {:?}.", timeout);
+        let active_sessions = self.active_sessions.clone();
+        let status = self.status.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+
+            let running_queries_count = status.read().running_queries_count;
+            if running_queries_count != 0 {
+                warn!(
+                    "Killing {} queries not finished by the drain deadline.",
+                    running_queries_count
+                );
+
+                for weak_ptr in active_sessions.read().values() {
+                    if let Some(active_session) = weak_ptr.upgrade() {
+                        active_session.force_kill_query(ErrorCode::AbortedQuery(
+                            "Aborted query, because it is not finished before the node is drained",
+                        ));
+                    }
+                }
+            }
+        });
+
+        true
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.status.read().drain_started_at.is_some()
+    }
+
     pub fn processes_info(&self) -> Vec<ProcessInfo> {
         let sessions = self.active_sessions.read();
 
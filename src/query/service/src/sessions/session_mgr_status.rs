@@ -20,6 +20,10 @@ pub struct SessionManagerStatus {
     pub last_query_started_at: Option<SystemTime>,
     pub last_query_finished_at: Option<SystemTime>,
     pub instance_started_at: SystemTime,
+    /// Set once the node is draining, the new queries are rejected from then on.
+    pub drain_started_at: Option<SystemTime>,
+    /// The running queries are killed if they are not finished by then.
+    pub drain_deadline: Option<SystemTime>,
 }
 
 impl SessionManagerStatus {
@@ -43,6 +47,8 @@ impl Default for SessionManagerStatus {
             last_query_started_at: None,
             last_query_finished_at: None,
             instance_started_at: SystemTime::now(),
+            drain_started_at: None,
+            drain_deadline: None,
         }
     }
 }
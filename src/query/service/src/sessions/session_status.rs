@@ -17,6 +17,8 @@ use std::time::Instant;
 pub struct SessionStatus {
     pub session_started_at: Instant,
     pub last_query_finished_at: Option<Instant>,
+    // Accessed without running a query, such as fetching the results of the last query.
+    pub last_accessed_at: Option<Instant>,
 }
 
 impl SessionStatus {
@@ -24,9 +26,18 @@ impl SessionStatus {
         self.last_query_finished_at = Some(Instant::now())
     }
 
+    pub(crate) fn access(&mut self) {
+        self.last_accessed_at = Some(Instant::now())
+    }
+
     pub(crate) fn last_access(&self) -> Instant {
-        self.last_query_finished_at
-            .unwrap_or(self.session_started_at)
+        let last_query_finished_at = self
+            .last_query_finished_at
+            .unwrap_or(self.session_started_at);
+        match self.last_accessed_at {
+            Some(last_accessed_at) => last_query_finished_at.max(last_accessed_at),
+            None => last_query_finished_at,
+        }
     }
 }
 
@@ -35,6 +46,7 @@ impl Default for SessionStatus {
         SessionStatus {
             session_started_at: Instant::now(),
             last_query_finished_at: None,
+            last_accessed_at: None,
         }
     }
 }
@@ -18,11 +18,22 @@ use std::fmt;
 pub enum SessionType {
     Clickhouse,
     MySQL,
+    Postgres,
     HTTPQuery,
     HTTPStreamingLoad,
     ClickHouseHttpHandler,
     FlightRPC,
     HTTPAPI(String),
+    // flushes the inserts buffered by the sessions in async insert mode
+    AsyncInsert,
+    // compacts the tables in background
+    AutoCompaction,
+    // runs the scheduled tasks
+    Task,
+    // builds the indexes of the existing data in background
+    IndexBuilder,
+    // persists the query logs
+    QueryLogPersister,
     Dummy,
     Fuzz,
 }
@@ -31,7 +42,13 @@ impl SessionType {
     pub fn is_user_session(&self) -> bool {
         !matches!(
             self,
-            SessionType::HTTPAPI(_) | SessionType::Dummy | SessionType::Fuzz
+            SessionType::HTTPAPI(_)
+                | SessionType::AutoCompaction
+                | SessionType::Task
+                | SessionType::IndexBuilder
+                | SessionType::QueryLogPersister
+                | SessionType::Dummy
+                | SessionType::Fuzz
         )
     }
 }
@@ -42,12 +59,18 @@ impl fmt::Display for SessionType {
             SessionType::ClickHouseHttpHandler => "ClickhouseHTTPHandler".to_string(),
             SessionType::Clickhouse => "Clickhouse".to_string(),
             SessionType::MySQL => "MySQL".to_string(),
+            SessionType::Postgres => "Postgres".to_string(),
             SessionType::HTTPQuery => "HTTPQuery".to_string(),
             SessionType::HTTPStreamingLoad => "HTTPStreamingLoad".to_string(),
             SessionType::Dummy => "Dummy".to_string(),
             SessionType::FlightRPC => "FlightRPC".to_string(),
             SessionType::HTTPAPI(usage) => format!("HTTPAPI({})", usage),
             SessionType::Fuzz => "Fuzz".to_string(),
+            SessionType::AsyncInsert => "AsyncInsert".to_string(),
+            SessionType::AutoCompaction => "AutoCompaction".to_string(),
+            SessionType::Task => "Task".to_string(),
+            SessionType::IndexBuilder => "IndexBuilder".to_string(),
+            SessionType::QueryLogPersister => "QueryLogPersister".to_string(),
         };
         write!(f, "{}", name)
     }
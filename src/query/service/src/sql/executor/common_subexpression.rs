@@ -0,0 +1,173 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_functions::scalars::FunctionFactory;
+
+use crate::sql::executor::ColumnID;
+use crate::sql::executor::PhysicalScalar;
+
+/// The subexpressions repeated in a list of scalars, e.g. the same `CASE` in several items of
+/// a projection, to evaluate once per block.
+#[derive(Clone, Debug, Default)]
+pub struct CommonSubexpressions {
+    /// The repeated subexpressions and the names of the temporary columns they are evaluated
+    /// into. Each one may refer to the columns of the previous ones.
+    pub common: Vec<(PhysicalScalar, ColumnID)>,
+    /// The scalars, with the repeated subexpressions replaced by their temporary columns.
+    pub scalars: Vec<PhysicalScalar>,
+}
+
+impl CommonSubexpressions {
+    pub fn extract(scalars: &[PhysicalScalar]) -> CommonSubexpressions {
+        // A subexpression is only counted once per occurrence of its enclosing repeated
+        // expression, as it is evaluated once with it.
+        let mut occurrences = HashMap::new();
+        for scalar in scalars {
+            count_occurrences(scalar, &mut occurrences);
+        }
+
+        let mut result = CommonSubexpressions::default();
+        let mut columns = HashMap::new();
+        for scalar in scalars {
+            let scalar = result.replace(scalar, &occurrences, &mut columns);
+            result.scalars.push(scalar);
+        }
+        result
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.common.is_empty()
+    }
+
+    fn replace(
+        &mut self,
+        scalar: &PhysicalScalar,
+        occurrences: &HashMap<&PhysicalScalar, usize>,
+        columns: &mut HashMap<PhysicalScalar, ColumnID>,
+    ) -> PhysicalScalar {
+        if occurrences.get(scalar).map_or(false, |count| *count > 1) {
+            if let Some(column_id) = columns.get(scalar) {
+                return PhysicalScalar::Variable {
+                    column_id: column_id.clone(),
+                    data_type: scalar.data_type(),
+                };
+            }
+
+            let common = self.replace_arguments(scalar, occurrences, columns);
+            let column_id = format!("_common_subexpression_{}", self.common.len());
+            self.common.push((common, column_id.clone()));
+            columns.insert(scalar.clone(), column_id.clone());
+            return PhysicalScalar::Variable {
+                column_id,
+                data_type: scalar.data_type(),
+            };
+        }
+
+        self.replace_arguments(scalar, occurrences, columns)
+    }
+
+    fn replace_arguments(
+        &mut self,
+        scalar: &PhysicalScalar,
+        occurrences: &HashMap<&PhysicalScalar, usize>,
+        columns: &mut HashMap<PhysicalScalar, ColumnID>,
+    ) -> PhysicalScalar {
+        match scalar {
+            PhysicalScalar::Function {
+                name,
+                args,
+                return_type,
+            } => PhysicalScalar::Function {
+                name: name.clone(),
+                args: args
+                    .iter()
+                    .map(|(arg, data_type)| {
+                        (self.replace(arg, occurrences, columns), data_type.clone())
+                    })
+                    .collect(),
+                return_type: return_type.clone(),
+            },
+            PhysicalScalar::Cast { input, target } => PhysicalScalar::Cast {
+                input: Box::new(self.replace(input, occurrences, columns)),
+                target: target.clone(),
+            },
+            PhysicalScalar::UDFServerCall {
+                func_name,
+                server_addr,
+                handler,
+                args,
+                return_type,
+                timeout,
+            } => PhysicalScalar::UDFServerCall {
+                func_name: func_name.clone(),
+                server_addr: server_addr.clone(),
+                handler: handler.clone(),
+                args: args
+                    .iter()
+                    .map(|(arg, data_type)| {
+                        (self.replace(arg, occurrences, columns), data_type.clone())
+                    })
+                    .collect(),
+                return_type: return_type.clone(),
+                timeout: *timeout,
+            },
+            PhysicalScalar::Variable { .. }
+            | PhysicalScalar::IndexedVariable { .. }
+            | PhysicalScalar::Constant { .. } => scalar.clone(),
+        }
+    }
+}
+
+fn count_occurrences<'a>(
+    scalar: &'a PhysicalScalar,
+    occurrences: &mut HashMap<&'a PhysicalScalar, usize>,
+) {
+    if is_shareable(scalar) {
+        let count = occurrences.entry(scalar).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            return;
+        }
+    }
+
+    match scalar {
+        PhysicalScalar::Function { args, .. } | PhysicalScalar::UDFServerCall { args, .. } => {
+            for (arg, _) in args {
+                count_occurrences(arg, occurrences);
+            }
+        }
+        PhysicalScalar::Cast { input, .. } => count_occurrences(input, occurrences),
+        PhysicalScalar::Variable { .. }
+        | PhysicalScalar::IndexedVariable { .. }
+        | PhysicalScalar::Constant { .. } => {}
+    }
+}
+
+/// Whether the result of `scalar` can be shared by its occurrences. The columns and the
+/// constants are cheap to evaluate, and the calls of the non-deterministic functions must be
+/// evaluated one by one.
+fn is_shareable(scalar: &PhysicalScalar) -> bool {
+    match scalar {
+        PhysicalScalar::Function { name, .. } => FunctionFactory::instance()
+            .get_features(name)
+            .map_or(false, |features| features.is_deterministic),
+        PhysicalScalar::Cast { .. } => true,
+        PhysicalScalar::UDFServerCall { .. }
+        | PhysicalScalar::Variable { .. }
+        | PhysicalScalar::IndexedVariable { .. }
+        | PhysicalScalar::Constant { .. } => false,
+    }
+}
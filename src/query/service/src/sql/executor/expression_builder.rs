@@ -134,6 +134,8 @@ where ExpressionBuilder<T>: FiledNameFormat
                 })
             }
             Scalar::SubqueryExpr(_) => Err(ErrorCode::UnImplement("Unsupported subquery expr")),
+            Scalar::WindowFunction(_) => Err(ErrorCode::UnImplement("Unsupported window function")),
+            Scalar::UDFServerCall(_) => Err(ErrorCode::UnImplement("Unsupported UDF server call")),
         }
     }
 
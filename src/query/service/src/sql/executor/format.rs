@@ -13,6 +13,10 @@
 // limitations under the License.
 
 use common_ast::ast::FormatTreeNode;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::TypeID;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_legacy_planners::StageKind;
@@ -30,35 +34,87 @@ use super::HashJoin;
 use super::Limit;
 use super::PhysicalPlan;
 use super::Project;
+use super::RecursiveUnion;
 use super::Sort;
+use super::SortDesc;
 use super::TableScan;
 use super::UnionAll;
+use super::Window;
 
 impl PhysicalPlan {
     pub fn format(&self, metadata: MetadataRef) -> Result<String> {
-        to_format_tree(self, &metadata)?.format_pretty()
+        to_format_tree(self, &metadata, false)?.format_pretty()
+    }
+
+    /// Format the plan with the estimated rows, bytes and cost of each operator.
+    pub fn format_with_estimates(&self, metadata: MetadataRef) -> Result<String> {
+        to_format_tree(self, &metadata, true)?.format_pretty()
     }
 }
 
-fn to_format_tree(plan: &PhysicalPlan, metadata: &MetadataRef) -> Result<FormatTreeNode<String>> {
-    match plan {
+fn to_format_tree(
+    plan: &PhysicalPlan,
+    metadata: &MetadataRef,
+    estimate: bool,
+) -> Result<FormatTreeNode<String>> {
+    let mut node = match plan {
         PhysicalPlan::TableScan(plan) => table_scan_to_format_tree(plan, metadata),
-        PhysicalPlan::Filter(plan) => filter_to_format_tree(plan, metadata),
-        PhysicalPlan::Project(plan) => project_to_format_tree(plan, metadata),
-        PhysicalPlan::EvalScalar(plan) => eval_scalar_to_format_tree(plan, metadata),
-        PhysicalPlan::AggregatePartial(plan) => aggregate_partial_to_format_tree(plan, metadata),
-        PhysicalPlan::AggregateFinal(plan) => aggregate_final_to_format_tree(plan, metadata),
-        PhysicalPlan::Sort(plan) => sort_to_format_tree(plan, metadata),
-        PhysicalPlan::Limit(plan) => limit_to_format_tree(plan, metadata),
-        PhysicalPlan::HashJoin(plan) => hash_join_to_format_tree(plan, metadata),
-        PhysicalPlan::Exchange(plan) => exchange_to_format_tree(plan, metadata),
-        PhysicalPlan::UnionAll(plan) => union_all_to_format_tree(plan, metadata),
+        PhysicalPlan::Filter(plan) => filter_to_format_tree(plan, metadata, estimate),
+        PhysicalPlan::Project(plan) => project_to_format_tree(plan, metadata, estimate),
+        PhysicalPlan::EvalScalar(plan) => eval_scalar_to_format_tree(plan, metadata, estimate),
+        PhysicalPlan::AggregatePartial(plan) => {
+            aggregate_partial_to_format_tree(plan, metadata, estimate)
+        }
+        PhysicalPlan::AggregateFinal(plan) => {
+            aggregate_final_to_format_tree(plan, metadata, estimate)
+        }
+        PhysicalPlan::Sort(plan) => sort_to_format_tree(plan, metadata, estimate),
+        PhysicalPlan::Window(plan) => window_to_format_tree(plan, metadata, estimate),
+        PhysicalPlan::Limit(plan) => limit_to_format_tree(plan, metadata, estimate),
+        PhysicalPlan::HashJoin(plan) => hash_join_to_format_tree(plan, metadata, estimate),
+        PhysicalPlan::Exchange(plan) => exchange_to_format_tree(plan, metadata, estimate),
+        PhysicalPlan::UnionAll(plan) => union_all_to_format_tree(plan, metadata, estimate),
+        PhysicalPlan::RecursiveUnion(plan) => {
+            recursive_union_to_format_tree(plan, metadata, estimate)
+        }
+        PhysicalPlan::RecursiveCteScan(plan) => Ok(FormatTreeNode::new(format!(
+            "RecursiveCteScan: {}",
+            plan.cte_name
+        ))),
         PhysicalPlan::ExchangeSource(_)
         | PhysicalPlan::ExchangeSink(_)
         | PhysicalPlan::DistributedInsertSelect(_) => {
             Err(ErrorCode::LogicalError("Invalid physical plan"))
         }
+    }?;
+
+    if let (true, Some(stat_info)) = (estimate, plan.stat_info()) {
+        let estimated_bytes = stat_info.estimated_rows * estimated_row_size(&plan.output_schema()?);
+        node.children.splice(0..0, vec![
+            FormatTreeNode::new(format!("estimated rows: {:.0}", stat_info.estimated_rows)),
+            FormatTreeNode::new(format!("estimated bytes: {:.0}", estimated_bytes)),
+            FormatTreeNode::new(format!("estimated cost: {:.0}", stat_info.cost)),
+        ]);
     }
+
+    Ok(node)
+}
+
+/// Estimated size of a row in bytes, the values of variable-length types are assumed to take
+/// 16 bytes.
+fn estimated_row_size(schema: &DataSchemaRef) -> f64 {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let data_type = remove_nullable(field.data_type());
+            let size = match data_type.data_type_id() {
+                TypeID::Boolean => 1,
+                type_id => type_id.numeric_byte_size().unwrap_or(16),
+            };
+            if field.is_nullable() { size + 1 } else { size }
+        })
+        .sum::<usize>() as f64
 }
 
 fn table_scan_to_format_tree(
@@ -114,7 +170,11 @@ fn table_scan_to_format_tree(
     ))
 }
 
-fn filter_to_format_tree(plan: &Filter, metadata: &MetadataRef) -> Result<FormatTreeNode<String>> {
+fn filter_to_format_tree(
+    plan: &Filter,
+    metadata: &MetadataRef,
+    estimate: bool,
+) -> Result<FormatTreeNode<String>> {
     let filter = plan
         .predicates
         .iter()
@@ -123,13 +183,14 @@ fn filter_to_format_tree(plan: &Filter, metadata: &MetadataRef) -> Result<Format
         .join(", ");
     Ok(FormatTreeNode::with_children("Filter".to_string(), vec![
         FormatTreeNode::new(format!("filters: [{filter}]")),
-        to_format_tree(&plan.input, metadata)?,
+        to_format_tree(&plan.input, metadata, estimate)?,
     ]))
 }
 
 fn project_to_format_tree(
     plan: &Project,
     metadata: &MetadataRef,
+    estimate: bool,
 ) -> Result<FormatTreeNode<String>> {
     let columns = plan
         .columns
@@ -140,13 +201,14 @@ fn project_to_format_tree(
         .join(", ");
     Ok(FormatTreeNode::with_children("Project".to_string(), vec![
         FormatTreeNode::new(format!("columns: [{columns}]")),
-        to_format_tree(&plan.input, metadata)?,
+        to_format_tree(&plan.input, metadata, estimate)?,
     ]))
 }
 
 fn eval_scalar_to_format_tree(
     plan: &EvalScalar,
     metadata: &MetadataRef,
+    estimate: bool,
 ) -> Result<FormatTreeNode<String>> {
     let scalars = plan
         .scalars
@@ -158,7 +220,7 @@ fn eval_scalar_to_format_tree(
         "EvalScalar".to_string(),
         vec![
             FormatTreeNode::new(format!("expressions: [{scalars}]")),
-            to_format_tree(&plan.input, metadata)?,
+            to_format_tree(&plan.input, metadata, estimate)?,
         ],
     ))
 }
@@ -166,6 +228,7 @@ fn eval_scalar_to_format_tree(
 fn aggregate_partial_to_format_tree(
     plan: &AggregatePartial,
     metadata: &MetadataRef,
+    estimate: bool,
 ) -> Result<FormatTreeNode<String>> {
     let group_by = plan
         .group_by
@@ -189,7 +252,7 @@ fn aggregate_partial_to_format_tree(
         vec![
             FormatTreeNode::new(format!("group by: [{group_by}]")),
             FormatTreeNode::new(format!("aggregate functions: [{agg_funcs}]")),
-            to_format_tree(&plan.input, metadata)?,
+            to_format_tree(&plan.input, metadata, estimate)?,
         ],
     ))
 }
@@ -197,6 +260,7 @@ fn aggregate_partial_to_format_tree(
 fn aggregate_final_to_format_tree(
     plan: &AggregateFinal,
     metadata: &MetadataRef,
+    estimate: bool,
 ) -> Result<FormatTreeNode<String>> {
     let group_by = plan
         .group_by
@@ -220,14 +284,13 @@ fn aggregate_final_to_format_tree(
         vec![
             FormatTreeNode::new(format!("group by: [{group_by}]")),
             FormatTreeNode::new(format!("aggregate functions: [{agg_funcs}]")),
-            to_format_tree(&plan.input, metadata)?,
+            to_format_tree(&plan.input, metadata, estimate)?,
         ],
     ))
 }
 
-fn sort_to_format_tree(plan: &Sort, metadata: &MetadataRef) -> Result<FormatTreeNode<String>> {
-    let sort_keys = plan
-        .order_by
+fn format_sort_keys(sort_keys: &[SortDesc], metadata: &MetadataRef) -> Result<String> {
+    Ok(sort_keys
         .iter()
         .map(|sort_key| {
             let index = sort_key.order_by.parse::<IndexType>()?;
@@ -244,14 +307,54 @@ fn sort_to_format_tree(plan: &Sort, metadata: &MetadataRef) -> Result<FormatTree
             ))
         })
         .collect::<Result<Vec<_>>>()?
-        .join(", ");
+        .join(", "))
+}
+
+fn sort_to_format_tree(
+    plan: &Sort,
+    metadata: &MetadataRef,
+    estimate: bool,
+) -> Result<FormatTreeNode<String>> {
+    let sort_keys = format_sort_keys(&plan.order_by, metadata)?;
     Ok(FormatTreeNode::with_children("Sort".to_string(), vec![
         FormatTreeNode::new(format!("sort keys: [{sort_keys}]")),
-        to_format_tree(&plan.input, metadata)?,
+        to_format_tree(&plan.input, metadata, estimate)?,
     ]))
 }
 
-fn limit_to_format_tree(plan: &Limit, metadata: &MetadataRef) -> Result<FormatTreeNode<String>> {
+fn window_to_format_tree(
+    plan: &Window,
+    metadata: &MetadataRef,
+    estimate: bool,
+) -> Result<FormatTreeNode<String>> {
+    let partition_by = plan
+        .partition_by
+        .iter()
+        .map(|column_id| {
+            let index = column_id.parse::<IndexType>()?;
+            let column = metadata.read().column(index).clone();
+            Ok(column.name().to_string())
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+    let order_by = format_sort_keys(&plan.order_by, metadata)?;
+    Ok(FormatTreeNode::with_children("Window".to_string(), vec![
+        FormatTreeNode::new(format!(
+            "window function: {}",
+            plan.func.pretty_display(metadata)?
+        )),
+        FormatTreeNode::new(format!("partition by: [{partition_by}]")),
+        FormatTreeNode::new(format!("order by: [{order_by}]")),
+        FormatTreeNode::new(format!("frame: [{}]", plan.frame)),
+        to_format_tree(&plan.input, metadata, estimate)?,
+    ]))
+}
+
+fn limit_to_format_tree(
+    plan: &Limit,
+    metadata: &MetadataRef,
+    estimate: bool,
+) -> Result<FormatTreeNode<String>> {
     Ok(FormatTreeNode::with_children("Limit".to_string(), vec![
         FormatTreeNode::new(format!(
             "limit: {}",
@@ -259,13 +362,14 @@ fn limit_to_format_tree(plan: &Limit, metadata: &MetadataRef) -> Result<FormatTr
                 .map_or("NONE".to_string(), |limit| limit.to_string())
         )),
         FormatTreeNode::new(format!("offset: {}", plan.offset)),
-        to_format_tree(&plan.input, metadata)?,
+        to_format_tree(&plan.input, metadata, estimate)?,
     ]))
 }
 
 fn hash_join_to_format_tree(
     plan: &HashJoin,
     metadata: &MetadataRef,
+    estimate: bool,
 ) -> Result<FormatTreeNode<String>> {
     let build_keys = plan
         .build_keys
@@ -286,8 +390,8 @@ fn hash_join_to_format_tree(
         .collect::<Result<Vec<_>>>()?
         .join(", ");
 
-    let mut build_child = to_format_tree(&plan.build, metadata)?;
-    let mut probe_child = to_format_tree(&plan.probe, metadata)?;
+    let mut build_child = to_format_tree(&plan.build, metadata, estimate)?;
+    let mut probe_child = to_format_tree(&plan.probe, metadata, estimate)?;
 
     build_child.payload = format!("{}(Build)", build_child.payload);
     probe_child.payload = format!("{}(Probe)", probe_child.payload);
@@ -305,6 +409,7 @@ fn hash_join_to_format_tree(
 fn exchange_to_format_tree(
     plan: &Exchange,
     metadata: &MetadataRef,
+    estimate: bool,
 ) -> Result<FormatTreeNode<String>> {
     Ok(FormatTreeNode::with_children("Exchange".to_string(), vec![
         FormatTreeNode::new(format!("exchange type: {}", match plan.kind {
@@ -319,16 +424,33 @@ fn exchange_to_format_tree(
             StageKind::Expansive => "Broadcast".to_string(),
             StageKind::Merge => "Merge".to_string(),
         })),
-        to_format_tree(&plan.input, metadata)?,
+        to_format_tree(&plan.input, metadata, estimate)?,
     ]))
 }
 
 fn union_all_to_format_tree(
     plan: &UnionAll,
     metadata: &MetadataRef,
+    estimate: bool,
 ) -> Result<FormatTreeNode<String>> {
     Ok(FormatTreeNode::with_children("UnionAll".to_string(), vec![
-        to_format_tree(&plan.left, metadata)?,
-        to_format_tree(&plan.right, metadata)?,
+        to_format_tree(&plan.left, metadata, estimate)?,
+        to_format_tree(&plan.right, metadata, estimate)?,
     ]))
 }
+
+fn recursive_union_to_format_tree(
+    plan: &RecursiveUnion,
+    metadata: &MetadataRef,
+    estimate: bool,
+) -> Result<FormatTreeNode<String>> {
+    Ok(FormatTreeNode::with_children(
+        "RecursiveUnion".to_string(),
+        vec![
+            FormatTreeNode::new(format!("cte: {}", plan.cte_name)),
+            FormatTreeNode::new(format!("distinct: {}", plan.distinct)),
+            to_format_tree(&plan.anchor, metadata, estimate)?,
+            to_format_tree(&plan.recursive, metadata, estimate)?,
+        ],
+    ))
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod common_subexpression;
 mod expression_builder;
 mod format;
 mod physical_plan;
@@ -22,6 +23,7 @@ mod physical_scalar;
 mod pipeline_builder;
 mod util;
 
+pub use common_subexpression::CommonSubexpressions;
 pub use expression_builder::ExpressionBuilder;
 pub use expression_builder::ExpressionBuilderWithRenaming;
 pub use expression_builder::ExpressionBuilderWithoutRenaming;
@@ -33,12 +33,22 @@ use common_planner::IndexType;
 use super::physical_scalar::PhysicalScalar;
 use super::AggregateFunctionDesc;
 use super::SortDesc;
+use super::WindowFunctionDesc;
 use crate::sql::optimizer::ColumnSet;
 use crate::sql::plans::JoinType;
+use crate::sql::plans::WindowFuncFrame;
 use crate::sql::ColumnBinding;
 
 pub type ColumnID = String;
 
+/// Estimated output and cost of a plan derived from the table statistics by the optimizer.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PlanStatsInfo {
+    pub estimated_rows: f64,
+    /// Cost of the plan, including its inputs.
+    pub cost: f64,
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct TableScan {
     pub name_mapping: BTreeMap<String, ColumnID>,
@@ -46,6 +56,8 @@ pub struct TableScan {
 
     /// Only used for display
     pub table_index: IndexType,
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl TableScan {
@@ -64,6 +76,9 @@ impl TableScan {
 pub struct Filter {
     pub input: Box<PhysicalPlan>,
     pub predicates: Vec<PhysicalScalar>,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl Filter {
@@ -96,6 +111,9 @@ impl Project {
 pub struct EvalScalar {
     pub input: Box<PhysicalPlan>,
     pub scalars: Vec<(PhysicalScalar, ColumnID)>,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl EvalScalar {
@@ -115,6 +133,9 @@ pub struct AggregatePartial {
     pub input: Box<PhysicalPlan>,
     pub group_by: Vec<ColumnID>,
     pub agg_funcs: Vec<AggregateFunctionDesc>,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl AggregatePartial {
@@ -139,6 +160,9 @@ pub struct AggregateFinal {
     pub group_by: Vec<ColumnID>,
     pub agg_funcs: Vec<AggregateFunctionDesc>,
     pub before_group_by_schema: DataSchemaRef,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl AggregateFinal {
@@ -166,6 +190,9 @@ pub struct Sort {
     pub order_by: Vec<SortDesc>,
     // limit = Limit.limit + Limit.offset
     pub limit: Option<usize>,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl Sort {
@@ -174,11 +201,38 @@ impl Sort {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Window {
+    pub input: Box<PhysicalPlan>,
+    pub func: WindowFunctionDesc,
+    pub partition_by: Vec<ColumnID>,
+    pub order_by: Vec<SortDesc>,
+    pub frame: WindowFuncFrame,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
+}
+
+impl Window {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        let input_schema = self.input.output_schema()?;
+        let mut fields = input_schema.fields().clone();
+        fields.push(DataField::new(
+            self.func.column_id.as_str(),
+            self.func.return_type.clone(),
+        ));
+        Ok(DataSchemaRefExt::create(fields))
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Limit {
     pub input: Box<PhysicalPlan>,
     pub limit: Option<usize>,
     pub offset: usize,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl Limit {
@@ -197,6 +251,9 @@ pub struct HashJoin {
     pub join_type: JoinType,
     pub marker_index: Option<IndexType>,
     pub from_correlated_subquery: bool,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl HashJoin {
@@ -276,6 +333,9 @@ pub struct Exchange {
     pub input: Box<PhysicalPlan>,
     pub kind: StageKind,
     pub keys: Vec<PhysicalScalar>,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl Exchange {
@@ -327,6 +387,9 @@ pub struct UnionAll {
     pub right: Box<PhysicalPlan>,
     pub pairs: Vec<(String, String)>,
     pub schema: DataSchemaRef,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
 }
 
 impl UnionAll {
@@ -335,6 +398,41 @@ impl UnionAll {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RecursiveUnion {
+    pub anchor: Box<PhysicalPlan>,
+    pub recursive: Box<PhysicalPlan>,
+    pub cte_name: String,
+    pub pairs: Vec<(String, String)>,
+    pub distinct: bool,
+    pub schema: DataSchemaRef,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
+}
+
+impl RecursiveUnion {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
+/// Reads the working table of a recursive CTE, which holds the rows of the last iteration.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RecursiveCteScan {
+    pub cte_name: String,
+    pub schema: DataSchemaRef,
+
+    /// Only used for display
+    pub stat_info: Option<PlanStatsInfo>,
+}
+
+impl RecursiveCteScan {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct DistributedInsertSelect {
     pub input: Box<PhysicalPlan>,
@@ -363,8 +461,11 @@ pub enum PhysicalPlan {
     Sort(Sort),
     Limit(Limit),
     HashJoin(HashJoin),
+    Window(Window),
     Exchange(Exchange),
     UnionAll(UnionAll),
+    RecursiveUnion(RecursiveUnion),
+    RecursiveCteScan(RecursiveCteScan),
 
     /// For insert into ... select ... in cluster
     DistributedInsertSelect(Box<DistributedInsertSelect>),
@@ -383,6 +484,12 @@ impl PhysicalPlan {
             )
     }
 
+    /// Whether executing the plan changes nothing, so that it can be executed again.
+    pub fn is_side_effect_free(&self) -> bool {
+        !matches!(self, Self::DistributedInsertSelect(_))
+            && self.children().all(|child| child.is_side_effect_free())
+    }
+
     pub fn output_schema(&self) -> Result<DataSchemaRef> {
         match self {
             PhysicalPlan::TableScan(plan) => plan.output_schema(),
@@ -392,16 +499,41 @@ impl PhysicalPlan {
             PhysicalPlan::AggregatePartial(plan) => plan.output_schema(),
             PhysicalPlan::AggregateFinal(plan) => plan.output_schema(),
             PhysicalPlan::Sort(plan) => plan.output_schema(),
+            PhysicalPlan::Window(plan) => plan.output_schema(),
             PhysicalPlan::Limit(plan) => plan.output_schema(),
             PhysicalPlan::HashJoin(plan) => plan.output_schema(),
             PhysicalPlan::Exchange(plan) => plan.output_schema(),
             PhysicalPlan::ExchangeSource(plan) => plan.output_schema(),
             PhysicalPlan::ExchangeSink(plan) => plan.output_schema(),
             PhysicalPlan::UnionAll(plan) => plan.output_schema(),
+            PhysicalPlan::RecursiveUnion(plan) => plan.output_schema(),
+            PhysicalPlan::RecursiveCteScan(plan) => plan.output_schema(),
             PhysicalPlan::DistributedInsertSelect(plan) => plan.output_schema(),
         }
     }
 
+    pub fn stat_info(&self) -> Option<&PlanStatsInfo> {
+        match self {
+            PhysicalPlan::TableScan(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::Filter(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::EvalScalar(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::AggregatePartial(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::AggregateFinal(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::Sort(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::Window(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::Limit(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::HashJoin(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::Exchange(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::UnionAll(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::RecursiveUnion(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::RecursiveCteScan(plan) => plan.stat_info.as_ref(),
+            PhysicalPlan::Project(_)
+            | PhysicalPlan::ExchangeSource(_)
+            | PhysicalPlan::ExchangeSink(_)
+            | PhysicalPlan::DistributedInsertSelect(_) => None,
+        }
+    }
+
     pub fn children<'a>(&'a self) -> Box<dyn Iterator<Item = &'a PhysicalPlan> + 'a> {
         match self {
             PhysicalPlan::TableScan(_) => Box::new(std::iter::empty()),
@@ -411,6 +543,7 @@ impl PhysicalPlan {
             PhysicalPlan::AggregatePartial(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::AggregateFinal(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::Sort(plan) => Box::new(std::iter::once(plan.input.as_ref())),
+            PhysicalPlan::Window(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::Limit(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::HashJoin(plan) => Box::new(
                 std::iter::once(plan.probe.as_ref()).chain(std::iter::once(plan.build.as_ref())),
@@ -421,6 +554,11 @@ impl PhysicalPlan {
             PhysicalPlan::UnionAll(plan) => Box::new(
                 std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
             ),
+            PhysicalPlan::RecursiveUnion(plan) => Box::new(
+                std::iter::once(plan.anchor.as_ref())
+                    .chain(std::iter::once(plan.recursive.as_ref())),
+            ),
+            PhysicalPlan::RecursiveCteScan(_) => Box::new(std::iter::empty()),
             PhysicalPlan::DistributedInsertSelect(plan) => {
                 Box::new(std::iter::once(plan.input.as_ref()))
             }
@@ -18,6 +18,7 @@ use std::sync::Arc;
 
 use common_catalog::catalog::CatalogManager;
 use common_catalog::catalog::CATALOG_DEFAULT;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
@@ -40,8 +41,11 @@ use super::Exchange as PhysicalExchange;
 use super::Filter;
 use super::HashJoin;
 use super::Limit;
+use super::RecursiveCteScan;
+use super::RecursiveUnion;
 use super::Sort;
 use super::TableScan;
+use super::Window;
 use crate::catalogs::CatalogManagerHelper;
 use crate::sessions::QueryContext;
 use crate::sql::executor::util::check_physical;
@@ -52,8 +56,10 @@ use crate::sql::executor::EvalScalar;
 use crate::sql::executor::ExpressionBuilderWithoutRenaming;
 use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::PhysicalScalar;
+use crate::sql::executor::PlanStatsInfo;
 use crate::sql::executor::SortDesc;
 use crate::sql::executor::UnionAll;
+use crate::sql::executor::WindowFunctionDesc;
 use crate::sql::optimizer::ColumnSet;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::AggregateMode;
@@ -74,12 +80,18 @@ impl PhysicalPlanBuilder {
         Self { metadata, ctx }
     }
 
+    /// The projection of the columns of the schema, the virtual columns are read by names.
     fn build_projection(
         metadata: &Metadata,
         schema: &DataSchemaRef,
         columns: &ColumnSet,
         has_inner_column: bool,
     ) -> Projection {
+        let columns = columns
+            .iter()
+            .filter(|index| !metadata.column(**index).is_virtual_column())
+            .copied()
+            .collect::<Vec<_>>();
         if !has_inner_column {
             let col_indices = columns
                 .iter()
@@ -114,6 +126,8 @@ impl PhysicalPlanBuilder {
     pub async fn build(&self, s_expr: &SExpr) -> Result<PhysicalPlan> {
         debug_assert!(check_physical(s_expr));
 
+        let stat_info = Self::build_plan_stat_info(s_expr);
+
         match s_expr.plan() {
             RelOperator::PhysicalScan(scan) => {
                 let mut has_inner_column = false;
@@ -153,6 +167,7 @@ impl PhysicalPlanBuilder {
                     name_mapping,
                     source: Box::new(source),
                     table_index: scan.table_index,
+                    stat_info,
                 }))
             }
             RelOperator::DummyTableScan(_) => {
@@ -168,6 +183,7 @@ impl PhysicalPlanBuilder {
                     name_mapping: BTreeMap::from([("dummy".to_string(), "dummy".to_string())]),
                     source: Box::new(source),
                     table_index: DUMMY_TABLE_INDEX,
+                    stat_info,
                 }))
             }
             RelOperator::PhysicalHashJoin(join) => {
@@ -203,6 +219,7 @@ impl PhysicalPlanBuilder {
                         .collect::<Result<_>>()?,
                     marker_index: join.marker_index,
                     from_correlated_subquery: join.from_correlated_subquery,
+                    stat_info,
                 }))
             }
             RelOperator::EvalScalar(eval_scalar) => Ok(PhysicalPlan::EvalScalar(EvalScalar {
@@ -215,6 +232,7 @@ impl PhysicalPlanBuilder {
                         Ok((builder.build(&item.scalar)?, item.index.to_string()))
                     })
                     .collect::<Result<_>>()?,
+                stat_info,
             })),
 
             RelOperator::Filter(filter) => Ok(PhysicalPlan::Filter(Filter {
@@ -227,6 +245,7 @@ impl PhysicalPlanBuilder {
                         builder.build(pred)
                     })
                     .collect::<Result<_>>()?,
+                stat_info,
             })),
             RelOperator::Aggregate(agg) => {
                 let input = self.build(s_expr.child(0)?).await?;
@@ -268,6 +287,7 @@ impl PhysicalPlanBuilder {
                                 input,
                                 agg_funcs,
                                 group_by: group_items,
+                                stat_info: stat_info.clone(),
                             };
 
                             let output_schema = aggregate_partial.output_schema()?;
@@ -281,12 +301,14 @@ impl PhysicalPlanBuilder {
                                     column_id: group_by_key_field.name().clone(),
                                     data_type: group_by_key_field.data_type().clone(),
                                 }],
+                                stat_info,
                             })
                         }
                         _ => PhysicalPlan::AggregatePartial(AggregatePartial {
                             agg_funcs,
                             group_by: group_items,
                             input: Box::new(input),
+                            stat_info,
                         }),
                     },
 
@@ -299,6 +321,7 @@ impl PhysicalPlanBuilder {
                                 group_by: group_items,
                                 agg_funcs,
                                 before_group_by_schema,
+                                stat_info,
                             })
                         }
 
@@ -312,6 +335,7 @@ impl PhysicalPlanBuilder {
                                 group_by: group_items,
                                 agg_funcs,
                                 before_group_by_schema,
+                                stat_info,
                             })
                         }
 
@@ -334,11 +358,53 @@ impl PhysicalPlanBuilder {
                     })
                     .collect(),
                 limit: sort.limit,
+                stat_info,
             })),
+            RelOperator::Window(window) => {
+                let column_id = |scalar: &Scalar| {
+                    if let Scalar::BoundColumnRef(col) = scalar {
+                        Ok(col.column.index.to_string())
+                    } else {
+                        Err(ErrorCode::LogicalError(
+                            "Window function argument must be a BoundColumnRef",
+                        ))
+                    }
+                };
+                let func = &window.function;
+                Ok(PhysicalPlan::Window(Window {
+                    input: Box::new(self.build(s_expr.child(0)?).await?),
+                    func: WindowFunctionDesc {
+                        func: func.func.clone(),
+                        column_id: window.index.to_string(),
+                        args: func.args.iter().map(column_id).collect::<Result<_>>()?,
+                        arg_types: func.args.iter().map(|arg| arg.data_type()).collect(),
+                        return_type: *func.return_type.clone(),
+                    },
+                    partition_by: func
+                        .partition_by
+                        .iter()
+                        .map(column_id)
+                        .collect::<Result<_>>()?,
+                    order_by: func
+                        .order_by
+                        .iter()
+                        .map(|item| {
+                            Ok(SortDesc {
+                                asc: item.asc,
+                                nulls_first: item.nulls_first,
+                                order_by: column_id(&item.expr)?,
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                    frame: func.frame.clone(),
+                    stat_info,
+                }))
+            }
             RelOperator::Limit(limit) => Ok(PhysicalPlan::Limit(Limit {
                 input: Box::new(self.build(s_expr.child(0)?).await?),
                 limit: limit.limit,
                 offset: limit.offset,
+                stat_info,
             })),
             RelOperator::Exchange(exchange) => {
                 let mut keys = vec![];
@@ -357,6 +423,7 @@ impl PhysicalPlanBuilder {
                     input: Box::new(self.build(s_expr.child(0)?).await?),
                     kind,
                     keys,
+                    stat_info,
                 }))
             }
             RelOperator::UnionAll(op) => {
@@ -376,6 +443,44 @@ impl PhysicalPlanBuilder {
                     right: Box::new(self.build(s_expr.child(1)?).await?),
                     pairs,
                     schema: DataSchemaRefExt::create(fields),
+                    stat_info,
+                }))
+            }
+            RelOperator::RecursiveUnion(op) => {
+                let anchor = self.build(s_expr.child(0)?).await?;
+                let anchor_schema = anchor.output_schema()?;
+                let pairs = op
+                    .pairs
+                    .iter()
+                    .map(|(l, r)| (l.to_string(), r.to_string()))
+                    .collect::<Vec<_>>();
+                let fields = pairs
+                    .iter()
+                    .map(|(anchor, _)| Ok(anchor_schema.field_with_name(anchor)?.clone()))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PhysicalPlan::RecursiveUnion(RecursiveUnion {
+                    anchor: Box::new(anchor),
+                    recursive: Box::new(self.build(s_expr.child(1)?).await?),
+                    cte_name: op.cte_name.clone(),
+                    pairs,
+                    distinct: op.distinct,
+                    schema: DataSchemaRefExt::create(fields),
+                    stat_info,
+                }))
+            }
+            RelOperator::RecursiveCteScan(scan) => {
+                let fields = scan
+                    .columns
+                    .iter()
+                    .map(|index| {
+                        let data_type = self.metadata.read().column(*index).data_type().clone();
+                        DataField::new(&index.to_string(), data_type)
+                    })
+                    .collect();
+                Ok(PhysicalPlan::RecursiveCteScan(RecursiveCteScan {
+                    cte_name: scan.cte_name.clone(),
+                    schema: DataSchemaRefExt::create(fields),
+                    stat_info,
                 }))
             }
             _ => Err(ErrorCode::LogicalError(format!(
@@ -385,6 +490,21 @@ impl PhysicalPlanBuilder {
         }
     }
 
+    /// Estimate the plan with the properties and the cost attached by the optimizer. The exchanges
+    /// added by the distributed optimization are estimated as their inputs.
+    fn build_plan_stat_info(s_expr: &SExpr) -> Option<PlanStatsInfo> {
+        match (&s_expr.rel_prop, s_expr.cost) {
+            (Some(prop), Some(cost)) => Some(PlanStatsInfo {
+                estimated_rows: prop.cardinality,
+                cost: cost.0,
+            }),
+            _ if matches!(s_expr.plan(), RelOperator::Exchange(_)) => {
+                s_expr.child(0).ok().and_then(Self::build_plan_stat_info)
+            }
+            _ => None,
+        }
+    }
+
     fn push_downs(
         &self,
         scan: &PhysicalScan,
@@ -461,6 +581,15 @@ impl PhysicalPlanBuilder {
             })
             .transpose()?;
 
+        let virtual_columns = scan
+            .columns
+            .iter()
+            .sorted()
+            .map(|index| metadata.column(*index))
+            .filter(|column| column.is_virtual_column())
+            .map(|column| column.name().to_string())
+            .collect();
+
         let order_by = scan
             .order_by
             .clone()
@@ -488,6 +617,8 @@ impl PhysicalPlanBuilder {
             prewhere: prewhere_info,
             limit: scan.limit,
             order_by: order_by.unwrap_or_default(),
+            sample: scan.sample,
+            virtual_columns,
         })
     }
 }
@@ -544,6 +675,19 @@ impl PhysicalScalarBuilder {
                 input: Box::new(self.build(&cast.argument)?),
                 target: *cast.target_type.clone(),
             }),
+            Scalar::UDFServerCall(udf) => Ok(PhysicalScalar::UDFServerCall {
+                func_name: udf.func_name.clone(),
+                server_addr: udf.server_addr.clone(),
+                handler: udf.handler.clone(),
+                args: udf
+                    .arguments
+                    .iter()
+                    .zip(udf.arg_types.iter())
+                    .map(|(arg, typ)| Ok((self.build(arg)?, typ.clone())))
+                    .collect::<Result<_>>()?,
+                return_type: *udf.return_type.clone(),
+                timeout: udf.timeout,
+            }),
 
             _ => Err(ErrorCode::LogicalError(format!(
                 "Unsupported physical scalar: {:?}",
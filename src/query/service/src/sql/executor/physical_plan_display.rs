@@ -31,9 +31,12 @@ use crate::sql::executor::Limit;
 use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::PhysicalScalar;
 use crate::sql::executor::Project;
+use crate::sql::executor::RecursiveCteScan;
+use crate::sql::executor::RecursiveUnion;
 use crate::sql::executor::Sort;
 use crate::sql::executor::TableScan;
 use crate::sql::executor::UnionAll;
+use crate::sql::executor::Window;
 use crate::sql::plans::JoinType;
 
 impl PhysicalPlan {
@@ -59,12 +62,15 @@ impl<'a> Display for PhysicalPlanIndentFormatDisplay<'a> {
             PhysicalPlan::AggregatePartial(aggregate) => write!(f, "{}", aggregate)?,
             PhysicalPlan::AggregateFinal(aggregate) => write!(f, "{}", aggregate)?,
             PhysicalPlan::Sort(sort) => write!(f, "{}", sort)?,
+            PhysicalPlan::Window(window) => write!(f, "{}", window)?,
             PhysicalPlan::Limit(limit) => write!(f, "{}", limit)?,
             PhysicalPlan::HashJoin(join) => write!(f, "{}", join)?,
             PhysicalPlan::Exchange(exchange) => write!(f, "{}", exchange)?,
             PhysicalPlan::ExchangeSource(source) => write!(f, "{}", source)?,
             PhysicalPlan::ExchangeSink(sink) => write!(f, "{}", sink)?,
             PhysicalPlan::UnionAll(union_all) => write!(f, "{}", union_all)?,
+            PhysicalPlan::RecursiveUnion(union) => write!(f, "{}", union)?,
+            PhysicalPlan::RecursiveCteScan(scan) => write!(f, "{}", scan)?,
             PhysicalPlan::DistributedInsertSelect(insert_select) => write!(f, "{}", insert_select)?,
         }
 
@@ -113,6 +119,17 @@ impl Display for PhysicalScalar {
                 write!(f, "CAST({} AS {})", input, format_data_type_sql(target))
             }
             PhysicalScalar::IndexedVariable { index, .. } => write!(f, "${index}"),
+            PhysicalScalar::UDFServerCall {
+                func_name, args, ..
+            } => write!(
+                f,
+                "{}({})",
+                func_name,
+                args.iter()
+                    .map(|(arg, _)| format!("{}", arg))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -153,6 +170,31 @@ impl Display for Sort {
     }
 }
 
+impl Display for Window {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let order_by = self
+            .order_by
+            .iter()
+            .map(|item| {
+                format!(
+                    "{} {}",
+                    item.order_by,
+                    if item.asc { "ASC" } else { "DESC" }
+                )
+            })
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            "Window: [{}({})], Partition By: [{}], Order By: [{}], Frame: [{}]",
+            self.func.func,
+            self.func.args.join(", "),
+            self.partition_by.join(", "),
+            order_by.join(", "),
+            self.frame
+        )
+    }
+}
+
 impl Display for EvalScalar {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let scalars = self
@@ -317,6 +359,22 @@ impl Display for UnionAll {
     }
 }
 
+impl Display for RecursiveUnion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RecursiveUnion: cte: {}, distinct: {}",
+            self.cte_name, self.distinct
+        )
+    }
+}
+
+impl Display for RecursiveCteScan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RecursiveCteScan: cte: {}", self.cte_name)
+    }
+}
+
 impl Display for DistributedInsertSelect {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "DistributedInsertSelect")
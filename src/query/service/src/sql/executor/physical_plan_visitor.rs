@@ -26,8 +26,11 @@ use super::HashJoin;
 use super::Limit;
 use super::PhysicalPlan;
 use super::Project;
+use super::RecursiveCteScan;
+use super::RecursiveUnion;
 use super::Sort;
 use super::TableScan;
+use super::Window;
 use crate::sql::executor::UnionAll;
 
 pub trait PhysicalPlanReplacer {
@@ -40,12 +43,15 @@ pub trait PhysicalPlanReplacer {
             PhysicalPlan::AggregatePartial(plan) => self.replace_aggregate_partial(plan),
             PhysicalPlan::AggregateFinal(plan) => self.replace_aggregate_final(plan),
             PhysicalPlan::Sort(plan) => self.replace_sort(plan),
+            PhysicalPlan::Window(plan) => self.replace_window(plan),
             PhysicalPlan::Limit(plan) => self.replace_limit(plan),
             PhysicalPlan::HashJoin(plan) => self.replace_hash_join(plan),
             PhysicalPlan::Exchange(plan) => self.replace_exchange(plan),
             PhysicalPlan::ExchangeSource(plan) => self.replace_exchange_source(plan),
             PhysicalPlan::ExchangeSink(plan) => self.replace_exchange_sink(plan),
             PhysicalPlan::UnionAll(plan) => self.replace_union(plan),
+            PhysicalPlan::RecursiveUnion(plan) => self.replace_recursive_union(plan),
+            PhysicalPlan::RecursiveCteScan(plan) => self.replace_recursive_cte_scan(plan),
             PhysicalPlan::DistributedInsertSelect(plan) => self.replace_insert_select(plan),
         }
     }
@@ -60,6 +66,7 @@ pub trait PhysicalPlanReplacer {
         Ok(PhysicalPlan::Filter(Filter {
             input: Box::new(input),
             predicates: plan.predicates.clone(),
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
@@ -79,6 +86,7 @@ pub trait PhysicalPlanReplacer {
         Ok(PhysicalPlan::EvalScalar(EvalScalar {
             input: Box::new(input),
             scalars: plan.scalars.clone(),
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
@@ -89,6 +97,7 @@ pub trait PhysicalPlanReplacer {
             input: Box::new(input),
             group_by: plan.group_by.clone(),
             agg_funcs: plan.agg_funcs.clone(),
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
@@ -100,6 +109,7 @@ pub trait PhysicalPlanReplacer {
             before_group_by_schema: plan.before_group_by_schema.clone(),
             group_by: plan.group_by.clone(),
             agg_funcs: plan.agg_funcs.clone(),
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
@@ -116,6 +126,7 @@ pub trait PhysicalPlanReplacer {
             join_type: plan.join_type.clone(),
             marker_index: plan.marker_index,
             from_correlated_subquery: plan.from_correlated_subquery,
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
@@ -126,6 +137,20 @@ pub trait PhysicalPlanReplacer {
             input: Box::new(input),
             order_by: plan.order_by.clone(),
             limit: plan.limit,
+            stat_info: plan.stat_info.clone(),
+        }))
+    }
+
+    fn replace_window(&mut self, plan: &Window) -> Result<PhysicalPlan> {
+        let input = self.replace(&plan.input)?;
+
+        Ok(PhysicalPlan::Window(Window {
+            input: Box::new(input),
+            func: plan.func.clone(),
+            partition_by: plan.partition_by.clone(),
+            order_by: plan.order_by.clone(),
+            frame: plan.frame.clone(),
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
@@ -136,6 +161,7 @@ pub trait PhysicalPlanReplacer {
             input: Box::new(input),
             limit: plan.limit,
             offset: plan.offset,
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
@@ -146,6 +172,7 @@ pub trait PhysicalPlanReplacer {
             input: Box::new(input),
             kind: plan.kind.clone(),
             keys: plan.keys.clone(),
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
@@ -175,9 +202,28 @@ pub trait PhysicalPlanReplacer {
             right: Box::new(right),
             schema: plan.schema.clone(),
             pairs: plan.pairs.clone(),
+            stat_info: plan.stat_info.clone(),
         }))
     }
 
+    fn replace_recursive_union(&mut self, plan: &RecursiveUnion) -> Result<PhysicalPlan> {
+        let anchor = self.replace(&plan.anchor)?;
+        let recursive = self.replace(&plan.recursive)?;
+        Ok(PhysicalPlan::RecursiveUnion(RecursiveUnion {
+            anchor: Box::new(anchor),
+            recursive: Box::new(recursive),
+            cte_name: plan.cte_name.clone(),
+            pairs: plan.pairs.clone(),
+            distinct: plan.distinct,
+            schema: plan.schema.clone(),
+            stat_info: plan.stat_info.clone(),
+        }))
+    }
+
+    fn replace_recursive_cte_scan(&mut self, plan: &RecursiveCteScan) -> Result<PhysicalPlan> {
+        Ok(PhysicalPlan::RecursiveCteScan(plan.clone()))
+    }
+
     fn replace_insert_select(&mut self, plan: &DistributedInsertSelect) -> Result<PhysicalPlan> {
         let input = self.replace(&plan.input)?;
 
@@ -224,6 +270,9 @@ impl PhysicalPlan {
                 PhysicalPlan::Sort(plan) => {
                     Self::traverse(&plan.input, pre_visit, visit, post_visit);
                 }
+                PhysicalPlan::Window(plan) => {
+                    Self::traverse(&plan.input, pre_visit, visit, post_visit);
+                }
                 PhysicalPlan::Limit(plan) => {
                     Self::traverse(&plan.input, pre_visit, visit, post_visit);
                 }
@@ -242,6 +291,11 @@ impl PhysicalPlan {
                     Self::traverse(&plan.left, pre_visit, visit, post_visit);
                     Self::traverse(&plan.right, pre_visit, visit, post_visit);
                 }
+                PhysicalPlan::RecursiveUnion(plan) => {
+                    Self::traverse(&plan.anchor, pre_visit, visit, post_visit);
+                    Self::traverse(&plan.recursive, pre_visit, visit, post_visit);
+                }
+                PhysicalPlan::RecursiveCteScan(_) => {}
                 PhysicalPlan::DistributedInsertSelect(plan) => {
                     Self::traverse(&plan.input, pre_visit, visit, post_visit);
                 }
@@ -20,9 +20,10 @@ use common_planner::IndexType;
 use common_planner::MetadataRef;
 
 use super::ColumnID;
+use crate::sql::plans::WindowFuncType;
 
 /// Serializable and desugared representation of `Scalar`.
-#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum PhysicalScalar {
     Variable {
         column_id: ColumnID,
@@ -45,6 +46,14 @@ pub enum PhysicalScalar {
         input: Box<PhysicalScalar>,
         target: DataTypeImpl,
     },
+    UDFServerCall {
+        func_name: String,
+        server_addr: String,
+        handler: String,
+        args: Vec<(PhysicalScalar, DataTypeImpl)>,
+        return_type: DataTypeImpl,
+        timeout: u64,
+    },
 }
 
 impl PhysicalScalar {
@@ -55,6 +64,7 @@ impl PhysicalScalar {
             PhysicalScalar::Function { return_type, .. } => return_type.clone(),
             PhysicalScalar::Cast { target, .. } => target.clone(),
             PhysicalScalar::IndexedVariable { data_type, .. } => data_type.clone(),
+            PhysicalScalar::UDFServerCall { return_type, .. } => return_type.clone(),
         }
     }
 
@@ -93,6 +103,16 @@ impl PhysicalScalar {
                 format_data_type_sql(target)
             )),
             PhysicalScalar::IndexedVariable { index, .. } => Ok(format!("${index}")),
+            PhysicalScalar::UDFServerCall {
+                func_name, args, ..
+            } => {
+                let args = args
+                    .iter()
+                    .map(|(arg, _)| arg.pretty_display(metadata))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ");
+                Ok(format!("{}({})", func_name, args))
+            }
         }
     }
 }
@@ -136,3 +156,30 @@ pub struct SortDesc {
     pub nulls_first: bool,
     pub order_by: ColumnID,
 }
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowFunctionDesc {
+    pub func: WindowFuncType,
+    pub column_id: ColumnID,
+    pub args: Vec<ColumnID>,
+    pub arg_types: Vec<DataTypeImpl>,
+    pub return_type: DataTypeImpl,
+}
+
+impl WindowFunctionDesc {
+    pub fn pretty_display(&self, metadata: &MetadataRef) -> Result<String> {
+        Ok(format!(
+            "{}({})",
+            self.func,
+            self.args
+                .iter()
+                .map(|arg| {
+                    let index = arg.parse::<IndexType>()?;
+                    let column = metadata.read().column(index).clone();
+                    Ok(column.name().to_string())
+                })
+                .collect::<Result<Vec<_>>>()?
+                .join(", ")
+        ))
+    }
+}
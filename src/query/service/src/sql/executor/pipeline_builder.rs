@@ -17,6 +17,7 @@ use std::sync::Arc;
 use async_channel::Receiver;
 use common_datablocks::DataBlock;
 use common_datablocks::SortColumnDescription;
+use common_datavalues::remove_nullable;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
@@ -29,6 +30,7 @@ use common_functions::scalars::CastFunction;
 use common_functions::scalars::FunctionFactory;
 use common_pipeline_core::Pipe;
 use common_pipeline_sinks::processors::sinks::UnionReceiveSink;
+use parking_lot::Mutex;
 
 use super::AggregateFinal;
 use super::AggregatePartial;
@@ -40,14 +42,19 @@ use super::Filter;
 use super::HashJoin;
 use super::Limit;
 use super::Project;
+use super::RecursiveCteScan;
+use super::RecursiveUnion;
 use super::Sort;
 use super::TableScan;
+use super::Window;
 use crate::evaluator::EvalNode;
 use crate::evaluator::Evaluator;
 use crate::interpreters::fill_missing_columns;
 use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::transforms::ExpressionTransformV2;
 use crate::pipelines::processors::transforms::HashJoinDesc;
+use crate::pipelines::processors::transforms::RuntimeFilterDesc;
 use crate::pipelines::processors::transforms::TransformFilterV2;
 use crate::pipelines::processors::transforms::TransformMarkJoin;
 use crate::pipelines::processors::transforms::TransformMergeBlock;
@@ -56,26 +63,32 @@ use crate::pipelines::processors::transforms::TransformRename;
 use crate::pipelines::processors::transforms::TransformRightJoin;
 use crate::pipelines::processors::AggregatorParams;
 use crate::pipelines::processors::AggregatorTransformParams;
+use crate::pipelines::processors::BlocksSource;
 use crate::pipelines::processors::JoinHashTable;
 use crate::pipelines::processors::MarkJoinCompactor;
+use crate::pipelines::processors::RecursiveCteSource;
 use crate::pipelines::processors::RightJoinCompactor;
 use crate::pipelines::processors::SinkBuildHashTable;
 use crate::pipelines::processors::Sinker;
 use crate::pipelines::processors::SortMergeCompactor;
 use crate::pipelines::processors::TransformAggregator;
 use crate::pipelines::processors::TransformCastSchema;
+use crate::pipelines::processors::TransformExternalSort;
 use crate::pipelines::processors::TransformHashJoinProbe;
 use crate::pipelines::processors::TransformLimit;
 use crate::pipelines::processors::TransformSortMerge;
 use crate::pipelines::processors::TransformSortPartial;
+use crate::pipelines::processors::TransformWindow;
 use crate::pipelines::Pipeline;
 use crate::pipelines::PipelineBuildResult;
 use crate::pipelines::SinkPipeBuilder;
+use crate::pipelines::SourcePipeBuilder;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
 use crate::sql::executor::physical_plan::ColumnID;
 use crate::sql::executor::physical_plan::PhysicalPlan;
 use crate::sql::executor::AggregateFunctionDesc;
+use crate::sql::executor::CommonSubexpressions;
 use crate::sql::executor::PhysicalScalar;
 use crate::sql::executor::UnionAll;
 use crate::sql::plans::JoinType;
@@ -124,9 +137,12 @@ impl PipelineBuilder {
             PhysicalPlan::Sort(sort) => self.build_sort(sort),
             PhysicalPlan::Limit(limit) => self.build_limit(limit),
             PhysicalPlan::HashJoin(join) => self.build_join(join),
+            PhysicalPlan::Window(window) => self.build_window(window),
             PhysicalPlan::ExchangeSink(sink) => self.build_exchange_sink(sink),
             PhysicalPlan::ExchangeSource(source) => self.build_exchange_source(source),
             PhysicalPlan::UnionAll(union_all) => self.build_union_all(union_all),
+            PhysicalPlan::RecursiveUnion(union) => self.build_recursive_union(union),
+            PhysicalPlan::RecursiveCteScan(scan) => self.build_recursive_cte_scan(scan),
             PhysicalPlan::DistributedInsertSelect(insert_select) => {
                 self.build_distributed_insert_select(insert_select)
             }
@@ -148,10 +164,71 @@ impl PipelineBuilder {
             &join.build_keys,
             join.build.output_schema()?,
             join.probe.output_schema()?,
-            HashJoinDesc::create(join)?,
+            HashJoinDesc::create(join, self.runtime_filters(join)?)?,
         )
     }
 
+    /// Find the keys of the probe side which are columns of the table scanned in the pipeline of
+    /// the join, whose rows can be filtered by the keys of the build side before the join.
+    fn runtime_filters(&self, join: &HashJoin) -> Result<Vec<RuntimeFilterDesc>> {
+        // The other joins output the rows of the probe side matching nothing
+        let filterable = matches!(
+            join.join_type,
+            JoinType::Inner | JoinType::Semi | JoinType::Right
+        );
+        if !filterable || !self.ctx.get_settings().get_enable_runtime_filter()? {
+            return Ok(vec![]);
+        }
+
+        let scan = match Self::probe_side_scan(&join.probe) {
+            Some(scan) => scan,
+            None => return Ok(vec![]),
+        };
+
+        let mut runtime_filters = vec![];
+        let keys = join.build_keys.iter().zip(&join.probe_keys);
+        for (index, (build_key, probe_key)) in keys.enumerate() {
+            if let PhysicalScalar::Variable {
+                column_id,
+                data_type,
+            } = probe_key
+            {
+                // The keys are hashed in the same way only if they are of the same type
+                if remove_nullable(data_type) != remove_nullable(&build_key.data_type()) {
+                    continue;
+                }
+
+                if let Some((name, _)) = scan.name_mapping.iter().find(|(_, id)| *id == column_id) {
+                    runtime_filters.push(RuntimeFilterDesc {
+                        build_key: index,
+                        column_name: name.clone(),
+                    });
+                }
+            }
+        }
+        Ok(runtime_filters)
+    }
+
+    /// The table scan in the same pipeline as the probe side, if filtering its rows doesn't change
+    /// the result of the operators between them.
+    fn probe_side_scan(plan: &PhysicalPlan) -> Option<&TableScan> {
+        match plan {
+            PhysicalPlan::TableScan(scan) => Some(scan),
+            PhysicalPlan::Filter(filter) => Self::probe_side_scan(&filter.input),
+            PhysicalPlan::Project(project) => Self::probe_side_scan(&project.input),
+            PhysicalPlan::EvalScalar(eval_scalar) => Self::probe_side_scan(&eval_scalar.input),
+            PhysicalPlan::HashJoin(join)
+                if matches!(
+                    join.join_type,
+                    JoinType::Inner | JoinType::Left | JoinType::Semi | JoinType::Anti
+                ) =>
+            {
+                Self::probe_side_scan(&join.probe)
+            }
+            _ => None,
+        }
+    }
+
     fn expand_build_side_pipeline(
         &mut self,
         build: &PhysicalPlan,
@@ -265,11 +342,16 @@ impl PipelineBuilder {
         }
         let func_ctx = self.ctx.try_get_function_context()?;
 
+        let subexpressions = CommonSubexpressions::extract(&[predicate]);
+        let common_nodes = Self::build_common_expressions(&subexpressions)?;
+        let predicate = Evaluator::compile_physical_scalar(&subexpressions.scalars[0])?;
+
         self.main_pipeline.add_transform(|input, output| {
             TransformFilterV2::try_create(
                 input,
                 output,
-                Evaluator::eval_physical_scalar(&predicate)?,
+                common_nodes.clone(),
+                predicate.clone(),
                 func_ctx.clone(),
             )
         })?;
@@ -291,10 +373,18 @@ impl PipelineBuilder {
     fn build_eval_scalar(&mut self, eval_scalar: &EvalScalar) -> Result<()> {
         self.build_pipeline(&eval_scalar.input)?;
 
-        let eval_nodes: Vec<(EvalNode, String)> = eval_scalar
+        let scalars: Vec<PhysicalScalar> = eval_scalar
+            .scalars
+            .iter()
+            .map(|(scalar, _)| scalar.clone())
+            .collect();
+        let subexpressions = CommonSubexpressions::extract(&scalars);
+        let common_nodes = Self::build_common_expressions(&subexpressions)?;
+        let eval_nodes: Vec<(EvalNode, String)> = subexpressions
             .scalars
             .iter()
-            .map(|(scalar, id)| Ok((Evaluator::eval_physical_scalar(scalar)?, id.clone())))
+            .zip(eval_scalar.scalars.iter())
+            .map(|(scalar, (_, id))| Ok((Evaluator::compile_physical_scalar(scalar)?, id.clone())))
             .collect::<Result<_>>()?;
         let func_ctx = self.ctx.try_get_function_context()?;
 
@@ -302,6 +392,7 @@ impl PipelineBuilder {
             Ok(ExpressionTransformV2::create(
                 input,
                 output,
+                common_nodes.clone(),
                 eval_nodes.clone(),
                 func_ctx.clone(),
             ))
@@ -310,6 +401,16 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    fn build_common_expressions(
+        subexpressions: &CommonSubexpressions,
+    ) -> Result<Vec<(EvalNode, String)>> {
+        subexpressions
+            .common
+            .iter()
+            .map(|(scalar, id)| Ok((Evaluator::compile_physical_scalar(scalar)?, id.clone())))
+            .collect()
+    }
+
     fn build_aggregate_partial(&mut self, aggregate: &AggregatePartial) -> Result<()> {
         self.build_pipeline(&aggregate.input)?;
         let params = Self::build_aggregator_params(
@@ -411,17 +512,40 @@ impl PipelineBuilder {
             })
             .collect();
 
+        self.build_sort_pipeline(sort.limit, sort_desc)
+    }
+
+    /// Sort the output of the main pipeline into a single stream.
+    fn build_sort_pipeline(
+        &mut self,
+        limit: Option<usize>,
+        sort_desc: Vec<SortColumnDescription>,
+    ) -> Result<()> {
         // Sort
         self.main_pipeline.add_transform(|input, output| {
-            TransformSortPartial::try_create(input, output, sort.limit, sort_desc.clone())
+            TransformSortPartial::try_create(input, output, limit, sort_desc.clone())
         })?;
 
+        // Sorted blocks may not fit in memory, merge them with an external sort in single thread
+        if self.ctx.get_settings().get_spilling_memory_threshold()? > 0 {
+            self.main_pipeline.resize(1)?;
+            return self.main_pipeline.add_transform(|input, output| {
+                TransformExternalSort::try_create(
+                    input,
+                    output,
+                    self.ctx.clone(),
+                    limit,
+                    sort_desc.clone(),
+                )
+            });
+        }
+
         // Merge
         self.main_pipeline.add_transform(|input, output| {
             TransformSortMerge::try_create(
                 input,
                 output,
-                SortMergeCompactor::new(sort.limit, sort_desc.clone()),
+                SortMergeCompactor::new(limit, sort_desc.clone()),
             )
         })?;
 
@@ -432,7 +556,49 @@ impl PipelineBuilder {
             TransformSortMerge::try_create(
                 input,
                 output,
-                SortMergeCompactor::new(sort.limit, sort_desc.clone()),
+                SortMergeCompactor::new(limit, sort_desc.clone()),
+            )
+        })
+    }
+
+    fn build_window(&mut self, window: &Window) -> Result<()> {
+        self.build_pipeline(&window.input)?;
+
+        // Rows of a partition are adjacent once sorted by the partition keys first
+        let sort_desc: Vec<SortColumnDescription> = window
+            .partition_by
+            .iter()
+            .map(|column_id| SortColumnDescription {
+                column_name: column_id.clone(),
+                asc: true,
+                nulls_first: false,
+            })
+            .chain(window.order_by.iter().map(|desc| SortColumnDescription {
+                column_name: desc.order_by.clone(),
+                asc: desc.asc,
+                nulls_first: desc.nulls_first,
+            }))
+            .collect();
+
+        if sort_desc.is_empty() {
+            self.main_pipeline.resize(1)?;
+        } else {
+            self.build_sort_pipeline(None, sort_desc)?;
+        }
+
+        let order_by: Vec<ColumnID> = window
+            .order_by
+            .iter()
+            .map(|desc| desc.order_by.clone())
+            .collect();
+        self.main_pipeline.add_transform(|input, output| {
+            TransformWindow::try_create(
+                input,
+                output,
+                window.func.clone(),
+                window.partition_by.clone(),
+                order_by.clone(),
+                window.frame.clone(),
             )
         })
     }
@@ -547,6 +713,36 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    fn build_recursive_union(&mut self, union: &RecursiveUnion) -> Result<()> {
+        // The iterations depend on each other, so they are evaluated one by one in a source.
+        let output = OutputPort::create();
+        let mut source_builder = SourcePipeBuilder::create();
+        source_builder.add_source(
+            output.clone(),
+            RecursiveCteSource::try_create(self.ctx.clone(), output, union.clone())?,
+        );
+        self.main_pipeline.add_pipe(source_builder.finalize());
+        Ok(())
+    }
+
+    fn build_recursive_cte_scan(&mut self, scan: &RecursiveCteScan) -> Result<()> {
+        let blocks = self
+            .ctx
+            .get_recursive_cte_table(&scan.cte_name)
+            .into_iter()
+            .map(|block| DataBlock::create(scan.schema.clone(), block.columns().to_vec()))
+            .collect();
+
+        let output = OutputPort::create();
+        let mut source_builder = SourcePipeBuilder::create();
+        source_builder.add_source(
+            output.clone(),
+            BlocksSource::create(self.ctx.clone(), output, Arc::new(Mutex::new(blocks)))?,
+        );
+        self.main_pipeline.add_pipe(source_builder.finalize());
+        Ok(())
+    }
+
     pub fn build_distributed_insert_select(
         &mut self,
         insert_select: &DistributedInsertSelect,
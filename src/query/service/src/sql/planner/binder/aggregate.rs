@@ -24,6 +24,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planner::MetadataRef;
 
+use crate::sql::binder::contain_window_function;
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::select::SelectList;
 use crate::sql::binder::Binder;
@@ -43,6 +44,7 @@ use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarExpr;
 use crate::sql::plans::ScalarItem;
+use crate::sql::plans::UDFServerCall;
 use crate::sql::BindContext;
 
 #[derive(Default, Clone, PartialEq, Eq, Debug)]
@@ -126,6 +128,18 @@ impl<'a> AggregateRewriter<'a> {
                 }
                 .into())
             }
+            Scalar::UDFServerCall(udf) => {
+                let new_args = udf
+                    .arguments
+                    .iter()
+                    .map(|arg| self.visit(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(UDFServerCall {
+                    arguments: new_args,
+                    ..udf.clone()
+                }
+                .into())
+            }
             Scalar::CastExpr(cast) => Ok(CastExpr {
                 argument: Box::new(self.visit(&cast.argument)?),
                 from_type: cast.from_type.clone(),
@@ -137,6 +151,16 @@ impl<'a> AggregateRewriter<'a> {
             Scalar::SubqueryExpr(_) => Ok(scalar.clone()),
 
             Scalar::AggregateFunction(agg_func) => self.replace_aggregate_function(agg_func),
+
+            // The window functions are evaluated after aggregation, so the aggregate functions
+            // in their arguments are rewritten as well.
+            Scalar::WindowFunction(func) => {
+                let mut func = func.clone();
+                for scalar in func.inner_scalars_mut() {
+                    *scalar = self.visit(scalar)?;
+                }
+                Ok(func.into())
+            }
         }
     }
 
@@ -325,6 +349,7 @@ impl<'a> Binder {
             } = expr
             {
                 let (scalar, alias) = Self::resolve_index_item(expr, *index, select_list)?;
+                Self::check_group_item(expr, &scalar)?;
                 let key = format!("{:?}", &scalar);
                 if let Entry::Vacant(entry) = bind_context.aggregate_info.group_items_map.entry(key)
                 {
@@ -355,6 +380,7 @@ impl<'a> Binder {
                 .bind(expr)
                 .await
                 .or_else(|e| Self::resolve_alias_item(bind_context, expr, available_aliases, e))?;
+            Self::check_group_item(expr, &scalar_expr)?;
 
             if bind_context
                 .aggregate_info
@@ -393,6 +419,15 @@ impl<'a> Binder {
         Ok(())
     }
 
+    fn check_group_item(expr: &Expr<'a>, scalar: &Scalar) -> Result<()> {
+        if contain_window_function(scalar)? {
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                "window functions are not allowed in GROUP BY".to_string(),
+            )));
+        }
+        Ok(())
+    }
+
     fn resolve_index_item(
         expr: &Expr<'a>,
         index: u64,
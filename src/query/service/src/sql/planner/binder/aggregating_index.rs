@@ -0,0 +1,445 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_ast::ast::BinaryOperator;
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::OrderByExpr;
+use common_ast::ast::SelectStmt;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::TableAlias;
+use common_ast::ast::TableReference;
+use common_ast::parser::token::Token;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use common_users::UserApiProvider;
+
+use crate::sql::binder::Binder;
+use crate::sql::optimizer::SExpr;
+use crate::sql::planner::semantic::normalize_identifier;
+use crate::sql::planner::semantic::NameResolutionContext;
+use crate::sql::BindContext;
+use crate::storages::fuse::AggregatingIndexTable;
+use crate::storages::fuse::FuseTable;
+use crate::storages::index::AggregatingIndexMeta;
+use crate::storages::Table;
+
+/// A `SELECT` rewritten to read the pre-aggregated rows of an aggregating index.
+pub struct AggregatingIndexRewrite<'a> {
+    pub stmt: SelectStmt<'a>,
+    pub order_by: Vec<OrderByExpr<'a>>,
+    catalog: String,
+    database: String,
+    table: Arc<dyn Table>,
+    alias: Option<TableAlias<'a>>,
+}
+
+impl<'a> Binder {
+    /// Try to answer the aggregation of `stmt` from an aggregating index of its table.
+    ///
+    /// The query can be rewritten if it groups by the columns of the index and all of its
+    /// aggregates can be merged from the aggregates of the index, e.g. `COUNT(a)` is the
+    /// `SUM` of the partial counts.
+    pub(super) async fn rewrite_by_aggregating_index(
+        &self,
+        bind_context: &BindContext,
+        stmt: &SelectStmt<'a>,
+        order_by: &[OrderByExpr<'a>],
+    ) -> Result<Option<AggregatingIndexRewrite<'a>>> {
+        if stmt.group_by.is_empty() || !self.ctx.get_settings().get_enable_aggregating_index()? {
+            return Ok(None);
+        }
+        let (catalog, database, table, alias) = match stmt.from.as_slice() {
+            [
+                TableReference::Table {
+                    catalog,
+                    database,
+                    table,
+                    alias,
+                    travel_point: None,
+                    sample: None,
+                    ..
+                },
+            ] if alias
+                .as_ref()
+                .map_or(true, |alias| alias.columns.is_empty()) =>
+            {
+                (catalog, database, table, alias)
+            }
+            _ => return Ok(None),
+        };
+        let table_name = normalize_identifier(table, &self.name_resolution_ctx).name;
+        if bind_context.ctes_map.read().contains_key(&table_name) {
+            return Ok(None);
+        }
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+
+        let tenant = self.ctx.get_tenant();
+        let table = self
+            .resolve_data_source(&tenant, &catalog, &database, &table_name, &None)
+            .await?;
+        let fuse_table = match FuseTable::try_from_table(table.as_ref()) {
+            Ok(fuse_table) => fuse_table,
+            Err(_) => return Ok(None),
+        };
+        let indexes = fuse_table.aggregating_indexes()?;
+        if indexes.is_empty() {
+            return Ok(None);
+        }
+
+        // The policies are applied to the rows of the table, which are not in the index.
+        let user_api = UserApiProvider::instance();
+        let has_row_access_policy = user_api
+            .get_row_access_policies(&tenant)
+            .await?
+            .iter()
+            .any(|p| p.catalog == catalog && p.database == database && p.table == table_name);
+        let has_masking_policy = user_api
+            .get_masking_policies(&tenant)
+            .await?
+            .iter()
+            .any(|p| p.is_attached_to(&catalog, &database, &table_name));
+        if has_row_access_policy || has_masking_policy {
+            return Ok(None);
+        }
+
+        for index in indexes {
+            let rewriter = IndexRewriter::new(&index, &self.name_resolution_ctx, stmt);
+            if let Some((stmt, order_by)) = rewriter.rewrite_select(stmt, order_by) {
+                return Ok(Some(AggregatingIndexRewrite {
+                    stmt,
+                    order_by,
+                    catalog,
+                    database,
+                    table: AggregatingIndexTable::create(fuse_table, index)?,
+                    alias: alias.clone(),
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    pub(super) async fn bind_aggregating_index_table(
+        &mut self,
+        bind_context: &BindContext,
+        rewrite: &AggregatingIndexRewrite<'a>,
+    ) -> Result<(SExpr, BindContext)> {
+        let table_index = self.metadata.write().add_table(
+            rewrite.catalog.clone(),
+            rewrite.database.clone(),
+            rewrite.table.clone(),
+        );
+        let (s_expr, mut bind_context) = self
+            .bind_base_table(bind_context, &rewrite.database, table_index)
+            .await?;
+        if let Some(alias) = &rewrite.alias {
+            bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
+        }
+        Ok((s_expr, bind_context))
+    }
+}
+
+struct IndexRewriter<'i> {
+    index: &'i AggregatingIndexMeta,
+    name_resolution_ctx: &'i NameResolutionContext,
+    // Aliases of the select list, which can be referenced by `HAVING` and `ORDER BY`.
+    aliases: Vec<String>,
+}
+
+impl<'i> IndexRewriter<'i> {
+    fn new(
+        index: &'i AggregatingIndexMeta,
+        name_resolution_ctx: &'i NameResolutionContext,
+        stmt: &SelectStmt,
+    ) -> Self {
+        let aliases = stmt
+            .select_list
+            .iter()
+            .filter_map(|target| match target {
+                SelectTarget::AliasedExpr {
+                    alias: Some(alias), ..
+                } => Some(normalize_identifier(alias, name_resolution_ctx).name),
+                _ => None,
+            })
+            .collect();
+        IndexRewriter {
+            index,
+            name_resolution_ctx,
+            aliases,
+        }
+    }
+
+    fn rewrite_select<'a>(
+        &self,
+        stmt: &SelectStmt<'a>,
+        order_by: &[OrderByExpr<'a>],
+    ) -> Option<(SelectStmt<'a>, Vec<OrderByExpr<'a>>)> {
+        let mut select_list = Vec::with_capacity(stmt.select_list.len());
+        for target in &stmt.select_list {
+            match target {
+                SelectTarget::AliasedExpr { expr, alias } => {
+                    let rewritten = self.rewrite_expr(expr, true, false)?;
+                    // Keep the output name of the original expression.
+                    let alias = match (alias, expr.as_ref()) {
+                        (Some(_), _) | (None, Expr::ColumnRef { .. }) => alias.clone(),
+                        (None, _) => Some(Identifier {
+                            name: format!("{:#}", expr).to_lowercase(),
+                            quote: None,
+                            span: expr.span()[0].clone(),
+                        }),
+                    };
+                    select_list.push(SelectTarget::AliasedExpr {
+                        expr: Box::new(rewritten),
+                        alias,
+                    });
+                }
+                // `*` would expose the columns of the index.
+                SelectTarget::QualifiedName(_) => return None,
+            }
+        }
+
+        let selection = match &stmt.selection {
+            Some(expr) => Some(self.rewrite_expr(expr, false, false)?),
+            None => None,
+        };
+        let group_by = stmt
+            .group_by
+            .iter()
+            .map(|expr| self.rewrite_expr(expr, false, false))
+            .collect::<Option<Vec<_>>>()?;
+        let having = match &stmt.having {
+            Some(expr) => Some(self.rewrite_expr(expr, true, true)?),
+            None => None,
+        };
+        let order_by = order_by
+            .iter()
+            .map(|order| {
+                Some(OrderByExpr {
+                    expr: self.rewrite_expr(&order.expr, true, true)?,
+                    asc: order.asc,
+                    nulls_first: order.nulls_first,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let stmt = SelectStmt {
+            span: stmt.span,
+            distinct: stmt.distinct,
+            select_list,
+            from: stmt.from.clone(),
+            selection,
+            group_by,
+            having,
+        };
+        Some((stmt, order_by))
+    }
+
+    // Rewrite the expression over the columns of the index, return `None` if it can't be
+    // answered by the index.
+    fn rewrite_expr<'a>(
+        &self,
+        expr: &Expr<'a>,
+        allow_aggregate: bool,
+        allow_alias: bool,
+    ) -> Option<Expr<'a>> {
+        let rewrite = |expr: &Expr<'a>| self.rewrite_expr(expr, allow_aggregate, allow_alias);
+        let rewrite_box = |expr: &Expr<'a>| rewrite(expr).map(Box::new);
+        let rewritten = match expr {
+            Expr::ColumnRef { column, .. } => {
+                let name = normalize_identifier(column, self.name_resolution_ctx).name;
+                if !self.index.group_by.contains(&name)
+                    && !(allow_alias && self.aliases.contains(&name))
+                {
+                    return None;
+                }
+                expr.clone()
+            }
+            Expr::Literal { .. } => expr.clone(),
+            Expr::CountAll { span } if allow_aggregate => {
+                self.merge_aggregate(span, "count", None)?
+            }
+            Expr::FunctionCall {
+                span,
+                distinct,
+                name,
+                args,
+                params,
+                window,
+            } if AggregateFunctionFactory::instance().check(&name.name) => {
+                if !allow_aggregate || *distinct || window.is_some() || !params.is_empty() {
+                    return None;
+                }
+                let func_name = name.name.to_lowercase();
+                let arg = match args.as_slice() {
+                    [] => None,
+                    [Expr::ColumnRef { column, .. }] => {
+                        Some(normalize_identifier(column, self.name_resolution_ctx).name)
+                    }
+                    _ => return None,
+                };
+                self.merge_aggregate(span, &func_name, arg.as_deref())?
+            }
+            Expr::FunctionCall {
+                span,
+                distinct: false,
+                name,
+                args,
+                params,
+                window: None,
+            } => Expr::FunctionCall {
+                span: *span,
+                distinct: false,
+                name: name.clone(),
+                args: args.iter().map(rewrite).collect::<Option<_>>()?,
+                params: params.clone(),
+                window: None,
+            },
+            Expr::IsNull { span, expr, not } => Expr::IsNull {
+                span: *span,
+                expr: rewrite_box(expr)?,
+                not: *not,
+            },
+            Expr::InList {
+                span,
+                expr,
+                list,
+                not,
+            } => Expr::InList {
+                span: *span,
+                expr: rewrite_box(expr)?,
+                list: list.iter().map(rewrite).collect::<Option<_>>()?,
+                not: *not,
+            },
+            Expr::Between {
+                span,
+                expr,
+                low,
+                high,
+                not,
+            } => Expr::Between {
+                span: *span,
+                expr: rewrite_box(expr)?,
+                low: rewrite_box(low)?,
+                high: rewrite_box(high)?,
+                not: *not,
+            },
+            Expr::BinaryOp {
+                span,
+                op,
+                left,
+                right,
+            } => Expr::BinaryOp {
+                span: *span,
+                op: op.clone(),
+                left: rewrite_box(left)?,
+                right: rewrite_box(right)?,
+            },
+            Expr::UnaryOp { span, op, expr } => Expr::UnaryOp {
+                span: *span,
+                op: op.clone(),
+                expr: rewrite_box(expr)?,
+            },
+            Expr::Cast {
+                span,
+                expr,
+                target_type,
+                pg_style,
+            } => Expr::Cast {
+                span: *span,
+                expr: rewrite_box(expr)?,
+                target_type: target_type.clone(),
+                pg_style: *pg_style,
+            },
+            Expr::TryCast {
+                span,
+                expr,
+                target_type,
+            } => Expr::TryCast {
+                span: *span,
+                expr: rewrite_box(expr)?,
+                target_type: target_type.clone(),
+            },
+            Expr::Case {
+                span,
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => Expr::Case {
+                span: *span,
+                operand: match operand {
+                    Some(operand) => Some(rewrite_box(operand)?),
+                    None => None,
+                },
+                conditions: conditions.iter().map(rewrite).collect::<Option<_>>()?,
+                results: results.iter().map(rewrite).collect::<Option<_>>()?,
+                else_result: match else_result {
+                    Some(else_result) => Some(rewrite_box(else_result)?),
+                    None => None,
+                },
+            },
+            _ => return None,
+        };
+        Some(rewritten)
+    }
+
+    // Merge the partial aggregates of the index into the aggregate `func_name(arg)`.
+    fn merge_aggregate<'a>(
+        &self,
+        span: &'a [Token<'a>],
+        func_name: &str,
+        arg: Option<&str>,
+    ) -> Option<Expr<'a>> {
+        let merge = |merge_func: &str, partial_func: &str| {
+            let index = self.index.find_aggregate(partial_func, arg)?;
+            let ident = |name: String| Identifier {
+                name,
+                quote: None,
+                span: span[0].clone(),
+            };
+            Some(Expr::FunctionCall {
+                span,
+                distinct: false,
+                name: ident(merge_func.to_string()),
+                args: vec![Expr::ColumnRef {
+                    span,
+                    database: None,
+                    table: None,
+                    column: ident(AggregatingIndexMeta::column_name(index)),
+                }],
+                params: vec![],
+                window: None,
+            })
+        };
+        match func_name {
+            "count" => merge("sum", "count"),
+            "sum" | "min" | "max" => merge(func_name, func_name),
+            "avg" => Some(Expr::BinaryOp {
+                span,
+                op: BinaryOperator::Divide,
+                left: Box::new(merge("sum", "sum")?),
+                right: Box::new(merge("sum", "count")?),
+            }),
+            _ => None,
+        }
+    }
+}
@@ -29,6 +29,7 @@ use common_planner::IndexType;
 use parking_lot::RwLock;
 
 use super::AggregateInfo;
+use super::WindowInfo;
 use crate::sql::normalize_identifier;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Scalar;
@@ -91,6 +92,8 @@ pub struct BindContext {
 
     pub aggregate_info: AggregateInfo,
 
+    pub window_info: WindowInfo,
+
     /// True if there is aggregation in current context, which means
     /// non-grouping columns cannot be referenced outside aggregation
     /// functions, otherwise a grouping error will be raised.
@@ -115,6 +118,7 @@ impl BindContext {
             parent: None,
             columns: Vec::new(),
             aggregate_info: AggregateInfo::default(),
+            window_info: WindowInfo::default(),
             in_grouping: false,
             format: None,
             ctes_map: Arc::new(RwLock::new(HashMap::new())),
@@ -126,6 +130,7 @@ impl BindContext {
             parent: Some(parent.clone()),
             columns: vec![],
             aggregate_info: Default::default(),
+            window_info: Default::default(),
             in_grouping: false,
             format: None,
             ctes_map: parent.ctes_map.clone(),
@@ -34,6 +34,7 @@ use common_planner::plans::DropRolePlan;
 use common_planner::plans::DropStagePlan;
 use common_planner::plans::DropUDFPlan;
 use common_planner::plans::DropUserPlan;
+use common_planner::plans::SetRolePlan;
 use common_planner::plans::ShowGrantsPlan;
 use common_planner::plans::UseDatabasePlan;
 use common_planner::MetadataRef;
@@ -148,12 +149,15 @@ impl<'a> Binder {
                 self.bind_show_tables_status(bind_context, stmt).await?
             }
             Statement::CreateTable(stmt) => self.bind_create_table(stmt).await?,
+            Statement::AttachTable(stmt) => self.bind_attach_table(stmt).await?,
             Statement::DropTable(stmt) => self.bind_drop_table(stmt).await?,
             Statement::UndropTable(stmt) => self.bind_undrop_table(stmt).await?,
             Statement::AlterTable(stmt) => self.bind_alter_table(bind_context, stmt).await?,
             Statement::RenameTable(stmt) => self.bind_rename_table(stmt).await?,
             Statement::TruncateTable(stmt) => self.bind_truncate_table(stmt).await?,
+            Statement::FlashbackTable(stmt) => self.bind_flashback_table(stmt).await?,
             Statement::OptimizeTable(stmt) => self.bind_optimize_table(stmt).await?,
+            Statement::AnalyzeTable(stmt) => self.bind_analyze_table(stmt).await?,
             Statement::ExistsTable(stmt) => self.bind_exists_table(stmt).await?,
 
             // Views
@@ -161,6 +165,25 @@ impl<'a> Binder {
             Statement::AlterView(stmt) => self.bind_alter_view(stmt).await?,
             Statement::DropView(stmt) => self.bind_drop_view(stmt).await?,
 
+            // Streams
+            Statement::CreateStream(stmt) => self.bind_create_stream(stmt).await?,
+            Statement::DropStream(stmt) => self.bind_drop_stream(stmt).await?,
+
+            // Aggregating indexes
+            Statement::CreateAggregatingIndex(stmt) => {
+                self.bind_create_aggregating_index(stmt).await?
+            }
+            Statement::DropAggregatingIndex(stmt) => self.bind_drop_aggregating_index(stmt).await?,
+
+            // Indexes
+            Statement::CreateIndex(stmt) => self.bind_create_index(stmt).await?,
+            Statement::DropIndex(stmt) => self.bind_drop_index(stmt).await?,
+
+            // Virtual columns
+            Statement::CreateVirtualColumns(stmt) => self.bind_create_virtual_columns(stmt).await?,
+            Statement::AlterVirtualColumns(stmt) => self.bind_alter_virtual_columns(stmt).await?,
+            Statement::DropVirtualColumns(stmt) => self.bind_drop_virtual_columns(stmt).await?,
+
             // Users
             Statement::CreateUser(stmt) => self.bind_create_user(stmt).await?,
             Statement::DropUser { if_exists, user } => Plan::DropUser(Box::new(DropUserPlan {
@@ -186,6 +209,9 @@ impl<'a> Binder {
                 if_exists: *if_exists,
                 role_name: role_name.to_string(),
             })),
+            Statement::SetRole { role_name } => Plan::SetRole(Box::new(SetRolePlan {
+                role_name: role_name.clone(),
+            })),
 
             // Stages
             Statement::ShowStages => self.bind_rewrite_to_query(bind_context, "SELECT name, stage_type, number_of_files, creator, comment FROM system.stages ORDER BY name", RewriteKind::ShowStages).await?,
@@ -239,6 +265,8 @@ impl<'a> Binder {
                     parameters: validator.parameters,
                     definition: definition.to_string(),
                     description: description.clone().unwrap_or_default(),
+                    overloads: vec![],
+                    server: None,
                 };
 
                 Plan::CreateUDF(Box::new(CreateUDFPlan {
@@ -246,6 +274,7 @@ impl<'a> Binder {
                     udf
                 }))
             },
+            Statement::CreateUDFServer(stmt) => self.bind_create_udf_server(stmt).await?,
             Statement::AlterUDF {
                 udf_name,
                 parameters,
@@ -263,6 +292,8 @@ impl<'a> Binder {
                     parameters: validator.parameters,
                     definition: definition.to_string(),
                     description: description.clone().unwrap_or_default(),
+                    overloads: vec![],
+                    server: None,
                 };
 
                 Plan::AlterUDF(Box::new(AlterUDFPlan {
@@ -283,6 +314,33 @@ impl<'a> Binder {
 
             Statement::Presign(stmt) => self.bind_presign(bind_context, stmt).await?,
 
+            // Row access policies
+            Statement::ShowRowAccessPolicies => self.bind_rewrite_to_query(bind_context, "SELECT name, catalog, database, table, role, predicate FROM system.row_access_policies ORDER BY name", RewriteKind::ShowRowAccessPolicies).await?,
+            Statement::CreateRowAccessPolicy(stmt) => {
+                self.bind_create_row_access_policy(stmt).await?
+            }
+            Statement::DropRowAccessPolicy(stmt) => self.bind_drop_row_access_policy(stmt).await?,
+
+            // Masking policies
+            Statement::ShowMaskingPolicies => self.bind_rewrite_to_query(bind_context, "SELECT name, parameter, body, exempt_roles, columns FROM system.masking_policies ORDER BY name", RewriteKind::ShowMaskingPolicies).await?,
+            Statement::CreateMaskingPolicy(stmt) => self.bind_create_masking_policy(stmt).await?,
+            Statement::DropMaskingPolicy(stmt) => self.bind_drop_masking_policy(stmt).await?,
+
+            // Network policies
+            Statement::ShowNetworkPolicies => self.bind_rewrite_to_query(bind_context, "SELECT name, allowed_ip_list, blocked_ip_list, comment FROM system.network_policies ORDER BY name", RewriteKind::ShowNetworkPolicies).await?,
+            Statement::CreateNetworkPolicy(stmt) => self.bind_create_network_policy(stmt).await?,
+            Statement::DropNetworkPolicy(stmt) => self.bind_drop_network_policy(stmt).await?,
+
+            // Workload groups
+            Statement::ShowWorkloadGroups => self.bind_rewrite_to_query(bind_context, "SELECT name, cpu_share, memory_limit, max_concurrency, max_scan_bytes, comment FROM system.workload_groups ORDER BY name", RewriteKind::ShowWorkloadGroups).await?,
+            Statement::CreateWorkloadGroup(stmt) => self.bind_create_workload_group(stmt).await?,
+            Statement::DropWorkloadGroup(stmt) => self.bind_drop_workload_group(stmt).await?,
+
+            // Tasks
+            Statement::ShowTasks => self.bind_rewrite_to_query(bind_context, "SELECT name, schedule, owner, definition, error_notification, comment, created_on FROM system.tasks ORDER BY name", RewriteKind::ShowTasks).await?,
+            Statement::CreateTask(stmt) => self.bind_create_task(stmt).await?,
+            Statement::DropTask(stmt) => self.bind_drop_task(stmt).await?,
+
             Statement::SetVariable {
                 is_global,
                 variable,
@@ -324,6 +382,10 @@ impl<'a> Binder {
             Statement::ShowGrantsOfShare(stmt) => {
                 self.bind_show_grants_of_share(stmt).await?
             }
+
+            Statement::Begin => Plan::Begin,
+            Statement::Commit => Plan::Commit,
+            Statement::Rollback => Plan::Rollback,
         };
         Ok(plan)
     }
@@ -18,12 +18,14 @@ use std::sync::Arc;
 
 use common_ast::ast::CopyStmt;
 use common_ast::ast::CopyUnit;
+use common_ast::ast::Expr;
 use common_ast::ast::Query;
 use common_ast::ast::Statement;
 use common_ast::parser::parse_sql;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_ast::Dialect;
+use common_ast::DisplayError;
 use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -32,6 +34,7 @@ use common_legacy_planners::ReadDataSourcePlan;
 use common_legacy_planners::SourceInfo;
 use common_legacy_planners::StageTableInfo;
 use common_meta_types::FileFormatOptions;
+use common_meta_types::OnErrorMode;
 use common_meta_types::StageFileFormatType;
 use common_meta_types::UserStageInfo;
 use common_storage::parse_uri_location;
@@ -52,6 +55,12 @@ impl<'a> Binder {
         bind_context: &BindContext,
         stmt: &CopyStmt<'a>,
     ) -> Result<Plan> {
+        if !stmt.partition_by.is_empty() && matches!(stmt.dst, CopyUnit::Table { .. }) {
+            return Err(ErrorCode::SyntaxException(
+                "PARTITION BY is only supported by COPY INTO <location>",
+            ));
+        }
+
         match (&stmt.src, &stmt.dst) {
             (
                 CopyUnit::StageLocation { name, path },
@@ -235,6 +244,7 @@ impl<'a> Binder {
                 stage_info,
                 path,
                 files: vec![],
+                partition_by: vec![],
             }),
             scan_fields: None,
             parts: vec![],
@@ -294,6 +304,7 @@ impl<'a> Binder {
                 stage_info,
                 path,
                 files: vec![],
+                partition_by: vec![],
             }),
             scan_fields: None,
             parts: vec![],
@@ -335,11 +346,8 @@ impl<'a> Binder {
         let backtrace = Backtrace::new();
         let sub_stmt_msg = parse_sql(&tokens, Dialect::PostgreSQL, &backtrace)?;
         let sub_stmt = sub_stmt_msg.0;
-        let query = match &sub_stmt {
-            Statement::Query(query) => {
-                self.bind_statement(bind_context, &Statement::Query(query.clone()))
-                    .await?
-            }
+        let (query, partition_by) = match &sub_stmt {
+            Statement::Query(query) => self.bind_copy_query(bind_context, stmt, query).await?,
             _ => {
                 return Err(ErrorCode::SyntaxException(
                     "COPY INTO <location> FROM <non-query> is invalid",
@@ -360,6 +368,7 @@ impl<'a> Binder {
             path,
             validation_mode,
             from: Box::new(query),
+            partition_by,
         })))
     }
 
@@ -380,11 +389,8 @@ impl<'a> Binder {
         let backtrace = Backtrace::new();
         let sub_stmt_msg = parse_sql(&tokens, Dialect::PostgreSQL, &backtrace)?;
         let sub_stmt = sub_stmt_msg.0;
-        let query = match &sub_stmt {
-            Statement::Query(query) => {
-                self.bind_statement(bind_context, &Statement::Query(query.clone()))
-                    .await?
-            }
+        let (query, partition_by) = match &sub_stmt {
+            Statement::Query(query) => self.bind_copy_query(bind_context, stmt, query).await?,
             _ => {
                 return Err(ErrorCode::SyntaxException(
                     "COPY INTO <location> FROM <non-query> is invalid",
@@ -411,6 +417,7 @@ impl<'a> Binder {
             path,
             validation_mode,
             from: Box::new(query),
+            partition_by,
         })))
     }
 
@@ -423,9 +430,7 @@ impl<'a> Binder {
         dst_stage: &str,
         dst_path: &str,
     ) -> Result<Plan> {
-        let query = self
-            .bind_statement(bind_context, &Statement::Query(Box::new(src_query.clone())))
-            .await?;
+        let (query, partition_by) = self.bind_copy_query(bind_context, stmt, src_query).await?;
 
         // Validation mode.
         let validation_mode = ValidationMode::from_str(stmt.validation_mode.as_str())
@@ -440,6 +445,7 @@ impl<'a> Binder {
             path,
             validation_mode,
             from: Box::new(query),
+            partition_by,
         })))
     }
 
@@ -452,9 +458,7 @@ impl<'a> Binder {
         src_query: &Query<'_>,
         dst_uri_location: &UriLocation,
     ) -> Result<Plan> {
-        let query = self
-            .bind_statement(bind_context, &Statement::Query(Box::new(src_query.clone())))
-            .await?;
+        let (query, partition_by) = self.bind_copy_query(bind_context, stmt, src_query).await?;
 
         // Validation mode.
         let validation_mode = ValidationMode::from_str(stmt.validation_mode.as_str())
@@ -475,9 +479,61 @@ impl<'a> Binder {
             path,
             validation_mode,
             from: Box::new(query),
+            partition_by,
         })))
     }
 
+    /// Binds the query to unload, the values of the keys of `PARTITION BY` are appended to the
+    /// columns of the query as strings. Returns the names of the keys as well.
+    async fn bind_copy_query(
+        &mut self,
+        bind_context: &BindContext,
+        stmt: &CopyStmt<'a>,
+        query: &Query<'_>,
+    ) -> Result<(Plan, Vec<String>)> {
+        if stmt.partition_by.is_empty() {
+            let plan = self
+                .bind_statement(bind_context, &Statement::Query(Box::new(query.clone())))
+                .await?;
+            return Ok((plan, vec![]));
+        }
+
+        let mut names = Vec::with_capacity(stmt.partition_by.len());
+        let mut targets = Vec::with_capacity(stmt.partition_by.len());
+        for (index, key) in stmt.partition_by.iter().enumerate() {
+            let name = match (&key.alias, &key.expr) {
+                (Some(alias), _) => alias,
+                (None, Expr::ColumnRef { column, .. }) => column,
+                (None, expr) => {
+                    return Err(ErrorCode::SemanticError(expr.span().display_error(
+                        "the PARTITION BY expression requires an alias".to_string(),
+                    )));
+                }
+            };
+            let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+            if names.contains(&name) {
+                return Err(ErrorCode::SemanticError(format!(
+                    "duplicate PARTITION BY key {}",
+                    name
+                )));
+            }
+            names.push(name);
+            // the keys are evaluated on the columns of the query
+            targets.push(format!("to_varchar({}) AS __partition_{}", key.expr, index));
+        }
+
+        let subquery = format!(
+            "SELECT *, {} FROM ({}) AS _unload",
+            targets.join(", "),
+            query
+        );
+        let tokens = tokenize_sql(&subquery)?;
+        let backtrace = Backtrace::new();
+        let (sub_stmt, _) = parse_sql(&tokens, Dialect::PostgreSQL, &backtrace)?;
+        let plan = self.bind_statement(bind_context, &sub_stmt).await?;
+        Ok((plan, names))
+    }
+
     fn apply_stage_options(
         &mut self,
         stmt: &CopyStmt<'a>,
@@ -489,17 +545,29 @@ impl<'a> Binder {
 
         // Copy options.
         {
-            // TODO(xuanwo): COPY should handle on error.
             // on_error.
-            // if !stmt.on_error.is_empty() {
-            //     stage_info.copy_options.on_error =
-            //         OnErrorMode::from_str(&self.on_error).map_err(ErrorCode::SyntaxException)?;
-            // }
+            if !stmt.on_error.is_empty() {
+                stage.copy_options.on_error =
+                    OnErrorMode::from_str(&stmt.on_error).map_err(ErrorCode::SyntaxException)?;
+            }
+
+            // max_errors.
+            if stmt.max_errors != 0 {
+                stage.copy_options.max_errors = stmt.max_errors;
+            }
 
             // size_limit.
             if stmt.size_limit != 0 {
                 stage.copy_options.size_limit = stmt.size_limit;
             }
+
+            // max_file_size and single, only for unloading.
+            if stmt.max_file_size != 0 {
+                stage.copy_options.max_file_size = stmt.max_file_size;
+            }
+            if stmt.single {
+                stage.copy_options.single = stmt.single;
+            }
             stage.copy_options.purge = stmt.purge;
         }
 
@@ -618,11 +686,56 @@ pub fn parse_copy_file_format_options(
     .parse()
     .map_err(ErrorCode::UnknownCompressionType)?;
 
+    // Quote and escape characters of CSV.
+    let quote = parse_single_char_option(file_format_options, "quote")?;
+    let escape = parse_single_char_option(file_format_options, "escape")?;
+
+    // NULL representation.
+    let null_display = parse_escape_string(
+        file_format_options
+            .get("null_display")
+            .unwrap_or(&"".to_string())
+            .as_bytes(),
+    );
+
+    // Allow rows with more or less fields than the columns.
+    let allow_column_count_mismatch = file_format_options
+        .get("allow_column_count_mismatch")
+        .unwrap_or(&"false".to_string())
+        .to_lowercase()
+        .parse::<bool>()
+        .map_err(|_| {
+            ErrorCode::SyntaxException("allow_column_count_mismatch must be true or false")
+        })?;
+
     Ok(FileFormatOptions {
         format: file_format,
         skip_header,
         field_delimiter,
         record_delimiter,
         compression,
+        quote,
+        escape,
+        null_display,
+        allow_column_count_mismatch,
     })
 }
+
+fn parse_single_char_option(
+    file_format_options: &BTreeMap<String, String>,
+    name: &str,
+) -> Result<String> {
+    let value = parse_escape_string(
+        file_format_options
+            .get(name)
+            .unwrap_or(&"".to_string())
+            .as_bytes(),
+    );
+    if value.len() > 1 {
+        return Err(ErrorCode::SyntaxException(format!(
+            "{} must be a single character, got {:?}",
+            name, value
+        )));
+    }
+    Ok(value)
+}
@@ -139,6 +139,8 @@ impl<'a> Binder {
                     .unwrap_or_else(|| self.ctx.get_current_database());
                 GrantObject::Database(catalog_name, database_name)
             }
+            AccountMgrLevel::Stage(stage_name) => GrantObject::Stage(stage_name.clone()),
+            AccountMgrLevel::UDF(udf_name) => GrantObject::UDF(udf_name.clone()),
         }
     }
 
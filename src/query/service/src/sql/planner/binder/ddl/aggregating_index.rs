@@ -0,0 +1,189 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_ast::ast::CreateAggregatingIndexStmt;
+use common_ast::ast::DropAggregatingIndexStmt;
+use common_ast::ast::Expr;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::SetExpr;
+use common_ast::ast::TableReference;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planner::plans::CreateAggregatingIndexPlan;
+use common_planner::plans::DropAggregatingIndexPlan;
+use common_storages_index::check_aggregating_index_function;
+
+use crate::sql::binder::Binder;
+use crate::sql::normalize_identifier;
+use crate::sql::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_aggregating_index(
+        &mut self,
+        stmt: &CreateAggregatingIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateAggregatingIndexStmt {
+            if_not_exists,
+            index_name,
+            query,
+        } = stmt;
+
+        let illegal = |msg: &str| {
+            ErrorCode::IllegalAggregatingIndexFormat(format!(
+                "Invalid aggregating index {}: {}",
+                index_name, msg
+            ))
+        };
+
+        let select = match &query.body {
+            SetExpr::Select(select)
+                if query.with.is_none()
+                    && query.order_by.is_empty()
+                    && query.limit.is_empty()
+                    && query.offset.is_none() =>
+            {
+                select
+            }
+            _ => return Err(illegal("expect a SELECT without WITH, ORDER BY or LIMIT")),
+        };
+        if select.distinct || select.selection.is_some() || select.having.is_some() {
+            return Err(illegal("DISTINCT, WHERE and HAVING are not supported"));
+        }
+
+        let (catalog, database, table) = match select.from.as_slice() {
+            [
+                TableReference::Table {
+                    catalog,
+                    database,
+                    table,
+                    alias: None,
+                    travel_point: None,
+                    sample: None,
+                    ..
+                },
+            ] => (catalog, database, table),
+            _ => return Err(illegal("expect a SELECT from a single table")),
+        };
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+
+        let mut group_by = Vec::with_capacity(select.group_by.len());
+        for expr in &select.group_by {
+            match expr {
+                Expr::ColumnRef { column, .. } => {
+                    group_by.push(normalize_identifier(column, &self.name_resolution_ctx).name)
+                }
+                _ => return Err(illegal("expect columns in GROUP BY")),
+            }
+        }
+        if group_by.is_empty() {
+            return Err(illegal("GROUP BY is required"));
+        }
+
+        let mut aggregates = vec![];
+        for target in &select.select_list {
+            let expr = match target {
+                SelectTarget::AliasedExpr { expr, .. } => expr,
+                SelectTarget::QualifiedName(_) => return Err(illegal("`*` is not supported")),
+            };
+            let aggregate = match expr.as_ref() {
+                Expr::ColumnRef { column, .. } => {
+                    let column = normalize_identifier(column, &self.name_resolution_ctx).name;
+                    if !group_by.contains(&column) {
+                        return Err(illegal(&format!("column {} is not in GROUP BY", column)));
+                    }
+                    continue;
+                }
+                Expr::CountAll { .. } => ("count".to_string(), None),
+                Expr::FunctionCall {
+                    distinct: false,
+                    name,
+                    args,
+                    params,
+                    window: None,
+                    ..
+                } if params.is_empty() => {
+                    let func_name = name.name.to_lowercase();
+                    check_aggregating_index_function(&func_name)?;
+                    let arg = match args.as_slice() {
+                        [] if func_name == "count" => None,
+                        [Expr::ColumnRef { column, .. }] => {
+                            Some(normalize_identifier(column, &self.name_resolution_ctx).name)
+                        }
+                        _ => return Err(illegal(&format!("expect a column argument of {}", expr))),
+                    };
+                    (func_name, arg)
+                }
+                _ => return Err(illegal(&format!("unsupported expression {}", expr))),
+            };
+            if !aggregates.contains(&aggregate) {
+                aggregates.push(aggregate);
+            }
+        }
+        if aggregates.is_empty() {
+            return Err(illegal("expect at least one aggregate function"));
+        }
+
+        let plan = CreateAggregatingIndexPlan {
+            if_not_exists: *if_not_exists,
+            tenant: self.ctx.get_tenant(),
+            catalog,
+            database,
+            table,
+            index_name: normalize_identifier(index_name, &self.name_resolution_ctx).name,
+            query: query.to_string(),
+            group_by,
+            aggregates,
+        };
+        Ok(Plan::CreateAggregatingIndex(Box::new(plan)))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_drop_aggregating_index(
+        &mut self,
+        stmt: &DropAggregatingIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let DropAggregatingIndexStmt {
+            if_exists,
+            index_name,
+            catalog,
+            database,
+            table,
+        } = stmt;
+
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+
+        let plan = DropAggregatingIndexPlan {
+            if_exists: *if_exists,
+            tenant: self.ctx.get_tenant(),
+            catalog,
+            database,
+            table: normalize_identifier(table, &self.name_resolution_ctx).name,
+            index_name: normalize_identifier(index_name, &self.name_resolution_ctx).name,
+        };
+        Ok(Plan::DropAggregatingIndex(Box::new(plan)))
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_ast::ast::CreateIndexStmt;
+use common_ast::ast::DropIndexStmt;
+use common_ast::ast::IndexType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::Tokenizer;
+use common_planner::plans::CreateBloomIndexPlan;
+use common_planner::plans::CreateInvertedIndexPlan;
+use common_planner::plans::DropIndexPlan;
+
+use crate::sql::binder::Binder;
+use crate::sql::normalize_identifier;
+use crate::sql::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_index(
+        &mut self,
+        stmt: &CreateIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateIndexStmt {
+            if_not_exists,
+            index_name,
+            index_type,
+            catalog,
+            database,
+            table,
+            columns,
+            tokenizer,
+        } = stmt;
+
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+
+        let mut column_names: Vec<String> = Vec::with_capacity(columns.len());
+        for column in columns {
+            let name = normalize_identifier(column, &self.name_resolution_ctx).name;
+            if column_names.contains(&name) {
+                return Err(ErrorCode::IllegalIndexFormat(format!(
+                    "Duplicated column {} in index {}",
+                    name, index_name
+                )));
+            }
+            column_names.push(name);
+        }
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        let index_name = normalize_identifier(index_name, &self.name_resolution_ctx).name;
+
+        match index_type {
+            IndexType::Bloom => {
+                if tokenizer.is_some() {
+                    return Err(ErrorCode::IllegalIndexFormat(format!(
+                        "TOKENIZER is only allowed for inverted indexes, but {} is a bloom index",
+                        index_name
+                    )));
+                }
+                let plan = CreateBloomIndexPlan {
+                    if_not_exists: *if_not_exists,
+                    tenant: self.ctx.get_tenant(),
+                    catalog,
+                    database,
+                    table,
+                    index_name,
+                    columns: column_names,
+                };
+                Ok(Plan::CreateBloomIndex(Box::new(plan)))
+            }
+            IndexType::Inverted => {
+                let tokenizer = match tokenizer {
+                    Some(name) => Tokenizer::from_name(name)?,
+                    None => Tokenizer::default(),
+                };
+                let plan = CreateInvertedIndexPlan {
+                    if_not_exists: *if_not_exists,
+                    tenant: self.ctx.get_tenant(),
+                    catalog,
+                    database,
+                    table,
+                    index_name,
+                    columns: column_names,
+                    tokenizer: tokenizer.name().to_string(),
+                };
+                Ok(Plan::CreateInvertedIndex(Box::new(plan)))
+            }
+        }
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_drop_index(
+        &mut self,
+        stmt: &DropIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let DropIndexStmt {
+            if_exists,
+            index_name,
+            catalog,
+            database,
+            table,
+        } = stmt;
+
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+
+        // The index is looked up among the indexes of all the types by the interpreter.
+        let plan = DropIndexPlan {
+            if_exists: *if_exists,
+            tenant: self.ctx.get_tenant(),
+            catalog,
+            database,
+            table: normalize_identifier(table, &self.name_resolution_ctx).name,
+            index_name: normalize_identifier(index_name, &self.name_resolution_ctx).name,
+        };
+        Ok(Plan::DropIndex(Box::new(plan)))
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateMaskingPolicyStmt;
+use common_ast::ast::DropMaskingPolicyStmt;
+use common_exception::Result;
+use common_meta_types::MaskingPolicy;
+use common_planner::plans::CreateMaskingPolicyPlan;
+use common_planner::plans::DropMaskingPolicyPlan;
+
+use crate::sql::binder::Binder;
+use crate::sql::normalize_identifier;
+use crate::sql::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_masking_policy(
+        &mut self,
+        stmt: &CreateMaskingPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateMaskingPolicyStmt {
+            if_not_exists,
+            name,
+            parameter,
+            body,
+            exempt_roles,
+        } = stmt;
+
+        let plan = CreateMaskingPolicyPlan {
+            if_not_exists: *if_not_exists,
+            policy: MaskingPolicy {
+                name: normalize_identifier(name, &self.name_resolution_ctx).name,
+                parameter: normalize_identifier(parameter, &self.name_resolution_ctx).name,
+                body: body.to_string(),
+                exempt_roles: exempt_roles.clone(),
+                columns: vec![],
+            },
+        };
+        Ok(Plan::CreateMaskingPolicy(Box::new(plan)))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_drop_masking_policy(
+        &mut self,
+        stmt: &DropMaskingPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let DropMaskingPolicyStmt { if_exists, name } = stmt;
+
+        let plan = DropMaskingPolicyPlan {
+            if_exists: *if_exists,
+            name: normalize_identifier(name, &self.name_resolution_ctx).name,
+        };
+        Ok(Plan::DropMaskingPolicy(Box::new(plan)))
+    }
+}
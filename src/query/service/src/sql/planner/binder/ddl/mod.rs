@@ -13,8 +13,17 @@
 // limitations under the License.
 
 mod account;
+mod aggregating_index;
 mod database;
+mod index;
+mod masking_policy;
+mod network_policy;
+mod row_access_policy;
 mod share;
 mod stage;
+mod stream;
 mod table;
+mod task;
 mod view;
+mod virtual_column;
+mod workload_group;
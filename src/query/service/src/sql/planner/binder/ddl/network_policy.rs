@@ -0,0 +1,63 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateNetworkPolicyStmt;
+use common_ast::ast::DropNetworkPolicyStmt;
+use common_exception::Result;
+use common_meta_types::NetworkPolicy;
+use common_planner::plans::CreateNetworkPolicyPlan;
+use common_planner::plans::DropNetworkPolicyPlan;
+
+use crate::sql::binder::Binder;
+use crate::sql::normalize_identifier;
+use crate::sql::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_network_policy(
+        &mut self,
+        stmt: &CreateNetworkPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateNetworkPolicyStmt {
+            if_not_exists,
+            name,
+            allowed_ip_list,
+            blocked_ip_list,
+            comment,
+        } = stmt;
+
+        let plan = CreateNetworkPolicyPlan {
+            if_not_exists: *if_not_exists,
+            policy: NetworkPolicy {
+                name: normalize_identifier(name, &self.name_resolution_ctx).name,
+                allowed_ip_list: allowed_ip_list.clone(),
+                blocked_ip_list: blocked_ip_list.clone(),
+                comment: comment.clone().unwrap_or_default(),
+            },
+        };
+        Ok(Plan::CreateNetworkPolicy(Box::new(plan)))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_drop_network_policy(
+        &mut self,
+        stmt: &DropNetworkPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let DropNetworkPolicyStmt { if_exists, name } = stmt;
+
+        let plan = DropNetworkPolicyPlan {
+            if_exists: *if_exists,
+            name: normalize_identifier(name, &self.name_resolution_ctx).name,
+        };
+        Ok(Plan::DropNetworkPolicy(Box::new(plan)))
+    }
+}
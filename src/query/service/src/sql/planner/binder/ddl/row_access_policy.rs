@@ -0,0 +1,81 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateRowAccessPolicyStmt;
+use common_ast::ast::DropRowAccessPolicyStmt;
+use common_exception::Result;
+use common_meta_types::RowAccessPolicy;
+use common_planner::plans::CreateRowAccessPolicyPlan;
+use common_planner::plans::DropRowAccessPolicyPlan;
+
+use crate::sql::binder::Binder;
+use crate::sql::normalize_identifier;
+use crate::sql::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_row_access_policy(
+        &mut self,
+        stmt: &CreateRowAccessPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateRowAccessPolicyStmt {
+            if_not_exists,
+            name,
+            catalog,
+            database,
+            table,
+            role_name,
+            predicate,
+        } = stmt;
+
+        let name = normalize_identifier(name, &self.name_resolution_ctx).name;
+        let catalog = catalog
+            .as_ref()
+            .map(|catalog| normalize_identifier(catalog, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+
+        // Make sure the table exists, the predicate is checked against it when it is read.
+        self.ctx.get_table(&catalog, &database, &table).await?;
+
+        let plan = CreateRowAccessPolicyPlan {
+            if_not_exists: *if_not_exists,
+            policy: RowAccessPolicy {
+                name,
+                catalog,
+                database,
+                table,
+                role: role_name.clone(),
+                predicate: predicate.to_string(),
+            },
+        };
+        Ok(Plan::CreateRowAccessPolicy(Box::new(plan)))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_drop_row_access_policy(
+        &mut self,
+        stmt: &DropRowAccessPolicyStmt<'a>,
+    ) -> Result<Plan> {
+        let DropRowAccessPolicyStmt { if_exists, name } = stmt;
+
+        let plan = DropRowAccessPolicyPlan {
+            if_exists: *if_exists,
+            name: normalize_identifier(name, &self.name_resolution_ctx).name,
+        };
+        Ok(Plan::DropRowAccessPolicy(Box::new(plan)))
+    }
+}
@@ -17,6 +17,7 @@ use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use common_ast::ast::CompactTarget as AstCompactTarget;
 use common_ast::ast::OptimizeTableAction as AstOptimizeTableAction;
 use common_ast::ast::*;
 use common_ast::parser::parse_sql;
@@ -24,32 +25,46 @@ use common_ast::parser::tokenize_sql;
 use common_ast::walk_expr_mut;
 use common_ast::Backtrace;
 use common_ast::Dialect;
+use common_catalog::table::AlterColumnAction;
+use common_catalog::table::CompactTarget;
 use common_datavalues::type_coercion::compare_coercion;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataTypeImpl;
 use common_datavalues::ToDataType;
 use common_datavalues::TypeFactory;
 use common_datavalues::Vu8;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planner::plans::AlterTableClusterKeyPlan;
+use common_planner::plans::AlterTableColumnPlan;
+use common_planner::plans::AnalyzeTablePlan;
+use common_planner::plans::AttachTablePlan;
 use common_planner::plans::DescribeTablePlan;
 use common_planner::plans::DropTableClusterKeyPlan;
 use common_planner::plans::DropTablePlan;
 use common_planner::plans::ExistsTablePlan;
+use common_planner::plans::FlashbackTablePlan;
 use common_planner::plans::OptimizeTableAction;
 use common_planner::plans::OptimizeTablePlan;
 use common_planner::plans::RenameTableEntity;
 use common_planner::plans::RenameTablePlan;
+use common_planner::plans::SetColumnMaskingPolicyPlan;
 use common_planner::plans::ShowCreateTablePlan;
 use common_planner::plans::TruncateTablePlan;
 use common_planner::plans::UndropTablePlan;
+use common_planner::plans::UnsetColumnMaskingPolicyPlan;
 use tracing::debug;
 
+use super::super::copy::parse_copy_file_format_options;
+use super::super::copy::parse_stage_location_v2;
+use crate::evaluator::Evaluator;
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::Binder;
 use crate::sql::binder::Visibility;
+use crate::sql::executor::PhysicalScalar;
+use crate::sql::executor::PhysicalScalarBuilder;
 use crate::sql::is_reserved_opt_key;
 use crate::sql::optimizer::optimize;
 use crate::sql::optimizer::OptimizerConfig;
@@ -66,6 +81,12 @@ use crate::sql::BindContext;
 use crate::sql::ColumnBinding;
 use crate::sql::ScalarExpr;
 use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::storages::stage::data_dir;
+use crate::storages::stage::infer_partition_columns;
+use crate::storages::stage::StageTable;
+use crate::storages::stage::OPT_KEY_FILE_FORMAT;
+use crate::storages::stage::OPT_KEY_LOCATION;
+use crate::storages::stage::OPT_KEY_PARTITION_COLUMNS;
 
 struct SelectBuilder {
     from: String,
@@ -340,6 +361,7 @@ impl<'a> Binder {
             as_query,
             transient,
             engine,
+            external,
         } = stmt;
 
         let catalog = catalog
@@ -353,7 +375,10 @@ impl<'a> Binder {
         let table = normalize_identifier(table, &self.name_resolution_ctx).name;
 
         // Take FUSE engine AS default engine
-        let engine = engine.unwrap_or(Engine::Fuse);
+        let engine = match external {
+            Some(_) => Engine::External,
+            None => engine.unwrap_or(Engine::Fuse),
+        };
         let mut options: BTreeMap<String, String> = BTreeMap::new();
         for table_option in table_options.iter() {
             self.insert_table_option_with_validation(
@@ -369,7 +394,7 @@ impl<'a> Binder {
         }
 
         // Build table schema
-        let (schema, field_default_exprs, field_comments) = match (&source, &as_query) {
+        let (mut schema, mut field_default_exprs, mut field_comments) = match (&source, &as_query) {
             (Some(source), None) => {
                 // `CREATE TABLE` without `AS SELECT ...`
                 self.analyze_create_table_schema(source).await?
@@ -419,6 +444,34 @@ impl<'a> Binder {
             ))?,
         };
 
+        if let Some(external) = external {
+            let partition_columns = self.bind_external_table(external, &mut options).await?;
+            if schema
+                .fields()
+                .iter()
+                .all(|field| partition_columns.contains(field.name()))
+            {
+                return Err(ErrorCode::BadArguments(
+                    "external table requires at least one column which is not a partition column",
+                ));
+            }
+            // The partition columns not declared are read as strings.
+            let mut fields = schema.fields().clone();
+            for name in &partition_columns {
+                if schema.has_field(name) {
+                    continue;
+                }
+                fields.push(DataField::new_nullable(name, Vu8::to_data_type()));
+                if !field_default_exprs.is_empty() {
+                    field_default_exprs.push(None);
+                    field_comments.push("".to_string());
+                }
+            }
+            if fields.len() > schema.fields().len() {
+                schema = DataSchemaRefExt::create(fields);
+            }
+        }
+
         if engine == Engine::Fuse {
             // Currently, [Table] can not accesses its database id yet, thus
             // here we keep the db id AS an entry of `table_meta.options`.
@@ -474,6 +527,54 @@ impl<'a> Binder {
         Ok(Plan::CreateTable(Box::new(plan)))
     }
 
+    pub(in crate::sql::planner::binder) async fn bind_attach_table(
+        &mut self,
+        stmt: &AttachTableStmt<'a>,
+    ) -> Result<Plan> {
+        let AttachTableStmt {
+            if_not_exists,
+            catalog,
+            database,
+            table,
+            storage_prefix,
+            read_only,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|catalog| normalize_identifier(catalog, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+
+        // The data is read by the operator of the storage of this cluster, the prefix is relative
+        // to the root of the storage, like `<database_id>/<table_id>`.
+        let storage_prefix = storage_prefix.trim_matches('/').to_string();
+        if storage_prefix.is_empty()
+            || storage_prefix.contains("://")
+            || storage_prefix.split('/').any(|part| part == "..")
+        {
+            return Err(ErrorCode::BadArguments(format!(
+                "invalid storage prefix '{}', expect the prefix of a fuse table relative to the root of the storage, like '1/2/'",
+                stmt.storage_prefix
+            )));
+        }
+
+        Ok(Plan::AttachTable(Box::new(AttachTablePlan {
+            if_not_exists: *if_not_exists,
+            tenant,
+            catalog,
+            database,
+            table,
+            storage_prefix,
+            read_only: *read_only,
+        })))
+    }
+
     pub(in crate::sql::planner::binder) async fn bind_drop_table(
         &mut self,
         stmt: &DropTableStmt<'a>,
@@ -648,9 +749,145 @@ impl<'a> Binder {
                     push_downs,
                 })))
             }
+            AlterTableAction::SetColumnMaskingPolicy { column, policy } => {
+                let column = normalize_identifier(column, &self.name_resolution_ctx).name;
+                let schema = self
+                    .ctx
+                    .get_table(&catalog, &database, &table)
+                    .await?
+                    .schema();
+                schema.field_with_name(&column)?;
+
+                Ok(Plan::SetColumnMaskingPolicy(Box::new(
+                    SetColumnMaskingPolicyPlan {
+                        catalog,
+                        database,
+                        table,
+                        column,
+                        policy: normalize_identifier(policy, &self.name_resolution_ctx).name,
+                    },
+                )))
+            }
+            AlterTableAction::UnsetColumnMaskingPolicy { column } => Ok(
+                Plan::UnsetColumnMaskingPolicy(Box::new(UnsetColumnMaskingPolicyPlan {
+                    catalog,
+                    database,
+                    table,
+                    column: normalize_identifier(column, &self.name_resolution_ctx).name,
+                })),
+            ),
+            AlterTableAction::AddColumn { column } => {
+                let action = self.analyze_add_column(column).await?;
+                Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    action,
+                })))
+            }
+            AlterTableAction::DropColumn { column } => {
+                let action = AlterColumnAction::Drop {
+                    column: normalize_identifier(column, &self.name_resolution_ctx).name,
+                };
+                Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    action,
+                })))
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                let action = AlterColumnAction::Rename {
+                    column: normalize_identifier(old_column, &self.name_resolution_ctx).name,
+                    new_column: normalize_identifier(new_column, &self.name_resolution_ctx).name,
+                };
+                Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    action,
+                })))
+            }
+            AlterTableAction::ModifyColumn { column, data_type } => {
+                let column = normalize_identifier(column, &self.name_resolution_ctx).name;
+                let data_type = TypeFactory::instance().get(data_type.to_string())?;
+                let schema = self
+                    .ctx
+                    .get_table(&catalog, &database, &table)
+                    .await?
+                    .schema();
+                // the default expression is kept, cast to the new type
+                let default_expr = match schema.field_with_name(&column)?.default_expr() {
+                    Some(default_expr) => {
+                        let input: PhysicalScalar = serde_json::from_str(default_expr)?;
+                        let default_expr = PhysicalScalar::Cast {
+                            input: Box::new(input),
+                            target: data_type.clone(),
+                        };
+                        Some(serde_json::to_string(&default_expr)?)
+                    }
+                    None => None,
+                };
+                let action = AlterColumnAction::Modify {
+                    field: DataField::new(&column, data_type).with_default_expr(default_expr),
+                };
+                Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    action,
+                })))
+            }
         }
     }
 
+    /// The rows already in the table take the value of the default expression of the added
+    /// column, it is evaluated once when the column is added.
+    async fn analyze_add_column(&self, column: &ColumnDefinition<'a>) -> Result<AlterColumnAction> {
+        let name = normalize_identifier(&column.name, &self.name_resolution_ctx).name;
+        let data_type = TypeFactory::instance().get(column.data_type.to_string())?;
+        let field = DataField::new(&name, data_type.clone());
+
+        let (field, default) = match &column.default_expr {
+            Some(default_expr) => {
+                let bind_context = BindContext::new();
+                let mut scalar_binder = ScalarBinder::new(
+                    &bind_context,
+                    self.ctx.clone(),
+                    &self.name_resolution_ctx,
+                    self.metadata.clone(),
+                    &[],
+                );
+                let scalar = Self::bind_column_default_expr(
+                    &mut scalar_binder,
+                    &name,
+                    data_type,
+                    default_expr,
+                )
+                .await?;
+                let func_ctx = self.ctx.try_get_function_context()?;
+                let (default, _) = Evaluator::eval_scalar(&scalar)?.try_eval_const(&func_ctx)?;
+                let mut builder = PhysicalScalarBuilder;
+                let default_expr = serde_json::to_string(&builder.build(&scalar)?)?;
+                (field.with_default_expr(Some(default_expr)), default)
+            }
+            None => (field, data_type.default_value()),
+        };
+
+        Ok(AlterColumnAction::Add {
+            field,
+            default,
+            comment: column.comment.clone().unwrap_or_default(),
+        })
+    }
+
     pub(in crate::sql::planner::binder) async fn bind_rename_table(
         &mut self,
         stmt: &RenameTableStmt<'a>,
@@ -735,6 +972,38 @@ impl<'a> Binder {
         })))
     }
 
+    pub(in crate::sql::planner::binder) async fn bind_flashback_table(
+        &mut self,
+        stmt: &FlashbackTableStmt<'a>,
+    ) -> Result<Plan> {
+        let FlashbackTableStmt {
+            catalog,
+            database,
+            table,
+            point,
+        } = stmt;
+
+        let catalog = catalog
+            .as_ref()
+            .map(|catalog| normalize_identifier(catalog, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        let point = self
+            .resolve_data_travel_point(&BindContext::new(), point)
+            .await?;
+
+        Ok(Plan::FlashbackTable(Box::new(FlashbackTablePlan {
+            catalog,
+            database,
+            table,
+            point,
+        })))
+    }
+
     pub(in crate::sql::planner::binder) async fn bind_optimize_table(
         &mut self,
         stmt: &OptimizeTableStmt<'a>,
@@ -758,7 +1027,10 @@ impl<'a> Binder {
         let action = action.map_or(OptimizeTableAction::Purge, |v| match v {
             AstOptimizeTableAction::All => OptimizeTableAction::All,
             AstOptimizeTableAction::Purge => OptimizeTableAction::Purge,
-            AstOptimizeTableAction::Compact => OptimizeTableAction::Compact,
+            AstOptimizeTableAction::Compact { target } => match target {
+                AstCompactTarget::Block => OptimizeTableAction::Compact(CompactTarget::Blocks),
+                AstCompactTarget::Segment => OptimizeTableAction::Compact(CompactTarget::Segments),
+            },
         });
 
         Ok(Plan::OptimizeTable(Box::new(OptimizeTablePlan {
@@ -769,6 +1041,33 @@ impl<'a> Binder {
         })))
     }
 
+    pub(in crate::sql::planner::binder) async fn bind_analyze_table(
+        &mut self,
+        stmt: &AnalyzeTableStmt<'a>,
+    ) -> Result<Plan> {
+        let AnalyzeTableStmt {
+            catalog,
+            database,
+            table,
+        } = stmt;
+
+        let catalog = catalog
+            .as_ref()
+            .map(|catalog| normalize_identifier(catalog, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+
+        Ok(Plan::AnalyzeTable(Box::new(AnalyzeTablePlan {
+            catalog,
+            database,
+            table,
+        })))
+    }
+
     pub(in crate::sql::planner::binder) async fn bind_exists_table(
         &mut self,
         stmt: &ExistsTableStmt<'a>,
@@ -820,18 +1119,15 @@ impl<'a> Binder {
                     fields.push(DataField::new(&name, data_type.clone()));
                     fields_default_expr.push({
                         if let Some(default_expr) = &column.default_expr {
-                            let (mut expr, expr_type) = scalar_binder.bind(default_expr).await?;
-                            if compare_coercion(&data_type, &expr_type).is_err() {
-                                return Err(ErrorCode::SemanticError(format!("column {name} is of type {} but default expression is of type {}", data_type, expr_type)));
-                            }
-                            if !expr_type.eq(&data_type) {
-                                expr = Scalar::CastExpr(CastExpr {
-                                    argument: Box::new(expr),
-                                    from_type: Box::new(expr_type),
-                                    target_type: Box::new(data_type),
-                                })
-                            }
-                            Some(expr)
+                            Some(
+                                Self::bind_column_default_expr(
+                                    &mut scalar_binder,
+                                    &name,
+                                    data_type,
+                                    default_expr,
+                                )
+                                .await?,
+                            )
                         } else {
                             None
                         }
@@ -862,6 +1158,29 @@ impl<'a> Binder {
         }
     }
 
+    async fn bind_column_default_expr(
+        scalar_binder: &mut ScalarBinder<'_>,
+        name: &str,
+        data_type: DataTypeImpl,
+        default_expr: &Expr<'a>,
+    ) -> Result<Scalar> {
+        let (mut expr, expr_type) = scalar_binder.bind(default_expr).await?;
+        if compare_coercion(&data_type, &expr_type).is_err() {
+            return Err(ErrorCode::SemanticError(format!(
+                "column {name} is of type {} but default expression is of type {}",
+                data_type, expr_type
+            )));
+        }
+        if !expr_type.eq(&data_type) {
+            expr = Scalar::CastExpr(CastExpr {
+                argument: Box::new(expr),
+                from_type: Box::new(expr_type),
+                target_type: Box::new(data_type),
+            })
+        }
+        Ok(expr)
+    }
+
     /// Validate the schema of the table to be created.
     fn validate_create_table_schema(schema: &DataSchemaRef) -> Result<()> {
         // Check if there are duplicated column names
@@ -878,6 +1197,63 @@ impl<'a> Binder {
         Ok(())
     }
 
+    /// Validates the location and the file format of `CREATE EXTERNAL TABLE`, and keeps them in
+    /// the table options. Returns the partition columns, inferred from the paths of the files if
+    /// `PARTITION BY` is not specified.
+    async fn bind_external_table(
+        &self,
+        external: &ExternalTableOptions<'a>,
+        options: &mut BTreeMap<String, String>,
+    ) -> Result<Vec<String>> {
+        let (mut stage, dir) =
+            parse_stage_location_v2(&self.ctx, &external.stage, &external.path).await?;
+        if !external.file_format.is_empty() {
+            stage.file_format_options = parse_copy_file_format_options(&external.file_format)?;
+        }
+
+        let partition_columns = match external.partition_by.is_empty() {
+            true => {
+                let operator = StageTable::get_op(&self.ctx, &stage).await?;
+                infer_partition_columns(&operator, &data_dir(&dir, "")).await?
+            }
+            false => external
+                .partition_by
+                .iter()
+                .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+                .collect(),
+        };
+        let mut names = HashSet::new();
+        for name in &partition_columns {
+            if !names.insert(name) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "duplicated partition column {}",
+                    name
+                )));
+            }
+        }
+
+        self.insert_table_option_with_validation(
+            options,
+            OPT_KEY_LOCATION.to_string(),
+            format!("@{}{}", external.stage, external.path),
+        )?;
+        if !external.file_format.is_empty() {
+            self.insert_table_option_with_validation(
+                options,
+                OPT_KEY_FILE_FORMAT.to_string(),
+                serde_json::to_string(&external.file_format)?,
+            )?;
+        }
+        if !partition_columns.is_empty() {
+            self.insert_table_option_with_validation(
+                options,
+                OPT_KEY_PARTITION_COLUMNS.to_string(),
+                partition_columns.join(","),
+            )?;
+        }
+        Ok(partition_columns)
+    }
+
     fn insert_table_option_with_validation(
         &self,
         options: &mut BTreeMap<String, String>,
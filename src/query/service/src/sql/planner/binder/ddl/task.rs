@@ -0,0 +1,86 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateTaskStmt;
+use common_ast::ast::DropTaskStmt;
+use common_ast::ast::Statement;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::CronSchedule;
+use common_meta_types::Task;
+use common_planner::plans::CreateTaskPlan;
+use common_planner::plans::DropTaskPlan;
+
+use crate::sql::binder::Binder;
+use crate::sql::normalize_identifier;
+use crate::sql::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_task(
+        &mut self,
+        stmt: &CreateTaskStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateTaskStmt {
+            if_not_exists,
+            name,
+            schedule,
+            error_notification,
+            comment,
+            statement,
+        } = stmt;
+
+        // A run of a task is a single statement committed on its own.
+        if matches!(
+            statement.as_ref(),
+            Statement::Begin
+                | Statement::Commit
+                | Statement::Rollback
+                | Statement::CreateTask(_)
+                | Statement::DropTask(_)
+        ) {
+            return Err(ErrorCode::SemanticError(format!(
+                "Statement `{}` can not be run by a task",
+                statement
+            )));
+        }
+        CronSchedule::parse(schedule)?;
+
+        // The owner and the creation time are set when the task is created.
+        let plan = CreateTaskPlan {
+            if_not_exists: *if_not_exists,
+            task: Task {
+                name: normalize_identifier(name, &self.name_resolution_ctx).name,
+                schedule: schedule.clone(),
+                definition: statement.to_string(),
+                error_notification: error_notification.clone().unwrap_or_default(),
+                comment: comment.clone().unwrap_or_default(),
+                ..Default::default()
+            },
+        };
+        Ok(Plan::CreateTask(Box::new(plan)))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_drop_task(
+        &mut self,
+        stmt: &DropTaskStmt<'a>,
+    ) -> Result<Plan> {
+        let DropTaskStmt { if_exists, name } = stmt;
+
+        let plan = DropTaskPlan {
+            if_exists: *if_exists,
+            name: normalize_identifier(name, &self.name_resolution_ctx).name,
+        };
+        Ok(Plan::DropTask(Box::new(plan)))
+    }
+}
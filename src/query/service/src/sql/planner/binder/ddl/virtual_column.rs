@@ -0,0 +1,176 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::AlterVirtualColumnsStmt;
+use common_ast::ast::CreateVirtualColumnsStmt;
+use common_ast::ast::DropVirtualColumnsStmt;
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::Literal;
+use common_ast::ast::MapAccessor;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planner::plans::AlterVirtualColumnsPlan;
+use common_planner::plans::CreateVirtualColumnsPlan;
+use common_planner::plans::DropVirtualColumnsPlan;
+
+use crate::sql::binder::Binder;
+use crate::sql::normalize_identifier;
+use crate::sql::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_virtual_columns(
+        &mut self,
+        stmt: &CreateVirtualColumnsStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateVirtualColumnsStmt {
+            if_not_exists,
+            catalog,
+            database,
+            table,
+            virtual_columns,
+        } = stmt;
+
+        let (catalog, database, table) = self.normalize_table_name(catalog, database, table);
+        let plan = CreateVirtualColumnsPlan {
+            if_not_exists: *if_not_exists,
+            tenant: self.ctx.get_tenant(),
+            catalog,
+            database,
+            table,
+            virtual_columns: self.bind_virtual_columns(virtual_columns)?,
+        };
+        Ok(Plan::CreateVirtualColumns(Box::new(plan)))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_alter_virtual_columns(
+        &mut self,
+        stmt: &AlterVirtualColumnsStmt<'a>,
+    ) -> Result<Plan> {
+        let AlterVirtualColumnsStmt {
+            catalog,
+            database,
+            table,
+            virtual_columns,
+        } = stmt;
+
+        let (catalog, database, table) = self.normalize_table_name(catalog, database, table);
+        let plan = AlterVirtualColumnsPlan {
+            tenant: self.ctx.get_tenant(),
+            catalog,
+            database,
+            table,
+            virtual_columns: self.bind_virtual_columns(virtual_columns)?,
+        };
+        Ok(Plan::AlterVirtualColumns(Box::new(plan)))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_drop_virtual_columns(
+        &mut self,
+        stmt: &DropVirtualColumnsStmt<'a>,
+    ) -> Result<Plan> {
+        let DropVirtualColumnsStmt {
+            if_exists,
+            catalog,
+            database,
+            table,
+        } = stmt;
+
+        let (catalog, database, table) = self.normalize_table_name(catalog, database, table);
+        let plan = DropVirtualColumnsPlan {
+            if_exists: *if_exists,
+            tenant: self.ctx.get_tenant(),
+            catalog,
+            database,
+            table,
+        };
+        Ok(Plan::DropVirtualColumns(Box::new(plan)))
+    }
+
+    fn normalize_table_name(
+        &self,
+        catalog: &Option<Identifier<'a>>,
+        database: &Option<Identifier<'a>>,
+        table: &Identifier<'a>,
+    ) -> (String, String, String) {
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, &self.name_resolution_ctx).name)
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = normalize_identifier(table, &self.name_resolution_ctx).name;
+        (catalog, database, table)
+    }
+
+    /// Binds the paths of the VARIANT columns, like `v['a'][0]`, to the columns and the keys
+    /// and indices of the paths.
+    fn bind_virtual_columns(
+        &self,
+        virtual_columns: &[Expr<'a>],
+    ) -> Result<Vec<(String, Vec<DataValue>)>> {
+        virtual_columns
+            .iter()
+            .map(|virtual_column| {
+                let mut path = vec![];
+                let mut expr = virtual_column;
+                while let Expr::MapAccess {
+                    expr: inner,
+                    accessor,
+                    ..
+                } = expr
+                {
+                    let element = match accessor {
+                        MapAccessor::Bracket {
+                            key: Literal::Integer(index),
+                        } => DataValue::UInt64(*index),
+                        MapAccessor::Bracket {
+                            key: Literal::String(key),
+                        } => DataValue::String(key.as_bytes().to_vec()),
+                        MapAccessor::Period { key } | MapAccessor::Colon { key } => {
+                            DataValue::String(key.name.as_bytes().to_vec())
+                        }
+                        _ => return Err(invalid_virtual_column(virtual_column)),
+                    };
+                    path.push(element);
+                    expr = inner.as_ref();
+                }
+                match expr {
+                    Expr::ColumnRef {
+                        database: None,
+                        table: None,
+                        column,
+                        ..
+                    } if !path.is_empty() => {
+                        // the accessors are visited from the outermost one
+                        path.reverse();
+                        let column = normalize_identifier(column, &self.name_resolution_ctx).name;
+                        Ok((column, path))
+                    }
+                    _ => Err(invalid_virtual_column(virtual_column)),
+                }
+            })
+            .collect()
+    }
+}
+
+fn invalid_virtual_column(virtual_column: &Expr) -> ErrorCode {
+    ErrorCode::SemanticError(format!(
+        "Invalid virtual column {}, expect a path of a column like v['a'][0]",
+        virtual_column
+    ))
+}
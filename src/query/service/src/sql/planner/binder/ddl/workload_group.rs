@@ -0,0 +1,67 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateWorkloadGroupStmt;
+use common_ast::ast::DropWorkloadGroupStmt;
+use common_exception::Result;
+use common_meta_types::WorkloadGroup;
+use common_planner::plans::CreateWorkloadGroupPlan;
+use common_planner::plans::DropWorkloadGroupPlan;
+
+use crate::sql::binder::Binder;
+use crate::sql::normalize_identifier;
+use crate::sql::plans::Plan;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_workload_group(
+        &mut self,
+        stmt: &CreateWorkloadGroupStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateWorkloadGroupStmt {
+            if_not_exists,
+            name,
+            cpu_share,
+            memory_limit,
+            max_concurrency,
+            max_scan_bytes,
+            comment,
+        } = stmt;
+
+        let plan = CreateWorkloadGroupPlan {
+            if_not_exists: *if_not_exists,
+            group: WorkloadGroup {
+                name: normalize_identifier(name, &self.name_resolution_ctx).name,
+                cpu_share: cpu_share.unwrap_or_default(),
+                memory_limit: memory_limit.unwrap_or_default(),
+                max_concurrency: max_concurrency.unwrap_or_default(),
+                max_scan_bytes: max_scan_bytes.unwrap_or_default(),
+                comment: comment.clone().unwrap_or_default(),
+            },
+        };
+        Ok(Plan::CreateWorkloadGroup(Box::new(plan)))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_drop_workload_group(
+        &mut self,
+        stmt: &DropWorkloadGroupStmt<'a>,
+    ) -> Result<Plan> {
+        let DropWorkloadGroupStmt { if_exists, name } = stmt;
+
+        let plan = DropWorkloadGroupPlan {
+            if_exists: *if_exists,
+            name: normalize_identifier(name, &self.name_resolution_ctx).name,
+        };
+        Ok(Plan::DropWorkloadGroup(Box::new(plan)))
+    }
+}
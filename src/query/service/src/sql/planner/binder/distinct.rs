@@ -21,6 +21,7 @@ use crate::sql::binder::Binder;
 use crate::sql::binder::ColumnBinding;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::semantic::GroupingChecker;
+use crate::sql::planner::semantic::WindowChecker;
 use crate::sql::plans::Aggregate;
 use crate::sql::plans::AggregateMode;
 use crate::sql::plans::BoundColumnRef;
@@ -47,6 +48,13 @@ impl Binder {
                         scalar,
                         index: item.index,
                     })
+                } else if !bind_context.window_info.window_functions.is_empty() {
+                    let mut window_checker = WindowChecker::new(bind_context);
+                    let scalar = window_checker.resolve(&item.scalar)?;
+                    Ok(ScalarItem {
+                        scalar,
+                        index: item.index,
+                    })
                 } else {
                     Ok(item)
                 }
@@ -14,10 +14,13 @@
 
 use common_ast::ast::Expr;
 use common_ast::parser::token::Token;
+use common_ast::DisplayError;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use super::select::SelectList;
 use crate::sql::binder::aggregate::AggregateRewriter;
+use crate::sql::binder::contain_window_function;
 use crate::sql::binder::split_conjunctions;
 use crate::sql::binder::ScalarBinder;
 use crate::sql::optimizer::SExpr;
@@ -49,6 +52,11 @@ impl<'a> Binder {
             &aliases,
         );
         let (scalar, _) = scalar_binder.bind(having).await?;
+        if contain_window_function(&scalar)? {
+            return Err(ErrorCode::SemanticError(having.span().display_error(
+                "window functions are not allowed in HAVING".to_string(),
+            )));
+        }
         let mut rewriter = AggregateRewriter::new(bind_context, self.metadata.clone());
         Ok((rewriter.visit(&scalar)?, having.span()))
     }
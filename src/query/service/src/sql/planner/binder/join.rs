@@ -52,8 +52,19 @@ impl<'a> Binder {
     ) -> Result<(SExpr, BindContext)> {
         let (left_child, left_context) =
             self.bind_table_reference(bind_context, &join.left).await?;
-        let (right_child, right_context) =
-            self.bind_table_reference(bind_context, &join.right).await?;
+        let (right_child, right_context) = if join.right.is_lateral() {
+            // The columns of the left side are visible to the right side as outer columns, the
+            // correlation is eliminated by the subquery rewriter.
+            if matches!(join.op, JoinOperator::RightOuter | JoinOperator::FullOuter) {
+                return Err(ErrorCode::SemanticError(
+                    "LATERAL can not be the right side of a RIGHT or FULL join",
+                ));
+            }
+            self.bind_table_reference(&left_context, &join.right)
+                .await?
+        } else {
+            self.bind_table_reference(bind_context, &join.right).await?
+        };
 
         check_duplicate_join_tables(&left_context, &right_context)?;
 
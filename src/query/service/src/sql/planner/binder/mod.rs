@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod aggregate;
+mod aggregating_index;
 mod bind_context;
 #[allow(clippy::module_inception)]
 mod binder;
@@ -35,9 +36,14 @@ mod setting;
 mod show;
 mod sort;
 mod table;
+mod udf;
+mod window;
 
 pub use aggregate::AggregateInfo;
 pub use bind_context::*;
 pub use binder::Binder;
+pub use copy::parse_copy_file_format_options;
 pub use scalar::ScalarBinder;
 pub use scalar_common::*;
+pub use udf::check_udf_server_allowed;
+pub use window::WindowInfo;
@@ -31,6 +31,7 @@ use crate::sql::planner::binder::Binder;
 use crate::sql::planner::binder::ColumnBinding;
 use crate::sql::planner::semantic::normalize_identifier;
 use crate::sql::planner::semantic::GroupingChecker;
+use crate::sql::planner::semantic::WindowChecker;
 use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::EvalScalar;
 use crate::sql::plans::Scalar;
@@ -112,6 +113,13 @@ impl<'a> Binder {
                         scalar,
                         index: item.index,
                     })
+                } else if !bind_context.window_info.window_functions.is_empty() {
+                    let mut window_checker = WindowChecker::new(bind_context);
+                    let scalar = window_checker.resolve(&item.scalar)?;
+                    Ok(ScalarItem {
+                        scalar,
+                        index: item.index,
+                    })
                 } else {
                     Ok(item.clone())
                 }
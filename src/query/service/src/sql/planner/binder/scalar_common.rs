@@ -27,6 +27,7 @@ use crate::sql::plans::FunctionCall;
 use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarExpr;
+use crate::sql::plans::UDFServerCall;
 
 // Visitor that find Expressions that match a particular predicate
 struct Finder<'a, F>
@@ -40,7 +41,6 @@ impl<'a, F> Finder<'a, F>
 where F: Fn(&Scalar) -> bool
 {
     /// Create a new finder with the `test_fn`
-    #[allow(dead_code)]
     fn new(find_fn: &'a F) -> Self {
         Self {
             find_fn,
@@ -65,6 +65,17 @@ where F: Fn(&Scalar) -> bool
     }
 }
 
+/// Find the window functions in the scalar expression.
+pub fn find_window_functions(scalar: &Scalar) -> Result<Vec<Scalar>> {
+    let find_fn = |scalar: &Scalar| matches!(scalar, Scalar::WindowFunction(_));
+    let finder = scalar.accept(Finder::new(&find_fn))?;
+    Ok(finder.scalars)
+}
+
+pub fn contain_window_function(scalar: &Scalar) -> Result<bool> {
+    Ok(!find_window_functions(scalar)?.is_empty())
+}
+
 pub fn split_conjunctions(scalar: &Scalar) -> Vec<Scalar> {
     match scalar {
         Scalar::AndExpr(AndExpr { left, right, .. }) => {
@@ -187,6 +198,9 @@ pub fn contain_subquery(scalar: &Scalar) -> bool {
             arguments.iter().any(contain_subquery)
         }
         Scalar::CastExpr(CastExpr { argument, .. }) => contain_subquery(argument),
+        Scalar::UDFServerCall(UDFServerCall { arguments, .. }) => {
+            arguments.iter().any(contain_subquery)
+        }
         _ => false,
     }
 }
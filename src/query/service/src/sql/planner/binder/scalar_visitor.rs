@@ -21,6 +21,7 @@ use crate::sql::plans::ComparisonExpr;
 use crate::sql::plans::FunctionCall;
 use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
+use crate::sql::plans::UDFServerCall;
 
 /// Controls how the visitor recursion should proceed.
 pub enum Recursion<V: ScalarVisitor> {
@@ -57,6 +58,11 @@ pub trait ScalarVisitor: Sized {
                                         stack.push(RecursionProcessing::Call(arg));
                                     }
                                 }
+                                Scalar::WindowFunction(func) => {
+                                    for scalar in func.inner_scalars() {
+                                        stack.push(RecursionProcessing::Call(scalar));
+                                    }
+                                }
                                 Scalar::ComparisonExpr(ComparisonExpr { left, right, .. }) => {
                                     stack.push(RecursionProcessing::Call(left));
                                     stack.push(RecursionProcessing::Call(right));
@@ -79,6 +85,11 @@ pub trait ScalarVisitor: Sized {
                                     stack.push(RecursionProcessing::Call(argument))
                                 }
                                 Scalar::SubqueryExpr(_) => {}
+                                Scalar::UDFServerCall(UDFServerCall { arguments, .. }) => {
+                                    for arg in arguments.iter() {
+                                        stack.push(RecursionProcessing::Call(arg));
+                                    }
+                                }
                             }
 
                             visitor
@@ -26,12 +26,15 @@ use common_ast::ast::SelectTarget;
 use common_ast::ast::SetExpr;
 use common_ast::ast::SetOperator;
 use common_ast::ast::TableReference;
+use common_ast::ast::CTE;
+use common_ast::DisplayError;
 use common_datavalues::type_coercion::compare_coercion;
 use common_datavalues::DataTypeImpl;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planner::IndexType;
 
+use crate::sql::binder::scalar_common::contain_window_function;
 use crate::sql::binder::scalar_common::split_conjunctions;
 use crate::sql::binder::CteInfo;
 use crate::sql::binder::Visibility;
@@ -44,6 +47,9 @@ use crate::sql::plans::CastExpr;
 use crate::sql::plans::EvalScalar;
 use crate::sql::plans::Filter;
 use crate::sql::plans::JoinType;
+use crate::sql::plans::RecursiveCteScan;
+use crate::sql::plans::RecursiveUnion;
+use crate::sql::plans::RelOperator;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarItem;
 use crate::sql::plans::UnionAll;
@@ -69,7 +75,19 @@ impl<'a> Binder {
         stmt: &SelectStmt<'a>,
         order_by: &[OrderByExpr<'a>],
     ) -> Result<(SExpr, BindContext)> {
-        let (mut s_expr, mut from_context) = if stmt.from.is_empty() {
+        if let Some(rewrite) = self
+            .rewrite_by_aggregating_index(bind_context, stmt, order_by)
+            .await?
+        {
+            let from = self
+                .bind_aggregating_index_table(bind_context, &rewrite)
+                .await?;
+            return self
+                .bind_select_body(&rewrite.stmt, &rewrite.order_by, from)
+                .await;
+        }
+
+        let from = if stmt.from.is_empty() {
             self.bind_one_table(bind_context, stmt).await?
         } else {
             let cross_joins = stmt
@@ -89,6 +107,16 @@ impl<'a> Binder {
             self.bind_table_reference(bind_context, &cross_joins)
                 .await?
         };
+        self.bind_select_body(stmt, order_by, from).await
+    }
+
+    async fn bind_select_body(
+        &mut self,
+        stmt: &SelectStmt<'a>,
+        order_by: &[OrderByExpr<'a>],
+        from: (SExpr, BindContext),
+    ) -> Result<(SExpr, BindContext)> {
+        let (mut s_expr, mut from_context) = from;
 
         if let Some(expr) = &stmt.selection {
             s_expr = self.bind_where(&from_context, expr, s_expr).await?;
@@ -107,6 +135,8 @@ impl<'a> Binder {
 
         self.analyze_aggregate_select(&mut from_context, &mut select_list)?;
 
+        self.analyze_window_select(&mut from_context, &select_list)?;
+
         let having = if let Some(having) = &stmt.having {
             Some(
                 self.analyze_aggregate_having(&mut from_context, &select_list, having)
@@ -137,6 +167,10 @@ impl<'a> Binder {
                 .await?;
         }
 
+        if !from_context.window_info.window_functions.is_empty() {
+            s_expr = self.bind_window(&from_context, s_expr)?;
+        }
+
         if stmt.distinct {
             s_expr = self.bind_distinct(&from_context, &projections, &mut scalar_items, s_expr)?;
         }
@@ -200,7 +234,11 @@ impl<'a> Binder {
                         "duplicate cte {table_name}"
                     )));
                 }
-                let (s_expr, cte_bind_context) = self.bind_query(bind_context, &cte.query).await?;
+                let (s_expr, cte_bind_context) = if with.recursive {
+                    self.bind_recursive_cte(bind_context, cte).await?
+                } else {
+                    self.bind_query(bind_context, &cte.query).await?
+                };
                 let cte_info = CteInfo {
                     columns_alias: cte.alias.columns.iter().map(|c| c.name.clone()).collect(),
                     s_expr,
@@ -269,6 +307,11 @@ impl<'a> Binder {
             &[],
         );
         let (scalar, _) = scalar_binder.bind(expr).await?;
+        if contain_window_function(&scalar)? {
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                "window functions are not allowed in WHERE".to_string(),
+            )));
+        }
         let filter_plan = Filter {
             predicates: split_conjunctions(&scalar),
             is_having: false,
@@ -277,6 +320,135 @@ impl<'a> Binder {
         Ok(new_expr)
     }
 
+    /// Bind a CTE of `WITH RECURSIVE`, whose query is the union of an anchor term and a
+    /// recursive term reading the CTE itself. The CTE is bound as a plain one if it doesn't
+    /// reference itself.
+    async fn bind_recursive_cte(
+        &mut self,
+        bind_context: &BindContext,
+        cte: &CTE<'_>,
+    ) -> Result<(SExpr, BindContext)> {
+        let table_name = cte.alias.name.name.clone();
+        let query = &cte.query;
+        let set_operation = match &query.body {
+            SetExpr::SetOperation(set_operation)
+                if set_operation.op == SetOperator::Union
+                    && query.with.is_none()
+                    && query.order_by.is_empty()
+                    && query.limit.is_empty()
+                    && query.offset.is_none() =>
+            {
+                set_operation
+            }
+            _ => return self.bind_query(bind_context, query).await,
+        };
+
+        let (anchor_expr, anchor_context) = self
+            .bind_set_expr(bind_context, &set_operation.left, &[])
+            .await?;
+
+        // The recursive term reads the rows produced by the last iteration, register the CTE
+        // as a scan of them while binding it.
+        let mut scan_context = BindContext::new();
+        for column in anchor_context.columns.iter() {
+            let column_binding = self.create_column_binding(
+                None,
+                Some(table_name.clone()),
+                column.column_name.clone(),
+                *column.data_type.clone(),
+            );
+            scan_context.add_column_binding(column_binding);
+        }
+        let scan = RecursiveCteScan {
+            cte_name: table_name.clone(),
+            columns: scan_context.columns.iter().map(|c| c.index).collect(),
+        };
+        let cte_info = CteInfo {
+            columns_alias: cte.alias.columns.iter().map(|c| c.name.clone()).collect(),
+            s_expr: SExpr::create_leaf(scan.into()),
+            bind_context: scan_context,
+        };
+        bind_context
+            .ctes_map
+            .write()
+            .insert(table_name.clone(), cte_info);
+        let recursive = self
+            .bind_set_expr(bind_context, &set_operation.right, &[])
+            .await;
+        bind_context.ctes_map.write().remove(&table_name);
+        let (mut recursive_expr, recursive_context) = recursive?;
+
+        if !Self::contains_recursive_cte_scan(&recursive_expr) {
+            return self.bind_query(bind_context, query).await;
+        }
+        if anchor_context.columns.len() != recursive_context.columns.len() {
+            return Err(ErrorCode::SemanticError(format!(
+                "recursive cte {table_name} must have the same number of columns in its terms"
+            )));
+        }
+
+        // The output columns are the ones of the anchor term, cast the recursive term to them.
+        let mut scalar_items = vec![];
+        let mut pairs = Vec::with_capacity(anchor_context.columns.len());
+        for (anchor_col, recursive_col) in anchor_context
+            .columns
+            .iter()
+            .zip(recursive_context.columns.iter())
+        {
+            let recursive_index = if recursive_col.data_type != anchor_col.data_type {
+                let new_column_index = self.metadata.write().add_column(
+                    recursive_col.column_name.clone(),
+                    *anchor_col.data_type.clone(),
+                    None,
+                    None,
+                );
+                let cast_expr = CastExpr {
+                    argument: Box::new(
+                        BoundColumnRef {
+                            column: recursive_col.clone(),
+                        }
+                        .into(),
+                    ),
+                    from_type: recursive_col.data_type.clone(),
+                    target_type: anchor_col.data_type.clone(),
+                };
+                scalar_items.push(ScalarItem {
+                    scalar: cast_expr.into(),
+                    index: new_column_index,
+                });
+                new_column_index
+            } else {
+                recursive_col.index
+            };
+            pairs.push((anchor_col.index, recursive_index));
+        }
+        if !scalar_items.is_empty() {
+            recursive_expr = SExpr::create_unary(
+                EvalScalar {
+                    items: scalar_items,
+                }
+                .into(),
+                recursive_expr,
+            );
+        }
+
+        let union = RecursiveUnion {
+            cte_name: table_name,
+            pairs,
+            distinct: !set_operation.all,
+        };
+        let s_expr = SExpr::create_binary(union.into(), anchor_expr, recursive_expr);
+        Ok((s_expr, anchor_context))
+    }
+
+    fn contains_recursive_cte_scan(s_expr: &SExpr) -> bool {
+        matches!(s_expr.plan(), RelOperator::RecursiveCteScan(_))
+            || s_expr
+                .children()
+                .iter()
+                .any(Self::contains_recursive_cte_scan)
+    }
+
     pub(super) async fn bind_set_operator(
         &mut self,
         bind_context: &BindContext,
@@ -24,6 +24,7 @@ use common_exception::Result;
 use common_planner::IndexType;
 
 use super::bind_context::NameResolutionResult;
+use crate::sql::binder::contain_window_function;
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::select::SelectList;
 use crate::sql::binder::Binder;
@@ -31,6 +32,7 @@ use crate::sql::binder::ColumnBinding;
 use crate::sql::normalize_identifier;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::semantic::GroupingChecker;
+use crate::sql::planner::semantic::WindowChecker;
 use crate::sql::plans::AggregateFunction;
 use crate::sql::plans::AndExpr;
 use crate::sql::plans::BoundColumnRef;
@@ -43,6 +45,7 @@ use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarItem;
 use crate::sql::plans::Sort;
 use crate::sql::plans::SortItem;
+use crate::sql::plans::UDFServerCall;
 use crate::sql::BindContext;
 use crate::sql::ScalarExpr;
 
@@ -255,6 +258,9 @@ impl<'a> Binder {
                     if from_context.in_grouping {
                         let mut group_checker = GroupingChecker::new(from_context);
                         scalar = group_checker.resolve(&scalar, None)?;
+                    } else if contain_window_function(&scalar)? {
+                        let mut window_checker = WindowChecker::new(from_context);
+                        scalar = window_checker.resolve(&scalar)?;
                     }
                     scalars.push(ScalarItem { scalar, index });
                 }
@@ -429,6 +435,17 @@ impl<'a> Binder {
                         return_type: return_type.clone(),
                     }))
                 }
+                Scalar::UDFServerCall(udf) => {
+                    let arguments = udf
+                        .arguments
+                        .iter()
+                        .map(|arg| self.rewrite_scalar_with_replacement(arg, replacement_fn))
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(Scalar::UDFServerCall(UDFServerCall {
+                        arguments,
+                        ..udf.clone()
+                    }))
+                }
                 Scalar::CastExpr(CastExpr {
                     argument,
                     from_type,
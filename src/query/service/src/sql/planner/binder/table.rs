@@ -15,13 +15,17 @@
 use std::sync::Arc;
 
 use common_ast::ast::Indirection;
+use common_ast::ast::SampleMethod;
 use common_ast::ast::SelectStmt;
 use common_ast::ast::SelectTarget;
 use common_ast::ast::Statement;
 use common_ast::ast::TableAlias;
 use common_ast::ast::TableReference;
+use common_ast::ast::TableSample;
 use common_ast::ast::TimeTravelPoint;
+use common_ast::parser::parse_expr;
 use common_ast::parser::parse_sql;
+use common_ast::parser::token::Token;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_ast::Dialect;
@@ -31,20 +35,29 @@ use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_legacy_expression::LegacyExpression;
+use common_legacy_planners::ScanSample;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
 use common_planner::IndexType;
+use common_users::UserApiProvider;
 
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::Binder;
 use crate::sql::binder::ColumnBinding;
 use crate::sql::binder::CteInfo;
 use crate::sql::binder::Visibility;
+use crate::sql::optimizer::ColumnStat;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::semantic::normalize_identifier;
 use crate::sql::planner::semantic::TypeChecker;
 use crate::sql::plans::ConstantExpr;
+use crate::sql::plans::EvalScalar;
 use crate::sql::plans::LogicalGet;
 use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarItem;
 use crate::sql::BindContext;
+use crate::storages::fuse::FuseTable;
+use crate::storages::index::VirtualColumnMeta;
 use crate::storages::view::view_table::QUERY;
 use crate::storages::NavigationPoint;
 use crate::storages::Table;
@@ -90,16 +103,22 @@ impl<'a> Binder {
     ) -> Result<(SExpr, BindContext)> {
         match table_ref {
             TableReference::Table {
-                span: _,
+                span,
                 catalog,
                 database,
                 table,
                 alias,
                 travel_point,
+                sample,
             } => {
                 let table_name = normalize_identifier(table, &self.name_resolution_ctx).name;
                 // Check and bind common table expression
                 if let Some(cte_info) = bind_context.ctes_map.read().get(&table_name) {
+                    if sample.is_some() {
+                        return Err(ErrorCode::SemanticError(span.display_error(
+                            "TABLESAMPLE is only supported by the FUSE tables".to_string(),
+                        )));
+                    }
                     return self.bind_cte(bind_context, &table_name, alias, cte_info);
                 }
                 // Get catalog name
@@ -131,6 +150,17 @@ impl<'a> Binder {
                         &navigation_point,
                     )
                     .await?;
+                let sample = match sample {
+                    Some(_) if table_meta.engine() != "FUSE" => {
+                        return Err(ErrorCode::SemanticError(span.display_error(format!(
+                            "TABLESAMPLE is only supported by the FUSE tables, but {} is {}",
+                            table_name,
+                            table_meta.engine()
+                        ))));
+                    }
+                    Some(sample) => Some(Self::bind_table_sample(span, sample)?),
+                    None => None,
+                };
                 match table_meta.engine() {
                     "VIEW" => {
                         let query = table_meta
@@ -150,14 +180,41 @@ impl<'a> Binder {
                         }
                     }
                     _ => {
-                        let table_index =
-                            self.metadata
-                                .write()
-                                .add_table(catalog, database.clone(), table_meta);
+                        let table_index = self.metadata.write().add_table(
+                            catalog.clone(),
+                            database.clone(),
+                            table_meta,
+                        );
 
                         let (s_expr, mut bind_context) = self
                             .bind_base_table(bind_context, database.as_str(), table_index)
                             .await?;
+                        let s_expr = match sample {
+                            Some(sample) => {
+                                let mut get: LogicalGet = s_expr.plan().clone().try_into()?;
+                                get.sample = Some(sample);
+                                SExpr::create_leaf(get.into())
+                            }
+                            None => s_expr,
+                        };
+                        let s_expr = self
+                            .bind_row_access_policies(
+                                &bind_context,
+                                &catalog,
+                                &database,
+                                &table_name,
+                                s_expr,
+                            )
+                            .await?;
+                        let s_expr = self
+                            .bind_masking_policies(
+                                &mut bind_context,
+                                &catalog,
+                                &database,
+                                &table_name,
+                                s_expr,
+                            )
+                            .await?;
                         if let Some(alias) = alias {
                             bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
                         }
@@ -167,6 +224,7 @@ impl<'a> Binder {
             }
             TableReference::TableFunction {
                 span: _,
+                lateral,
                 name,
                 params,
                 alias,
@@ -193,6 +251,10 @@ impl<'a> Binder {
                                 data_type: *data_type,
                             })
                         }
+                        _ if *lateral => Err(ErrorCode::UnImplement(format!(
+                            "LATERAL table function {} only accepts constant arguments",
+                            name
+                        ))),
                         _ => Err(ErrorCode::UnImplement(format!(
                             "Unsupported table argument type: {:?}",
                             scalar
@@ -229,6 +291,7 @@ impl<'a> Binder {
             TableReference::Join { span: _, join } => self.bind_join(bind_context, join).await,
             TableReference::Subquery {
                 span: _,
+                lateral: _,
                 subquery,
                 alias,
             } => {
@@ -282,15 +345,16 @@ impl<'a> Binder {
         Ok((cte_info.s_expr.clone(), new_bind_context))
     }
 
-    async fn bind_base_table(
+    pub(super) async fn bind_base_table(
         &mut self,
         bind_context: &BindContext,
         database_name: &str,
         table_index: IndexType,
     ) -> Result<(SExpr, BindContext)> {
         let mut bind_context = BindContext::with_parent(Box::new(bind_context.clone()));
-        let columns = self.metadata.read().columns_by_table_index(table_index);
         let table = self.metadata.read().table(table_index).clone();
+        self.add_virtual_columns(&table.table(), table_index)?;
+        let columns = self.metadata.read().columns_by_table_index(table_index);
         for column in columns.iter() {
             let column_binding = ColumnBinding {
                 database_name: Some(database_name.to_string()),
@@ -298,7 +362,7 @@ impl<'a> Binder {
                 column_name: column.name().to_string(),
                 index: column.index(),
                 data_type: Box::new(column.data_type().clone()),
-                visibility: if column.has_path_indices() {
+                visibility: if column.has_path_indices() || column.is_virtual_column() {
                     Visibility::InVisible
                 } else {
                     Visibility::Visible
@@ -307,6 +371,24 @@ impl<'a> Binder {
             bind_context.add_column_binding(column_binding);
         }
         let stat = table.table().statistics(self.ctx.clone()).await?;
+        let table_column_stats = table.table().column_statistics(self.ctx.clone()).await?;
+        // The columns of the schema come first, in the order of the schema.
+        let column_stats = columns
+            .iter()
+            .filter(|column| !column.has_path_indices() && !column.is_virtual_column())
+            .enumerate()
+            .filter_map(|(field_index, column)| {
+                let stat = table_column_stats.get(&field_index)?;
+                Some((column.index(), ColumnStat {
+                    min: stat.min.clone(),
+                    max: stat.max.clone(),
+                    ndv: stat.number_of_distinct_values as f64,
+                    null_count: stat.null_count,
+                    histogram: stat.histogram.clone(),
+                    is_exact: stat.is_exact,
+                }))
+            })
+            .collect();
         Ok((
             SExpr::create_leaf(
                 LogicalGet {
@@ -316,7 +398,9 @@ impl<'a> Binder {
                     limit: None,
                     order_by: None,
                     statistics: stat,
+                    column_stats,
                     prewhere: None,
+                    sample: None,
                 }
                 .into(),
             ),
@@ -324,7 +408,199 @@ impl<'a> Binder {
         ))
     }
 
-    async fn resolve_data_source(
+    /// Adds the virtual columns of a fuse table to the metadata, so that the path expressions
+    /// of the VARIANT columns are resolved to them.
+    fn add_virtual_columns(&self, table: &Arc<dyn Table>, table_index: IndexType) -> Result<()> {
+        let fuse_table = match FuseTable::try_from_table(table.as_ref()) {
+            Ok(fuse_table) => fuse_table,
+            Err(_) => return Ok(()),
+        };
+        let virtual_columns = match fuse_table.virtual_columns()? {
+            Some(virtual_columns) => virtual_columns,
+            None => return Ok(()),
+        };
+        let mut metadata = self.metadata.write();
+        if metadata
+            .columns_by_table_index(table_index)
+            .iter()
+            .any(|column| column.is_virtual_column())
+        {
+            return Ok(());
+        }
+        for column in virtual_columns.columns {
+            metadata.add_virtual_column(column.name(), VirtualColumnMeta::data_type(), table_index);
+        }
+        Ok(())
+    }
+
+    fn bind_table_sample(span: &[Token<'a>], sample: &TableSample) -> Result<ScanSample> {
+        if !(0.0..=100.0).contains(&sample.percent) {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "The percentage of TABLESAMPLE must be in [0, 100], but got {}",
+                sample.percent
+            ))));
+        }
+        let probability = sample.percent / 100.0;
+        Ok(match sample.method {
+            SampleMethod::System => ScanSample::Block(probability),
+            SampleMethod::Bernoulli => ScanSample::Row(probability),
+        })
+    }
+
+    // Filter the rows of the table by the row access policies on it, the rows are visible if the
+    // predicate of any policy of the session roles holds, and none of them is visible if the table
+    // has policies but none of the session roles. The users with SUPER privilege see all the rows.
+    async fn bind_row_access_policies(
+        &mut self,
+        bind_context: &BindContext,
+        catalog: &str,
+        database: &str,
+        table: &str,
+        s_expr: SExpr,
+    ) -> Result<SExpr> {
+        if database.eq_ignore_ascii_case("system")
+            || database.eq_ignore_ascii_case("information_schema")
+        {
+            return Ok(s_expr);
+        }
+
+        let tenant = self.ctx.get_tenant();
+        let policies = UserApiProvider::instance()
+            .get_row_access_policies(&tenant)
+            .await?
+            .into_iter()
+            .filter(|p| p.catalog == catalog && p.database == database && p.table == table)
+            .collect::<Vec<_>>();
+        if policies.is_empty() {
+            return Ok(s_expr);
+        }
+
+        let user = self.ctx.get_current_user()?;
+        let roles = self.ctx.get_related_roles().await?;
+        let super_verified = std::iter::once(&user.grants)
+            .chain(roles.iter().map(|r| &r.grants))
+            .any(|grants| grants.verify_privilege(&GrantObject::Global, UserPrivilegeType::Super));
+        if super_verified {
+            return Ok(s_expr);
+        }
+
+        let predicates = policies
+            .iter()
+            .filter(|p| roles.iter().any(|r| r.name == p.role))
+            .map(|p| format!("({})", p.predicate))
+            .collect::<Vec<_>>();
+        let predicate = if predicates.is_empty() {
+            "FALSE".to_string()
+        } else {
+            predicates.join(" OR ")
+        };
+
+        let sql_dialect = self.ctx.get_settings().get_sql_dialect()?;
+        let backtrace = Backtrace::new();
+        let tokens = tokenize_sql(&predicate)?;
+        let expr = parse_expr(&tokens, sql_dialect, &backtrace)?;
+        self.bind_where(bind_context, &expr, s_expr).await
+    }
+
+    // Replace the masked columns of the table with the bodies of their masking policies, the
+    // parameter of a policy is bound to the value of the column. The users with SUPER privilege
+    // and the sessions with an exempt role see the values unmasked.
+    async fn bind_masking_policies(
+        &mut self,
+        bind_context: &mut BindContext,
+        catalog: &str,
+        database: &str,
+        table: &str,
+        s_expr: SExpr,
+    ) -> Result<SExpr> {
+        if database.eq_ignore_ascii_case("system")
+            || database.eq_ignore_ascii_case("information_schema")
+        {
+            return Ok(s_expr);
+        }
+
+        let tenant = self.ctx.get_tenant();
+        let policies = UserApiProvider::instance()
+            .get_masking_policies(&tenant)
+            .await?
+            .into_iter()
+            .filter(|p| p.is_attached_to(catalog, database, table))
+            .collect::<Vec<_>>();
+        if policies.is_empty() {
+            return Ok(s_expr);
+        }
+
+        let user = self.ctx.get_current_user()?;
+        let roles = self.ctx.get_related_roles().await?;
+        let super_verified = std::iter::once(&user.grants)
+            .chain(roles.iter().map(|r| &r.grants))
+            .any(|grants| grants.verify_privilege(&GrantObject::Global, UserPrivilegeType::Super));
+        if super_verified {
+            return Ok(s_expr);
+        }
+
+        let sql_dialect = self.ctx.get_settings().get_sql_dialect()?;
+        let mut items = vec![];
+        for policy in policies.iter() {
+            if roles.iter().any(|r| policy.exempt_roles.contains(&r.name)) {
+                continue;
+            }
+            let masked_columns = policy
+                .columns
+                .iter()
+                .filter(|c| c.catalog == catalog && c.database == database && c.table == table);
+            for masked in masked_columns {
+                let column = match bind_context
+                    .columns
+                    .iter_mut()
+                    .find(|c| c.column_name == masked.column)
+                {
+                    Some(column) => column,
+                    None => continue,
+                };
+
+                // The body only sees the parameter, bound to the column.
+                let mut policy_context = BindContext::new();
+                policy_context.add_column_binding(ColumnBinding {
+                    database_name: None,
+                    table_name: None,
+                    column_name: policy.parameter.clone(),
+                    index: column.index,
+                    data_type: column.data_type.clone(),
+                    visibility: Visibility::Visible,
+                });
+
+                let backtrace = Backtrace::new();
+                let tokens = tokenize_sql(&policy.body)?;
+                let expr = parse_expr(&tokens, sql_dialect, &backtrace)?;
+                let mut scalar_binder = ScalarBinder::new(
+                    &policy_context,
+                    self.ctx.clone(),
+                    &self.name_resolution_ctx,
+                    self.metadata.clone(),
+                    &[],
+                );
+                let (scalar, data_type) = scalar_binder.bind(&expr).await?;
+
+                let index = self.metadata.write().add_column(
+                    column.column_name.clone(),
+                    data_type.clone(),
+                    None,
+                    None,
+                );
+                column.index = index;
+                column.data_type = Box::new(data_type);
+                items.push(ScalarItem { scalar, index });
+            }
+        }
+        if items.is_empty() {
+            return Ok(s_expr);
+        }
+
+        Ok(SExpr::create_unary(EvalScalar { items }.into(), s_expr))
+    }
+
+    pub(super) async fn resolve_data_source(
         &self,
         tenant: &str,
         catalog_name: &str,
@@ -342,7 +618,7 @@ impl<'a> Binder {
         Ok(table_meta)
     }
 
-    async fn resolve_data_travel_point(
+    pub(super) async fn resolve_data_travel_point(
         &self,
         bind_context: &BindContext,
         travel_point: &TimeTravelPoint<'a>,
@@ -0,0 +1,90 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateUDFServerStmt;
+use common_config::Config;
+use common_datavalues::DataType;
+use common_datavalues::TypeFactory;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::UDFServer;
+use common_meta_types::UserDefinedFunction;
+use common_planner::plans::CreateUDFPlan;
+
+use crate::sql::binder::Binder;
+use crate::sql::plans::Plan;
+
+/// Check that the UDF servers are enabled and `address` is in the allow list of the config.
+pub fn check_udf_server_allowed(config: &Config, address: &str) -> Result<()> {
+    if !config.query.enable_udf_server {
+        return Err(ErrorCode::PermissionDenied(
+            "UDF server is disabled, set `enable_udf_server` in the config to enable it",
+        ));
+    }
+    let allow_list = &config.query.udf_server_allow_list;
+    if !allow_list.is_empty() && !allow_list.iter().any(|allowed| allowed == address) {
+        return Err(ErrorCode::PermissionDenied(format!(
+            "UDF server address {} is not in the allow list {:?}",
+            address, allow_list
+        )));
+    }
+    Ok(())
+}
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_udf_server(
+        &mut self,
+        stmt: &CreateUDFServerStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateUDFServerStmt {
+            if_not_exists,
+            udf_name,
+            arg_types,
+            return_type,
+            language,
+            handler,
+            address,
+            description,
+        } = stmt;
+
+        check_udf_server_allowed(&self.ctx.get_config(), address)?;
+
+        let arg_types = arg_types
+            .iter()
+            .map(|arg_type| {
+                let data_type = TypeFactory::instance().get(arg_type.to_string())?;
+                Ok(data_type.name())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let return_type = TypeFactory::instance().get(return_type.to_string())?.name();
+
+        let server = UDFServer {
+            address: address.clone(),
+            handler: handler.clone(),
+            language: language.to_string(),
+            arg_types,
+            return_type,
+        };
+        let udf = UserDefinedFunction::new_udf_server(
+            &udf_name.to_string(),
+            server,
+            &description.clone().unwrap_or_default(),
+        );
+
+        Ok(Plan::CreateUDF(Box::new(CreateUDFPlan {
+            if_not_exists: *if_not_exists,
+            udf,
+        })))
+    }
+}
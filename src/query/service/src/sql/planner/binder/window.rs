@@ -0,0 +1,140 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::Result;
+
+use crate::sql::binder::find_window_functions;
+use crate::sql::binder::select::SelectList;
+use crate::sql::binder::Binder;
+use crate::sql::optimizer::SExpr;
+use crate::sql::planner::semantic::GroupingChecker;
+use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::EvalScalar;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+use crate::sql::plans::ScalarItem;
+use crate::sql::plans::Window;
+use crate::sql::plans::WindowFunction;
+use crate::sql::BindContext;
+
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct WindowInfo {
+    /// Window functions
+    pub window_functions: Vec<ScalarItem>,
+
+    /// Mapping: (window function display name) -> (index of window func in `window_functions`)
+    /// This is used to find a window function in current context.
+    pub window_functions_map: HashMap<String, usize>,
+}
+
+impl<'a> Binder {
+    /// Analyze window functions in select clause, each distinct window function will be
+    /// evaluated by a `Window` plan into a new column.
+    ///
+    /// This must be called after `analyze_aggregate_select`, since the aggregate functions
+    /// in the arguments of window functions have to be rewritten first.
+    pub(super) fn analyze_window_select(
+        &mut self,
+        bind_context: &mut BindContext,
+        select_list: &SelectList<'a>,
+    ) -> Result<()> {
+        for item in select_list.items.iter() {
+            for scalar in find_window_functions(&item.scalar)? {
+                let func = WindowFunction::try_from(scalar)?;
+                let window_info = &mut bind_context.window_info;
+                if window_info
+                    .window_functions_map
+                    .contains_key(&func.display_name)
+                {
+                    continue;
+                }
+
+                let index = self.metadata.write().add_column(
+                    func.display_name.clone(),
+                    *func.return_type.clone(),
+                    None,
+                    None,
+                );
+                window_info.window_functions_map.insert(
+                    func.display_name.clone(),
+                    window_info.window_functions.len(),
+                );
+                window_info.window_functions.push(ScalarItem {
+                    scalar: func.into(),
+                    index,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Bind the window functions, which are evaluated after `HAVING` and before `DISTINCT`.
+    ///
+    /// The arguments, partition keys and order keys which are not columns are evaluated by
+    /// an `EvalScalar` plan first, then each window function is evaluated by a `Window` plan.
+    pub(super) fn bind_window(
+        &mut self,
+        bind_context: &BindContext,
+        child: SExpr,
+    ) -> Result<SExpr> {
+        let mut scalar_items = vec![];
+        let mut windows = Vec::with_capacity(bind_context.window_info.window_functions.len());
+        for item in bind_context.window_info.window_functions.iter() {
+            let mut func = WindowFunction::try_from(item.scalar.clone())?;
+            let arg_name = format!("{}_arg", func.display_name);
+            for scalar in func.inner_scalars_mut() {
+                if bind_context.in_grouping {
+                    let mut grouping_checker = GroupingChecker::new(bind_context);
+                    *scalar = grouping_checker.resolve(scalar, None)?;
+                }
+                if !matches!(scalar, Scalar::BoundColumnRef(_)) {
+                    let column_binding = self.create_column_binding(
+                        None,
+                        None,
+                        arg_name.clone(),
+                        scalar.data_type(),
+                    );
+                    scalar_items.push(ScalarItem {
+                        scalar: scalar.clone(),
+                        index: column_binding.index,
+                    });
+                    *scalar = BoundColumnRef {
+                        column: column_binding,
+                    }
+                    .into();
+                }
+            }
+            windows.push(Window {
+                index: item.index,
+                function: func,
+            });
+        }
+
+        let mut new_expr = child;
+        if !scalar_items.is_empty() {
+            let eval_scalar = EvalScalar {
+                items: scalar_items,
+            };
+            new_expr = SExpr::create_unary(eval_scalar.into(), new_expr);
+        }
+        for window in windows {
+            new_expr = SExpr::create_unary(window.into(), new_expr);
+        }
+
+        Ok(new_expr)
+    }
+}
@@ -45,6 +45,7 @@ impl Plan {
             // Tables
             Plan::ShowCreateTable(show_create_table) => Ok(format!("{:?}", show_create_table)),
             Plan::CreateTable(create_table) => Ok(format!("{:?}", create_table)),
+            Plan::AttachTable(attach_table) => Ok(format!("{:?}", attach_table)),
             Plan::DropTable(drop_table) => Ok(format!("{:?}", drop_table)),
             Plan::UndropTable(undrop_table) => Ok(format!("{:?}", undrop_table)),
             Plan::DescribeTable(describe_table) => Ok(format!("{:?}", describe_table)),
@@ -52,18 +53,35 @@ impl Plan {
             Plan::AlterTableClusterKey(alter_table_cluster_key) => {
                 Ok(format!("{:?}", alter_table_cluster_key))
             }
+            Plan::AlterTableColumn(alter_table_column) => Ok(format!("{:?}", alter_table_column)),
             Plan::DropTableClusterKey(drop_table_cluster_key) => {
                 Ok(format!("{:?}", drop_table_cluster_key))
             }
             Plan::ReclusterTable(recluster_table) => Ok(format!("{:?}", recluster_table)),
             Plan::TruncateTable(truncate_table) => Ok(format!("{:?}", truncate_table)),
+            Plan::FlashbackTable(flashback_table) => Ok(format!("{:?}", flashback_table)),
             Plan::OptimizeTable(optimize_table) => Ok(format!("{:?}", optimize_table)),
+            Plan::AnalyzeTable(analyze_table) => Ok(format!("{:?}", analyze_table)),
             Plan::ExistsTable(exists_table) => Ok(format!("{:?}", exists_table)),
 
             // Views
             Plan::CreateView(create_view) => Ok(format!("{:?}", create_view)),
             Plan::AlterView(alter_view) => Ok(format!("{:?}", alter_view)),
             Plan::DropView(drop_view) => Ok(format!("{:?}", drop_view)),
+            Plan::CreateStream(create_stream) => Ok(format!("{:?}", create_stream)),
+            Plan::DropStream(drop_stream) => Ok(format!("{:?}", drop_stream)),
+
+            // Aggregating indexes
+            Plan::CreateAggregatingIndex(create_index) => Ok(format!("{:?}", create_index)),
+            Plan::DropAggregatingIndex(drop_index) => Ok(format!("{:?}", drop_index)),
+            Plan::CreateBloomIndex(create_index) => Ok(format!("{:?}", create_index)),
+            Plan::CreateInvertedIndex(create_index) => Ok(format!("{:?}", create_index)),
+            Plan::DropIndex(drop_index) => Ok(format!("{:?}", drop_index)),
+
+            // Virtual columns
+            Plan::CreateVirtualColumns(create) => Ok(format!("{:?}", create)),
+            Plan::AlterVirtualColumns(alter) => Ok(format!("{:?}", alter)),
+            Plan::DropVirtualColumns(drop) => Ok(format!("{:?}", drop)),
 
             // Insert
             Plan::Insert(insert) => Ok(format!("{:?}", insert)),
@@ -89,6 +107,19 @@ impl Plan {
             Plan::AlterUser(alter_user) => Ok(format!("{:?}", alter_user)),
             Plan::CreateRole(create_role) => Ok(format!("{:?}", create_role)),
             Plan::DropRole(drop_role) => Ok(format!("{:?}", drop_role)),
+            Plan::SetRole(set_role) => Ok(format!("{:?}", set_role)),
+            Plan::CreateRowAccessPolicy(create_policy) => Ok(format!("{:?}", create_policy)),
+            Plan::DropRowAccessPolicy(drop_policy) => Ok(format!("{:?}", drop_policy)),
+            Plan::CreateMaskingPolicy(create_policy) => Ok(format!("{:?}", create_policy)),
+            Plan::DropMaskingPolicy(drop_policy) => Ok(format!("{:?}", drop_policy)),
+            Plan::SetColumnMaskingPolicy(set_policy) => Ok(format!("{:?}", set_policy)),
+            Plan::UnsetColumnMaskingPolicy(unset_policy) => Ok(format!("{:?}", unset_policy)),
+            Plan::CreateNetworkPolicy(create_policy) => Ok(format!("{:?}", create_policy)),
+            Plan::DropNetworkPolicy(drop_policy) => Ok(format!("{:?}", drop_policy)),
+            Plan::CreateWorkloadGroup(create_group) => Ok(format!("{:?}", create_group)),
+            Plan::DropWorkloadGroup(drop_group) => Ok(format!("{:?}", drop_group)),
+            Plan::CreateTask(create_task) => Ok(format!("{:?}", create_task)),
+            Plan::DropTask(drop_task) => Ok(format!("{:?}", drop_task)),
 
             Plan::Presign(presign) => Ok(format!("{:?}", presign)),
 
@@ -105,6 +136,10 @@ impl Plan {
             Plan::ShowShares(p) => Ok(format!("{:?}", p)),
             Plan::ShowObjectGrantPrivileges(p) => Ok(format!("{:?}", p)),
             Plan::ShowGrantTenantsOfShare(p) => Ok(format!("{:?}", p)),
+
+            Plan::Begin => Ok("BEGIN".to_string()),
+            Plan::Commit => Ok("COMMIT".to_string()),
+            Plan::Rollback => Ok("ROLLBACK".to_string()),
         }
     }
 }
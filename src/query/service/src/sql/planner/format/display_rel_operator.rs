@@ -38,6 +38,7 @@ use crate::sql::plans::PhysicalScan;
 use crate::sql::plans::RelOperator;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::Sort;
+use crate::sql::plans::Window;
 use crate::sql::ScalarExpr;
 
 #[derive(Clone)]
@@ -81,6 +82,9 @@ impl Display for FormatContext {
                 RelOperator::UnionAll(_) => write!(f, "Union"),
                 RelOperator::Pattern(_) => write!(f, "Pattern"),
                 RelOperator::DummyTableScan(_) => write!(f, "DummyTableScan"),
+                RelOperator::Window(_) => write!(f, "Window"),
+                RelOperator::RecursiveUnion(_) => write!(f, "RecursiveUnion"),
+                RelOperator::RecursiveCteScan(_) => write!(f, "RecursiveCteScan"),
             },
             Self::Text(text) => write!(f, "{}", text),
         }
@@ -120,6 +124,7 @@ pub fn format_scalar(_metadata: &MetadataRef, scalar: &Scalar) -> String {
             format_scalar(_metadata, &comp.right)
         ),
         Scalar::AggregateFunction(agg) => agg.display_name.clone(),
+        Scalar::WindowFunction(func) => func.display_name.clone(),
         Scalar::FunctionCall(func) => {
             format!(
                 "{}({})",
@@ -139,6 +144,17 @@ pub fn format_scalar(_metadata: &MetadataRef, scalar: &Scalar) -> String {
             )
         }
         Scalar::SubqueryExpr(_) => "SUBQUERY".to_string(),
+        Scalar::UDFServerCall(udf) => {
+            format!(
+                "{}({})",
+                &udf.func_name,
+                udf.arguments
+                    .iter()
+                    .map(|arg| { format_scalar(_metadata, arg) })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        }
     }
 }
 
@@ -217,6 +233,7 @@ fn to_format_tree(
         RelOperator::Sort(op) => sort_to_format_tree(op, metadata, children),
         RelOperator::Limit(op) => limit_to_format_tree(op, metadata, children),
         RelOperator::Exchange(op) => exchange_to_format_tree(op, metadata, children),
+        RelOperator::Window(op) => window_to_format_tree(op, metadata, children),
 
         _ => FormatTreeNode::with_children(
             FormatContext::RelOp {
@@ -586,6 +603,55 @@ fn sort_to_format_tree(
     )
 }
 
+fn window_to_format_tree(
+    op: &Window,
+    metadata: MetadataRef,
+    children: Vec<FormatTreeNode<FormatContext>>,
+) -> FormatTreeNode<FormatContext> {
+    let func = &op.function;
+    let partition_by = func
+        .partition_by
+        .iter()
+        .map(|scalar| format_scalar(&metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let order_by = func
+        .order_by
+        .iter()
+        .map(|item| {
+            format!(
+                "{} {}",
+                format_scalar(&metadata, &item.expr),
+                if item.asc { "ASC" } else { "DESC" }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    FormatTreeNode::with_children(
+        FormatContext::RelOp {
+            metadata,
+            rel_operator: Box::new(op.clone().into()),
+        },
+        vec![
+            vec![
+                FormatTreeNode::new(FormatContext::Text(format!(
+                    "function: {} (#{})",
+                    func.display_name, op.index
+                ))),
+                FormatTreeNode::new(FormatContext::Text(format!(
+                    "partition by: [{}]",
+                    partition_by
+                ))),
+                FormatTreeNode::new(FormatContext::Text(format!("order by: [{}]", order_by))),
+                FormatTreeNode::new(FormatContext::Text(format!("frame: [{}]", func.frame))),
+            ],
+            children,
+        ]
+        .concat(),
+    )
+}
+
 fn limit_to_format_tree(
     op: &Limit,
     metadata: MetadataRef,
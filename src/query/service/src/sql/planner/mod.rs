@@ -22,6 +22,7 @@ pub(crate) mod binder;
 pub mod optimizer;
 pub mod plans;
 
+pub use binder::parse_copy_file_format_options;
 pub use binder::BindContext;
 pub use binder::Binder;
 pub use binder::ColumnBinding;
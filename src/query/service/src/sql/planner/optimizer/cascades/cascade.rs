@@ -176,7 +176,14 @@ impl CascadesOptimizer {
             .map(|index| self.find_optimal_plan(*index))
             .collect::<Result<Vec<_>>>()?;
 
-        let result = SExpr::create(m_expr.plan.clone(), children, None, None);
+        // The properties and the cost are kept for estimating the plan in EXPLAIN.
+        let mut result = SExpr::create(
+            m_expr.plan.clone(),
+            children,
+            None,
+            Some(Box::new(group.relational_prop.clone())),
+        );
+        result.cost = Some(cost_context.cost);
 
         Ok(result)
     }
@@ -19,6 +19,7 @@ use super::Cost;
 use super::CostModel;
 use crate::sql::optimizer::MExpr;
 use crate::sql::optimizer::Memo;
+use crate::sql::optimizer::SelectivityEstimator;
 use crate::sql::plans::PhysicalHashJoin;
 use crate::sql::plans::PhysicalScan;
 use crate::sql::plans::RelOperator;
@@ -40,13 +41,17 @@ fn compute_cost_impl(memo: &Memo, m_expr: &MExpr) -> Result<Cost> {
         RelOperator::PhysicalScan(plan) => compute_cost_physical_scan(memo, m_expr, plan),
         RelOperator::DummyTableScan(_) => Ok(Cost(0.0)),
         RelOperator::PhysicalHashJoin(plan) => compute_cost_hash_join(memo, m_expr, plan),
-        RelOperator::UnionAll(_) => compute_cost_union_all(memo, m_expr),
+        RelOperator::UnionAll(_) | RelOperator::RecursiveUnion(_) => {
+            compute_cost_union_all(memo, m_expr)
+        }
+        RelOperator::RecursiveCteScan(_) => Ok(Cost(0.0)),
 
         RelOperator::EvalScalar(_)
         | RelOperator::Filter(_)
         | RelOperator::Aggregate(_)
         | RelOperator::Sort(_)
-        | RelOperator::Limit(_) => compute_cost_unary_common_operator(memo, m_expr),
+        | RelOperator::Limit(_)
+        | RelOperator::Window(_) => compute_cost_unary_common_operator(memo, m_expr),
 
         _ => Err(ErrorCode::LogicalError(
             "Cannot compute cost from logical plan",
@@ -54,12 +59,22 @@ fn compute_cost_impl(memo: &Memo, m_expr: &MExpr) -> Result<Cost> {
     }
 }
 
-fn compute_cost_physical_scan(memo: &Memo, m_expr: &MExpr, _plan: &PhysicalScan) -> Result<Cost> {
+fn compute_cost_physical_scan(memo: &Memo, m_expr: &MExpr, plan: &PhysicalScan) -> Result<Cost> {
     // Since we don't have alternations(e.g. index scan) for table scan for now, we just ignore
     // the I/O cost and treat `PhysicalScan` as normal computation.
+    //
+    // The blocks can be pruned by the pushed down predicates with their min/max indexes, so
+    // only the rows estimated to satisfy the predicates are counted.
     let group = memo.group(m_expr.group_index)?;
     let prop = &group.relational_prop;
-    let cost = prop.cardinality * COST_FACTOR_COMPUTE_PER_ROW;
+    let estimator = SelectivityEstimator::new(&prop.column_stats);
+    let selectivity = plan
+        .push_down_predicates
+        .iter()
+        .flatten()
+        .map(|predicate| estimator.compute_selectivity(predicate))
+        .product::<f64>();
+    let cost = prop.cardinality * selectivity * COST_FACTOR_COMPUTE_PER_ROW;
     Ok(Cost(cost))
 }
 
@@ -0,0 +1,27 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod rewriter;
+mod rule_eliminate_cast;
+mod rule_fold_constant;
+mod rule_normalize_predicate;
+mod rule_propagate_null;
+
+pub use rewriter::ScalarRewriter;
+pub use rewriter::ScalarRule;
+pub use rewriter::DEFAULT_SCALAR_RULES;
+pub use rule_eliminate_cast::RuleEliminateCast;
+pub use rule_fold_constant::RuleFoldConstant;
+pub use rule_normalize_predicate::RuleNormalizePredicate;
+pub use rule_propagate_null::RulePropagateNull;
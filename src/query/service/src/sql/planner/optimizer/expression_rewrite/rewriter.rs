@@ -0,0 +1,201 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+use once_cell::sync::Lazy;
+
+use crate::sql::optimizer::expression_rewrite::RuleEliminateCast;
+use crate::sql::optimizer::expression_rewrite::RuleFoldConstant;
+use crate::sql::optimizer::expression_rewrite::RuleNormalizePredicate;
+use crate::sql::optimizer::expression_rewrite::RulePropagateNull;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+
+/// A rule rewriting a scalar expression into an equivalent one.
+pub trait ScalarRule: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Rewrite the root of `scalar`, whose arguments have been rewritten already. Return `None`
+    /// if the rule doesn't apply.
+    ///
+    /// The result must be simpler than `scalar` in some way, the rules are applied again to it
+    /// until none of them applies.
+    fn apply(&self, scalar: &Scalar, func_ctx: &FunctionContext) -> Result<Option<Scalar>>;
+}
+
+pub static DEFAULT_SCALAR_RULES: Lazy<Vec<Arc<dyn ScalarRule>>> = Lazy::new(|| {
+    vec![
+        Arc::new(RuleEliminateCast),
+        Arc::new(RulePropagateNull),
+        Arc::new(RuleNormalizePredicate),
+        Arc::new(RuleFoldConstant),
+    ]
+});
+
+/// Rewrite the scalar expressions bottom-up with a list of `ScalarRule`s.
+///
+/// The rules are tried in the order they are registered, the first one that applies wins.
+pub struct ScalarRewriter {
+    func_ctx: FunctionContext,
+    rules: Vec<Arc<dyn ScalarRule>>,
+}
+
+impl ScalarRewriter {
+    /// Create a rewriter without any rule.
+    pub fn new(func_ctx: FunctionContext) -> Self {
+        Self {
+            func_ctx,
+            rules: vec![],
+        }
+    }
+
+    /// Create a rewriter with the `DEFAULT_SCALAR_RULES`.
+    pub fn with_default_rules(func_ctx: FunctionContext) -> Self {
+        Self {
+            func_ctx,
+            rules: DEFAULT_SCALAR_RULES.clone(),
+        }
+    }
+
+    /// Register a rule, it is tried after the rules already registered.
+    pub fn register(&mut self, rule: Arc<dyn ScalarRule>) {
+        self.rules.push(rule);
+    }
+
+    /// Rewrite `scalar` and all its arguments.
+    pub fn rewrite(&self, scalar: &Scalar) -> Result<Scalar> {
+        let scalar = self.rewrite_arguments(scalar)?;
+        self.rewrite_root(scalar)
+    }
+
+    /// Rewrite the root of `scalar` only, its arguments are assumed rewritten already.
+    pub fn rewrite_root(&self, mut scalar: Scalar) -> Result<Scalar> {
+        'apply: loop {
+            for rule in self.rules.iter() {
+                if let Some(result) = rule.apply(&scalar, &self.func_ctx)? {
+                    scalar = result;
+                    continue 'apply;
+                }
+            }
+            return Ok(scalar);
+        }
+    }
+
+    /// Rewrite the scalar expressions of all the operators of the plan.
+    pub fn rewrite_plan(&self, s_expr: &SExpr) -> Result<SExpr> {
+        let children = s_expr
+            .children()
+            .iter()
+            .map(|child| self.rewrite_plan(child))
+            .collect::<Result<Vec<_>>>()?;
+
+        let plan = match s_expr.plan().clone() {
+            RelOperator::Filter(mut filter) => {
+                for predicate in filter.predicates.iter_mut() {
+                    *predicate = self.rewrite(predicate)?;
+                }
+                filter.into()
+            }
+            RelOperator::EvalScalar(mut eval_scalar) => {
+                for item in eval_scalar.items.iter_mut() {
+                    item.scalar = self.rewrite(&item.scalar)?;
+                }
+                eval_scalar.into()
+            }
+            RelOperator::Aggregate(mut aggregate) => {
+                for item in aggregate
+                    .group_items
+                    .iter_mut()
+                    .chain(aggregate.aggregate_functions.iter_mut())
+                {
+                    item.scalar = self.rewrite(&item.scalar)?;
+                }
+                aggregate.into()
+            }
+            RelOperator::LogicalInnerJoin(mut join) => {
+                for condition in join
+                    .left_conditions
+                    .iter_mut()
+                    .chain(join.right_conditions.iter_mut())
+                    .chain(join.other_conditions.iter_mut())
+                {
+                    *condition = self.rewrite(condition)?;
+                }
+                join.into()
+            }
+            RelOperator::Window(mut window) => {
+                for scalar in window.function.inner_scalars_mut() {
+                    *scalar = self.rewrite(scalar)?;
+                }
+                window.into()
+            }
+            plan => plan,
+        };
+
+        Ok(SExpr::create(plan, children, s_expr.original_group(), None))
+    }
+
+    fn rewrite_arguments(&self, scalar: &Scalar) -> Result<Scalar> {
+        let mut scalar = scalar.clone();
+        match &mut scalar {
+            Scalar::BoundColumnRef(_) | Scalar::ConstantExpr(_) => {}
+            Scalar::AndExpr(and) => {
+                and.left = Box::new(self.rewrite(&and.left)?);
+                and.right = Box::new(self.rewrite(&and.right)?);
+            }
+            Scalar::OrExpr(or) => {
+                or.left = Box::new(self.rewrite(&or.left)?);
+                or.right = Box::new(self.rewrite(&or.right)?);
+            }
+            Scalar::ComparisonExpr(comparison) => {
+                comparison.left = Box::new(self.rewrite(&comparison.left)?);
+                comparison.right = Box::new(self.rewrite(&comparison.right)?);
+            }
+            Scalar::AggregateFunction(aggregate) => {
+                for arg in aggregate.args.iter_mut() {
+                    *arg = self.rewrite(arg)?;
+                }
+            }
+            Scalar::WindowFunction(window) => {
+                for arg in window.inner_scalars_mut() {
+                    *arg = self.rewrite(arg)?;
+                }
+            }
+            Scalar::FunctionCall(func) => {
+                for arg in func.arguments.iter_mut() {
+                    *arg = self.rewrite(arg)?;
+                }
+            }
+            Scalar::UDFServerCall(udf) => {
+                for arg in udf.arguments.iter_mut() {
+                    *arg = self.rewrite(arg)?;
+                }
+            }
+            Scalar::CastExpr(cast) => {
+                cast.argument = Box::new(self.rewrite(&cast.argument)?);
+            }
+            Scalar::SubqueryExpr(subquery) => {
+                if let Some(child_expr) = &subquery.child_expr {
+                    subquery.child_expr = Some(Box::new(self.rewrite(child_expr)?));
+                }
+                subquery.subquery = Box::new(self.rewrite_plan(&subquery.subquery)?);
+            }
+        }
+        Ok(scalar)
+    }
+}
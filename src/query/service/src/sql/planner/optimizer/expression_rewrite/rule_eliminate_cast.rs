@@ -0,0 +1,39 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+
+use crate::sql::optimizer::expression_rewrite::ScalarRule;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+
+/// Remove the casts of an expression to its own type, e.g. `CAST(a AS UInt64)` where `a` is
+/// an `UInt64` => `a`.
+pub struct RuleEliminateCast;
+
+impl ScalarRule for RuleEliminateCast {
+    fn name(&self) -> &'static str {
+        "EliminateCast"
+    }
+
+    fn apply(&self, scalar: &Scalar, _func_ctx: &FunctionContext) -> Result<Option<Scalar>> {
+        match scalar {
+            Scalar::CastExpr(cast) if cast.argument.data_type() == *cast.target_type => {
+                Ok(Some(*cast.argument.clone()))
+            }
+            _ => Ok(None),
+        }
+    }
+}
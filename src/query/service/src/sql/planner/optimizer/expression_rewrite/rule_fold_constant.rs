@@ -0,0 +1,51 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+
+use crate::evaluator::Evaluator;
+use crate::sql::optimizer::expression_rewrite::ScalarRule;
+use crate::sql::plans::ConstantExpr;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+
+/// Evaluate the deterministic expressions without any column, e.g. `1 + 1` => `2`.
+pub struct RuleFoldConstant;
+
+impl ScalarRule for RuleFoldConstant {
+    fn name(&self) -> &'static str {
+        "FoldConstant"
+    }
+
+    fn apply(&self, scalar: &Scalar, func_ctx: &FunctionContext) -> Result<Option<Scalar>> {
+        if matches!(scalar, Scalar::ConstantExpr(_))
+            || !scalar.used_columns().is_empty()
+            || !scalar.is_deterministic()
+        {
+            return Ok(None);
+        }
+
+        // The expressions failing to evaluate, e.g. `1 / 0`, are left to fail at runtime.
+        let folded = Evaluator::eval_scalar(scalar).and_then(|evaluator| {
+            let (value, data_type) = evaluator.try_eval_const(func_ctx)?;
+            Ok(ConstantExpr {
+                value,
+                data_type: Box::new(data_type),
+            }
+            .into())
+        });
+        Ok(folded.ok())
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+
+use crate::sql::optimizer::expression_rewrite::ScalarRule;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::ComparisonOp;
+use crate::sql::plans::ConstantExpr;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+
+/// Simplify the boolean expressions:
+/// - `TRUE AND a` => `a`, `FALSE AND a` => `FALSE`
+/// - `TRUE OR a` => `TRUE`, `FALSE OR a` => `a`
+/// - `NOT NOT a` => `a`
+/// - `NOT a = b` => `a <> b`, and likewise for the other comparisons
+pub struct RuleNormalizePredicate;
+
+impl RuleNormalizePredicate {
+    fn is_boolean(scalar: &Scalar, expected: bool) -> bool {
+        matches!(
+            scalar,
+            Scalar::ConstantExpr(ConstantExpr {
+                value: DataValue::Boolean(value),
+                ..
+            }) if *value == expected
+        )
+    }
+
+    fn boolean(value: bool, data_type: &DataTypeImpl) -> Scalar {
+        ConstantExpr {
+            value: DataValue::Boolean(value),
+            data_type: Box::new(data_type.clone()),
+        }
+        .into()
+    }
+
+    /// `scalar` if it can replace an expression of `data_type`, the boolean operators accept the
+    /// arguments of the other types too.
+    fn replacement(scalar: &Scalar, data_type: &DataTypeImpl) -> Option<Scalar> {
+        if scalar.data_type() == *data_type {
+            Some(scalar.clone())
+        } else {
+            None
+        }
+    }
+
+    fn negate(op: &ComparisonOp) -> ComparisonOp {
+        match op {
+            ComparisonOp::Equal => ComparisonOp::NotEqual,
+            ComparisonOp::NotEqual => ComparisonOp::Equal,
+            ComparisonOp::GT => ComparisonOp::LTE,
+            ComparisonOp::LTE => ComparisonOp::GT,
+            ComparisonOp::LT => ComparisonOp::GTE,
+            ComparisonOp::GTE => ComparisonOp::LT,
+        }
+    }
+
+    fn not_argument(scalar: &Scalar) -> Option<&Scalar> {
+        match scalar {
+            Scalar::FunctionCall(func)
+                if func.func_name.eq_ignore_ascii_case("not") && func.arguments.len() == 1 =>
+            {
+                Some(&func.arguments[0])
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ScalarRule for RuleNormalizePredicate {
+    fn name(&self) -> &'static str {
+        "NormalizePredicate"
+    }
+
+    fn apply(&self, scalar: &Scalar, _func_ctx: &FunctionContext) -> Result<Option<Scalar>> {
+        match scalar {
+            Scalar::AndExpr(and) => {
+                if Self::is_boolean(&and.left, false) || Self::is_boolean(&and.right, false) {
+                    return Ok(Some(Self::boolean(false, &and.return_type)));
+                }
+                if Self::is_boolean(&and.left, true) {
+                    return Ok(Self::replacement(&and.right, &and.return_type));
+                }
+                if Self::is_boolean(&and.right, true) {
+                    return Ok(Self::replacement(&and.left, &and.return_type));
+                }
+                Ok(None)
+            }
+            Scalar::OrExpr(or) => {
+                if Self::is_boolean(&or.left, true) || Self::is_boolean(&or.right, true) {
+                    return Ok(Some(Self::boolean(true, &or.return_type)));
+                }
+                if Self::is_boolean(&or.left, false) {
+                    return Ok(Self::replacement(&or.right, &or.return_type));
+                }
+                if Self::is_boolean(&or.right, false) {
+                    return Ok(Self::replacement(&or.left, &or.return_type));
+                }
+                Ok(None)
+            }
+            Scalar::FunctionCall(_) => match Self::not_argument(scalar) {
+                Some(argument) => match argument {
+                    Scalar::ComparisonExpr(comparison) => Ok(Some(
+                        ComparisonExpr {
+                            op: Self::negate(&comparison.op),
+                            left: comparison.left.clone(),
+                            right: comparison.right.clone(),
+                            return_type: comparison.return_type.clone(),
+                        }
+                        .into(),
+                    )),
+                    _ => Ok(Self::not_argument(argument)
+                        .and_then(|argument| Self::replacement(argument, &scalar.data_type()))),
+                },
+                None => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    }
+}
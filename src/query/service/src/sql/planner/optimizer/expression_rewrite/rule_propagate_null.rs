@@ -0,0 +1,85 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+use common_functions::scalars::FunctionFactory;
+
+use crate::sql::optimizer::expression_rewrite::ScalarRule;
+use crate::sql::plans::ConstantExpr;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+
+/// Replace the calls of the functions returning NULL for any NULL argument, by NULL, if one of
+/// their arguments is NULL, e.g. `a + NULL` => `NULL`.
+pub struct RulePropagateNull;
+
+impl RulePropagateNull {
+    fn is_null(scalar: &Scalar) -> bool {
+        matches!(
+            scalar,
+            Scalar::ConstantExpr(ConstantExpr {
+                value: DataValue::Null,
+                ..
+            })
+        )
+    }
+
+    fn passthrough_null(func_name: &str) -> bool {
+        FunctionFactory::instance()
+            .get_features(func_name)
+            .map_or(false, |features| features.passthrough_null)
+    }
+
+    fn null_of(data_type: DataTypeImpl) -> Option<Scalar> {
+        // NULL is only a value of the nullable types.
+        if !data_type.is_nullable() && !data_type.is_null() {
+            return None;
+        }
+        Some(
+            ConstantExpr {
+                value: DataValue::Null,
+                data_type: Box::new(data_type),
+            }
+            .into(),
+        )
+    }
+}
+
+impl ScalarRule for RulePropagateNull {
+    fn name(&self) -> &'static str {
+        "PropagateNull"
+    }
+
+    fn apply(&self, scalar: &Scalar, _func_ctx: &FunctionContext) -> Result<Option<Scalar>> {
+        let propagate = match scalar {
+            Scalar::ComparisonExpr(comparison) => {
+                (Self::is_null(&comparison.left) || Self::is_null(&comparison.right))
+                    && Self::passthrough_null(&comparison.op.to_func_name())
+            }
+            Scalar::FunctionCall(func) => {
+                func.arguments.iter().any(Self::is_null) && Self::passthrough_null(&func.func_name)
+            }
+            _ => false,
+        };
+
+        if propagate {
+            return Ok(Self::null_of(scalar.data_type()));
+        }
+        Ok(None)
+    }
+}
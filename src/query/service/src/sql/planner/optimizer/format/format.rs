@@ -66,5 +66,8 @@ pub fn display_rel_op(rel_op: &RelOperator) -> String {
         RelOperator::Exchange(_) => "Exchange".to_string(),
         RelOperator::Pattern(_) => "Pattern".to_string(),
         RelOperator::DummyTableScan(_) => "DummyTableScan".to_string(),
+        RelOperator::Window(_) => "Window".to_string(),
+        RelOperator::RecursiveUnion(_) => "RecursiveUnion".to_string(),
+        RelOperator::RecursiveCteScan(_) => "RecursiveCteScan".to_string(),
     }
 }
@@ -15,6 +15,7 @@
 use std::collections::HashSet;
 
 use common_datavalues::type_coercion::compare_coercion;
+use common_datavalues::wrap_nullable;
 use common_datavalues::BooleanType;
 use common_datavalues::DataTypeImpl;
 use common_datavalues::NullableType;
@@ -24,6 +25,7 @@ use common_planner::IndexType;
 use common_planner::MetadataRef;
 
 use crate::sql::binder::wrap_cast;
+use crate::sql::binder::wrap_cast_if_needed;
 use crate::sql::binder::JoinPredicate;
 use crate::sql::binder::Visibility;
 use crate::sql::optimizer::heuristic::subquery_rewriter::FlattenInfo;
@@ -53,6 +55,7 @@ use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarItem;
 use crate::sql::plans::SubqueryExpr;
 use crate::sql::plans::SubqueryType;
+use crate::sql::plans::UDFServerCall;
 use crate::sql::ColumnBinding;
 use crate::sql::ScalarExpr;
 
@@ -357,6 +360,120 @@ impl SubqueryRewriter {
         }
     }
 
+    /// Decorrelate the right side of a LATERAL join, which references the columns of the left
+    /// side. The right side is flattened over the distinct values of the correlated columns of
+    /// the left side, then joined with the left side by them.
+    pub fn try_decorrelate_lateral_join(
+        &mut self,
+        join: &LogicalInnerJoin,
+        left: &SExpr,
+        right: &SExpr,
+        correlated_columns: &ColumnSet,
+    ) -> Result<SExpr> {
+        let mut join_type = match join.join_type {
+            JoinType::Inner | JoinType::Cross => JoinType::Inner,
+            JoinType::Left => JoinType::Left,
+            _ => {
+                return Err(ErrorCode::SemanticError(format!(
+                    "LATERAL is not supported in {} join",
+                    join.join_type
+                )));
+            }
+        };
+
+        self.derived_columns.clear();
+        let mut flatten_info = FlattenInfo {
+            from_count_func: false,
+            domain: Some(left.clone()),
+        };
+        let flatten_plan = self.flatten(right, correlated_columns, &mut flatten_info)?;
+
+        // An aggregation without GROUP BY produces a row even if there are no matching rows,
+        // keep the left rows without matching rows.
+        if Self::is_scalar_aggregate(right) {
+            if flatten_info.from_count_func {
+                return Err(ErrorCode::UnImplement(
+                    "COUNT without GROUP BY in a LATERAL subquery is not supported",
+                ));
+            }
+            join_type = JoinType::Left;
+        }
+
+        let mut left_conditions = join.left_conditions.clone();
+        let mut right_conditions = join.right_conditions.clone();
+        self.add_equi_conditions(
+            correlated_columns,
+            &mut right_conditions,
+            &mut left_conditions,
+        )?;
+        let join_plan = LogicalInnerJoin {
+            left_conditions,
+            right_conditions,
+            other_conditions: join.other_conditions.clone(),
+            join_type,
+            marker_index: None,
+            from_correlated_subquery: false,
+        };
+        Ok(SExpr::create_binary(
+            join_plan.into(),
+            left.clone(),
+            flatten_plan,
+        ))
+    }
+
+    fn is_scalar_aggregate(plan: &SExpr) -> bool {
+        match plan.plan() {
+            RelOperator::Aggregate(aggregate) => aggregate.group_items.is_empty(),
+            RelOperator::EvalScalar(_) | RelOperator::Sort(_) | RelOperator::Limit(_) => {
+                Self::is_scalar_aggregate(&plan.children()[0])
+            }
+            _ => false,
+        }
+    }
+
+    // The distinct values of the correlated columns of `domain`, as the derived columns.
+    fn distinct_domain(&mut self, domain: &SExpr, correlated_columns: &ColumnSet) -> Result<SExpr> {
+        let mut group_items = Vec::with_capacity(correlated_columns.len());
+        for correlated_column in correlated_columns.iter() {
+            let column_entry = self.metadata.read().column(*correlated_column).clone();
+            let data_type = wrap_nullable(column_entry.data_type());
+            let derived_column = self.metadata.write().add_column(
+                column_entry.name().to_string(),
+                data_type.clone(),
+                None,
+                None,
+            );
+            self.derived_columns
+                .insert(*correlated_column, derived_column);
+
+            let column_ref = Scalar::BoundColumnRef(BoundColumnRef {
+                column: ColumnBinding {
+                    database_name: None,
+                    table_name: None,
+                    column_name: column_entry.name().to_string(),
+                    index: *correlated_column,
+                    data_type: Box::new(column_entry.data_type().clone()),
+                    visibility: Visibility::Visible,
+                },
+            });
+            group_items.push(ScalarItem {
+                scalar: wrap_cast_if_needed(column_ref, &data_type),
+                index: derived_column,
+            });
+        }
+
+        Ok(SExpr::create_unary(
+            Aggregate {
+                mode: AggregateMode::Initial,
+                group_items,
+                aggregate_functions: vec![],
+                from_distinct: true,
+            }
+            .into(),
+            domain.clone(),
+        ))
+    }
+
     fn flatten(
         &mut self,
         plan: &SExpr,
@@ -366,6 +483,20 @@ impl SubqueryRewriter {
         let rel_expr = RelExpr::with_s_expr(plan);
         let prop = rel_expr.derive_relational_prop()?;
         if prop.outer_columns.is_empty() {
+            if let Some(domain) = &flatten_info.domain {
+                let domain = self.distinct_domain(domain, correlated_columns)?;
+                let cross_join = LogicalInnerJoin {
+                    left_conditions: vec![],
+                    right_conditions: vec![],
+                    other_conditions: vec![],
+                    join_type: JoinType::Cross,
+                    marker_index: None,
+                    from_correlated_subquery: false,
+                }
+                .into();
+                return Ok(SExpr::create_binary(cross_join, domain, plan.clone()));
+            }
+
             // Construct a LogicalGet plan by correlated columns.
             // Finally generate a cross join, so we finish flattening the subquery.
             let mut metadata = self.metadata.write();
@@ -400,7 +531,9 @@ impl SubqueryRewriter {
                     limit: None,
                     order_by: None,
                     statistics: None,
+                    column_stats: Default::default(),
                     prewhere: None,
+                    sample: None,
                 }
                 .into(),
             );
@@ -543,6 +676,9 @@ impl SubqueryRewriter {
                     flatten_plan,
                 ))
             }
+            RelOperator::Limit(_) if flatten_info.domain.is_some() => Err(ErrorCode::UnImplement(
+                "LIMIT in a correlated LATERAL subquery is not supported",
+            )),
             RelOperator::Sort(_) | RelOperator::Limit(_) => {
                 // Currently, we don't support sort and limit contain subquery.
                 let flatten_plan =
@@ -550,6 +686,14 @@ impl SubqueryRewriter {
                 Ok(SExpr::create_unary(plan.plan().clone(), flatten_plan))
             }
 
+            RelOperator::Window(_) => Err(ErrorCode::UnImplement(
+                "Window functions in correlated subqueries are not supported",
+            )),
+
+            RelOperator::RecursiveUnion(_) | RelOperator::RecursiveCteScan(_) => Err(
+                ErrorCode::UnImplement("Recursive CTEs in correlated subqueries are not supported"),
+            ),
+
             RelOperator::UnionAll(op) => {
                 let left_flatten_plan =
                     self.flatten(plan.child(0)?, correlated_columns, flatten_info)?;
@@ -651,6 +795,16 @@ impl SubqueryRewriter {
                     return_type: fun_call.return_type.clone(),
                 }))
             }
+            Scalar::UDFServerCall(udf) => {
+                let mut arguments = Vec::with_capacity(udf.arguments.len());
+                for arg in &udf.arguments {
+                    arguments.push(self.flatten_scalar(arg, correlated_columns)?);
+                }
+                Ok(Scalar::UDFServerCall(UDFServerCall {
+                    arguments,
+                    ..udf.clone()
+                }))
+            }
             Scalar::CastExpr(cast_expr) => {
                 let scalar = self.flatten_scalar(&cast_expr.argument, correlated_columns)?;
                 Ok(Scalar::CastExpr(CastExpr {
@@ -45,6 +45,7 @@ pub static DEFAULT_REWRITE_RULES: Lazy<Vec<RuleID>> = Lazy::new(|| {
         RuleID::PushDownFilterEvalScalar,
         RuleID::PushDownFilterJoin,
         RuleID::FoldCountAggregate,
+        RuleID::FoldMinMaxAggregate,
         RuleID::SplitAggregate,
         RuleID::PushDownFilterScan,
     ]
@@ -73,6 +73,11 @@ impl PrewhereOptimizer {
             Scalar::CastExpr(cast) => {
                 Self::collect_columns_impl(cast.argument.as_ref(), columns);
             }
+            Scalar::UDFServerCall(udf) => {
+                for arg in udf.arguments.iter() {
+                    Self::collect_columns_impl(arg, columns);
+                }
+            }
             // 1. ConstantExpr is not collected.
             // 2. SubqueryExpr and AggregateFunction will not appear in Filter-LogicalGet
             _ => {}
@@ -100,6 +105,14 @@ impl PrewhereOptimizer {
                 // cannot optimize
                 return Ok(s_expr);
             }
+            if get
+                .columns
+                .iter()
+                .any(|index| metadata.column(*index).is_virtual_column())
+            {
+                // the virtual columns are not read by the prewhere readers
+                return Ok(s_expr);
+            }
 
             let mut prewhere_columns = ColumnSet::new();
             let mut prewhere_pred = Vec::new();
@@ -90,7 +90,9 @@ impl UnusedColumnPruner {
                     limit: p.limit,
                     order_by: p.order_by.clone(),
                     statistics: p.statistics,
+                    column_stats: p.column_stats.clone(),
                     prewhere,
+                    sample: p.sample,
                 })))
             }
             RelOperator::LogicalInnerJoin(p) => {
@@ -209,12 +211,26 @@ impl UnusedColumnPruner {
                     self.keep_required_columns(expr.child(0)?, required)?,
                 ))
             }
+            RelOperator::Window(p) => {
+                // The result of the window function is never used, skip evaluating it.
+                if !required.contains(&p.index) {
+                    return self.keep_required_columns(expr.child(0)?, required);
+                }
+                required.extend(p.function.used_columns());
+                Ok(SExpr::create_unary(
+                    RelOperator::Window(p.clone()),
+                    self.keep_required_columns(expr.child(0)?, required)?,
+                ))
+            }
             RelOperator::Limit(p) => Ok(SExpr::create_unary(
                 RelOperator::Limit(p.clone()),
                 self.keep_required_columns(expr.child(0)?, required)?,
             )),
 
-            RelOperator::DummyTableScan(_) | RelOperator::UnionAll(_) => Ok(expr.clone()),
+            RelOperator::DummyTableScan(_)
+            | RelOperator::UnionAll(_)
+            | RelOperator::RecursiveUnion(_)
+            | RelOperator::RecursiveCteScan(_) => Ok(expr.clone()),
 
             _ => Err(ErrorCode::LogicalError(
                 "Attempting to prune columns of a physical plan is not allowed",
@@ -27,6 +27,7 @@ use common_planner::MetadataRef;
 
 use crate::sql::binder::ColumnBinding;
 use crate::sql::binder::Visibility;
+use crate::sql::optimizer::ColumnSet;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Aggregate;
@@ -49,6 +50,7 @@ use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarItem;
 use crate::sql::plans::SubqueryExpr;
 use crate::sql::plans::SubqueryType;
+use crate::sql::plans::UDFServerCall;
 use crate::sql::ScalarExpr;
 
 #[allow(clippy::enum_variant_names)]
@@ -61,6 +63,8 @@ pub enum UnnestResult {
 
 pub struct FlattenInfo {
     pub from_count_func: bool,
+    // The left side of a LATERAL join, whose distinct correlated values the plan is flattened over
+    pub domain: Option<SExpr>,
 }
 
 /// Rewrite subquery into `Apply` operator
@@ -119,20 +123,43 @@ impl SubqueryRewriter {
                 Ok(SExpr::create_unary(plan.into(), input))
             }
 
-            RelOperator::LogicalInnerJoin(_) | RelOperator::UnionAll(_) => {
-                Ok(SExpr::create_binary(
-                    s_expr.plan().clone(),
-                    self.rewrite(s_expr.child(0)?)?,
-                    self.rewrite(s_expr.child(1)?)?,
-                ))
+            RelOperator::LogicalInnerJoin(join) => {
+                let left = self.rewrite(s_expr.child(0)?)?;
+                let right = self.rewrite(s_expr.child(1)?)?;
+
+                // The right side of a LATERAL join references the columns of the left side.
+                let left_prop = RelExpr::with_s_expr(&left).derive_relational_prop()?;
+                let right_prop = RelExpr::with_s_expr(&right).derive_relational_prop()?;
+                let correlated_columns: ColumnSet = right_prop
+                    .outer_columns
+                    .intersection(&left_prop.output_columns)
+                    .cloned()
+                    .collect();
+                if !correlated_columns.is_empty() {
+                    return self.try_decorrelate_lateral_join(
+                        &join,
+                        &left,
+                        &right,
+                        &correlated_columns,
+                    );
+                }
+
+                Ok(SExpr::create_binary(join.into(), left, right))
             }
 
-            RelOperator::Limit(_) | RelOperator::Sort(_) => Ok(SExpr::create_unary(
+            RelOperator::UnionAll(_) | RelOperator::RecursiveUnion(_) => Ok(SExpr::create_binary(
                 s_expr.plan().clone(),
                 self.rewrite(s_expr.child(0)?)?,
+                self.rewrite(s_expr.child(1)?)?,
             )),
 
-            RelOperator::DummyTableScan(_) | RelOperator::LogicalGet(_) => Ok(s_expr.clone()),
+            RelOperator::Limit(_) | RelOperator::Sort(_) | RelOperator::Window(_) => Ok(
+                SExpr::create_unary(s_expr.plan().clone(), self.rewrite(s_expr.child(0)?)?),
+            ),
+
+            RelOperator::DummyTableScan(_)
+            | RelOperator::LogicalGet(_)
+            | RelOperator::RecursiveCteScan(_) => Ok(s_expr.clone()),
 
             RelOperator::PhysicalHashJoin(_)
             | RelOperator::Pattern(_)
@@ -199,7 +226,9 @@ impl SubqueryRewriter {
                 ))
             }
 
-            Scalar::AggregateFunction(_) => Ok((scalar.clone(), s_expr.clone())),
+            Scalar::AggregateFunction(_) | Scalar::WindowFunction(_) => {
+                Ok((scalar.clone(), s_expr.clone()))
+            }
 
             Scalar::FunctionCall(func) => {
                 let mut args = vec![];
@@ -221,6 +250,24 @@ impl SubqueryRewriter {
                 Ok((expr, s_expr))
             }
 
+            Scalar::UDFServerCall(udf) => {
+                let mut args = vec![];
+                let mut s_expr = s_expr.clone();
+                for arg in udf.arguments.iter() {
+                    let res = self.try_rewrite_subquery(arg, &s_expr, false)?;
+                    s_expr = res.1;
+                    args.push(res.0);
+                }
+
+                let expr: Scalar = UDFServerCall {
+                    arguments: args,
+                    ..udf.clone()
+                }
+                .into();
+
+                Ok((expr, s_expr))
+            }
+
             Scalar::CastExpr(cast) => {
                 let (scalar, s_expr) = self.try_rewrite_subquery(&cast.argument, s_expr, false)?;
                 Ok((
@@ -246,6 +293,7 @@ impl SubqueryRewriter {
                 let prop = rel_expr.derive_relational_prop()?;
                 let mut flatten_info = FlattenInfo {
                     from_count_func: false,
+                    domain: None,
                 };
                 let (s_expr, result) = if prop.outer_columns.is_empty() {
                     self.try_rewrite_uncorrelated_subquery(s_expr, &subquery)?
@@ -15,6 +15,7 @@
 mod cascades;
 mod cost;
 mod distributed;
+mod expression_rewrite;
 mod format;
 mod group;
 mod heuristic;
@@ -28,6 +29,10 @@ mod rule;
 mod s_expr;
 mod util;
 
+pub use cost::Cost;
+pub use expression_rewrite::ScalarRewriter;
+pub use expression_rewrite::ScalarRule;
+pub use expression_rewrite::DEFAULT_SCALAR_RULES;
 pub use heuristic::HeuristicOptimizer;
 pub use heuristic::DEFAULT_REWRITE_RULES;
 pub use m_expr::MExpr;
@@ -36,12 +41,16 @@ pub use optimizer::optimize;
 pub use optimizer::OptimizerConfig;
 pub use optimizer::OptimizerContext;
 pub use pattern_extractor::PatternExtractor;
+pub use property::bound_column_stats;
 pub use property::ColumnSet;
+pub use property::ColumnStat;
+pub use property::ColumnStatSet;
 pub use property::Distribution;
 pub use property::PhysicalProperty;
 pub use property::RelExpr;
 pub use property::RelationalProperty;
 pub use property::RequiredProperty;
+pub use property::SelectivityEstimator;
 pub use rule::RuleFactory;
 pub use rule::RuleID;
 pub use rule::RuleSet;
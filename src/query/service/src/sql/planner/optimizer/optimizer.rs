@@ -26,6 +26,7 @@ use crate::sql::optimizer::util::contains_local_table_scan;
 use crate::sql::optimizer::util::validate_distributed_query;
 use crate::sql::optimizer::HeuristicOptimizer;
 use crate::sql::optimizer::SExpr;
+use crate::sql::optimizer::ScalarRewriter;
 use crate::sql::optimizer::DEFAULT_REWRITE_RULES;
 use crate::sql::plans::CopyPlanV2;
 use crate::sql::plans::Plan;
@@ -86,6 +87,7 @@ pub fn optimize(
                     path,
                     validation_mode,
                     from,
+                    partition_by,
                 } => {
                     CopyPlanV2::IntoStage {
                         stage,
@@ -93,6 +95,7 @@ pub fn optimize(
                         validation_mode,
                         // Make sure the subquery has been optimized.
                         from: Box::new(optimize(ctx, opt_ctx, *from)?),
+                        partition_by,
                     }
                 }
                 into_table => into_table,
@@ -112,12 +115,15 @@ pub fn optimize_query(
 ) -> Result<SExpr> {
     let rules = RuleList::create(DEFAULT_REWRITE_RULES.clone())?;
 
+    let func_ctx = ctx.try_get_function_context()?;
+    let s_expr = ScalarRewriter::with_default_rules(func_ctx).rewrite_plan(&s_expr)?;
+
     let contains_local_table_scan = contains_local_table_scan(&s_expr, &metadata);
 
     let mut heuristic = HeuristicOptimizer::new(ctx.clone(), bind_context, metadata, rules);
     let mut result = heuristic.optimize(s_expr)?;
 
-    let cascades = CascadesOptimizer::create(ctx)?;
+    let cascades = CascadesOptimizer::create(ctx.clone())?;
     result = cascades.optimize(result)?;
 
     // So far, we don't have ability to execute distributed query
@@ -125,7 +131,7 @@ pub fn optimize_query(
     let enable_distributed_query =
         opt_ctx.config.enable_distributed_optimization && !contains_local_table_scan;
     if enable_distributed_query && validate_distributed_query(&result) {
-        result = optimize_distributed_query(&result)?;
+        result = optimize_distributed_query(ctx, &result)?;
     }
 
     Ok(result)
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
 use common_exception::Result;
 
 use crate::sql::optimizer::property::Distribution;
@@ -21,19 +24,24 @@ use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Exchange;
 
 /// Require and enforce physical property from a physical `SExpr`
-pub fn require_property(required: &RequiredProperty, s_expr: &SExpr) -> Result<SExpr> {
+pub fn require_property(
+    ctx: Arc<dyn TableContext>,
+    required: &RequiredProperty,
+    s_expr: &SExpr,
+) -> Result<SExpr> {
     // First, we will require the child SExpr with input `RequiredProperty`
     let optimized_children = s_expr
         .children()
         .iter()
-        .map(|child| require_property(required, child))
+        .map(|child| require_property(ctx.clone(), required, child))
         .collect::<Result<Vec<SExpr>>>()?;
-    let optimized_expr = SExpr::create(s_expr.plan().clone(), optimized_children, None, None);
+    // The relational properties are kept for choosing the distribution, and the costs for EXPLAIN.
+    let optimized_expr = s_expr.replace_children(optimized_children);
 
     let rel_expr = RelExpr::with_s_expr(&optimized_expr);
     let mut children = Vec::with_capacity(s_expr.arity());
     for index in 0..optimized_expr.arity() {
-        let required = rel_expr.compute_required_prop_child(index, required)?;
+        let required = rel_expr.compute_required_prop_child(ctx.clone(), index, required)?;
         let physical = rel_expr.derive_physical_prop_child(index)?;
         if required.satisfied_by(&physical) {
             children.push(optimized_expr.child(index)?.clone());
@@ -45,12 +53,7 @@ pub fn require_property(required: &RequiredProperty, s_expr: &SExpr) -> Result<S
         children.push(enforced_child);
     }
 
-    Ok(SExpr::create(
-        optimized_expr.plan().clone(),
-        children,
-        None,
-        None,
-    ))
+    Ok(optimized_expr.replace_children(children))
 }
 
 /// Try to enforce physical property from a physical `SExpr`
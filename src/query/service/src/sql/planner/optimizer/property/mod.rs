@@ -16,6 +16,7 @@ mod builder;
 mod enforcer;
 #[allow(clippy::module_inception)]
 mod property;
+mod selectivity;
 mod stat;
 
 pub use builder::RelExpr;
@@ -25,3 +26,7 @@ pub use property::Distribution;
 pub use property::PhysicalProperty;
 pub use property::RelationalProperty;
 pub use property::RequiredProperty;
+pub use selectivity::SelectivityEstimator;
+pub use stat::bound_column_stats;
+pub use stat::ColumnStat;
+pub use stat::ColumnStatSet;
@@ -16,6 +16,7 @@ use std::collections::HashSet;
 
 use common_planner::IndexType;
 
+use super::ColumnStatSet;
 use crate::sql::plans::Scalar;
 
 pub type ColumnSet = HashSet<IndexType>;
@@ -42,6 +43,9 @@ pub struct RelationalProperty {
     // We can get the precise row count of a table in databend,
     // which information is useful to optimize some queries like `COUNT(*)`.
     pub precise_cardinality: Option<u64>,
+
+    // The statistics of the output columns, the columns without statistics are absent.
+    pub column_stats: ColumnStatSet,
 }
 
 #[derive(Default, Clone)]
@@ -74,9 +78,11 @@ impl Distribution {
             | (Distribution::Random, _)
             | (Distribution::Serial, Distribution::Serial)
             | (Distribution::Broadcast, Distribution::Broadcast) => true,
-            (Distribution::Hash(ref keys), Distribution::Hash(ref other_keys)) => keys
-                .iter()
-                .all(|key| other_keys.iter().any(|other_key| key == other_key)),
+            // The data hashed by a subset or a permutation of the keys is not partitioned the
+            // same way, e.g. the two sides of a join must be hashed by exactly the join keys.
+            (Distribution::Hash(ref keys), Distribution::Hash(ref other_keys)) => {
+                keys == other_keys
+            }
             _ => false,
         }
     }
@@ -0,0 +1,155 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_catalog::table::Histogram;
+use common_datavalues::DataValue;
+
+use super::ColumnStat;
+use super::ColumnStatSet;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::ComparisonOp;
+use crate::sql::plans::Scalar;
+
+/// The selectivity of the predicates that can't be estimated with the statistics.
+pub const DEFAULT_SELECTIVITY: f64 = 0.2;
+
+/// Estimate the fraction of the rows satisfying a predicate, with the statistics of the columns.
+pub struct SelectivityEstimator<'a> {
+    column_stats: &'a ColumnStatSet,
+}
+
+impl<'a> SelectivityEstimator<'a> {
+    pub fn new(column_stats: &'a ColumnStatSet) -> Self {
+        Self { column_stats }
+    }
+
+    pub fn compute_selectivity(&self, predicate: &Scalar) -> f64 {
+        match predicate {
+            Scalar::ConstantExpr(constant) => match constant.value {
+                DataValue::Null | DataValue::Boolean(false) => 0.0,
+                _ => 1.0,
+            },
+
+            // The predicates are assumed to be independent.
+            Scalar::AndExpr(and) => {
+                self.compute_selectivity(&and.left) * self.compute_selectivity(&and.right)
+            }
+            Scalar::OrExpr(or) => {
+                let left = self.compute_selectivity(&or.left);
+                let right = self.compute_selectivity(&or.right);
+                left + right - left * right
+            }
+
+            Scalar::FunctionCall(func) if func.func_name == "not" && func.arguments.len() == 1 => {
+                1.0 - self.compute_selectivity(&func.arguments[0])
+            }
+
+            Scalar::ComparisonExpr(comparison) => self.compute_comparison_selectivity(comparison),
+
+            _ => DEFAULT_SELECTIVITY,
+        }
+    }
+
+    /// Only the comparisons between a column and a constant can be estimated, the values are
+    /// assumed to be distributed uniformly between the min and max of the column.
+    fn compute_comparison_selectivity(&self, comparison: &ComparisonExpr) -> f64 {
+        let (column, value, op) = match (&*comparison.left, &*comparison.right) {
+            (Scalar::BoundColumnRef(column), Scalar::ConstantExpr(constant)) => {
+                (column, &constant.value, comparison.op.clone())
+            }
+            (Scalar::ConstantExpr(constant), Scalar::BoundColumnRef(column)) => {
+                let op = match comparison.op {
+                    ComparisonOp::GT => ComparisonOp::LT,
+                    ComparisonOp::LT => ComparisonOp::GT,
+                    ComparisonOp::GTE => ComparisonOp::LTE,
+                    ComparisonOp::LTE => ComparisonOp::GTE,
+                    ref op => op.clone(),
+                };
+                (column, &constant.value, op)
+            }
+            _ => return DEFAULT_SELECTIVITY,
+        };
+
+        let stat = match self.column_stats.get(&column.column.index) {
+            Some(stat) => stat,
+            None => return DEFAULT_SELECTIVITY,
+        };
+
+        match op {
+            ComparisonOp::Equal => equal_selectivity(stat, value),
+            ComparisonOp::NotEqual => 1.0 - equal_selectivity(stat, value),
+            ComparisonOp::LT | ComparisonOp::LTE => match fraction_below(stat, value) {
+                Some(fraction) => fraction,
+                None => DEFAULT_SELECTIVITY,
+            },
+            ComparisonOp::GT | ComparisonOp::GTE => match fraction_below(stat, value) {
+                Some(fraction) => 1.0 - fraction,
+                None => DEFAULT_SELECTIVITY,
+            },
+        }
+    }
+}
+
+fn equal_selectivity(stat: &ColumnStat, value: &DataValue) -> f64 {
+    if let (Ok(min), Ok(max), Ok(value)) = (stat.min.as_f64(), stat.max.as_f64(), value.as_f64()) {
+        if value < min || value > max {
+            return 0.0;
+        }
+    }
+
+    if stat.ndv < 1.0 { 0.0 } else { 1.0 / stat.ndv }
+}
+
+/// The fraction of the values of the column below the value, only for the numeric columns.
+fn fraction_below(stat: &ColumnStat, value: &DataValue) -> Option<f64> {
+    let min = stat.min.as_f64().ok()?;
+    let max = stat.max.as_f64().ok()?;
+    let value = value.as_f64().ok()?;
+
+    if let Some(histogram) = &stat.histogram {
+        let total: f64 = histogram.buckets.iter().map(|bucket| bucket.count).sum();
+        if total > 0.0 {
+            return Some(histogram_count_below(histogram, value) / total);
+        }
+    }
+
+    let fraction = if value <= min {
+        0.0
+    } else if value > max {
+        1.0
+    } else if max > min {
+        (value - min) / (max - min)
+    } else {
+        DEFAULT_SELECTIVITY
+    };
+
+    Some(fraction)
+}
+
+/// The values in a bucket are assumed to be distributed uniformly between its bounds.
+fn histogram_count_below(histogram: &Histogram, value: f64) -> f64 {
+    histogram
+        .buckets
+        .iter()
+        .map(|bucket| {
+            if value >= bucket.upper {
+                bucket.count
+            } else if value <= bucket.lower {
+                0.0
+            } else {
+                bucket.count * (value - bucket.lower) / (bucket.upper - bucket.lower)
+            }
+        })
+        .sum()
+}
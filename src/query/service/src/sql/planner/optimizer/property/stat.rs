@@ -11,3 +11,40 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+
+use std::collections::HashMap;
+
+use common_catalog::table::Histogram;
+use common_datavalues::DataValue;
+use common_planner::IndexType;
+
+/// Column index -> the statistics of the column.
+pub type ColumnStatSet = HashMap<IndexType, ColumnStat>;
+
+#[derive(Debug, Clone)]
+pub struct ColumnStat {
+    pub min: DataValue,
+    pub max: DataValue,
+
+    /// Number of distinct values, it's always estimated.
+    pub ndv: f64,
+    pub null_count: u64,
+
+    /// Histogram of the values, only collected by `ANALYZE TABLE` for the numeric columns.
+    pub histogram: Option<Histogram>,
+    /// Whether min, max and null_count are exact, only for the columns read by a table scan.
+    pub is_exact: bool,
+}
+
+/// The number of distinct values of the columns can't be more than the cardinality. The rows are
+/// changed, so the statistics are no longer exact.
+pub fn bound_column_stats(column_stats: ColumnStatSet, cardinality: f64) -> ColumnStatSet {
+    column_stats
+        .into_iter()
+        .map(|(index, mut stat)| {
+            stat.ndv = stat.ndv.min(cardinality);
+            stat.is_exact = false;
+            (index, stat)
+        })
+        .collect()
+}
@@ -15,6 +15,7 @@
 mod rule_eliminate_eval_scalar;
 mod rule_eliminate_filter;
 mod rule_fold_count_aggregate;
+mod rule_fold_min_max_aggregate;
 mod rule_merge_eval_scalar;
 mod rule_merge_filter;
 mod rule_normalize_disjunctive_filter;
@@ -31,6 +32,7 @@ mod rule_split_aggregate;
 pub use rule_eliminate_eval_scalar::RuleEliminateEvalScalar;
 pub use rule_eliminate_filter::RuleEliminateFilter;
 pub use rule_fold_count_aggregate::RuleFoldCountAggregate;
+pub use rule_fold_min_max_aggregate::RuleFoldMinMaxAggregate;
 pub use rule_merge_eval_scalar::RuleMergeEvalScalar;
 pub use rule_merge_filter::RuleMergeFilter;
 pub use rule_normalize_disjunctive_filter::RuleNormalizeDisjunctiveFilter;
@@ -0,0 +1,247 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::rule::TransformState;
+use crate::sql::optimizer::ColumnStat;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::Aggregate;
+use crate::sql::plans::AggregateMode;
+use crate::sql::plans::ComparisonOp;
+use crate::sql::plans::ConstantExpr;
+use crate::sql::plans::DummyTableScan;
+use crate::sql::plans::EvalScalar;
+use crate::sql::plans::PatternPlan;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+use crate::sql::ScalarExpr;
+
+/// Fold the aggregates made of `MIN`, `MAX` and `COUNT` with the exact statistics of the columns
+/// of a table scan, so the table is not read.
+///
+/// If the input is filtered, they are folded only if the filters match none of the rows by the
+/// ranges of the columns, the input is empty then.
+pub struct RuleFoldMinMaxAggregate {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleFoldMinMaxAggregate {
+    pub fn new() -> Self {
+        Self {
+            id: RuleID::FoldMinMaxAggregate,
+            //  Aggregate
+            //  \
+            //   *
+            pattern: SExpr::create_unary(
+                PatternPlan {
+                    plan_type: RelOp::Aggregate,
+                }
+                .into(),
+                SExpr::create_leaf(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ),
+            ),
+        }
+    }
+
+    /// Whether the input of the aggregate is filtered by the predicates which match none of the
+    /// rows, the arguments of the aggregate may be evaluated above the filter.
+    fn is_filtered_out(s_expr: &SExpr) -> Result<bool> {
+        let mut s_expr = s_expr.child(0)?;
+        while let RelOperator::EvalScalar(_) = s_expr.plan() {
+            s_expr = s_expr.child(0)?;
+        }
+
+        match s_expr.plan() {
+            RelOperator::Filter(filter) => {
+                let input_prop = RelExpr::with_s_expr(s_expr).derive_relational_prop_child(0)?;
+                Ok(filter
+                    .predicates
+                    .iter()
+                    .any(|predicate| Self::matches_none(predicate, &input_prop)))
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn matches_none(predicate: &Scalar, input_prop: &RelationalProperty) -> bool {
+        match predicate {
+            Scalar::ConstantExpr(constant) => {
+                matches!(constant.value, DataValue::Null | DataValue::Boolean(false))
+            }
+            Scalar::AndExpr(and) => {
+                Self::matches_none(&and.left, input_prop)
+                    || Self::matches_none(&and.right, input_prop)
+            }
+            Scalar::OrExpr(or) => {
+                Self::matches_none(&or.left, input_prop)
+                    && Self::matches_none(&or.right, input_prop)
+            }
+            Scalar::ComparisonExpr(comparison) => {
+                let (column, value, op) = match (&*comparison.left, &*comparison.right) {
+                    (Scalar::BoundColumnRef(column), Scalar::ConstantExpr(constant)) => {
+                        (column, &constant.value, comparison.op.clone())
+                    }
+                    (Scalar::ConstantExpr(constant), Scalar::BoundColumnRef(column)) => {
+                        let op = match comparison.op {
+                            ComparisonOp::GT => ComparisonOp::LT,
+                            ComparisonOp::LT => ComparisonOp::GT,
+                            ComparisonOp::GTE => ComparisonOp::LTE,
+                            ComparisonOp::LTE => ComparisonOp::GTE,
+                            ref op => op.clone(),
+                        };
+                        (column, &constant.value, op)
+                    }
+                    _ => return false,
+                };
+                match input_prop.column_stats.get(&column.column.index) {
+                    Some(stat) if stat.is_exact => Self::out_of_range(stat, value, op),
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether `column <op> value` is true for none of the values in the range of the column.
+    fn out_of_range(stat: &ColumnStat, value: &DataValue, op: ComparisonOp) -> bool {
+        if value.is_null() {
+            return true;
+        }
+        let (min, max) = match (compare(&stat.min, value), compare(&stat.max, value)) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return false,
+        };
+        match op {
+            ComparisonOp::Equal => min == Ordering::Greater || max == Ordering::Less,
+            ComparisonOp::NotEqual => min == Ordering::Equal && max == Ordering::Equal,
+            ComparisonOp::GT => max != Ordering::Greater,
+            ComparisonOp::GTE => max == Ordering::Less,
+            ComparisonOp::LT => min != Ordering::Less,
+            ComparisonOp::LTE => min == Ordering::Greater,
+        }
+    }
+
+    /// The value of an aggregate over the rows described by the statistics.
+    fn fold(scalar: &Scalar, input_prop: &RelationalProperty, rows: u64) -> Option<DataValue> {
+        let agg_func = match scalar {
+            Scalar::AggregateFunction(agg_func) if agg_func.params.is_empty() => agg_func,
+            _ => return None,
+        };
+        let stat = match agg_func.args.as_slice() {
+            [] => None,
+            [Scalar::BoundColumnRef(column)] => {
+                match input_prop.column_stats.get(&column.column.index) {
+                    Some(stat) if stat.is_exact => Some(stat),
+                    // the values of the column are unknown, unless the input is empty
+                    _ if rows == 0 => None,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        let non_null_rows = rows - stat.map_or(0, |stat| stat.null_count.min(rows));
+
+        let value = match (agg_func.func_name.as_str(), stat) {
+            ("count", _) if !agg_func.distinct => return Some(DataValue::UInt64(non_null_rows)),
+            ("min", Some(stat)) if non_null_rows > 0 => stat.min.clone(),
+            ("max", Some(stat)) if non_null_rows > 0 => stat.max.clone(),
+            ("min" | "max", _) if non_null_rows == 0 => DataValue::Null,
+            _ => return None,
+        };
+        match value.is_null() {
+            // the columns without min/max, like the VARIANT ones
+            true if non_null_rows > 0 => None,
+            true if !matches!(*agg_func.return_type, DataTypeImpl::Nullable(_)) => None,
+            _ => Some(value),
+        }
+    }
+}
+
+/// The order of a value of the statistics and a constant, if they are comparable.
+fn compare(stat: &DataValue, value: &DataValue) -> Option<Ordering> {
+    if stat.is_null() {
+        return None;
+    }
+    if stat.value_type() == value.value_type() {
+        return Some(stat.cmp(value));
+    }
+    match (stat, value) {
+        (DataValue::String(_), _) | (_, DataValue::String(_)) => None,
+        _ => stat.as_f64().ok()?.partial_cmp(&value.as_f64().ok()?),
+    }
+}
+
+impl Rule for RuleFoldMinMaxAggregate {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, s_expr: &SExpr, state: &mut TransformState) -> Result<()> {
+        let agg: Aggregate = s_expr.plan().clone().try_into()?;
+
+        if agg.mode == AggregateMode::Final
+            || agg.mode == AggregateMode::Partial
+            || !agg.group_items.is_empty()
+        {
+            return Ok(());
+        }
+
+        let rel_expr = RelExpr::with_s_expr(s_expr);
+        let input_prop = rel_expr.derive_relational_prop_child(0)?;
+        let rows = match input_prop.precise_cardinality {
+            Some(rows) => rows,
+            None if Self::is_filtered_out(s_expr)? => 0,
+            None => return Ok(()),
+        };
+
+        let mut scalars = agg.aggregate_functions;
+        for item in scalars.iter_mut() {
+            let value = match Self::fold(&item.scalar, &input_prop, rows) {
+                Some(value) => value,
+                None => return Ok(()),
+            };
+            item.scalar = Scalar::ConstantExpr(ConstantExpr {
+                value,
+                data_type: Box::new(item.scalar.data_type()),
+            });
+        }
+        let eval_scalar = EvalScalar { items: scalars };
+        let dummy_table_scan = DummyTableScan;
+        state.add_result(SExpr::create_unary(
+            eval_scalar.into(),
+            SExpr::create_leaf(dummy_table_scan.into()),
+        ));
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
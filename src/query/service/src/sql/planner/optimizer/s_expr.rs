@@ -16,6 +16,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planner::IndexType;
 
+use super::Cost;
 use super::RelationalProperty;
 use crate::sql::optimizer::rule::AppliedRules;
 use crate::sql::optimizer::rule::RuleID;
@@ -32,6 +33,9 @@ pub struct SExpr {
 
     pub(in crate::sql) original_group: Option<IndexType>,
     pub(in crate::sql) rel_prop: Option<Box<RelationalProperty>>,
+    /// Cost of the plan rooted at current SExpr, set for the optimal plan found by the
+    /// cascades optimizer.
+    pub(in crate::sql) cost: Option<Cost>,
 
     /// A bitmap to record applied rules on current SExpr, to prevent
     /// redundant transformations.
@@ -50,6 +54,7 @@ impl SExpr {
             children,
             original_group,
             rel_prop,
+            cost: None,
 
             applied_rules: AppliedRules::default(),
         }
@@ -133,6 +138,7 @@ impl SExpr {
             plan: self.plan.clone(),
             original_group: self.original_group,
             rel_prop: self.rel_prop.clone(),
+            cost: self.cost,
             applied_rules: self.applied_rules.clone(),
             children,
         }
@@ -45,6 +45,8 @@ pub fn validate_distributed_query(s_expr: &SExpr) -> bool {
                 | JoinType::Mark
                 | JoinType::Single => false,
             },
+            // the iterations of a recursive CTE are evaluated locally
+            RelOperator::RecursiveUnion(_) | RelOperator::RecursiveCteScan(_) => false,
             _ => true,
         }
 }
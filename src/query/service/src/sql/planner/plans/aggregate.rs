@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
 use common_exception::Result;
 
+use crate::sql::optimizer::bound_column_stats;
 use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::ColumnStatSet;
 use crate::sql::optimizer::Distribution;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
@@ -24,6 +29,7 @@ use crate::sql::plans::LogicalOperator;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalOperator;
 use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarItem;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Copy)]
@@ -76,6 +82,7 @@ impl PhysicalOperator for Aggregate {
 
     fn compute_required_prop_child<'a>(
         &self,
+        _ctx: Arc<dyn TableContext>,
         rel_expr: &RelExpr<'a>,
         _child_index: usize,
         required: &RequiredProperty,
@@ -136,13 +143,30 @@ impl LogicalOperator for Aggregate {
             .cloned()
             .collect();
 
-        // Derive cardinality. We can not estimate the cardinality of an aggregate with group by, until
-        // we have information about distribution of group keys. So we pass through the cardinality.
+        // Derive column statistics of the group keys
+        let mut column_stats = ColumnStatSet::new();
+        for group_item in self.group_items.iter() {
+            if let Scalar::BoundColumnRef(column_ref) = &group_item.scalar {
+                if let Some(stat) = input_prop.column_stats.get(&column_ref.column.index) {
+                    column_stats.insert(group_item.index, stat.clone());
+                }
+            }
+        }
+
+        // Derive cardinality. The number of groups is bounded by the product of the numbers of
+        // distinct values of the group keys, we pass through the cardinality if any of them is
+        // unknown.
         let cardinality = if self.group_items.is_empty() {
             // Scalar aggregation
             1.0
         } else {
-            input_prop.cardinality
+            self.group_items
+                .iter()
+                .map(|group_item| column_stats.get(&group_item.index).map(|stat| stat.ndv))
+                .product::<Option<f64>>()
+                .map_or(input_prop.cardinality, |groups| {
+                    groups.min(input_prop.cardinality)
+                })
         };
 
         let precise_cardinality = if self.group_items.is_empty() {
@@ -156,6 +180,7 @@ impl LogicalOperator for Aggregate {
             outer_columns,
             cardinality,
             precise_cardinality,
+            column_stats: bound_column_stats(column_stats, cardinality),
         })
     }
 }
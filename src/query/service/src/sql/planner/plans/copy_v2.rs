@@ -73,6 +73,8 @@ pub enum CopyPlanV2 {
         path: String,
         validation_mode: ValidationMode,
         from: Box<Plan>,
+        /// The values of the keys are the last columns of `from`.
+        partition_by: Vec<String>,
     },
 }
 
@@ -106,11 +108,15 @@ impl Debug for CopyPlanV2 {
                 stage,
                 path,
                 validation_mode,
+                partition_by,
                 ..
             } => {
                 write!(f, "Copy into {:?}", stage)?;
                 write!(f, ", path: {:?}", path)?;
                 write!(f, ", validation_mode: {:?}", validation_mode)?;
+                if !partition_by.is_empty() {
+                    write!(f, ", partition_by: {:?}", partition_by)?;
+                }
             }
         }
         Ok(())
@@ -54,6 +54,7 @@ impl LogicalOperator for DummyTableScan {
             outer_columns: ColumnSet::new(),
             cardinality: 1.0,
             precise_cardinality: Some(1),
+            column_stats: Default::default(),
         })
     }
 }
@@ -70,6 +71,7 @@ impl PhysicalOperator for DummyTableScan {
 
     fn compute_required_prop_child<'a>(
         &self,
+        _ctx: std::sync::Arc<dyn common_catalog::table_context::TableContext>,
         _rel_expr: &crate::sql::optimizer::RelExpr<'a>,
         _child_index: usize,
         required: &crate::sql::optimizer::RequiredProperty,
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.#[derive(Clone, Debug)]
 
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
 use common_exception::Result;
 use common_planner::IndexType;
 
@@ -68,6 +71,7 @@ impl PhysicalOperator for EvalScalar {
 
     fn compute_required_prop_child<'a>(
         &self,
+        _ctx: Arc<dyn TableContext>,
         _rel_expr: &RelExpr<'a>,
         _child_index: usize,
         required: &RequiredProperty,
@@ -102,11 +106,22 @@ impl LogicalOperator for EvalScalar {
         let cardinality = input_prop.cardinality;
         let precise_cardinality = input_prop.precise_cardinality;
 
+        // Derive column statistics, the renamed columns share the statistics
+        let mut column_stats = input_prop.column_stats;
+        for item in self.items.iter() {
+            if let Scalar::BoundColumnRef(column_ref) = &item.scalar {
+                if let Some(stat) = column_stats.get(&column_ref.column.index).cloned() {
+                    column_stats.insert(item.index, stat);
+                }
+            }
+        }
+
         Ok(RelationalProperty {
             output_columns,
             outer_columns,
             cardinality,
             precise_cardinality,
+            column_stats,
         })
     }
 }
@@ -12,13 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
 use common_exception::Result;
 
+use crate::sql::optimizer::bound_column_stats;
 use crate::sql::optimizer::ColumnSet;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
 use crate::sql::optimizer::RequiredProperty;
+use crate::sql::optimizer::SelectivityEstimator;
 use crate::sql::plans::LogicalOperator;
 use crate::sql::plans::Operator;
 use crate::sql::plans::PhysicalOperator;
@@ -62,6 +67,7 @@ impl PhysicalOperator for Filter {
 
     fn compute_required_prop_child<'a>(
         &self,
+        _ctx: Arc<dyn TableContext>,
         _rel_expr: &RelExpr<'a>,
         _child_index: usize,
         required: &RequiredProperty,
@@ -87,9 +93,15 @@ impl LogicalOperator for Filter {
         }
         outer_columns = outer_columns.difference(&output_columns).cloned().collect();
 
-        // Derive cardinality. We can not estimate the cardinality of the filter until we have
-        // NDV(Number of Distinct Values), so we pass it through.
-        let cardinality = input_prop.cardinality;
+        // Derive cardinality with the selectivity of the predicates, which is estimated with the
+        // statistics of the input columns.
+        let estimator = SelectivityEstimator::new(&input_prop.column_stats);
+        let selectivity = self
+            .predicates
+            .iter()
+            .map(|predicate| estimator.compute_selectivity(predicate))
+            .product::<f64>();
+        let cardinality = input_prop.cardinality * selectivity;
 
         Ok(RelationalProperty {
             output_columns,
@@ -98,6 +110,7 @@ impl LogicalOperator for Filter {
             // TODO(leiysky): if the predicate is always true, then we can pass through
             // precise cardinality
             precise_cardinality: None,
+            column_stats: bound_column_stats(input_prop.column_stats, cardinality),
         })
     }
 }
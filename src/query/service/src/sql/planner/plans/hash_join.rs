@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
 use common_exception::Result;
 use common_planner::IndexType;
 
@@ -64,6 +67,10 @@ impl PhysicalOperator for PhysicalHashJoin {
         let build_prop = rel_expr.derive_physical_prop_child(1)?;
 
         match (&probe_prop.distribution, &build_prop.distribution) {
+            // If the build side is broadcast, the distribution of probe side is kept.
+            (_, Distribution::Broadcast) => Ok(PhysicalProperty {
+                distribution: probe_prop.distribution.clone(),
+            }),
             // If the distribution of probe side is Random, we will pass through
             // the distribution of build side.
             (Distribution::Random, _) => Ok(PhysicalProperty {
@@ -78,6 +85,7 @@ impl PhysicalOperator for PhysicalHashJoin {
 
     fn compute_required_prop_child<'a>(
         &self,
+        ctx: Arc<dyn TableContext>,
         rel_expr: &RelExpr<'a>,
         child_index: usize,
         required: &RequiredProperty,
@@ -88,19 +96,61 @@ impl PhysicalOperator for PhysicalHashJoin {
         let build_physical_prop = rel_expr.derive_physical_prop_child(1)?;
 
         if probe_physical_prop.distribution == Distribution::Serial
-            || build_physical_prop.distribution == Distribution::Serial
+            && build_physical_prop.distribution == Distribution::Serial
         {
-            // TODO(leiysky): we can enforce redistribution here
+            // Both sides are on the coordinator already.
             required.distribution = Distribution::Serial;
-        } else {
-            // A simple heuristic, we will always enforce `Hash` distribution for join
+        } else if probe_physical_prop.distribution == Distribution::Serial {
+            // The probe side is shuffled from the coordinator to all the nodes, instead of
+            // merging the other side to the coordinator.
+            required.distribution = if self.build_keys.is_empty() {
+                Distribution::Serial
+            } else if child_index == 0 {
+                Distribution::Hash(self.probe_keys.clone())
+            } else {
+                Distribution::Hash(self.build_keys.clone())
+            };
+        } else if self.broadcast_build_side(ctx, rel_expr)? {
+            // The probe side is kept where it is, joined with the whole build side.
             if child_index == 0 {
-                required.distribution = Distribution::Hash(self.probe_keys.clone());
+                required.distribution = Distribution::Any;
             } else {
-                required.distribution = Distribution::Hash(self.build_keys.clone());
+                required.distribution = Distribution::Broadcast;
             }
+        } else if self.build_keys.is_empty() {
+            // The joins without equi-conditions can't be shuffled.
+            required.distribution = Distribution::Serial;
+        } else if child_index == 0 {
+            // Otherwise both sides are shuffled by the join keys.
+            required.distribution = Distribution::Hash(self.probe_keys.clone());
+        } else {
+            required.distribution = Distribution::Hash(self.build_keys.clone());
         }
 
         Ok(required)
     }
 }
+
+impl PhysicalHashJoin {
+    /// Broadcasting the build side sends `build * (nodes - 1)` rows, while shuffling both sides
+    /// by the join keys sends `(build + probe) * (nodes - 1) / nodes` rows, so the build side is
+    /// broadcast if `build * nodes < build + probe`. It's only correct for the joins that don't
+    /// output the unmatched rows of the build side. The joins without equi-conditions are always
+    /// broadcast if it's correct, since they can't be shuffled.
+    fn broadcast_build_side(&self, ctx: Arc<dyn TableContext>, rel_expr: &RelExpr) -> Result<bool> {
+        if !matches!(
+            self.join_type,
+            JoinType::Inner | JoinType::Left | JoinType::Semi | JoinType::Anti | JoinType::Cross
+        ) {
+            return Ok(false);
+        }
+        if self.build_keys.is_empty() {
+            return Ok(true);
+        }
+
+        let nodes = ctx.get_cluster().nodes.len() as f64;
+        let probe_cardinality = rel_expr.derive_relational_prop_child(0)?.cardinality;
+        let build_cardinality = rel_expr.derive_relational_prop_child(1)?.cardinality;
+        Ok(build_cardinality * nodes < build_cardinality + probe_cardinality)
+    }
+}
@@ -28,6 +28,8 @@ pub enum InsertInputSource {
     StreamingWithFormat(String, usize, Option<Arc<InputContext>>),
     // From cloned String and format
     Values(String),
+    // From the blocks sent by the client, such as the ClickHouse native protocol
+    Blocks(Vec<DataBlock>),
 }
 
 #[derive(Clone)]
@@ -69,6 +71,7 @@ impl Insert {
             InsertInputSource::SelectPlan(_) => None,
             InsertInputSource::StreamingWithFormat(v, ..) => Some(v.as_str()),
             InsertInputSource::Values(v) => Some(v.as_str()),
+            InsertInputSource::Blocks(_) => None,
         }
     }
 }
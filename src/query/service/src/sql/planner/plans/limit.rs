@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
 use common_exception::Result;
 
+use crate::sql::optimizer::bound_column_stats;
 use crate::sql::optimizer::Distribution;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
@@ -59,6 +63,7 @@ impl PhysicalOperator for Limit {
 
     fn compute_required_prop_child<'a>(
         &self,
+        _ctx: Arc<dyn TableContext>,
         _rel_expr: &RelExpr<'a>,
         _child_index: usize,
         required: &RequiredProperty,
@@ -73,14 +78,17 @@ impl LogicalOperator for Limit {
     fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
         let input_prop = rel_expr.derive_relational_prop_child(0)?;
 
+        let cardinality = match self.limit {
+            Some(limit) if (limit as f64) < input_prop.cardinality => limit as f64,
+            _ => input_prop.cardinality,
+        };
+
         Ok(RelationalProperty {
             output_columns: input_prop.output_columns,
             outer_columns: input_prop.outer_columns,
-            cardinality: match self.limit {
-                Some(limit) if (limit as f64) < input_prop.cardinality => limit as f64,
-                _ => input_prop.cardinality,
-            },
+            cardinality,
             precise_cardinality: None,
+            column_stats: bound_column_stats(input_prop.column_stats, cardinality),
         })
     }
 }
@@ -14,10 +14,12 @@
 
 use common_catalog::table::TableStatistics;
 use common_exception::Result;
+use common_legacy_planners::ScanSample;
 use common_planner::IndexType;
 use itertools::Itertools;
 
 use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::ColumnStatSet;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
 use crate::sql::plans::LogicalOperator;
@@ -45,9 +47,11 @@ pub struct LogicalGet {
     pub limit: Option<usize>,
     pub order_by: Option<Vec<SortItem>>,
     pub prewhere: Option<Prewhere>,
+    pub sample: Option<ScanSample>,
 
     // statistics will be ignored in comparison and hashing
     pub statistics: Option<TableStatistics>,
+    pub column_stats: ColumnStatSet,
 }
 
 impl PartialEq for LogicalGet {
@@ -55,6 +59,7 @@ impl PartialEq for LogicalGet {
         self.table_index == other.table_index
             && self.columns == other.columns
             && self.push_down_predicates == other.push_down_predicates
+            && self.sample == other.sample
     }
 }
 
@@ -94,14 +99,35 @@ impl Operator for LogicalGet {
 
 impl LogicalOperator for LogicalGet {
     fn derive_relational_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let cardinality = self
+            .statistics
+            .as_ref()
+            .map_or(0.0, |stat| stat.num_rows.map_or(0.0, |num| num as f64));
+        // the number of the rows sampled is estimated only, so are the statistics of the columns
+        let (cardinality, precise_cardinality) = match self.sample {
+            Some(ScanSample::Block(probability)) | Some(ScanSample::Row(probability)) => {
+                (cardinality * probability, None)
+            }
+            None => (
+                cardinality,
+                self.statistics.as_ref().and_then(|stat| stat.num_rows),
+            ),
+        };
         Ok(RelationalProperty {
             output_columns: self.columns.clone(),
             outer_columns: Default::default(),
-            cardinality: self
-                .statistics
-                .as_ref()
-                .map_or(0.0, |stat| stat.num_rows.map_or(0.0, |num| num as f64)),
-            precise_cardinality: self.statistics.as_ref().and_then(|stat| stat.num_rows),
+            cardinality,
+            precise_cardinality,
+            column_stats: self
+                .column_stats
+                .iter()
+                .filter(|(index, _)| self.columns.contains(index))
+                .map(|(index, stat)| {
+                    let mut stat = stat.clone();
+                    stat.is_exact = stat.is_exact && self.sample.is_none();
+                    (*index, stat)
+                })
+                .collect(),
         })
     }
 }
@@ -19,6 +19,7 @@ use common_exception::Result;
 use common_planner::IndexType;
 
 use super::ScalarExpr;
+use crate::sql::optimizer::bound_column_stats;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
 use crate::sql::plans::LogicalOperator;
@@ -154,25 +155,61 @@ impl LogicalOperator for LogicalInnerJoin {
         }
         outer_columns = outer_columns.difference(&output_columns).cloned().collect();
 
-        // Derive cardinality. We can not estimate the cardinality of inner join until we have
-        // distribution information of join keys, so we set it to the maximum value.
+        // Derive cardinality. Each value of a join key is assumed to match a value of the other
+        // key if it has fewer distinct values, and the join keys are assumed to be independent.
+        // The keys without statistics don't reduce the cardinality.
+        let mut inner_cardinality = left_prop.cardinality * right_prop.cardinality;
+        let mut column_stats = left_prop.column_stats;
+        column_stats.extend(right_prop.column_stats);
+        for (left, right) in self
+            .left_conditions
+            .iter()
+            .zip(self.right_conditions.iter())
+        {
+            if let (Scalar::BoundColumnRef(left), Scalar::BoundColumnRef(right)) = (left, right) {
+                let left_index = left.column.index;
+                let right_index = right.column.index;
+                if let (Some(left_stat), Some(right_stat)) = (
+                    column_stats.get(&left_index),
+                    column_stats.get(&right_index),
+                ) {
+                    let max_ndv = left_stat.ndv.max(right_stat.ndv);
+                    if max_ndv >= 1.0 {
+                        inner_cardinality /= max_ndv;
+                    }
+
+                    // Only the values in both keys are kept after the join.
+                    let min_ndv = left_stat.ndv.min(right_stat.ndv);
+                    for index in [left_index, right_index] {
+                        if let Some(stat) = column_stats.get_mut(&index) {
+                            stat.ndv = min_ndv;
+                        }
+                    }
+                }
+            }
+        }
+
         let cardinality = match self.join_type {
-            JoinType::Inner
-            | JoinType::Left
-            | JoinType::Right
-            | JoinType::Full
-            | JoinType::Cross => left_prop.cardinality * right_prop.cardinality,
+            JoinType::Inner | JoinType::Cross => inner_cardinality,
+            JoinType::Left => inner_cardinality.max(left_prop.cardinality),
+            JoinType::Right => inner_cardinality.max(right_prop.cardinality),
+            JoinType::Full => inner_cardinality
+                .max(left_prop.cardinality)
+                .max(right_prop.cardinality),
 
             JoinType::Semi | JoinType::Anti | JoinType::Mark | JoinType::Single => {
                 left_prop.cardinality
             }
         };
 
+        column_stats.retain(|index, _| output_columns.contains(index));
+
         Ok(RelationalProperty {
             output_columns,
             outer_columns,
             cardinality,
             precise_cardinality: None,
+            column_stats: bound_column_stats(column_stats, cardinality),
         })
     }
 }
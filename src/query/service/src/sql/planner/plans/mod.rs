@@ -29,10 +29,12 @@ mod pattern;
 mod physical_scan;
 mod presign;
 mod recluster_table;
+mod recursive_cte;
 mod scalar;
 pub mod share;
 mod sort;
 mod union_all;
+mod window;
 
 use std::fmt::Display;
 use std::sync::Arc;
@@ -47,26 +49,50 @@ use common_datavalues::StringType;
 use common_legacy_planners::DeletePlan;
 use common_legacy_planners::SettingPlan;
 use common_planner::plans::AlterTableClusterKeyPlan;
+use common_planner::plans::AlterTableColumnPlan;
 use common_planner::plans::AlterUDFPlan;
 use common_planner::plans::AlterUserPlan;
 use common_planner::plans::AlterViewPlan;
+use common_planner::plans::AlterVirtualColumnsPlan;
+use common_planner::plans::AnalyzeTablePlan;
+use common_planner::plans::AttachTablePlan;
 use common_planner::plans::CallPlan;
+use common_planner::plans::CreateAggregatingIndexPlan;
+use common_planner::plans::CreateBloomIndexPlan;
 use common_planner::plans::CreateDatabasePlan;
+use common_planner::plans::CreateInvertedIndexPlan;
+use common_planner::plans::CreateMaskingPolicyPlan;
+use common_planner::plans::CreateNetworkPolicyPlan;
 use common_planner::plans::CreateRolePlan;
+use common_planner::plans::CreateRowAccessPolicyPlan;
 use common_planner::plans::CreateStagePlan;
+use common_planner::plans::CreateStreamPlan;
+use common_planner::plans::CreateTaskPlan;
 use common_planner::plans::CreateUDFPlan;
 use common_planner::plans::CreateUserPlan;
 use common_planner::plans::CreateViewPlan;
+use common_planner::plans::CreateVirtualColumnsPlan;
+use common_planner::plans::CreateWorkloadGroupPlan;
 use common_planner::plans::DescribeTablePlan;
+use common_planner::plans::DropAggregatingIndexPlan;
 use common_planner::plans::DropDatabasePlan;
+use common_planner::plans::DropIndexPlan;
+use common_planner::plans::DropMaskingPolicyPlan;
+use common_planner::plans::DropNetworkPolicyPlan;
 use common_planner::plans::DropRolePlan;
+use common_planner::plans::DropRowAccessPolicyPlan;
 use common_planner::plans::DropStagePlan;
+use common_planner::plans::DropStreamPlan;
 use common_planner::plans::DropTableClusterKeyPlan;
 use common_planner::plans::DropTablePlan;
+use common_planner::plans::DropTaskPlan;
 use common_planner::plans::DropUDFPlan;
 use common_planner::plans::DropUserPlan;
 use common_planner::plans::DropViewPlan;
+use common_planner::plans::DropVirtualColumnsPlan;
+use common_planner::plans::DropWorkloadGroupPlan;
 use common_planner::plans::ExistsTablePlan;
+use common_planner::plans::FlashbackTablePlan;
 use common_planner::plans::GrantPrivilegePlan;
 use common_planner::plans::GrantRolePlan;
 use common_planner::plans::KillPlan;
@@ -77,12 +103,15 @@ use common_planner::plans::RenameDatabasePlan;
 use common_planner::plans::RenameTablePlan;
 use common_planner::plans::RevokePrivilegePlan;
 use common_planner::plans::RevokeRolePlan;
+use common_planner::plans::SetColumnMaskingPolicyPlan;
+use common_planner::plans::SetRolePlan;
 use common_planner::plans::ShowCreateDatabasePlan;
 use common_planner::plans::ShowCreateTablePlan;
 use common_planner::plans::ShowGrantsPlan;
 use common_planner::plans::TruncateTablePlan;
 use common_planner::plans::UndropDatabasePlan;
 use common_planner::plans::UndropTablePlan;
+use common_planner::plans::UnsetColumnMaskingPolicyPlan;
 use common_planner::plans::UseDatabasePlan;
 use common_planner::MetadataRef;
 pub use copy_v2::CopyPlanV2;
@@ -108,11 +137,14 @@ pub use physical_scan::PhysicalScan;
 pub use presign::PresignAction;
 pub use presign::PresignPlan;
 pub use recluster_table::ReclusterTablePlan;
+pub use recursive_cte::RecursiveCteScan;
+pub use recursive_cte::RecursiveUnion;
 pub use scalar::*;
 pub use share::*;
 pub use sort::Sort;
 pub use sort::SortItem;
 pub use union_all::UnionAll;
+pub use window::*;
 
 use super::BindContext;
 use crate::sql::optimizer::SExpr;
@@ -156,14 +188,18 @@ pub enum Plan {
     ShowCreateTable(Box<ShowCreateTablePlan>),
     DescribeTable(Box<DescribeTablePlan>),
     CreateTable(Box<CreateTablePlanV2>),
+    AttachTable(Box<AttachTablePlan>),
     DropTable(Box<DropTablePlan>),
     UndropTable(Box<UndropTablePlan>),
     RenameTable(Box<RenameTablePlan>),
     AlterTableClusterKey(Box<AlterTableClusterKeyPlan>),
+    AlterTableColumn(Box<AlterTableColumnPlan>),
     DropTableClusterKey(Box<DropTableClusterKeyPlan>),
     ReclusterTable(Box<ReclusterTablePlan>),
     TruncateTable(Box<TruncateTablePlan>),
+    FlashbackTable(Box<FlashbackTablePlan>),
     OptimizeTable(Box<OptimizeTablePlan>),
+    AnalyzeTable(Box<AnalyzeTablePlan>),
     ExistsTable(Box<ExistsTablePlan>),
 
     // Insert
@@ -175,6 +211,24 @@ pub enum Plan {
     AlterView(Box<AlterViewPlan>),
     DropView(Box<DropViewPlan>),
 
+    // Streams
+    CreateStream(Box<CreateStreamPlan>),
+    DropStream(Box<DropStreamPlan>),
+
+    // Aggregating indexes
+    CreateAggregatingIndex(Box<CreateAggregatingIndexPlan>),
+    DropAggregatingIndex(Box<DropAggregatingIndexPlan>),
+
+    // Bloom and inverted indexes
+    CreateBloomIndex(Box<CreateBloomIndexPlan>),
+    CreateInvertedIndex(Box<CreateInvertedIndexPlan>),
+    DropIndex(Box<DropIndexPlan>),
+
+    // Virtual columns
+    CreateVirtualColumns(Box<CreateVirtualColumnsPlan>),
+    AlterVirtualColumns(Box<AlterVirtualColumnsPlan>),
+    DropVirtualColumns(Box<DropVirtualColumnsPlan>),
+
     // Account
     AlterUser(Box<AlterUserPlan>),
     CreateUser(Box<CreateUserPlan>),
@@ -188,12 +242,35 @@ pub enum Plan {
     // Role
     CreateRole(Box<CreateRolePlan>),
     DropRole(Box<DropRolePlan>),
+    SetRole(Box<SetRolePlan>),
     GrantRole(Box<GrantRolePlan>),
     GrantPriv(Box<GrantPrivilegePlan>),
     ShowGrants(Box<ShowGrantsPlan>),
     RevokePriv(Box<RevokePrivilegePlan>),
     RevokeRole(Box<RevokeRolePlan>),
 
+    // Row access policies
+    CreateRowAccessPolicy(Box<CreateRowAccessPolicyPlan>),
+    DropRowAccessPolicy(Box<DropRowAccessPolicyPlan>),
+
+    // Masking policies
+    CreateMaskingPolicy(Box<CreateMaskingPolicyPlan>),
+    DropMaskingPolicy(Box<DropMaskingPolicyPlan>),
+    SetColumnMaskingPolicy(Box<SetColumnMaskingPolicyPlan>),
+    UnsetColumnMaskingPolicy(Box<UnsetColumnMaskingPolicyPlan>),
+
+    // Network policies
+    CreateNetworkPolicy(Box<CreateNetworkPolicyPlan>),
+    DropNetworkPolicy(Box<DropNetworkPolicyPlan>),
+
+    // Workload groups
+    CreateWorkloadGroup(Box<CreateWorkloadGroupPlan>),
+    DropWorkloadGroup(Box<DropWorkloadGroupPlan>),
+
+    // Tasks
+    CreateTask(Box<CreateTaskPlan>),
+    DropTask(Box<DropTaskPlan>),
+
     // Stages
     ListStage(Box<ListPlan>),
     CreateStage(Box<CreateStagePlan>),
@@ -217,6 +294,11 @@ pub enum Plan {
     ShowShares(Box<ShowSharesPlan>),
     ShowObjectGrantPrivileges(Box<ShowObjectGrantPrivilegesPlan>),
     ShowGrantTenantsOfShare(Box<ShowGrantTenantsOfSharePlan>),
+
+    // Transactions
+    Begin,
+    Commit,
+    Rollback,
 }
 
 #[derive(Clone, Debug)]
@@ -236,6 +318,11 @@ pub enum RewriteKind {
     ShowStages,
     DescribeStage,
     ShowRoles,
+    ShowRowAccessPolicies,
+    ShowMaskingPolicies,
+    ShowNetworkPolicies,
+    ShowWorkloadGroups,
+    ShowTasks,
 }
 
 impl Display for Plan {
@@ -253,23 +340,50 @@ impl Display for Plan {
             Plan::ShowCreateTable(_) => write!(f, "ShowCreateTable"),
             Plan::DescribeTable(_) => write!(f, "DescribeTable"),
             Plan::CreateTable(_) => write!(f, "CreateTable"),
+            Plan::AttachTable(_) => write!(f, "AttachTable"),
             Plan::DropTable(_) => write!(f, "DropTable"),
             Plan::UndropTable(_) => write!(f, "UndropTable"),
             Plan::RenameTable(_) => write!(f, "RenameTable"),
             Plan::AlterTableClusterKey(_) => write!(f, "AlterTableClusterKey"),
+            Plan::AlterTableColumn(_) => write!(f, "AlterTableColumn"),
             Plan::DropTableClusterKey(_) => write!(f, "DropTableClusterKey"),
             Plan::ReclusterTable(_) => write!(f, "ReclusterTable"),
             Plan::TruncateTable(_) => write!(f, "TruncateTable"),
+            Plan::FlashbackTable(_) => write!(f, "FlashbackTable"),
             Plan::OptimizeTable(_) => write!(f, "OptimizeTable"),
+            Plan::AnalyzeTable(_) => write!(f, "AnalyzeTable"),
             Plan::ExistsTable(_) => write!(f, "ExistsTable"),
             Plan::CreateView(_) => write!(f, "CreateView"),
             Plan::AlterView(_) => write!(f, "AlterView"),
             Plan::DropView(_) => write!(f, "DropView"),
+            Plan::CreateStream(_) => write!(f, "CreateStream"),
+            Plan::DropStream(_) => write!(f, "DropStream"),
+            Plan::CreateAggregatingIndex(_) => write!(f, "CreateAggregatingIndex"),
+            Plan::DropAggregatingIndex(_) => write!(f, "DropAggregatingIndex"),
+            Plan::CreateBloomIndex(_) => write!(f, "CreateBloomIndex"),
+            Plan::CreateInvertedIndex(_) => write!(f, "CreateInvertedIndex"),
+            Plan::DropIndex(_) => write!(f, "DropIndex"),
+            Plan::CreateVirtualColumns(_) => write!(f, "CreateVirtualColumns"),
+            Plan::AlterVirtualColumns(_) => write!(f, "AlterVirtualColumns"),
+            Plan::DropVirtualColumns(_) => write!(f, "DropVirtualColumns"),
             Plan::AlterUser(_) => write!(f, "AlterUser"),
             Plan::CreateUser(_) => write!(f, "CreateUser"),
             Plan::DropUser(_) => write!(f, "DropUser"),
             Plan::CreateRole(_) => write!(f, "CreateRole"),
             Plan::DropRole(_) => write!(f, "DropRole"),
+            Plan::SetRole(_) => write!(f, "SetRole"),
+            Plan::CreateRowAccessPolicy(_) => write!(f, "CreateRowAccessPolicy"),
+            Plan::DropRowAccessPolicy(_) => write!(f, "DropRowAccessPolicy"),
+            Plan::CreateMaskingPolicy(_) => write!(f, "CreateMaskingPolicy"),
+            Plan::DropMaskingPolicy(_) => write!(f, "DropMaskingPolicy"),
+            Plan::SetColumnMaskingPolicy(_) => write!(f, "SetColumnMaskingPolicy"),
+            Plan::UnsetColumnMaskingPolicy(_) => write!(f, "UnsetColumnMaskingPolicy"),
+            Plan::CreateNetworkPolicy(_) => write!(f, "CreateNetworkPolicy"),
+            Plan::DropNetworkPolicy(_) => write!(f, "DropNetworkPolicy"),
+            Plan::CreateWorkloadGroup(_) => write!(f, "CreateWorkloadGroup"),
+            Plan::DropWorkloadGroup(_) => write!(f, "DropWorkloadGroup"),
+            Plan::CreateTask(_) => write!(f, "CreateTask"),
+            Plan::DropTask(_) => write!(f, "DropTask"),
             Plan::ListStage(_) => write!(f, "ListStage"),
             Plan::CreateStage(_) => write!(f, "CreateStage"),
             Plan::DropStage(_) => write!(f, "DropStage"),
@@ -299,6 +413,9 @@ impl Display for Plan {
             Plan::ShowGrantTenantsOfShare(_) => write!(f, "ShowGrantTenantsOfShare"),
             Plan::ExplainAst { .. } => write!(f, "ExplainAst"),
             Plan::ExplainSyntax { .. } => write!(f, "ExplainSyntax"),
+            Plan::Begin => write!(f, "Begin"),
+            Plan::Commit => write!(f, "Commit"),
+            Plan::Rollback => write!(f, "Rollback"),
         }
     }
 }
@@ -326,23 +443,50 @@ impl Plan {
             Plan::ShowCreateTable(plan) => plan.schema(),
             Plan::DescribeTable(plan) => plan.schema(),
             Plan::CreateTable(plan) => plan.schema(),
+            Plan::AttachTable(plan) => plan.schema(),
             Plan::DropTable(plan) => plan.schema(),
             Plan::UndropTable(plan) => plan.schema(),
             Plan::RenameTable(plan) => plan.schema(),
             Plan::AlterTableClusterKey(plan) => plan.schema(),
+            Plan::AlterTableColumn(plan) => plan.schema(),
             Plan::DropTableClusterKey(plan) => plan.schema(),
             Plan::ReclusterTable(plan) => plan.schema(),
             Plan::TruncateTable(plan) => plan.schema(),
+            Plan::FlashbackTable(plan) => plan.schema(),
             Plan::OptimizeTable(plan) => plan.schema(),
+            Plan::AnalyzeTable(plan) => plan.schema(),
             Plan::ExistsTable(plan) => plan.schema(),
             Plan::CreateView(plan) => plan.schema(),
             Plan::AlterView(plan) => plan.schema(),
             Plan::DropView(plan) => plan.schema(),
+            Plan::CreateStream(plan) => plan.schema(),
+            Plan::DropStream(plan) => plan.schema(),
+            Plan::CreateAggregatingIndex(plan) => plan.schema(),
+            Plan::DropAggregatingIndex(plan) => plan.schema(),
+            Plan::CreateBloomIndex(plan) => plan.schema(),
+            Plan::CreateInvertedIndex(plan) => plan.schema(),
+            Plan::DropIndex(plan) => plan.schema(),
+            Plan::CreateVirtualColumns(plan) => plan.schema(),
+            Plan::AlterVirtualColumns(plan) => plan.schema(),
+            Plan::DropVirtualColumns(plan) => plan.schema(),
             Plan::AlterUser(plan) => plan.schema(),
             Plan::CreateUser(plan) => plan.schema(),
             Plan::DropUser(plan) => plan.schema(),
             Plan::CreateRole(plan) => plan.schema(),
             Plan::DropRole(plan) => plan.schema(),
+            Plan::SetRole(plan) => plan.schema(),
+            Plan::CreateRowAccessPolicy(plan) => plan.schema(),
+            Plan::DropRowAccessPolicy(plan) => plan.schema(),
+            Plan::CreateMaskingPolicy(plan) => plan.schema(),
+            Plan::DropMaskingPolicy(plan) => plan.schema(),
+            Plan::SetColumnMaskingPolicy(plan) => plan.schema(),
+            Plan::UnsetColumnMaskingPolicy(plan) => plan.schema(),
+            Plan::CreateNetworkPolicy(plan) => plan.schema(),
+            Plan::DropNetworkPolicy(plan) => plan.schema(),
+            Plan::CreateWorkloadGroup(plan) => plan.schema(),
+            Plan::DropWorkloadGroup(plan) => plan.schema(),
+            Plan::CreateTask(plan) => plan.schema(),
+            Plan::DropTask(plan) => plan.schema(),
             Plan::GrantRole(plan) => plan.schema(),
             Plan::GrantPriv(plan) => plan.schema(),
             Plan::ShowGrants(plan) => plan.schema(),
@@ -370,6 +514,29 @@ impl Plan {
             Plan::ShowShares(plan) => plan.schema(),
             Plan::ShowObjectGrantPrivileges(plan) => plan.schema(),
             Plan::ShowGrantTenantsOfShare(plan) => plan.schema(),
+            Plan::Begin | Plan::Commit | Plan::Rollback => Arc::new(DataSchema::empty()),
         }
     }
+
+    /// Whether the plan returns rows to the client, the schema of the other plans is not sent.
+    pub fn has_result_set(&self) -> bool {
+        matches!(
+            self,
+            Plan::Query { .. }
+                | Plan::Explain { .. }
+                | Plan::ExplainAst { .. }
+                | Plan::ExplainSyntax { .. }
+                | Plan::Call(_)
+                | Plan::ShowCreateDatabase(_)
+                | Plan::ShowCreateTable(_)
+                | Plan::DescShare(_)
+                | Plan::ShowShares(_)
+                | Plan::ShowObjectGrantPrivileges(_)
+                | Plan::ShowGrantTenantsOfShare(_)
+                | Plan::DescribeTable(_)
+                | Plan::ShowGrants(_)
+                | Plan::ListStage(_)
+                | Plan::Presign(_)
+        )
+    }
 }
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
@@ -25,8 +28,11 @@ use super::logical_get::LogicalGet;
 use super::logical_join::LogicalInnerJoin;
 use super::pattern::PatternPlan;
 use super::physical_scan::PhysicalScan;
+use super::recursive_cte::RecursiveCteScan;
+use super::recursive_cte::RecursiveUnion;
 use super::sort::Sort;
 use super::union_all::UnionAll;
+use super::window::Window;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
@@ -58,6 +64,7 @@ pub trait PhysicalOperator {
 
     fn compute_required_prop_child<'a>(
         &self,
+        ctx: Arc<dyn TableContext>,
         rel_expr: &RelExpr<'a>,
         child_index: usize,
         required: &RequiredProperty,
@@ -84,6 +91,9 @@ pub enum RelOp {
     Exchange,
     UnionAll,
     DummyTableScan,
+    Window,
+    RecursiveUnion,
+    RecursiveCteScan,
 
     // Pattern
     Pattern,
@@ -106,6 +116,9 @@ pub enum RelOperator {
     Exchange(Exchange),
     UnionAll(UnionAll),
     DummyTableScan(DummyTableScan),
+    Window(Window),
+    RecursiveUnion(RecursiveUnion),
+    RecursiveCteScan(RecursiveCteScan),
 
     Pattern(PatternPlan),
 }
@@ -121,6 +134,9 @@ impl Operator for RelOperator {
             RelOperator::Filter(rel_op) => rel_op.rel_op(),
             RelOperator::Aggregate(rel_op) => rel_op.rel_op(),
             RelOperator::Sort(rel_op) => rel_op.rel_op(),
+            RelOperator::Window(rel_op) => rel_op.rel_op(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.rel_op(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.rel_op(),
             RelOperator::Limit(rel_op) => rel_op.rel_op(),
             RelOperator::Pattern(rel_op) => rel_op.rel_op(),
             RelOperator::Exchange(rel_op) => rel_op.rel_op(),
@@ -139,6 +155,9 @@ impl Operator for RelOperator {
             RelOperator::Filter(rel_op) => rel_op.is_physical(),
             RelOperator::Aggregate(rel_op) => rel_op.is_physical(),
             RelOperator::Sort(rel_op) => rel_op.is_physical(),
+            RelOperator::Window(rel_op) => rel_op.is_physical(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.is_physical(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.is_physical(),
             RelOperator::Limit(rel_op) => rel_op.is_physical(),
             RelOperator::Pattern(rel_op) => rel_op.is_physical(),
             RelOperator::Exchange(rel_op) => rel_op.is_physical(),
@@ -157,6 +176,9 @@ impl Operator for RelOperator {
             RelOperator::Filter(rel_op) => rel_op.is_logical(),
             RelOperator::Aggregate(rel_op) => rel_op.is_logical(),
             RelOperator::Sort(rel_op) => rel_op.is_logical(),
+            RelOperator::Window(rel_op) => rel_op.is_logical(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.is_logical(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.is_logical(),
             RelOperator::Limit(rel_op) => rel_op.is_logical(),
             RelOperator::Pattern(rel_op) => rel_op.is_logical(),
             RelOperator::Exchange(rel_op) => rel_op.is_logical(),
@@ -175,6 +197,9 @@ impl Operator for RelOperator {
             RelOperator::Filter(rel_op) => rel_op.as_logical(),
             RelOperator::Aggregate(rel_op) => rel_op.as_logical(),
             RelOperator::Sort(rel_op) => rel_op.as_logical(),
+            RelOperator::Window(rel_op) => rel_op.as_logical(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.as_logical(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.as_logical(),
             RelOperator::Limit(rel_op) => rel_op.as_logical(),
             RelOperator::Pattern(rel_op) => rel_op.as_logical(),
             RelOperator::Exchange(rel_op) => rel_op.as_logical(),
@@ -193,6 +218,9 @@ impl Operator for RelOperator {
             RelOperator::Filter(rel_op) => rel_op.as_physical(),
             RelOperator::Aggregate(rel_op) => rel_op.as_physical(),
             RelOperator::Sort(rel_op) => rel_op.as_physical(),
+            RelOperator::Window(rel_op) => rel_op.as_physical(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.as_physical(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.as_physical(),
             RelOperator::Limit(rel_op) => rel_op.as_physical(),
             RelOperator::Pattern(rel_op) => rel_op.as_physical(),
             RelOperator::Exchange(rel_op) => rel_op.as_physical(),
@@ -449,3 +477,60 @@ impl TryFrom<RelOperator> for DummyTableScan {
         }
     }
 }
+
+impl From<Window> for RelOperator {
+    fn from(v: Window) -> Self {
+        Self::Window(v)
+    }
+}
+
+impl TryFrom<RelOperator> for Window {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::Window(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to Window",
+            ))
+        }
+    }
+}
+
+impl From<RecursiveUnion> for RelOperator {
+    fn from(v: RecursiveUnion) -> Self {
+        Self::RecursiveUnion(v)
+    }
+}
+
+impl TryFrom<RelOperator> for RecursiveUnion {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::RecursiveUnion(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to RecursiveUnion",
+            ))
+        }
+    }
+}
+
+impl From<RecursiveCteScan> for RelOperator {
+    fn from(v: RecursiveCteScan) -> Self {
+        Self::RecursiveCteScan(v)
+    }
+}
+
+impl TryFrom<RelOperator> for RecursiveCteScan {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::RecursiveCteScan(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to RecursiveCteScan",
+            ))
+        }
+    }
+}
@@ -13,8 +13,11 @@
 // limitations under the License.
 
 use std::hash::Hash;
+use std::sync::Arc;
 
+use common_catalog::table_context::TableContext;
 use common_exception::Result;
+use common_legacy_planners::ScanSample;
 use common_planner::IndexType;
 use itertools::Itertools;
 
@@ -39,6 +42,7 @@ pub struct PhysicalScan {
     pub limit: Option<usize>,
     pub order_by: Option<Vec<SortItem>>,
     pub prewhere: Option<Prewhere>,
+    pub sample: Option<ScanSample>,
 }
 
 #[allow(clippy::derive_hash_xor_eq)]
@@ -84,6 +88,7 @@ impl PhysicalOperator for PhysicalScan {
     // Won't be invoked at all, since `PhysicalScan` is leaf node
     fn compute_required_prop_child<'a>(
         &self,
+        _ctx: Arc<dyn TableContext>,
         _rel_expr: &RelExpr<'a>,
         _child_index: usize,
         _required: &RequiredProperty,
@@ -0,0 +1,167 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_planner::IndexType;
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalOperator;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalOperator;
+use crate::sql::plans::RelOp;
+
+/// Evaluates a recursive CTE, whose children are the anchor term and the recursive term.
+///
+/// The anchor term is evaluated once, then the recursive term is evaluated repeatedly, reading
+/// the rows produced by the last iteration through `RecursiveCteScan`, until it produces no rows.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecursiveUnion {
+    pub cte_name: String,
+    // Pairs of unioned columns, the output columns are the ones of the anchor term
+    pub pairs: Vec<(IndexType, IndexType)>,
+    // Drop the duplicated rows (UNION rather than UNION ALL), which ends the cycles of the graph
+    pub distinct: bool,
+}
+
+/// Reads the rows produced by the last iteration of a recursive CTE, the working table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecursiveCteScan {
+    pub cte_name: String,
+    pub columns: Vec<IndexType>,
+}
+
+impl Operator for RecursiveUnion {
+    fn rel_op(&self) -> RelOp {
+        RelOp::RecursiveUnion
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl LogicalOperator for RecursiveUnion {
+    fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let anchor_prop = rel_expr.derive_relational_prop_child(0)?;
+        let recursive_prop = rel_expr.derive_relational_prop_child(1)?;
+
+        let outer_columns = anchor_prop
+            .outer_columns
+            .union(&recursive_prop.outer_columns)
+            .cloned()
+            .collect();
+
+        Ok(RelationalProperty {
+            output_columns: anchor_prop.output_columns,
+            outer_columns,
+            // the number of iterations is unknown
+            cardinality: anchor_prop.cardinality + recursive_prop.cardinality,
+            precise_cardinality: None,
+            column_stats: Default::default(),
+        })
+    }
+}
+
+impl PhysicalOperator for RecursiveUnion {
+    fn derive_physical_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        Ok(PhysicalProperty {
+            distribution: Distribution::Serial,
+        })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        // The iterations are evaluated in one place.
+        let mut required = required.clone();
+        required.distribution = Distribution::Serial;
+        Ok(required)
+    }
+}
+
+impl Operator for RecursiveCteScan {
+    fn rel_op(&self) -> RelOp {
+        RelOp::RecursiveCteScan
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl LogicalOperator for RecursiveCteScan {
+    fn derive_relational_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        Ok(RelationalProperty {
+            output_columns: self.columns.iter().cloned().collect(),
+            outer_columns: ColumnSet::new(),
+            cardinality: 1.0,
+            precise_cardinality: None,
+            column_stats: Default::default(),
+        })
+    }
+}
+
+impl PhysicalOperator for RecursiveCteScan {
+    fn derive_physical_prop<'a>(&self, _rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        Ok(PhysicalProperty {
+            distribution: Distribution::Serial,
+        })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        Ok(required.clone())
+    }
+}
@@ -27,6 +27,8 @@ use common_planner::IndexType;
 use crate::sql::binder::ColumnBinding;
 use crate::sql::optimizer::ColumnSet;
 use crate::sql::optimizer::SExpr;
+use crate::sql::plans::WindowFuncFrame;
+use crate::sql::plans::WindowFuncType;
 
 pub trait ScalarExpr {
     /// Get return type and nullability
@@ -52,11 +54,13 @@ pub enum Scalar {
     OrExpr(OrExpr),
     ComparisonExpr(ComparisonExpr),
     AggregateFunction(AggregateFunction),
+    WindowFunction(WindowFunction),
     FunctionCall(FunctionCall),
     // TODO(leiysky): maybe we don't need this variant any more
     // after making functions static typed?
     CastExpr(CastExpr),
     SubqueryExpr(SubqueryExpr),
+    UDFServerCall(UDFServerCall),
 }
 
 impl ScalarExpr for Scalar {
@@ -68,9 +72,11 @@ impl ScalarExpr for Scalar {
             Scalar::OrExpr(scalar) => scalar.data_type(),
             Scalar::ComparisonExpr(scalar) => scalar.data_type(),
             Scalar::AggregateFunction(scalar) => scalar.data_type(),
+            Scalar::WindowFunction(scalar) => scalar.data_type(),
             Scalar::FunctionCall(scalar) => scalar.data_type(),
             Scalar::CastExpr(scalar) => scalar.data_type(),
             Scalar::SubqueryExpr(scalar) => scalar.data_type(),
+            Scalar::UDFServerCall(scalar) => scalar.data_type(),
         }
     }
 
@@ -82,9 +88,11 @@ impl ScalarExpr for Scalar {
             Scalar::OrExpr(scalar) => scalar.used_columns(),
             Scalar::ComparisonExpr(scalar) => scalar.used_columns(),
             Scalar::AggregateFunction(scalar) => scalar.used_columns(),
+            Scalar::WindowFunction(scalar) => scalar.used_columns(),
             Scalar::FunctionCall(scalar) => scalar.used_columns(),
             Scalar::CastExpr(scalar) => scalar.used_columns(),
             Scalar::SubqueryExpr(scalar) => scalar.used_columns(),
+            Scalar::UDFServerCall(scalar) => scalar.used_columns(),
         }
     }
 
@@ -96,9 +104,11 @@ impl ScalarExpr for Scalar {
             Scalar::OrExpr(scalar) => scalar.is_deterministic(),
             Scalar::ComparisonExpr(scalar) => scalar.is_deterministic(),
             Scalar::AggregateFunction(scalar) => scalar.is_deterministic(),
+            Scalar::WindowFunction(scalar) => scalar.is_deterministic(),
             Scalar::FunctionCall(scalar) => scalar.is_deterministic(),
             Scalar::CastExpr(scalar) => scalar.is_deterministic(),
             Scalar::SubqueryExpr(scalar) => scalar.is_deterministic(),
+            Scalar::UDFServerCall(scalar) => scalar.is_deterministic(),
         }
     }
 }
@@ -213,6 +223,25 @@ impl TryFrom<Scalar> for AggregateFunction {
     }
 }
 
+impl From<WindowFunction> for Scalar {
+    fn from(v: WindowFunction) -> Self {
+        Self::WindowFunction(v)
+    }
+}
+
+impl TryFrom<Scalar> for WindowFunction {
+    type Error = ErrorCode;
+    fn try_from(value: Scalar) -> Result<Self> {
+        if let Scalar::WindowFunction(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast Scalar to WindowFunction",
+            ))
+        }
+    }
+}
+
 impl From<FunctionCall> for Scalar {
     fn from(v: FunctionCall) -> Self {
         Self::FunctionCall(v)
@@ -232,6 +261,25 @@ impl TryFrom<Scalar> for FunctionCall {
     }
 }
 
+impl From<UDFServerCall> for Scalar {
+    fn from(v: UDFServerCall) -> Self {
+        Self::UDFServerCall(v)
+    }
+}
+
+impl TryFrom<Scalar> for UDFServerCall {
+    type Error = ErrorCode;
+    fn try_from(value: Scalar) -> Result<Self> {
+        if let Scalar::UDFServerCall(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast Scalar to UDFServerCall",
+            ))
+        }
+    }
+}
+
 impl From<CastExpr> for Scalar {
     fn from(v: CastExpr) -> Self {
         Self::CastExpr(v)
@@ -465,6 +513,61 @@ impl ScalarExpr for AggregateFunction {
     }
 }
 
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WindowFunction {
+    pub display_name: String,
+
+    pub func: WindowFuncType,
+    pub args: Vec<Scalar>,
+    pub partition_by: Vec<Scalar>,
+    pub order_by: Vec<WindowOrderBy>,
+    pub frame: WindowFuncFrame,
+    pub return_type: Box<DataTypeImpl>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WindowOrderBy {
+    pub expr: Scalar,
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
+impl WindowFunction {
+    /// All the scalars the function is evaluated from, the arguments, then the partition keys,
+    /// then the order keys.
+    pub fn inner_scalars(&self) -> impl Iterator<Item = &Scalar> {
+        self.args
+            .iter()
+            .chain(self.partition_by.iter())
+            .chain(self.order_by.iter().map(|item| &item.expr))
+    }
+
+    pub fn inner_scalars_mut(&mut self) -> impl Iterator<Item = &mut Scalar> {
+        self.args
+            .iter_mut()
+            .chain(self.partition_by.iter_mut())
+            .chain(self.order_by.iter_mut().map(|item| &mut item.expr))
+    }
+}
+
+impl ScalarExpr for WindowFunction {
+    fn data_type(&self) -> DataTypeImpl {
+        *self.return_type.clone()
+    }
+
+    fn used_columns(&self) -> ColumnSet {
+        let mut result = ColumnSet::new();
+        for scalar in self.inner_scalars() {
+            result = result.union(&scalar.used_columns()).cloned().collect();
+        }
+        result
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct FunctionCall {
     pub arguments: Vec<Scalar>,
@@ -516,6 +619,38 @@ impl ScalarExpr for CastExpr {
     }
 }
 
+/// Call of a user defined function which is evaluated by an external UDF server.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct UDFServerCall {
+    pub arguments: Vec<Scalar>,
+
+    pub func_name: String,
+    pub server_addr: String,
+    pub handler: String,
+    pub arg_types: Vec<DataTypeImpl>,
+    pub return_type: Box<DataTypeImpl>,
+    /// Timeout of the requests to the UDF server, in seconds.
+    pub timeout: u64,
+}
+
+impl ScalarExpr for UDFServerCall {
+    fn data_type(&self) -> DataTypeImpl {
+        *self.return_type.clone()
+    }
+
+    fn used_columns(&self) -> ColumnSet {
+        let mut result = ColumnSet::new();
+        for scalar in self.arguments.iter() {
+            result = result.union(&scalar.used_columns()).cloned().collect();
+        }
+        result
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum SubqueryType {
     Any,
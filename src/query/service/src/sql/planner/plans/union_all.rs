@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
 use common_exception::Result;
 use common_planner::IndexType;
 
@@ -85,6 +88,7 @@ impl LogicalOperator for UnionAll {
             outer_columns,
             cardinality,
             precise_cardinality,
+            column_stats: Default::default(),
         })
     }
 }
@@ -98,6 +102,7 @@ impl PhysicalOperator for UnionAll {
 
     fn compute_required_prop_child<'a>(
         &self,
+        _ctx: Arc<dyn TableContext>,
         _rel_expr: &RelExpr<'a>,
         _child_index: usize,
         required: &RequiredProperty,
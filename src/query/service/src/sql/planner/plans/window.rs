@@ -0,0 +1,189 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planner::IndexType;
+
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalOperator;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalOperator;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::WindowFunction;
+
+/// Evaluates a window function over the rows of its input, and appends the result as the
+/// column `index`.
+///
+/// The arguments, partition keys and order keys of the function are all columns of the input.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Window {
+    pub index: IndexType,
+    pub function: WindowFunction,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum WindowFuncType {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag {
+        offset: u64,
+    },
+    Lead {
+        offset: u64,
+    },
+    Aggregate {
+        func_name: String,
+        params: Vec<DataValue>,
+    },
+}
+
+impl WindowFuncType {
+    /// Whether the function computes over the frame of the row, the others ignore it.
+    pub fn is_framed(&self) -> bool {
+        matches!(self, WindowFuncType::Aggregate { .. })
+    }
+}
+
+impl Display for WindowFuncType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFuncType::RowNumber => write!(f, "row_number"),
+            WindowFuncType::Rank => write!(f, "rank"),
+            WindowFuncType::DenseRank => write!(f, "dense_rank"),
+            WindowFuncType::Lag { .. } => write!(f, "lag"),
+            WindowFuncType::Lead { .. } => write!(f, "lead"),
+            WindowFuncType::Aggregate { func_name, .. } => write!(f, "{func_name}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct WindowFuncFrame {
+    pub units: WindowFuncFrameUnits,
+    pub start_bound: WindowFuncFrameBound,
+    pub end_bound: WindowFuncFrameBound,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum WindowFuncFrameUnits {
+    Rows,
+    Range,
+}
+
+/// Bound of a frame, `None` offsets are unbounded.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum WindowFuncFrameBound {
+    CurrentRow,
+    Preceding(Option<u64>),
+    Following(Option<u64>),
+}
+
+impl WindowFuncFrameBound {
+    /// Position of the bound relative to the current row, for checking the order of the bounds.
+    pub fn position(&self) -> i128 {
+        match self {
+            WindowFuncFrameBound::CurrentRow => 0,
+            WindowFuncFrameBound::Preceding(None) => i128::MIN,
+            WindowFuncFrameBound::Preceding(Some(n)) => -(*n as i128),
+            WindowFuncFrameBound::Following(None) => i128::MAX,
+            WindowFuncFrameBound::Following(Some(n)) => *n as i128,
+        }
+    }
+}
+
+impl Display for WindowFuncFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let units = match self.units {
+            WindowFuncFrameUnits::Rows => "ROWS",
+            WindowFuncFrameUnits::Range => "RANGE",
+        };
+        write!(
+            f,
+            "{units} BETWEEN {} AND {}",
+            self.start_bound, self.end_bound
+        )
+    }
+}
+
+impl Display for WindowFuncFrameBound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFuncFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFuncFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFuncFrameBound::Preceding(Some(n)) => write!(f, "{n} PRECEDING"),
+            WindowFuncFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFuncFrameBound::Following(Some(n)) => write!(f, "{n} FOLLOWING"),
+        }
+    }
+}
+
+impl Operator for Window {
+    fn rel_op(&self) -> RelOp {
+        RelOp::Window
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl PhysicalOperator for Window {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        // All the rows of a partition must be in one place.
+        let mut required = required.clone();
+        required.distribution = Distribution::Serial;
+        Ok(required)
+    }
+}
+
+impl LogicalOperator for Window {
+    fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let mut prop = rel_expr.derive_relational_prop_child(0)?;
+        prop.output_columns.insert(self.index);
+        Ok(prop)
+    }
+}
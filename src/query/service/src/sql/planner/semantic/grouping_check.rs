@@ -19,6 +19,7 @@ use common_exception::Result;
 
 use crate::sql::binder::ColumnBinding;
 use crate::sql::binder::Visibility;
+use crate::sql::planner::semantic::WindowChecker;
 use crate::sql::plans::AndExpr;
 use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::CastExpr;
@@ -27,6 +28,7 @@ use crate::sql::plans::FunctionCall;
 use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::ScalarExpr;
+use crate::sql::plans::UDFServerCall;
 use crate::sql::BindContext;
 
 /// Check validity of scalar expression in a grouping context.
@@ -107,6 +109,18 @@ impl<'a> GroupingChecker<'a> {
                 }
                 .into())
             }
+            Scalar::UDFServerCall(udf) => {
+                let args = udf
+                    .arguments
+                    .iter()
+                    .map(|arg| self.resolve(arg, span))
+                    .collect::<Result<Vec<Scalar>>>()?;
+                Ok(UDFServerCall {
+                    arguments: args,
+                    ..udf.clone()
+                }
+                .into())
+            }
             Scalar::CastExpr(cast) => Ok(CastExpr {
                 argument: Box::new(self.resolve(&cast.argument, span)?),
                 from_type: cast.from_type.clone(),
@@ -118,6 +132,13 @@ impl<'a> GroupingChecker<'a> {
                 Ok(scalar.clone())
             }
 
+            Scalar::WindowFunction(_) => {
+                // The window functions are evaluated after aggregation, the grouping check
+                // of their arguments has been performed while binding the windows.
+                let mut window_checker = WindowChecker::new(self.bind_context);
+                window_checker.resolve(scalar)
+            }
+
             Scalar::AggregateFunction(agg) => {
                 if let Some(column) = self
                     .bind_context
@@ -15,9 +15,11 @@
 mod grouping_check;
 mod name_resolution;
 mod type_check;
+mod window_check;
 
 pub use grouping_check::GroupingChecker;
 pub use name_resolution::normalize_identifier;
 pub use name_resolution::IdentifierNormalizer;
 pub use name_resolution::NameResolutionContext;
 pub use type_check::TypeChecker;
+pub use window_check::WindowChecker;
@@ -25,13 +25,19 @@ use common_ast::ast::Query;
 use common_ast::ast::SubqueryModifier;
 use common_ast::ast::TrimWhere;
 use common_ast::ast::UnaryOperator;
+use common_ast::ast::WindowFrame;
+use common_ast::ast::WindowFrameBound;
+use common_ast::ast::WindowFrameUnits;
+use common_ast::ast::WindowSpec;
 use common_ast::parser::parse_expr;
 use common_ast::parser::token::Token;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_ast::DisplayError;
 use common_catalog::catalog::CatalogManager;
+use common_datavalues::remove_nullable;
 use common_datavalues::type_coercion::merge_types;
+use common_datavalues::wrap_nullable;
 use common_datavalues::ArrayType;
 use common_datavalues::DataField;
 use common_datavalues::DataType;
@@ -44,7 +50,9 @@ use common_datavalues::NullableType;
 use common_datavalues::StringType;
 use common_datavalues::StructType;
 use common_datavalues::TimestampType;
+use common_datavalues::TypeFactory;
 use common_datavalues::TypeID;
+use common_datavalues::UInt64Type;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
@@ -53,18 +61,20 @@ use common_functions::scalars::CastFunction;
 use common_functions::scalars::FunctionFactory;
 use common_functions::scalars::TupleFunction;
 use common_legacy_expression::validate_function_arg;
+use common_meta_types::UDFServer;
 use common_planner::MetadataRef;
 use common_users::UserApiProvider;
 
 use super::name_resolution::NameResolutionContext;
 use super::normalize_identifier;
 use crate::catalogs::CatalogManagerHelper;
-use crate::evaluator::Evaluator;
 use crate::sessions::TableContext;
+use crate::sql::binder::check_udf_server_allowed;
 use crate::sql::binder::wrap_cast_if_needed;
 use crate::sql::binder::Binder;
 use crate::sql::binder::NameResolutionResult;
 use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::ScalarRewriter;
 use crate::sql::planner::metadata::optimize_remove_count_args;
 use crate::sql::plans::AggregateFunction;
 use crate::sql::plans::AndExpr;
@@ -78,8 +88,17 @@ use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::SubqueryExpr;
 use crate::sql::plans::SubqueryType;
+use crate::sql::plans::UDFServerCall;
+use crate::sql::plans::WindowFuncFrame;
+use crate::sql::plans::WindowFuncFrameBound;
+use crate::sql::plans::WindowFuncFrameUnits;
+use crate::sql::plans::WindowFuncType;
+use crate::sql::plans::WindowFunction;
+use crate::sql::plans::WindowOrderBy;
 use crate::sql::BindContext;
 use crate::sql::ScalarExpr;
+use crate::storages::index::VirtualColumnMeta;
+use crate::storages::index::VirtualPathElement;
 
 /// A helper for type checking.
 ///
@@ -101,6 +120,13 @@ pub struct TypeChecker<'a> {
     // true if current expr is inside an aggregate function.
     // This is used to check if there is nested aggregate function.
     in_aggregate_function: bool,
+
+    // true if current expr is inside a window function.
+    // This is used to check if there is nested window function.
+    in_window_function: bool,
+
+    // The UDFs being expanded, used to detect the recursive definitions.
+    expanding_udfs: Vec<String>,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -118,6 +144,8 @@ impl<'a> TypeChecker<'a> {
             metadata,
             aliases,
             in_aggregate_function: false,
+            in_window_function: false,
+            expanding_udfs: vec![],
         }
     }
 
@@ -126,28 +154,10 @@ impl<'a> TypeChecker<'a> {
         scalar: &Scalar,
         data_type: &DataTypeImpl,
     ) -> Result<(Scalar, DataTypeImpl)> {
-        // Try constant folding
-        if let Ok((value, value_type)) = Evaluator::eval_scalar(scalar).and_then(|evaluator| {
-            let func_ctx = self.ctx.try_get_function_context()?;
-            if scalar.is_deterministic() {
-                evaluator.try_eval_const(&func_ctx)
-            } else {
-                Err(ErrorCode::LogicalError(
-                    "Constant folding requires the function deterministic",
-                ))
-            }
-        }) {
-            Ok((
-                ConstantExpr {
-                    value,
-                    data_type: Box::new(value_type),
-                }
-                .into(),
-                data_type.clone(),
-            ))
-        } else {
-            Ok((scalar.clone(), data_type.clone()))
-        }
+        // The arguments have been rewritten while being resolved.
+        let func_ctx = self.ctx.try_get_function_context()?;
+        let scalar = ScalarRewriter::with_default_rules(func_ctx).rewrite_root(scalar.clone())?;
+        Ok((scalar, data_type.clone()))
     }
 
     /// Resolve types of `expr` with given `required_type`.
@@ -442,6 +452,7 @@ impl<'a> TypeChecker<'a> {
                                 },
                                 args: vec![*operand.clone(), c.clone()],
                                 params: vec![],
+                                window: None,
                             };
                             arguments.push(equal_expr)
                         }
@@ -497,9 +508,27 @@ impl<'a> TypeChecker<'a> {
                 name,
                 args,
                 params,
-                ..
+                window,
             } => {
                 let func_name = name.name.as_str();
+                if let Some(window) = window {
+                    if *distinct {
+                        return Err(ErrorCode::SemanticError(span.display_error(
+                            "DISTINCT is not supported in window functions".to_string(),
+                        )));
+                    }
+                    return self
+                        .resolve_window_function(
+                            span,
+                            format!("{:#}", expr),
+                            func_name,
+                            args,
+                            params,
+                            window,
+                        )
+                        .await;
+                }
+
                 if !is_builtin_function(func_name)
                     && !Self::is_rewritable_scalar_function(func_name)
                 {
@@ -677,6 +706,18 @@ impl<'a> TypeChecker<'a> {
                             ..
                         } => {
                             let box (_, data_type) = self.resolve(&expr, None).await?;
+                            if remove_nullable(&data_type).data_type_id().is_variant() {
+                                // if the path is a virtual column, read the materialized values
+                                if let Some(result) = self.resolve_virtual_column(
+                                    &accessores,
+                                    database,
+                                    table,
+                                    column,
+                                )? {
+                                    return Ok(result);
+                                }
+                                break;
+                            }
                             if data_type.data_type_id() != TypeID::Struct {
                                 break;
                             }
@@ -926,6 +967,8 @@ impl<'a> TypeChecker<'a> {
             | BinaryOperator::BitwiseOr
             | BinaryOperator::BitwiseAnd
             | BinaryOperator::BitwiseXor
+            | BinaryOperator::JsonGet
+            | BinaryOperator::JsonGetText
             | BinaryOperator::Xor => {
                 self.resolve_function(span, op.to_string().as_str(), &[left, right], required_type)
                     .await
@@ -1445,6 +1488,7 @@ impl<'a> TypeChecker<'a> {
                             },
                             args: vec![(*arg_x).clone()],
                             params: vec![],
+                            window: None,
                         }],
                         None,
                     )
@@ -1467,6 +1511,7 @@ impl<'a> TypeChecker<'a> {
                             },
                             args: args.iter().copied().cloned().collect(),
                             params: vec![],
+                            window: None,
                         }],
                         None,
                     )
@@ -1548,6 +1593,7 @@ impl<'a> TypeChecker<'a> {
                         },
                         args: vec![(*arg).clone()],
                         params: vec![],
+                        window: None,
                     };
 
                     new_args.push(is_not_null_expr);
@@ -1703,6 +1749,200 @@ impl<'a> TypeChecker<'a> {
     }
 
     #[async_recursion::async_recursion]
+    /// Resolve a function call with an `OVER` clause, which is evaluated over the rows of the
+    /// partition of each row instead of the row alone.
+    async fn resolve_window_function(
+        &mut self,
+        span: &[Token<'_>],
+        display_name: String,
+        func_name: &str,
+        args: &[Expr<'_>],
+        params: &[Literal],
+        window: &WindowSpec<'_>,
+    ) -> Result<Box<(Scalar, DataTypeImpl)>> {
+        if self.in_aggregate_function {
+            // Reset the state
+            self.in_aggregate_function = false;
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "window function calls cannot be used in aggregate functions".to_string(),
+            )));
+        }
+        if self.in_window_function {
+            // Reset the state
+            self.in_window_function = false;
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "window function calls cannot be nested".to_string(),
+            )));
+        }
+
+        self.in_window_function = true;
+        let mut arguments = vec![];
+        for arg in args.iter() {
+            arguments.push(self.resolve(arg, None).await?);
+        }
+        let mut partition_by = vec![];
+        for expr in window.partition_by.iter() {
+            let box (scalar, _) = self.resolve(expr, None).await?;
+            partition_by.push(scalar);
+        }
+        let mut order_by = vec![];
+        for order in window.order_by.iter() {
+            let box (scalar, _) = self.resolve(&order.expr, None).await?;
+            order_by.push(WindowOrderBy {
+                expr: scalar,
+                asc: order.asc.unwrap_or(true),
+                nulls_first: order.nulls_first.unwrap_or(false),
+            });
+        }
+        self.in_window_function = false;
+
+        let name = func_name.to_lowercase();
+        let (func, args, return_type) = match name.as_str() {
+            "row_number" | "rank" | "dense_rank" => {
+                if !arguments.is_empty() {
+                    return Err(ErrorCode::SemanticError(span.display_error(format!(
+                        "window function {name} takes no arguments"
+                    ))));
+                }
+                let func = match name.as_str() {
+                    "row_number" => WindowFuncType::RowNumber,
+                    "rank" => WindowFuncType::Rank,
+                    _ => WindowFuncType::DenseRank,
+                };
+                (func, vec![], UInt64Type::new_impl())
+            }
+            "lag" | "lead" => {
+                if arguments.is_empty() || arguments.len() > 3 {
+                    return Err(ErrorCode::SemanticError(span.display_error(format!(
+                        "window function {name} takes 1 to 3 arguments, but got {}",
+                        arguments.len()
+                    ))));
+                }
+                let offset = match args.get(1) {
+                    None => 1,
+                    Some(Expr::Literal {
+                        lit: Literal::Integer(offset),
+                        ..
+                    }) => *offset,
+                    Some(arg) => {
+                        return Err(ErrorCode::SemanticError(arg.span().display_error(format!(
+                            "offset of {name} must be a non-negative integer literal"
+                        ))));
+                    }
+                };
+
+                let mut arguments = arguments.into_iter();
+                let box (arg, arg_type) = arguments.next().unwrap();
+                let return_type = wrap_nullable(&arg_type);
+                let mut func_args = vec![arg];
+                if let Some(box (default, _)) = arguments.nth(1) {
+                    func_args.push(wrap_cast_if_needed(default, &return_type));
+                }
+                let func = if name == "lag" {
+                    WindowFuncType::Lag { offset }
+                } else {
+                    WindowFuncType::Lead { offset }
+                };
+                (func, func_args, return_type)
+            }
+            _ if AggregateFunctionFactory::instance().check(&name) => {
+                let params = params
+                    .iter()
+                    .map(|literal| {
+                        self.resolve_literal(literal, None)
+                            .map(|box (value, _)| value)
+                    })
+                    .collect::<Result<Vec<DataValue>>>()?;
+                let data_fields = arguments
+                    .iter()
+                    .map(|box (_, data_type)| DataField::new("", data_type.clone()))
+                    .collect();
+                let agg_func = AggregateFunctionFactory::instance()
+                    .get(&name, params.clone(), data_fields)
+                    .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
+                let func = WindowFuncType::Aggregate {
+                    func_name: name.clone(),
+                    params,
+                };
+                let args = arguments.into_iter().map(|box (arg, _)| arg).collect();
+                (func, args, agg_func.return_type()?)
+            }
+            _ => {
+                return Err(ErrorCode::SemanticError(
+                    span.display_error(format!("unknown window function {func_name}")),
+                ));
+            }
+        };
+
+        let frame = match &window.window_frame {
+            Some(frame) => Self::resolve_window_frame(span, frame)?,
+            // The frame of a window with ordering ends at the last peer of the current row
+            None if !order_by.is_empty() => WindowFuncFrame {
+                units: WindowFuncFrameUnits::Range,
+                start_bound: WindowFuncFrameBound::Preceding(None),
+                end_bound: WindowFuncFrameBound::CurrentRow,
+            },
+            None => WindowFuncFrame {
+                units: WindowFuncFrameUnits::Rows,
+                start_bound: WindowFuncFrameBound::Preceding(None),
+                end_bound: WindowFuncFrameBound::Following(None),
+            },
+        };
+
+        Ok(Box::new((
+            WindowFunction {
+                display_name,
+                func,
+                args,
+                partition_by,
+                order_by,
+                frame,
+                return_type: Box::new(return_type.clone()),
+            }
+            .into(),
+            return_type,
+        )))
+    }
+
+    fn resolve_window_frame(span: &[Token<'_>], frame: &WindowFrame) -> Result<WindowFuncFrame> {
+        let resolve_bound = |bound: &WindowFrameBound| match bound {
+            WindowFrameBound::CurrentRow => WindowFuncFrameBound::CurrentRow,
+            WindowFrameBound::Preceding(offset) => WindowFuncFrameBound::Preceding(*offset),
+            WindowFrameBound::Following(offset) => WindowFuncFrameBound::Following(*offset),
+        };
+        let frame = WindowFuncFrame {
+            units: match frame.units {
+                WindowFrameUnits::Rows => WindowFuncFrameUnits::Rows,
+                WindowFrameUnits::Range => WindowFuncFrameUnits::Range,
+            },
+            start_bound: resolve_bound(&frame.start_bound),
+            end_bound: resolve_bound(&frame.end_bound),
+        };
+
+        let has_offset = |bound: &WindowFuncFrameBound| {
+            matches!(
+                bound,
+                WindowFuncFrameBound::Preceding(Some(_)) | WindowFuncFrameBound::Following(Some(_))
+            )
+        };
+        if frame.units == WindowFuncFrameUnits::Range
+            && (has_offset(&frame.start_bound) || has_offset(&frame.end_bound))
+        {
+            return Err(ErrorCode::UnImplement(span.display_error(
+                "RANGE frames with offsets are not supported yet, use ROWS instead".to_string(),
+            )));
+        }
+        if frame.start_bound == WindowFuncFrameBound::Following(None)
+            || frame.end_bound == WindowFuncFrameBound::Preceding(None)
+            || frame.start_bound.position() > frame.end_bound.position()
+        {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "invalid window frame {frame}, the frame starts after it ends"
+            ))));
+        }
+        Ok(frame)
+    }
+
     async fn resolve_udf(
         &mut self,
         span: &[Token<'_>],
@@ -1713,18 +1953,39 @@ impl<'a> TypeChecker<'a> {
             .get_udf(self.ctx.get_tenant().as_str(), func_name)
             .await;
         if let Ok(udf) = udf {
-            let parameters = udf.parameters;
-            if parameters.len() != arguments.len() {
-                return Err(ErrorCode::SyntaxException(span.display_error(format!(
-                    "Require {} parameters, but got: {}",
-                    parameters.len(),
-                    arguments.len()
+            if let Some(server) = &udf.server {
+                return self
+                    .resolve_udf_server(span, &udf.name, server, arguments)
+                    .await;
+            }
+            let (parameters, definition) = match udf.get_overload(arguments.len()) {
+                Some(overload) => overload,
+                None if udf.overloads.is_empty() => {
+                    return Err(ErrorCode::SyntaxException(span.display_error(format!(
+                        "Require {} parameters, but got: {}",
+                        udf.parameters.len(),
+                        arguments.len()
+                    ))));
+                }
+                None => {
+                    return Err(ErrorCode::SyntaxException(span.display_error(format!(
+                        "No overload of {func_name} takes {} parameters, candidates: {:?}",
+                        arguments.len(),
+                        udf.arities()
+                    ))));
+                }
+            };
+            if self.expanding_udfs.iter().any(|name| name == &udf.name) {
+                return Err(ErrorCode::SemanticError(span.display_error(format!(
+                    "Recursive definition of function {func_name} is not allowed"
                 ))));
             }
+            self.metadata.write().add_udf(udf.name.clone());
+
             let settings = self.ctx.get_settings();
             let sql_dialect = settings.get_sql_dialect()?;
             let backtrace = Backtrace::new();
-            let sql_tokens = tokenize_sql(udf.definition.as_str())?;
+            let sql_tokens = tokenize_sql(definition)?;
             let expr = parse_expr(&sql_tokens, sql_dialect, &backtrace)?;
             let mut args_map = HashMap::new();
             arguments.iter().enumerate().for_each(|(idx, argument)| {
@@ -1742,7 +2003,11 @@ impl<'a> TypeChecker<'a> {
                     Ok(None)
                 })
                 .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
-            self.resolve(&udf_expr, None).await
+
+            self.expanding_udfs.push(udf.name.clone());
+            let res = self.resolve(&udf_expr, None).await;
+            self.expanding_udfs.pop();
+            res
         } else {
             Err(ErrorCode::SemanticError(span.display_error(format!(
                 "No function matches the given name: {func_name}"
@@ -1750,6 +2015,51 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Resolve the call of a UDF evaluated by an external UDF server, the arguments are
+    /// casted to the types declared by the function.
+    async fn resolve_udf_server(
+        &mut self,
+        span: &[Token<'_>],
+        func_name: &str,
+        server: &UDFServer,
+        arguments: &[Expr<'_>],
+    ) -> Result<Box<(Scalar, DataTypeImpl)>> {
+        check_udf_server_allowed(&self.ctx.get_config(), &server.address)?;
+        if arguments.len() != server.arg_types.len() {
+            return Err(ErrorCode::SyntaxException(span.display_error(format!(
+                "Require {} parameters, but got: {}",
+                server.arg_types.len(),
+                arguments.len()
+            ))));
+        }
+        self.metadata.write().add_udf(func_name.to_string());
+
+        let mut args = Vec::with_capacity(arguments.len());
+        let mut arg_types = Vec::with_capacity(arguments.len());
+        for (argument, arg_type) in arguments.iter().zip(server.arg_types.iter()) {
+            let arg_type = TypeFactory::instance().get(arg_type)?;
+            let box (arg, _) = self.resolve(argument, None).await?;
+            args.push(wrap_cast_if_needed(arg, &arg_type));
+            arg_types.push(arg_type);
+        }
+        let return_type = TypeFactory::instance().get(&server.return_type)?;
+        let timeout = self.ctx.get_settings().get_udf_server_timeout()?;
+
+        Ok(Box::new((
+            UDFServerCall {
+                arguments: args,
+                func_name: func_name.to_string(),
+                server_addr: server.address.clone(),
+                handler: server.handler.clone(),
+                arg_types,
+                return_type: Box::new(return_type.clone()),
+                timeout,
+            }
+            .into(),
+            return_type,
+        )))
+    }
+
     #[async_recursion::async_recursion]
     async fn resolve_map_access_pushdown(
         &mut self,
@@ -1841,6 +2151,62 @@ impl<'a> TypeChecker<'a> {
         Ok(Box::new((scalar, data_type)))
     }
 
+    /// Resolves the path of a VARIANT column to the virtual column of the path, `None` if the
+    /// path is not a virtual column.
+    fn resolve_virtual_column(
+        &self,
+        accessores: &[MapAccessor<'a>],
+        database: &Option<Identifier<'a>>,
+        table: &Option<Identifier<'a>>,
+        column: &Identifier<'a>,
+    ) -> Result<Option<Box<(Scalar, DataTypeImpl)>>> {
+        let mut path = Vec::with_capacity(accessores.len());
+        // the accessores are pushed from the outermost one
+        for accessor in accessores.iter().rev() {
+            let element = match accessor {
+                MapAccessor::Bracket {
+                    key: Literal::Integer(index),
+                } => VirtualPathElement::Index(*index),
+                MapAccessor::Bracket {
+                    key: Literal::String(key),
+                } => VirtualPathElement::Key(key.clone()),
+                MapAccessor::Period { key } | MapAccessor::Colon { key } => {
+                    VirtualPathElement::Key(key.name.clone())
+                }
+                _ => return Ok(None),
+            };
+            path.push(element);
+        }
+        let virtual_column = VirtualColumnMeta {
+            source: normalize_identifier(column, self.name_resolution_ctx).name,
+            path,
+        };
+
+        let database = database
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, self.name_resolution_ctx).name);
+        let table = table
+            .as_ref()
+            .map(|ident| normalize_identifier(ident, self.name_resolution_ctx).name);
+        let result = self.bind_context.resolve_name(
+            database.as_deref(),
+            table.as_deref(),
+            &virtual_column.name(),
+            &column.span,
+            self.aliases,
+        );
+        match result {
+            Ok(NameResolutionResult::Column(column)) => {
+                let data_type = *column.data_type.clone();
+                Ok(Some(Box::new((
+                    BoundColumnRef { column }.into(),
+                    data_type,
+                ))))
+            }
+            _ => Ok(None),
+        }
+    }
+
     #[allow(clippy::only_used_in_recursion)]
     fn clone_expr_with_replacement<F>(
         &self,
@@ -2018,6 +2384,7 @@ impl<'a> TypeChecker<'a> {
                     name,
                     args,
                     params,
+                    window,
                 } => Ok(Expr::FunctionCall {
                     span,
                     distinct: *distinct,
@@ -2027,6 +2394,7 @@ impl<'a> TypeChecker<'a> {
                         .map(|arg| self.clone_expr_with_replacement(arg, replacement_fn))
                         .collect::<Result<Vec<Expr>>>()?,
                     params: params.clone(),
+                    window: window.clone(),
                 }),
                 Expr::Case {
                     span,
@@ -0,0 +1,124 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::binder::ColumnBinding;
+use crate::sql::binder::Visibility;
+use crate::sql::plans::AndExpr;
+use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::CastExpr;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::FunctionCall;
+use crate::sql::plans::OrExpr;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+use crate::sql::plans::UDFServerCall;
+use crate::sql::BindContext;
+
+/// Replace the window functions in a scalar expression with a BoundColumnRef
+/// to the column evaluated by the corresponding `Window` plan.
+pub struct WindowChecker<'a> {
+    bind_context: &'a BindContext,
+}
+
+impl<'a> WindowChecker<'a> {
+    pub fn new(bind_context: &'a BindContext) -> Self {
+        Self { bind_context }
+    }
+
+    pub fn resolve(&mut self, scalar: &Scalar) -> Result<Scalar> {
+        match scalar {
+            Scalar::BoundColumnRef(_)
+            | Scalar::ConstantExpr(_)
+            | Scalar::AggregateFunction(_)
+            | Scalar::SubqueryExpr(_) => Ok(scalar.clone()),
+            Scalar::AndExpr(scalar) => Ok(AndExpr {
+                left: Box::new(self.resolve(&scalar.left)?),
+                right: Box::new(self.resolve(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::OrExpr(scalar) => Ok(OrExpr {
+                left: Box::new(self.resolve(&scalar.left)?),
+                right: Box::new(self.resolve(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::ComparisonExpr(scalar) => Ok(ComparisonExpr {
+                op: scalar.op.clone(),
+                left: Box::new(self.resolve(&scalar.left)?),
+                right: Box::new(self.resolve(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::FunctionCall(func) => {
+                let args = func
+                    .arguments
+                    .iter()
+                    .map(|arg| self.resolve(arg))
+                    .collect::<Result<Vec<Scalar>>>()?;
+                Ok(FunctionCall {
+                    arguments: args,
+                    func_name: func.func_name.clone(),
+                    arg_types: func.arg_types.clone(),
+                    return_type: func.return_type.clone(),
+                }
+                .into())
+            }
+            Scalar::UDFServerCall(udf) => {
+                let args = udf
+                    .arguments
+                    .iter()
+                    .map(|arg| self.resolve(arg))
+                    .collect::<Result<Vec<Scalar>>>()?;
+                Ok(UDFServerCall {
+                    arguments: args,
+                    ..udf.clone()
+                }
+                .into())
+            }
+            Scalar::CastExpr(cast) => Ok(CastExpr {
+                argument: Box::new(self.resolve(&cast.argument)?),
+                from_type: cast.from_type.clone(),
+                target_type: cast.target_type.clone(),
+            }
+            .into()),
+
+            Scalar::WindowFunction(func) => {
+                let window_info = &self.bind_context.window_info;
+                if let Some(index) = window_info.window_functions_map.get(&func.display_name) {
+                    let window_func = &window_info.window_functions[*index];
+                    let column_binding = ColumnBinding {
+                        database_name: None,
+                        table_name: None,
+                        column_name: func.display_name.clone(),
+                        index: window_func.index,
+                        data_type: Box::new(window_func.scalar.data_type()),
+                        visibility: Visibility::Visible,
+                    };
+                    return Ok(BoundColumnRef {
+                        column: column_binding,
+                    }
+                    .into());
+                }
+                Err(ErrorCode::SemanticError(format!(
+                    "window function {} must appear in the select list",
+                    func.display_name
+                )))
+            }
+        }
+    }
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 pub mod cache;
+#[cfg(feature = "delta")]
+pub use common_storages_delta as delta;
 pub use common_storages_fuse as fuse;
 pub use common_storages_index as index;
 pub mod result;
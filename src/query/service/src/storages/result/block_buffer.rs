@@ -51,19 +51,30 @@ pub struct BlockBufferInner {
     push_stopped: bool,
     curr_rows: usize,
     max_rows: usize,
+    curr_bytes: usize,
+    max_bytes: usize,
     blocks: VecDeque<BlockDataOrInfo>,
     block_notify: Arc<Notify>,
+    space_notify: Arc<Notify>,
 }
 
 impl BlockBufferInner {
-    pub fn new(max_rows: usize, block_notify: Arc<Notify>) -> Self {
+    pub fn new(
+        max_rows: usize,
+        max_bytes: usize,
+        block_notify: Arc<Notify>,
+        space_notify: Arc<Notify>,
+    ) -> Self {
         BlockBufferInner {
             pop_stopped: false,
             push_stopped: false,
             curr_rows: 0,
+            curr_bytes: 0,
             blocks: Default::default(),
             max_rows,
+            max_bytes,
             block_notify,
+            space_notify,
         }
     }
 }
@@ -73,7 +84,10 @@ impl BlockBufferInner {
         if self.pop_stopped {
             return false;
         }
-        if self.curr_rows + block.num_rows() >= self.max_rows {
+        // the blocks out of the limits are read back from the result table when popped.
+        if self.curr_rows + block.num_rows() >= self.max_rows
+            || self.curr_bytes + block.memory_size() > self.max_bytes
+        {
             false
         } else {
             self.push_block(block);
@@ -81,8 +95,16 @@ impl BlockBufferInner {
         }
     }
 
+    // A block larger than the limits is taken once the buffer is empty.
+    fn has_room_for(&self, block: &DataBlock) -> bool {
+        self.blocks.is_empty()
+            || (self.curr_rows + block.num_rows() <= self.max_rows
+                && self.curr_bytes + block.memory_size() <= self.max_bytes)
+    }
+
     fn push_block(&mut self, block: DataBlock) {
         self.curr_rows += block.num_rows();
+        self.curr_bytes += block.memory_size();
         self.blocks.push_back(BlockDataOrInfo::Data(block));
         self.block_notify.notify_one();
     }
@@ -101,6 +123,8 @@ impl BlockBufferInner {
         let block = self.blocks.pop_front();
         if let Some(BlockDataOrInfo::Data(b)) = &block {
             self.curr_rows -= b.num_rows();
+            self.curr_bytes -= b.memory_size();
+            self.space_notify.notify_one();
         }
         let done = self.is_pop_done();
         (block, done)
@@ -112,24 +136,32 @@ impl BlockBufferInner {
 
     pub fn stop_pop(&mut self) {
         self.pop_stopped = true;
-        self.blocks.truncate(0)
+        self.blocks.truncate(0);
+        self.curr_rows = 0;
+        self.curr_bytes = 0;
+        self.space_notify.notify_one();
     }
 }
 
 pub struct BlockBuffer {
     buffer: Arc<Mutex<BlockBufferInner>>,
     pub block_notify: Arc<Notify>,
+    space_notify: Arc<Notify>,
 }
 
 impl BlockBuffer {
-    pub fn new(max_rows: usize) -> Arc<Self> {
+    pub fn new(max_rows: usize, max_bytes: usize) -> Arc<Self> {
         let block_notify = Arc::new(Notify::new());
+        let space_notify = Arc::new(Notify::new());
         let buffer = Arc::new(Mutex::new(BlockBufferInner::new(
             max_rows,
+            max_bytes,
             block_notify.clone(),
+            space_notify.clone(),
         )));
         Arc::new(BlockBuffer {
             block_notify,
+            space_notify,
             buffer,
         })
     }
@@ -170,10 +202,25 @@ impl BlockBuffer {
         guard.push_info(block_info)
     }
 
+    // Wait until the client has taken enough blocks for this one to fit in the limits, so that a
+    // slow client pauses the query instead of the results piling up in memory. The block is
+    // dropped if the results are not read anymore.
     async fn push(&self, block: DataBlock) -> Result<()> {
-        let mut guard = self.buffer.lock().await;
-        guard.push_block(block);
-        Ok(())
+        loop {
+            {
+                let mut guard = self.buffer.lock().await;
+                if guard.pop_stopped {
+                    return Ok(());
+                }
+
+                if guard.has_room_for(&block) {
+                    guard.push_block(block);
+                    return Ok(());
+                }
+            }
+
+            self.space_notify.notified().await;
+        }
     }
 
     pub(crate) async fn stop_push(&self) -> Result<()> {
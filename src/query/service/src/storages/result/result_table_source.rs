@@ -28,11 +28,12 @@ use crate::pipelines::processors::processor::ProcessorPtr;
 use crate::pipelines::processors::Processor;
 use crate::sessions::TableContext;
 use crate::storages::fuse::io::BlockReader;
+use crate::storages::fuse::io::ColumnsData;
 use crate::storages::result::result_table_source::State::Generated;
 
 enum State {
     ReadData(PartInfoPtr),
-    Deserialize(PartInfoPtr, Vec<(usize, Vec<u8>)>),
+    Deserialize(PartInfoPtr, ColumnsData),
     Generated(Option<PartInfoPtr>, DataBlock),
     Finish,
 }
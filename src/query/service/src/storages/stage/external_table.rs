@@ -0,0 +1,438 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_catalog::catalog::StorageDescription;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::default_column_cast;
+use common_fuse_meta::meta::ColumnStatistics;
+use common_fuse_meta::meta::StatisticsOfColumns;
+use common_legacy_planners::Extras;
+use common_legacy_planners::Partitions;
+use common_legacy_planners::ReadDataSourcePlan;
+use common_legacy_planners::Statistics;
+use common_meta_app::schema::TableInfo;
+use common_pipeline_sources::processors::sources::input_formats::InputContext;
+use common_pipeline_sources::processors::sources::input_formats::PartitionColumns;
+use common_storages_index::RangeFilter;
+use common_users::UserApiProvider;
+use futures::TryStreamExt;
+use opendal::Operator;
+use parking_lot::Mutex;
+
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::EmptySource;
+use crate::pipelines::Pipe;
+use crate::pipelines::Pipeline;
+use crate::sessions::TableContext;
+use crate::sql::parse_copy_file_format_options;
+use crate::storages::stage::stage_table::projected_columns;
+use crate::storages::stage::stage_table::unescape_partition_path;
+use crate::storages::stage::stage_table::DEFAULT_PARTITION_VALUE;
+use crate::storages::stage::StageTable;
+use crate::storages::StorageContext;
+use crate::storages::Table;
+
+pub const EXTERNAL_TABLE_ENGINE: &str = "EXTERNAL";
+
+/// Table option of the directory of the files in a stage, like `@my_stage/path/`.
+pub const OPT_KEY_LOCATION: &str = "location";
+/// Table option of the `FILE_FORMAT` options, as a json object.
+pub const OPT_KEY_FILE_FORMAT: &str = "file_format";
+/// Table option of the columns of the values in the paths, separated by commas.
+pub const OPT_KEY_PARTITION_COLUMNS: &str = "partition_columns";
+
+/// A read-only table over the files in a stage, created by `CREATE EXTERNAL TABLE`.
+///
+/// The files are listed each time the table is read, so the files added to the location are
+/// queried without reloading them. The partition columns are not stored in the files, their
+/// values are parsed from the paths of the files like `dt=2022-10-01/`, the other columns are
+/// matched with the columns of the files the same as `COPY INTO <table>`.
+pub struct ExternalTable {
+    table_info: TableInfo,
+    stage_name: String,
+    // starts with `/`
+    path: String,
+    file_format: BTreeMap<String, String>,
+    partition_columns: Vec<String>,
+    input_context: Mutex<Option<Arc<InputContext>>>,
+}
+
+impl ExternalTable {
+    pub fn try_create(_ctx: StorageContext, table_info: TableInfo) -> Result<Box<dyn Table>> {
+        let options = table_info.options();
+        let location = options.get(OPT_KEY_LOCATION).ok_or_else(|| {
+            ErrorCode::BadOption(format!(
+                "table option {} of external table {} is required",
+                OPT_KEY_LOCATION, table_info.desc
+            ))
+        })?;
+        let (stage_name, path) = match location.trim_start_matches('@').split_once('/') {
+            Some((stage_name, path)) => (stage_name.to_string(), format!("/{}", path)),
+            None => (
+                location.trim_start_matches('@').to_string(),
+                "/".to_string(),
+            ),
+        };
+
+        let file_format = match options.get(OPT_KEY_FILE_FORMAT) {
+            Some(file_format) => serde_json::from_str(file_format).map_err(|e| {
+                ErrorCode::BadOption(format!(
+                    "invalid table option {} of external table {}: {}",
+                    OPT_KEY_FILE_FORMAT, table_info.desc, e
+                ))
+            })?,
+            None => BTreeMap::new(),
+        };
+        let partition_columns = options
+            .get(OPT_KEY_PARTITION_COLUMNS)
+            .map(|columns| {
+                columns
+                    .split(',')
+                    .filter(|column| !column.is_empty())
+                    .map(|column| column.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Box::new(ExternalTable {
+            table_info,
+            stage_name,
+            path,
+            file_format,
+            partition_columns,
+            input_context: Default::default(),
+        }))
+    }
+
+    pub fn description() -> StorageDescription {
+        StorageDescription {
+            engine_name: EXTERNAL_TABLE_ENGINE.to_string(),
+            comment: "EXTERNAL Storage Engine".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The clauses of `CREATE EXTERNAL TABLE` following the columns, for `SHOW CREATE TABLE`.
+    pub fn external_options_sql(&self) -> String {
+        let mut sql = format!("LOCATION = @{}{}", self.stage_name, self.path);
+        if !self.file_format.is_empty() {
+            sql.push_str(" FILE_FORMAT = (");
+            for (k, v) in self.file_format.iter() {
+                sql.push_str(&format!(" {} = '{}'", k, v));
+            }
+            sql.push_str(" )");
+        }
+        if !self.partition_columns.is_empty() {
+            sql.push_str(&format!(
+                " PARTITION BY ({})",
+                self.partition_columns.join(", ")
+            ));
+        }
+        sql
+    }
+
+    fn get_input_context(&self) -> Option<Arc<InputContext>> {
+        let guard = self.input_context.lock();
+        guard.clone()
+    }
+
+    #[tracing::instrument(level = "info", skip(self, ctx))]
+    async fn do_read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        let mut stage = UserApiProvider::instance()
+            .get_stage(&ctx.get_tenant(), &self.stage_name)
+            .await?;
+        if !self.file_format.is_empty() {
+            stage.file_format_options = parse_copy_file_format_options(&self.file_format)?;
+        }
+        let dir = data_dir(&stage.get_prefix(), &self.path);
+        let operator = StageTable::get_op(&ctx, &stage).await?;
+        let files = list_data_files(&operator, &dir).await?;
+
+        let schema = self.table_info.schema();
+        let values = self.partition_values(&schema, &files)?;
+        let range_filter = match push_downs.as_ref().map(|extras| &extras.filters) {
+            Some(filters) if !filters.is_empty() => Some(RangeFilter::try_create(
+                ctx.clone(),
+                filters,
+                schema.clone(),
+            )?),
+            _ => None,
+        };
+
+        let files_total = files.len();
+        let mut read_bytes = 0;
+        let mut kept_files = vec![];
+        let mut partition_values = HashMap::new();
+        for (file, values) in files.into_iter().zip(values) {
+            if let Some(range_filter) = &range_filter {
+                if !self.partition_columns.is_empty()
+                    && !self.should_keep(range_filter, &schema, &values)
+                {
+                    continue;
+                }
+            }
+            read_bytes += file.size as usize;
+            partition_values.insert(file.path.clone(), values);
+            kept_files.push(file.path);
+        }
+        let statistics = Statistics::new_estimated(0, read_bytes, kept_files.len(), files_total);
+
+        let input_ctx = match kept_files.is_empty() {
+            true => None,
+            false => {
+                let data_schema = DataSchemaRefExt::create(
+                    schema
+                        .fields()
+                        .iter()
+                        .filter(|field| !self.partition_columns.contains(field.name()))
+                        .cloned()
+                        .collect(),
+                );
+                let mut input_ctx = InputContext::try_create_from_copy(
+                    operator,
+                    ctx.get_settings().clone(),
+                    ctx.get_format_settings()?,
+                    data_schema,
+                    stage,
+                    kept_files,
+                    ctx.get_scan_progress(),
+                )
+                .await?;
+                // the formats prune the parts of the files by their statistics, together with
+                // the values of the partition columns
+                input_ctx.range_filter = range_filter;
+                input_ctx.projection =
+                    projected_columns(&schema, &input_ctx.schema, push_downs.as_ref());
+                if !self.partition_columns.is_empty() {
+                    input_ctx.partition_columns = Some(PartitionColumns {
+                        schema,
+                        names: self.partition_columns.clone(),
+                        values: partition_values,
+                    });
+                }
+                Some(Arc::new(input_ctx))
+            }
+        };
+        let mut guard = self.input_context.lock();
+        *guard = input_ctx;
+        Ok((statistics, vec![]))
+    }
+
+    /// The values of the partition columns of each file, parsed from the path of the file
+    /// relative to the location, like `dt=2022-10-01/part-0000.parquet`.
+    fn partition_values(
+        &self,
+        schema: &DataSchemaRef,
+        files: &[DataFile],
+    ) -> Result<Vec<Vec<DataValue>>> {
+        let mut columns = Vec::with_capacity(self.partition_columns.len());
+        for name in &self.partition_columns {
+            let field = schema.field_with_name(name)?;
+            let values = files
+                .iter()
+                .map(|file| partition_value(&file.relative_path, name))
+                .collect::<Vec<_>>();
+            let column = Series::from_data(values.iter().map(|v| v.as_deref()).collect::<Vec<_>>());
+            let data_type = wrap_nullable(field.data_type());
+            let column = default_column_cast(&column, &data_type).map_err(|e| {
+                ErrorCode::BadBytes(format!(
+                    "invalid value of partition column {} of external table {}: {}",
+                    name, self.table_info.desc, e
+                ))
+            })?;
+            columns.push((field, column));
+        }
+
+        let mut values = Vec::with_capacity(files.len());
+        for (row, file) in files.iter().enumerate() {
+            let mut file_values = Vec::with_capacity(columns.len());
+            for (field, column) in &columns {
+                let value = column.get(row);
+                if value.is_null() && !field.is_nullable() {
+                    return Err(ErrorCode::BadBytes(format!(
+                        "partition column {} of file {} is NULL, but the column is not nullable",
+                        field.name(),
+                        file.path
+                    )));
+                }
+                file_values.push(value);
+            }
+            values.push(file_values);
+        }
+        Ok(values)
+    }
+
+    // the partition columns hold a single value in each file
+    fn should_keep(
+        &self,
+        range_filter: &RangeFilter,
+        schema: &DataSchemaRef,
+        values: &[DataValue],
+    ) -> bool {
+        let mut stats = StatisticsOfColumns::new();
+        for (name, value) in self.partition_columns.iter().zip(values) {
+            if let Ok(index) = schema.index_of(name) {
+                stats.insert(index as u32, ColumnStatistics {
+                    min: value.clone(),
+                    max: value.clone(),
+                    null_count: if value.is_null() { u64::MAX } else { 0 },
+                    in_memory_size: 0,
+                });
+            }
+        }
+
+        // the number of rows is unknown, make sure `is_not_null` never prunes the file
+        match range_filter.eval(&stats, u64::MAX) {
+            Ok(keep) => keep,
+            Err(e) => {
+                tracing::debug!("failed to prune files of external table, keep them. {}", e);
+                true
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for ExternalTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        self.do_read_partitions(ctx, push_downs).await
+    }
+
+    fn read2(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _plan: &ReadDataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        match self.get_input_context() {
+            Some(input_ctx) => input_ctx.format.exec_copy(input_ctx.clone(), pipeline),
+            None => {
+                // no file to read
+                let output = OutputPort::create();
+                pipeline.add_pipe(Pipe::SimplePipe {
+                    inputs_port: vec![],
+                    outputs_port: vec![output.clone()],
+                    processors: vec![EmptySource::create(output)?],
+                });
+                Ok(())
+            }
+        }
+    }
+
+    async fn truncate(&self, _ctx: Arc<dyn TableContext>, _: bool) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "truncate for external table {} is not implemented",
+            self.name()
+        )))
+    }
+}
+
+struct DataFile {
+    path: String,
+    // relative to the location of the table
+    relative_path: String,
+    size: u64,
+}
+
+/// The directory of the files of the table in the operator, ends with `/`.
+pub fn data_dir(prefix: &str, path: &str) -> String {
+    let dir = format!("{}{}", prefix, path.trim_start_matches('/'));
+    match dir.ends_with('/') {
+        true => dir,
+        false => format!("{}/", dir),
+    }
+}
+
+/// Lists the files under the directory, except the hidden ones like `_SUCCESS`.
+async fn list_data_files(operator: &Operator, dir: &str) -> Result<Vec<DataFile>> {
+    let mut files = vec![];
+    let mut objects = operator.batch().walk_top_down(dir)?;
+    while let Some(de) = objects.try_next().await? {
+        if de.mode().is_dir() {
+            continue;
+        }
+        let path = de.path().to_string();
+        let relative_path = path
+            .trim_start_matches('/')
+            .trim_start_matches(dir.trim_start_matches('/'))
+            .to_string();
+        if relative_path
+            .split('/')
+            .any(|segment| segment.starts_with('_') || segment.starts_with('.'))
+        {
+            continue;
+        }
+        let size = de.metadata().await?.content_length();
+        files.push(DataFile {
+            path,
+            relative_path,
+            size,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Infers the partition columns from the path of a file under the directory, the keys of the
+/// directories like `dt=2022-10-01/`.
+pub async fn infer_partition_columns(operator: &Operator, dir: &str) -> Result<Vec<String>> {
+    let files = list_data_files(operator, dir).await?;
+    let path = match files.first() {
+        Some(file) => &file.relative_path,
+        None => return Ok(vec![]),
+    };
+
+    let mut segments = path.split('/').collect::<Vec<_>>();
+    segments.pop();
+    Ok(segments
+        .iter()
+        .filter_map(|segment| segment.split_once('='))
+        .map(|(key, _)| unescape_partition_path(key))
+        .collect())
+}
+/// The value of the partition column `name` in the relative path of a file, `None` if missing
+/// or the default partition.
+fn partition_value(path: &str, name: &str) -> Option<String> {
+    let mut segments = path.split('/').collect::<Vec<_>>();
+    segments.pop();
+    segments
+        .iter()
+        .filter_map(|segment| segment.split_once('='))
+        .find(|(key, _)| unescape_partition_path(key) == name)
+        .map(|(_, value)| unescape_partition_path(value))
+        .filter(|value| !value.is_empty() && value != DEFAULT_PARTITION_VALUE)
+}
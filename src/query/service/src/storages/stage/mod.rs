@@ -12,6 +12,14 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod external_table;
 mod stage_table;
 
+pub use external_table::data_dir;
+pub use external_table::infer_partition_columns;
+pub use external_table::ExternalTable;
+pub use external_table::EXTERNAL_TABLE_ENGINE;
+pub use external_table::OPT_KEY_FILE_FORMAT;
+pub use external_table::OPT_KEY_LOCATION;
+pub use external_table::OPT_KEY_PARTITION_COLUMNS;
 pub use stage_table::StageTable;
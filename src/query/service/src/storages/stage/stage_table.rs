@@ -13,15 +13,23 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io::Write;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use bzip2::write::BzEncoder;
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_formats::output_format::OutputFormatType;
 use common_legacy_planners::Extras;
 use common_legacy_planners::Partitions;
+use common_legacy_planners::Projection;
 use common_legacy_planners::ReadDataSourcePlan;
 use common_legacy_planners::StageTableInfo;
 use common_legacy_planners::Statistics;
@@ -32,9 +40,15 @@ use common_pipeline_core::processors::port::InputPort;
 use common_pipeline_core::SinkPipeBuilder;
 use common_pipeline_sources::processors::sources::input_formats::InputContext;
 use common_storage::init_operator;
+use common_storages_index::RangeFilter;
+use flate2::write::DeflateEncoder;
+use flate2::write::GzEncoder;
+use flate2::write::ZlibEncoder;
+use opendal::io_util::CompressAlgorithm;
 use opendal::Operator;
 use parking_lot::Mutex;
 use tracing::info;
+use xz2::write::XzEncoder;
 
 use crate::pipelines::processors::ContextSink;
 use crate::pipelines::processors::TransformLimit;
@@ -42,6 +56,12 @@ use crate::pipelines::Pipeline;
 use crate::sessions::TableContext;
 use crate::storages::Table;
 
+/// Target size of each file unloaded, if `MAX_FILE_SIZE` is not specified.
+const DEFAULT_MAX_FILE_SIZE: usize = 64 * 1024 * 1024;
+
+/// The partition of NULL and empty values, the same as Hive.
+pub(crate) const DEFAULT_PARTITION_VALUE: &str = "__HIVE_DEFAULT_PARTITION__";
+
 pub struct StageTable {
     table_info: StageTableInfo,
     // This is no used but a placeholder.
@@ -76,6 +96,16 @@ impl StageTable {
             Ok(init_operator(&stage.stage_params.storage)?)
         }
     }
+
+    /// Write the rows skipped by `ON_ERROR = CONTINUE` to the error file in the stage.
+    pub async fn write_rejected_rows(&self, ctx: &Arc<dyn TableContext>) -> Result<()> {
+        if let Some(input_ctx) = self.get_input_context() {
+            if let Some(path) = input_ctx.write_rejected_rows(&ctx.get_id()).await? {
+                info!("rows failed to load are written to {}", path);
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -92,21 +122,34 @@ impl Table for StageTable {
     async fn read_partitions(
         &self,
         ctx: Arc<dyn TableContext>,
-        _push_downs: Option<Extras>,
+        push_downs: Option<Extras>,
     ) -> Result<(Statistics, Partitions)> {
         let operator = StageTable::get_op(&ctx, &self.table_info.stage_info).await?;
-        let input_ctx = Arc::new(
-            InputContext::try_create_from_copy(
-                operator,
-                ctx.get_settings().clone(),
-                ctx.get_format_settings()?,
-                self.table_info.schema.clone(),
-                self.table_info.stage_info.clone(),
-                self.table_info.files.clone(),
-                ctx.get_scan_progress(),
-            )
-            .await?,
+        let mut input_ctx = InputContext::try_create_from_copy(
+            operator,
+            ctx.get_settings().clone(),
+            ctx.get_format_settings()?,
+            self.table_info.schema.clone(),
+            self.table_info.stage_info.clone(),
+            self.table_info.files.clone(),
+            ctx.get_scan_progress(),
+        )
+        .await?;
+        if let Some(filters) = push_downs.as_ref().map(|extras| &extras.filters) {
+            if !filters.is_empty() {
+                input_ctx.range_filter = Some(RangeFilter::try_create(
+                    ctx.clone(),
+                    filters,
+                    self.table_info.schema.clone(),
+                )?);
+            }
+        }
+        input_ctx.projection = projected_columns(
+            &self.table_info.schema,
+            &self.table_info.schema,
+            push_downs.as_ref(),
         );
+        let input_ctx = Arc::new(input_ctx);
         info!("copy into {:?}", input_ctx);
         let mut guard = self.input_context.lock();
         *guard = Some(input_ctx);
@@ -157,27 +200,20 @@ impl Table for StageTable {
         operations: Vec<DataBlock>,
         _overwrite: bool,
     ) -> Result<()> {
-        let format_name = format!(
-            "{:?}",
-            self.table_info.stage_info.file_format_options.format
-        );
-        let path = format!(
-            "{}{}.{}",
-            self.table_info.path,
-            uuid::Uuid::new_v4(),
-            format_name.to_ascii_lowercase()
-        );
-        info!(
-            "try commit stage table {} to file {path}",
-            self.table_info.stage_info.stage_name
-        );
-
-        let op = StageTable::get_op(&ctx, &self.table_info.stage_info).await?;
-
+        let stage_info = &self.table_info.stage_info;
+        let format_name = format!("{:?}", stage_info.file_format_options.format);
         let fmt = OutputFormatType::from_str(format_name.as_str())?;
-        let mut format_settings = ctx.get_format_settings()?;
+        // no suffix to detect the compression by, `AUTO` is taken as `NONE`
+        let compression =
+            InputContext::get_compression_alg_copy(stage_info.file_format_options.compression, "")?;
+        if compression.is_some() && fmt == OutputFormatType::Parquet {
+            return Err(ErrorCode::BadArguments(
+                "COMPRESSION is not supported to unload parquet files, the columns are compressed",
+            ));
+        }
 
-        let format_options = &self.table_info.stage_info.file_format_options;
+        let mut format_settings = ctx.get_format_settings()?;
+        let format_options = &stage_info.file_format_options;
         {
             format_settings.skip_header = format_options.skip_header;
             if !format_options.field_delimiter.is_empty() {
@@ -190,26 +226,69 @@ impl Table for StageTable {
             }
         }
 
-        let mut output_format = fmt.create_format(self.table_info.schema(), format_settings);
+        let max_file_size = match stage_info.copy_options.max_file_size {
+            _ if stage_info.copy_options.single => usize::MAX,
+            0 => DEFAULT_MAX_FILE_SIZE,
+            size => size as usize,
+        };
+        let schema = self.table_info.schema();
+        let partition_by = &self.table_info.partition_by;
+        let (schema, partitions) = if partition_by.is_empty() {
+            (schema, BTreeMap::from([(String::new(), operations)]))
+        } else {
+            partition_blocks(&schema, partition_by, operations)?
+        };
 
-        let prefix = output_format.serialize_prefix()?;
-        let written_bytes: usize = operations.iter().map(|b| b.memory_size()).sum();
-        let mut bytes = Vec::with_capacity(written_bytes + prefix.len());
-        bytes.extend_from_slice(&prefix);
-        for block in operations {
-            let bs = output_format.serialize_block(&block)?;
-            bytes.extend_from_slice(bs.as_slice());
+        let mut extension = format_name.to_ascii_lowercase();
+        if let Some(compression) = &compression {
+            extension = format!("{}.{}", extension, compression.extension());
         }
+        let id = uuid::Uuid::new_v4();
+
+        let op = StageTable::get_op(&ctx, stage_info).await?;
+        for (dir, blocks) in partitions {
+            let files = split_into_files(blocks, max_file_size);
+            info!(
+                "try commit stage table {} to {} files in {}{dir}",
+                stage_info.stage_name,
+                files.len(),
+                self.table_info.path
+            );
 
-        let bs = output_format.finalize()?;
-        bytes.extend_from_slice(bs.as_slice());
+            for (index, blocks) in files.into_iter().enumerate() {
+                let mut output_format = fmt.create_format(schema.clone(), format_settings.clone());
+                let prefix = output_format.serialize_prefix()?;
+                let written_bytes: usize = blocks.iter().map(|b| b.memory_size()).sum();
+                let mut bytes = Vec::with_capacity(written_bytes + prefix.len());
+                bytes.extend_from_slice(&prefix);
+                for block in blocks {
+                    let bs = output_format.serialize_block(&block)?;
+                    bytes.extend_from_slice(bs.as_slice());
+                }
 
-        ctx.get_dal_context()
-            .get_metrics()
-            .inc_write_bytes(bytes.len());
+                let bs = output_format.finalize()?;
+                bytes.extend_from_slice(bs.as_slice());
 
-        let object = op.object(&path);
-        object.write(bytes.as_slice()).await?;
+                if let Some(compression) = &compression {
+                    bytes = compress(compression, &bytes)?;
+                }
+
+                ctx.get_dal_context()
+                    .get_metrics()
+                    .inc_write_bytes(bytes.len());
+
+                let path = if partition_by.is_empty() {
+                    format!("{}{id}_{index}.{extension}", self.table_info.path)
+                } else {
+                    format!(
+                        "{}{dir}part-{index:04}-{id}.{extension}",
+                        self.table_info.path
+                    )
+                };
+                let object = op.object(&path);
+                object.write(bytes.as_slice()).await?;
+            }
+        }
         Ok(())
     }
 
@@ -220,3 +299,215 @@ impl Table for StageTable {
         ))
     }
 }
+
+/// Splits the rows of the blocks by the values of the partition keys, which are the last columns
+/// of the blocks, into the Hive style directories like `dt=2022-10-01/`. Like Hive, the columns
+/// named as the keys are not written into the files. Returns the schema of the files too.
+fn partition_blocks(
+    schema: &DataSchemaRef,
+    keys: &[String],
+    blocks: Vec<DataBlock>,
+) -> Result<(DataSchemaRef, BTreeMap<String, Vec<DataBlock>>)> {
+    let num_columns = schema.num_fields() - keys.len();
+    let data_columns = (0..num_columns)
+        .filter(|i| !keys.contains(schema.field(*i).name()))
+        .collect::<Vec<_>>();
+    if data_columns.is_empty() {
+        return Err(ErrorCode::BadArguments(
+            "No columns to unload besides the PARTITION BY keys",
+        ));
+    }
+    let fields = data_columns.iter().map(|i| schema.field(*i).clone());
+    let data_schema = DataSchemaRefExt::create(fields.collect());
+
+    let mut partitions = BTreeMap::new();
+    for block in blocks {
+        let mut dirs = vec![];
+        let mut dir_indices = HashMap::new();
+        let mut indices = Vec::with_capacity(block.num_rows());
+        for row in 0..block.num_rows() {
+            let mut dir = String::new();
+            for (i, key) in keys.iter().enumerate() {
+                let value = match block.column(num_columns + i).get(row) {
+                    DataValue::String(v) if !v.is_empty() => {
+                        escape_partition_path(&String::from_utf8_lossy(&v))
+                    }
+                    _ => DEFAULT_PARTITION_VALUE.to_string(),
+                };
+                dir.push_str(&format!("{}={}/", escape_partition_path(key), value));
+            }
+            let index = match dir_indices.get(&dir) {
+                Some(index) => *index,
+                None => {
+                    dir_indices.insert(dir.clone(), dirs.len());
+                    dirs.push(dir);
+                    dirs.len() - 1
+                }
+            };
+            indices.push(index);
+        }
+
+        let columns = data_columns.iter().map(|i| block.column(*i).clone());
+        let data_block = DataBlock::create(data_schema.clone(), columns.collect());
+        let scattered = DataBlock::scatter_block(&data_block, &indices, dirs.len())?;
+        for (dir, block) in dirs.into_iter().zip(scattered) {
+            partitions.entry(dir).or_insert_with(Vec::new).push(block);
+        }
+    }
+    Ok((data_schema, partitions))
+}
+
+/// Escapes the characters of the partition keys and values not allowed in the paths, as Hive.
+fn escape_partition_path(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\u{01}'..='\u{1F}'
+            | '\u{7F}'
+            | '"'
+            | '#'
+            | '%'
+            | '\''
+            | '*'
+            | '/'
+            | ':'
+            | '='
+            | '?'
+            | '\\'
+            | '['
+            | ']'
+            | '^'
+            | '{' => escaped.push_str(&format!("%{:02X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Decodes the characters escaped by `escape_partition_path`, like `%3D`.
+pub(crate) fn unescape_partition_path(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes[i] == b'%' && i + 2 < bytes.len() {
+            true => std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            false => None,
+        };
+        match escaped {
+            Some(byte) => {
+                unescaped.push(byte);
+                i += 3;
+            }
+            None => {
+                unescaped.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+/// The columns of `schema` read by the query on the table of `table_schema`, for
+/// `InputContext::projection`.
+///
+/// Returns none if all of them are read, or none of them, since a block without any column
+/// holds no rows.
+pub(crate) fn projected_columns(
+    table_schema: &DataSchemaRef,
+    schema: &DataSchemaRef,
+    push_downs: Option<&Extras>,
+) -> Option<Vec<usize>> {
+    let indices = match push_downs.and_then(|extras| extras.projection.as_ref()) {
+        Some(Projection::Columns(indices)) => indices,
+        _ => return None,
+    };
+    let columns = indices
+        .iter()
+        .filter_map(|i| schema.index_of(table_schema.field(*i).name()).ok())
+        .collect::<Vec<_>>();
+    match columns.is_empty() || columns.len() == schema.num_fields() {
+        true => None,
+        false => Some(columns),
+    }
+}
+
+/// Groups the blocks into the files of about `max_file_size` bytes in memory, a block larger
+/// than that is split by rows. At least one file is returned, so that an empty result is
+/// unloaded into an empty file.
+fn split_into_files(blocks: Vec<DataBlock>, max_file_size: usize) -> Vec<Vec<DataBlock>> {
+    let mut files = vec![vec![]];
+    let mut file_size = 0;
+    for block in blocks {
+        let block_size = block.memory_size();
+        let parts = if block_size > max_file_size && block.num_rows() > 1 {
+            let ratio = max_file_size as f64 / block_size as f64;
+            let rows = ((block.num_rows() as f64 * ratio) as usize).max(1);
+            (0..block.num_rows())
+                .step_by(rows)
+                .map(|offset| {
+                    DataBlock::slice_block(&block, offset, rows.min(block.num_rows() - offset))
+                })
+                .collect()
+        } else {
+            vec![block]
+        };
+
+        for part in parts {
+            let part_size = part.memory_size();
+            if file_size > 0 && file_size + part_size > max_file_size {
+                files.push(vec![]);
+                file_size = 0;
+            }
+            file_size += part_size;
+            files.last_mut().unwrap().push(part);
+        }
+    }
+    files
+}
+
+fn compress(compression: &CompressAlgorithm, data: &[u8]) -> Result<Vec<u8>> {
+    let buf = Vec::with_capacity(data.len() / 4);
+    let compressed = match compression {
+        CompressAlgorithm::Gzip => {
+            let mut encoder = GzEncoder::new(buf, flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        CompressAlgorithm::Zlib => {
+            let mut encoder = ZlibEncoder::new(buf, flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        CompressAlgorithm::Deflate => {
+            let mut encoder = DeflateEncoder::new(buf, flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        CompressAlgorithm::Bz2 => {
+            let mut encoder = BzEncoder::new(buf, bzip2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        CompressAlgorithm::Xz => {
+            let mut encoder = XzEncoder::new(buf, 6);
+            encoder.write_all(data)?;
+            encoder.finish()?
+        }
+        CompressAlgorithm::Zstd => zstd::stream::encode_all(data, 0)?,
+        CompressAlgorithm::Brotli => {
+            let mut encoder = brotli::CompressorWriter::new(buf, 4096, 9, 22);
+            encoder.write_all(data)?;
+            encoder.into_inner()
+        }
+        other => {
+            return Err(ErrorCode::UnImplement(format!(
+                "compress type {:?} is unimplemented to unload",
+                other
+            )));
+        }
+    };
+    Ok(compressed)
+}
@@ -22,9 +22,13 @@ use common_meta_app::schema::TableInfo;
 use parking_lot::RwLock;
 
 use super::random::RandomTable;
+#[cfg(feature = "delta")]
+use crate::storages::delta::DeltaTable;
 use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::StreamTable;
 use crate::storages::memory::MemoryTable;
 use crate::storages::null::NullTable;
+use crate::storages::stage::ExternalTable;
 use crate::storages::view::ViewTable;
 use crate::storages::StorageContext;
 use crate::storages::Table;
@@ -98,12 +102,31 @@ impl StorageFactory {
             descriptor: Arc::new(ViewTable::description),
         });
 
+        // Register STREAM table engine
+        creators.insert("STREAM".to_string(), Storage {
+            creator: Arc::new(StreamTable::try_create),
+            descriptor: Arc::new(StreamTable::description),
+        });
+
         // Register RANDOM table engine
         creators.insert("RANDOM".to_string(), Storage {
             creator: Arc::new(RandomTable::try_create),
             descriptor: Arc::new(RandomTable::description),
         });
 
+        // Register DELTA table engine
+        #[cfg(feature = "delta")]
+        creators.insert("DELTA".to_string(), Storage {
+            creator: Arc::new(DeltaTable::try_create),
+            descriptor: Arc::new(DeltaTable::description),
+        });
+
+        // Register EXTERNAL table engine
+        creators.insert("EXTERNAL".to_string(), Storage {
+            creator: Arc::new(ExternalTable::try_create),
+            descriptor: Arc::new(ExternalTable::description),
+        });
+
         StorageFactory {
             storages: RwLock::new(creators),
         }
@@ -15,6 +15,7 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use common_base::base::ProgressValues;
 use common_datavalues::DataField;
 use common_datavalues::DataSchema;
 use common_exception::Result;
@@ -24,6 +25,7 @@ use common_legacy_planners::ReadDataSourcePlan;
 use common_legacy_planners::SourceInfo;
 
 use crate::sessions::QueryContext;
+use crate::storages::index::VirtualColumnMeta;
 use crate::storages::Table;
 
 #[async_trait::async_trait]
@@ -54,7 +56,13 @@ impl ToReadDataSourcePlan for dyn Table {
         catalog: String,
         push_downs: Option<Extras>,
     ) -> Result<ReadDataSourcePlan> {
-        let (statistics, parts) = self.read_partitions(ctx, push_downs.clone()).await?;
+        let (statistics, parts) = self
+            .read_partitions(ctx.clone(), push_downs.clone())
+            .await?;
+        ctx.get_total_scan_progress().incr(&ProgressValues {
+            rows: statistics.read_rows,
+            bytes: statistics.read_bytes,
+        });
 
         let table_info = self.get_table_info();
         let table_meta = &table_info.meta;
@@ -75,6 +83,12 @@ impl ToReadDataSourcePlan for dyn Table {
             },
             _ => None,
         };
+        let scan_fields = match &push_downs {
+            Some(push_downs) if !push_downs.virtual_columns.is_empty() => Some(
+                append_virtual_fields(&table_meta.schema, scan_fields, &push_downs.virtual_columns),
+            ),
+            _ => scan_fields,
+        };
 
         // TODO pass in catalog name
 
@@ -115,3 +129,19 @@ fn extract_scan_fields_from_projection(
         }
     }
 }
+
+/// The virtual columns are scanned after the columns of the schema.
+fn append_virtual_fields(
+    schema: &DataSchema,
+    scan_fields: Option<BTreeMap<usize, DataField>>,
+    virtual_columns: &[String],
+) -> BTreeMap<usize, DataField> {
+    let mut scan_fields =
+        scan_fields.unwrap_or_else(|| schema.fields().iter().cloned().enumerate().collect());
+    let next_key = scan_fields.keys().max().map_or(0, |key| key + 1);
+    for (i, name) in virtual_columns.iter().enumerate() {
+        let field = DataField::new(name, VirtualColumnMeta::data_type());
+        scan_fields.insert(next_key + i, field);
+    }
+    scan_fields
+}
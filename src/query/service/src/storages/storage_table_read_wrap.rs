@@ -47,7 +47,7 @@ impl<T: Table> TableStreamReadWrap for T {
 
         let settings = ctx.get_settings();
         pipeline.set_max_threads(settings.get_max_threads()? as usize);
-        let executor_settings = ExecutorSettings::try_create(&settings)?;
+        let executor_settings = ExecutorSettings::try_create(&ctx)?;
 
         let executor = PipelinePullingExecutor::try_create(pipeline, executor_settings)?;
         ctx.set_executor(Arc::downgrade(&executor.get_inner()));
@@ -67,7 +67,7 @@ impl TableStreamReadWrap for dyn Table {
 
         let settings = ctx.get_settings();
         pipeline.set_max_threads(settings.get_max_threads()? as usize);
-        let executor_settings = ExecutorSettings::try_create(&settings)?;
+        let executor_settings = ExecutorSettings::try_create(&ctx)?;
         let executor = PipelinePullingExecutor::try_create(pipeline, executor_settings)?;
         ctx.set_executor(Arc::downgrade(&executor.get_inner()));
         Ok(Box::pin(PullingExecutorStream::create(executor)?))
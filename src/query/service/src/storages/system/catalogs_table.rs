@@ -0,0 +1,73 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+
+use crate::catalogs::CatalogManager;
+use crate::catalogs::CatalogManagerHelper;
+use crate::sessions::TableContext;
+use crate::storages::system::SyncOneBlockSystemTable;
+use crate::storages::system::SyncSystemTable;
+use crate::storages::Table;
+
+/// The catalogs the tables can be addressed in, like `<catalog>.<database>.<table>`.
+pub struct CatalogsTable {
+    table_info: TableInfo,
+}
+
+impl SyncSystemTable for CatalogsTable {
+    const NAME: &'static str = "system.catalogs";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn get_full_data(&self, _ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let catalog_names = CatalogManager::instance().list_catalogs();
+
+        let mut names = MutableStringColumn::with_capacity(catalog_names.len());
+        for name in &catalog_names {
+            names.append_value(name.as_bytes());
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            names.finish().arc(),
+        ]))
+    }
+}
+
+impl CatalogsTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![DataField::new("name", Vu8::to_data_type())]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'catalogs'".to_string(),
+            name: "catalogs".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemCatalogs".to_string(),
+                ..Default::default()
+            },
+        };
+
+        SyncOneBlockSystemTable::create(CatalogsTable { table_info })
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_catalog::catalog::CATALOG_DEFAULT;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+use crate::storages::system::AsyncOneBlockSystemTable;
+use crate::storages::system::AsyncSystemTable;
+use crate::storages::Table;
+
+/// The aggregating, bloom and inverted indexes of all the FUSE tables.
+pub struct IndexesTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for IndexesTable {
+    const NAME: &'static str = "system.indexes";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+        let databases = catalog.list_databases(tenant.as_str()).await?;
+
+        let mut database_names: Vec<Vec<u8>> = vec![];
+        let mut table_names: Vec<Vec<u8>> = vec![];
+        let mut names: Vec<Vec<u8>> = vec![];
+        let mut types: Vec<Vec<u8>> = vec![];
+        let mut definitions: Vec<Vec<u8>> = vec![];
+        for database in databases {
+            for table in catalog
+                .list_tables(tenant.as_str(), database.name())
+                .await?
+            {
+                let fuse_table = match FuseTable::try_from_table(table.as_ref()) {
+                    Ok(fuse_table) => fuse_table,
+                    Err(_) => continue,
+                };
+
+                let mut indexes = vec![];
+                for index in fuse_table.aggregating_indexes()? {
+                    indexes.push((index.name, "AGGREGATING", index.query));
+                }
+                for index in fuse_table.bloom_indexes()? {
+                    indexes.push((index.name, "BLOOM", index.columns.join(", ")));
+                }
+                for index in fuse_table.inverted_indexes()? {
+                    let definition = format!(
+                        "{} TOKENIZER = '{}'",
+                        index.columns.join(", "),
+                        index.tokenizer
+                    );
+                    indexes.push((index.name, "INVERTED", definition));
+                }
+                for (name, index_type, definition) in indexes {
+                    database_names.push(database.name().as_bytes().to_vec());
+                    table_names.push(table.name().as_bytes().to_vec());
+                    names.push(name.into_bytes());
+                    types.push(index_type.as_bytes().to_vec());
+                    definitions.push(definition.into_bytes());
+                }
+            }
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(database_names),
+            Series::from_data(table_names),
+            Series::from_data(names),
+            Series::from_data(types),
+            Series::from_data(definitions),
+        ]))
+    }
+}
+
+impl IndexesTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("database", Vu8::to_data_type()),
+            DataField::new("table", Vu8::to_data_type()),
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("type", Vu8::to_data_type()),
+            // the query of an aggregating index, or the columns of a bloom or inverted index
+            DataField::new("definition", Vu8::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'indexes'".to_string(),
+            name: "indexes".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemIndexes".to_string(),
+                ..Default::default()
+            },
+        };
+
+        AsyncOneBlockSystemTable::create(IndexesTable { table_info })
+    }
+}
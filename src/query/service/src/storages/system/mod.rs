@@ -12,7 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod catalogs_table;
 mod clusters_table;
+mod indexes_table;
+mod table_snapshots_table;
 
+pub use catalogs_table::CatalogsTable;
 pub use clusters_table::ClustersTable;
 pub use common_storages_preludes::system::*;
+pub use indexes_table::IndexesTable;
+pub use table_snapshots_table::TableSnapshotsTable;
@@ -0,0 +1,107 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::catalog::CATALOG_DEFAULT;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use futures::TryStreamExt;
+
+use crate::sessions::TableContext;
+use crate::storages::fuse::table_functions::FuseSnapshot;
+use crate::storages::fuse::FuseTable;
+use crate::storages::system::AsyncOneBlockSystemTable;
+use crate::storages::system::AsyncSystemTable;
+use crate::storages::Table;
+
+/// The snapshots of all the FUSE tables, the columns are those of `fuse_snapshot` prefixed
+/// by the database and the table.
+pub struct TableSnapshotsTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for TableSnapshotsTable {
+    const NAME: &'static str = "system.table_snapshots";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+        let databases = catalog.list_databases(tenant.as_str()).await?;
+
+        let mut blocks = vec![];
+        for database in databases {
+            for table in catalog
+                .list_tables(tenant.as_str(), database.name())
+                .await?
+            {
+                if FuseTable::try_from_table(table.as_ref()).is_err() {
+                    continue;
+                }
+                let table_name = table.name().to_string();
+                let snapshots = FuseSnapshot::new(ctx.clone(), table)
+                    .get_history_stream_as_blocks(None)?
+                    .try_collect::<Vec<_>>()
+                    .await?;
+                for block in snapshots {
+                    let num_rows = block.num_rows();
+                    let mut columns = vec![
+                        Series::from_data(vec![database.name().as_bytes().to_vec(); num_rows]),
+                        Series::from_data(vec![table_name.as_bytes().to_vec(); num_rows]),
+                    ];
+                    columns.extend(block.columns().iter().cloned());
+                    blocks.push(DataBlock::create(self.table_info.schema(), columns));
+                }
+            }
+        }
+
+        if blocks.is_empty() {
+            return Ok(DataBlock::empty_with_schema(self.table_info.schema()));
+        }
+        DataBlock::concat_blocks(&blocks)
+    }
+}
+
+impl TableSnapshotsTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let mut fields = vec![
+            DataField::new("database", Vu8::to_data_type()),
+            DataField::new("table", Vu8::to_data_type()),
+        ];
+        fields.extend(FuseSnapshot::schema().fields().iter().cloned());
+        let schema = DataSchemaRefExt::create(fields);
+
+        let table_info = TableInfo {
+            desc: "'system'.'table_snapshots'".to_string(),
+            name: "table_snapshots".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemTableSnapshots".to_string(),
+                ..Default::default()
+            },
+        };
+
+        AsyncOneBlockSystemTable::create(TableSnapshotsTable { table_info })
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_base::base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::UserIdentity;
+use common_users::UserApiProvider;
+use databend_query::api::http::v1::drain::drain_handler;
+use databend_query::api::http::v1::drain::drain_status_handler;
+use databend_query::api::http::v1::drain::DrainState;
+use databend_query::api::http::v1::drain::DrainStatus;
+use databend_query::interpreters::InterpreterFactory;
+use databend_query::sessions::SessionType;
+use databend_query::sessions::TableContext;
+use databend_query::sql::Planner;
+use poem::get;
+use poem::http::Method;
+use poem::http::StatusCode;
+use poem::http::Uri;
+use poem::Endpoint;
+use poem::Request;
+use poem::Route;
+use pretty_assertions::assert_eq;
+
+use crate::tests::create_query_context_with_type;
+
+async fn call_drain(ep: &Route, method: Method, uri: &'static str) -> DrainStatus {
+    let response = ep
+        .call(
+            Request::builder()
+                .uri(Uri::from_static(uri))
+                .method(method)
+                .finish(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().into_vec().await.unwrap();
+    serde_json::from_str::<DrainStatus>(&String::from_utf8_lossy(&body)).unwrap()
+}
+
+#[tokio::test]
+async fn test_drain() -> Result<()> {
+    let (_guard, query_ctx) = create_query_context_with_type(SessionType::HTTPQuery).await?;
+    let ep = Route::new().at("/v1/drain", get(drain_status_handler).post(drain_handler));
+
+    let status = call_drain(&ep, Method::GET, "/v1/drain").await;
+    assert_eq!(status.state, DrainState::Running);
+    assert_eq!(status.drain_deadline, None);
+
+    let status = call_drain(&ep, Method::POST, "/v1/drain?timeout_secs=60").await;
+    assert_eq!(status.state, DrainState::Drained);
+    let started_at = status.drain_started_at.unwrap();
+    assert_eq!(status.drain_deadline, Some(started_at + 60));
+
+    // Draining again keeps the first deadline.
+    let status = call_drain(&ep, Method::POST, "/v1/drain?timeout_secs=1").await;
+    assert_eq!(status.drain_deadline, Some(started_at + 60));
+
+    let sql = "select * from numbers(1)";
+    let user = UserApiProvider::instance()
+        .get_user("test", UserIdentity::new("root", "localhost"))
+        .await?;
+    query_ctx.set_current_user(user);
+    let mut planner = Planner::new(query_ctx.clone());
+    let (plan, _, _) = planner.plan_sql(sql).await?;
+    query_ctx.attach_query_str(plan.to_string(), sql);
+    let interpreter = InterpreterFactory::get(query_ctx.clone(), &plan).await?;
+    match interpreter.execute(query_ctx.clone()).await {
+        Ok(_) => panic!("the query should be rejected while draining"),
+        Err(cause) => assert_eq!(cause.code(), ErrorCode::NodeDrainingCode()),
+    }
+
+    let status = call_drain(&ep, Method::GET, "/v1/drain").await;
+    assert_eq!(status.state, DrainState::Drained);
+    assert_eq!(status.running_queries_count, 0);
+
+    Ok(())
+}
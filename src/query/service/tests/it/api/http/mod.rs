@@ -14,5 +14,6 @@
 
 mod cluster;
 mod config;
+mod drain;
 mod logs;
 mod status;
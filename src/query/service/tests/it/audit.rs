@@ -0,0 +1,41 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use databend_query::audit::AuditEventClass;
+
+#[test]
+fn test_audit_event_class() -> Result<()> {
+    let cases = [
+        ("Insert", Some(AuditEventClass::Dml)),
+        ("Delete", Some(AuditEventClass::Dml)),
+        ("Copy", Some(AuditEventClass::Dml)),
+        ("CreateTable", Some(AuditEventClass::Ddl)),
+        ("DropUser", Some(AuditEventClass::Ddl)),
+        ("GrantPriv", Some(AuditEventClass::Ddl)),
+        ("SetColumnMaskingPolicy", Some(AuditEventClass::Ddl)),
+        ("Query", None),
+        ("ShowCreateTable", None),
+        ("SetVariable", None),
+    ];
+    for (kind, expected) in cases {
+        assert_eq!(AuditEventClass::of_query_kind(kind), expected, "{}", kind);
+    }
+
+    assert_eq!(AuditEventClass::try_from("DDL")?, AuditEventClass::Ddl);
+    assert_eq!(AuditEventClass::try_from("denied")?.as_str(), "denied");
+    assert!(AuditEventClass::try_from("select").is_err());
+
+    Ok(())
+}
@@ -16,9 +16,14 @@ use base64::encode_config;
 use base64::URL_SAFE_NO_PAD;
 use common_base::base::tokio;
 use common_exception::Result;
+use common_meta_types::NetworkPolicy;
 use common_meta_types::UserIdentity;
+use common_meta_types::UserInfo;
+use common_meta_types::UserOption;
 use common_users::CustomClaims;
 use common_users::EnsureUser;
+use common_users::OidcClient;
+use common_users::OidcConfig;
 use common_users::UserApiProvider;
 use databend_query::auth::Credential;
 use databend_query::sessions::TableContext;
@@ -294,3 +299,279 @@ async fn test_jwt_auth_mgr_with_management() -> Result<()> {
         Ok(())
     }
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_jwt_auth_mgr_with_user_claim() -> Result<()> {
+    let server = MockServer::start().await;
+    let mut key_pairs = vec![];
+    let mut jwks_urls = vec![];
+    for kid in ["test_kid1", "test_kid2"] {
+        let key_pair = RS256KeyPair::generate(2048)?.with_key_id(kid);
+        let rsa_components = key_pair.public_key().to_components();
+        let e = encode_config(rsa_components.e, URL_SAFE_NO_PAD);
+        let n = encode_config(rsa_components.n, URL_SAFE_NO_PAD);
+        let j = serde_json::json!({"keys": [ {"kty": "RSA", "kid": kid, "e": e, "n": n, } ] })
+            .to_string();
+
+        let json_path = format!("/{}/jwks.json", kid);
+        let template = ResponseTemplate::new(200).set_body_raw(j, "application/json");
+        Mock::given(method("GET"))
+            .and(path(json_path.as_str()))
+            .respond_with(template)
+            .mount(&server)
+            .await;
+        key_pairs.push(key_pair);
+        jwks_urls.push(format!("http://{}{}", server.address(), json_path));
+    }
+
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.jwt_key_file = jwks_urls[0].clone();
+    conf.query.jwt_key_files = vec![jwks_urls[1].clone()];
+    conf.query.jwt_user_claim = "email".to_string();
+    let (_guard, ctx) = crate::tests::create_query_context_with_config(conf, None).await?;
+    let auth_mgr = ctx.get_auth_manager();
+    let user_name = "test@example.com";
+
+    // without the user claim
+    {
+        let claims = Claims::create(Duration::from_hours(2)).with_subject(user_name.to_string());
+        let token = key_pairs[0].sign(claims)?;
+
+        let res = auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Jwt {
+                token,
+                hostname: None,
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(
+            "Code: 1051, displayText = missing field `email` in jwt.",
+            res.err().unwrap().to_string()
+        );
+    }
+
+    // signed by the key of the second endpoint
+    {
+        let custom_claims = CustomClaims::new()
+            .with_claim("email", user_name)
+            .with_ensure_user(EnsureUser::default());
+        let claims = Claims::with_custom_claims(custom_claims, Duration::from_hours(2))
+            .with_subject("test".to_string());
+        let token = key_pairs[1].sign(claims)?;
+
+        auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Jwt {
+                token,
+                hostname: None,
+            })
+            .await?;
+        let user_info = ctx.get_current_user()?;
+        assert_eq!(user_info.name, user_name);
+    }
+
+    // the token as the password of the user identified by jwt
+    {
+        let custom_claims = CustomClaims::new().with_claim("email", user_name);
+        let claims = Claims::with_custom_claims(custom_claims, Duration::from_hours(2));
+        let token = key_pairs[0].sign(claims)?;
+
+        auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Password {
+                name: user_name.to_string(),
+                password: Some(token.into_bytes()),
+                hostname: None,
+            })
+            .await?;
+        let user_info = ctx.get_current_user()?;
+        assert_eq!(user_info.name, user_name);
+    }
+
+    // the token of another user as the password
+    {
+        let custom_claims = CustomClaims::new().with_claim("email", "other@example.com");
+        let claims = Claims::with_custom_claims(custom_claims, Duration::from_hours(2));
+        let token = key_pairs[0].sign(claims)?;
+
+        let res = auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Password {
+                name: user_name.to_string(),
+                password: Some(token.into_bytes()),
+                hostname: None,
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(
+            "Code: 1051, displayText = jwt of another user.",
+            res.err().unwrap().to_string()
+        );
+    }
+
+    // not signed by the keys of the endpoints
+    {
+        let key_pair = RS256KeyPair::generate(2048)?.with_key_id("test_kid3");
+        let custom_claims = CustomClaims::new().with_claim("email", user_name);
+        let claims = Claims::with_custom_claims(custom_claims, Duration::from_hours(2));
+        let token = key_pair.sign(claims)?;
+
+        let res = auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Jwt {
+                token,
+                hostname: None,
+            })
+            .await;
+        assert!(res.is_err());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_auth_mgr_with_network_policy() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let auth_mgr = ctx.get_auth_manager();
+    let tenant = ctx.get_tenant();
+    let user_name = "np_user";
+
+    let policy = NetworkPolicy {
+        name: "np1".to_string(),
+        allowed_ip_list: vec!["192.168.1.0/24".to_string()],
+        blocked_ip_list: vec!["192.168.1.99".to_string()],
+        comment: "".to_string(),
+    };
+    UserApiProvider::instance()
+        .add_network_policy(&tenant, policy, false)
+        .await?;
+
+    let mut user_info = UserInfo::new_no_auth(user_name, "%");
+    user_info.option = UserOption::default().with_network_policy(Some("np1".to_string()));
+    UserApiProvider::instance()
+        .add_user(&tenant, user_info, false)
+        .await?;
+
+    // from an allowed address
+    {
+        auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Password {
+                name: user_name.to_string(),
+                password: None,
+                hostname: Some("192.168.1.10".to_string()),
+            })
+            .await?;
+        let user_info = ctx.get_current_user()?;
+        assert_eq!(user_info.name, user_name);
+    }
+
+    // from a blocked address or an address out of the allowed list
+    for hostname in ["192.168.1.99", "10.0.0.1"] {
+        let res = auth_mgr
+            .auth(ctx.get_current_session(), &Credential::Password {
+                name: user_name.to_string(),
+                password: None,
+                hostname: Some(hostname.to_string()),
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(res.err().unwrap().code(), 2975);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_oidc_client() -> Result<()> {
+    let kid = "test_kid";
+    let key_pair = RS256KeyPair::generate(2048)?.with_key_id(kid);
+    let rsa_components = key_pair.public_key().to_components();
+    let e = encode_config(rsa_components.e, URL_SAFE_NO_PAD);
+    let n = encode_config(rsa_components.n, URL_SAFE_NO_PAD);
+    let jwks =
+        serde_json::json!({"keys": [ {"kty": "RSA", "kid": kid, "e": e, "n": n, } ] }).to_string();
+
+    let server = MockServer::start().await;
+    let issuer = format!("http://{}", server.address());
+    let metadata = serde_json::json!({
+        "issuer": issuer,
+        "authorization_endpoint": format!("{}/authorize", issuer),
+        "token_endpoint": format!("{}/token", issuer),
+        "jwks_uri": format!("{}/jwks.json", issuer),
+    })
+    .to_string();
+    let discovery_path = "/.well-known/openid-configuration";
+    for (json_path, body) in [(discovery_path, metadata), ("/jwks.json", jwks)] {
+        let template = ResponseTemplate::new(200).set_body_raw(body, "application/json");
+        Mock::given(method("GET"))
+            .and(path(json_path))
+            .respond_with(template)
+            .mount(&server)
+            .await;
+    }
+
+    let oidc = OidcClient::try_create(OidcConfig {
+        issuer: issuer.clone(),
+        client_id: "databend".to_string(),
+        client_secret: "secret".to_string(),
+        redirect_url: "http://127.0.0.1:8000/v1/oauth/callback".to_string(),
+        scopes: "openid".to_string(),
+        user_claim: "email".to_string(),
+        role_claim: "groups".to_string(),
+        role_mapping: "admins:admin;analysts:analyst".to_string(),
+        ..Default::default()
+    })?
+    .unwrap();
+    let sign = |audience: &str| {
+        let mut custom_claims = CustomClaims::new().with_claim("email", "test@example.com");
+        custom_claims.others.insert(
+            "groups".to_string(),
+            serde_json::json!(["admins", "others"]),
+        );
+        let claims = Claims::with_custom_claims(custom_claims, Duration::from_hours(2))
+            .with_subject("test".to_string())
+            .with_issuer(&issuer)
+            .with_audience(audience);
+        key_pair.sign(claims)
+    };
+
+    // the ID token of the client
+    {
+        let token = sign("databend")?;
+        let parsed = oidc.verify(&token).await?;
+        assert_eq!(oidc.user_name(parsed.claims())?, "test@example.com");
+        assert_eq!(oidc.roles(parsed.claims()), vec!["admin".to_string()]);
+    }
+
+    // issued for another client
+    {
+        let token = sign("another")?;
+        assert!(oidc.verify(&token).await.is_err());
+    }
+
+    // the authorization code flow
+    {
+        let id_token = sign("databend")?;
+        let body = serde_json::json!({
+            "access_token": "opaque",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+            "id_token": id_token,
+        })
+        .to_string();
+        let template = ResponseTemplate::new(200).set_body_raw(body, "application/json");
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(template)
+            .mount(&server)
+            .await;
+
+        let url = oidc.authorize_url().await?;
+        let prefix = format!("{}/authorize?response_type=code&client_id=databend", issuer);
+        assert!(url.starts_with(&prefix));
+        let state = url.split("state=").nth(1).unwrap();
+        let token = oidc.exchange_code("code", state).await?;
+        assert_eq!(token.id_token, Some(id_token));
+
+        // the state is signed by the client secret
+        assert!(oidc.exchange_code("code", "0000.0.0000").await.is_err());
+    }
+
+    Ok(())
+}
@@ -14,3 +14,4 @@
 
 mod database_catalog;
 mod immutable_catalogs;
+mod runtime_filter;
@@ -0,0 +1,68 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_catalog::runtime_filter::RuntimeFilter;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+#[test]
+fn test_runtime_filter_range() -> Result<()> {
+    let keys = Series::from_data(vec![1i64, 3, 5, 7]);
+    let filter = RuntimeFilter::try_create("a".to_string(), &[&keys])?;
+    assert_eq!(filter.column_name(), "a");
+
+    assert!(filter.may_match_range(&DataValue::Int64(0), &DataValue::Int64(1)));
+    assert!(filter.may_match_range(&DataValue::Int64(4), &DataValue::Int64(4)));
+    assert!(filter.may_match_range(&DataValue::Null, &DataValue::Null));
+    assert!(!filter.may_match_range(&DataValue::Int64(-3), &DataValue::Int64(0)));
+    assert!(!filter.may_match_range(&DataValue::Int64(8), &DataValue::Int64(10)));
+
+    // no key matches anything
+    let keys = Series::from_data(Vec::<i64>::new());
+    let filter = RuntimeFilter::try_create("a".to_string(), &[&keys])?;
+    assert!(!filter.may_match_range(&DataValue::Int64(0), &DataValue::Int64(10)));
+    Ok(())
+}
+
+#[test]
+fn test_runtime_filter_rows() -> Result<()> {
+    let chunk_1 = Series::from_data(vec![1i64, 3]);
+    let chunk_2 = Series::from_data(vec![Some(5i64), None]);
+    let filter = RuntimeFilter::try_create("a".to_string(), &[&chunk_1, &chunk_2])?;
+
+    // the keys always pass, and the nulls never
+    let column = Series::from_data(vec![Some(1i64), Some(3), Some(5), None]);
+    let predicate = filter.filter(&column).unwrap();
+    assert_eq!(predicate.to_values(), vec![
+        DataValue::Boolean(true),
+        DataValue::Boolean(true),
+        DataValue::Boolean(true),
+        DataValue::Boolean(false),
+    ]);
+    Ok(())
+}
+
+#[test]
+fn test_runtime_filter_adaptive_disabling() -> Result<()> {
+    let keys = Series::from_data((0..1000i64).collect::<Vec<_>>());
+    let filter = RuntimeFilter::try_create("a".to_string(), &[&keys])?;
+
+    // all the rows pass, the filter is disabled once it has checked 65536 rows
+    let column = Series::from_data((0..1000i64).collect::<Vec<_>>());
+    for _ in 0..66 {
+        assert!(filter.filter(&column).is_some());
+    }
+    assert!(filter.filter(&column).is_none());
+    Ok(())
+}
@@ -14,10 +14,18 @@
 
 use common_base::base::tokio;
 use common_exception::Result;
+use common_meta_types::UserIdentity;
+use common_users::UserApiProvider;
 use databend_query::clusters::ClusterDiscovery;
 use databend_query::clusters::ClusterHelper;
+use databend_query::interpreters::InterpreterFactory;
+use databend_query::sessions::SessionType;
+use databend_query::sessions::TableContext;
+use databend_query::sql::Planner;
 use pretty_assertions::assert_eq;
+use tokio_stream::StreamExt;
 
+use crate::tests::create_query_context_with_type;
 use crate::tests::ConfigBuilder;
 use crate::tests::TestGlobalServices;
 
@@ -35,6 +43,29 @@ async fn test_single_cluster_discovery() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_local_load() -> Result<()> {
+    let (_guard, query_ctx) = create_query_context_with_type(SessionType::HTTPQuery).await?;
+    assert_eq!(ClusterDiscovery::instance().local_load(), 0);
+
+    let sql = "select sleep(1) from numbers(1)";
+    let user = UserApiProvider::instance()
+        .get_user("test", UserIdentity::new("root", "localhost"))
+        .await?;
+    query_ctx.set_current_user(user);
+    let mut planner = Planner::new(query_ctx.clone());
+    let (plan, _, _) = planner.plan_sql(sql).await?;
+    query_ctx.attach_query_str(plan.to_string(), sql);
+    let interpreter = InterpreterFactory::get(query_ctx.clone(), &plan).await?;
+
+    let mut stream = interpreter.execute(query_ctx.clone()).await?;
+    assert_eq!(ClusterDiscovery::instance().local_load(), 1);
+
+    while (stream.next().await).is_some() {}
+    assert_eq!(ClusterDiscovery::instance().local_load(), 0);
+    Ok(())
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn test_remove_invalid_nodes() -> Result<()> {
     let config_1 = ConfigBuilder::create()
@@ -16,6 +16,10 @@ use std::env::temp_dir;
 use std::fs;
 use std::io::Write;
 
+use common_config::CatalogConfig;
+use common_config::CatalogHiveConfig;
+use common_config::CatalogIcebergConfig;
+use common_config::ThriftProtocol;
 use common_exception::Result;
 use databend_query::Config;
 use pretty_assertions::assert_eq;
@@ -39,6 +43,8 @@ clickhouse_handler_host = "127.0.0.1"
 clickhouse_handler_port = 9000
 clickhouse_http_handler_host = "127.0.0.1"
 clickhouse_http_handler_port = 8124
+postgres_handler_host = "127.0.0.1"
+postgres_handler_port = 5433
 http_handler_host = "127.0.0.1"
 http_handler_port = 8000
 http_handler_result_timeout_millis = 10000
@@ -48,6 +54,14 @@ metric_api_address = "127.0.0.1:7070"
 http_handler_tls_server_cert = ""
 http_handler_tls_server_key = ""
 http_handler_tls_server_root_ca_cert = ""
+postgres_handler_tls_server_cert = ""
+postgres_handler_tls_server_key = ""
+mysql_handler_tls_server_cert = ""
+mysql_handler_tls_server_key = ""
+mysql_handler_tls_server_root_ca_cert = ""
+clickhouse_handler_tls_server_cert = ""
+clickhouse_handler_tls_server_key = ""
+clickhouse_handler_tls_server_root_ca_cert = ""
 api_tls_server_cert = ""
 api_tls_server_key = ""
 api_tls_server_root_ca_cert = ""
@@ -66,11 +80,53 @@ table_cache_block_meta_count = 102400
 table_memory_cache_mb_size = 256
 table_disk_cache_root = "_cache"
 table_disk_cache_mb_size = 1024
+table_data_block_memory_cache_mb_size = 256
+table_data_block_disk_cache_enabled = false
+table_query_result_cache_mb_size = 256
 management_mode = false
 jwt_key_file = ""
+jwt_key_files = []
+jwt_user_claim = "sub"
 async_insert_max_data_size = 10000
 async_insert_busy_timeout = 200
 async_insert_stale_timeout = 0
+max_running_queries = 0
+max_running_queries_per_user = 0
+max_queued_queries = 0
+query_queue_timeout = 0
+enable_udf_server = false
+udf_server_allow_list = []
+enable_auto_compaction = false
+auto_compaction_interval = 60
+auto_compaction_threshold = 100
+auto_compaction_off_peak_hours = ""
+task_scheduler_interval = 10
+enable_persistent_query_log = false
+query_log_flush_interval = 10
+query_log_retention_days = 30
+slow_query_threshold_ms = 0
+audit_log_events = "login"
+audit_log_sinks = "file"
+audit_log_webhook_url = ""
+ldap_url = ""
+ldap_starttls = false
+ldap_bind_dn = ""
+ldap_search_base_dn = ""
+ldap_search_filter = "(uid={user})"
+ldap_search_bind_dn = ""
+ldap_search_bind_password = ""
+ldap_group_attribute = "memberOf"
+ldap_role_mapping = ""
+oidc_issuer = ""
+oidc_client_id = ""
+oidc_client_secret = ""
+oidc_redirect_url = ""
+oidc_scopes = "openid email profile"
+oidc_audience = ""
+oidc_user_claim = "email"
+oidc_role_claim = "groups"
+oidc_role_mapping = ""
+cluster_heartbeat_ttl = 60
 users = []
 
 [log]
@@ -144,6 +200,9 @@ root = ""
 [catalog]
 meta_store_address = "127.0.0.1:9083"
 protocol = "binary"
+iceberg_warehouse = ""
+
+[catalogs]
 "#;
 
     let tom_actual = toml::to_string(&actual.into_outer()).unwrap();
@@ -452,6 +511,8 @@ clickhouse_handler_host = "127.0.0.1"
 clickhouse_handler_port = 9000
 clickhouse_http_handler_host = "127.0.0.1"
 clickhouse_http_handler_port = 8124
+postgres_handler_host = "127.0.0.1"
+postgres_handler_port = 5433
 http_handler_host = "127.0.0.1"
 http_handler_port = 8000
 http_handler_result_timeout_millis = 10000
@@ -461,6 +522,14 @@ metric_api_address = "127.0.0.1:7070"
 http_handler_tls_server_cert = ""
 http_handler_tls_server_key = ""
 http_handler_tls_server_root_ca_cert = ""
+postgres_handler_tls_server_cert = ""
+postgres_handler_tls_server_key = ""
+mysql_handler_tls_server_cert = ""
+mysql_handler_tls_server_key = ""
+mysql_handler_tls_server_root_ca_cert = ""
+clickhouse_handler_tls_server_cert = ""
+clickhouse_handler_tls_server_key = ""
+clickhouse_handler_tls_server_root_ca_cert = ""
 api_tls_server_cert = ""
 api_tls_server_key = ""
 api_tls_server_root_ca_cert = ""
@@ -479,11 +548,53 @@ table_cache_block_meta_count = 102400
 table_memory_cache_mb_size = 256
 table_disk_cache_root = "_cache"
 table_disk_cache_mb_size = 1024
+table_data_block_memory_cache_mb_size = 256
+table_data_block_disk_cache_enabled = false
+table_query_result_cache_mb_size = 256
 management_mode = false
 jwt_key_file = ""
+jwt_key_files = []
+jwt_user_claim = "sub"
 async_insert_max_data_size = 10000
 async_insert_busy_timeout = 200
 async_insert_stale_timeout = 0
+max_running_queries = 0
+max_running_queries_per_user = 0
+max_queued_queries = 0
+query_queue_timeout = 0
+enable_udf_server = false
+udf_server_allow_list = []
+enable_auto_compaction = false
+auto_compaction_interval = 60
+auto_compaction_threshold = 100
+auto_compaction_off_peak_hours = ""
+task_scheduler_interval = 10
+enable_persistent_query_log = false
+query_log_flush_interval = 10
+query_log_retention_days = 30
+slow_query_threshold_ms = 0
+audit_log_events = "login"
+audit_log_sinks = "file"
+audit_log_webhook_url = ""
+ldap_url = ""
+ldap_starttls = false
+ldap_bind_dn = ""
+ldap_search_base_dn = ""
+ldap_search_filter = "(uid={user})"
+ldap_search_bind_dn = ""
+ldap_search_bind_password = ""
+ldap_group_attribute = "memberOf"
+ldap_role_mapping = ""
+oidc_issuer = ""
+oidc_client_id = ""
+oidc_client_secret = ""
+oidc_redirect_url = ""
+oidc_scopes = "openid email profile"
+oidc_audience = ""
+oidc_user_claim = "email"
+oidc_role_claim = "groups"
+oidc_role_mapping = ""
+cluster_heartbeat_ttl = 60
 users = []
 
 [log]
@@ -566,3 +677,53 @@ protocol = "binary"
 
     Ok(())
 }
+
+#[test]
+fn test_catalogs_config() -> Result<()> {
+    let file_path = temp_dir().join("databend_test_catalogs_config.toml");
+
+    let mut f = fs::File::create(&file_path)?;
+    f.write_all(
+        r#"config_file = ""
+
+[catalogs.hive_prod]
+type = "hive"
+address = "127.0.0.1:9083"
+
+[catalogs.lake]
+type = "iceberg"
+warehouse = "iceberg/warehouse"
+"#
+        .as_bytes(),
+    )?;
+
+    // Make sure all data flushed.
+    f.flush()?;
+
+    temp_env::with_vars(
+        vec![("CONFIG_FILE", Some(file_path.to_string_lossy().as_ref()))],
+        || {
+            let cfg = Config::load().expect("config load success");
+
+            assert_eq!(cfg.catalogs.len(), 2);
+            assert_eq!(
+                cfg.catalogs.get("hive_prod"),
+                Some(&CatalogConfig::Hive(CatalogHiveConfig {
+                    address: "127.0.0.1:9083".to_string(),
+                    protocol: ThriftProtocol::Binary,
+                }))
+            );
+            assert_eq!(
+                cfg.catalogs.get("lake"),
+                Some(&CatalogConfig::Iceberg(CatalogIcebergConfig {
+                    warehouse: "iceberg/warehouse".to_string(),
+                }))
+            );
+        },
+    );
+
+    // remove temp file
+    fs::remove_file(file_path)?;
+
+    Ok(())
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use common_base::base::tokio;
+use common_datablocks::pretty_format_blocks;
 use common_exception::Result;
 use databend_query::interpreters::*;
 use databend_query::sql::Planner;
@@ -58,3 +59,27 @@ async fn test_explain_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_analyze_interpreter() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+
+    let query = "EXPLAIN ANALYZE SELECT number FROM numbers(10) WHERE number % 3 = 0";
+
+    let (plan, _, _) = planner.plan_sql(query).await?;
+    let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+    assert_eq!(executor.name(), "ExplainInterpreterV2");
+
+    let stream = executor.execute(ctx).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let output = pretty_format_blocks(result.as_slice())?;
+
+    // The times vary from run to run, only the rows are checked.
+    assert!(output.contains("NumbersSourceTransform × 1 processor [rows: 10, "));
+    assert!(output.contains("Filter × 1 processor [rows: 4, "));
+    assert!(output.contains("wall time: "));
+    assert!(output.contains("cpu time: "));
+
+    Ok(())
+}
@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
 use common_base::base::tokio;
 use common_exception::Result;
 use databend_query::interpreters::*;
+use databend_query::sessions::TableContext;
 use databend_query::sql::*;
 use futures::TryStreamExt;
 
@@ -121,3 +124,61 @@ async fn test_insert_into_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_async_insert_interpreter() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+
+    {
+        let query = "create table default.async_table(a Int32, b String) Engine = Memory";
+        let (plan, _, _) = planner.plan_sql(query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+        let _ = executor.execute(ctx.clone()).await?;
+    }
+
+    let settings = ctx.get_settings();
+    settings.set_enable_async_insert(1)?;
+
+    // The inserts return once queued, and are flushed together after the busy timeout.
+    settings.set_wait_for_async_insert(0)?;
+    for query in [
+        "insert into default.async_table values(1, 'a')",
+        "insert into default.async_table values(2, 'b'), (3, 'c')",
+    ] {
+        let (plan, _, _) = planner.plan_sql(query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+        let _ = executor.execute(ctx.clone()).await?;
+    }
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // The insert returns once flushed.
+    settings.set_wait_for_async_insert(1)?;
+    {
+        let query = "insert into default.async_table values(4, 'd')";
+        let (plan, _, _) = planner.plan_sql(query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+        let _ = executor.execute(ctx.clone()).await?;
+    }
+
+    {
+        let query = "select * from default.async_table";
+        let (plan, _, _) = planner.plan_sql(query).await?;
+        let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+        let stream = executor.execute(ctx.clone()).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | a |",
+            "| 2 | b |",
+            "| 3 | c |",
+            "| 4 | d |",
+            "+---+---+",
+        ];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
+    Ok(())
+}
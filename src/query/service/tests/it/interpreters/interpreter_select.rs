@@ -15,6 +15,7 @@
 use common_base::base::tokio;
 use common_exception::Result;
 use databend_query::interpreters::*;
+use databend_query::sessions::TableContext;
 use databend_query::sql::*;
 use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
@@ -76,3 +77,53 @@ async fn test_select_interpreter() -> Result<()> {
     }
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_select_interpreter_with_memory_limit() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    ctx.get_settings().set_max_query_memory_usage(1024 * 1024)?;
+
+    // The limit is taken when the query context is created.
+    let ctx = ctx.get_current_session().create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+
+    let query = "select number from numbers_mt(10000000) order by number desc";
+    let (plan, _, _) = planner.plan_sql(query).await?;
+    let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+    let res = match executor.execute(ctx.clone()).await {
+        Ok(stream) => stream.try_collect::<Vec<_>>().await.map(|_| ()),
+        Err(cause) => Err(cause),
+    };
+    assert_eq!(res.unwrap_err().code(), 1110);
+    assert!(ctx.get_peak_memory_usage() > 1024 * 1024);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_select_interpreter_with_aggregate_spilling() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    // Any states exceed the threshold, so both the partial and the final aggregators spill.
+    ctx.get_settings().set_spilling_memory_threshold(1)?;
+    ctx.get_settings().set_max_threads(4)?;
+    let ctx = ctx.get_current_session().create_query_context().await?;
+    let mut planner = Planner::new(ctx.clone());
+
+    let query = "select count(*), sum(c), sum(s) from (select number % 1000 as k, count(*) as c, \
+                 sum(number) as s from numbers_mt(100000) group by k) t";
+    let (plan, _, _) = planner.plan_sql(query).await?;
+    let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+    let stream = executor.execute(ctx.clone()).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let expected = vec![
+        "+----------+--------+------------+",
+        "| count(*) | sum(c) | sum(s)     |",
+        "+----------+--------+------------+",
+        "| 1000     | 100000 | 4999950000 |",
+        "+----------+--------+------------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
@@ -58,3 +58,4 @@ mod interpreter_user_stage;
 mod interpreter_user_udf_alter;
 mod interpreter_user_udf_create;
 mod interpreter_user_udf_drop;
+mod query_result_cache;
@@ -0,0 +1,185 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_exception::Result;
+use common_meta_types::UserInfo;
+use databend_query::interpreters::*;
+use databend_query::sessions::QueryContext;
+use databend_query::sessions::TableContext;
+use databend_query::sql::plans::Plan;
+use databend_query::sql::Planner;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+async fn execute_sql(ctx: &Arc<QueryContext>, query: &str) -> Result<()> {
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner.plan_sql(query).await?;
+    let executor = InterpreterFactory::get(ctx.clone(), &plan).await?;
+    let stream = executor.execute(ctx.clone()).await?;
+    stream.try_collect::<Vec<_>>().await?;
+    Ok(())
+}
+
+async fn cache_key(ctx: &Arc<QueryContext>, query: &str) -> Result<Option<QueryResultCacheKey>> {
+    ctx.attach_query_str("Query".to_string(), query);
+
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner.plan_sql(query).await?;
+    match plan {
+        Plan::Query {
+            s_expr, metadata, ..
+        } => QueryResultCacheKey::try_create(ctx, &metadata, &s_expr).await,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_normalize_statement() -> Result<()> {
+    assert_eq!(
+        Some("select a , 'x  y' from t where a = 1".to_string()),
+        normalize_statement("select  a,\n 'x  y' from t\twhere a=1;")
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_result_cache_key() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+
+    execute_sql(&ctx, "create table default.t(a int)").await?;
+    execute_sql(&ctx, "insert into default.t values (1), (2)").await?;
+
+    // Disabled by default.
+    assert_eq!(None, cache_key(&ctx, "select a from t").await?);
+
+    ctx.get_settings().set_enable_query_result_cache(true)?;
+
+    let key = cache_key(&ctx, "select a from t").await?;
+    assert!(key.is_some());
+
+    // Whitespace does not matter.
+    assert_eq!(key, cache_key(&ctx, "select  a\nfrom t").await?);
+
+    // Non-deterministic functions and non-fuse tables are not cached.
+    assert_eq!(None, cache_key(&ctx, "select a, now() from t").await?);
+    assert_eq!(
+        None,
+        cache_key(&ctx, "select number from numbers(3)").await?
+    );
+
+    // A change to the base table invalidates the cached result.
+    execute_sql(&ctx, "insert into default.t values (3)").await?;
+    let changed = cache_key(&ctx, "select a from t").await?;
+    assert_eq!(
+        key.as_ref().map(|k| &k.name),
+        changed.as_ref().map(|k| &k.name)
+    );
+    assert_ne!(key, changed);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_result_cache_key_with_policies() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    ctx.get_settings().set_enable_query_result_cache(true)?;
+
+    execute_sql(&ctx, "create table default.t(a int, region varchar)").await?;
+    execute_sql(
+        &ctx,
+        "insert into default.t values (1, 'east'), (2, 'west')",
+    )
+    .await?;
+    execute_sql(&ctx, "create role 'role1'").await?;
+    execute_sql(&ctx, "create role 'role2'").await?;
+    assert!(cache_key(&ctx, "select a from t").await?.is_some());
+
+    // The rows seen depend on the roles, the result of one role must not be served to another.
+    execute_sql(
+        &ctx,
+        "create row access policy p1 on default.t to role 'role1' using (region = 'east')",
+    )
+    .await?;
+    for role in ["role1", "role2"] {
+        ctx.get_current_session()
+            .set_current_role(Some(role.to_string()));
+        assert_eq!(None, cache_key(&ctx, "select a from t").await?);
+    }
+    execute_sql(&ctx, "drop row access policy p1").await?;
+    assert!(cache_key(&ctx, "select a from t").await?.is_some());
+
+    // So do the values seen with a masking policy.
+    execute_sql(
+        &ctx,
+        "create masking policy m1 as (val) -> '******' exempt roles ('role1')",
+    )
+    .await?;
+    execute_sql(
+        &ctx,
+        "alter table default.t modify column region set masking policy m1",
+    )
+    .await?;
+    for role in ["role1", "role2"] {
+        ctx.get_current_session()
+            .set_current_role(Some(role.to_string()));
+        assert_eq!(None, cache_key(&ctx, "select region from t").await?);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_result_cache_key_with_session() -> Result<()> {
+    let (_guard, ctx) = crate::tests::create_query_context().await?;
+    ctx.get_settings().set_enable_query_result_cache(true)?;
+
+    execute_sql(&ctx, "create table default.t(a varchar)").await?;
+    execute_sql(&ctx, "create database db1").await?;
+    execute_sql(&ctx, "create role 'role1'").await?;
+
+    // The session functions are bound as constants, the result of one session must not be
+    // served to another session with a different user, role or database.
+    let query = "select a from default.t where a = current_user() or a = database()";
+    let session = ctx.get_current_session();
+    let mut names = vec![];
+
+    names.push(cache_key(&ctx, query).await?.map(|k| k.name));
+
+    session.set_current_role(Some("role1".to_string()));
+    names.push(cache_key(&ctx, query).await?.map(|k| k.name));
+    session.set_current_role(None);
+
+    session.set_current_database("db1".to_string());
+    names.push(cache_key(&ctx, query).await?.map(|k| k.name));
+    session.set_current_database("default".to_string());
+
+    session.set_current_user(UserInfo::new_no_auth("user1", "%"));
+    names.push(cache_key(&ctx, query).await?.map(|k| k.name));
+
+    assert!(names.iter().all(|name| name.is_some()));
+    for (i, name) in names.iter().enumerate() {
+        assert!(!names[i + 1..].contains(name));
+    }
+
+    // The result of `connection_id()` is only valid for the connection.
+    assert_eq!(
+        None,
+        cache_key(&ctx, "select a from default.t where a = connection_id()").await?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,466 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_base::base::tokio::io::AsyncRead;
+use common_base::base::tokio::io::AsyncReadExt;
+use common_base::base::tokio::io::AsyncWrite;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_base::base::tokio::net::TcpStream;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::servers::ClickHouseHandler;
+use naive_cityhash::cityhash128;
+use tokio_rustls::rustls::Certificate;
+use tokio_rustls::rustls::ClientConfig;
+use tokio_rustls::rustls::RootCertStore;
+use tokio_rustls::rustls::ServerName;
+use tokio_rustls::TlsConnector;
+
+use crate::tests::tls_constants::TEST_CA_CERT;
+use crate::tests::tls_constants::TEST_CN_NAME;
+use crate::tests::tls_constants::TEST_SERVER_CERT;
+use crate::tests::tls_constants::TEST_SERVER_KEY;
+use crate::tests::ConfigBuilder;
+use crate::tests::TestGlobalServices;
+
+const REVISION: u64 = 54429;
+// The size of the compressed frames sent by the client, small to split a block into many frames.
+const FRAME_SIZE: usize = 4;
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_clickhouse_handler_query() -> Result<()> {
+    let conf = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
+
+    let mut handler = ClickHouseHandler::create(conf)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut client = Client::connect(listening.port()).await?;
+
+    // ping
+    client.write(&[4]).await?;
+    assert_eq!(client.stream.read_u8().await?, 4);
+
+    let (columns, rows) = client
+        .query("SELECT number, to_string(number) AS s FROM numbers(3)")
+        .await?;
+    assert_eq!(columns, vec![
+        ("number".to_string(), "UInt64".to_string()),
+        ("s".to_string(), "String".to_string()),
+    ]);
+    assert_eq!(rows, vec![vec!["0", "0"], vec!["1", "1"], vec!["2", "2"]]);
+
+    let error = client.query("SELECT * FROM not_exists").await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::UnknownTable("").code());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_clickhouse_handler_insert() -> Result<()> {
+    let conf = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
+
+    let mut handler = ClickHouseHandler::create(conf)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut client = Client::connect(listening.port()).await?;
+
+    client
+        .query("CREATE TABLE t(a INT, b VARCHAR) Engine = Fuse")
+        .await?;
+
+    // the client receives the columns, and sends the data in blocks
+    client.send_query("INSERT INTO t VALUES").await?;
+    let columns = client.read_insert_header().await?;
+    assert_eq!(columns, vec![
+        ("a".to_string(), "Int32".to_string()),
+        ("b".to_string(), "String".to_string()),
+    ]);
+    let mut block = vec![];
+    write_block_header(&mut block, 2, 2);
+    write_string(&mut block, "a");
+    write_string(&mut block, "Int32");
+    block.extend_from_slice(&1i32.to_le_bytes());
+    block.extend_from_slice(&2i32.to_le_bytes());
+    write_string(&mut block, "b");
+    write_string(&mut block, "String");
+    write_string(&mut block, "x");
+    write_string(&mut block, "y");
+    client.send_data(block).await?;
+    client.send_empty_data().await?;
+    client.read_result().await?;
+
+    let (_, rows) = client.query("SELECT * FROM t ORDER BY a").await?;
+    assert_eq!(rows, vec![vec!["1", "x"], vec!["2", "y"]]);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_clickhouse_handler_insert_compressed() -> Result<()> {
+    let conf = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
+
+    let mut handler = ClickHouseHandler::create(conf)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut client = Client::connect(listening.port()).await?;
+
+    client
+        .query("CREATE TABLE t(a INT, b VARCHAR) Engine = Fuse")
+        .await?;
+
+    // the blocks are sent in many compressed frames
+    client.compression = true;
+    client.send_query("INSERT INTO t VALUES").await?;
+    let header = client.read_compressed_insert_header().await?;
+    // block info, 2 columns and 0 rows
+    assert_eq!(&header[8..10], &[2, 0]);
+    let mut block = vec![];
+    write_block_header(&mut block, 2, 3);
+    write_string(&mut block, "a");
+    write_string(&mut block, "Int32");
+    for a in 1..=3i32 {
+        block.extend_from_slice(&a.to_le_bytes());
+    }
+    write_string(&mut block, "b");
+    write_string(&mut block, "String");
+    write_string(&mut block, "x");
+    write_string(&mut block, &"y".repeat(100));
+    write_string(&mut block, "");
+    client.send_data(block).await?;
+    client.send_empty_data().await?;
+    client.read_result().await?;
+
+    client.compression = false;
+    let (_, rows) = client
+        .query("SELECT a, length(b) FROM t ORDER BY a")
+        .await?;
+    assert_eq!(rows, vec![vec!["1", "1"], vec!["2", "100"], vec!["3", "0"]]);
+
+    // the sizes of a frame are checked before its data is received
+    let mut client = Client::connect(listening.port()).await?;
+    client.compression = true;
+    client.send_query("INSERT INTO t VALUES").await?;
+    client.read_compressed_insert_header().await?;
+    let mut packet = vec![];
+    write_varint(&mut packet, 2);
+    write_string(&mut packet, "");
+    packet.extend_from_slice(&[0; 16]);
+    packet.push(0x82);
+    packet.extend_from_slice(&100u32.to_le_bytes());
+    packet.extend_from_slice(&u32::MAX.to_le_bytes());
+    client.write(&packet).await?;
+    let error = client.read_result().await.unwrap_err();
+    assert_eq!(error.code(), ErrorCode::BadBytes("").code());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_clickhouse_handler_tls() -> Result<()> {
+    let conf = ConfigBuilder::create()
+        .clickhouse_handler_tls_server_cert(TEST_SERVER_CERT)
+        .clickhouse_handler_tls_server_key(TEST_SERVER_KEY)
+        .build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
+
+    let mut handler = ClickHouseHandler::create(conf)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut client = Client::connect_tls(listening.port()).await?;
+
+    let (_, rows) = client.query("SELECT number FROM numbers(2)").await?;
+    assert_eq!(rows, vec![vec!["0"], vec!["1"]]);
+
+    Ok(())
+}
+
+trait ClientStream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> ClientStream for T {}
+
+struct Client {
+    stream: Box<dyn ClientStream>,
+    compression: bool,
+}
+
+type QueryResult = (Vec<(String, String)>, Vec<Vec<String>>);
+
+impl Client {
+    async fn connect(port: u16) -> Result<Client> {
+        let stream = TcpStream::connect(format!("127.0.0.1:{}", port)).await?;
+        Self::handshake(Box::new(stream)).await
+    }
+
+    async fn connect_tls(port: u16) -> Result<Client> {
+        let mut roots = RootCertStore::empty();
+        let mut reader = BufReader::new(File::open(TEST_CA_CERT)?);
+        for cert in rustls_pemfile::certs(&mut reader)? {
+            roots.add(&Certificate(cert)).unwrap();
+        }
+        let config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = TlsConnector::from(Arc::new(config));
+
+        let stream = TcpStream::connect(format!("127.0.0.1:{}", port)).await?;
+        let server_name = ServerName::try_from(TEST_CN_NAME).unwrap();
+        let stream = connector.connect(server_name, stream).await?;
+        Self::handshake(Box::new(stream)).await
+    }
+
+    async fn handshake(stream: Box<dyn ClientStream>) -> Result<Client> {
+        let mut client = Client {
+            stream,
+            compression: false,
+        };
+
+        let mut hello = vec![];
+        write_varint(&mut hello, 0);
+        write_string(&mut hello, "test-client");
+        write_varint(&mut hello, 22);
+        write_varint(&mut hello, 3);
+        write_varint(&mut hello, REVISION);
+        write_string(&mut hello, "default");
+        write_string(&mut hello, "root");
+        write_string(&mut hello, "");
+        client.write(&hello).await?;
+
+        assert_eq!(client.read_varint().await?, 0);
+        assert_eq!(client.read_string().await?, "Databend");
+        // version major, minor and revision
+        for _ in 0..3 {
+            client.read_varint().await?;
+        }
+        // timezone, display name and version patch
+        client.read_string().await?;
+        client.read_string().await?;
+        client.read_varint().await?;
+        Ok(client)
+    }
+
+    async fn query(&mut self, query: &str) -> Result<QueryResult> {
+        self.send_query(query).await?;
+        self.read_result().await
+    }
+
+    async fn send_query(&mut self, query: &str) -> Result<()> {
+        let mut packet = vec![];
+        write_varint(&mut packet, 1);
+        write_string(&mut packet, "");
+        // client info: query kind, initial user, query id and address
+        packet.push(1);
+        write_string(&mut packet, "");
+        write_string(&mut packet, "");
+        write_string(&mut packet, "127.0.0.1:0");
+        // interface, os user, hostname, client name, version and revision
+        packet.push(1);
+        write_string(&mut packet, "");
+        write_string(&mut packet, "");
+        write_string(&mut packet, "test-client");
+        write_varint(&mut packet, 22);
+        write_varint(&mut packet, 3);
+        write_varint(&mut packet, REVISION);
+        // quota key and version patch
+        write_string(&mut packet, "");
+        write_varint(&mut packet, 0);
+        // settings
+        write_string(&mut packet, "max_threads");
+        write_varint(&mut packet, 0);
+        write_string(&mut packet, "2");
+        write_string(&mut packet, "");
+        // stage, compression and query
+        write_varint(&mut packet, 2);
+        write_varint(&mut packet, self.compression as u64);
+        write_string(&mut packet, query);
+        self.write(&packet).await?;
+
+        // no external tables
+        self.send_empty_data().await
+    }
+
+    async fn send_data(&mut self, block: Vec<u8>) -> Result<()> {
+        let mut packet = vec![];
+        write_varint(&mut packet, 2);
+        write_string(&mut packet, "");
+        match self.compression {
+            true => {
+                for data in block.chunks(FRAME_SIZE) {
+                    write_frame(&mut packet, data);
+                }
+            }
+            false => packet.extend(block),
+        }
+        self.write(&packet).await
+    }
+
+    async fn write(&mut self, buf: &[u8]) -> Result<()> {
+        self.stream.write_all(buf).await?;
+        self.stream.flush().await?;
+        Ok(())
+    }
+
+    async fn send_empty_data(&mut self) -> Result<()> {
+        let mut block = vec![];
+        write_block_header(&mut block, 0, 0);
+        self.send_data(block).await
+    }
+
+    // Read the packets until the end of the stream.
+    async fn read_result(&mut self) -> Result<QueryResult> {
+        let mut columns = vec![];
+        let mut rows = vec![];
+        loop {
+            match self.read_varint().await? {
+                // data
+                1 => {
+                    let (block_columns, block_rows) = self.read_block().await?;
+                    columns = block_columns;
+                    rows.extend(block_rows);
+                }
+                // exception
+                2 => {
+                    let code = self.stream.read_i32_le().await?;
+                    let _name = self.read_string().await?;
+                    let message = self.read_string().await?;
+                    let _stack_trace = self.read_string().await?;
+                    self.stream.read_u8().await?;
+                    return Err(ErrorCode::create(code as u16, message, None, None));
+                }
+                // progress
+                3 => {
+                    for _ in 0..5 {
+                        self.read_varint().await?;
+                    }
+                }
+                // end of stream
+                5 => return Ok((columns, rows)),
+                packet => panic!("unexpected packet: {}", packet),
+            }
+        }
+    }
+
+    // The server replies an insert with the header of the table, before the client sends data.
+    async fn read_insert_header(&mut self) -> Result<Vec<(String, String)>> {
+        assert_eq!(self.read_varint().await?, 1);
+        let (columns, _) = self.read_block().await?;
+        Ok(columns)
+    }
+
+    // The header is compressed, returns the decompressed bytes of its block.
+    async fn read_compressed_insert_header(&mut self) -> Result<Vec<u8>> {
+        assert_eq!(self.read_varint().await?, 1);
+        self.read_string().await?;
+        let mut checksum = [0; 16];
+        self.stream.read_exact(&mut checksum).await?;
+        assert_eq!(self.stream.read_u8().await?, 0x82);
+        let compressed_size = self.stream.read_u32_le().await? as usize;
+        let decompressed_size = self.stream.read_u32_le().await?;
+        let mut data = vec![0; compressed_size - 9];
+        self.stream.read_exact(&mut data).await?;
+        Ok(lz4::block::decompress(
+            &data,
+            Some(decompressed_size as i32),
+        )?)
+    }
+
+    async fn read_block(&mut self) -> Result<QueryResult> {
+        self.read_string().await?;
+        let mut info = [0; 8];
+        self.stream.read_exact(&mut info).await?;
+        let num_columns = self.read_varint().await? as usize;
+        let num_rows = self.read_varint().await? as usize;
+
+        let mut columns = vec![];
+        let mut rows = vec![vec![]; num_rows];
+        for _ in 0..num_columns {
+            let name = self.read_string().await?;
+            let data_type = self.read_string().await?;
+            for row in rows.iter_mut() {
+                row.push(self.read_value(&data_type).await?);
+            }
+            columns.push((name, data_type));
+        }
+        Ok((columns, rows))
+    }
+
+    async fn read_value(&mut self, data_type: &str) -> Result<String> {
+        match data_type {
+            "UInt64" => Ok(self.stream.read_u64_le().await?.to_string()),
+            "Int32" => Ok(self.stream.read_i32_le().await?.to_string()),
+            "String" => self.read_string().await,
+            _ => panic!("unexpected type: {}", data_type),
+        }
+    }
+
+    async fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for i in 0..10 {
+            let byte = self.stream.read_u8().await?;
+            value |= ((byte & 0x7F) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        Ok(value)
+    }
+
+    async fn read_string(&mut self) -> Result<String> {
+        let len = self.read_varint().await? as usize;
+        let mut buf = vec![0; len];
+        self.stream.read_exact(&mut buf).await?;
+        Ok(String::from_utf8(buf).unwrap())
+    }
+}
+
+fn write_block_header(buf: &mut Vec<u8>, num_columns: u64, num_rows: u64) {
+    // block info: is_overflows, bucket_num
+    buf.extend_from_slice(&[1, 0, 2, 0xff, 0xff, 0xff, 0xff, 0]);
+    write_varint(buf, num_columns);
+    write_varint(buf, num_rows);
+}
+
+// A frame of the data which is not compressed: checksum, method, sizes and the data.
+fn write_frame(buf: &mut Vec<u8>, data: &[u8]) {
+    let mut frame = vec![0x02];
+    frame.extend_from_slice(&(data.len() as u32 + 9).to_le_bytes());
+    frame.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    frame.extend_from_slice(data);
+    let checksum = cityhash128(&frame);
+    buf.extend_from_slice(&checksum.lo.to_le_bytes());
+    buf.extend_from_slice(&checksum.hi.to_le_bytes());
+    buf.extend(frame);
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_string(buf: &mut Vec<u8>, value: &str) {
+    write_varint(buf, value.len() as u64);
+    buf.extend_from_slice(value.as_bytes());
+}
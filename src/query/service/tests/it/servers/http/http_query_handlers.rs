@@ -14,11 +14,15 @@
 
 use std::collections::BTreeMap;
 use std::fs::File;
+use std::io::Cursor;
 use std::io::Read;
 use std::time::Duration;
 
 use base64::encode_config;
 use base64::URL_SAFE_NO_PAD;
+use common_arrow::arrow::io::ipc::read::read_stream_metadata;
+use common_arrow::arrow::io::ipc::read::StreamReader;
+use common_arrow::arrow::io::ipc::read::StreamState;
 use common_base::base::get_free_tcp_port;
 use common_base::base::tokio;
 use common_exception::ErrorCode;
@@ -32,10 +36,12 @@ use databend_query::servers::http::middleware::HTTPSessionEndpoint;
 use databend_query::servers::http::middleware::HTTPSessionMiddleware;
 use databend_query::servers::http::v1::make_final_uri;
 use databend_query::servers::http::v1::make_page_uri;
+use databend_query::servers::http::v1::make_progress_uri;
 use databend_query::servers::http::v1::make_state_uri;
 use databend_query::servers::http::v1::query_route;
 use databend_query::servers::http::v1::ExecuteStateKind;
 use databend_query::servers::http::v1::HttpSessionConf;
+use databend_query::servers::http::v1::QueryProgress;
 use databend_query::servers::http::v1::QueryResponse;
 use databend_query::servers::HttpHandler;
 use databend_query::servers::HttpHandlerKind;
@@ -199,6 +205,49 @@ async fn test_return_when_finish() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn test_query_progress() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let sql = "select * from numbers(100)";
+    let ep = create_endpoint().await?;
+    let (status, result) = post_sql_to_endpoint_new_session(&ep, sql, 1).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    assert_eq!(result.state, ExecuteStateKind::Succeeded, "{:?}", result);
+    assert_eq!(
+        result.progress_uri,
+        Some(make_progress_uri(&result.id)),
+        "{:?}",
+        result
+    );
+    // the query went through the stages up to the execution
+    let stages = result
+        .stats
+        .stages
+        .iter()
+        .map(|timing| timing.stage.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(stages.first(), Some(&"Planning"), "{:?}", result);
+    assert_eq!(stages.last(), Some(&"Executing"), "{:?}", result);
+
+    let response = get_uri(&ep, &make_progress_uri(&result.id)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().into_string().await.unwrap();
+    let progress = serde_json::from_str::<QueryProgress>(&body)?;
+    assert_eq!(progress.id, result.id);
+    assert_eq!(progress.state, ExecuteStateKind::Succeeded);
+    assert_eq!(
+        progress.stats.progresses.scan_progress.rows, 100,
+        "{}",
+        body
+    );
+    assert_eq!(progress.stats.stages.len(), stages.len(), "{}", body);
+
+    let response = get_uri(&ep, &make_progress_uri("not_exists")).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
 // ref: query_log not recored correctly.
 // It could be uncommented when we remove SEE_YOU_AGAIN stmt
 
@@ -375,6 +424,36 @@ async fn test_pagination() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn test_pagination_max_bytes() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let ep = create_endpoint().await?;
+    let sql = "select * from numbers(10)";
+    // a page has one row at least, even if the row is larger than max_bytes_per_page
+    let json = serde_json::json!({"sql": sql.to_string(), "pagination": {"wait_time_secs": 1, "max_rows_per_page": 100, "max_bytes_per_page": 1}});
+
+    let (status, result) = post_json_to_endpoint(&ep, &json).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    assert!(result.error.is_none(), "{:?}", result);
+    assert_eq!(result.data.len(), 1, "{:?}", result);
+
+    let mut rows = result.data.len();
+    let mut next_uri = result.next_uri;
+    while let Some(uri) = next_uri {
+        let (status, result) = get_uri_checked(&ep, &uri).await?;
+        assert_eq!(status, StatusCode::OK, "{:?}", result);
+        assert!(result.data.len() <= 1, "{:?}", result);
+        rows += result.data.len();
+        next_uri = result.next_uri;
+    }
+    assert_eq!(rows, 10);
+
+    let status = delete_query(&ep, &result.id).await;
+    assert_eq!(status, StatusCode::OK);
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_http_session() -> Result<()> {
     let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
@@ -1190,6 +1269,76 @@ async fn test_download_killed() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn test_query_arrow_stream() -> Result<()> {
+    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+
+    let ep = create_endpoint().await?;
+    let json = serde_json::json!({"sql": "select number, number + 1 from numbers(3)"});
+    let arrow_stream = "application/vnd.apache.arrow.stream";
+
+    // by the Accept header or the format parameter
+    for (uri, accept) in [
+        ("/v1/query", arrow_stream),
+        ("/v1/query?format=arrow", "*/*"),
+    ] {
+        let resp = post_json_with_accept(&ep, uri, accept, &json).await;
+        assert_eq!(resp.status(), StatusCode::OK, "{}", uri);
+        assert_eq!(resp.content_type(), Some(arrow_stream), "{}", uri);
+
+        let mut cursor = Cursor::new(resp.into_body().into_vec().await.unwrap());
+        let metadata = read_stream_metadata(&mut cursor)?;
+        let names = metadata
+            .schema
+            .fields
+            .iter()
+            .map(|field| field.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["number", "number + 1"]);
+
+        let mut num_rows = 0;
+        for state in StreamReader::new(cursor, metadata, None) {
+            if let StreamState::Some(chunk) = state? {
+                num_rows += chunk.len();
+            }
+        }
+        assert_eq!(num_rows, 3);
+    }
+
+    // the results are in JSON by default
+    let (status, result) = post_json_to_endpoint(&ep, &json).await?;
+    assert_eq!(status, StatusCode::OK, "{:?}", result);
+    assert_eq!(result.data.len(), 3, "{:?}", result);
+
+    let resp = post_json_with_accept(&ep, "/v1/query?format=xml", "*/*", &json).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // the errors before the results are sent
+    let json = serde_json::json!({"sql": "select * from not_exists"});
+    let resp = post_json_with_accept(&ep, "/v1/query", arrow_stream, &json).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+async fn post_json_with_accept(
+    ep: &EndpointType,
+    uri: &str,
+    accept: &str,
+    json: &serde_json::Value,
+) -> Response {
+    let body = serde_json::to_vec(&json).unwrap();
+    let basic = headers::Authorization::basic("root", "");
+    let req = Request::builder()
+        .uri(uri.parse().unwrap())
+        .method(Method::POST)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::ACCEPT, accept)
+        .typed_header(basic)
+        .body(body);
+    ep.call(req).await.unwrap_or_else(|err| err.into_response())
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn test_no_download_in_management_mode() -> Result<()> {
     // Setup
@@ -12,5 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod clickhouse;
 mod http;
 mod mysql;
+mod postgres;
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::net::SocketAddr;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -26,18 +27,25 @@ use databend_query::servers::MySQLHandler;
 use mysql_async::prelude::FromRow;
 use mysql_async::prelude::Queryable;
 use mysql_async::FromRowError;
+use mysql_async::Opts;
+use mysql_async::OptsBuilder;
 use mysql_async::Row;
+use mysql_async::SslOpts;
 use tokio::sync::Barrier;
 
+use crate::tests::tls_constants::TEST_CA_CERT;
+use crate::tests::tls_constants::TEST_SERVER_CERT;
+use crate::tests::tls_constants::TEST_SERVER_KEY;
 use crate::tests::ConfigBuilder;
 use crate::tests::TestGlobalServices;
 
 #[tokio::test(flavor = "current_thread")]
 async fn test_generic_code_with_on_query() -> Result<()> {
     // Setup
-    let _guard = TestGlobalServices::setup(ConfigBuilder::create().build()).await?;
+    let conf = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
 
-    let mut handler = MySQLHandler::create()?;
+    let mut handler = MySQLHandler::create(conf)?;
 
     let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
     let runnable_server = handler.start(listening).await?;
@@ -49,12 +57,81 @@ async fn test_generic_code_with_on_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "current_thread")]
+async fn test_prepared_statement() -> Result<()> {
+    let conf = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
+
+    let mut handler = MySQLHandler::create(conf)?;
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port()).await?;
+
+    let statement = connection
+        .prep("SELECT number + ?, ?, '?' FROM numbers(3) WHERE number > ?")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Prepare failed")?;
+    let rows: Vec<(u64, String, String)> = connection
+        .exec(&statement, (10, "it's", 0))
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Execute failed")?;
+    assert_eq!(rows, vec![
+        (11, "it's".to_string(), "?".to_string()),
+        (12, "it's".to_string(), "?".to_string()),
+    ]);
+
+    connection
+        .close(statement)
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Close failed")?;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_mysql_handler_tls() -> Result<()> {
+    let conf = ConfigBuilder::create()
+        .mysql_handler_tls_server_cert(TEST_SERVER_CERT)
+        .mysql_handler_tls_server_key(TEST_SERVER_KEY)
+        .build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
+
+    let mut handler = MySQLHandler::create(conf)?;
+
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+
+    // the connection is upgraded to TLS by the SSLRequest
+    let ssl_opts = SslOpts::default()
+        .with_root_cert_path(Some(Path::new(TEST_CA_CERT)))
+        .with_danger_skip_domain_validation(true);
+    let uri = format!("mysql://root@127.0.0.1:{}", listening.port());
+    let opts = Opts::from_url(&uri).unwrap();
+    let opts = OptsBuilder::from_opts(opts).ssl_opts(ssl_opts);
+    let mut connection = mysql_async::Conn::new(opts)
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Reject connection")?;
+    let rows: Vec<u64> = connection
+        .query("SELECT number FROM numbers(3)")
+        .await
+        .map_err_to_code(ErrorCode::UnknownException, || "Query failed")?;
+    assert_eq!(rows, vec![0, 1, 2]);
+
+    // the clients without TLS go on in plain text
+    let mut connection = create_connection(listening.port()).await?;
+    let result = connection.query_iter("SELECT 1").await;
+    assert!(result.is_ok());
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "current_thread")]
 async fn test_rejected_session_with_sequence() -> Result<()> {
-    let _guard =
-        TestGlobalServices::setup(ConfigBuilder::create().max_active_sessions(1).build()).await?;
+    let conf = ConfigBuilder::create().max_active_sessions(1).build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
 
-    let mut handler = MySQLHandler::create()?;
+    let mut handler = MySQLHandler::create(conf)?;
 
     let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
     let listening = handler.start(listening).await?;
@@ -117,10 +194,10 @@ async fn test_rejected_session_with_parallel() -> Result<()> {
     }
 
     // Setup
-    let _guard =
-        TestGlobalServices::setup(ConfigBuilder::create().max_active_sessions(1).build()).await?;
+    let conf = ConfigBuilder::create().max_active_sessions(1).build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
 
-    let mut handler = MySQLHandler::create()?;
+    let mut handler = MySQLHandler::create(conf)?;
 
     let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
     let listening = handler.start(listening).await?;
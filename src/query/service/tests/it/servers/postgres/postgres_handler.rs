@@ -0,0 +1,407 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use common_base::base::tokio;
+use common_base::base::tokio::io::AsyncReadExt;
+use common_base::base::tokio::io::AsyncWriteExt;
+use common_base::base::tokio::net::TcpStream;
+use common_exception::Result;
+use databend_query::servers::PostgresHandler;
+
+use crate::tests::ConfigBuilder;
+use crate::tests::TestGlobalServices;
+
+const INT4: i32 = 23;
+const TEXT: i32 = 25;
+const NUMERIC: i32 = 1700;
+const DATE: i32 = 1082;
+const TIMESTAMP: i32 = 1114;
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_postgres_handler_query() -> Result<()> {
+    let conf = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
+
+    let mut handler = PostgresHandler::create(conf)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let (mut client, params) = Client::connect(listening.port()).await?;
+    assert_eq!(params.get("server_version"), Some(&"14.5".to_string()));
+    assert_eq!(params.get("client_encoding"), Some(&"UTF8".to_string()));
+
+    let result = client
+        .query("SELECT number, to_string(number) AS s FROM numbers(3)")
+        .await?
+        .unwrap();
+    assert_eq!(result.columns, vec![
+        ("number".to_string(), NUMERIC),
+        ("s".to_string(), TEXT),
+    ]);
+    assert_eq!(result.rows, vec![
+        vec![Some("0".to_string()), Some("0".to_string())],
+        vec![Some("1".to_string()), Some("1".to_string())],
+        vec![Some("2".to_string()), Some("2".to_string())],
+    ]);
+    assert_eq!(result.tag, "SELECT 3");
+
+    // the error is followed by ReadyForQuery, the connection goes on
+    let error = client.query("SELECT * FROM not_exists").await?.unwrap_err();
+    assert_eq!(error.0, "42P01");
+    assert!(error.1.contains("not_exists"));
+
+    let result = client.query("SELECT NULL").await?.unwrap();
+    assert_eq!(result.rows, vec![vec![None]]);
+
+    // the commands of the drivers
+    let result = client.query("SET extra_float_digits = 3").await?.unwrap();
+    assert!(result.columns.is_empty());
+    assert_eq!(result.tag, "SET");
+    let result = client.query("BEGIN").await?.unwrap();
+    assert_eq!(result.tag, "BEGIN");
+
+    let result = client.query(";").await?.unwrap();
+    assert_eq!(result.tag, "");
+
+    client.send(b'X', vec![]).await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_postgres_handler_extended_query() -> Result<()> {
+    let conf = ConfigBuilder::create().build();
+    let _guard = TestGlobalServices::setup(conf.clone()).await?;
+
+    let mut handler = PostgresHandler::create(conf)?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let (mut client, _) = Client::connect(listening.port()).await?;
+
+    let result = client
+        .query("CREATE TABLE t(a INT, b VARCHAR) Engine = Fuse")
+        .await?
+        .unwrap();
+    assert_eq!(result.tag, "CREATE TABLE");
+    let result = client
+        .query("INSERT INTO t VALUES (1, 'x'), (2, 'y'), (3, 'z')")
+        .await?
+        .unwrap();
+    assert_eq!(result.tag, "INSERT 0 3");
+
+    // Parse, Describe, Bind, Execute with the parameter in text
+    let query = "SELECT a, b FROM t WHERE a >= $1 ORDER BY a";
+    client.parse("s1", query, &[INT4]).await?;
+    client.describe(b'S', "s1").await?;
+    client
+        .bind("", "s1", &[0], &[Some(b"2".to_vec())], &[0])
+        .await?;
+    client.execute("", 0).await?;
+    client.send(b'S', vec![]).await?;
+    let messages = client.read_until_ready().await?;
+    assert_eq!(tags(&messages), "1tT2DDCZ");
+    let mut reader = Reader::new(&messages[1].1);
+    assert_eq!(reader.i16(), 1);
+    assert_eq!(reader.i32(), INT4);
+    let result = query_result(&messages).unwrap();
+    assert_eq!(result.columns, vec![
+        ("a".to_string(), INT4),
+        ("b".to_string(), TEXT)
+    ]);
+    assert_eq!(result.rows, vec![
+        vec![Some("2".to_string()), Some("y".to_string())],
+        vec![Some("3".to_string()), Some("z".to_string())],
+    ]);
+    assert_eq!(result.tag, "SELECT 2");
+
+    // the parameter and the result in binary, the rows are fetched one by one
+    let param = 1i32.to_be_bytes().to_vec();
+    client.bind("p1", "s1", &[1], &[Some(param)], &[1]).await?;
+    client.describe(b'P', "p1").await?;
+    client.execute("p1", 2).await?;
+    client.execute("p1", 2).await?;
+    client.send(b'S', vec![]).await?;
+    let messages = client.read_until_ready().await?;
+    assert_eq!(tags(&messages), "2TDDsDCZ");
+    let mut reader = Reader::new(&messages[2].1);
+    assert_eq!(reader.i16(), 2);
+    assert_eq!(reader.i32(), 4);
+    assert_eq!(reader.i32(), 1);
+    let result = query_result(&messages).unwrap();
+    assert_eq!(result.rows.len(), 3);
+    assert_eq!(result.tag, "SELECT 1");
+
+    // binary dates and timestamps out of range are refused
+    client
+        .parse("s2", "SELECT $1, $2", &[DATE, TIMESTAMP])
+        .await?;
+    client.send(b'S', vec![]).await?;
+    let messages = client.read_until_ready().await?;
+    assert_eq!(tags(&messages), "1Z");
+    let max_days = i32::MAX.to_be_bytes().to_vec();
+    let min_micros = i64::MIN.to_be_bytes().to_vec();
+    for params in [[Some(max_days), None], [None, Some(min_micros)]] {
+        client.bind("", "s2", &[1], &params, &[0]).await?;
+        client.execute("", 0).await?;
+        client.send(b'S', vec![]).await?;
+        let messages = client.read_until_ready().await?;
+        assert_eq!(tags(&messages), "EZ");
+    }
+
+    // the messages after an error are discarded until Sync
+    client.bind("", "s1", &[0], &[], &[0]).await?;
+    client.bind("", "not_exists", &[0], &[], &[0]).await?;
+    client.execute("", 0).await?;
+    client.send(b'S', vec![]).await?;
+    let messages = client.read_until_ready().await?;
+    assert_eq!(tags(&messages), "2EZ");
+
+    client.send(b'X', vec![]).await?;
+    Ok(())
+}
+
+struct Client {
+    stream: TcpStream,
+}
+
+struct QueryResult {
+    columns: Vec<(String, i32)>,
+    rows: Vec<Vec<Option<String>>>,
+    tag: String,
+}
+
+impl Client {
+    async fn connect(port: u16) -> Result<(Client, HashMap<String, String>)> {
+        let stream = TcpStream::connect(format!("127.0.0.1:{}", port)).await?;
+        let mut client = Client { stream };
+
+        // SSLRequest is refused without the TLS config of the handler
+        let mut ssl_request = 8i32.to_be_bytes().to_vec();
+        ssl_request.extend_from_slice(&80877103i32.to_be_bytes());
+        client.stream.write_all(&ssl_request).await?;
+        assert_eq!(client.stream.read_u8().await?, b'N');
+
+        let mut startup = 196608i32.to_be_bytes().to_vec();
+        for value in ["user", "root", "database", "default", ""] {
+            write_cstr(&mut startup, value);
+        }
+        let mut packet = ((startup.len() + 4) as i32).to_be_bytes().to_vec();
+        packet.extend_from_slice(&startup);
+        client.stream.write_all(&packet).await?;
+
+        let (tag, body) = client.read_message().await?;
+        assert_eq!((tag, body), (b'R', 3i32.to_be_bytes().to_vec()));
+        let mut password = vec![];
+        write_cstr(&mut password, "");
+        client.send(b'p', password).await?;
+
+        let mut params = HashMap::new();
+        for (tag, body) in client.read_until_ready().await? {
+            match tag {
+                b'R' => assert_eq!(body, 0i32.to_be_bytes().to_vec()),
+                b'S' => {
+                    let mut reader = Reader::new(&body);
+                    params.insert(reader.cstr(), reader.cstr());
+                }
+                b'K' | b'Z' => {}
+                tag => panic!("unexpected message {}", tag as char),
+            }
+        }
+        Ok((client, params))
+    }
+
+    async fn query(&mut self, query: &str) -> Result<std::result::Result<QueryResult, Error>> {
+        let mut body = vec![];
+        write_cstr(&mut body, query);
+        self.send(b'Q', body).await?;
+        let messages = self.read_until_ready().await?;
+        Ok(query_result(&messages))
+    }
+
+    async fn parse(&mut self, name: &str, query: &str, param_types: &[i32]) -> Result<()> {
+        let mut body = vec![];
+        write_cstr(&mut body, name);
+        write_cstr(&mut body, query);
+        body.extend_from_slice(&(param_types.len() as i16).to_be_bytes());
+        for param_type in param_types {
+            body.extend_from_slice(&param_type.to_be_bytes());
+        }
+        self.send(b'P', body).await
+    }
+
+    async fn bind(
+        &mut self,
+        portal: &str,
+        statement: &str,
+        param_formats: &[i16],
+        params: &[Option<Vec<u8>>],
+        result_formats: &[i16],
+    ) -> Result<()> {
+        let mut body = vec![];
+        write_cstr(&mut body, portal);
+        write_cstr(&mut body, statement);
+        body.extend_from_slice(&(param_formats.len() as i16).to_be_bytes());
+        for format in param_formats {
+            body.extend_from_slice(&format.to_be_bytes());
+        }
+        body.extend_from_slice(&(params.len() as i16).to_be_bytes());
+        for param in params {
+            match param {
+                None => body.extend_from_slice(&(-1i32).to_be_bytes()),
+                Some(value) => {
+                    body.extend_from_slice(&(value.len() as i32).to_be_bytes());
+                    body.extend_from_slice(value);
+                }
+            }
+        }
+        body.extend_from_slice(&(result_formats.len() as i16).to_be_bytes());
+        for format in result_formats {
+            body.extend_from_slice(&format.to_be_bytes());
+        }
+        self.send(b'B', body).await
+    }
+
+    async fn describe(&mut self, target: u8, name: &str) -> Result<()> {
+        let mut body = vec![target];
+        write_cstr(&mut body, name);
+        self.send(b'D', body).await
+    }
+
+    async fn execute(&mut self, portal: &str, max_rows: i32) -> Result<()> {
+        let mut body = vec![];
+        write_cstr(&mut body, portal);
+        body.extend_from_slice(&max_rows.to_be_bytes());
+        self.send(b'E', body).await
+    }
+
+    async fn send(&mut self, tag: u8, body: Vec<u8>) -> Result<()> {
+        let mut packet = vec![tag];
+        packet.extend_from_slice(&((body.len() + 4) as i32).to_be_bytes());
+        packet.extend_from_slice(&body);
+        self.stream.write_all(&packet).await?;
+        Ok(())
+    }
+
+    async fn read_message(&mut self) -> Result<(u8, Vec<u8>)> {
+        let tag = self.stream.read_u8().await?;
+        let len = self.stream.read_i32().await?;
+        let mut body = vec![0; len as usize - 4];
+        self.stream.read_exact(&mut body).await?;
+        Ok((tag, body))
+    }
+
+    async fn read_until_ready(&mut self) -> Result<Vec<(u8, Vec<u8>)>> {
+        let mut messages = vec![];
+        loop {
+            let (tag, body) = self.read_message().await?;
+            messages.push((tag, body));
+            if tag == b'Z' {
+                return Ok(messages);
+            }
+        }
+    }
+}
+
+// The SQLSTATE and the message of ErrorResponse.
+#[derive(Debug)]
+struct Error(String, String);
+
+fn query_result(messages: &[(u8, Vec<u8>)]) -> std::result::Result<QueryResult, Error> {
+    let mut result = QueryResult {
+        columns: vec![],
+        rows: vec![],
+        tag: "".to_string(),
+    };
+    for (tag, body) in messages {
+        let mut reader = Reader::new(body);
+        match tag {
+            b'T' => {
+                for _ in 0..reader.i16() {
+                    let name = reader.cstr();
+                    reader.i32();
+                    reader.i16();
+                    let oid = reader.i32();
+                    reader.i16();
+                    reader.i32();
+                    reader.i16();
+                    result.columns.push((name, oid));
+                }
+            }
+            b'D' => {
+                let row = (0..reader.i16())
+                    .map(|_| match reader.i32() {
+                        -1 => None,
+                        len => Some(reader.bytes(len as usize)),
+                    })
+                    .map(|value| value.map(|value| String::from_utf8_lossy(&value).to_string()))
+                    .collect();
+                result.rows.push(row);
+            }
+            b'C' => result.tag = reader.cstr(),
+            b'E' => {
+                let mut fields = HashMap::new();
+                loop {
+                    match reader.bytes(1)[0] {
+                        0 => break,
+                        field => fields.insert(field, reader.cstr()),
+                    };
+                }
+                return Err(Error(fields[&b'C'].clone(), fields[&b'M'].clone()));
+            }
+            _ => {}
+        }
+    }
+    Ok(result)
+}
+
+fn tags(messages: &[(u8, Vec<u8>)]) -> String {
+    messages.iter().map(|(tag, _)| *tag as char).collect()
+}
+
+fn write_cstr(buf: &mut Vec<u8>, value: &str) {
+    buf.extend_from_slice(value.as_bytes());
+    buf.push(0);
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Reader { buf, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        self.pos += len;
+        self.buf[self.pos - len..self.pos].to_vec()
+    }
+
+    fn i16(&mut self) -> i16 {
+        i16::from_be_bytes(self.bytes(2).try_into().unwrap())
+    }
+
+    fn i32(&mut self) -> i32 {
+        i32::from_be_bytes(self.bytes(4).try_into().unwrap())
+    }
+
+    fn cstr(&mut self) -> String {
+        let len = self.buf[self.pos..].iter().position(|b| *b == 0).unwrap();
+        let value = String::from_utf8(self.bytes(len)).unwrap();
+        self.pos += 1;
+        value
+    }
+}
@@ -17,6 +17,7 @@ use common_exception::Result;
 use common_storage::StorageFsConfig;
 use common_storage::StorageParams;
 use common_storage::StorageS3Config;
+use databend_query::clusters::ClusterHelper;
 use databend_query::sessions::TableContext;
 use wiremock::matchers::method;
 use wiremock::matchers::path;
@@ -63,3 +64,28 @@ async fn test_get_storage_accessor_fs() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_exclude_cluster_node() -> Result<()> {
+    let (_guard, qctx) = crate::tests::create_query_context_with_cluster(
+        crate::tests::ClusterDescriptor::new()
+            .with_node("node1", "127.0.0.1:9090")
+            .with_node("node2", "127.0.0.1:9091")
+            .with_node("node3", "127.0.0.1:9092")
+            .with_local_id("node1"),
+    )
+    .await?;
+
+    qctx.exclude_cluster_node("node2");
+
+    let cluster = qctx.get_cluster();
+    let nodes = cluster
+        .get_nodes()
+        .iter()
+        .map(|node| node.id.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(nodes, vec!["node1", "node3"]);
+    assert_eq!(cluster.local_id(), "node1");
+
+    Ok(())
+}
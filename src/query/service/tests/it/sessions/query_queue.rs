@@ -0,0 +1,128 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_exception::Result;
+use common_meta_types::WorkloadGroup;
+use databend_query::sessions::QueryQueue;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_query_queue() -> Result<()> {
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.max_running_queries = 1;
+    conf.query.max_queued_queries = 1;
+    conf.query.query_queue_timeout = 200;
+    let queue = QueryQueue::create(&conf);
+
+    let permit1 = queue.acquire("q1", "u1", None, "SELECT 1").await?;
+
+    // Waits in the queue until the running query finished.
+    let handle = {
+        let queue = queue.clone();
+        tokio::spawn(async move { queue.acquire("q2", "u2", None, "SELECT 2").await })
+    };
+    while queue.queue_info().len() < 2 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let queue_info = queue.queue_info();
+    assert_eq!(queue_info[0].query_id, "q1");
+    assert_eq!(queue_info[0].state, "Running");
+    assert_eq!(queue_info[1].query_id, "q2");
+    assert_eq!(queue_info[1].state, "Queued");
+
+    // The queue is full.
+    let res = queue.acquire("q3", "u3", None, "SELECT 3").await;
+    assert_eq!(res.err().unwrap().code(), 1108);
+
+    drop(permit1);
+    let permit2 = handle.await.unwrap()?;
+    let queue_info = queue.queue_info();
+    assert_eq!(queue_info.len(), 1);
+    assert_eq!(queue_info[0].query_id, "q2");
+    assert_eq!(queue_info[0].state, "Running");
+
+    // Timed out in the queue, and removed from the queue.
+    let res = queue.acquire("q4", "u4", None, "SELECT 4").await;
+    assert_eq!(res.err().unwrap().code(), 1109);
+    assert_eq!(queue.queue_info().len(), 1);
+
+    drop(permit2);
+    assert!(queue.queue_info().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_query_queue_per_user() -> Result<()> {
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.max_running_queries_per_user = 1;
+    conf.query.query_queue_timeout = 200;
+    let queue = QueryQueue::create(&conf);
+
+    let _permit1 = queue.acquire("q1", "u1", None, "SELECT 1").await?;
+    let _permit2 = queue.acquire("q2", "u2", None, "SELECT 2").await?;
+
+    let handle = {
+        let queue = queue.clone();
+        tokio::spawn(async move { queue.acquire("q3", "u1", None, "SELECT 3").await })
+    };
+    while queue.queue_info().len() < 3 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    // The queries of the other users are not blocked by the queued ones.
+    let _permit4 = queue.acquire("q4", "u3", None, "SELECT 4").await?;
+    assert_eq!(queue.queue_info().len(), 4);
+
+    let res = handle.await.unwrap();
+    assert_eq!(res.err().unwrap().code(), 1109);
+    assert_eq!(queue.queue_info().len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_query_queue_per_workload_group() -> Result<()> {
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.query_queue_timeout = 200;
+    let queue = QueryQueue::create(&conf);
+    let group = WorkloadGroup {
+        name: "etl".to_string(),
+        max_concurrency: 1,
+        ..Default::default()
+    };
+
+    let permit1 = queue.acquire("q1", "u1", Some(&group), "SELECT 1").await?;
+
+    // The other user of the group waits for the running query of the group.
+    let handle = {
+        let queue = queue.clone();
+        let group = group.clone();
+        tokio::spawn(async move { queue.acquire("q2", "u2", Some(&group), "SELECT 2").await })
+    };
+    while queue.queue_info().len() < 2 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    // The queries out of the group are not limited.
+    let _permit3 = queue.acquire("q3", "u2", None, "SELECT 3").await?;
+
+    drop(permit1);
+    let _permit2 = handle.await.unwrap()?;
+    assert_eq!(queue.queue_info().len(), 2);
+
+    Ok(())
+}
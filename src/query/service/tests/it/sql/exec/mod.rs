@@ -22,3 +22,38 @@ pub fn test_format_field_name() {
     let (decoded_name, decoded_index) = decode_field_name(field_name.as_str()).unwrap();
     assert!(decoded_name == display_name && decoded_index == index);
 }
+
+#[test]
+pub fn test_extract_common_subexpressions() {
+    use common_datavalues::prelude::*;
+    use databend_query::sql::executor::CommonSubexpressions;
+    use databend_query::sql::executor::PhysicalScalar;
+
+    let plus = |left: PhysicalScalar, right: PhysicalScalar| PhysicalScalar::Function {
+        name: "plus".to_string(),
+        args: vec![(left, u64::to_data_type()), (right, u64::to_data_type())],
+        return_type: u64::to_data_type(),
+    };
+    let variable = |column_id: &str| PhysicalScalar::Variable {
+        column_id: column_id.to_string(),
+        data_type: u64::to_data_type(),
+    };
+    let common = plus(variable("0"), variable("1"));
+
+    // (a + b) + (a + b), a + b
+    let subexpressions =
+        CommonSubexpressions::extract(&[plus(common.clone(), common.clone()), common.clone()]);
+    assert_eq!(subexpressions.common, vec![(
+        common,
+        "_common_subexpression_0".to_string()
+    )]);
+    let shared = variable("_common_subexpression_0");
+    assert_eq!(subexpressions.scalars, vec![
+        plus(shared.clone(), shared.clone()),
+        shared,
+    ]);
+
+    // Nothing is repeated
+    let subexpressions = CommonSubexpressions::extract(&[plus(variable("0"), variable("0"))]);
+    assert!(subexpressions.is_empty());
+}
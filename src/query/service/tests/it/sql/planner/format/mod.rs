@@ -142,6 +142,7 @@ fn test_format() {
                     limit: None,
                     order_by: None,
                     prewhere: None,
+                    sample: None,
                 }
                 .into(),
             ),
@@ -154,6 +155,7 @@ fn test_format() {
                 limit: None,
                 order_by: None,
                 prewhere: None,
+                sample: None,
             }
             .into(),
         ),
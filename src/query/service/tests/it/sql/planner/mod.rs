@@ -13,4 +13,5 @@
 // limitations under the License.
 
 mod format;
+mod optimizer;
 mod semantic;
@@ -0,0 +1,153 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::scalars::FunctionContext;
+use databend_query::sql::optimizer::ScalarRewriter;
+use databend_query::sql::optimizer::ScalarRule;
+use databend_query::sql::plans::AndExpr;
+use databend_query::sql::plans::BoundColumnRef;
+use databend_query::sql::plans::CastExpr;
+use databend_query::sql::plans::ComparisonExpr;
+use databend_query::sql::plans::ComparisonOp;
+use databend_query::sql::plans::ConstantExpr;
+use databend_query::sql::plans::FunctionCall;
+use databend_query::sql::plans::Scalar;
+use databend_query::sql::ColumnBinding;
+use databend_query::sql::Visibility;
+
+fn column(index: usize) -> Scalar {
+    BoundColumnRef {
+        column: ColumnBinding {
+            database_name: None,
+            table_name: None,
+            column_name: format!("c{}", index),
+            index,
+            data_type: Box::new(u64::to_data_type()),
+            visibility: Visibility::Visible,
+        },
+    }
+    .into()
+}
+
+fn constant(value: DataValue, data_type: DataTypeImpl) -> Scalar {
+    ConstantExpr {
+        value,
+        data_type: Box::new(data_type),
+    }
+    .into()
+}
+
+fn equal(left: Scalar, right: Scalar) -> Scalar {
+    ComparisonExpr {
+        op: ComparisonOp::Equal,
+        left: Box::new(left),
+        right: Box::new(right),
+        return_type: Box::new(BooleanType::new_impl()),
+    }
+    .into()
+}
+
+fn not(argument: Scalar) -> Scalar {
+    FunctionCall {
+        arguments: vec![argument],
+        func_name: "not".to_string(),
+        arg_types: vec![BooleanType::new_impl()],
+        return_type: Box::new(BooleanType::new_impl()),
+    }
+    .into()
+}
+
+#[test]
+fn test_scalar_rewriter_default_rules() -> Result<()> {
+    let rewriter = ScalarRewriter::with_default_rules(FunctionContext::default());
+    let predicate = equal(
+        column(0),
+        constant(DataValue::UInt64(1), u64::to_data_type()),
+    );
+
+    // TRUE AND CAST(c0 AS UInt64) = 0 + 1
+    let scalar: Scalar = AndExpr {
+        left: Box::new(constant(DataValue::Boolean(true), BooleanType::new_impl())),
+        right: Box::new(equal(
+            CastExpr {
+                argument: Box::new(column(0)),
+                from_type: Box::new(u64::to_data_type()),
+                target_type: Box::new(u64::to_data_type()),
+            }
+            .into(),
+            FunctionCall {
+                arguments: vec![
+                    constant(DataValue::UInt64(0), u64::to_data_type()),
+                    constant(DataValue::UInt64(1), u64::to_data_type()),
+                ],
+                func_name: "plus".to_string(),
+                arg_types: vec![u64::to_data_type(), u64::to_data_type()],
+                return_type: Box::new(u64::to_data_type()),
+            }
+            .into(),
+        )),
+        return_type: Box::new(BooleanType::new_impl()),
+    }
+    .into();
+    assert_eq!(rewriter.rewrite(&scalar)?, predicate);
+
+    // NOT NOT c0 = 1
+    assert_eq!(rewriter.rewrite(&not(not(predicate.clone())))?, predicate);
+
+    // NOT c0 = 1
+    let expected: Scalar = ComparisonExpr {
+        op: ComparisonOp::NotEqual,
+        left: Box::new(column(0)),
+        right: Box::new(constant(DataValue::UInt64(1), u64::to_data_type())),
+        return_type: Box::new(BooleanType::new_impl()),
+    }
+    .into();
+    assert_eq!(rewriter.rewrite(&not(predicate))?, expected);
+
+    Ok(())
+}
+
+/// Replace the column 0 by the column 1.
+struct RuleReplaceColumn;
+
+impl ScalarRule for RuleReplaceColumn {
+    fn name(&self) -> &'static str {
+        "ReplaceColumn"
+    }
+
+    fn apply(&self, scalar: &Scalar, _func_ctx: &FunctionContext) -> Result<Option<Scalar>> {
+        match scalar {
+            Scalar::BoundColumnRef(column_ref) if column_ref.column.index == 0 => {
+                Ok(Some(column(1)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[test]
+fn test_scalar_rewriter_register() -> Result<()> {
+    let mut rewriter = ScalarRewriter::new(FunctionContext::default());
+    let scalar = not(equal(column(0), column(1)));
+    assert_eq!(rewriter.rewrite(&scalar)?, scalar);
+
+    rewriter.register(Arc::new(RuleReplaceColumn));
+    assert_eq!(rewriter.rewrite(&scalar)?, not(equal(column(1), column(1))));
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_fuse_meta::caches::DataBlockCache;
+
+#[test]
+fn test_data_block_cache_disk_tier() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let field = DataField::new("a", i32::to_data_type());
+    let column = Series::from_data(vec![1i32, 2, 3]);
+
+    // the memory tier can only hold a few bytes, the column is served from disk
+    let cache = DataBlockCache::create(1, dir.path(), 1024 * 1024)?;
+    assert!(cache.get("t/b1-[0]-4-20", &field).is_none());
+
+    cache.put("t/b1-[0]-4-20".to_string(), &field, column.clone());
+
+    // the column is written to disk in background
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let cached = loop {
+        match cache.get("t/b1-[0]-4-20", &field) {
+            Some(cached) => break cached,
+            None if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(10)),
+            None => panic!("column is not written to the disk tier"),
+        }
+    };
+    assert_eq!(column, cached);
+    assert!(cache.get("t/b2-[0]-4-20", &field).is_none());
+    Ok(())
+}
+
+#[test]
+fn test_data_block_cache_memory_only() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let field = DataField::new("a", i32::to_data_type());
+    let column = Series::from_data(vec![1i32, 2, 3]);
+
+    // the disk tier is disabled, nothing is written to the directory
+    let cache = DataBlockCache::create(1024 * 1024, dir.path(), 0)?;
+    cache.put("t/b1-[0]-4-20".to_string(), &field, column.clone());
+    assert_eq!(Some(column), cache.get("t/b1-[0]-4-20", &field));
+    assert_eq!(0, std::fs::read_dir(dir.path())?.count());
+    Ok(())
+}
@@ -24,6 +24,7 @@ use common_fuse_meta::meta::Versioned;
 use databend_query::sessions::TableContext;
 use databend_query::storages::fuse::io::write_block;
 use databend_query::storages::fuse::io::BlockReader;
+use databend_query::storages::fuse::io::MultipartOptions;
 use databend_query::storages::fuse::io::TableMetaLocationGenerator;
 use opendal::ops::OpRead;
 use opendal::ops::OpWrite;
@@ -157,7 +158,7 @@ async fn test_block_writer_retry() -> Result<()> {
     let mock = Arc::new(Mock::with_exception(errors));
     let op = Operator::new(mock.clone());
     let block = DataBlock::empty();
-    let r = write_block(block, &op, "loc").await;
+    let r = write_block(block, &op, "loc", &MultipartOptions::default()).await;
     assert!(r.is_err());
     let e = r.unwrap_err();
     assert_eq!(ErrorCode::storage_other_code(), e.code());
@@ -13,3 +13,4 @@
 //  limitations under the License.
 
 mod snapshot;
+mod snapshot_statistics;
@@ -0,0 +1,85 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_fuse_meta::meta::Histogram;
+use common_fuse_meta::meta::HyperLogLog;
+
+#[test]
+fn test_hyper_log_log_estimate() {
+    let mut sketch = HyperLogLog::default();
+    assert_eq!(sketch.estimate(), 0);
+
+    for i in 0..10u64 {
+        // duplicates are not counted
+        sketch.add(&i);
+        sketch.add(&i);
+    }
+    assert_eq!(sketch.estimate(), 10);
+
+    let mut sketch = HyperLogLog::default();
+    for i in 0..100_000u64 {
+        sketch.add(&i);
+    }
+    let estimate = sketch.estimate() as f64;
+    assert!(
+        (estimate - 100_000.0).abs() < 100_000.0 * 0.05,
+        "{estimate}"
+    );
+}
+
+#[test]
+fn test_hyper_log_log_merge() {
+    let mut left = HyperLogLog::default();
+    let mut right = HyperLogLog::default();
+    for i in 0..1000u64 {
+        left.add(&i);
+        right.add(&(i + 500));
+    }
+
+    let estimate = left.merge(&right).estimate() as f64;
+    assert!((estimate - 1500.0).abs() < 1500.0 * 0.05, "{estimate}");
+}
+
+#[test]
+fn test_histogram_from_sorted_values() {
+    let values: Vec<f64> = (0..100).map(|v| v as f64).collect();
+    let histogram = Histogram::from_sorted_values(&values, 10);
+    assert_eq!(histogram.buckets.len(), 10);
+    assert_eq!(histogram.total(), 100.0);
+    assert_eq!(histogram.count_le(49.0), 50.0);
+    assert_eq!(histogram.count_le(-1.0), 0.0);
+    assert_eq!(histogram.count_le(99.0), 100.0);
+
+    // the repeated values stay in one bucket
+    let values = vec![1.0; 50];
+    let histogram = Histogram::from_sorted_values(&values, 10);
+    assert_eq!(histogram.buckets.len(), 1);
+    assert_eq!(histogram.count_le(1.0), 50.0);
+    assert_eq!(histogram.count_le(0.0), 0.0);
+}
+
+#[test]
+fn test_histogram_merge() {
+    let left: Vec<f64> = (0..100).map(|v| v as f64).collect();
+    let right: Vec<f64> = (100..400).map(|v| v as f64).collect();
+    let left = Histogram::from_sorted_values(&left, 10);
+    let right = Histogram::from_sorted_values(&right, 10);
+
+    let merged = left.merge(&right, 10);
+    assert!(merged.buckets.len() <= 10);
+    assert!((merged.total() - 400.0).abs() < 1e-6);
+    // a quarter of the values are below 100
+    let below = merged.count_le(99.0);
+    assert!((below - 100.0).abs() < 10.0, "{below}");
+}
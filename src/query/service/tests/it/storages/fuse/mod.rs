@@ -12,11 +12,13 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod data_block_cache;
 mod io;
 mod meta;
 mod misc;
 mod operations;
 mod pruning;
+mod schema_history;
 mod statistics;
 mod table;
 mod table_functions;
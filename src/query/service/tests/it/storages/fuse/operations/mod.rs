@@ -19,3 +19,4 @@ mod optimize;
 mod purge_drop;
 mod purge_truncate;
 mod read_plan;
+mod truncate;
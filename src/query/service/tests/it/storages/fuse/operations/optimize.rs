@@ -13,7 +13,10 @@
 //  limitations under the License.
 
 use common_base::base::tokio;
+use common_catalog::table::CompactTarget;
 use common_exception::Result;
+use databend_query::sessions::TableContext;
+use databend_query::storages::fuse::FuseTable;
 use futures::TryStreamExt;
 
 use crate::storages::fuse::table_test_fixture::append_sample_data;
@@ -109,3 +112,117 @@ async fn test_fuse_snapshot_optimize_compact() -> Result<()> {
     )
     .await
 }
+
+#[tokio::test]
+async fn test_fuse_snapshot_optimize_compact_segment() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    fixture.create_default_table().await?;
+
+    // insert 3 blocks, 1 segment for each
+    for _ in 0..3 {
+        let table = fixture.latest_default_table().await?;
+        let stream = TestFixture::gen_sample_blocks_stream(1, 1);
+
+        let blocks = stream.try_collect().await?;
+        fixture
+            .append_commit_blocks(table.clone(), blocks, false, true)
+            .await?;
+    }
+
+    // optimize compact segment
+    let qry = format!("optimize table {}.{} compact segment", db, tbl);
+    execute_command(fixture.ctx(), qry.as_str()).await?;
+
+    // the segments are merged, but the blocks are kept as they are
+    let expected = vec![
+        "+---------------+-------------+",
+        "| segment_count | block_count |",
+        "+---------------+-------------+",
+        "| 1             | 3           |",
+        "+---------------+-------------+",
+    ];
+    let qry = format!(
+        "select segment_count, block_count from fuse_snapshot('{}', '{}') order by timestamp desc limit 1",
+        db, tbl
+    );
+
+    expects_ok(
+        "segments_should_be_merged",
+        execute_query(fixture.ctx(), qry.as_str()).await,
+        expected,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_fuse_snapshot_optimize_purge_with_retention() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+
+    // 2 snapshots, both within the retention period
+    do_insertions(&fixture).await?;
+
+    ctx.get_settings()
+        .set_settings("retention_period".to_string(), "1".to_string(), false)?;
+    let qry = format!("optimize table {}.{} purge", db, tbl);
+    execute_command(ctx.clone(), &qry).await?;
+
+    // nothing should be purged
+    check_data_dir(&fixture, "purge with retention", 2, 2, 2, 2).await;
+
+    ctx.get_settings()
+        .set_settings("retention_period".to_string(), "0".to_string(), false)?;
+    execute_command(ctx, &qry).await?;
+
+    check_data_dir(&fixture, "purge without retention", 1, 1, 1, 1).await;
+    history_should_have_only_one_item(&fixture, "purge without retention").await
+}
+
+#[tokio::test]
+async fn test_fuse_table_compaction_target() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // no snapshot, nothing to compact
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    assert_eq!(fuse_table.compaction_target(ctx.clone(), 1).await?, None);
+
+    // insert 3 small blocks, 1 segment for each
+    for _ in 0..3 {
+        let table = fixture.latest_default_table().await?;
+        let stream = TestFixture::gen_sample_blocks_stream(1, 1);
+
+        let blocks = stream.try_collect().await?;
+        fixture
+            .append_commit_blocks(table.clone(), blocks, false, true)
+            .await?;
+    }
+
+    // the data fits in 1 block, 2 blocks more than needed
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    assert_eq!(
+        fuse_table.compaction_target(ctx.clone(), 2).await?,
+        Some(CompactTarget::Blocks)
+    );
+    assert_eq!(fuse_table.compaction_target(ctx.clone(), 3).await?, None);
+
+    // nothing to compact once the blocks are compacted
+    let qry = format!(
+        "optimize table {}.{} compact",
+        fixture.default_db_name(),
+        fixture.default_table_name()
+    );
+    execute_command(ctx.clone(), &qry).await?;
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    assert_eq!(fuse_table.compaction_target(ctx, 1).await?, None);
+
+    Ok(())
+}
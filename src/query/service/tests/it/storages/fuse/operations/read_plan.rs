@@ -105,7 +105,7 @@ fn test_to_partitions() -> Result<()> {
     let column_leafs = ColumnLeaves { column_leaves };
 
     // CASE I:  no projection
-    let (s, parts) = FuseTable::to_partitions(&blocks_metas, &column_leafs, None);
+    let (s, parts) = FuseTable::to_partitions(&blocks_metas, &column_leafs, 0, None);
     assert_eq!(parts.len(), num_of_block as usize);
     let expected_block_size: u64 = cols_metas.iter().map(|(_, col_meta)| col_meta.len).sum();
     assert_eq!(expected_block_size * num_of_block, s.read_bytes as u64);
@@ -132,9 +132,11 @@ fn test_to_partitions() -> Result<()> {
         limit: None,
         order_by: vec![],
         prewhere: None,
+        sample: None,
+        virtual_columns: vec![],
     });
 
-    let (stats, parts) = FuseTable::to_partitions(&blocks_metas, &column_leafs, push_down);
+    let (stats, parts) = FuseTable::to_partitions(&blocks_metas, &column_leafs, 0, push_down);
     assert_eq!(parts.len(), num_of_block as usize);
     assert_eq!(expected_block_size * num_of_block, stats.read_bytes as u64);
 
@@ -174,6 +176,8 @@ async fn test_fuse_table_exact_statistic() -> Result<()> {
             prewhere: None,
             limit: None,
             order_by: vec![],
+            sample: None,
+            virtual_columns: vec![],
         };
         let (stats, parts) = table.read_partitions(ctx.clone(), Some(push_downs)).await?;
         assert_eq!(stats.read_rows, num_blocks * rows_per_block);
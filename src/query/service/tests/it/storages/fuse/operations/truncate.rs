@@ -0,0 +1,64 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_base::base::tokio;
+use common_cache::Cache;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_fuse_meta::caches::CacheManager;
+use databend_query::sessions::TableContext;
+use databend_query::storages::fuse::FuseTable;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::append_sample_data;
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_truncate_snapshot_cached() -> Result<()> {
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.table_cache_enabled = true;
+    let fixture = TestFixture::new_with_config(conf).await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    append_sample_data(1, &fixture).await?;
+
+    let qry = format!("truncate table {}.{}", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the snapshot written by the truncation is cached
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let snapshot_loc = fuse_table.snapshot_loc().unwrap();
+    let snapshot_cache = CacheManager::instance().get_table_snapshot_cache().unwrap();
+    assert!(snapshot_cache.read().await.contains(&snapshot_loc));
+
+    // remove the snapshot file, the following read can only be served by the cache
+    let operator = ctx.get_storage_operator()?;
+    operator.object(&snapshot_loc).delete().await?;
+
+    let qry = format!("select * from {}.{}", db, tbl);
+    let blocks = execute_query(ctx.clone(), qry.as_str())
+        .await?
+        .try_collect::<Vec<DataBlock>>()
+        .await?;
+    let num_rows: usize = blocks.iter().map(|b| b.num_rows()).sum();
+    assert_eq!(0, num_rows);
+
+    Ok(())
+}
@@ -175,12 +175,22 @@ async fn test_block_pruner() -> Result<()> {
     }];
     e4.limit = Some(4);
 
+    // Sort asc Limit, the rows of more than one block are needed
+    let mut e5 = e3.clone();
+    e5.limit = Some(row_per_block + 5);
+
+    // Sort asc Limit with filters, nothing is pruned
+    let mut e6 = e3.clone();
+    e6.filters = vec![col("a").gt(lit(0u64))];
+
     let extras = vec![
         (None, num_blocks, num_blocks * row_per_block),
         (Some(e1), 0, 0),
         (Some(e2), b2, b2 * row_per_block),
-        (Some(e3), 3, 3 * row_per_block),
-        (Some(e4), 4, 4 * row_per_block),
+        (Some(e3), 1, row_per_block),
+        (Some(e4), 1, row_per_block),
+        (Some(e5), 2, 2 * row_per_block),
+        (Some(e6), num_blocks, num_blocks * row_per_block),
     ];
 
     for (extra, expected_blocks, expected_rows) in extras {
@@ -0,0 +1,135 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_catalog::table::AlterColumnAction;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::storages::fuse::SchemaHistory;
+
+#[test]
+fn test_schema_history_evolve() -> Result<()> {
+    let schema = DataSchema::new(vec![
+        DataField::new("a", i32::to_data_type()),
+        DataField::new("b", i16::to_data_type()),
+    ]);
+    assert_eq!(0, SchemaHistory::version_of(&schema)?);
+
+    // version 1: add c with default 10
+    let schema = SchemaHistory::from_schema(&schema)?.evolve(&schema, &AlterColumnAction::Add {
+        field: DataField::new("c", i32::to_data_type()),
+        default: DataValue::Int64(10),
+        comment: "".to_string(),
+    })?;
+    // version 2: rename a to x
+    let schema =
+        SchemaHistory::from_schema(&schema)?.evolve(&schema, &AlterColumnAction::Rename {
+            column: "a".to_string(),
+            new_column: "x".to_string(),
+        })?;
+    // version 3: widen b to int
+    let schema =
+        SchemaHistory::from_schema(&schema)?.evolve(&schema, &AlterColumnAction::Modify {
+            field: DataField::new("b", i32::to_data_type()),
+        })?;
+    // version 4: drop x
+    let schema =
+        SchemaHistory::from_schema(&schema)?.evolve(&schema, &AlterColumnAction::Drop {
+            column: "x".to_string(),
+        })?;
+
+    let history = SchemaHistory::from_schema(&schema)?;
+    assert_eq!(4, history.version());
+    assert_eq!(vec!["b", "c"], field_names(&schema));
+    assert_eq!(schema.fields(), history.schema_of(4)?.fields());
+
+    // the blocks of version 0 have a and b, c is filled with its default
+    assert_eq!(vec!["a", "b"], field_names(&history.schema_of(0)?));
+    assert_eq!(vec![Some(1), None], history.positions_in(0)?);
+    assert_eq!(DataValue::Int64(10), history.default_value(1)?);
+    assert_eq!(vec![Some(1), Some(2)], history.positions_in(2)?);
+
+    let err = history.schema_of(5).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::logical_error_code());
+    Ok(())
+}
+
+#[test]
+fn test_schema_history_evolve_errors() -> Result<()> {
+    let schema = DataSchema::new(vec![DataField::new("a", i32::to_data_type())]);
+    let history = SchemaHistory::from_schema(&schema)?;
+
+    let cases = vec![
+        AlterColumnAction::Add {
+            field: DataField::new("a", i64::to_data_type()),
+            default: DataValue::Int64(0),
+            comment: "".to_string(),
+        },
+        AlterColumnAction::Drop {
+            column: "a".to_string(),
+        },
+        AlterColumnAction::Modify {
+            field: DataField::new("a", i16::to_data_type()),
+        },
+    ];
+    for action in cases {
+        let err = history.clone().evolve(&schema, &action).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::semantic_error_code());
+    }
+
+    let err = history
+        .evolve(&schema, &AlterColumnAction::Rename {
+            column: "b".to_string(),
+            new_column: "c".to_string(),
+        })
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::unknown_column_code());
+    Ok(())
+}
+
+#[test]
+fn test_schema_history_is_widening() {
+    let cases = vec![
+        (i8::to_data_type(), i64::to_data_type(), true),
+        (u16::to_data_type(), u32::to_data_type(), true),
+        (f32::to_data_type(), f64::to_data_type(), true),
+        (
+            i32::to_data_type(),
+            wrap_nullable(&i32::to_data_type()),
+            true,
+        ),
+        (i32::to_data_type(), i16::to_data_type(), false),
+        (u8::to_data_type(), i16::to_data_type(), false),
+        (i32::to_data_type(), f64::to_data_type(), false),
+        (
+            wrap_nullable(&i32::to_data_type()),
+            i64::to_data_type(),
+            false,
+        ),
+        (Vu8::to_data_type(), i64::to_data_type(), false),
+    ];
+    for (from_type, to_type, expected) in cases {
+        assert_eq!(
+            expected,
+            SchemaHistory::is_widening(&from_type, &to_type),
+            "{:?} to {:?}",
+            from_type,
+            to_type
+        );
+    }
+}
+
+fn field_names(schema: &DataSchema) -> Vec<&str> {
+    schema.fields().iter().map(|f| f.name().as_str()).collect()
+}
@@ -52,6 +52,7 @@ use databend_query::storages::Table;
 use databend_query::storages::TableStreamReadWrap;
 use databend_query::storages::ToReadDataSourcePlan;
 use databend_query::table_functions::TableArgs;
+use databend_query::Config;
 use futures::TryStreamExt;
 use parking_lot::Mutex;
 use tempfile::TempDir;
@@ -70,8 +71,11 @@ pub struct TestFixture {
 
 impl TestFixture {
     pub async fn new() -> TestFixture {
+        Self::new_with_config(crate::tests::ConfigBuilder::create().config()).await
+    }
+
+    pub async fn new_with_config(mut conf: Config) -> TestFixture {
         let tmp_dir = TempDir::new().unwrap();
-        let mut conf = crate::tests::ConfigBuilder::create().config();
 
         // make sure we are suing `fs` storage
         conf.storage.params = StorageParams::Fs(StorageFsConfig {
@@ -79,6 +79,8 @@ async fn test_memorytable() -> Result<()> {
                             limit: None,
                             order_by: vec![],
                             prewhere: None,
+                            sample: None,
+                            virtual_columns: vec![],
                         }
                     })
                 })
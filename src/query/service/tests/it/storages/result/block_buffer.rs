@@ -0,0 +1,80 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::Series;
+use common_datavalues::prelude::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::ToDataType;
+use common_exception::Result;
+use databend_query::storages::result::block_buffer::BlockBuffer;
+use databend_query::storages::result::block_buffer::BlockBufferWriter;
+use databend_query::storages::result::block_buffer::BlockBufferWriterMemOnly;
+
+fn create_block(rows: u32) -> DataBlock {
+    let schema = Arc::new(DataSchema::new(vec![DataField::new(
+        "number",
+        u32::to_data_type(),
+    )]));
+    DataBlock::create(schema, vec![Series::from_data(
+        (0..rows).collect::<Vec<u32>>(),
+    )])
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_block_buffer_backpressure() -> Result<()> {
+    let buffer = BlockBuffer::new(4, usize::MAX);
+    let mut writer = BlockBufferWriterMemOnly(buffer.clone());
+
+    // A block larger than the limits is taken by an empty buffer.
+    writer.push(create_block(5)).await?;
+
+    let pushing = tokio::spawn(async move {
+        writer.push(create_block(2)).await?;
+        writer.push(create_block(2)).await?;
+        writer.stop_push(false).await
+    });
+
+    // The writer waits for the blocks to be popped.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!pushing.is_finished());
+
+    let (block, done) = buffer.pop().await?;
+    assert_eq!((block.map(|b| b.num_rows()), done), (Some(5), false));
+
+    pushing.await.unwrap()?;
+    let (block, done) = buffer.pop().await?;
+    assert_eq!((block.map(|b| b.num_rows()), done), (Some(2), false));
+    let (block, done) = buffer.pop().await?;
+    assert_eq!((block.map(|b| b.num_rows()), done), (Some(2), true));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_block_buffer_stop_pop_releases_writer() -> Result<()> {
+    let buffer = BlockBuffer::new(4, usize::MAX);
+    let mut writer = BlockBufferWriterMemOnly(buffer.clone());
+    writer.push(create_block(4)).await?;
+
+    let pushing = tokio::spawn(async move { writer.push(create_block(4)).await });
+    buffer.stop_pop().await;
+
+    pushing.await.unwrap()?;
+    Ok(())
+}
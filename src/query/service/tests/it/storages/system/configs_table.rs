@@ -43,99 +43,151 @@ async fn test_configs_table() -> Result<()> {
     assert_eq!(block.num_columns(), 4);
 
     let expected = vec![
-        "+---------+--------------------------------------+--------------------------------+-------------+",
-        "| group   | name                                 | value                          | description |",
-        "+---------+--------------------------------------+--------------------------------+-------------+",
-        "| log     | dir                                  | ./.databend/logs               |             |",
-        "| log     | file.dir                             | ./.databend/logs               |             |",
-        "| log     | file.level                           | DEBUG                          |             |",
-        "| log     | file.on                              | true                           |             |",
-        "| log     | level                                | DEBUG                          |             |",
-        "| log     | query_enabled                        | false                          |             |",
-        "| log     | stderr.level                         | DEBUG                          |             |",
-        "| log     | stderr.on                            | true                           |             |",
-        "| meta    | address                              |                                |             |",
-        "| meta    | auto_sync_interval                   | 10                             |             |",
-        "| meta    | client_timeout_in_second             | 10                             |             |",
-        "| meta    | embedded_dir                         | ./.databend/meta_embedded      |             |",
-        "| meta    | endpoints                            |                                |             |",
-        "| meta    | password                             |                                |             |",
-        "| meta    | rpc_tls_meta_server_root_ca_cert     |                                |             |",
-        "| meta    | rpc_tls_meta_service_domain_name     | localhost                      |             |",
-        "| meta    | username                             | root                           |             |",
-        "| query   | admin_api_address                    | 127.0.0.1:8080                 |             |",
-        "| query   | api_tls_server_cert                  |                                |             |",
-        "| query   | api_tls_server_key                   |                                |             |",
-        "| query   | api_tls_server_root_ca_cert          |                                |             |",
-        "| query   | async_insert_busy_timeout            | 200                            |             |",
-        "| query   | async_insert_max_data_size           | 10000                          |             |",
-        "| query   | async_insert_stale_timeout           | 0                              |             |",
-        "| query   | clickhouse_handler_host              | 127.0.0.1                      |             |",
-        "| query   | clickhouse_handler_port              | 9000                           |             |",
-        "| query   | clickhouse_http_handler_host         | 127.0.0.1                      |             |",
-        "| query   | clickhouse_http_handler_port         | 8124                           |             |",
-        "| query   | cluster_id                           |                                |             |",
-        "| query   | database_engine_github_enabled       | true                           |             |",
-        "| query   | flight_api_address                   | 127.0.0.1:9090                 |             |",
-        "| query   | http_handler_host                    | 127.0.0.1                      |             |",
-        "| query   | http_handler_port                    | 8000                           |             |",
-        "| query   | http_handler_result_timeout_millis   | 10000                          |             |",
-        "| query   | http_handler_tls_server_cert         |                                |             |",
-        "| query   | http_handler_tls_server_key          |                                |             |",
-        "| query   | http_handler_tls_server_root_ca_cert |                                |             |",
-        "| query   | jwt_key_file                         |                                |             |",
-        "| query   | management_mode                      | false                          |             |",
-        "| query   | max_active_sessions                  | 256                            |             |",
-        "| query   | max_query_log_size                   | 10000                          |             |",
-        "| query   | metric_api_address                   | 127.0.0.1:7070                 |             |",
-        "| query   | mysql_handler_host                   | 127.0.0.1                      |             |",
-        "| query   | mysql_handler_port                   | 3307                           |             |",
-        "| query   | num_cpus                             | 0                              |             |",
-        "| query   | rpc_tls_query_server_root_ca_cert    |                                |             |",
-        "| query   | rpc_tls_query_service_domain_name    | localhost                      |             |",
-        "| query   | rpc_tls_server_cert                  |                                |             |",
-        "| query   | rpc_tls_server_key                   |                                |             |",
-        "| query   | table_cache_block_meta_count         | 102400                         |             |",
-        "| query   | table_cache_enabled                  | false                          |             |",
-        "| query   | table_cache_segment_count            | 10240                          |             |",
-        "| query   | table_cache_snapshot_count           | 256                            |             |",
-        "| query   | table_disk_cache_mb_size             | 1024                           |             |",
-        "| query   | table_disk_cache_root                | _cache                         |             |",
-        "| query   | table_engine_memory_enabled          | true                           |             |",
-        "| query   | table_memory_cache_mb_size           | 256                            |             |",
-        "| query   | tenant_id                            | test                           |             |",
-        "| query   | users                                |                                |             |",
-        "| query   | wait_timeout_mills                   | 5000                           |             |",
-        "| storage | allow_insecure                       | false                          |             |",
-        "| storage | azblob.account_key                   |                                |             |",
-        "| storage | azblob.account_name                  |                                |             |",
-        "| storage | azblob.container                     |                                |             |",
-        "| storage | azblob.endpoint_url                  |                                |             |",
-        "| storage | azblob.root                          |                                |             |",
-        "| storage | fs.data_path                         | _data                          |             |",
-        "| storage | gcs.bucket                           |                                |             |",
-        "| storage | gcs.credential                       |                                |             |",
-        "| storage | gcs.endpoint_url                     | https://storage.googleapis.com |             |",
-        "| storage | gcs.root                             |                                |             |",
-        "| storage | hdfs.name_node                       |                                |             |",
-        "| storage | hdfs.root                            |                                |             |",
-        "| storage | num_cpus                             | 0                              |             |",
-        "| storage | obs.access_key_id                    |                                |             |",
-        "| storage | obs.bucket                           |                                |             |",
-        "| storage | obs.endpoint_url                     |                                |             |",
-        "| storage | obs.root                             |                                |             |",
-        "| storage | obs.secret_access_key                |                                |             |",
-        "| storage | s3.access_key_id                     |                                |             |",
-        "| storage | s3.bucket                            |                                |             |",
-        "| storage | s3.enable_virtual_host_style         | false                          |             |",
-        "| storage | s3.endpoint_url                      | https://s3.amazonaws.com       |             |",
-        "| storage | s3.master_key                        |                                |             |",
-        "| storage | s3.region                            |                                |             |",
-        "| storage | s3.root                              |                                |             |",
-        "| storage | s3.secret_access_key                 |                                |             |",
-        "| storage | s3.security_token                    |                                |             |",
-        "| storage | type                                 | fs                             |             |",
-        "+---------+--------------------------------------+--------------------------------+-------------+",
+        "+---------+--------------------------------------------+--------------------------------+-------------+",
+        "| group   | name                                       | value                          | description |",
+        "+---------+--------------------------------------------+--------------------------------+-------------+",
+        "| log     | dir                                        | ./.databend/logs               |             |",
+        "| log     | file.dir                                   | ./.databend/logs               |             |",
+        "| log     | file.level                                 | DEBUG                          |             |",
+        "| log     | file.on                                    | true                           |             |",
+        "| log     | level                                      | DEBUG                          |             |",
+        "| log     | query_enabled                              | false                          |             |",
+        "| log     | stderr.level                               | DEBUG                          |             |",
+        "| log     | stderr.on                                  | true                           |             |",
+        "| meta    | address                                    |                                |             |",
+        "| meta    | auto_sync_interval                         | 10                             |             |",
+        "| meta    | client_timeout_in_second                   | 10                             |             |",
+        "| meta    | embedded_dir                               | ./.databend/meta_embedded      |             |",
+        "| meta    | endpoints                                  |                                |             |",
+        "| meta    | password                                   |                                |             |",
+        "| meta    | rpc_tls_meta_server_root_ca_cert           |                                |             |",
+        "| meta    | rpc_tls_meta_service_domain_name           | localhost                      |             |",
+        "| meta    | username                                   | root                           |             |",
+        "| query   | admin_api_address                          | 127.0.0.1:8080                 |             |",
+        "| query   | api_tls_server_cert                        |                                |             |",
+        "| query   | api_tls_server_key                         |                                |             |",
+        "| query   | api_tls_server_root_ca_cert                |                                |             |",
+        "| query   | async_insert_busy_timeout                  | 200                            |             |",
+        "| query   | async_insert_max_data_size                 | 10000                          |             |",
+        "| query   | async_insert_stale_timeout                 | 0                              |             |",
+        "| query   | audit_log_events                           | login                          |             |",
+        "| query   | audit_log_sinks                            | file                           |             |",
+        "| query   | audit_log_webhook_url                      |                                |             |",
+        "| query   | auto_compaction_interval                   | 60                             |             |",
+        "| query   | auto_compaction_off_peak_hours             |                                |             |",
+        "| query   | auto_compaction_threshold                  | 100                            |             |",
+        "| query   | clickhouse_handler_host                    | 127.0.0.1                      |             |",
+        "| query   | clickhouse_handler_port                    | 9000                           |             |",
+        "| query   | clickhouse_handler_tls_server_cert         |                                |             |",
+        "| query   | clickhouse_handler_tls_server_key          |                                |             |",
+        "| query   | clickhouse_handler_tls_server_root_ca_cert |                                |             |",
+        "| query   | clickhouse_http_handler_host               | 127.0.0.1                      |             |",
+        "| query   | clickhouse_http_handler_port               | 8124                           |             |",
+        "| query   | cluster_heartbeat_ttl                      | 60                             |             |",
+        "| query   | cluster_id                                 |                                |             |",
+        "| query   | database_engine_github_enabled             | true                           |             |",
+        "| query   | enable_auto_compaction                     | false                          |             |",
+        "| query   | enable_persistent_query_log                | false                          |             |",
+        "| query   | enable_udf_server                          | false                          |             |",
+        "| query   | flight_api_address                         | 127.0.0.1:9090                 |             |",
+        "| query   | http_handler_host                          | 127.0.0.1                      |             |",
+        "| query   | http_handler_port                          | 8000                           |             |",
+        "| query   | http_handler_result_timeout_millis         | 10000                          |             |",
+        "| query   | http_handler_tls_server_cert               |                                |             |",
+        "| query   | http_handler_tls_server_key                |                                |             |",
+        "| query   | http_handler_tls_server_root_ca_cert       |                                |             |",
+        "| query   | jwt_key_file                               |                                |             |",
+        "| query   | jwt_key_files                              |                                |             |",
+        "| query   | jwt_user_claim                             | sub                            |             |",
+        "| query   | ldap_bind_dn                               |                                |             |",
+        "| query   | ldap_group_attribute                       | memberOf                       |             |",
+        "| query   | ldap_role_mapping                          |                                |             |",
+        "| query   | ldap_search_base_dn                        |                                |             |",
+        "| query   | ldap_search_bind_dn                        |                                |             |",
+        "| query   | ldap_search_bind_password                  |                                |             |",
+        "| query   | ldap_search_filter                         | (uid={user})                   |             |",
+        "| query   | ldap_starttls                              | false                          |             |",
+        "| query   | ldap_url                                   |                                |             |",
+        "| query   | management_mode                            | false                          |             |",
+        "| query   | max_active_sessions                        | 256                            |             |",
+        "| query   | max_queued_queries                         | 0                              |             |",
+        "| query   | max_query_log_size                         | 10000                          |             |",
+        "| query   | max_running_queries                        | 0                              |             |",
+        "| query   | max_running_queries_per_user               | 0                              |             |",
+        "| query   | metric_api_address                         | 127.0.0.1:7070                 |             |",
+        "| query   | mysql_handler_host                         | 127.0.0.1                      |             |",
+        "| query   | mysql_handler_port                         | 3307                           |             |",
+        "| query   | mysql_handler_tls_server_cert              |                                |             |",
+        "| query   | mysql_handler_tls_server_key               |                                |             |",
+        "| query   | mysql_handler_tls_server_root_ca_cert      |                                |             |",
+        "| query   | num_cpus                                   | 0                              |             |",
+        "| query   | oidc_audience                              |                                |             |",
+        "| query   | oidc_client_id                             |                                |             |",
+        "| query   | oidc_client_secret                         |                                |             |",
+        "| query   | oidc_issuer                                |                                |             |",
+        "| query   | oidc_redirect_url                          |                                |             |",
+        "| query   | oidc_role_claim                            | groups                         |             |",
+        "| query   | oidc_role_mapping                          |                                |             |",
+        "| query   | oidc_scopes                                | openid email profile           |             |",
+        "| query   | oidc_user_claim                            | email                          |             |",
+        "| query   | postgres_handler_host                      | 127.0.0.1                      |             |",
+        "| query   | postgres_handler_port                      | 5433                           |             |",
+        "| query   | postgres_handler_tls_server_cert           |                                |             |",
+        "| query   | postgres_handler_tls_server_key            |                                |             |",
+        "| query   | query_log_flush_interval                   | 10                             |             |",
+        "| query   | query_log_retention_days                   | 30                             |             |",
+        "| query   | query_queue_timeout                        | 0                              |             |",
+        "| query   | rpc_tls_query_server_root_ca_cert          |                                |             |",
+        "| query   | rpc_tls_query_service_domain_name          | localhost                      |             |",
+        "| query   | rpc_tls_server_cert                        |                                |             |",
+        "| query   | rpc_tls_server_key                         |                                |             |",
+        "| query   | slow_query_threshold_ms                    | 0                              |             |",
+        "| query   | table_cache_block_meta_count               | 102400                         |             |",
+        "| query   | table_cache_enabled                        | false                          |             |",
+        "| query   | table_cache_segment_count                  | 10240                          |             |",
+        "| query   | table_cache_snapshot_count                 | 256                            |             |",
+        "| query   | table_data_block_disk_cache_enabled        | false                          |             |",
+        "| query   | table_data_block_memory_cache_mb_size      | 256                            |             |",
+        "| query   | table_disk_cache_mb_size                   | 1024                           |             |",
+        "| query   | table_disk_cache_root                      | _cache                         |             |",
+        "| query   | table_engine_memory_enabled                | true                           |             |",
+        "| query   | table_memory_cache_mb_size                 | 256                            |             |",
+        "| query   | table_query_result_cache_mb_size           | 256                            |             |",
+        "| query   | task_scheduler_interval                    | 10                             |             |",
+        "| query   | tenant_id                                  | test                           |             |",
+        "| query   | udf_server_allow_list                      |                                |             |",
+        "| query   | users                                      |                                |             |",
+        "| query   | wait_timeout_mills                         | 5000                           |             |",
+        "| storage | allow_insecure                             | false                          |             |",
+        "| storage | azblob.account_key                         |                                |             |",
+        "| storage | azblob.account_name                        |                                |             |",
+        "| storage | azblob.container                           |                                |             |",
+        "| storage | azblob.endpoint_url                        |                                |             |",
+        "| storage | azblob.root                                |                                |             |",
+        "| storage | fs.data_path                               | _data                          |             |",
+        "| storage | gcs.bucket                                 |                                |             |",
+        "| storage | gcs.credential                             |                                |             |",
+        "| storage | gcs.endpoint_url                           | https://storage.googleapis.com |             |",
+        "| storage | gcs.root                                   |                                |             |",
+        "| storage | hdfs.name_node                             |                                |             |",
+        "| storage | hdfs.root                                  |                                |             |",
+        "| storage | num_cpus                                   | 0                              |             |",
+        "| storage | obs.access_key_id                          |                                |             |",
+        "| storage | obs.bucket                                 |                                |             |",
+        "| storage | obs.endpoint_url                           |                                |             |",
+        "| storage | obs.root                                   |                                |             |",
+        "| storage | obs.secret_access_key                      |                                |             |",
+        "| storage | s3.access_key_id                           |                                |             |",
+        "| storage | s3.bucket                                  |                                |             |",
+        "| storage | s3.enable_virtual_host_style               | false                          |             |",
+        "| storage | s3.endpoint_url                            | https://s3.amazonaws.com       |             |",
+        "| storage | s3.master_key                              |                                |             |",
+        "| storage | s3.region                                  |                                |             |",
+        "| storage | s3.root                                    |                                |             |",
+        "| storage | s3.secret_access_key                       |                                |             |",
+        "| storage | s3.security_token                          |                                |             |",
+        "| storage | type                                       | fs                             |             |",
+        "+---------+--------------------------------------------+--------------------------------+-------------+",
     ];
     common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
     Ok(())
@@ -172,104 +224,156 @@ async fn test_configs_table_redact() -> Result<()> {
     assert_eq!(block.num_columns(), 4);
 
     let endpoint_url_link = format!(
-        "| storage | s3.endpoint_url                      | {:<24}       |             |",
+        "| storage | s3.endpoint_url                            | {:<24}       |             |",
         mock_server.uri()
     );
 
     let expected = vec![
-        "+---------+--------------------------------------+--------------------------------+-------------+",
-        "| group   | name                                 | value                          | description |",
-        "+---------+--------------------------------------+--------------------------------+-------------+",
-        "| log     | dir                                  | ./.databend/logs               |             |",
-        "| log     | file.dir                             | ./.databend/logs               |             |",
-        "| log     | file.level                           | DEBUG                          |             |",
-        "| log     | file.on                              | true                           |             |",
-        "| log     | level                                | DEBUG                          |             |",
-        "| log     | query_enabled                        | false                          |             |",
-        "| log     | stderr.level                         | DEBUG                          |             |",
-        "| log     | stderr.on                            | true                           |             |",
-        "| meta    | address                              |                                |             |",
-        "| meta    | auto_sync_interval                   | 10                             |             |",
-        "| meta    | client_timeout_in_second             | 10                             |             |",
-        "| meta    | embedded_dir                         | ./.databend/meta_embedded      |             |",
-        "| meta    | endpoints                            |                                |             |",
-        "| meta    | password                             |                                |             |",
-        "| meta    | rpc_tls_meta_server_root_ca_cert     |                                |             |",
-        "| meta    | rpc_tls_meta_service_domain_name     | localhost                      |             |",
-        "| meta    | username                             | root                           |             |",
-        "| query   | admin_api_address                    | 127.0.0.1:8080                 |             |",
-        "| query   | api_tls_server_cert                  |                                |             |",
-        "| query   | api_tls_server_key                   |                                |             |",
-        "| query   | api_tls_server_root_ca_cert          |                                |             |",
-        "| query   | async_insert_busy_timeout            | 200                            |             |",
-        "| query   | async_insert_max_data_size           | 10000                          |             |",
-        "| query   | async_insert_stale_timeout           | 0                              |             |",
-        "| query   | clickhouse_handler_host              | 127.0.0.1                      |             |",
-        "| query   | clickhouse_handler_port              | 9000                           |             |",
-        "| query   | clickhouse_http_handler_host         | 127.0.0.1                      |             |",
-        "| query   | clickhouse_http_handler_port         | 8124                           |             |",
-        "| query   | cluster_id                           |                                |             |",
-        "| query   | database_engine_github_enabled       | true                           |             |",
-        "| query   | flight_api_address                   | 127.0.0.1:9090                 |             |",
-        "| query   | http_handler_host                    | 127.0.0.1                      |             |",
-        "| query   | http_handler_port                    | 8000                           |             |",
-        "| query   | http_handler_result_timeout_millis   | 10000                          |             |",
-        "| query   | http_handler_tls_server_cert         |                                |             |",
-        "| query   | http_handler_tls_server_key          |                                |             |",
-        "| query   | http_handler_tls_server_root_ca_cert |                                |             |",
-        "| query   | jwt_key_file                         |                                |             |",
-        "| query   | management_mode                      | false                          |             |",
-        "| query   | max_active_sessions                  | 256                            |             |",
-        "| query   | max_query_log_size                   | 10000                          |             |",
-        "| query   | metric_api_address                   | 127.0.0.1:7070                 |             |",
-        "| query   | mysql_handler_host                   | 127.0.0.1                      |             |",
-        "| query   | mysql_handler_port                   | 3307                           |             |",
-        "| query   | num_cpus                             | 0                              |             |",
-        "| query   | rpc_tls_query_server_root_ca_cert    |                                |             |",
-        "| query   | rpc_tls_query_service_domain_name    | localhost                      |             |",
-        "| query   | rpc_tls_server_cert                  |                                |             |",
-        "| query   | rpc_tls_server_key                   |                                |             |",
-        "| query   | table_cache_block_meta_count         | 102400                         |             |",
-        "| query   | table_cache_enabled                  | false                          |             |",
-        "| query   | table_cache_segment_count            | 10240                          |             |",
-        "| query   | table_cache_snapshot_count           | 256                            |             |",
-        "| query   | table_disk_cache_mb_size             | 1024                           |             |",
-        "| query   | table_disk_cache_root                | _cache                         |             |",
-        "| query   | table_engine_memory_enabled          | true                           |             |",
-        "| query   | table_memory_cache_mb_size           | 256                            |             |",
-        "| query   | tenant_id                            | test                           |             |",
-        "| query   | users                                |                                |             |",
-        "| query   | wait_timeout_mills                   | 5000                           |             |",
-        "| storage | allow_insecure                       | false                          |             |",
-        "| storage | azblob.account_key                   |                                |             |",
-        "| storage | azblob.account_name                  |                                |             |",
-        "| storage | azblob.container                     |                                |             |",
-        "| storage | azblob.endpoint_url                  |                                |             |",
-        "| storage | azblob.root                          |                                |             |",
-        "| storage | fs.data_path                         | _data                          |             |",
-        "| storage | gcs.bucket                           |                                |             |",
-        "| storage | gcs.credential                       |                                |             |",
-        "| storage | gcs.endpoint_url                     | https://storage.googleapis.com |             |",
-        "| storage | gcs.root                             |                                |             |",
-        "| storage | hdfs.name_node                       |                                |             |",
-        "| storage | hdfs.root                            |                                |             |",
-        "| storage | num_cpus                             | 0                              |             |",
-        "| storage | obs.access_key_id                    |                                |             |",
-        "| storage | obs.bucket                           |                                |             |",
-        "| storage | obs.endpoint_url                     |                                |             |",
-        "| storage | obs.root                             |                                |             |",
-        "| storage | obs.secret_access_key                |                                |             |",
-        "| storage | s3.access_key_id                     | ******_id                      |             |",
-        "| storage | s3.bucket                            | test                           |             |",
-        "| storage | s3.enable_virtual_host_style         | false                          |             |",
+        "+---------+--------------------------------------------+--------------------------------+-------------+",
+        "| group   | name                                       | value                          | description |",
+        "+---------+--------------------------------------------+--------------------------------+-------------+",
+        "| log     | dir                                        | ./.databend/logs               |             |",
+        "| log     | file.dir                                   | ./.databend/logs               |             |",
+        "| log     | file.level                                 | DEBUG                          |             |",
+        "| log     | file.on                                    | true                           |             |",
+        "| log     | level                                      | DEBUG                          |             |",
+        "| log     | query_enabled                              | false                          |             |",
+        "| log     | stderr.level                               | DEBUG                          |             |",
+        "| log     | stderr.on                                  | true                           |             |",
+        "| meta    | address                                    |                                |             |",
+        "| meta    | auto_sync_interval                         | 10                             |             |",
+        "| meta    | client_timeout_in_second                   | 10                             |             |",
+        "| meta    | embedded_dir                               | ./.databend/meta_embedded      |             |",
+        "| meta    | endpoints                                  |                                |             |",
+        "| meta    | password                                   |                                |             |",
+        "| meta    | rpc_tls_meta_server_root_ca_cert           |                                |             |",
+        "| meta    | rpc_tls_meta_service_domain_name           | localhost                      |             |",
+        "| meta    | username                                   | root                           |             |",
+        "| query   | admin_api_address                          | 127.0.0.1:8080                 |             |",
+        "| query   | api_tls_server_cert                        |                                |             |",
+        "| query   | api_tls_server_key                         |                                |             |",
+        "| query   | api_tls_server_root_ca_cert                |                                |             |",
+        "| query   | async_insert_busy_timeout                  | 200                            |             |",
+        "| query   | async_insert_max_data_size                 | 10000                          |             |",
+        "| query   | async_insert_stale_timeout                 | 0                              |             |",
+        "| query   | audit_log_events                           | login                          |             |",
+        "| query   | audit_log_sinks                            | file                           |             |",
+        "| query   | audit_log_webhook_url                      |                                |             |",
+        "| query   | auto_compaction_interval                   | 60                             |             |",
+        "| query   | auto_compaction_off_peak_hours             |                                |             |",
+        "| query   | auto_compaction_threshold                  | 100                            |             |",
+        "| query   | clickhouse_handler_host                    | 127.0.0.1                      |             |",
+        "| query   | clickhouse_handler_port                    | 9000                           |             |",
+        "| query   | clickhouse_handler_tls_server_cert         |                                |             |",
+        "| query   | clickhouse_handler_tls_server_key          |                                |             |",
+        "| query   | clickhouse_handler_tls_server_root_ca_cert |                                |             |",
+        "| query   | clickhouse_http_handler_host               | 127.0.0.1                      |             |",
+        "| query   | clickhouse_http_handler_port               | 8124                           |             |",
+        "| query   | cluster_heartbeat_ttl                      | 60                             |             |",
+        "| query   | cluster_id                                 |                                |             |",
+        "| query   | database_engine_github_enabled             | true                           |             |",
+        "| query   | enable_auto_compaction                     | false                          |             |",
+        "| query   | enable_persistent_query_log                | false                          |             |",
+        "| query   | enable_udf_server                          | false                          |             |",
+        "| query   | flight_api_address                         | 127.0.0.1:9090                 |             |",
+        "| query   | http_handler_host                          | 127.0.0.1                      |             |",
+        "| query   | http_handler_port                          | 8000                           |             |",
+        "| query   | http_handler_result_timeout_millis         | 10000                          |             |",
+        "| query   | http_handler_tls_server_cert               |                                |             |",
+        "| query   | http_handler_tls_server_key                |                                |             |",
+        "| query   | http_handler_tls_server_root_ca_cert       |                                |             |",
+        "| query   | jwt_key_file                               |                                |             |",
+        "| query   | jwt_key_files                              |                                |             |",
+        "| query   | jwt_user_claim                             | sub                            |             |",
+        "| query   | ldap_bind_dn                               |                                |             |",
+        "| query   | ldap_group_attribute                       | memberOf                       |             |",
+        "| query   | ldap_role_mapping                          |                                |             |",
+        "| query   | ldap_search_base_dn                        |                                |             |",
+        "| query   | ldap_search_bind_dn                        |                                |             |",
+        "| query   | ldap_search_bind_password                  |                                |             |",
+        "| query   | ldap_search_filter                         | (uid={user})                   |             |",
+        "| query   | ldap_starttls                              | false                          |             |",
+        "| query   | ldap_url                                   |                                |             |",
+        "| query   | management_mode                            | false                          |             |",
+        "| query   | max_active_sessions                        | 256                            |             |",
+        "| query   | max_queued_queries                         | 0                              |             |",
+        "| query   | max_query_log_size                         | 10000                          |             |",
+        "| query   | max_running_queries                        | 0                              |             |",
+        "| query   | max_running_queries_per_user               | 0                              |             |",
+        "| query   | metric_api_address                         | 127.0.0.1:7070                 |             |",
+        "| query   | mysql_handler_host                         | 127.0.0.1                      |             |",
+        "| query   | mysql_handler_port                         | 3307                           |             |",
+        "| query   | mysql_handler_tls_server_cert              |                                |             |",
+        "| query   | mysql_handler_tls_server_key               |                                |             |",
+        "| query   | mysql_handler_tls_server_root_ca_cert      |                                |             |",
+        "| query   | num_cpus                                   | 0                              |             |",
+        "| query   | oidc_audience                              |                                |             |",
+        "| query   | oidc_client_id                             |                                |             |",
+        "| query   | oidc_client_secret                         |                                |             |",
+        "| query   | oidc_issuer                                |                                |             |",
+        "| query   | oidc_redirect_url                          |                                |             |",
+        "| query   | oidc_role_claim                            | groups                         |             |",
+        "| query   | oidc_role_mapping                          |                                |             |",
+        "| query   | oidc_scopes                                | openid email profile           |             |",
+        "| query   | oidc_user_claim                            | email                          |             |",
+        "| query   | postgres_handler_host                      | 127.0.0.1                      |             |",
+        "| query   | postgres_handler_port                      | 5433                           |             |",
+        "| query   | postgres_handler_tls_server_cert           |                                |             |",
+        "| query   | postgres_handler_tls_server_key            |                                |             |",
+        "| query   | query_log_flush_interval                   | 10                             |             |",
+        "| query   | query_log_retention_days                   | 30                             |             |",
+        "| query   | query_queue_timeout                        | 0                              |             |",
+        "| query   | rpc_tls_query_server_root_ca_cert          |                                |             |",
+        "| query   | rpc_tls_query_service_domain_name          | localhost                      |             |",
+        "| query   | rpc_tls_server_cert                        |                                |             |",
+        "| query   | rpc_tls_server_key                         |                                |             |",
+        "| query   | slow_query_threshold_ms                    | 0                              |             |",
+        "| query   | table_cache_block_meta_count               | 102400                         |             |",
+        "| query   | table_cache_enabled                        | false                          |             |",
+        "| query   | table_cache_segment_count                  | 10240                          |             |",
+        "| query   | table_cache_snapshot_count                 | 256                            |             |",
+        "| query   | table_data_block_disk_cache_enabled        | false                          |             |",
+        "| query   | table_data_block_memory_cache_mb_size      | 256                            |             |",
+        "| query   | table_disk_cache_mb_size                   | 1024                           |             |",
+        "| query   | table_disk_cache_root                      | _cache                         |             |",
+        "| query   | table_engine_memory_enabled                | true                           |             |",
+        "| query   | table_memory_cache_mb_size                 | 256                            |             |",
+        "| query   | table_query_result_cache_mb_size           | 256                            |             |",
+        "| query   | task_scheduler_interval                    | 10                             |             |",
+        "| query   | tenant_id                                  | test                           |             |",
+        "| query   | udf_server_allow_list                      |                                |             |",
+        "| query   | users                                      |                                |             |",
+        "| query   | wait_timeout_mills                         | 5000                           |             |",
+        "| storage | allow_insecure                             | false                          |             |",
+        "| storage | azblob.account_key                         |                                |             |",
+        "| storage | azblob.account_name                        |                                |             |",
+        "| storage | azblob.container                           |                                |             |",
+        "| storage | azblob.endpoint_url                        |                                |             |",
+        "| storage | azblob.root                                |                                |             |",
+        "| storage | fs.data_path                               | _data                          |             |",
+        "| storage | gcs.bucket                                 |                                |             |",
+        "| storage | gcs.credential                             |                                |             |",
+        "| storage | gcs.endpoint_url                           | https://storage.googleapis.com |             |",
+        "| storage | gcs.root                                   |                                |             |",
+        "| storage | hdfs.name_node                             |                                |             |",
+        "| storage | hdfs.root                                  |                                |             |",
+        "| storage | num_cpus                                   | 0                              |             |",
+        "| storage | obs.access_key_id                          |                                |             |",
+        "| storage | obs.bucket                                 |                                |             |",
+        "| storage | obs.endpoint_url                           |                                |             |",
+        "| storage | obs.root                                   |                                |             |",
+        "| storage | obs.secret_access_key                      |                                |             |",
+        "| storage | s3.access_key_id                           | ******_id                      |             |",
+        "| storage | s3.bucket                                  | test                           |             |",
+        "| storage | s3.enable_virtual_host_style               | false                          |             |",
         &endpoint_url_link,
-        "| storage | s3.master_key                        |                                |             |",
-        "| storage | s3.region                            | us-east-2                      |             |",
-        "| storage | s3.root                              |                                |             |",
-        "| storage | s3.secret_access_key                 | ******key                      |             |",
-        "| storage | s3.security_token                    |                                |             |",
-        "| storage | type                                 | s3                             |             |",
-        "+---------+--------------------------------------+--------------------------------+-------------+",
+        "| storage | s3.master_key                              |                                |             |",
+        "| storage | s3.region                                  | us-east-2                      |             |",
+        "| storage | s3.root                                    |                                |             |",
+        "| storage | s3.secret_access_key                       | ******key                      |             |",
+        "| storage | s3.security_token                          |                                |             |",
+        "| storage | type                                       | s3                             |             |",
+        "+---------+--------------------------------------------+--------------------------------+-------------+",
     ];
 
     common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
@@ -34,7 +34,7 @@ async fn test_metrics_table() -> Result<()> {
     let stream = table.read(ctx, &source_plan).await?;
     let result = stream.try_collect::<Vec<_>>().await?;
     let block = &result[0];
-    assert_eq!(block.num_columns(), 4);
+    assert_eq!(block.num_columns(), 5);
     assert!(block.num_rows() >= 1);
 
     let output = pretty_format_blocks(result.as_slice())?;
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod catalogs_table;
 mod clusters_table;
 mod columns_table;
 mod configs_table;
@@ -21,6 +22,7 @@ mod databases_table;
 mod engines_table;
 mod functions_table;
 mod metrics_table;
+mod processes_table;
 mod roles_table;
 mod settings_table;
 mod stages_table;
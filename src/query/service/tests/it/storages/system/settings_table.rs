@@ -31,33 +31,53 @@ async fn test_settings_table() -> Result<()> {
     let stream = table.read(ctx, &source_plan).await?;
     let result = stream.try_collect::<Vec<_>>().await?;
     let expected = vec![
-        "+--------------------------------+------------+------------+---------+----------------------------------------------------------------------------------------------------+--------+",
-        "| name                           | value      | default    | level   | description                                                                                        | type   |",
-        "+--------------------------------+------------+------------+---------+----------------------------------------------------------------------------------------------------+--------+",
-        "| compression                    | None       | None       | SESSION | Format compression, default value: None                                                            | String |",
-        "| empty_as_default               | 1          | 1          | SESSION | Format empty_as_default, default value: 1                                                          | UInt64 |",
-        "| enable_async_insert            | 0          | 0          | SESSION | Whether the client open async insert mode, default value: 0                                        | UInt64 |",
-        "| enable_cbo                     | 1          | 1          | SESSION | If enable cost based optimization, default value: 1                                                | UInt64 |",
-        "| enable_new_processor_framework | 1          | 1          | SESSION | Enable new processor framework if value != 0, default value: 1                                     | UInt64 |",
-        "| enable_planner_v2              | 1          | 1          | SESSION | Enable planner v2 by setting this variable to 1, default value: 1                                  | UInt64 |",
-        "| field_delimiter                | ,          | ,          | SESSION | Format field delimiter, default value: ,                                                           | String |",
-        "| flight_client_timeout          | 60         | 60         | SESSION | Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds | UInt64 |",
-        "| input_read_buffer_size         | 1048576    | 1048576    | SESSION | The size of buffer in bytes for input with format. By default, it is 1MB.                          | UInt64 |",
-        "| group_by_two_level_threshold   | 10000      | 10000      | SESSION | The threshold of keys to open two-level aggregation, default value: 10000                          | UInt64 |",
-        "| max_block_size                 | 10000      | 10000      | SESSION | Maximum block size for reading                                                                     | UInt64 |",
-        "| max_execute_time               | 0          | 0          | SESSION | The maximum query execution time. it means no limit if the value is zero. default value: 0         | UInt64 |",
-        "| max_threads                    | 2          | 16         | SESSION | The maximum number of threads to execute the request. By default, it is determined automatically.  | UInt64 |",
-        "| quote_char                     | '\"'        | '\"'        | SESSION | The quote char for CSV. default value: '\"'.                                                        | String |",
-        "| quoted_ident_case_sensitive    | 1          | 1          | SESSION | Case sensitivity of quoted identifiers, default value: 1 (aka case-sensitive)                      | UInt64 |",
-        "| record_delimiter               | \"\\n\"       | \"\\n\"       | SESSION | Format record_delimiter, default value: \"\\n\"                                                       | String |",
-        "| skip_header                    | 0          | 0          | SESSION | Whether to skip the input header, default value: 0                                                 | UInt64 |",
-        "| sql_dialect                    | PostgreSQL | PostgreSQL | SESSION | SQL dialect, support \"PostgreSQL\" and \"MySQL\", default value: \"PostgreSQL\"                         | String |",
-        "| storage_read_buffer_size       | 1048576    | 1048576    | SESSION | The size of buffer in bytes for buffered reader of dal. By default, it is 1MB.                     | UInt64 |",
-        "| timezone                       | UTC        | UTC        | SESSION | Timezone, default value: UTC,                                                                      | String |",
-        "| unquoted_ident_case_sensitive  | 0          | 0          | SESSION | Case sensitivity of unquoted identifiers, default value: 0 (aka case-insensitive)                  | UInt64 |",
-        "| wait_for_async_insert          | 1          | 1          | SESSION | Whether the client wait for the reply of async insert, default value: 1                            | UInt64 |",
-        "| wait_for_async_insert_timeout  | 100        | 100        | SESSION | The timeout in seconds for waiting for processing of async insert, default value: 100              | UInt64 |",
-        "+--------------------------------+------------+------------+---------+----------------------------------------------------------------------------------------------------+--------+",
+        "+--------------------------------+------------+------------+---------+-------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+--------+",
+        "| name                           | value      | default    | level   | description                                                                                                                                                                   | type   |",
+        "+--------------------------------+------------+------------+---------+-------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+--------+",
+        "| compression                    | None       | None       | DEFAULT | Format compression, default value: None                                                                                                                                       | String |",
+        "| cte_max_recursion_depth        | 1000       | 1000       | DEFAULT | The maximum iterations of the recursive term of a recursive CTE, default value: 1000                                                                                          | UInt64 |",
+        "| empty_as_default               | 1          | 1          | DEFAULT | Format empty_as_default, default value: 1                                                                                                                                     | UInt64 |",
+        "| enable_adaptive_parallelism    | 0          | 0          | DEFAULT | Whether to adapt the parallelism to the data read and the load of the node, default value: 0                                                                                  | UInt64 |",
+        "| enable_aggregating_index       | 1          | 1          | DEFAULT | Whether to answer the matching aggregations from the aggregating indexes, default value: 1                                                                                    | UInt64 |",
+        "| enable_async_insert            | 0          | 0          | DEFAULT | Whether the client open async insert mode, default value: 0                                                                                                                   | UInt64 |",
+        "| enable_cbo                     | 1          | 1          | DEFAULT | If enable cost based optimization, default value: 1                                                                                                                           | UInt64 |",
+        "| enable_dictionary_encoding     | 0          | 0          | DEFAULT | Whether to keep the low-cardinality string columns scanned as dictionaries, default value: 0                                                                                  | UInt64 |",
+        "| enable_new_processor_framework | 1          | 1          | DEFAULT | Enable new processor framework if value != 0, default value: 1                                                                                                                | UInt64 |",
+        "| enable_planner_v2              | 1          | 1          | DEFAULT | Enable planner v2 by setting this variable to 1, default value: 1                                                                                                             | UInt64 |",
+        "| enable_query_result_cache      | 0          | 0          | DEFAULT | Whether to cache the results of deterministic queries, default value: 0                                                                                                       | UInt64 |",
+        "| enable_query_routing           | 0          | 0          | DEFAULT | Whether to run the local queries on the least loaded node of the cluster, default value: 0                                                                                    | UInt64 |",
+        "| enable_runtime_filter          | 1          | 1          | DEFAULT | Whether to prune the probe side of hash joins by the keys of the build side, default value: 1                                                                                 | UInt64 |",
+        "| field_delimiter                | ,          | ,          | DEFAULT | Format field delimiter, default value: ,                                                                                                                                      | String |",
+        "| flight_client_timeout          | 60         | 60         | DEFAULT | Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds                                                                            | UInt64 |",
+        "| input_read_buffer_size         | 1048576    | 1048576    | DEFAULT | The size of buffer in bytes for input with format. By default, it is 1MB.                                                                                                     | UInt64 |",
+        "| group_by_two_level_threshold   | 10000      | 10000      | DEFAULT | The threshold of keys to open two-level aggregation, default value: 10000                                                                                                     | UInt64 |",
+        "| max_block_size                 | 10000      | 10000      | DEFAULT | Maximum block size for reading                                                                                                                                                | UInt64 |",
+        "| max_execute_time               | 0          | 0          | DEFAULT | The maximum query execution time. it means no limit if the value is zero. default value: 0                                                                                    | UInt64 |",
+        "| max_node_failure_retries       | 3          | 3          | DEFAULT | Times to reschedule a query on the rest of the cluster if nodes are lost, default value: 3                                                                                    | UInt64 |",
+        "| max_query_memory_usage         | 0          | 0          | DEFAULT | The maximum memory usage in bytes of a query, no limit if the value is zero. Hash joins spill to disk before reaching it, except right, full and mark joins. default value: 0 | UInt64 |",
+        "| max_threads                    | 2          | 16         | SESSION | The maximum number of threads to execute the request. By default, it is determined automatically.                                                                             | UInt64 |",
+        "| min_bytes_per_thread           | 4194304    | 4194304    | DEFAULT | The minimum bytes read by a thread with adaptive parallelism. By default, it is 4MB.                                                                                          | UInt64 |",
+        "| ndjson_unknown_fields_column   |            |            | DEFAULT | The VARIANT column to hold the NDJSON fields matching no column, default value: \"\"                                                                                          | String |",
+        "| network_policy                 |            |            | DEFAULT | The network policy applied to all the users of the tenant if set globally, default value: \"\"                                                                                | String |",
+        "| quote_char                     | '\"'        | '\"'        | DEFAULT | The quote char for CSV. default value: '\"'.                                                                                                                                  | String |",
+        "| quoted_ident_case_sensitive    | 1          | 1          | DEFAULT | Case sensitivity of quoted identifiers, default value: 1 (aka case-sensitive)                                                                                                 | UInt64 |",
+        "| record_delimiter               | \"\\n\"       | \"\\n\"       | DEFAULT | Format record_delimiter, default value: \"\\n\"                                                                                                                               | String |",
+        "| retention_period               | 0          | 0          | DEFAULT | Hours of history kept for time travel by OPTIMIZE TABLE PURGE, default value: 0                                                                                               | UInt64 |",
+        "| skip_bad_rows                  | 0          | 0          | DEFAULT | Whether to skip the rows failed to parse in streaming load, default value: 0                                                                                                  | UInt64 |",
+        "| skip_header                    | 0          | 0          | DEFAULT | Whether to skip the input header, default value: 0                                                                                                                            | UInt64 |",
+        "| spilling_memory_threshold      | 0          | 0          | DEFAULT | Memory in bytes an aggregation or a sort may use before spilling to disk, default value: 0 (no spilling)                                                                      | UInt64 |",
+        "| sql_dialect                    | PostgreSQL | PostgreSQL | DEFAULT | SQL dialect, support \"PostgreSQL\" and \"MySQL\", default value: \"PostgreSQL\"                                                                                              | String |",
+        "| storage_read_buffer_size       | 1048576    | 1048576    | DEFAULT | The size of buffer in bytes for buffered reader of dal. By default, it is 1MB.                                                                                                | UInt64 |",
+        "| storage_write_concurrency      | 8          | 8          | DEFAULT | The maximum number of parts uploaded concurrently, default value: 8                                                                                                           | UInt64 |",
+        "| storage_write_part_size        | 16777216   | 16777216   | DEFAULT | Objects larger than it are uploaded in parts of this size. By default, it is 16MB.                                                                                            | UInt64 |",
+        "| streaming_load_batch_size      | 0          | 0          | DEFAULT | Input bytes committed in a transaction by streaming load, default value: 0 (all in one)                                                                                       | UInt64 |",
+        "| streaming_load_buffer_size     | 16777216   | 16777216   | DEFAULT | The input buffered to be parsed in streaming load. By default, it is 16MB.                                                                                                    | UInt64 |",
+        "| timezone                       | UTC        | UTC        | DEFAULT | Timezone, default value: UTC,                                                                                                                                                 | String |",
+        "| udf_server_timeout             | 60         | 60         | DEFAULT | The timeout in seconds of the requests to the UDF servers, default value: 60                                                                                                  | UInt64 |",
+        "| unquoted_ident_case_sensitive  | 0          | 0          | DEFAULT | Case sensitivity of unquoted identifiers, default value: 0 (aka case-insensitive)                                                                                             | UInt64 |",
+        "| wait_for_async_insert          | 1          | 1          | DEFAULT | Whether the client wait for the reply of async insert, default value: 1                                                                                                       | UInt64 |",
+        "| wait_for_async_insert_timeout  | 100        | 100        | DEFAULT | The timeout in seconds for waiting for processing of async insert, default value: 100                                                                                         | UInt64 |",
+        "+--------------------------------+------------+------------+---------+-------------------------------------------------------------------------------------------------------------------------------------------------------------------------------+--------+",
     ];
     common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
 
@@ -31,6 +31,9 @@ use common_users::UserApiProvider;
 use databend_query::api::DataExchangeManager;
 use databend_query::catalogs::CatalogManagerHelper;
 use databend_query::clusters::ClusterDiscovery;
+use databend_query::interpreters::AsyncInsertManager;
+use databend_query::interpreters::AutoCompactionManager;
+use databend_query::interpreters::TaskScheduler;
 use databend_query::servers::http::v1::HttpQueryManager;
 use databend_query::sessions::SessionManager;
 use databend_query::Config;
@@ -75,6 +78,9 @@ pub struct TestGlobalServices {
     session_manager: Mutex<HashMap<String, Arc<SessionManager>>>,
     users_manager: Mutex<HashMap<String, Arc<UserApiProvider>>>,
     users_role_manager: Mutex<HashMap<String, Arc<RoleCacheManager>>>,
+    async_insert_manager: Mutex<HashMap<String, Arc<AsyncInsertManager>>>,
+    auto_compaction_manager: Mutex<HashMap<String, Arc<AutoCompactionManager>>>,
+    task_scheduler: Mutex<HashMap<String, Arc<TaskScheduler>>>,
 }
 
 unsafe impl Send for TestGlobalServices {}
@@ -100,6 +106,9 @@ impl TestGlobalServices {
                 session_manager: Mutex::new(HashMap::new()),
                 users_manager: Mutex::new(HashMap::new()),
                 users_role_manager: Mutex::new(HashMap::new()),
+                async_insert_manager: Mutex::new(HashMap::new()),
+                auto_compaction_manager: Mutex::new(HashMap::new()),
+                task_scheduler: Mutex::new(HashMap::new()),
             })
         });
 
@@ -118,6 +127,7 @@ impl TestGlobalServices {
         HttpQueryManager::init(&config, global_services.clone()).await?;
         DataExchangeManager::init(config.clone(), global_services.clone())?;
         SessionManager::init(config.clone(), global_services.clone())?;
+        ClusterDiscovery::instance().init_load_probe();
         UserApiProvider::init(
             config.meta.to_meta_grpc_client_conf(),
             config.query.idm.clone(),
@@ -125,6 +135,9 @@ impl TestGlobalServices {
         )
         .await?;
         RoleCacheManager::init(global_services.clone())?;
+        AsyncInsertManager::init(&config, global_services.clone())?;
+        AutoCompactionManager::init(&config, global_services.clone())?;
+        TaskScheduler::init(&config, global_services.clone())?;
 
         ClusterDiscovery::instance()
             .register_to_metastore(&config)
@@ -206,6 +219,24 @@ impl TestGlobalServices {
             drop(session_manager_guard);
             drop(session_manager);
         }
+        {
+            let mut async_insert_manager_guard = self.async_insert_manager.lock();
+            let async_insert_manager = async_insert_manager_guard.remove(key);
+            drop(async_insert_manager_guard);
+            drop(async_insert_manager);
+        }
+        {
+            let mut auto_compaction_manager_guard = self.auto_compaction_manager.lock();
+            let auto_compaction_manager = auto_compaction_manager_guard.remove(key);
+            drop(auto_compaction_manager_guard);
+            drop(auto_compaction_manager);
+        }
+        {
+            let mut task_scheduler_guard = self.task_scheduler.lock();
+            let task_scheduler = task_scheduler_guard.remove(key);
+            drop(task_scheduler_guard);
+            drop(task_scheduler);
+        }
     }
 }
 
@@ -472,3 +503,80 @@ impl SingletonImpl<Arc<RoleCacheManager>> for TestGlobalServices {
         Ok(())
     }
 }
+
+impl SingletonImpl<Arc<AsyncInsertManager>> for TestGlobalServices {
+    fn get(&self) -> Arc<AsyncInsertManager> {
+        match std::thread::current().name() {
+            None => panic!("AsyncInsertManager is not init"),
+            Some(name) => match self.async_insert_manager.lock().get(name) {
+                None => panic!("AsyncInsertManager is not init, while in test '{}'", name),
+                Some(async_insert_manager) => async_insert_manager.clone(),
+            },
+        }
+    }
+
+    fn init(&self, value: Arc<AsyncInsertManager>) -> Result<()> {
+        match std::thread::current().name() {
+            None => panic!("thread name is none"),
+            Some(name) => match self.async_insert_manager.lock().entry(name.to_string()) {
+                Entry::Vacant(v) => v.insert(value),
+                Entry::Occupied(_v) => panic!("AsyncInsertManager set twice in test[{:?}]", name),
+            },
+        };
+
+        Ok(())
+    }
+}
+
+impl SingletonImpl<Arc<AutoCompactionManager>> for TestGlobalServices {
+    fn get(&self) -> Arc<AutoCompactionManager> {
+        match std::thread::current().name() {
+            None => panic!("AutoCompactionManager is not init"),
+            Some(name) => match self.auto_compaction_manager.lock().get(name) {
+                None => panic!(
+                    "AutoCompactionManager is not init, while in test '{}'",
+                    name
+                ),
+                Some(auto_compaction_manager) => auto_compaction_manager.clone(),
+            },
+        }
+    }
+
+    fn init(&self, value: Arc<AutoCompactionManager>) -> Result<()> {
+        match std::thread::current().name() {
+            None => panic!("thread name is none"),
+            Some(name) => match self.auto_compaction_manager.lock().entry(name.to_string()) {
+                Entry::Vacant(v) => v.insert(value),
+                Entry::Occupied(_v) => {
+                    panic!("AutoCompactionManager set twice in test[{:?}]", name)
+                }
+            },
+        };
+
+        Ok(())
+    }
+}
+
+impl SingletonImpl<Arc<TaskScheduler>> for TestGlobalServices {
+    fn get(&self) -> Arc<TaskScheduler> {
+        match std::thread::current().name() {
+            None => panic!("TaskScheduler is not init"),
+            Some(name) => match self.task_scheduler.lock().get(name) {
+                None => panic!("TaskScheduler is not init, while in test '{}'", name),
+                Some(task_scheduler) => task_scheduler.clone(),
+            },
+        }
+    }
+
+    fn init(&self, value: Arc<TaskScheduler>) -> Result<()> {
+        match std::thread::current().name() {
+            None => panic!("thread name is none"),
+            Some(name) => match self.task_scheduler.lock().entry(name.to_string()) {
+                Entry::Vacant(v) => v.insert(value),
+                Entry::Occupied(_v) => panic!("TaskScheduler set twice in test[{:?}]", name),
+            },
+        };
+
+        Ok(())
+    }
+}
@@ -33,19 +33,29 @@ use common_users::UserApiProvider;
 use itertools::Itertools;
 use parking_lot::RwLock;
 
-#[derive(Clone)]
+/// Where the value of a setting comes from, the later ones take precedence.
+#[derive(Clone, PartialEq)]
 enum ScopeLevel {
-    #[allow(dead_code)]
+    Default,
+    /// Set by `SET GLOBAL`, persisted in the meta for the tenant.
     Global,
+    /// The default settings of the user, persisted in the meta with the user.
+    User,
     Session,
 }
 
 impl Debug for ScopeLevel {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
+            ScopeLevel::Default => {
+                write!(f, "DEFAULT")
+            }
             ScopeLevel::Global => {
                 write!(f, "GLOBAL")
             }
+            ScopeLevel::User => {
+                write!(f, "USER")
+            }
             ScopeLevel::Session => {
                 write!(f, "SESSION")
             }
@@ -80,21 +90,6 @@ impl Settings {
     ) -> Result<Arc<Settings>> {
         let settings = Self::default_settings(&tenant);
 
-        let ret = {
-            // Overwrite settings from metasrv
-            let global_settings = user_api
-                .get_setting_api_client(&tenant)?
-                .get_settings()
-                .await?;
-
-            for global_setting in global_settings {
-                let name = global_setting.name;
-                let val = global_setting.value.as_string()?;
-                settings.set_settings(name, val, false)?;
-            }
-            settings
-        };
-
         // Overwrite settings from conf.
         {
             // Set max threads.
@@ -103,10 +98,70 @@ impl Settings {
             } else {
                 conf.query.num_cpus
             };
-            ret.set_max_threads(cpus)?;
+            settings.set_value(
+                "max_threads",
+                UserSettingValue::UInt64(cpus),
+                ScopeLevel::Default,
+            )?;
+        }
+
+        // Overwrite settings from metasrv
+        let global_settings = user_api
+            .get_setting_api_client(&tenant)?
+            .get_settings()
+            .await?;
+
+        for global_setting in global_settings {
+            let name = global_setting.name;
+            let val = global_setting.value.as_string()?;
+            settings.apply_persisted_setting(&name, val, ScopeLevel::Global)?;
         }
 
-        Ok(ret)
+        Ok(settings)
+    }
+
+    /// Applies the default settings of the user when the session is authenticated. The settings
+    /// set in the session are kept, and the unknown settings are ignored, e.g. the settings
+    /// removed since they were saved.
+    pub fn apply_user_settings(&self, user_settings: &BTreeMap<String, String>) -> Result<()> {
+        for (name, val) in user_settings {
+            if self.has_setting(name) {
+                self.apply_persisted_setting(name, val.clone(), ScopeLevel::User)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the setting `key` exists and accepts the value `val`.
+    pub fn check_setting_value(&self, key: &str, val: String) -> Result<()> {
+        self.parse_setting_value(key, val).map(|_| ())
+    }
+
+    fn parse_setting_value(&self, key: &str, val: String) -> Result<UserSettingValue> {
+        let setting = self.check_and_get_setting_value(key)?;
+        let val = self.check_possible_values(&setting, val)?;
+        match setting.user_setting.value {
+            UserSettingValue::UInt64(_) => Ok(UserSettingValue::UInt64(val.parse::<u64>()?)),
+            UserSettingValue::String(_) => Ok(UserSettingValue::String(val)),
+        }
+    }
+
+    fn apply_persisted_setting(&self, key: &str, val: String, level: ScopeLevel) -> Result<()> {
+        let value = self.parse_setting_value(key, val)?;
+        if self.check_and_get_setting_value(key)?.level != ScopeLevel::Session {
+            self.set_value(key, value, level)?;
+        }
+        Ok(())
+    }
+
+    fn set_value(&self, key: &str, value: UserSettingValue, level: ScopeLevel) -> Result<()> {
+        let mut settings = self.settings.write();
+        let setting = settings
+            .get_mut(key)
+            .ok_or_else(|| ErrorCode::UnknownVariable(format!("Unknown variable: {:?}", key)))?;
+        setting.user_setting.value = value;
+        setting.level = level;
+        Ok(())
     }
 
     pub fn default_settings(tenant: &str) -> Arc<Settings> {
@@ -118,7 +173,7 @@ impl Settings {
                     "max_block_size",
                     UserSettingValue::UInt64(10000),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Maximum block size for reading",
                 possible_values: None,
             },
@@ -126,7 +181,7 @@ impl Settings {
             SettingValue {
                 default_value: UserSettingValue::UInt64(16),
                 user_setting: UserSetting::create("max_threads", UserSettingValue::UInt64(16)),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "The maximum number of threads to execute the request. By default, it is determined automatically.",
                 possible_values: None,
             },
@@ -137,10 +192,32 @@ impl Settings {
                     "flight_client_timeout",
                     UserSettingValue::UInt64(60),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds",
                 possible_values: None,
             },
+            // max_node_failure_retries
+            SettingValue {
+                default_value: UserSettingValue::UInt64(3),
+                user_setting: UserSetting::create(
+                    "max_node_failure_retries",
+                    UserSettingValue::UInt64(3),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Times to reschedule a query on the rest of the cluster if nodes are lost, default value: 3",
+                possible_values: None,
+            },
+            // udf_server_timeout
+            SettingValue {
+                default_value: UserSettingValue::UInt64(60),
+                user_setting: UserSetting::create(
+                    "udf_server_timeout",
+                    UserSettingValue::UInt64(60),
+                ),
+                level: ScopeLevel::Default,
+                desc: "The timeout in seconds of the requests to the UDF servers, default value: 60",
+                possible_values: None,
+            },
             // storage_read_buffer_size
             SettingValue {
                 default_value: UserSettingValue::UInt64(1024 * 1024),
@@ -148,17 +225,37 @@ impl Settings {
                     "storage_read_buffer_size",
                     UserSettingValue::UInt64(1024 * 1024),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "The size of buffer in bytes for buffered reader of dal. By default, it is 1MB.",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(16 * 1024 * 1024),
+                user_setting: UserSetting::create(
+                    "storage_write_part_size",
+                    UserSettingValue::UInt64(16 * 1024 * 1024),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Objects larger than it are uploaded in parts of this size. By default, it is 16MB.",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(8),
+                user_setting: UserSetting::create(
+                    "storage_write_concurrency",
+                    UserSettingValue::UInt64(8),
+                ),
+                level: ScopeLevel::Default,
+                desc: "The maximum number of parts uploaded concurrently, default value: 8",
+                possible_values: None,
+            },
             SettingValue {
                 default_value: UserSettingValue::UInt64(1024 * 1024),
                 user_setting: UserSetting::create(
                     "input_read_buffer_size",
                     UserSettingValue::UInt64(1024 * 1024),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "The size of buffer in bytes for input with format. By default, it is 1MB.",
                 possible_values: None,
             },
@@ -169,7 +266,7 @@ impl Settings {
                     "enable_new_processor_framework",
                     UserSettingValue::UInt64(1),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Enable new processor framework if value != 0, default value: 1",
                 possible_values: None,
             },
@@ -177,7 +274,7 @@ impl Settings {
             SettingValue {
                 default_value: UserSettingValue::UInt64(1),
                 user_setting: UserSetting::create("enable_planner_v2", UserSettingValue::UInt64(1)),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Enable planner v2 by setting this variable to 1, default value: 1",
                 possible_values: None,
             },
@@ -187,7 +284,7 @@ impl Settings {
                     "record_delimiter",
                     UserSettingValue::String("\n".to_owned()),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Format record_delimiter, default value: \"\\n\"",
                 possible_values: None,
             },
@@ -197,31 +294,48 @@ impl Settings {
                     "field_delimiter",
                     UserSettingValue::String(",".to_owned()),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Format field delimiter, default value: ,",
                 possible_values: None,
             },
             SettingValue {
                 default_value: UserSettingValue::UInt64(1),
                 user_setting: UserSetting::create("empty_as_default", UserSettingValue::UInt64(1)),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Format empty_as_default, default value: 1",
                 possible_values: None,
             },
             SettingValue {
                 default_value: UserSettingValue::UInt64(0),
                 user_setting: UserSetting::create("skip_header", UserSettingValue::UInt64(0)),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Whether to skip the input header, default value: 0",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create("skip_bad_rows", UserSettingValue::UInt64(0)),
+                level: ScopeLevel::Default,
+                desc: "Whether to skip the rows failed to parse in streaming load, default value: 0",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::String("".to_owned()),
+                user_setting: UserSetting::create(
+                    "ndjson_unknown_fields_column",
+                    UserSettingValue::String("".to_owned()),
+                ),
+                level: ScopeLevel::Default,
+                desc: "The VARIANT column to hold the NDJSON fields matching no column, default value: \"\"",
+                possible_values: None,
+            },
             SettingValue {
                 default_value: UserSettingValue::String("None".to_owned()),
                 user_setting: UserSetting::create(
                     "compression",
                     UserSettingValue::String("None".to_owned()),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Format compression, default value: None",
                 possible_values: None,
             },
@@ -231,7 +345,7 @@ impl Settings {
                     "timezone",
                     UserSettingValue::String("UTC".to_owned()),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Timezone, default value: UTC,",
                 possible_values: None,
             },
@@ -241,7 +355,7 @@ impl Settings {
                     "group_by_two_level_threshold",
                     UserSettingValue::UInt64(10000),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "The threshold of keys to open two-level aggregation, default value: 10000",
                 possible_values: None,
             },
@@ -251,7 +365,7 @@ impl Settings {
                     "enable_async_insert",
                     UserSettingValue::UInt64(0),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Whether the client open async insert mode, default value: 0",
                 possible_values: None,
             },
@@ -261,7 +375,7 @@ impl Settings {
                     "wait_for_async_insert",
                     UserSettingValue::UInt64(1),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Whether the client wait for the reply of async insert, default value: 1",
                 possible_values: None,
             },
@@ -271,7 +385,7 @@ impl Settings {
                     "wait_for_async_insert_timeout",
                     UserSettingValue::UInt64(100),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "The timeout in seconds for waiting for processing of async insert, default value: 100",
                 possible_values: None,
             },
@@ -281,7 +395,7 @@ impl Settings {
                     "unquoted_ident_case_sensitive",
                     UserSettingValue::UInt64(0),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Case sensitivity of unquoted identifiers, default value: 0 (aka case-insensitive)",
                 possible_values: None,
             },
@@ -291,7 +405,7 @@ impl Settings {
                     "quoted_ident_case_sensitive",
                     UserSettingValue::UInt64(1),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "Case sensitivity of quoted identifiers, default value: 1 (aka case-sensitive)",
                 possible_values: None,
             },
@@ -301,35 +415,173 @@ impl Settings {
                     "sql_dialect",
                     UserSettingValue::String("PostgreSQL".to_owned()),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "SQL dialect, support \"PostgreSQL\" and \"MySQL\", default value: \"PostgreSQL\"",
                 possible_values: Some(vec!["PostgreSQL", "MySQL"]),
             },
             SettingValue {
                 default_value: UserSettingValue::UInt64(1),
                 user_setting: UserSetting::create("enable_cbo", UserSettingValue::UInt64(1)),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "If enable cost based optimization, default value: 1",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "enable_query_result_cache",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Whether to cache the results of deterministic queries, default value: 0",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "enable_query_routing",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Whether to run the local queries on the least loaded node of the cluster, default value: 0",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "enable_dictionary_encoding",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Whether to keep the low-cardinality string columns scanned as dictionaries, default value: 0",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(1),
+                user_setting: UserSetting::create(
+                    "enable_runtime_filter",
+                    UserSettingValue::UInt64(1),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Whether to prune the probe side of hash joins by the keys of the build side, default value: 1",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(1),
+                user_setting: UserSetting::create(
+                    "enable_aggregating_index",
+                    UserSettingValue::UInt64(1),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Whether to answer the matching aggregations from the aggregating indexes, default value: 1",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "enable_adaptive_parallelism",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Whether to adapt the parallelism to the data read and the load of the node, default value: 0",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(4 * 1024 * 1024),
+                user_setting: UserSetting::create(
+                    "min_bytes_per_thread",
+                    UserSettingValue::UInt64(4 * 1024 * 1024),
+                ),
+                level: ScopeLevel::Default,
+                desc: "The minimum bytes read by a thread with adaptive parallelism. By default, it is 4MB.",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "spilling_memory_threshold",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Memory in bytes an aggregation or a sort may use before spilling to disk, default value: 0 (no spilling)",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(1000),
+                user_setting: UserSetting::create(
+                    "cte_max_recursion_depth",
+                    UserSettingValue::UInt64(1000),
+                ),
+                level: ScopeLevel::Default,
+                desc: "The maximum iterations of the recursive term of a recursive CTE, default value: 1000",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create("retention_period", UserSettingValue::UInt64(0)),
+                level: ScopeLevel::Default,
+                desc: "Hours of history kept for time travel by OPTIMIZE TABLE PURGE, default value: 0",
+                possible_values: None,
+            },
             // max_execute_time
             SettingValue {
                 default_value: UserSettingValue::UInt64(0),
                 user_setting: UserSetting::create("max_execute_time", UserSettingValue::UInt64(0)),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "The maximum query execution time. it means no limit if the value is zero. default value: 0",
                 possible_values: None,
             },
+            // max_query_memory_usage
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "max_query_memory_usage",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Default,
+                desc: "The maximum memory usage in bytes of a query, no limit if the value is zero. Hash joins spill to disk before reaching it, except right, full and mark joins. default value: 0",
+                possible_values: None,
+            },
             SettingValue {
                 default_value: UserSettingValue::String("\"".to_owned()),
                 user_setting: UserSetting::create(
                     "quote_char",
                     UserSettingValue::String("\"".to_owned()),
                 ),
-                level: ScopeLevel::Session,
+                level: ScopeLevel::Default,
                 desc: "The quote char for CSV. default value: '\"'.",
                 possible_values: None,
             },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "streaming_load_batch_size",
+                    UserSettingValue::UInt64(0),
+                ),
+                level: ScopeLevel::Default,
+                desc: "Input bytes committed in a transaction by streaming load, default value: 0 (all in one)",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::UInt64(16 * 1024 * 1024),
+                user_setting: UserSetting::create(
+                    "streaming_load_buffer_size",
+                    UserSettingValue::UInt64(16 * 1024 * 1024),
+                ),
+                level: ScopeLevel::Default,
+                desc: "The input buffered to be parsed in streaming load. By default, it is 16MB.",
+                possible_values: None,
+            },
+            SettingValue {
+                default_value: UserSettingValue::String("".to_owned()),
+                user_setting: UserSetting::create(
+                    "network_policy",
+                    UserSettingValue::String("".to_owned()),
+                ),
+                level: ScopeLevel::Default,
+                desc: "The network policy applied to all the users of the tenant if set globally, default value: \"\"",
+                possible_values: None,
+            },
         ];
 
         let settings: Arc<RwLock<HashMap<String, SettingValue>>> =
@@ -378,18 +630,61 @@ impl Settings {
         self.try_set_u64("max_execute_time", val, false)
     }
 
+    // Get max_query_memory_usage.
+    pub fn get_max_query_memory_usage(&self) -> Result<u64> {
+        self.try_get_u64("max_query_memory_usage")
+    }
+
+    // Set max_query_memory_usage.
+    pub fn set_max_query_memory_usage(&self, val: u64) -> Result<()> {
+        self.try_set_u64("max_query_memory_usage", val, false)
+    }
+
     // Get flight client timeout.
     pub fn get_flight_client_timeout(&self) -> Result<u64> {
         let key = "flight_client_timeout";
         self.try_get_u64(key)
     }
 
+    // Get the times to reschedule a query on the rest of the cluster if nodes are lost.
+    pub fn get_max_node_failure_retries(&self) -> Result<u64> {
+        let key = "max_node_failure_retries";
+        self.try_get_u64(key)
+    }
+
+    // Get the timeout of the requests to the UDF servers.
+    pub fn get_udf_server_timeout(&self) -> Result<u64> {
+        let key = "udf_server_timeout";
+        self.try_get_u64(key)
+    }
+
     // Get storage read buffer size.
     pub fn get_storage_read_buffer_size(&self) -> Result<u64> {
         let key = "storage_read_buffer_size";
         self.try_get_u64(key)
     }
 
+    // Get the part size of multipart uploads.
+    pub fn get_storage_write_part_size(&self) -> Result<u64> {
+        let key = "storage_write_part_size";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_storage_write_concurrency(&self) -> Result<u64> {
+        let key = "storage_write_concurrency";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_streaming_load_batch_size(&self) -> Result<u64> {
+        let key = "streaming_load_batch_size";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_streaming_load_buffer_size(&self) -> Result<u64> {
+        let key = "streaming_load_buffer_size";
+        self.try_get_u64(key)
+    }
+
     pub fn get_input_read_buffer_size(&self) -> Result<u64> {
         let key = "input_read_buffer_size";
         self.try_get_u64(key)
@@ -439,6 +734,17 @@ impl Settings {
         self.try_get_u64(key)
     }
 
+    pub fn get_skip_bad_rows(&self) -> Result<u64> {
+        let key = "skip_bad_rows";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_ndjson_unknown_fields_column(&self) -> Result<String> {
+        let key = "ndjson_unknown_fields_column";
+        self.check_and_get_setting_value(key)
+            .and_then(|v| v.user_setting.value.as_string())
+    }
+
     pub fn get_timezone(&self) -> Result<String> {
         let key = "timezone";
         self.check_and_get_setting_value(key)
@@ -523,6 +829,89 @@ impl Settings {
         self.try_set_u64(KEY, v, false)
     }
 
+    pub fn get_enable_query_result_cache(&self) -> Result<bool> {
+        static KEY: &str = "enable_query_result_cache";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn set_enable_query_result_cache(&self, val: bool) -> Result<()> {
+        static KEY: &str = "enable_query_result_cache";
+        let v = u64::from(val);
+        self.try_set_u64(KEY, v, false)
+    }
+
+    pub fn get_enable_query_routing(&self) -> Result<bool> {
+        static KEY: &str = "enable_query_routing";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn get_enable_dictionary_encoding(&self) -> Result<bool> {
+        static KEY: &str = "enable_dictionary_encoding";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn get_enable_aggregating_index(&self) -> Result<bool> {
+        static KEY: &str = "enable_aggregating_index";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn get_enable_runtime_filter(&self) -> Result<bool> {
+        static KEY: &str = "enable_runtime_filter";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn set_enable_runtime_filter(&self, val: bool) -> Result<()> {
+        static KEY: &str = "enable_runtime_filter";
+        let v = u64::from(val);
+        self.try_set_u64(KEY, v, false)
+    }
+
+    pub fn get_enable_adaptive_parallelism(&self) -> Result<bool> {
+        static KEY: &str = "enable_adaptive_parallelism";
+        let v = self.try_get_u64(KEY)?;
+        Ok(v != 0)
+    }
+
+    pub fn set_enable_adaptive_parallelism(&self, val: bool) -> Result<()> {
+        static KEY: &str = "enable_adaptive_parallelism";
+        let v = u64::from(val);
+        self.try_set_u64(KEY, v, false)
+    }
+
+    // Get the minimum bytes read by a thread of a scan with adaptive parallelism
+    pub fn get_min_bytes_per_thread(&self) -> Result<u64> {
+        let key = "min_bytes_per_thread";
+        self.try_get_u64(key)
+    }
+
+    // Get the memory threshold of spilling, 0 means spilling is disabled
+    pub fn get_spilling_memory_threshold(&self) -> Result<u64> {
+        let key = "spilling_memory_threshold";
+        self.try_get_u64(key)
+    }
+
+    pub fn set_spilling_memory_threshold(&self, val: u64) -> Result<()> {
+        let key = "spilling_memory_threshold";
+        self.try_set_u64(key, val, false)
+    }
+
+    // Get the maximum iterations of the recursive term of a recursive CTE
+    pub fn get_cte_max_recursion_depth(&self) -> Result<u64> {
+        let key = "cte_max_recursion_depth";
+        self.try_get_u64(key)
+    }
+
+    // Get the hours of history kept by purging
+    pub fn get_retention_period(&self) -> Result<u64> {
+        let key = "retention_period";
+        self.try_get_u64(key)
+    }
+
     pub fn get_sql_dialect(&self) -> Result<Dialect> {
         let key = "sql_dialect";
         self.check_and_get_setting_value(key)
@@ -577,6 +966,7 @@ impl Settings {
             .get_mut(key)
             .ok_or_else(|| ErrorCode::UnknownVariable(format!("Unknown variable: {:?}", key)))?;
         setting.user_setting.value = UserSettingValue::UInt64(val);
+        setting.level = ScopeLevel::Session;
 
         if is_global {
             let tenant = self.tenant.clone();
@@ -600,6 +990,7 @@ impl Settings {
             .get_mut(key)
             .ok_or_else(|| ErrorCode::UnknownVariable(format!("Unknown variable: {:?}", key)))?;
         setting.user_setting.value = UserSettingValue::String(val);
+        setting.level = ScopeLevel::Session;
 
         if is_global {
             let tenant = self.tenant.clone();
@@ -0,0 +1,120 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::chrono::DateTime;
+use common_datavalues::chrono::NaiveDate;
+use common_datavalues::chrono::NaiveDateTime;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Parse a partition value of a data file, serialized as a string.
+///
+/// See https://github.com/delta-io/delta/blob/master/PROTOCOL.md#partition-value-serialization
+pub fn parse_partition_value(data_type: &DataTypeImpl, value: Option<&str>) -> Result<DataValue> {
+    let value = match value {
+        None => return Ok(DataValue::Null),
+        Some(value) => value,
+    };
+
+    let data_type = remove_nullable(data_type);
+    let parsed = match &data_type {
+        DataTypeImpl::String(_) => Some(DataValue::String(value.as_bytes().to_vec())),
+        // empty strings are the same as null, for other types
+        _ if value.is_empty() => Some(DataValue::Null),
+        DataTypeImpl::Boolean(_) => value.parse::<bool>().ok().map(DataValue::Boolean),
+        DataTypeImpl::Int8(_)
+        | DataTypeImpl::Int16(_)
+        | DataTypeImpl::Int32(_)
+        | DataTypeImpl::Int64(_) => value.parse::<i64>().ok().map(DataValue::Int64),
+        DataTypeImpl::UInt8(_)
+        | DataTypeImpl::UInt16(_)
+        | DataTypeImpl::UInt32(_)
+        | DataTypeImpl::UInt64(_) => value.parse::<u64>().ok().map(DataValue::UInt64),
+        DataTypeImpl::Float32(_) | DataTypeImpl::Float64(_) => {
+            value.parse::<f64>().ok().map(DataValue::Float64)
+        }
+        DataTypeImpl::Date(_) => parse_date(value).map(DataValue::Int64),
+        DataTypeImpl::Timestamp(_) => parse_timestamp(value).map(DataValue::Int64),
+        _ => {
+            return Err(ErrorCode::UnImplement(format!(
+                "unsupported type of delta partition column: {:?}",
+                data_type
+            )));
+        }
+    };
+
+    parsed.ok_or_else(|| {
+        ErrorCode::BadBytes(format!(
+            "invalid delta partition value {} of type {:?}",
+            value, data_type
+        ))
+    })
+}
+
+/// Convert a minimum or maximum value of the statistics of a data file.
+///
+/// Returns none if the value can't be used for pruning.
+pub fn try_into_data_value(
+    data_type: &DataTypeImpl,
+    value: &serde_json::Value,
+    is_max: bool,
+) -> Option<DataValue> {
+    match remove_nullable(data_type) {
+        DataTypeImpl::Boolean(_) => value.as_bool().map(DataValue::Boolean),
+        DataTypeImpl::Int8(_)
+        | DataTypeImpl::Int16(_)
+        | DataTypeImpl::Int32(_)
+        | DataTypeImpl::Int64(_) => value.as_i64().map(DataValue::Int64),
+        DataTypeImpl::UInt8(_)
+        | DataTypeImpl::UInt16(_)
+        | DataTypeImpl::UInt32(_)
+        | DataTypeImpl::UInt64(_) => value.as_u64().map(DataValue::UInt64),
+        DataTypeImpl::Float32(_) | DataTypeImpl::Float64(_) => {
+            value.as_f64().map(DataValue::Float64)
+        }
+        DataTypeImpl::Date(_) => value.as_str().and_then(parse_date).map(DataValue::Int64),
+        DataTypeImpl::Timestamp(_) => {
+            // timestamps are truncated to milliseconds
+            let micros = value.as_str().and_then(parse_timestamp)?;
+            let micros = if is_max { micros + 999 } else { micros };
+            Some(DataValue::Int64(micros))
+        }
+        DataTypeImpl::String(_) => {
+            // long strings may be truncated, so their prefixes are not upper bounds
+            let value = value.as_str()?;
+            match is_max && value.chars().count() >= 32 {
+                true => None,
+                false => Some(DataValue::String(value.as_bytes().to_vec())),
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_date(value: &str) -> Option<i64> {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Some((date - NaiveDate::from_ymd(1970, 1, 1)).num_days())
+}
+
+fn parse_timestamp(value: &str) -> Option<i64> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Some(ts.timestamp_micros());
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|ts| ts.timestamp_micros())
+}
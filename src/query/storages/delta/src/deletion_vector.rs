@@ -0,0 +1,309 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::AsyncReadExt;
+use opendal::Operator;
+
+/// Magic number of the `RoaringBitmapArray` serialization, in little endian
+const ROARING_BITMAP_ARRAY_MAGIC: u32 = 1681511377;
+const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+const SERIAL_COOKIE: u32 = 12347;
+const NO_OFFSET_THRESHOLD: usize = 4;
+const MAX_ARRAY_CONTAINER_CARDINALITY: usize = 4096;
+
+const Z85_ALPHABET: &[u8] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Descriptor of the deletion vector of a data file, marking the rows deleted from it.
+///
+/// See https://github.com/delta-io/delta/blob/master/PROTOCOL.md#deletion-vectors
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletionVector {
+    /// 'u' for a file relative to the table, 'p' for an absolute path, and 'i' for inline
+    pub storage_type: String,
+    pub path_or_inline_dv: String,
+    #[serde(default)]
+    pub offset: Option<i32>,
+    pub size_in_bytes: i32,
+    pub cardinality: i64,
+}
+
+impl DeletionVector {
+    /// Read the indexes of the deleted rows, in ascending order.
+    ///
+    /// `table_location` is the directory of the table in the storage, and `resolve` maps an
+    /// absolute path to its location in the storage.
+    pub async fn read(
+        &self,
+        operator: &Operator,
+        table_location: &str,
+        resolve: impl Fn(&str) -> String,
+    ) -> Result<Vec<u64>> {
+        let size = self.size_in_bytes as usize;
+        let data = match self.storage_type.as_str() {
+            "i" => {
+                let mut data = z85_decode(&self.path_or_inline_dv)?;
+                data.truncate(size);
+                data
+            }
+            "u" | "p" => {
+                let location = match self.storage_type.as_str() {
+                    "u" => self.relative_location(table_location)?,
+                    _ => resolve(&self.path_or_inline_dv),
+                };
+                // the serialized bitmap is prefixed by its size, and followed by a checksum
+                let start = self.offset.unwrap_or(0) as u64 + 4;
+                let object = operator.object(&location);
+                let mut reader = object.range_reader(start..start + size as u64).await?;
+                let mut data = vec![0; size];
+                reader.read_exact(&mut data).await?;
+                data
+            }
+            storage_type => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "unknown storage type of deletion vector: {}",
+                    storage_type
+                )));
+            }
+        };
+
+        let rows = deserialize_roaring_bitmap_array(&data)?;
+        if rows.len() as i64 != self.cardinality {
+            return Err(ErrorCode::BadBytes(format!(
+                "deletion vector holds {} rows, {} expected",
+                rows.len(),
+                self.cardinality
+            )));
+        }
+        Ok(rows)
+    }
+
+    /// Identify the deletion vector among the ones of a data file.
+    pub fn unique_id(&self) -> String {
+        match self.offset {
+            Some(offset) => format!("{}{}@{}", self.storage_type, self.path_or_inline_dv, offset),
+            None => format!("{}{}", self.storage_type, self.path_or_inline_dv),
+        }
+    }
+
+    // `<random prefix><z85 encoded uuid>`, the file is `<prefix>/deletion_vector_<uuid>.bin`
+    fn relative_location(&self, table_location: &str) -> Result<String> {
+        let path = self.path_or_inline_dv.as_str();
+        if path.len() < 20 {
+            return Err(ErrorCode::BadBytes(format!(
+                "invalid deletion vector path: {}",
+                path
+            )));
+        }
+
+        let (prefix, uuid) = path.split_at(path.len() - 20);
+        let uuid = z85_decode(uuid)?
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        let uuid = format!(
+            "{}-{}-{}-{}-{}",
+            &uuid[0..8],
+            &uuid[8..12],
+            &uuid[12..16],
+            &uuid[16..20],
+            &uuid[20..32]
+        );
+
+        match prefix.is_empty() {
+            true => Ok(format!("{}deletion_vector_{}.bin", table_location, uuid)),
+            false => Ok(format!(
+                "{}{}/deletion_vector_{}.bin",
+                table_location, prefix, uuid
+            )),
+        }
+    }
+}
+
+fn z85_decode(encoded: &str) -> Result<Vec<u8>> {
+    let encoded = encoded.as_bytes();
+    if encoded.len() % 5 != 0 {
+        return Err(ErrorCode::BadBytes(
+            "length of z85 data must be a multiple of 5",
+        ));
+    }
+
+    let mut decoded = Vec::with_capacity(encoded.len() / 5 * 4);
+    for chunk in encoded.chunks(5) {
+        let mut value = 0u32;
+        for c in chunk {
+            let digit = Z85_ALPHABET
+                .iter()
+                .position(|a| a == c)
+                .ok_or_else(|| ErrorCode::BadBytes("invalid z85 character"))?;
+            value = value
+                .checked_mul(85)
+                .and_then(|v| v.checked_add(digit as u32))
+                .ok_or_else(|| ErrorCode::BadBytes("invalid z85 data"))?;
+        }
+        decoded.extend_from_slice(&value.to_be_bytes());
+    }
+    Ok(decoded)
+}
+
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.pos + len > self.data.len() {
+            return Err(ErrorCode::BadBytes("deletion vector is truncated"));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+}
+
+/// Deserialize a `RoaringBitmapArray`, a sequence of 32-bit roaring bitmaps of the high 32 bits.
+fn deserialize_roaring_bitmap_array(data: &[u8]) -> Result<Vec<u64>> {
+    let mut reader = ByteReader { data, pos: 0 };
+    if reader.u32()? != ROARING_BITMAP_ARRAY_MAGIC {
+        return Err(ErrorCode::BadBytes("unknown format of deletion vector"));
+    }
+
+    let mut values = vec![];
+    let bitmaps = reader.u64()?;
+    for _ in 0..bitmaps {
+        let high = (reader.u32()? as u64) << 32;
+        deserialize_roaring_bitmap(&mut reader, |low| values.push(high | low as u64))?;
+    }
+    Ok(values)
+}
+
+// https://github.com/RoaringBitmap/RoaringFormatSpec
+fn deserialize_roaring_bitmap(reader: &mut ByteReader, mut f: impl FnMut(u32)) -> Result<()> {
+    let cookie = reader.u32()?;
+    let (size, run_flags) = if cookie & 0xFFFF == SERIAL_COOKIE {
+        let size = (cookie >> 16) as usize + 1;
+        (size, Some(reader.bytes((size + 7) / 8)?))
+    } else if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+        (reader.u32()? as usize, None)
+    } else {
+        return Err(ErrorCode::BadBytes("unknown format of roaring bitmap"));
+    };
+
+    let mut headers = Vec::with_capacity(size);
+    for _ in 0..size {
+        let key = reader.u16()? as u32;
+        let cardinality = reader.u16()? as usize + 1;
+        headers.push((key, cardinality));
+    }
+    if run_flags.is_none() || size >= NO_OFFSET_THRESHOLD {
+        reader.bytes(size * 4)?;
+    }
+
+    for (i, (key, cardinality)) in headers.into_iter().enumerate() {
+        let high = key << 16;
+        let is_run = run_flags.map_or(false, |flags| flags[i / 8] & (1 << (i % 8)) != 0);
+        if is_run {
+            let runs = reader.u16()?;
+            for _ in 0..runs {
+                let start = reader.u16()? as u32;
+                let length = reader.u16()? as u32;
+                (start..=start + length).for_each(|low| f(high | low));
+            }
+        } else if cardinality <= MAX_ARRAY_CONTAINER_CARDINALITY {
+            for _ in 0..cardinality {
+                f(high | reader.u16()? as u32);
+            }
+        } else {
+            for word_index in 0..1024 {
+                let mut word = reader.u64()?;
+                while word != 0 {
+                    let bit = word.trailing_zeros();
+                    f(high | (word_index * 64 + bit));
+                    word &= word - 1;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::deserialize_roaring_bitmap_array;
+    use super::z85_decode;
+    use super::ROARING_BITMAP_ARRAY_MAGIC;
+    use super::SERIAL_COOKIE;
+    use super::SERIAL_COOKIE_NO_RUNCONTAINER;
+
+    #[test]
+    fn test_z85_decode() {
+        // the example of https://rfc.zeromq.org/spec/32/
+        let decoded = z85_decode("HelloWorld").unwrap();
+        assert_eq!(decoded, vec![
+            0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B
+        ]);
+        assert!(z85_decode("Hello").is_ok());
+        assert!(z85_decode("Hell").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_roaring_bitmap_array() {
+        let mut data = vec![];
+        data.extend_from_slice(&ROARING_BITMAP_ARRAY_MAGIC.to_le_bytes());
+        data.extend_from_slice(&2u64.to_le_bytes());
+
+        // rows 1, 3 and 65537 with an array container for each of the two keys
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&SERIAL_COOKIE_NO_RUNCONTAINER.to_le_bytes());
+        data.extend_from_slice(&2u32.to_le_bytes());
+        for (key, cardinality) in [(0u16, 2u16), (1, 1)] {
+            data.extend_from_slice(&key.to_le_bytes());
+            data.extend_from_slice(&(cardinality - 1).to_le_bytes());
+        }
+        data.extend_from_slice(&[0; 8]);
+        for low in [1u16, 3, 1] {
+            data.extend_from_slice(&low.to_le_bytes());
+        }
+
+        // rows (1 << 32) + 10..=12 with a run container
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&SERIAL_COOKIE.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&10u16.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+
+        let rows = deserialize_roaring_bitmap_array(&data).unwrap();
+        let high = 1u64 << 32;
+        assert_eq!(rows, vec![1, 3, 65537, high + 10, high + 11, high + 12]);
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::Int32Array;
+use common_arrow::arrow::array::Int64Array;
+use common_arrow::arrow::array::ListArray;
+use common_arrow::arrow::array::MapArray;
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::array::Utf8Array;
+use common_arrow::arrow::io::parquet::read::infer_schema;
+use common_arrow::arrow::io::parquet::read::read_columns;
+use common_arrow::arrow::io::parquet::read::to_deserializer;
+use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
+use common_arrow::parquet::read::read_metadata;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opendal::Operator;
+
+use crate::deletion_vector::DeletionVector;
+use crate::delta_log::Action;
+use crate::delta_log::AddFile;
+use crate::delta_log::Metadata;
+use crate::delta_log::Protocol;
+use crate::delta_log::RemoveFile;
+
+/// Read the actions of a checkpoint of the delta log, each row of it holds one of them.
+///
+/// See https://github.com/delta-io/delta/blob/master/PROTOCOL.md#checkpoint-schema
+pub async fn read_checkpoint(operator: &Operator, location: &str) -> Result<Vec<Action>> {
+    let data = operator.object(location).read().await?;
+    let mut reader = Cursor::new(data);
+    let meta = read_metadata(&mut reader).map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    let fields = infer_schema(&meta)?
+        .fields
+        .into_iter()
+        .filter(|f| ["add", "remove", "metaData", "protocol"].contains(&f.name.as_str()))
+        .collect::<Vec<_>>();
+
+    let mut actions = vec![];
+    for row_group in &meta.row_groups {
+        let num_rows = row_group.num_rows();
+        let mut columns = Vec::with_capacity(fields.len());
+        for field in &fields {
+            let chunks = read_columns(&mut reader, row_group.columns(), &field.name)?;
+            columns.push(to_deserializer(
+                chunks,
+                field.clone(),
+                num_rows,
+                None,
+                None,
+            )?);
+        }
+
+        for chunk in RowGroupDeserializer::new(columns, num_rows, None) {
+            let chunk = chunk?;
+            for (field, array) in fields.iter().zip(chunk.arrays()) {
+                let array = downcast::<StructArray>(array.as_ref(), &field.name)?;
+                for row in 0..array.len() {
+                    if array.is_null(row) {
+                        continue;
+                    }
+                    let action = match field.name.as_str() {
+                        "add" => Action {
+                            add: Some(read_add(array, row)?),
+                            ..Default::default()
+                        },
+                        "remove" => Action {
+                            remove: Some(read_remove(array, row)?),
+                            ..Default::default()
+                        },
+                        "metaData" => Action {
+                            meta_data: Some(read_metadata_action(array, row)?),
+                            ..Default::default()
+                        },
+                        _ => Action {
+                            protocol: Some(read_protocol(array, row)?),
+                            ..Default::default()
+                        },
+                    };
+                    actions.push(action);
+                }
+            }
+        }
+    }
+
+    Ok(actions)
+}
+
+fn read_add(array: &StructArray, row: usize) -> Result<AddFile> {
+    Ok(AddFile {
+        path: required(get_string(array, "path", row)?, "add.path")?,
+        partition_values: get_string_map(array, "partitionValues", row)?,
+        size: required(get_i64(array, "size", row)?, "add.size")?,
+        stats: get_string(array, "stats", row)?,
+        deletion_vector: read_deletion_vector(array, row)?,
+    })
+}
+
+fn read_remove(array: &StructArray, row: usize) -> Result<RemoveFile> {
+    Ok(RemoveFile {
+        path: required(get_string(array, "path", row)?, "remove.path")?,
+        deletion_vector: read_deletion_vector(array, row)?,
+    })
+}
+
+fn read_metadata_action(array: &StructArray, row: usize) -> Result<Metadata> {
+    let configuration = get_string_map(array, "configuration", row)?
+        .into_iter()
+        .filter_map(|(key, value)| Some((key, value?)))
+        .collect();
+    Ok(Metadata {
+        partition_columns: get_string_list(array, "partitionColumns", row)?.unwrap_or_default(),
+        configuration,
+    })
+}
+
+fn read_protocol(array: &StructArray, row: usize) -> Result<Protocol> {
+    let min_reader_version = get_i64(array, "minReaderVersion", row)?;
+    Ok(Protocol {
+        min_reader_version: required(min_reader_version, "protocol.minReaderVersion")? as i32,
+        reader_features: get_string_list(array, "readerFeatures", row)?,
+    })
+}
+
+fn read_deletion_vector(array: &StructArray, row: usize) -> Result<Option<DeletionVector>> {
+    let array = match get_column(array, "deletionVector") {
+        Some(column) => downcast::<StructArray>(column, "deletionVector")?,
+        None => return Ok(None),
+    };
+    if array.is_null(row) {
+        return Ok(None);
+    }
+
+    Ok(Some(DeletionVector {
+        storage_type: required(get_string(array, "storageType", row)?, "storageType")?,
+        path_or_inline_dv: required(get_string(array, "pathOrInlineDv", row)?, "pathOrInlineDv")?,
+        offset: get_i64(array, "offset", row)?.map(|offset| offset as i32),
+        size_in_bytes: required(get_i64(array, "sizeInBytes", row)?, "sizeInBytes")? as i32,
+        cardinality: required(get_i64(array, "cardinality", row)?, "cardinality")?,
+    }))
+}
+
+fn get_column<'a>(array: &'a StructArray, name: &str) -> Option<&'a dyn Array> {
+    array
+        .fields()
+        .iter()
+        .position(|field| field.name == name)
+        .map(|index| array.values()[index].as_ref())
+}
+
+fn get_string(array: &StructArray, name: &str, row: usize) -> Result<Option<String>> {
+    match get_column(array, name) {
+        None => Ok(None),
+        Some(column) => {
+            let column = downcast::<Utf8Array<i32>>(column, name)?;
+            Ok(column.is_valid(row).then(|| column.value(row).to_string()))
+        }
+    }
+}
+
+fn get_i64(array: &StructArray, name: &str, row: usize) -> Result<Option<i64>> {
+    let column = match get_column(array, name) {
+        None => return Ok(None),
+        Some(column) if column.is_null(row) => return Ok(None),
+        Some(column) => column,
+    };
+
+    match column.as_any().downcast_ref::<Int32Array>() {
+        Some(column) => Ok(Some(column.value(row) as i64)),
+        None => Ok(Some(downcast::<Int64Array>(column, name)?.value(row))),
+    }
+}
+
+fn get_string_list(array: &StructArray, name: &str, row: usize) -> Result<Option<Vec<String>>> {
+    let column = match get_column(array, name) {
+        None => return Ok(None),
+        Some(column) if column.is_null(row) => return Ok(None),
+        Some(column) => downcast::<ListArray<i32>>(column, name)?,
+    };
+
+    let offsets = column.offsets();
+    let values = downcast::<Utf8Array<i32>>(column.values().as_ref(), name)?;
+    let (start, end) = (offsets[row] as usize, offsets[row + 1] as usize);
+    Ok(Some(
+        (start..end)
+            .filter(|i| values.is_valid(*i))
+            .map(|i| values.value(i).to_string())
+            .collect(),
+    ))
+}
+
+fn get_string_map(
+    array: &StructArray,
+    name: &str,
+    row: usize,
+) -> Result<BTreeMap<String, Option<String>>> {
+    let column = match get_column(array, name) {
+        None => return Ok(BTreeMap::new()),
+        Some(column) if column.is_null(row) => return Ok(BTreeMap::new()),
+        Some(column) => downcast::<MapArray>(column, name)?,
+    };
+
+    let offsets = column.offsets();
+    let entries = downcast::<StructArray>(column.field().as_ref(), name)?;
+    let keys = downcast::<Utf8Array<i32>>(entries.values()[0].as_ref(), name)?;
+    let values = downcast::<Utf8Array<i32>>(entries.values()[1].as_ref(), name)?;
+    let (start, end) = (offsets[row] as usize, offsets[row + 1] as usize);
+    Ok((start..end)
+        .map(|i| {
+            let value = values.is_valid(i).then(|| values.value(i).to_string());
+            (keys.value(i).to_string(), value)
+        })
+        .collect())
+}
+
+fn downcast<'a, T: 'static>(array: &'a dyn Array, name: &str) -> Result<&'a T> {
+    array.as_any().downcast_ref::<T>().ok_or_else(|| {
+        ErrorCode::ParquetError(format!(
+            "unexpected type of {} in delta checkpoint: {:?}",
+            name,
+            array.data_type()
+        ))
+    })
+}
+
+fn required<T>(value: Option<T>, name: &str) -> Result<T> {
+    value.ok_or_else(|| ErrorCode::ParquetError(format!("{} is missing in delta checkpoint", name)))
+}
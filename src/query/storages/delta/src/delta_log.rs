@@ -0,0 +1,257 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+use opendal::Operator;
+use serde::Deserialize;
+
+use crate::deletion_vector::DeletionVector;
+use crate::delta_checkpoint::read_checkpoint;
+
+/// Reader features which don't change how data files are read.
+const SUPPORTED_READER_FEATURES: [&str; 3] = ["columnMapping", "deletionVectors", "timestampNtz"];
+
+/// An action of a commit in the delta log, only one of the fields is set.
+///
+/// See https://github.com/delta-io/delta/blob/master/PROTOCOL.md#actions
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Action {
+    #[serde(default)]
+    pub add: Option<AddFile>,
+    #[serde(default)]
+    pub remove: Option<RemoveFile>,
+    #[serde(default)]
+    pub meta_data: Option<Metadata>,
+    #[serde(default)]
+    pub protocol: Option<Protocol>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AddFile {
+    /// Relative paths are percent encoded, from the directory of the table
+    pub path: String,
+    #[serde(default)]
+    pub partition_values: BTreeMap<String, Option<String>>,
+    pub size: i64,
+    /// Statistics of the file, serialized as json
+    #[serde(default)]
+    pub stats: Option<String>,
+    #[serde(default)]
+    pub deletion_vector: Option<DeletionVector>,
+}
+
+impl AddFile {
+    /// Value of the partition column `name` in the file, none if it's not a partition column.
+    pub fn partition_value(&self, name: &str) -> Option<Option<&str>> {
+        self.partition_values
+            .iter()
+            .find(|(column, _)| column.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_deref())
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveFile {
+    pub path: String,
+    #[serde(default)]
+    pub deletion_vector: Option<DeletionVector>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    #[serde(default)]
+    pub partition_columns: Vec<String>,
+    #[serde(default)]
+    pub configuration: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Protocol {
+    pub min_reader_version: i32,
+    /// Set since reader version 3
+    #[serde(default)]
+    pub reader_features: Option<Vec<String>>,
+}
+
+/// The files of a delta table at a version, resolved by replaying its log.
+#[derive(Debug)]
+pub struct DeltaSnapshot {
+    pub version: i64,
+    pub metadata: Metadata,
+    pub files: Vec<AddFile>,
+}
+
+impl DeltaSnapshot {
+    /// Load the latest version of the table stored in the directory `table_dir`.
+    ///
+    /// The log is replayed from the latest complete checkpoint, if there is one, then the commits
+    /// after it are applied in order.
+    pub async fn load(operator: &Operator, table_dir: &str) -> Result<DeltaSnapshot> {
+        let log_dir = format!("{}_delta_log/", table_dir);
+        let mut commits = BTreeMap::new();
+        // parts of the checkpoints of each version, by the number of parts
+        let mut checkpoints: BTreeMap<i64, BTreeMap<usize, Vec<String>>> = BTreeMap::new();
+
+        let mut entries = operator.object(&log_dir).list().await?;
+        while let Some(entry) = entries.try_next().await? {
+            let name = entry.path().rsplit('/').next().unwrap_or_default();
+            if let Some(version) = name.strip_suffix(".json") {
+                if let Ok(version) = version.parse::<i64>() {
+                    commits.insert(version, entry.path().to_string());
+                }
+                continue;
+            }
+
+            let parts = match name.strip_suffix(".parquet") {
+                Some(name) => name.split('.').collect::<Vec<_>>(),
+                None => continue,
+            };
+            let (version, num_parts) = match parts.as_slice() {
+                [version, "checkpoint"] => (version.parse::<i64>(), Ok(1)),
+                [version, "checkpoint", _, num_parts] => {
+                    (version.parse::<i64>(), num_parts.parse::<usize>())
+                }
+                _ => continue,
+            };
+            if let (Ok(version), Ok(num_parts)) = (version, num_parts) {
+                checkpoints
+                    .entry(version)
+                    .or_default()
+                    .entry(num_parts)
+                    .or_default()
+                    .push(entry.path().to_string());
+            }
+        }
+
+        // a checkpoint written by several writers may be incomplete
+        let checkpoint = checkpoints.into_iter().rev().find_map(|(version, parts)| {
+            parts
+                .into_iter()
+                .find(|(num_parts, files)| files.len() == *num_parts)
+                .map(|(_, mut files)| {
+                    files.sort();
+                    (version, files)
+                })
+        });
+
+        let mut replay = LogReplay::default();
+        let mut version = -1;
+        if let Some((checkpoint_version, files)) = checkpoint {
+            for file in files {
+                for action in read_checkpoint(operator, &file).await? {
+                    replay.apply(action);
+                }
+            }
+            version = checkpoint_version;
+        }
+
+        for (commit_version, location) in commits.range(version + 1..) {
+            if *commit_version != version + 1 {
+                return Err(ErrorCode::TableInfoError(format!(
+                    "version {} of delta table {} is missing",
+                    version + 1,
+                    table_dir
+                )));
+            }
+
+            let data = operator.object(location).read().await?;
+            for line in data.split(|b| *b == b'\n') {
+                if !line.iter().all(u8::is_ascii_whitespace) {
+                    replay.apply(serde_json::from_slice(line)?);
+                }
+            }
+            version = *commit_version;
+        }
+
+        if version < 0 {
+            return Err(ErrorCode::UnknownTable(format!(
+                "no delta log found in {}",
+                table_dir
+            )));
+        }
+
+        replay.finish(version)
+    }
+}
+
+#[derive(Default)]
+struct LogReplay {
+    /// Files of the table, by their paths and the ids of their deletion vectors
+    files: BTreeMap<(String, Option<String>), AddFile>,
+    metadata: Option<Metadata>,
+    protocol: Option<Protocol>,
+}
+
+impl LogReplay {
+    fn apply(&mut self, action: Action) {
+        if let Some(add) = action.add {
+            let dv_id = add.deletion_vector.as_ref().map(|dv| dv.unique_id());
+            self.files.insert((add.path.clone(), dv_id), add);
+        }
+        if let Some(remove) = action.remove {
+            let dv_id = remove.deletion_vector.as_ref().map(|dv| dv.unique_id());
+            self.files.remove(&(remove.path, dv_id));
+        }
+        if let Some(metadata) = action.meta_data {
+            self.metadata = Some(metadata);
+        }
+        if let Some(protocol) = action.protocol {
+            self.protocol = Some(protocol);
+        }
+    }
+
+    fn finish(self, version: i64) -> Result<DeltaSnapshot> {
+        if let Some(protocol) = &self.protocol {
+            if protocol.min_reader_version > 3 {
+                return Err(ErrorCode::UnImplement(format!(
+                    "delta reader version {} is not supported",
+                    protocol.min_reader_version
+                )));
+            }
+            for feature in protocol.reader_features.iter().flatten() {
+                if !SUPPORTED_READER_FEATURES.contains(&feature.as_str()) {
+                    return Err(ErrorCode::UnImplement(format!(
+                        "delta reader feature {} is not supported",
+                        feature
+                    )));
+                }
+            }
+        }
+
+        let metadata = self.metadata.unwrap_or_default();
+        if let Some(mode) = metadata.configuration.get("delta.columnMapping.mode") {
+            if mode != "none" {
+                return Err(ErrorCode::UnImplement(format!(
+                    "delta column mapping mode {} is not supported",
+                    mode
+                )));
+            }
+        }
+
+        Ok(DeltaSnapshot {
+            version,
+            metadata,
+            files: self.files.into_values().collect(),
+        })
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_legacy_planners::PartInfo;
+use common_legacy_planners::PartInfoPtr;
+use common_storages_hive::HivePartInfo;
+
+use crate::deletion_vector::DeletionVector;
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct DeltaPartInfo {
+    // location of the data file in the storage
+    pub filename: String,
+    // only the row groups in ranges belong to this partition
+    pub range: Range<u64>,
+    // values of the partition columns, serialized as strings
+    pub partition_values: BTreeMap<String, Option<String>>,
+    // rows deleted from the data file
+    pub deletion_vector: Option<DeletionVector>,
+}
+
+#[typetag::serde(name = "delta")]
+impl PartInfo for DeltaPartInfo {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn equals(&self, info: &Box<dyn PartInfo>) -> bool {
+        match info.as_any().downcast_ref::<DeltaPartInfo>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+impl DeltaPartInfo {
+    pub fn create(
+        filename: String,
+        range: Range<u64>,
+        partition_values: BTreeMap<String, Option<String>>,
+        deletion_vector: Option<DeletionVector>,
+    ) -> Arc<Box<dyn PartInfo>> {
+        Arc::new(Box::new(DeltaPartInfo {
+            filename,
+            range,
+            partition_values,
+            deletion_vector,
+        }))
+    }
+
+    pub fn from_part(info: &PartInfoPtr) -> Result<&DeltaPartInfo> {
+        match info.as_any().downcast_ref::<DeltaPartInfo>() {
+            Some(part_ref) => Ok(part_ref),
+            None => Err(ErrorCode::LogicalError(
+                "Cannot downcast from PartInfo to DeltaPartInfo.",
+            )),
+        }
+    }
+
+    /// Value of the partition column `name`, none if it's not a partition column.
+    pub fn partition_value(&self, name: &str) -> Option<Option<&str>> {
+        self.partition_values
+            .iter()
+            .find(|(column, _)| column.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_deref())
+    }
+
+    /// The part of the data file to read, partition columns are filled in afterwards.
+    pub fn to_hive_part(&self) -> HivePartInfo {
+        HivePartInfo {
+            filename: self.filename.clone(),
+            partitions: None,
+            range: self.range.clone(),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_fuse_meta::meta::ColumnStatistics;
+use common_fuse_meta::meta::StatisticsOfColumns;
+use common_legacy_expression::LegacyExpression;
+use common_storages_index::RangeFilter;
+use serde::Deserialize;
+
+use crate::converters::parse_partition_value;
+use crate::converters::try_into_data_value;
+use crate::delta_log::AddFile;
+
+/// Statistics of a data file, serialized as json in its `add` action.
+///
+/// See https://github.com/delta-io/delta/blob/master/PROTOCOL.md#per-file-statistics
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FileStats {
+    #[serde(default)]
+    pub num_records: Option<u64>,
+    #[serde(default)]
+    pub min_values: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub max_values: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub null_count: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Prune the data files of a delta table, by the filters pushed down.
+///
+/// Partition columns hold a single value in each file, other columns are pruned by the minimum
+/// and maximum values in the statistics of the files.
+pub struct DeltaPruner {
+    schema: DataSchemaRef,
+    range_filter: Option<RangeFilter>,
+}
+
+impl DeltaPruner {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        filters: &[LegacyExpression],
+        schema: DataSchemaRef,
+    ) -> Result<Self> {
+        let range_filter = match filters.is_empty() {
+            true => None,
+            false => Some(RangeFilter::try_create(ctx, filters, schema.clone())?),
+        };
+        Ok(DeltaPruner {
+            schema,
+            range_filter,
+        })
+    }
+
+    pub fn should_keep(&self, add: &AddFile) -> bool {
+        let range_filter = match &self.range_filter {
+            None => return true,
+            Some(range_filter) => range_filter,
+        };
+
+        let file_stats = add
+            .stats
+            .as_ref()
+            .and_then(|stats| serde_json::from_str::<FileStats>(stats).ok())
+            .unwrap_or_default();
+        // the number of rows may be unknown, make sure `is_not_null` never prunes the file
+        let row_count = file_stats.num_records.unwrap_or(u64::MAX);
+
+        let mut stats = StatisticsOfColumns::new();
+        for (index, field) in self.schema.fields().iter().enumerate() {
+            let data_type = field.data_type();
+            let column_stats = match add.partition_value(field.name()) {
+                Some(value) => match parse_partition_value(data_type, value) {
+                    Ok(value) => Some(ColumnStatistics {
+                        null_count: if value.is_null() { row_count } else { 0 },
+                        min: value.clone(),
+                        max: value,
+                        in_memory_size: 0,
+                    }),
+                    Err(_) => None,
+                },
+                None => {
+                    let min = get_stat(&file_stats.min_values, field.name())
+                        .and_then(|min| try_into_data_value(data_type, min, false));
+                    let max = get_stat(&file_stats.max_values, field.name())
+                        .and_then(|max| try_into_data_value(data_type, max, true));
+                    let null_count = get_stat(&file_stats.null_count, field.name())
+                        .and_then(|null_count| null_count.as_u64());
+                    match (min, max, null_count) {
+                        (Some(min), Some(max), Some(null_count)) => Some(ColumnStatistics {
+                            min,
+                            max,
+                            null_count,
+                            in_memory_size: 0,
+                        }),
+                        // all the values are null
+                        (None, None, Some(null_count)) if null_count == row_count => {
+                            Some(ColumnStatistics {
+                                min: DataValue::Null,
+                                max: DataValue::Null,
+                                null_count,
+                                in_memory_size: 0,
+                            })
+                        }
+                        _ => None,
+                    }
+                }
+            };
+
+            if let Some(column_stats) = column_stats {
+                stats.insert(index as u32, column_stats);
+            }
+        }
+
+        match range_filter.eval(&stats, row_count) {
+            Ok(keep) => keep,
+            Err(e) => {
+                // columns without statistics can't be pruned
+                tracing::debug!("failed to prune delta files, keep them. {}", e);
+                true
+            }
+        }
+    }
+}
+
+// names of delta columns are case sensitive, the ones of the table may be folded to lower case
+fn get_stat<'a>(
+    values: &'a serde_json::Map<String, serde_json::Value>,
+    name: &str,
+) -> Option<&'a serde_json::Value> {
+    values.get(name).or_else(|| {
+        values
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    })
+}
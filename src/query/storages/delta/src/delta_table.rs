@@ -0,0 +1,319 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use common_catalog::catalog::StorageDescription;
+use common_catalog::table::Table;
+use common_catalog::table::TableStatistics;
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_legacy_expression::LegacyExpression;
+use common_legacy_planners::Extras;
+use common_legacy_planners::Partitions;
+use common_legacy_planners::Projection;
+use common_legacy_planners::ReadDataSourcePlan;
+use common_legacy_planners::Statistics;
+use common_meta_app::schema::TableInfo;
+use common_pipeline_core::processors::port::OutputPort;
+use common_pipeline_core::Pipeline;
+use common_pipeline_core::SourcePipeBuilder;
+use common_storages_hive::convert_hdfs_path;
+use common_storages_hive::HiveFileSplitter;
+use common_storages_util::storage_context::StorageContext;
+
+use crate::delta_log::DeltaSnapshot;
+use crate::delta_partition::DeltaPartInfo;
+use crate::delta_pruner::DeltaPruner;
+use crate::delta_pruner::FileStats;
+use crate::delta_table_source::DeltaTableSource;
+
+pub const DELTA_TABLE_ENGINE: &str = "DELTA";
+
+/// Table option of the directory which holds the delta table, in the storage.
+pub const OPT_KEY_LOCATION: &str = "location";
+
+/// A read-only delta lake table, read at its latest version.
+///
+/// The columns of the table are declared when it's created, and matched with the columns of the
+/// data files by name.
+pub struct DeltaTable {
+    table_info: TableInfo,
+    location: String,
+}
+
+impl DeltaTable {
+    pub fn try_create(_ctx: StorageContext, table_info: TableInfo) -> Result<Box<dyn Table>> {
+        let location = table_info.options().get(OPT_KEY_LOCATION).ok_or_else(|| {
+            ErrorCode::BadOption(format!(
+                "table option {} of delta table {} is required",
+                OPT_KEY_LOCATION, table_info.desc
+            ))
+        })?;
+
+        let location = match location.contains("://") {
+            true => convert_hdfs_path(location, true),
+            false => match location.ends_with('/') {
+                true => location.clone(),
+                false => format!("{}/", location),
+            },
+        };
+        Ok(Box::new(DeltaTable {
+            table_info,
+            location,
+        }))
+    }
+
+    pub fn description() -> StorageDescription {
+        StorageDescription {
+            engine_name: DELTA_TABLE_ENGINE.to_string(),
+            comment: "DELTA Storage Engine".to_string(),
+            ..Default::default()
+        }
+    }
+
+    // relative paths of data files are from the directory of the table
+    fn file_location(&self, path: &str) -> String {
+        let path = percent_decode(path);
+        match path.contains("://") || path.starts_with('/') {
+            true => convert_hdfs_path(&path, false),
+            false => format!("{}{}", self.location, path),
+        }
+    }
+
+    fn projection(&self, push_downs: &Option<Extras>) -> Result<Vec<usize>> {
+        match push_downs {
+            Some(Extras {
+                projection: Some(Projection::Columns(indices)),
+                ..
+            }) => Ok(indices.clone()),
+            Some(Extras {
+                projection: Some(Projection::InnerColumns(_)),
+                ..
+            }) => Err(ErrorCode::UnImplement(
+                "does not support projection inner columns",
+            )),
+            _ => Ok((0..self.table_info.schema().fields().len()).collect()),
+        }
+    }
+
+    #[tracing::instrument(level = "info", skip(self, ctx))]
+    async fn do_read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        let start = Instant::now();
+        let operator = ctx.get_storage_operator()?;
+        let snapshot = DeltaSnapshot::load(&operator, &self.location).await?;
+
+        let filters = push_downs
+            .as_ref()
+            .map(|p| p.filters.clone())
+            .unwrap_or_default();
+        let pruner = DeltaPruner::try_create(ctx.clone(), &filters, self.table_info.schema())?;
+
+        let files_total = snapshot.files.len();
+        let mut files_scanned = 0;
+        let mut read_rows = 0;
+        let mut read_bytes = 0;
+        let mut partitions = vec![];
+        let splitter = HiveFileSplitter::create(128 * 1024 * 1024_u64);
+        for add in snapshot.files {
+            if !pruner.should_keep(&add) {
+                continue;
+            }
+
+            let num_records = add
+                .stats
+                .as_ref()
+                .and_then(|stats| serde_json::from_str::<FileStats>(stats).ok())
+                .and_then(|stats| stats.num_records);
+            let num_deleted = add
+                .deletion_vector
+                .as_ref()
+                .map_or(0, |dv| dv.cardinality as u64);
+            files_scanned += 1;
+            read_rows += num_records.unwrap_or_default().saturating_sub(num_deleted) as usize;
+            read_bytes += add.size as usize;
+
+            let filename = self.file_location(&add.path);
+            for range in splitter.split_length(add.size as u64) {
+                partitions.push(DeltaPartInfo::create(
+                    filename.clone(),
+                    range,
+                    add.partition_values.clone(),
+                    add.deletion_vector.clone(),
+                ));
+            }
+        }
+
+        let statistics =
+            Statistics::new_estimated(read_rows, read_bytes, files_scanned, files_total);
+        tracing::info!(
+            "read partitions of delta table at version {}, elapsed:{:?}",
+            snapshot.version,
+            start.elapsed()
+        );
+
+        Ok((statistics, partitions))
+    }
+
+    fn do_read2(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let projection = self.projection(&plan.push_downs)?;
+
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        let max_threads = std::cmp::min(plan.parts.len(), max_threads);
+
+        let mut source_builder = SourcePipeBuilder::create();
+        for _ in 0..std::cmp::max(1, max_threads) {
+            let output = OutputPort::create();
+            source_builder.add_source(
+                output.clone(),
+                DeltaTableSource::create(
+                    ctx.clone(),
+                    output,
+                    self.table_info.schema(),
+                    projection.clone(),
+                    self.location.clone(),
+                )?,
+            );
+        }
+
+        pipeline.add_pipe(source_builder.finalize());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for DeltaTable {
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    fn as_any(&self) -> &(dyn std::any::Any + 'static) {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn benefit_column_prune(&self) -> bool {
+        true
+    }
+
+    fn has_exact_total_row_count(&self) -> bool {
+        false
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        self.do_read_partitions(ctx, push_downs).await
+    }
+
+    fn table_args(&self) -> Option<Vec<LegacyExpression>> {
+        None
+    }
+
+    fn read2(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        self.do_read2(ctx, plan, pipeline)
+    }
+
+    async fn commit_insertion(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _operations: Vec<DataBlock>,
+        _overwrite: bool,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "commit_insertion operation for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    async fn truncate(&self, _ctx: Arc<dyn TableContext>, _: bool) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "truncate for table {} is not implemented",
+            self.name()
+        )))
+    }
+
+    async fn optimize(&self, _ctx: Arc<dyn TableContext>, _keep_last_snapshot: bool) -> Result<()> {
+        Ok(())
+    }
+
+    async fn statistics(&self, _ctx: Arc<dyn TableContext>) -> Result<Option<TableStatistics>> {
+        Ok(None)
+    }
+}
+
+/// Decode the escaped characters of a path in the delta log, like `%20`.
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes[i] == b'%' && i + 2 < bytes.len() {
+            true => std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            false => None,
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::percent_decode;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("part-0000.parquet"), "part-0000.parquet");
+        assert_eq!(
+            percent_decode("city=New%20York/part-0000.parquet"),
+            "city=New York/part-0000.parquet"
+        );
+        assert_eq!(percent_decode("a%3Db%25/x%2"), "a=b%/x%2");
+        assert_eq!(percent_decode("%e4%bd%a0"), "你");
+    }
+}
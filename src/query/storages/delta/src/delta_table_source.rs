@@ -0,0 +1,339 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_base::base::Progress;
+use common_base::base::ProgressValues;
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::ScalarColumn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_legacy_planners::PartInfoPtr;
+use common_pipeline_core::processors::port::OutputPort;
+use common_pipeline_core::processors::processor::Event;
+use common_pipeline_core::processors::processor::ProcessorPtr;
+use common_pipeline_core::processors::Processor;
+use common_storages_hive::convert_hdfs_path;
+use common_storages_hive::DataBlockDeserializer;
+use common_storages_hive::HiveBlocks;
+use common_storages_hive::HiveParquetBlockReader;
+
+use crate::converters::parse_partition_value;
+use crate::delta_partition::DeltaPartInfo;
+
+/// Row groups of a data file to read, with the rows deleted from the file.
+struct DeltaBlocks {
+    blocks: HiveBlocks,
+    /// Reads the columns stored in the data file, partition columns excluded
+    block_reader: Arc<HiveParquetBlockReader>,
+    part: DeltaPartInfo,
+    /// Indexes of the deleted rows in the file, in ascending order
+    deleted_rows: Vec<u64>,
+    /// Index in the file of the first row of the next block
+    row_offset: u64,
+}
+
+enum State {
+    /// Read parquet file meta data and the deletion vector
+    /// IO bound
+    ReadMeta(PartInfoPtr),
+
+    /// Read blocks from data groups (without deserialization)
+    /// IO bound
+    ReadData(DeltaBlocks),
+
+    /// Deserialize block from the given data groups
+    /// CPU bound
+    Deserialize(DeltaBlocks, DataBlockDeserializer),
+
+    /// `(_, _, Some(_))` indicates that a data block is ready, and needs to be consumed
+    /// `(_, _, None)` indicates that there are no more blocks left for the current row group
+    Generated(DeltaBlocks, DataBlockDeserializer, Option<DataBlock>),
+    Finish,
+}
+
+/// Read the data files of a delta table, skip the deleted rows and fill the partition columns.
+pub struct DeltaTableSource {
+    state: State,
+    ctx: Arc<dyn TableContext>,
+    scan_progress: Arc<Progress>,
+    schema: DataSchemaRef,
+    projection: Vec<usize>,
+    /// Schema of the output blocks, partition columns included
+    output_schema: DataSchemaRef,
+    /// Directory of the table, which deletion vectors may be relative to
+    table_location: String,
+    output: Arc<OutputPort>,
+}
+
+impl DeltaTableSource {
+    pub fn create(
+        ctx: Arc<dyn TableContext>,
+        output: Arc<OutputPort>,
+        schema: DataSchemaRef,
+        projection: Vec<usize>,
+        table_location: String,
+    ) -> Result<ProcessorPtr> {
+        let output_schema = Arc::new(schema.project(&projection));
+        let scan_progress = ctx.get_scan_progress();
+        let mut partitions = ctx.try_get_partitions(1)?;
+        let state = match partitions.is_empty() {
+            true => State::Finish,
+            false => State::ReadMeta(partitions.remove(0)),
+        };
+
+        Ok(ProcessorPtr::create(Box::new(DeltaTableSource {
+            state,
+            ctx,
+            scan_progress,
+            schema,
+            projection,
+            output_schema,
+            table_location,
+            output,
+        })))
+    }
+
+    fn try_get_partitions(&mut self) -> Result<()> {
+        let partitions = self.ctx.try_get_partitions(1)?;
+        match partitions.is_empty() {
+            true => self.state = State::Finish,
+            false => {
+                self.state = State::ReadMeta(partitions[0].clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn generate_data_block(
+        &self,
+        delta_blocks: &mut DeltaBlocks,
+        data_block: DataBlock,
+    ) -> Result<DataBlock> {
+        // there is no column in the block if only partition columns are read
+        let num_rows = match data_block.num_columns() {
+            0 => delta_blocks
+                .blocks
+                .get_current_row_group_meta_data()
+                .num_rows(),
+            _ => data_block.num_rows(),
+        };
+
+        let start = delta_blocks.row_offset;
+        let end = start + num_rows as u64;
+        delta_blocks.row_offset = end;
+
+        let deleted_rows = &delta_blocks.deleted_rows;
+        let begin = deleted_rows.partition_point(|row| *row < start);
+        let deleted_rows = &deleted_rows[begin..deleted_rows.partition_point(|row| *row < end)];
+        let (data_block, num_rows) = match deleted_rows.is_empty() {
+            true => (data_block, num_rows),
+            false => {
+                let mut predicate = vec![true; num_rows];
+                for row in deleted_rows {
+                    predicate[(*row - start) as usize] = false;
+                }
+                let predicate = BooleanColumn::from_slice(&predicate);
+                let data_block = DataBlock::filter_block_with_bool_column(data_block, &predicate)?;
+                (data_block, num_rows - deleted_rows.len())
+            }
+        };
+
+        let mut data_columns = data_block.columns().iter();
+        let mut columns = Vec::with_capacity(self.output_schema.num_fields());
+        for field in self.output_schema.fields() {
+            match delta_blocks.part.partition_value(field.name()) {
+                Some(value) => {
+                    let value = parse_partition_value(field.data_type(), value)?;
+                    columns.push(field.data_type().create_constant_column(&value, num_rows)?);
+                }
+                None => match data_columns.next() {
+                    Some(column) => columns.push(column.clone()),
+                    None => {
+                        return Err(ErrorCode::LogicalError(format!(
+                            "column {} not found in delta file {}",
+                            field.name(),
+                            delta_blocks.part.filename
+                        )));
+                    }
+                },
+            }
+        }
+
+        Ok(DataBlock::create(self.output_schema.clone(), columns))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for DeltaTableSource {
+    fn name(&self) -> &'static str {
+        "DeltaEngineSource"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if matches!(self.state, State::Finish) {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if matches!(self.state, State::Generated(_, _, _)) {
+            if let State::Generated(mut delta_blocks, rowgroup_deserializer, data_block) =
+                std::mem::replace(&mut self.state, State::Finish)
+            {
+                if let Some(data_block) = data_block {
+                    let progress_values = ProgressValues {
+                        rows: data_block.num_rows(),
+                        bytes: data_block.memory_size(),
+                    };
+                    self.scan_progress.incr(&progress_values);
+                    self.output.push_data(Ok(data_block));
+                    self.state = State::Deserialize(delta_blocks, rowgroup_deserializer);
+                    return Ok(Event::NeedConsume);
+                }
+
+                // read current rowgroup finished, try read next rowgroup
+                delta_blocks.blocks.advance();
+                match delta_blocks.blocks.has_blocks() {
+                    true => {
+                        self.state = State::ReadData(delta_blocks);
+                    }
+                    false => {
+                        self.try_get_partitions()?;
+                    }
+                }
+            }
+        }
+
+        match self.state {
+            State::Finish => {
+                self.output.finish();
+                Ok(Event::Finished)
+            }
+            State::ReadMeta(_) => Ok(Event::Async),
+            State::ReadData(_) => Ok(Event::Async),
+            State::Deserialize(_, _) => Ok(Event::Sync),
+            State::Generated(_, _, _) => Err(ErrorCode::LogicalError("It's a bug.")),
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::Deserialize(mut delta_blocks, mut rowgroup_deserializer) => {
+                let data_block = delta_blocks.block_reader.create_data_block(
+                    &mut rowgroup_deserializer,
+                    delta_blocks.blocks.get_part_info(),
+                )?;
+                let data_block = match data_block {
+                    Some(data_block) => {
+                        Some(self.generate_data_block(&mut delta_blocks, data_block)?)
+                    }
+                    None => None,
+                };
+
+                self.state = State::Generated(delta_blocks, rowgroup_deserializer, data_block);
+                Ok(())
+            }
+            _ => Err(ErrorCode::LogicalError("It's a bug.")),
+        }
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::ReadMeta(part) => {
+                let part = DeltaPartInfo::from_part(&part)?;
+                let operator = self.ctx.get_storage_operator()?;
+                // partition columns of the file are not stored in it
+                let projection = self
+                    .projection
+                    .iter()
+                    .copied()
+                    .filter(|i| part.partition_value(self.schema.field(*i).name()).is_none())
+                    .collect();
+                let block_reader = HiveParquetBlockReader::create(
+                    operator.clone(),
+                    self.schema.clone(),
+                    projection,
+                    None,
+                )?;
+
+                let file_meta = block_reader
+                    .read_meta_data(self.ctx.clone(), &part.filename)
+                    .await?;
+                let deleted_rows = match &part.deletion_vector {
+                    Some(deletion_vector) => {
+                        deletion_vector
+                            .read(&operator, &self.table_location, |path| {
+                                convert_hdfs_path(path, false)
+                            })
+                            .await?
+                    }
+                    None => vec![],
+                };
+
+                let mut blocks = HiveBlocks::create(file_meta, part.to_hive_part());
+                match blocks.prune() {
+                    true => {
+                        self.state = State::ReadData(DeltaBlocks {
+                            blocks,
+                            block_reader,
+                            part: part.clone(),
+                            deleted_rows,
+                            row_offset: 0,
+                        });
+                    }
+                    false => {
+                        self.try_get_partitions()?;
+                    }
+                }
+                Ok(())
+            }
+            State::ReadData(mut delta_blocks) => {
+                let file_meta = &delta_blocks.blocks.file_meta;
+                let index = delta_blocks.blocks.valid_rowgroups[delta_blocks.blocks.current_index];
+                delta_blocks.row_offset = file_meta.row_groups[..index]
+                    .iter()
+                    .map(|row_group| row_group.num_rows() as u64)
+                    .sum();
+
+                let row_group = delta_blocks.blocks.get_current_row_group_meta_data();
+                let part = delta_blocks.blocks.get_part_info();
+                let block_reader = &delta_blocks.block_reader;
+                let chunks = block_reader.read_columns_data(row_group, &part).await?;
+                let rowgroup_deserializer =
+                    block_reader.create_rowgroup_deserializer(chunks, row_group)?;
+                self.state = State::Deserialize(delta_blocks, rowgroup_deserializer);
+                Ok(())
+            }
+            _ => Err(ErrorCode::LogicalError("It's a bug.")),
+        }
+    }
+}
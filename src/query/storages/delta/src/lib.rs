@@ -0,0 +1,31 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![deny(unused_crate_dependencies)]
+
+mod converters;
+mod deletion_vector;
+mod delta_checkpoint;
+mod delta_log;
+mod delta_partition;
+mod delta_pruner;
+mod delta_table;
+mod delta_table_source;
+
+pub use deletion_vector::DeletionVector;
+pub use delta_log::AddFile;
+pub use delta_log::DeltaSnapshot;
+pub use delta_partition::DeltaPartInfo;
+pub use delta_table::DeltaTable;
+pub use delta_table::DELTA_TABLE_ENGINE;
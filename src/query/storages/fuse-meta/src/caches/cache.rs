@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::path::Path;
 use std::sync::Arc;
 
 use common_base::base::Singleton;
@@ -19,30 +20,48 @@ use common_config::QueryConfig;
 use common_exception::Result;
 use once_cell::sync::OnceCell;
 
+use crate::caches::memory_cache::new_bloom_index_filter_cache;
 use crate::caches::memory_cache::new_bytes_cache;
+use crate::caches::memory_cache::new_file_meta_data_cache;
 use crate::caches::memory_cache::BloomIndexCache;
+use crate::caches::memory_cache::BloomIndexFilterCache;
 use crate::caches::memory_cache::BloomIndexMetaCache;
 use crate::caches::memory_cache::BytesCache;
 use crate::caches::memory_cache::FileMetaDataCache;
 use crate::caches::new_item_cache;
+use crate::caches::new_query_result_cache;
+use crate::caches::DataBlockCache;
+use crate::caches::DataBlockCacheRef;
 use crate::caches::ItemCache;
+use crate::caches::QueryResultCache;
 use crate::caches::SegmentInfoCache;
 use crate::caches::TableSnapshotCache;
+use crate::caches::TableSnapshotStatisticsCache;
 
 // default number of index meta cached, default 3000 items
 static DEFAULT_BLOOM_INDEX_META_CACHE_ITEMS: u64 = 3000;
 // default size of cached bloom filter index (in bytes), 1G
 static DEFAULT_BLOOM_INDEX_COLUMN_CACHE_SIZE: u64 = 1024 * 1024 * 1024;
-// default number of file meta data cached, default 3000 items
-static DEFAULT_FILE_META_DATA_CACHE_ITEMS: u64 = 3000;
+// default size of deserialized bloom filters cached (in bytes), 512M
+static DEFAULT_BLOOM_INDEX_FILTER_CACHE_SIZE: u64 = 512 * 1024 * 1024;
+// default size of parsed parquet footers cached (in bytes), 256M
+static DEFAULT_FILE_META_DATA_CACHE_SIZE: u64 = 256 * 1024 * 1024;
+// sub directory of the table disk cache root, for cached query results
+static QUERY_RESULT_CACHE_DIR: &str = "query_result";
+// sub directory of the table disk cache root, for cached columns of data blocks
+static DATA_BLOCK_CACHE_DIR: &str = "data_block";
 
 /// Where all the caches reside
 pub struct CacheManager {
     table_snapshot_cache: Option<TableSnapshotCache>,
+    table_statistics_cache: Option<TableSnapshotStatisticsCache>,
     segment_info_cache: Option<SegmentInfoCache>,
     bloom_index_cache: Option<BloomIndexCache>,
+    bloom_index_filter_cache: Option<BloomIndexFilterCache>,
     bloom_index_meta_cache: Option<BloomIndexMetaCache>,
     file_meta_data_cache: Option<FileMetaDataCache>,
+    query_result_cache: Option<QueryResultCache>,
+    data_block_cache: Option<DataBlockCacheRef>,
     cluster_id: String,
     tenant_id: String,
 }
@@ -57,10 +76,14 @@ impl CacheManager {
         if !config.table_cache_enabled {
             v.init(Arc::new(Self {
                 table_snapshot_cache: None,
+                table_statistics_cache: None,
                 segment_info_cache: None,
                 bloom_index_cache: None,
+                bloom_index_filter_cache: None,
                 bloom_index_meta_cache: None,
                 file_meta_data_cache: None,
+                query_result_cache: None,
+                data_block_cache: None,
                 cluster_id: config.cluster_id.clone(),
                 tenant_id: config.tenant_id.clone(),
             }))?;
@@ -68,18 +91,40 @@ impl CacheManager {
             CACHE_MANAGER.set(v).ok();
         } else {
             let table_snapshot_cache = Self::new_item_cache(config.table_cache_snapshot_count);
+            let table_statistics_cache = Self::new_item_cache(config.table_cache_snapshot_count);
             let segment_info_cache = Self::new_item_cache(config.table_cache_segment_count);
             let bloom_index_cache = Self::new_bytes_cache(DEFAULT_BLOOM_INDEX_COLUMN_CACHE_SIZE);
+            let bloom_index_filter_cache =
+                Self::new_bloom_index_filter_cache(DEFAULT_BLOOM_INDEX_FILTER_CACHE_SIZE);
             let bloom_index_meta_cache = Self::new_item_cache(DEFAULT_BLOOM_INDEX_META_CACHE_ITEMS);
 
-            let file_meta_data_cache = Self::new_item_cache(DEFAULT_FILE_META_DATA_CACHE_ITEMS);
+            let file_meta_data_cache =
+                Self::new_file_meta_data_cache(DEFAULT_FILE_META_DATA_CACHE_SIZE);
+            let query_result_cache = Self::new_query_result_cache(
+                Path::new(&config.table_disk_cache_root).join(QUERY_RESULT_CACHE_DIR),
+                config.table_query_result_cache_mb_size * 1024 * 1024,
+            )?;
+            let data_block_disk_cache_size = if config.table_data_block_disk_cache_enabled {
+                config.table_disk_cache_mb_size * 1024 * 1024
+            } else {
+                0
+            };
+            let data_block_cache = Self::new_data_block_cache(
+                config.table_data_block_memory_cache_mb_size * 1024 * 1024,
+                Path::new(&config.table_disk_cache_root).join(DATA_BLOCK_CACHE_DIR),
+                data_block_disk_cache_size,
+            )?;
 
             v.init(Arc::new(Self {
                 table_snapshot_cache,
+                table_statistics_cache,
                 segment_info_cache,
                 bloom_index_cache,
+                bloom_index_filter_cache,
                 bloom_index_meta_cache,
                 file_meta_data_cache,
+                query_result_cache,
+                data_block_cache,
                 cluster_id: config.cluster_id.clone(),
                 tenant_id: config.tenant_id.clone(),
             }))?;
@@ -101,6 +146,10 @@ impl CacheManager {
         self.table_snapshot_cache.clone()
     }
 
+    pub fn get_table_snapshot_statistics_cache(&self) -> Option<TableSnapshotStatisticsCache> {
+        self.table_statistics_cache.clone()
+    }
+
     pub fn get_table_segment_cache(&self) -> Option<SegmentInfoCache> {
         self.segment_info_cache.clone()
     }
@@ -109,6 +158,10 @@ impl CacheManager {
         self.bloom_index_cache.clone()
     }
 
+    pub fn get_bloom_index_filter_cache(&self) -> Option<BloomIndexFilterCache> {
+        self.bloom_index_filter_cache.clone()
+    }
+
     pub fn get_bloom_index_meta_cache(&self) -> Option<BloomIndexMetaCache> {
         self.bloom_index_meta_cache.clone()
     }
@@ -117,6 +170,14 @@ impl CacheManager {
         self.file_meta_data_cache.clone()
     }
 
+    pub fn get_query_result_cache(&self) -> Option<QueryResultCache> {
+        self.query_result_cache.clone()
+    }
+
+    pub fn get_data_block_cache(&self) -> Option<DataBlockCacheRef> {
+        self.data_block_cache.clone()
+    }
+
     pub fn get_tenant_id(&self) -> &str {
         self.tenant_id.as_str()
     }
@@ -133,6 +194,33 @@ impl CacheManager {
         }
     }
 
+    fn new_query_result_cache(
+        path: impl AsRef<Path>,
+        capacity: u64,
+    ) -> Result<Option<QueryResultCache>> {
+        if capacity > 0 {
+            Ok(Some(new_query_result_cache(path, capacity)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn new_data_block_cache(
+        memory_capacity: u64,
+        disk_root: impl AsRef<Path>,
+        disk_capacity: u64,
+    ) -> Result<Option<DataBlockCacheRef>> {
+        if memory_capacity > 0 {
+            Ok(Some(DataBlockCache::create(
+                memory_capacity,
+                disk_root,
+                disk_capacity,
+            )?))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn new_bytes_cache(capacity: u64) -> Option<BytesCache> {
         if capacity > 0 {
             Some(new_bytes_cache(capacity))
@@ -140,4 +228,20 @@ impl CacheManager {
             None
         }
     }
+
+    fn new_file_meta_data_cache(capacity: u64) -> Option<FileMetaDataCache> {
+        if capacity > 0 {
+            Some(new_file_meta_data_cache(capacity))
+        } else {
+            None
+        }
+    }
+
+    fn new_bloom_index_filter_cache(capacity: u64) -> Option<BloomIndexFilterCache> {
+        if capacity > 0 {
+            Some(new_bloom_index_filter_cache(capacity))
+        } else {
+            None
+        }
+    }
 }
@@ -0,0 +1,214 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::borrow::Borrow;
+use std::io::Cursor;
+use std::io::Read;
+use std::path::Path;
+use std::sync::mpsc::sync_channel;
+use std::sync::mpsc::SyncSender;
+use std::sync::mpsc::TrySendError;
+use std::sync::Arc;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::ipc::read::read_file_metadata;
+use common_arrow::arrow::io::ipc::read::FileReader;
+use common_arrow::arrow::io::ipc::write::FileWriter;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_base::base::Thread;
+use common_cache::Cache;
+use common_cache::DefaultHashBuilder;
+use common_cache::LruCache;
+use common_cache::LruDiskCache;
+use common_cache::Meter;
+use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use parking_lot::Mutex;
+use tracing::warn;
+
+// max number of columns waiting to be written to the disk tier, more are dropped
+const DISK_WRITE_QUEUE_SIZE: usize = 1024;
+
+/// Measures a cached column by its memory size.
+pub struct ColumnMeter;
+
+impl<K> Meter<K, ColumnRef> for ColumnMeter {
+    type Measure = usize;
+    fn measure<Q: ?Sized>(&self, _: &Q, v: &ColumnRef) -> usize
+    where K: Borrow<Q> {
+        v.memory_size()
+    }
+}
+
+/// A two-tier cache of decoded columns of data blocks.
+///
+/// Columns are kept in memory and, if the disk tier is enabled, also written to local disk.
+/// A column evicted from memory can still be loaded from disk, without fetching and decoding it again.
+///
+/// Disk writes are done by a background thread, so that caching a column never blocks the decoding.
+pub struct DataBlockCache {
+    memory: Mutex<LruCache<String, ColumnRef, DefaultHashBuilder, ColumnMeter>>,
+    disk: Option<Arc<Mutex<LruDiskCache>>>,
+    disk_writer: Option<SyncSender<DiskWrite>>,
+}
+
+/// A column waiting to be written to the disk tier.
+struct DiskWrite {
+    key: String,
+    field: DataField,
+    column: ColumnRef,
+}
+
+pub type DataBlockCacheRef = Arc<DataBlockCache>;
+
+impl DataBlockCache {
+    /// Create a cache with `memory_capacity` bytes in memory, and `disk_capacity` bytes in directory `disk_root`.
+    ///
+    /// The disk tier is disabled if `disk_capacity` is 0.
+    pub fn create(
+        memory_capacity: u64,
+        disk_root: impl AsRef<Path>,
+        disk_capacity: u64,
+    ) -> Result<DataBlockCacheRef> {
+        let memory = LruCache::with_meter_and_hasher(
+            memory_capacity,
+            ColumnMeter,
+            DefaultHashBuilder::new(),
+        );
+
+        let (disk, disk_writer) = if disk_capacity > 0 {
+            let c = LruDiskCache::new(disk_root.as_ref().to_path_buf(), disk_capacity)?;
+            let disk = Arc::new(Mutex::new(c));
+            let (tx, rx) = sync_channel::<DiskWrite>(DISK_WRITE_QUEUE_SIZE);
+
+            // The thread exits once the cache, and the sender with it, is dropped.
+            let writer_disk = disk.clone();
+            Thread::named_spawn(Some("data-block-cache-writer".to_string()), move || {
+                for write in rx {
+                    if let Err(cause) = Self::write_to_disk(&writer_disk, &write) {
+                        warn!(
+                            "failed to write column {} to disk cache: {}",
+                            write.key, cause
+                        );
+                    }
+                }
+            });
+            (Some(disk), Some(tx))
+        } else {
+            (None, None)
+        };
+
+        Ok(Arc::new(DataBlockCache {
+            memory: Mutex::new(memory),
+            disk,
+            disk_writer,
+        }))
+    }
+
+    /// Get a cached column. A column found on disk is loaded into memory.
+    ///
+    /// `field` is the type of the column, it is required to decode a column stored on disk.
+    pub fn get(&self, key: &str, field: &DataField) -> Option<ColumnRef> {
+        if let Some(column) = self.memory.lock().get(key) {
+            return Some(column.clone());
+        }
+
+        let disk = self.disk.as_ref()?;
+        let bytes = {
+            let mut disk = disk.lock();
+            let disk_key = Self::disk_key(key);
+            if !disk.contains_key(&disk_key) {
+                return None;
+            }
+            let mut bytes = vec![];
+            disk.get(&disk_key).ok()?.read_to_end(&mut bytes).ok()?;
+            bytes
+        };
+
+        let column = Self::decode(field, &bytes).ok()?;
+        self.memory.lock().put(key.to_string(), column.clone());
+        Some(column)
+    }
+
+    /// Put a column into the memory tier, and queue it to be written to the disk tier.
+    ///
+    /// The disk write is skipped if the queue is full.
+    pub fn put(&self, key: String, field: &DataField, column: ColumnRef) {
+        if let Some(disk_writer) = self.disk_writer.as_ref() {
+            let write = DiskWrite {
+                key: key.clone(),
+                field: field.clone(),
+                column: column.clone(),
+            };
+            if let Err(TrySendError::Disconnected(write)) = disk_writer.try_send(write) {
+                warn!(
+                    "disk cache writer is gone, column {} is not written",
+                    write.key
+                );
+            }
+        }
+
+        self.memory.lock().put(key, column);
+    }
+
+    fn write_to_disk(disk: &Mutex<LruDiskCache>, write: &DiskWrite) -> Result<()> {
+        // encode outside of the lock, readers of the disk tier wait on it
+        let bytes = Self::encode(&write.field, &write.column)?;
+        let mut disk = disk.lock();
+        if disk.can_store(bytes.len() as u64) {
+            let disk_key = Self::disk_key(&write.key);
+            disk.remove(&disk_key)?;
+            disk.insert_bytes(&disk_key, &bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Keys contain the path of a block, make it a flat file name.
+    fn disk_key(key: &str) -> String {
+        key.replace('/', "_")
+    }
+
+    fn encode(field: &DataField, column: &ColumnRef) -> Result<Vec<u8>> {
+        let schema = Arc::new(DataSchema::new(vec![field.clone()]));
+        let block = DataBlock::create(schema.clone(), vec![column.clone()]);
+        let chunk = Chunk::try_from(block)?;
+
+        let mut bytes = vec![];
+        let options = WriteOptions { compression: None };
+        let mut writer = FileWriter::new(&mut bytes, schema.to_arrow(), None, options);
+        writer.start()?;
+        writer.write(&chunk, None)?;
+        writer.finish()?;
+        Ok(bytes)
+    }
+
+    fn decode(field: &DataField, bytes: &[u8]) -> Result<ColumnRef> {
+        let schema = Arc::new(DataSchema::new(vec![field.clone()]));
+
+        let mut cursor = Cursor::new(bytes);
+        let metadata = read_file_metadata(&mut cursor)?;
+        let mut reader = FileReader::new(cursor, metadata, None, None);
+        let chunk = match reader.next() {
+            Some(chunk) => chunk?,
+            None => return Err(ErrorCode::StorageOther("cached column file is empty")),
+        };
+
+        let block = DataBlock::from_chunk(&schema, &chunk)?;
+        Ok(block.column(0).clone())
+    }
+}
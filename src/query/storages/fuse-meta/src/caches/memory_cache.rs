@@ -12,21 +12,31 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::borrow::Borrow;
 use std::sync::Arc;
 
+use common_arrow::parquet::metadata::ColumnChunkMetaData;
 use common_arrow::parquet::metadata::FileMetaData;
+use common_arrow::parquet::metadata::RowGroupMetaData;
 use common_base::base::tokio::sync::RwLock;
 use common_cache::BytesMeter;
 use common_cache::Cache;
 use common_cache::Count;
 use common_cache::DefaultHashBuilder;
 use common_cache::LruCache;
+use common_cache::Meter;
+use common_datavalues::ColumnRef;
 
+use crate::caches::data_block_cache::ColumnMeter;
 use crate::meta::SegmentInfo;
 use crate::meta::TableSnapshot;
+use crate::meta::TableSnapshotStatistics;
+
+// cache meters by the given meter
+pub type MeteredItemCache<V, M> = Arc<RwLock<LruCache<String, Arc<V>, DefaultHashBuilder, M>>>;
 
 // cache meters by counting number of items
-pub type ItemCache<V> = Arc<RwLock<LruCache<String, Arc<V>, DefaultHashBuilder, Count>>>;
+pub type ItemCache<V> = MeteredItemCache<V, Count>;
 
 // cache meters by bytes
 pub type BytesCache = Arc<RwLock<LruCache<String, Arc<Vec<u8>>, DefaultHashBuilder, BytesMeter>>>;
@@ -40,13 +50,67 @@ pub fn new_bytes_cache(capacity: u64) -> BytesCache {
     Arc::new(RwLock::new(c))
 }
 
+pub fn new_file_meta_data_cache(capacity: u64) -> FileMetaDataCache {
+    let c = LruCache::with_meter_and_hasher(capacity, FileMetaDataMeter, DefaultHashBuilder::new());
+    Arc::new(RwLock::new(c))
+}
+
+pub fn new_bloom_index_filter_cache(capacity: u64) -> BloomIndexFilterCache {
+    let c = LruCache::with_meter_and_hasher(capacity, ColumnMeter, DefaultHashBuilder::new());
+    Arc::new(RwLock::new(c))
+}
+
 pub type SegmentInfoCache = ItemCache<SegmentInfo>;
 pub type TableSnapshotCache = ItemCache<TableSnapshot>;
+pub type TableSnapshotStatisticsCache = ItemCache<TableSnapshotStatistics>;
 /// Cache bloom filter.
 /// For each index block, columns are cached individually.
 pub type BloomIndexCache = BytesCache;
+/// Deserialized filters of bloom filter index, so that they are not parsed again on each pruning.
+/// Each cache item per filter column of an index block, metered by memory size.
+pub type BloomIndexFilterCache =
+    Arc<RwLock<LruCache<String, ColumnRef, DefaultHashBuilder, ColumnMeter>>>;
 /// FileMetaCache of bloom filter index data.
 /// Each cache item per block
 pub type BloomIndexMetaCache = ItemCache<FileMetaData>;
 
-pub type FileMetaDataCache = ItemCache<FileMetaData>;
+/// Parsed footers of parquet files, metered by the estimated memory size.
+/// Each cache item per file
+pub type FileMetaDataCache = MeteredItemCache<FileMetaData, FileMetaDataMeter>;
+
+/// Measures a parsed parquet footer by its estimated memory size.
+///
+/// `FileMetaData` doesn't expose its size, the estimation counts the structures of row groups
+/// and column chunks, and the min/max values of column statistics, which dominate large footers.
+pub struct FileMetaDataMeter;
+
+impl<K> Meter<K, Arc<FileMetaData>> for FileMetaDataMeter {
+    type Measure = usize;
+    fn measure<Q: ?Sized>(&self, _: &Q, v: &Arc<FileMetaData>) -> usize
+    where K: Borrow<Q> {
+        let row_groups: usize = v
+            .row_groups
+            .iter()
+            .map(|row_group| {
+                let columns: usize = row_group.columns().iter().map(column_chunk_size).sum();
+                std::mem::size_of::<RowGroupMetaData>() + columns
+            })
+            .sum();
+        std::mem::size_of::<FileMetaData>() + row_groups
+    }
+}
+
+fn column_chunk_size(column: &ColumnChunkMetaData) -> usize {
+    let statistics = column
+        .metadata()
+        .statistics
+        .as_ref()
+        .map(|s| {
+            [&s.max, &s.min, &s.max_value, &s.min_value]
+                .iter()
+                .filter_map(|v| v.as_ref().map(|v| v.len()))
+                .sum()
+        })
+        .unwrap_or(0);
+    std::mem::size_of::<ColumnChunkMetaData>() + statistics
+}
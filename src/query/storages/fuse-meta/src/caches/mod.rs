@@ -13,14 +13,24 @@
 // limitations under the License.
 
 mod cache;
+mod data_block_cache;
 mod memory_cache;
 mod metrics;
+mod query_result_cache;
 
 pub use cache::CacheManager;
+pub use data_block_cache::DataBlockCache;
+pub use data_block_cache::DataBlockCacheRef;
 pub use memory_cache::new_item_cache;
+pub use memory_cache::FileMetaDataCache;
+pub use memory_cache::FileMetaDataMeter;
 pub use memory_cache::ItemCache;
+pub use memory_cache::MeteredItemCache;
 pub use memory_cache::SegmentInfoCache;
 pub use memory_cache::TableSnapshotCache;
+pub use memory_cache::TableSnapshotStatisticsCache;
+pub use query_result_cache::new_query_result_cache;
+pub use query_result_cache::QueryResultCache;
 
 pub use self::metrics::CacheDeferMetrics;
 pub use self::metrics::TenantLabel;
@@ -0,0 +1,29 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use common_base::base::tokio::sync::RwLock;
+use common_cache::LruDiskCache;
+use common_exception::Result;
+
+/// Serialized result sets of queries, stored as files in a local directory.
+/// The size of all cached result sets is limited, the least recently used ones are evicted first.
+pub type QueryResultCache = Arc<RwLock<LruDiskCache>>;
+
+pub fn new_query_result_cache(path: impl AsRef<Path>, capacity: u64) -> Result<QueryResultCache> {
+    let c = LruDiskCache::new(path.as_ref().to_path_buf(), capacity)?;
+    Ok(Arc::new(RwLock::new(c)))
+}
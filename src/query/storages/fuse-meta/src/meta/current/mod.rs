@@ -15,8 +15,14 @@
 pub use v0::ColumnMeta;
 pub use v1::BlockBloomFilterIndex;
 pub use v1::BlockMeta;
+pub use v1::ColumnDistribution;
+pub use v1::Histogram;
+pub use v1::HistogramBucket;
+pub use v1::HyperLogLog;
 pub use v1::SegmentInfo;
 pub use v1::TableSnapshot;
+pub use v1::TableSnapshotStatistics;
+pub use v1::DEFAULT_HISTOGRAM_BUCKETS;
 
 use super::v0;
 use super::v1;
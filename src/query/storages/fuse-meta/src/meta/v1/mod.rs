@@ -15,8 +15,15 @@
 mod index;
 mod segment;
 mod snapshot;
+mod snapshot_statistics;
 
 pub use index::BlockBloomFilterIndex;
 pub use segment::BlockMeta;
 pub use segment::SegmentInfo;
 pub use snapshot::TableSnapshot;
+pub use snapshot_statistics::ColumnDistribution;
+pub use snapshot_statistics::Histogram;
+pub use snapshot_statistics::HistogramBucket;
+pub use snapshot_statistics::HyperLogLog;
+pub use snapshot_statistics::TableSnapshotStatistics;
+pub use snapshot_statistics::DEFAULT_HISTOGRAM_BUCKETS;
@@ -64,6 +64,12 @@ pub struct BlockMeta {
     /// used in the write path.
     #[serde(default = "Compression::legacy")]
     compression: Compression,
+
+    /// Version of the table schema the block is written with
+    ///
+    /// Blocks written before the first change of the columns of the table are of version 0.
+    #[serde(default)]
+    pub schema_version: u64,
 }
 
 impl BlockMeta {
@@ -90,6 +96,7 @@ impl BlockMeta {
             bloom_filter_index_location,
             bloom_filter_index_size,
             compression: Compression::Lz4Raw,
+            schema_version: 0,
         }
     }
 
@@ -137,6 +144,7 @@ impl From<v0::BlockMeta> for BlockMeta {
             bloom_filter_index_location: None,
             bloom_filter_index_size: 0,
             compression: Compression::Lz4,
+            schema_version: 0,
         }
     }
 }
@@ -57,6 +57,11 @@ pub struct TableSnapshot {
 
     // The metadata of the cluster keys.
     pub cluster_key_meta: Option<ClusterKey>,
+
+    /// Pointer to the statistics of the columns, if the table has been analyzed
+    //  for backward compatibility, `serde(default)` is used
+    #[serde(default)]
+    pub table_statistics_location: Option<Location>,
 }
 
 impl TableSnapshot {
@@ -86,13 +91,14 @@ impl TableSnapshot {
             summary,
             segments,
             cluster_key_meta,
+            table_statistics_location: None,
         }
     }
 
     pub fn from_previous(previous: &TableSnapshot) -> Self {
         let id = Uuid::new_v4();
         let clone = previous.clone();
-        Self::new(
+        let mut snapshot = Self::new(
             id,
             &clone.timestamp,
             Some((clone.snapshot_id, clone.format_version)),
@@ -100,7 +106,9 @@ impl TableSnapshot {
             clone.summary,
             clone.segments,
             clone.cluster_key_meta,
-        )
+        );
+        snapshot.table_statistics_location = clone.table_statistics_location;
+        snapshot
     }
 
     pub fn format_version(&self) -> u64 {
@@ -121,6 +129,7 @@ impl From<v0::TableSnapshot> for TableSnapshot {
             summary: s.summary,
             segments: s.segments.into_iter().map(|l| (l, 0)).collect(),
             cluster_key_meta: None,
+            table_statistics_location: None,
         }
     }
 }
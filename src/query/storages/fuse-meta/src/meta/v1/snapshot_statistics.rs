@@ -0,0 +1,327 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use common_base::base::uuid::Uuid;
+use common_datavalues::DataValue;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::meta::common::FormatVersion;
+use crate::meta::ColumnId;
+use crate::meta::Versioned;
+
+/// Number of the buckets of the histograms.
+pub const DEFAULT_HISTOGRAM_BUCKETS: usize = 100;
+
+// 2^12 registers, the standard error of the estimation is about 1.6%
+const HLL_PRECISION: u32 = 12;
+
+/// The statistics of the columns of a snapshot, collected by `ANALYZE TABLE` and kept up to date
+/// while data is appended.
+///
+/// They are stored apart from the snapshot, since the sketches of the distinct values are large.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TableSnapshotStatistics {
+    /// format version of the statistics
+    format_version: FormatVersion,
+
+    /// id of the statistics, which the location is generated from
+    pub id: Uuid,
+
+    /// Number of the rows the statistics are collected from
+    pub row_count: u64,
+
+    /// Distributions of the values of the columns, keyed by the leaf column id
+    pub column_distributions: HashMap<ColumnId, ColumnDistribution>,
+}
+
+impl TableSnapshotStatistics {
+    pub fn new(
+        row_count: u64,
+        column_distributions: HashMap<ColumnId, ColumnDistribution>,
+    ) -> Self {
+        Self {
+            format_version: TableSnapshotStatistics::VERSION,
+            id: Uuid::new_v4(),
+            row_count,
+            column_distributions,
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+
+    /// Fold the statistics of more rows in.
+    pub fn merge(mut self, other: &TableSnapshotStatistics) -> TableSnapshotStatistics {
+        for (column_id, distribution) in &other.column_distributions {
+            let merged = match self.column_distributions.get(column_id) {
+                Some(previous) => previous.merge(distribution),
+                None => distribution.clone(),
+            };
+            self.column_distributions.insert(*column_id, merged);
+        }
+
+        TableSnapshotStatistics::new(self.row_count + other.row_count, self.column_distributions)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ColumnDistribution {
+    /// Min of the non-null values, `Null` if there is none
+    pub min: DataValue,
+    /// Max of the non-null values, `Null` if there is none
+    pub max: DataValue,
+    pub null_count: u64,
+    /// Sketch of the distinct non-null values
+    pub distinct_values: HyperLogLog,
+    /// Histogram of the values, only for the numeric columns
+    pub histogram: Option<Histogram>,
+}
+
+impl Default for ColumnDistribution {
+    fn default() -> Self {
+        Self {
+            min: DataValue::Null,
+            max: DataValue::Null,
+            null_count: 0,
+            distinct_values: HyperLogLog::default(),
+            histogram: None,
+        }
+    }
+}
+
+impl ColumnDistribution {
+    pub fn merge(&self, other: &ColumnDistribution) -> ColumnDistribution {
+        let min = match (&self.min, &other.min) {
+            (DataValue::Null, v) | (v, DataValue::Null) => v.clone(),
+            (l, r) => l.min(r).clone(),
+        };
+        let max = match (&self.max, &other.max) {
+            (DataValue::Null, v) | (v, DataValue::Null) => v.clone(),
+            (l, r) => l.max(r).clone(),
+        };
+        let histogram = match (&self.histogram, &other.histogram) {
+            (Some(l), Some(r)) => Some(l.merge(r, DEFAULT_HISTOGRAM_BUCKETS)),
+            (Some(h), None) | (None, Some(h)) => Some(h.clone()),
+            (None, None) => None,
+        };
+
+        ColumnDistribution {
+            min,
+            max,
+            null_count: self.null_count + other.null_count,
+            distinct_values: self.distinct_values.merge(&other.distinct_values),
+            histogram,
+        }
+    }
+}
+
+/// A HyperLogLog sketch, counting the distinct values approximately in a fixed size.
+///
+/// Sketches are merged losslessly, so that the distinct values of appended data can be folded in
+/// without reading the data that has been analyzed.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self {
+            registers: vec![0; 1 << HLL_PRECISION],
+        }
+    }
+}
+
+impl HyperLogLog {
+    pub fn add<T: Hash + ?Sized>(&mut self, value: &T) {
+        let mut hasher = SketchHasher::default();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let index = (hash >> (64 - HLL_PRECISION)) as usize;
+        // position of the first set bit of the remaining bits, the sentinel bit bounds it
+        let rank = ((hash << HLL_PRECISION) | (1 << (HLL_PRECISION - 1))).leading_zeros() + 1;
+        self.registers[index] = self.registers[index].max(rank as u8);
+    }
+
+    pub fn merge(&self, other: &HyperLogLog) -> HyperLogLog {
+        let registers = self
+            .registers
+            .iter()
+            .zip(&other.registers)
+            .map(|(l, r)| *l.max(r))
+            .collect();
+        HyperLogLog { registers }
+    }
+
+    pub fn estimate(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum: f64 = self.registers.iter().map(|r| 2f64.powi(-(*r as i32))).sum();
+        let estimate = alpha * m * m / sum;
+
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            // linear counting is more accurate for the small cardinalities
+            (m * (m / zeros as f64).ln()).round() as u64
+        } else {
+            estimate.round() as u64
+        }
+    }
+}
+
+/// FNV-1a, finalized by the mixer of MurmurHash3. The hashes are persisted in the sketches, so
+/// they must be stable across releases, which `DefaultHasher` doesn't promise.
+struct SketchHasher(u64);
+
+impl Default for SketchHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for SketchHasher {
+    fn finish(&self) -> u64 {
+        let mut h = self.0;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51afd7ed558ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ceb9fe1a85ec53);
+        h ^ (h >> 33)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+/// An equi-height histogram, each bucket holds about the same number of values, which are
+/// assumed to be spread uniformly between the bounds of the bucket.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Histogram {
+    pub buckets: Vec<HistogramBucket>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HistogramBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: f64,
+}
+
+impl Histogram {
+    pub fn from_sorted_values(values: &[f64], num_buckets: usize) -> Histogram {
+        let num_buckets = num_buckets.min(values.len());
+        let mut buckets: Vec<HistogramBucket> = Vec::with_capacity(num_buckets);
+        for i in 0..num_buckets {
+            let start = i * values.len() / num_buckets;
+            let end = (i + 1) * values.len() / num_buckets;
+            match buckets.last_mut() {
+                // the repeated values stay in one bucket
+                Some(last) if last.upper == values[end - 1] => last.count += (end - start) as f64,
+                _ => buckets.push(HistogramBucket {
+                    lower: values[start],
+                    upper: values[end - 1],
+                    count: (end - start) as f64,
+                }),
+            }
+        }
+
+        Histogram { buckets }
+    }
+
+    pub fn total(&self) -> f64 {
+        self.buckets.iter().map(|bucket| bucket.count).sum()
+    }
+
+    /// Number of the values less than or equal to `value`.
+    pub fn count_le(&self, value: f64) -> f64 {
+        self.buckets
+            .iter()
+            .map(|bucket| {
+                if value >= bucket.upper {
+                    bucket.count
+                } else if value < bucket.lower {
+                    0.0
+                } else {
+                    bucket.count * (value - bucket.lower) / (bucket.upper - bucket.lower)
+                }
+            })
+            .sum()
+    }
+
+    /// Merge two histograms into one of at most `num_buckets` buckets, whose bounds are the
+    /// quantiles of the values of both, interpolated between the bounds of their buckets.
+    pub fn merge(&self, other: &Histogram, num_buckets: usize) -> Histogram {
+        let mut bounds: Vec<f64> = self
+            .buckets
+            .iter()
+            .chain(&other.buckets)
+            .flat_map(|bucket| [bucket.lower, bucket.upper])
+            .collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+
+        let total = self.total() + other.total();
+        if bounds.is_empty() || total <= 0.0 {
+            return Histogram::default();
+        }
+
+        let cumulative: Vec<f64> = bounds
+            .iter()
+            .map(|bound| self.count_le(*bound) + other.count_le(*bound))
+            .collect();
+
+        let mut buckets: Vec<HistogramBucket> = Vec::with_capacity(num_buckets);
+        let mut lower = bounds[0];
+        let mut counted = 0.0;
+        let mut i = 0;
+        for k in 1..=num_buckets {
+            let target = total * k as f64 / num_buckets as f64;
+            while i + 1 < bounds.len() && cumulative[i] < target {
+                i += 1;
+            }
+
+            let upper = if i == 0 || cumulative[i] <= cumulative[i - 1] {
+                bounds[i]
+            } else {
+                let fraction = (target - cumulative[i - 1]) / (cumulative[i] - cumulative[i - 1]);
+                bounds[i - 1] + (bounds[i] - bounds[i - 1]) * fraction.clamp(0.0, 1.0)
+            };
+            let count = target - counted;
+            counted = target;
+
+            match buckets.last_mut() {
+                Some(last) if last.upper >= upper => last.count += count,
+                _ => buckets.push(HistogramBucket {
+                    lower,
+                    upper,
+                    count,
+                }),
+            }
+            lower = upper;
+        }
+
+        Histogram { buckets }
+    }
+}
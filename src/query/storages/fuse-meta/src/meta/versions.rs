@@ -20,6 +20,7 @@ use common_exception::ErrorCode;
 use crate::meta::v0;
 use crate::meta::v1;
 use crate::meta::v1::BlockBloomFilterIndex;
+use crate::meta::v1::TableSnapshotStatistics;
 use crate::meta::Versioned;
 
 // Here versions of meta are tagged with numeric values
@@ -71,6 +72,12 @@ pub enum BlockBloomFilterIndexVersion {
     V1(PhantomData<v1::BlockBloomFilterIndex>),
 }
 
+impl Versioned<1> for TableSnapshotStatistics {}
+
+pub enum TableSnapshotStatisticsVersion {
+    V1(PhantomData<v1::TableSnapshotStatistics>),
+}
+
 mod converters {
 
     use super::*;
@@ -119,6 +126,20 @@ mod converters {
         }
     }
 
+    impl TryFrom<u64> for TableSnapshotStatisticsVersion {
+        type Error = ErrorCode;
+        fn try_from(value: u64) -> Result<Self, Self::Error> {
+            match value {
+                1 => Ok(TableSnapshotStatisticsVersion::V1(ver_eq::<_, 1>(
+                    PhantomData,
+                ))),
+                _ => Err(ErrorCode::LogicalError(format!(
+                    "unknown table snapshot statistics version {value}, versions supported: 1"
+                ))),
+            }
+        }
+    }
+
     /// Statically check that if T implements Versoined<U> where U equals V
     #[inline]
     fn ver_eq<T, const V: u64>(t: PhantomData<T>) -> PhantomData<T>
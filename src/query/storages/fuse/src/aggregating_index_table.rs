@@ -0,0 +1,223 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_catalog::catalog::CATALOG_DEFAULT;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::BlockMeta;
+use common_legacy_planners::Extras;
+use common_legacy_planners::Partitions;
+use common_legacy_planners::Projection;
+use common_legacy_planners::ReadDataSourcePlan;
+use common_legacy_planners::Statistics;
+use common_meta_app::schema::TableInfo;
+
+use crate::index::AggregatingIndexMeta;
+use crate::io::read_aggregating_index;
+use crate::io::BlockReader;
+use crate::io::MetaReaders;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::AsyncSource;
+use crate::pipelines::processors::AsyncSourcer;
+use crate::pipelines::Pipe;
+use crate::pipelines::Pipeline;
+use crate::sessions::TableContext;
+use crate::FuseTable;
+use crate::Table;
+use crate::OPT_KEY_AGGREGATING_INDEX_SCAN;
+
+/// Reads the pre-aggregated data of an aggregating index instead of the blocks of a fuse table.
+///
+/// The table info is the one of the base table, with the schema replaced by the schema of the
+/// index and the index name recorded in option `OPT_KEY_AGGREGATING_INDEX_SCAN`, so that the
+/// table can be rebuilt from the table info by [`FuseTable::try_create`].
+pub struct AggregatingIndexTable {
+    table_info: TableInfo,
+    index: AggregatingIndexMeta,
+}
+
+impl AggregatingIndexTable {
+    pub fn create(table: &FuseTable, index: AggregatingIndexMeta) -> Result<Arc<dyn Table>> {
+        let mut table_info = table.get_table_info().clone();
+        table_info.meta.schema = index.schema(&table.schema())?;
+        table_info.meta.options.insert(
+            OPT_KEY_AGGREGATING_INDEX_SCAN.to_string(),
+            index.name.clone(),
+        );
+        Ok(Arc::new(AggregatingIndexTable { table_info, index }))
+    }
+
+    pub fn try_create(table_info: TableInfo) -> Result<Box<dyn Table>> {
+        let index_name = &table_info.meta.options[OPT_KEY_AGGREGATING_INDEX_SCAN];
+        let fuse_table = FuseTable::do_create(table_info.clone(), true)?;
+        let index = fuse_table
+            .aggregating_indexes()?
+            .into_iter()
+            .find(|index| &index.name == index_name)
+            .ok_or_else(|| {
+                ErrorCode::UnknownAggregatingIndex(format!(
+                    "Unknown aggregating index {} of table {}",
+                    index_name, table_info.desc
+                ))
+            })?;
+        Ok(Box::new(AggregatingIndexTable { table_info, index }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for AggregatingIndexTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read_partitions(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        Ok((Statistics::default(), vec![]))
+    }
+
+    fn read2(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        _: &ReadDataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let output = OutputPort::create();
+        pipeline.add_pipe(Pipe::SimplePipe {
+            inputs_port: vec![],
+            outputs_port: vec![output.clone()],
+            processors: vec![AggregatingIndexSource::create(
+                ctx,
+                output,
+                self.table_info.clone(),
+                self.index.clone(),
+            )?],
+        });
+
+        Ok(())
+    }
+}
+
+struct AggregatingIndexSource {
+    ctx: Arc<dyn TableContext>,
+    table_info: TableInfo,
+    index: AggregatingIndexMeta,
+    state: Option<SourceState>,
+}
+
+struct SourceState {
+    table: Box<FuseTable>,
+    // Used for the blocks written before the index is created.
+    block_reader: Arc<BlockReader>,
+    blocks: VecDeque<BlockMeta>,
+}
+
+impl AggregatingIndexSource {
+    pub fn create(
+        ctx: Arc<dyn TableContext>,
+        output: Arc<OutputPort>,
+        table_info: TableInfo,
+        index: AggregatingIndexMeta,
+    ) -> Result<ProcessorPtr> {
+        AsyncSourcer::create(ctx.clone(), output, AggregatingIndexSource {
+            ctx,
+            table_info,
+            index,
+            state: None,
+        })
+    }
+
+    async fn init_state(&self) -> Result<SourceState> {
+        // Restore the base table from the table info, the schema is the latest one.
+        let catalog = self.ctx.get_catalog(CATALOG_DEFAULT)?;
+        let (_, table_meta) = catalog
+            .get_table_meta_by_id(self.table_info.ident.table_id)
+            .await?;
+        let mut table_info = self.table_info.clone();
+        table_info.meta.schema = table_meta.schema.clone();
+        table_info
+            .meta
+            .options
+            .remove(OPT_KEY_AGGREGATING_INDEX_SCAN);
+        let table = FuseTable::do_create(table_info, true)?;
+
+        let schema = table.schema();
+        let projection = self
+            .index
+            .source_columns()
+            .iter()
+            .map(|name| schema.index_of(name))
+            .collect::<Result<Vec<_>>>()?;
+        let block_reader = table.create_block_reader(&self.ctx, Projection::Columns(projection))?;
+
+        let mut blocks = VecDeque::new();
+        if let Some(snapshot) = table.read_table_snapshot(self.ctx.clone()).await? {
+            let reader = MetaReaders::segment_info_reader(self.ctx.as_ref());
+            for (location, ver) in &snapshot.segments {
+                let segment = reader.read(location, None, *ver).await?;
+                blocks.extend(segment.blocks.iter().cloned());
+            }
+        }
+
+        Ok(SourceState {
+            table,
+            block_reader,
+            blocks,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncSource for AggregatingIndexSource {
+    const NAME: &'static str = "aggregating_index";
+
+    #[async_trait::unboxed_simple]
+    async fn generate(&mut self) -> Result<Option<DataBlock>> {
+        if self.state.is_none() {
+            self.state = Some(self.init_state().await?);
+        }
+
+        let schema = self.table_info.schema();
+        let state = self.state.as_mut().unwrap();
+        let operator = self.ctx.get_storage_operator()?;
+        while let Some(block_meta) = state.blocks.pop_front() {
+            let location = state
+                .table
+                .meta_location_generator()
+                .aggregating_index_location(&self.index.id, &block_meta.location.0);
+            let block = match read_aggregating_index(&operator, &location, &schema).await? {
+                Some(block) => block,
+                None => {
+                    let block = state.block_reader.read_with_block_meta(&block_meta).await?;
+                    self.index.aggregate_block(&schema, &block)?
+                }
+            };
+            if !block.is_empty() {
+                return Ok(Some(block));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -19,9 +19,14 @@ pub const FUSE_OPT_KEY_ROW_AVG_DEPTH_THRESHOLD: &str = "row_avg_depth_threshold"
 
 pub const FUSE_TBL_BLOCK_PREFIX: &str = "_b";
 pub const FUSE_TBL_BLOCK_INDEX_PREFIX: &str = "_i";
+pub const FUSE_TBL_AGGREGATING_INDEX_PREFIX: &str = "_i_a";
+pub const FUSE_TBL_INVERTED_INDEX_PREFIX: &str = "_i_i";
 pub const FUSE_TBL_SEGMENT_PREFIX: &str = "_sg";
 pub const FUSE_TBL_SNAPSHOT_PREFIX: &str = "_ss";
+pub const FUSE_TBL_SNAPSHOT_STATISTICS_PREFIX: &str = "_ts";
+pub const FUSE_TBL_VIRTUAL_COLUMN_PREFIX: &str = "_vc";
 pub const FUSE_TBL_LAST_SNAPSHOT_HINT: &str = "last_snapshot_location_hint";
+pub const FUSE_TBL_DATA_OWNER: &str = "data_owner";
 
 pub const DEFAULT_BLOCK_PER_SEGMENT: usize = 1000;
 pub const DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD: usize = 100 * 1024 * 1024;
@@ -22,11 +22,12 @@ use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::meta::Compression;
+use common_fuse_meta::meta::StatisticsOfColumns;
 use common_legacy_planners::PartInfo;
 use common_legacy_planners::PartInfoPtr;
 use common_legacy_planners::Projection;
 
-#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
 pub struct ColumnMeta {
     pub offset: u64,
     pub length: u64,
@@ -43,7 +44,7 @@ impl ColumnMeta {
     }
 }
 
-#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
 pub struct FusePartInfo {
     pub location: String,
     /// FusePartInfo itself is not versioned
@@ -51,7 +52,13 @@ pub struct FusePartInfo {
     pub format_version: u64,
     pub nums_rows: usize,
     pub columns_meta: HashMap<usize, ColumnMeta>,
+    /// Statistics of the columns read, keyed by the leaf column id
+    pub columns_stat: StatisticsOfColumns,
     pub compression: Compression,
+    /// Version of the table schema the block is written with
+    pub schema_version: u64,
+    /// The sorted rows of the block read, selected by the inverted indexes, `None` for all rows
+    pub row_selection: Option<Vec<u32>>,
 }
 
 #[typetag::serde(name = "fuse")]
@@ -74,14 +81,27 @@ impl FusePartInfo {
         format_version: u64,
         rows_count: u64,
         columns_meta: HashMap<usize, ColumnMeta>,
+        columns_stat: StatisticsOfColumns,
         compression: Compression,
+        schema_version: u64,
     ) -> Arc<Box<dyn PartInfo>> {
         Arc::new(Box::new(FusePartInfo {
             location,
             format_version,
             columns_meta,
+            columns_stat,
             nums_rows: rows_count as usize,
             compression,
+            schema_version,
+            row_selection: None,
+        }))
+    }
+
+    /// The part reading the `rows` of the block only.
+    pub fn with_row_selection(&self, rows: Vec<u32>) -> PartInfoPtr {
+        Arc::new(Box::new(FusePartInfo {
+            row_selection: Some(rows),
+            ..self.clone()
         }))
     }
 
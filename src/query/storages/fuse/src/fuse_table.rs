@@ -13,10 +13,15 @@
 //  limitations under the License.
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
+use chrono::Duration;
+use chrono::Utc;
 use common_catalog::catalog::StorageDescription;
+use common_catalog::table::AlterColumnAction;
+use common_catalog::table::ColumnStatistics;
 use common_catalog::table_context::TableContext;
 use common_catalog::table_mutator::TableMutator;
 use common_datablocks::DataBlock;
@@ -38,21 +43,35 @@ use common_storages_util::storage_context::StorageContext;
 use common_storages_util::table_storage_prefix::table_storage_prefix;
 use uuid::Uuid;
 
+use crate::index::AggregatingIndexMeta;
+use crate::index::BloomIndexMeta;
+use crate::index::InvertedIndexMeta;
+use crate::index::VirtualColumnsMeta;
 use crate::io::BlockCompactor;
 use crate::io::MetaReaders;
 use crate::io::TableMetaLocationGenerator;
 use crate::operations::AppendOperationLogEntry;
 use crate::pipelines::Pipeline;
+use crate::AggregatingIndexTable;
+use crate::CompactTarget;
 use crate::NavigationPoint;
+use crate::SchemaHistory;
 use crate::Table;
 use crate::TableStatistics;
 use crate::DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD;
 use crate::DEFAULT_ROW_PER_BLOCK;
 use crate::FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD;
 use crate::FUSE_OPT_KEY_ROW_PER_BLOCK;
+use crate::OPT_KEY_AGGREGATING_INDEX_PREFIX;
+use crate::OPT_KEY_AGGREGATING_INDEX_SCAN;
+use crate::OPT_KEY_BLOOM_INDEX_PREFIX;
 use crate::OPT_KEY_DATABASE_ID;
+use crate::OPT_KEY_INVERTED_INDEX_PREFIX;
 use crate::OPT_KEY_LEGACY_SNAPSHOT_LOC;
 use crate::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::OPT_KEY_STORAGE_PREFIX;
+use crate::OPT_KEY_TABLE_ATTACHED_READ_ONLY;
+use crate::OPT_KEY_VIRTUAL_COLUMNS;
 
 #[derive(Clone)]
 pub struct FuseTable {
@@ -66,7 +85,16 @@ pub struct FuseTable {
 
 impl FuseTable {
     pub fn try_create(_ctx: StorageContext, table_info: TableInfo) -> Result<Box<dyn Table>> {
-        let r = Self::do_create(table_info, false)?;
+        if table_info
+            .options()
+            .contains_key(OPT_KEY_AGGREGATING_INDEX_SCAN)
+        {
+            return AggregatingIndexTable::try_create(table_info);
+        }
+        let read_only = table_info
+            .options()
+            .contains_key(OPT_KEY_TABLE_ATTACHED_READ_ONLY);
+        let r = Self::do_create(table_info, read_only)?;
         Ok(r)
     }
 
@@ -100,6 +128,10 @@ impl FuseTable {
     }
 
     pub fn parse_storage_prefix(table_info: &TableInfo) -> Result<String> {
+        // the data of an attached table is stored by another table
+        if let Some(storage_prefix) = table_info.options().get(OPT_KEY_STORAGE_PREFIX) {
+            return Ok(storage_prefix.clone());
+        }
         let table_id = table_info.ident.table_id;
         let db_id = table_info
             .options()
@@ -118,15 +150,34 @@ impl FuseTable {
         &self,
         ctx: Arc<dyn TableContext>,
     ) -> Result<Option<Arc<TableSnapshot>>> {
-        if let Some(loc) = self.snapshot_loc() {
+        if let Some(loc) = self.current_snapshot_loc(ctx.as_ref()).await? {
             let reader = MetaReaders::table_snapshot_reader(ctx);
-            let ver = self.snapshot_format_version();
+            let ver = TableMetaLocationGenerator::snapshot_version(loc.as_str());
             Ok(Some(reader.read(loc.as_str(), None, ver).await?))
         } else {
             Ok(None)
         }
     }
 
+    /// The location of the snapshot to read.
+    ///
+    /// The snapshots of a table attached by `ATTACH TABLE ... READ_ONLY` are written by the owner
+    /// of the data, the latest one is located by the hint file the owner leaves.
+    pub async fn current_snapshot_loc(&self, ctx: &dyn TableContext) -> Result<Option<String>> {
+        if !self.is_attached_read_only() {
+            return Ok(self.snapshot_loc());
+        }
+        let operator = ctx.get_storage_operator()?;
+        let hint = Self::read_last_snapshot_hint(&operator, &self.meta_location_generator).await?;
+        Ok(hint.or_else(|| self.snapshot_loc()))
+    }
+
+    pub fn is_attached_read_only(&self) -> bool {
+        self.table_info
+            .options()
+            .contains_key(OPT_KEY_TABLE_ATTACHED_READ_ONLY)
+    }
+
     pub fn snapshot_format_version(&self) -> u64 {
         match self.snapshot_loc() {
             Some(loc) => TableMetaLocationGenerator::snapshot_version(loc.as_str()),
@@ -172,6 +223,55 @@ impl FuseTable {
         self.table_info.meta.options.contains_key("TRANSIENT")
     }
 
+    /// Returns the aggregating indexes of the table, ordered by name.
+    pub fn aggregating_indexes(&self) -> Result<Vec<AggregatingIndexMeta>> {
+        self.table_info
+            .options()
+            .iter()
+            .filter(|(key, _)| key.starts_with(OPT_KEY_AGGREGATING_INDEX_PREFIX))
+            .map(|(_, value)| Ok(serde_json::from_str(value)?))
+            .collect()
+    }
+
+    /// Returns the bloom indexes of the table, ordered by name.
+    pub fn bloom_indexes(&self) -> Result<Vec<BloomIndexMeta>> {
+        self.table_info
+            .options()
+            .iter()
+            .filter(|(key, _)| key.starts_with(OPT_KEY_BLOOM_INDEX_PREFIX))
+            .map(|(_, value)| Ok(serde_json::from_str(value)?))
+            .collect()
+    }
+
+    /// Columns the bloom filters are built for, `None` for all the applicable columns.
+    pub fn bloom_index_columns(&self) -> Result<Option<Vec<String>>> {
+        Ok(BloomIndexMeta::covered_columns(&self.bloom_indexes()?))
+    }
+
+    /// Returns the inverted indexes of the table, ordered by name.
+    pub fn inverted_indexes(&self) -> Result<Vec<InvertedIndexMeta>> {
+        self.table_info
+            .options()
+            .iter()
+            .filter(|(key, _)| key.starts_with(OPT_KEY_INVERTED_INDEX_PREFIX))
+            .map(|(_, value)| Ok(serde_json::from_str(value)?))
+            .collect()
+    }
+
+    /// Returns the virtual columns of the table, `None` if none is created.
+    pub fn virtual_columns(&self) -> Result<Option<VirtualColumnsMeta>> {
+        self.table_info
+            .options()
+            .get(OPT_KEY_VIRTUAL_COLUMNS)
+            .map(|value| Ok(serde_json::from_str(value)?))
+            .transpose()
+    }
+
+    /// Version of the schema of the table, recorded by the blocks written.
+    pub fn schema_version(&self) -> Result<u64> {
+        SchemaHistory::version_of(&self.table_info.schema())
+    }
+
     pub(crate) fn get_block_compactor(&self) -> BlockCompactor {
         let max_rows_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
         let min_rows_per_block = (max_rows_per_block as f64 * 0.8) as usize;
@@ -227,13 +327,17 @@ impl Table for FuseTable {
         let prev_version = self.snapshot_format_version();
         let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
         let prev_snapshot_id = prev.as_ref().map(|v| (v.snapshot_id, prev_version));
-        let (summary, segments) = if let Some(v) = prev {
-            (v.summary.clone(), v.segments.clone())
+        let (summary, segments, table_statistics_location) = if let Some(v) = prev {
+            (
+                v.summary.clone(),
+                v.segments.clone(),
+                v.table_statistics_location.clone(),
+            )
         } else {
-            (FuseStatistics::default(), vec![])
+            (FuseStatistics::default(), vec![], None)
         };
 
-        let new_snapshot = TableSnapshot::new(
+        let mut new_snapshot = TableSnapshot::new(
             Uuid::new_v4(),
             &prev_timestamp,
             prev_snapshot_id,
@@ -242,6 +346,7 @@ impl Table for FuseTable {
             segments,
             cluster_key_meta,
         );
+        new_snapshot.table_statistics_location = table_statistics_location;
 
         let mut table_info = self.table_info.clone();
         table_info.meta = new_table_meta;
@@ -270,13 +375,17 @@ impl Table for FuseTable {
         let prev_version = self.snapshot_format_version();
         let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
         let prev_snapshot_id = prev.as_ref().map(|v| (v.snapshot_id, prev_version));
-        let (summary, segments) = if let Some(v) = prev {
-            (v.summary.clone(), v.segments.clone())
+        let (summary, segments, table_statistics_location) = if let Some(v) = prev {
+            (
+                v.summary.clone(),
+                v.segments.clone(),
+                v.table_statistics_location.clone(),
+            )
         } else {
-            (FuseStatistics::default(), vec![])
+            (FuseStatistics::default(), vec![], None)
         };
 
-        let new_snapshot = TableSnapshot::new(
+        let mut new_snapshot = TableSnapshot::new(
             Uuid::new_v4(),
             &prev_timestamp,
             prev_snapshot_id,
@@ -285,6 +394,7 @@ impl Table for FuseTable {
             segments,
             None,
         );
+        new_snapshot.table_statistics_location = table_statistics_location;
 
         let mut table_info = self.table_info.clone();
         table_info.meta = new_table_meta;
@@ -298,6 +408,15 @@ impl Table for FuseTable {
         .await
     }
 
+    async fn alter_table_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        action: AlterColumnAction,
+    ) -> Result<()> {
+        self.check_mutable()?;
+        self.do_alter_table_column(ctx, action).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_read_partitions", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn read_partitions(
         &self,
@@ -352,7 +471,19 @@ impl Table for FuseTable {
     #[tracing::instrument(level = "debug", name = "fuse_table_optimize", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn optimize(&self, ctx: Arc<dyn TableContext>, keep_last_snapshot: bool) -> Result<()> {
         self.check_mutable()?;
-        self.do_gc(&ctx, keep_last_snapshot).await
+        let retention_period = ctx.get_settings().get_retention_period()?;
+        let retain_since = if retention_period > 0 {
+            Some(Utc::now() - Duration::hours(retention_period as i64))
+        } else {
+            None
+        };
+        self.do_gc(&ctx, keep_last_snapshot, retain_since).await
+    }
+
+    #[tracing::instrument(level = "debug", name = "fuse_table_analyze", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn analyze(&self, ctx: Arc<dyn TableContext>) -> Result<()> {
+        self.check_mutable()?;
+        self.do_analyze(&ctx).await
     }
 
     async fn statistics(&self, _ctx: Arc<dyn TableContext>) -> Result<Option<TableStatistics>> {
@@ -365,6 +496,13 @@ impl Table for FuseTable {
         }))
     }
 
+    async fn column_statistics(
+        &self,
+        ctx: Arc<dyn TableContext>,
+    ) -> Result<HashMap<usize, ColumnStatistics>> {
+        self.do_column_statistics(ctx).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_navigate_to", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn navigate_to(
         &self,
@@ -381,6 +519,12 @@ impl Table for FuseTable {
         }
     }
 
+    #[tracing::instrument(level = "debug", name = "fuse_table_flashback", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn flashback(&self, ctx: Arc<dyn TableContext>, point: &NavigationPoint) -> Result<()> {
+        self.check_mutable()?;
+        self.do_flashback(ctx, point).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_delete", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn delete(&self, ctx: Arc<dyn TableContext>, delete_plan: DeletePlan) -> Result<()> {
         self.do_delete(ctx, &delete_plan).await
@@ -389,9 +533,10 @@ impl Table for FuseTable {
     async fn compact(
         &self,
         ctx: Arc<dyn TableContext>,
+        target: CompactTarget,
         pipeline: &mut Pipeline,
     ) -> Result<Option<Arc<dyn TableMutator>>> {
-        self.do_compact(ctx, pipeline).await
+        self.do_compact(ctx, target, pipeline).await
     }
 
     async fn recluster(
@@ -20,13 +20,19 @@ use common_fuse_meta::meta::BlockBloomFilterIndex;
 use common_fuse_meta::meta::Location;
 use common_fuse_meta::meta::SegmentInfo;
 use common_fuse_meta::meta::SnapshotVersion;
+use common_fuse_meta::meta::TableSnapshotStatistics;
 use common_fuse_meta::meta::Versioned;
 use uuid::Uuid;
 
+use crate::constants::FUSE_TBL_AGGREGATING_INDEX_PREFIX;
 use crate::constants::FUSE_TBL_BLOCK_PREFIX;
+use crate::constants::FUSE_TBL_INVERTED_INDEX_PREFIX;
 use crate::constants::FUSE_TBL_SEGMENT_PREFIX;
 use crate::constants::FUSE_TBL_SNAPSHOT_PREFIX;
+use crate::constants::FUSE_TBL_SNAPSHOT_STATISTICS_PREFIX;
+use crate::constants::FUSE_TBL_VIRTUAL_COLUMN_PREFIX;
 use crate::FUSE_TBL_BLOCK_INDEX_PREFIX;
+use crate::FUSE_TBL_DATA_OWNER;
 use crate::FUSE_TBL_LAST_SNAPSHOT_HINT;
 
 static SNAPSHOT_V0: SnapshotVersion = SnapshotVersion::V0(PhantomData);
@@ -76,6 +82,40 @@ impl TableMetaLocationGenerator {
         )
     }
 
+    /// Location of the aggregating index `index_id` of the block at `block_location`.
+    pub fn aggregating_index_location(&self, index_id: &str, block_location: &str) -> String {
+        let block_name = block_location.rsplit('/').next().unwrap_or(block_location);
+        let block_stem = block_name.trim_end_matches(".parquet");
+        format!(
+            "{}/{}/{}/{}.arrow",
+            &self.prefix, FUSE_TBL_AGGREGATING_INDEX_PREFIX, index_id, block_stem,
+        )
+    }
+
+    /// Location of the inverted index `index_id` of the block at `block_location`.
+    pub fn inverted_index_location(&self, index_id: &str, block_location: &str) -> String {
+        let block_name = block_location.rsplit('/').next().unwrap_or(block_location);
+        let block_stem = block_name.trim_end_matches(".parquet");
+        format!(
+            "{}/{}/{}/{}.bin",
+            &self.prefix, FUSE_TBL_INVERTED_INDEX_PREFIX, index_id, block_stem,
+        )
+    }
+
+    /// Location of the virtual columns `virtual_columns_id` of the block at `block_location`.
+    pub fn virtual_column_location(
+        &self,
+        virtual_columns_id: &str,
+        block_location: &str,
+    ) -> String {
+        let block_name = block_location.rsplit('/').next().unwrap_or(block_location);
+        let block_stem = block_name.trim_end_matches(".parquet");
+        format!(
+            "{}/{}/{}/{}.bin",
+            &self.prefix, FUSE_TBL_VIRTUAL_COLUMN_PREFIX, virtual_columns_id, block_stem,
+        )
+    }
+
     pub fn gen_segment_info_location(&self) -> String {
         let segment_uuid = Uuid::new_v4().simple().to_string();
         format!(
@@ -92,6 +132,19 @@ impl TableMetaLocationGenerator {
         Ok(snapshot_version.create(id, &self.prefix))
     }
 
+    pub fn snapshot_statistics_location_from_uuid(&self, id: &Uuid) -> Location {
+        (
+            format!(
+                "{}/{}/{}_v{}.json",
+                &self.prefix,
+                FUSE_TBL_SNAPSHOT_STATISTICS_PREFIX,
+                id.simple(),
+                TableSnapshotStatistics::VERSION,
+            ),
+            TableSnapshotStatistics::VERSION,
+        )
+    }
+
     pub fn snapshot_version(location: impl AsRef<str>) -> u64 {
         if location.as_ref().ends_with(SNAPSHOT_V1.suffix()) {
             SNAPSHOT_V1.version()
@@ -103,6 +156,10 @@ impl TableMetaLocationGenerator {
     pub fn gen_last_snapshot_hint_location(&self) -> String {
         format!("{}/{}", &self.prefix, FUSE_TBL_LAST_SNAPSHOT_HINT)
     }
+
+    pub fn gen_data_owner_location(&self) -> String {
+        format!("{}/{}", &self.prefix, FUSE_TBL_DATA_OWNER)
+    }
 }
 
 trait SnapshotLocationCreator {
@@ -19,15 +19,25 @@ mod write;
 
 pub use locations::TableMetaLocationGenerator;
 pub use read::load_bloom_filter_by_columns;
+pub use read::read_aggregating_index;
+pub use read::read_inverted_index;
+pub use read::read_virtual_columns;
 pub use read::BlockBloomFilterIndexReader;
 pub use read::BlockReader;
+pub use read::ColumnsData;
 pub use read::MetaReaders;
 pub use read::SegmentInfoReader;
 pub use read::SnapshotHistoryReader;
 pub use read::TableSnapshotReader;
+pub use read::TableSnapshotStatisticsReader;
+pub use read::VirtualColumnReader;
+pub use write::serialize_aggregating_index;
+pub use write::serialize_block;
 pub use write::write_block;
 pub use write::write_data;
+pub use write::write_data_multipart;
 pub use write::write_meta;
 pub use write::BlockCompactor;
 pub use write::BlockWriter;
+pub use write::MultipartOptions;
 pub use write::SegmentWriter;
@@ -0,0 +1,47 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::io::Cursor;
+use std::io::ErrorKind;
+
+use common_arrow::arrow::io::ipc::read::read_file_metadata;
+use common_arrow::arrow::io::ipc::read::FileReader;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use opendal::Operator;
+
+/// Reads the data of an aggregating index, returns `None` if the index file does not exist,
+/// e.g. the block is written before the index is created.
+pub async fn read_aggregating_index(
+    dal: &Operator,
+    location: &str,
+    schema: &DataSchemaRef,
+) -> Result<Option<DataBlock>> {
+    let bytes = match dal.object(location).read().await {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut cursor = Cursor::new(bytes);
+    let metadata = read_file_metadata(&mut cursor)?;
+    let reader = FileReader::new(cursor, metadata, None, None);
+
+    let mut blocks = vec![];
+    for chunk in reader {
+        blocks.push(DataBlock::from_chunk(schema, &chunk?)?);
+    }
+    Ok(Some(DataBlock::concat_blocks(&blocks)?))
+}
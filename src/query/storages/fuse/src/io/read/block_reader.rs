@@ -29,9 +29,13 @@ use common_arrow::parquet::read::BasicDecompressor;
 use common_arrow::parquet::read::PageMetaData;
 use common_arrow::parquet::read::PageReader;
 use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_fuse_meta::caches::CacheManager;
 use common_fuse_meta::meta::BlockMeta;
 use common_fuse_meta::meta::Compression;
 use common_legacy_planners::PartInfoPtr;
@@ -49,8 +53,25 @@ use crate::fuse_part::ColumnLeaf;
 use crate::fuse_part::ColumnLeaves;
 use crate::fuse_part::ColumnMeta;
 use crate::fuse_part::FusePartInfo;
+use crate::io::read::evolved_block_reader::EvolvedBlockReader;
 use crate::io::retry;
 use crate::io::retry::Retryable;
+use crate::SchemaHistory;
+
+/// Data of the projected columns of a part, returned by `BlockReader::read_columns_data()`.
+#[derive(Default)]
+pub struct ColumnsData {
+    /// Column chunks read from storage, keyed by leaf index.
+    pub chunks: Vec<(usize, Vec<u8>)>,
+    /// Decoded columns found in the data block cache, keyed by the position in the projection.
+    pub cached_columns: HashMap<usize, ColumnRef>,
+}
+
+impl ColumnsData {
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty() && self.cached_columns.is_empty()
+    }
+}
 
 #[derive(Clone)]
 pub struct BlockReader {
@@ -59,6 +80,9 @@ pub struct BlockReader {
     projected_schema: DataSchemaRef,
     column_leaves: ColumnLeaves,
     parquet_schema_descriptor: SchemaDescriptor,
+    schema_version: u64,
+    /// Readers of the blocks written with the older versions of the schema, keyed by version.
+    evolved_readers: HashMap<u64, Arc<EvolvedBlockReader>>,
 }
 
 impl BlockReader {
@@ -67,6 +91,32 @@ impl BlockReader {
         schema: DataSchemaRef,
         projection: Projection,
     ) -> Result<Arc<BlockReader>> {
+        let history = SchemaHistory::from_schema(&schema)?;
+        let schema_version = history.version();
+        let mut reader =
+            Self::create_of_version(operator.clone(), schema, projection, schema_version)?;
+        for version in history.versions().filter(|v| *v != schema_version) {
+            let evolved_reader = EvolvedBlockReader::create(
+                operator.clone(),
+                &history,
+                version,
+                &reader.projection,
+                reader.projected_schema.clone(),
+            )?;
+            reader
+                .evolved_readers
+                .insert(version, Arc::new(evolved_reader));
+        }
+        Ok(Arc::new(reader))
+    }
+
+    /// Create a reader of the blocks written with the schema of the given version.
+    pub(crate) fn create_of_version(
+        operator: Operator,
+        schema: DataSchemaRef,
+        projection: Projection,
+        schema_version: u64,
+    ) -> Result<BlockReader> {
         let projected_schema = match projection {
             Projection::Columns(ref indices) => DataSchemaRef::new(schema.project(indices)),
             Projection::InnerColumns(ref path_indices) => {
@@ -78,13 +128,29 @@ impl BlockReader {
         let parquet_schema_descriptor = to_parquet_schema(&arrow_schema)?;
         let column_leaves = ColumnLeaves::new_from_schema(&arrow_schema);
 
-        Ok(Arc::new(BlockReader {
+        Ok(BlockReader {
             operator,
             projection,
             projected_schema,
             parquet_schema_descriptor,
             column_leaves,
-        }))
+            schema_version,
+            evolved_readers: HashMap::new(),
+        })
+    }
+
+    /// The reader of the blocks written with the given schema version, if it is an older one.
+    fn evolved_reader(&self, schema_version: u64) -> Result<Option<&EvolvedBlockReader>> {
+        if schema_version == self.schema_version {
+            return Ok(None);
+        }
+        match self.evolved_readers.get(&schema_version) {
+            Some(reader) => Ok(Some(reader.as_ref())),
+            None => Err(ErrorCode::LogicalError(format!(
+                "The block is written with schema version {}, unknown to the table of version {}",
+                schema_version, self.schema_version
+            ))),
+        }
     }
 
     pub fn schema(&self) -> DataSchemaRef {
@@ -138,6 +204,9 @@ impl BlockReader {
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read_with_block_meta(&self, meta: &BlockMeta) -> Result<DataBlock> {
+        if let Some(reader) = self.evolved_reader(meta.schema_version)? {
+            return reader.read_with_block_meta(meta).await;
+        }
         let (num_rows, columns_array_iter) = self.read_columns_with_block_meta(meta).await?;
         let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
         self.try_next_block(&mut deserializer)
@@ -281,19 +350,35 @@ impl BlockReader {
         Ok((num_rows, columns_array_iter))
     }
 
-    pub fn deserialize(
-        &self,
-        part: PartInfoPtr,
-        chunks: Vec<(usize, Vec<u8>)>,
-    ) -> Result<DataBlock> {
+    pub fn deserialize(&self, part: PartInfoPtr, data: ColumnsData) -> Result<DataBlock> {
+        if let Some(reader) = self.evolved_reader(FusePartInfo::from_part(&part)?.schema_version)? {
+            return reader.deserialize(part, data);
+        }
         let part = FusePartInfo::from_part(&part)?;
+        let ColumnsData {
+            chunks,
+            mut cached_columns,
+        } = data;
         let mut chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
-        let mut columns_array_iter = Vec::with_capacity(self.projection.len());
 
         let num_rows = part.nums_rows;
         let columns = self.column_leaves.get_by_projection(&self.projection)?;
-        let mut cnt_map = Self::build_projection_count_map(&columns);
-        for column in &columns {
+        let uncached = columns
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !cached_columns.contains_key(i))
+            .map(|(_, column)| *column)
+            .collect::<Vec<_>>();
+        let mut cnt_map = Self::build_projection_count_map(&uncached);
+
+        let cache = CacheManager::instance().get_data_block_cache();
+        let mut block_columns = Vec::with_capacity(columns.len());
+        for (i, column) in columns.iter().enumerate() {
+            if let Some(cached) = cached_columns.remove(&i) {
+                block_columns.push(cached);
+                continue;
+            }
+
             let field = column.field.clone();
             let indices = &column.leaf_ids;
             let mut column_metas = Vec::with_capacity(indices.len());
@@ -313,27 +398,93 @@ impl BlockReader {
                 column_chunks.push(column_chunk);
                 column_descriptors.push(column_descriptor);
             }
-            columns_array_iter.push(Self::to_array_iter(
+            let array_iter = Self::to_array_iter(
                 column_metas,
                 column_chunks,
                 num_rows,
                 column_descriptors,
                 field,
                 &part.compression,
-            )?);
+            )?;
+
+            let data_field = self.projected_schema.field(i);
+            let decoded = self.decode_column(data_field, array_iter, num_rows)?;
+
+            if let Some(cache) = &cache {
+                let key = Self::column_cache_key(&part, column);
+                cache.put(key, data_field, decoded.clone());
+            }
+            block_columns.push(decoded);
         }
 
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
+        Ok(DataBlock::create(
+            self.projected_schema.clone(),
+            block_columns,
+        ))
+    }
 
-        self.try_next_block(&mut deserializer)
+    /// Decode one column from its array iterator.
+    fn decode_column(
+        &self,
+        field: &DataField,
+        array_iter: ArrayIter<'static>,
+        num_rows: usize,
+    ) -> Result<ColumnRef> {
+        let schema = Arc::new(DataSchema::new(vec![field.clone()]));
+        let mut deserializer = RowGroupDeserializer::new(vec![array_iter], num_rows, None);
+        let block = match deserializer.next() {
+            None => Err(ErrorCode::ParquetError("fail to get a chunk")),
+            Some(Err(cause)) => Err(ErrorCode::from(cause)),
+            Some(Ok(chunk)) => DataBlock::from_chunk(&schema, &chunk),
+        }?;
+        Ok(block.column(0).clone())
+    }
+
+    /// The key of a decoded column in the data block cache: the block file, the column and its byte range.
+    fn column_cache_key(part: &FusePartInfo, column: &ColumnLeaf) -> String {
+        let metas = column
+            .leaf_ids
+            .iter()
+            .map(|index| &part.columns_meta[index])
+            .collect::<Vec<_>>();
+        let start = metas.iter().map(|m| m.offset).min().unwrap_or_default();
+        let end = metas
+            .iter()
+            .map(|m| m.offset + m.length)
+            .max()
+            .unwrap_or_default();
+        format!("{}-{:?}-{}-{}", part.location, column.leaf_ids, start, end)
     }
 
-    pub async fn read_columns_data(&self, part: PartInfoPtr) -> Result<Vec<(usize, Vec<u8>)>> {
+    /// Read the data of projected columns of a part.
+    ///
+    /// Columns found in the data block cache are not read from storage.
+    pub async fn read_columns_data(&self, part: PartInfoPtr) -> Result<ColumnsData> {
+        if let Some(reader) = self.evolved_reader(FusePartInfo::from_part(&part)?.schema_version)? {
+            return reader.read_columns_data(part).await;
+        }
         let part = FusePartInfo::from_part(&part)?;
         let mut join_handlers = Vec::with_capacity(self.projection.len());
 
         let columns = self.column_leaves.get_by_projection(&self.projection)?;
-        let indices = Self::build_projection_indices(&columns);
+
+        let mut cached_columns = HashMap::new();
+        if let Some(cache) = CacheManager::instance().get_data_block_cache() {
+            for (i, column) in columns.iter().enumerate() {
+                let key = Self::column_cache_key(part, column);
+                if let Some(cached) = cache.get(&key, self.projected_schema.field(i)) {
+                    cached_columns.insert(i, cached);
+                }
+            }
+        }
+
+        let uncached = columns
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !cached_columns.contains_key(i))
+            .map(|(_, column)| *column)
+            .collect::<Vec<_>>();
+        let indices = Self::build_projection_indices(&uncached);
         for index in indices {
             let column_meta = &part.columns_meta[&index];
             join_handlers.push(Self::read_column(
@@ -344,7 +495,11 @@ impl BlockReader {
             ));
         }
 
-        futures::future::try_join_all(join_handlers).await
+        let chunks = futures::future::try_join_all(join_handlers).await?;
+        Ok(ColumnsData {
+            chunks,
+            cached_columns,
+        })
     }
 
     pub async fn read_column(
@@ -376,6 +531,9 @@ impl BlockReader {
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read(&self, part: PartInfoPtr) -> Result<DataBlock> {
+        if let Some(reader) = self.evolved_reader(FusePartInfo::from_part(&part)?.schema_version)? {
+            return reader.read(part).await;
+        }
         let (num_rows, columns_array_iter) = self.read_columns(part).await?;
         let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
         self.try_next_block(&mut deserializer)
@@ -90,6 +90,68 @@ mod util_v1 {
         column_needed: &[String],
         path: &str,
         length: u64,
+    ) -> Result<DataBlock> {
+        let fields = column_needed
+            .iter()
+            .map(|name| DataField::new(name, Vu8::to_data_type()))
+            .collect::<Vec<_>>();
+        let schema = Arc::new(DataSchema::new(fields));
+
+        // 1. lookup the deserialized filters in cache
+        let filter_cache = CacheManager::instance().get_bloom_index_filter_cache();
+        let mut columns = vec![None; column_needed.len()];
+        if let Some(filter_cache) = &filter_cache {
+            let cache = &mut filter_cache.write().await;
+            for (i, col_name) in column_needed.iter().enumerate() {
+                columns[i] = cache.get(&filter_cache_key(path, col_name)).cloned();
+            }
+        }
+
+        // 2. load and deserialize the missing ones, and populate the cache
+        let missing = column_needed
+            .iter()
+            .zip(columns.iter())
+            .filter(|(_, column)| column.is_none())
+            .map(|(col_name, _)| col_name.clone())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let block = load_and_deserialize_columns(ctx, dal, &missing, path, length).await?;
+            let mut decoded = block.columns().iter();
+            for (col_name, column) in column_needed.iter().zip(columns.iter_mut()) {
+                if column.is_none() {
+                    let decoded = decoded.next().cloned().ok_or_else(|| {
+                        ErrorCode::LogicalError(format!(
+                            "bloom index column {col_name} is not loaded"
+                        ))
+                    })?;
+                    *column = Some(decoded);
+                }
+            }
+
+            if let Some(filter_cache) = &filter_cache {
+                let cache = &mut filter_cache.write().await;
+                for (col_name, column) in missing.iter().zip(block.columns()) {
+                    cache.put(filter_cache_key(path, col_name), column.clone());
+                }
+            }
+        }
+
+        let columns = columns.into_iter().flatten().collect::<Vec<_>>();
+        Ok(DataBlock::create(schema, columns))
+    }
+
+    /// Key of a deserialized filter in the cache: the index file and the name of the filter column
+    fn filter_cache_key(path: &str, col_name: &str) -> String {
+        format!("{path}-{col_name}")
+    }
+
+    /// load and deserialize index column data
+    async fn load_and_deserialize_columns(
+        ctx: Arc<dyn TableContext>,
+        dal: Operator,
+        column_needed: &[String],
+        path: &str,
+        length: u64,
     ) -> Result<DataBlock> {
         let file_meta = load_index_meta(&ctx, path, length).await?;
         if file_meta.row_groups.len() != 1 {
@@ -107,7 +169,7 @@ mod util_v1 {
 
         let schema = Arc::new(DataSchema::new(fields));
 
-        // 1. load column data, as bytes
+        // load column data, as bytes
         let futs = column_needed
             .iter()
             .map(|col_name| load_column_bytes(&ctx, &file_meta, col_name, path, &dal))
@@ -121,7 +183,7 @@ mod util_v1 {
         let mut columns_array_iter = Vec::with_capacity(cols_data.len());
         let num_values = row_group.num_rows();
 
-        // 2. deserialize column data
+        // deserialize column data
 
         // wrapping around Arc<Vec<u8>>, so that bytes clone can be avoided
         // later in the construction of PageReader
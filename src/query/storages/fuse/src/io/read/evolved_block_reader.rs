@@ -0,0 +1,243 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::Column;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_functions::scalars::cast_with_type;
+use common_functions::scalars::FunctionContext;
+use common_functions::scalars::DEFAULT_CAST_OPTIONS;
+use common_fuse_meta::meta::BlockMeta;
+use common_legacy_planners::PartInfoPtr;
+use common_legacy_planners::Projection;
+use opendal::Operator;
+
+use crate::fuse_part::FusePartInfo;
+use crate::io::BlockReader;
+use crate::io::ColumnsData;
+use crate::SchemaHistory;
+
+/// Source of a projected column, in the blocks written with an older schema version.
+enum EvolvedColumn {
+    /// The column at the position of the projection of the older version.
+    Read(usize),
+    /// The column is added after the version, all the rows take the value.
+    Default(DataValue),
+}
+
+/// Reads the blocks written with an older version of the table schema, as blocks of the latest
+/// version.
+///
+/// The columns of the block are read with the schema of its own version. The columns added
+/// after the version are filled with their default values, and the columns whose types are
+/// widened after the version are cast to the new types.
+pub struct EvolvedBlockReader {
+    reader: Arc<BlockReader>,
+    columns: Vec<EvolvedColumn>,
+    projected_schema: DataSchemaRef,
+}
+
+impl EvolvedBlockReader {
+    pub fn create(
+        operator: Operator,
+        history: &SchemaHistory,
+        version: u64,
+        projection: &Projection,
+        projected_schema: DataSchemaRef,
+    ) -> Result<EvolvedBlockReader> {
+        let positions = history.positions_in(version)?;
+        let mut columns = Vec::with_capacity(projection.len());
+        let older_projection = match projection {
+            Projection::Columns(indices) => {
+                let mut older_indices = Vec::with_capacity(indices.len());
+                for index in indices {
+                    match positions[*index] {
+                        Some(position) => {
+                            columns.push(EvolvedColumn::Read(older_indices.len()));
+                            older_indices.push(position);
+                        }
+                        None => {
+                            columns.push(EvolvedColumn::Default(history.default_value(*index)?))
+                        }
+                    }
+                }
+                Projection::Columns(older_indices)
+            }
+            Projection::InnerColumns(path_indices) => {
+                let mut older_path_indices = BTreeMap::new();
+                for (i, path) in path_indices.values().enumerate() {
+                    match positions[path[0]] {
+                        Some(position) => {
+                            columns.push(EvolvedColumn::Read(older_path_indices.len()));
+                            let mut older_path = path.clone();
+                            older_path[0] = position;
+                            older_path_indices.insert(older_path_indices.len(), older_path);
+                        }
+                        None if path.len() == 1 => {
+                            columns.push(EvolvedColumn::Default(history.default_value(path[0])?))
+                        }
+                        None => {
+                            let data_type = projected_schema.field(i).data_type();
+                            columns.push(EvolvedColumn::Default(data_type.default_value()))
+                        }
+                    }
+                }
+                Projection::InnerColumns(older_path_indices)
+            }
+        };
+
+        let reader = BlockReader::create_of_version(
+            operator,
+            history.schema_of(version)?,
+            older_projection,
+            version,
+        )?;
+        Ok(EvolvedBlockReader {
+            reader: Arc::new(reader),
+            columns,
+            projected_schema,
+        })
+    }
+
+    fn reads_nothing(&self) -> bool {
+        self.reader.schema().num_fields() == 0
+    }
+
+    pub async fn read_with_block_meta(&self, meta: &BlockMeta) -> Result<DataBlock> {
+        let block = match self.reads_nothing() {
+            true => None,
+            false => Some(self.reader.read_with_block_meta(meta).await?),
+        };
+        self.evolve(block, HashMap::new(), meta.row_count as usize)
+    }
+
+    pub async fn read(&self, part: PartInfoPtr) -> Result<DataBlock> {
+        let num_rows = FusePartInfo::from_part(&part)?.nums_rows;
+        let block = match self.reads_nothing() {
+            true => None,
+            false => Some(self.reader.read(part).await?),
+        };
+        self.evolve(block, HashMap::new(), num_rows)
+    }
+
+    /// Read the data of the projected columns of a part.
+    ///
+    /// The decoded columns, including the columns of default values, are keyed by the position
+    /// in the projection of the latest version.
+    pub async fn read_columns_data(&self, part: PartInfoPtr) -> Result<ColumnsData> {
+        let num_rows = FusePartInfo::from_part(&part)?.nums_rows;
+        let ColumnsData {
+            chunks,
+            cached_columns: mut older_cached_columns,
+        } = match self.reads_nothing() {
+            true => ColumnsData::default(),
+            false => self.reader.read_columns_data(part).await?,
+        };
+
+        let mut cached_columns = HashMap::with_capacity(self.columns.len());
+        for (i, column) in self.columns.iter().enumerate() {
+            match column {
+                EvolvedColumn::Read(position) => {
+                    if let Some(cached) = older_cached_columns.remove(position) {
+                        cached_columns.insert(i, cached);
+                    }
+                }
+                EvolvedColumn::Default(value) => {
+                    cached_columns.insert(i, self.default_column(i, value, num_rows)?);
+                }
+            }
+        }
+        Ok(ColumnsData {
+            chunks,
+            cached_columns,
+        })
+    }
+
+    pub fn deserialize(&self, part: PartInfoPtr, data: ColumnsData) -> Result<DataBlock> {
+        let num_rows = FusePartInfo::from_part(&part)?.nums_rows;
+        let ColumnsData {
+            chunks,
+            mut cached_columns,
+        } = data;
+
+        let mut older_cached_columns = HashMap::new();
+        for (i, column) in self.columns.iter().enumerate() {
+            if let EvolvedColumn::Read(position) = column {
+                if let Some(cached) = cached_columns.remove(&i) {
+                    older_cached_columns.insert(*position, cached);
+                }
+            }
+        }
+
+        let block = match self.reads_nothing() {
+            true => None,
+            false => Some(self.reader.deserialize(part, ColumnsData {
+                chunks,
+                cached_columns: older_cached_columns,
+            })?),
+        };
+        self.evolve(block, cached_columns, num_rows)
+    }
+
+    /// Convert the block read with the older version to the latest version.
+    fn evolve(
+        &self,
+        block: Option<DataBlock>,
+        mut default_columns: HashMap<usize, ColumnRef>,
+        num_rows: usize,
+    ) -> Result<DataBlock> {
+        let mut columns = Vec::with_capacity(self.columns.len());
+        for (i, column) in self.columns.iter().enumerate() {
+            let column = match (column, &block) {
+                (EvolvedColumn::Read(position), Some(block)) => {
+                    let from_type = self.reader.schema().field(*position).data_type().clone();
+                    let to_type = self.projected_schema.field(i).data_type();
+                    let column = block.column(*position);
+                    match &from_type == to_type {
+                        true => column.clone(),
+                        false => cast_with_type(
+                            column,
+                            &from_type,
+                            to_type,
+                            &DEFAULT_CAST_OPTIONS,
+                            &FunctionContext::default(),
+                        )?,
+                    }
+                }
+                (EvolvedColumn::Default(value), _) => match default_columns.remove(&i) {
+                    Some(column) => column,
+                    None => self.default_column(i, value, num_rows)?,
+                },
+                (EvolvedColumn::Read(_), None) => unreachable!("the block of read columns is read"),
+            };
+            columns.push(column);
+        }
+        Ok(DataBlock::create(self.projected_schema.clone(), columns))
+    }
+
+    fn default_column(&self, i: usize, value: &DataValue, num_rows: usize) -> Result<ColumnRef> {
+        let data_type = self.projected_schema.field(i).data_type();
+        Ok(data_type
+            .create_constant_column(value, num_rows)?
+            .convert_full_column())
+    }
+}
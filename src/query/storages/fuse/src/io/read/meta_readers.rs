@@ -23,6 +23,8 @@ use common_fuse_meta::meta::SegmentInfo;
 use common_fuse_meta::meta::SegmentInfoVersion;
 use common_fuse_meta::meta::SnapshotVersion;
 use common_fuse_meta::meta::TableSnapshot;
+use common_fuse_meta::meta::TableSnapshotStatistics;
+use common_fuse_meta::meta::TableSnapshotStatisticsVersion;
 use common_storages_util::cached_reader::CachedReader;
 use common_storages_util::cached_reader::HasTenantLabel;
 use common_storages_util::cached_reader::Loader;
@@ -42,6 +44,8 @@ pub trait BufReaderProvider {
 
 pub type SegmentInfoReader<'a> = CachedReader<SegmentInfo, LoaderWrapper<&'a dyn TableContext>>;
 pub type TableSnapshotReader = CachedReader<TableSnapshot, LoaderWrapper<Arc<dyn TableContext>>>;
+pub type TableSnapshotStatisticsReader =
+    CachedReader<TableSnapshotStatistics, LoaderWrapper<Arc<dyn TableContext>>>;
 pub type BloomIndexFileMetaDataReader = CachedReader<FileMetaData, Arc<dyn TableContext>>;
 
 pub struct MetaReaders;
@@ -63,6 +67,16 @@ impl MetaReaders {
         )
     }
 
+    pub fn table_snapshot_statistics_reader(
+        ctx: Arc<dyn TableContext>,
+    ) -> TableSnapshotStatisticsReader {
+        TableSnapshotStatisticsReader::new(
+            CacheManager::instance().get_table_snapshot_statistics_cache(),
+            LoaderWrapper(ctx),
+            "TABLE_SNAPSHOT_STATISTICS_CACHE".to_owned(),
+        )
+    }
+
     pub fn file_meta_data_reader(ctx: Arc<dyn TableContext>) -> BloomIndexFileMetaDataReader {
         BloomIndexFileMetaDataReader::new(
             CacheManager::instance().get_bloom_index_meta_cache(),
@@ -92,6 +106,22 @@ where T: BufReaderProvider + Sync + Send
     }
 }
 
+#[async_trait::async_trait]
+impl<T> Loader<TableSnapshotStatistics> for LoaderWrapper<T>
+where T: BufReaderProvider + Sync + Send
+{
+    async fn load(
+        &self,
+        key: &str,
+        length_hint: Option<u64>,
+        version: u64,
+    ) -> Result<TableSnapshotStatistics> {
+        let version = TableSnapshotStatisticsVersion::try_from(version)?;
+        let reader = self.0.buf_reader(key, length_hint).await?;
+        version.read(reader).await
+    }
+}
+
 #[async_trait::async_trait]
 impl<T> Loader<SegmentInfo> for LoaderWrapper<T>
 where T: BufReaderProvider + Sync + Send
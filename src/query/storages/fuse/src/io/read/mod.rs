@@ -12,16 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod aggregating_index_reader;
 mod block_reader;
 mod bloom_index_reader;
+mod evolved_block_reader;
+mod inverted_index_reader;
 mod meta_readers;
 mod snapshot_history_reader;
 mod versioned_reader;
+mod virtual_column_reader;
 
+pub use aggregating_index_reader::read_aggregating_index;
 pub use block_reader::BlockReader;
+pub use block_reader::ColumnsData;
 pub use bloom_index_reader::load_bloom_filter_by_columns;
 pub use bloom_index_reader::BlockBloomFilterIndexReader;
+pub use inverted_index_reader::read_inverted_index;
 pub use meta_readers::MetaReaders;
 pub use meta_readers::SegmentInfoReader;
 pub use meta_readers::TableSnapshotReader;
+pub use meta_readers::TableSnapshotStatisticsReader;
 pub use snapshot_history_reader::SnapshotHistoryReader;
+pub use virtual_column_reader::read_virtual_columns;
+pub use virtual_column_reader::VirtualColumnReader;
@@ -19,6 +19,8 @@ use common_fuse_meta::meta::SegmentInfo;
 use common_fuse_meta::meta::SegmentInfoVersion;
 use common_fuse_meta::meta::SnapshotVersion;
 use common_fuse_meta::meta::TableSnapshot;
+use common_fuse_meta::meta::TableSnapshotStatistics;
+use common_fuse_meta::meta::TableSnapshotStatisticsVersion;
 use futures::AsyncRead;
 use serde::de::DeserializeOwned;
 use serde_json::from_slice;
@@ -53,6 +55,17 @@ impl VersionedReader<SegmentInfo> for SegmentInfoVersion {
     }
 }
 
+#[async_trait::async_trait]
+impl VersionedReader<TableSnapshotStatistics> for TableSnapshotStatisticsVersion {
+    async fn read<R>(&self, reader: R) -> Result<TableSnapshotStatistics>
+    where R: AsyncRead + Unpin + Send {
+        let r = match self {
+            TableSnapshotStatisticsVersion::V1(v) => load_by_version(reader, v).await?,
+        };
+        Ok(r)
+    }
+}
+
 async fn load_by_version<R, T>(mut reader: R, _v: &PhantomData<T>) -> Result<T>
 where
     T: DeserializeOwned,
@@ -0,0 +1,106 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::io::ErrorKind;
+use std::sync::Arc;
+
+use common_datavalues::ColumnRef;
+use common_exception::Result;
+use common_legacy_planners::PartInfoPtr;
+use opendal::Operator;
+
+use crate::fuse_part::FusePartInfo;
+use crate::index::BlockVirtualColumns;
+use crate::index::VirtualColumnMeta;
+use crate::io::BlockReader;
+use crate::io::TableMetaLocationGenerator;
+
+/// Reads the virtual columns of a block, returns `None` if the file of the virtual columns
+/// does not exist, e.g. the block is written before the virtual columns are created.
+pub async fn read_virtual_columns(
+    dal: &Operator,
+    location: &str,
+) -> Result<Option<BlockVirtualColumns>> {
+    let bytes = match dal.object(location).read().await {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(Some(BlockVirtualColumns::from_vec(&bytes)?))
+}
+
+/// Reads the virtual columns requested by a scan of the parts.
+pub struct VirtualColumnReader {
+    dal: Operator,
+    location_generator: TableMetaLocationGenerator,
+    virtual_columns_id: String,
+    columns: Vec<VirtualColumnMeta>,
+    // reads the source columns of the parts whose virtual columns are not materialized
+    source_reader: Arc<BlockReader>,
+}
+
+impl VirtualColumnReader {
+    pub fn create(
+        dal: Operator,
+        location_generator: TableMetaLocationGenerator,
+        virtual_columns_id: String,
+        columns: Vec<VirtualColumnMeta>,
+        source_reader: Arc<BlockReader>,
+    ) -> Self {
+        VirtualColumnReader {
+            dal,
+            location_generator,
+            virtual_columns_id,
+            columns,
+            source_reader,
+        }
+    }
+
+    pub fn columns(&self) -> &[VirtualColumnMeta] {
+        &self.columns
+    }
+
+    /// The values of the virtual columns of the part, in the order of the columns requested.
+    ///
+    /// The values are extracted from the source columns if the virtual columns of the part are
+    /// not materialized, or the file of them is unreadable.
+    pub async fn read(&self, part: PartInfoPtr) -> Result<Vec<ColumnRef>> {
+        let location = self.location_generator.virtual_column_location(
+            &self.virtual_columns_id,
+            &FusePartInfo::from_part(&part)?.location,
+        );
+        match read_virtual_columns(&self.dal, &location).await {
+            Ok(Some(virtual_columns)) => {
+                let columns = self
+                    .columns
+                    .iter()
+                    .map(|column| virtual_columns.column(&column.name()))
+                    .collect::<Result<Option<Vec<_>>>>();
+                match columns {
+                    Ok(Some(columns)) => return Ok(columns),
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("failed to load virtual columns {}. {}", location, e),
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("failed to read virtual columns {}. {}", location, e),
+        }
+
+        let block = self.source_reader.read(part).await?;
+        self.columns
+            .iter()
+            .map(|column| column.extract(block.try_column_by_name(&column.source)?))
+            .collect()
+    }
+}
@@ -0,0 +1,29 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::io::ipc::write::FileWriter;
+use common_arrow::arrow::io::ipc::write::WriteOptions;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+
+/// Serializes the data of an aggregating index into the arrow IPC file format.
+pub fn serialize_aggregating_index(block: DataBlock, buf: &mut Vec<u8>) -> Result<()> {
+    let options = WriteOptions { compression: None };
+    let mut writer = FileWriter::new(buf, block.schema().to_arrow(), None, options);
+    writer.start()?;
+    writer.write(&Chunk::try_from(block)?, None)?;
+    writer.finish()?;
+    Ok(())
+}
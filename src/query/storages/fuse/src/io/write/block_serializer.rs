@@ -0,0 +1,58 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::parquet::compression::CompressionOptions;
+use common_arrow::parquet::metadata::ThriftFileMetaData;
+use common_arrow::ArrayRef;
+use common_datablocks::serialize_chunks_with_compression;
+use common_datablocks::DataBlock;
+use common_datavalues::remove_nullable;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::Series;
+use common_datavalues::VariantColumn;
+use common_exception::Result;
+
+/// Serialize a data block into a parquet file.
+///
+/// The values of VARIANT columns are stored in the JSONB encoding rather than their JSON text,
+/// which saves the parsing when reading them.
+pub fn serialize_block(block: DataBlock, buf: &mut Vec<u8>) -> Result<(u64, ThriftFileMetaData)> {
+    let schema = block.schema().clone();
+    let arrays = block
+        .columns()
+        .iter()
+        .zip(schema.fields().iter())
+        .map(|(column, field)| column_to_arrow(column, field.data_type()))
+        .collect::<Result<Vec<_>>>()?;
+    let chunk = Chunk::try_new(arrays)?;
+    serialize_chunks_with_compression(vec![chunk], &schema, buf, CompressionOptions::Lz4Raw)
+}
+
+fn column_to_arrow(column: &ColumnRef, data_type: &DataTypeImpl) -> Result<ArrayRef> {
+    if !remove_nullable(data_type).data_type_id().is_variant() {
+        return Ok(column.as_arrow_array(data_type.clone()));
+    }
+
+    let column = column.convert_full_column();
+    let (_, validity) = column.validity();
+    let inner = Series::remove_nullable(&column);
+    let variant_column: &VariantColumn = Series::check_get(&inner)?;
+    let array = variant_column.as_binary_arrow_array(data_type.clone());
+    Ok(match validity {
+        Some(validity) => array.with_validity(Some(validity.clone())),
+        None => array,
+    })
+}
@@ -12,22 +12,26 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::io::ErrorKind;
 use std::sync::Arc;
 
 use common_arrow::parquet::compression::CompressionOptions;
 use common_arrow::parquet::metadata::ThriftFileMetaData;
 use common_catalog::table_context::TableContext;
-use common_datablocks::serialize_data_blocks;
 use common_datablocks::serialize_data_blocks_with_compression;
 use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
 use common_fuse_meta::meta::ClusterStatistics;
 use common_fuse_meta::meta::Location;
+use futures::StreamExt;
+use futures::TryStreamExt;
 use opendal::Operator;
 use tracing::warn;
 use uuid::Uuid;
 
+use super::serialize_block;
 use crate::index::BloomFilterIndexer;
 use crate::io::retry;
 use crate::io::retry::Retryable;
@@ -38,10 +42,37 @@ use crate::statistics::gen_columns_statistics;
 const DEFAULT_BLOOM_INDEX_WRITE_BUFFER_SIZE: usize = 300 * 1024;
 const DEFAULT_BLOCK_WRITE_BUFFER_SIZE: usize = 100 * 1024 * 1024;
 
+/// Options of the multipart upload, used for the objects larger than `part_size`.
+#[derive(Clone, Copy, Debug)]
+pub struct MultipartOptions {
+    pub part_size: usize,
+    pub concurrency: usize,
+}
+
+impl MultipartOptions {
+    pub fn try_create(ctx: &Arc<dyn TableContext>) -> Result<Self> {
+        let settings = ctx.get_settings();
+        Ok(MultipartOptions {
+            part_size: settings.get_storage_write_part_size()? as usize,
+            concurrency: settings.get_storage_write_concurrency()?.max(1) as usize,
+        })
+    }
+}
+
+impl Default for MultipartOptions {
+    fn default() -> Self {
+        MultipartOptions {
+            part_size: 16 * 1024 * 1024,
+            concurrency: 8,
+        }
+    }
+}
+
 pub struct BlockWriter<'a> {
     ctx: &'a Arc<dyn TableContext>,
     location_generator: &'a TableMetaLocationGenerator,
     data_accessor: &'a Operator,
+    bloom_index_columns: Option<&'a [String]>,
 }
 
 impl<'a> BlockWriter<'a> {
@@ -54,9 +85,16 @@ impl<'a> BlockWriter<'a> {
             ctx,
             location_generator,
             data_accessor,
+            bloom_index_columns: None,
         }
     }
 
+    /// Builds the bloom filters for the given columns only, instead of all the applicable columns.
+    pub fn with_bloom_index_columns(mut self, columns: Option<&'a [String]>) -> Self {
+        self.bloom_index_columns = columns;
+        self
+    }
+
     pub async fn write_with_location(
         &self,
         block: DataBlock,
@@ -71,7 +109,9 @@ impl<'a> BlockWriter<'a> {
         let (bloom_filter_index_size, bloom_filter_index_location) = self
             .build_block_index(data_accessor, &block, block_id)
             .await?;
-        let (file_size, file_meta_data) = write_block(block, data_accessor, &location.0).await?;
+        let multipart = MultipartOptions::try_create(self.ctx)?;
+        let (file_size, file_meta_data) =
+            write_block(block, data_accessor, &location.0, &multipart).await?;
         let col_metas = util::column_metas(&file_meta_data)?;
         let block_meta = BlockMeta::new(
             row_count,
@@ -103,7 +143,11 @@ impl<'a> BlockWriter<'a> {
         block: &DataBlock,
         block_id: Uuid,
     ) -> Result<(u64, Location)> {
-        let bloom_index = BloomFilterIndexer::try_create(self.ctx.clone(), &[block])?;
+        let bloom_index = BloomFilterIndexer::try_create_with_columns(
+            self.ctx.clone(),
+            &[block],
+            self.bloom_index_columns,
+        )?;
         let index_block = bloom_index.bloom_block;
         let location = self
             .location_generator
@@ -125,11 +169,11 @@ pub async fn write_block(
     block: DataBlock,
     data_accessor: &Operator,
     location: &str,
+    multipart: &MultipartOptions,
 ) -> Result<(u64, ThriftFileMetaData)> {
     let mut buf = Vec::with_capacity(DEFAULT_BLOCK_WRITE_BUFFER_SIZE);
-    let schema = block.schema().clone();
-    let result = serialize_data_blocks(vec![block], &schema, &mut buf)?;
-    write_data(&buf, data_accessor, location).await?;
+    let result = serialize_block(block, &mut buf)?;
+    write_data_multipart(&buf, data_accessor, location, multipart).await?;
     Ok(result)
 }
 
@@ -153,3 +197,80 @@ pub async fn write_data(data: &[u8], data_accessor: &Operator, location: &str) -
 
     Ok(())
 }
+
+/// Write data in parts if it's larger than the part size and the storage supports multipart
+/// uploads, otherwise it's written as a whole.
+///
+/// The parts are uploaded concurrently and each of them is retried on its own, the upload is
+/// aborted if any part fails at last, so that no parts are left in the storage.
+pub async fn write_data_multipart(
+    data: &[u8],
+    data_accessor: &Operator,
+    location: &str,
+    options: &MultipartOptions,
+) -> Result<()> {
+    if options.part_size == 0 || data.len() <= options.part_size {
+        return write_data(data, data_accessor, location).await;
+    }
+
+    let object = data_accessor.object(location);
+    let multipart = match object.create_multipart().await {
+        Ok(multipart) => multipart,
+        Err(e) if e.kind() == ErrorKind::Unsupported => {
+            return write_data(data, data_accessor, location).await;
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let multipart = &multipart;
+    let uploads = data
+        .chunks(options.part_size)
+        .enumerate()
+        .map(|(index, part)| async move {
+            // part numbers start from 1
+            let part_number = index + 1;
+            let op = || async {
+                multipart
+                    .write(part_number, part.to_vec())
+                    .await
+                    .map_err(retry::from_io_error)
+            };
+            let notify = |e: std::io::Error, duration| {
+                warn!(
+                    "transient error encountered while write part {}, location {}, at duration {:?} : {}",
+                    part_number, location, duration, e,
+                )
+            };
+            op.retry_with_notify(notify).await
+        });
+    let parts = futures::stream::iter(uploads)
+        .buffered(options.concurrency)
+        .try_collect::<Vec<_>>()
+        .await;
+
+    let result = match parts {
+        Ok(parts) => multipart.complete(parts).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        if let Err(abort_err) = multipart.abort().await {
+            warn!(
+                "abort multipart upload failed, location {} : {}",
+                location, abort_err
+            );
+        }
+        return Err(e.into());
+    }
+
+    // make sure all the parts are assembled
+    let size = object.metadata().await?.content_length();
+    if size != data.len() as u64 {
+        return Err(ErrorCode::StorageOther(format!(
+            "size of {} is {} after multipart upload, {} expected",
+            location,
+            size,
+            data.len()
+        )));
+    }
+    Ok(())
+}
@@ -12,14 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod aggregating_index_writer;
 mod block_compactor;
+mod block_serializer;
 mod block_writer;
 mod meta_writer;
 mod segment_writer;
 
+pub use aggregating_index_writer::serialize_aggregating_index;
 pub use block_compactor::BlockCompactor;
+pub use block_serializer::serialize_block;
 pub use block_writer::write_block;
 pub use block_writer::write_data;
+pub use block_writer::write_data_multipart;
 pub use block_writer::BlockWriter;
+pub use block_writer::MultipartOptions;
 pub use meta_writer::write_meta;
 pub use segment_writer::SegmentWriter;
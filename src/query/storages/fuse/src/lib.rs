@@ -16,14 +16,19 @@
 #![feature(io_error_other)]
 #![deny(unused_crate_dependencies)]
 
+mod aggregating_index_table;
 mod constants;
 mod fuse_part;
 mod fuse_table;
 pub mod io;
 pub mod operations;
 pub mod pruning;
+mod schema_history;
 pub mod statistics;
+mod stream_table;
 pub mod table_functions;
+pub use aggregating_index_table::AggregatingIndexTable;
+use common_catalog::table::CompactTarget;
 use common_catalog::table::NavigationPoint;
 use common_catalog::table::Table;
 use common_catalog::table::TableStatistics;
@@ -34,6 +39,13 @@ pub use constants::*;
 pub use fuse_part::ColumnLeaf;
 pub use fuse_part::ColumnLeaves;
 pub use fuse_table::FuseTable;
+pub use schema_history::SchemaHistory;
+pub use stream_table::StreamTable;
+pub use stream_table::CHANGE_ACTION_COLUMN;
+pub use stream_table::OPT_KEY_STREAM_OFFSET;
+pub use stream_table::OPT_KEY_STREAM_SOURCE_TABLE_ID;
+pub use stream_table::OPT_KEY_STREAM_SOURCE_TABLE_NAME;
+pub use stream_table::STREAM_ENGINE;
 pub use table_option_keys::*;
 
 mod sessions {
@@ -0,0 +1,144 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::AlterColumnAction;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::Statistics as FuseStatistics;
+use common_fuse_meta::meta::TableSnapshot;
+use common_legacy_expression::RequireColumnsVisitor;
+use uuid::Uuid;
+
+use crate::FuseTable;
+use crate::SchemaHistory;
+
+impl FuseTable {
+    /// Change the columns of the table.
+    ///
+    /// Only the table meta is changed, a new version of the schema is recorded in its schema
+    /// history. The blocks already written are kept as they are, and are read through the
+    /// schema of the version they are written with.
+    pub async fn do_alter_table_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        action: AlterColumnAction,
+    ) -> Result<()> {
+        let column = match &action {
+            AlterColumnAction::Add { .. } => None,
+            AlterColumnAction::Drop { column } => Some(column.as_str()),
+            AlterColumnAction::Rename { column, .. } => Some(column.as_str()),
+            AlterColumnAction::Modify { field } => Some(field.name().as_str()),
+        };
+        if let Some(column) = column {
+            self.check_column_unreferenced(column)?;
+        }
+
+        let schema = self.table_info.schema();
+        let history = SchemaHistory::from_schema(&schema)?;
+        let schema_version = history.version();
+        let new_schema = history.evolve(&schema, &action)?;
+        let leaf_ids = SchemaHistory::from_schema(&new_schema)?.leaf_ids_in(schema_version)?;
+
+        let prev = self.read_table_snapshot(ctx.clone()).await?;
+        let prev_version = self.snapshot_format_version();
+        let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
+        let prev_snapshot_id = prev.as_ref().map(|v| (v.snapshot_id, prev_version));
+        let (mut summary, segments) = if let Some(v) = prev {
+            (v.summary.clone(), v.segments.clone())
+        } else {
+            (FuseStatistics::default(), vec![])
+        };
+        // the column statistics are keyed by the leaf column ids, which are shifted by the change
+        summary.col_stats = leaf_ids
+            .iter()
+            .filter_map(|(id, older_id)| {
+                summary
+                    .col_stats
+                    .get(older_id)
+                    .map(|stats| (*id, stats.clone()))
+            })
+            .collect();
+
+        // the table statistics of the older schema are dropped, they are rebuilt by ANALYZE
+        let new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            &prev_timestamp,
+            prev_snapshot_id,
+            new_schema.clone(),
+            summary,
+            segments,
+            self.cluster_key_meta.clone(),
+        );
+
+        let mut table_info = self.table_info.clone();
+        let field_comments = &mut table_info.meta.field_comments;
+        if field_comments.len() == schema.num_fields() {
+            match &action {
+                AlterColumnAction::Add { comment, .. } => field_comments.push(comment.clone()),
+                AlterColumnAction::Drop { column } => {
+                    field_comments.remove(schema.index_of(column)?);
+                }
+                _ => {}
+            }
+        }
+        table_info.meta.schema = Arc::new(new_schema);
+
+        FuseTable::commit_to_meta_server(
+            ctx.as_ref(),
+            &table_info,
+            &self.meta_location_generator,
+            new_snapshot,
+        )
+        .await
+    }
+
+    /// The columns used by the cluster keys or the aggregating indexes can not be changed.
+    fn check_column_unreferenced(&self, column: &str) -> Result<()> {
+        for cluster_key in &self.cluster_keys {
+            if RequireColumnsVisitor::collect_columns_from_expr(cluster_key)?.contains(column) {
+                return Err(ErrorCode::SemanticError(format!(
+                    "Cannot alter column {}, it is used by the cluster keys of the table",
+                    column
+                )));
+            }
+        }
+
+        for index in self.aggregating_indexes()? {
+            let used = index.group_by.iter().any(|name| name == column)
+                || index
+                    .aggregates
+                    .iter()
+                    .any(|item| item.arg.as_deref() == Some(column));
+            if used {
+                return Err(ErrorCode::SemanticError(format!(
+                    "Cannot alter column {}, it is used by the aggregating index {}",
+                    column, index.name
+                )));
+            }
+        }
+
+        if let Some(virtual_columns) = self.virtual_columns()? {
+            if virtual_columns.columns.iter().any(|c| c.source == column) {
+                return Err(ErrorCode::SemanticError(format!(
+                    "Cannot alter column {}, it is the source of virtual columns of the table",
+                    column
+                )));
+            }
+        }
+        Ok(())
+    }
+}
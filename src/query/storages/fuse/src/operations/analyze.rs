@@ -0,0 +1,114 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_cache::Cache;
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_fuse_meta::caches::CacheManager;
+use common_fuse_meta::meta::BlockMeta;
+use common_fuse_meta::meta::Location;
+use common_fuse_meta::meta::TableSnapshot;
+use common_fuse_meta::meta::TableSnapshotStatistics;
+use common_legacy_planners::Projection;
+
+use crate::io::write_meta;
+use crate::io::MetaReaders;
+use crate::statistics::gen_columns_distributions;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Collect the statistics of the columns from all the data of the table, and commit a new
+    /// snapshot pointing to them. Appends keep them up to date afterwards.
+    pub(crate) async fn do_analyze(&self, ctx: &Arc<dyn TableContext>) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.clone()).await? {
+            Some(snapshot) => snapshot,
+            // empty table, have nothing to do here
+            None => return Ok(()),
+        };
+
+        let reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut statistics = TableSnapshotStatistics::new(0, HashMap::new());
+        for (location, version) in &snapshot.segments {
+            let segment = reader.read(location, None, *version).await?;
+            let collected = self
+                .collect_snapshot_statistics(ctx, segment.blocks.iter())
+                .await?;
+            statistics = statistics.merge(&collected);
+        }
+
+        let mut new_snapshot = TableSnapshot::from_previous(&snapshot);
+        new_snapshot.table_statistics_location =
+            Some(self.write_snapshot_statistics(ctx, statistics).await?);
+
+        FuseTable::commit_to_meta_server(
+            ctx.as_ref(),
+            &self.table_info,
+            &self.meta_location_generator,
+            new_snapshot,
+        )
+        .await
+    }
+
+    /// Collect the statistics of the columns of the blocks, by reading their data.
+    pub(crate) async fn collect_snapshot_statistics(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        blocks: impl Iterator<Item = &BlockMeta>,
+    ) -> Result<TableSnapshotStatistics> {
+        let num_fields = self.table_info.schema().num_fields();
+        let projection = Projection::Columns((0..num_fields).collect());
+        let block_reader = self.create_block_reader(ctx, projection)?;
+
+        let mut statistics = TableSnapshotStatistics::new(0, HashMap::new());
+        for block_meta in blocks {
+            let block = block_reader.read_with_block_meta(block_meta).await?;
+            let distributions = gen_columns_distributions(&block)?;
+            let collected = TableSnapshotStatistics::new(block.num_rows() as u64, distributions);
+            statistics = statistics.merge(&collected);
+        }
+        Ok(statistics)
+    }
+
+    pub(crate) async fn read_snapshot_statistics(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        location: &Location,
+    ) -> Result<Arc<TableSnapshotStatistics>> {
+        let (path, version) = location;
+        let reader = MetaReaders::table_snapshot_statistics_reader(ctx);
+        reader.read(path, None, *version).await
+    }
+
+    pub(crate) async fn write_snapshot_statistics(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        statistics: TableSnapshotStatistics,
+    ) -> Result<Location> {
+        let location = self
+            .meta_location_generator
+            .snapshot_statistics_location_from_uuid(&statistics.id);
+
+        let operator = ctx.get_storage_operator()?;
+        write_meta(&operator, &location.0, &statistics).await?;
+
+        if let Some(cache) = CacheManager::instance().get_table_snapshot_statistics_cache() {
+            let cache = &mut cache.write().await;
+            cache.put(location.0.clone(), Arc::new(statistics));
+        }
+        Ok(location)
+    }
+}
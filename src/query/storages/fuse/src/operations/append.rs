@@ -78,6 +78,11 @@ impl FuseTable {
             })?;
         }
 
+        let aggregating_indexes = self.aggregating_indexes()?;
+        let bloom_index_columns = self.bloom_index_columns()?;
+        let inverted_indexes = self.inverted_indexes()?;
+        let virtual_columns = self.virtual_columns()?;
+        let schema_version = self.schema_version()?;
         let da = ctx.get_storage_operator()?;
         if need_output {
             pipeline.add_transform(|transform_input_port, transform_output_port| {
@@ -88,6 +93,11 @@ impl FuseTable {
                     da.clone(),
                     self.meta_location_generator().clone(),
                     cluster_stats_gen.clone(),
+                    aggregating_indexes.clone(),
+                    bloom_index_columns.clone(),
+                    inverted_indexes.clone(),
+                    virtual_columns.clone(),
+                    schema_version,
                     Some(transform_output_port),
                 )
             })?;
@@ -104,6 +114,11 @@ impl FuseTable {
                         da.clone(),
                         self.meta_location_generator().clone(),
                         cluster_stats_gen.clone(),
+                        aggregating_indexes.clone(),
+                        bloom_index_columns.clone(),
+                        inverted_indexes.clone(),
+                        virtual_columns.clone(),
+                        schema_version,
                         None,
                     )?,
                 );
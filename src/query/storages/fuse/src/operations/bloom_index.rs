@@ -0,0 +1,132 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_fuse_meta::meta::BlockMeta;
+use common_legacy_planners::Projection;
+use opendal::Operator;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::index::BloomFilterIndexer;
+use crate::io::BlockBloomFilterIndexReader;
+use crate::io::BlockWriter;
+use crate::io::MetaReaders;
+use crate::operations::mutation::BaseMutator;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Builds the bloom filters of the columns of the bloom indexes for the existing blocks.
+    ///
+    /// The blocks whose bloom filter files lack any of the columns, e.g. the blocks written
+    /// before an index is created, get new bloom filter files, which are committed as a new
+    /// snapshot. The blocks written with the older schema versions are skipped, they are not
+    /// pruned by the bloom filters anyway.
+    pub async fn do_build_bloom_indexes(&self, ctx: Arc<dyn TableContext>) -> Result<()> {
+        let index_columns = match self.bloom_index_columns()? {
+            Some(index_columns) => index_columns,
+            None => return Ok(()),
+        };
+        let snapshot = match self.read_table_snapshot(ctx.clone()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let schema = self.table_info.schema();
+        let schema_version = self.schema_version()?;
+        let column_indices = index_columns
+            .iter()
+            .map(|name| schema.index_of(name))
+            .collect::<Result<Vec<_>>>()?;
+        let bloom_columns = index_columns
+            .iter()
+            .map(|name| BloomFilterIndexer::to_bloom_column_name(name))
+            .collect::<Vec<_>>();
+
+        let reader = self.create_block_reader(&ctx, Projection::Columns(column_indices))?;
+        let dal = ctx.get_storage_operator()?;
+        let block_writer = BlockWriter::new(&ctx, &dal, &self.meta_location_generator)
+            .with_bloom_index_columns(Some(&index_columns));
+        let mut mutator = BaseMutator::try_create(
+            ctx.clone(),
+            self.meta_location_generator.clone(),
+            snapshot.clone(),
+        )?;
+
+        let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut num_blocks = 0;
+        for (seg_idx, (location, version)) in snapshot.segments.iter().enumerate() {
+            let segment = segment_reader.read(location, None, *version).await?;
+            for block_meta in &segment.blocks {
+                if block_meta.schema_version != schema_version
+                    || Self::has_bloom_filters(&ctx, dal.clone(), block_meta, &bloom_columns).await
+                {
+                    continue;
+                }
+
+                let block = reader.read_with_block_meta(block_meta).await?;
+                // a new location, the files referenced by the previous snapshots are kept
+                let (size, location) = block_writer
+                    .build_block_index(&dal, &block, Uuid::new_v4())
+                    .await?;
+                let mut new_block_meta = block_meta.clone();
+                new_block_meta.bloom_filter_index_location = Some(location);
+                new_block_meta.bloom_filter_index_size = size;
+                mutator.add_mutation(seg_idx, block_meta.location.clone(), Some(new_block_meta));
+                num_blocks += 1;
+            }
+        }
+
+        if num_blocks == 0 {
+            return Ok(());
+        }
+        info!(
+            "build bloom filters of columns {:?} for {} blocks of table {}",
+            index_columns, num_blocks, self.table_info.desc
+        );
+        let (segments, summary) = mutator.generate_segments().await?;
+        let new_snapshot = mutator.into_new_snapshot(segments, summary).await?;
+        Self::commit_to_meta_server(
+            ctx.as_ref(),
+            &self.table_info,
+            &self.meta_location_generator,
+            new_snapshot,
+        )
+        .await
+    }
+
+    async fn has_bloom_filters(
+        ctx: &Arc<dyn TableContext>,
+        dal: Operator,
+        block_meta: &BlockMeta,
+        bloom_columns: &[String],
+    ) -> bool {
+        let location = match &block_meta.bloom_filter_index_location {
+            Some(location) => location,
+            None => return false,
+        };
+        // the filters of the missing columns fail to load
+        location
+            .read_bloom_filter_index(
+                ctx.clone(),
+                dal,
+                bloom_columns,
+                block_meta.bloom_filter_index_size,
+            )
+            .await
+            .is_ok()
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_catalog::table::ColumnStatistics;
+use common_catalog::table::Histogram;
+use common_catalog::table::HistogramBucket;
+use common_catalog::table_context::TableContext;
+use common_datavalues::DataValue;
+use common_exception::Result;
+
+use crate::fuse_part::ColumnLeaves;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// The statistics of the columns are taken from the ones collected by `ANALYZE TABLE`, if any.
+    ///
+    /// Otherwise they are taken from the summary of the snapshot. It has no distinct counts, so
+    /// the number of distinct values is estimated by its upper bound: the number of non-null
+    /// rows, or the size of the range between min and max for the integral columns.
+    ///
+    /// The summary is exact if the columns of the table are never changed. Otherwise the blocks
+    /// written before a column is added or modified are not summarized by the statistics of it.
+    pub(crate) async fn do_column_statistics(
+        &self,
+        ctx: Arc<dyn TableContext>,
+    ) -> Result<HashMap<usize, ColumnStatistics>> {
+        let snapshot = match self.read_table_snapshot(ctx.clone()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(HashMap::new()),
+        };
+
+        let analyzed = match &snapshot.table_statistics_location {
+            Some(location) => Some(self.read_snapshot_statistics(ctx, location).await?),
+            None => None,
+        };
+
+        let summary = &snapshot.summary;
+        let arrow_schema = self.table_info.schema().to_arrow();
+        let column_leaves = ColumnLeaves::new_from_schema(&arrow_schema);
+        let is_exact = self.schema_version()? == 0;
+
+        let mut statistics = HashMap::new();
+        for (index, column_leaf) in column_leaves.column_leaves.iter().enumerate() {
+            // Only the leaves of the nested columns have statistics.
+            if column_leaf.children.is_some() {
+                continue;
+            }
+
+            let leaf_id = column_leaf.leaf_ids[0] as u32;
+            let distribution = analyzed
+                .as_ref()
+                .and_then(|analyzed| analyzed.column_distributions.get(&leaf_id));
+            let summarized = summary.col_stats.get(&leaf_id).filter(|_| is_exact);
+            if let Some(distribution) = distribution {
+                // the table may be changed after it is analyzed, the exact summary is up to date
+                let (min, max, null_count) = match summarized {
+                    Some(col_stats) => (
+                        col_stats.min.clone(),
+                        col_stats.max.clone(),
+                        col_stats.null_count,
+                    ),
+                    None => (
+                        distribution.min.clone(),
+                        distribution.max.clone(),
+                        distribution.null_count,
+                    ),
+                };
+                let non_null_rows = summary.row_count.saturating_sub(null_count);
+                let histogram = distribution.histogram.as_ref().map(|histogram| Histogram {
+                    buckets: histogram
+                        .buckets
+                        .iter()
+                        .map(|bucket| HistogramBucket {
+                            lower: bucket.lower,
+                            upper: bucket.upper,
+                            count: bucket.count,
+                        })
+                        .collect(),
+                });
+
+                statistics.insert(index, ColumnStatistics {
+                    min,
+                    max,
+                    null_count,
+                    number_of_distinct_values: distribution
+                        .distinct_values
+                        .estimate()
+                        .min(non_null_rows),
+                    histogram,
+                    is_exact: summarized.is_some(),
+                });
+            } else if let Some(col_stats) = summary.col_stats.get(&leaf_id) {
+                let non_null_rows = summary.row_count.saturating_sub(col_stats.null_count);
+                let number_of_distinct_values = match range_size(&col_stats.min, &col_stats.max) {
+                    Some(range_size) => non_null_rows.min(range_size),
+                    None => non_null_rows,
+                };
+
+                statistics.insert(index, ColumnStatistics {
+                    min: col_stats.min.clone(),
+                    max: col_stats.max.clone(),
+                    null_count: col_stats.null_count,
+                    number_of_distinct_values,
+                    histogram: None,
+                    is_exact,
+                });
+            }
+        }
+
+        Ok(statistics)
+    }
+}
+
+/// The number of the integral values between min and max.
+fn range_size(min: &DataValue, max: &DataValue) -> Option<u64> {
+    match (min, max) {
+        (DataValue::Int64(min), DataValue::Int64(max)) if min <= max => {
+            Some(max.abs_diff(*min).saturating_add(1))
+        }
+        (DataValue::UInt64(min), DataValue::UInt64(max)) if min <= max => {
+            Some((max - min).saturating_add(1))
+        }
+        _ => None,
+    }
+}
@@ -34,6 +34,7 @@ use common_fuse_meta::meta::TableSnapshot;
 use common_fuse_meta::meta::Versioned;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableStatistics;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::MatchSeq;
 use common_storages_util::retry;
@@ -105,7 +106,7 @@ impl FuseTable {
                             tbl = FuseTable::try_from_table(latest.as_ref())?;
 
                             let keep_last_snapshot = true;
-                            if let Err(e) = tbl.do_gc(&ctx, keep_last_snapshot).await {
+                            if let Err(e) = tbl.do_gc(&ctx, keep_last_snapshot, None).await {
                                 // Errors of GC, if any, are ignored, since GC task can be picked up
                                 warn!(
                                     "GC of transient table not success (this is not a permanent error). the error : {}",
@@ -164,6 +165,9 @@ impl FuseTable {
         let prev = self.read_table_snapshot(ctx.clone()).await?;
         let prev_version = self.snapshot_format_version();
         let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
+        let prev_statistics_location = prev
+            .as_ref()
+            .and_then(|v| v.table_statistics_location.clone());
         let schema = self.table_info.meta.schema.as_ref().clone();
         let (segments, summary) = Self::merge_append_operations(operation_log)?;
 
@@ -178,7 +182,7 @@ impl FuseTable {
             .map(|loc| (loc, SegmentInfo::VERSION))
             .collect();
 
-        let new_snapshot = if overwrite {
+        let mut new_snapshot = if overwrite {
             TableSnapshot::new(
                 Uuid::new_v4(),
                 &prev_timestamp,
@@ -199,6 +203,23 @@ impl FuseTable {
             )?
         };
 
+        // keep the statistics of the columns of an analyzed table up to date, by folding the
+        // appended blocks in, the overwritten data is analyzed from scratch
+        if let Some(location) = &prev_statistics_location {
+            let blocks = operation_log
+                .iter()
+                .flat_map(|entry| entry.segment_info.blocks.iter());
+            let appended = self.collect_snapshot_statistics(&ctx, blocks).await?;
+            let statistics = if overwrite {
+                appended
+            } else {
+                let previous = self.read_snapshot_statistics(ctx.clone(), location).await?;
+                previous.as_ref().clone().merge(&appended)
+            };
+            new_snapshot.table_statistics_location =
+                Some(self.write_snapshot_statistics(&ctx, statistics).await?);
+        }
+
         let mut new_table_meta = self.get_table_info().meta.clone();
         // update statistics
         new_table_meta.statistics = TableStatistics {
@@ -262,6 +283,9 @@ impl FuseTable {
         let snapshot_location = location_generator
             .snapshot_location_from_uuid(&snapshot.snapshot_id, snapshot.format_version())?;
 
+        // 0. the data may be taken over by a table attached by `ATTACH TABLE`
+        Self::check_data_owner(ctx, table_info, location_generator).await?;
+
         // 1. write down snapshot
         let operator = ctx.get_storage_operator()?;
         write_meta(&operator, &snapshot_location, &snapshot).await?;
@@ -286,6 +310,27 @@ impl FuseTable {
             index_data_bytes: stats.index_size,
         };
 
+        // 2.3 the offsets of the streams read by the statement are advanced with the table
+        let consumed_streams = ctx.get_consumed_streams();
+
+        // 2.4 inside a transaction, the new table meta is staged, it is committed together with
+        // the other tables changed by the transaction
+        let txn_manager = ctx.get_txn_manager();
+        if txn_manager.lock().is_active() {
+            {
+                let mut txn_manager = txn_manager.lock();
+                txn_manager.stage_table_meta(table_info, new_table_meta);
+                for stream in &consumed_streams {
+                    txn_manager.stage_table_meta(stream, stream.meta.clone());
+                }
+            }
+            if let Some(snapshot_cache) = CacheManager::instance().get_table_snapshot_cache() {
+                let cache = &mut snapshot_cache.write().await;
+                cache.put(snapshot_location, Arc::new(snapshot));
+            }
+            return Ok(());
+        }
+
         // 3. prepare the request
         let catalog = ctx.get_catalog(&table_info.meta.catalog)?;
         let table_id = table_info.ident.table_id;
@@ -298,7 +343,23 @@ impl FuseTable {
         };
 
         // 3. let's roll
-        let reply = catalog.update_table_meta(req).await;
+        let reply = match consumed_streams.is_empty() {
+            true => catalog.update_table_meta(req).await.map(|_| ()),
+            false => {
+                let streams = consumed_streams
+                    .into_iter()
+                    .map(|stream| UpdateTableMetaReq {
+                        table_id: stream.ident.table_id,
+                        seq: MatchSeq::Exact(stream.ident.seq),
+                        new_table_meta: stream.meta,
+                    });
+                let update_table_metas = std::iter::once(req).chain(streams).collect();
+                catalog
+                    .update_multi_table_meta(UpdateMultiTableMetaReq { update_table_metas })
+                    .await
+                    .map(|_| ())
+            }
+        };
         match reply {
             Ok(_) => {
                 if let Some(snapshot_cache) = CacheManager::instance().get_table_snapshot_cache() {
@@ -362,6 +423,76 @@ impl FuseTable {
         catalog.get_table_by_info(&table_info)
     }
 
+    /// Reads the hint file of the latest snapshot, returns the location of the snapshot relative
+    /// to the root of the operator, `None` if no snapshot is committed yet.
+    pub async fn read_last_snapshot_hint(
+        operator: &Operator,
+        location_generator: &TableMetaLocationGenerator,
+    ) -> Result<Option<String>> {
+        let hint_path = location_generator.gen_last_snapshot_hint_location();
+        let content = match operator.object(&hint_path).read().await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        // the hint is written with the root of the operator, see `write_last_snapshot_hint`
+        let location = String::from_utf8_lossy(&content);
+        let operator_meta_data = operator.metadata();
+        let storage_prefix = operator_meta_data.root();
+        match location.strip_prefix(storage_prefix) {
+            Some(location) => Ok(Some(location.to_string())),
+            None => Err(ErrorCode::StorageOther(format!(
+                "the last snapshot {} is not under the root {} of the storage",
+                location, storage_prefix
+            ))),
+        }
+    }
+
+    /// The identity of the table written in the data owner file.
+    pub fn data_owner_identity(tenant: &str, table_info: &TableInfo) -> String {
+        format!("{}/{}", tenant, table_info.ident.table_id)
+    }
+
+    /// Takes over the data under the prefix of the location generator, the tables wrote the data
+    /// before can not commit any more.
+    pub async fn write_data_owner(
+        operator: &Operator,
+        location_generator: &TableMetaLocationGenerator,
+        owner: String,
+    ) -> Result<()> {
+        let owner_path = location_generator.gen_data_owner_location();
+        operator.object(&owner_path).write(owner).await?;
+        Ok(())
+    }
+
+    // Only the owner of the data can commit, if the data has ever been taken over by
+    // `ATTACH TABLE`.
+    pub(crate) async fn check_data_owner(
+        ctx: &dyn TableContext,
+        table_info: &TableInfo,
+        location_generator: &TableMetaLocationGenerator,
+    ) -> Result<()> {
+        let operator = ctx.get_storage_operator()?;
+        let owner_path = location_generator.gen_data_owner_location();
+        let owner = match operator.object(&owner_path).read().await {
+            Ok(owner) => owner,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let identity = Self::data_owner_identity(&ctx.get_tenant(), table_info);
+        if owner == identity.as_bytes() {
+            Ok(())
+        } else {
+            Err(ErrorCode::TableNotWritable(format!(
+                "the data of table {} is taken over by the attached table {}",
+                table_info.desc,
+                String::from_utf8_lossy(&owner)
+            )))
+        }
+    }
+
     // Left a hint file which indicates the location of the latest snapshot
     async fn write_last_snapshot_hint(
         operator: &Operator,
@@ -25,17 +25,23 @@ use common_pipeline_transforms::processors::transforms::TransformCompact;
 use super::FuseTableSink;
 use crate::operations::CompactMutator;
 use crate::statistics::ClusterStatsGenerator;
+use crate::CompactTarget;
 use crate::FuseTable;
 use crate::Table;
 use crate::TableContext;
 use crate::TableMutator;
 use crate::DEFAULT_BLOCK_PER_SEGMENT;
+use crate::DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD;
+use crate::DEFAULT_ROW_PER_BLOCK;
+use crate::FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD;
 use crate::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
+use crate::FUSE_OPT_KEY_ROW_PER_BLOCK;
 
 impl FuseTable {
     pub(crate) async fn do_compact(
         &self,
         ctx: Arc<dyn TableContext>,
+        target: CompactTarget,
         pipeline: &mut Pipeline,
     ) -> Result<Option<Arc<dyn TableMutator>>> {
         let snapshot_opt = self.read_table_snapshot(ctx.clone()).await?;
@@ -61,12 +67,19 @@ impl FuseTable {
             self.meta_location_generator().clone(),
             block_per_seg,
             self.cluster_key_meta.is_some(),
+            target,
+            self.schema_version()?,
         )?;
         let need_compact = mutator.blocks_select().await?;
         if !need_compact {
             return Ok(None);
         }
 
+        // The segments are regrouped while selecting, there are no blocks to rewrite.
+        if target == CompactTarget::Segments {
+            return Ok(Some(Arc::new(mutator)));
+        }
+
         let partitions_total = mutator.partitions_total();
         let (statistics, parts) = self.read_partitions_with_metas(
             ctx.clone(),
@@ -98,6 +111,11 @@ impl FuseTable {
             )
         })?;
 
+        let aggregating_indexes = self.aggregating_indexes()?;
+        let bloom_index_columns = self.bloom_index_columns()?;
+        let inverted_indexes = self.inverted_indexes()?;
+        let virtual_columns = self.virtual_columns()?;
+        let schema_version = self.schema_version()?;
         let mut sink_pipeline_builder = SinkPipeBuilder::create();
         for _ in 0..pipeline.output_len() {
             let input_port = InputPort::create();
@@ -110,6 +128,11 @@ impl FuseTable {
                     mutator.get_storage_operator(),
                     self.meta_location_generator().clone(),
                     ClusterStatsGenerator::default(),
+                    aggregating_indexes.clone(),
+                    bloom_index_columns.clone(),
+                    inverted_indexes.clone(),
+                    virtual_columns.clone(),
+                    schema_version,
                     None,
                 )?,
             );
@@ -118,4 +141,42 @@ impl FuseTable {
         pipeline.add_pipe(sink_pipeline_builder.finalize());
         Ok(Some(Arc::new(mutator)))
     }
+
+    /// Returns what to compact, if the blocks or the segments of the table are at least
+    /// `threshold` more than the ones the data would be laid out ideally.
+    pub async fn compaction_target(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        threshold: u64,
+    ) -> Result<Option<CompactTarget>> {
+        let snapshot = match self.read_table_snapshot(ctx).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+        let summary = &snapshot.summary;
+        let div_ceil = |a: u64, b: usize| {
+            let b = b.max(1) as u64;
+            (a + b - 1) / b
+        };
+
+        let row_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
+        let bytes_per_block = self.get_option(
+            FUSE_OPT_KEY_BLOCK_IN_MEM_SIZE_THRESHOLD,
+            DEFAULT_BLOCK_SIZE_IN_MEM_SIZE_THRESHOLD,
+        );
+        let ideal_blocks = div_ceil(summary.row_count, row_per_block)
+            .max(div_ceil(summary.uncompressed_byte_size, bytes_per_block));
+        if summary.block_count >= ideal_blocks + threshold {
+            return Ok(Some(CompactTarget::Blocks));
+        }
+
+        let block_per_seg =
+            self.get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT);
+        let ideal_segments = div_ceil(summary.block_count, block_per_seg);
+        if snapshot.segments.len() as u64 >= ideal_segments + threshold {
+            return Ok(Some(CompactTarget::Segments));
+        }
+
+        Ok(None)
+    }
 }
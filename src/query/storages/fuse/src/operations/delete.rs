@@ -84,6 +84,7 @@ impl FuseTable {
             self.meta_location_generator.clone(),
             snapshot.clone(),
             cluster_stats_gen,
+            self.bloom_index_columns()?,
         )?;
         let schema = self.table_info.schema();
         // TODO refine pruner
@@ -93,9 +94,12 @@ impl FuseTable {
             prewhere: None, // TBD: if delete rows need prewhere optimization
             limit: None,
             order_by: vec![],
+            sample: None,
+            virtual_columns: vec![],
         };
         let push_downs = Some(extras);
         let block_metas = BlockPruner::new(snapshot.clone())
+            .with_bloom_index_columns(self.bloom_index_columns()?)
             .prune(&ctx, schema, &push_downs)
             .await?;
 
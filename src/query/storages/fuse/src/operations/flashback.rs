@@ -0,0 +1,120 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_cache::Cache;
+use common_catalog::table::NavigationPoint;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::caches::CacheManager;
+use common_fuse_meta::meta::TableSnapshot;
+use common_fuse_meta::meta::Versioned;
+use common_meta_app::schema::TableStatistics;
+use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_types::MatchSeq;
+use uuid::Uuid;
+
+use crate::io::write_meta;
+use crate::FuseTable;
+use crate::OPT_KEY_SNAPSHOT_LOCATION;
+
+impl FuseTable {
+    /// Restore the table to the snapshot at `point`.
+    ///
+    /// The history is kept: a new snapshot with the segments and the schema of the historical
+    /// snapshot is appended to the snapshot chain, so that the flashback itself could be undone
+    /// by another flashback.
+    pub async fn do_flashback(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        point: &NavigationPoint,
+    ) -> Result<()> {
+        ctx.get_txn_manager()
+            .lock()
+            .check_autocommit("FLASHBACK TABLE")?;
+        let historical_table = match point {
+            NavigationPoint::SnapshotID(snapshot_id) => {
+                self.navigate_to_snapshot(ctx.clone(), snapshot_id).await?
+            }
+            NavigationPoint::TimePoint(time_point) => {
+                self.navigate_to_time_point(ctx.clone(), *time_point)
+                    .await?
+            }
+        };
+        let historical_snapshot = historical_table
+            .read_table_snapshot(ctx.clone())
+            .await?
+            .ok_or_else(|| ErrorCode::TableHistoricalDataNotFound("No historical data found"))?;
+        let prev_snapshot = self
+            .read_table_snapshot(ctx.clone())
+            .await?
+            .ok_or_else(|| ErrorCode::TableHistoricalDataNotFound("No historical data found"))?;
+        if historical_snapshot.snapshot_id == prev_snapshot.snapshot_id {
+            return Ok(());
+        }
+
+        let new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            &prev_snapshot.timestamp,
+            Some((prev_snapshot.snapshot_id, prev_snapshot.format_version())),
+            historical_snapshot.schema.clone(),
+            historical_snapshot.summary.clone(),
+            historical_snapshot.segments.clone(),
+            self.cluster_key_meta.clone(),
+        );
+        let loc = self.meta_location_generator();
+        let new_snapshot_loc =
+            loc.snapshot_location_from_uuid(&new_snapshot.snapshot_id, TableSnapshot::VERSION)?;
+        let operator = ctx.get_storage_operator()?;
+        write_meta(&operator, &new_snapshot_loc, &new_snapshot).await?;
+
+        let mut new_table_meta = self.table_info.meta.clone();
+        // update snapshot location
+        new_table_meta.options.insert(
+            OPT_KEY_SNAPSHOT_LOCATION.to_owned(),
+            new_snapshot_loc.clone(),
+        );
+
+        // restore the schema and the statistics of the historical snapshot
+        new_table_meta.schema = Arc::new(new_snapshot.schema.clone());
+        let summary = &new_snapshot.summary;
+        new_table_meta.statistics = TableStatistics {
+            number_of_rows: summary.row_count,
+            data_bytes: summary.uncompressed_byte_size,
+            compressed_data_bytes: summary.compressed_byte_size,
+            index_data_bytes: summary.index_size,
+        };
+
+        let table_id = self.table_info.ident.table_id;
+        let table_version = self.table_info.ident.seq;
+        let catalog = ctx.get_catalog(self.table_info.catalog())?;
+        catalog
+            .update_table_meta(UpdateTableMetaReq {
+                table_id,
+                seq: MatchSeq::Exact(table_version),
+                new_table_meta,
+            })
+            .await?;
+
+        // keep the new snapshot cached so that the following reads don't load it again
+        if let Some(snapshot_cache) = CacheManager::instance().get_table_snapshot_cache() {
+            let cache = &mut snapshot_cache.write().await;
+            cache.put(new_snapshot_loc, Arc::new(new_snapshot));
+        }
+
+        Ok(())
+    }
+}
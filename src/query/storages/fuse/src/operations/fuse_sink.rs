@@ -20,7 +20,6 @@ use common_arrow::parquet::compression::CompressionOptions;
 use common_arrow::parquet::metadata::ThriftFileMetaData;
 use common_cache::Cache;
 use common_catalog::table_context::TableContext;
-use common_datablocks::serialize_data_blocks;
 use common_datablocks::serialize_data_blocks_with_compression;
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
@@ -35,6 +34,7 @@ use opendal::Operator;
 
 use super::AppendOperationLogEntry;
 use crate::io;
+use crate::io::MultipartOptions;
 use crate::io::TableMetaLocationGenerator;
 use crate::pipelines::processors::port::InputPort;
 use crate::pipelines::processors::processor::Event;
@@ -50,6 +50,21 @@ struct BloomIndexState {
     location: Location,
 }
 
+struct AggregatingIndexState {
+    data: Vec<u8>,
+    location: String,
+}
+
+struct InvertedIndexState {
+    data: Vec<u8>,
+    location: String,
+}
+
+struct VirtualColumnsState {
+    data: Vec<u8>,
+    location: String,
+}
+
 enum State {
     None,
     NeedSerialize(DataBlock),
@@ -59,6 +74,9 @@ enum State {
         meta_data: Box<ThriftFileMetaData>,
         block_statistics: BlockStatistics,
         bloom_index_state: BloomIndexState,
+        aggregating_index_states: Vec<AggregatingIndexState>,
+        inverted_index_states: Vec<InvertedIndexState>,
+        virtual_columns_state: Option<VirtualColumnsState>,
     },
     GenerateSegment,
     SerializedSegment {
@@ -78,12 +96,20 @@ pub struct FuseTableSink {
     meta_locations: TableMetaLocationGenerator,
     accumulator: StatisticsAccumulator,
     cluster_stats_gen: ClusterStatsGenerator,
+    multipart: MultipartOptions,
+    aggregating_indexes: Vec<AggregatingIndexMeta>,
+    // The columns the bloom filters are built for, `None` for all the applicable columns.
+    bloom_index_columns: Option<Vec<String>>,
+    inverted_indexes: Vec<InvertedIndexMeta>,
+    virtual_columns: Option<VirtualColumnsMeta>,
+    schema_version: u64,
 
     // A dummy output port for distributed insert select to connect Exchange Sink.
     output: Option<Arc<OutputPort>>,
 }
 
 impl FuseTableSink {
+    #[allow(clippy::too_many_arguments)]
     pub fn try_create(
         input: Arc<InputPort>,
         ctx: Arc<dyn TableContext>,
@@ -91,8 +117,14 @@ impl FuseTableSink {
         data_accessor: Operator,
         meta_locations: TableMetaLocationGenerator,
         cluster_stats_gen: ClusterStatsGenerator,
+        aggregating_indexes: Vec<AggregatingIndexMeta>,
+        bloom_index_columns: Option<Vec<String>>,
+        inverted_indexes: Vec<InvertedIndexMeta>,
+        virtual_columns: Option<VirtualColumnsMeta>,
+        schema_version: u64,
         output: Option<Arc<OutputPort>>,
     ) -> Result<ProcessorPtr> {
+        let multipart = MultipartOptions::try_create(&ctx)?;
         Ok(ProcessorPtr::create(Box::new(FuseTableSink {
             ctx,
             input,
@@ -102,6 +134,12 @@ impl FuseTableSink {
             accumulator: Default::default(),
             num_block_threshold: num_block_threshold as u64,
             cluster_stats_gen,
+            multipart,
+            aggregating_indexes,
+            bloom_index_columns,
+            inverted_indexes,
+            virtual_columns,
+            schema_version,
             output,
         })))
     }
@@ -163,7 +201,11 @@ impl Processor for FuseTableSink {
 
                 let bloom_index_state = {
                     // write index
-                    let bloom_index = BloomFilterIndexer::try_create(self.ctx.clone(), &[&block])?;
+                    let bloom_index = BloomFilterIndexer::try_create_with_columns(
+                        self.ctx.clone(),
+                        &[&block],
+                        self.bloom_index_columns.as_deref(),
+                    )?;
                     let index_block = bloom_index.bloom_block;
                     let location = self.meta_locations.block_bloom_index_location(&block_id);
                     let mut data = Vec::with_capacity(100 * 1024);
@@ -181,12 +223,42 @@ impl Processor for FuseTableSink {
                     }
                 };
 
+                let mut aggregating_index_states = vec![];
+                for index in &self.aggregating_indexes {
+                    let schema = index.schema(block.schema())?;
+                    let index_block = index.aggregate_block(&schema, &block)?;
+                    let mut data = Vec::new();
+                    io::serialize_aggregating_index(index_block, &mut data)?;
+                    let location = self
+                        .meta_locations
+                        .aggregating_index_location(&index.id, &block_location.0);
+                    aggregating_index_states.push(AggregatingIndexState { data, location });
+                }
+
+                let mut inverted_index_states = vec![];
+                for index in &self.inverted_indexes {
+                    let data = BlockInvertedIndex::build(index, &block)?.to_vec()?;
+                    let location = self
+                        .meta_locations
+                        .inverted_index_location(&index.id, &block_location.0);
+                    inverted_index_states.push(InvertedIndexState { data, location });
+                }
+
+                let virtual_columns_state = match &self.virtual_columns {
+                    Some(virtual_columns) => Some(VirtualColumnsState {
+                        data: BlockVirtualColumns::build(virtual_columns, &block)?.to_vec()?,
+                        location: self
+                            .meta_locations
+                            .virtual_column_location(&virtual_columns.id, &block_location.0),
+                    }),
+                    None => None,
+                };
+
                 let block_statistics =
                     BlockStatistics::from(&block, block_location.0, cluster_stats)?;
                 // we need a configuration of block size threshold here
                 let mut data = Vec::with_capacity(100 * 1024 * 1024);
-                let schema = block.schema().clone();
-                let (size, meta_data) = serialize_data_blocks(vec![block], &schema, &mut data)?;
+                let (size, meta_data) = io::serialize_block(block, &mut data)?;
 
                 self.state = State::Serialized {
                     data,
@@ -194,13 +266,20 @@ impl Processor for FuseTableSink {
                     block_statistics,
                     meta_data: Box::new(meta_data),
                     bloom_index_state,
+                    aggregating_index_states,
+                    inverted_index_states,
+                    virtual_columns_state,
                 };
             }
             State::GenerateSegment => {
                 let acc = std::mem::take(&mut self.accumulator);
                 let col_stats = acc.summary()?;
+                let mut blocks_metas = acc.blocks_metas;
+                for block_meta in blocks_metas.iter_mut() {
+                    block_meta.schema_version = self.schema_version;
+                }
 
-                let segment_info = SegmentInfo::new(acc.blocks_metas, Statistics {
+                let segment_info = SegmentInfo::new(blocks_metas, Statistics {
                     row_count: acc.summary_row_count,
                     block_count: acc.summary_block_count,
                     uncompressed_byte_size: acc.in_memory_size,
@@ -231,12 +310,16 @@ impl Processor for FuseTableSink {
                 meta_data,
                 block_statistics,
                 bloom_index_state,
+                aggregating_index_states,
+                inverted_index_states,
+                virtual_columns_state,
             } => {
                 // write data block
-                io::write_data(
+                io::write_data_multipart(
                     &data,
                     &self.data_accessor,
                     &block_statistics.block_file_location,
+                    &self.multipart,
                 )
                 .await?;
 
@@ -248,6 +331,21 @@ impl Processor for FuseTableSink {
                 )
                 .await?;
 
+                // write aggregating indexes
+                for state in aggregating_index_states {
+                    io::write_data(&state.data, &self.data_accessor, &state.location).await?;
+                }
+
+                // write inverted indexes
+                for state in inverted_index_states {
+                    io::write_data(&state.data, &self.data_accessor, &state.location).await?;
+                }
+
+                // write virtual columns
+                if let Some(state) = virtual_columns_state {
+                    io::write_data(&state.data, &self.data_accessor, &state.location).await?;
+                }
+
                 let bloom_filter_index_size = bloom_index_state.size;
                 self.accumulator.add_block(
                     size,
@@ -15,6 +15,8 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_cache::Cache;
 use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
@@ -24,6 +26,7 @@ use common_fuse_meta::meta::Location;
 use common_fuse_meta::meta::SnapshotId;
 use futures::TryStreamExt;
 use opendal::Operator;
+use tracing::info;
 use tracing::warn;
 
 use crate::io::MetaReaders;
@@ -31,7 +34,20 @@ use crate::io::SnapshotHistoryReader;
 use crate::FuseTable;
 
 impl FuseTable {
-    pub async fn do_gc(&self, ctx: &Arc<dyn TableContext>, keep_last_snapshot: bool) -> Result<()> {
+    /// Removes the snapshots, and the segments, blocks and statistics only referenced by them.
+    ///
+    /// If `keep_last_snapshot` is set, the last snapshot is kept. If `retain_since` is also
+    /// given, the snapshots taken after it are kept as well, together with the newest one
+    /// taken at or before it, so that the table can still travel to any point since then.
+    pub async fn do_gc(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        keep_last_snapshot: bool,
+        retain_since: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        // the snapshots of the table are purged immediately, they could be referenced by the
+        // committed table if the transaction is rolled back
+        ctx.get_txn_manager().lock().check_autocommit("PURGE")?;
         let r = self.read_table_snapshot(ctx.clone()).await;
         let snapshot_opt = match r {
             Err(e) if e.code() == ErrorCode::storage_not_found_code() => {
@@ -71,9 +87,12 @@ impl FuseTable {
                 // just drop the whole snapshot,
                 let snapshots = vec![(last_snapshot.snapshot_id, self.snapshot_format_version())];
                 let segments = HashSet::from_iter(last_snapshot.segments.clone());
+                let statistics =
+                    HashSet::from_iter(last_snapshot.table_statistics_location.clone());
                 self.purge_blocks(ctx.as_ref(), segments.iter(), &HashSet::new())
                     .await?;
-                self.collect(ctx.as_ref(), segments, snapshots).await
+                self.collect(ctx.as_ref(), segments, statistics, snapshots)
+                    .await
             };
         };
 
@@ -90,7 +109,7 @@ impl FuseTable {
                 .push((last_snapshot.snapshot_id, self.snapshot_format_version()));
         }
 
-        let segments_referenced_by_gc_root: HashSet<Location> = if !keep_last_snapshot {
+        let mut segments_referenced_by_gc_root: HashSet<Location> = if !keep_last_snapshot {
             //  segment gc root references nothing;
             HashSet::new()
         } else {
@@ -98,23 +117,52 @@ impl FuseTable {
             HashSet::from_iter(last_snapshot.segments.clone())
         };
 
-        // segments which no longer need to be kept
+        let mut statistics_referenced_by_gc_root: HashSet<Location> = if !keep_last_snapshot {
+            HashSet::new()
+        } else {
+            HashSet::from_iter(last_snapshot.table_statistics_location.clone())
+        };
+
+        // the previous snapshots are retained (as gc roots) until the retention point is passed
+        let within_retention = |ts: Option<DateTime<Utc>>| matches!((ts, retain_since), (Some(ts), Some(since)) if ts > since);
+        let mut retaining = keep_last_snapshot && within_retention(last_snapshot.timestamp);
+
+        // segments and statistics which no longer need to be kept
         let mut segments_to_be_deleted: HashSet<_> = HashSet::new();
+        let mut statistics_to_be_deleted: HashSet<_> = HashSet::new();
         {
             if !keep_last_snapshot {
-                segments_to_be_deleted.extend(last_snapshot.segments.clone())
+                segments_to_be_deleted.extend(last_snapshot.segments.clone());
+                statistics_to_be_deleted.extend(last_snapshot.table_statistics_location.clone());
             }
 
             // collects
-            // - all the previous snapshots
+            // - all the previous snapshots out of the retention period
             // - segments referenced by previous snapshots, but not by gc_root
+            // - statistics referenced by previous snapshots, but not by gc_root
+            //
+            // the history is walked from the newest to the oldest, the gc roots are
+            // always collected before the snapshots to be deleted
             while let Some(s) = snapshot_history.try_next().await? {
+                if retaining {
+                    // keeps the newest snapshot at or before the retention point, and stops there
+                    retaining = within_retention(s.timestamp);
+                    segments_referenced_by_gc_root.extend(s.segments.clone());
+                    statistics_referenced_by_gc_root.extend(s.table_statistics_location.clone());
+                    continue;
+                }
+
                 snapshots_to_be_deleted.push((s.snapshot_id, s.format_version()));
                 for seg in &s.segments {
                     if !segments_referenced_by_gc_root.contains(seg) {
                         segments_to_be_deleted.insert(seg.clone());
                     }
                 }
+                for stat in &s.table_statistics_location {
+                    if !statistics_referenced_by_gc_root.contains(stat) {
+                        statistics_to_be_deleted.insert(stat.clone());
+                    }
+                }
             }
         }
 
@@ -133,6 +181,7 @@ impl FuseTable {
         self.collect(
             ctx.as_ref(),
             segments_to_be_deleted,
+            statistics_to_be_deleted,
             snapshots_to_be_deleted,
         )
         .await
@@ -170,6 +219,9 @@ impl FuseTable {
     /// rm all the blocks, which are
     /// - referenced by any one of `segments`
     /// - but NOT referenced by `root`
+    ///
+    /// together with their bloom indexes, aggregating indexes, inverted indexes and virtual
+    /// columns
     async fn purge_blocks(
         &self,
         ctx: &dyn TableContext,
@@ -178,6 +230,10 @@ impl FuseTable {
     ) -> Result<()> {
         let reader = MetaReaders::segment_info_reader(ctx);
         let accessor = ctx.get_storage_operator()?;
+        let aggregating_indexes = self.aggregating_indexes()?;
+        let inverted_indexes = self.inverted_indexes()?;
+        let virtual_columns = self.virtual_columns()?;
+        let mut purged_blocks = 0;
         for l in segments {
             let (x, ver) = l;
             let res = reader.read(x, None, *ver).await?;
@@ -189,14 +245,50 @@ impl FuseTable {
                             let cache = &mut *c.write().await;
                             cache.pop(path);
                         }
+                        if let Some(c) = CacheManager::instance().get_bloom_index_filter_cache() {
+                            let cache = &mut *c.write().await;
+                            let prefix = format!("{path}-");
+                            let keys = cache
+                                .iter()
+                                .filter(|(k, _)| k.starts_with(&prefix))
+                                .map(|(k, _)| k.clone())
+                                .collect::<Vec<_>>();
+                            for k in keys {
+                                cache.pop(&k);
+                            }
+                        }
                         self.remove_location(&accessor, bloom_index_location.0.as_str())
                             .await?;
                     }
+                    for index in &aggregating_indexes {
+                        let index_location = self
+                            .meta_location_generator
+                            .aggregating_index_location(&index.id, &block_meta.location.0);
+                        self.remove_location(&accessor, index_location).await?;
+                    }
+                    for index in &inverted_indexes {
+                        let index_location = self
+                            .meta_location_generator
+                            .inverted_index_location(&index.id, &block_meta.location.0);
+                        self.remove_location(&accessor, index_location).await?;
+                    }
+                    if let Some(virtual_columns) = &virtual_columns {
+                        let location = self
+                            .meta_location_generator
+                            .virtual_column_location(&virtual_columns.id, &block_meta.location.0);
+                        self.remove_location(&accessor, location).await?;
+                    }
                     self.remove_location(&accessor, block_meta.location.0.as_str())
                         .await?;
+                    purged_blocks += 1;
                 }
             }
         }
+
+        info!(
+            "purged {} blocks. table: {}, ident {}",
+            purged_blocks, self.table_info.desc, self.table_info.ident,
+        );
         Ok(())
     }
 
@@ -234,9 +326,18 @@ impl FuseTable {
         &self,
         ctx: &dyn TableContext,
         segments_to_be_deleted: HashSet<Location>,
+        statistics_to_be_deleted: HashSet<Location>,
         snapshots_to_be_deleted: Vec<(SnapshotId, u64)>,
     ) -> Result<()> {
         let accessor = ctx.get_storage_operator()?;
+        info!(
+            "purging {} snapshots, {} segments and {} statistics. table: {}, ident {}",
+            snapshots_to_be_deleted.len(),
+            segments_to_be_deleted.len(),
+            statistics_to_be_deleted.len(),
+            self.table_info.desc,
+            self.table_info.ident,
+        );
 
         // order matters, should always remove the blocks first, segment 2nd, snapshot last,
         // so that if something goes wrong, e.g. process crashed, gc task can be "picked up" and continued
@@ -250,8 +351,17 @@ impl FuseTable {
             self.remove_location(&accessor, x.as_str()).await?;
         }
 
+        // 2. remove the statistics of the columns
+        for (x, _v) in statistics_to_be_deleted {
+            if let Some(c) = CacheManager::instance().get_table_snapshot_statistics_cache() {
+                let cache = &mut *c.write().await;
+                cache.pop(x.as_str());
+            }
+            self.remove_location(&accessor, x.as_str()).await?;
+        }
+
         let locs = self.meta_location_generator();
-        // 2. remove the snapshots
+        // 3. remove the snapshots
         for (id, ver) in snapshots_to_be_deleted.iter().rev() {
             let loc = locs.snapshot_location_from_uuid(id, *ver)?;
             if let Some(c) = CacheManager::instance().get_table_snapshot_cache() {
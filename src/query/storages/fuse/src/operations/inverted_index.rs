@@ -0,0 +1,83 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_legacy_planners::Projection;
+use tracing::info;
+
+use crate::index::BlockInvertedIndex;
+use crate::index::InvertedIndexMeta;
+use crate::io::write_data;
+use crate::io::MetaReaders;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Builds the inverted index for the existing blocks.
+    ///
+    /// The index files are written for the blocks lacking them, e.g. the blocks written before
+    /// the index is created. They are located by the locations of the blocks, so no new snapshot
+    /// is committed. The blocks written with the older schema versions are skipped, they are not
+    /// pruned by the inverted indexes anyway.
+    pub async fn do_build_inverted_index(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        index: &InvertedIndexMeta,
+    ) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.clone()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let schema = self.table_info.schema();
+        let schema_version = self.schema_version()?;
+        let column_indices = index
+            .columns
+            .iter()
+            .map(|name| schema.index_of(name))
+            .collect::<Result<Vec<_>>>()?;
+        let reader = self.create_block_reader(&ctx, Projection::Columns(column_indices))?;
+        let dal = ctx.get_storage_operator()?;
+
+        let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut num_blocks = 0;
+        for (location, version) in &snapshot.segments {
+            let segment = segment_reader.read(location, None, *version).await?;
+            for block_meta in &segment.blocks {
+                if block_meta.schema_version != schema_version {
+                    continue;
+                }
+                let index_location = self
+                    .meta_location_generator
+                    .inverted_index_location(&index.id, &block_meta.location.0);
+                if dal.object(&index_location).is_exist().await? {
+                    continue;
+                }
+
+                let block = reader.read_with_block_meta(block_meta).await?;
+                let data = BlockInvertedIndex::build(index, &block)?.to_vec()?;
+                write_data(&data, &dal, &index_location).await?;
+                num_blocks += 1;
+            }
+        }
+
+        info!(
+            "build inverted index {} for {} blocks of table {}",
+            index.name, num_blocks, self.table_info.desc
+        );
+        Ok(())
+    }
+}
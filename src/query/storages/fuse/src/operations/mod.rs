@@ -12,12 +12,18 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod alter_column;
+mod analyze;
 mod append;
+mod bloom_index;
+mod column_statistics;
 mod commit;
 mod compact;
 mod delete;
+mod flashback;
 mod fuse_sink;
 mod gc;
+mod inverted_index;
 mod mutation;
 mod navigate;
 mod operation_log;
@@ -25,6 +31,7 @@ mod read;
 mod read_partitions;
 mod recluster;
 mod truncate;
+mod virtual_column;
 
 pub mod util;
 
@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_base::base::ProgressValues;
 use common_exception::Result;
 use common_fuse_meta::caches::CacheManager;
 use common_fuse_meta::meta::BlockMeta;
@@ -33,6 +34,7 @@ use crate::operations::AppendOperationLogEntry;
 use crate::statistics::merge_statistics;
 use crate::statistics::reducers::reduce_block_metas;
 use crate::statistics::reducers::reduce_statistics;
+use crate::CompactTarget;
 use crate::FuseTable;
 use crate::TableContext;
 use crate::TableMutator;
@@ -50,6 +52,8 @@ pub struct CompactMutator {
     block_per_seg: usize,
     // is_cluster indicates whether the table contains cluster key.
     is_cluster: bool,
+    target: CompactTarget,
+    schema_version: u64,
 }
 
 impl CompactMutator {
@@ -60,6 +64,8 @@ impl CompactMutator {
         location_generator: TableMetaLocationGenerator,
         block_per_seg: usize,
         is_cluster: bool,
+        target: CompactTarget,
+        schema_version: u64,
     ) -> Result<Self> {
         let data_accessor = ctx.get_storage_operator()?;
 
@@ -74,6 +80,8 @@ impl CompactMutator {
             summary: Statistics::default(),
             block_per_seg,
             is_cluster,
+            target,
+            schema_version,
         })
     }
 
@@ -105,10 +113,14 @@ impl TableMutator for CompactMutator {
             let mut remains = Vec::new();
             let segment = reader.read(x, None, ver).await?;
             segment.blocks.iter().for_each(|b| {
+                // Segment compaction only regroups the existing blocks, no block is rewritten.
+                // The blocks written with an older schema are rewritten with the latest one.
                 if self.is_cluster
-                    || self
-                        .block_compactor
-                        .check_perfect_block(b.row_count as usize, b.block_size as usize)
+                    || self.target == CompactTarget::Segments
+                    || (b.schema_version == self.schema_version
+                        && self
+                            .block_compactor
+                            .check_perfect_block(b.row_count as usize, b.block_size as usize))
                 {
                     remains.push(b.clone());
                 } else {
@@ -177,6 +189,13 @@ impl TableMutator for CompactMutator {
         new_snapshot.segments.append(&mut merged_segments);
         new_snapshot.summary = merge_statistics(&self.summary, &merged_summary)?;
 
+        // report the rows and bytes rewritten by the compaction
+        let progress_values = ProgressValues {
+            rows: merged_summary.row_count as usize,
+            bytes: merged_summary.uncompressed_byte_size as usize,
+        };
+        ctx.get_write_progress().incr(&progress_values);
+
         FuseTable::commit_to_meta_server(
             ctx.as_ref(),
             table_info,
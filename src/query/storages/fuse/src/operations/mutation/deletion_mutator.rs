@@ -25,6 +25,7 @@ use crate::io::BlockWriter;
 use crate::io::TableMetaLocationGenerator;
 use crate::operations::mutation::BaseMutator;
 use crate::statistics::ClusterStatsGenerator;
+use crate::SchemaHistory;
 
 pub enum Deletion {
     NothingDeleted,
@@ -34,6 +35,7 @@ pub enum Deletion {
 pub struct DeletionMutator {
     base_mutator: BaseMutator,
     cluster_stats_gen: ClusterStatsGenerator,
+    bloom_index_columns: Option<Vec<String>>,
 }
 
 impl DeletionMutator {
@@ -42,11 +44,13 @@ impl DeletionMutator {
         location_generator: TableMetaLocationGenerator,
         base_snapshot: Arc<TableSnapshot>,
         cluster_stats_gen: ClusterStatsGenerator,
+        bloom_index_columns: Option<Vec<String>>,
     ) -> Result<Self> {
         let base_mutator = BaseMutator::try_create(ctx, location_generator, base_snapshot)?;
         Ok(Self {
             base_mutator,
             cluster_stats_gen,
+            bloom_index_columns,
         })
     }
 
@@ -72,11 +76,15 @@ impl DeletionMutator {
                 &self.base_mutator.ctx,
                 &self.base_mutator.data_accessor,
                 &self.base_mutator.location_generator,
-            );
+            )
+            .with_bloom_index_columns(self.bloom_index_columns.as_deref());
             let cluster_stats = self
                 .cluster_stats_gen
                 .gen_with_origin_stats(&replace_with, origin_stats)?;
-            Some(block_writer.write(replace_with, cluster_stats).await?)
+            let mut block_meta = block_writer.write(replace_with, cluster_stats).await?;
+            block_meta.schema_version =
+                SchemaHistory::version_of(&self.base_mutator.base_snapshot.schema)?;
+            Some(block_meta)
         };
         let original_block_loc = location_of_block_to_be_replaced;
         self.base_mutator
@@ -25,8 +25,10 @@ use futures::TryStreamExt;
 
 use crate::io::MetaReaders;
 use crate::io::SnapshotHistoryReader;
+use crate::io::TableMetaLocationGenerator;
 use crate::FuseTable;
 use crate::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::OPT_KEY_TABLE_ATTACHED_READ_ONLY;
 
 impl FuseTable {
     pub async fn navigate_to_time_point(
@@ -61,7 +63,7 @@ impl FuseTable {
 
     pub async fn find<P>(&self, ctx: Arc<dyn TableContext>, mut pred: P) -> Result<Arc<FuseTable>>
     where P: FnMut(&TableSnapshot) -> bool {
-        let snapshot_location = if let Some(loc) = self.snapshot_loc() {
+        let snapshot_location = if let Some(loc) = self.current_snapshot_loc(ctx.as_ref()).await? {
             loc
         } else {
             // not an error?
@@ -70,7 +72,7 @@ impl FuseTable {
             ));
         };
 
-        let snapshot_version = self.snapshot_format_version();
+        let snapshot_version = TableMetaLocationGenerator::snapshot_version(&snapshot_location);
         let reader = MetaReaders::table_snapshot_reader(ctx);
 
         // grab the table history
@@ -119,6 +121,11 @@ impl FuseTable {
                 .meta
                 .options
                 .insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), loc);
+            // the historical table reads the snapshot found, not the latest one of the owner
+            table_info
+                .meta
+                .options
+                .remove(OPT_KEY_TABLE_ATTACHED_READ_ONLY);
 
             // 3. The statistics
             let summary = &snapshot.summary;
@@ -19,8 +19,12 @@ use common_base::base::Progress;
 use common_base::base::ProgressValues;
 use common_catalog::table_context::TableContext;
 use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
 use common_datavalues::ColumnRef;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
+use common_datavalues::ScalarColumn;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_legacy_planners::Extras;
@@ -28,6 +32,7 @@ use common_legacy_planners::PartInfoPtr;
 use common_legacy_planners::PrewhereInfo;
 use common_legacy_planners::Projection;
 use common_legacy_planners::ReadDataSourcePlan;
+use common_legacy_planners::ScanSample;
 use common_pipeline_core::processors::port::OutputPort;
 use common_pipeline_core::processors::processor::Event;
 use common_pipeline_core::processors::processor::ProcessorPtr;
@@ -35,9 +40,16 @@ use common_pipeline_core::processors::Processor;
 use common_pipeline_core::Pipeline;
 use common_pipeline_core::SourcePipeBuilder;
 use common_pipeline_transforms::processors::ExpressionExecutor;
+use rand::Rng;
 
+use crate::fuse_part::FusePartInfo;
+use crate::index::VirtualColumnsMeta;
 use crate::io::BlockReader;
+use crate::io::ColumnsData;
+use crate::io::VirtualColumnReader;
 use crate::operations::read::State::Generated;
+use crate::pruning::RuntimeFilterPruner;
+use crate::pruning::TopNFilter;
 use crate::FuseTable;
 
 impl FuseTable {
@@ -66,6 +78,58 @@ impl FuseTable {
         }
     }
 
+    /// The projection of the source columns of the virtual columns.
+    pub fn virtual_column_sources(
+        &self,
+        virtual_columns: &VirtualColumnsMeta,
+    ) -> Result<Projection> {
+        let schema = self.table_info.schema();
+        let mut indices = virtual_columns
+            .columns
+            .iter()
+            .map(|column| schema.index_of(&column.source))
+            .collect::<Result<Vec<_>>>()?;
+        indices.sort_unstable();
+        indices.dedup();
+        Ok(Projection::Columns(indices))
+    }
+
+    /// The reader of the virtual columns requested by the scan, if any.
+    fn virtual_column_reader(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        push_downs: &Option<Extras>,
+    ) -> Result<Option<VirtualColumnReader>> {
+        let names = match push_downs {
+            Some(extras) if !extras.virtual_columns.is_empty() => &extras.virtual_columns,
+            _ => return Ok(None),
+        };
+        let virtual_columns = self.virtual_columns()?.ok_or_else(|| {
+            ErrorCode::LogicalError("It's a bug. The virtual columns are not found")
+        })?;
+        let columns = names
+            .iter()
+            .map(|name| {
+                virtual_columns.column(name).cloned().ok_or_else(|| {
+                    ErrorCode::LogicalError(format!("Unknown virtual column {}", name))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let requested = VirtualColumnsMeta {
+            id: virtual_columns.id.clone(),
+            columns: columns.clone(),
+        };
+        let source_reader =
+            self.create_block_reader(ctx, self.virtual_column_sources(&requested)?)?;
+        Ok(Some(VirtualColumnReader::create(
+            ctx.get_storage_operator()?,
+            self.meta_location_generator.clone(),
+            virtual_columns.id,
+            columns,
+            source_reader,
+        )))
+    }
+
     pub fn prewhere_of_push_downs(&self, push_downs: &Option<Extras>) -> Option<PrewhereInfo> {
         if let Some(Extras { prewhere, .. }) = push_downs {
             prewhere.clone()
@@ -74,6 +138,45 @@ impl FuseTable {
         }
     }
 
+    /// The readers of the sorted column and the other columns of a top-n scan, the other columns
+    /// of a part are read only if some rows are kept by the filter.
+    fn top_n_readers(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        projection: &Projection,
+        top_n_filter: &TopNFilter,
+    ) -> Result<Option<(Arc<BlockReader>, Option<BlockReader>)>> {
+        let sort_idx = match self
+            .table_info
+            .schema()
+            .index_of(top_n_filter.column_name())
+        {
+            Ok(index) => index,
+            Err(_) => return Ok(None),
+        };
+
+        match projection {
+            Projection::Columns(indices) if indices.contains(&sort_idx) => {
+                let remain_columns = indices
+                    .iter()
+                    .copied()
+                    .filter(|index| *index != sort_idx)
+                    .collect::<Vec<_>>();
+                let sort_reader =
+                    self.create_block_reader(ctx, Projection::Columns(vec![sort_idx]))?;
+                let remain_reader = if remain_columns.is_empty() {
+                    None
+                } else {
+                    let reader =
+                        self.create_block_reader(ctx, Projection::Columns(remain_columns))?;
+                    Some((*reader).clone())
+                };
+                Ok(Some((sort_reader, remain_reader)))
+            }
+            _ => Ok(None),
+        }
+    }
+
     #[inline]
     pub fn do_read2(
         &self,
@@ -83,7 +186,18 @@ impl FuseTable {
     ) -> Result<()> {
         let table_schema = self.table_info.schema();
         let projection = self.projection_of_push_downs(&plan.push_downs);
-        let output_reader = self.create_block_reader(&ctx, projection)?; // for deserialize output blocks
+        let output_reader = self.create_block_reader(&ctx, projection.clone())?; // for deserialize output blocks
+        let virtual_column_reader = self.virtual_column_reader(&ctx, &plan.push_downs)?;
+        // the virtual columns are read after the rows are filtered by the sorted column
+        let top_n_filter = match virtual_column_reader {
+            Some(_) => None,
+            None => TopNFilter::try_create(&table_schema, &plan.push_downs)?,
+        };
+        let top_n_readers = match &top_n_filter {
+            Some(top_n_filter) => self.top_n_readers(&ctx, &projection, top_n_filter)?,
+            None => None,
+        };
+        let top_n_filter = top_n_filter.filter(|_| top_n_readers.is_some());
 
         let (output_reader, prewhere_reader, prewhere_filter, remain_reader) =
             if let Some(prewhere) = self.prewhere_of_push_downs(&plan.push_downs) {
@@ -116,20 +230,54 @@ impl FuseTable {
                     Some(executor),
                     remain_reader,
                 )
+            } else if let Some((sort_reader, remain_reader)) = top_n_readers {
+                // read the sorted column first, like the columns of a prewhere
+                (output_reader, sort_reader, None, remain_reader)
             } else {
                 (output_reader.clone(), output_reader, None, None)
             };
 
+        // the virtual columns are appended to the columns read
+        let mut output_fields = output_reader.schema().fields().clone();
+        if let Some(virtual_column_reader) = &virtual_column_reader {
+            output_fields.extend(
+                virtual_column_reader
+                    .columns()
+                    .iter()
+                    .map(|column| column.to_data_field()),
+            );
+        }
+        let output_schema = Arc::new(DataSchema::new(output_fields));
+        let virtual_column_reader = virtual_column_reader.map(Arc::new);
+
         let prewhere_filter = Arc::new(prewhere_filter);
         let remain_reader = Arc::new(remain_reader);
+        let runtime_filter_pruner = RuntimeFilterPruner::create(ctx.clone(), &table_schema)?;
+        let row_sample = match &plan.push_downs {
+            Some(Extras {
+                sample: Some(ScanSample::Row(probability)),
+                ..
+            }) => Some(*probability),
+            _ => None,
+        };
 
+        let settings = ctx.get_settings();
         let parts_len = plan.parts.len();
-        let max_threads = ctx.get_settings().get_max_threads()? as usize;
-        let max_threads = std::cmp::min(parts_len, max_threads);
+        let mut max_threads = settings.get_max_threads()? as usize;
+        let adaptive = settings.get_enable_adaptive_parallelism()?;
+        if adaptive {
+            // Each thread reads at least `min_bytes_per_thread`, so that the tiny scans are not
+            // over-parallelized.
+            let read_bytes = Self::parts_read_bytes(&plan.parts)?;
+            let min_bytes_per_thread = std::cmp::max(1, settings.get_min_bytes_per_thread()?);
+            let threads = (read_bytes as u64 + min_bytes_per_thread - 1) / min_bytes_per_thread;
+            max_threads = std::cmp::max(1, std::cmp::min(max_threads, threads as usize));
+        }
+        let sources = std::cmp::max(1, std::cmp::min(parts_len, max_threads));
 
         let mut source_builder = SourcePipeBuilder::create();
 
-        for _index in 0..std::cmp::max(1, max_threads) {
+        for _index in 0..sources {
             let output = OutputPort::create();
             source_builder.add_source(
                 output.clone(),
@@ -137,19 +285,42 @@ impl FuseTable {
                     ctx.clone(),
                     output,
                     output_reader.clone(),
+                    output_schema.clone(),
                     prewhere_reader.clone(),
                     prewhere_filter.clone(),
                     remain_reader.clone(),
+                    runtime_filter_pruner.clone(),
+                    top_n_filter.clone(),
+                    row_sample,
+                    virtual_column_reader.clone(),
                 )?,
             );
         }
 
         pipeline.add_pipe(source_builder.finalize());
+
+        // Few large partitions, e.g. a skewed table, are read by fewer sources than the threads
+        // the data deserves. The blocks read are spread to the threads for the transforms.
+        if adaptive && sources < max_threads {
+            pipeline.resize(max_threads)?;
+        }
         Ok(())
     }
-}
 
-type DataChunks = Vec<(usize, Vec<u8>)>;
+    /// The bytes of the columns read of the partitions.
+    fn parts_read_bytes(parts: &[PartInfoPtr]) -> Result<usize> {
+        let mut read_bytes = 0;
+        for part in parts {
+            let part = FusePartInfo::from_part(part)?;
+            read_bytes += part
+                .columns_meta
+                .values()
+                .map(|meta| meta.length as usize)
+                .sum::<usize>();
+        }
+        Ok(read_bytes)
+    }
+}
 
 struct PrewhereData {
     data_block: DataBlock,
@@ -159,8 +330,8 @@ struct PrewhereData {
 enum State {
     ReadDataPrewhere(PartInfoPtr),
     ReadDataRemain(PartInfoPtr, PrewhereData),
-    PrewhereFilter(PartInfoPtr, DataChunks),
-    Deserialize(PartInfoPtr, DataChunks, Option<PrewhereData>),
+    PrewhereFilter(PartInfoPtr, ColumnsData),
+    Deserialize(PartInfoPtr, ColumnsData, Option<PrewhereData>),
     Generated(Option<PartInfoPtr>, DataBlock),
     Finish,
 }
@@ -171,22 +342,45 @@ struct FuseTableSource {
     scan_progress: Arc<Progress>,
     output: Arc<OutputPort>,
     output_reader: Arc<BlockReader>,
+    // the columns of the output reader and the virtual columns
+    output_schema: DataSchemaRef,
 
     prewhere_reader: Arc<BlockReader>,
     prewhere_filter: Arc<Option<ExpressionExecutor>>,
     remain_reader: Arc<Option<BlockReader>>,
+    runtime_filter_pruner: Arc<RuntimeFilterPruner>,
+    // drops the rows which can not be in the result of `ORDER BY ... LIMIT`
+    top_n_filter: Option<Arc<TopNFilter>>,
+    // the probability of reading a row, for `TABLESAMPLE BERNOULLI`
+    row_sample: Option<f64>,
+    virtual_column_reader: Option<Arc<VirtualColumnReader>>,
+    // the virtual columns of the part being read
+    virtual_columns: Vec<ColumnRef>,
+    // whether to encode the low-cardinality string columns as dictionaries
+    dictionary_encoding: bool,
 }
 
+/// A string column is encoded as a dictionary if its distinct values are no more than the ratio of
+/// its rows.
+const DICTIONARY_MAX_RATIO: f64 = 0.25;
+
 impl FuseTableSource {
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         ctx: Arc<dyn TableContext>,
         output: Arc<OutputPort>,
         output_reader: Arc<BlockReader>,
+        output_schema: DataSchemaRef,
         prewhere_reader: Arc<BlockReader>,
         prewhere_filter: Arc<Option<ExpressionExecutor>>,
         remain_reader: Arc<Option<BlockReader>>,
+        runtime_filter_pruner: Arc<RuntimeFilterPruner>,
+        top_n_filter: Option<Arc<TopNFilter>>,
+        row_sample: Option<f64>,
+        virtual_column_reader: Option<Arc<VirtualColumnReader>>,
     ) -> Result<ProcessorPtr> {
         let scan_progress = ctx.get_scan_progress();
+        let dictionary_encoding = ctx.get_settings().get_enable_dictionary_encoding()?;
         let mut partitions = ctx.try_get_partitions(1)?;
         match partitions.is_empty() {
             true => Ok(ProcessorPtr::create(Box::new(FuseTableSource {
@@ -195,9 +389,16 @@ impl FuseTableSource {
                 scan_progress,
                 state: State::Finish,
                 output_reader,
+                output_schema,
                 prewhere_reader,
                 prewhere_filter,
                 remain_reader,
+                runtime_filter_pruner,
+                top_n_filter,
+                row_sample,
+                virtual_column_reader,
+                virtual_columns: vec![],
+                dictionary_encoding,
             }))),
             false => Ok(ProcessorPtr::create(Box::new(FuseTableSource {
                 ctx,
@@ -205,9 +406,16 @@ impl FuseTableSource {
                 scan_progress,
                 state: State::ReadDataPrewhere(partitions.remove(0)),
                 output_reader,
+                output_schema,
                 prewhere_reader,
                 prewhere_filter,
                 remain_reader,
+                runtime_filter_pruner,
+                top_n_filter,
+                row_sample,
+                virtual_column_reader,
+                virtual_columns: vec![],
+                dictionary_encoding,
             }))),
         }
     }
@@ -215,7 +423,16 @@ impl FuseTableSource {
     fn generate_one_block(&mut self, block: DataBlock) -> Result<()> {
         let mut partitions = self.ctx.try_get_partitions(1)?;
         // resort and prune columns
-        let block = block.resort(self.output_reader.schema())?;
+        let block = block.resort(self.output_schema.clone())?;
+        let block = self.runtime_filter_pruner.filter_block(block)?;
+        let block = match self.row_sample {
+            Some(probability) => Self::sample_rows(block, probability)?,
+            None => block,
+        };
+        let block = match self.dictionary_encoding {
+            true => DataBlock::encode_dictionaries(block, DICTIONARY_MAX_RATIO)?,
+            false => block,
+        };
         self.state = match partitions.is_empty() {
             true => State::Generated(None, block),
             false => State::Generated(Some(partitions.remove(0)), block),
@@ -223,16 +440,37 @@ impl FuseTableSource {
         Ok(())
     }
 
+    /// Keep the rows of the part selected by the inverted indexes, if any.
+    fn select_rows(part: &PartInfoPtr, block: DataBlock) -> Result<DataBlock> {
+        let rows = match &FusePartInfo::from_part(part)?.row_selection {
+            Some(rows) => rows,
+            None => return Ok(block),
+        };
+        let filter = BooleanColumn::from_owned_iterator(
+            (0..block.num_rows() as u32).map(|row| rows.binary_search(&row).is_ok()),
+        );
+        DataBlock::filter_block_with_bool_column(block, &filter)
+    }
+
+    /// Keep each row with the probability.
+    fn sample_rows(block: DataBlock, probability: f64) -> Result<DataBlock> {
+        let mut rng = rand::thread_rng();
+        let filter = BooleanColumn::from_owned_iterator(
+            (0..block.num_rows()).map(|_| rng.gen_bool(probability)),
+        );
+        DataBlock::filter_block_with_bool_column(block, &filter)
+    }
+
     fn generate_one_empty_block(&mut self) -> Result<()> {
         let mut partitions = self.ctx.try_get_partitions(1)?;
         self.state = match partitions.is_empty() {
             true => State::Generated(
                 None,
-                DataBlock::empty_with_schema(self.output_reader.schema()),
+                DataBlock::empty_with_schema(self.output_schema.clone()),
             ),
             false => State::Generated(
                 Some(partitions.remove(0)),
-                DataBlock::empty_with_schema(self.output_reader.schema()),
+                DataBlock::empty_with_schema(self.output_schema.clone()),
             ),
         };
         Ok(())
@@ -296,7 +534,8 @@ impl Processor for FuseTableSource {
                     let block = if chunks.is_empty() {
                         prewhere_blocks
                     } else if let Some(remain_reader) = self.remain_reader.as_ref() {
-                        let remain_block = remain_reader.deserialize(part, chunks)?;
+                        let remain_block = remain_reader.deserialize(part.clone(), chunks)?;
+                        let remain_block = Self::select_rows(&part, remain_block)?;
                         for (col, field) in remain_block
                             .columns()
                             .iter()
@@ -317,7 +556,16 @@ impl Processor for FuseTableSource {
                     self.scan_progress.incr(&progress_values);
                     DataBlock::filter_block(block, &filter)?
                 } else {
-                    let block = self.output_reader.deserialize(part, chunks)?;
+                    let mut block = self.output_reader.deserialize(part.clone(), chunks)?;
+                    if let Some(virtual_column_reader) = self.virtual_column_reader.as_ref() {
+                        let columns = std::mem::take(&mut self.virtual_columns);
+                        for (column, meta) in
+                            columns.into_iter().zip(virtual_column_reader.columns())
+                        {
+                            block = block.add_column(column, meta.to_data_field())?;
+                        }
+                    }
+                    let block = Self::select_rows(&part, block)?;
                     let progress_values = ProgressValues {
                         rows: block.num_rows(),
                         bytes: block.memory_size(),
@@ -333,10 +581,18 @@ impl Processor for FuseTableSource {
             State::PrewhereFilter(part, chunks) => {
                 // deserialize prewhere data block first
                 let data_block = self.prewhere_reader.deserialize(part.clone(), chunks)?;
-                if let Some(filter) = self.prewhere_filter.as_ref() {
+                let data_block = Self::select_rows(&part, data_block)?;
+                let filter = if let Some(filter) = self.prewhere_filter.as_ref() {
                     // do filter
                     let res = filter.execute(&data_block)?;
-                    let filter = DataBlock::cast_to_nonull_boolean(res.column(0))?;
+                    Some(DataBlock::cast_to_nonull_boolean(res.column(0))?)
+                } else if let Some(top_n_filter) = self.top_n_filter.as_ref() {
+                    let column = data_block.try_column_by_name(top_n_filter.column_name())?;
+                    Some(top_n_filter.filter(column))
+                } else {
+                    None
+                };
+                if let Some(filter) = filter {
                     // shortcut, if predicates is const boolean (or can be cast to boolean)
                     if !DataBlock::filter_exists(&filter)? {
                         // all rows in this block are filtered out
@@ -376,9 +632,23 @@ impl Processor for FuseTableSource {
     async fn async_process(&mut self) -> Result<()> {
         match std::mem::replace(&mut self.state, State::Finish) {
             State::ReadDataPrewhere(part) => {
+                if self.runtime_filter_pruner.should_prune(&part)? {
+                    // the part matches none of the keys of a join above, turn to read next part
+                    return self.generate_one_empty_block();
+                }
+                if let Some(top_n_filter) = self.top_n_filter.as_ref() {
+                    if top_n_filter.should_prune(&part)? {
+                        // all the rows of the part are after the rows read, turn to read next part
+                        return self.generate_one_empty_block();
+                    }
+                }
+
                 let chunks = self.prewhere_reader.read_columns_data(part.clone()).await?;
+                if let Some(virtual_column_reader) = self.virtual_column_reader.as_ref() {
+                    self.virtual_columns = virtual_column_reader.read(part.clone()).await?;
+                }
 
-                if self.prewhere_filter.is_some() {
+                if self.prewhere_filter.is_some() || self.top_n_filter.is_some() {
                     self.state = State::PrewhereFilter(part, chunks);
                 } else {
                     // all needed columns are read.
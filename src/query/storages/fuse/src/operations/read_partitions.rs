@@ -23,13 +23,18 @@ use common_legacy_planners::Extras;
 use common_legacy_planners::PartInfoPtr;
 use common_legacy_planners::Partitions;
 use common_legacy_planners::Projection;
+use common_legacy_planners::ScanSample;
 use common_legacy_planners::Statistics;
+use rand::Rng;
 
 use crate::fuse_part::ColumnLeaves;
 use crate::fuse_part::ColumnMeta;
 use crate::fuse_part::FusePartInfo;
 use crate::pruning::BlockPruner;
+use crate::pruning::InvertedIndexPruner;
+use crate::pruning::VirtualColumnPruner;
 use crate::FuseTable;
+use crate::SchemaHistory;
 
 impl FuseTable {
     #[inline]
@@ -46,18 +51,145 @@ impl FuseTable {
                 }
 
                 let block_metas = BlockPruner::new(snapshot.clone())
+                    .with_bloom_index_columns(self.bloom_index_columns()?)
                     .prune(&ctx, self.table_info.schema(), &push_downs)
                     .await?
                     .into_iter()
                     .map(|(_, v)| v)
                     .collect::<Vec<_>>();
+                let (block_metas, row_selections) = self
+                    .prune_by_inverted_indexes(&ctx, &push_downs, block_metas)
+                    .await?;
+                let (mut block_metas, virtual_row_selections) = self
+                    .prune_by_virtual_columns(&ctx, &push_downs, block_metas)
+                    .await?;
+                let row_selections =
+                    Self::intersect_row_selections(row_selections, virtual_row_selections);
+                if let Some(Extras {
+                    sample: Some(ScanSample::Block(probability)),
+                    ..
+                }) = &push_downs
+                {
+                    block_metas = Self::sample_blocks(block_metas, *probability);
+                }
                 let partitions_total = snapshot.summary.block_count as usize;
-                self.read_partitions_with_metas(ctx, push_downs, block_metas, partitions_total)
+                let (statistics, parts) = self.read_partitions_with_metas(
+                    ctx,
+                    push_downs,
+                    block_metas,
+                    partitions_total,
+                )?;
+                Ok((statistics, Self::select_rows(parts, &row_selections)?))
             }
             None => Ok((Statistics::default(), vec![])),
         }
     }
 
+    /// Prunes the blocks by the inverted indexes, returns the blocks kept and the rows selected
+    /// of them, keyed by the block locations.
+    async fn prune_by_inverted_indexes(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        push_downs: &Option<Extras>,
+        block_metas: Vec<BlockMeta>,
+    ) -> Result<(Vec<BlockMeta>, HashMap<String, Vec<u32>>)> {
+        let filters = match push_downs {
+            Some(extras) if !extras.filters.is_empty() => &extras.filters,
+            _ => return Ok((block_metas, HashMap::new())),
+        };
+        let pruner = InvertedIndexPruner::try_create(
+            ctx.get_storage_operator()?,
+            self.meta_location_generator.clone(),
+            self.schema_version()?,
+            &self.inverted_indexes()?,
+            filters,
+        )?;
+        match pruner {
+            Some(pruner) => pruner.prune(block_metas).await,
+            None => Ok((block_metas, HashMap::new())),
+        }
+    }
+
+    /// Prunes the blocks by the materialized virtual columns, returns the blocks kept and the
+    /// rows selected of them, keyed by the block locations.
+    async fn prune_by_virtual_columns(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        push_downs: &Option<Extras>,
+        block_metas: Vec<BlockMeta>,
+    ) -> Result<(Vec<BlockMeta>, HashMap<String, Vec<u32>>)> {
+        let filters = match push_downs {
+            Some(extras) if !extras.filters.is_empty() => &extras.filters,
+            _ => return Ok((block_metas, HashMap::new())),
+        };
+        let virtual_columns = match self.virtual_columns()? {
+            Some(virtual_columns) => virtual_columns,
+            None => return Ok((block_metas, HashMap::new())),
+        };
+        let pruner = VirtualColumnPruner::try_create(
+            ctx.clone(),
+            ctx.get_storage_operator()?,
+            self.meta_location_generator.clone(),
+            &virtual_columns,
+            filters,
+        )?;
+        match pruner {
+            Some(pruner) => pruner.prune(block_metas).await,
+            None => Ok((block_metas, HashMap::new())),
+        }
+    }
+
+    /// Merges the rows selected by the pruners, a block absent from a selection reads all rows.
+    fn intersect_row_selections(
+        mut left: HashMap<String, Vec<u32>>,
+        right: HashMap<String, Vec<u32>>,
+    ) -> HashMap<String, Vec<u32>> {
+        for (location, rows) in right {
+            match left.get_mut(&location) {
+                Some(selected) => {
+                    // both of the selections are sorted
+                    let mut others = rows.iter().peekable();
+                    selected.retain(|row| {
+                        while others.next_if(|other| *other < row).is_some() {}
+                        others.peek() == Some(&row)
+                    });
+                }
+                None => {
+                    left.insert(location, rows);
+                }
+            }
+        }
+        left
+    }
+
+    fn select_rows(
+        parts: Partitions,
+        row_selections: &HashMap<String, Vec<u32>>,
+    ) -> Result<Partitions> {
+        if row_selections.is_empty() {
+            return Ok(parts);
+        }
+        parts
+            .into_iter()
+            .map(|part| {
+                let fuse_part = FusePartInfo::from_part(&part)?;
+                Ok(match row_selections.get(&fuse_part.location) {
+                    Some(rows) => fuse_part.with_row_selection(rows.clone()),
+                    None => part,
+                })
+            })
+            .collect()
+    }
+
+    /// Keep each block with the probability, for `TABLESAMPLE SYSTEM`.
+    fn sample_blocks(block_metas: Vec<BlockMeta>, probability: f64) -> Vec<BlockMeta> {
+        let mut rng = rand::thread_rng();
+        block_metas
+            .into_iter()
+            .filter(|_| rng.gen_bool(probability))
+            .collect()
+    }
+
     pub fn read_partitions_with_metas(
         &self,
         ctx: Arc<dyn TableContext>,
@@ -69,10 +201,12 @@ impl FuseTable {
 
         let arrow_schema = schema.to_arrow();
         let column_leaves = ColumnLeaves::new_from_schema(&arrow_schema);
+        let schema_version = SchemaHistory::version_of(&schema)?;
 
         let partitions_scanned = block_metas.len();
 
-        let (mut statistics, parts) = Self::to_partitions(&block_metas, &column_leaves, push_downs);
+        let (mut statistics, parts) =
+            Self::to_partitions(&block_metas, &column_leaves, schema_version, push_downs);
 
         // Update planner statistics.
         statistics.partitions_total = partitions_total;
@@ -92,6 +226,7 @@ impl FuseTable {
     pub fn to_partitions(
         blocks_metas: &[BlockMeta],
         column_leaves: &ColumnLeaves,
+        schema_version: u64,
         push_down: Option<Extras>,
     ) -> (Statistics, Partitions) {
         let limit = push_down
@@ -104,9 +239,13 @@ impl FuseTable {
             None => Self::all_columns_partitions(blocks_metas, limit),
             Some(extras) => match &extras.projection {
                 None => Self::all_columns_partitions(blocks_metas, limit),
-                Some(projection) => {
-                    Self::projection_partitions(blocks_metas, column_leaves, projection, limit)
-                }
+                Some(projection) => Self::projection_partitions(
+                    blocks_metas,
+                    column_leaves,
+                    schema_version,
+                    projection,
+                    limit,
+                ),
             },
         };
 
@@ -117,7 +256,7 @@ impl FuseTable {
     fn is_exact(push_downs: &Option<Extras>) -> bool {
         match push_downs {
             None => true,
-            Some(extra) => extra.filters.is_empty(),
+            Some(extra) => extra.filters.is_empty() && extra.sample.is_none(),
         }
     }
 
@@ -154,6 +293,7 @@ impl FuseTable {
     fn projection_partitions(
         metas: &[BlockMeta],
         column_leaves: &ColumnLeaves,
+        schema_version: u64,
         projection: &Projection,
         limit: usize,
     ) -> (Statistics, Partitions) {
@@ -167,16 +307,23 @@ impl FuseTable {
         let mut remaining = limit;
 
         for block_meta in metas {
-            partitions.push(Self::projection_part(block_meta, column_leaves, projection));
             let rows = block_meta.row_count as usize;
-
             statistics.read_rows += rows;
-            let columns = column_leaves.get_by_projection(projection).unwrap();
-            for column in &columns {
-                let indices = &column.leaf_ids;
-                for index in indices {
-                    let col_metas = &block_meta.col_metas[&(*index as u32)];
-                    statistics.read_bytes += col_metas.len as usize;
+
+            if block_meta.schema_version != schema_version {
+                // the leaves of the block are of the schema of its own version, the projection is
+                // resolved by the block reader
+                partitions.push(Self::all_columns_part(block_meta));
+                statistics.read_bytes += block_meta.block_size as usize;
+            } else {
+                partitions.push(Self::projection_part(block_meta, column_leaves, projection));
+                let columns = column_leaves.get_by_projection(projection).unwrap();
+                for column in &columns {
+                    let indices = &column.leaf_ids;
+                    for index in indices {
+                        let col_metas = &block_meta.col_metas[&(*index as u32)];
+                        statistics.read_bytes += col_metas.len as usize;
+                    }
                 }
             }
 
@@ -211,7 +358,9 @@ impl FuseTable {
             format_version,
             rows_count,
             columns_meta,
+            meta.col_stats.clone(),
             meta.compression(),
+            meta.schema_version,
         )
     }
 
@@ -221,6 +370,7 @@ impl FuseTable {
         projection: &Projection,
     ) -> PartInfoPtr {
         let mut columns_meta = HashMap::with_capacity(projection.len());
+        let mut columns_stat = HashMap::with_capacity(projection.len());
 
         let columns = column_leaves.get_by_projection(projection).unwrap();
         for column in &columns {
@@ -232,6 +382,9 @@ impl FuseTable {
                     *index,
                     ColumnMeta::create(column_meta.offset, column_meta.len, column_meta.num_values),
                 );
+                if let Some(column_stat) = meta.col_stats.get(&(*index as u32)) {
+                    columns_stat.insert(*index as u32, column_stat.clone());
+                }
             }
         }
 
@@ -246,7 +399,9 @@ impl FuseTable {
             format_version,
             rows_count,
             columns_meta,
+            columns_stat,
             meta.compression(),
+            meta.schema_version,
         )
     }
 
@@ -259,8 +414,10 @@ impl FuseTable {
             Extras {
                 projection: Some(projs),
                 filters,
+                sample: None,
+                virtual_columns,
                 ..
-            } if projs.is_empty() && filters.is_empty() => {
+            } if projs.is_empty() && filters.is_empty() && virtual_columns.is_empty() => {
                 let summary = &snapshot.summary;
                 let stats = Statistics {
                     read_rows: summary.row_count as usize,
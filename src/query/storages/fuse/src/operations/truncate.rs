@@ -14,8 +14,10 @@
 
 use std::sync::Arc;
 
+use common_cache::Cache;
 use common_catalog::table_context::TableContext;
 use common_exception::Result;
+use common_fuse_meta::caches::CacheManager;
 use common_fuse_meta::meta::TableSnapshot;
 use common_fuse_meta::meta::Versioned;
 use common_meta_app::schema::TableStatistics;
@@ -24,12 +26,22 @@ use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::MatchSeq;
 use uuid::Uuid;
 
+use crate::io::write_meta;
 use crate::FuseTable;
 use crate::OPT_KEY_SNAPSHOT_LOCATION;
 
 impl FuseTable {
     #[inline]
     pub async fn do_truncate(&self, ctx: Arc<dyn TableContext>, purge: bool) -> Result<()> {
+        ctx.get_txn_manager()
+            .lock()
+            .check_autocommit("TRUNCATE TABLE")?;
+        Self::check_data_owner(
+            ctx.as_ref(),
+            &self.table_info,
+            &self.meta_location_generator,
+        )
+        .await?;
         if let Some(prev_snapshot) = self.read_table_snapshot(ctx.clone()).await? {
             let prev_id = prev_snapshot.snapshot_id;
 
@@ -46,19 +58,19 @@ impl FuseTable {
             let new_snapshot_loc =
                 loc.snapshot_location_from_uuid(&new_snapshot.snapshot_id, TableSnapshot::VERSION)?;
             let operator = ctx.get_storage_operator()?;
-            let bytes = serde_json::to_vec(&new_snapshot)?;
-            operator.object(&new_snapshot_loc).write(bytes).await?;
+            write_meta(&operator, &new_snapshot_loc, &new_snapshot).await?;
 
             if purge {
                 let keep_last_snapshot = false;
-                self.do_gc(&ctx, keep_last_snapshot).await?
+                self.do_gc(&ctx, keep_last_snapshot, None).await?
             }
 
             let mut new_table_meta = self.table_info.meta.clone();
             // update snapshot location
-            new_table_meta
-                .options
-                .insert(OPT_KEY_SNAPSHOT_LOCATION.to_owned(), new_snapshot_loc);
+            new_table_meta.options.insert(
+                OPT_KEY_SNAPSHOT_LOCATION.to_owned(),
+                new_snapshot_loc.clone(),
+            );
 
             // update table statistics, all zeros
             new_table_meta.statistics = TableStatistics::default();
@@ -78,6 +90,13 @@ impl FuseTable {
             catalog
                 .truncate_table(TruncateTableReq { table_id })
                 .await?;
+
+            // snapshots are immutable, only the snapshot location of the table is changed by the
+            // commit, keep the new snapshot cached so that the following reads don't load it again
+            if let Some(snapshot_cache) = CacheManager::instance().get_table_snapshot_cache() {
+                let cache = &mut snapshot_cache.write().await;
+                cache.put(new_snapshot_loc, Arc::new(new_snapshot));
+            }
         }
 
         Ok(())
@@ -0,0 +1,73 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use tracing::info;
+
+use crate::index::BlockVirtualColumns;
+use crate::index::VirtualColumnsMeta;
+use crate::io::write_data;
+use crate::io::MetaReaders;
+use crate::FuseTable;
+
+impl FuseTable {
+    /// Materializes the virtual columns for the existing blocks.
+    ///
+    /// The files of the virtual columns are written for the blocks lacking them, e.g. the blocks
+    /// written before the virtual columns are created. They are located by the locations of the
+    /// blocks, so no new snapshot is committed. Until the file of a block is written, the values
+    /// of the block are extracted from the source columns when read.
+    pub async fn do_build_virtual_columns(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        virtual_columns: &VirtualColumnsMeta,
+    ) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.clone()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(()),
+        };
+
+        let projection = self.virtual_column_sources(virtual_columns)?;
+        let reader = self.create_block_reader(&ctx, projection)?;
+        let dal = ctx.get_storage_operator()?;
+
+        let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut num_blocks = 0;
+        for (location, version) in &snapshot.segments {
+            let segment = segment_reader.read(location, None, *version).await?;
+            for block_meta in &segment.blocks {
+                let location = self
+                    .meta_location_generator
+                    .virtual_column_location(&virtual_columns.id, &block_meta.location.0);
+                if dal.object(&location).is_exist().await? {
+                    continue;
+                }
+
+                let block = reader.read_with_block_meta(block_meta).await?;
+                let data = BlockVirtualColumns::build(virtual_columns, &block)?.to_vec()?;
+                write_data(&data, &dal, &location).await?;
+                num_blocks += 1;
+            }
+        }
+
+        info!(
+            "build virtual columns {} for {} blocks of table {}",
+            virtual_columns.id, num_blocks, self.table_info.desc
+        );
+        Ok(())
+    }
+}
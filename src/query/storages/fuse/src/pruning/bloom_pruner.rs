@@ -107,20 +107,25 @@ impl BloomFilterPruner for BloomFilterIndexPruner {
 /// if `filter_expr` is none, or is not applicable, e.g. have no point queries
 /// a [NonPruner] will be return, which prunes nothing.
 /// otherwise, a [BloomFilterIndexer] backed pruner will be return
+///
+/// `index_columns` are the columns of the bloom indexes created by `CREATE INDEX`, only the
+/// point queries of them are considered, `None` if the table has no bloom indexes.
 pub fn new_bloom_filter_pruner(
     ctx: &Arc<dyn TableContext>,
     filter_exprs: Option<&[LegacyExpression]>,
     schema: &DataSchemaRef,
+    index_columns: Option<&[String]>,
     dal: Operator,
 ) -> Result<Arc<dyn BloomFilterPruner + Send + Sync>> {
     // due to issue
     // https://github.com/datafuselabs/databend/issues/7780
-    // bloom filter is disabled, just return a NonPruner unconditionally
-    if true {
-        return Ok(Arc::new(NonPruner));
-    }
+    // bloom filters built implicitly for all the columns are not used, only the explicitly
+    // created bloom indexes are.
+    let index_columns = match index_columns {
+        Some(index_columns) => index_columns,
+        None => return Ok(Arc::new(NonPruner)),
+    };
 
-    // the following codes is unreachable, but kept to help diagnostic performance issues
     if let Some(exprs) = filter_exprs {
         if exprs.is_empty() {
             return Ok(Arc::new(NonPruner));
@@ -134,7 +139,8 @@ pub fn new_bloom_filter_pruner(
             })
             .unwrap();
 
-        let point_query_cols = columns_names_of_eq_expressions(&expr)?;
+        let mut point_query_cols = columns_names_of_eq_expressions(&expr)?;
+        point_query_cols.retain(|col| index_columns.contains(col));
         if !point_query_cols.is_empty() {
             // convert to bloom filter block's column names
             let filter_block_cols = point_query_cols
@@ -0,0 +1,230 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_functions::scalars::TextQuery;
+use common_functions::scalars::Tokenizer;
+use common_fuse_meta::meta::BlockMeta;
+use common_legacy_expression::LegacyExpression;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use opendal::Operator;
+
+use crate::index::InvertedIndexMeta;
+use crate::io::read_inverted_index;
+use crate::io::TableMetaLocationGenerator;
+
+const FUTURE_BUFFER_SIZE: usize = 10;
+
+/// A `MATCH` or `QUERY` filter of a column covered by an inverted index.
+struct IndexSearch {
+    index_id: String,
+    column: String,
+    query: TextQuery,
+}
+
+/// Prunes the blocks, and selects the rows of the blocks kept, by the inverted indexes.
+///
+/// Only the `MATCH(<column>, '<text>')` and `QUERY(<column>, '<query>')` filters which are
+/// conjuncts of the filters of the scan are searched, the tokenizer of the filter must be the
+/// tokenizer of the index. The filters are still evaluated on the rows selected.
+pub struct InvertedIndexPruner {
+    dal: Operator,
+    location_generator: TableMetaLocationGenerator,
+    schema_version: u64,
+    searches: Vec<IndexSearch>,
+}
+
+impl InvertedIndexPruner {
+    /// Returns `None` if none of the filters can be searched with the indexes.
+    pub fn try_create(
+        dal: Operator,
+        location_generator: TableMetaLocationGenerator,
+        schema_version: u64,
+        indexes: &[InvertedIndexMeta],
+        filters: &[LegacyExpression],
+    ) -> Result<Option<InvertedIndexPruner>> {
+        if indexes.is_empty() {
+            return Ok(None);
+        }
+
+        let mut conjuncts = vec![];
+        for filter in filters {
+            split_conjuncts(filter, &mut conjuncts);
+        }
+        let mut searches = vec![];
+        for conjunct in conjuncts {
+            if let Some(search) = Self::index_search(indexes, conjunct)? {
+                searches.push(search);
+            }
+        }
+
+        if searches.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(InvertedIndexPruner {
+            dal,
+            location_generator,
+            schema_version,
+            searches,
+        }))
+    }
+
+    fn index_search(
+        indexes: &[InvertedIndexMeta],
+        expr: &LegacyExpression,
+    ) -> Result<Option<IndexSearch>> {
+        let (op, args) = match expr {
+            LegacyExpression::ScalarFunction { op, args } => (op, args),
+            _ => return Ok(None),
+        };
+        let is_query = op.eq_ignore_ascii_case("query");
+        if !is_query && !op.eq_ignore_ascii_case("match") {
+            return Ok(None);
+        }
+
+        let string_literal = |arg: Option<&LegacyExpression>| match arg {
+            Some(LegacyExpression::Literal {
+                value: DataValue::String(value),
+                ..
+            }) => Some(String::from_utf8_lossy(value).into_owned()),
+            _ => None,
+        };
+        let (column, text) = match (args.get(0), string_literal(args.get(1))) {
+            (Some(LegacyExpression::Column(column)), Some(text)) => (column, text),
+            _ => return Ok(None),
+        };
+        let tokenizer = match args.get(2) {
+            None => Tokenizer::default(),
+            arg => match string_literal(arg).map(|name| Tokenizer::from_name(&name)) {
+                Some(Ok(tokenizer)) => tokenizer,
+                _ => return Ok(None),
+            },
+        };
+
+        for index in indexes {
+            if !index.columns.contains(column) || index.tokenizer()? != tokenizer {
+                continue;
+            }
+            let query = match is_query {
+                // an invalid query fails the evaluation of the filter
+                true => match TextQuery::parse(&text, tokenizer) {
+                    Ok(query) => query,
+                    Err(_) => return Ok(None),
+                },
+                false => TextQuery::all_terms(&text, tokenizer),
+            };
+            return Ok(Some(IndexSearch {
+                index_id: index.id.clone(),
+                column: column.clone(),
+                query,
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Returns the blocks which may have rows matching the filters, and the rows selected of
+    /// them, keyed by the block locations. The blocks without selected rows read all the rows.
+    pub async fn prune(
+        &self,
+        block_metas: Vec<BlockMeta>,
+    ) -> Result<(Vec<BlockMeta>, HashMap<String, Vec<u32>>)> {
+        let num_blocks = block_metas.len();
+        let selections = futures::stream::iter(block_metas.iter())
+            .map(|block_meta| self.select_rows(block_meta))
+            .buffered(std::cmp::max(
+                1,
+                std::cmp::min(FUTURE_BUFFER_SIZE, num_blocks),
+            ))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut kept = Vec::with_capacity(num_blocks);
+        let mut row_selections = HashMap::new();
+        for (block_meta, selection) in block_metas.into_iter().zip(selections) {
+            match selection {
+                None => kept.push(block_meta),
+                Some(rows) if rows.is_empty() => {}
+                Some(rows) => {
+                    if rows.len() < block_meta.row_count as usize {
+                        row_selections.insert(block_meta.location.0.clone(), rows);
+                    }
+                    kept.push(block_meta);
+                }
+            }
+        }
+        Ok((kept, row_selections))
+    }
+
+    /// The sorted rows of the block which may match the filters, `None` if all the rows may
+    /// match, e.g. the index files of the block are missing.
+    async fn select_rows(&self, block_meta: &BlockMeta) -> Result<Option<Vec<u32>>> {
+        if block_meta.schema_version != self.schema_version {
+            return Ok(None);
+        }
+
+        let mut selection: Option<Vec<u32>> = None;
+        let mut index_files = HashMap::new();
+        for search in &self.searches {
+            if !index_files.contains_key(&search.index_id) {
+                let location = self
+                    .location_generator
+                    .inverted_index_location(&search.index_id, &block_meta.location.0);
+                let index = match read_inverted_index(&self.dal, &location).await {
+                    Ok(index) => index,
+                    Err(e) => {
+                        // a corrupted index should not prevent the execution
+                        tracing::warn!("failed to read inverted index {}. {}", location, e);
+                        None
+                    }
+                };
+                index_files.insert(search.index_id.clone(), index);
+            }
+
+            let rows = match &index_files[&search.index_id] {
+                Some(index) => index.search(&search.column, &search.query),
+                None => None,
+            };
+            if let Some(rows) = rows {
+                selection = Some(match selection {
+                    None => rows,
+                    Some(selection) => selection
+                        .into_iter()
+                        .filter(|row| rows.binary_search(row).is_ok())
+                        .collect(),
+                });
+            }
+        }
+        Ok(selection)
+    }
+}
+
+/// Splits the expression into the conjuncts of it.
+pub(crate) fn split_conjuncts<'a>(
+    expr: &'a LegacyExpression,
+    conjuncts: &mut Vec<&'a LegacyExpression>,
+) {
+    match expr {
+        LegacyExpression::BinaryExpression { left, op, right }
+            if op.eq_ignore_ascii_case("and") =>
+        {
+            split_conjuncts(left, conjuncts);
+            split_conjuncts(right, conjuncts);
+        }
+        _ => conjuncts.push(expr),
+    }
+}
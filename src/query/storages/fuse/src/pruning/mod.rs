@@ -13,9 +13,17 @@
 //  limitations under the License.
 
 mod bloom_pruner;
+mod inverted_index_pruner;
 mod limiter;
 mod pruning_executor;
 mod range_pruner;
+mod runtime_filter_pruner;
+mod topn_filter;
 mod topn_pruner;
+mod virtual_column_pruner;
 
+pub use inverted_index_pruner::InvertedIndexPruner;
 pub use pruning_executor::BlockPruner;
+pub use runtime_filter_pruner::RuntimeFilterPruner;
+pub use topn_filter::TopNFilter;
+pub use virtual_column_pruner::VirtualColumnPruner;
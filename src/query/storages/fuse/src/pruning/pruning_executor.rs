@@ -34,16 +34,27 @@ use crate::io::MetaReaders;
 use crate::pruning::limiter;
 use crate::pruning::range_pruner;
 use crate::pruning::topn_pruner;
+use crate::SchemaHistory;
 
 pub struct BlockPruner {
     table_snapshot: Arc<TableSnapshot>,
+    bloom_index_columns: Option<Vec<String>>,
 }
 
 const FUTURE_BUFFER_SIZE: usize = 10;
 
 impl BlockPruner {
     pub fn new(table_snapshot: Arc<TableSnapshot>) -> Self {
-        Self { table_snapshot }
+        Self {
+            table_snapshot,
+            bloom_index_columns: None,
+        }
+    }
+
+    /// Prunes the blocks by the bloom filters of the columns of the bloom indexes, if any.
+    pub fn with_bloom_index_columns(mut self, columns: Option<Vec<String>>) -> Self {
+        self.bloom_index_columns = columns;
+        self
     }
 
     // prune blocks by utilizing min_max index and bloom filter, according to the pushdowns
@@ -85,8 +96,17 @@ impl BlockPruner {
 
         // prepare the bloom filter, if filter_expression is none, an dummy pruner will be returned
         let dal = ctx.get_storage_operator()?;
-        let bloom_filter_pruner =
-            bloom_pruner::new_bloom_filter_pruner(ctx, filter_expressions, &schema, dal)?;
+        let bloom_filter_pruner = bloom_pruner::new_bloom_filter_pruner(
+            ctx,
+            filter_expressions,
+            &schema,
+            self.bloom_index_columns.as_deref(),
+            dal,
+        )?;
+
+        // the statistics and bloom filters of the blocks written with the older schema versions
+        // are of the columns of those versions, such blocks are not pruned by them.
+        let schema_version = SchemaHistory::version_of(&schema)?;
 
         // 2. kick off
         //
@@ -111,6 +131,7 @@ impl BlockPruner {
             let bloom_filter_pruner = bloom_filter_pruner.clone();
             let limiter = limiter.clone();
             let segment_pruning_fut = async move {
+                ctx.check_aborting()?;
                 let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
                 if limiter.exceeded() {
                     // before read segment info, check if limit already exceeded
@@ -118,32 +139,35 @@ impl BlockPruner {
                 }
                 let segment_info = segment_reader.read(seg_loc, None, ver).await?;
                 let mut result = Vec::with_capacity(segment_info.blocks.len());
-                if range_filter_pruner.should_keep(
-                    &segment_info.summary.col_stats,
-                    segment_info.summary.row_count,
-                ) {
+                let evolved = |block_meta: &BlockMeta| block_meta.schema_version != schema_version;
+                if segment_info.blocks.iter().any(evolved)
+                    || range_filter_pruner.should_keep(
+                        &segment_info.summary.col_stats,
+                        segment_info.summary.row_count,
+                    )
+                {
                     for block_meta in &segment_info.blocks {
                         // prune block using range filter
                         if limiter.exceeded() {
                             // before using bloom index to prune, check if limit already exceeded
                             return Ok(result);
                         }
-                        if range_filter_pruner
-                            .should_keep(&block_meta.col_stats, block_meta.row_count)
-                        {
-                            // prune block using bloom filter
-                            if bloom_filter_pruner
-                                .should_keep(
-                                    &block_meta.bloom_filter_index_location,
-                                    block_meta.bloom_filter_index_size,
-                                )
-                                .await
-                            {
-                                if limiter.within_limit(block_meta.row_count) {
-                                    result.push((idx, block_meta.clone()));
-                                } else {
-                                    break;
-                                }
+                        ctx.check_aborting()?;
+                        let keep = evolved(block_meta)
+                            || (range_filter_pruner
+                                .should_keep(&block_meta.col_stats, block_meta.row_count)
+                                // prune block using bloom filter
+                                && bloom_filter_pruner
+                                    .should_keep(
+                                        &block_meta.bloom_filter_index_location,
+                                        block_meta.bloom_filter_index_size,
+                                    )
+                                    .await);
+                        if keep {
+                            if limiter.within_limit(block_meta.row_count) {
+                                result.push((idx, block_meta.clone()));
+                            } else {
+                                break;
                             }
                         }
                     }
@@ -171,12 +195,15 @@ impl BlockPruner {
             });
         let metas = metas?;
 
-        // if there are ordering + limit clause, use topn pruner
-
+        // if there are ordering + limit clause, use topn pruner.
+        // the rows of a block may be filtered out, so the filters disable it.
         if push_down
             .as_ref()
-            .filter(|p| !p.order_by.is_empty() && p.limit.is_some())
+            .filter(|p| !p.order_by.is_empty() && p.limit.is_some() && p.filters.is_empty())
             .is_some()
+            && metas
+                .iter()
+                .all(|(_, block_meta)| block_meta.schema_version == schema_version)
         {
             let push_down = push_down.as_ref().unwrap();
             let limit = push_down.limit.unwrap();
@@ -195,6 +222,7 @@ impl BlockPruner {
         let segment_num = segment_locs.len();
         let block_metas = futures::stream::iter(segment_locs.into_iter().enumerate())
             .map(|(idx, (seg_loc, ver))| async move {
+                ctx.check_aborting()?;
                 let segment_reader = MetaReaders::segment_info_reader(ctx);
                 let segment_info = segment_reader.read(seg_loc, None, ver).await?;
                 Ok::<_, ErrorCode>(
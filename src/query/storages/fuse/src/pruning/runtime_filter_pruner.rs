@@ -0,0 +1,100 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_fuse_meta::meta::ColumnId;
+use common_legacy_planners::PartInfoPtr;
+
+use crate::fuse_part::FusePartInfo;
+use crate::ColumnLeaves;
+use crate::SchemaHistory;
+
+/// Prunes the blocks and the rows read by a scan with the runtime filters of the hash joins
+/// above it, which are attached to the context once their build sides are finished.
+pub struct RuntimeFilterPruner {
+    ctx: Arc<dyn TableContext>,
+    /// Leaf column ids of the columns of the table which are not nested
+    column_ids: HashMap<String, ColumnId>,
+    /// The statistics of the parts written with other schema versions are keyed by other ids
+    schema_version: u64,
+}
+
+impl RuntimeFilterPruner {
+    pub fn create(
+        ctx: Arc<dyn TableContext>,
+        schema: &DataSchemaRef,
+    ) -> Result<Arc<RuntimeFilterPruner>> {
+        let column_leaves = ColumnLeaves::new_from_schema(&schema.to_arrow());
+        let column_ids = column_leaves
+            .column_leaves
+            .iter()
+            .filter(|leaf| leaf.children.is_none())
+            .map(|leaf| (leaf.field.name.clone(), leaf.leaf_ids[0] as ColumnId))
+            .collect();
+        let schema_version = SchemaHistory::version_of(schema)?;
+
+        Ok(Arc::new(RuntimeFilterPruner {
+            ctx,
+            column_ids,
+            schema_version,
+        }))
+    }
+
+    /// Whether the part matches none of the keys of a runtime filter, by the range of its column.
+    pub fn should_prune(&self, part: &PartInfoPtr) -> Result<bool> {
+        let filters = self.ctx.get_runtime_filters();
+        if filters.is_empty() {
+            return Ok(false);
+        }
+
+        let part = FusePartInfo::from_part(part)?;
+        if part.schema_version != self.schema_version {
+            return Ok(false);
+        }
+        Ok(filters.iter().any(|filter| {
+            let stat = self
+                .column_ids
+                .get(filter.column_name())
+                .and_then(|column_id| part.columns_stat.get(column_id));
+            match stat {
+                Some(stat) => !filter.may_match_range(&stat.min, &stat.max),
+                None => false,
+            }
+        }))
+    }
+
+    /// Drop the rows of the block matching none of the keys of a runtime filter.
+    pub fn filter_block(&self, mut block: DataBlock) -> Result<DataBlock> {
+        for filter in self.ctx.get_runtime_filters() {
+            if block.num_rows() == 0 {
+                break;
+            }
+
+            let predicate = match block.try_column_by_name(filter.column_name()) {
+                Ok(column) => filter.filter(column),
+                Err(_) => None,
+            };
+            if let Some(predicate) = predicate {
+                block = DataBlock::filter_block(block, &predicate)?;
+            }
+        }
+        Ok(block)
+    }
+}
@@ -0,0 +1,180 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::sync::Arc;
+
+use common_datavalues::BooleanColumn;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_fuse_meta::meta::ColumnId;
+use common_legacy_planners::Extras;
+use common_legacy_planners::PartInfoPtr;
+use common_legacy_planners::ScanSample;
+use common_storages_index::MinMaxIndex;
+use common_storages_index::SupportedType;
+use parking_lot::Mutex;
+
+use crate::fuse_part::FusePartInfo;
+use crate::pruning::topn_pruner::TopNOrder;
+use crate::ColumnLeaves;
+use crate::SchemaHistory;
+
+/// Drops the parts and the rows read by a scan of `ORDER BY <column> LIMIT <n>` which can not be
+/// one of the first `n` rows, by the first `n` values of the column read so far by all the
+/// sources of the scan.
+pub struct TopNFilter {
+    column_name: String,
+    /// Leaf column id of the sorted column, `None` if it is nested
+    column_id: Option<ColumnId>,
+    order: TopNOrder,
+    limit: usize,
+    /// The statistics of the parts written with other schema versions are keyed by other ids
+    schema_version: u64,
+    /// The first `limit` values read so far, the last of them in the order is on the top.
+    heap: Mutex<BinaryHeap<TopNValue>>,
+}
+
+struct TopNValue {
+    value: DataValue,
+    order: TopNOrder,
+}
+
+impl Ord for TopNValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.order.compare(&self.value, &other.value)
+    }
+}
+
+impl PartialOrd for TopNValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for TopNValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TopNValue {}
+
+impl TopNFilter {
+    /// The filter of the scan if the push downs are a limited sort on a single column, the
+    /// rows can not be dropped before the filters or the sample of rows are applied.
+    pub fn try_create(
+        schema: &DataSchemaRef,
+        push_downs: &Option<Extras>,
+    ) -> Result<Option<Arc<TopNFilter>>> {
+        let extras = match push_downs {
+            Some(extras)
+                if extras.filters.is_empty()
+                    && extras.prewhere.is_none()
+                    && !matches!(extras.sample, Some(ScanSample::Row(_))) =>
+            {
+                extras
+            }
+            _ => return Ok(None),
+        };
+        let limit = match extras.limit {
+            Some(limit) if limit > 0 => limit,
+            _ => return Ok(None),
+        };
+        let (column_name, order) = match TopNOrder::from_sort(&extras.order_by) {
+            Some(sort) => sort,
+            None => return Ok(None),
+        };
+        match schema.field_with_name(&column_name) {
+            Ok(field) if MinMaxIndex::is_supported_type(field.data_type()) => {}
+            _ => return Ok(None),
+        }
+
+        let column_leaves = ColumnLeaves::new_from_schema(&schema.to_arrow());
+        let column_id = column_leaves
+            .column_leaves
+            .iter()
+            .find(|leaf| leaf.children.is_none() && leaf.field.name == column_name)
+            .map(|leaf| leaf.leaf_ids[0] as ColumnId);
+        let schema_version = SchemaHistory::version_of(schema)?;
+
+        Ok(Some(Arc::new(TopNFilter {
+            column_name,
+            column_id,
+            order,
+            limit,
+            schema_version,
+            heap: Mutex::new(BinaryHeap::with_capacity(limit)),
+        })))
+    }
+
+    pub fn column_name(&self) -> &str {
+        &self.column_name
+    }
+
+    /// The last of the first `limit` values, if `limit` values are read.
+    fn threshold(&self) -> Option<DataValue> {
+        let heap = self.heap.lock();
+        match heap.len() < self.limit {
+            true => None,
+            false => heap.peek().map(|top| top.value.clone()),
+        }
+    }
+
+    /// Whether all the rows of the part are after the first `limit` values read so far, by the
+    /// range of the column in the part.
+    pub fn should_prune(&self, part: &PartInfoPtr) -> Result<bool> {
+        let part = FusePartInfo::from_part(part)?;
+        if part.schema_version != self.schema_version {
+            return Ok(false);
+        }
+
+        let stat = self
+            .column_id
+            .and_then(|column_id| part.columns_stat.get(&column_id));
+        match (stat, self.threshold()) {
+            (Some(stat), Some(threshold)) => {
+                let (first, _) = self.order.bounds(stat, part.nums_rows as u64);
+                Ok(self.order.compare(&first, &threshold) != Ordering::Less)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// The filter of the rows whose values are before the first `limit` values read so far,
+    /// which replace the last of them.
+    pub fn filter(&self, column: &ColumnRef) -> ColumnRef {
+        let mut heap = self.heap.lock();
+        let filter = BooleanColumn::from_owned_iterator((0..column.len()).map(|row| {
+            let value = TopNValue {
+                value: column.get(row),
+                order: self.order,
+            };
+            if heap.len() < self.limit {
+                heap.push(value);
+                return true;
+            }
+            let before_last = heap.peek().map_or(false, |last| value < *last);
+            if before_last {
+                heap.pop();
+                heap.push(value);
+            }
+            before_last
+        }));
+        Arc::new(filter)
+    }
+}
@@ -12,12 +12,79 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::cmp::Ordering;
+
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
 use common_fuse_meta::meta::ColumnStatistics;
 use common_legacy_expression::LegacyExpression;
+use common_storages_index::MinMaxIndex;
+use common_storages_index::SupportedType;
+
+/// The order of a single-column sort, a null value is `DataValue::Null`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TopNOrder {
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
+impl TopNOrder {
+    /// The sorted column and the order of a sort on a single column.
+    // TODO: support monadic + multi expression + order by cluster key sort.
+    pub(crate) fn from_sort(sort: &[LegacyExpression]) -> Option<(String, TopNOrder)> {
+        match sort {
+            [
+                LegacyExpression::Sort {
+                    expr,
+                    asc,
+                    nulls_first,
+                    ..
+                },
+            ] => match expr.as_ref() {
+                LegacyExpression::Column(column) => Some((column.clone(), TopNOrder {
+                    asc: *asc,
+                    nulls_first: *nulls_first,
+                })),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// `Ordering::Less` if `a` is sorted before `b`.
+    pub(crate) fn compare(&self, a: &DataValue, b: &DataValue) -> Ordering {
+        match (a.is_null(), b.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) if self.nulls_first => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            (false, true) if self.nulls_first => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) if self.asc => a.cmp(b),
+            (false, false) => b.cmp(a),
+        }
+    }
+
+    /// The first and the last values of the `rows` rows of a block in the order, by the range
+    /// of the column in the block.
+    pub(crate) fn bounds(&self, stat: &ColumnStatistics, rows: u64) -> (DataValue, DataValue) {
+        if stat.null_count >= rows {
+            return (DataValue::Null, DataValue::Null);
+        }
+
+        let (first, last) = match self.asc {
+            true => (stat.min.clone(), stat.max.clone()),
+            false => (stat.max.clone(), stat.min.clone()),
+        };
+        match (stat.null_count > 0, self.nulls_first) {
+            (true, true) => (DataValue::Null, last),
+            (true, false) => (first, DataValue::Null),
+            (false, _) => (first, last),
+        }
+    }
+}
 
 pub(crate) struct TopNPrunner {
     schema: DataSchemaRef,
@@ -36,31 +103,15 @@ impl TopNPrunner {
 }
 
 impl TopNPrunner {
+    /// Keep the blocks which may contain one of the first `limit` rows in the order.
+    ///
+    /// The blocks are ordered by their last values, the last value of the block where the
+    /// number of rows reaches `limit` is the threshold, the blocks whose first values are
+    /// after the threshold are pruned, as there are already `limit` rows before them.
     pub(crate) fn prune(&self, metas: Vec<(usize, BlockMeta)>) -> Result<Vec<(usize, BlockMeta)>> {
-        if self.sort.len() != 1 {
-            return Ok(metas);
-        }
-
-        if self.limit >= metas.len() {
-            return Ok(metas);
-        }
-
-        let (sort, asc, nulls_first) = match &self.sort[0] {
-            LegacyExpression::Sort {
-                expr,
-                asc,
-                nulls_first,
-                ..
-            } => (expr, asc, nulls_first),
-            _ => unreachable!(),
-        };
-
-        // Currently, we only support topn on single-column sort.
-        // TODO: support monadic + multi expression + order by cluster key sort.
-        let column = if let LegacyExpression::Column(c) = sort.as_ref() {
-            c
-        } else {
-            return Ok(metas);
+        let (column, order) = match TopNOrder::from_sort(&self.sort) {
+            Some(v) => v,
+            None => return Ok(metas),
         };
 
         let sort_idx = if let Ok(index) = self.schema.index_of(column.as_str()) {
@@ -69,34 +120,48 @@ impl TopNPrunner {
             return Ok(metas);
         };
 
-        let mut id_stats = metas
+        // Only the columns with min/max statistics can be pruned.
+        let data_type = self.schema.field(sort_idx as usize).data_type();
+        if !MinMaxIndex::is_supported_type(data_type) {
+            return Ok(metas);
+        }
+
+        let bounds = metas
             .iter()
-            .map(|(id, meta)| {
+            .map(|(_, meta)| {
                 let stat = meta.col_stats.get(&sort_idx).ok_or_else(|| {
                     ErrorCode::UnknownException(format!(
                         "Unable to get the colStats by ColumnId: {}",
                         sort_idx
                     ))
                 })?;
-                Ok((*id, stat.clone(), meta.clone()))
+                Ok(order.bounds(stat, meta.row_count))
             })
-            .collect::<Result<Vec<(usize, ColumnStatistics, BlockMeta)>>>()?;
+            .collect::<Result<Vec<(DataValue, DataValue)>>>()?;
 
-        id_stats.sort_by(|a, b| {
-            if a.1.null_count + b.1.null_count != 0 && *nulls_first {
-                return a.1.null_count.cmp(&b.1.null_count).reverse();
-            }
-            // no nulls
-            if *asc {
-                a.1.min.cmp(&b.1.min)
-            } else {
-                a.1.max.cmp(&b.1.max).reverse()
+        let mut by_last = (0..metas.len()).collect::<Vec<_>>();
+        by_last.sort_by(|a, b| order.compare(&bounds[*a].1, &bounds[*b].1));
+
+        let mut rows = 0;
+        let mut threshold = None;
+        for idx in by_last {
+            rows += metas[idx].1.row_count as usize;
+            if rows >= self.limit {
+                threshold = Some(&bounds[idx].1);
+                break;
             }
-        });
-        Ok(id_stats
-            .iter()
-            .map(|s| (s.0, s.2.clone()))
-            .take(self.limit as usize)
+        }
+        let threshold = match threshold {
+            Some(threshold) => threshold,
+            // all the rows are needed
+            None => return Ok(metas),
+        };
+
+        Ok(metas
+            .into_iter()
+            .zip(bounds.iter())
+            .filter(|(_, (first, _))| order.compare(first, threshold) != Ordering::Greater)
+            .map(|(meta, _)| meta)
             .collect())
     }
 }
@@ -0,0 +1,192 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Series;
+use common_exception::Result;
+use common_fuse_meta::meta::BlockMeta;
+use common_legacy_expression::LegacyExpression;
+use common_legacy_expression::RequireColumnsVisitor;
+use common_pipeline_transforms::processors::ExpressionExecutor;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use opendal::Operator;
+
+use super::inverted_index_pruner::split_conjuncts;
+use crate::index::VirtualColumnsMeta;
+use crate::io::read_virtual_columns;
+use crate::io::TableMetaLocationGenerator;
+
+const FUTURE_BUFFER_SIZE: usize = 10;
+
+/// Prunes the blocks, and selects the rows of the blocks kept, by the materialized values of
+/// the virtual columns.
+///
+/// Only the conjuncts of the filters of the scan which reference nothing but the virtual
+/// columns are evaluated, on the values of the virtual columns of each block. The filters are
+/// still evaluated on the rows selected.
+pub struct VirtualColumnPruner {
+    dal: Operator,
+    location_generator: TableMetaLocationGenerator,
+    virtual_columns_id: String,
+    // the virtual columns referenced by the conjuncts
+    schema: DataSchemaRef,
+    executor: ExpressionExecutor,
+}
+
+impl VirtualColumnPruner {
+    /// Returns `None` if none of the filters can be evaluated with the virtual columns.
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        dal: Operator,
+        location_generator: TableMetaLocationGenerator,
+        virtual_columns: &VirtualColumnsMeta,
+        filters: &[LegacyExpression],
+    ) -> Result<Option<VirtualColumnPruner>> {
+        let mut conjuncts = vec![];
+        for filter in filters {
+            split_conjuncts(filter, &mut conjuncts);
+        }
+
+        let mut fields = BTreeMap::new();
+        let mut predicates = vec![];
+        for conjunct in conjuncts {
+            let columns = RequireColumnsVisitor::collect_columns_from_expr(conjunct)?;
+            let conjunct_fields = columns
+                .iter()
+                .map(|name| virtual_columns.column(name).map(|c| c.to_data_field()))
+                .collect::<Option<Vec<_>>>();
+            match conjunct_fields {
+                Some(conjunct_fields) if !conjunct_fields.is_empty() => {
+                    for field in conjunct_fields {
+                        fields.insert(field.name().clone(), field);
+                    }
+                    predicates.push(conjunct.clone());
+                }
+                _ => {}
+            }
+        }
+
+        let filter = match predicates.into_iter().reduce(|acc, item| acc.and(item)) {
+            Some(filter) => filter,
+            None => return Ok(None),
+        };
+        let schema = DataSchemaRefExt::create(fields.into_values().collect());
+        let expr_field = filter.to_data_field(&schema)?;
+        let executor = ExpressionExecutor::try_create(
+            ctx,
+            "filter expression executor (virtual columns)",
+            schema.clone(),
+            DataSchemaRefExt::create(vec![expr_field]),
+            vec![filter],
+            false,
+        )?;
+        Ok(Some(VirtualColumnPruner {
+            dal,
+            location_generator,
+            virtual_columns_id: virtual_columns.id.clone(),
+            schema,
+            executor,
+        }))
+    }
+
+    /// Returns the blocks which may have rows matching the filters, and the rows selected of
+    /// them, keyed by the block locations. The blocks without selected rows read all the rows.
+    pub async fn prune(
+        &self,
+        block_metas: Vec<BlockMeta>,
+    ) -> Result<(Vec<BlockMeta>, HashMap<String, Vec<u32>>)> {
+        let num_blocks = block_metas.len();
+        let selections = futures::stream::iter(block_metas.iter())
+            .map(|block_meta| self.select_rows(block_meta))
+            .buffered(std::cmp::max(
+                1,
+                std::cmp::min(FUTURE_BUFFER_SIZE, num_blocks),
+            ))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut kept = Vec::with_capacity(num_blocks);
+        let mut row_selections = HashMap::new();
+        for (block_meta, selection) in block_metas.into_iter().zip(selections) {
+            match selection {
+                None => kept.push(block_meta),
+                Some(rows) if rows.is_empty() => {}
+                Some(rows) => {
+                    if rows.len() < block_meta.row_count as usize {
+                        row_selections.insert(block_meta.location.0.clone(), rows);
+                    }
+                    kept.push(block_meta);
+                }
+            }
+        }
+        Ok((kept, row_selections))
+    }
+
+    /// The sorted rows of the block which may match the filters, `None` if all the rows may
+    /// match, e.g. the virtual columns of the block are not materialized yet.
+    async fn select_rows(&self, block_meta: &BlockMeta) -> Result<Option<Vec<u32>>> {
+        let location = self
+            .location_generator
+            .virtual_column_location(&self.virtual_columns_id, &block_meta.location.0);
+        let block = match read_virtual_columns(&self.dal, &location).await {
+            Ok(Some(virtual_columns)) => {
+                let mut columns = Vec::with_capacity(self.schema.num_fields());
+                for field in self.schema.fields() {
+                    match virtual_columns.column(field.name())? {
+                        Some(column) => columns.push(column),
+                        None => return Ok(None),
+                    }
+                }
+                DataBlock::create(self.schema.clone(), columns)
+            }
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                // a corrupted file should not prevent the execution
+                tracing::warn!("failed to read virtual columns {}. {}", location, e);
+                return Ok(None);
+            }
+        };
+
+        let filter = match self.executor.execute(&block) {
+            Ok(result) => DataBlock::cast_to_nonull_boolean(result.column(0))?,
+            Err(e) => {
+                // the error is raised by the evaluation of the filters of the scan, if any
+                tracing::warn!("failed to filter virtual columns {}. {}", location, e);
+                return Ok(None);
+            }
+        };
+        if let Some(const_bool) = DataBlock::try_as_const_bool(&filter)? {
+            return Ok(if const_bool { None } else { Some(vec![]) });
+        }
+        let filter: &BooleanColumn = Series::check_get(&filter)?;
+        Ok(Some(
+            filter
+                .values()
+                .iter()
+                .enumerate()
+                .filter(|(_, selected)| *selected)
+                .map(|(row, _)| row as u32)
+                .collect(),
+        ))
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_catalog::table::AlterColumnAction;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_datavalues::TypeID;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::ColumnId;
+
+use crate::fuse_part::ColumnLeaves;
+
+/// Key of the schema history in the metadata of the schema of a fuse table.
+pub const FUSE_SCHEMA_HISTORY_KEY: &str = "fuse.schema_history";
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct VersionedColumn {
+    /// Id of the column, it is never changed or reused once assigned.
+    pub id: u32,
+    pub field: DataField,
+}
+
+/// The versions of the schema of a fuse table.
+///
+/// Every change of the columns of a table creates a new version of its schema, and the blocks
+/// record the version they are written with. The blocks written with an older version are
+/// read through the schema of their version, the columns are matched by id, so that the table
+/// does not need to be rewritten after its columns are changed.
+///
+/// A table whose columns are never changed has no history, its schema is of version 0.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct SchemaHistory {
+    next_column_id: u32,
+    versions: BTreeMap<u64, Vec<VersionedColumn>>,
+    /// Values of the added columns in the blocks written before they are added, keyed by id.
+    defaults: BTreeMap<u32, DataValue>,
+}
+
+impl SchemaHistory {
+    pub fn from_schema(schema: &DataSchema) -> Result<SchemaHistory> {
+        if let Some(history) = schema.meta().get(FUSE_SCHEMA_HISTORY_KEY) {
+            return Ok(serde_json::from_str(history)?);
+        }
+
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(id, field)| VersionedColumn {
+                id: id as u32,
+                field: field.clone(),
+            })
+            .collect::<Vec<_>>();
+        Ok(SchemaHistory {
+            next_column_id: columns.len() as u32,
+            versions: BTreeMap::from([(0, columns)]),
+            defaults: BTreeMap::new(),
+        })
+    }
+
+    /// Version of the schema of a fuse table.
+    pub fn version_of(schema: &DataSchema) -> Result<u64> {
+        Ok(Self::from_schema(schema)?.version())
+    }
+
+    pub fn version(&self) -> u64 {
+        self.versions
+            .keys()
+            .next_back()
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn versions(&self) -> impl Iterator<Item = u64> + '_ {
+        self.versions.keys().cloned()
+    }
+
+    fn columns(&self, version: u64) -> Result<&Vec<VersionedColumn>> {
+        self.versions.get(&version).ok_or_else(|| {
+            ErrorCode::LogicalError(format!(
+                "Unknown schema version {}, the latest version is {}",
+                version,
+                self.version()
+            ))
+        })
+    }
+
+    /// The schema of the blocks written with the given version.
+    pub fn schema_of(&self, version: u64) -> Result<DataSchemaRef> {
+        let fields = self
+            .columns(version)?
+            .iter()
+            .map(|column| column.field.clone())
+            .collect();
+        Ok(Arc::new(DataSchema::new(fields)))
+    }
+
+    /// Positions of the columns of the latest version in the schema of the given version, the
+    /// position is `None` if the column is added after that version.
+    pub fn positions_in(&self, version: u64) -> Result<Vec<Option<usize>>> {
+        let columns = self.columns(version)?;
+        Ok(self
+            .columns(self.version())?
+            .iter()
+            .map(|current| columns.iter().position(|column| column.id == current.id))
+            .collect())
+    }
+
+    /// Value of the column at the given position of the latest version, in the blocks written
+    /// before the column is added.
+    pub fn default_value(&self, position: usize) -> Result<DataValue> {
+        let column = &self.columns(self.version())?[position];
+        Ok(match self.defaults.get(&column.id) {
+            Some(value) => value.clone(),
+            None => column.field.data_type().default_value(),
+        })
+    }
+
+    /// Map the leaf column ids of the latest version to the ones of the given version.
+    pub fn leaf_ids_in(&self, version: u64) -> Result<HashMap<ColumnId, ColumnId>> {
+        let current = ColumnLeaves::new_from_schema(&self.schema_of(self.version())?.to_arrow());
+        let older = ColumnLeaves::new_from_schema(&self.schema_of(version)?.to_arrow());
+
+        let mut leaf_ids = HashMap::new();
+        for (current, position) in current
+            .column_leaves
+            .iter()
+            .zip(self.positions_in(version)?)
+        {
+            if let Some(position) = position {
+                let older = &older.column_leaves[position];
+                if current.leaf_ids.len() == older.leaf_ids.len() {
+                    for (current_id, older_id) in current.leaf_ids.iter().zip(&older.leaf_ids) {
+                        leaf_ids.insert(*current_id as ColumnId, *older_id as ColumnId);
+                    }
+                }
+            }
+        }
+        Ok(leaf_ids)
+    }
+
+    /// Apply the change of columns to `schema`, the schema of the latest version, and return
+    /// the schema of the new version.
+    pub fn evolve(mut self, schema: &DataSchema, action: &AlterColumnAction) -> Result<DataSchema> {
+        let mut columns = self.columns(self.version())?.clone();
+        match action {
+            AlterColumnAction::Add { field, default, .. } => {
+                Self::check_absent(&columns, field.name())?;
+                let id = self.next_column_id;
+                self.next_column_id += 1;
+                self.defaults.insert(id, default.clone());
+                columns.push(VersionedColumn {
+                    id,
+                    field: field.clone(),
+                });
+            }
+            AlterColumnAction::Drop { column } => {
+                let position = Self::check_present(&columns, column)?;
+                if columns.len() == 1 {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "Cannot drop column {}, the only column of the table",
+                        column
+                    )));
+                }
+                columns.remove(position);
+            }
+            AlterColumnAction::Rename { column, new_column } => {
+                let position = Self::check_present(&columns, column)?;
+                Self::check_absent(&columns, new_column)?;
+                let field = &columns[position].field;
+                let renamed = DataField::new(new_column, field.data_type().clone())
+                    .with_default_expr(field.default_expr().cloned());
+                columns[position].field = renamed;
+            }
+            AlterColumnAction::Modify { field } => {
+                let position = Self::check_present(&columns, field.name())?;
+                let from_type = columns[position].field.data_type();
+                if !Self::is_widening(from_type, field.data_type()) {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "Cannot change the type of column {} from {} to {}, it is not widening",
+                        field.name(),
+                        from_type.name(),
+                        field.data_type().name()
+                    )));
+                }
+                columns[position].field = field.clone();
+            }
+        }
+
+        let fields = columns.iter().map(|column| column.field.clone()).collect();
+        self.versions.insert(self.version() + 1, columns);
+        let mut metadata = schema.meta().clone();
+        metadata.insert(
+            FUSE_SCHEMA_HISTORY_KEY.to_string(),
+            serde_json::to_string(&self)?,
+        );
+        Ok(DataSchema::new_from(fields, metadata))
+    }
+
+    fn check_absent(columns: &[VersionedColumn], name: &str) -> Result<()> {
+        match columns.iter().any(|column| column.field.name() == name) {
+            true => Err(ErrorCode::SemanticError(format!(
+                "Column {} already exists",
+                name
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    fn check_present(columns: &[VersionedColumn], name: &str) -> Result<usize> {
+        columns
+            .iter()
+            .position(|column| column.field.name() == name)
+            .ok_or_else(|| ErrorCode::UnknownColumn(format!("Unknown column {}", name)))
+    }
+
+    /// Whether all the values of `from_type` can be represented by `to_type`, without changing
+    /// the statistics of the blocks.
+    ///
+    /// Integers can be widened within the signed or unsigned ones, floats from `Float32` to
+    /// `Float64`, and a column can be made nullable.
+    pub fn is_widening(from_type: &DataTypeImpl, to_type: &DataTypeImpl) -> bool {
+        if from_type.is_nullable() && !to_type.is_nullable() {
+            return false;
+        }
+
+        let from_id = remove_nullable(from_type).data_type_id();
+        let to_id = remove_nullable(to_type).data_type_id();
+        if from_id == to_id {
+            return true;
+        }
+
+        let rank = |id: TypeID| match id {
+            TypeID::Int8 | TypeID::UInt8 | TypeID::Float32 => Some(0),
+            TypeID::Int16 | TypeID::UInt16 | TypeID::Float64 => Some(1),
+            TypeID::Int32 | TypeID::UInt32 => Some(2),
+            TypeID::Int64 | TypeID::UInt64 => Some(3),
+            _ => None,
+        };
+        let same_family = (from_id.is_signed_integer() && to_id.is_signed_integer())
+            || (from_id.is_unsigned_integer() && to_id.is_unsigned_integer())
+            || (from_id.is_floating() && to_id.is_floating());
+        match (rank(from_id), rank(to_id)) {
+            (Some(from_rank), Some(to_rank)) => same_family && from_rank < to_rank,
+            _ => false,
+        }
+    }
+}
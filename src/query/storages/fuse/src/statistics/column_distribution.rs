@@ -0,0 +1,73 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_fuse_meta::meta::ColumnDistribution;
+use common_fuse_meta::meta::ColumnId;
+use common_fuse_meta::meta::Histogram;
+use common_fuse_meta::meta::DEFAULT_HISTOGRAM_BUCKETS;
+use common_storages_index::MinMaxIndex;
+use common_storages_index::SupportedType;
+
+use crate::statistics::traverse;
+
+/// Collect the distributions of the values of the leaf columns of the block, keyed by the leaf
+/// column id like the column statistics of the block.
+pub fn gen_columns_distributions(
+    data_block: &DataBlock,
+) -> Result<HashMap<ColumnId, ColumnDistribution>> {
+    let mut distributions = HashMap::new();
+
+    let leaves = traverse::traverse_columns_dfs(data_block.columns())?;
+
+    for (idx, col) in leaves.iter().enumerate() {
+        if !MinMaxIndex::is_supported_type(&col.data_type()) {
+            continue;
+        }
+
+        let mut distribution = ColumnDistribution::default();
+        let mut numbers = vec![];
+        for value in col.to_values() {
+            if value.is_null() {
+                distribution.null_count += 1;
+                continue;
+            }
+
+            distribution.distinct_values.add(&value);
+            if value.is_numeric() {
+                numbers.push(value.as_f64()?);
+            }
+            if distribution.min.is_null() || value < distribution.min {
+                distribution.min = value.clone();
+            }
+            if distribution.max.is_null() || value > distribution.max {
+                distribution.max = value;
+            }
+        }
+
+        if !numbers.is_empty() {
+            numbers.sort_by(f64::total_cmp);
+            distribution.histogram = Some(Histogram::from_sorted_values(
+                &numbers,
+                DEFAULT_HISTOGRAM_BUCKETS,
+            ));
+        }
+
+        distributions.insert(idx as u32, distribution);
+    }
+    Ok(distributions)
+}
@@ -13,9 +13,14 @@
 //  limitations under the License.
 
 use common_datablocks::DataBlock;
+use common_datavalues::remove_nullable;
+use common_datavalues::ColumnRef;
 use common_datavalues::ColumnWithField;
 use common_datavalues::DataField;
 use common_datavalues::DataValue;
+use common_datavalues::ObjectType;
+use common_datavalues::Series;
+use common_datavalues::VariantColumn;
 use common_exception::Result;
 use common_functions::aggregates::eval_aggr;
 use common_fuse_meta::meta::ColumnStatistics;
@@ -30,6 +35,10 @@ pub fn gen_columns_statistics(data_block: &DataBlock) -> Result<StatisticsOfColu
 
     for (idx, col) in leaves.iter().enumerate() {
         let col_data_type = col.data_type();
+        if remove_nullable(&col_data_type).data_type_id().is_variant() {
+            statistics.insert(idx as u32, gen_variant_statistics(col)?);
+            continue;
+        }
         if !MinMaxIndex::is_supported_type(&col_data_type) {
             continue;
         }
@@ -71,6 +80,36 @@ pub fn gen_columns_statistics(data_block: &DataBlock) -> Result<StatisticsOfColu
     Ok(statistics)
 }
 
+// VARIANT values have no min/max, as they are not totally ordered across types, but the null
+// count still helps the pruning of `IS [NOT] NULL`. The size is the one of the JSONB encoding,
+// which is how the values are stored.
+fn gen_variant_statistics(col: &ColumnRef) -> Result<ColumnStatistics> {
+    let col = col.convert_full_column();
+    let (is_all_null, bitmap) = col.validity();
+    let null_count = match (is_all_null, bitmap) {
+        (true, _) => col.len(),
+        (false, Some(bitmap)) => bitmap.unset_bits(),
+        (false, None) => 0,
+    };
+
+    let inner = Series::remove_nullable(&col);
+    let variant_col: &VariantColumn = Series::check_get(&inner)?;
+    let mut buf = Vec::new();
+    let mut in_memory_size = 0;
+    for value in variant_col.values() {
+        buf.clear();
+        value.encode_binary(&mut buf);
+        in_memory_size += buf.len() as u64;
+    }
+
+    Ok(ColumnStatistics {
+        min: DataValue::Null,
+        max: DataValue::Null,
+        null_count: null_count as u64,
+        in_memory_size,
+    })
+}
+
 pub mod traverse {
     use common_datavalues::ColumnRef;
     use common_datavalues::DataTypeImpl;
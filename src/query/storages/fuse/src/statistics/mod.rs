@@ -15,12 +15,14 @@
 pub mod accumulator;
 mod block_statistics;
 mod cluster_statistics;
+mod column_distribution;
 mod column_statistic;
 pub mod reducers;
 
 pub use accumulator::StatisticsAccumulator;
 pub use block_statistics::BlockStatistics;
 pub use cluster_statistics::ClusterStatsGenerator;
+pub use column_distribution::gen_columns_distributions;
 pub use column_statistic::gen_columns_statistics;
 pub use column_statistic::traverse;
 pub use reducers::merge_statistics;
@@ -0,0 +1,389 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_catalog::catalog::StorageDescription;
+use common_catalog::table::Table;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::BlockMeta;
+use common_fuse_meta::meta::Location;
+use common_fuse_meta::meta::TableSnapshot;
+use common_legacy_planners::Extras;
+use common_legacy_planners::PartInfo;
+use common_legacy_planners::PartInfoPtr;
+use common_legacy_planners::Partitions;
+use common_legacy_planners::Projection;
+use common_legacy_planners::ReadDataSourcePlan;
+use common_legacy_planners::Statistics;
+use common_meta_app::schema::TableInfo;
+use common_storages_util::storage_context::StorageContext;
+
+use crate::io::BlockReader;
+use crate::io::MetaReaders;
+use crate::io::TableMetaLocationGenerator;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::AsyncSource;
+use crate::pipelines::processors::AsyncSourcer;
+use crate::pipelines::Pipe;
+use crate::pipelines::Pipeline;
+use crate::sessions::TableContext;
+use crate::FuseTable;
+
+pub const STREAM_ENGINE: &str = "STREAM";
+
+/// Id of the table whose changes are captured by the stream.
+pub const OPT_KEY_STREAM_SOURCE_TABLE_ID: &str = "source_table_id";
+/// Name of the source table, as `database.table`, only for display.
+pub const OPT_KEY_STREAM_SOURCE_TABLE_NAME: &str = "source_table";
+/// Location of the snapshot of the source table at the last consumption point, absent if the
+/// source table had no snapshot then.
+pub const OPT_KEY_STREAM_OFFSET: &str = "offset";
+
+/// The column of a stream telling whether the row is inserted or deleted.
+pub const CHANGE_ACTION_COLUMN: &str = "_change_action";
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeAction {
+    Insert,
+    Delete,
+}
+
+impl ChangeAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeAction::Insert => "INSERT",
+            ChangeAction::Delete => "DELETE",
+        }
+    }
+}
+
+/// A block of the source table changed since the offset of the stream.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct StreamPartInfo {
+    pub action: ChangeAction,
+    pub block: BlockMeta,
+}
+
+#[typetag::serde(name = "fuse_stream")]
+impl PartInfo for StreamPartInfo {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn equals(&self, info: &Box<dyn PartInfo>) -> bool {
+        match info.as_any().downcast_ref::<StreamPartInfo>() {
+            None => false,
+            Some(other) => {
+                self.action == other.action && self.block.location == other.block.location
+            }
+        }
+    }
+}
+
+impl StreamPartInfo {
+    pub fn create(action: ChangeAction, block: BlockMeta) -> PartInfoPtr {
+        Arc::new(Box::new(StreamPartInfo { action, block }))
+    }
+
+    pub fn from_part(info: &PartInfoPtr) -> Result<&StreamPartInfo> {
+        match info.as_any().downcast_ref::<StreamPartInfo>() {
+            Some(part) => Ok(part),
+            None => Err(ErrorCode::LogicalError(
+                "Cannot downcast from PartInfo to StreamPartInfo.",
+            )),
+        }
+    }
+}
+
+/// The rows inserted into and deleted from a fuse table since the offset of the stream.
+///
+/// The changes are the difference of the blocks between the snapshot of the offset and the
+/// latest snapshot of the source table: the rows of the new blocks are inserted, and the rows
+/// of the removed blocks are deleted. A block rewritten by a deletion or a compaction shows up
+/// as both, the rows left in the new block are deleted and inserted again.
+///
+/// Reading the stream does not move its offset. When a statement reading the stream commits
+/// its changes to a fuse table, the offset is advanced to the snapshot read, in the same meta
+/// transaction as the changes, so that the changes are consumed exactly once.
+pub struct StreamTable {
+    table_info: TableInfo,
+    source_table_id: u64,
+    offset: Option<String>,
+}
+
+impl StreamTable {
+    pub fn try_create(_ctx: StorageContext, table_info: TableInfo) -> Result<Box<dyn Table>> {
+        let options = table_info.options();
+        let source_table_id = options
+            .get(OPT_KEY_STREAM_SOURCE_TABLE_ID)
+            .and_then(|id| id.parse::<u64>().ok())
+            .ok_or_else(|| {
+                ErrorCode::LogicalError(format!("Invalid STREAM object: {}", table_info.desc))
+            })?;
+        let offset = options.get(OPT_KEY_STREAM_OFFSET).cloned();
+        Ok(Box::new(StreamTable {
+            table_info,
+            source_table_id,
+            offset,
+        }))
+    }
+
+    pub fn description() -> StorageDescription {
+        StorageDescription {
+            engine_name: STREAM_ENGINE.to_string(),
+            comment: "STREAM STORAGE (CHANGES OF A FUSE TABLE)".to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The schema of the stream is the schema of the source table, followed by the column of
+    /// change actions.
+    pub fn schema_of_source(source_schema: &DataSchema) -> DataSchema {
+        let mut fields = source_schema.fields().clone();
+        fields.push(DataField::new(CHANGE_ACTION_COLUMN, StringType::new_impl()));
+        DataSchema::new_from(fields, source_schema.meta().clone())
+    }
+
+    fn source_schema(&self) -> DataSchemaRef {
+        let schema = self.table_info.schema();
+        let mut fields = schema.fields().clone();
+        fields.pop();
+        Arc::new(DataSchema::new_from(fields, schema.meta().clone()))
+    }
+
+    async fn source_table(&self, ctx: &Arc<dyn TableContext>) -> Result<Box<FuseTable>> {
+        // the changes made to the source table by the transaction of the session are included
+        let staged = ctx
+            .get_txn_manager()
+            .lock()
+            .get_staged_table(self.source_table_id);
+        let table_info = match staged {
+            Some(table_info) => table_info,
+            None => {
+                let catalog = ctx.get_catalog(&self.table_info.meta.catalog)?;
+                let (ident, meta) = catalog.get_table_meta_by_id(self.source_table_id).await?;
+                let name = self.table_info.options()[OPT_KEY_STREAM_SOURCE_TABLE_NAME].clone();
+                TableInfo {
+                    ident,
+                    desc: name.clone(),
+                    name,
+                    meta: meta.as_ref().clone(),
+                }
+            }
+        };
+
+        if table_info.schema() != self.source_schema() {
+            return Err(ErrorCode::SemanticError(format!(
+                "The columns of table {} are changed after stream {} is created",
+                table_info.desc, self.table_info.name
+            )));
+        }
+        FuseTable::do_create(table_info, true)
+    }
+
+    async fn read_snapshot(
+        ctx: &Arc<dyn TableContext>,
+        location: Option<&String>,
+    ) -> Result<Option<Arc<TableSnapshot>>> {
+        match location {
+            Some(location) => {
+                let reader = MetaReaders::table_snapshot_reader(ctx.clone());
+                let ver = TableMetaLocationGenerator::snapshot_version(location);
+                Ok(Some(reader.read(location.as_str(), None, ver).await?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn read_blocks(
+        ctx: &Arc<dyn TableContext>,
+        segments: &[Location],
+    ) -> Result<Vec<BlockMeta>> {
+        let reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut blocks = vec![];
+        for (location, ver) in segments {
+            let segment = reader.read(location, None, *ver).await?;
+            blocks.extend(segment.blocks.iter().cloned());
+        }
+        Ok(blocks)
+    }
+
+    /// The blocks inserted and deleted between the snapshot of the offset and `snapshot`.
+    async fn changed_blocks(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        snapshot: Option<&TableSnapshot>,
+    ) -> Result<Vec<(ChangeAction, BlockMeta)>> {
+        let offset = Self::read_snapshot(ctx, self.offset.as_ref()).await?;
+        let segments_of = |snapshot: Option<&TableSnapshot>| {
+            snapshot
+                .map(|snapshot| snapshot.segments.clone())
+                .unwrap_or_default()
+        };
+        let current_segments = segments_of(snapshot);
+        let offset_segments = segments_of(offset.as_deref());
+
+        // the segments kept by the snapshots contain the same blocks
+        let inserted_segments = current_segments
+            .iter()
+            .filter(|segment| !offset_segments.contains(segment))
+            .cloned()
+            .collect::<Vec<_>>();
+        let deleted_segments = offset_segments
+            .iter()
+            .filter(|segment| !current_segments.contains(segment))
+            .cloned()
+            .collect::<Vec<_>>();
+        let inserted = Self::read_blocks(ctx, &inserted_segments).await?;
+        let deleted = Self::read_blocks(ctx, &deleted_segments).await?;
+
+        // the blocks moved to another segment, by the compaction of segments, are not changed
+        let inserted_locations = inserted
+            .iter()
+            .map(|block| block.location.0.clone())
+            .collect::<HashSet<_>>();
+        let deleted_locations = deleted
+            .iter()
+            .map(|block| block.location.0.clone())
+            .collect::<HashSet<_>>();
+        let mut changes = vec![];
+        for block in deleted {
+            if !inserted_locations.contains(&block.location.0) {
+                changes.push((ChangeAction::Delete, block));
+            }
+        }
+        for block in inserted {
+            if !deleted_locations.contains(&block.location.0) {
+                changes.push((ChangeAction::Insert, block));
+            }
+        }
+        Ok(changes)
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for StreamTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        _push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        let source = self.source_table(&ctx).await?;
+        let snapshot = source.read_table_snapshot(ctx.clone()).await?;
+        let changes = self.changed_blocks(&ctx, snapshot.as_deref()).await?;
+
+        // the offset is advanced to the snapshot read if the statement commits
+        let mut table_info = self.table_info.clone();
+        match source.snapshot_loc() {
+            Some(location) => table_info
+                .meta
+                .options
+                .insert(OPT_KEY_STREAM_OFFSET.to_string(), location),
+            None => table_info.meta.options.remove(OPT_KEY_STREAM_OFFSET),
+        };
+        ctx.add_consumed_stream(table_info);
+
+        let mut read_rows = 0;
+        let mut read_bytes = 0;
+        let mut parts = Vec::with_capacity(changes.len());
+        for (action, block) in changes {
+            read_rows += block.row_count as usize;
+            read_bytes += block.block_size as usize;
+            parts.push(StreamPartInfo::create(action, block));
+        }
+        let statistics = Statistics::new_exact(read_rows, read_bytes, parts.len(), parts.len());
+        Ok((statistics, parts))
+    }
+
+    fn read2(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let source_schema = self.source_schema();
+        let projection = Projection::Columns((0..source_schema.num_fields()).collect());
+        let operator = ctx.get_storage_operator()?;
+        let block_reader = BlockReader::create(operator, source_schema, projection)?;
+
+        let output = OutputPort::create();
+        pipeline.add_pipe(Pipe::SimplePipe {
+            inputs_port: vec![],
+            outputs_port: vec![output.clone()],
+            processors: vec![StreamSource::create(
+                ctx,
+                output,
+                block_reader,
+                plan.schema(),
+            )?],
+        });
+
+        Ok(())
+    }
+}
+
+struct StreamSource {
+    ctx: Arc<dyn TableContext>,
+    block_reader: Arc<BlockReader>,
+    output_schema: DataSchemaRef,
+}
+
+impl StreamSource {
+    pub fn create(
+        ctx: Arc<dyn TableContext>,
+        output: Arc<OutputPort>,
+        block_reader: Arc<BlockReader>,
+        output_schema: DataSchemaRef,
+    ) -> Result<ProcessorPtr> {
+        AsyncSourcer::create(ctx.clone(), output, StreamSource {
+            ctx,
+            block_reader,
+            output_schema,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncSource for StreamSource {
+    const NAME: &'static str = "stream";
+
+    #[async_trait::unboxed_simple]
+    async fn generate(&mut self) -> Result<Option<DataBlock>> {
+        let part = match self.ctx.try_get_partitions(1)?.pop() {
+            Some(part) => part,
+            None => return Ok(None),
+        };
+        let part = StreamPartInfo::from_part(&part)?;
+        let block = self.block_reader.read_with_block_meta(&part.block).await?;
+        let action = Series::from_data(vec![part.action.as_str(); block.num_rows()]);
+        let field = DataField::new(CHANGE_ACTION_COLUMN, StringType::new_impl());
+        let block = block.add_column(action, field)?;
+        Ok(Some(block.resort(self.output_schema.clone())?))
+    }
+}
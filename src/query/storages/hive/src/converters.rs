@@ -41,21 +41,27 @@ use common_meta_app::schema::TableIdent;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableMeta;
 
-use crate::hive_catalog::HIVE_CATALOG;
 use crate::hive_database::HiveDatabase;
 use crate::hive_database::HIVE_DATABASE_ENGIE;
 use crate::hive_table::HIVE_TABLE_ENGIE;
+use crate::hive_table_options::HiveFileFormat;
 use crate::hive_table_options::HiveTableOptions;
 
 /// ! Skeleton of mappers
 impl From<hms::Database> for HiveDatabase {
     fn from(hms_database: hms::Database) -> Self {
+        HiveDatabase::from_name(hms_database.name.unwrap_or_default())
+    }
+}
+
+impl HiveDatabase {
+    pub fn from_name(db_name: String) -> Self {
         HiveDatabase {
             database_info: DatabaseInfo {
                 ident: DatabaseIdent { db_id: 0, seq: 0 },
                 name_ident: DatabaseNameIdent {
                     tenant: "TODO".to_owned(),
-                    db_name: hms_database.name.unwrap_or_default(),
+                    db_name,
                 },
                 meta: DatabaseMeta {
                     engine: HIVE_DATABASE_ENGIE.to_owned(),
@@ -68,6 +74,7 @@ impl From<hms::Database> for HiveDatabase {
 }
 
 pub fn try_into_table_info(
+    catalog_name: &str,
     hms_table: hms::Table,
     fields: Vec<hms::FieldSchema>,
 ) -> Result<TableInfo> {
@@ -91,14 +98,24 @@ pub fn try_into_table_info(
         None
     };
 
+    let format = match hms_table
+        .sd
+        .as_ref()
+        .and_then(|storage| storage.input_format.as_ref())
+    {
+        Some(input_format) => HiveFileFormat::from_input_format(input_format),
+        None => HiveFileFormat::Parquet,
+    };
+
     let table_options = HiveTableOptions {
         partition_keys,
         location,
+        format,
     };
 
     let meta = TableMeta {
         schema,
-        catalog: HIVE_CATALOG.to_string(),
+        catalog: catalog_name.to_string(),
         engine: HIVE_TABLE_ENGIE.to_owned(),
         engine_options: table_options.into(),
         created_on: Utc::now(),
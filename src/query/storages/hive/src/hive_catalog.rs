@@ -67,13 +67,19 @@ pub const HIVE_CATALOG: &str = "hive";
 
 #[derive(Clone)]
 pub struct HiveCatalog {
+    /// name of the catalog, which the tables of it refer to
+    name: String,
     /// address of hive meta store service
     client_address: String,
 }
 
 impl HiveCatalog {
-    pub fn try_create(hms_address: impl Into<String>) -> Result<HiveCatalog> {
+    pub fn try_create(
+        name: impl Into<String>,
+        hms_address: impl Into<String>,
+    ) -> Result<HiveCatalog> {
         Ok(HiveCatalog {
+            name: name.into(),
             client_address: hms_address.into(),
         })
     }
@@ -154,11 +160,11 @@ impl HiveCatalog {
     }
 
     fn do_get_table(
-        client: impl TThriftHiveMetastoreSyncClient,
+        client: &mut impl TThriftHiveMetastoreSyncClient,
+        catalog_name: &str,
         db_name: String,
         table_name: String,
     ) -> Result<Arc<dyn Table>> {
-        let mut client = client;
         let table_meta = client
             .get_table(db_name.clone(), table_name.clone())
             .map_err(from_thrift_error)?;
@@ -177,7 +183,8 @@ impl HiveCatalog {
         let fields = client
             .get_schema(db_name, table_name)
             .map_err(from_thrift_error)?;
-        let table_info: TableInfo = super::converters::try_into_table_info(table_meta, fields)?;
+        let table_info: TableInfo =
+            super::converters::try_into_table_info(catalog_name, table_meta, fields)?;
         let res: Arc<dyn Table> = Arc::new(HiveTable::try_create(table_info)?);
         Ok(res)
     }
@@ -192,6 +199,45 @@ impl HiveCatalog {
         let res: Arc<dyn Database> = Arc::new(hive_database);
         Ok(res)
     }
+
+    fn do_list_databases(
+        client: impl TThriftHiveMetastoreSyncClient,
+    ) -> Result<Vec<Arc<dyn Database>>> {
+        let mut client = client;
+        let db_names = client.get_all_databases().map_err(from_thrift_error)?;
+        Ok(db_names
+            .into_iter()
+            .map(|db_name| Arc::new(HiveDatabase::from_name(db_name)) as Arc<dyn Database>)
+            .collect())
+    }
+
+    fn do_list_tables(
+        client: impl TThriftHiveMetastoreSyncClient,
+        catalog_name: &str,
+        db_name: String,
+    ) -> Result<Vec<Arc<dyn Table>>> {
+        let mut client = client;
+        let table_names = client
+            .get_all_tables(db_name.clone())
+            .map_err(from_thrift_error)?;
+
+        // tables of unsupported formats or data types are not listed
+        let unsupported = [
+            ErrorCode::UnImplementCode(),
+            ErrorCode::IllegalDataTypeCode(),
+        ];
+        let mut tables = Vec::with_capacity(table_names.len());
+        for table_name in table_names {
+            match Self::do_get_table(&mut client, catalog_name, db_name.clone(), table_name) {
+                Ok(table) => tables.push(table),
+                Err(e) if unsupported.contains(&e.code()) => {
+                    tracing::warn!("skip hive table in database {}: {}", db_name, e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(tables)
+    }
 }
 
 fn from_thrift_error(error: thrift::Error) -> ErrorCode {
@@ -219,8 +265,12 @@ impl Catalog for HiveCatalog {
     }
 
     // Get all the databases.
+    #[tracing::instrument(level = "info", skip(self))]
     async fn list_databases(&self, _tenant: &str) -> Result<Vec<Arc<dyn Database>>> {
-        todo!()
+        let client = self.get_client()?;
+        tokio::task::spawn_blocking(move || Self::do_list_databases(client))
+            .await
+            .unwrap()
     }
 
     // Operation with database.
@@ -271,15 +321,25 @@ impl Catalog for HiveCatalog {
         table_name: &str,
     ) -> Result<Arc<dyn Table>> {
         let client = self.get_client()?;
+        let catalog_name = self.name.clone();
         let db_name = db_name.to_string();
         let table_name = table_name.to_string();
-        tokio::task::spawn_blocking(move || Self::do_get_table(client, db_name, table_name))
-            .await
-            .unwrap()
+        tokio::task::spawn_blocking(move || {
+            let mut client = client;
+            Self::do_get_table(&mut client, &catalog_name, db_name, table_name)
+        })
+        .await
+        .unwrap()
     }
 
-    async fn list_tables(&self, _tenant: &str, _db_name: &str) -> Result<Vec<Arc<dyn Table>>> {
-        todo!()
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn list_tables(&self, _tenant: &str, db_name: &str) -> Result<Vec<Arc<dyn Table>>> {
+        let client = self.get_client()?;
+        let catalog_name = self.name.clone();
+        let db_name = db_name.to_string();
+        tokio::task::spawn_blocking(move || Self::do_list_tables(client, &catalog_name, db_name))
+            .await
+            .unwrap()
     }
 
     async fn list_tables_history(
@@ -348,22 +408,30 @@ impl Catalog for HiveCatalog {
         &self,
         _req: GetTableCopiedFileReq,
     ) -> Result<GetTableCopiedFileReply> {
-        unimplemented!()
+        Err(ErrorCode::UnImplement(
+            "Cannot get table copied file info in HIVE catalog",
+        ))
     }
 
     async fn upsert_table_copied_file_info(
         &self,
         _req: UpsertTableCopiedFileReq,
     ) -> Result<UpsertTableCopiedFileReply> {
-        unimplemented!()
+        Err(ErrorCode::UnImplement(
+            "Cannot upsert table copied file info in HIVE catalog",
+        ))
     }
 
     async fn truncate_table(&self, _req: TruncateTableReq) -> Result<TruncateTableReply> {
-        unimplemented!()
+        Err(ErrorCode::UnImplement(
+            "Cannot truncate table in HIVE catalog",
+        ))
     }
 
     async fn count_tables(&self, _req: CountTablesReq) -> Result<CountTablesReply> {
-        unimplemented!()
+        Err(ErrorCode::UnImplement(
+            "Cannot count tables in HIVE catalog",
+        ))
     }
 
     /// Table function
@@ -374,11 +442,13 @@ impl Catalog for HiveCatalog {
         _func_name: &str,
         _tbl_args: TableArgs,
     ) -> Result<Arc<dyn TableFunction>> {
-        unimplemented!()
+        Err(ErrorCode::UnImplement(
+            "Cannot get table function in HIVE catalog",
+        ))
     }
 
     // Get table engines
     fn get_table_engines(&self) -> Vec<StorageDescription> {
-        unimplemented!()
+        vec![]
     }
 }
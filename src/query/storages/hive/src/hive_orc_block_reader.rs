@@ -0,0 +1,164 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use common_arrow::arrow::chunk::Chunk;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::io::orc::format::read::read_metadata;
+use common_arrow::arrow::io::orc::format::read::read_stripe_column;
+use common_arrow::arrow::io::orc::format::read::read_stripe_footer;
+use common_arrow::arrow::io::orc::format::read::FileMetadata;
+use common_arrow::arrow::io::orc::read;
+use common_datablocks::DataBlock;
+use common_datavalues::remove_nullable;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opendal::Operator;
+
+use crate::hive_partition::HivePartInfo;
+use crate::hive_partition_filler::HivePartitionFiller;
+
+#[derive(Clone)]
+pub struct HiveOrcBlockReader {
+    operator: Operator,
+    projection: Vec<usize>,
+    projected_schema: DataSchemaRef,
+    hive_partition_filler: Option<HivePartitionFiller>,
+}
+
+impl HiveOrcBlockReader {
+    pub fn create(
+        operator: Operator,
+        schema: DataSchemaRef,
+        projection: Vec<usize>,
+        hive_partition_filler: Option<HivePartitionFiller>,
+    ) -> Result<Arc<HiveOrcBlockReader>> {
+        let projected_schema = DataSchemaRef::new(schema.project(&projection));
+        Ok(Arc::new(HiveOrcBlockReader {
+            operator,
+            projection,
+            projected_schema,
+            hive_partition_filler,
+        }))
+    }
+
+    // the footer is at the end of the file, stripes can't be located before reading it
+    pub async fn read_file(&self, part: &HivePartInfo) -> Result<Vec<u8>> {
+        let data = self.operator.object(&part.filename).read().await?;
+        Ok(data)
+    }
+
+    /// Deserialize the stripes of the file which belong to the part, one block for each stripe.
+    pub fn deserialize(&self, file: Vec<u8>, part: &HivePartInfo) -> Result<VecDeque<DataBlock>> {
+        let mut cursor = Cursor::new(file);
+        let metadata = read_metadata(&mut cursor).map_err(|e| orc_error(part, e))?;
+        let (column_ids, fields) = self.get_columns(&metadata)?;
+
+        let mut blocks = VecDeque::with_capacity(metadata.footer.stripes.len());
+        let mut scratch = vec![];
+        for (index, info) in metadata.footer.stripes.iter().enumerate() {
+            // same as the row groups of parquet files, a stripe belongs to the part its middle is in
+            let length = info.index_length() + info.data_length() + info.footer_length();
+            let mid = info.offset() + length / 2;
+            if !part.range.contains(&mid) {
+                continue;
+            }
+
+            let footer = read_stripe_footer(&mut cursor, &metadata, index, &mut scratch)
+                .map_err(|e| orc_error(part, e))?;
+            let mut arrays = Vec::with_capacity(column_ids.len());
+            for (column_id, field) in column_ids.iter().zip(fields.iter()) {
+                let column = read_stripe_column(
+                    &mut cursor,
+                    &metadata,
+                    index,
+                    footer.clone(),
+                    *column_id,
+                    vec![],
+                )
+                .map_err(|e| orc_error(part, e))?;
+                let array = read::deserialize(field.data_type.clone(), &column)
+                    .map_err(|e| orc_error(part, e))?;
+                arrays.push(array);
+            }
+
+            let chunk = Chunk::try_new(arrays)?;
+            let block = DataBlock::from_chunk(&self.projected_schema, &chunk)?;
+            let block = match &self.hive_partition_filler {
+                Some(filler) => filler.fill_data(block, part, info.number_of_rows() as usize)?,
+                None => block,
+            };
+            blocks.push_back(block);
+        }
+        Ok(blocks)
+    }
+
+    /// Find the ORC column of each projected column, by name.
+    ///
+    /// Files written by hive before 2.0 name their columns `_col0`, `_col1`...,
+    /// the columns of these files are matched by their positions in the table schema.
+    fn get_columns(&self, metadata: &FileMetadata) -> Result<(Vec<u32>, Vec<Field>)> {
+        let infer_schema = read::infer_schema(&metadata.footer)?;
+        let root = metadata
+            .footer
+            .types
+            .first()
+            .ok_or_else(|| ErrorCode::BadBytes("ORC file without columns"))?;
+        let positional = root.field_names.iter().all(|name| name.starts_with("_col"));
+
+        let mut column_ids = Vec::with_capacity(self.projection.len());
+        let mut read_fields = Vec::with_capacity(self.projection.len());
+        for (f, index) in self
+            .projected_schema
+            .fields()
+            .iter()
+            .zip(self.projection.iter())
+        {
+            let position = match positional {
+                true => Some(*index).filter(|index| *index < root.field_names.len()),
+                false => root
+                    .field_names
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(f.name())),
+            };
+            let position = position.ok_or_else(|| {
+                ErrorCode::BadBytes(format!("couldn't find column:{} in orc file", f.name()))
+            })?;
+
+            let field = &infer_schema.fields[position];
+            let data_type = DataField::from(field).data_type().clone();
+            if remove_nullable(&data_type) != remove_nullable(f.data_type()) {
+                return Err(ErrorCode::BadBytes(format!(
+                    "ORC schema mismatch, column {} is of type {:?} in the file, {:?} expected",
+                    f.name(),
+                    data_type,
+                    f.data_type()
+                )));
+            }
+            column_ids.push(root.subtypes[position]);
+            read_fields.push(field.clone());
+        }
+        Ok((column_ids, read_fields))
+    }
+}
+
+fn orc_error(part: &HivePartInfo, e: impl Display) -> ErrorCode {
+    ErrorCode::BadBytes(format!("fail to read ORC file {}: {}", part.filename, e))
+}
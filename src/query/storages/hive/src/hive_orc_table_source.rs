@@ -0,0 +1,164 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_base::base::tokio::time::sleep;
+use common_base::base::tokio::time::Duration;
+use common_base::base::Progress;
+use common_base::base::ProgressValues;
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_legacy_planners::PartInfoPtr;
+use common_pipeline_core::processors::port::OutputPort;
+use common_pipeline_core::processors::processor::Event;
+use common_pipeline_core::processors::processor::ProcessorPtr;
+use common_pipeline_core::processors::Processor;
+
+use crate::hive_orc_block_reader::HiveOrcBlockReader;
+use crate::HivePartInfo;
+
+enum State {
+    /// Read the whole orc file of the part
+    /// IO bound
+    ReadFile(PartInfoPtr),
+
+    /// Deserialize the stripes of the part
+    /// CPU bound
+    Deserialize(HivePartInfo, Vec<u8>),
+
+    /// The blocks of the part which are not consumed yet
+    Generated(VecDeque<DataBlock>),
+    Finish,
+}
+
+pub struct HiveOrcTableSource {
+    state: State,
+    ctx: Arc<dyn TableContext>,
+    scan_progress: Arc<Progress>,
+    block_reader: Arc<HiveOrcBlockReader>,
+    output: Arc<OutputPort>,
+    delay: usize,
+}
+
+impl HiveOrcTableSource {
+    pub fn create(
+        ctx: Arc<dyn TableContext>,
+        output: Arc<OutputPort>,
+        block_reader: Arc<HiveOrcBlockReader>,
+        delay: usize,
+    ) -> Result<ProcessorPtr> {
+        let scan_progress = ctx.get_scan_progress();
+        let mut source = HiveOrcTableSource {
+            state: State::Finish,
+            ctx,
+            scan_progress,
+            block_reader,
+            output,
+            delay,
+        };
+        source.try_get_partitions()?;
+        Ok(ProcessorPtr::create(Box::new(source)))
+    }
+
+    fn try_get_partitions(&mut self) -> Result<()> {
+        let partitions = self.ctx.try_get_partitions(1)?;
+        match partitions.is_empty() {
+            true => self.state = State::Finish,
+            false => {
+                self.state = State::ReadFile(partitions[0].clone());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for HiveOrcTableSource {
+    fn name(&self) -> &'static str {
+        "HiveEngineOrcSource"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            return Ok(Event::NeedConsume);
+        }
+
+        if let State::Generated(blocks) = &mut self.state {
+            if let Some(data_block) = blocks.pop_front() {
+                let progress_values = ProgressValues {
+                    rows: data_block.num_rows(),
+                    bytes: data_block.memory_size(),
+                };
+                self.scan_progress.incr(&progress_values);
+                self.output.push_data(Ok(data_block));
+                return Ok(Event::NeedConsume);
+            }
+
+            // all the stripes of current part are consumed, try read next part
+            self.try_get_partitions()?;
+        }
+
+        match self.state {
+            State::Finish => {
+                self.output.finish();
+                Ok(Event::Finished)
+            }
+            State::ReadFile(_) => Ok(Event::Async),
+            State::Deserialize(_, _) => Ok(Event::Sync),
+            State::Generated(_) => Err(ErrorCode::LogicalError("It's a bug.")),
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::Deserialize(part, file) => {
+                let blocks = self.block_reader.deserialize(file, &part)?;
+                self.state = State::Generated(blocks);
+                Ok(())
+            }
+            _ => Err(ErrorCode::LogicalError("It's a bug.")),
+        }
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::Finish) {
+            State::ReadFile(part) => {
+                if self.delay > 0 {
+                    sleep(Duration::from_millis(self.delay as u64)).await;
+                    tracing::debug!("sleep for {}ms", self.delay);
+                    self.delay = 0;
+                }
+                let part = HivePartInfo::from_part(&part)?.clone();
+                let file = self.block_reader.read_file(&part).await?;
+                self.state = State::Deserialize(part, file);
+                Ok(())
+            }
+            _ => Err(ErrorCode::LogicalError("It's a bug.")),
+        }
+    }
+}
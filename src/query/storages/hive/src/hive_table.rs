@@ -48,12 +48,14 @@ use opendal::Operator;
 
 use super::hive_catalog::HiveCatalog;
 use super::hive_partition_pruner::HivePartitionPruner;
+use super::hive_table_options::HiveFileFormat;
 use super::hive_table_options::HiveTableOptions;
+use crate::hive_orc_block_reader::HiveOrcBlockReader;
+use crate::hive_orc_table_source::HiveOrcTableSource;
 use crate::hive_parquet_block_reader::HiveParquetBlockReader;
 use crate::hive_partition_filler::HivePartitionFiller;
 use crate::hive_table_source::HiveTableSource;
 use crate::HiveFileSplitter;
-use crate::CATALOG_HIVE;
 
 pub const HIVE_TABLE_ENGIE: &str = "hive";
 
@@ -103,7 +105,9 @@ impl HiveTable {
         pipeline: &mut Pipeline,
     ) -> Result<()> {
         let push_downs = &plan.push_downs;
-        let block_reader = self.create_block_reader(&ctx, push_downs)?;
+        let (projection, hive_partition_filler) = self.get_projection(push_downs)?;
+        let operator = ctx.get_storage_operator()?;
+        let table_schema = self.table_info.schema();
 
         let parts_len = plan.parts.len();
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
@@ -117,17 +121,47 @@ impl HiveTable {
             |_| 0
         };
 
-        for index in 0..std::cmp::max(1, max_threads) {
-            let output = OutputPort::create();
-            source_builder.add_source(
-                output.clone(),
-                HiveTableSource::create(
-                    ctx.clone(),
-                    output,
-                    block_reader.clone(),
-                    delay_timer(index),
-                )?,
-            );
+        match self.table_options.format {
+            HiveFileFormat::Parquet => {
+                let block_reader = HiveParquetBlockReader::create(
+                    operator,
+                    table_schema,
+                    projection,
+                    hive_partition_filler,
+                )?;
+                for index in 0..std::cmp::max(1, max_threads) {
+                    let output = OutputPort::create();
+                    source_builder.add_source(
+                        output.clone(),
+                        HiveTableSource::create(
+                            ctx.clone(),
+                            output,
+                            block_reader.clone(),
+                            delay_timer(index),
+                        )?,
+                    );
+                }
+            }
+            HiveFileFormat::Orc => {
+                let block_reader = HiveOrcBlockReader::create(
+                    operator,
+                    table_schema,
+                    projection,
+                    hive_partition_filler,
+                )?;
+                for index in 0..std::cmp::max(1, max_threads) {
+                    let output = OutputPort::create();
+                    source_builder.add_source(
+                        output.clone(),
+                        HiveOrcTableSource::create(
+                            ctx.clone(),
+                            output,
+                            block_reader.clone(),
+                            delay_timer(index),
+                        )?,
+                    );
+                }
+            }
         }
 
         pipeline.add_pipe(source_builder.finalize());
@@ -173,11 +207,11 @@ impl HiveTable {
             .collect::<HashSet<_>>()
     }
 
-    fn create_block_reader(
+    // returns the projected columns stored in the files, and the filler of the projected partition columns
+    fn get_projection(
         &self,
-        ctx: &Arc<dyn TableContext>,
         push_downs: &Option<Extras>,
-    ) -> Result<Arc<HiveParquetBlockReader>> {
+    ) -> Result<(Vec<usize>, Option<HivePartitionFiller>)> {
         let projection = if let Some(Extras {
             projection: Some(prj),
             ..
@@ -208,10 +242,7 @@ impl HiveTable {
             None
         };
 
-        let operator = ctx.get_storage_operator()?;
-        let table_schema = self.table_info.schema();
-        // todo, support csv, orc format
-        HiveParquetBlockReader::create(operator, table_schema, projection, hive_partition_filler)
+        Ok((projection, hive_partition_filler))
     }
 
     fn get_column_schemas(&self, columns: Vec<String>) -> Result<Arc<DataSchema>> {
@@ -231,7 +262,7 @@ impl HiveTable {
         partition_keys: Vec<String>,
         filter_expressions: Vec<LegacyExpression>,
     ) -> Result<Vec<(String, Option<String>)>> {
-        let hive_catalog = ctx.get_catalog(CATALOG_HIVE)?;
+        let hive_catalog = ctx.get_catalog(self.table_info.catalog())?;
         let hive_catalog = hive_catalog.as_any().downcast_ref::<HiveCatalog>().unwrap();
 
         // todo may use get_partition_names_ps to filter
@@ -340,7 +371,12 @@ impl HiveTable {
         let dirs = self.get_query_locations(ctx.clone(), &push_downs).await?;
         let all_files = self.list_files_from_dirs(ctx.clone(), dirs).await?;
 
-        let splitter = HiveFileSplitter::create(128 * 1024 * 1024_u64);
+        // the footer of an orc file is at its end, each split would read the whole file
+        let min_split_size = match self.table_options.format {
+            HiveFileFormat::Parquet => 128 * 1024 * 1024_u64,
+            HiveFileFormat::Orc => u64::MAX,
+        };
+        let splitter = HiveFileSplitter::create(min_split_size);
         let partitions = splitter.get_splits(all_files);
 
         tracing::info!("read partition, elapsed:{:?}", start.elapsed());
@@ -356,7 +392,7 @@ impl Table for HiveTable {
     }
 
     fn as_any(&self) -> &(dyn std::any::Any + 'static) {
-        todo!()
+        self
     }
 
     fn get_table_info(&self) -> &TableInfo {
@@ -14,21 +14,64 @@
 
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
+use std::str::FromStr;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
 
 pub const PARTITION_KEYS: &str = "partition_keys";
 pub const LOCATION: &str = "location";
+pub const FORMAT: &str = "format";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HiveFileFormat {
+    Parquet,
+    Orc,
+}
+
+impl HiveFileFormat {
+    // input format of the storage descriptor, such as: org.apache.hadoop.hive.ql.io.orc.OrcInputFormat
+    pub fn from_input_format(input_format: &str) -> Self {
+        if input_format.to_lowercase().contains("orc") {
+            HiveFileFormat::Orc
+        } else {
+            HiveFileFormat::Parquet
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HiveFileFormat::Parquet => "parquet",
+            HiveFileFormat::Orc => "orc",
+        }
+    }
+}
+
+impl FromStr for HiveFileFormat {
+    type Err = ErrorCode;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "parquet" => Ok(HiveFileFormat::Parquet),
+            "orc" => Ok(HiveFileFormat::Orc),
+            _ => Err(ErrorCode::UnexpectedError(format!(
+                "Hive engine table with unknown format {}",
+                s
+            ))),
+        }
+    }
+}
 
 // represents hive table schema info
 //
 // partition_keys,  hive partition keys, such as:  "p_date", "p_hour"
 // location,  hive table location, such as: hdfs://namenode:8020/user/hive/warehouse/a.db/b.table/
+// format,  format of the data files, parquet or orc
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HiveTableOptions {
     pub partition_keys: Option<Vec<String>>,
     pub location: Option<String>,
+    pub format: HiveFileFormat,
 }
 
 impl From<HiveTableOptions> for BTreeMap<String, String> {
@@ -42,6 +85,7 @@ impl From<HiveTableOptions> for BTreeMap<String, String> {
         options
             .location
             .map(|v| map.insert(LOCATION.to_string(), v));
+        map.insert(FORMAT.to_string(), options.format.as_str().to_string());
         map
     }
 }
@@ -61,9 +105,15 @@ impl TryFrom<&BTreeMap<String, String>> for HiveTableOptions {
             .get(LOCATION)
             .ok_or_else(|| ErrorCode::UnexpectedError("Hive engine table missing location key"))?
             .clone();
+        // tables created before the format was recorded are all parquet
+        let format = match options.get(FORMAT) {
+            Some(format) => format.parse()?,
+            None => HiveFileFormat::Parquet,
+        };
         let options = HiveTableOptions {
             partition_keys,
             location: Some(location),
+            format,
         };
         Ok(options)
     }
@@ -73,6 +123,7 @@ impl TryFrom<&BTreeMap<String, String>> for HiveTableOptions {
 mod tests {
     use std::collections::BTreeMap;
 
+    use super::HiveFileFormat;
     use super::HiveTableOptions;
 
     fn do_test_hive_table_options(hive_table_options: HiveTableOptions) {
@@ -86,6 +137,7 @@ mod tests {
         let hive_table_options = HiveTableOptions {
             partition_keys: Some(vec!["a".to_string(), "b".to_string()]),
             location: Some("test".to_string()),
+            format: HiveFileFormat::Parquet,
         };
 
         do_test_hive_table_options(hive_table_options);
@@ -93,7 +145,15 @@ mod tests {
         let empty = HiveTableOptions {
             partition_keys: None,
             location: Some("test".to_string()),
+            format: HiveFileFormat::Parquet,
         };
         do_test_hive_table_options(empty);
+
+        let orc = HiveTableOptions {
+            partition_keys: None,
+            location: Some("test".to_string()),
+            format: HiveFileFormat::Orc,
+        };
+        do_test_hive_table_options(orc);
     }
 }
@@ -19,6 +19,8 @@ mod hive_blocks;
 mod hive_catalog;
 mod hive_database;
 mod hive_file_splitter;
+mod hive_orc_block_reader;
+mod hive_orc_table_source;
 mod hive_parquet_block_reader;
 mod hive_partition;
 mod hive_partition_filler;
@@ -32,7 +34,13 @@ pub const CATALOG_HIVE: &str = "hive";
 pub use hive_blocks::HiveBlocks;
 pub use hive_catalog::HiveCatalog;
 pub use hive_file_splitter::HiveFileSplitter;
+pub use hive_orc_block_reader::HiveOrcBlockReader;
+pub use hive_orc_table_source::HiveOrcTableSource;
+pub use hive_parquet_block_reader::DataBlockDeserializer;
+pub use hive_parquet_block_reader::HiveParquetBlockReader;
 pub use hive_partition::HivePartInfo;
 pub use hive_partition_filler::HivePartitionFiller;
+pub use hive_table::convert_hdfs_path;
 pub use hive_table::HiveFileInfo;
 pub use hive_table::HiveTable;
+pub use hive_table_source::HiveTableSource;
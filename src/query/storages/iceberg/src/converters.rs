@@ -0,0 +1,170 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::chrono::Utc;
+use common_datavalues::remove_nullable;
+use common_datavalues::type_primitive::Float32Type;
+use common_datavalues::type_primitive::Float64Type;
+use common_datavalues::type_primitive::Int32Type;
+use common_datavalues::type_primitive::Int64Type;
+use common_datavalues::type_string::StringType;
+use common_datavalues::ArrayType;
+use common_datavalues::BooleanType;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_datavalues::DateType;
+use common_datavalues::NullableType;
+use common_datavalues::TimestampType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::DatabaseIdent;
+use common_meta_app::schema::DatabaseInfo;
+use common_meta_app::schema::DatabaseMeta;
+use common_meta_app::schema::DatabaseNameIdent;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+
+use crate::iceberg_database::IcebergDatabase;
+use crate::iceberg_database::ICEBERG_DATABASE_ENGINE;
+use crate::iceberg_metadata::Schema;
+use crate::iceberg_metadata::TableMetadata;
+use crate::iceberg_table::ICEBERG_TABLE_ENGINE;
+use crate::iceberg_table_options::IcebergTableOptions;
+
+pub fn into_database(db_name: &str) -> IcebergDatabase {
+    IcebergDatabase {
+        database_info: DatabaseInfo {
+            ident: DatabaseIdent { db_id: 0, seq: 0 },
+            name_ident: DatabaseNameIdent {
+                tenant: "TODO".to_owned(),
+                db_name: db_name.to_owned(),
+            },
+            meta: DatabaseMeta {
+                engine: ICEBERG_DATABASE_ENGINE.to_owned(),
+                created_on: Utc::now(),
+                ..Default::default()
+            },
+        },
+    }
+}
+
+pub fn try_into_table_info(
+    catalog_name: &str,
+    db_name: &str,
+    table_name: &str,
+    table_options: IcebergTableOptions,
+    metadata: &TableMetadata,
+) -> Result<TableInfo> {
+    let schema = Arc::new(try_into_schema(metadata.current_schema()?)?);
+
+    let meta = TableMeta {
+        schema,
+        catalog: catalog_name.to_string(),
+        engine: ICEBERG_TABLE_ENGINE.to_owned(),
+        engine_options: table_options.into(),
+        created_on: Utc::now(),
+        ..Default::default()
+    };
+
+    Ok(TableInfo {
+        ident: TableIdent {
+            table_id: 0,
+            seq: 0,
+        },
+        desc: format!("{}.{}", db_name, table_name),
+        name: table_name.to_owned(),
+        meta,
+    })
+}
+
+fn try_into_schema(schema: &Schema) -> Result<DataSchema> {
+    let mut fields = Vec::with_capacity(schema.fields.len());
+    for field in &schema.fields {
+        let data_type = try_from_field_type(&field.field_type)?;
+        let data_type = match field.required {
+            true => data_type,
+            false => NullableType::new_impl(data_type),
+        };
+        fields.push(DataField::new(&field.name, data_type));
+    }
+    Ok(DataSchema::new(fields))
+}
+
+// https://iceberg.apache.org/spec/#schemas-and-data-types
+fn try_from_field_type(field_type: &serde_json::Value) -> Result<DataTypeImpl> {
+    if let Some(nested) = field_type.as_object() {
+        if nested.get("type").and_then(|t| t.as_str()) == Some("list") {
+            if let Some(element) = nested.get("element") {
+                let element_type = try_from_field_type(element)?;
+                let element_required = nested
+                    .get("element-required")
+                    .and_then(|r| r.as_bool())
+                    .unwrap_or(false);
+                let element_type = match element_required {
+                    true => element_type,
+                    false => NullableType::new_impl(element_type),
+                };
+                return Ok(DataTypeImpl::Array(ArrayType::create(element_type)));
+            }
+        }
+    }
+
+    // TODO decimal, fixed, uuid, time, struct and map are not supported yet
+    match field_type.as_str() {
+        Some("boolean") => Ok(DataTypeImpl::Boolean(BooleanType::default())),
+        Some("int") => Ok(DataTypeImpl::Int32(Int32Type::default())),
+        Some("long") => Ok(DataTypeImpl::Int64(Int64Type::default())),
+        Some("float") => Ok(DataTypeImpl::Float32(Float32Type::default())),
+        Some("double") => Ok(DataTypeImpl::Float64(Float64Type::default())),
+        Some("date") => Ok(DataTypeImpl::Date(DateType::default())),
+        Some("timestamp") | Some("timestamptz") => {
+            Ok(DataTypeImpl::Timestamp(TimestampType::create(6)))
+        }
+        Some("string") | Some("binary") => Ok(DataTypeImpl::String(StringType::default())),
+        _ => Err(ErrorCode::IllegalDataType(format!(
+            "unsupported iceberg data type [{}]",
+            field_type
+        ))),
+    }
+}
+
+/// Decode a lower or upper bound of column, stored in iceberg's single-value serialization.
+///
+/// See https://iceberg.apache.org/spec/#appendix-d-single-value-serialization
+pub fn try_into_data_value(data_type: &DataTypeImpl, bound: &[u8]) -> Option<DataValue> {
+    match remove_nullable(data_type) {
+        DataTypeImpl::Boolean(_) => bound.first().map(|v| DataValue::Boolean(*v != 0)),
+        DataTypeImpl::Int32(_) | DataTypeImpl::Date(_) => {
+            let v = i32::from_le_bytes(bound.try_into().ok()?);
+            Some(DataValue::Int64(v as i64))
+        }
+        DataTypeImpl::Int64(_) | DataTypeImpl::Timestamp(_) => {
+            Some(DataValue::Int64(i64::from_le_bytes(bound.try_into().ok()?)))
+        }
+        DataTypeImpl::Float32(_) => {
+            let v = f32::from_le_bytes(bound.try_into().ok()?);
+            Some(DataValue::Float64(v as f64))
+        }
+        DataTypeImpl::Float64(_) => Some(DataValue::Float64(f64::from_le_bytes(
+            bound.try_into().ok()?,
+        ))),
+        DataTypeImpl::String(_) => Some(DataValue::String(bound.to_vec())),
+        _ => None,
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_catalog::catalog::Catalog;
+use common_catalog::catalog::StorageDescription;
+use common_catalog::database::Database;
+use common_catalog::table::Table;
+use common_catalog::table_args::TableArgs;
+use common_catalog::table_function::TableFunction;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::CountTablesReply;
+use common_meta_app::schema::CountTablesReq;
+use common_meta_app::schema::CreateDatabaseReply;
+use common_meta_app::schema::CreateDatabaseReq;
+use common_meta_app::schema::CreateTableReq;
+use common_meta_app::schema::DropDatabaseReq;
+use common_meta_app::schema::DropTableReply;
+use common_meta_app::schema::DropTableReq;
+use common_meta_app::schema::GetTableCopiedFileReply;
+use common_meta_app::schema::GetTableCopiedFileReq;
+use common_meta_app::schema::RenameDatabaseReply;
+use common_meta_app::schema::RenameDatabaseReq;
+use common_meta_app::schema::RenameTableReply;
+use common_meta_app::schema::RenameTableReq;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::TruncateTableReply;
+use common_meta_app::schema::TruncateTableReq;
+use common_meta_app::schema::UndropDatabaseReply;
+use common_meta_app::schema::UndropDatabaseReq;
+use common_meta_app::schema::UndropTableReply;
+use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateTableMetaReply;
+use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_app::schema::UpsertTableCopiedFileReply;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
+use common_meta_app::schema::UpsertTableOptionReply;
+use common_meta_app::schema::UpsertTableOptionReq;
+use common_meta_types::MetaId;
+use futures::TryStreamExt;
+use opendal::ObjectMode;
+use opendal::Operator;
+
+use crate::converters::into_database;
+use crate::iceberg_table::IcebergTable;
+
+/// Catalog of the iceberg tables stored in a warehouse directory of the storage.
+///
+/// Tables are laid out like the hadoop catalog of iceberg does, each database is a directory of
+/// the warehouse, and each table a directory of its database: `<warehouse>/<db>/<table>/`.
+#[derive(Clone)]
+pub struct IcebergCatalog {
+    /// name of the catalog, which the tables of it refer to
+    name: String,
+    operator: Operator,
+    /// directory of the warehouse in the storage, ends with '/'
+    warehouse: String,
+}
+
+impl IcebergCatalog {
+    pub fn try_create(name: &str, operator: Operator, warehouse: &str) -> Result<IcebergCatalog> {
+        let warehouse = match warehouse.trim_matches('/') {
+            "" => "".to_string(),
+            warehouse => format!("{}/", warehouse),
+        };
+        Ok(IcebergCatalog {
+            name: name.to_string(),
+            operator,
+            warehouse,
+        })
+    }
+
+    fn database_location(&self, db_name: &str) -> String {
+        format!("{}{}/", self.warehouse, db_name)
+    }
+
+    fn table_location(&self, db_name: &str, table_name: &str) -> String {
+        format!("{}{}/{}/", self.warehouse, db_name, table_name)
+    }
+
+    /// Names of the sub directories of `location`.
+    async fn list_dirs(&self, location: &str) -> Result<Vec<String>> {
+        let mut dirs = vec![];
+        let mut entries = self.operator.object(location).list().await?;
+        while let Some(entry) = entries.try_next().await? {
+            if entry.mode() != ObjectMode::DIR {
+                continue;
+            }
+            let name = entry.path().trim_end_matches('/');
+            let name = name.rsplit('/').next().unwrap_or_default();
+            if !name.is_empty() && !name.starts_with('.') {
+                dirs.push(name.to_string());
+            }
+        }
+        Ok(dirs)
+    }
+}
+
+#[async_trait::async_trait]
+impl Catalog for IcebergCatalog {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn is_case_insensitive_db(&self, _: &str) -> bool {
+        false
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_database(&self, _tenant: &str, db_name: &str) -> Result<Arc<dyn Database>> {
+        let dbs = self.list_dirs(&self.warehouse).await?;
+        if !dbs.iter().any(|db| db == db_name) {
+            return Err(ErrorCode::UnknownDatabase(format!(
+                "Unknown database '{}'",
+                db_name
+            )));
+        }
+        Ok(Arc::new(into_database(db_name)))
+    }
+
+    // Get all the databases.
+    async fn list_databases(&self, _tenant: &str) -> Result<Vec<Arc<dyn Database>>> {
+        let dbs = self.list_dirs(&self.warehouse).await?;
+        Ok(dbs
+            .iter()
+            .map(|db| Arc::new(into_database(db)) as Arc<dyn Database>)
+            .collect())
+    }
+
+    // Operation with database.
+    async fn create_database(&self, _req: CreateDatabaseReq) -> Result<CreateDatabaseReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot create database in ICEBERG catalog",
+        ))
+    }
+
+    async fn drop_database(&self, _req: DropDatabaseReq) -> Result<()> {
+        Err(ErrorCode::UnImplement(
+            "Cannot drop database in ICEBERG catalog",
+        ))
+    }
+
+    async fn undrop_database(&self, _req: UndropDatabaseReq) -> Result<UndropDatabaseReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot undrop database in ICEBERG catalog",
+        ))
+    }
+
+    async fn rename_database(&self, _req: RenameDatabaseReq) -> Result<RenameDatabaseReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot rename database in ICEBERG catalog",
+        ))
+    }
+
+    fn get_table_by_info(&self, table_info: &TableInfo) -> Result<Arc<dyn Table>> {
+        let res: Arc<dyn Table> = Arc::new(IcebergTable::try_create(table_info.clone())?);
+        Ok(res)
+    }
+
+    async fn get_table_meta_by_id(
+        &self,
+        _table_id: MetaId,
+    ) -> Result<(TableIdent, Arc<TableMeta>)> {
+        Err(ErrorCode::UnImplement(
+            "Cannot get table by id in ICEBERG catalog",
+        ))
+    }
+
+    // Get one table by db and table name.
+    #[tracing::instrument(level = "info", skip(self))]
+    async fn get_table(
+        &self,
+        _tenant: &str,
+        db_name: &str,
+        table_name: &str,
+    ) -> Result<Arc<dyn Table>> {
+        let location = self.table_location(db_name, table_name);
+        let table =
+            IcebergTable::try_load(&self.operator, &self.name, db_name, table_name, location)
+                .await?;
+        Ok(Arc::new(table))
+    }
+
+    async fn list_tables(&self, _tenant: &str, db_name: &str) -> Result<Vec<Arc<dyn Table>>> {
+        let table_names = self.list_dirs(&self.database_location(db_name)).await?;
+        let mut tables: Vec<Arc<dyn Table>> = Vec::with_capacity(table_names.len());
+        for table_name in table_names {
+            let location = self.table_location(db_name, &table_name);
+            let table =
+                IcebergTable::try_load(&self.operator, &self.name, db_name, &table_name, location)
+                    .await;
+            match table {
+                Ok(table) => tables.push(Arc::new(table)),
+                // directories without iceberg metadata are not tables
+                Err(e) if e.code() == ErrorCode::UnknownTableCode() => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(tables)
+    }
+
+    async fn list_tables_history(
+        &self,
+        _tenant: &str,
+        _db_name: &str,
+    ) -> Result<Vec<Arc<dyn Table>>> {
+        Err(ErrorCode::UnImplement(
+            "Cannot list table history in ICEBERG catalog",
+        ))
+    }
+
+    async fn create_table(&self, _req: CreateTableReq) -> Result<()> {
+        Err(ErrorCode::UnImplement(
+            "Cannot create table in ICEBERG catalog",
+        ))
+    }
+
+    async fn drop_table(&self, _req: DropTableReq) -> Result<DropTableReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot drop table in ICEBERG catalog",
+        ))
+    }
+
+    async fn undrop_table(&self, _req: UndropTableReq) -> Result<UndropTableReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot undrop table in ICEBERG catalog",
+        ))
+    }
+
+    async fn rename_table(&self, _req: RenameTableReq) -> Result<RenameTableReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot rename table in ICEBERG catalog",
+        ))
+    }
+
+    // Check a db.table is exists or not.
+    async fn exists_table(&self, tenant: &str, db_name: &str, table_name: &str) -> Result<bool> {
+        match self.get_table(tenant, db_name, table_name).await {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                if err.code() == ErrorCode::UnknownTableCode() {
+                    Ok(false)
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    async fn upsert_table_option(
+        &self,
+        _req: UpsertTableOptionReq,
+    ) -> Result<UpsertTableOptionReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot upsert table option in ICEBERG catalog",
+        ))
+    }
+
+    async fn update_table_meta(&self, _req: UpdateTableMetaReq) -> Result<UpdateTableMetaReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot update table meta in ICEBERG catalog",
+        ))
+    }
+
+    async fn get_table_copied_file_info(
+        &self,
+        _req: GetTableCopiedFileReq,
+    ) -> Result<GetTableCopiedFileReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot get table copied file info in ICEBERG catalog",
+        ))
+    }
+
+    async fn upsert_table_copied_file_info(
+        &self,
+        _req: UpsertTableCopiedFileReq,
+    ) -> Result<UpsertTableCopiedFileReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot upsert table copied file info in ICEBERG catalog",
+        ))
+    }
+
+    async fn truncate_table(&self, _req: TruncateTableReq) -> Result<TruncateTableReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot truncate table in ICEBERG catalog",
+        ))
+    }
+
+    async fn count_tables(&self, _req: CountTablesReq) -> Result<CountTablesReply> {
+        Err(ErrorCode::UnImplement(
+            "Cannot count tables in ICEBERG catalog",
+        ))
+    }
+
+    /// Table function
+
+    // Get function by name.
+    fn get_table_function(
+        &self,
+        _func_name: &str,
+        _tbl_args: TableArgs,
+    ) -> Result<Arc<dyn TableFunction>> {
+        Err(ErrorCode::UnImplement(
+            "Cannot get table function in ICEBERG catalog",
+        ))
+    }
+
+    // Get table engines
+    fn get_table_engines(&self) -> Vec<StorageDescription> {
+        vec![]
+    }
+}
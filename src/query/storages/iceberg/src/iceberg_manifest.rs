@@ -0,0 +1,229 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_formats::avro::Reader;
+use common_formats::avro::Value;
+use opendal::Operator;
+
+/// Status of a manifest entry whose data file is removed by the snapshot
+const ENTRY_STATUS_DELETED: i32 = 2;
+
+type Record = Vec<(String, Value)>;
+
+/// An entry of the manifest list of a snapshot.
+///
+/// See https://iceberg.apache.org/spec/#manifest-lists
+#[derive(Debug, Clone)]
+pub struct ManifestFile {
+    pub manifest_path: String,
+    pub partition_spec_id: i32,
+    /// 0 if the manifest tracks data files, 1 if it tracks delete files
+    pub content: i32,
+    /// Summaries of the partition fields of the data files tracked by the manifest
+    pub partitions: Vec<FieldSummary>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldSummary {
+    pub contains_null: bool,
+    pub lower_bound: Option<Vec<u8>>,
+    pub upper_bound: Option<Vec<u8>>,
+}
+
+/// A live data file tracked by a manifest.
+///
+/// See https://iceberg.apache.org/spec/#manifests
+#[derive(Debug, Clone)]
+pub struct DataFile {
+    /// 0 for data files, 1 for position deletes and 2 for equality deletes
+    pub content: i32,
+    pub file_path: String,
+    pub file_format: String,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+    /// Statistics of the columns, keyed by field id
+    pub null_value_counts: HashMap<i32, i64>,
+    pub lower_bounds: HashMap<i32, Vec<u8>>,
+    pub upper_bounds: HashMap<i32, Vec<u8>>,
+}
+
+impl ManifestFile {
+    /// Format version 1 snapshots may list their manifests directly, without any summary.
+    pub fn create(manifest_path: String) -> ManifestFile {
+        ManifestFile {
+            manifest_path,
+            partition_spec_id: 0,
+            content: 0,
+            partitions: vec![],
+        }
+    }
+
+    pub async fn read_list(operator: &Operator, location: &str) -> Result<Vec<ManifestFile>> {
+        let data = operator.object(location).read().await?;
+        read_records(&data)?
+            .iter()
+            .map(|record| {
+                let partitions = match field(record, "partitions") {
+                    Some(Value::Array(summaries)) => summaries
+                        .iter()
+                        .map(|summary| match unwrap_union(summary) {
+                            Value::Record(summary) => Ok(FieldSummary {
+                                contains_null: get_bool(summary, "contains_null")?,
+                                lower_bound: get_optional_bytes(summary, "lower_bound"),
+                                upper_bound: get_optional_bytes(summary, "upper_bound"),
+                            }),
+                            _ => Err(bad_manifest("partitions")),
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                    _ => vec![],
+                };
+
+                Ok(ManifestFile {
+                    manifest_path: get_string(record, "manifest_path")?,
+                    partition_spec_id: get_int(record, "partition_spec_id").unwrap_or(0),
+                    content: get_int(record, "content").unwrap_or(0),
+                    partitions,
+                })
+            })
+            .collect()
+    }
+}
+
+impl DataFile {
+    /// Read the data files tracked by a manifest, ignoring the deleted entries.
+    pub async fn read_manifest(operator: &Operator, location: &str) -> Result<Vec<DataFile>> {
+        let data = operator.object(location).read().await?;
+        let mut data_files = vec![];
+        for entry in read_records(&data)? {
+            if get_int(&entry, "status")? == ENTRY_STATUS_DELETED {
+                continue;
+            }
+
+            let data_file = match field(&entry, "data_file") {
+                Some(Value::Record(data_file)) => data_file,
+                _ => return Err(bad_manifest("data_file")),
+            };
+            data_files.push(DataFile {
+                content: get_int(data_file, "content").unwrap_or(0),
+                file_path: get_string(data_file, "file_path")?,
+                file_format: get_string(data_file, "file_format")?,
+                record_count: get_long(data_file, "record_count")?,
+                file_size_in_bytes: get_long(data_file, "file_size_in_bytes")?,
+                null_value_counts: get_map(data_file, "null_value_counts", |v| match v {
+                    Value::Long(v) => Some(*v),
+                    _ => None,
+                }),
+                lower_bounds: get_map(data_file, "lower_bounds", bytes),
+                upper_bounds: get_map(data_file, "upper_bounds", bytes),
+            });
+        }
+        Ok(data_files)
+    }
+}
+
+fn read_records(data: &[u8]) -> Result<Vec<Record>> {
+    Reader::new(data)?
+        .map(|value| match value? {
+            Value::Record(record) => Ok(record),
+            _ => Err(ErrorCode::BadBytes(
+                "iceberg manifest entry is not a record",
+            )),
+        })
+        .collect()
+}
+
+fn bad_manifest(name: &str) -> ErrorCode {
+    ErrorCode::BadBytes(format!("invalid field {} in iceberg manifest", name))
+}
+
+fn unwrap_union(value: &Value) -> &Value {
+    match value {
+        Value::Union(_, value) => value.as_ref(),
+        value => value,
+    }
+}
+
+/// Value of a field of the record, `None` if it is missing or null.
+fn field<'a>(record: &'a [(String, Value)], name: &str) -> Option<&'a Value> {
+    record
+        .iter()
+        .find(|(field_name, _)| field_name == name)
+        .map(|(_, value)| unwrap_union(value))
+        .filter(|value| !matches!(value, Value::Null))
+}
+
+fn get_string(record: &[(String, Value)], name: &str) -> Result<String> {
+    match field(record, name) {
+        Some(Value::String(v)) | Some(Value::Enum(_, v)) => Ok(v.clone()),
+        _ => Err(bad_manifest(name)),
+    }
+}
+
+fn get_int(record: &[(String, Value)], name: &str) -> Result<i32> {
+    match field(record, name) {
+        Some(Value::Int(v)) => Ok(*v),
+        _ => Err(bad_manifest(name)),
+    }
+}
+
+fn get_long(record: &[(String, Value)], name: &str) -> Result<i64> {
+    match field(record, name) {
+        Some(Value::Long(v)) => Ok(*v),
+        Some(Value::Int(v)) => Ok(*v as i64),
+        _ => Err(bad_manifest(name)),
+    }
+}
+
+fn get_bool(record: &[(String, Value)], name: &str) -> Result<bool> {
+    match field(record, name) {
+        Some(Value::Boolean(v)) => Ok(*v),
+        _ => Err(bad_manifest(name)),
+    }
+}
+
+fn get_optional_bytes(record: &[(String, Value)], name: &str) -> Option<Vec<u8>> {
+    field(record, name).and_then(bytes)
+}
+
+fn bytes(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::Bytes(v) | Value::Fixed(_, v) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+/// Maps keyed by field ids are stored as arrays of key-value records.
+fn get_map<T>(
+    record: &[(String, Value)],
+    name: &str,
+    f: impl Fn(&Value) -> Option<T>,
+) -> HashMap<i32, T> {
+    let mut map = HashMap::new();
+    if let Some(Value::Array(entries)) = field(record, name) {
+        for entry in entries {
+            if let Value::Record(entry) = unwrap_union(entry) {
+                let key = field(entry, "key");
+                let value = field(entry, "value").and_then(&f);
+                if let (Some(Value::Int(key)), Some(value)) = (key, value) {
+                    map.insert(*key, value);
+                }
+            }
+        }
+    }
+    map
+}
@@ -0,0 +1,170 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+use opendal::Operator;
+use serde::Deserialize;
+
+/// Table metadata of an iceberg table, as stored in `metadata/v<N>.metadata.json`.
+///
+/// Both format version 1 and 2 are supported, fields only present in one of them are optional.
+/// See https://iceberg.apache.org/spec/#table-metadata
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableMetadata {
+    pub format_version: i32,
+    /// Base location of the table, data and metadata files are usually stored under it
+    pub location: String,
+    #[serde(default)]
+    pub current_schema_id: Option<i32>,
+    #[serde(default)]
+    schema: Option<Schema>,
+    #[serde(default)]
+    schemas: Vec<Schema>,
+    #[serde(default)]
+    pub default_spec_id: Option<i32>,
+    #[serde(default)]
+    partition_spec: Vec<PartitionField>,
+    #[serde(default)]
+    partition_specs: Vec<PartitionSpec>,
+    #[serde(default)]
+    pub current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Schema {
+    #[serde(default)]
+    pub schema_id: i32,
+    pub fields: Vec<NestedField>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct NestedField {
+    pub id: i32,
+    pub name: String,
+    pub required: bool,
+    /// Either the name of a primitive type, or an object describing a nested type
+    #[serde(rename = "type")]
+    pub field_type: serde_json::Value,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionSpec {
+    pub spec_id: i32,
+    pub fields: Vec<PartitionField>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct PartitionField {
+    pub source_id: i32,
+    pub name: String,
+    pub transform: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct Snapshot {
+    pub snapshot_id: i64,
+    #[serde(default)]
+    pub parent_snapshot_id: Option<i64>,
+    pub timestamp_ms: i64,
+    /// Location of the manifest list, required since format version 2
+    #[serde(default)]
+    pub manifest_list: Option<String>,
+    /// Locations of the manifests, only used by format version 1 if there is no manifest list
+    #[serde(default)]
+    pub manifests: Vec<String>,
+    #[serde(default)]
+    pub summary: BTreeMap<String, String>,
+}
+
+impl TableMetadata {
+    pub async fn load(operator: &Operator, location: &str) -> Result<TableMetadata> {
+        let data = operator.object(location).read().await?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Location of the current metadata file of the table stored in `table_dir`.
+    ///
+    /// The version is read from `metadata/version-hint.text` if there is one, otherwise the
+    /// metadata file with the highest version is picked.
+    pub async fn current_location(operator: &Operator, table_dir: &str) -> Result<String> {
+        let metadata_dir = format!("{}metadata/", table_dir);
+        let hint = operator.object(&format!("{}version-hint.text", metadata_dir));
+        let version = match hint.read().await {
+            Ok(data) => String::from_utf8(data)?.trim().parse::<u64>().ok(),
+            Err(_) => None,
+        };
+
+        let version = match version {
+            Some(version) => version,
+            None => {
+                let mut latest = None;
+                let mut entries = operator.object(&metadata_dir).list().await?;
+                while let Some(entry) = entries.try_next().await? {
+                    let name = entry.path().rsplit('/').next().unwrap_or_default();
+                    let version = name
+                        .strip_prefix('v')
+                        .and_then(|name| name.strip_suffix(".metadata.json"))
+                        .and_then(|version| version.parse::<u64>().ok());
+                    latest = latest.max(version);
+                }
+                latest.ok_or_else(|| {
+                    ErrorCode::UnknownTable(format!("no iceberg metadata found in {}", table_dir))
+                })?
+            }
+        };
+
+        Ok(format!("{}v{}.metadata.json", metadata_dir, version))
+    }
+
+    pub fn current_schema(&self) -> Result<&Schema> {
+        let schema = match self.current_schema_id {
+            Some(schema_id) => self.schemas.iter().find(|s| s.schema_id == schema_id),
+            None => self.schema.as_ref(),
+        };
+        schema.ok_or_else(|| {
+            ErrorCode::TableInfoError(format!(
+                "current schema of iceberg table {} not found",
+                self.location
+            ))
+        })
+    }
+
+    /// Partition fields of the given spec, format version 1 may only have a single spec.
+    pub fn partition_fields(&self, spec_id: i32) -> &[PartitionField] {
+        match self.partition_specs.iter().find(|s| s.spec_id == spec_id) {
+            Some(spec) => &spec.fields,
+            None => &self.partition_spec,
+        }
+    }
+
+    pub fn current_snapshot(&self) -> Option<&Snapshot> {
+        self.current_snapshot_id
+            .and_then(|snapshot_id| self.snapshot(snapshot_id))
+    }
+
+    pub fn snapshot(&self, snapshot_id: i64) -> Option<&Snapshot> {
+        self.snapshots.iter().find(|s| s.snapshot_id == snapshot_id)
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_fuse_meta::meta::ColumnStatistics;
+use common_fuse_meta::meta::StatisticsOfColumns;
+use common_legacy_expression::LegacyExpression;
+use common_storages_index::RangeFilter;
+
+use crate::converters::try_into_data_value;
+use crate::iceberg_manifest::DataFile;
+use crate::iceberg_manifest::ManifestFile;
+use crate::iceberg_metadata::PartitionField;
+use crate::iceberg_metadata::Schema;
+
+/// Prune the manifests and data files of an iceberg table, by the filters pushed down.
+///
+/// Manifests are pruned by the summaries of their identity partition fields, data files by the
+/// lower and upper bounds of their columns.
+pub struct IcebergPruner {
+    schema: DataSchemaRef,
+    /// Index in the table schema of the column of each iceberg field id
+    columns: HashMap<i32, usize>,
+    range_filter: Option<RangeFilter>,
+}
+
+impl IcebergPruner {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        filters: &[LegacyExpression],
+        schema: DataSchemaRef,
+        iceberg_schema: &Schema,
+    ) -> Result<Self> {
+        let columns = iceberg_schema
+            .fields
+            .iter()
+            .filter_map(|field| Some((field.id, schema.index_of(&field.name).ok()?)))
+            .collect();
+        let range_filter = match filters.is_empty() {
+            true => None,
+            false => Some(RangeFilter::try_create(ctx, filters, schema.clone())?),
+        };
+
+        Ok(IcebergPruner {
+            schema,
+            columns,
+            range_filter,
+        })
+    }
+
+    pub fn should_keep_manifest(
+        &self,
+        manifest: &ManifestFile,
+        partition_fields: &[PartitionField],
+    ) -> bool {
+        if self.range_filter.is_none() {
+            return true;
+        }
+
+        let mut stats = StatisticsOfColumns::new();
+        for (field, summary) in partition_fields.iter().zip(manifest.partitions.iter()) {
+            // values of other transforms are not comparable with the values of their columns
+            if field.transform != "identity" {
+                continue;
+            }
+            let bounds = (&summary.lower_bound, &summary.upper_bound);
+            if let (Some(lower), Some(upper)) = bounds {
+                let null_count = summary.contains_null as u64;
+                self.add_column_stats(&mut stats, field.source_id, lower, upper, null_count);
+            }
+        }
+
+        // the number of rows is unknown, make sure `is_not_null` never prunes the manifest
+        self.eval(&stats, u64::MAX)
+    }
+
+    pub fn should_keep_data_file(&self, data_file: &DataFile) -> bool {
+        if self.range_filter.is_none() {
+            return true;
+        }
+
+        let mut stats = StatisticsOfColumns::new();
+        for (field_id, lower) in &data_file.lower_bounds {
+            let upper = data_file.upper_bounds.get(field_id);
+            let null_count = data_file.null_value_counts.get(field_id);
+            if let (Some(upper), Some(null_count)) = (upper, null_count) {
+                self.add_column_stats(&mut stats, *field_id, lower, upper, *null_count as u64);
+            }
+        }
+
+        self.eval(&stats, data_file.record_count as u64)
+    }
+
+    fn add_column_stats(
+        &self,
+        stats: &mut StatisticsOfColumns,
+        field_id: i32,
+        lower: &[u8],
+        upper: &[u8],
+        null_count: u64,
+    ) {
+        if let Some(index) = self.columns.get(&field_id) {
+            let data_type = self.schema.field(*index).data_type();
+            let min = try_into_data_value(data_type, lower);
+            let max = try_into_data_value(data_type, upper);
+            if let (Some(min), Some(max)) = (min, max) {
+                stats.insert(*index as u32, ColumnStatistics {
+                    min,
+                    max,
+                    null_count,
+                    in_memory_size: 0,
+                });
+            }
+        }
+    }
+
+    fn eval(&self, stats: &StatisticsOfColumns, row_count: u64) -> bool {
+        match &self.range_filter {
+            None => true,
+            Some(range_filter) => match range_filter.eval(stats, row_count) {
+                Ok(keep) => keep,
+                Err(e) => {
+                    // columns without statistics can't be pruned
+                    tracing::debug!("failed to prune iceberg files, keep them. {}", e);
+                    true
+                }
+            },
+        }
+    }
+}
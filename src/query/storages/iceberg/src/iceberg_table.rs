@@ -0,0 +1,343 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use common_catalog::table::Table;
+use common_catalog::table::TableStatistics;
+use common_catalog::table_context::TableContext;
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_legacy_expression::LegacyExpression;
+use common_legacy_planners::Extras;
+use common_legacy_planners::Partitions;
+use common_legacy_planners::Projection;
+use common_legacy_planners::ReadDataSourcePlan;
+use common_legacy_planners::Statistics;
+use common_meta_app::schema::TableInfo;
+use common_pipeline_core::processors::port::OutputPort;
+use common_pipeline_core::Pipeline;
+use common_pipeline_core::SourcePipeBuilder;
+use common_storages_hive::convert_hdfs_path;
+use common_storages_hive::HiveFileInfo;
+use common_storages_hive::HiveFileSplitter;
+use common_storages_hive::HiveParquetBlockReader;
+use common_storages_hive::HiveTableSource;
+use futures::future::try_join_all;
+use opendal::Operator;
+
+use crate::converters::try_into_table_info;
+use crate::iceberg_manifest::DataFile;
+use crate::iceberg_manifest::ManifestFile;
+use crate::iceberg_metadata::Snapshot;
+use crate::iceberg_metadata::TableMetadata;
+use crate::iceberg_pruner::IcebergPruner;
+use crate::iceberg_table_options::IcebergTableOptions;
+
+pub const ICEBERG_TABLE_ENGINE: &str = "iceberg";
+
+/// Content of manifests and data files which hold rows, instead of row-level deletes
+const CONTENT_DATA: i32 = 0;
+
+/// A read-only iceberg table, read at the snapshot which was current when it was loaded.
+pub struct IcebergTable {
+    table_info: TableInfo,
+    table_options: IcebergTableOptions,
+}
+
+impl IcebergTable {
+    pub fn try_create(table_info: TableInfo) -> Result<IcebergTable> {
+        let table_options = table_info.engine_options().try_into()?;
+        Ok(IcebergTable {
+            table_info,
+            table_options,
+        })
+    }
+
+    /// Load the table stored in the directory `location` of the storage, at its current snapshot.
+    pub async fn try_load(
+        operator: &Operator,
+        catalog_name: &str,
+        db_name: &str,
+        table_name: &str,
+        location: String,
+    ) -> Result<IcebergTable> {
+        let metadata_location = TableMetadata::current_location(operator, &location).await?;
+        let metadata = TableMetadata::load(operator, &metadata_location).await?;
+        let table_options = IcebergTableOptions {
+            location,
+            metadata_location,
+            snapshot_id: metadata.current_snapshot().map(|s| s.snapshot_id),
+        };
+        let table_info =
+            try_into_table_info(catalog_name, db_name, table_name, table_options, &metadata)?;
+        Self::try_create(table_info)
+    }
+
+    /// All the snapshots of the table, the one it is read at included.
+    pub async fn list_snapshots(&self, operator: &Operator) -> Result<Vec<Snapshot>> {
+        Ok(self.read_metadata(operator).await?.snapshots)
+    }
+
+    async fn read_metadata(&self, operator: &Operator) -> Result<TableMetadata> {
+        TableMetadata::load(operator, &self.table_options.metadata_location).await
+    }
+
+    // metadata and data files are referenced by absolute uris, the ones under the table location
+    // are resolved from the directory of the table, so that the warehouse can be moved around.
+    fn file_location(&self, metadata: &TableMetadata, uri: &str) -> String {
+        match uri.strip_prefix(metadata.location.as_str()) {
+            Some(path) => format!(
+                "{}{}",
+                self.table_options.location,
+                path.trim_start_matches('/')
+            ),
+            None => convert_hdfs_path(uri, false),
+        }
+    }
+
+    async fn read_manifests(
+        &self,
+        operator: &Operator,
+        metadata: &TableMetadata,
+        snapshot: &Snapshot,
+    ) -> Result<Vec<ManifestFile>> {
+        match &snapshot.manifest_list {
+            Some(manifest_list) => {
+                let location = self.file_location(metadata, manifest_list);
+                ManifestFile::read_list(operator, &location).await
+            }
+            None => Ok(snapshot
+                .manifests
+                .iter()
+                .map(|manifest| ManifestFile::create(manifest.clone()))
+                .collect()),
+        }
+    }
+
+    fn create_block_reader(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        push_downs: &Option<Extras>,
+    ) -> Result<Arc<HiveParquetBlockReader>> {
+        let projection = match push_downs {
+            Some(Extras {
+                projection: Some(Projection::Columns(indices)),
+                ..
+            }) => indices.clone(),
+            Some(Extras {
+                projection: Some(Projection::InnerColumns(_)),
+                ..
+            }) => {
+                return Err(ErrorCode::UnImplement(
+                    "does not support projection inner columns",
+                ));
+            }
+            _ => (0..self.table_info.schema().fields().len()).collect(),
+        };
+
+        let operator = ctx.get_storage_operator()?;
+        HiveParquetBlockReader::create(operator, self.table_info.schema(), projection, None)
+    }
+
+    #[tracing::instrument(level = "info", skip(self, ctx))]
+    async fn do_read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        let start = Instant::now();
+        let snapshot_id = match self.table_options.snapshot_id {
+            Some(snapshot_id) => snapshot_id,
+            None => return Ok((Statistics::default(), vec![])),
+        };
+
+        let operator = ctx.get_storage_operator()?;
+        let metadata = self.read_metadata(&operator).await?;
+        let snapshot = metadata.snapshot(snapshot_id).ok_or_else(|| {
+            ErrorCode::TableInfoError(format!(
+                "snapshot {} of iceberg table {} not found",
+                snapshot_id, self.table_info.desc
+            ))
+        })?;
+
+        let filters = push_downs
+            .as_ref()
+            .map(|p| p.filters.clone())
+            .unwrap_or_default();
+        let pruner = IcebergPruner::try_create(
+            ctx.clone(),
+            &filters,
+            self.table_info.schema(),
+            metadata.current_schema()?,
+        )?;
+
+        let manifests = self
+            .read_manifests(&operator, &metadata, snapshot)
+            .await?
+            .into_iter()
+            .filter(|manifest| {
+                let partition_fields = metadata.partition_fields(manifest.partition_spec_id);
+                pruner.should_keep_manifest(manifest, partition_fields)
+            })
+            .collect::<Vec<_>>();
+        if manifests
+            .iter()
+            .any(|manifest| manifest.content != CONTENT_DATA)
+        {
+            return Err(ErrorCode::UnImplement(format!(
+                "row-level deletes of iceberg table {} are not supported",
+                self.table_info.desc
+            )));
+        }
+
+        let data_files = try_join_all(manifests.iter().map(|manifest| {
+            let location = self.file_location(&metadata, &manifest.manifest_path);
+            let operator = operator.clone();
+            async move { DataFile::read_manifest(&operator, &location).await }
+        }))
+        .await?;
+
+        let mut files_total = 0;
+        let mut read_rows = 0;
+        let mut read_bytes = 0;
+        let mut files = vec![];
+        for data_file in data_files.into_iter().flatten() {
+            if !data_file.file_format.eq_ignore_ascii_case("parquet") {
+                return Err(ErrorCode::UnImplement(format!(
+                    "only support parquet, {} not support",
+                    data_file.file_format
+                )));
+            }
+
+            files_total += 1;
+            if !pruner.should_keep_data_file(&data_file) {
+                continue;
+            }
+
+            read_rows += data_file.record_count as usize;
+            read_bytes += data_file.file_size_in_bytes as usize;
+            files.push(HiveFileInfo::create(
+                self.file_location(&metadata, &data_file.file_path),
+                data_file.file_size_in_bytes as u64,
+            ));
+        }
+
+        let statistics = Statistics::new_estimated(read_rows, read_bytes, files.len(), files_total);
+        let splitter = HiveFileSplitter::create(128 * 1024 * 1024_u64);
+        let partitions = splitter.get_splits(files);
+
+        tracing::info!("read partition, elapsed:{:?}", start.elapsed());
+
+        Ok((statistics, partitions))
+    }
+
+    fn do_read2(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let block_reader = self.create_block_reader(&ctx, &plan.push_downs)?;
+
+        let max_threads = ctx.get_settings().get_max_threads()? as usize;
+        let max_threads = std::cmp::min(plan.parts.len(), max_threads);
+
+        let mut source_builder = SourcePipeBuilder::create();
+        for _ in 0..std::cmp::max(1, max_threads) {
+            let output = OutputPort::create();
+            source_builder.add_source(
+                output.clone(),
+                HiveTableSource::create(ctx.clone(), output, block_reader.clone(), 0)?,
+            );
+        }
+
+        pipeline.add_pipe(source_builder.finalize());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for IcebergTable {
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    fn as_any(&self) -> &(dyn std::any::Any + 'static) {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn benefit_column_prune(&self) -> bool {
+        true
+    }
+
+    fn has_exact_total_row_count(&self) -> bool {
+        false
+    }
+
+    async fn read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<Extras>,
+    ) -> Result<(Statistics, Partitions)> {
+        self.do_read_partitions(ctx, push_downs).await
+    }
+
+    fn table_args(&self) -> Option<Vec<LegacyExpression>> {
+        None
+    }
+
+    fn read2(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        self.do_read2(ctx, plan, pipeline)
+    }
+
+    async fn commit_insertion(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _operations: Vec<DataBlock>,
+        _overwrite: bool,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "commit_insertion operation for table {} is not implemented, table engine is {}",
+            self.name(),
+            self.get_table_info().meta.engine
+        )))
+    }
+
+    async fn truncate(&self, _ctx: Arc<dyn TableContext>, _: bool) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "truncate for table {} is not implemented",
+            self.name()
+        )))
+    }
+
+    async fn optimize(&self, _ctx: Arc<dyn TableContext>, _keep_last_snapshot: bool) -> Result<()> {
+        Ok(())
+    }
+
+    async fn statistics(&self, _ctx: Arc<dyn TableContext>) -> Result<Option<TableStatistics>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+pub const LOCATION: &str = "location";
+pub const METADATA_LOCATION: &str = "metadata_location";
+pub const SNAPSHOT_ID: &str = "snapshot_id";
+
+// represents iceberg table info
+//
+// location,  directory of the table in the storage, such as: warehouse/db/tbl/
+// metadata_location,  metadata file the table is loaded from, such as: warehouse/db/tbl/metadata/v3.metadata.json
+// snapshot_id,  snapshot the table is read at, none if the table has no snapshot yet
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IcebergTableOptions {
+    pub location: String,
+    pub metadata_location: String,
+    pub snapshot_id: Option<i64>,
+}
+
+impl From<IcebergTableOptions> for BTreeMap<String, String> {
+    fn from(options: IcebergTableOptions) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+        map.insert(LOCATION.to_string(), options.location);
+        map.insert(METADATA_LOCATION.to_string(), options.metadata_location);
+        options
+            .snapshot_id
+            .map(|v| map.insert(SNAPSHOT_ID.to_string(), v.to_string()));
+        map
+    }
+}
+
+impl TryFrom<&BTreeMap<String, String>> for IcebergTableOptions {
+    type Error = ErrorCode;
+    fn try_from(options: &BTreeMap<String, String>) -> Result<IcebergTableOptions> {
+        let get = |key: &str| {
+            options.get(key).cloned().ok_or_else(|| {
+                ErrorCode::UnexpectedError(format!("Iceberg engine table missing {} key", key))
+            })
+        };
+
+        let snapshot_id = match options.get(SNAPSHOT_ID) {
+            Some(snapshot_id) => Some(snapshot_id.parse::<i64>()?),
+            None => None,
+        };
+        Ok(IcebergTableOptions {
+            location: get(LOCATION)?,
+            metadata_location: get(METADATA_LOCATION)?,
+            snapshot_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::IcebergTableOptions;
+
+    #[test]
+    fn test_iceberg_table_options() {
+        let options = IcebergTableOptions {
+            location: "warehouse/db/tbl/".to_string(),
+            metadata_location: "warehouse/db/tbl/metadata/v2.metadata.json".to_string(),
+            snapshot_id: Some(3051729675574597004),
+        };
+        let m: BTreeMap<String, String> = options.clone().into();
+        assert_eq!(options, IcebergTableOptions::try_from(&m).unwrap());
+
+        let empty = IcebergTableOptions {
+            snapshot_id: None,
+            ..options
+        };
+        let m: BTreeMap<String, String> = empty.clone().into();
+        assert_eq!(empty, IcebergTableOptions::try_from(&m).unwrap());
+    }
+}
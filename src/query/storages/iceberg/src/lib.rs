@@ -0,0 +1,33 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![deny(unused_crate_dependencies)]
+
+mod converters;
+mod iceberg_catalog;
+mod iceberg_database;
+mod iceberg_manifest;
+mod iceberg_metadata;
+mod iceberg_pruner;
+mod iceberg_table;
+mod iceberg_table_options;
+
+pub const CATALOG_ICEBERG: &str = "iceberg";
+
+pub use iceberg_catalog::IcebergCatalog;
+pub use iceberg_manifest::DataFile;
+pub use iceberg_manifest::ManifestFile;
+pub use iceberg_metadata::Snapshot;
+pub use iceberg_metadata::TableMetadata;
+pub use iceberg_table::IcebergTable;
@@ -0,0 +1,133 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::eval_aggr;
+use common_functions::aggregates::AggregateFunctionFactory;
+
+/// Aggregate functions which can be pre-computed per block and rolled up later.
+pub const AGGREGATING_INDEX_FUNCTIONS: [&str; 4] = ["count", "sum", "min", "max"];
+
+/// An aggregate pre-computed by an aggregating index, `func_name(arg)`.
+///
+/// `arg` is `None` for `count(*)`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AggregatingIndexItem {
+    pub func_name: String,
+    pub arg: Option<String>,
+}
+
+/// Definition of an aggregating index.
+///
+/// For every block of the table, the index stores the rows of
+/// `SELECT <group_by>, <aggregates> FROM <block> GROUP BY <group_by>`,
+/// the aggregate columns are named by [`AggregatingIndexMeta::column_name`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AggregatingIndexMeta {
+    /// Identifies the index files, so that a re-created index never reads stale files.
+    pub id: String,
+    pub name: String,
+    /// The `SELECT` statement the index is defined by.
+    pub query: String,
+    pub group_by: Vec<String>,
+    pub aggregates: Vec<AggregatingIndexItem>,
+}
+
+impl AggregatingIndexMeta {
+    pub fn column_name(index: usize) -> String {
+        format!("_aggregate_{}", index)
+    }
+
+    /// Returns the position of `func_name(arg)` in the aggregates of the index.
+    pub fn find_aggregate(&self, func_name: &str, arg: Option<&str>) -> Option<usize> {
+        self.aggregates
+            .iter()
+            .position(|item| item.func_name == func_name && item.arg.as_deref() == arg)
+    }
+
+    /// Columns of the table the index is computed from.
+    pub fn source_columns(&self) -> Vec<String> {
+        let mut columns = self.group_by.clone();
+        for arg in self.aggregates.iter().filter_map(|item| item.arg.as_ref()) {
+            if !columns.contains(arg) {
+                columns.push(arg.clone());
+            }
+        }
+        columns
+    }
+
+    /// Schema of the index data, the group by columns followed by the aggregate columns.
+    pub fn schema(&self, table_schema: &DataSchema) -> Result<DataSchemaRef> {
+        let factory = AggregateFunctionFactory::instance();
+        let mut fields = Vec::with_capacity(self.group_by.len() + self.aggregates.len());
+        for name in &self.group_by {
+            fields.push(table_schema.field_with_name(name)?.clone());
+        }
+        for (i, item) in self.aggregates.iter().enumerate() {
+            let arguments = match &item.arg {
+                Some(arg) => vec![table_schema.field_with_name(arg)?.clone()],
+                None => vec![],
+            };
+            let func = factory.get(&item.func_name, vec![], arguments)?;
+            fields.push(DataField::new(&Self::column_name(i), func.return_type()?));
+        }
+        Ok(Arc::new(DataSchema::new(fields)))
+    }
+
+    /// Computes the index data of `block`, which must contain the source columns.
+    pub fn aggregate_block(&self, schema: &DataSchemaRef, block: &DataBlock) -> Result<DataBlock> {
+        if block.is_empty() {
+            return Ok(DataBlock::empty_with_schema(schema.clone()));
+        }
+
+        let groups = DataBlock::group_by_blocks(block, &self.group_by)?;
+        let mut results = Vec::with_capacity(groups.len());
+        for group in groups {
+            let num_rows = group.num_rows();
+            let mut columns = Vec::with_capacity(schema.num_fields());
+            for name in &self.group_by {
+                columns.push(group.try_column_by_name(name)?.slice(0, 1));
+            }
+            for item in &self.aggregates {
+                let arguments = match &item.arg {
+                    Some(arg) => {
+                        let field = group.schema().field_with_name(arg)?.clone();
+                        let column = group.try_column_by_name(arg)?.clone();
+                        vec![ColumnWithField::new(column, field)]
+                    }
+                    None => vec![],
+                };
+                columns.push(eval_aggr(&item.func_name, vec![], &arguments, num_rows)?);
+            }
+            results.push(DataBlock::create(schema.clone(), columns));
+        }
+        DataBlock::concat_blocks(&results)
+    }
+}
+
+/// Checks that `func_name` can be used in an aggregating index.
+pub fn check_aggregating_index_function(func_name: &str) -> Result<()> {
+    if AGGREGATING_INDEX_FUNCTIONS.contains(&func_name) {
+        Ok(())
+    } else {
+        Err(ErrorCode::IllegalAggregatingIndexFormat(format!(
+            "Aggregate function {} is not supported by aggregating index, expect one of {:?}",
+            func_name, AGGREGATING_INDEX_FUNCTIONS
+        )))
+    }
+}
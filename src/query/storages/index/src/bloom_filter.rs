@@ -135,6 +135,37 @@ impl BloomFilterIndexer {
         Self::try_create_with_seed(source_data_blocks, seed, ctx)
     }
 
+    /// Create a bloom filter block of the given columns of source data only, or of all the
+    /// applicable columns if `columns` is `None`.
+    pub fn try_create_with_columns(
+        ctx: Arc<dyn TableContext>,
+        source_data_blocks: &[&DataBlock],
+        columns: Option<&[String]>,
+    ) -> Result<Self> {
+        let columns = match columns {
+            None => return Self::try_create(ctx, source_data_blocks),
+            Some(columns) => columns,
+        };
+        let blocks = source_data_blocks
+            .iter()
+            .map(|block| Self::project_columns(block, columns))
+            .collect::<Vec<_>>();
+        let block_refs = blocks.iter().collect::<Vec<_>>();
+        Self::try_create(ctx, &block_refs)
+    }
+
+    fn project_columns(block: &DataBlock, columns: &[String]) -> DataBlock {
+        let mut fields = vec![];
+        let mut projected = vec![];
+        for (field, column) in block.schema().fields().iter().zip(block.columns()) {
+            if columns.contains(field.name()) {
+                fields.push(field.clone());
+                projected.push(column.clone());
+            }
+        }
+        DataBlock::create(Arc::new(DataSchema::new(fields)), projected)
+    }
+
     /// Create a bloom filter block from source data blocks and seed(s).
     ///
     /// All input blocks should be belong to a Parquet file, e.g. the block array represents the parquet file in memory.
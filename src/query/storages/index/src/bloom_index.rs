@@ -0,0 +1,59 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_datavalues::DataField;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::BloomFilter;
+use crate::SupportedType;
+
+/// Definition of a bloom index, created by `CREATE INDEX ... TYPE BLOOM`.
+///
+/// Without bloom indexes, the bloom filters are built for all the applicable columns of a
+/// table. Once a table has bloom indexes, they are built for the columns of the indexes only.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BloomIndexMeta {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+impl BloomIndexMeta {
+    /// Columns covered by any of the `indexes`, `None` if there are no indexes, that is, all
+    /// the applicable columns are covered.
+    pub fn covered_columns(indexes: &[BloomIndexMeta]) -> Option<Vec<String>> {
+        if indexes.is_empty() {
+            return None;
+        }
+        let mut columns: Vec<String> = vec![];
+        for column in indexes.iter().flat_map(|index| &index.columns) {
+            if !columns.contains(column) {
+                columns.push(column.clone());
+            }
+        }
+        Some(columns)
+    }
+}
+
+/// Checks that bloom filters can be built for the column `field`.
+pub fn check_bloom_index_column(field: &DataField) -> Result<()> {
+    if BloomFilter::is_supported_type(field.data_type()) {
+        Ok(())
+    } else {
+        Err(ErrorCode::IllegalIndexFormat(format!(
+            "Column {} of type {} is not supported by bloom index",
+            field.name(),
+            field.data_type().name()
+        )))
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataField;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::column_texts;
+use common_functions::scalars::TextQuery;
+use common_functions::scalars::Tokenizer;
+
+use crate::remove_nullable;
+
+/// Definition of an inverted index, created by `CREATE INDEX ... TYPE INVERTED`.
+///
+/// For every block of the table, the index maps the terms of the texts of its columns to the
+/// rows containing them, see [`BlockInvertedIndex`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct InvertedIndexMeta {
+    /// Identifies the index files, so that a re-created index never reads stale files.
+    pub id: String,
+    pub name: String,
+    pub columns: Vec<String>,
+    /// Name of the [`Tokenizer`] splitting the texts into terms.
+    pub tokenizer: String,
+}
+
+impl InvertedIndexMeta {
+    pub fn tokenizer(&self) -> Result<Tokenizer> {
+        Tokenizer::from_name(&self.tokenizer)
+    }
+}
+
+/// Checks that an inverted index can be built for the column `field`.
+pub fn check_inverted_index_column(field: &DataField) -> Result<()> {
+    let data_type_id = remove_nullable(field.data_type()).data_type_id();
+    if data_type_id.is_string() || data_type_id.is_variant() {
+        Ok(())
+    } else {
+        Err(ErrorCode::IllegalIndexFormat(format!(
+            "Column {} of type {} is not supported by inverted index, expect a string or variant",
+            field.name(),
+            field.data_type().name()
+        )))
+    }
+}
+
+/// The inverted index of the columns of a block, the sorted rows containing each term.
+///
+/// The nulls are not indexed, they match no query.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BlockInvertedIndex {
+    pub num_rows: usize,
+    pub columns: BTreeMap<String, BTreeMap<String, Vec<u32>>>,
+}
+
+impl BlockInvertedIndex {
+    pub fn build(index: &InvertedIndexMeta, block: &DataBlock) -> Result<Self> {
+        let tokenizer = index.tokenizer()?;
+        let mut columns = BTreeMap::new();
+        for name in &index.columns {
+            let mut terms: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+            let texts = column_texts(block.try_column_by_name(name)?)?;
+            for (row, text) in texts.iter().enumerate() {
+                let text = match text {
+                    Some(text) => text,
+                    None => continue,
+                };
+                for term in tokenizer.tokenize(text) {
+                    let rows = terms.entry(term).or_default();
+                    // a term may occur more than once in a text
+                    if rows.last() != Some(&(row as u32)) {
+                        rows.push(row as u32);
+                    }
+                }
+            }
+            columns.insert(name.clone(), terms);
+        }
+
+        Ok(BlockInvertedIndex {
+            num_rows: block.num_rows(),
+            columns,
+        })
+    }
+
+    /// The sorted rows of which the text of `column` matches the query, `None` if the column is
+    /// not indexed.
+    pub fn search(&self, column: &str, query: &TextQuery) -> Option<Vec<u32>> {
+        let terms = self.columns.get(column)?;
+        Some(self.search_terms(terms, query))
+    }
+
+    fn search_terms(&self, terms: &BTreeMap<String, Vec<u32>>, query: &TextQuery) -> Vec<u32> {
+        match query {
+            TextQuery::Term(term) => terms.get(term).cloned().unwrap_or_default(),
+            TextQuery::And(queries) => {
+                // an empty conjunction matches all the rows
+                let mut rows: Option<Vec<u32>> = None;
+                for query in queries {
+                    let matched = self.search_terms(terms, query);
+                    rows = Some(match rows {
+                        None => matched,
+                        Some(rows) => rows
+                            .into_iter()
+                            .filter(|row| matched.binary_search(row).is_ok())
+                            .collect(),
+                    });
+                }
+                rows.unwrap_or_else(|| (0..self.num_rows as u32).collect())
+            }
+            TextQuery::Or(queries) => queries
+                .iter()
+                .flat_map(|query| self.search_terms(terms, query))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        match bincode::serde::encode_to_vec(self, bincode::config::standard()) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(ErrorCode::StorageOther(format!(
+                "bincode serialization error: {} ",
+                e
+            ))),
+        }
+    }
+
+    pub fn from_vec(bytes: &[u8]) -> Result<Self> {
+        match bincode::serde::decode_from_slice(bytes, bincode::config::standard()) {
+            Ok((index, _)) => Ok(index),
+            Err(e) => Err(ErrorCode::StorageOther(format!(
+                "bincode deserialization error: {} ",
+                e
+            ))),
+        }
+    }
+}
@@ -19,14 +19,22 @@ use common_datavalues::DataType;
 use common_datavalues::DataTypeImpl;
 use common_datavalues::NullableType;
 
+pub mod aggregating_index;
 pub mod bloom;
 pub mod bloom_filter;
+pub mod bloom_index;
 pub mod index_min_max;
+pub mod inverted_index;
 pub mod range_filter;
+pub mod virtual_column;
 
+pub use aggregating_index::*;
 pub use bloom_filter::*;
+pub use bloom_index::*;
 pub use index_min_max::*;
+pub use inverted_index::*;
 pub use range_filter::*;
+pub use virtual_column::*;
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum IndexSchemaVersion {
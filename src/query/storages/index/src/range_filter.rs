@@ -414,6 +414,17 @@ impl<'a> VerifiableExprBuilder<'a> {
             fields.push((right_field, right_cols));
         }
 
+        // VARIANT columns only have the null counts in their statistics, without min/max.
+        let has_variant = fields.iter().any(|(_, cols)| {
+            cols.values()
+                .any(|f| remove_nullable(f.data_type()).data_type_id().is_variant())
+        });
+        if has_variant && !matches!(op, "is_null" | "is_not_null") {
+            return Err(ErrorCode::UnknownException(
+                "VARIANT columns have no min/max statistics",
+            ));
+        }
+
         Ok(Self {
             op,
             args,
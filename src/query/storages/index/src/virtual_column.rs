@@ -0,0 +1,201 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::remove_nullable;
+
+/// An element of the path of a virtual column.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum VirtualPathElement {
+    /// `['key']`, the value of a key of an object
+    Key(String),
+    /// `[0]`, an element of an array
+    Index(u64),
+}
+
+impl Display for VirtualPathElement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VirtualPathElement::Key(key) => write!(f, "['{}']", key.replace('\'', "\\'")),
+            VirtualPathElement::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+/// A path of a VARIANT column materialized as a column, created by `CREATE VIRTUAL COLUMNS`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VirtualColumnMeta {
+    /// The VARIANT column the values are extracted from.
+    pub source: String,
+    pub path: Vec<VirtualPathElement>,
+}
+
+impl VirtualColumnMeta {
+    /// Name of the column, like `v['a'][0]`, the path expressions of the queries are resolved
+    /// to the virtual columns by the names.
+    pub fn name(&self) -> String {
+        let mut name = self.source.clone();
+        for element in &self.path {
+            name.push_str(&element.to_string());
+        }
+        name
+    }
+
+    /// Type of the virtual columns, the value is null if the path does not exist.
+    pub fn data_type() -> DataTypeImpl {
+        NullableType::new_impl(VariantType::new_impl())
+    }
+
+    pub fn to_data_field(&self) -> DataField {
+        DataField::new(&self.name(), Self::data_type())
+    }
+
+    /// The value of the path of the value, the same as the `GET` functions of the path.
+    pub fn extract_value(&self, value: &VariantValue) -> Option<VariantValue> {
+        let mut value = value.as_ref();
+        for element in &self.path {
+            value = match element {
+                VirtualPathElement::Key(key) => value.get(key.as_str())?,
+                VirtualPathElement::Index(index) => value.get(*index as usize)?,
+            };
+        }
+        Some(VariantValue::from(value))
+    }
+
+    /// Extracts the values of the path from the column `source`.
+    pub fn extract(&self, source: &ColumnRef) -> Result<ColumnRef> {
+        let num_rows = source.len();
+        let mut builder = NullableColumnBuilder::<VariantValue>::with_capacity(num_rows);
+        for row in 0..num_rows {
+            match self.extract_row(source, row) {
+                Some(value) => builder.append(&value, true),
+                None => builder.append_null(),
+            }
+        }
+        Ok(builder.build(num_rows))
+    }
+
+    fn extract_row(&self, source: &ColumnRef, row: usize) -> Option<VariantValue> {
+        match source.get(row) {
+            DataValue::Variant(value) => self.extract_value(&value),
+            _ => None,
+        }
+    }
+}
+
+/// The virtual columns of a table, stored in the table options.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VirtualColumnsMeta {
+    /// Identifies the files of the virtual columns, so that the re-created virtual columns never
+    /// read stale files.
+    pub id: String,
+    pub columns: Vec<VirtualColumnMeta>,
+}
+
+impl VirtualColumnsMeta {
+    pub fn column(&self, name: &str) -> Option<&VirtualColumnMeta> {
+        self.columns.iter().find(|column| column.name() == name)
+    }
+}
+
+/// Checks that virtual columns can be created for the column `field`.
+pub fn check_virtual_column_source(field: &DataField) -> Result<()> {
+    if remove_nullable(field.data_type())
+        .data_type_id()
+        .is_variant()
+    {
+        Ok(())
+    } else {
+        Err(ErrorCode::IllegalDataType(format!(
+            "Column {} of type {} is not supported by virtual columns, expect a variant",
+            field.name(),
+            field.data_type().name()
+        )))
+    }
+}
+
+/// The values of the virtual columns of a block, the JSON texts of the values keyed by the
+/// names of the virtual columns.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BlockVirtualColumns {
+    pub num_rows: usize,
+    pub columns: BTreeMap<String, Vec<Option<String>>>,
+}
+
+impl BlockVirtualColumns {
+    pub fn build(meta: &VirtualColumnsMeta, block: &DataBlock) -> Result<Self> {
+        let mut columns = BTreeMap::new();
+        for column in &meta.columns {
+            let source = block.try_column_by_name(&column.source)?;
+            let values = (0..block.num_rows())
+                .map(|row| {
+                    column
+                        .extract_row(source, row)
+                        .map(|value| value.to_string())
+                })
+                .collect();
+            columns.insert(column.name(), values);
+        }
+
+        Ok(BlockVirtualColumns {
+            num_rows: block.num_rows(),
+            columns,
+        })
+    }
+
+    /// The values of the virtual column `name`, `None` if the column is not materialized.
+    pub fn column(&self, name: &str) -> Result<Option<ColumnRef>> {
+        let values = match self.columns.get(name) {
+            Some(values) => values,
+            None => return Ok(None),
+        };
+        let mut builder = NullableColumnBuilder::<VariantValue>::with_capacity(self.num_rows);
+        for value in values {
+            match value {
+                Some(value) => builder.append(&VariantValue::from_str(value)?, true),
+                None => builder.append_null(),
+            }
+        }
+        Ok(Some(builder.build(self.num_rows)))
+    }
+
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        match bincode::serde::encode_to_vec(self, bincode::config::standard()) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(ErrorCode::StorageOther(format!(
+                "bincode serialization error: {} ",
+                e
+            ))),
+        }
+    }
+
+    pub fn from_vec(bytes: &[u8]) -> Result<Self> {
+        match bincode::serde::decode_from_slice(bytes, bincode::config::standard()) {
+            Ok((columns, _)) => Ok(columns),
+            Err(e) => Err(ErrorCode::StorageOther(format!(
+                "bincode deserialization error: {} ",
+                e
+            ))),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::scalars::TextQuery;
+use common_functions::scalars::Tokenizer;
+use common_storages_index::BlockInvertedIndex;
+use common_storages_index::InvertedIndexMeta;
+
+#[test]
+fn test_block_inverted_index() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("id", i32::to_data_type()),
+        DataField::new_nullable("message", Vu8::to_data_type()),
+    ]);
+    let block = DataBlock::create(schema, vec![
+        Series::from_data(vec![1, 2, 3, 4]),
+        Series::from_data(vec![
+            Some("Error: connection timeout"),
+            Some("error: connection refused, error"),
+            None,
+            Some("warn: slow query"),
+        ]),
+    ]);
+    let meta = InvertedIndexMeta {
+        id: "id".to_string(),
+        name: "idx".to_string(),
+        columns: vec!["message".to_string()],
+        tokenizer: "standard".to_string(),
+    };
+
+    let index = BlockInvertedIndex::build(&meta, &block)?;
+    let index = BlockInvertedIndex::from_vec(&index.to_vec()?)?;
+    assert_eq!(index.num_rows, 4);
+
+    let search = |query: &str| -> Result<Option<Vec<u32>>> {
+        let query = TextQuery::parse(query, Tokenizer::Standard)?;
+        Ok(index.search("message", &query))
+    };
+    assert_eq!(search("error")?, Some(vec![0, 1]));
+    assert_eq!(search("connection AND timeout")?, Some(vec![0]));
+    assert_eq!(search("timeout OR slow")?, Some(vec![0, 3]));
+    assert_eq!(search("debug")?, Some(vec![]));
+    assert_eq!(search("::")?, Some(vec![0, 1, 2, 3]));
+    assert_eq!(index.search("id", &TextQuery::Term("1".to_string())), None);
+
+    Ok(())
+}
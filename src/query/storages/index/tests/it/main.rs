@@ -13,3 +13,5 @@
 // limitations under the License.
 
 mod bloom;
+mod inverted_index;
+mod virtual_column;
@@ -0,0 +1,96 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_storages_index::check_virtual_column_source;
+use common_storages_index::BlockVirtualColumns;
+use common_storages_index::VirtualColumnMeta;
+use common_storages_index::VirtualColumnsMeta;
+use common_storages_index::VirtualPathElement;
+
+#[test]
+fn test_block_virtual_columns() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("id", i32::to_data_type()),
+        DataField::new("v", VariantType::new_impl()),
+    ]);
+    let values = [
+        r#"{"a": [1, 2], "b": "x"}"#,
+        r#"{"a": [3]}"#,
+        r#"[1, 2]"#,
+        r#"{"b": null}"#,
+    ];
+    let block = DataBlock::create(schema.clone(), vec![
+        Series::from_data(vec![1, 2, 3, 4]),
+        Series::from_data(
+            values
+                .iter()
+                .map(|value| VariantValue::from_str(value))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+    ]);
+
+    let a_1 = VirtualColumnMeta {
+        source: "v".to_string(),
+        path: vec![
+            VirtualPathElement::Key("a".to_string()),
+            VirtualPathElement::Index(1),
+        ],
+    };
+    let b = VirtualColumnMeta {
+        source: "v".to_string(),
+        path: vec![VirtualPathElement::Key("b".to_string())],
+    };
+    assert_eq!(a_1.name(), "v['a'][1]");
+    assert_eq!(b.name(), "v['b']");
+
+    let meta = VirtualColumnsMeta {
+        id: "id".to_string(),
+        columns: vec![a_1.clone(), b.clone()],
+    };
+    let columns = BlockVirtualColumns::build(&meta, &block)?;
+    let columns = BlockVirtualColumns::from_vec(&columns.to_vec()?)?;
+    assert_eq!(columns.num_rows, 4);
+    assert_eq!(columns.columns["v['a'][1]"], vec![
+        Some("2".to_string()),
+        None,
+        None,
+        None
+    ]);
+    assert_eq!(columns.columns["v['b']"], vec![
+        Some("\"x\"".to_string()),
+        None,
+        None,
+        Some("null".to_string())
+    ]);
+
+    // the materialized values are the values extracted from the source column
+    let source = block.try_column_by_name("v")?;
+    for column in [&a_1, &b] {
+        let materialized = columns.column(&column.name())?.unwrap();
+        let extracted = column.extract(source)?;
+        assert_eq!(materialized.data_type(), VirtualColumnMeta::data_type());
+        assert_eq!(materialized.to_values(), extracted.to_values());
+    }
+    assert!(columns.column("v['c']")?.is_none());
+
+    assert!(check_virtual_column_source(schema.field_with_name("v")?).is_ok());
+    assert!(check_virtual_column_source(schema.field_with_name("id")?).is_err());
+
+    Ok(())
+}
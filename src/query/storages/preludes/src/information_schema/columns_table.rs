@@ -28,13 +28,13 @@ pub struct ColumnsTable {}
 impl ColumnsTable {
     pub fn create(table_id: u64) -> Arc<dyn Table> {
         let query = "SELECT
-            database AS table_catalog,
+            'default' AS table_catalog,
             database AS table_schema,
             table AS table_name,
             name AS column_name,
-            1 AS ordinal_position,
-            NULL AS column_default,
-            is_nullable AS is_nullable,
+            ordinal_position AS ordinal_position,
+            if(default_kind = '', NULL, default_expression) AS column_default,
+            if(is_nullable, 'YES', 'NO') AS is_nullable,
             type AS data_type,
             NULL AS character_maximum_length,
             NULL AS character_octet_length,
@@ -50,7 +50,10 @@ impl ColumnsTable {
             NULL AS collation_name,
             NULL AS domain_catalog,
             NULL AS domain_schema,
-            NULL AS domain_name
+            NULL AS domain_name,
+            type AS column_type,
+            '' AS column_key,
+            comment AS column_comment
         FROM system.columns;";
 
         let mut options = BTreeMap::new();
@@ -28,7 +28,7 @@ pub struct SchemataTable {}
 impl SchemataTable {
     pub fn create(table_id: u64) -> Arc<dyn Table> {
         let query = "SELECT
-            name AS catalog_name,
+            'default' AS catalog_name,
             name AS schema_name,
             'default' AS schema_owner,
             NULL AS default_character_set_catalog,
@@ -28,13 +28,18 @@ pub struct TablesTable {}
 impl TablesTable {
     pub fn create(table_id: u64) -> Arc<dyn Table> {
         let query = "SELECT
-            database AS table_catalog,
+            'default' AS table_catalog,
             database AS table_schema,
             name AS table_name,
-            'BASE TABLE' AS table_type,
+            multi_if(
+                database IN ('system', 'INFORMATION_SCHEMA'), 'SYSTEM VIEW',
+                engine = 'VIEW', 'VIEW',
+                'BASE TABLE'
+            ) AS table_type,
             engine AS engine,
             created_on AS create_time,
             dropped_on AS drop_time,
+            num_rows AS table_rows,
             data_size AS data_length,
             index_size AS index_length,
             '' AS table_comment
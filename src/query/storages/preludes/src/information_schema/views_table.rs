@@ -28,18 +28,17 @@ pub struct ViewsTable {}
 impl ViewsTable {
     pub fn create(table_id: u64) -> Arc<dyn Table> {
         let query = "SELECT
-            database AS table_catalog,
+            'default' AS table_catalog,
             database AS table_schema,
             name AS table_name,
-            NULL AS view_definition,
+            view_query AS view_definition,
             'NONE' AS check_option,
-            0 AS is_updatable,
-            engine = 'MaterializedView' AS is_insertable_into,
-            0 AS is_trigger_updatable,
-            0 AS is_trigger_deletable,
-            0 AS is_trigger_insertable_into
-        FROM system.tables
-        WHERE engine LIKE '%View';";
+            'NO' AS is_updatable,
+            'NO' AS is_insertable_into,
+            'NO' AS is_trigger_updatable,
+            'NO' AS is_trigger_deletable,
+            'NO' AS is_trigger_insertable_into
+        FROM system.views;";
 
         let mut options = BTreeMap::new();
         options.insert(QUERY.to_string(), query.to_string());
@@ -0,0 +1,95 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use serde::Serialize;
+
+use crate::system::query_log_table::datetime_str;
+use crate::system::SystemLogElement;
+use crate::system::SystemLogQueue;
+use crate::system::SystemLogTable;
+
+/// An event of the security audit, like a failed login or a DDL statement.
+#[derive(Clone, Serialize)]
+pub struct AuditLogElement {
+    #[serde(serialize_with = "datetime_str")]
+    pub event_time: i64,
+    /// `login`, `ddl`, `dml` or `denied`.
+    pub event_class: String,
+
+    // User.
+    pub tenant_id: String,
+    pub sql_user: String,
+    pub client_address: String,
+    pub handler_type: String,
+
+    // Query, empty for the login events.
+    pub query_id: String,
+    pub query_kind: String,
+    pub query_text: String,
+
+    // Exception, the event failed if the code is not 0.
+    pub exception_code: i32,
+    pub exception_text: String,
+}
+
+impl SystemLogElement for AuditLogElement {
+    const TABLE_NAME: &'static str = "audit_log";
+
+    fn schema() -> DataSchemaRef {
+        DataSchemaRefExt::create(vec![
+            DataField::new("event_time", TimestampType::new_impl(3)),
+            DataField::new("event_class", Vu8::to_data_type()),
+            // User.
+            DataField::new("tenant_id", Vu8::to_data_type()),
+            DataField::new("sql_user", Vu8::to_data_type()),
+            DataField::new("client_address", Vu8::to_data_type()),
+            DataField::new("handler_type", Vu8::to_data_type()),
+            // Query.
+            DataField::new("query_id", Vu8::to_data_type()),
+            DataField::new("query_kind", Vu8::to_data_type()),
+            DataField::new("query_text", Vu8::to_data_type()),
+            // Exception.
+            DataField::new("exception_code", i32::to_data_type()),
+            DataField::new("exception_text", Vu8::to_data_type()),
+        ])
+    }
+
+    fn fill_to_data_block(&self, columns: &mut Vec<Box<dyn MutableColumn>>) -> Result<()> {
+        let values = vec![
+            DataValue::Int64(self.event_time),
+            DataValue::String(self.event_class.as_bytes().to_vec()),
+            // User.
+            DataValue::String(self.tenant_id.as_bytes().to_vec()),
+            DataValue::String(self.sql_user.as_bytes().to_vec()),
+            DataValue::String(self.client_address.as_bytes().to_vec()),
+            DataValue::String(self.handler_type.as_bytes().to_vec()),
+            // Query.
+            DataValue::String(self.query_id.as_bytes().to_vec()),
+            DataValue::String(self.query_kind.as_bytes().to_vec()),
+            DataValue::String(self.query_text.as_bytes().to_vec()),
+            // Exception.
+            DataValue::Int64(self.exception_code as i64),
+            DataValue::String(self.exception_text.as_bytes().to_vec()),
+        ];
+        for (column, value) in columns.iter_mut().zip(values) {
+            column.append_data_value(value)?;
+        }
+        Ok(())
+    }
+}
+
+pub type AuditLogQueue = SystemLogQueue<AuditLogElement>;
+pub type AuditLogTable = SystemLogTable<AuditLogElement>;
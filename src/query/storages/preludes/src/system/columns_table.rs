@@ -49,7 +49,8 @@ impl AsyncSystemTable for ColumnsTable {
         let mut default_exprs: Vec<Vec<u8>> = Vec::with_capacity(rows.len());
         let mut is_nullables: Vec<bool> = Vec::with_capacity(rows.len());
         let mut comments: Vec<Vec<u8>> = Vec::with_capacity(rows.len());
-        for (database_name, table_name, field) in rows.into_iter() {
+        let mut ordinal_positions: Vec<u64> = Vec::with_capacity(rows.len());
+        for (database_name, table_name, ordinal_position, field) in rows.into_iter() {
             names.push(field.name().clone().into_bytes());
             tables.push(table_name.into_bytes());
             databases.push(database_name.into_bytes());
@@ -68,6 +69,7 @@ impl AsyncSystemTable for ColumnsTable {
             default_exprs.push(default_expr.into_bytes());
             is_nullables.push(field.is_nullable());
             comments.push("".to_string().into_bytes());
+            ordinal_positions.push(ordinal_position);
         }
 
         Ok(DataBlock::create(self.table_info.schema(), vec![
@@ -79,6 +81,7 @@ impl AsyncSystemTable for ColumnsTable {
             Series::from_data(default_exprs),
             Series::from_data(is_nullables),
             Series::from_data(comments),
+            Series::from_data(ordinal_positions),
         ]))
     }
 }
@@ -94,6 +97,8 @@ impl ColumnsTable {
             DataField::new("default_expression", Vu8::to_data_type()),
             DataField::new("is_nullable", bool::to_data_type()),
             DataField::new("comment", Vu8::to_data_type()),
+            // the position of the column in the table, starting from 1
+            DataField::new("ordinal_position", u64::to_data_type()),
         ]);
 
         let table_info = TableInfo {
@@ -113,19 +118,24 @@ impl ColumnsTable {
     async fn dump_table_columns(
         &self,
         ctx: Arc<dyn TableContext>,
-    ) -> Result<Vec<(String, String, DataField)>> {
+    ) -> Result<Vec<(String, String, u64, DataField)>> {
         let tenant = ctx.get_tenant();
         let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
         let databases = catalog.list_databases(tenant.as_str()).await?;
 
-        let mut rows: Vec<(String, String, DataField)> = vec![];
+        let mut rows: Vec<(String, String, u64, DataField)> = vec![];
         for database in databases {
             for table in catalog
                 .list_tables(tenant.as_str(), database.name())
                 .await?
             {
-                for field in table.schema().fields() {
-                    rows.push((database.name().into(), table.name().into(), field.clone()))
+                for (i, field) in table.schema().fields().iter().enumerate() {
+                    rows.push((
+                        database.name().into(),
+                        table.name().into(),
+                        i as u64 + 1,
+                        field.clone(),
+                    ))
                 }
             }
         }
@@ -0,0 +1,98 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Vu8;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_users::UserApiProvider;
+
+use super::table::AsyncOneBlockSystemTable;
+use super::table::AsyncSystemTable;
+use crate::sessions::TableContext;
+use crate::storages::Table;
+
+pub struct MaskingPoliciesTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for MaskingPoliciesTable {
+    const NAME: &'static str = "system.masking_policies";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let policies = UserApiProvider::instance()
+            .get_masking_policies(&tenant)
+            .await?;
+
+        let names: Vec<&str> = policies.iter().map(|x| x.name.as_str()).collect();
+        let parameters: Vec<&str> = policies.iter().map(|x| x.parameter.as_str()).collect();
+        let bodies: Vec<&str> = policies.iter().map(|x| x.body.as_str()).collect();
+        let exempt_roles: Vec<String> =
+            policies.iter().map(|x| x.exempt_roles.join(", ")).collect();
+        let columns: Vec<String> = policies
+            .iter()
+            .map(|x| {
+                x.columns
+                    .iter()
+                    .map(|c| format!("{}.{}.{}", c.database, c.table, c.column))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(parameters),
+            Series::from_data(bodies),
+            Series::from_data(exempt_roles),
+            Series::from_data(columns),
+        ]))
+    }
+}
+
+impl MaskingPoliciesTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("parameter", Vu8::to_data_type()),
+            DataField::new("body", Vu8::to_data_type()),
+            DataField::new("exempt_roles", Vu8::to_data_type()),
+            DataField::new("columns", Vu8::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'masking_policies'".to_string(),
+            name: "masking_policies".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemMaskingPolicies".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(MaskingPoliciesTable { table_info })
+    }
+}
@@ -41,17 +41,20 @@ impl SyncSystemTable for MetricsTable {
         &self.table_info
     }
 
-    fn get_full_data(&self, _: Arc<dyn TableContext>) -> Result<DataBlock> {
+    fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
         let prometheus_handle = common_metrics::try_handle().ok_or_else(|| {
             ErrorCode::InitPrometheusFailure("Prometheus recorder is not initialized yet.")
         })?;
 
+        let local_node = ctx.get_cluster().local_id.clone();
         let samples = common_metrics::dump_metric_samples(prometheus_handle)?;
+        let mut nodes: Vec<Vec<u8>> = Vec::with_capacity(samples.len());
         let mut metrics: Vec<Vec<u8>> = Vec::with_capacity(samples.len());
         let mut labels: Vec<Vec<u8>> = Vec::with_capacity(samples.len());
         let mut kinds: Vec<Vec<u8>> = Vec::with_capacity(samples.len());
         let mut values: Vec<Vec<u8>> = Vec::with_capacity(samples.len());
         for sample in samples.into_iter() {
+            nodes.push(local_node.clone().into_bytes());
             metrics.push(sample.name.clone().into_bytes());
             kinds.push(sample.kind.clone().into_bytes());
             labels.push(self.display_sample_labels(&sample.labels)?.into_bytes());
@@ -63,6 +66,7 @@ impl SyncSystemTable for MetricsTable {
             Series::from_data(kinds),
             Series::from_data(labels),
             Series::from_data(values),
+            Series::from_data(nodes),
         ]))
     }
 }
@@ -74,6 +78,7 @@ impl MetricsTable {
             DataField::new("kind", Vu8::to_data_type()),
             DataField::new("labels", Vu8::to_data_type()),
             DataField::new("value", Vu8::to_data_type()),
+            DataField::new("node", Vu8::to_data_type()),
         ]);
 
         let table_info = TableInfo {
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod audit_log_table;
 mod clustering_history_table;
 mod columns_table;
 mod configs_table;
@@ -21,19 +22,31 @@ mod databases_table;
 mod engines_table;
 mod functions_table;
 mod log_queue;
+mod masking_policies_table;
 mod metrics_table;
+mod network_policies_table;
 mod one_table;
 mod processes_table;
 mod query_log_table;
+mod query_queue_table;
 mod roles_table;
+mod row_access_policies_table;
 mod settings_table;
+mod slow_query_log_table;
 mod stages_table;
 mod table;
 mod tables_table;
+mod task_history_table;
+mod tasks_table;
 mod tracing_table;
 mod tracing_table_stream;
 mod users_table;
+mod views_table;
+mod workload_groups_table;
 
+pub use audit_log_table::AuditLogElement;
+pub use audit_log_table::AuditLogQueue;
+pub use audit_log_table::AuditLogTable;
 pub use clustering_history_table::ClusteringHistoryLogElement;
 pub use clustering_history_table::ClusteringHistoryQueue;
 pub use clustering_history_table::ClusteringHistoryTable;
@@ -47,21 +60,34 @@ pub use functions_table::FunctionsTable;
 pub use log_queue::SystemLogElement;
 pub use log_queue::SystemLogQueue;
 pub use log_queue::SystemLogTable;
+pub use masking_policies_table::MaskingPoliciesTable;
 pub use metrics_table::MetricsTable;
+pub use network_policies_table::NetworkPoliciesTable;
 pub use one_table::OneTable;
 pub use processes_table::ProcessesTable;
 pub use query_log_table::LogType;
 pub use query_log_table::QueryLogElement;
 pub use query_log_table::QueryLogQueue;
 pub use query_log_table::QueryLogTable;
+pub use query_queue_table::QueryQueueTable;
 pub use roles_table::RolesTable;
+pub use row_access_policies_table::RowAccessPoliciesTable;
 pub use settings_table::SettingsTable;
+pub use slow_query_log_table::SlowQueryLogElement;
+pub use slow_query_log_table::SlowQueryLogQueue;
+pub use slow_query_log_table::SlowQueryLogTable;
 pub use stages_table::StagesTable;
+pub use table::AsyncOneBlockSystemTable;
+pub use table::AsyncSystemTable;
 pub use table::SyncOneBlockSystemTable;
 pub use table::SyncSystemTable;
 pub use tables_table::TablesTable;
 pub use tables_table::TablesTableWithHistory;
 pub use tables_table::TablesTableWithoutHistory;
+pub use task_history_table::TaskHistoryTable;
+pub use tasks_table::TasksTable;
 pub use tracing_table::TracingTable;
 pub use tracing_table_stream::TracingTableStream;
 pub use users_table::UsersTable;
+pub use views_table::ViewsTable;
+pub use workload_groups_table::WorkloadGroupsTable;
@@ -43,8 +43,11 @@ impl SyncSystemTable for ProcessesTable {
     }
 
     fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let local_node = ctx.get_cluster().local_id.clone();
         let processes_info = ctx.get_processes_info();
 
+        let mut processes_node = Vec::with_capacity(processes_info.len());
+
         let mut processes_id = Vec::with_capacity(processes_info.len());
         let mut processes_type = Vec::with_capacity(processes_info.len());
         let mut processes_host = Vec::with_capacity(processes_info.len());
@@ -57,9 +60,16 @@ impl SyncSystemTable for ProcessesTable {
         let mut processes_dal_metrics_write_bytes = Vec::with_capacity(processes_info.len());
         let mut processes_scan_progress_read_rows = Vec::with_capacity(processes_info.len());
         let mut processes_scan_progress_read_bytes = Vec::with_capacity(processes_info.len());
+        let mut processes_scan_progress_total_rows = Vec::with_capacity(processes_info.len());
+        let mut processes_scan_progress_total_bytes = Vec::with_capacity(processes_info.len());
         let mut processes_mysql_connection_id = Vec::with_capacity(processes_info.len());
+        let mut processes_query_id = Vec::with_capacity(processes_info.len());
+        let mut processes_query_duration_ms = Vec::with_capacity(processes_info.len());
+        let mut processes_progress = Vec::with_capacity(processes_info.len());
+        let mut processes_stage = Vec::with_capacity(processes_info.len());
 
         for process_info in &processes_info {
+            processes_node.push(local_node.clone().into_bytes());
             processes_id.push(process_info.id.clone().into_bytes());
             processes_type.push(process_info.typ.clone().into_bytes());
             processes_state.push(process_info.state.clone().into_bytes());
@@ -78,7 +88,20 @@ impl SyncSystemTable for ProcessesTable {
                 ProcessesTable::process_scan_progress_values(&process_info.scan_progress_value);
             processes_scan_progress_read_rows.push(scan_progress_read_rows);
             processes_scan_progress_read_bytes.push(scan_progress_read_bytes);
+            let (scan_progress_total_rows, scan_progress_total_bytes) =
+                ProcessesTable::process_scan_progress_values(
+                    &process_info.total_scan_progress_value,
+                );
+            processes_scan_progress_total_rows.push(scan_progress_total_rows);
+            processes_scan_progress_total_bytes.push(scan_progress_total_bytes);
             processes_mysql_connection_id.push(process_info.mysql_connection_id);
+            processes_query_id.push(process_info.query_id.clone().map(|s| s.into_bytes()));
+            processes_query_duration_ms.push(process_info.query_duration_ms);
+            processes_progress.push(ProcessesTable::process_progress(
+                &process_info.scan_progress_value,
+                &process_info.total_scan_progress_value,
+            ));
+            processes_stage.push(process_info.query_stage.clone().map(|s| s.into_bytes()));
         }
 
         Ok(DataBlock::create(self.table_info.schema(), vec![
@@ -94,7 +117,14 @@ impl SyncSystemTable for ProcessesTable {
             Series::from_data(processes_dal_metrics_write_bytes),
             Series::from_data(processes_scan_progress_read_rows),
             Series::from_data(processes_scan_progress_read_bytes),
+            Series::from_data(processes_scan_progress_total_rows),
+            Series::from_data(processes_scan_progress_total_bytes),
             Series::from_data(processes_mysql_connection_id),
+            Series::from_data(processes_query_id),
+            Series::from_data(processes_query_duration_ms),
+            Series::from_data(processes_node),
+            Series::from_data(processes_progress),
+            Series::from_data(processes_stage),
         ]))
     }
 }
@@ -114,7 +144,15 @@ impl ProcessesTable {
             DataField::new_nullable("dal_metrics_write_bytes", u64::to_data_type()),
             DataField::new_nullable("scan_progress_read_rows", u64::to_data_type()),
             DataField::new_nullable("scan_progress_read_bytes", u64::to_data_type()),
+            DataField::new_nullable("scan_progress_total_rows", u64::to_data_type()),
+            DataField::new_nullable("scan_progress_total_bytes", u64::to_data_type()),
             DataField::new_nullable("mysql_connection_id", u32::to_data_type()),
+            DataField::new_nullable("query_id", Vu8::to_data_type()),
+            DataField::new_nullable("query_duration_ms", u64::to_data_type()),
+            DataField::new("node", Vu8::to_data_type()),
+            // percentage of the rows scanned, against the rows estimated to scan
+            DataField::new_nullable("progress", f64::to_data_type()),
+            DataField::new_nullable("stage", Vu8::to_data_type()),
         ]);
 
         let table_info = TableInfo {
@@ -156,6 +194,20 @@ impl ProcessesTable {
         }
     }
 
+    fn process_progress(
+        scan_progress_opt: &Option<ProgressValues>,
+        total_scan_progress_opt: &Option<ProgressValues>,
+    ) -> Option<f64> {
+        match (scan_progress_opt, total_scan_progress_opt) {
+            (Some(scan_progress), Some(total)) if total.rows > 0 => {
+                // the estimation may fall behind the rows actually scanned
+                let progress = scan_progress.rows as f64 * 100.0 / total.rows as f64;
+                Some((progress.min(100.0) * 100.0).round() / 100.0)
+            }
+            _ => None,
+        }
+    }
+
     fn process_scan_progress_values(
         scan_progress_opt: &Option<ProgressValues>,
     ) -> (Option<u64>, Option<u64>) {
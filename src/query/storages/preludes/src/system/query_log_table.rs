@@ -38,7 +38,7 @@ where S: Serializer {
     s.serialize_str(t.format("%Y-%m-%d").to_string().as_str())
 }
 
-fn datetime_str<S>(dt: &i64, s: S) -> Result<S::Ok, S::Error>
+pub(crate) fn datetime_str<S>(dt: &i64, s: S) -> Result<S::Ok, S::Error>
 where S: Serializer {
     let t = NaiveDateTime::from_timestamp(
         dt / 1_000_000,
@@ -96,6 +96,8 @@ pub struct QueryLogElement {
     pub result_bytes: u64,
     pub cpu_usage: u32,
     pub memory_usage: u64,
+    pub peak_memory_usage: u64,
+    pub query_duration_ms: u64,
 
     // Client.
     pub client_info: String,
@@ -158,6 +160,8 @@ impl SystemLogElement for QueryLogElement {
             DataField::new("result_bytes", u64::to_data_type()),
             DataField::new("cpu_usage", u32::to_data_type()),
             DataField::new("memory_usage", u64::to_data_type()),
+            DataField::new("peak_memory_usage", u64::to_data_type()),
+            DataField::new("query_duration_ms", u64::to_data_type()),
             // Client.
             DataField::new("client_info", Vu8::to_data_type()),
             DataField::new("client_address", Vu8::to_data_type()),
@@ -307,6 +311,14 @@ impl SystemLogElement for QueryLogElement {
             .next()
             .unwrap()
             .append_data_value(DataValue::UInt64(self.memory_usage))?;
+        columns
+            .next()
+            .unwrap()
+            .append_data_value(DataValue::UInt64(self.peak_memory_usage))?;
+        columns
+            .next()
+            .unwrap()
+            .append_data_value(DataValue::UInt64(self.query_duration_ms))?;
         // Client.
         columns
             .next()
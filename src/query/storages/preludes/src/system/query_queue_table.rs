@@ -0,0 +1,91 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+
+use crate::sessions::TableContext;
+use crate::storages::Table;
+use crate::system::SyncOneBlockSystemTable;
+use crate::system::SyncSystemTable;
+
+pub struct QueryQueueTable {
+    table_info: TableInfo,
+}
+
+impl SyncSystemTable for QueryQueueTable {
+    const NAME: &'static str = "system.query_queue";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let queue_info = ctx.get_query_queue_info();
+
+        let mut query_ids = Vec::with_capacity(queue_info.len());
+        let mut users = Vec::with_capacity(queue_info.len());
+        let mut states = Vec::with_capacity(queue_info.len());
+        let mut queries = Vec::with_capacity(queue_info.len());
+        let mut wait_times = Vec::with_capacity(queue_info.len());
+
+        for info in queue_info {
+            query_ids.push(info.query_id.into_bytes());
+            users.push(info.user.into_bytes());
+            states.push(info.state.into_bytes());
+            queries.push(info.query.into_bytes());
+            wait_times.push(info.wait_time_ms);
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(query_ids),
+            Series::from_data(users),
+            Series::from_data(states),
+            Series::from_data(queries),
+            Series::from_data(wait_times),
+        ]))
+    }
+}
+
+impl QueryQueueTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("query_id", Vu8::to_data_type()),
+            DataField::new("user", Vu8::to_data_type()),
+            DataField::new("state", Vu8::to_data_type()),
+            DataField::new("query", Vu8::to_data_type()),
+            DataField::new("wait_time_ms", u64::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'query_queue'".to_string(),
+            name: "query_queue".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemQueryQueue".to_string(),
+
+                ..Default::default()
+            },
+        };
+
+        SyncOneBlockSystemTable::create(QueryQueueTable { table_info })
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Vu8;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_users::UserApiProvider;
+
+use super::table::AsyncOneBlockSystemTable;
+use super::table::AsyncSystemTable;
+use crate::sessions::TableContext;
+use crate::storages::Table;
+
+pub struct RowAccessPoliciesTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for RowAccessPoliciesTable {
+    const NAME: &'static str = "system.row_access_policies";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let policies = UserApiProvider::instance()
+            .get_row_access_policies(&tenant)
+            .await?;
+
+        let names: Vec<&str> = policies.iter().map(|x| x.name.as_str()).collect();
+        let catalogs: Vec<&str> = policies.iter().map(|x| x.catalog.as_str()).collect();
+        let databases: Vec<&str> = policies.iter().map(|x| x.database.as_str()).collect();
+        let tables: Vec<&str> = policies.iter().map(|x| x.table.as_str()).collect();
+        let roles: Vec<&str> = policies.iter().map(|x| x.role.as_str()).collect();
+        let predicates: Vec<&str> = policies.iter().map(|x| x.predicate.as_str()).collect();
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(catalogs),
+            Series::from_data(databases),
+            Series::from_data(tables),
+            Series::from_data(roles),
+            Series::from_data(predicates),
+        ]))
+    }
+}
+
+impl RowAccessPoliciesTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("catalog", Vu8::to_data_type()),
+            DataField::new("database", Vu8::to_data_type()),
+            DataField::new("table", Vu8::to_data_type()),
+            DataField::new("role", Vu8::to_data_type()),
+            DataField::new("predicate", Vu8::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'row_access_policies'".to_string(),
+            name: "row_access_policies".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemRowAccessPolicies".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(RowAccessPoliciesTable { table_info })
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use serde::Serialize;
+
+use crate::system::query_log_table::datetime_str;
+use crate::system::QueryLogElement;
+use crate::system::SystemLogElement;
+use crate::system::SystemLogQueue;
+use crate::system::SystemLogTable;
+
+/// A query slower than `slow_query_threshold_ms`, with the plan executed.
+#[derive(Clone, Serialize)]
+pub struct SlowQueryLogElement {
+    // User.
+    pub tenant_id: String,
+    pub cluster_id: String,
+    pub sql_user: String,
+
+    // Query.
+    pub query_id: String,
+    pub query_kind: String,
+    pub query_text: String,
+    #[serde(serialize_with = "datetime_str")]
+    pub event_time: i64,
+    pub current_database: String,
+    pub query_duration_ms: u64,
+
+    // Stats.
+    pub scan_rows: u64,
+    pub scan_bytes: u64,
+    pub scan_partitions: u64,
+    pub total_partitions: u64,
+    pub written_rows: u64,
+    pub written_bytes: u64,
+    pub result_rows: u64,
+    pub result_bytes: u64,
+    pub peak_memory_usage: u64,
+
+    // Exception.
+    pub exception_code: i32,
+    pub exception_text: String,
+
+    // The summary of the plan executed, empty if the plan is not a query.
+    pub plan: String,
+}
+
+impl SlowQueryLogElement {
+    pub fn create(event: &QueryLogElement, plan: String) -> Self {
+        SlowQueryLogElement {
+            tenant_id: event.tenant_id.clone(),
+            cluster_id: event.cluster_id.clone(),
+            sql_user: event.sql_user.clone(),
+            query_id: event.query_id.clone(),
+            query_kind: event.query_kind.clone(),
+            query_text: event.query_text.clone(),
+            event_time: event.event_time,
+            current_database: event.current_database.clone(),
+            query_duration_ms: event.query_duration_ms,
+            scan_rows: event.scan_rows,
+            scan_bytes: event.scan_bytes,
+            scan_partitions: event.scan_partitions,
+            total_partitions: event.total_partitions,
+            written_rows: event.written_rows,
+            written_bytes: event.written_bytes,
+            result_rows: event.result_rows,
+            result_bytes: event.result_bytes,
+            peak_memory_usage: event.peak_memory_usage,
+            exception_code: event.exception_code,
+            exception_text: event.exception_text.clone(),
+            plan,
+        }
+    }
+}
+
+impl SystemLogElement for SlowQueryLogElement {
+    const TABLE_NAME: &'static str = "slow_query_log";
+
+    fn schema() -> DataSchemaRef {
+        DataSchemaRefExt::create(vec![
+            // User.
+            DataField::new("tenant_id", Vu8::to_data_type()),
+            DataField::new("cluster_id", Vu8::to_data_type()),
+            DataField::new("sql_user", Vu8::to_data_type()),
+            // Query.
+            DataField::new("query_id", Vu8::to_data_type()),
+            DataField::new("query_kind", Vu8::to_data_type()),
+            DataField::new("query_text", Vu8::to_data_type()),
+            DataField::new("event_time", TimestampType::new_impl(3)),
+            DataField::new("current_database", Vu8::to_data_type()),
+            DataField::new("query_duration_ms", u64::to_data_type()),
+            // Stats.
+            DataField::new("scan_rows", u64::to_data_type()),
+            DataField::new("scan_bytes", u64::to_data_type()),
+            DataField::new("scan_partitions", u64::to_data_type()),
+            DataField::new("total_partitions", u64::to_data_type()),
+            DataField::new("written_rows", u64::to_data_type()),
+            DataField::new("written_bytes", u64::to_data_type()),
+            DataField::new("result_rows", u64::to_data_type()),
+            DataField::new("result_bytes", u64::to_data_type()),
+            DataField::new("peak_memory_usage", u64::to_data_type()),
+            // Exception.
+            DataField::new("exception_code", i32::to_data_type()),
+            DataField::new("exception_text", Vu8::to_data_type()),
+            // Plan.
+            DataField::new("plan", Vu8::to_data_type()),
+        ])
+    }
+
+    fn fill_to_data_block(&self, columns: &mut Vec<Box<dyn MutableColumn>>) -> Result<()> {
+        let values = vec![
+            // User.
+            DataValue::String(self.tenant_id.as_bytes().to_vec()),
+            DataValue::String(self.cluster_id.as_bytes().to_vec()),
+            DataValue::String(self.sql_user.as_bytes().to_vec()),
+            // Query.
+            DataValue::String(self.query_id.as_bytes().to_vec()),
+            DataValue::String(self.query_kind.as_bytes().to_vec()),
+            DataValue::String(self.query_text.as_bytes().to_vec()),
+            DataValue::Int64(self.event_time),
+            DataValue::String(self.current_database.as_bytes().to_vec()),
+            DataValue::UInt64(self.query_duration_ms),
+            // Stats.
+            DataValue::UInt64(self.scan_rows),
+            DataValue::UInt64(self.scan_bytes),
+            DataValue::UInt64(self.scan_partitions),
+            DataValue::UInt64(self.total_partitions),
+            DataValue::UInt64(self.written_rows),
+            DataValue::UInt64(self.written_bytes),
+            DataValue::UInt64(self.result_rows),
+            DataValue::UInt64(self.result_bytes),
+            DataValue::UInt64(self.peak_memory_usage),
+            // Exception.
+            DataValue::Int64(self.exception_code as i64),
+            DataValue::String(self.exception_text.as_bytes().to_vec()),
+            // Plan.
+            DataValue::String(self.plan.as_bytes().to_vec()),
+        ];
+        for (column, value) in columns.iter_mut().zip(values) {
+            column.append_data_value(value)?;
+        }
+        Ok(())
+    }
+}
+
+pub type SlowQueryLogQueue = SystemLogQueue<SlowQueryLogElement>;
+pub type SlowQueryLogTable = SystemLogTable<SlowQueryLogElement>;
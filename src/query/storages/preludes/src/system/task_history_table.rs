@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Vu8;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_users::UserApiProvider;
+
+use super::table::AsyncOneBlockSystemTable;
+use super::table::AsyncSystemTable;
+use crate::sessions::TableContext;
+use crate::storages::Table;
+
+/// The runs of the tasks in the last 7 days.
+pub struct TaskHistoryTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for TaskHistoryTable {
+    const NAME: &'static str = "system.task_history";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let runs = UserApiProvider::instance().get_task_runs(&tenant).await?;
+
+        let to_micros = |secs: u64| secs as i64 * 1_000_000;
+        let names: Vec<&str> = runs.iter().map(|x| x.task.as_str()).collect();
+        let definitions: Vec<&str> = runs.iter().map(|x| x.definition.as_str()).collect();
+        let scheduled_times: Vec<i64> = runs.iter().map(|x| to_micros(x.scheduled_time)).collect();
+        let start_times: Vec<i64> = runs.iter().map(|x| to_micros(x.start_time)).collect();
+        // the end time is NULL while the run is executing
+        let end_times: Vec<Option<i64>> = runs
+            .iter()
+            .map(|x| (x.end_time > 0).then(|| to_micros(x.end_time)))
+            .collect();
+        let states: Vec<String> = runs.iter().map(|x| x.state.to_string()).collect();
+        let errors: Vec<&str> = runs.iter().map(|x| x.error.as_str()).collect();
+        let nodes: Vec<&str> = runs.iter().map(|x| x.node.as_str()).collect();
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(definitions),
+            Series::from_data(scheduled_times),
+            Series::from_data(start_times),
+            Series::from_data(end_times),
+            Series::from_data(states),
+            Series::from_data(errors),
+            Series::from_data(nodes),
+        ]))
+    }
+}
+
+impl TaskHistoryTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("definition", Vu8::to_data_type()),
+            DataField::new("scheduled_time", TimestampType::new_impl(0)),
+            DataField::new("start_time", TimestampType::new_impl(0)),
+            DataField::new_nullable("end_time", TimestampType::new_impl(0)),
+            DataField::new("state", Vu8::to_data_type()),
+            DataField::new("error", Vu8::to_data_type()),
+            DataField::new("node", Vu8::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'task_history'".to_string(),
+            name: "task_history".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemTaskHistory".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(TaskHistoryTable { table_info })
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Vu8;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_users::UserApiProvider;
+
+use super::table::AsyncOneBlockSystemTable;
+use super::table::AsyncSystemTable;
+use crate::sessions::TableContext;
+use crate::storages::Table;
+
+pub struct TasksTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for TasksTable {
+    const NAME: &'static str = "system.tasks";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let tasks = UserApiProvider::instance().get_tasks(&tenant).await?;
+
+        let names: Vec<&str> = tasks.iter().map(|x| x.name.as_str()).collect();
+        let schedules: Vec<&str> = tasks.iter().map(|x| x.schedule.as_str()).collect();
+        let owners: Vec<String> = tasks.iter().map(|x| x.owner.to_string()).collect();
+        let definitions: Vec<&str> = tasks.iter().map(|x| x.definition.as_str()).collect();
+        let error_notifications: Vec<&str> = tasks
+            .iter()
+            .map(|x| x.error_notification.as_str())
+            .collect();
+        let comments: Vec<&str> = tasks.iter().map(|x| x.comment.as_str()).collect();
+        let created_ons: Vec<i64> = tasks
+            .iter()
+            .map(|x| x.created_on as i64 * 1_000_000)
+            .collect();
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(schedules),
+            Series::from_data(owners),
+            Series::from_data(definitions),
+            Series::from_data(error_notifications),
+            Series::from_data(comments),
+            Series::from_data(created_ons),
+        ]))
+    }
+}
+
+impl TasksTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("schedule", Vu8::to_data_type()),
+            DataField::new("owner", Vu8::to_data_type()),
+            DataField::new("definition", Vu8::to_data_type()),
+            DataField::new("error_notification", Vu8::to_data_type()),
+            DataField::new("comment", Vu8::to_data_type()),
+            DataField::new("created_on", TimestampType::new_impl(0)),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'tasks'".to_string(),
+            name: "tasks".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemTasks".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(TasksTable { table_info })
+    }
+}
@@ -0,0 +1,108 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::catalog::CATALOG_DEFAULT;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+
+use crate::sessions::TableContext;
+use crate::storages::system::table::AsyncOneBlockSystemTable;
+use crate::storages::system::table::AsyncSystemTable;
+use crate::storages::view::view_table::QUERY;
+use crate::storages::view::view_table::VIEW_ENGINE;
+use crate::storages::Table;
+
+/// The views of all the databases, with the queries defining them.
+pub struct ViewsTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for ViewsTable {
+    const NAME: &'static str = "system.views";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+        let databases = catalog.list_databases(tenant.as_str()).await?;
+
+        let mut database_names: Vec<Vec<u8>> = vec![];
+        let mut names: Vec<Vec<u8>> = vec![];
+        let mut created_ons: Vec<Vec<u8>> = vec![];
+        let mut view_queries: Vec<Vec<u8>> = vec![];
+        for database in databases {
+            for table in catalog
+                .list_tables(tenant.as_str(), database.name())
+                .await?
+            {
+                if table.engine() != VIEW_ENGINE {
+                    continue;
+                }
+
+                let meta = &table.get_table_info().meta;
+                let query = meta.options.get(QUERY).cloned().unwrap_or_default();
+                database_names.push(database.name().as_bytes().to_vec());
+                names.push(table.name().as_bytes().to_vec());
+                created_ons.push(
+                    meta.created_on
+                        .format("%Y-%m-%d %H:%M:%S.%3f %z")
+                        .to_string()
+                        .into_bytes(),
+                );
+                view_queries.push(query.into_bytes());
+            }
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(database_names),
+            Series::from_data(names),
+            Series::from_data(created_ons),
+            Series::from_data(view_queries),
+        ]))
+    }
+}
+
+impl ViewsTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("database", Vu8::to_data_type()),
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("created_on", Vu8::to_data_type()),
+            DataField::new("view_query", Vu8::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'views'".to_string(),
+            name: "views".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemViews".to_string(),
+                ..Default::default()
+            },
+        };
+
+        AsyncOneBlockSystemTable::create(ViewsTable { table_info })
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Vu8;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_users::UserApiProvider;
+
+use super::table::AsyncOneBlockSystemTable;
+use super::table::AsyncSystemTable;
+use crate::sessions::TableContext;
+use crate::storages::Table;
+
+pub struct WorkloadGroupsTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for WorkloadGroupsTable {
+    const NAME: &'static str = "system.workload_groups";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let groups = UserApiProvider::instance()
+            .get_workload_groups(&tenant)
+            .await?;
+
+        let names: Vec<&str> = groups.iter().map(|x| x.name.as_str()).collect();
+        let cpu_shares: Vec<u64> = groups.iter().map(|x| x.cpu_share).collect();
+        let memory_limits: Vec<u64> = groups.iter().map(|x| x.memory_limit).collect();
+        let max_concurrencies: Vec<u64> = groups.iter().map(|x| x.max_concurrency).collect();
+        let max_scan_bytes: Vec<u64> = groups.iter().map(|x| x.max_scan_bytes).collect();
+        let comments: Vec<&str> = groups.iter().map(|x| x.comment.as_str()).collect();
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(names),
+            Series::from_data(cpu_shares),
+            Series::from_data(memory_limits),
+            Series::from_data(max_concurrencies),
+            Series::from_data(max_scan_bytes),
+            Series::from_data(comments),
+        ]))
+    }
+}
+
+impl WorkloadGroupsTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("name", Vu8::to_data_type()),
+            DataField::new("cpu_share", u64::to_data_type()),
+            DataField::new("memory_limit", u64::to_data_type()),
+            DataField::new("max_concurrency", u64::to_data_type()),
+            DataField::new("max_scan_bytes", u64::to_data_type()),
+            DataField::new("comment", Vu8::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'workload_groups'".to_string(),
+            name: "workload_groups".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemWorkloadGroups".to_string(),
+                ..Default::default()
+            },
+        };
+        AsyncOneBlockSystemTable::create(WorkloadGroupsTable { table_info })
+    }
+}
@@ -15,12 +15,14 @@
 use std::sync::Arc;
 
 use common_cache::Cache;
+use common_cache::Count;
+use common_cache::CountableMeter;
 use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::caches::CacheDeferMetrics;
 use common_fuse_meta::caches::CacheManager;
-use common_fuse_meta::caches::ItemCache;
+use common_fuse_meta::caches::MeteredItemCache;
 use common_fuse_meta::caches::TenantLabel;
 use tracing::warn;
 
@@ -39,16 +41,18 @@ pub trait HasTenantLabel {
 }
 
 /// A "cache-aware" reader
-pub struct CachedReader<T, L> {
-    cache: Option<ItemCache<T>>,
+pub struct CachedReader<T, L, M = Count> {
+    cache: Option<MeteredItemCache<T, M>>,
     loader: L,
     name: String,
 }
 
-impl<T, L> CachedReader<T, L>
-where L: Loader<T> + HasTenantLabel
+impl<T, L, M> CachedReader<T, L, M>
+where
+    L: Loader<T> + HasTenantLabel,
+    M: CountableMeter<String, Arc<T>>,
 {
-    pub fn new(cache: Option<ItemCache<T>>, loader: L, name: impl Into<String>) -> Self {
+    pub fn new(cache: Option<MeteredItemCache<T, M>>, loader: L, name: impl Into<String>) -> Self {
         Self {
             cache,
             loader,
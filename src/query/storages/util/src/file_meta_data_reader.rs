@@ -20,12 +20,13 @@ use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::caches::CacheManager;
+use common_fuse_meta::caches::FileMetaDataMeter;
 use futures_util::io::BufReader;
 
 use super::cached_reader::CachedReader;
 use super::cached_reader::Loader;
 
-pub type FileMetaDataReader = CachedReader<FileMetaData, Arc<dyn TableContext>>;
+pub type FileMetaDataReader = CachedReader<FileMetaData, Arc<dyn TableContext>, FileMetaDataMeter>;
 
 impl FileMetaDataReader {
     pub fn new_reader(ctx: Arc<dyn TableContext>) -> FileMetaDataReader {
@@ -24,16 +24,33 @@ use backoff::ExponentialBackoff;
 use backoff::Notify;
 use common_base::base::tokio;
 use common_exception::ErrorCode;
+use common_metrics::label_counter_with_val_and_labels;
+
+const STORAGE_RETRY_COUNT: &str = "storage_retry_count";
+const STORAGE_THROTTLED_COUNT: &str = "storage_throttled_count";
 
 pub fn from_io_error(e: std::io::Error) -> backoff::Error<std::io::Error> {
     if e.kind() == std::io::ErrorKind::NotFound {
         // shall we count `PermissionDenied` as permanent too?
         backoff::Error::permanent(e)
     } else {
+        record_retry(&e);
         backoff::Error::transient(e)
     }
 }
 
+/// Count the transient error to be retried, and whether the storage is throttling us.
+fn record_retry(e: &std::io::Error) {
+    let labels = vec![("kind", format!("{:?}", e.kind()))];
+    label_counter_with_val_and_labels(STORAGE_RETRY_COUNT, labels.clone(), 1);
+
+    // Services respond `429 Too Many Requests` or `503 Slow Down` when throttling requests.
+    let msg = e.to_string();
+    if msg.contains("429") || msg.contains("503") || msg.contains("SlowDown") {
+        label_counter_with_val_and_labels(STORAGE_THROTTLED_COUNT, labels, 1);
+    }
+}
+
 pub fn from_error_code(e: ErrorCode) -> backoff::Error<ErrorCode> {
     // Range of Storage errors: [3001, 4000].
     if e.code() >= 3001 && e.code() <= 4000 {
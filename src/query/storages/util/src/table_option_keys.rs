@@ -19,6 +19,17 @@ use once_cell::sync::Lazy;
 pub const OPT_KEY_DATABASE_ID: &str = "database_id";
 pub const OPT_KEY_SNAPSHOT_LOCATION: &str = "snapshot_location";
 
+/// Storage prefix of the data of a table attached by `ATTACH TABLE`
+///
+/// The data of the other tables is stored under `<database_id>/<table_id>`, an attached table
+/// reads and writes the data of the fuse table under this prefix instead.
+pub const OPT_KEY_STORAGE_PREFIX: &str = "storage_prefix";
+
+/// Set if the table is attached by `ATTACH TABLE ... READ_ONLY`
+///
+/// The table reads the latest snapshot written by the owner of the data, and can not be written.
+pub const OPT_KEY_TABLE_ATTACHED_READ_ONLY: &str = "read_only_attached";
+
 /// Legacy table snapshot location key
 ///
 /// # Deprecated
@@ -29,6 +40,35 @@ pub const OPT_KEY_SNAPSHOT_LOCATION: &str = "snapshot_location";
 /// If both OPT_KEY_SNAPSHOT_LOC and OPT_KEY_SNAPSHOT_LOCATION exist, the latter will be used
 pub const OPT_KEY_LEGACY_SNAPSHOT_LOC: &str = "snapshot_loc";
 
+/// Prefix of the option keys which store the aggregating indexes of a table
+///
+/// The definition of index `idx` is stored under `aggregating_index.idx`, these keys are
+/// maintained by `CREATE/DROP AGGREGATING INDEX` and are reserved for internal usage.
+pub const OPT_KEY_AGGREGATING_INDEX_PREFIX: &str = "aggregating_index.";
+
+/// Prefix of the option keys which store the bloom indexes of a table
+///
+/// The definition of index `idx` is stored under `bloom_index.idx`, these keys are
+/// maintained by `CREATE/DROP INDEX` and are reserved for internal usage.
+pub const OPT_KEY_BLOOM_INDEX_PREFIX: &str = "bloom_index.";
+
+/// Prefix of the option keys which store the inverted indexes of a table
+///
+/// The definition of index `idx` is stored under `inverted_index.idx`, these keys are
+/// maintained by `CREATE/DROP INDEX` and are reserved for internal usage.
+pub const OPT_KEY_INVERTED_INDEX_PREFIX: &str = "inverted_index.";
+
+/// Key of the option which stores the virtual columns of a table
+///
+/// The key is maintained by `CREATE/ALTER/DROP VIRTUAL COLUMNS` and is reserved for internal
+/// usage.
+pub const OPT_KEY_VIRTUAL_COLUMNS: &str = "virtual_columns";
+
+/// Name of the aggregating index a table scan reads instead of the table blocks
+///
+/// Only set in the table info of the scans rewritten to use an aggregating index.
+pub const OPT_KEY_AGGREGATING_INDEX_SCAN: &str = "aggregating_index_scan";
+
 /// Table option keys that reserved for internal usage only
 /// - Users are not allowed to specified this option keys in DDL
 /// - Should not be shown in `show create table` statement
@@ -36,6 +76,10 @@ pub static RESERVED_TABLE_OPTION_KEYS: Lazy<HashSet<&'static str>> = Lazy::new(|
     let mut r = HashSet::new();
     r.insert(OPT_KEY_DATABASE_ID);
     r.insert(OPT_KEY_LEGACY_SNAPSHOT_LOC);
+    r.insert(OPT_KEY_AGGREGATING_INDEX_SCAN);
+    r.insert(OPT_KEY_VIRTUAL_COLUMNS);
+    r.insert(OPT_KEY_STORAGE_PREFIX);
+    r.insert(OPT_KEY_TABLE_ATTACHED_READ_ONLY);
     r
 });
 
@@ -44,13 +88,25 @@ pub static INTERNAL_TABLE_OPTION_KEYS: Lazy<HashSet<&'static str>> = Lazy::new(|
     let mut r = HashSet::new();
     r.insert(OPT_KEY_LEGACY_SNAPSHOT_LOC);
     r.insert(OPT_KEY_DATABASE_ID);
+    r.insert(OPT_KEY_AGGREGATING_INDEX_SCAN);
+    r.insert(OPT_KEY_VIRTUAL_COLUMNS);
+    r.insert(OPT_KEY_STORAGE_PREFIX);
+    r.insert(OPT_KEY_TABLE_ATTACHED_READ_ONLY);
     r
 });
 
 pub fn is_reserved_opt_key<S: AsRef<str>>(opt_key: S) -> bool {
-    RESERVED_TABLE_OPTION_KEYS.contains(opt_key.as_ref().to_lowercase().as_str())
+    let opt_key = opt_key.as_ref().to_lowercase();
+    RESERVED_TABLE_OPTION_KEYS.contains(opt_key.as_str())
+        || opt_key.starts_with(OPT_KEY_AGGREGATING_INDEX_PREFIX)
+        || opt_key.starts_with(OPT_KEY_BLOOM_INDEX_PREFIX)
+        || opt_key.starts_with(OPT_KEY_INVERTED_INDEX_PREFIX)
 }
 
 pub fn is_internal_opt_key<S: AsRef<str>>(opt_key: S) -> bool {
-    INTERNAL_TABLE_OPTION_KEYS.contains(opt_key.as_ref().to_lowercase().as_str())
+    let opt_key = opt_key.as_ref().to_lowercase();
+    INTERNAL_TABLE_OPTION_KEYS.contains(opt_key.as_str())
+        || opt_key.starts_with(OPT_KEY_AGGREGATING_INDEX_PREFIX)
+        || opt_key.starts_with(OPT_KEY_BLOOM_INDEX_PREFIX)
+        || opt_key.starts_with(OPT_KEY_INVERTED_INDEX_PREFIX)
 }
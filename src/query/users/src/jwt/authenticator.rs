@@ -12,18 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
 use jwtk::jwk::RemoteJwksVerifier;
+use jwtk::Claims;
 use jwtk::HeaderAndClaims;
 use serde::Deserialize;
 use serde::Serialize;
 
 pub struct JwtAuthenticator {
     // Todo(youngsofun): verify settings, like issuer
-    verifier: RemoteJwksVerifier,
+    verifiers: Vec<RemoteJwksVerifier>,
+    // the claim taken as the user name
+    user_claim: String,
 }
 
 #[derive(Default, Deserialize, Serialize)]
@@ -36,6 +40,9 @@ pub struct CustomClaims {
     pub tenant_id: Option<String>,
     pub role: Option<String>,
     pub ensure_user: Option<EnsureUser>,
+    // the claims not known by databend, which may be mapped to the user name
+    #[serde(flatten)]
+    pub others: BTreeMap<String, serde_json::Value>,
 }
 
 impl CustomClaims {
@@ -44,6 +51,7 @@ impl CustomClaims {
             tenant_id: None,
             role: None,
             ensure_user: None,
+            others: BTreeMap::new(),
         }
     }
 
@@ -61,28 +69,70 @@ impl CustomClaims {
         self.ensure_user = Some(ensure_user);
         self
     }
+
+    pub fn with_claim(mut self, name: &str, value: &str) -> Self {
+        self.others.insert(name.to_string(), value.into());
+        self
+    }
 }
 
 impl JwtAuthenticator {
-    pub async fn try_create(jwt_key_file: String) -> Result<Option<Self>> {
-        if jwt_key_file.is_empty() {
+    /// None if no JWKS endpoint is configured.
+    pub async fn try_create(
+        jwt_key_file: String,
+        jwt_key_files: Vec<String>,
+        jwt_user_claim: String,
+    ) -> Result<Option<Self>> {
+        let verifiers = std::iter::once(jwt_key_file)
+            .chain(jwt_key_files.into_iter())
+            .filter(|url| !url.is_empty())
+            .map(|url| {
+                let mut verifier = RemoteJwksVerifier::new(url, None, Duration::from_secs(15 * 60));
+                verifier.set_require_kid(false);
+                verifier
+            })
+            .collect::<Vec<_>>();
+        if verifiers.is_empty() {
             return Ok(None);
         }
-        let mut verifier =
-            RemoteJwksVerifier::new(jwt_key_file, None, Duration::from_secs(15 * 60));
-        verifier.set_require_kid(false);
-        Ok(Some(JwtAuthenticator { verifier }))
+        Ok(Some(JwtAuthenticator {
+            verifiers,
+            user_claim: jwt_user_claim,
+        }))
     }
 
+    /// Verify the token by the JWKS endpoints in order, the first one accepting it wins.
     pub async fn parse_jwt(&self, token: &str) -> Result<HeaderAndClaims<CustomClaims>> {
-        match self.verifier.verify::<CustomClaims>(token).await {
-            Ok(c) => match c.claims().sub {
-                None => Err(ErrorCode::AuthenticateFailure(
-                    "missing field `subject` in jwt",
-                )),
-                Some(_) => Ok(c),
-            },
-            Err(e) => Err(ErrorCode::AuthenticateFailure(e.to_string())),
+        let mut errors = Vec::with_capacity(self.verifiers.len());
+        for verifier in &self.verifiers {
+            match verifier.verify::<CustomClaims>(token).await {
+                Ok(c) => {
+                    self.user_name(c.claims())?;
+                    return Ok(c);
+                }
+                Err(e) => errors.push(e.to_string()),
+            }
         }
+        Err(ErrorCode::AuthenticateFailure(errors.join("; ")))
+    }
+
+    /// The user name of the claims, taken from the claim `jwt_user_claim`.
+    pub fn user_name(&self, claims: &Claims<CustomClaims>) -> Result<String> {
+        let user_name = match self.user_claim.as_str() {
+            "sub" => claims.sub.clone(),
+            claim => claims
+                .extra
+                .others
+                .get(claim)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string()),
+        };
+        user_name.ok_or_else(|| {
+            let field = match self.user_claim.as_str() {
+                "sub" => "subject",
+                claim => claim,
+            };
+            ErrorCode::AuthenticateFailure(format!("missing field `{}` in jwt", field))
+        })
     }
 }
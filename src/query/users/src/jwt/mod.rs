@@ -13,7 +13,11 @@
 // limitations under the License.
 
 mod authenticator;
+mod oidc;
 
 pub use authenticator::CustomClaims;
 pub use authenticator::EnsureUser;
 pub use authenticator::JwtAuthenticator;
+pub use oidc::OAuthToken;
+pub use oidc::OidcClient;
+pub use oidc::OidcConfig;
@@ -0,0 +1,392 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use common_base::base::tokio::sync::OnceCell as AsyncOnceCell;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use hmac::Hmac;
+use hmac::Mac;
+use jwtk::jwk::RemoteJwksVerifier;
+use jwtk::Claims;
+use jwtk::HeaderAndClaims;
+use once_cell::sync::OnceCell;
+use rand::RngCore;
+use reqwest::Url;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::CustomClaims;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// the authorization code flows not completed in time are rejected
+const STATE_TTL: Duration = Duration::from_secs(10 * 60);
+
+static OIDC_CLIENT: OnceCell<Arc<OidcClient>> = OnceCell::new();
+
+/// The settings of the OpenID Connect provider, taken from the `oidc_*` settings of the query
+/// config.
+#[derive(Clone, Debug, Default)]
+pub struct OidcConfig {
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_url: String,
+    pub scopes: String,
+    pub audience: String,
+    pub user_claim: String,
+    pub role_claim: String,
+    pub role_mapping: String,
+}
+
+/// The token responded by the token endpoint of the provider.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OAuthToken {
+    pub access_token: String,
+    pub token_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+}
+
+// https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata
+#[derive(Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+/// The client of an OpenID Connect provider, like Okta and Azure AD, for the single sign-on of
+/// the HTTP API.
+///
+/// The endpoints of the provider are discovered from the issuer on the first use. The tokens
+/// issued by the provider for the client, or for the `audience`, are accepted as bearer tokens,
+/// the users are named by the claim `user_claim`, and the values of the claim `role_claim` are
+/// mapped to the roles by `role_mapping`.
+pub struct OidcClient {
+    config: OidcConfig,
+    http: reqwest::Client,
+    metadata: AsyncOnceCell<ProviderMetadata>,
+    verifier: AsyncOnceCell<RemoteJwksVerifier>,
+    // the roles granted for the values of the role claim
+    role_mapping: BTreeMap<String, String>,
+}
+
+impl OidcClient {
+    /// Does nothing if no issuer is configured.
+    pub fn init(config: OidcConfig) -> Result<()> {
+        if let Some(client) = Self::try_create(config)? {
+            OIDC_CLIENT.set(Arc::new(client)).ok();
+        }
+        Ok(())
+    }
+
+    pub fn instance() -> Option<Arc<OidcClient>> {
+        OIDC_CLIENT.get().cloned()
+    }
+
+    /// None if no issuer is configured.
+    pub fn try_create(config: OidcConfig) -> Result<Option<Self>> {
+        if config.issuer.is_empty() {
+            return Ok(None);
+        }
+        if config.client_id.is_empty() {
+            return Err(ErrorCode::InvalidConfig(
+                "oidc_client_id must be set with oidc_issuer",
+            ));
+        }
+
+        let mut role_mapping = BTreeMap::new();
+        for item in config.role_mapping.split(';').map(str::trim) {
+            if item.is_empty() {
+                continue;
+            }
+            match item.rsplit_once(':') {
+                Some((value, role)) if !value.trim().is_empty() && !role.trim().is_empty() => {
+                    role_mapping.insert(value.trim().to_string(), role.trim().to_string());
+                }
+                _ => {
+                    return Err(ErrorCode::InvalidConfig(format!(
+                        "invalid oidc_role_mapping {}, expect <claim_value>:<role>",
+                        item
+                    )));
+                }
+            }
+        }
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                ErrorCode::InvalidConfig(format!("failed to create oidc client: {}", e))
+            })?;
+        Ok(Some(OidcClient {
+            config,
+            http,
+            metadata: AsyncOnceCell::new(),
+            verifier: AsyncOnceCell::new(),
+            role_mapping,
+        }))
+    }
+
+    /// The URL of the authorization endpoint the users are redirected to for logging in, by the
+    /// authorization code flow.
+    pub async fn authorize_url(&self) -> Result<String> {
+        let metadata = self.metadata().await?;
+        let url = Url::parse_with_params(&metadata.authorization_endpoint, &[
+            ("response_type", "code"),
+            ("client_id", self.config.client_id.as_str()),
+            ("redirect_uri", self.config.redirect_url.as_str()),
+            ("scope", self.config.scopes.as_str()),
+            ("state", self.new_state().as_str()),
+        ])
+        .map_err(|e| {
+            ErrorCode::AuthenticateFailure(format!("bad authorization endpoint: {}", e))
+        })?;
+        Ok(url.to_string())
+    }
+
+    /// Exchanges the code of the authorization code flow for the tokens, the `state` must be the
+    /// one of the authorization URL.
+    pub async fn exchange_code(&self, code: &str, state: &str) -> Result<OAuthToken> {
+        self.check_state(state)?;
+        self.request_token(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.config.redirect_url.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+            ("client_secret", self.config.client_secret.as_str()),
+        ])
+        .await
+    }
+
+    /// Requests a token by the client credentials flow, for the clients without users, like the
+    /// notebooks and the scheduled jobs.
+    pub async fn client_credentials(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+        scope: Option<&str>,
+    ) -> Result<OAuthToken> {
+        let mut params = vec![
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+        ];
+        if let Some(scope) = scope {
+            params.push(("scope", scope));
+        }
+        self.request_token(&params).await
+    }
+
+    /// Verifies the token by the keys of the provider, it must be issued by the issuer for the
+    /// client or the audience.
+    pub async fn verify(&self, token: &str) -> Result<HeaderAndClaims<CustomClaims>> {
+        let metadata = self.metadata().await?;
+        let verifier = self
+            .verifier
+            .get_or_init(|| async {
+                let mut verifier = RemoteJwksVerifier::new(
+                    metadata.jwks_uri.clone(),
+                    None,
+                    Duration::from_secs(15 * 60),
+                );
+                verifier.set_require_kid(false);
+                verifier
+            })
+            .await;
+        let parsed = verifier
+            .verify::<CustomClaims>(token)
+            .await
+            .map_err(|e| ErrorCode::AuthenticateFailure(e.to_string()))?;
+
+        let payload = token_payload(token)?;
+        if payload.get("iss").and_then(|v| v.as_str()) != Some(metadata.issuer.as_str()) {
+            return Err(ErrorCode::AuthenticateFailure(
+                "the token is not issued by the oidc issuer",
+            ));
+        }
+        // the ID tokens are issued for the client, and the access tokens for the audience
+        let audiences = [&self.config.client_id, &self.config.audience];
+        let matches_audience = |aud: &serde_json::Value| {
+            audiences
+                .iter()
+                .any(|audience| !audience.is_empty() && aud.as_str() == Some(audience.as_str()))
+        };
+        let aud_matched = match payload.get("aud") {
+            Some(serde_json::Value::Array(auds)) => auds.iter().any(matches_audience),
+            Some(aud) => matches_audience(aud),
+            None => false,
+        };
+        if !aud_matched {
+            return Err(ErrorCode::AuthenticateFailure(
+                "the token is not issued for the client or the audience",
+            ));
+        }
+        self.user_name(parsed.claims())?;
+        Ok(parsed)
+    }
+
+    /// The user name of the claims, taken from the claim `user_claim`, or else the subject, e.g.
+    /// the tokens of the client credentials flow name the clients by the subjects.
+    pub fn user_name(&self, claims: &Claims<CustomClaims>) -> Result<String> {
+        claims
+            .extra
+            .others
+            .get(&self.config.user_claim)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+            .or_else(|| claims.sub.clone())
+            .ok_or_else(|| {
+                ErrorCode::AuthenticateFailure(format!(
+                    "missing field `{}` in the oidc token",
+                    self.config.user_claim
+                ))
+            })
+    }
+
+    /// All the roles the values of the role claim may be mapped to.
+    pub fn mapped_roles(&self) -> BTreeSet<String> {
+        self.role_mapping.values().cloned().collect()
+    }
+
+    /// The roles mapped from the values of the role claim, which is a string or an array of
+    /// strings.
+    pub fn roles(&self, claims: &Claims<CustomClaims>) -> Vec<String> {
+        let values = match claims.extra.others.get(&self.config.role_claim) {
+            Some(serde_json::Value::String(value)) => vec![value.as_str()],
+            Some(serde_json::Value::Array(values)) => {
+                values.iter().filter_map(|v| v.as_str()).collect()
+            }
+            _ => vec![],
+        };
+        let roles = values
+            .into_iter()
+            .filter_map(|value| self.role_mapping.get(value))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        roles.into_iter().collect()
+    }
+
+    async fn metadata(&self) -> Result<&ProviderMetadata> {
+        // not cached on failures, to recover once the provider is reachable
+        self.metadata
+            .get_or_try_init(|| async {
+                let url = format!(
+                    "{}/.well-known/openid-configuration",
+                    self.config.issuer.trim_end_matches('/')
+                );
+                let metadata = self
+                    .http
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(|res| res.error_for_status())
+                    .map_err(|e| oidc_error("discover", e))?
+                    .json::<ProviderMetadata>()
+                    .await
+                    .map_err(|e| oidc_error("discover", e))?;
+                Ok(metadata)
+            })
+            .await
+    }
+
+    async fn request_token(&self, params: &[(&str, &str)]) -> Result<OAuthToken> {
+        let metadata = self.metadata().await?;
+        let res = self
+            .http
+            .post(&metadata.token_endpoint)
+            .form(params)
+            .send()
+            .await
+            .map_err(|e| oidc_error("request the token from", e))?;
+        let status = res.status();
+        if !status.is_success() {
+            let body = res.text().await.unwrap_or_default();
+            return Err(ErrorCode::AuthenticateFailure(format!(
+                "the oidc provider rejected the token request: {} {}",
+                status, body
+            )));
+        }
+        res.json::<OAuthToken>()
+            .await
+            .map_err(|e| oidc_error("request the token from", e))
+    }
+
+    // The state of the authorization code flow is the expiration time signed by the client
+    // secret, so that any query node can complete the flow.
+    fn new_state(&self) -> String {
+        let mut nonce = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let expire_at = (SystemTime::now() + STATE_TTL)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let payload = format!("{}.{}", hex::encode(nonce), expire_at);
+        let signature = self.mac(&payload).finalize().into_bytes();
+        format!("{}.{}", payload, hex::encode(signature))
+    }
+
+    fn check_state(&self, state: &str) -> Result<()> {
+        let bad_state = || ErrorCode::AuthenticateFailure("bad oauth state");
+        let (payload, signature) = state.rsplit_once('.').ok_or_else(bad_state)?;
+        let signature = hex::decode(signature).map_err(|_| bad_state())?;
+        self.mac(payload)
+            .verify_slice(&signature)
+            .map_err(|_| bad_state())?;
+        let expire_at = payload
+            .rsplit_once('.')
+            .and_then(|(_, expire_at)| expire_at.parse::<u64>().ok())
+            .ok_or_else(bad_state)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if now > expire_at {
+            return Err(ErrorCode::AuthenticateFailure("oauth state expired"));
+        }
+        Ok(())
+    }
+
+    fn mac(&self, payload: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.config.client_secret.as_bytes())
+            .expect("hmac accepts keys of any length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+// the claims of the verified token, which are not all kept by `Claims`
+fn token_payload(token: &str) -> Result<serde_json::Value> {
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| ErrorCode::AuthenticateFailure("bad token"))?;
+    let payload = base64::decode_config(payload, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| ErrorCode::AuthenticateFailure("bad token"))?;
+    serde_json::from_slice(&payload).map_err(|_| ErrorCode::AuthenticateFailure("bad token"))
+}
+
+fn oidc_error(action: &str, e: reqwest::Error) -> ErrorCode {
+    ErrorCode::AuthenticateFailure(format!("failed to {} the oidc provider: {}", action, e))
+}
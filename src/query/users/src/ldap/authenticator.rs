@@ -0,0 +1,222 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use ldap3::dn_escape;
+use ldap3::ldap_escape;
+use ldap3::Ldap;
+use ldap3::LdapConnAsync;
+use ldap3::LdapConnSettings;
+use ldap3::LdapError;
+use ldap3::Scope;
+use ldap3::SearchEntry;
+
+const USER_PLACEHOLDER: &str = "{user}";
+
+/// The settings of the LDAP server, taken from the `ldap_*` settings of the query config.
+#[derive(Clone, Debug, Default)]
+pub struct LdapConfig {
+    pub url: String,
+    pub starttls: bool,
+    pub bind_dn: String,
+    pub search_base_dn: String,
+    pub search_filter: String,
+    pub search_bind_dn: String,
+    pub search_bind_password: String,
+    pub group_attribute: String,
+    pub role_mapping: String,
+}
+
+/// Authenticates the users by binding to the LDAP server as them.
+///
+/// The DN of a user is either made from the template `bind_dn`, or searched for under
+/// `search_base_dn` by `search_filter`. The groups of the user are read from the attribute
+/// `group_attribute` of its entry, and mapped to the roles by `role_mapping`.
+pub struct LdapAuthenticator {
+    config: LdapConfig,
+    // the roles granted to the members of the groups, keyed by the normalized group DNs
+    role_mapping: BTreeMap<String, String>,
+}
+
+impl LdapAuthenticator {
+    /// None if no LDAP server is configured.
+    pub fn try_create(config: LdapConfig) -> Result<Option<Self>> {
+        if config.url.is_empty() {
+            return Ok(None);
+        }
+        if config.bind_dn.is_empty() && config.search_base_dn.is_empty() {
+            return Err(ErrorCode::InvalidConfig(
+                "either ldap_bind_dn or ldap_search_base_dn must be set",
+            ));
+        }
+
+        let mut role_mapping = BTreeMap::new();
+        for item in config.role_mapping.split(';').map(str::trim) {
+            if item.is_empty() {
+                continue;
+            }
+            match item.rsplit_once(':') {
+                Some((group, role)) if !group.trim().is_empty() && !role.trim().is_empty() => {
+                    role_mapping.insert(normalize_dn(group), role.trim().to_string());
+                }
+                _ => {
+                    return Err(ErrorCode::InvalidConfig(format!(
+                        "invalid ldap_role_mapping {}, expect <group_dn>:<role>",
+                        item
+                    )));
+                }
+            }
+        }
+        Ok(Some(LdapAuthenticator {
+            config,
+            role_mapping,
+        }))
+    }
+
+    /// All the roles the groups may be mapped to.
+    pub fn mapped_roles(&self) -> BTreeSet<String> {
+        self.role_mapping.values().cloned().collect()
+    }
+
+    /// Verifies the password of the user by binding as it, and returns the roles mapped from
+    /// the groups of the user.
+    pub async fn authenticate(&self, user: &str, password: &[u8]) -> Result<Vec<String>> {
+        // a simple bind with an empty password is an unauthenticated bind, which always succeeds
+        if password.is_empty() {
+            return Err(ErrorCode::AuthenticateFailure("password required"));
+        }
+        let password = std::str::from_utf8(password)
+            .map_err(|_| ErrorCode::AuthenticateFailure("password is not valid utf-8"))?;
+
+        let settings = LdapConnSettings::new()
+            .set_starttls(self.config.starttls)
+            .set_conn_timeout(Duration::from_secs(10));
+        let (conn, mut ldap) = LdapConnAsync::with_settings(settings, &self.config.url)
+            .await
+            .map_err(|e| ldap_error("connect to", e))?;
+        ldap3::drive!(conn);
+
+        let res = self.bind_and_get_groups(&mut ldap, user, password).await;
+        if let Err(e) = ldap.unbind().await {
+            tracing::warn!("failed to unbind from the LDAP server: {}", e);
+        }
+
+        let roles = res?
+            .iter()
+            .filter_map(|group| self.role_mapping.get(&normalize_dn(group)))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        Ok(roles.into_iter().collect())
+    }
+
+    async fn bind_and_get_groups(
+        &self,
+        ldap: &mut Ldap,
+        user: &str,
+        password: &str,
+    ) -> Result<Vec<String>> {
+        let attrs = vec![self.config.group_attribute.as_str()];
+        if !self.config.bind_dn.is_empty() {
+            let dn = self
+                .config
+                .bind_dn
+                .replace(USER_PLACEHOLDER, &dn_escape(user));
+            simple_bind(ldap, &dn, password).await?;
+            let (entries, _) = ldap
+                .search(&dn, Scope::Base, "(objectClass=*)", attrs)
+                .await
+                .and_then(|res| res.success())
+                .map_err(|e| ldap_error("read the groups from", e))?;
+            return Ok(match entries.into_iter().next() {
+                Some(entry) => self.groups(SearchEntry::construct(entry)),
+                None => vec![],
+            });
+        }
+
+        if !self.config.search_bind_dn.is_empty() {
+            simple_bind(
+                ldap,
+                &self.config.search_bind_dn,
+                &self.config.search_bind_password,
+            )
+            .await?;
+        }
+        let filter = self
+            .config
+            .search_filter
+            .replace(USER_PLACEHOLDER, &ldap_escape(user));
+        let (mut entries, _) = ldap
+            .search(&self.config.search_base_dn, Scope::Subtree, &filter, attrs)
+            .await
+            .and_then(|res| res.success())
+            .map_err(|e| ldap_error("search for the user on", e))?;
+        let entry = match entries.len() {
+            1 => SearchEntry::construct(entries.remove(0)),
+            0 => {
+                return Err(ErrorCode::AuthenticateFailure(format!(
+                    "user {} not found on the LDAP server",
+                    user
+                )));
+            }
+            _ => {
+                return Err(ErrorCode::AuthenticateFailure(format!(
+                    "user {} matches multiple entries on the LDAP server",
+                    user
+                )));
+            }
+        };
+        simple_bind(ldap, &entry.dn, password).await?;
+        Ok(self.groups(entry))
+    }
+
+    fn groups(&self, entry: SearchEntry) -> Vec<String> {
+        // the attribute names are case insensitive
+        entry
+            .attrs
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&self.config.group_attribute))
+            .map(|(_, groups)| groups)
+            .unwrap_or_default()
+    }
+}
+
+async fn simple_bind(ldap: &mut Ldap, dn: &str, password: &str) -> Result<()> {
+    ldap.simple_bind(dn, password)
+        .await
+        .and_then(|res| res.success())
+        .map(|_| ())
+        .map_err(|e| match e {
+            LdapError::LdapResult { result } => {
+                ErrorCode::AuthenticateFailure(format!("ldap bind as {} failed: {}", dn, result))
+            }
+            e => ldap_error("bind to", e),
+        })
+}
+
+fn ldap_error(action: &str, e: LdapError) -> ErrorCode {
+    ErrorCode::AuthenticateFailure(format!("failed to {} the LDAP server: {}", action, e))
+}
+
+// the DNs are compared case insensitively, ignoring the spaces around the separators
+fn normalize_dn(dn: &str) -> String {
+    dn.split(',')
+        .map(|rdn| rdn.trim().to_lowercase())
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -13,19 +13,26 @@
 // limitations under the License.
 
 mod jwt;
+mod ldap;
+mod masking_policy;
+mod network_policy;
 mod role_mgr;
+mod row_access_policy;
+mod task;
 mod user;
 mod user_api;
 mod user_mgr;
 mod user_setting;
 mod user_stage;
 mod user_udf;
+mod workload_group;
 
 pub mod idm_config;
 pub mod role_cache_mgr;
 pub mod role_util;
 
 pub use jwt::*;
+pub use ldap::*;
 pub use role_cache_mgr::RoleCacheManager;
 pub use user::CertifiedInfo;
 pub use user_api::UserApiProvider;
@@ -0,0 +1,137 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::MaskedColumn;
+use common_meta_types::MaskingPolicy;
+
+use crate::UserApiProvider;
+
+/// Masking policy operations.
+impl UserApiProvider {
+    // Add a new masking policy.
+    pub async fn add_masking_policy(
+        &self,
+        tenant: &str,
+        policy: MaskingPolicy,
+        if_not_exists: bool,
+    ) -> Result<u64> {
+        let policy_api_client = self.get_masking_policy_api_client(tenant)?;
+        match policy_api_client.add_policy(policy).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_not_exists && e.code() == ErrorCode::masking_policy_already_exists_code() {
+                    Ok(u64::MIN)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    // Get all the masking policies for the tenant.
+    pub async fn get_masking_policies(&self, tenant: &str) -> Result<Vec<MaskingPolicy>> {
+        let policy_api_client = self.get_masking_policy_api_client(tenant)?;
+        match policy_api_client.get_policies().await {
+            Err(e) => Err(e.add_message_back("(while get masking policies).")),
+            Ok(policies) => Ok(policies),
+        }
+    }
+
+    // Drop a masking policy by name, the policy must not be attached to any column.
+    pub async fn drop_masking_policy(
+        &self,
+        tenant: &str,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        let policy_api_client = self.get_masking_policy_api_client(tenant)?;
+        let res = match policy_api_client.get_policy(name, None).await {
+            Ok(seq_policy) if !seq_policy.data.columns.is_empty() => {
+                Err(ErrorCode::MaskingPolicyInUse(format!(
+                    "Masking policy {} is attached to {} column(s)",
+                    name,
+                    seq_policy.data.columns.len()
+                )))
+            }
+            Ok(seq_policy) => {
+                policy_api_client
+                    .drop_policy(name, Some(seq_policy.seq))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        match res {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_exists && e.code() == ErrorCode::unknown_masking_policy_code() {
+                    Ok(())
+                } else {
+                    Err(e.add_message_back("(while drop masking policy)"))
+                }
+            }
+        }
+    }
+
+    // Attach a masking policy to a column, a column has at most one masking policy.
+    pub async fn set_column_masking_policy(
+        &self,
+        tenant: &str,
+        column: MaskedColumn,
+        name: &str,
+    ) -> Result<()> {
+        let policy_api_client = self.get_masking_policy_api_client(tenant)?;
+        let seq_policy = policy_api_client.get_policy(name, None).await?;
+        if seq_policy.data.columns.contains(&column) {
+            return Ok(());
+        }
+
+        for policy in policy_api_client.get_policies().await? {
+            if policy.columns.contains(&column) {
+                return Err(ErrorCode::MaskingPolicyInUse(format!(
+                    "Column {} already has the masking policy {}",
+                    column.column, policy.name
+                )));
+            }
+        }
+
+        let mut policy = seq_policy.data;
+        policy.columns.push(column);
+        policy_api_client
+            .update_policy(policy, Some(seq_policy.seq))
+            .await?;
+        Ok(())
+    }
+
+    // Detach the masking policy of a column, if any.
+    pub async fn unset_column_masking_policy(
+        &self,
+        tenant: &str,
+        column: &MaskedColumn,
+    ) -> Result<()> {
+        let policy_api_client = self.get_masking_policy_api_client(tenant)?;
+        for policy in policy_api_client.get_policies().await? {
+            if policy.columns.contains(column) {
+                let seq_policy = policy_api_client.get_policy(&policy.name, None).await?;
+                let mut policy = seq_policy.data;
+                policy.columns.retain(|c| c != column);
+                policy_api_client
+                    .update_policy(policy, Some(seq_policy.seq))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
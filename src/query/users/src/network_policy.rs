@@ -0,0 +1,145 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::IpAddr;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::NetworkPolicy;
+use common_meta_types::UserInfo;
+
+use crate::UserApiProvider;
+
+/// The setting of the network policy applied to all the users of the tenant.
+const TENANT_NETWORK_POLICY_SETTING: &str = "network_policy";
+
+/// Network policy operations.
+impl UserApiProvider {
+    // Add a new network policy.
+    pub async fn add_network_policy(
+        &self,
+        tenant: &str,
+        policy: NetworkPolicy,
+        if_not_exists: bool,
+    ) -> Result<u64> {
+        policy.validate()?;
+
+        let policy_api_client = self.get_network_policy_api_client(tenant)?;
+        match policy_api_client.add_policy(policy).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_not_exists && e.code() == ErrorCode::network_policy_already_exists_code() {
+                    Ok(u64::MIN)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    // Get a network policy by name.
+    pub async fn get_network_policy(&self, tenant: &str, name: &str) -> Result<NetworkPolicy> {
+        let policy_api_client = self.get_network_policy_api_client(tenant)?;
+        Ok(policy_api_client.get_policy(name, None).await?.data)
+    }
+
+    // Get all the network policies for the tenant.
+    pub async fn get_network_policies(&self, tenant: &str) -> Result<Vec<NetworkPolicy>> {
+        let policy_api_client = self.get_network_policy_api_client(tenant)?;
+        match policy_api_client.get_policies().await {
+            Err(e) => Err(e.add_message_back("(while get network policies).")),
+            Ok(policies) => Ok(policies),
+        }
+    }
+
+    // Drop a network policy by name, the policy must not be used by the tenant or any user.
+    pub async fn drop_network_policy(
+        &self,
+        tenant: &str,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        if self.get_tenant_network_policy(tenant).await?.as_deref() == Some(name) {
+            return Err(ErrorCode::NetworkPolicyInUse(format!(
+                "Network policy {} is used by the tenant",
+                name
+            )));
+        }
+        for user in self.get_users(tenant).await? {
+            if user.option.network_policy().map(|p| p.as_str()) == Some(name) {
+                return Err(ErrorCode::NetworkPolicyInUse(format!(
+                    "Network policy {} is used by the user {}",
+                    name,
+                    user.identity()
+                )));
+            }
+        }
+
+        let policy_api_client = self.get_network_policy_api_client(tenant)?;
+        match policy_api_client.drop_policy(name, None).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_exists && e.code() == ErrorCode::unknown_network_policy_code() {
+                    Ok(())
+                } else {
+                    Err(e.add_message_back("(while drop network policy)"))
+                }
+            }
+        }
+    }
+
+    // Get the network policy of the tenant, it is set by `SET GLOBAL network_policy`.
+    pub async fn get_tenant_network_policy(&self, tenant: &str) -> Result<Option<String>> {
+        for setting in self.get_settings(tenant).await? {
+            if setting.name == TENANT_NETWORK_POLICY_SETTING {
+                let name = setting.value.as_string()?;
+                return Ok(Some(name).filter(|name| !name.is_empty()));
+            }
+        }
+        Ok(None)
+    }
+
+    // Check the client address of the user against the network policies of the tenant and the
+    // user, an unknown address is rejected if any policy applies.
+    pub async fn verify_client_ip(
+        &self,
+        tenant: &str,
+        user: &UserInfo,
+        client_ip: &str,
+    ) -> Result<()> {
+        let policy_names = self
+            .get_tenant_network_policy(tenant)
+            .await?
+            .into_iter()
+            .chain(user.option.network_policy().cloned());
+
+        let ip = client_ip.parse::<IpAddr>();
+        for name in policy_names {
+            let policy = self.get_network_policy(tenant, &name).await?;
+            let allowed = match &ip {
+                Ok(ip) => policy.is_allowed(ip),
+                Err(_) => false,
+            };
+            if !allowed {
+                return Err(ErrorCode::NetworkPolicyViolation(format!(
+                    "Client address {} of user {} is not allowed by the network policy {}",
+                    client_ip,
+                    user.identity(),
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::Task;
+use common_meta_types::TaskRun;
+
+use crate::UserApiProvider;
+
+/// Task operations.
+impl UserApiProvider {
+    // Add a new task.
+    pub async fn add_task(&self, tenant: &str, task: Task, if_not_exists: bool) -> Result<u64> {
+        task.validate()?;
+
+        let task_api_client = self.get_task_api_client(tenant)?;
+        match task_api_client.add_task(task).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_not_exists && e.code() == ErrorCode::task_already_exists_code() {
+                    Ok(u64::MIN)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    // Get all the tasks for the tenant.
+    pub async fn get_tasks(&self, tenant: &str) -> Result<Vec<Task>> {
+        let task_api_client = self.get_task_api_client(tenant)?;
+        match task_api_client.get_tasks().await {
+            Err(e) => Err(e.add_message_back("(while get tasks).")),
+            Ok(tasks) => Ok(tasks),
+        }
+    }
+
+    // Drop a task by name, the runs of the task are kept until they expire.
+    pub async fn drop_task(&self, tenant: &str, name: &str, if_exists: bool) -> Result<()> {
+        let task_api_client = self.get_task_api_client(tenant)?;
+        match task_api_client.drop_task(name, None).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_exists && e.code() == ErrorCode::unknown_task_code() {
+                    Ok(())
+                } else {
+                    Err(e.add_message_back("(while drop task)"))
+                }
+            }
+        }
+    }
+
+    // Get the runs of all the tasks for the tenant.
+    pub async fn get_task_runs(&self, tenant: &str) -> Result<Vec<TaskRun>> {
+        let task_api_client = self.get_task_api_client(tenant)?;
+        match task_api_client.get_runs().await {
+            Err(e) => Err(e.add_message_back("(while get task runs).")),
+            Ok(runs) => Ok(runs),
+        }
+    }
+}
@@ -17,18 +17,28 @@ use std::sync::Arc;
 use common_base::base::Singleton;
 use common_exception::Result;
 use common_grpc::RpcClientConf;
+use common_management::MaskingPolicyApi;
+use common_management::MaskingPolicyMgr;
+use common_management::NetworkPolicyApi;
+use common_management::NetworkPolicyMgr;
 use common_management::QuotaApi;
 use common_management::QuotaMgr;
 use common_management::RoleApi;
 use common_management::RoleMgr;
+use common_management::RowAccessPolicyApi;
+use common_management::RowAccessPolicyMgr;
 use common_management::SettingApi;
 use common_management::SettingMgr;
 use common_management::StageApi;
 use common_management::StageMgr;
+use common_management::TaskApi;
+use common_management::TaskMgr;
 use common_management::UdfApi;
 use common_management::UdfMgr;
 use common_management::UserApi;
 use common_management::UserMgr;
+use common_management::WorkloadGroupApi;
+use common_management::WorkloadGroupMgr;
 use common_meta_api::KVApi;
 use common_meta_store::MetaStore;
 use common_meta_store::MetaStoreProvider;
@@ -96,6 +106,30 @@ impl UserApiProvider {
         Ok(Arc::new(UdfMgr::create(self.client.clone(), tenant)?))
     }
 
+    pub fn get_masking_policy_api_client(&self, tenant: &str) -> Result<Arc<dyn MaskingPolicyApi>> {
+        Ok(Arc::new(MaskingPolicyMgr::create(
+            self.client.clone(),
+            tenant,
+        )?))
+    }
+
+    pub fn get_network_policy_api_client(&self, tenant: &str) -> Result<Arc<dyn NetworkPolicyApi>> {
+        Ok(Arc::new(NetworkPolicyMgr::create(
+            self.client.clone(),
+            tenant,
+        )?))
+    }
+
+    pub fn get_row_access_policy_api_client(
+        &self,
+        tenant: &str,
+    ) -> Result<Arc<dyn RowAccessPolicyApi>> {
+        Ok(Arc::new(RowAccessPolicyMgr::create(
+            self.client.clone(),
+            tenant,
+        )?))
+    }
+
     pub fn get_tenant_quota_api_client(&self, tenant: &str) -> Result<Arc<dyn QuotaApi>> {
         Ok(Arc::new(QuotaMgr::create(self.client.clone(), tenant)?))
     }
@@ -104,6 +138,17 @@ impl UserApiProvider {
         Ok(Arc::new(SettingMgr::create(self.client.clone(), tenant)?))
     }
 
+    pub fn get_workload_group_api_client(&self, tenant: &str) -> Result<Arc<dyn WorkloadGroupApi>> {
+        Ok(Arc::new(WorkloadGroupMgr::create(
+            self.client.clone(),
+            tenant,
+        )?))
+    }
+
+    pub fn get_task_api_client(&self, tenant: &str) -> Result<Arc<dyn TaskApi>> {
+        Ok(Arc::new(TaskMgr::create(self.client.clone(), tenant)?))
+    }
+
     pub fn get_meta_store_client(&self) -> Arc<MetaStore> {
         Arc::new(self.meta.clone())
     }
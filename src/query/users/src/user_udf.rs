@@ -20,7 +20,8 @@ use crate::UserApiProvider;
 
 /// UDF operations.
 impl UserApiProvider {
-    // Add a new UDF.
+    // Add a new UDF, or a new overload of an existing UDF which takes a different number of
+    // parameters.
     pub async fn add_udf(
         &self,
         tenant: &str,
@@ -28,8 +29,25 @@ impl UserApiProvider {
         if_not_exists: bool,
     ) -> Result<u64> {
         let udf_api_client = self.get_udf_api_client(tenant)?;
-        let add_udf = udf_api_client.add_udf(info);
-        match add_udf.await {
+        let add_udf = udf_api_client.add_udf(info.clone());
+        let res = match add_udf.await {
+            Err(e) if e.code() == ErrorCode::udf_already_exists_code() => {
+                match udf_api_client.get_udf(&info.name, None).await {
+                    Ok(seq_udf) => {
+                        let mut udf = seq_udf.data;
+                        match udf.add_overload(info) {
+                            Ok(_) => udf_api_client.update_udf(udf, Some(seq_udf.seq)).await,
+                            Err(e) => Err(e),
+                        }
+                    }
+                    // The name is taken by a builtin function.
+                    Err(_) => Err(e),
+                }
+            }
+            res => res,
+        };
+
+        match res {
             Ok(res) => Ok(res),
             Err(e) => {
                 if if_not_exists && e.code() == ErrorCode::udf_already_exists_code() {
@@ -41,10 +59,15 @@ impl UserApiProvider {
         }
     }
 
-    // Update a UDF.
+    // Update the definition of a UDF which takes the same number of parameters.
     pub async fn update_udf(&self, tenant: &str, info: UserDefinedFunction) -> Result<u64> {
         let udf_api_client = self.get_udf_api_client(tenant)?;
-        let update_udf = udf_api_client.update_udf(info, None);
+        let update_udf = async {
+            let seq_udf = udf_api_client.get_udf(&info.name, None).await?;
+            let mut udf = seq_udf.data;
+            udf.replace_overload(info)?;
+            udf_api_client.update_udf(udf, Some(seq_udf.seq)).await
+        };
         match update_udf.await {
             Ok(res) => Ok(res),
             Err(e) => Err(e.add_message_back("(while update UDF).")),
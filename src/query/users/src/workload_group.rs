@@ -0,0 +1,101 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_types::UserInfo;
+use common_meta_types::WorkloadGroup;
+
+use crate::UserApiProvider;
+
+/// Workload group operations.
+impl UserApiProvider {
+    // Add a new workload group.
+    pub async fn add_workload_group(
+        &self,
+        tenant: &str,
+        group: WorkloadGroup,
+        if_not_exists: bool,
+    ) -> Result<u64> {
+        group.validate()?;
+
+        let group_api_client = self.get_workload_group_api_client(tenant)?;
+        match group_api_client.add_group(group).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_not_exists && e.code() == ErrorCode::workload_group_already_exists_code() {
+                    Ok(u64::MIN)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    // Get a workload group by name.
+    pub async fn get_workload_group(&self, tenant: &str, name: &str) -> Result<WorkloadGroup> {
+        let group_api_client = self.get_workload_group_api_client(tenant)?;
+        Ok(group_api_client.get_group(name, None).await?.data)
+    }
+
+    // Get all the workload groups for the tenant.
+    pub async fn get_workload_groups(&self, tenant: &str) -> Result<Vec<WorkloadGroup>> {
+        let group_api_client = self.get_workload_group_api_client(tenant)?;
+        match group_api_client.get_groups().await {
+            Err(e) => Err(e.add_message_back("(while get workload groups).")),
+            Ok(groups) => Ok(groups),
+        }
+    }
+
+    // Drop a workload group by name, the group must not be assigned to any user.
+    pub async fn drop_workload_group(
+        &self,
+        tenant: &str,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        for user in self.get_users(tenant).await? {
+            if user.option.workload_group().map(|g| g.as_str()) == Some(name) {
+                return Err(ErrorCode::WorkloadGroupInUse(format!(
+                    "Workload group {} is assigned to the user {}",
+                    name,
+                    user.identity()
+                )));
+            }
+        }
+
+        let group_api_client = self.get_workload_group_api_client(tenant)?;
+        match group_api_client.drop_group(name, None).await {
+            Ok(res) => Ok(res),
+            Err(e) => {
+                if if_exists && e.code() == ErrorCode::unknown_workload_group_code() {
+                    Ok(())
+                } else {
+                    Err(e.add_message_back("(while drop workload group)"))
+                }
+            }
+        }
+    }
+
+    // Get the workload group the user is assigned to.
+    pub async fn get_user_workload_group(
+        &self,
+        tenant: &str,
+        user: &UserInfo,
+    ) -> Result<Option<WorkloadGroup>> {
+        match user.option.workload_group() {
+            None => Ok(None),
+            Some(name) => Ok(Some(self.get_workload_group(tenant, name).await?)),
+        }
+    }
+}
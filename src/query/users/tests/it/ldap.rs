@@ -0,0 +1,55 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_users::LdapAuthenticator;
+use common_users::LdapConfig;
+
+#[test]
+fn test_ldap_authenticator_create() -> Result<()> {
+    // disabled without the URL
+    assert!(LdapAuthenticator::try_create(LdapConfig::default())?.is_none());
+
+    let config = LdapConfig {
+        url: "ldap://127.0.0.1:389".to_string(),
+        bind_dn: "uid={user},ou=people,dc=example,dc=com".to_string(),
+        role_mapping: "cn=admins, ou=groups, dc=example, dc=com:admin; \
+            cn=analysts,ou=groups,dc=example,dc=com:reader;"
+            .to_string(),
+        ..Default::default()
+    };
+    let ldap_auth = LdapAuthenticator::try_create(config.clone())?.unwrap();
+    assert_eq!(
+        ldap_auth.mapped_roles(),
+        BTreeSet::from(["admin".to_string(), "reader".to_string()])
+    );
+
+    // neither the DN template nor the search base DN
+    let res = LdapAuthenticator::try_create(LdapConfig {
+        bind_dn: "".to_string(),
+        ..config.clone()
+    });
+    assert_eq!(res.err().unwrap().code(), ErrorCode::invalid_config_code());
+
+    let res = LdapAuthenticator::try_create(LdapConfig {
+        role_mapping: "cn=admins,ou=groups,dc=example,dc=com".to_string(),
+        ..config
+    });
+    assert_eq!(res.err().unwrap().code(), ErrorCode::invalid_config_code());
+
+    Ok(())
+}
@@ -62,6 +62,53 @@ async fn test_user_udf() -> Result<()> {
         assert_eq!(isempty, udf.name);
     }
 
+    // add an overload of isempty.
+    {
+        let udf = UserDefinedFunction::new(
+            isempty,
+            vec!["p".to_string(), "q".to_string()],
+            "is_null(p) and is_null(q)",
+            description,
+        );
+        user_mgr.add_udf(tenant, udf, if_not_exists).await?;
+
+        let udf = user_mgr.get_udf(tenant, isempty).await?;
+        assert_eq!(vec![1, 2], udf.arities());
+        let (parameters, definition) = udf.get_overload(2).unwrap();
+        assert_eq!(vec!["p".to_string(), "q".to_string()], parameters);
+        assert_eq!("is_null(p) and is_null(q)", definition);
+        assert!(udf.get_overload(3).is_none());
+    }
+
+    // repeat add the overload with the same number of parameters.
+    {
+        let udf = UserDefinedFunction::new(isempty, vec!["a".to_string()], "a", description);
+        let res = user_mgr.add_udf(tenant, udf.clone(), false).await;
+        assert!(res.is_err());
+        let res = user_mgr.add_udf(tenant, udf, true).await;
+        assert!(res.is_ok());
+    }
+
+    // update the overload.
+    {
+        let udf = UserDefinedFunction::new(
+            isempty,
+            vec!["a".to_string(), "b".to_string()],
+            "is_null(a) or is_null(b)",
+            description,
+        );
+        user_mgr.update_udf(tenant, udf).await?;
+
+        let udf = user_mgr.get_udf(tenant, isempty).await?;
+        assert_eq!("is_null(p)", udf.definition);
+        let (_, definition) = udf.get_overload(2).unwrap();
+        assert_eq!("is_null(a) or is_null(b)", definition);
+
+        let udf = UserDefinedFunction::new(isempty, vec![], "true", description);
+        let res = user_mgr.update_udf(tenant, udf).await;
+        assert!(res.is_err());
+    }
+
     // drop.
     {
         user_mgr.drop_udf(tenant, isnotempty, false).await?;
@@ -0,0 +1,39 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+
+## Create table t12_0005
+echo "create table t12_0005(c int)" | $MYSQL_CLIENT_CONNECT
+echo "two insertions"
+echo "insert into t12_0005 values(1),(2)" | $MYSQL_CLIENT_CONNECT
+
+echo "insert into t12_0005 values(3)" | $MYSQL_CLIENT_CONNECT
+echo "latest snapshot should contain 3 rows"
+echo "select count(*)  from t12_0005" | $MYSQL_CLIENT_CONNECT
+
+## Get the snapshot ids from system.table_snapshots
+SNAPSHOT_ID_2=$(echo "select snapshot_id from system.table_snapshots where database = 'default' and table = 't12_0005' and row_count=2" | $MYSQL_CLIENT_CONNECT)
+SNAPSHOT_ID_3=$(echo "select snapshot_id from system.table_snapshots where database = 'default' and table = 't12_0005' and row_count=3" | $MYSQL_CLIENT_CONNECT)
+
+echo "flashback to the first insertion, which should contain 2 rows"
+echo "flashback table t12_0005 to (snapshot => '$SNAPSHOT_ID_2')" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from t12_0005" | $MYSQL_CLIENT_CONNECT
+
+echo "the flashback appends a new snapshot"
+echo "select count(*) from system.table_snapshots where database = 'default' and table = 't12_0005'" | $MYSQL_CLIENT_CONNECT
+
+echo "undo the flashback, which should contain 3 rows"
+echo "flashback table t12_0005 to (snapshot => '$SNAPSHOT_ID_3')" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from t12_0005" | $MYSQL_CLIENT_CONNECT
+
+# Get a time point at/after the first insertion.
+TIMEPOINT=$(echo "select timestamp from system.table_snapshots where database = 'default' and table = 't12_0005' and snapshot_id = '$SNAPSHOT_ID_2'" | $MYSQL_CLIENT_CONNECT)
+
+echo "flashback to the first insertion by timestamp, which should contain 2 rows"
+echo "flashback table t12_0005 to (TIMESTAMP => '$TIMEPOINT'::TIMESTAMP)" | $MYSQL_CLIENT_CONNECT
+echo "select count(*) from t12_0005" | $MYSQL_CLIENT_CONNECT
+
+## Drop table.
+echo "drop table t12_0005" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,33 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+
+echo "drop table if exists attach_src;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists attach_ro;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists attach_rw;" | $MYSQL_CLIENT_CONNECT
+
+echo "create table attach_src (a int);" | $MYSQL_CLIENT_CONNECT
+echo "insert into attach_src values (1), (2);" | $MYSQL_CLIENT_CONNECT
+
+## the storage prefix of the table precedes the snapshot directory
+PREFIX=$(echo "select snapshot_location from fuse_snapshot('default', 'attach_src') limit 1" | $MYSQL_CLIENT_CONNECT | sed 's#/_ss/.*##')
+
+## a read-only attached table follows the latest snapshot of the source table
+echo "attach table attach_ro from '$PREFIX' read_only;" | $MYSQL_CLIENT_CONNECT
+echo "select sum(a) from attach_ro;" | $MYSQL_CLIENT_CONNECT
+echo "insert into attach_src values (3);" | $MYSQL_CLIENT_CONNECT
+echo "select sum(a) from attach_ro;" | $MYSQL_CLIENT_CONNECT
+echo "insert into attach_ro values (4);" | $MYSQL_CLIENT_CONNECT 2>&1 | grep -c "read-only"
+
+## a read-write attached table takes over the data
+echo "attach table attach_rw from '$PREFIX';" | $MYSQL_CLIENT_CONNECT
+echo "insert into attach_rw values (4);" | $MYSQL_CLIENT_CONNECT
+echo "select sum(a) from attach_rw;" | $MYSQL_CLIENT_CONNECT
+echo "insert into attach_src values (5);" | $MYSQL_CLIENT_CONNECT 2>&1 | grep -c "taken over"
+echo "select sum(a) from attach_ro;" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table attach_ro;" | $MYSQL_CLIENT_CONNECT
+echo "drop table attach_rw;" | $MYSQL_CLIENT_CONNECT
+echo "drop table attach_src;" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,39 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists test_csv_options;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_csv_options;" | $MYSQL_CLIENT_CONNECT
+
+echo "create table test_csv_options(a int, b string null);" | $MYSQL_CLIENT_CONNECT
+echo "create stage s_csv_options;" | $MYSQL_CLIENT_CONNECT
+
+cat > /tmp/csv_options.csv <<'EOF'
+a,b
+1,'x,y'
+2,'it\'s'
+3,NULL
+4
+5,e,extra
+z,f
+EOF
+curl -u root: -XPUT -H "stage_name:s_csv_options" -F "upload=@/tmp/csv_options.csv" "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/upload_to_stage" > /dev/null 2>&1
+
+echo "---lenient"
+cat <<'EOF' | $MYSQL_CLIENT_CONNECT
+copy into test_csv_options from @s_csv_options FILES = ('csv_options.csv') FILE_FORMAT = (type = 'CSV' skip_header = 1 quote = '\'' escape = '\\' null_display = 'NULL' allow_column_count_mismatch = 'true') ON_ERROR = continue;
+select a, b from test_csv_options order by a;
+truncate table test_csv_options;
+EOF
+echo "list @s_csv_options PATTERN = '_errors/.*'" | $MYSQL_CLIENT_CONNECT | wc -l
+
+echo "---strict"
+cat <<'EOF' | $MYSQL_CLIENT_CONNECT
+copy into test_csv_options from @s_csv_options FILES = ('csv_options.csv') FILE_FORMAT = (type = 'CSV' skip_header = 1 quote = '\'' escape = '\\' null_display = 'NULL') ON_ERROR = continue force = true;
+select a, b from test_csv_options order by a;
+EOF
+
+echo "drop table if exists test_csv_options;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_csv_options;" | $MYSQL_CLIENT_CONNECT
+rm -f /tmp/csv_options.csv
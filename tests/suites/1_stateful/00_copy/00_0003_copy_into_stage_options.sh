@@ -0,0 +1,34 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists test_unload;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_reload;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_unload;" | $MYSQL_CLIENT_CONNECT
+
+echo "create table test_unload(a int, b string);" | $MYSQL_CLIENT_CONNECT
+echo "create table test_reload(a int, b string);" | $MYSQL_CLIENT_CONNECT
+echo "create stage s_unload;" | $MYSQL_CLIENT_CONNECT
+echo "insert into test_unload select number, to_varchar(number) from numbers(100000);" | $MYSQL_CLIENT_CONNECT
+
+echo "---split"
+echo "copy into @s_unload/split/ from test_unload FILE_FORMAT = (type = 'CSV' compression = 'gzip') max_file_size = 100000;" | $MYSQL_CLIENT_CONNECT
+files=$(echo "list @s_unload/split/ PATTERN = '.*[.]csv[.]gz'" | $MYSQL_CLIENT_CONNECT | wc -l)
+[ "$files" -gt 1 ] && echo "split into files"
+echo "copy into test_reload from @s_unload/split/ FILE_FORMAT = (type = 'CSV' compression = 'auto');" | $MYSQL_CLIENT_CONNECT
+echo "select count(), sum(a) from test_reload;" | $MYSQL_CLIENT_CONNECT
+
+echo "---single"
+echo "copy into @s_unload/single/ from (select a, b from test_unload where a < 10) FILE_FORMAT = (type = 'NDJSON' compression = 'zstd') max_file_size = 1 single = true;" | $MYSQL_CLIENT_CONNECT
+echo "list @s_unload/single/ PATTERN = '.*[.]ndjson[.]zstd'" | $MYSQL_CLIENT_CONNECT | wc -l | sed 's/ //g'
+
+echo "---parquet"
+echo "copy into @s_unload/parquet/ from test_unload FILE_FORMAT = (type = 'PARQUET') max_file_size = 1000000;" | $MYSQL_CLIENT_CONNECT
+echo "truncate table test_reload;" | $MYSQL_CLIENT_CONNECT
+echo "copy into test_reload from @s_unload/parquet/ FILE_FORMAT = (type = 'PARQUET');" | $MYSQL_CLIENT_CONNECT
+echo "select count(), sum(a) from test_reload;" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table if exists test_unload;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_reload;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_unload;" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,33 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists test_unload;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_reload;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_unload;" | $MYSQL_CLIENT_CONNECT
+
+echo "create table test_unload(a int, b string, dt string);" | $MYSQL_CLIENT_CONNECT
+echo "create table test_reload(a int, b string);" | $MYSQL_CLIENT_CONNECT
+echo "create stage s_unload;" | $MYSQL_CLIENT_CONNECT
+echo "insert into test_unload select number, to_varchar(number), if(number < 50, '2022-10-01', '2022-10-02') from numbers(100);" | $MYSQL_CLIENT_CONNECT
+
+echo "---column"
+echo "copy into @s_unload/column/ from test_unload PARTITION BY (dt) FILE_FORMAT = (type = 'CSV');" | $MYSQL_CLIENT_CONNECT
+echo "list @s_unload/column/dt=2022-10-01/ PATTERN = '.*part-0000-.*[.]csv'" | $MYSQL_CLIENT_CONNECT | wc -l | sed 's/ //g'
+echo "list @s_unload/column/dt=2022-10-02/ PATTERN = '.*part-0000-.*[.]csv'" | $MYSQL_CLIENT_CONNECT | wc -l | sed 's/ //g'
+echo "copy into test_reload from @s_unload/column/dt=2022-10-01/ FILE_FORMAT = (type = 'CSV');" | $MYSQL_CLIENT_CONNECT
+echo "select count(), sum(a) from test_reload;" | $MYSQL_CLIENT_CONNECT
+
+echo "---expression"
+echo "copy into @s_unload/expression/ from (select a, b from test_unload) PARTITION BY (a % 2 AS parity) FILE_FORMAT = (type = 'CSV');" | $MYSQL_CLIENT_CONNECT
+echo "truncate table test_reload;" | $MYSQL_CLIENT_CONNECT
+echo "copy into test_reload from @s_unload/expression/parity=1/ FILE_FORMAT = (type = 'CSV');" | $MYSQL_CLIENT_CONNECT
+echo "select count(), sum(a) from test_reload;" | $MYSQL_CLIENT_CONNECT
+
+echo "---unnamed"
+echo "copy into @s_unload/unnamed/ from test_unload PARTITION BY (a % 2) FILE_FORMAT = (type = 'CSV');" 2>&1 | $MYSQL_CLIENT_CONNECT 2>&1 | grep -c "alias"
+
+echo "drop table if exists test_unload;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_reload;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_unload;" | $MYSQL_CLIENT_CONNECT
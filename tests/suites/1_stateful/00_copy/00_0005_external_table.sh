@@ -0,0 +1,37 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists test_unload;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_external;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_inferred;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_external;" | $MYSQL_CLIENT_CONNECT
+
+echo "create table test_unload(a int, b string, dt string);" | $MYSQL_CLIENT_CONNECT
+echo "create stage s_external;" | $MYSQL_CLIENT_CONNECT
+echo "insert into test_unload select number, to_varchar(number), if(number < 50, '2022-10-01', '2022-10-02') from numbers(100);" | $MYSQL_CLIENT_CONNECT
+echo "copy into @s_external/data/ from test_unload PARTITION BY (dt) FILE_FORMAT = (type = 'CSV');" | $MYSQL_CLIENT_CONNECT
+
+echo "---declared"
+echo "create external table test_external(a int, b string, dt date) location = @s_external/data/ file_format = (type = 'CSV') partition by (dt);" | $MYSQL_CLIENT_CONNECT
+echo "select count(), sum(a) from test_external;" | $MYSQL_CLIENT_CONNECT
+echo "select count(), sum(a) from test_external where dt = '2022-10-02';" | $MYSQL_CLIENT_CONNECT
+echo "select dt, count() from test_external group by dt order by dt;" | $MYSQL_CLIENT_CONNECT
+
+echo "---new files"
+echo "insert into test_unload select number, to_varchar(number), '2022-10-03' from numbers(10);" | $MYSQL_CLIENT_CONNECT
+echo "copy into @s_external/data/ from (select * from test_unload where dt = '2022-10-03') PARTITION BY (dt) FILE_FORMAT = (type = 'CSV');" | $MYSQL_CLIENT_CONNECT
+echo "select count(), sum(a) from test_external where dt > '2022-10-01';" | $MYSQL_CLIENT_CONNECT
+
+echo "---inferred"
+echo "create external table test_inferred(a int, b string) location = @s_external/data/ file_format = (type = 'CSV');" | $MYSQL_CLIENT_CONNECT
+echo "select dt, count() from test_inferred group by dt order by dt;" | $MYSQL_CLIENT_CONNECT
+
+echo "---read only"
+echo "insert into test_external values (1, '1', '2022-10-01');" | $MYSQL_CLIENT_CONNECT 2>&1 | grep -c "EXTERNAL"
+
+echo "drop table if exists test_unload;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_external;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_inferred;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_external;" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists test_unload;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_parquet;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_parquet;" | $MYSQL_CLIENT_CONNECT
+
+echo "create table test_unload(a int, b string, dt string);" | $MYSQL_CLIENT_CONNECT
+echo "create stage s_parquet;" | $MYSQL_CLIENT_CONNECT
+echo "insert into test_unload select number, to_varchar(number), if(number < 500, '2022-10-01', '2022-10-02') from numbers(1000);" | $MYSQL_CLIENT_CONNECT
+echo "copy into @s_parquet/data/ from test_unload PARTITION BY (dt) FILE_FORMAT = (type = 'PARQUET');" | $MYSQL_CLIENT_CONNECT
+echo "create external table test_parquet(a int, b string, dt date) location = @s_parquet/data/ file_format = (type = 'PARQUET') partition by (dt);" | $MYSQL_CLIENT_CONNECT
+
+echo "---filters"
+echo "select count(), sum(a) from test_parquet where a >= 900;" | $MYSQL_CLIENT_CONNECT
+echo "select count() from test_parquet where a > 2000;" | $MYSQL_CLIENT_CONNECT
+echo "select b from test_parquet where a = 42;" | $MYSQL_CLIENT_CONNECT
+echo "select count(), sum(a) from test_parquet where dt = '2022-10-02' and a < 600;" | $MYSQL_CLIENT_CONNECT
+
+echo "---projection"
+echo "select count(b) from test_parquet where a < 10;" | $MYSQL_CLIENT_CONNECT
+echo "select count(), sum(a) from test_parquet;" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table if exists test_unload;" | $MYSQL_CLIENT_CONNECT
+echo "drop table if exists test_parquet;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s_parquet;" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,23 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists avro_test;" | $MYSQL_CLIENT_CONNECT
+## create avro_test table, `missing` is not in the file
+echo "create table avro_test(id int, name string, score double null,
+birthday date, created_at timestamp, tags array(string), price string, missing int null);" | $MYSQL_CLIENT_CONNECT
+
+aws --endpoint-url http://127.0.0.1:9900/ s3 cp s3://testbucket/admin/data/sample.avro /tmp/sample.avro > /dev/null 2>&1
+
+# do the Data integrity check
+echo "b7ea688ffdb3dc79fca6b9666433dd877cbfe367d763f39f9057800032731140 /tmp/sample.avro" | sha256sum --check > /dev/null 2>&1
+if [ $? -ne 0 ]; then
+	echo "The downloaded dataset has been corrupted, please remove and fetch it again."
+	exit 1
+fi
+
+# load avro
+curl -H "insert_sql:insert into avro_test format Avro" -F "upload=@/tmp/sample.avro" -u root: -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" > /dev/null 2>&1
+echo "select * from avro_test order by id" | $MYSQL_CLIENT_CONNECT
+echo "drop table avro_test;" | $MYSQL_CLIENT_CONNECT
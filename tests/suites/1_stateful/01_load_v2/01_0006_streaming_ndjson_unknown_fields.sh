@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists ndjson_test;" | $MYSQL_CLIENT_CONNECT
+echo "create table ndjson_test(id int, name string, extra variant null);" | $MYSQL_CLIENT_CONNECT
+
+cat > /tmp/ndjson_test.ndjson <<'EOF'
+{"id": 1, "name": "a"}
+{"id": 2, "name": "b", "level": "warn", "tags": ["x", "y"]}
+{"id": "three", "name": "c"}
+{"id": 4, "name": "d", "cost": 1.5}
+EOF
+
+# load ndjson, skipping the bad record
+curl -H "insert_sql:insert into ndjson_test format NDJSON" -H "skip_bad_rows:1" -H "ndjson_unknown_fields_column:extra" -F "upload=@/tmp/ndjson_test.ndjson" -u root: -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" | grep -o '"row":[0-9]*'
+echo "select * from ndjson_test order by id" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table ndjson_test;" | $MYSQL_CLIENT_CONNECT
+rm -f /tmp/ndjson_test.ndjson
@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "drop table if exists batch_test;" | $MYSQL_CLIENT_CONNECT
+echo "create table batch_test(id int, note string);" | $MYSQL_CLIENT_CONNECT
+
+rm -f /tmp/batch_test.csv
+for i in $(seq 1 1000); do
+	echo "$i,\"line $i
+of $i\"" >> /tmp/batch_test.csv
+done
+
+# the raw body is read 64 bytes at a time, and committed about every 1KB, records with newlines in quotes are not cut
+curl -s -H "insert_sql:insert into batch_test format CSV" -H "input_read_buffer_size:64" -H "streaming_load_batch_size:1024" -T /tmp/batch_test.csv -u root: -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" | grep -o '"rows":[0-9]*'
+echo "select count(), sum(id), count_if(note = concat('line ', id::string, '\nof ', id::string)) from batch_test" | $MYSQL_CLIENT_CONNECT
+echo "select count() > 1 from fuse_snapshot('default', 'batch_test')" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table batch_test;" | $MYSQL_CLIENT_CONNECT
+rm -f /tmp/batch_test.csv